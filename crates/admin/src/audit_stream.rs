@@ -0,0 +1,101 @@
+//! Live tail of the audit log over WebSocket.
+//!
+//! `GET /audit/stream` upgrades to a WebSocket that pushes each new entry
+//! matching the `/audit` filters (`user_id`, `action`, `resource`,
+//! `trace_id`, `session_id`, `preset`) as
+//! `{"type": "audit", "entry": {...}}`. Timestamp and paging parameters do
+//! not apply. A client too slow to keep up is sent
+//! `{"type": "lagged", "skipped": n}` and continues with newer entries.
+//!
+//! Browsers cannot set headers on WebSocket requests, so the admin token
+//! may instead be offered as the subprotocols `bearer, <token>`.
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket},
+        Query, State, WebSocketUpgrade,
+    },
+    response::Response,
+    Extension,
+};
+use multi_agent_governance::rbac::UserRoles;
+use multi_agent_governance::{AuditEntry, AuditFilter};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+
+use crate::audit_view::resolve_filter;
+use crate::{AdminState, AuditQuery};
+
+/// Subprotocol carrying the admin token for browser clients.
+pub const BEARER_PROTOCOL: &str = "bearer";
+/// A subscriber that cannot take a message for this long is dropped.
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// `GET /audit/stream`
+pub(crate) async fn stream_audit(
+    State(state): State<Arc<AdminState>>,
+    roles: Option<Extension<UserRoles>>,
+    Query(query): Query<AuditQuery>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let roles = roles.map(|Extension(r)| r);
+    let filter = match resolve_filter(&state, roles.as_ref(), &query).await {
+        Ok((filter, _)) => filter,
+        Err(response) => return response,
+    };
+    // Subscribed before the upgrade so no entry logged meanwhile is missed
+    let rx = state.audit_store.subscribe();
+    ws.protocols([BEARER_PROTOCOL])
+        .on_upgrade(move |socket| handle_stream(socket, filter, rx))
+}
+
+async fn send_json(socket: &mut WebSocket, value: serde_json::Value) -> bool {
+    matches!(
+        tokio::time::timeout(SEND_TIMEOUT, socket.send(Message::Text(value.to_string()))).await,
+        Ok(Ok(()))
+    )
+}
+
+async fn handle_stream(
+    mut socket: WebSocket,
+    filter: AuditFilter,
+    rx: Option<Receiver<AuditEntry>>,
+) {
+    let Some(mut rx) = rx else {
+        let message = "The audit store does not support streaming";
+        let _ = send_json(
+            &mut socket,
+            serde_json::json!({ "type": "error", "message": message }),
+        )
+        .await;
+        return;
+    };
+    metrics::gauge!("admin_audit_stream_subscribers").increment(1.0);
+
+    loop {
+        let message = tokio::select! {
+            received = rx.recv() => match received {
+                Ok(entry) if filter.matches(&entry) => {
+                    serde_json::json!({ "type": "audit", "entry": entry })
+                }
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => {
+                    metrics::counter!("admin_audit_stream_lagged_total").increment(skipped);
+                    serde_json::json!({ "type": "lagged", "skipped": skipped })
+                }
+                Err(RecvError::Closed) => break,
+            },
+            incoming = socket.recv() => match incoming {
+                // Clients only listen; anything but a close is ignored
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+        };
+        if !send_json(&mut socket, message).await {
+            break;
+        }
+    }
+
+    metrics::gauge!("admin_audit_stream_subscribers").decrement(1.0);
+}
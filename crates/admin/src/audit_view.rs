@@ -0,0 +1,509 @@
+//! Audit viewer support: pagination, CSV export and saved filter presets.
+//!
+//! The dashboard pages through `/audit` instead of pulling the full log, exports
+//! exactly the rows and columns a reviewer selected as CSV, and keeps named
+//! filter presets per admin user.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use multi_agent_governance::rbac::UserRoles;
+use multi_agent_governance::{AuditEntry, AuditFilter, AuditOutcome};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::{AdminState, AuditQuery};
+
+/// Default and maximum page sizes for the paginated audit view.
+const DEFAULT_PAGE_SIZE: usize = 50;
+const MAX_PAGE_SIZE: usize = 500;
+/// Row cap for a single CSV export, matching the ZIP bundle.
+const MAX_CSV_ROWS: usize = 10_000;
+
+/// Columns available for CSV export, in default order.
+pub const AUDIT_COLUMNS: &[&str] = &[
+    "id",
+    "timestamp",
+    "user_id",
+    "action",
+    "resource",
+    "outcome",
+    "metadata",
+    "previous_hash",
+    "hash",
+    "trace_id",
+    "session_id",
+];
+
+/// Filter fields a preset can pin.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AuditPresetFilter {
+    pub user_id: Option<String>,
+    pub action: Option<String>,
+    pub resource: Option<String>,
+    pub from_timestamp: Option<String>,
+    pub to_timestamp: Option<String>,
+}
+
+/// A named, saved audit filter.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditPreset {
+    pub name: String,
+    #[serde(default)]
+    pub filter: AuditPresetFilter,
+    /// CSV columns to export (empty = all).
+    #[serde(default)]
+    pub columns: Vec<String>,
+    #[serde(default)]
+    pub updated_at: String,
+}
+
+/// Saved audit filter presets, keyed by admin user ID.
+///
+/// Optionally persisted to a JSON file so presets survive restarts.
+#[derive(Default)]
+pub struct AuditPresetStore {
+    presets: RwLock<HashMap<String, Vec<AuditPreset>>>,
+    path: Option<PathBuf>,
+}
+
+impl AuditPresetStore {
+    /// In-memory preset store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Preset store persisted at `path`, loading existing presets if present.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let presets = match std::fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                tracing::warn!(path = %path.display(), error = %e, "Ignoring unreadable audit presets");
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+        Self {
+            presets: RwLock::new(presets),
+            path: Some(path),
+        }
+    }
+
+    pub async fn list(&self, user_id: &str) -> Vec<AuditPreset> {
+        self.presets
+            .read()
+            .await
+            .get(user_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub async fn get(&self, user_id: &str, name: &str) -> Option<AuditPreset> {
+        self.presets
+            .read()
+            .await
+            .get(user_id)
+            .and_then(|presets| presets.iter().find(|p| p.name == name).cloned())
+    }
+
+    /// Create or replace the preset with the same name.
+    pub async fn save(&self, user_id: &str, preset: AuditPreset) {
+        let mut presets = self.presets.write().await;
+        let list = presets.entry(user_id.to_string()).or_default();
+        list.retain(|p| p.name != preset.name);
+        list.push(preset);
+        list.sort_by(|a, b| a.name.cmp(&b.name));
+        self.persist(&presets).await;
+    }
+
+    /// Delete a preset. Returns whether it existed.
+    pub async fn delete(&self, user_id: &str, name: &str) -> bool {
+        let mut presets = self.presets.write().await;
+        let Some(list) = presets.get_mut(user_id) else {
+            return false;
+        };
+        let before = list.len();
+        list.retain(|p| p.name != name);
+        let removed = list.len() != before;
+        if removed {
+            self.persist(&presets).await;
+        }
+        removed
+    }
+
+    async fn persist(&self, presets: &HashMap<String, Vec<AuditPreset>>) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        match serde_json::to_string_pretty(presets) {
+            Ok(json) => {
+                if let Err(e) = tokio::fs::write(path, json).await {
+                    tracing::error!(path = %path.display(), error = %e, "Failed to persist audit presets");
+                }
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to serialize audit presets"),
+        }
+    }
+}
+
+/// Resolve requested CSV columns, rejecting unknown names.
+pub fn parse_columns(spec: Option<&str>) -> Result<Vec<&'static str>, String> {
+    let Some(spec) = spec.map(str::trim).filter(|s| !s.is_empty()) else {
+        return Ok(AUDIT_COLUMNS.to_vec());
+    };
+    spec.split(',')
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .map(|c| {
+            AUDIT_COLUMNS
+                .iter()
+                .find(|known| **known == c)
+                .copied()
+                .ok_or_else(|| format!("Unknown audit column '{}'", c))
+        })
+        .collect()
+}
+
+fn column_value(entry: &AuditEntry, column: &str) -> String {
+    match column {
+        "id" => entry.id.clone(),
+        "timestamp" => entry.timestamp.clone(),
+        "user_id" => entry.user_id.clone(),
+        "action" => entry.action.clone(),
+        "resource" => entry.resource.clone(),
+        "outcome" => match &entry.outcome {
+            AuditOutcome::Success => "Success".to_string(),
+            AuditOutcome::Denied => "Denied".to_string(),
+            AuditOutcome::Error(msg) => format!("Error: {}", msg),
+        },
+        "metadata" => entry
+            .metadata
+            .as_ref()
+            .map(|m| m.to_string())
+            .unwrap_or_default(),
+        "previous_hash" => entry.previous_hash.clone().unwrap_or_default(),
+        "hash" => entry.hash.clone().unwrap_or_default(),
+        "trace_id" => entry.trace_id.clone().unwrap_or_default(),
+        "session_id" => entry.session_id.clone().unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+/// Quote a CSV field (RFC 4180). Cells that spreadsheets would evaluate as
+/// formulas are prefixed with `'` so exported logs cannot inject formulas.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// Render entries as CSV with a header row.
+pub fn to_csv(entries: &[AuditEntry], columns: &[&str]) -> String {
+    let mut out = columns.join(",");
+    out.push_str("\r\n");
+    for entry in entries {
+        let row: Vec<String> = columns
+            .iter()
+            .map(|c| csv_field(&column_value(entry, c)))
+            .collect();
+        out.push_str(&row.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// Build the store filter from query parameters, filling unset fields from
+/// the named preset (explicit parameters win).
+pub(crate) async fn resolve_filter(
+    state: &AdminState,
+    roles: Option<&UserRoles>,
+    query: &AuditQuery,
+) -> Result<(AuditFilter, Option<AuditPreset>), Response> {
+    let preset = match (&query.preset, roles) {
+        (Some(name), Some(roles)) => match state.audit_presets.get(&roles.user_id, name).await {
+            Some(preset) => Some(preset),
+            None => {
+                return Err(error_response(
+                    StatusCode::NOT_FOUND,
+                    &format!("Audit preset '{}' not found", name),
+                ))
+            }
+        },
+        _ => None,
+    };
+    let saved = preset
+        .as_ref()
+        .map(|p| p.filter.clone())
+        .unwrap_or_default();
+
+    let filter = AuditFilter {
+        user_id: query.user_id.clone().or(saved.user_id),
+        action: query.action.clone().or(saved.action),
+        resource: query.resource.clone().or(saved.resource),
+        trace_id: query.trace_id.clone(),
+        session_id: query.session_id.clone(),
+        from_timestamp: query.from_timestamp.clone().or(saved.from_timestamp),
+        to_timestamp: query.to_timestamp.clone().or(saved.to_timestamp),
+        limit: query.limit,
+        offset: None,
+        after_id: query.after_id.clone(),
+        order: query.order.unwrap_or_default(),
+    };
+    Ok((filter, preset))
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (status, Json(serde_json::json!({ "error": message }))).into_response()
+}
+
+/// Paginated audit response.
+#[derive(Debug, Serialize)]
+pub struct AuditPage {
+    pub entries: Vec<AuditEntry>,
+    /// Page number; absent when paging by cursor.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<usize>,
+    pub page_size: usize,
+    pub total: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_pages: Option<usize>,
+    /// `after_id` of the next page; absent on the last page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// `GET /audit`
+///
+/// With `page`, `page_size` or `after_id` the response is an [`AuditPage`];
+/// without them the plain entry array is returned for existing clients.
+/// Cursor pages (`after_id`) stay consistent while entries are logged,
+/// unlike numbered pages.
+pub(crate) async fn get_audit(
+    State(state): State<Arc<AdminState>>,
+    roles: Option<Extension<UserRoles>>,
+    Query(query): Query<AuditQuery>,
+) -> Response {
+    let roles = roles.map(|Extension(r)| r);
+    let (mut filter, _) = match resolve_filter(&state, roles.as_ref(), &query).await {
+        Ok(resolved) => resolved,
+        Err(response) => return response,
+    };
+
+    if query.page.is_none() && query.page_size.is_none() && query.after_id.is_none() {
+        return match state.audit_store.query(filter).await {
+            Ok(entries) => Json(entries).into_response(),
+            Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        };
+    }
+
+    let page_size = query
+        .page_size
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    // Cursor paging ignores page numbers
+    let page = match filter.after_id {
+        Some(_) => None,
+        None => Some(query.page.unwrap_or(1).max(1)),
+    };
+
+    let total = match state.audit_store.count(filter.clone()).await {
+        Ok(total) => total,
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+    filter.offset = page.map(|page| (page - 1) * page_size);
+
+    match state.audit_store.query_page(filter, page_size).await {
+        Ok(result) => Json(AuditPage {
+            entries: result.entries,
+            page,
+            page_size,
+            total,
+            total_pages: page.map(|_| total.div_ceil(page_size)),
+            next_cursor: result.next_cursor,
+        })
+        .into_response(),
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+/// `GET /audit/export.csv`
+///
+/// Exports the filtered entries (up to 10,000) with the columns listed in
+/// `columns`, or the preset's columns, or all columns.
+pub(crate) async fn export_audit_csv(
+    State(state): State<Arc<AdminState>>,
+    roles: Option<Extension<UserRoles>>,
+    Query(query): Query<AuditQuery>,
+) -> Response {
+    let roles = roles.map(|Extension(r)| r);
+    let (mut filter, preset) = match resolve_filter(&state, roles.as_ref(), &query).await {
+        Ok(resolved) => resolved,
+        Err(response) => return response,
+    };
+
+    let preset_columns = preset
+        .filter(|p| !p.columns.is_empty())
+        .map(|p| p.columns.join(","));
+    let columns = match parse_columns(query.columns.as_deref().or(preset_columns.as_deref())) {
+        Ok(columns) => columns,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, &e),
+    };
+
+    filter.limit = Some(filter.limit.unwrap_or(MAX_CSV_ROWS).min(MAX_CSV_ROWS));
+    let entries = match state.audit_store.query(filter).await {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::error!("Failed to export audit logs: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let filename = format!("audit_{}.csv", chrono::Utc::now().format("%Y%m%d_%H%M%S"));
+    (
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        to_csv(&entries, &columns),
+    )
+        .into_response()
+}
+
+fn preset_owner(roles: Option<Extension<UserRoles>>) -> Option<String> {
+    roles.map(|Extension(r)| r.user_id)
+}
+
+/// `GET /audit/presets`
+pub(crate) async fn list_audit_presets(
+    State(state): State<Arc<AdminState>>,
+    roles: Option<Extension<UserRoles>>,
+) -> Response {
+    match preset_owner(roles) {
+        Some(user_id) => Json(state.audit_presets.list(&user_id).await).into_response(),
+        None => StatusCode::UNAUTHORIZED.into_response(),
+    }
+}
+
+/// `POST /audit/presets`
+pub(crate) async fn save_audit_preset(
+    State(state): State<Arc<AdminState>>,
+    roles: Option<Extension<UserRoles>>,
+    Json(mut preset): Json<AuditPreset>,
+) -> Response {
+    let Some(user_id) = preset_owner(roles) else {
+        return StatusCode::UNAUTHORIZED.into_response();
+    };
+    preset.name = preset.name.trim().to_string();
+    if preset.name.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "Preset name is required");
+    }
+    if !preset.columns.is_empty() {
+        if let Err(e) = parse_columns(Some(&preset.columns.join(","))) {
+            return error_response(StatusCode::BAD_REQUEST, &e);
+        }
+    }
+    preset.updated_at = chrono::Utc::now().to_rfc3339();
+    state.audit_presets.save(&user_id, preset.clone()).await;
+    (StatusCode::CREATED, Json(preset)).into_response()
+}
+
+/// `DELETE /audit/presets/:name`
+pub(crate) async fn delete_audit_preset(
+    State(state): State<Arc<AdminState>>,
+    roles: Option<Extension<UserRoles>>,
+    Path(name): Path<String>,
+) -> Response {
+    let Some(user_id) = preset_owner(roles) else {
+        return StatusCode::UNAUTHORIZED.into_response();
+    };
+    if state.audit_presets.delete(&user_id, &name).await {
+        StatusCode::NO_CONTENT.into_response()
+    } else {
+        error_response(
+            StatusCode::NOT_FOUND,
+            &format!("Audit preset '{}' not found", name),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, metadata: Option<serde_json::Value>) -> AuditEntry {
+        AuditEntry {
+            id: id.to_string(),
+            timestamp: "2024-01-01T00:00:00Z".to_string(),
+            user_id: "alice".to_string(),
+            action: "=HYPERLINK(\"x\")".to_string(),
+            resource: "res".to_string(),
+            outcome: AuditOutcome::Error("boom, again".to_string()),
+            metadata,
+            previous_hash: None,
+            hash: Some("abc".to_string()),
+            trace_id: None,
+            session_id: None,
+        }
+    }
+
+    #[test]
+    fn test_csv_escapes_and_selects_columns() {
+        let columns = parse_columns(Some("id, action,outcome,metadata")).unwrap();
+        let csv = to_csv(
+            &[entry("e1", Some(serde_json::json!({"k": "v"})))],
+            &columns,
+        );
+        let lines: Vec<_> = csv.split("\r\n").collect();
+        assert_eq!(lines[0], "id,action,outcome,metadata");
+        assert_eq!(
+            lines[1],
+            r#"e1,"'=HYPERLINK(""x"")","Error: boom, again","{""k"":""v""}""#
+        );
+
+        assert_eq!(parse_columns(None).unwrap().len(), AUDIT_COLUMNS.len());
+        assert!(parse_columns(Some("id,secret")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_presets_are_per_user_and_persisted() {
+        let dir = std::env::temp_dir().join(format!("audit-presets-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("presets.json");
+
+        let store = AuditPresetStore::load(&path);
+        let preset = AuditPreset {
+            name: "denials".to_string(),
+            filter: AuditPresetFilter {
+                action: Some("TOOL_DENIED".to_string()),
+                ..Default::default()
+            },
+            columns: vec!["id".to_string()],
+            updated_at: String::new(),
+        };
+        store.save("alice", preset.clone()).await;
+        assert!(store.list("bob").await.is_empty());
+
+        let reloaded = AuditPresetStore::load(&path);
+        assert_eq!(reloaded.get("alice", "denials").await, Some(preset));
+        assert!(reloaded.delete("alice", "denials").await);
+        assert!(!reloaded.delete("alice", "denials").await);
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
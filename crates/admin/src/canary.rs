@@ -0,0 +1,370 @@
+//! Canary rollout of provider model upgrades.
+//!
+//! `PUT /providers/:id/model` switches a provider to another `model_id`.
+//! With a [`ModelCanary`] configured, the new model serves traffic right away
+//! while the canary suite runs against the previous and the new model in the
+//! background. If the new model passes fewer cases or costs more than the
+//! thresholds allow, the previous model is restored. Either outcome is
+//! audited and sent to the notification sinks; `GET /providers/:id/canary`
+//! reports the latest run.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use multi_agent_core::config::ModelCanaryConfig;
+use multi_agent_core::traits::{LlmClient, Notification, NotificationSeverity, NotificationSink};
+use multi_agent_governance::{AuditContext, AuditEntry, AuditOutcome};
+use multi_agent_model_gateway::{CanaryMetrics, CanarySuite, CanaryThresholds, PricingRegistry};
+
+use crate::{
+    provider_sync, save_provider, spend_caps, AdminState, ModelUpgradeRequest, ProviderEntry,
+};
+
+/// Builds an inference client for a provider entry and its API key.
+pub type ClientFactory =
+    dyn Fn(&AdminState, &ProviderEntry, String) -> Option<Arc<dyn LlmClient>> + Send + Sync;
+
+/// State of a canary run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CanaryStatus {
+    Running,
+    /// The new model is kept.
+    Passed,
+    /// The previous model was restored.
+    RolledBack,
+}
+
+/// A canary run of one model upgrade.
+#[derive(Debug, Clone, Serialize)]
+pub struct CanaryRun {
+    pub provider_id: String,
+    pub previous_model_id: String,
+    pub model_id: String,
+    pub status: CanaryStatus,
+    pub started_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<CanaryMetrics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub candidate: Option<CanaryMetrics>,
+    /// Why the upgrade was rolled back.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub regressions: Vec<String>,
+}
+
+impl CanaryRun {
+    fn started(provider: &ProviderEntry, previous_model_id: &str) -> Self {
+        Self {
+            provider_id: provider.id.clone(),
+            previous_model_id: previous_model_id.to_string(),
+            model_id: provider.model_id.clone(),
+            status: CanaryStatus::Running,
+            started_at: chrono::Utc::now().to_rfc3339(),
+            finished_at: None,
+            baseline: None,
+            candidate: None,
+            regressions: Vec::new(),
+        }
+    }
+}
+
+/// Evaluates model upgrades and rolls back regressions.
+pub struct ModelCanary {
+    suite: CanarySuite,
+    thresholds: CanaryThresholds,
+    pricing: PricingRegistry,
+    sinks: Vec<Arc<dyn NotificationSink>>,
+    clients: Box<ClientFactory>,
+    /// Latest run per provider id.
+    runs: RwLock<HashMap<String, CanaryRun>>,
+}
+
+impl ModelCanary {
+    pub fn new(suite: CanarySuite, thresholds: CanaryThresholds) -> Self {
+        Self {
+            suite,
+            thresholds,
+            pricing: PricingRegistry::with_defaults(),
+            sinks: Vec::new(),
+            clients: Box::new(|state, provider, api_key| {
+                provider_sync::client_for(state, provider, api_key)
+                    .map(|client| Arc::new(client) as Arc<dyn LlmClient>)
+            }),
+            runs: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn from_config(config: &ModelCanaryConfig) -> Self {
+        Self::new(
+            CanarySuite::from_config(config),
+            CanaryThresholds::from_config(config),
+        )
+    }
+
+    /// Prices used to compare cost, keyed `vendor:model`.
+    pub fn with_pricing(mut self, pricing: PricingRegistry) -> Self {
+        self.pricing = pricing;
+        self
+    }
+
+    pub fn with_sink(mut self, sink: Arc<dyn NotificationSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Replace how clients are built for the compared models.
+    pub fn with_client_factory(
+        mut self,
+        factory: impl Fn(&AdminState, &ProviderEntry, String) -> Option<Arc<dyn LlmClient>>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.clients = Box::new(factory);
+        self
+    }
+
+    /// Latest run for a provider.
+    pub fn run(&self, provider_id: &str) -> Option<CanaryRun> {
+        self.runs
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(provider_id)
+            .cloned()
+    }
+
+    fn record(&self, run: &CanaryRun) {
+        self.runs
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(run.provider_id.clone(), run.clone());
+    }
+
+    /// Compare `provider`, already switched to its new model, against
+    /// `previous_model_id` and restore the previous model on regression.
+    pub async fn evaluate(
+        &self,
+        state: &AdminState,
+        provider: &ProviderEntry,
+        previous_model_id: &str,
+    ) -> CanaryRun {
+        let mut run = CanaryRun::started(provider, previous_model_id);
+        self.record(&run);
+
+        let previous = ProviderEntry {
+            model_id: previous_model_id.to_string(),
+            ..provider.clone()
+        };
+        match self.compare(state, &previous, provider).await {
+            Ok((baseline, candidate)) => {
+                run.regressions = self.thresholds.regressions(&baseline, &candidate);
+                run.baseline = Some(baseline);
+                run.candidate = Some(candidate);
+            }
+            Err(reason) => run.regressions.push(reason),
+        }
+
+        run.status = if run.regressions.is_empty() {
+            CanaryStatus::Passed
+        } else {
+            self.roll_back(state, &run).await;
+            CanaryStatus::RolledBack
+        };
+        run.finished_at = Some(chrono::Utc::now().to_rfc3339());
+        self.record(&run);
+        self.report(state, &run).await;
+        run
+    }
+
+    async fn compare(
+        &self,
+        state: &AdminState,
+        previous: &ProviderEntry,
+        provider: &ProviderEntry,
+    ) -> Result<(CanaryMetrics, CanaryMetrics), String> {
+        let api_key = match state.secrets.retrieve(&provider.api_key_id).await {
+            Ok(Some(key)) => key,
+            Ok(None) => return Err("provider API key is missing".to_string()),
+            Err(e) => return Err(format!("provider API key unavailable: {}", e)),
+        };
+        let (Some(baseline), Some(candidate)) = (
+            (self.clients)(state, previous, api_key.clone()),
+            (self.clients)(state, provider, api_key),
+        ) else {
+            return Err(format!("vendor {} is not supported", provider.vendor));
+        };
+        let baseline_key = spend_caps::pricing_key(previous);
+        let candidate_key = spend_caps::pricing_key(provider);
+        Ok(tokio::join!(
+            self.suite.run(
+                &baseline_key,
+                baseline.as_ref(),
+                self.pricing.get(&baseline_key)
+            ),
+            self.suite.run(
+                &candidate_key,
+                candidate.as_ref(),
+                self.pricing.get(&candidate_key)
+            ),
+        ))
+    }
+
+    async fn roll_back(&self, state: &AdminState, run: &CanaryRun) {
+        // Leave the provider alone if it was changed again meanwhile
+        let Some(mut provider) = spend_caps::load_providers(state)
+            .await
+            .into_iter()
+            .find(|p| p.id == run.provider_id && p.model_id == run.model_id)
+        else {
+            return;
+        };
+        provider.model_id = run.previous_model_id.clone();
+        if let Err(e) = save_provider(state, &provider).await {
+            tracing::error!(provider = %run.provider_id, error = %e, "Failed to roll back model upgrade");
+        }
+    }
+
+    async fn report(&self, state: &AdminState, run: &CanaryRun) {
+        let (action, notification) = match run.status {
+            CanaryStatus::RolledBack => (
+                "MODEL_CANARY_ROLLED_BACK",
+                Notification::new(
+                    NotificationSeverity::Critical,
+                    "Model upgrade rolled back",
+                    format!(
+                        "Provider {} was switched back from {} to {}: {}.",
+                        run.provider_id,
+                        run.model_id,
+                        run.previous_model_id,
+                        run.regressions.join("; ")
+                    ),
+                ),
+            ),
+            _ => (
+                "MODEL_CANARY_PASSED",
+                Notification::new(
+                    NotificationSeverity::Info,
+                    "Model upgrade kept",
+                    format!(
+                        "Provider {} passed the canary on {} (previously {}).",
+                        run.provider_id, run.model_id, run.previous_model_id
+                    ),
+                ),
+            ),
+        };
+        tracing::info!(provider = %run.provider_id, model = %run.model_id, action, "Model canary finished");
+        let _ = state
+            .audit_store
+            .log(AuditEntry {
+                id: uuid::Uuid::new_v4().to_string(),
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                user_id: "system".to_string(),
+                action: action.to_string(),
+                resource: run.provider_id.clone(),
+                outcome: AuditOutcome::Success,
+                metadata: Some(serde_json::to_value(run).unwrap_or_default()),
+                previous_hash: None,
+                hash: None,
+                trace_id: None,
+                session_id: None,
+            })
+            .await;
+        for sink in &self.sinks {
+            if let Err(e) = sink.send(&notification).await {
+                tracing::warn!(sink = sink.name(), error = %e, "Notification delivery failed");
+            }
+        }
+    }
+}
+
+/// Switch a provider to another model, canarying the change if configured.
+pub(crate) async fn set_provider_model(
+    State(state): State<Arc<AdminState>>,
+    Path(id): Path<String>,
+    Json(req): Json<ModelUpgradeRequest>,
+) -> Response {
+    let model_id = req.model_id.trim().to_string();
+    if model_id.is_empty() {
+        return (StatusCode::BAD_REQUEST, "model_id is required").into_response();
+    }
+    let Some(mut provider) = spend_caps::load_providers(&state)
+        .await
+        .into_iter()
+        .find(|p| p.id == id)
+    else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let previous_model_id = std::mem::replace(&mut provider.model_id, model_id);
+    if previous_model_id == provider.model_id {
+        return Json(serde_json::json!({ "id": id, "model_id": provider.model_id }))
+            .into_response();
+    }
+    let canary = state.model_canary.clone().filter(|c| !c.suite.is_empty());
+    if canary
+        .as_ref()
+        .and_then(|c| c.run(&id))
+        .is_some_and(|run| run.status == CanaryStatus::Running)
+    {
+        return (
+            StatusCode::CONFLICT,
+            "A canary run for this provider is in progress",
+        )
+            .into_response();
+    }
+    if let Err(e) = save_provider(&state, &provider).await {
+        tracing::error!(provider = %id, error = %e, "Failed to update provider model");
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+    let _ = state
+        .audit_store
+        .log(AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            user_id: AuditContext::user_or("admin"),
+            action: "UPDATE_PROVIDER_MODEL".to_string(),
+            resource: id.clone(),
+            outcome: AuditOutcome::Success,
+            metadata: Some(serde_json::json!({
+                "model_id": provider.model_id,
+                "previous_model_id": previous_model_id,
+                "canary": canary.is_some(),
+            })),
+            previous_hash: None,
+            hash: None,
+            trace_id: None,
+            session_id: None,
+        })
+        .await;
+
+    let Some(canary) = canary else {
+        return Json(serde_json::json!({ "id": id, "model_id": provider.model_id }))
+            .into_response();
+    };
+    // Recorded before responding so the run can be polled right away
+    let run = CanaryRun::started(&provider, &previous_model_id);
+    canary.record(&run);
+    tokio::spawn(async move {
+        canary.evaluate(&state, &provider, &previous_model_id).await;
+    });
+    (StatusCode::ACCEPTED, Json(run)).into_response()
+}
+
+/// Latest canary run of a provider.
+pub(crate) async fn get_canary_run(
+    State(state): State<Arc<AdminState>>,
+    Path(id): Path<String>,
+) -> Response {
+    match state.model_canary.as_ref().and_then(|c| c.run(&id)) {
+        Some(run) => Json(run).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
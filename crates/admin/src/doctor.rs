@@ -174,6 +174,7 @@ pub async fn check_all(State(state): State<Arc<AdminState>>) -> Json<DoctorRepor
                 Ok(_) => {
                     let latency = start.elapsed().as_millis() as u64;
                     checks.push(CheckResult::pass("Infrastructure", "Docker", Some(latency)));
+                    checks.push(check_sandbox_security(&d).await);
                 }
                 Err(e) => checks.push(CheckResult::fail("Infrastructure", "Docker", e.to_string())),
             }
@@ -225,3 +226,32 @@ pub async fn check_all(State(state): State<Arc<AdminState>>) -> Json<DoctorRepor
         overall_status,
     })
 }
+
+/// Verify the Docker host can enforce the sandbox's seccomp/AppArmor/SELinux profiles.
+async fn check_sandbox_security(docker: &bollard::Docker) -> CheckResult {
+    let start = Instant::now();
+    let host = match multi_agent_sandbox::HostSecurity::detect(docker).await {
+        Ok(h) => h,
+        Err(e) => return CheckResult::fail("Infrastructure", "Sandbox Security", e.to_string()),
+    };
+    let profiles = multi_agent_sandbox::SandboxConfig::default().security;
+    let missing = profiles.missing_host_support(&host);
+    if !missing.is_empty() {
+        return CheckResult::fail(
+            "Infrastructure",
+            "Sandbox Security",
+            format!("Host does not support: {}", missing.join(", ")),
+        );
+    }
+
+    let mut result = CheckResult::pass(
+        "Infrastructure",
+        "Sandbox Security",
+        Some(start.elapsed().as_millis() as u64),
+    );
+    result.message = Some(format!(
+        "seccomp: {}, apparmor: {}, selinux: {}",
+        host.seccomp, host.apparmor, host.selinux
+    ));
+    result
+}
@@ -1,41 +1,53 @@
 //! Admin API for OpenCoordex management dashboard.
 //!
 //! Provides endpoints for:
-//! - LLM Provider management
+//! - LLM Provider management (including edits with API key rotation, monthly
+//!   spend caps, switching the active default provider and canaried model
+//!   upgrades); stored providers are registered with the model gateway as
+//!   inference targets
 //! - S3 Persistence configuration
 //! - MCP Registry management
+//! - OpenAPI spec import
 //! - Metrics and observability
 //! - Audit log queries
+//! - Session export/import bundles
 //! - Static dashboard UI
 
 use axum::{
-    body::Body,
     extract::{Path, Query, Request, State},
-    http::{header, StatusCode},
+    http::{header, HeaderMap, StatusCode},
     middleware::{self, Next},
     response::{IntoResponse, Response},
-    routing::{delete, get, post},
+    routing::{delete, get, post, put},
     Json, Router,
 };
-use multi_agent_governance::{AuditFilter, AuditStore, RbacConnector};
+use multi_agent_governance::{AuditContext, AuditFilter, AuditStore, RbacConnector};
 use multi_agent_governance::{PrivacyController, SecretsManager};
-use rust_embed::RustEmbed;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-/// Embedded static assets for the dashboard.
-#[derive(RustEmbed)]
-#[folder = "../../dashboard/static"]
-struct Asset;
-
-use multi_agent_core::traits::{ArtifactStore, ProviderStore, SessionStore};
+use multi_agent_core::config::StateFile;
+use multi_agent_core::traits::{
+    ArtifactFilter, ArtifactMetadata, ArtifactStore, ProviderStore, SessionStore,
+};
 use multi_agent_core::types::RefId;
+use multi_agent_model_gateway::{ActiveLlmClient, EndpointPool, EndpointRegistry, EndpointStatus};
 use multi_agent_skills::mcp_registry::{McpRegistry, McpServerInfo};
+use multi_agent_skills::openapi::{ApiKeyLocation, AuthProfile, OpenApiRegistry, OpenApiSpec};
 use sha2::{Digest, Sha256};
 use std::io::Write;
 
+pub mod audit_stream;
+pub mod audit_view;
+pub mod canary;
 pub mod doctor;
+pub mod provider_sync;
+pub mod s3_anchor;
+pub mod s3_compliance;
+pub mod session_bundle;
+pub mod spend_caps;
+pub mod static_assets;
 
 // =========================================
 // State & Data Structures
@@ -47,6 +59,8 @@ pub struct AdminState {
     pub rbac: Arc<dyn RbacConnector>,
     pub metrics: Option<metrics_exporter_prometheus::PrometheusHandle>,
     pub mcp_registry: Arc<McpRegistry>,
+    /// APIs imported from OpenAPI specs, exposed as tools.
+    pub openapi_registry: Option<Arc<OpenApiRegistry>>,
     /// In-memory provider storage (used when `provider_store` is None).
     pub providers: Arc<RwLock<Vec<ProviderEntry>>>,
     /// External provider store (e.g., Redis/PostgreSQL).
@@ -64,6 +78,22 @@ pub struct AdminState {
     pub app_config: multi_agent_core::config::AppConfig,
     /// Network Policy (mutable).
     pub network_policy: Arc<RwLock<multi_agent_governance::network::NetworkPolicy>>,
+    /// Saved audit viewer filter presets per admin user.
+    pub audit_presets: Arc<audit_view::AuditPresetStore>,
+    /// Anchors the audit chain to an external notary (WORM mode).
+    pub audit_anchorer: Option<Arc<multi_agent_governance::AuditAnchorer>>,
+    /// Per-channel guardrails (mutable, shared with the gateway).
+    pub guardrails: Arc<multi_agent_governance::RouteGuardrails>,
+    /// Per-tool usage statistics.
+    pub tool_analytics: Option<Arc<multi_agent_skills::ToolAnalytics>>,
+    /// Regional endpoint health, keyed by provider id or vendor.
+    pub endpoint_registry: Option<Arc<EndpointRegistry>>,
+    /// Default LLM client, switched by `POST /providers/:id/activate`.
+    pub active_client: Option<Arc<ActiveLlmClient>>,
+    /// Registers stored providers with the model gateway for inference.
+    pub provider_sync: Option<Arc<provider_sync::ProviderSync>>,
+    /// Evaluates `model_id` changes and rolls back regressions.
+    pub model_canary: Option<Arc<canary::ModelCanary>>,
 }
 
 /// LLM Provider entry.
@@ -75,6 +105,9 @@ pub struct ProviderEntry {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     pub base_url: String,
+    /// Additional regional base URLs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub regional_urls: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
     /// Key ID for retrieving the encrypted API key from SecretsManager.
@@ -83,6 +116,12 @@ pub struct ProviderEntry {
     pub api_key_id: String,
     pub capabilities: Vec<String>,
     pub status: String,
+    /// Monthly spend cap in USD; see [`spend_caps`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly_spend_cap_usd: Option<f64>,
+    /// Per-endpoint health, filled in when listing providers.
+    #[serde(default, skip_deserializing, skip_serializing_if = "Vec::is_empty")]
+    pub endpoints: Vec<EndpointStatus>,
 }
 
 /// Request to add a provider.
@@ -92,9 +131,73 @@ pub struct AddProviderRequest {
     pub model_id: String,
     pub description: Option<String>,
     pub base_url: String,
+    #[serde(default)]
+    pub regional_urls: Vec<String>,
     pub version: Option<String>,
     pub api_key: String,
     pub capabilities: Vec<String>,
+    pub monthly_spend_cap_usd: Option<f64>,
+}
+
+/// Request to replace a provider's editable fields. Omitted optional fields
+/// are cleared; `api_key` rotates the key and is kept otherwise.
+#[derive(Debug, Deserialize)]
+pub struct UpdateProviderRequest {
+    pub base_url: String,
+    #[serde(default)]
+    pub regional_urls: Vec<String>,
+    pub description: Option<String>,
+    pub version: Option<String>,
+    pub capabilities: Vec<String>,
+    pub api_key: Option<String>,
+}
+
+/// Request to change some of a provider's editable fields. `null` clears
+/// `description` and `version`; omitted fields are kept.
+#[derive(Debug, Default, Deserialize)]
+pub struct PatchProviderRequest {
+    pub base_url: Option<String>,
+    pub regional_urls: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "present")]
+    pub description: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    pub version: Option<Option<String>>,
+    pub capabilities: Option<Vec<String>>,
+    pub api_key: Option<String>,
+}
+
+impl From<UpdateProviderRequest> for PatchProviderRequest {
+    fn from(req: UpdateProviderRequest) -> Self {
+        Self {
+            base_url: Some(req.base_url),
+            regional_urls: Some(req.regional_urls),
+            description: Some(req.description),
+            version: Some(req.version),
+            capabilities: Some(req.capabilities),
+            api_key: req.api_key,
+        }
+    }
+}
+
+/// Tells a field sent as `null` (`Some(None)`) from an omitted one (`None`).
+fn present<'de, D, T>(deserializer: D) -> std::result::Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+/// Request to set or clear a provider's monthly spend cap.
+#[derive(Debug, Deserialize)]
+pub struct SpendCapRequest {
+    pub monthly_spend_cap_usd: Option<f64>,
+}
+
+/// Request to switch a provider to another model.
+#[derive(Debug, Deserialize)]
+pub struct ModelUpgradeRequest {
+    pub model_id: String,
 }
 
 /// Request to test a provider connection.
@@ -124,6 +227,34 @@ pub struct RegisterMcpRequest {
     pub capabilities: Vec<String>,
 }
 
+/// OpenAPI spec import request.
+#[derive(Debug, Deserialize)]
+pub struct ImportOpenApiRequest {
+    /// Tool name prefix, e.g. `crm` for `crm__list_contacts`.
+    pub namespace: String,
+    /// The spec, as a JSON/YAML string or a JSON object.
+    pub spec: serde_json::Value,
+    /// Overrides `servers[0].url` from the spec.
+    pub server_url: Option<String>,
+    pub auth: Option<OpenApiAuthRequest>,
+}
+
+/// Credentials for an imported API. Either `secret` (stored encrypted under
+/// `openapi:<namespace>`) or `secret_id` (an existing secret) is required
+/// unless `type` is `none`.
+#[derive(Debug, Deserialize)]
+pub struct OpenApiAuthRequest {
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Header or query parameter name for `api_key`.
+    pub name: Option<String>,
+    pub location: Option<ApiKeyLocation>,
+    /// Username for `basic`.
+    pub username: Option<String>,
+    pub secret: Option<String>,
+    pub secret_id: Option<String>,
+}
+
 /// Request to rotate secrets.
 #[derive(Debug, Deserialize)]
 pub struct RotateSecretsRequest {
@@ -159,9 +290,23 @@ pub struct AuditQuery {
     pub user_id: Option<String>,
     pub action: Option<String>,
     pub resource: Option<String>,
+    pub trace_id: Option<String>,
+    pub session_id: Option<String>,
     pub from_timestamp: Option<String>,
     pub to_timestamp: Option<String>,
     pub limit: Option<usize>,
+    /// 1-based page number; enables the paginated response.
+    pub page: Option<usize>,
+    /// Entries per page; enables the paginated response.
+    pub page_size: Option<usize>,
+    /// `next_cursor` of the previous page; enables the paginated response.
+    pub after_id: Option<String>,
+    /// `desc` (newest first, the default) or `asc`.
+    pub order: Option<multi_agent_governance::AuditOrder>,
+    /// Comma-separated CSV export columns.
+    pub columns: Option<String>,
+    /// Name of a saved preset supplying defaults for the filters.
+    pub preset: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -170,6 +315,30 @@ pub struct SessionFilter {
     pub user_id: Option<String>,
 }
 
+/// Query parameters for the artifact listing.
+#[derive(Deserialize)]
+pub struct ArtifactQuery {
+    pub user_id: Option<String>,
+    pub session_id: Option<String>,
+    /// Unix timestamp; only artifacts created at or after it.
+    pub created_after: Option<i64>,
+    /// Unix timestamp; only artifacts created before it.
+    pub created_before: Option<i64>,
+    /// `next_cursor` of the previous page.
+    pub after_id: Option<String>,
+    pub page_size: Option<usize>,
+}
+
+/// One page of artifacts, in ascending id order.
+#[derive(Serialize)]
+pub struct ArtifactPage {
+    pub artifacts: Vec<ArtifactMetadata>,
+    pub page_size: usize,
+    /// `after_id` of the next page; absent on the last page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
 // =========================================
 // Middleware
 // =========================================
@@ -184,12 +353,15 @@ async fn auth_middleware(
         .headers()
         .get(header::AUTHORIZATION)
         .and_then(|h| h.to_str().ok())
-        .and_then(|h| h.strip_prefix("Bearer "));
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .or_else(|| websocket_token(&req));
 
     match auth_header {
         Some(token) => match state.rbac.validate(token).await {
             Ok(roles) => {
                 if roles.is_admin {
+                    let mut req = req;
+                    req.extensions_mut().insert(roles);
                     next.run(req).await
                 } else {
                     StatusCode::FORBIDDEN.into_response()
@@ -201,6 +373,23 @@ async fn auth_middleware(
     }
 }
 
+/// Token offered as the WebSocket subprotocols `bearer, <token>`.
+fn websocket_token(req: &Request) -> Option<&str> {
+    let upgrade = req.headers().get(header::UPGRADE)?.to_str().ok()?;
+    if !upgrade.eq_ignore_ascii_case("websocket") {
+        return None;
+    }
+    let protocols = req
+        .headers()
+        .get(header::SEC_WEBSOCKET_PROTOCOL)?
+        .to_str()
+        .ok()?;
+    let mut protocols = protocols.split(',').map(str::trim);
+    (protocols.next()? == audit_stream::BEARER_PROTOCOL)
+        .then(|| protocols.next())
+        .flatten()
+}
+
 // =========================================
 // Provider Endpoints
 // =========================================
@@ -218,11 +407,15 @@ async fn list_providers(State(state): State<Arc<AdminState>>) -> Response {
                     model_id: p.model_id,
                     description: p.description,
                     base_url: p.base_url,
+                    regional_urls: p.regional_urls,
                     version: p.version,
                     api_key_id: p.api_key_id,
                     capabilities: p.capabilities,
                     status: p.status,
+                    monthly_spend_cap_usd: p.monthly_spend_cap_usd,
+                    endpoints: Vec::new(),
                 })
+                .map(|p| with_endpoint_health(&state, p))
                 .collect();
             return Json(admin_providers).into_response();
         }
@@ -230,7 +423,104 @@ async fn list_providers(State(state): State<Arc<AdminState>>) -> Response {
         return Json(Vec::<ProviderEntry>::new()).into_response();
     }
     let providers = state.providers.read().await;
-    Json(providers.clone()).into_response()
+    let providers: Vec<ProviderEntry> = providers
+        .iter()
+        .cloned()
+        .map(|p| with_endpoint_health(&state, p))
+        .collect();
+    Json(providers).into_response()
+}
+
+/// Attach regional endpoint health, looked up by provider id, then vendor.
+fn with_endpoint_health(state: &AdminState, mut provider: ProviderEntry) -> ProviderEntry {
+    if let Some(registry) = &state.endpoint_registry {
+        provider.endpoints = registry.status(&provider.id);
+        if provider.endpoints.is_empty() {
+            provider.endpoints = registry.status(&provider.vendor);
+        }
+    }
+    provider
+}
+
+/// Create or replace a provider entry; its API key must already be stored
+/// under `api_key_id`.
+pub async fn save_provider(
+    state: &AdminState,
+    entry: &ProviderEntry,
+) -> multi_agent_core::Result<()> {
+    if let Some(registry) = &state.endpoint_registry {
+        if entry.regional_urls.is_empty() {
+            registry.remove(&entry.id);
+        } else {
+            let urls = std::iter::once(&entry.base_url).chain(&entry.regional_urls);
+            registry.register(&entry.id, Arc::new(EndpointPool::new(urls.cloned())));
+        }
+    }
+
+    if let Some(store) = &state.provider_store {
+        // Convert to core::ProviderEntry
+        let core_entry = multi_agent_core::traits::ProviderEntry {
+            id: entry.id.clone(),
+            vendor: entry.vendor.clone(),
+            model_id: entry.model_id.clone(),
+            description: entry.description.clone(),
+            base_url: entry.base_url.clone(),
+            regional_urls: entry.regional_urls.clone(),
+            version: entry.version.clone(),
+            api_key_id: entry.api_key_id.clone(),
+            capabilities: entry.capabilities.clone(),
+            status: entry.status.clone(),
+            monthly_spend_cap_usd: entry.monthly_spend_cap_usd,
+        };
+        store.upsert(&core_entry).await?;
+    } else {
+        let mut providers = state.providers.write().await;
+        match providers.iter_mut().find(|p| p.id == entry.id) {
+            Some(existing) => *existing = entry.clone(),
+            None => providers.push(entry.clone()),
+        }
+    }
+    if let Some(sync) = &state.provider_sync {
+        sync.sync(state).await;
+    }
+    Ok(())
+}
+
+/// Delete a provider entry and its API key; returns false if it does not exist.
+pub async fn remove_provider(state: &AdminState, id: &str) -> bool {
+    let mut deleted = false;
+    let mut api_key_id = None;
+
+    if let Some(store) = &state.provider_store {
+        // First get the provider to find the api_key_id
+        if let Ok(Some(provider)) = store.get(id).await {
+            api_key_id = Some(provider.api_key_id.clone());
+            if let Ok(result) = store.delete(id).await {
+                deleted = result;
+            }
+        }
+    } else {
+        let mut providers = state.providers.write().await;
+        if let Some(pos) = providers.iter().position(|p| p.id == id) {
+            api_key_id = Some(providers[pos].api_key_id.clone());
+            providers.remove(pos);
+            deleted = true;
+        }
+    }
+
+    if deleted {
+        // Also cleanup the secret
+        if let Some(key_id) = api_key_id {
+            let _ = state.secrets.delete(&key_id).await;
+        }
+        if let Some(registry) = &state.endpoint_registry {
+            registry.remove(id);
+        }
+        if let Some(sync) = &state.provider_sync {
+            sync.sync(state).await;
+        }
+    }
+    deleted
 }
 
 /// Add a new provider.
@@ -257,31 +547,17 @@ async fn add_provider(
         model_id: req.model_id,
         description: req.description,
         base_url: req.base_url,
+        regional_urls: req.regional_urls,
         version: req.version,
         api_key_id,
         capabilities: req.capabilities,
         status: "active".to_string(), // Set to active by default
+        monthly_spend_cap_usd: req.monthly_spend_cap_usd,
+        endpoints: Vec::new(),
     };
 
-    if let Some(store) = &state.provider_store {
-        // Convert to core::ProviderEntry
-        let core_entry = multi_agent_core::traits::ProviderEntry {
-            id: entry.id.clone(),
-            vendor: entry.vendor.clone(),
-            model_id: entry.model_id.clone(),
-            description: entry.description.clone(),
-            base_url: entry.base_url.clone(),
-            version: entry.version.clone(),
-            api_key_id: entry.api_key_id.clone(),
-            capabilities: entry.capabilities.clone(),
-            status: entry.status.clone(),
-        };
-        if store.upsert(&core_entry).await.is_err() {
-            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-        }
-    } else {
-        let mut providers = state.providers.write().await;
-        providers.push(entry.clone());
+    if save_provider(&state, &entry).await.is_err() {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
     }
 
     // Log audit event
@@ -290,7 +566,7 @@ async fn add_provider(
         .log(multi_agent_governance::AuditEntry {
             id: uuid::Uuid::new_v4().to_string(),
             timestamp: chrono::Utc::now().to_rfc3339(),
-            user_id: "admin".to_string(),
+            user_id: AuditContext::user_or("admin"),
             action: "ADD_PROVIDER".to_string(),
             resource: entry.id.clone(),
             outcome: multi_agent_governance::AuditOutcome::Success,
@@ -299,6 +575,8 @@ async fn add_provider(
             })),
             previous_hash: None,
             hash: None,
+            trace_id: None,
+            session_id: None,
         })
         .await;
 
@@ -367,44 +645,21 @@ async fn test_provider_by_id(
 
 /// Delete a provider.
 async fn delete_provider(State(state): State<Arc<AdminState>>, Path(id): Path<String>) -> Response {
-    let mut deleted = false;
-    let mut api_key_id = None;
-
-    if let Some(store) = &state.provider_store {
-        // First get the provider to find the api_key_id
-        if let Ok(Some(provider)) = store.get(&id).await {
-            api_key_id = Some(provider.api_key_id.clone());
-            if let Ok(result) = store.delete(&id).await {
-                deleted = result;
-            }
-        }
-    } else {
-        let mut providers = state.providers.write().await;
-        if let Some(pos) = providers.iter().position(|p| p.id == id) {
-            api_key_id = Some(providers[pos].api_key_id.clone());
-            providers.remove(pos);
-            deleted = true;
-        }
-    }
-
-    if deleted {
-        // Also cleanup the secret
-        if let Some(key_id) = api_key_id {
-            let _ = state.secrets.delete(&key_id).await;
-        }
-
+    if remove_provider(&state, &id).await {
         let _ = state
             .audit_store
             .log(multi_agent_governance::AuditEntry {
                 id: uuid::Uuid::new_v4().to_string(),
                 timestamp: chrono::Utc::now().to_rfc3339(),
-                user_id: "admin".to_string(),
+                user_id: AuditContext::user_or("admin"),
                 action: "DELETE_PROVIDER".to_string(),
                 resource: id,
                 outcome: multi_agent_governance::AuditOutcome::Success,
                 metadata: None,
                 previous_hash: None,
                 hash: None,
+                trace_id: None,
+                session_id: None,
             })
             .await;
 
@@ -414,11 +669,224 @@ async fn delete_provider(State(state): State<Arc<AdminState>>, Path(id): Path<St
     }
 }
 
+/// Replace a provider's editable fields.
+async fn update_provider(
+    State(state): State<Arc<AdminState>>,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateProviderRequest>,
+) -> Response {
+    apply_provider_update(&state, &id, req.into()).await
+}
+
+/// Change some of a provider's editable fields.
+async fn patch_provider(
+    State(state): State<Arc<AdminState>>,
+    Path(id): Path<String>,
+    Json(req): Json<PatchProviderRequest>,
+) -> Response {
+    apply_provider_update(&state, &id, req).await
+}
+
+/// Apply an edit to a provider, auditing the changed fields and any key
+/// rotation. `model_id` changes go through `PUT /providers/:id/model`.
+async fn apply_provider_update(
+    state: &AdminState,
+    id: &str,
+    req: PatchProviderRequest,
+) -> Response {
+    let Some(mut provider) = spend_caps::load_providers(state)
+        .await
+        .into_iter()
+        .find(|p| p.id == id)
+    else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    if req
+        .base_url
+        .as_deref()
+        .is_some_and(|url| url.trim().is_empty())
+    {
+        return (StatusCode::BAD_REQUEST, "base_url must not be empty").into_response();
+    }
+    if req.api_key.as_deref().is_some_and(|key| key.is_empty()) {
+        return (StatusCode::BAD_REQUEST, "api_key must not be empty").into_response();
+    }
+
+    let mut changes = serde_json::Map::new();
+    let mut change = |field: &str, from: serde_json::Value, to: serde_json::Value| {
+        if from != to {
+            changes.insert(
+                field.to_string(),
+                serde_json::json!({ "from": from, "to": to }),
+            );
+        }
+    };
+    if let Some(base_url) = req.base_url {
+        change(
+            "base_url",
+            provider.base_url.clone().into(),
+            base_url.clone().into(),
+        );
+        provider.base_url = base_url;
+    }
+    if let Some(regional_urls) = req.regional_urls {
+        change(
+            "regional_urls",
+            provider.regional_urls.clone().into(),
+            regional_urls.clone().into(),
+        );
+        provider.regional_urls = regional_urls;
+    }
+    if let Some(description) = req.description {
+        change(
+            "description",
+            provider.description.clone().into(),
+            description.clone().into(),
+        );
+        provider.description = description;
+    }
+    if let Some(version) = req.version {
+        change(
+            "version",
+            provider.version.clone().into(),
+            version.clone().into(),
+        );
+        provider.version = version;
+    }
+    if let Some(capabilities) = req.capabilities {
+        change(
+            "capabilities",
+            provider.capabilities.clone().into(),
+            capabilities.clone().into(),
+        );
+        provider.capabilities = capabilities;
+    }
+
+    // The new key is stored under a fresh id so a failed save keeps the old one
+    let previous_key_id = provider.api_key_id.clone();
+    if let Some(api_key) = &req.api_key {
+        let key_id = format!("api_key:{}:{}", id, chrono::Utc::now().timestamp_millis());
+        if let Err(e) = state.secrets.store(&key_id, api_key).await {
+            tracing::error!(provider = %id, error = %e, "Failed to store rotated API key");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+        provider.api_key_id = key_id;
+    }
+    let rotated = provider.api_key_id != previous_key_id;
+    if changes.is_empty() && !rotated {
+        return Json(provider).into_response();
+    }
+
+    if let Err(e) = save_provider(state, &provider).await {
+        tracing::error!(provider = %id, error = %e, "Failed to update provider");
+        if rotated {
+            let _ = state.secrets.delete(&provider.api_key_id).await;
+        }
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+    if rotated {
+        let _ = state.secrets.delete(&previous_key_id).await;
+    }
+
+    let audit = |action: &str, metadata: Option<serde_json::Value>| {
+        state.audit_store.log(multi_agent_governance::AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            user_id: AuditContext::user_or("admin"),
+            action: action.to_string(),
+            resource: id.to_string(),
+            outcome: multi_agent_governance::AuditOutcome::Success,
+            metadata,
+            previous_hash: None,
+            hash: None,
+            trace_id: None,
+            session_id: None,
+        })
+    };
+    if !changes.is_empty() {
+        let _ = audit(
+            "UPDATE_PROVIDER",
+            Some(serde_json::json!({ "changes": changes })),
+        )
+        .await;
+    }
+    if rotated {
+        let _ = audit("ROTATE_PROVIDER_KEY", None).await;
+    }
+
+    Json(with_endpoint_health(state, provider)).into_response()
+}
+
+/// Make a provider the default LLM client without a restart.
+async fn activate_provider(
+    State(state): State<Arc<AdminState>>,
+    Path(id): Path<String>,
+) -> Response {
+    let Some(active) = &state.active_client else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Runtime provider switching is not enabled",
+        )
+            .into_response();
+    };
+    let Some(provider) = spend_caps::load_providers(&state)
+        .await
+        .into_iter()
+        .find(|p| p.id == id)
+    else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    if provider.status == spend_caps::OVER_BUDGET_STATUS {
+        return (
+            StatusCode::CONFLICT,
+            "Provider is over its monthly spend cap",
+        )
+            .into_response();
+    }
+    let api_key = match state.secrets.retrieve(&provider.api_key_id).await {
+        Ok(Some(key)) => key,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+    let Some(client) = provider_sync::client_for(&state, &provider, api_key) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            format!("Unsupported vendor: {}", provider.vendor),
+        )
+            .into_response();
+    };
+    let model = client.model_id();
+    let previous = active.model_id();
+    active.activate(Arc::new(client), model.clone(), Some(provider.id.clone()));
+
+    let _ = state
+        .audit_store
+        .log(multi_agent_governance::AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            user_id: AuditContext::user_or("admin"),
+            action: "ACTIVATE_PROVIDER".to_string(),
+            resource: provider.id.clone(),
+            outcome: multi_agent_governance::AuditOutcome::Success,
+            metadata: Some(serde_json::json!({
+                "model": model,
+                "previous_model": previous,
+            })),
+            previous_hash: None,
+            hash: None,
+            trace_id: None,
+            session_id: None,
+        })
+        .await;
+
+    Json(serde_json::json!({ "id": provider.id, "model": model })).into_response()
+}
+
 // =========================================
 // Persistence Endpoints
 // =========================================
 
-/// Test S3 connection.
+/// Test S3 connection and report bucket compliance (encryption, versioning, public access).
 async fn test_s3_connection(Json(req): Json<S3ConfigRequest>) -> Response {
     use aws_config::Region;
     use aws_sdk_s3::config::{Builder as S3ConfigBuilder, Credentials};
@@ -439,10 +907,30 @@ async fn test_s3_connection(Json(req): Json<S3ConfigRequest>) -> Response {
     let s3_config = config_builder.build();
     let client = aws_sdk_s3::Client::from_conf(s3_config);
 
-    match client.head_bucket().bucket(&req.bucket).send().await {
-        Ok(_) => Json(serde_json::json!({"status": "connected"})).into_response(),
-        Err(_) => StatusCode::SERVICE_UNAVAILABLE.into_response(),
+    if client
+        .head_bucket()
+        .bucket(&req.bucket)
+        .send()
+        .await
+        .is_err()
+    {
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    }
+
+    let compliance = s3_compliance::S3ComplianceReport::probe(&client, &req.bucket).await;
+    if !compliance.warnings.is_empty() {
+        tracing::warn!(
+            bucket = %req.bucket,
+            warnings = ?compliance.warnings,
+            "S3 bucket compliance warnings"
+        );
     }
+
+    Json(serde_json::json!({
+        "status": "connected",
+        "compliance": compliance,
+    }))
+    .into_response()
 }
 
 #[derive(Deserialize)]
@@ -464,15 +952,18 @@ async fn forget_user(
             .log(multi_agent_governance::AuditEntry {
                 id: uuid::Uuid::new_v4().to_string(),
                 timestamp: chrono::Utc::now().to_rfc3339(),
-                user_id: "admin".to_string(),
+                user_id: AuditContext::user_or("admin"),
                 action: "FORGET_USER".to_string(),
                 resource: user_id,
                 outcome: multi_agent_governance::AuditOutcome::Success,
                 metadata: Some(serde_json::json!({
-                    "total_deleted": report.total_deleted
+                    "total_deleted": report.total_deleted,
+                    "per_store": report.per_store,
                 })),
                 previous_hash: None,
                 hash: None,
+                trace_id: None,
+                session_id: None,
             })
             .await;
 
@@ -503,13 +994,15 @@ async fn rotate_secrets_handler(
                 .log(multi_agent_governance::AuditEntry {
                     id: uuid::Uuid::new_v4().to_string(),
                     timestamp: chrono::Utc::now().to_rfc3339(),
-                    user_id: "admin".to_string(),
+                    user_id: AuditContext::user_or("admin"),
                     action: "ROTATE_SECRETS".to_string(),
                     resource: "secrets".to_string(),
                     outcome: multi_agent_governance::AuditOutcome::Success,
                     metadata: None,
                     previous_hash: None,
                     hash: None,
+                    trace_id: None,
+                    session_id: None,
                 })
                 .await;
             StatusCode::OK.into_response()
@@ -562,6 +1055,9 @@ async fn register_mcp(
         keywords: vec![req.name.clone()],
         connection_uri: req.command,
         args: vec![],
+        env: Default::default(),
+        launch: Default::default(),
+        call_policy: Default::default(),
         transport_type: req.transport_type,
         priority: 50,
         available: true,
@@ -574,7 +1070,7 @@ async fn register_mcp(
         .log(multi_agent_governance::AuditEntry {
             id: uuid::Uuid::new_v4().to_string(),
             timestamp: chrono::Utc::now().to_rfc3339(),
-            user_id: "admin".to_string(),
+            user_id: AuditContext::user_or("admin"),
             action: "REGISTER_MCP_SERVER".to_string(),
             resource: info.id.clone(),
             outcome: multi_agent_governance::AuditOutcome::Success,
@@ -584,12 +1080,71 @@ async fn register_mcp(
             })),
             previous_hash: None,
             hash: None,
+            trace_id: None,
+            session_id: None,
         })
         .await;
 
     Json(serde_json::json!({"id": info.id, "status": "registered"})).into_response()
 }
 
+/// Process status, restarts and resource usage of supervised stdio servers.
+async fn get_mcp_processes(State(state): State<Arc<AdminState>>) -> Response {
+    match state.mcp_registry.adapter().supervisor() {
+        Some(supervisor) => Json(supervisor.list()).into_response(),
+        None => Json(Vec::<multi_agent_skills::McpProcessStatus>::new()).into_response(),
+    }
+}
+
+/// Bulk-import MCP servers from an `mcp.json` / Claude Desktop config.
+///
+/// Every server is registered, connected and probed for tools; the response
+/// lists the outcome per server.
+async fn import_mcp(
+    State(state): State<Arc<AdminState>>,
+    Json(config): Json<serde_json::Value>,
+) -> Response {
+    let parsed = match multi_agent_skills::parse_mcp_config(&config.to_string()) {
+        Ok(parsed) => parsed,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+    if parsed.servers.is_empty() && parsed.disabled.is_empty() && parsed.invalid.is_empty() {
+        return (StatusCode::BAD_REQUEST, "No MCP servers found in config").into_response();
+    }
+
+    let results = multi_agent_skills::import_mcp_servers(&state.mcp_registry, parsed).await;
+    let imported = results
+        .iter()
+        .filter(|r| r.status == "registered" || r.status == "updated")
+        .count();
+
+    let _ = state
+        .audit_store
+        .log(multi_agent_governance::AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            user_id: AuditContext::user_or("admin"),
+            action: "IMPORT_MCP_SERVERS".to_string(),
+            resource: "mcp".to_string(),
+            outcome: multi_agent_governance::AuditOutcome::Success,
+            metadata: Some(serde_json::json!({
+                "servers": results.iter().map(|r| serde_json::json!({"id": r.id, "status": r.status})).collect::<Vec<_>>(),
+            })),
+            previous_hash: None,
+            hash: None,
+            trace_id: None,
+            session_id: None,
+        })
+        .await;
+
+    Json(serde_json::json!({
+        "imported": imported,
+        "total": results.len(),
+        "servers": results,
+    }))
+    .into_response()
+}
+
 /// Remove MCP server.
 async fn remove_mcp(State(state): State<Arc<AdminState>>, Path(id): Path<String>) -> Response {
     state.mcp_registry.unregister(&id);
@@ -599,13 +1154,207 @@ async fn remove_mcp(State(state): State<Arc<AdminState>>, Path(id): Path<String>
         .log(multi_agent_governance::AuditEntry {
             id: uuid::Uuid::new_v4().to_string(),
             timestamp: chrono::Utc::now().to_rfc3339(),
-            user_id: "admin".to_string(),
+            user_id: AuditContext::user_or("admin"),
             action: "REMOVE_MCP_SERVER".to_string(),
             resource: id,
             outcome: multi_agent_governance::AuditOutcome::Success,
             metadata: None,
             previous_hash: None,
             hash: None,
+            trace_id: None,
+            session_id: None,
+        })
+        .await;
+
+    StatusCode::NO_CONTENT.into_response()
+}
+
+// =========================================
+// Tool Analytics
+// =========================================
+
+/// Per-tool invocation counts, success rates, latencies and observation cost.
+async fn get_tool_analytics(State(state): State<Arc<AdminState>>) -> Response {
+    match &state.tool_analytics {
+        Some(analytics) => Json(analytics.snapshot()).into_response(),
+        None => Json(Vec::<multi_agent_skills::ToolStats>::new()).into_response(),
+    }
+}
+
+// =========================================
+// OpenAPI Endpoints
+// =========================================
+
+/// Summary of an imported API (operations are listed by tool name only).
+fn openapi_summary(api: &OpenApiSpec) -> serde_json::Value {
+    serde_json::json!({
+        "namespace": api.namespace,
+        "title": api.spec.title,
+        "version": api.spec.version,
+        "server_url": api.spec.server_url,
+        "auth": api.auth,
+        "tools": api.tool_names(),
+        "skipped": api.spec.skipped,
+    })
+}
+
+/// List imported APIs.
+async fn list_openapi_specs(State(state): State<Arc<AdminState>>) -> Response {
+    match &state.openapi_registry {
+        Some(registry) => {
+            let apis: Vec<_> = registry
+                .apis()
+                .iter()
+                .map(|api| openapi_summary(api))
+                .collect();
+            Json(apis).into_response()
+        }
+        None => StatusCode::SERVICE_UNAVAILABLE.into_response(),
+    }
+}
+
+/// Build the auth profile for an import, storing an inline secret first.
+async fn openapi_auth(
+    state: &AdminState,
+    namespace: &str,
+    req: Option<OpenApiAuthRequest>,
+) -> Result<AuthProfile, Response> {
+    let bad_request = |msg: &str| (StatusCode::BAD_REQUEST, msg.to_string()).into_response();
+    let Some(req) = req else {
+        return Ok(AuthProfile::None);
+    };
+    match req.kind.as_str() {
+        "none" => return Ok(AuthProfile::None),
+        "bearer" => {}
+        "api_key" if req.name.is_none() => return Err(bad_request("api_key auth requires name")),
+        "basic" if req.username.is_none() => {
+            return Err(bad_request("basic auth requires username"))
+        }
+        "api_key" | "basic" => {}
+        _ => {
+            return Err(bad_request(
+                "auth type must be none, bearer, api_key or basic",
+            ))
+        }
+    }
+
+    let secret_id = match (req.secret, req.secret_id) {
+        (Some(secret), _) => {
+            let id = format!("openapi:{}", namespace);
+            if state.secrets.store(&id, &secret).await.is_err() {
+                return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+            id
+        }
+        (None, Some(id)) => id,
+        (None, None) => return Err(bad_request("auth requires secret or secret_id")),
+    };
+    Ok(match req.kind.as_str() {
+        "bearer" => AuthProfile::Bearer { secret_id },
+        "api_key" => AuthProfile::ApiKey {
+            secret_id,
+            name: req.name.unwrap_or_default(),
+            location: req.location.unwrap_or_default(),
+        },
+        _ => AuthProfile::Basic {
+            username: req.username.unwrap_or_default(),
+            secret_id,
+        },
+    })
+}
+
+/// Import an OpenAPI spec, exposing its operations as tools.
+async fn import_openapi(
+    State(state): State<Arc<AdminState>>,
+    Json(req): Json<ImportOpenApiRequest>,
+) -> Response {
+    let Some(registry) = &state.openapi_registry else {
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    };
+    let server_url = req.server_url.as_deref();
+    let api = match &req.spec {
+        serde_json::Value::String(source) => OpenApiSpec::parse(&req.namespace, source, server_url),
+        doc => OpenApiSpec::from_document(&req.namespace, doc, server_url),
+    };
+    let api = match api {
+        Ok(api) => api,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+    let auth = match openapi_auth(&state, &api.namespace, req.auth).await {
+        Ok(auth) => auth,
+        Err(response) => return response,
+    };
+
+    let namespace = api.namespace.clone();
+    let server_url = api.spec.server_url.clone();
+    let result = registry.import(api.with_auth(auth)).await;
+    let outcome = match &result {
+        Ok(_) => multi_agent_governance::AuditOutcome::Success,
+        Err(_) => multi_agent_governance::AuditOutcome::Denied,
+    };
+    let _ = state
+        .audit_store
+        .log(multi_agent_governance::AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            user_id: AuditContext::user_or("admin"),
+            action: "IMPORT_OPENAPI_SPEC".to_string(),
+            resource: namespace,
+            outcome,
+            metadata: Some(serde_json::json!({
+                "server_url": server_url,
+                "tools": result.as_ref().map(|api| api.tool_names().len()).unwrap_or(0),
+                "reason": result.as_ref().err().map(|e| e.to_string()),
+            })),
+            previous_hash: None,
+            hash: None,
+            trace_id: None,
+            session_id: None,
+        })
+        .await;
+
+    match result {
+        Ok(api) => Json(openapi_summary(&api)).into_response(),
+        Err(e) => (StatusCode::FORBIDDEN, e.to_string()).into_response(),
+    }
+}
+
+/// Remove an imported API and any secret stored with it.
+async fn remove_openapi_spec(
+    State(state): State<Arc<AdminState>>,
+    Path(namespace): Path<String>,
+) -> Response {
+    let Some(registry) = &state.openapi_registry else {
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    };
+    let Some(api) = registry.remove(&namespace) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let owned_secret = format!("openapi:{}", namespace);
+    let uses_owned_secret = match &api.auth {
+        AuthProfile::None => false,
+        AuthProfile::Bearer { secret_id }
+        | AuthProfile::ApiKey { secret_id, .. }
+        | AuthProfile::Basic { secret_id, .. } => *secret_id == owned_secret,
+    };
+    if uses_owned_secret {
+        let _ = state.secrets.delete(&owned_secret).await;
+    }
+
+    let _ = state
+        .audit_store
+        .log(multi_agent_governance::AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            user_id: AuditContext::user_or("admin"),
+            action: "REMOVE_OPENAPI_SPEC".to_string(),
+            resource: namespace,
+            outcome: multi_agent_governance::AuditOutcome::Success,
+            metadata: None,
+            previous_hash: None,
+            hash: None,
+            trace_id: None,
+            session_id: None,
         })
         .await;
 
@@ -675,13 +1424,15 @@ async fn delete_session_admin(
                 .log(multi_agent_governance::AuditEntry {
                     id: uuid::Uuid::new_v4().to_string(),
                     timestamp: chrono::Utc::now().to_rfc3339(),
-                    user_id: "admin".to_string(),
+                    user_id: AuditContext::user_or("admin"),
                     action: "DELETE_SESSION".to_string(),
                     resource: id,
                     outcome: multi_agent_governance::AuditOutcome::Success,
                     metadata: None,
                     previous_hash: None,
                     hash: None,
+                    trace_id: None,
+                    session_id: None,
                 })
                 .await;
             StatusCode::NO_CONTENT.into_response()
@@ -693,6 +1444,57 @@ async fn delete_session_admin(
     }
 }
 
+// =========================================
+// Artifact Endpoints
+// =========================================
+
+const DEFAULT_ARTIFACT_PAGE_SIZE: usize = 50;
+const MAX_ARTIFACT_PAGE_SIZE: usize = 500;
+
+/// List stored artifacts page by page.
+async fn list_artifacts(
+    State(state): State<Arc<AdminState>>,
+    Query(query): Query<ArtifactQuery>,
+) -> Response {
+    let store = match &state.artifact_store {
+        Some(s) => s,
+        None => return StatusCode::SERVICE_UNAVAILABLE.into_response(),
+    };
+
+    let page_size = query
+        .page_size
+        .unwrap_or(DEFAULT_ARTIFACT_PAGE_SIZE)
+        .clamp(1, MAX_ARTIFACT_PAGE_SIZE);
+    let filter = ArtifactFilter {
+        owner_user_id: query.user_id,
+        session_id: query.session_id,
+        created_after: query.created_after,
+        created_before: query.created_before,
+        after_id: query.after_id.map(RefId::from_string),
+        // One extra artifact tells whether another page follows.
+        limit: Some(page_size + 1),
+    };
+
+    match store.list(filter).await {
+        Ok(mut artifacts) => {
+            let next_cursor = (artifacts.len() > page_size).then(|| {
+                artifacts.truncate(page_size);
+                artifacts[page_size - 1].id.as_str().to_string()
+            });
+            Json(ArtifactPage {
+                artifacts,
+                page_size,
+                next_cursor,
+            })
+            .into_response()
+        }
+        Err(e) => {
+            tracing::error!("Failed to list artifacts: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
 // =========================================
 // Config & Health Endpoints
 // =========================================
@@ -714,8 +1516,10 @@ async fn get_config(State(state): State<Arc<AdminState>>) -> Response {
         ("In-Memory".to_string(), None, None)
     };
 
-    let providers_path = std::path::Path::new("providers.json");
-    let has_providers = providers_path.exists();
+    let has_providers = state
+        .app_config
+        .state_path(StateFile::Providers)
+        .is_some_and(|path| path.exists());
     let source = if has_providers {
         "File (providers.json)"
     } else {
@@ -748,28 +1552,44 @@ async fn get_config(State(state): State<Arc<AdminState>>) -> Response {
 // Audit & Metrics Endpoints
 // =========================================
 
-/// Query audit logs.
-async fn get_audit(
-    State(state): State<Arc<AdminState>>,
-    Query(query): Query<AuditQuery>,
-) -> Response {
-    let filter = AuditFilter {
-        user_id: query.user_id,
-        action: query.action,
-        resource: query.resource,
-        from_timestamp: query.from_timestamp,
-        to_timestamp: query.to_timestamp,
-        limit: query.limit,
+/// Verify the audit hash chain and its external anchors (WORM mode).
+async fn verify_audit_chain(State(state): State<Arc<AdminState>>) -> Response {
+    let Some(anchorer) = &state.audit_anchorer else {
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
     };
+    match anchorer.verify().await {
+        Ok(report) => {
+            if !report.is_valid() {
+                tracing::warn!(
+                    errors = report.errors.len(),
+                    "Audit chain verification failed"
+                );
+            }
+            Json(report).into_response()
+        }
+        Err(e) => {
+            tracing::error!("Audit chain verification error: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
 
-    match state.audit_store.query(filter).await {
-        Ok(entries) => Json(entries).into_response(),
-        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+/// Anchor the audit chain head now instead of waiting for the next interval.
+async fn anchor_audit_chain(State(state): State<Arc<AdminState>>) -> Response {
+    let Some(anchorer) = &state.audit_anchorer else {
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    };
+    match anchorer.anchor_now().await {
+        Ok(Some(anchor)) => (StatusCode::CREATED, Json(anchor)).into_response(),
+        Ok(None) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => {
+            tracing::error!("Audit anchoring failed: {}", e);
+            (StatusCode::BAD_GATEWAY, e.to_string()).into_response()
+        }
     }
 }
 
-/// Export audit logs as JSON file.
-/// Export audit logs as JSON file.
+/// Export audit logs as a ZIP bundle (events, hashes, manifest, artifacts).
 async fn export_audit_log(State(state): State<Arc<AdminState>>) -> Response {
     let filter = AuditFilter {
         limit: Some(10000), // Hard limit for safety
@@ -946,40 +1766,13 @@ async fn get_metrics(State(state): State<Arc<AdminState>>) -> Response {
 // =========================================
 
 /// Serve static files from embedded assets.
-async fn static_handler(Path(path): Path<String>) -> Response {
-    let path = if path.is_empty() {
-        "index.html".to_string()
-    } else {
-        path
-    };
-
-    match Asset::get(&path) {
-        Some(content) => {
-            let mime = mime_guess::from_path(&path).first_or_octet_stream();
-            Response::builder()
-                .header(header::CONTENT_TYPE, mime.as_ref())
-                .body(Body::from(content.data.to_vec()))
-                .unwrap()
-        }
-        None => Response::builder()
-            .status(StatusCode::NOT_FOUND)
-            .body(Body::from("Not Found"))
-            .unwrap(),
-    }
+async fn static_handler(Path(path): Path<String>, headers: HeaderMap) -> Response {
+    static_assets::serve(&path, &headers)
 }
 
 /// Serve index.html for root path.
-async fn index_handler() -> Response {
-    match Asset::get("index.html") {
-        Some(content) => Response::builder()
-            .header(header::CONTENT_TYPE, "text/html")
-            .body(Body::from(content.data.to_vec()))
-            .unwrap(),
-        None => Response::builder()
-            .status(StatusCode::NOT_FOUND)
-            .body(Body::from("Dashboard not found"))
-            .unwrap(),
-    }
+async fn index_handler(headers: HeaderMap) -> Response {
+    static_assets::serve_index(&headers)
 }
 
 // =========================================
@@ -991,21 +1784,61 @@ pub fn admin_api_router(state: Arc<AdminState>) -> Router {
     let api_routes = Router::new()
         .route("/providers", get(list_providers).post(add_provider))
         .route("/providers/test", post(test_provider))
-        .route("/providers/:id", delete(delete_provider))
+        .route(
+            "/providers/:id",
+            delete(delete_provider)
+                .put(update_provider)
+                .patch(patch_provider),
+        )
         .route("/providers/:id/test", post(test_provider_by_id))
+        .route("/providers/:id/activate", post(activate_provider))
+        .route("/providers/:id/spend-cap", put(spend_caps::set_spend_cap))
+        .route("/providers/:id/model", put(canary::set_provider_model))
+        .route("/providers/:id/canary", get(canary::get_canary_run))
         .route("/config", get(get_config))
-        .route("/config/network", post(update_network_policy))
+        .route(
+            "/config/network",
+            get(get_network_policy).post(update_network_policy),
+        )
+        .route("/config/network/check", post(check_network_policy))
+        .route(
+            "/config/guardrails",
+            get(get_guardrail_policy).put(update_guardrail_policy),
+        )
         .route("/config/s3/test", post(test_s3_connection))
-        .route("/audit", get(get_audit))
+        .route("/audit", get(audit_view::get_audit))
+        .route("/audit/stream", get(audit_stream::stream_audit))
         .route("/audit/export", get(export_audit_log))
+        .route("/audit/export.csv", get(audit_view::export_audit_csv))
+        .route("/audit/verify", get(verify_audit_chain))
+        .route("/audit/anchor", post(anchor_audit_chain))
+        .route(
+            "/audit/presets",
+            get(audit_view::list_audit_presets).post(audit_view::save_audit_preset),
+        )
+        .route(
+            "/audit/presets/:name",
+            delete(audit_view::delete_audit_preset),
+        )
         .route("/metrics", get(get_metrics))
         .route("/mcp/servers", get(get_mcp_servers).post(register_mcp))
         .route("/mcp/servers/:id", delete(remove_mcp))
+        .route("/mcp/import", post(import_mcp))
+        .route("/mcp/processes", get(get_mcp_processes))
+        .route("/tools/analytics", get(get_tool_analytics))
+        .route(
+            "/openapi/specs",
+            get(list_openapi_specs).post(import_openapi),
+        )
+        .route("/openapi/specs/:namespace", delete(remove_openapi_spec))
         .route("/sessions", get(list_sessions_admin))
         .route(
             "/sessions/:id",
             get(get_session_admin).delete(delete_session_admin),
         )
+        .route("/sessions/:id/export", get(session_bundle::export_session))
+        .route("/sessions/import", post(session_bundle::import_session))
+        .route("/artifacts", get(list_artifacts))
         .route("/privacy/forget-user", post(forget_user))
         .route("/secrets/rotate", post(rotate_secrets_handler));
 
@@ -1013,8 +1846,12 @@ pub fn admin_api_router(state: Arc<AdminState>) -> Router {
         .merge(api_routes)
         // Public routes
         .route("/health", get(health))
-        .route("/dashboard/*file", get(dashboard_assets))
-        .route("/", get(dashboard_index))
+        .merge(
+            Router::new()
+                .route("/dashboard/*file", get(dashboard_assets))
+                .route("/", get(dashboard_index))
+                .layer(static_assets::compression_layer()),
+        )
         .layer(middleware::from_fn_with_state(
             state.clone(),
             auth_middleware,
@@ -1022,43 +1859,94 @@ pub fn admin_api_router(state: Arc<AdminState>) -> Router {
         .with_state(state)
 }
 
-async fn dashboard_index() -> impl IntoResponse {
-    dashboard_assets(Path("index.html".to_string())).await
+async fn dashboard_index(headers: HeaderMap) -> Response {
+    static_assets::serve("index.html", &headers)
 }
 
-async fn dashboard_assets(Path(file): Path<String>) -> impl IntoResponse {
-    let filename = if file.is_empty() || file == "/" {
-        "index.html"
-    } else {
-        &file
-    };
+async fn dashboard_assets(Path(file): Path<String>, headers: HeaderMap) -> Response {
+    static_assets::serve(&file, &headers)
+}
+
+/// Get the effective network policy; `source` tells whether it came from the
+/// config file, `network_policy.json` or the API.
+async fn get_network_policy(State(state): State<Arc<AdminState>>) -> Response {
+    Json(state.network_policy.read().await.clone()).into_response()
+}
+
+fn invalid_network_policy(problems: Vec<String>) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(serde_json::json!({
+            "error": "Invalid network policy",
+            "problems": problems,
+        })),
+    )
+        .into_response()
+}
 
-    match Asset::get(filename) {
-        Some(content) => {
-            let mime = mime_guess::from_path(filename).first_or_octet_stream();
-            ([(header::CONTENT_TYPE, mime.as_ref())], content.data).into_response()
+/// Body of a network policy dry run.
+#[derive(Deserialize)]
+struct NetworkCheckRequest {
+    urls: Vec<String>,
+    /// Candidate policy; the effective policy is used when absent.
+    #[serde(default)]
+    policy: Option<multi_agent_governance::network::NetworkPolicy>,
+}
+
+/// Check sample URLs against the effective or a candidate policy.
+async fn check_network_policy(
+    State(state): State<Arc<AdminState>>,
+    Json(request): Json<NetworkCheckRequest>,
+) -> Response {
+    use multi_agent_governance::network::NetworkDecision;
+
+    let policy = match request.policy {
+        Some(policy) => {
+            if let Err(problems) = policy.validate() {
+                return invalid_network_policy(problems);
+            }
+            policy
         }
-        None => StatusCode::NOT_FOUND.into_response(),
-    }
+        None => state.network_policy.read().await.clone(),
+    };
+    let results: Vec<_> = request
+        .urls
+        .iter()
+        .map(|url| match policy.check(url) {
+            Ok(NetworkDecision::Allowed) => serde_json::json!({ "url": url, "allowed": true }),
+            Ok(NetworkDecision::Denied(reason)) => {
+                serde_json::json!({ "url": url, "allowed": false, "reason": reason })
+            }
+            Err(e) => serde_json::json!({ "url": url, "allowed": false, "reason": e.to_string() }),
+        })
+        .collect();
+    Json(serde_json::json!({ "results": results })).into_response()
 }
 
 /// Update network policy.
 async fn update_network_policy(
     State(state): State<Arc<AdminState>>,
-    Json(policy): Json<multi_agent_governance::network::NetworkPolicy>,
+    Json(mut policy): Json<multi_agent_governance::network::NetworkPolicy>,
 ) -> Response {
+    if let Err(problems) = policy.validate() {
+        return invalid_network_policy(problems);
+    }
+    policy.version = uuid::Uuid::new_v4().to_string();
+    policy.source = multi_agent_governance::network::PolicySource::Api;
+
     // 1. Update in-memory
-    {
+    let previous = {
         let mut guard = state.network_policy.write().await;
-        *guard = policy.clone();
-        // Force new version
-        guard.version = uuid::Uuid::new_v4().to_string();
-    }
+        std::mem::replace(&mut *guard, policy.clone())
+    };
 
-    // 2. Persist to file (simple JSON dump)
-    let path = std::path::PathBuf::from("network_policy.json");
+    // 2. Persist to file (simple JSON dump), unless local state files are disabled
+    let path = state.app_config.state_path(StateFile::NetworkPolicy);
     // We could use a proper store, but for now this suffices as per plan.
-    if let Ok(json) = serde_json::to_string_pretty(&policy) {
+    if let (Some(path), Ok(json)) = (path, serde_json::to_string_pretty(&policy)) {
+        if let Some(parent) = path.parent() {
+            let _ = tokio::fs::create_dir_all(parent).await;
+        }
         if let Err(e) = tokio::fs::write(path, json).await {
             tracing::error!("Failed to persist network policy: {}", e);
             // Verify if we should return error?
@@ -1073,20 +1961,66 @@ async fn update_network_policy(
         .log(multi_agent_governance::AuditEntry {
             id: uuid::Uuid::new_v4().to_string(),
             timestamp: chrono::Utc::now().to_rfc3339(),
-            user_id: "admin".to_string(),
+            user_id: AuditContext::user_or("admin"),
             action: "UPDATE_NETWORK_POLICY".to_string(),
             resource: "network_policy".to_string(),
             outcome: multi_agent_governance::AuditOutcome::Success,
             metadata: Some(serde_json::json!({
                 "allow_domains_count": policy.allow_domains.len(),
-                "deny_domains_count": policy.deny_domains.len()
+                "deny_domains_count": policy.deny_domains.len(),
+                "previous_version": previous.version,
+                "version": policy.version,
+                "diff": previous.diff(&policy),
             })),
             previous_hash: None,
             hash: None,
+            trace_id: None,
+            session_id: None,
+        })
+        .await;
+
+    Json(policy).into_response()
+}
+
+/// Get the per-channel guardrail policy.
+async fn get_guardrail_policy(State(state): State<Arc<AdminState>>) -> Response {
+    Json(state.guardrails.policy()).into_response()
+}
+
+/// Replace the per-channel guardrail policy.
+async fn update_guardrail_policy(
+    State(state): State<Arc<AdminState>>,
+    Json(policy): Json<multi_agent_governance::GuardrailPolicy>,
+) -> Response {
+    if let Err(e) = state.guardrails.set_policy(policy.clone()) {
+        return (StatusCode::BAD_REQUEST, e.to_string()).into_response();
+    }
+
+    if let Ok(json) = serde_json::to_string_pretty(&policy) {
+        if let Err(e) = tokio::fs::write("guardrail_policy.json", json).await {
+            tracing::error!("Failed to persist guardrail policy: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    }
+
+    let _ = state
+        .audit_store
+        .log(multi_agent_governance::AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            user_id: AuditContext::user_or("admin"),
+            action: "UPDATE_GUARDRAIL_POLICY".to_string(),
+            resource: "guardrail_policy".to_string(),
+            outcome: multi_agent_governance::AuditOutcome::Success,
+            metadata: serde_json::to_value(&policy).ok(),
+            previous_hash: None,
+            hash: None,
+            trace_id: None,
+            session_id: None,
         })
         .await;
 
-    StatusCode::OK.into_response()
+    Json(policy).into_response()
 }
 
 /// Build the admin static asset router.
@@ -1094,6 +2028,7 @@ pub fn admin_static_router() -> Router {
     Router::new()
         .route("/", get(index_handler))
         .route("/*path", get(static_handler))
+        .layer(static_assets::compression_layer())
 }
 
 /// Build the consolidated admin router (backward compatibility).
@@ -0,0 +1,179 @@
+//! Inference clients for providers registered through the admin API.
+//!
+//! [`ProviderSync`] keeps the model gateway's [`ProviderRegistry`] in step
+//! with the provider store: every entry of a supported vendor gets a client
+//! built from its encrypted API key, so the model selector can route to it.
+//! Entries removed from the store are unregistered.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use multi_agent_core::traits::LlmClient;
+use multi_agent_model_gateway::{
+    ClientLayer, EndpointPool, ProviderRegistry, RigConfig, RigLlmClient, RigProvider,
+};
+
+use crate::{spend_caps, AdminState, ProviderEntry};
+
+/// Rig client for a provider entry; `None` if its vendor is unsupported.
+pub(crate) fn client_for(
+    state: &AdminState,
+    provider: &ProviderEntry,
+    api_key: String,
+) -> Option<RigLlmClient> {
+    let vendor = RigProvider::from_vendor(&provider.vendor)?;
+    let pool = state
+        .endpoint_registry
+        .as_ref()
+        .and_then(|registry| registry.get(&provider.id))
+        .or_else(|| {
+            (!provider.base_url.is_empty()).then(|| {
+                let urls = std::iter::once(&provider.base_url).chain(&provider.regional_urls);
+                Arc::new(EndpointPool::new(urls.cloned()))
+            })
+        });
+    let mut config = RigConfig {
+        provider: vendor,
+        model: provider.model_id.clone(),
+        ..RigConfig::default()
+    }
+    .with_api_key(api_key);
+    if let Some(pool) = pool {
+        config = config.with_endpoints(pool);
+    }
+    // Share quota state with the registry, which routes around throttled providers
+    let tracker = state
+        .provider_sync
+        .as_ref()
+        .and_then(|sync| sync.registry().rate_limits());
+    if let Some(tracker) = tracker {
+        config = config.with_rate_limits(tracker.clone());
+    }
+    Some(RigLlmClient::new(config))
+}
+
+/// Fields that require rebuilding a provider's client when they change.
+fn fingerprint(provider: &ProviderEntry) -> String {
+    format!(
+        "{}|{}|{}|{}|{}",
+        provider.vendor,
+        provider.model_id,
+        provider.base_url,
+        provider.regional_urls.join(","),
+        provider.api_key_id
+    )
+}
+
+/// Registers stored providers as inference targets.
+pub struct ProviderSync {
+    registry: Arc<ProviderRegistry>,
+    layer: Option<Box<ClientLayer>>,
+    /// Provider id -> (registry key, fingerprint).
+    registered: Mutex<HashMap<String, (String, String)>>,
+}
+
+impl ProviderSync {
+    pub fn new(registry: Arc<ProviderRegistry>) -> Self {
+        Self {
+            registry,
+            layer: None,
+            registered: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Wrap each registered client, given its pricing key (metering, queueing).
+    pub fn with_layer(
+        mut self,
+        layer: impl Fn(Arc<dyn LlmClient>, &str) -> Arc<dyn LlmClient> + Send + Sync + 'static,
+    ) -> Self {
+        self.layer = Some(Box::new(layer));
+        self
+    }
+
+    pub fn registry(&self) -> &Arc<ProviderRegistry> {
+        &self.registry
+    }
+
+    /// Reconcile the registry with the provider store; returns the number of
+    /// providers registered or removed.
+    pub async fn sync(&self, state: &AdminState) -> usize {
+        let providers = spend_caps::load_providers(state).await;
+        let mut registered = self.registered.lock().await;
+        let mut changes = 0;
+
+        let removed: Vec<String> = registered
+            .keys()
+            .filter(|id| !providers.iter().any(|p| &p.id == *id))
+            .cloned()
+            .collect();
+        for id in removed {
+            if let Some((key, _)) = registered.remove(&id) {
+                self.registry.unregister(&key);
+                // Entries sharing the key are registered again below
+                registered.retain(|_, (k, _)| *k != key);
+                tracing::info!(provider = %id, key = %key, "Unregistered inference provider");
+                changes += 1;
+            }
+        }
+
+        for provider in &providers {
+            let fingerprint = fingerprint(provider);
+            if registered
+                .get(&provider.id)
+                .is_some_and(|(_, f)| *f == fingerprint)
+            {
+                continue;
+            }
+            let api_key = match state.secrets.retrieve(&provider.api_key_id).await {
+                Ok(Some(key)) => key,
+                Ok(None) => {
+                    tracing::warn!(provider = %provider.id, "No API key stored for provider");
+                    continue;
+                }
+                Err(e) => {
+                    tracing::warn!(provider = %provider.id, error = %e, "Failed to decrypt provider API key");
+                    continue;
+                }
+            };
+            let Some(client) = client_for(state, provider, api_key) else {
+                tracing::debug!(provider = %provider.id, vendor = %provider.vendor, "Vendor not supported for inference");
+                continue;
+            };
+            let key = client.model_id();
+            let client: Arc<dyn LlmClient> = match &self.layer {
+                Some(layer) => layer(Arc::new(client), &key),
+                None => Arc::new(client),
+            };
+            if let Some((old_key, _)) = registered.get(&provider.id) {
+                if *old_key != key {
+                    self.registry.unregister(old_key);
+                }
+            }
+            let (vendor, model) = key.split_once(':').unwrap_or((&key, ""));
+            self.registry.register(vendor, model, client);
+            tracing::info!(provider = %provider.id, key = %key, "Registered inference provider");
+            registered.insert(provider.id.clone(), (key, fingerprint));
+            changes += 1;
+        }
+        changes
+    }
+
+    /// Run [`Self::sync`] every `interval`, picking up changes made by other
+    /// instances sharing the provider store.
+    pub fn spawn(
+        self: Arc<Self>,
+        state: Arc<AdminState>,
+        interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                self.sync(&state).await;
+            }
+        })
+    }
+}
@@ -0,0 +1,170 @@
+//! S3 object-lock notary for WORM audit anchoring.
+
+use async_trait::async_trait;
+use aws_sdk_s3::primitives::{ByteStream, DateTime};
+use aws_sdk_s3::types::{ChecksumAlgorithm, ObjectLockMode};
+use aws_sdk_s3::Client;
+use multi_agent_core::{Error, Result};
+use multi_agent_governance::{AuditAnchor, AuditNotary};
+use serde::{Deserialize, Serialize};
+
+/// Where an anchor object was written.
+#[derive(Debug, Serialize, Deserialize)]
+struct S3Receipt {
+    key: String,
+    version_id: Option<String>,
+}
+
+/// Writes each anchor statement as a compliance-mode object-locked S3 object.
+///
+/// The bucket must have object lock enabled. Verification reads back the
+/// exact object version and checks both its content and its lock.
+pub struct S3ObjectLockNotary {
+    client: Client,
+    bucket: String,
+    prefix: String,
+    retention_days: u32,
+}
+
+impl S3ObjectLockNotary {
+    /// Create a notary using the default AWS credential chain.
+    pub async fn new(
+        bucket: &str,
+        prefix: &str,
+        endpoint: Option<&str>,
+        retention_days: u32,
+    ) -> Self {
+        let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest());
+        if let Some(url) = endpoint {
+            loader = loader.endpoint_url(url);
+        }
+        let config = loader.load().await;
+        Self::new_with_client(Client::new(&config), bucket, prefix, retention_days)
+    }
+
+    /// Create with a custom client.
+    pub fn new_with_client(
+        client: Client,
+        bucket: &str,
+        prefix: &str,
+        retention_days: u32,
+    ) -> Self {
+        Self {
+            client,
+            bucket: bucket.to_string(),
+            prefix: prefix.to_string(),
+            retention_days,
+        }
+    }
+
+    fn key(&self, anchor: &AuditAnchor) -> String {
+        format!(
+            "{}{:012}-{}.json",
+            self.prefix, anchor.entry_count, anchor.head_hash
+        )
+    }
+}
+
+/// Object body for an anchor; the receipt is excluded since it is the output.
+fn anchor_body(anchor: &AuditAnchor) -> Vec<u8> {
+    serde_json::json!({
+        "id": anchor.id,
+        "head_hash": anchor.head_hash,
+        "entry_count": anchor.entry_count,
+        "anchored_at": anchor.anchored_at,
+        "statement": anchor.statement(),
+    })
+    .to_string()
+    .into_bytes()
+}
+
+#[async_trait]
+impl AuditNotary for S3ObjectLockNotary {
+    fn name(&self) -> &str {
+        "s3"
+    }
+
+    async fn anchor(&self, anchor: &AuditAnchor) -> Result<String> {
+        let key = self.key(anchor);
+        let retain_until =
+            DateTime::from_secs(anchor.anchored_at + i64::from(self.retention_days) * 86_400);
+        let output = self
+            .client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(&key)
+            .content_type("application/json")
+            .body(ByteStream::from(anchor_body(anchor)))
+            .object_lock_mode(ObjectLockMode::Compliance)
+            .object_lock_retain_until_date(retain_until)
+            .checksum_algorithm(ChecksumAlgorithm::Sha256)
+            .send()
+            .await
+            .map_err(|e| Error::governance(format!("S3 anchor write failed: {}", e)))?;
+
+        let receipt = S3Receipt {
+            key,
+            version_id: output.version_id().map(str::to_string),
+        };
+        serde_json::to_string(&receipt).map_err(|e| Error::internal(e.to_string()))
+    }
+
+    async fn verify(&self, anchor: &AuditAnchor) -> Result<()> {
+        let receipt: S3Receipt = serde_json::from_str(&anchor.receipt)
+            .map_err(|e| Error::governance(format!("Invalid S3 anchor receipt: {}", e)))?;
+
+        let mut request = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(&receipt.key);
+        if let Some(version) = &receipt.version_id {
+            request = request.version_id(version);
+        }
+        let object = request
+            .send()
+            .await
+            .map_err(|e| Error::governance(format!("S3 anchor read failed: {}", e)))?;
+
+        if object.object_lock_mode() != Some(&ObjectLockMode::Compliance) {
+            return Err(Error::governance(
+                "S3 anchor object is not compliance-locked",
+            ));
+        }
+        let body = object
+            .body
+            .collect()
+            .await
+            .map_err(|e| Error::governance(format!("S3 anchor read failed: {}", e)))?
+            .into_bytes();
+        if body.as_ref() != anchor_body(anchor).as_slice() {
+            return Err(Error::governance(
+                "S3 anchor object does not match the recorded anchor",
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchor_body_excludes_receipt() {
+        let mut anchor = AuditAnchor {
+            id: "a1".into(),
+            head_hash: "abc".into(),
+            entry_count: 42,
+            anchored_at: 1_700_000_000,
+            notary: "s3".into(),
+            receipt: String::new(),
+        };
+        let before = anchor_body(&anchor);
+        anchor.receipt = "{\"key\":\"k\"}".into();
+        assert_eq!(before, anchor_body(&anchor));
+
+        let body: serde_json::Value = serde_json::from_slice(&before).unwrap();
+        assert_eq!(body["statement"], "42:abc");
+    }
+}
@@ -0,0 +1,171 @@
+//! S3 bucket compliance checks (encryption, versioning, public access).
+
+use aws_sdk_s3::error::ProvideErrorMetadata;
+use aws_sdk_s3::Client;
+use serde::Serialize;
+
+/// Outcome of reading a single bucket setting.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "state", content = "value", rename_all = "snake_case")]
+pub enum BucketSetting<T> {
+    /// The setting is configured with the given value.
+    Configured(T),
+    /// The bucket has no such configuration.
+    Missing,
+    /// The setting could not be read (permissions, unsupported backend, ...).
+    Unknown(String),
+}
+
+/// Compliance report for an S3 bucket, returned by the connection test.
+#[derive(Debug, Serialize)]
+pub struct S3ComplianceReport {
+    /// Default server-side encryption algorithm (e.g. `AES256`, `aws:kms`).
+    pub encryption: BucketSetting<String>,
+    /// Versioning status (`Enabled` or `Suspended`).
+    pub versioning: BucketSetting<String>,
+    /// Whether all four public-access-block flags are set.
+    pub public_access_blocked: BucketSetting<bool>,
+    /// Human-readable compliance warnings for the admin UI.
+    pub warnings: Vec<String>,
+}
+
+impl S3ComplianceReport {
+    /// Build a report from probed settings, deriving the warnings.
+    pub fn new(
+        encryption: BucketSetting<String>,
+        versioning: BucketSetting<String>,
+        public_access_blocked: BucketSetting<bool>,
+    ) -> Self {
+        let mut warnings = Vec::new();
+
+        match &encryption {
+            BucketSetting::Configured(_) => {}
+            BucketSetting::Missing => {
+                warnings.push("Bucket has no default server-side encryption configured".to_string())
+            }
+            BucketSetting::Unknown(e) => {
+                warnings.push(format!("Could not verify bucket encryption: {}", e))
+            }
+        }
+
+        match &versioning {
+            BucketSetting::Configured(status) if status == "Enabled" => {}
+            BucketSetting::Configured(status) => {
+                warnings.push(format!("Bucket versioning is {}", status))
+            }
+            BucketSetting::Missing => {
+                warnings.push("Bucket versioning has never been enabled".to_string())
+            }
+            BucketSetting::Unknown(e) => {
+                warnings.push(format!("Could not verify bucket versioning: {}", e))
+            }
+        }
+
+        match &public_access_blocked {
+            BucketSetting::Configured(true) => {}
+            BucketSetting::Configured(false) => {
+                warnings.push("Public access block is only partially enabled".to_string())
+            }
+            BucketSetting::Missing => {
+                warnings.push("Bucket has no public access block configured".to_string())
+            }
+            BucketSetting::Unknown(e) => {
+                warnings.push(format!("Could not verify public access block: {}", e))
+            }
+        }
+
+        Self {
+            encryption,
+            versioning,
+            public_access_blocked,
+            warnings,
+        }
+    }
+
+    /// Probe the bucket's encryption, versioning and public-access-block settings.
+    pub async fn probe(client: &Client, bucket: &str) -> Self {
+        let encryption = match client.get_bucket_encryption().bucket(bucket).send().await {
+            Ok(output) => output
+                .server_side_encryption_configuration()
+                .and_then(|c| c.rules().first())
+                .and_then(|r| r.apply_server_side_encryption_by_default())
+                .map(|d| BucketSetting::Configured(d.sse_algorithm().as_str().to_string()))
+                .unwrap_or(BucketSetting::Missing),
+            Err(e) if e.code() == Some("ServerSideEncryptionConfigurationNotFoundError") => {
+                BucketSetting::Missing
+            }
+            Err(e) => BucketSetting::Unknown(error_label(&e)),
+        };
+
+        let versioning = match client.get_bucket_versioning().bucket(bucket).send().await {
+            Ok(output) => output
+                .status()
+                .map(|s| BucketSetting::Configured(s.as_str().to_string()))
+                .unwrap_or(BucketSetting::Missing),
+            Err(e) => BucketSetting::Unknown(error_label(&e)),
+        };
+
+        let public_access_blocked =
+            match client.get_public_access_block().bucket(bucket).send().await {
+                Ok(output) => output
+                    .public_access_block_configuration()
+                    .map(|c| {
+                        BucketSetting::Configured(
+                            c.block_public_acls().unwrap_or(false)
+                                && c.ignore_public_acls().unwrap_or(false)
+                                && c.block_public_policy().unwrap_or(false)
+                                && c.restrict_public_buckets().unwrap_or(false),
+                        )
+                    })
+                    .unwrap_or(BucketSetting::Missing),
+                Err(e) if e.code() == Some("NoSuchPublicAccessBlockConfiguration") => {
+                    BucketSetting::Missing
+                }
+                Err(e) => BucketSetting::Unknown(error_label(&e)),
+            };
+
+        Self::new(encryption, versioning, public_access_blocked)
+    }
+}
+
+fn error_label(err: &impl ProvideErrorMetadata) -> String {
+    err.code().unwrap_or("request failed").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compliant_bucket_has_no_warnings() {
+        let report = S3ComplianceReport::new(
+            BucketSetting::Configured("aws:kms".into()),
+            BucketSetting::Configured("Enabled".into()),
+            BucketSetting::Configured(true),
+        );
+        assert!(report.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_non_compliant_bucket_warns_per_setting() {
+        let report = S3ComplianceReport::new(
+            BucketSetting::Missing,
+            BucketSetting::Configured("Suspended".into()),
+            BucketSetting::Unknown("AccessDenied".into()),
+        );
+        assert_eq!(report.warnings.len(), 3);
+        assert!(report.warnings[1].contains("Suspended"));
+        assert!(report.warnings[2].contains("AccessDenied"));
+    }
+
+    #[test]
+    fn test_setting_serialization() {
+        let json = serde_json::to_value(BucketSetting::Configured("AES256".to_string())).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"state": "configured", "value": "AES256"})
+        );
+        let json = serde_json::to_value(BucketSetting::<bool>::Missing).unwrap();
+        assert_eq!(json, serde_json::json!({"state": "missing"}));
+    }
+}
@@ -0,0 +1,356 @@
+//! Session export/import bundles for support and reproduction.
+//!
+//! `GET /sessions/:id/export` packs a session with the artifacts it references
+//! and its audit trail into one JSON bundle that users can attach to a bug
+//! report. `POST /sessions/import` loads a bundle into another (typically dev)
+//! instance: the session and its artifacts are restored, while the audit
+//! entries stay in the bundle for inspection so the local audit chain is not
+//! altered.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use base64::Engine;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use multi_agent_core::types::{RefId, Session, SessionStatus};
+use multi_agent_core::Error;
+use multi_agent_governance::rbac::UserRoles;
+use multi_agent_governance::{AuditEntry, AuditFilter, AuditOutcome};
+
+use crate::AdminState;
+
+/// Bundle format understood by this version.
+pub const BUNDLE_VERSION: u32 = 1;
+/// Artifacts larger than this are listed without their content.
+const MAX_ARTIFACT_BYTES: usize = 16 * 1024 * 1024;
+
+/// A session packed for support and reproduction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionBundle {
+    pub version: u32,
+    /// RFC 3339 export time.
+    pub exported_at: String,
+    pub session: Session,
+    /// Artifacts referenced by the session.
+    #[serde(default)]
+    pub artifacts: Vec<BundledArtifact>,
+    /// Audit entries recorded for the session.
+    #[serde(default)]
+    pub events: Vec<AuditEntry>,
+    /// Approval, guardrail and policy decisions taken during the session.
+    #[serde(default)]
+    pub policy_decisions: Vec<AuditEntry>,
+}
+
+/// An artifact inside a bundle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundledArtifact {
+    pub id: String,
+    pub content_type: String,
+    pub size: usize,
+    /// Base64 content; omitted for artifacts over the size limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+}
+
+/// Options for `POST /sessions/import`.
+#[derive(Debug, Default, Deserialize)]
+pub struct ImportQuery {
+    /// Replace an existing session with the same id.
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+/// Candidate artifact ids referenced by a session: UUID-shaped tokens in its
+/// text, and string values of tool arguments named like references.
+pub fn referenced_ids(session: &Session) -> BTreeSet<String> {
+    let mut ids = BTreeSet::new();
+    let mut scan = |text: &str| {
+        for token in text.split(|c: char| !(c.is_ascii_hexdigit() || c == '-')) {
+            if token.len() == 36 && uuid::Uuid::parse_str(token).is_ok() {
+                ids.insert(token.to_string());
+            }
+        }
+    };
+    for entry in &session.history {
+        scan(&entry.content);
+        let Some(call) = &entry.tool_call else {
+            continue;
+        };
+        scan(&call.arguments.to_string());
+        if let Some(result) = &call.result {
+            scan(result);
+        }
+    }
+    if let Some(task) = &session.task_state {
+        scan(&task.goal);
+        for observation in &task.observations {
+            scan(observation);
+        }
+    }
+    for call in session.history.iter().filter_map(|e| e.tool_call.as_ref()) {
+        let Some(args) = call.arguments.as_object() else {
+            continue;
+        };
+        for (key, value) in args {
+            if let (true, Some(id)) = (is_reference_key(key), value.as_str()) {
+                ids.insert(id.to_string());
+            }
+        }
+    }
+    ids
+}
+
+fn is_reference_key(key: &str) -> bool {
+    let key = key.to_lowercase();
+    key == "ref" || key.ends_with("ref_id") || key.ends_with("artifact_id")
+}
+
+/// Whether an audit entry records a policy decision.
+fn is_policy_decision(entry: &AuditEntry) -> bool {
+    let action = entry.action.to_lowercase();
+    entry.user_id == "approval_gate"
+        || ["approval", "guardrail", "policy"]
+            .iter()
+            .any(|prefix| action.starts_with(prefix))
+}
+
+/// Whether an audit entry belongs to `session_id`.
+fn concerns_session(entry: &AuditEntry, session_id: &str) -> bool {
+    entry.resource == session_id
+        || entry
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get("session_id"))
+            .and_then(|v| v.as_str())
+            == Some(session_id)
+}
+
+/// Build the bundle for a session; `None` if it does not exist.
+pub async fn export_bundle(
+    state: &AdminState,
+    session_id: &str,
+) -> multi_agent_core::Result<Option<SessionBundle>> {
+    let Some(store) = &state.session_store else {
+        return Ok(None);
+    };
+    let Some(session) = store.load(session_id).await? else {
+        return Ok(None);
+    };
+
+    let mut artifacts = Vec::new();
+    if let Some(artifact_store) = &state.artifact_store {
+        for id in referenced_ids(&session) {
+            let ref_id = RefId::from_string(id.clone());
+            let Some(data) = artifact_store.load(&ref_id).await? else {
+                continue;
+            };
+            let content_type = artifact_store
+                .metadata(&ref_id)
+                .await?
+                .map(|m| m.content_type)
+                .unwrap_or_else(|| "application/octet-stream".to_string());
+            artifacts.push(BundledArtifact {
+                id,
+                content_type,
+                size: data.len(),
+                data: (data.len() <= MAX_ARTIFACT_BYTES)
+                    .then(|| base64::engine::general_purpose::STANDARD.encode(&data)),
+            });
+        }
+    }
+
+    let created = chrono::DateTime::from_timestamp(session.created_at, 0)
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_default();
+    let (policy_decisions, events): (Vec<AuditEntry>, Vec<AuditEntry>) = state
+        .audit_store
+        .query(AuditFilter {
+            from_timestamp: Some(created),
+            ..AuditFilter::default()
+        })
+        .await?
+        .into_iter()
+        .filter(|entry| concerns_session(entry, session_id))
+        .partition(is_policy_decision);
+
+    Ok(Some(SessionBundle {
+        version: BUNDLE_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        session,
+        artifacts,
+        events,
+        policy_decisions,
+    }))
+}
+
+async fn audit(
+    state: &AdminState,
+    user_id: &str,
+    action: &str,
+    session_id: &str,
+    metadata: serde_json::Value,
+) {
+    let _ = state
+        .audit_store
+        .log(AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            user_id: user_id.to_string(),
+            action: action.to_string(),
+            resource: session_id.to_string(),
+            outcome: AuditOutcome::Success,
+            metadata: Some(metadata),
+            previous_hash: None,
+            hash: None,
+            trace_id: None,
+            session_id: None,
+        })
+        .await;
+}
+
+fn admin_id(roles: &Option<Extension<UserRoles>>) -> String {
+    roles
+        .as_ref()
+        .map(|Extension(r)| r.user_id.clone())
+        .unwrap_or_else(|| "admin".to_string())
+}
+
+/// Download a session bundle.
+pub(crate) async fn export_session(
+    State(state): State<Arc<AdminState>>,
+    roles: Option<Extension<UserRoles>>,
+    Path(id): Path<String>,
+) -> Response {
+    if state.session_store.is_none() {
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    }
+    match export_bundle(&state, &id).await {
+        Ok(Some(bundle)) => {
+            audit(
+                &state,
+                &admin_id(&roles),
+                "EXPORT_SESSION",
+                &id,
+                serde_json::json!({ "artifacts": bundle.artifacts.len() }),
+            )
+            .await;
+            let disposition = format!("attachment; filename=\"session-{}.json\"", id);
+            ([(header::CONTENT_DISPOSITION, disposition)], Json(bundle)).into_response()
+        }
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            tracing::error!(session = %id, error = %e, "Failed to export session");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Load a session bundle into this instance.
+pub(crate) async fn import_session(
+    State(state): State<Arc<AdminState>>,
+    roles: Option<Extension<UserRoles>>,
+    Query(query): Query<ImportQuery>,
+    Json(mut bundle): Json<SessionBundle>,
+) -> Response {
+    let Some(store) = &state.session_store else {
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    };
+    if bundle.version > BUNDLE_VERSION {
+        return (
+            StatusCode::BAD_REQUEST,
+            format!("Unsupported bundle version {}", bundle.version),
+        )
+            .into_response();
+    }
+    let id = bundle.session.id.clone();
+    match store.load(&id).await {
+        Ok(Some(_)) if !query.overwrite => {
+            return (StatusCode::CONFLICT, "Session already exists").into_response()
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!(session = %id, error = %e, "Failed to check session");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    }
+
+    let mut restored = 0;
+    if let Some(artifact_store) = &state.artifact_store {
+        for artifact in &bundle.artifacts {
+            let Some(data) = &artifact.data else {
+                continue;
+            };
+            let Ok(data) = base64::engine::general_purpose::STANDARD.decode(data) else {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    format!("Artifact {} is not valid base64", artifact.id),
+                )
+                    .into_response();
+            };
+            let ref_id = RefId::from_string(artifact.id.clone());
+            if let Err(e) = artifact_store
+                .save_with_id(&ref_id, Bytes::from(data))
+                .await
+            {
+                if let Error::FileTypeRejected {
+                    content_type,
+                    size,
+                    reason,
+                } = &e
+                {
+                    return (
+                        StatusCode::UNPROCESSABLE_ENTITY,
+                        Json(serde_json::json!({
+                            "error": e.to_string(),
+                            "code": "FILE_TYPE_REJECTED",
+                            "artifact_id": artifact.id,
+                            "content_type": content_type,
+                            "size": size,
+                            "reason": reason,
+                        })),
+                    )
+                        .into_response();
+                }
+                tracing::error!(artifact = %artifact.id, error = %e, "Failed to restore artifact");
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+            restored += 1;
+        }
+    }
+
+    // An imported session must not be resumed as if it were still running here
+    if bundle.session.status == SessionStatus::Running {
+        bundle.session.status = SessionStatus::Paused;
+    }
+    if let Err(e) = store.save(&bundle.session).await {
+        tracing::error!(session = %id, error = %e, "Failed to import session");
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+    audit(
+        &state,
+        &admin_id(&roles),
+        "IMPORT_SESSION",
+        &id,
+        serde_json::json!({
+            "exported_at": bundle.exported_at,
+            "artifacts": restored,
+        }),
+    )
+    .await;
+
+    Json(serde_json::json!({
+        "session_id": id,
+        "artifacts_restored": restored,
+        "events": bundle.events.len(),
+        "policy_decisions": bundle.policy_decisions.len(),
+    }))
+    .into_response()
+}
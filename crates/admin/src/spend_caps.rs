@@ -0,0 +1,262 @@
+//! Monthly spend caps on provider entries.
+//!
+//! Admins set a cap per provider with `PUT /providers/:id/spend-cap`. The
+//! [`SpendCapEnforcer`] periodically compares month-to-date spend against the
+//! caps: a provider over its cap is marked `over_budget` and blocked by the
+//! model gateway's [`SpendGuard`] for non-privileged workspaces; it is
+//! re-activated when the month rolls over or the cap is raised. Both
+//! transitions are audited and sent to the notification sinks.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+use multi_agent_core::traits::{Notification, NotificationSeverity, NotificationSink};
+use multi_agent_governance::{AuditEntry, AuditOutcome};
+use multi_agent_model_gateway::{SpendCap, SpendCapChange, SpendGuard};
+
+use crate::{AdminState, ProviderEntry, SpendCapRequest};
+
+/// Status of a provider disabled by its spend cap.
+pub const OVER_BUDGET_STATUS: &str = "over_budget";
+
+/// Pricing key of a provider entry, as used by the usage ledger.
+pub fn pricing_key(provider: &ProviderEntry) -> String {
+    format!("{}:{}", provider.vendor.to_lowercase(), provider.model_id)
+}
+
+/// All provider entries, from the provider store if configured.
+pub async fn load_providers(state: &AdminState) -> Vec<ProviderEntry> {
+    match &state.provider_store {
+        Some(store) => store
+            .list()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|p| ProviderEntry {
+                id: p.id,
+                vendor: p.vendor,
+                model_id: p.model_id,
+                description: p.description,
+                base_url: p.base_url,
+                regional_urls: p.regional_urls,
+                version: p.version,
+                api_key_id: p.api_key_id,
+                capabilities: p.capabilities,
+                status: p.status,
+                monthly_spend_cap_usd: p.monthly_spend_cap_usd,
+                endpoints: Vec::new(),
+            })
+            .collect(),
+        None => state.providers.read().await.clone(),
+    }
+}
+
+/// Apply `update` to a provider; returns false if it does not exist.
+async fn update_provider(
+    state: &AdminState,
+    id: &str,
+    update: impl FnOnce(&mut Option<f64>, &mut String),
+) -> multi_agent_core::Result<bool> {
+    match &state.provider_store {
+        Some(store) => {
+            let Some(mut provider) = store.get(id).await? else {
+                return Ok(false);
+            };
+            update(&mut provider.monthly_spend_cap_usd, &mut provider.status);
+            store.upsert(&provider).await?;
+            Ok(true)
+        }
+        None => {
+            let mut providers = state.providers.write().await;
+            let Some(provider) = providers.iter_mut().find(|p| p.id == id) else {
+                return Ok(false);
+            };
+            update(&mut provider.monthly_spend_cap_usd, &mut provider.status);
+            Ok(true)
+        }
+    }
+}
+
+async fn audit(
+    state: &AdminState,
+    user_id: &str,
+    action: &str,
+    resource: &str,
+    metadata: serde_json::Value,
+) {
+    let _ = state
+        .audit_store
+        .log(AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            user_id: user_id.to_string(),
+            action: action.to_string(),
+            resource: resource.to_string(),
+            outcome: AuditOutcome::Success,
+            metadata: Some(metadata),
+            previous_hash: None,
+            hash: None,
+            trace_id: None,
+            session_id: None,
+        })
+        .await;
+}
+
+/// Set or clear a provider's monthly spend cap.
+pub(crate) async fn set_spend_cap(
+    State(state): State<Arc<AdminState>>,
+    Path(id): Path<String>,
+    Json(req): Json<SpendCapRequest>,
+) -> Response {
+    if req
+        .monthly_spend_cap_usd
+        .is_some_and(|cap| !cap.is_finite() || cap < 0.0)
+    {
+        return (
+            StatusCode::BAD_REQUEST,
+            "Spend cap must be a non-negative amount",
+        )
+            .into_response();
+    }
+    match update_provider(&state, &id, |cap, _| *cap = req.monthly_spend_cap_usd).await {
+        Ok(true) => {
+            audit(
+                &state,
+                "admin",
+                "SET_PROVIDER_SPEND_CAP",
+                &id,
+                serde_json::json!({ "monthly_spend_cap_usd": req.monthly_spend_cap_usd }),
+            )
+            .await;
+            Json(serde_json::json!({
+                "id": id,
+                "monthly_spend_cap_usd": req.monthly_spend_cap_usd,
+            }))
+            .into_response()
+        }
+        Ok(false) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to update spend cap");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Disables providers over their monthly spend cap and re-enables them
+/// when spend falls back under it.
+pub struct SpendCapEnforcer {
+    state: Arc<AdminState>,
+    guard: Arc<SpendGuard>,
+    sinks: Vec<Arc<dyn NotificationSink>>,
+}
+
+impl SpendCapEnforcer {
+    pub fn new(state: Arc<AdminState>, guard: Arc<SpendGuard>) -> Self {
+        Self {
+            state,
+            guard,
+            sinks: Vec::new(),
+        }
+    }
+
+    pub fn with_sink(mut self, sink: Arc<dyn NotificationSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Check all caps once; returns the providers whose state changed.
+    pub async fn enforce(&self) -> Vec<SpendCapChange> {
+        let caps: Vec<SpendCap> = load_providers(&self.state)
+            .await
+            .iter()
+            .filter_map(|p| {
+                p.monthly_spend_cap_usd.map(|limit| SpendCap {
+                    provider_id: p.id.clone(),
+                    model: pricing_key(p),
+                    monthly_limit_usd: limit,
+                })
+            })
+            .collect();
+        let changes = self.guard.evaluate(&caps);
+        for change in &changes {
+            self.apply(change).await;
+        }
+        changes
+    }
+
+    async fn apply(&self, change: &SpendCapChange) {
+        let status = if change.exceeded {
+            OVER_BUDGET_STATUS
+        } else {
+            "active"
+        };
+        let result = update_provider(&self.state, &change.provider_id, |_, s| {
+            // Only lift the block the cap put in place
+            if change.exceeded || s == OVER_BUDGET_STATUS {
+                *s = status.to_string();
+            }
+        })
+        .await;
+        if let Err(e) = result {
+            tracing::error!(provider = %change.provider_id, error = %e, "Failed to update provider status");
+        }
+
+        let (action, notification) = if change.exceeded {
+            (
+                "PROVIDER_SPEND_CAP_EXCEEDED",
+                Notification::new(
+                    NotificationSeverity::Critical,
+                    "Provider spend cap reached",
+                    format!(
+                        "Provider {} ({}) spent ${:.2} this month, reaching its ${:.2} cap. \
+                         It is disabled for non-privileged workspaces until the cap resets.",
+                        change.provider_id, change.model, change.spent_usd, change.limit_usd
+                    ),
+                ),
+            )
+        } else {
+            (
+                "PROVIDER_SPEND_CAP_RESET",
+                Notification::new(
+                    NotificationSeverity::Info,
+                    "Provider re-enabled",
+                    format!(
+                        "Provider {} ({}) is back under its ${:.2} monthly cap.",
+                        change.provider_id, change.model, change.limit_usd
+                    ),
+                ),
+            )
+        };
+        audit(
+            &self.state,
+            "system",
+            action,
+            &change.provider_id,
+            serde_json::to_value(change).unwrap_or_default(),
+        )
+        .await;
+        for sink in &self.sinks {
+            if let Err(e) = sink.send(&notification).await {
+                tracing::warn!(sink = sink.name(), error = %e, "Notification delivery failed");
+            }
+        }
+    }
+
+    /// Run [`Self::enforce`] every `interval`.
+    pub fn spawn(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                self.enforce().await;
+            }
+        })
+    }
+}
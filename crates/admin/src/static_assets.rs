@@ -0,0 +1,192 @@
+//! Embedded dashboard assets.
+//!
+//! Every asset is served with a strong `ETag` (its SHA-256) and answers a
+//! matching `If-None-Match` with `304 Not Modified`. `index.html` references
+//! its scripts and stylesheets by fingerprinted name (`app.<hash>.js`); those
+//! paths are content-addressed and cached as `immutable`, while unfingerprinted
+//! paths must be revalidated. Responses are compressed with brotli or gzip by
+//! [`compression_layer`] according to `Accept-Encoding`.
+
+use axum::{
+    body::Body,
+    http::{header, HeaderMap, StatusCode},
+    response::Response,
+};
+use rust_embed::RustEmbed;
+use sha2::{Digest, Sha256};
+use std::sync::OnceLock;
+use tower_http::compression::CompressionLayer;
+
+/// Embedded static assets for the dashboard.
+#[derive(RustEmbed)]
+#[folder = "../../dashboard/static"]
+struct Asset;
+
+/// Hex characters of the content hash kept in fingerprinted file names.
+const FINGERPRINT_LEN: usize = 8;
+
+/// Cache policy of fingerprinted paths, whose content never changes.
+const IMMUTABLE_CACHE: &str = "public, max-age=31536000, immutable";
+
+/// Cache policy of plain paths: cache, but revalidate with the ETag first.
+const REVALIDATE_CACHE: &str = "no-cache";
+
+fn content_hash(path: &str) -> Option<String> {
+    Asset::get(path).map(|file| hex::encode(file.metadata.sha256_hash()))
+}
+
+/// Fingerprinted name of an embedded asset, e.g. `app.js` -> `app.1a2b3c4d.js`.
+pub fn fingerprinted_path(path: &str) -> Option<String> {
+    let (stem, ext) = path.rsplit_once('.')?;
+    let hash = content_hash(path)?;
+    Some(format!("{}.{}.{}", stem, &hash[..FINGERPRINT_LEN], ext))
+}
+
+/// Resolves a fingerprinted name back to its asset, if the fingerprint still
+/// matches the embedded content.
+fn resolve_fingerprinted(path: &str) -> Option<String> {
+    let (rest, ext) = path.rsplit_once('.')?;
+    let (stem, fingerprint) = rest.rsplit_once('.')?;
+    if fingerprint.len() != FINGERPRINT_LEN {
+        return None;
+    }
+    let original = format!("{}.{}", stem, ext);
+    content_hash(&original)
+        .filter(|hash| hash.starts_with(fingerprint))
+        .map(|_| original)
+}
+
+/// `index.html` with asset references rewritten to their fingerprinted names.
+fn index_html() -> &'static [u8] {
+    static INDEX: OnceLock<Vec<u8>> = OnceLock::new();
+    INDEX.get_or_init(|| {
+        let Some(file) = Asset::get("index.html") else {
+            return Vec::new();
+        };
+        let mut html = String::from_utf8_lossy(&file.data).into_owned();
+        for path in Asset::iter().filter(|p| p != "index.html") {
+            if let Some(fingerprinted) = fingerprinted_path(&path) {
+                html = html
+                    .replace(
+                        &format!("src=\"{}\"", path),
+                        &format!("src=\"{}\"", fingerprinted),
+                    )
+                    .replace(
+                        &format!("href=\"{}\"", path),
+                        &format!("href=\"{}\"", fingerprinted),
+                    );
+            }
+        }
+        html.into_bytes()
+    })
+}
+
+fn etag_matches(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|candidate| {
+            let candidate = candidate.trim();
+            candidate == "*" || candidate.trim_start_matches("W/") == etag
+        })
+}
+
+fn not_found(message: &'static str) -> Response {
+    Response::builder()
+        .status(StatusCode::NOT_FOUND)
+        .body(Body::from(message))
+        .unwrap()
+}
+
+/// Serves the embedded asset at `path`; an empty path serves `index.html`.
+pub fn serve(path: &str, headers: &HeaderMap) -> Response {
+    let path = path.trim_start_matches('/');
+    let path = if path.is_empty() { "index.html" } else { path };
+
+    let (asset, cache_control) = if Asset::get(path).is_some() {
+        (path.to_string(), REVALIDATE_CACHE)
+    } else if let Some(original) = resolve_fingerprinted(path) {
+        (original, IMMUTABLE_CACHE)
+    } else {
+        return not_found("Not Found");
+    };
+
+    let (body, etag) = if asset == "index.html" {
+        let body = index_html();
+        let etag = format!("\"{}\"", hex::encode(Sha256::digest(body)));
+        (body.to_vec(), etag)
+    } else {
+        let Some(file) = Asset::get(&asset) else {
+            return not_found("Not Found");
+        };
+        let etag = format!("\"{}\"", hex::encode(file.metadata.sha256_hash()));
+        (file.data.to_vec(), etag)
+    };
+
+    if etag_matches(headers, &etag) {
+        return Response::builder()
+            .status(StatusCode::NOT_MODIFIED)
+            .header(header::ETAG, etag)
+            .header(header::CACHE_CONTROL, cache_control)
+            .body(Body::empty())
+            .unwrap();
+    }
+
+    let mime = mime_guess::from_path(&asset).first_or_octet_stream();
+    Response::builder()
+        .header(header::CONTENT_TYPE, mime.as_ref())
+        .header(header::ETAG, etag)
+        .header(header::CACHE_CONTROL, cache_control)
+        .body(Body::from(body))
+        .unwrap()
+}
+
+/// Serves `index.html`, reporting a missing dashboard build.
+pub fn serve_index(headers: &HeaderMap) -> Response {
+    if Asset::get("index.html").is_none() {
+        return not_found("Dashboard not found");
+    }
+    serve("index.html", headers)
+}
+
+/// Brotli/gzip compression for asset responses.
+pub fn compression_layer() -> CompressionLayer {
+    CompressionLayer::new().br(true).gzip(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprinted_path_round_trip() {
+        let fingerprinted = fingerprinted_path("app.js").unwrap();
+        assert_ne!(fingerprinted, "app.js");
+        assert!(fingerprinted.starts_with("app.") && fingerprinted.ends_with(".js"));
+        assert_eq!(
+            resolve_fingerprinted(&fingerprinted).as_deref(),
+            Some("app.js")
+        );
+
+        assert_eq!(resolve_fingerprinted("app.00000000.js"), None);
+        assert_eq!(resolve_fingerprinted("app.js"), None);
+    }
+
+    #[test]
+    fn test_index_references_fingerprinted_assets() {
+        let html = String::from_utf8_lossy(index_html()).into_owned();
+        let script = fingerprinted_path("app.js").unwrap();
+        assert!(html.contains(&format!("src=\"{}\"", script)));
+        assert!(!html.contains("src=\"app.js\""));
+    }
+
+    #[test]
+    fn test_if_none_match() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, "\"abc\", W/\"def\"".parse().unwrap());
+        assert!(etag_matches(&headers, "\"def\""));
+        assert!(!etag_matches(&headers, "\"xyz\""));
+    }
+}
@@ -4,8 +4,8 @@ use axum::{
 };
 use multi_agent_admin::AdminState;
 use multi_agent_governance::{
-    network::NetworkPolicy, AesGcmSecretsManager, InMemoryAuditStore, NoOpRbacConnector,
-    SecretsManager,
+    network::NetworkPolicy, AesGcmSecretsManager, AuditEntry, AuditOutcome, AuditStore,
+    InMemoryAuditStore, NoOpRbacConnector, SecretsManager,
 };
 use multi_agent_skills::McpRegistry;
 use serde_json::{json, Value};
@@ -13,27 +13,39 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use tower::ServiceExt;
 
-#[tokio::test]
-async fn test_admin_provider_crud_with_encryption() {
-    let audit_store = Arc::new(InMemoryAuditStore::new());
-    let rbac = Arc::new(NoOpRbacConnector);
-    let mcp_registry = Arc::new(McpRegistry::new());
-    let secrets = Arc::new(AesGcmSecretsManager::new(None));
-    let providers = Arc::new(RwLock::new(Vec::new()));
-
-    let state = Arc::new(AdminState {
-        audit_store,
-        rbac,
+fn test_admin_state() -> AdminState {
+    AdminState {
+        audit_store: Arc::new(InMemoryAuditStore::new()),
+        rbac: Arc::new(NoOpRbacConnector),
         metrics: None,
-        mcp_registry,
-        providers,
+        mcp_registry: Arc::new(McpRegistry::new()),
+        openapi_registry: None,
+        providers: Arc::new(RwLock::new(Vec::new())),
         provider_store: None,
-        secrets: secrets.clone(),
+        secrets: Arc::new(AesGcmSecretsManager::new(None)),
         privacy_controller: None,
         artifact_store: None,
         session_store: None,
         app_config: multi_agent_core::config::AppConfig::default(),
         network_policy: Arc::new(RwLock::new(NetworkPolicy::default())),
+        audit_presets: Arc::new(multi_agent_admin::audit_view::AuditPresetStore::new()),
+        audit_anchorer: None,
+        guardrails: Arc::new(multi_agent_governance::RouteGuardrails::default()),
+        tool_analytics: None,
+        endpoint_registry: None,
+        active_client: None,
+        provider_sync: None,
+        model_canary: None,
+    }
+}
+
+#[tokio::test]
+async fn test_admin_provider_crud_with_encryption() {
+    let secrets = Arc::new(AesGcmSecretsManager::new(None));
+
+    let state = Arc::new(AdminState {
+        secrets: secrets.clone(),
+        ..test_admin_state()
     });
 
     let app = multi_agent_admin::admin_router(state);
@@ -97,7 +109,7 @@ async fn test_admin_provider_crud_with_encryption() {
         .await
         .unwrap();
     let list: Value = serde_json::from_slice(&body).unwrap();
-    assert!(list.as_array().unwrap().len() > 0);
+    assert!(!list.as_array().unwrap().is_empty());
 
     // 4. Delete provider
     let response = app
@@ -105,7 +117,7 @@ async fn test_admin_provider_crud_with_encryption() {
         .oneshot(
             Request::builder()
                 .method("DELETE")
-                .uri(&format!("/api/providers/{}", provider_id))
+                .uri(format!("/api/providers/{}", provider_id))
                 .header("Authorization", "Bearer admin")
                 .body(Body::empty())
                 .unwrap(),
@@ -118,3 +130,1277 @@ async fn test_admin_provider_crud_with_encryption() {
     let retrieved_key_after_delete = secrets.retrieve(&api_key_id).await.unwrap();
     assert!(retrieved_key_after_delete.is_none());
 }
+
+#[tokio::test]
+async fn test_provider_update_rotates_key_and_audits_changes() {
+    let audit_store = Arc::new(InMemoryAuditStore::new());
+    let secrets = Arc::new(AesGcmSecretsManager::new(None));
+    let state = Arc::new(AdminState {
+        audit_store: audit_store.clone(),
+        secrets: secrets.clone(),
+        ..test_admin_state()
+    });
+    let app = multi_agent_admin::admin_router(state.clone());
+    let send = |method: &str, uri: String, body: Value| {
+        app.clone().oneshot(
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("Content-Type", "application/json")
+                .header("Authorization", "Bearer admin")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+    };
+
+    let response = send(
+        "POST",
+        "/api/providers".to_string(),
+        json!({
+            "vendor": "openai",
+            "model_id": "gpt-4",
+            "description": "Primary",
+            "base_url": "https://api.openai.com/v1",
+            "api_key": "sk-old",
+            "capabilities": ["text"]
+        }),
+    )
+    .await
+    .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let provider_id = serde_json::from_slice::<Value>(&body).unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let old_key_id = format!("api_key:{}", provider_id);
+
+    // PATCH changes only the given fields; null clears the description
+    let response = send(
+        "PATCH",
+        format!("/api/providers/{}", provider_id),
+        json!({ "base_url": "https://proxy.internal/v1", "description": null }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let patched: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(patched["base_url"], "https://proxy.internal/v1");
+    assert!(patched["description"].is_null());
+    assert_eq!(patched["capabilities"], json!(["text"]));
+    assert_eq!(patched["model_id"], "gpt-4");
+
+    // PUT replaces the editable fields and rotates the key
+    let response = send(
+        "PUT",
+        format!("/api/providers/{}", provider_id),
+        json!({
+            "base_url": "https://proxy.internal/v1",
+            "description": "Behind the proxy",
+            "capabilities": ["text", "vision"],
+            "api_key": "sk-new"
+        }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let provider = state.providers.read().await[0].clone();
+    assert_eq!(provider.capabilities, vec!["text", "vision"]);
+    assert_ne!(provider.api_key_id, old_key_id);
+    assert_eq!(
+        secrets.retrieve(&provider.api_key_id).await.unwrap(),
+        Some("sk-new".to_string())
+    );
+    assert!(secrets.retrieve(&old_key_id).await.unwrap().is_none());
+
+    let response = send(
+        "PUT",
+        format!("/api/providers/{}", provider_id),
+        json!({ "base_url": " ", "capabilities": [] }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = send(
+        "PATCH",
+        "/api/providers/prov-missing".to_string(),
+        json!({ "description": "x" }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let entries = audit_store
+        .query(multi_agent_governance::AuditFilter::default())
+        .await
+        .unwrap();
+    let updates: Vec<&AuditEntry> = entries
+        .iter()
+        .filter(|e| e.action == "UPDATE_PROVIDER")
+        .collect();
+    assert_eq!(updates.len(), 2);
+    assert!(updates.iter().any(|e| {
+        let changes = &e.metadata.as_ref().unwrap()["changes"];
+        changes["base_url"]["from"] == "https://api.openai.com/v1"
+            && changes["description"]["to"].is_null()
+    }));
+    // Key values never reach the audit log
+    let rotations: Vec<&AuditEntry> = entries
+        .iter()
+        .filter(|e| e.action == "ROTATE_PROVIDER_KEY")
+        .collect();
+    assert_eq!(rotations.len(), 1);
+    assert!(!serde_json::to_string(&entries).unwrap().contains("sk-new"));
+}
+
+#[tokio::test]
+async fn test_openapi_import_and_removal() {
+    let secrets = Arc::new(AesGcmSecretsManager::new(None));
+    let policy = Arc::new(RwLock::new(NetworkPolicy::new(
+        vec!["api.crm.example.com".to_string()],
+        vec![],
+        vec![443],
+    )));
+    let openapi = Arc::new(multi_agent_skills::OpenApiRegistry::new(
+        secrets.clone(),
+        policy.clone(),
+        multi_agent_core::config::SafetyConfig::default(),
+    ));
+    let state = Arc::new(AdminState {
+        openapi_registry: Some(openapi.clone()),
+        secrets: secrets.clone(),
+        network_policy: policy,
+        ..test_admin_state()
+    });
+    let app = multi_agent_admin::admin_router(state);
+
+    let spec = "openapi: 3.0.0\ninfo: {title: CRM, version: '1'}\n\
+                servers: [{url: 'https://api.crm.example.com'}]\n\
+                paths:\n  /contacts:\n    get: {operationId: listContacts}\n";
+    let import = |body: Value| {
+        Request::builder()
+            .method("POST")
+            .uri("/api/openapi/specs")
+            .header("Content-Type", "application/json")
+            .header("Authorization", "Bearer admin")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    };
+
+    let response = app
+        .clone()
+        .oneshot(import(json!({
+            "namespace": "crm",
+            "spec": spec,
+            "auth": {"type": "api_key", "name": "X-Api-Key", "secret": "k-123"}
+        })))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["tools"], json!(["crm__list_contacts"]));
+    assert_eq!(json["auth"]["secret_id"], "openapi:crm");
+    assert_eq!(
+        secrets.retrieve("openapi:crm").await.unwrap().as_deref(),
+        Some("k-123")
+    );
+
+    // A server outside the network policy is refused.
+    let response = app
+        .clone()
+        .oneshot(import(json!({
+            "namespace": "evil",
+            "spec": spec,
+            "server_url": "https://evil.example.net"
+        })))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // Incomplete auth is rejected before anything is stored.
+    let response = app
+        .clone()
+        .oneshot(import(json!({
+            "namespace": "other",
+            "spec": spec,
+            "auth": {"type": "basic", "secret": "pw"}
+        })))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(secrets.retrieve("openapi:other").await.unwrap().is_none());
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("DELETE")
+                .uri("/api/openapi/specs/crm")
+                .header("Authorization", "Bearer admin")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert!(openapi.apis().is_empty());
+    assert!(secrets.retrieve("openapi:crm").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_mcp_bulk_import() {
+    let mcp_registry = Arc::new(McpRegistry::new());
+    let state = Arc::new(AdminState {
+        mcp_registry: mcp_registry.clone(),
+        ..test_admin_state()
+    });
+    let app = multi_agent_admin::admin_router(state);
+
+    let import = |body: Value| {
+        Request::builder()
+            .method("POST")
+            .uri("/api/mcp/import")
+            .header("Content-Type", "application/json")
+            .header("Authorization", "Bearer admin")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    };
+
+    let response = app
+        .clone()
+        .oneshot(import(json!({
+            "mcpServers": {
+                "github": {
+                    "command": "npx",
+                    "args": ["-y", "@modelcontextprotocol/server-github"],
+                    "env": {"GITHUB_PERSONAL_ACCESS_TOKEN": "ghp_x"}
+                },
+                "docs": {"url": "wss://mcp.example.com/ws"},
+                "nothing": {}
+            }
+        })))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["imported"], 2);
+    assert_eq!(json["total"], 3);
+    assert_eq!(json["servers"][0]["id"], "docs");
+    assert_eq!(json["servers"][0]["transport"], "websocket");
+    assert_eq!(json["servers"][2]["status"], "invalid");
+    assert!(mcp_registry.contains("github"));
+
+    let response = app
+        .clone()
+        .oneshot(import(json!({"unrelated": true})))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_tool_analytics_endpoint() {
+    let analytics = Arc::new(multi_agent_skills::ToolAnalytics::new());
+    analytics.record("search", std::time::Duration::from_millis(40), 800, None);
+    analytics.record(
+        "search",
+        std::time::Duration::from_millis(60),
+        0,
+        Some("rate limited".to_string()),
+    );
+    let state = Arc::new(AdminState {
+        tool_analytics: Some(analytics),
+        ..test_admin_state()
+    });
+    let app = multi_agent_admin::admin_router(state);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/tools/analytics")
+                .header("Authorization", "Bearer admin")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json[0]["tool"], "search");
+    assert_eq!(json[0]["invocations"], 2);
+    assert_eq!(json[0]["success_rate"], 0.5);
+    assert_eq!(json[0]["avg_latency_ms"], 50);
+    assert_eq!(json[0]["observation_tokens"], 200);
+    assert_eq!(json[0]["last_error"], "rate limited");
+}
+
+#[tokio::test]
+async fn test_audit_pagination_csv_and_presets() {
+    let audit_store = Arc::new(InMemoryAuditStore::new());
+    for i in 0..5 {
+        audit_store
+            .log(AuditEntry {
+                id: format!("e{}", i),
+                timestamp: format!("2024-01-01T00:00:0{}Z", i),
+                user_id: if i % 2 == 0 { "alice" } else { "bob" }.to_string(),
+                action: "execute_tool".to_string(),
+                resource: format!("tool,{}", i),
+                outcome: AuditOutcome::Success,
+                metadata: None,
+                previous_hash: None,
+                hash: None,
+                trace_id: None,
+                session_id: None,
+            })
+            .await
+            .unwrap();
+    }
+
+    let state = Arc::new(AdminState {
+        audit_store,
+        ..test_admin_state()
+    });
+    let app = multi_agent_admin::admin_router(state);
+
+    let request = |method: &str, uri: &str, body: Body| {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/json")
+            .header("Authorization", "Bearer admin")
+            .body(body)
+            .unwrap()
+    };
+    let read = |response: axum::response::Response| async move {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    };
+
+    // 1. Second page of two
+    let response = app
+        .clone()
+        .oneshot(request(
+            "GET",
+            "/api/audit?page=2&page_size=2",
+            Body::empty(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let page: Value = serde_json::from_str(&read(response).await).unwrap();
+    assert_eq!(page["total"], 5);
+    assert_eq!(page["total_pages"], 3);
+    assert_eq!(page["entries"].as_array().unwrap().len(), 2);
+    assert_eq!(page["entries"][0]["id"], "e2");
+    assert_eq!(page["next_cursor"], "e1");
+
+    // 1b. Continue by cursor, then page oldest first
+    let response = app
+        .clone()
+        .oneshot(request(
+            "GET",
+            "/api/audit?after_id=e1&page_size=2",
+            Body::empty(),
+        ))
+        .await
+        .unwrap();
+    let page: Value = serde_json::from_str(&read(response).await).unwrap();
+    assert_eq!(page["entries"].as_array().unwrap().len(), 1);
+    assert_eq!(page["entries"][0]["id"], "e0");
+    assert!(page.get("next_cursor").is_none());
+    assert!(page.get("page").is_none());
+
+    let response = app
+        .clone()
+        .oneshot(request(
+            "GET",
+            "/api/audit?order=asc&page_size=2",
+            Body::empty(),
+        ))
+        .await
+        .unwrap();
+    let page: Value = serde_json::from_str(&read(response).await).unwrap();
+    assert_eq!(page["entries"][0]["id"], "e0");
+    assert_eq!(page["next_cursor"], "e1");
+
+    // 2. Save a preset for alice's entries
+    let response = app
+        .clone()
+        .oneshot(request(
+            "POST",
+            "/api/audit/presets",
+            Body::from(
+                json!({
+                    "name": "alice",
+                    "filter": { "user_id": "alice" },
+                    "columns": ["id", "resource"]
+                })
+                .to_string(),
+            ),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // 3. CSV export through the preset
+    let response = app
+        .clone()
+        .oneshot(request(
+            "GET",
+            "/api/audit/export.csv?preset=alice",
+            Body::empty(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let csv = read(response).await;
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "id,resource");
+    assert_eq!(lines.len(), 4);
+    assert!(lines.contains(&"e2,\"tool,2\""));
+
+    // 4. Unknown columns are rejected
+    let response = app
+        .clone()
+        .oneshot(request(
+            "GET",
+            "/api/audit/export.csv?columns=id,password",
+            Body::empty(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // 5. Delete the preset
+    let response = app
+        .clone()
+        .oneshot(request("DELETE", "/api/audit/presets/alice", Body::empty()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let response = app
+        .oneshot(request("GET", "/api/audit/presets", Body::empty()))
+        .await
+        .unwrap();
+    assert_eq!(read(response).await, "[]");
+}
+
+#[tokio::test]
+async fn test_provider_view_shows_regional_endpoint_health() {
+    let registry = Arc::new(multi_agent_model_gateway::EndpointRegistry::new());
+    let state = Arc::new(AdminState {
+        endpoint_registry: Some(registry.clone()),
+        ..test_admin_state()
+    });
+    let app = multi_agent_admin::admin_router(state);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/providers")
+                .header("Content-Type", "application/json")
+                .header("Authorization", "Bearer admin")
+                .body(Body::from(
+                    json!({
+                        "vendor": "openai",
+                        "model_id": "gpt-4o",
+                        "base_url": "https://us.llm.example.com/v1",
+                        "regional_urls": ["https://eu.llm.example.com/v1"],
+                        "api_key": "sk-test-key",
+                        "capabilities": ["text"]
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let provider_id = serde_json::from_slice::<Value>(&body).unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let pool = registry.get(&provider_id).expect("pool registered");
+    pool.record_failure("https://eu.llm.example.com/v1", "timeout");
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/providers")
+                .header("Authorization", "Bearer admin")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let list: Value = serde_json::from_slice(&body).unwrap();
+    let endpoints = list[0]["endpoints"].as_array().unwrap();
+    assert_eq!(endpoints.len(), 2);
+    assert_eq!(endpoints[1]["base_url"], "https://eu.llm.example.com/v1");
+    assert_eq!(endpoints[1]["last_error"], "timeout");
+    assert_eq!(endpoints[1]["consecutive_failures"], 1);
+}
+
+#[tokio::test]
+async fn test_spend_cap_disables_provider_and_audits() {
+    use multi_agent_model_gateway::{ModelPricing, PricingRegistry, SpendGuard, UsageLedger};
+
+    let audit_store = Arc::new(InMemoryAuditStore::new());
+    let state = Arc::new(AdminState {
+        audit_store: audit_store.clone(),
+        ..test_admin_state()
+    });
+    let app = multi_agent_admin::admin_router(state.clone());
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/providers")
+                .header("Content-Type", "application/json")
+                .header("Authorization", "Bearer admin")
+                .body(Body::from(
+                    json!({
+                        "vendor": "OpenAI",
+                        "model_id": "gpt-4o",
+                        "base_url": "https://api.openai.com/v1",
+                        "api_key": "sk-test-key",
+                        "capabilities": ["text"]
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let provider_id = serde_json::from_slice::<Value>(&body).unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri(format!("/api/providers/{}/spend-cap", provider_id))
+                .header("Content-Type", "application/json")
+                .header("Authorization", "Bearer admin")
+                .body(Body::from(
+                    json!({"monthly_spend_cap_usd": 5.0}).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let mut pricing = PricingRegistry::new();
+    pricing.register(ModelPricing::new("openai:gpt-4o", 10.0, 10.0));
+    let ledger = Arc::new(UsageLedger::new().with_pricing(pricing));
+    let guard = Arc::new(SpendGuard::new(ledger.clone()).with_privileged_workspaces(["ops"]));
+    let enforcer =
+        multi_agent_admin::spend_caps::SpendCapEnforcer::new(state.clone(), guard.clone());
+
+    ledger.record_call("openai:gpt-4o", 600, 0);
+    let changes = enforcer.enforce().await;
+    assert_eq!(changes.len(), 1);
+    assert!(guard.is_blocked("openai:gpt-4o"));
+    assert!(guard.allows("openai:gpt-4o", Some("ops")));
+    assert!(!guard.allows("openai:gpt-4o", Some("team-a")));
+    assert_eq!(state.providers.read().await[0].status, "over_budget");
+
+    let actions: Vec<String> = audit_store
+        .query(multi_agent_governance::AuditFilter::default())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|e| e.action)
+        .collect();
+    assert!(actions.contains(&"SET_PROVIDER_SPEND_CAP".to_string()));
+    assert!(actions.contains(&"PROVIDER_SPEND_CAP_EXCEEDED".to_string()));
+
+    // Raising the cap re-enables the provider
+    state.providers.write().await[0].monthly_spend_cap_usd = Some(50.0);
+    assert!(!enforcer.enforce().await[0].exceeded);
+    assert_eq!(state.providers.read().await[0].status, "active");
+}
+
+#[tokio::test]
+async fn test_model_upgrade_canary_rolls_back_regressions() {
+    use multi_agent_admin::canary::ModelCanary;
+    use multi_agent_core::config::CanaryCase;
+    use multi_agent_model_gateway::{CanarySuite, CanaryThresholds, MockLlmClient};
+
+    // Only gpt-4o and its dated snapshot know the answer
+    let canary = ModelCanary::new(
+        CanarySuite::new(vec![CanaryCase {
+            name: "arithmetic".to_string(),
+            prompt: "What is 17 * 23?".to_string(),
+            expect: vec!["391".to_string()],
+        }]),
+        CanaryThresholds {
+            max_success_drop: 0.0,
+            max_cost_increase: 0.5,
+        },
+    )
+    .with_client_factory(|_, provider, _| {
+        let answer = if provider.model_id.starts_with("gpt-4o") {
+            "391"
+        } else {
+            "not sure"
+        };
+        Some(Arc::new(MockLlmClient::new(answer)))
+    });
+
+    let audit_store = Arc::new(InMemoryAuditStore::new());
+    let state = Arc::new(AdminState {
+        audit_store: audit_store.clone(),
+        model_canary: Some(Arc::new(canary)),
+        ..test_admin_state()
+    });
+    let app = multi_agent_admin::admin_router(state.clone());
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/providers")
+                .header("Content-Type", "application/json")
+                .header("Authorization", "Bearer admin")
+                .body(Body::from(
+                    json!({
+                        "vendor": "OpenAI",
+                        "model_id": "gpt-4o",
+                        "base_url": "https://api.openai.com/v1",
+                        "api_key": "sk-test-key",
+                        "capabilities": ["text"]
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let provider_id = serde_json::from_slice::<Value>(&body).unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let upgrade = |model_id: &str| {
+        app.clone().oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri(format!("/api/providers/{}/model", provider_id))
+                .header("Content-Type", "application/json")
+                .header("Authorization", "Bearer admin")
+                .body(Body::from(json!({ "model_id": model_id }).to_string()))
+                .unwrap(),
+        )
+    };
+    let finished_run = || async {
+        for _ in 0..100 {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/api/providers/{}/canary", provider_id))
+                        .header("Authorization", "Bearer admin")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let run: Value = serde_json::from_slice(&body).unwrap();
+            if run["status"] != "running" {
+                return run;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        panic!("canary did not finish");
+    };
+
+    let response = upgrade("gpt-5-preview").await.unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let run = finished_run().await;
+    assert_eq!(run["status"], "rolled_back");
+    assert_eq!(run["candidate"]["failures"], json!(["arithmetic"]));
+    assert_eq!(state.providers.read().await[0].model_id, "gpt-4o");
+
+    let response = upgrade("gpt-4o-2024-08-06").await.unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    assert_eq!(finished_run().await["status"], "passed");
+    assert_eq!(
+        state.providers.read().await[0].model_id,
+        "gpt-4o-2024-08-06"
+    );
+
+    let actions: Vec<String> = audit_store
+        .query(multi_agent_governance::AuditFilter::default())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|e| e.action)
+        .collect();
+    assert!(actions.contains(&"UPDATE_PROVIDER_MODEL".to_string()));
+    assert!(actions.contains(&"MODEL_CANARY_ROLLED_BACK".to_string()));
+    assert!(actions.contains(&"MODEL_CANARY_PASSED".to_string()));
+}
+
+#[tokio::test]
+async fn test_activate_provider_switches_default_client() {
+    use multi_agent_model_gateway::{ActiveLlmClient, MockLlmClient};
+
+    let audit_store = Arc::new(InMemoryAuditStore::new());
+    let active = Arc::new(ActiveLlmClient::new(
+        Arc::new(MockLlmClient::new("startup")),
+        "openai:gpt-4o-mini",
+    ));
+    let state = Arc::new(AdminState {
+        audit_store: audit_store.clone(),
+        active_client: Some(active.clone()),
+        ..test_admin_state()
+    });
+    let app = multi_agent_admin::admin_router(state.clone());
+
+    let mut ids = Vec::new();
+    for vendor in ["Anthropic", "Mistral"] {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/providers")
+                    .header("Content-Type", "application/json")
+                    .header("Authorization", "Bearer admin")
+                    .body(Body::from(
+                        json!({
+                            "vendor": vendor,
+                            "model_id": "claude-3-5-haiku",
+                            "base_url": "https://api.example.com/v1",
+                            "api_key": "sk-test-key",
+                            "capabilities": ["text"]
+                        })
+                        .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        ids.push(
+            serde_json::from_slice::<Value>(&body).unwrap()["id"]
+                .as_str()
+                .unwrap()
+                .to_string(),
+        );
+        // Provider ids are derived from the timestamp
+        tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+    }
+
+    let activate = |id: &str| {
+        Request::builder()
+            .method("POST")
+            .uri(format!("/api/providers/{}/activate", id))
+            .header("Authorization", "Bearer admin")
+            .body(Body::empty())
+            .unwrap()
+    };
+    let response = app.clone().oneshot(activate(&ids[0])).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(active.model_id(), "anthropic:claude-3-5-haiku");
+    assert_eq!(active.provider_id().as_deref(), Some(ids[0].as_str()));
+
+    let response = app.clone().oneshot(activate(&ids[1])).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let response = app.clone().oneshot(activate("prov-missing")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(active.model_id(), "anthropic:claude-3-5-haiku");
+
+    let entries = audit_store
+        .query(multi_agent_governance::AuditFilter::default())
+        .await
+        .unwrap();
+    let entry = entries
+        .iter()
+        .find(|e| e.action == "ACTIVATE_PROVIDER")
+        .expect("activation audited");
+    assert_eq!(entry.resource, ids[0]);
+    assert_eq!(
+        entry.metadata.as_ref().unwrap()["previous_model"],
+        "openai:gpt-4o-mini"
+    );
+}
+
+#[tokio::test]
+async fn test_registered_providers_become_inference_targets() {
+    use multi_agent_admin::provider_sync::ProviderSync;
+    use multi_agent_model_gateway::ProviderRegistry;
+
+    let sync = Arc::new(ProviderSync::new(Arc::new(ProviderRegistry::new())));
+    let state = Arc::new(AdminState {
+        provider_sync: Some(sync.clone()),
+        ..test_admin_state()
+    });
+    let app = multi_agent_admin::admin_router(state.clone());
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/providers")
+                .header("Content-Type", "application/json")
+                .header("Authorization", "Bearer admin")
+                .body(Body::from(
+                    json!({
+                        "vendor": "OpenAI",
+                        "model_id": "gpt-4o",
+                        "base_url": "https://api.openai.com/v1",
+                        "api_key": "sk-test-key",
+                        "capabilities": ["text"]
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let provider_id = serde_json::from_slice::<Value>(&body).unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let registry = sync.registry();
+    assert_eq!(registry.get_healthy(), vec!["openai:gpt-4o".to_string()]);
+    assert_eq!(sync.sync(&state).await, 0, "unchanged providers are kept");
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("DELETE")
+                .uri(format!("/api/providers/{}", provider_id))
+                .header("Authorization", "Bearer admin")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert!(registry.get_healthy().is_empty());
+}
+
+#[tokio::test]
+async fn test_session_bundle_export_and_import() {
+    use multi_agent_core::traits::{ArtifactStore, SessionStore};
+    use multi_agent_core::types::{HistoryEntry, Session, SessionStatus, TokenUsage, ToolCallInfo};
+
+    let state_with = |sessions: Arc<multi_agent_store::InMemorySessionStore>,
+                      artifacts: Arc<multi_agent_store::InMemoryStore>,
+                      audit: Arc<InMemoryAuditStore>| {
+        Arc::new(AdminState {
+            audit_store: audit,
+            artifact_store: Some(artifacts),
+            session_store: Some(sessions),
+            ..test_admin_state()
+        })
+    };
+
+    // Source instance with a session that references an artifact
+    let artifacts = Arc::new(multi_agent_store::InMemoryStore::new());
+    let ref_id = artifacts
+        .save_with_type(bytes::Bytes::from_static(b"a,b\n1,2\n"), "text/csv")
+        .await
+        .unwrap();
+    let sessions = Arc::new(multi_agent_store::InMemorySessionStore::new());
+    let now = chrono::Utc::now().timestamp();
+    sessions
+        .save(&Session {
+            id: "sess-1".to_string(),
+            trace_id: "trace-1".to_string(),
+            user_id: Some("alice".to_string()),
+            workspace_id: None,
+            dry_run: false,
+            status: SessionStatus::Running,
+            history: vec![HistoryEntry {
+                role: "tool".to_string(),
+                content: Arc::new("Loaded table".to_string()),
+                tool_call: Some(ToolCallInfo {
+                    name: "read_table".to_string(),
+                    arguments: json!({ "ref_id": ref_id.as_str() }),
+                    result: None,
+                }),
+                timestamp: now,
+            }],
+            task_state: None,
+            token_usage: TokenUsage::default(),
+            created_at: now - 5,
+            updated_at: now,
+        })
+        .await
+        .unwrap();
+    let audit = Arc::new(InMemoryAuditStore::new());
+    audit
+        .log(AuditEntry {
+            id: "a1".to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            user_id: "approval_gate".to_string(),
+            action: "approval.pre_authorize".to_string(),
+            resource: "read_table".to_string(),
+            outcome: AuditOutcome::Success,
+            metadata: Some(json!({ "session_id": "sess-1" })),
+            previous_hash: None,
+            hash: None,
+            trace_id: None,
+            session_id: None,
+        })
+        .await
+        .unwrap();
+    let source = multi_agent_admin::admin_router(state_with(sessions, artifacts, audit));
+
+    let response = source
+        .oneshot(
+            Request::builder()
+                .uri("/api/sessions/sess-1/export")
+                .header("Authorization", "Bearer admin")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bundle = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let parsed: Value = serde_json::from_slice(&bundle).unwrap();
+    assert_eq!(parsed["artifacts"][0]["id"], ref_id.as_str());
+    assert_eq!(parsed["artifacts"][0]["content_type"], "text/csv");
+    assert_eq!(parsed["policy_decisions"][0]["id"], "a1");
+
+    // Import into an empty dev instance
+    let dev_sessions = Arc::new(multi_agent_store::InMemorySessionStore::new());
+    let dev_artifacts = Arc::new(multi_agent_store::InMemoryStore::new());
+    let dev = multi_agent_admin::admin_router(state_with(
+        dev_sessions.clone(),
+        dev_artifacts.clone(),
+        Arc::new(InMemoryAuditStore::new()),
+    ));
+    let import = || {
+        Request::builder()
+            .method("POST")
+            .uri("/api/sessions/import")
+            .header("Content-Type", "application/json")
+            .header("Authorization", "Bearer admin")
+            .body(Body::from(bundle.clone()))
+            .unwrap()
+    };
+    let response = dev.clone().oneshot(import()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let imported = dev_sessions.load("sess-1").await.unwrap().unwrap();
+    assert_eq!(imported.status, SessionStatus::Paused);
+    assert_eq!(
+        dev_artifacts.load(&ref_id).await.unwrap().unwrap(),
+        bytes::Bytes::from_static(b"a,b\n1,2\n")
+    );
+
+    // Existing sessions are only replaced on request
+    let response = dev.oneshot(import()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_network_policy_validation_check_and_diff() {
+    let audit_store = Arc::new(InMemoryAuditStore::new());
+    let mut app_config = multi_agent_core::config::AppConfig::default();
+    let data_dir = std::env::temp_dir().join(format!("netpolicy-{}", uuid::Uuid::new_v4()));
+    app_config.store.data_dir = Some(data_dir.display().to_string());
+    let policy = Arc::new(RwLock::new(NetworkPolicy::new(
+        vec!["api.github.com".into()],
+        vec![],
+        vec![443],
+    )));
+    let state = Arc::new(AdminState {
+        audit_store: audit_store.clone(),
+        app_config,
+        network_policy: policy.clone(),
+        ..test_admin_state()
+    });
+    let app = multi_agent_admin::admin_router(state);
+    let send = |method: &str, uri: &str, body: Option<Value>| {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/json")
+            .header("Authorization", "Bearer admin")
+            .body(body.map(|b| Body::from(b.to_string())).unwrap_or_default())
+            .unwrap()
+    };
+    let json_body = |response: axum::response::Response| async move {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice::<Value>(&body).unwrap()
+    };
+
+    // The effective policy reports its provenance
+    let response = app
+        .clone()
+        .oneshot(send("GET", "/api/config/network", None))
+        .await
+        .unwrap();
+    let body = json_body(response).await;
+    assert_eq!(body["source"], "config");
+    let version = body["version"].as_str().unwrap().to_string();
+
+    // Invalid rules are rejected with every problem listed
+    let response = app
+        .clone()
+        .oneshot(send(
+            "POST",
+            "/api/config/network",
+            Some(json!({
+                "version": "",
+                "allow_domains": ["*.com", "10.0.0.1"],
+                "deny_domains": [],
+                "allow_ports": [443]
+            })),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        json_body(response).await["problems"]
+            .as_array()
+            .unwrap()
+            .len(),
+        2
+    );
+    assert_eq!(policy.read().await.version, version);
+
+    // Dry run against a candidate policy leaves the effective one untouched
+    let candidate = json!({
+        "version": "",
+        "allow_domains": ["*.example.com"],
+        "deny_domains": ["admin.example.com"],
+        "allow_ports": [443]
+    });
+    let response = app
+        .clone()
+        .oneshot(send(
+            "POST",
+            "/api/config/network/check",
+            Some(json!({
+                "urls": [
+                    "https://docs.example.com/a",
+                    "https://admin.example.com",
+                    "https://api.github.com"
+                ],
+                "policy": candidate
+            })),
+        ))
+        .await
+        .unwrap();
+    let results = json_body(response).await["results"].clone();
+    assert_eq!(results[0]["allowed"], true);
+    assert_eq!(results[1]["allowed"], false);
+    assert_eq!(results[2]["allowed"], false);
+
+    // Saving records the rule diff in the audit log
+    let response = app
+        .clone()
+        .oneshot(send("POST", "/api/config/network", Some(candidate)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = json_body(response).await;
+    assert_eq!(body["source"], "api");
+    assert_ne!(body["version"], version);
+
+    let entries = audit_store
+        .query(multi_agent_governance::AuditFilter::default())
+        .await
+        .unwrap();
+    let entry = entries
+        .iter()
+        .find(|e| e.action == "UPDATE_NETWORK_POLICY")
+        .unwrap();
+    let diff = &entry.metadata.as_ref().unwrap()["diff"];
+    assert_eq!(diff["added_allow_domains"], json!(["*.example.com"]));
+    assert_eq!(diff["removed_allow_domains"], json!(["api.github.com"]));
+    assert_eq!(diff["added_deny_domains"], json!(["admin.example.com"]));
+
+    let _ = std::fs::remove_dir_all(data_dir);
+}
+
+#[tokio::test]
+async fn test_static_assets_are_compressed_and_revalidated() {
+    use axum::http::header;
+
+    let app = multi_agent_admin::admin_static_router();
+    let script = multi_agent_admin::static_assets::fingerprinted_path("app.js").unwrap();
+
+    // The index links the fingerprinted script, which is cached as immutable
+    let response = app
+        .clone()
+        .oneshot(Request::get("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CACHE_CONTROL], "no-cache");
+    let html = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(String::from_utf8_lossy(&html).contains(&script));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::get(format!("/{}", script))
+                .header(header::ACCEPT_ENCODING, "br, gzip")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_ENCODING], "br");
+    assert!(response.headers()[header::CACHE_CONTROL]
+        .to_str()
+        .unwrap()
+        .contains("immutable"));
+    let etag = response.headers()[header::ETAG].clone();
+
+    // A matching ETag is answered without a body
+    let response = app
+        .clone()
+        .oneshot(
+            Request::get("/app.js")
+                .header(header::IF_NONE_MATCH, etag)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+    let response = app
+        .oneshot(
+            Request::get("/app.00000000.js")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_audit_stream_pushes_matching_entries() {
+    use futures::StreamExt;
+    use tokio_tungstenite::tungstenite::{client::IntoClientRequest, Message};
+
+    let audit_store = Arc::new(InMemoryAuditStore::new());
+    let state = Arc::new(AdminState {
+        audit_store: audit_store.clone(),
+        ..test_admin_state()
+    });
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, multi_agent_admin::admin_router(state))
+            .await
+            .unwrap();
+    });
+    let url = format!("ws://{}/api/audit/stream?action=DELETE_PROVIDER", addr);
+
+    // Without a token the upgrade is refused
+    assert!(tokio_tungstenite::connect_async(url.as_str())
+        .await
+        .is_err());
+
+    // Browsers offer the token as a subprotocol
+    let mut request = url.as_str().into_client_request().unwrap();
+    request
+        .headers_mut()
+        .insert("Sec-WebSocket-Protocol", "bearer, admin".parse().unwrap());
+    let (mut socket, response) = tokio_tungstenite::connect_async(request).await.unwrap();
+    assert_eq!(
+        response.headers()["sec-websocket-protocol"],
+        multi_agent_admin::audit_stream::BEARER_PROTOCOL
+    );
+
+    let entry = |id: &str, action: &str| AuditEntry {
+        id: id.into(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        user_id: "admin".into(),
+        action: action.into(),
+        resource: "prov-1".into(),
+        outcome: AuditOutcome::Success,
+        metadata: None,
+        previous_hash: None,
+        hash: None,
+        trace_id: None,
+        session_id: None,
+    };
+    audit_store.log(entry("a1", "ADD_PROVIDER")).await.unwrap();
+    audit_store
+        .log(entry("a2", "DELETE_PROVIDER"))
+        .await
+        .unwrap();
+
+    let message = tokio::time::timeout(std::time::Duration::from_secs(5), socket.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    let Message::Text(text) = message else {
+        panic!("expected a text message, got {:?}", message);
+    };
+    let json: Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(json["type"], "audit");
+    assert_eq!(json["entry"]["id"], "a2");
+    assert_eq!(json["entry"]["action"], "DELETE_PROVIDER");
+
+    socket.close(None).await.unwrap();
+}
@@ -102,6 +102,7 @@ impl multi_agent_core::traits::EventEmitter for AuditSubscriber {
             EventType::ToolExecFinished
                 | EventType::ApprovalDecided
                 | EventType::PolicyEvaluated
+                | EventType::ModerationEvaluated
                 | EventType::FsWrite
                 | EventType::FsRead
         );
@@ -16,8 +16,8 @@ use multi_agent_skills::{
     McpRegistry,
 };
 use multi_agent_store::{
-    knowledge::InMemoryKnowledgeStore, InMemorySessionStore, InMemoryStore, RedisSessionStore,
-    S3ArtifactStore, TieredStore,
+    knowledge::InMemoryKnowledgeStore, CloudArtifactStore, InMemorySessionStore, InMemoryStore,
+    RedisSessionStore, S3ArtifactStore, SessionCodec, TieredStore,
 };
 
 /// A writer that broadcasts log lines to a channel.
@@ -112,6 +112,23 @@ pub async fn start_server() -> Result<()> {
     // =========================================================================
     // Initialize L3: Artifact Store
     // =========================================================================
+    // Native GCS/Azure cold tier, used when no S3 bucket is configured.
+    let cloud = if app_config.store.s3_bucket.is_some() {
+        None
+    } else if let Some(gcs) = &app_config.store.gcs {
+        tracing::info!(bucket = %gcs.bucket, "Initializing GCS Artifact Store (Tiered)");
+        Some(Arc::new(CloudArtifactStore::gcs(gcs)?))
+    } else if let Some(azure) = &app_config.store.azure_blob {
+        tracing::info!(
+            account = %azure.account,
+            container = %azure.container,
+            "Initializing Azure Blob Artifact Store (Tiered)"
+        );
+        Some(Arc::new(CloudArtifactStore::azure(azure)?))
+    } else {
+        None
+    };
+
     let (store, artifacts_erasables, artifacts_prunables): (
         Arc<dyn ArtifactStore>,
         Vec<Arc<dyn Erasable>>,
@@ -120,13 +137,26 @@ pub async fn start_server() -> Result<()> {
         let endpoint = app_config.store.s3_endpoint.as_deref();
         tracing::info!(bucket = %bucket, endpoint = ?endpoint, "Initializing S3 Artifact Store (Tiered)");
 
-        let s3 = Arc::new(S3ArtifactStore::new(bucket, "", endpoint).await);
+        let s3 = Arc::new(
+            S3ArtifactStore::new(bucket, "", endpoint)
+                .await
+                .with_retry_config(app_config.store.s3_retry.clone())
+                .with_sse(app_config.store.s3_sse.clone()),
+        );
         let hot = Arc::new(InMemoryStore::new());
         let tiered = Arc::new(TieredStore::new(hot.clone()).with_cold(s3.clone()));
 
         let erasables: Vec<Arc<dyn Erasable>> = vec![s3.clone(), hot.clone()];
         let prunables: Vec<Arc<dyn Prunable>> = vec![s3, hot];
 
+        (tiered, erasables, prunables)
+    } else if let Some(cloud) = cloud {
+        let hot = Arc::new(InMemoryStore::new());
+        let tiered = Arc::new(TieredStore::new(hot.clone()).with_cold(cloud.clone()));
+
+        let erasables: Vec<Arc<dyn Erasable>> = vec![cloud.clone(), hot.clone()];
+        let prunables: Vec<Arc<dyn Prunable>> = vec![cloud, hot];
+
         (tiered, erasables, prunables)
     } else {
         tracing::info!("Initializing In-Memory Artifact Store");
@@ -141,11 +171,11 @@ pub async fn start_server() -> Result<()> {
         Arc<dyn Prunable>,
     ) = if let Some(redis_url) = &app_config.store.redis_url {
         tracing::info!(url = %redis_url, "Initializing Redis Session Store");
-        let s = Arc::new(RedisSessionStore::new(
-            redis_url,
-            "opencoordex:session",
-            3600 * 24,
-        )?);
+        let s = Arc::new(
+            RedisSessionStore::new(redis_url, "opencoordex:session", 3600 * 24)?.with_codec(
+                SessionCodec::from_config(&app_config.store.session_encoding),
+            ),
+        );
         (s.clone(), s.clone(), s)
     } else {
         tracing::info!("Initializing In-Memory Session Store");
@@ -160,6 +190,11 @@ pub async fn start_server() -> Result<()> {
 
     local_registry.register(Box::new(EchoTool)).await?;
     local_registry.register(Box::new(CalculatorTool)).await?;
+    local_registry
+        .register(Box::new(multi_agent_skills::TabularTool::new(
+            store.clone(),
+        )))
+        .await?;
 
     // =========================================================================
     // Initialize Sandbox
@@ -190,6 +225,17 @@ pub async fn start_server() -> Result<()> {
                     manager.clone(),
                 )))
                 .await?;
+            local_registry
+                .register(Box::new(multi_agent_skills::RepoMapTool::new(
+                    manager.clone(),
+                    store.clone(),
+                )))
+                .await?;
+            local_registry
+                .register(Box::new(multi_agent_skills::ApplyPatchTool::new(
+                    manager.clone(),
+                )))
+                .await?;
 
             tracing::info!("🐳 Sovereign Sandbox initialized");
             Some(manager)
@@ -329,37 +375,47 @@ pub async fn start_server() -> Result<()> {
         ".sovereign_claw/providers.json",
     ));
 
+    // SMTP servers are admin providers; every email goes through the approval gate.
+    local_registry
+        .register(Box::new(multi_agent_skills::SendEmailTool::new(
+            provider_store.clone(),
+            secrets.clone(),
+            audit_store.clone(),
+            app_config.email.clone(),
+        )))
+        .await?;
+
+    // Calendar and issue-tracker connectors read their credentials from admin providers.
+    let connectors =
+        multi_agent_skills::ConnectorContext::new(provider_store.clone(), secrets.clone());
+    for tool in multi_agent_skills::connector_tools(connectors) {
+        local_registry.register(tool).await?;
+    }
+
+    // Clarification questions are answered over /ws/approval or /v1/answer.
+    let human_input = Arc::new(multi_agent_governance::ChannelHumanInput::new());
+    local_registry
+        .register(Box::new(multi_agent_skills::AskUserTool::new(
+            human_input.clone(),
+        )))
+        .await?;
+
+    // REST APIs imported through the admin API as OpenAPI specs.
+    let openapi_registry = Arc::new(multi_agent_skills::OpenApiRegistry::new(
+        secrets.clone(),
+        network_policy.clone(),
+        app_config.safety.clone(),
+    ));
+
     // Policy Engine Initialization
     let policy_dir = ".sovereign_claw/policies";
     let default_policy_path = format!("{}/default.yaml", policy_dir);
     if !std::path::Path::new(&default_policy_path).exists() {
         let _ = std::fs::create_dir_all(policy_dir);
-        let default_policy = r#"version: "1.0"
-name: "Default Security Policy"
-rules:
-  - id: "block-rm-rf"
-    description: "Block recursive force delete"
-    match_rule:
-      tool_glob: "sandbox_*"
-      args_contain: ["rm -rf", "rm -r"]
-    action:
-      risk: critical
-      reason: "Destructive filesystem operation detected"
-  - id: "high-risk-fs"
-    description: "Elevate risk for sensitive FS operations"
-    match_rule:
-      tool_glob: "fs_write*"
-    action:
-      risk: high
-      reason: "Filesystem write detected"
-thresholds:
-  low: 10
-  medium: 30
-  high: 60
-  critical: 90
-  approval_required: 50
-"#;
-        let _ = std::fs::write(&default_policy_path, default_policy);
+        let _ = std::fs::write(
+            &default_policy_path,
+            multi_agent_governance::policy::DEFAULT_POLICY_YAML,
+        );
     }
 
     let policy_engine = match multi_agent_governance::PolicyEngine::load(&default_policy_path) {
@@ -373,6 +429,8 @@ thresholds:
                         name: "Empty Backup Policy".into(),
                         rules: vec![],
                         thresholds: multi_agent_governance::policy::PolicyThresholds::default(),
+                        tool_overrides: Default::default(),
+                        workspace_overrides: Default::default(),
                     },
                 ),
             ))
@@ -408,11 +466,13 @@ thresholds:
 
     let privacy_controller = Arc::new(PrivacyController::new(all_erasables, event_emitter.clone()));
 
+    let guardrails = Arc::new(multi_agent_governance::RouteGuardrails::default());
     let admin_state = Arc::new(multi_agent_admin::AdminState {
-        audit_store,
+        audit_store: audit_store.clone(),
         rbac,
         metrics: None, // metrics recorder handles this globally
         mcp_registry: mcp_registry.clone(),
+        openapi_registry: Some(openapi_registry.clone()),
         providers: Arc::new(tokio::sync::RwLock::new(vec![])),
         provider_store: Some(provider_store),
         secrets,
@@ -421,12 +481,21 @@ thresholds:
         session_store: Some(session_store.clone()),
         app_config: app_config.clone(),
         network_policy: network_policy.clone(),
+        audit_presets: Arc::new(multi_agent_admin::audit_view::AuditPresetStore::new()),
+        audit_anchorer: None,
+        guardrails: guardrails.clone(),
+        tool_analytics: None,
+        endpoint_registry: None,
+        active_client: None,
+        provider_sync: None,
+        model_canary: None,
     });
 
     // Composite Registry
     let mut composite_tools = CompositeToolRegistry::new();
     composite_tools.add_registry(local_registry.clone());
     composite_tools.add_registry(mcp_registry.clone());
+    composite_tools.add_registry(openapi_registry);
     let tools = Arc::new(composite_tools);
 
     // Initialize Plugin Manager
@@ -448,8 +517,15 @@ thresholds:
     // Sync initially
     plugin_manager.sync_registry(&mcp_registry).await;
 
+    let approval_gate = Arc::new(ChannelApprovalGate::new(ToolRiskLevel::Medium));
+    // Remembers "N identical calls" and "rest of session" approvals
+    let batching_gate = Arc::new(
+        multi_agent_governance::BatchingApprovalGate::new(approval_gate.clone())
+            .with_audit(audit_store),
+    );
     let controller = Arc::new(
         ReActController::builder()
+            .with_approval_gate(batching_gate)
             .with_store(store.clone())
             .with_session_store(session_store.clone())
             .with_tools(tools.clone())
@@ -501,24 +577,30 @@ thresholds:
         enable_tracing: true,
         allowed_origins,
         tls: app_config.gateway.tls.clone(),
+        unix_socket: app_config.server.unix_socket.clone(),
     };
 
     // =========================================================================
     // Initialize Research P0 Components
     // =========================================================================
-    let approval_gate = Arc::new(ChannelApprovalGate::new(ToolRiskLevel::Medium));
     let knowledge_store = Arc::new(InMemoryKnowledgeStore::new());
 
-    let research_orchestrator = Arc::new(ResearchOrchestrator::new(
-        admin_state.clone(),
-        approval_gate.clone(),
-        network_policy.clone(),
-        Some(policy_engine.clone()),
-        app_config.safety.clone(),
-        store.clone(),
-        knowledge_store.clone(),
-        Some(tx.clone()),
-    ));
+    let research_orchestrator = Arc::new(
+        ResearchOrchestrator::new(
+            admin_state.clone(),
+            approval_gate.clone(),
+            network_policy.clone(),
+            Some(policy_engine.clone()),
+            app_config.safety.clone(),
+            store.clone(),
+            knowledge_store.clone(),
+            Some(tx.clone()),
+        )
+        .with_budgets(app_config.governance.research.clone()),
+    );
+    research_orchestrator
+        .clone()
+        .spawn_recrawl(app_config.governance.research.recrawl.clone());
 
     let server = GatewayServer::new(gateway_config.clone(), router, cache)
         .with_controller(controller)
@@ -527,7 +609,12 @@ thresholds:
         .with_logs_channel(tx)
         .with_policy_engine(policy_engine)
         .with_approval_gate(approval_gate)
-        .with_research_orchestrator(research_orchestrator);
+        .with_human_input(human_input)
+        .with_tool_registry(tools.clone())
+        .with_research_orchestrator(research_orchestrator)
+        .with_artifact_store(store.clone())
+        .with_knowledge_store(knowledge_store.clone())
+        .with_guardrails(guardrails);
 
     tracing::info!(
         host = %app_config.server.host,
@@ -541,19 +628,24 @@ thresholds:
     );
 
     let app = server.build_router();
-    let listener = tokio::net::TcpListener::bind(format!(
-        "{}:{}",
-        app_config.server.host, app_config.server.port
-    ))
-    .await?;
+    if let Some(path) = &app_config.server.unix_socket {
+        tracing::info!("Server listening on {}", path);
+        multi_agent_gateway::unix_socket::serve(app, path).await?;
+    } else {
+        let listener = tokio::net::TcpListener::bind(format!(
+            "{}:{}",
+            app_config.server.host, app_config.server.port
+        ))
+        .await?;
 
-    tracing::info!("Server listening on {}", listener.local_addr()?);
+        tracing::info!("Server listening on {}", listener.local_addr()?);
 
-    axum::serve(
-        listener,
-        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
-    )
-    .await?;
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+        )
+        .await?;
+    }
 
     // =========================================================================
     // Start Background Retention Pruning
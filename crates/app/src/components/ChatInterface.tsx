@@ -1,9 +1,10 @@
 import { useState, useRef, useEffect } from "react";
-// import { invoke } from "@tauri-apps/api/core";
+import { AgentResult, ResultView } from "./ResultView";
 
 interface Message {
     role: "user" | "assistant";
     content: string;
+    result?: AgentResult;
 }
 
 export function ChatInterface() {
@@ -24,25 +25,29 @@ export function ChatInterface() {
         e.preventDefault();
         if (!input.trim() || loading) return;
 
-        const userMsg: Message = { role: "user", content: input };
+        const message = input;
+        const userMsg: Message = { role: "user", content: message };
         setMessages(prev => [...prev, userMsg]);
         setInput("");
         setLoading(true);
 
         try {
-            // TODO: Implement actual backend connection
-            // const response = await invoke("chat", { message: input });
-
-            // Simulate response for now
-            setTimeout(() => {
-                setMessages(prev => [...prev, {
-                    role: "assistant",
-                    content: "I received your message but I am currently in 'Fog of War' mode. My actual reasoning capabilities are being integrated."
-                }]);
-                setLoading(false);
-            }, 1000);
+            const response = await fetch("http://127.0.0.1:3000/v1/chat", {
+                method: "POST",
+                headers: { "Content-Type": "application/json" },
+                body: JSON.stringify({ message }),
+            });
+            const envelope = await response.json();
+            const result: AgentResult | undefined = envelope.data?.result ?? undefined;
+            setMessages(prev => [...prev, {
+                role: "assistant",
+                content: result ? "" : "No result returned.",
+                result,
+            }]);
         } catch (err) {
             console.error("Chat error:", err);
+            setMessages(prev => [...prev, { role: "assistant", content: "Failed to reach the gateway." }]);
+        } finally {
             setLoading(false);
         }
     };
@@ -60,7 +65,7 @@ export function ChatInterface() {
                                 ? "bg-blue-600 text-white"
                                 : "bg-slate-800 text-slate-200 border border-slate-700"
                             }`}>
-                            {msg.content}
+                            {msg.result ? <ResultView result={msg.result} /> : msg.content}
                         </div>
                     </div>
                 ))}
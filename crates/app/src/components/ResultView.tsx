@@ -0,0 +1,146 @@
+// Mirrors `AgentResult` in crates/core/src/types/agent.rs (serde tag "type", content "payload").
+
+export interface Citation {
+    id?: string;
+    url: string;
+    hash: string;
+    context: string;
+    artifact_id?: string;
+}
+
+export interface ResultArtifact {
+    ref_id: string;
+    filename: string;
+    mime_type: string;
+}
+
+export type AgentResult =
+    | { type: "Text"; payload: string }
+    | { type: "File"; payload: ResultArtifact }
+    | { type: "Artifacts"; payload: { artifacts: ResultArtifact[] } }
+    | { type: "Data"; payload: unknown }
+    | { type: "Table"; payload: { columns: string[]; rows: unknown[][]; caption?: string } }
+    | { type: "CitedText"; payload: { text: string; citations: Citation[] } }
+    | { type: "Reference"; payload: { ref_id: string; mime_type: string; size: number; preview: string } }
+    | { type: "UiComponent"; payload: { component_type: string; props: unknown } }
+    | { type: "Error"; payload: { message: string; code: string } };
+
+const API_BASE = "http://127.0.0.1:3000";
+
+function cellText(cell: unknown): string {
+    if (cell === null || cell === undefined) return "";
+    return typeof cell === "string" ? cell : JSON.stringify(cell);
+}
+
+function ArtifactLink({ artifact }: { artifact: ResultArtifact }) {
+    return (
+        <a
+            className="flex items-center justify-between gap-3 rounded border border-slate-700 px-3 py-1.5 hover:border-blue-500"
+            href={`${API_BASE}/v1/agent/artifacts/${artifact.ref_id}`}
+            target="_blank"
+            rel="noreferrer"
+        >
+            <span className="truncate">{artifact.filename}</span>
+            <span className="text-xs text-slate-500">{artifact.mime_type}</span>
+        </a>
+    );
+}
+
+export function ResultView({ result }: { result: AgentResult }) {
+    switch (result.type) {
+        case "Text":
+            return <div className="whitespace-pre-wrap">{result.payload}</div>;
+        case "File":
+            return <ArtifactLink artifact={result.payload} />;
+        case "Artifacts":
+            return (
+                <div className="space-y-1">
+                    {result.payload.artifacts.map((a) => (
+                        <ArtifactLink key={a.ref_id} artifact={a} />
+                    ))}
+                </div>
+            );
+        case "Data":
+            return (
+                <pre className="overflow-x-auto text-xs bg-slate-950 rounded p-2">
+                    {JSON.stringify(result.payload, null, 2)}
+                </pre>
+            );
+        case "Table":
+            return (
+                <div className="overflow-x-auto">
+                    {result.payload.caption && (
+                        <div className="mb-1 text-xs text-slate-400">{result.payload.caption}</div>
+                    )}
+                    <table className="text-xs border-collapse">
+                        <thead>
+                            <tr>
+                                {result.payload.columns.map((c) => (
+                                    <th key={c} className="border border-slate-700 px-2 py-1 text-left">{c}</th>
+                                ))}
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {result.payload.rows.map((row, i) => (
+                                <tr key={i}>
+                                    {row.map((cell, j) => (
+                                        <td key={j} className="border border-slate-700 px-2 py-1">{cellText(cell)}</td>
+                                    ))}
+                                </tr>
+                            ))}
+                        </tbody>
+                    </table>
+                </div>
+            );
+        case "CitedText":
+            return (
+                <div>
+                    <div className="whitespace-pre-wrap">{result.payload.text}</div>
+                    {result.payload.citations.length > 0 && (
+                        <ol className="mt-2 list-decimal pl-5 text-xs text-slate-400">
+                            {result.payload.citations.map((c, i) => (
+                                <li key={c.id || i}>
+                                    {c.id && <span className="mr-1 font-mono">[{c.id}]</span>}
+                                    <a href={c.url} target="_blank" rel="noreferrer" className="hover:text-blue-400">
+                                        {c.context || c.url}
+                                    </a>
+                                    {c.artifact_id && (
+                                        <a
+                                            href={`${API_BASE}/v1/agent/artifacts/${c.artifact_id}`}
+                                            target="_blank"
+                                            rel="noreferrer"
+                                            className="ml-2 hover:text-blue-400"
+                                        >
+                                            snapshot
+                                        </a>
+                                    )}
+                                </li>
+                            ))}
+                        </ol>
+                    )}
+                </div>
+            );
+        case "Reference":
+            return (
+                <div>
+                    <div className="whitespace-pre-wrap">{result.payload.preview}…</div>
+                    <a
+                        className="mt-2 inline-block text-xs text-blue-400 hover:underline"
+                        href={`${API_BASE}/v1/agent/artifacts/${result.payload.ref_id}`}
+                        target="_blank"
+                        rel="noreferrer"
+                    >
+                        Full result ({Math.ceil(result.payload.size / 1024)} KB)
+                    </a>
+                </div>
+            );
+        case "UiComponent":
+            return <div className="text-xs text-slate-400">[{result.payload.component_type}]</div>;
+        case "Error":
+            return (
+                <div className="text-red-400">
+                    {result.payload.message} <span className="text-xs">({result.payload.code})</span>
+                </div>
+            );
+    }
+}
@@ -1,7 +1,8 @@
 //! Builder for ReActController.
 
+use multi_agent_core::prompts::PromptRegistry;
 use multi_agent_core::traits::{
-    ApprovalGate, ArtifactStore, LlmClient, SessionStore, ToolRegistry,
+    ApprovalGate, ArtifactStore, HumanInputChannel, LlmClient, SessionStore, ToolRegistry,
 };
 use multi_agent_governance::Guardrail;
 use std::sync::Arc;
@@ -12,6 +13,7 @@ use crate::capability::{
 };
 use crate::context::{CompressionConfig, ContextCompressor};
 use crate::delegation::Delegator;
+use crate::plan_estimate::PlanEstimator;
 use crate::react::{ReActConfig, ReActController};
 use crate::{MemoryCapability, PlanningCapability};
 
@@ -24,9 +26,18 @@ pub struct ReActBuilder {
     session_store: Option<Arc<dyn SessionStore>>,
     compression_config: CompressionConfig,
     capabilities: Vec<Arc<dyn AgentCapability>>,
+    /// Planner LLM and its position among the capabilities; the planner is
+    /// built last so it picks up the estimator and approval gate.
+    planning: Option<(usize, Arc<dyn LlmClient>)>,
+    plan_estimator: Option<Arc<PlanEstimator>>,
     approval_gate: Option<Arc<dyn ApprovalGate>>,
     policy_engine: Option<Arc<tokio::sync::RwLock<multi_agent_governance::PolicyEngine>>>,
     event_emitter: Option<Arc<dyn multi_agent_core::traits::EventEmitter>>,
+    model_selector: Option<Arc<multi_agent_model_gateway::AdaptiveModelSelector>>,
+    token_counter: Option<Arc<multi_agent_model_gateway::TokenCounter>>,
+    usage_ledger: Option<Arc<multi_agent_model_gateway::UsageLedger>>,
+    human_input: Option<Arc<dyn HumanInputChannel>>,
+    prompts: Option<Arc<PromptRegistry>>,
 }
 
 impl ReActBuilder {
@@ -40,9 +51,16 @@ impl ReActBuilder {
             session_store: None,
             compression_config: CompressionConfig::default(),
             capabilities: Vec::new(),
+            planning: None,
+            plan_estimator: None,
             approval_gate: None,
             policy_engine: None,
             event_emitter: None,
+            model_selector: None,
+            token_counter: None,
+            usage_ledger: None,
+            human_input: None,
+            prompts: None,
         }
     }
 
@@ -129,9 +147,17 @@ impl ReActBuilder {
     }
 
     /// Set plan-and-solve capability (compatibility mode).
+    ///
+    /// Plans are estimated with [`Self::with_plan_estimator`] and gated by
+    /// [`Self::with_approval_gate`] when those are set.
     pub fn with_planning(mut self, llm: Arc<dyn multi_agent_core::traits::LlmClient>) -> Self {
-        self.capabilities
-            .push(Arc::new(PlanningCapability::new(llm)));
+        self.planning = Some((self.capabilities.len(), llm));
+        self
+    }
+
+    /// Price plans before they run; plans over its threshold need approval.
+    pub fn with_plan_estimator(mut self, estimator: Arc<PlanEstimator>) -> Self {
+        self.plan_estimator = Some(estimator);
         self
     }
 
@@ -165,8 +191,62 @@ impl ReActBuilder {
         self
     }
 
+    /// Route each iteration to a model chosen by task class and past outcomes.
+    ///
+    /// Takes precedence over [`Self::with_llm`] for reasoning calls.
+    pub fn with_model_selector(
+        mut self,
+        selector: Arc<multi_agent_model_gateway::AdaptiveModelSelector>,
+    ) -> Self {
+        self.model_selector = Some(selector);
+        self
+    }
+
+    /// Check prompts against the budget and context window before each call.
+    ///
+    /// Without a counter, only the adaptive selector's model is checked.
+    pub fn with_token_counter(
+        mut self,
+        counter: Arc<multi_agent_model_gateway::TokenCounter>,
+    ) -> Self {
+        self.token_counter = Some(counter);
+        self
+    }
+
+    /// Attribute session tokens and tool calls to the daily usage ledger.
+    pub fn with_usage_ledger(
+        mut self,
+        ledger: Arc<multi_agent_model_gateway::UsageLedger>,
+    ) -> Self {
+        self.usage_ledger = Some(ledger);
+        self
+    }
+
+    /// Set the channel used to ask the human for revised instructions
+    /// when a session deadlocks.
+    pub fn with_human_input(mut self, channel: Arc<dyn HumanInputChannel>) -> Self {
+        self.human_input = Some(channel);
+        self
+    }
+
+    /// Set the localized prompts; the built-in English texts otherwise.
+    pub fn with_prompts(mut self, prompts: Arc<PromptRegistry>) -> Self {
+        self.prompts = Some(prompts);
+        self
+    }
+
     /// Build the ReActController.
-    pub fn build(self) -> ReActController {
+    pub fn build(mut self) -> ReActController {
+        if let Some((index, llm)) = self.planning.take() {
+            let mut planner = PlanningCapability::new(llm);
+            if let Some(estimator) = self.plan_estimator.take() {
+                planner = planner.with_estimator(estimator);
+            }
+            if let Some(gate) = &self.approval_gate {
+                planner = planner.with_approval_gate(gate.clone());
+            }
+            self.capabilities.insert(index, Arc::new(planner));
+        }
         ReActController {
             config: self.config,
             llm: self.llm,
@@ -178,6 +258,11 @@ impl ReActBuilder {
             approval_gate: self.approval_gate,
             policy_engine: self.policy_engine,
             event_emitter: self.event_emitter,
+            model_selector: self.model_selector,
+            token_counter: self.token_counter,
+            usage_ledger: self.usage_ledger,
+            human_input: self.human_input,
+            prompts: self.prompts.unwrap_or_default(),
         }
     }
 }
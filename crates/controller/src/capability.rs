@@ -6,12 +6,14 @@
 //! Capabilities can hook into the agent's lifecycle:
 //! - `on_start`: Called when a task begins.
 //! - `on_pre_reasoning`: Called before sending history to the LLM (e.g., compression, security).
+//! - `on_reasoning`: Called to replace the LLM call itself (e.g., speculative drafting).
 //! - `on_instruction`: Called to parse custom instructions from the LLM response.
 //! - `on_execute`: Called to execute custom actions.
 
 use crate::parser::ReActAction;
 use async_trait::async_trait;
 use chrono::Utc;
+use multi_agent_core::traits::{ChatMessage, LlmResponse};
 use multi_agent_core::types::{AgentResult, HistoryEntry, Session};
 use multi_agent_core::{Error, Result};
 use std::sync::Arc; // Ensure chrono is available or use via core if re-exported
@@ -22,6 +24,12 @@ pub trait AgentCapability: Send + Sync {
     /// Unique name of the capability.
     fn name(&self) -> &str;
 
+    /// Feature flag gating the capability; requests with the flag turned
+    /// off run without it.
+    fn feature_flag(&self) -> Option<&str> {
+        None
+    }
+
     /// Called when a new task starts.
     /// Useful for initializing state or validating the goal.
     async fn on_start(&self, _session: &mut Session) -> Result<()> {
@@ -34,6 +42,16 @@ pub trait AgentCapability: Send + Sync {
         Ok(())
     }
 
+    /// Called in place of the controller's own LLM call.
+    /// Returns `Some(Response)` if this capability produced the response.
+    async fn on_reasoning(
+        &self,
+        _session: &Session,
+        _messages: &[ChatMessage],
+    ) -> Result<Option<LlmResponse>> {
+        Ok(None)
+    }
+
     /// Called to parse a raw LLM response into an action.
     /// Returns `Some(Action)` if this capability recognizes the pattern.
     fn parse_action(&self, _response: &str) -> Option<ReActAction> {
@@ -201,6 +219,10 @@ impl AgentCapability for DelegationCapability {
         "subagent_delegation"
     }
 
+    fn feature_flag(&self) -> Option<&str> {
+        Some(multi_agent_core::types::flags::DELEGATION)
+    }
+
     fn parse_action(&self, response: &str) -> Option<ReActAction> {
         if response.contains("DELEGATE:") {
             if let Some((_, rest)) = response.split_once("DELEGATE:") {
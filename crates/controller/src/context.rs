@@ -8,6 +8,7 @@ use multi_agent_core::{
     traits::{ChatMessage, LlmClient},
     Result,
 };
+use multi_agent_model_gateway::TokenCounter;
 use serde::{Deserialize, Serialize};
 
 /// Configuration for context compression.
@@ -67,11 +68,21 @@ pub trait ContextCompressor: Send + Sync {
 }
 
 /// Simple truncation strategy - removes oldest messages.
-pub struct TruncationCompressor;
+pub struct TruncationCompressor {
+    counter: TokenCounter,
+}
 
 impl TruncationCompressor {
     pub fn new() -> Self {
-        Self
+        Self {
+            counter: TokenCounter::default(),
+        }
+    }
+
+    /// Count tokens with the target model's tokenizer instead of ~4 chars/token.
+    pub fn with_token_counter(mut self, counter: TokenCounter) -> Self {
+        self.counter = counter;
+        self
     }
 }
 
@@ -118,7 +129,7 @@ impl ContextCompressor for TruncationCompressor {
 
         result.extend(recent);
 
-        let compressed_count = total - result.len();
+        let compressed_count = total.saturating_sub(result.len());
         let estimated = self.estimate_tokens(&result);
 
         Ok(CompressionResult {
@@ -129,19 +140,28 @@ impl ContextCompressor for TruncationCompressor {
     }
 
     fn estimate_tokens(&self, messages: &[ChatMessage]) -> usize {
-        // Rough estimation: ~4 chars per token on average
-        messages.iter().map(|m| m.content.len() / 4).sum()
+        self.counter.count_messages(messages)
     }
 }
 
 /// Summarization strategy - uses LLM to summarize old messages.
 pub struct SummarizationCompressor<C: LlmClient> {
     client: C,
+    counter: TokenCounter,
 }
 
 impl<C: LlmClient> SummarizationCompressor<C> {
     pub fn new(client: C) -> Self {
-        Self { client }
+        Self {
+            client,
+            counter: TokenCounter::default(),
+        }
+    }
+
+    /// Count tokens with the target model's tokenizer instead of ~4 chars/token.
+    pub fn with_token_counter(mut self, counter: TokenCounter) -> Self {
+        self.counter = counter;
+        self
     }
 }
 
@@ -211,7 +231,7 @@ impl<C: LlmClient + 'static> ContextCompressor for SummarizationCompressor<C> {
     }
 
     fn estimate_tokens(&self, messages: &[ChatMessage]) -> usize {
-        messages.iter().map(|m| m.content.len() / 4).sum()
+        self.counter.count_messages(messages)
     }
 }
 
@@ -0,0 +1,312 @@
+//! Keeps session history inside the model's context window.
+//!
+//! Before each LLM call the controller measures the prompt with the target
+//! model's tokenizer. When less than [`MIN_HEADROOM_RATIO`] of the window
+//! would remain, history is shrunk in two steps instead of letting the
+//! provider reject the request:
+//!
+//! 1. Oversized tool observations are split into chunks and summarized (or,
+//!    without a summarizer, cut down to their head and tail).
+//! 2. If that is not enough, older messages are truncated, keeping the
+//!    system prompt and the most recent turns.
+
+use chrono::Utc;
+use std::sync::Arc;
+
+use multi_agent_core::{
+    traits::LlmClient,
+    types::{HistoryEntry, Session},
+    Result,
+};
+use multi_agent_model_gateway::TokenCounter;
+
+use crate::context::{CompressionConfig, ContextCompressor, TruncationCompressor};
+
+/// Fraction of the window kept free before history is shrunk.
+pub const MIN_HEADROOM_RATIO: f64 = 0.1;
+/// Observations larger than 1/N of the window are summarized.
+const MAX_OBSERVATION_RATIO: usize = 8;
+/// Recent messages kept when older history is truncated.
+const PRESERVE_RECENT: usize = 4;
+
+/// What [`fit_to_window`] did to the session.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FitReport {
+    pub prompt_tokens_before: usize,
+    pub prompt_tokens_after: usize,
+    /// Observations replaced by chunked summaries.
+    pub observations_summarized: usize,
+    /// Messages dropped by truncation.
+    pub messages_truncated: usize,
+}
+
+impl FitReport {
+    pub fn changed(&self) -> bool {
+        self.observations_summarized > 0 || self.messages_truncated > 0
+    }
+}
+
+/// Shrink session history until the prompt plus `reserved_output` tokens
+/// leaves [`MIN_HEADROOM_RATIO`] of the window free.
+pub async fn fit_to_window(
+    session: &mut Session,
+    counter: &TokenCounter,
+    summarizer: Option<&dyn LlmClient>,
+    reserved_output: usize,
+) -> Result<FitReport> {
+    let window = counter.context_window();
+    let limit = window - (window as f64 * MIN_HEADROOM_RATIO) as usize;
+    let limit = limit.saturating_sub(reserved_output);
+    let prompt_tokens = |s: &Session| {
+        counter.count_messages(&crate::react::ReActController::build_messages_static(s))
+    };
+
+    let mut report = FitReport {
+        prompt_tokens_before: prompt_tokens(session),
+        ..FitReport::default()
+    };
+    report.prompt_tokens_after = report.prompt_tokens_before;
+    if report.prompt_tokens_before <= limit {
+        return Ok(report);
+    }
+
+    tracing::info!(
+        session_id = %session.id,
+        prompt_tokens = report.prompt_tokens_before,
+        context_window = window,
+        "Context headroom low, shrinking history"
+    );
+
+    // 1. Summarize oversized observations, largest first
+    let max_observation = (window / MAX_OBSERVATION_RATIO).max(1);
+    let mut oversized: Vec<(usize, usize)> = session
+        .history
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.role == "user" && e.content.starts_with("OBSERVATION:"))
+        .map(|(i, e)| (i, counter.count(&e.content)))
+        .filter(|(_, tokens)| *tokens > max_observation)
+        .collect();
+    oversized.sort_by_key(|&(_, tokens)| std::cmp::Reverse(tokens));
+
+    for (index, tokens) in oversized {
+        let content = session.history[index].content.clone();
+        let summary =
+            summarize_chunked(&content, tokens, max_observation, counter, summarizer).await;
+        session.history[index].content = Arc::new(summary);
+        report.observations_summarized += 1;
+        report.prompt_tokens_after = prompt_tokens(session);
+        if report.prompt_tokens_after <= limit {
+            return Ok(report);
+        }
+    }
+
+    // 2. Truncate older history (system prompt + recent turns are kept)
+    let messages = crate::react::ReActController::build_messages_static(session);
+    let total = messages.len();
+    if total <= PRESERVE_RECENT + 1 {
+        return Ok(report);
+    }
+    if let Err(e) =
+        crate::memory_writeback::flush_pre_compaction(session, report.prompt_tokens_after)
+    {
+        tracing::warn!(error = %e, "Pre-compaction flush failed");
+    }
+    let config = CompressionConfig {
+        max_tokens: limit,
+        preserve_recent: PRESERVE_RECENT,
+        ..CompressionConfig::default()
+    };
+    let result = TruncationCompressor::new()
+        .with_token_counter(counter.clone())
+        .compress(messages, &config)
+        .await?;
+    let now = Utc::now().timestamp();
+    session.history = result
+        .messages
+        .into_iter()
+        .map(|msg| HistoryEntry {
+            role: msg.role,
+            content: Arc::new(msg.content),
+            tool_call: None,
+            timestamp: now,
+        })
+        .collect();
+    report.messages_truncated = total.saturating_sub(session.history.len());
+    report.prompt_tokens_after = prompt_tokens(session);
+    Ok(report)
+}
+
+/// Summarize `content` chunk by chunk so each summarizer call fits the window.
+async fn summarize_chunked(
+    content: &str,
+    tokens: usize,
+    max_tokens: usize,
+    counter: &TokenCounter,
+    summarizer: Option<&dyn LlmClient>,
+) -> String {
+    let body = content
+        .strip_prefix("OBSERVATION:")
+        .unwrap_or(content)
+        .trim();
+    // Approximate chunk size in chars from the observed density
+    let chunk_chars = (body.len() * max_tokens / tokens.max(1)).max(1);
+    let chunks = split_chars(body, chunk_chars);
+
+    if let Some(llm) = summarizer {
+        let mut summaries = Vec::with_capacity(chunks.len());
+        for (i, chunk) in chunks.iter().enumerate() {
+            let prompt = format!(
+                "Summarize part {} of {} of a tool output. Keep facts, numbers, \
+                 identifiers and errors needed to continue the task:\n\n{}",
+                i + 1,
+                chunks.len(),
+                chunk
+            );
+            match llm.complete(&prompt).await {
+                Ok(response) => summaries.push(response.content.trim().to_string()),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Observation summarization failed, truncating");
+                    summaries.clear();
+                    break;
+                }
+            }
+        }
+        let summary = summaries.join("\n");
+        if !summaries.is_empty() && counter.count(&summary) <= max_tokens {
+            return format!(
+                "OBSERVATION (summarized from {} tokens in {} parts): {}",
+                tokens,
+                chunks.len(),
+                summary
+            );
+        }
+    }
+
+    // No usable summary: keep the head and tail
+    let half = (chunk_chars / 2).max(1);
+    let head = chunks
+        .first()
+        .map(|c| split_chars(c, half)[0])
+        .unwrap_or("");
+    let tail = chunks
+        .last()
+        .map(|c| *split_chars(c, half).last().unwrap_or(&""))
+        .unwrap_or("");
+    format!(
+        "OBSERVATION (truncated from {} tokens): {}\n[...]\n{}",
+        tokens, head, tail
+    )
+}
+
+/// Split at char boundaries into pieces of about `size` bytes.
+fn split_chars(text: &str, size: usize) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let mut end = size.min(rest.len());
+        while !rest.is_char_boundary(end) {
+            end += 1;
+        }
+        let (piece, tail) = rest.split_at(end);
+        pieces.push(piece);
+        rest = tail;
+    }
+    if pieces.is_empty() {
+        pieces.push("");
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use multi_agent_core::traits::{ChatMessage, LlmResponse, LlmUsage};
+    use multi_agent_core::types::{SessionStatus, TokenUsage};
+
+    struct FixedLlm;
+
+    #[async_trait]
+    impl LlmClient for FixedLlm {
+        async fn complete(&self, _prompt: &str) -> Result<LlmResponse> {
+            Ok(LlmResponse {
+                content: "all lines ok".to_string(),
+                finish_reason: "stop".to_string(),
+                usage: LlmUsage::default(),
+                tool_calls: None,
+            })
+        }
+        async fn chat(&self, _messages: &[ChatMessage]) -> Result<LlmResponse> {
+            self.complete("").await
+        }
+        async fn embed(&self, _text: &str) -> Result<Vec<f32>> {
+            Ok(vec![])
+        }
+    }
+
+    fn entry(role: &str, content: String) -> HistoryEntry {
+        HistoryEntry {
+            role: role.to_string(),
+            content: Arc::new(content),
+            tool_call: None,
+            timestamp: 0,
+        }
+    }
+
+    fn session(history: Vec<HistoryEntry>) -> Session {
+        Session {
+            id: "s".to_string(),
+            trace_id: "t".to_string(),
+            user_id: None,
+            workspace_id: None,
+            dry_run: false,
+            status: SessionStatus::Running,
+            history,
+            task_state: None,
+            token_usage: TokenUsage::default(),
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_summarizes_oversized_observation() {
+        let counter = TokenCounter::approximate(2_000);
+        let mut session = session(vec![
+            entry("system", "GOAL: inspect logs".to_string()),
+            entry("assistant", "ACTION: read_file".to_string()),
+            entry(
+                "user",
+                format!("OBSERVATION: {}", "log line ok\n".repeat(800)),
+            ),
+        ]);
+        let report = fit_to_window(&mut session, &counter, Some(&FixedLlm), 100)
+            .await
+            .unwrap();
+        assert_eq!(report.observations_summarized, 1);
+        assert_eq!(report.messages_truncated, 0);
+        assert!(report.prompt_tokens_after < report.prompt_tokens_before);
+        assert!(session.history[2]
+            .content
+            .starts_with("OBSERVATION (summarized from"));
+        assert_eq!(session.history.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_untouched_when_headroom_is_enough() {
+        let counter = TokenCounter::approximate(100_000);
+        let mut session = session(vec![entry("system", "GOAL: hi".to_string())]);
+        let report = fit_to_window(&mut session, &counter, None, 1_000)
+            .await
+            .unwrap();
+        assert!(!report.changed());
+    }
+
+    #[test]
+    fn test_split_chars_respects_boundaries() {
+        let pieces = split_chars("héllo wörld", 2);
+        assert_eq!(pieces.concat(), "héllo wörld");
+        assert!(pieces.iter().all(|p| !p.is_empty()));
+    }
+}
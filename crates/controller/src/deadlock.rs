@@ -0,0 +1,272 @@
+//! Recovery from approval deadlocks.
+//!
+//! A session is deadlocked once a reviewer has denied
+//! `controller.deadlock.max_rejections` tool calls in a row. The workspace's
+//! [`DeadlockStrategy`] decides what happens next: fail the session, ask the
+//! human for revised instructions, submit a plan for approval, or stop acting
+//! and answer with a report of the findings so far. A report ends the session
+//! as [`SessionStatus::Degraded`](multi_agent_core::types::SessionStatus).
+//! Strategies that cannot run (no input channel, gate or model) fail.
+
+use std::sync::Arc;
+
+use multi_agent_core::config::DeadlockStrategy;
+use multi_agent_core::traits::{ChatMessage, LlmClient};
+use multi_agent_core::types::{
+    AgentResult, ApprovalRequest, ApprovalResponse, HistoryEntry, HumanAnswer, HumanQuestion,
+    Session, SessionStatus, ToolRiskLevel,
+};
+use multi_agent_core::{Error, Result};
+use multi_agent_model_gateway::TaskClass;
+
+use crate::react::{chrono_timestamp, ReActController};
+
+/// Tool name under which recovery plans are sent to the approval gate.
+pub const PLAN_APPROVAL_TOOL: &str = "approve_plan";
+
+/// Seconds a recovery question or plan waits for the human.
+const RECOVERY_EXPIRY_SECS: i64 = 300;
+
+const PLAN_PROMPT: &str = "Your recent tool calls were denied by the human reviewer. \
+     Do not call any tool now. Propose a short numbered plan for reaching the goal \
+     that the reviewer is likely to accept, naming every tool you intend to use.";
+
+const REPORT_PROMPT: &str = "Your recent tool calls were denied by the human reviewer, \
+     so you may not act any further. Do not call any tool. Write a report of what you \
+     found so far, what remains undone, and which actions would need approval to finish.";
+
+/// How a deadlocked session continues.
+pub(crate) enum Recovery {
+    /// The deadlock is resolved; keep iterating.
+    Resume,
+    /// End the session with this report.
+    Report(AgentResult),
+}
+
+fn strategy_label(strategy: DeadlockStrategy) -> &'static str {
+    match strategy {
+        DeadlockStrategy::Fail => "fail",
+        DeadlockStrategy::AskHuman => "ask_human",
+        DeadlockStrategy::PlanApproval => "plan_approval",
+        DeadlockStrategy::ReportOnly => "report_only",
+    }
+}
+
+fn expires_at() -> i64 {
+    chrono_timestamp() + RECOVERY_EXPIRY_SECS
+}
+
+fn goal(session: &Session) -> String {
+    session
+        .task_state
+        .as_ref()
+        .map(|t| t.goal.clone())
+        .unwrap_or_default()
+}
+
+fn push_user_message(session: &mut Session, content: String) {
+    session.history.push(HistoryEntry {
+        role: "user".to_string(),
+        content: Arc::new(content),
+        tool_call: None,
+        timestamp: chrono_timestamp(),
+    });
+}
+
+impl ReActController {
+    /// Whether the session has hit the consecutive rejection limit.
+    pub(crate) fn is_deadlocked(&self, session: &Session) -> bool {
+        let limit = self.config.deadlock.max_rejections;
+        limit > 0
+            && session
+                .task_state
+                .as_ref()
+                .is_some_and(|t| t.consecutive_rejections >= limit)
+    }
+
+    /// Apply the workspace's deadlock strategy; errors when the session must fail.
+    pub(crate) async fn recover_deadlock(&self, session: &mut Session) -> Result<Recovery> {
+        let strategy = self
+            .config
+            .deadlock
+            .strategy_for(session.workspace_id.as_deref());
+        tracing::warn!(
+            session_id = %session.id,
+            strategy = ?strategy,
+            "Deadlock detected: too many consecutive rejections"
+        );
+
+        let recovery = match strategy {
+            DeadlockStrategy::Fail => None,
+            DeadlockStrategy::AskHuman => self.ask_for_instructions(session).await?,
+            DeadlockStrategy::PlanApproval => self.approve_plan(session).await?,
+            DeadlockStrategy::ReportOnly => self.report_findings(session).await?,
+        };
+        metrics::counter!(
+            "deadlock_recoveries_total",
+            "strategy" => strategy_label(strategy),
+            "outcome" => match &recovery {
+                Some(Recovery::Resume) => "resumed",
+                Some(Recovery::Report(_)) => "reported",
+                None => "failed",
+            }
+        )
+        .increment(1);
+
+        let recovery = recovery.ok_or_else(|| {
+            Error::controller(format!(
+                "Deadlock: Too many consecutive human rejections ({}). Terminating session.",
+                self.config.deadlock.max_rejections
+            ))
+        })?;
+        if let Some(ref mut task_state) = session.task_state {
+            task_state.consecutive_rejections = 0;
+        }
+        Ok(recovery)
+    }
+
+    /// Ask the human how to proceed and continue with their answer.
+    async fn ask_for_instructions(&self, session: &mut Session) -> Result<Option<Recovery>> {
+        let Some(channel) = &self.human_input else {
+            tracing::warn!("Deadlock strategy ask_human needs a human input channel");
+            return Ok(None);
+        };
+        let question = HumanQuestion {
+            request_id: uuid::Uuid::new_v4().to_string(),
+            session_id: session.id.clone(),
+            question: format!(
+                "The agent's last {} tool calls were denied while working on: {}. \
+                 How should it proceed?",
+                self.config.deadlock.max_rejections,
+                goal(session)
+            ),
+            options: Vec::new(),
+            timeout_secs: None,
+            nonce: uuid::Uuid::new_v4().to_string(),
+            expires_at: expires_at(),
+        };
+
+        session.status = SessionStatus::Paused;
+        self.persist_session(session).await;
+        let answer = channel.ask(&question).await?;
+        session.status = SessionStatus::Running;
+
+        match answer {
+            HumanAnswer::Answered { answer } => {
+                tracing::info!(session_id = %session.id, "Resuming deadlocked session with revised instructions");
+                push_user_message(
+                    session,
+                    format!(
+                        "REVISED INSTRUCTIONS from the human reviewer: {}\n\
+                         Follow them instead of repeating the denied actions.",
+                        answer
+                    ),
+                );
+                Ok(Some(Recovery::Resume))
+            }
+            HumanAnswer::TimedOut => Ok(None),
+        }
+    }
+
+    /// Have the agent propose a plan and continue once the reviewer approves it.
+    async fn approve_plan(&self, session: &mut Session) -> Result<Option<Recovery>> {
+        let Some(gate) = self.approval_gate.clone() else {
+            tracing::warn!("Deadlock strategy plan_approval needs an approval gate");
+            return Ok(None);
+        };
+        let Some(plan) = self.complete_without_tools(session, PLAN_PROMPT).await? else {
+            return Ok(None);
+        };
+
+        let request = ApprovalRequest {
+            request_id: uuid::Uuid::new_v4().to_string(),
+            session_id: session.id.clone(),
+            tool_name: PLAN_APPROVAL_TOOL.to_string(),
+            args: serde_json::json!({ "plan": plan }),
+            risk_level: ToolRiskLevel::High,
+            context: format!(
+                "Recovery plan after {} denied tool calls. Session Goal: {}",
+                self.config.deadlock.max_rejections,
+                goal(session)
+            ),
+            timeout_secs: None,
+            nonce: uuid::Uuid::new_v4().to_string(),
+            expires_at: expires_at(),
+            mandatory: false,
+        };
+        let approved = match gate.request_approval(&request).await? {
+            ApprovalResponse::Approved { .. } => plan,
+            ApprovalResponse::Modified { args, .. } => args
+                .get("plan")
+                .and_then(|p| p.as_str())
+                .map(str::to_string)
+                .unwrap_or(plan),
+            ApprovalResponse::Denied { reason, .. } => {
+                tracing::warn!(session_id = %session.id, reason = %reason, "Recovery plan DENIED");
+                return Ok(None);
+            }
+        };
+
+        push_user_message(
+            session,
+            format!(
+                "The human reviewer approved this plan. Follow it and do not attempt \
+                 actions outside it:\n{}",
+                approved
+            ),
+        );
+        Ok(Some(Recovery::Resume))
+    }
+
+    /// Stop acting and answer with a report of the findings so far.
+    async fn report_findings(&self, session: &mut Session) -> Result<Option<Recovery>> {
+        Ok(self
+            .complete_without_tools(session, REPORT_PROMPT)
+            .await?
+            .map(|report| Recovery::Report(AgentResult::Text(report))))
+    }
+
+    /// One LLM call with `instruction` appended; `None` without a model.
+    async fn complete_without_tools(
+        &self,
+        session: &mut Session,
+        instruction: &str,
+    ) -> Result<Option<String>> {
+        let mut messages = self.build_messages(session);
+        messages.push(ChatMessage {
+            role: "user".to_string(),
+            content: instruction.to_string(),
+            tool_calls: None,
+        });
+
+        let llm: Arc<dyn LlmClient> = match (&self.llm, &self.model_selector) {
+            (Some(llm), _) => llm.clone(),
+            (None, Some(selector)) if selector.has_available() => {
+                Arc::from(selector.select_for_class(TaskClass::classify(&messages))?.0)
+            }
+            _ => return Ok(None),
+        };
+        let response = llm
+            .chat_with_params(&messages, &self.config.provider_params)
+            .await?;
+        session.token_usage.add(
+            response.usage.prompt_tokens,
+            response.usage.completion_tokens,
+        );
+        if let Some(ledger) = &self.usage_ledger {
+            ledger.record_session(
+                &session.id,
+                response.usage.prompt_tokens + response.usage.completion_tokens,
+            );
+        }
+
+        let content = response.content.trim().to_string();
+        session.history.push(HistoryEntry {
+            role: "assistant".to_string(),
+            content: Arc::new(content.clone()),
+            tool_call: None,
+            timestamp: chrono_timestamp(),
+        });
+        Ok(Some(content))
+    }
+}
@@ -0,0 +1,187 @@
+//! Dry-run missions.
+//!
+//! A dry-run session runs the ReAct loop as usual, but mutating tool calls
+//! (risk at or above `Medium`, or tools that always need approval) are not
+//! executed. The agent receives a simulated observation instead, and the
+//! final answer is wrapped in a report listing every action it took or
+//! would have taken.
+
+use serde::Serialize;
+use std::sync::Arc;
+
+use multi_agent_core::types::{
+    AgentResult, AgentStreamEvent, HistoryEntry, Session, ToolCallInfo, ToolRiskLevel,
+};
+
+use crate::react::chrono_timestamp;
+
+/// Prefix of simulated observations; also how the report tells them apart.
+pub const DRY_RUN_MARKER: &str = "[DRY RUN]";
+
+/// Whether a tool call is simulated rather than executed in a dry run.
+pub fn is_simulated(risk: ToolRiskLevel, requires_approval: bool) -> bool {
+    requires_approval || risk >= ToolRiskLevel::Medium
+}
+
+/// Record a simulated call in the session history, in place of running it.
+pub fn record_simulated_call(
+    session: &mut Session,
+    tool: &str,
+    args: serde_json::Value,
+    risk: ToolRiskLevel,
+) {
+    let observation = format!(
+        "{} Tool '{}' ({:?} risk) was not executed. It would have been called with {}. \
+         Assume it succeeded and continue planning.",
+        DRY_RUN_MARKER, tool, risk, args
+    );
+    AgentStreamEvent::Observation {
+        tool: Some(tool.to_string()),
+        content: observation.clone(),
+    }
+    .emit();
+    session.history.push(HistoryEntry {
+        role: "user".to_string(),
+        content: Arc::new(format!("OBSERVATION: {}", observation)),
+        tool_call: Some(ToolCallInfo {
+            name: tool.to_string(),
+            arguments: args,
+            result: Some(Arc::new(observation.clone())),
+        }),
+        timestamp: chrono_timestamp(),
+    });
+    if let Some(ref mut task_state) = session.task_state {
+        task_state.observations.push(Arc::new(observation));
+    }
+}
+
+/// One tool call in a dry-run report.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedAction {
+    pub step: usize,
+    pub tool: String,
+    pub args: serde_json::Value,
+    /// `false` when the call was simulated.
+    pub executed: bool,
+}
+
+/// Action plan produced by a dry-run mission.
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRunReport {
+    pub dry_run: bool,
+    pub goal: String,
+    pub actions: Vec<PlannedAction>,
+    /// Number of simulated (mutating) calls.
+    pub simulated: usize,
+    /// The agent's final answer, written as if the simulated calls succeeded.
+    pub answer: String,
+}
+
+impl DryRunReport {
+    /// Build the report from the session's tool call history.
+    pub fn from_session(session: &Session, answer: &AgentResult) -> Self {
+        let actions: Vec<PlannedAction> = session
+            .history
+            .iter()
+            .filter_map(|entry| entry.tool_call.as_ref())
+            .enumerate()
+            .map(|(i, call)| PlannedAction {
+                step: i + 1,
+                tool: call.name.clone(),
+                args: call.arguments.clone(),
+                executed: !call
+                    .result
+                    .as_ref()
+                    .is_some_and(|r| r.starts_with(DRY_RUN_MARKER)),
+            })
+            .collect();
+
+        Self {
+            dry_run: true,
+            goal: session
+                .task_state
+                .as_ref()
+                .map(|t| t.goal.clone())
+                .unwrap_or_default(),
+            simulated: actions.iter().filter(|a| !a.executed).count(),
+            actions,
+            answer: answer.to_text(),
+        }
+    }
+
+    pub fn into_result(self) -> AgentResult {
+        AgentResult::Data(serde_json::to_value(self).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multi_agent_core::types::{SessionStatus, TaskState, TokenUsage};
+
+    fn session() -> Session {
+        Session {
+            id: "s1".into(),
+            trace_id: "t1".into(),
+            user_id: None,
+            workspace_id: None,
+            dry_run: true,
+            status: SessionStatus::Running,
+            history: Vec::new(),
+            task_state: Some(TaskState {
+                iteration: 0,
+                goal: "Clean up old files".into(),
+                observations: Vec::new(),
+                pending_actions: Vec::new(),
+                consecutive_rejections: 0,
+            }),
+            token_usage: TokenUsage::default(),
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_simulated_threshold() {
+        assert!(!is_simulated(ToolRiskLevel::Low, false));
+        assert!(is_simulated(ToolRiskLevel::Low, true));
+        assert!(is_simulated(ToolRiskLevel::Medium, false));
+        assert!(is_simulated(ToolRiskLevel::Critical, false));
+    }
+
+    #[test]
+    fn test_report_separates_simulated_calls() {
+        let mut session = session();
+        session.history.push(HistoryEntry {
+            role: "user".into(),
+            content: Arc::new("OBSERVATION: a.log b.log".into()),
+            tool_call: Some(ToolCallInfo {
+                name: "fs_list".into(),
+                arguments: serde_json::json!({"path": "/tmp"}),
+                result: Some(Arc::new("a.log b.log".into())),
+            }),
+            timestamp: 0,
+        });
+        record_simulated_call(
+            &mut session,
+            "fs_delete",
+            serde_json::json!({"path": "/tmp/a.log"}),
+            ToolRiskLevel::High,
+        );
+
+        let report =
+            DryRunReport::from_session(&session, &AgentResult::Text("Deleted a.log".into()));
+        assert_eq!(report.goal, "Clean up old files");
+        assert_eq!(report.actions.len(), 2);
+        assert!(report.actions[0].executed);
+        assert!(!report.actions[1].executed);
+        assert_eq!(report.actions[1].step, 2);
+        assert_eq!(report.simulated, 1);
+
+        let AgentResult::Data(data) = report.into_result() else {
+            panic!("Expected Data result");
+        };
+        assert_eq!(data["dry_run"], true);
+        assert_eq!(data["actions"][1]["tool"], "fs_delete");
+    }
+}
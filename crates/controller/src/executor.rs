@@ -87,6 +87,8 @@ impl ToolExecutor {
                     id: "security_check".to_string(),
                     trace_id: "security_check".to_string(),
                     user_id: None,
+                    workspace_id: None,
+                    dry_run: false,
                     status: multi_agent_core::types::SessionStatus::Running,
                     history: vec![HistoryEntry {
                         role: "user".to_string(),
@@ -7,16 +7,21 @@
 pub mod builder;
 pub mod capability;
 pub mod context;
+pub mod context_window;
 pub mod dag;
+pub mod deadlock;
 pub mod delegation;
+pub mod dry_run;
 pub mod executor;
 pub mod memory;
 pub mod memory_writeback;
 pub mod parser;
 pub mod persistence;
+pub mod plan_estimate;
 pub mod planning;
 pub mod react;
 pub mod sop;
+pub mod speculative;
 pub mod summarization;
 
 pub use builder::ReActBuilder;
@@ -24,11 +29,14 @@ pub use capability::{
     AgentCapability, CompressionCapability, DelegationCapability, McpCapability,
     ReflectionCapability, SecurityCapability,
 };
+pub use dry_run::DryRunReport;
 pub use memory::MemoryCapability;
 pub use memory_writeback::MemoryWritebackCapability;
 pub use multi_agent_core::traits::SessionStore;
 pub use parser::{ActionParser, ReActAction};
 pub use persistence::InMemorySessionStore;
+pub use plan_estimate::{EstimateAccuracy, PlanApprovalThreshold, PlanEstimate, PlanEstimator};
 pub use planning::PlanningCapability;
 pub use react::{chrono_timestamp, ReActConfig, ReActController};
+pub use speculative::{SpeculativeCapability, SpeculativeConfig, SpeculativeStats};
 pub use summarization::SummarizationCapability;
@@ -125,6 +125,11 @@ impl AgentCapability for MemoryCapability {
                 AgentResult::File { filename, .. } => {
                     format!("Goal: {}\nResult File: {}", goal_text, filename)
                 }
+                AgentResult::Artifacts { .. }
+                | AgentResult::Table { .. }
+                | AgentResult::CitedText { .. } => {
+                    format!("Goal: {}\nResult: {}", goal_text, result.to_text())
+                }
                 _ => return Ok(()),
             };
 
@@ -270,15 +270,7 @@ impl AgentCapability for MemoryWritebackCapability {
             return Ok(());
         }
 
-        let result_text = match result {
-            AgentResult::Text(s) => s.clone(),
-            AgentResult::Data(v) => v.to_string(),
-            AgentResult::File { filename, .. } => format!("file: {}", filename),
-            AgentResult::UiComponent { component_type, .. } => {
-                format!("ui_component: {}", component_type)
-            }
-            AgentResult::Error { message, .. } => format!("error: {}", message),
-        };
+        let result_text = result.to_text();
 
         std::fs::create_dir_all(&self.base_dir)
             .map_err(|e| Error::controller(format!("create memory dir failed: {}", e)))?;
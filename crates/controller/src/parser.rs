@@ -65,6 +65,62 @@ impl ActionParser {
         ReActAction::Think(response_trimmed.to_string())
     }
 
+    /// Reasoning stated on a response's `THOUGHT:` line, if any.
+    pub fn thought(response: &str) -> Option<String> {
+        let thought = response
+            .lines()
+            .find_map(|l| l.trim_start().strip_prefix("THOUGHT:"))?
+            .trim();
+        (!thought.is_empty()).then(|| thought.to_string())
+    }
+
+    /// Explain why a response that looks like an action could not be parsed.
+    ///
+    /// Returns `None` for well-formed actions and for plain thoughts; only
+    /// attempted-but-broken actions (an `ACTION:` line with bad or missing
+    /// `ARGS:`, unparseable tool-call JSON) yield an error message.
+    pub fn diagnose(&self, response: &str) -> Option<String> {
+        let response = response.trim();
+        if !matches!(self.parse(response), ReActAction::Think(_)) {
+            return None;
+        }
+
+        if response.starts_with('{') || response.starts_with('[') {
+            return Some(match serde_json::from_str::<serde_json::Value>(response) {
+                Err(e) => format!("Tool call JSON is invalid: {}", e),
+                Ok(_) => "Tool call JSON needs a \"name\" and an \"arguments\" object".to_string(),
+            });
+        }
+
+        let action = response
+            .lines()
+            .find_map(|l| l.strip_prefix("ACTION:"))?
+            .trim();
+        if action.is_empty() {
+            return Some("ACTION line has no tool name".to_string());
+        }
+        let Some(args) = response
+            .lines()
+            .find_map(|l| l.strip_prefix("ARGS:"))
+            .map(str::trim)
+        else {
+            return Some(format!(
+                "ACTION '{}' is missing an ARGS line with a JSON object",
+                action
+            ));
+        };
+        if args.is_empty() {
+            return Some(format!(
+                "ARGS for '{}' is empty; the JSON object must be on the same line",
+                action
+            ));
+        }
+        Some(match serde_json::from_str::<serde_json::Value>(args) {
+            Err(e) => format!("ARGS for '{}' is not valid JSON: {}", action, e),
+            Ok(_) => format!("ARGS for '{}' could not be read", action),
+        })
+    }
+
     /// Try to parse OpenAI-style function call from JSON.
     fn try_parse_function_call(&self, response: &str) -> Option<ReActAction> {
         // Look for tool_calls in the response (common in structured output)
@@ -173,6 +229,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_diagnose_malformed_actions() {
+        let parser = ActionParser::new(vec![]);
+        assert!(parser
+            .diagnose("ACTION: search\nARGS: {\"q\": 1}")
+            .is_none());
+        assert!(parser.diagnose("Still thinking about it").is_none());
+
+        let err = parser
+            .diagnose("ACTION: search\nARGS: {query: rust}")
+            .unwrap();
+        assert!(err.contains("not valid JSON"), "{err}");
+        let err = parser.diagnose("ACTION: search").unwrap();
+        assert!(err.contains("missing an ARGS line"), "{err}");
+        let err = parser.diagnose("{\"name\": \"search\", ").unwrap();
+        assert!(err.contains("Tool call JSON is invalid"), "{err}");
+    }
+
     #[test]
     fn test_parse_think() {
         let parser = ActionParser::new(vec![]);
@@ -91,6 +91,8 @@ mod tests {
             id: id.to_string(),
             trace_id: format!("trace-{}", id),
             user_id: None,
+            workspace_id: None,
+            dry_run: false,
             status: SessionStatus::Running,
             history: vec![],
             task_state: None,
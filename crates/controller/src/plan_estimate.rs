@@ -0,0 +1,323 @@
+//! Cost estimates for execution plans.
+//!
+//! [`PlanEstimator`] prices a plan before it runs: tokens per step come from
+//! the sessions it has seen complete, tool observation sizes from
+//! [`ToolAnalytics`], and the risk from the highest-risk tool the plan
+//! mentions. After the run, the actual token count is recorded so the
+//! per-step average converges and the estimate accuracy can be tracked.
+
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+
+use multi_agent_core::config::PlanningConfig;
+use multi_agent_core::traits::ToolRegistry;
+use multi_agent_core::types::ToolRiskLevel;
+use multi_agent_model_gateway::ModelPricing;
+use multi_agent_skills::ToolAnalytics;
+
+use crate::planning::PlanStep;
+
+/// Tokens per step assumed until a plan has been observed.
+const DEFAULT_STEP_TOKENS: u64 = 2_000;
+/// Share of step tokens spent on prompts, until observed.
+const DEFAULT_PROMPT_SHARE: f64 = 0.8;
+
+/// Predicted cost of executing a plan.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanEstimate {
+    pub steps: usize,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+    /// `None` without pricing for the model.
+    pub cost_usd: Option<f64>,
+    /// Tools the plan mentions, in plan order.
+    pub tools: Vec<String>,
+    /// Highest risk among the mentioned tools.
+    pub risk: ToolRiskLevel,
+    /// Whether the per-step figure comes from history rather than the default.
+    pub from_history: bool,
+}
+
+/// How far estimates have been from the actual usage.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EstimateAccuracy {
+    pub samples: u64,
+    /// Mean of `|actual - estimate| / estimate`.
+    pub mean_abs_error: f64,
+    /// Mean of `actual / estimate`; above 1 means plans are underestimated.
+    pub mean_ratio: f64,
+}
+
+/// When a plan needs approval before it runs. Zero disables a limit.
+#[derive(Debug, Clone, Default)]
+pub struct PlanApprovalThreshold {
+    pub tokens: u64,
+    pub cost_usd: f64,
+    /// Plans mentioning a tool at or above this risk need approval.
+    pub risk: Option<ToolRiskLevel>,
+}
+
+impl PlanApprovalThreshold {
+    pub fn from_config(config: &PlanningConfig) -> Self {
+        Self {
+            tokens: config.approval_tokens,
+            cost_usd: config.approval_cost_usd,
+            risk: config.approval_risk,
+        }
+    }
+
+    /// Reasons `estimate` crosses the threshold; empty when it does not.
+    pub fn exceeded_by(&self, estimate: &PlanEstimate) -> Vec<String> {
+        let mut reasons = Vec::new();
+        if self.tokens > 0 && estimate.total_tokens > self.tokens {
+            reasons.push(format!(
+                "~{} tokens exceeds the {} token threshold",
+                estimate.total_tokens, self.tokens
+            ));
+        }
+        if let Some(cost) = estimate.cost_usd {
+            if self.cost_usd > 0.0 && cost > self.cost_usd {
+                reasons.push(format!(
+                    "~${:.4} exceeds the ${:.4} cost threshold",
+                    cost, self.cost_usd
+                ));
+            }
+        }
+        if self.risk.is_some_and(|risk| estimate.risk >= risk) {
+            reasons.push(format!("uses {:?}-risk tools", estimate.risk));
+        }
+        reasons
+    }
+}
+
+#[derive(Debug, Default)]
+struct History {
+    steps: u64,
+    prompt_tokens: u64,
+    completion_tokens: u64,
+    samples: u64,
+    abs_error: f64,
+    ratio: f64,
+}
+
+/// Estimates plan cost from historical per-step and per-tool statistics.
+pub struct PlanEstimator {
+    analytics: Option<Arc<ToolAnalytics>>,
+    tools: Option<Arc<dyn ToolRegistry>>,
+    pricing: Option<ModelPricing>,
+    threshold: PlanApprovalThreshold,
+    history: Mutex<History>,
+}
+
+impl PlanEstimator {
+    pub fn new() -> Self {
+        Self {
+            analytics: None,
+            tools: None,
+            pricing: None,
+            threshold: PlanApprovalThreshold::default(),
+            history: Mutex::new(History::default()),
+        }
+    }
+
+    /// Add the average observation size of the tools a step mentions.
+    pub fn with_analytics(mut self, analytics: Arc<ToolAnalytics>) -> Self {
+        self.analytics = Some(analytics);
+        self
+    }
+
+    /// Tools the plan may mention, for matching and risk levels.
+    pub fn with_tools(mut self, tools: Arc<dyn ToolRegistry>) -> Self {
+        self.tools = Some(tools);
+        self
+    }
+
+    /// Price estimates with this model's rates.
+    pub fn with_pricing(mut self, pricing: ModelPricing) -> Self {
+        self.pricing = Some(pricing);
+        self
+    }
+
+    /// Require approval for plans above this threshold.
+    pub fn with_approval_threshold(mut self, threshold: PlanApprovalThreshold) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn threshold(&self) -> &PlanApprovalThreshold {
+        &self.threshold
+    }
+
+    /// Estimate the cost of executing `steps`.
+    pub async fn estimate(&self, steps: &[PlanStep]) -> PlanEstimate {
+        let (step_prompt, step_completion, from_history) = {
+            let h = self.history.lock().unwrap();
+            match (h.prompt_tokens + h.completion_tokens).checked_div(h.steps) {
+                Some(per_step) if per_step > 0 => (
+                    h.prompt_tokens / h.steps,
+                    per_step - h.prompt_tokens / h.steps,
+                    true,
+                ),
+                _ => {
+                    let prompt = (DEFAULT_STEP_TOKENS as f64 * DEFAULT_PROMPT_SHARE) as u64;
+                    (prompt, DEFAULT_STEP_TOKENS - prompt, false)
+                }
+            }
+        };
+
+        let known = match &self.tools {
+            Some(tools) => tools.list().await.unwrap_or_default(),
+            None => Vec::new(),
+        };
+        let mut prompt_tokens = step_prompt * steps.len() as u64;
+        let mut mentioned: Vec<String> = Vec::new();
+        let mut risk = ToolRiskLevel::Low;
+        for step in steps {
+            let text = step.description.to_lowercase();
+            for tool in known
+                .iter()
+                .filter(|t| text.contains(&t.name.to_lowercase()))
+            {
+                // Observations are fed back into the next prompt
+                if let Some(stats) = self.analytics.as_ref().and_then(|a| a.stats(&tool.name)) {
+                    prompt_tokens += stats.avg_observation_tokens;
+                }
+                if let Some(tools) = &self.tools {
+                    risk = risk.max(tools.get_risk_level(&tool.name).await);
+                }
+                if !mentioned.contains(&tool.name) {
+                    mentioned.push(tool.name.clone());
+                }
+            }
+        }
+        let completion_tokens = step_completion * steps.len() as u64;
+
+        PlanEstimate {
+            steps: steps.len(),
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens + completion_tokens,
+            cost_usd: self
+                .pricing
+                .as_ref()
+                .map(|p| p.estimate_cost(prompt_tokens, completion_tokens)),
+            tools: mentioned,
+            risk,
+            from_history,
+        }
+    }
+
+    /// Record the usage of a finished plan and the estimate it had.
+    pub fn record_actual(
+        &self,
+        estimate: &PlanEstimate,
+        prompt_tokens: u64,
+        completion_tokens: u64,
+    ) {
+        let actual = prompt_tokens + completion_tokens;
+        let mut h = self.history.lock().unwrap();
+        h.steps += estimate.steps.max(1) as u64;
+        h.prompt_tokens += prompt_tokens;
+        h.completion_tokens += completion_tokens;
+        if estimate.total_tokens > 0 {
+            let ratio = actual as f64 / estimate.total_tokens as f64;
+            h.samples += 1;
+            h.ratio += ratio;
+            h.abs_error += (ratio - 1.0).abs();
+            metrics::histogram!("plan_estimate_ratio").record(ratio);
+        }
+        tracing::debug!(
+            estimated = estimate.total_tokens,
+            actual,
+            "Recorded plan estimate accuracy"
+        );
+    }
+
+    /// Accuracy of the estimates recorded so far.
+    pub fn accuracy(&self) -> EstimateAccuracy {
+        let h = self.history.lock().unwrap();
+        if h.samples == 0 {
+            return EstimateAccuracy::default();
+        }
+        EstimateAccuracy {
+            samples: h.samples,
+            mean_abs_error: h.abs_error / h.samples as f64,
+            mean_ratio: h.ratio / h.samples as f64,
+        }
+    }
+}
+
+impl Default for PlanEstimator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planning::StepStatus;
+
+    fn steps(descriptions: &[&str]) -> Vec<PlanStep> {
+        descriptions
+            .iter()
+            .enumerate()
+            .map(|(i, d)| PlanStep {
+                id: i + 1,
+                description: d.to_string(),
+                status: StepStatus::Pending,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_estimate_learns_from_actuals() {
+        let estimator = PlanEstimator::new();
+        let plan = steps(&["Research", "Write", "Review"]);
+
+        let first = estimator.estimate(&plan).await;
+        assert!(!first.from_history);
+        assert_eq!(first.total_tokens, 3 * DEFAULT_STEP_TOKENS);
+        assert!(first.cost_usd.is_none());
+
+        // The plan took twice as long as estimated
+        estimator.record_actual(&first, 9_000, 3_000);
+        let accuracy = estimator.accuracy();
+        assert_eq!(accuracy.samples, 1);
+        assert!((accuracy.mean_ratio - 2.0).abs() < 1e-9);
+        assert!((accuracy.mean_abs_error - 1.0).abs() < 1e-9);
+
+        let second = estimator.estimate(&plan).await;
+        assert!(second.from_history);
+        assert_eq!(second.prompt_tokens, 9_000);
+        assert_eq!(second.completion_tokens, 3_000);
+    }
+
+    #[test]
+    fn test_threshold_reasons() {
+        let estimate = PlanEstimate {
+            steps: 2,
+            prompt_tokens: 8_000,
+            completion_tokens: 2_000,
+            total_tokens: 10_000,
+            cost_usd: Some(0.5),
+            tools: vec!["shell".into()],
+            risk: ToolRiskLevel::High,
+            from_history: false,
+        };
+        assert!(PlanApprovalThreshold::default()
+            .exceeded_by(&estimate)
+            .is_empty());
+
+        let threshold = PlanApprovalThreshold {
+            tokens: 5_000,
+            cost_usd: 1.0,
+            risk: Some(ToolRiskLevel::High),
+        };
+        let reasons = threshold.exceeded_by(&estimate);
+        assert_eq!(reasons.len(), 2, "{:?}", reasons);
+        assert!(reasons[0].contains("token threshold"));
+        assert!(reasons[1].contains("High-risk"));
+    }
+}
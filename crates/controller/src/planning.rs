@@ -1,20 +1,27 @@
 //! Capability for Plan-and-Solve (Advanced Planning).
 //!
 //! This capability prompts the agent to create a structured plan before execution
-//! and keeps the agent focused on the current step.
+//! and keeps the agent focused on the current step. With a [`PlanEstimator`],
+//! the plan is priced first and sent through the approval gate when the
+//! estimate crosses the configured threshold.
 
 use async_trait::async_trait;
+use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 use crate::capability::AgentCapability;
+use crate::plan_estimate::{PlanEstimate, PlanEstimator};
 use multi_agent_core::{
-    traits::LlmClient,
-    types::{HistoryEntry, Session},
+    traits::{ApprovalGate, LlmClient},
+    types::{AgentResult, ApprovalRequest, ApprovalResponse, HistoryEntry, Session, ToolRiskLevel},
     Error, Result,
 };
 
+/// Tool name shown to reviewers for plan approvals.
+const PLAN_APPROVAL_TOOL: &str = "execute_plan";
+
 /// A step in the execution plan.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlanStep {
@@ -35,6 +42,10 @@ pub enum StepStatus {
 pub struct PlanningCapability {
     llm: Arc<dyn LlmClient>,
     plan: Mutex<Option<Vec<PlanStep>>>,
+    estimator: Option<Arc<PlanEstimator>>,
+    approval_gate: Option<Arc<dyn ApprovalGate>>,
+    /// Estimates of running plans by session, compared with actuals on finish.
+    estimates: DashMap<String, PlanEstimate>,
 }
 
 impl PlanningCapability {
@@ -42,6 +53,70 @@ impl PlanningCapability {
         Self {
             llm,
             plan: Mutex::new(None),
+            estimator: None,
+            approval_gate: None,
+            estimates: DashMap::new(),
+        }
+    }
+
+    /// Estimate each plan's cost before it runs.
+    pub fn with_estimator(mut self, estimator: Arc<PlanEstimator>) -> Self {
+        self.estimator = Some(estimator);
+        self
+    }
+
+    /// Gate for plans whose estimate crosses the estimator's threshold.
+    pub fn with_approval_gate(mut self, gate: Arc<dyn ApprovalGate>) -> Self {
+        self.approval_gate = Some(gate);
+        self
+    }
+
+    /// Ask for approval when `estimate` crosses the threshold.
+    async fn review_estimate(
+        &self,
+        session: &Session,
+        goal: &str,
+        steps: &[PlanStep],
+        estimate: &PlanEstimate,
+        reasons: Vec<String>,
+    ) -> Result<()> {
+        if reasons.is_empty() {
+            return Ok(());
+        }
+        let summary = reasons.join("; ");
+        let Some(gate) = &self.approval_gate else {
+            return Err(Error::governance(format!(
+                "Plan requires approval ({}), but no approval gate is configured",
+                summary
+            )));
+        };
+        tracing::info!(session_id = %session.id, reasons = %summary, "Plan estimate requires approval");
+
+        let request = ApprovalRequest {
+            request_id: uuid::Uuid::new_v4().to_string(),
+            session_id: session.id.clone(),
+            tool_name: PLAN_APPROVAL_TOOL.to_string(),
+            args: serde_json::json!({ "plan": steps, "estimate": estimate }),
+            risk_level: estimate.risk.max(ToolRiskLevel::Medium),
+            context: format!("Plan estimate: {}. Session Goal: {}", summary, goal),
+            timeout_secs: None,
+            nonce: uuid::Uuid::new_v4().to_string(),
+            expires_at: chrono::Utc::now().timestamp() + 300,
+            mandatory: false,
+        };
+        match gate.request_approval(&request).await? {
+            ApprovalResponse::Approved { reason_code, .. }
+            | ApprovalResponse::Modified { reason_code, .. } => {
+                tracing::info!(session_id = %session.id, reason_code = %reason_code, "Plan APPROVED");
+                Ok(())
+            }
+            ApprovalResponse::Denied {
+                reason,
+                reason_code,
+            } => Err(Error::governance(format!(
+                "Plan was DENIED by human reviewer ({}): {}",
+                reason_code, reason
+            ))),
         }
     }
 
@@ -133,9 +208,28 @@ impl AgentCapability for PlanningCapability {
         tracing::info!("Generating plan for goal: {}", goal);
         let steps = self.generate_plan(goal).await?;
 
-        let plan_str = Self::format_plan(&steps);
+        let mut plan_str = Self::format_plan(&steps);
         tracing::info!("Generated Plan:\n{}", plan_str);
 
+        if let Some(estimator) = &self.estimator {
+            let estimate = estimator.estimate(&steps).await;
+            tracing::info!(
+                session_id = %session.id,
+                tokens = estimate.total_tokens,
+                cost_usd = ?estimate.cost_usd,
+                risk = ?estimate.risk,
+                "Estimated plan cost"
+            );
+            let reasons = estimator.threshold().exceeded_by(&estimate);
+            self.review_estimate(session, goal, &steps, &estimate, reasons)
+                .await?;
+            plan_str.push_str(&format!(
+                "Estimated budget: ~{} tokens\n",
+                estimate.total_tokens
+            ));
+            self.estimates.insert(session.id.clone(), estimate);
+        }
+
         // Store plan
         *self.plan.lock().await = Some(steps);
 
@@ -182,6 +276,19 @@ impl AgentCapability for PlanningCapability {
         Ok(())
     }
 
+    async fn on_finish(&self, session: &mut Session, _result: &AgentResult) -> Result<()> {
+        if let (Some(estimator), Some((_, estimate))) =
+            (&self.estimator, self.estimates.remove(&session.id))
+        {
+            estimator.record_actual(
+                &estimate,
+                session.token_usage.prompt_tokens,
+                session.token_usage.completion_tokens,
+            );
+        }
+        Ok(())
+    }
+
     // TODO: Implement parsing logic to detect when a step is done (e.g., "STEP_COMPLETE")
     // For now, we rely on the LLM to follow the plan implicitly,
     // or we can add a tool `complete_step(id)`?
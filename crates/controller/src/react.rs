@@ -16,17 +16,26 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use multi_agent_core::{
+    config::DeadlockConfig,
+    prompts::{keys as prompt_keys, PromptRegistry},
     traits::{
-        ApprovalGate, ChatMessage, Controller, LlmClient, LlmResponse, SessionStore, ToolRegistry,
+        ApprovalGate, ChatMessage, Controller, HumanInputChannel, LlmClient, ProviderParams,
+        SessionStore, ToolRegistry,
     },
     types::{
-        AgentResult, ApprovalRequest, ApprovalResponse, HistoryEntry, Session, SessionStatus,
-        TaskState, TokenUsage, ToolCallInfo, ToolRiskLevel, UserIntent,
+        AgentResult, AgentStreamEvent, ApprovalRequest, ApprovalResponse, ArtifactOwner,
+        HistoryEntry, RequestContext, Session, SessionStatus, TaskState, TokenUsage, ToolCallInfo,
+        ToolRiskLevel, UserIntent,
     },
     Error, Result,
 };
 
+use multi_agent_governance::{ApprovalMode, ApprovalRequirement, AuditContext};
+use multi_agent_model_gateway::{ModelDecision, TaskClass, TokenCounter};
+
 use crate::capability::AgentCapability;
+use crate::deadlock::Recovery;
+use crate::dry_run::{self, DryRunReport};
 
 // v0.3: Security Integration
 // (Guardrail unused in pure Controller struct if verified via capabilities)
@@ -43,6 +52,13 @@ pub struct ReActConfig {
     pub persist_state: bool,
     /// Temperature for LLM calls.
     pub temperature: f32,
+    /// Re-prompts allowed per iteration when the model emits a malformed action.
+    pub max_repair_attempts: usize,
+    /// Provider-specific parameters sent with every LLM call (`top_p`, `stop`,
+    /// `seed`, `reasoning_effort`, ...). Unsupported ones are dropped by the client.
+    pub provider_params: ProviderParams,
+    /// Recovery after consecutive approval denials.
+    pub deadlock: DeadlockConfig,
 }
 
 impl Default for ReActConfig {
@@ -52,6 +68,9 @@ impl Default for ReActConfig {
             default_budget: 50_000,
             persist_state: true,
             temperature: 0.7,
+            max_repair_attempts: 2,
+            provider_params: ProviderParams::new(),
+            deadlock: DeadlockConfig::default(),
         }
     }
 }
@@ -59,6 +78,9 @@ impl Default for ReActConfig {
 // Use the new parser module
 use crate::parser::ReActAction;
 
+/// Tokens kept free for the reply when checking the context window.
+const RESERVED_OUTPUT_TOKENS: usize = 1024;
+
 /// ReAct controller for executing complex tasks.
 pub struct ReActController {
     /// Configuration.
@@ -78,6 +100,16 @@ pub struct ReActController {
         Option<Arc<tokio::sync::RwLock<multi_agent_governance::PolicyEngine>>>,
     /// Event emitter for structured events.
     pub(crate) event_emitter: Option<Arc<dyn multi_agent_core::traits::EventEmitter>>,
+    /// Per-iteration model routing; overrides `llm` when set.
+    pub(crate) model_selector: Option<Arc<multi_agent_model_gateway::AdaptiveModelSelector>>,
+    /// Tokenizer for pre-call budget and context-window checks.
+    pub(crate) token_counter: Option<Arc<TokenCounter>>,
+    /// Daily usage ledger for cost alerts and digests.
+    pub(crate) usage_ledger: Option<Arc<multi_agent_model_gateway::UsageLedger>>,
+    /// Channel for asking the human, used to recover from deadlocks.
+    pub(crate) human_input: Option<Arc<dyn HumanInputChannel>>,
+    /// Prompts and messages, localized by the request locale.
+    pub(crate) prompts: Arc<PromptRegistry>,
 }
 
 impl ReActController {
@@ -97,6 +129,11 @@ impl ReActController {
             approval_gate: None,
             event_emitter: None,
             policy_engine: None,
+            model_selector: None,
+            token_counter: None,
+            usage_ledger: None,
+            human_input: None,
+            prompts: Arc::new(PromptRegistry::default()),
         }
     }
 
@@ -106,6 +143,8 @@ impl ReActController {
             id: Uuid::new_v4().to_string(),
             trace_id: trace_id.to_string(),
             user_id,
+            workspace_id: None,
+            dry_run: false,
             status: SessionStatus::Running,
             history: vec![HistoryEntry {
                 role: "system".to_string(),
@@ -129,35 +168,9 @@ impl ReActController {
     /// Build the system prompt for the agent.
     fn build_system_prompt(&self, goal: &str) -> String {
         let tools_description = self.get_tools_description();
-
-        format!(
-            r#"You are an AI assistant that uses the ReAct (Reasoning + Acting) pattern.
-
-GOAL: {goal}
-
-AVAILABLE TOOLS:
-{tools_description}
-
-INSTRUCTIONS:
-1. Think step by step about what needs to be done
-2. Use tools when needed by responding with ACTION
-3. After receiving tool results, continue reasoning
-4. When done, provide your FINAL ANSWER
-
-RESPONSE FORMAT:
-Use exactly one of these formats in each response:
-
-For thinking/reasoning:
-THOUGHT: <your reasoning here>
-
-For tool calls:
-ACTION: <tool_name>
-ARGS: <json arguments>
-
-For final answer (when task is complete):
-FINAL ANSWER: <your complete answer>
-
-Always think before acting. Be concise and focused on the goal."#
+        self.prompts.render_current(
+            prompt_keys::REACT_SYSTEM,
+            &[("goal", goal), ("tools", &tools_description)],
         )
     }
 
@@ -165,7 +178,8 @@ Always think before acting. Be concise and focused on the goal."#
     fn get_tools_description(&self) -> String {
         // For the system prompt, we return a placeholder since we can't call async here.
         // The actual tools list is fetched async when executing.
-        "Tools will be loaded when execution starts.".to_string()
+        self.prompts
+            .render_current(prompt_keys::REACT_TOOLS_PENDING, &[])
     }
 
     /// Build chat messages from session history (static version for capabilities).
@@ -182,13 +196,27 @@ Always think before acting. Be concise and focused on the goal."#
     }
 
     /// Build chat messages from session history.
-    fn build_messages(&self, session: &Session) -> Vec<ChatMessage> {
+    pub(crate) fn build_messages(&self, session: &Session) -> Vec<ChatMessage> {
         Self::build_messages_static(session)
     }
 
+    /// Capabilities not switched off by a feature flag for the current request.
+    fn active_capabilities(&self) -> Vec<Arc<dyn AgentCapability>> {
+        self.capabilities
+            .iter()
+            .filter(|cap| !cap.feature_flag().is_some_and(RequestContext::flag_off))
+            .cloned()
+            .collect()
+    }
+
     /// Parse the LLM response to extract action.
     fn parse_action(&self, response: &str) -> ReActAction {
-        crate::parser::ActionParser::new(self.capabilities.clone()).parse(response)
+        crate::parser::ActionParser::new(self.active_capabilities()).parse(response)
+    }
+
+    /// Parse error for a malformed action, if any.
+    fn diagnose_action(&self, response: &str) -> Option<String> {
+        crate::parser::ActionParser::new(self.active_capabilities()).diagnose(response)
     }
 
     /// Execute a single ReAct iteration with LLM.
@@ -197,11 +225,6 @@ Always think before acting. Be concise and focused on the goal."#
         session: &mut Session,
         iteration: usize,
     ) -> Result<Option<AgentResult>> {
-        let llm = self
-            .llm
-            .as_ref()
-            .ok_or_else(|| Error::controller("LLM client not configured"))?;
-
         tracing::info!(
             session_id = %session.id,
             iteration = iteration,
@@ -210,22 +233,135 @@ Always think before acting. Be concise and focused on the goal."#
         );
 
         // v0.3: Capabilities On-Pre-Reasoning Hook (Compression, Security, etc.)
-        for cap in &self.capabilities {
+        for cap in self.active_capabilities() {
             cap.on_pre_reasoning(session)
                 .await
                 .map_err(|e| Error::controller(e.to_string()))?;
         }
 
-        let messages = self.build_messages(session); // Rebuild messages after potential compression
+        let mut messages = self.build_messages(session); // Rebuild messages after potential compression
+
+        if let Some(counter) = &self.token_counter {
+            messages = self
+                .prepare_context(session, counter, self.llm.as_deref())
+                .await?;
+        }
 
-        // Call LLM with (possibly compressed) messages
-        let response: LlmResponse = llm.chat(&messages).await?;
+        // A capability may take over reasoning (e.g., speculative drafting)
+        let mut supplied = None;
+        for cap in self.active_capabilities() {
+            if let Some(response) = cap.on_reasoning(session, &messages).await? {
+                supplied = Some(response);
+                break;
+            }
+        }
+
+        // Call LLM with (possibly compressed) messages; the selector's
+        // providers serve the call when any is available
+        let selector = self
+            .model_selector
+            .as_ref()
+            .filter(|s| self.llm.is_none() || s.has_available());
+        let (mut response, llm, model, messages) = match (supplied, selector) {
+            (Some(response), _) => (
+                response,
+                self.llm.clone(),
+                "capability".to_string(),
+                messages,
+            ),
+            (None, Some(selector)) => {
+                let class = TaskClass::classify(&messages);
+                let (llm, decision) = selector.select_for_class(class)?;
+                let llm: Arc<dyn LlmClient> = Arc::from(llm);
+                let messages = match self.token_counter {
+                    Some(_) => messages,
+                    None => {
+                        let counter = selector.token_counter(&decision.model);
+                        self.prepare_context(session, &counter, Some(llm.as_ref()))
+                            .await?
+                    }
+                };
+                self.emit_model_selected(session, iteration, &decision)
+                    .await;
+                let response = llm
+                    .chat_with_params(&messages, &self.config.provider_params)
+                    .await;
+                let success = match &response {
+                    Ok(r) => {
+                        Self::is_productive_response(&self.parse_action(&r.content), &r.content)
+                    }
+                    Err(_) => false,
+                };
+                selector.record_outcome(class, &decision.model, success);
+                (response?, Some(llm), decision.model, messages)
+            }
+            (None, None) => {
+                let llm = self
+                    .llm
+                    .clone()
+                    .ok_or_else(|| Error::controller("LLM client not configured"))?;
+                let response = llm
+                    .chat_with_params(&messages, &self.config.provider_params)
+                    .await?;
+                (response, Some(llm), "default".to_string(), messages)
+            }
+        };
+
+        // Re-prompt with the specific parse error when an action is malformed
+        let tokens_before = session.token_usage.total_tokens;
+        let mut attempts = 0;
+        while let Some(parse_error) = self.diagnose_action(&response.content) {
+            metrics::counter!("llm_malformed_responses_total", "model" => model.clone())
+                .increment(1);
+            let Some(llm) = llm
+                .as_ref()
+                .filter(|_| attempts < self.config.max_repair_attempts)
+            else {
+                tracing::warn!(model = %model, error = %parse_error, "Malformed action left unrepaired");
+                break;
+            };
+            attempts += 1;
+            tracing::info!(model = %model, attempt = attempts, error = %parse_error, "Re-prompting after malformed action");
+            session.token_usage.add(
+                response.usage.prompt_tokens,
+                response.usage.completion_tokens,
+            );
+            let mut repair = messages.clone();
+            repair.push(ChatMessage {
+                role: "assistant".to_string(),
+                content: response.content.clone(),
+                tool_calls: None,
+            });
+            repair.push(ChatMessage {
+                role: "user".to_string(),
+                content: format!(
+                    "Your last response could not be parsed: {}.\n\
+                     Reply again with exactly one action, either:\n\
+                     ACTION: <tool name>\nARGS: <JSON object on one line>\n\
+                     or\nFINAL ANSWER: <answer>",
+                    parse_error
+                ),
+                tool_calls: None,
+            });
+            response = llm
+                .chat_with_params(&repair, &self.config.provider_params)
+                .await?;
+        }
 
         // Update token usage
         session.token_usage.add(
             response.usage.prompt_tokens,
             response.usage.completion_tokens,
         );
+        if let Some(ledger) = &self.usage_ledger {
+            ledger.record_session(
+                &session.id,
+                session
+                    .token_usage
+                    .total_tokens
+                    .saturating_sub(tokens_before),
+            );
+        }
 
         tracing::debug!(
             response_len = response.content.len(),
@@ -243,11 +379,23 @@ Always think before acting. Be concise and focused on the goal."#
 
         // Parse and execute action
         let action = self.parse_action(&response.content);
+        let thought = match &action {
+            ReActAction::Think(text) => Some(
+                text.strip_prefix("THOUGHT:")
+                    .unwrap_or(text)
+                    .trim()
+                    .to_string(),
+            ),
+            _ => crate::parser::ActionParser::thought(&response.content),
+        };
+        if let Some(content) = thought.filter(|t| !t.is_empty()) {
+            AgentStreamEvent::Thought { content }.emit();
+        }
 
         match action {
             ReActAction::FinalAnswer(ref answer) => {
                 // Check capabilities on execution (Security Output check)
-                for cap in &self.capabilities {
+                for cap in self.active_capabilities() {
                     if let Some(result) = cap.on_execute(&action, session).await? {
                         // If a capability interrupts/handles FinalAnswer (e.g., blocks it), return that result
                         // Standard security cap returns Err on violation, keeping this flow simple.
@@ -257,10 +405,22 @@ Always think before acting. Be concise and focused on the goal."#
                     }
                 }
 
-                let final_result = AgentResult::Text(answer.clone());
+                let final_result = AgentResult::from_final_answer(answer);
+
+                // A dry-run answer rests on simulated observations; report the
+                // plan instead, and keep it out of memory and other hooks.
+                if session.dry_run {
+                    let report = DryRunReport::from_session(session, &final_result);
+                    tracing::info!(
+                        actions = report.actions.len(),
+                        simulated = report.simulated,
+                        "Dry run completed"
+                    );
+                    return Ok(Some(report.into_result()));
+                }
 
                 // Run on_finish hooks (e.g., knowledge summarization)
-                for cap in &self.capabilities {
+                for cap in self.active_capabilities() {
                     if let Err(e) = cap.on_finish(session, &final_result).await {
                         tracing::warn!(
                             capability = cap.name(),
@@ -293,7 +453,7 @@ Always think before acting. Be concise and focused on the goal."#
                 });
 
                 // v0.4: Post-Execute Hook
-                for cap in &self.capabilities {
+                for cap in self.active_capabilities() {
                     cap.on_post_execute(session)
                         .await
                         .map_err(|e| Error::controller(e.to_string()))?;
@@ -304,10 +464,15 @@ Always think before acting. Be concise and focused on the goal."#
 
             // Fallback: Check custom capability actions
             _ => {
-                for cap in &self.capabilities {
+                for cap in self.active_capabilities() {
                     if let Some(result) = cap.on_execute(&action, session).await? {
                         // Add observation to history if returned
                         if let AgentResult::Text(observation) = &result {
+                            AgentStreamEvent::Observation {
+                                tool: None,
+                                content: observation.clone(),
+                            }
+                            .emit();
                             session.history.push(HistoryEntry {
                                 role: "user".to_string(),
                                 content: Arc::new(format!("OBSERVATION: {}", observation)),
@@ -321,7 +486,7 @@ Always think before acting. Be concise and focused on the goal."#
                         }
 
                         // v0.4: Post-Execute Hook
-                        for cap in &self.capabilities {
+                        for cap in self.active_capabilities() {
                             cap.on_post_execute(session)
                                 .await
                                 .map_err(|e| Error::controller(e.to_string()))?;
@@ -336,13 +501,104 @@ Always think before acting. Be concise and focused on the goal."#
         }
     }
 
-    /// Execute iteration (mock if no LLM, real if LLM configured).
+    /// Shrink history to fit the model's context window, then run the
+    /// preflight check. Returns the messages to send.
+    async fn prepare_context(
+        &self,
+        session: &mut Session,
+        counter: &TokenCounter,
+        summarizer: Option<&dyn LlmClient>,
+    ) -> Result<Vec<ChatMessage>> {
+        let report = crate::context_window::fit_to_window(
+            session,
+            counter,
+            summarizer,
+            RESERVED_OUTPUT_TOKENS,
+        )
+        .await?;
+        if report.changed() {
+            tracing::info!(
+                session_id = %session.id,
+                before = report.prompt_tokens_before,
+                after = report.prompt_tokens_after,
+                observations_summarized = report.observations_summarized,
+                messages_truncated = report.messages_truncated,
+                "History shrunk to fit context window"
+            );
+        }
+        let messages = self.build_messages(session);
+        self.preflight(session, &messages, counter).await?;
+        Ok(messages)
+    }
+
+    /// Estimate the prompt before sending it and stop the session if it
+    /// would overflow the context window or the remaining token budget.
+    async fn preflight(
+        &self,
+        session: &mut Session,
+        messages: &[ChatMessage],
+        counter: &TokenCounter,
+    ) -> Result<()> {
+        let remaining = session.token_usage.remaining();
+        match counter.preflight(messages, RESERVED_OUTPUT_TOKENS, Some(remaining)) {
+            Ok(estimate) => {
+                tracing::debug!(
+                    prompt_tokens = estimate.prompt_tokens,
+                    context_window = estimate.context_window,
+                    remaining_budget = remaining,
+                    "Preflight token check passed"
+                );
+                Ok(())
+            }
+            Err(e) => {
+                tracing::warn!(session_id = %session.id, error = %e, "Preflight token check failed");
+                session.status = SessionStatus::Failed;
+                self.persist_session(session).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Whether a response moved the task forward. Unstructured text falls
+    /// back to a Think action and counts against the model.
+    fn is_productive_response(action: &ReActAction, content: &str) -> bool {
+        match action {
+            ReActAction::Think(_) => content.trim_start().starts_with("THOUGHT:"),
+            _ => true,
+        }
+    }
+
+    /// Emit MODEL_SELECTED for an adaptive routing decision.
+    async fn emit_model_selected(
+        &self,
+        session: &Session,
+        iteration: usize,
+        decision: &ModelDecision,
+    ) {
+        if let Some(emitter) = &self.event_emitter {
+            use multi_agent_core::events::{EventEnvelope, EventType};
+            let mut payload = serde_json::to_value(decision).unwrap_or_default();
+            payload["iteration"] = serde_json::json!(iteration);
+            let event = EventEnvelope::new(EventType::ModelSelected, payload)
+                .with_trace(&session.trace_id)
+                .with_session(&session.id)
+                .with_actor(session.user_id.as_deref().unwrap_or("system"));
+            emitter.emit(event).await;
+        }
+    }
+
+    /// Execute iteration (mock if no LLM, real if an LLM or a selectable
+    /// provider is configured).
     async fn execute_iteration(
         &self,
         session: &mut Session,
         iteration: usize,
     ) -> Result<Option<AgentResult>> {
-        if self.llm.is_some() {
+        let selectable = self
+            .model_selector
+            .as_ref()
+            .is_some_and(|s| s.has_available());
+        if self.llm.is_some() || selectable {
             self.execute_iteration_with_llm(session, iteration).await
         } else {
             // Mock implementation for testing without LLM
@@ -363,7 +619,7 @@ Always think before acting. Be concise and focused on the goal."#
         }
     }
 
-    async fn persist_session(&self, session: &Session) {
+    pub(crate) async fn persist_session(&self, session: &Session) {
         if self.config.persist_state {
             if let Some(store) = &self.session_store {
                 if let Err(e) = store.save(session).await {
@@ -374,7 +630,7 @@ Always think before acting. Be concise and focused on the goal."#
     }
 
     async fn validate_fast_action_security(&self, args: &serde_json::Value) -> Result<()> {
-        for cap in &self.capabilities {
+        for cap in self.active_capabilities() {
             if cap.name() == "security_guardrails" {
                 let mut temp_session =
                     self.create_session("fast_action_check", "temp-trace-id", None);
@@ -399,19 +655,26 @@ Always think before acting. Be concise and focused on the goal."#
         args: serde_json::Value,
     ) -> Result<Option<AgentResult>> {
         tracing::info!(tool = %name, "Executing tool call");
+        AgentStreamEvent::ToolCall {
+            tool: name.clone(),
+            args: args.clone(),
+        }
+        .emit();
 
         // Emit TOOL_CALL_PROPOSED
         if let Some(emitter) = &self.event_emitter {
-            use multi_agent_core::events::{EventEnvelope, EventType};
+            use multi_agent_core::events::{EventEnvelope, EventType, ToolCallProposedPayload};
             let event = EventEnvelope::new(
                 EventType::ToolCallProposed,
-                serde_json::json!({
-                    "tool_name": name,
-                    "args": args
-                }),
+                serde_json::to_value(ToolCallProposedPayload {
+                    tool_name: name.clone(),
+                    args: args.clone(),
+                })
+                .unwrap_or_default(),
             )
             .with_trace(&session.trace_id)
-            .with_session(&session.id);
+            .with_session(&session.id)
+            .with_actor(session.user_id.as_deref().unwrap_or("system"));
             emitter.emit(event).await;
         }
 
@@ -421,16 +684,19 @@ Always think before acting. Be concise and focused on the goal."#
         let mut effective_args = args.clone();
 
         // 1. Evaluate Policy
-        let (risk, risk_score, reason, matched_rule, policy_version) =
+        let (risk, risk_score, reason, matched_rule, policy_version, approval, shadow) =
             if let Some(ref engine) = self.policy_engine {
                 let engine = engine.read().await;
                 let decision = engine.evaluate(&name, &effective_args);
+                let approval = engine.approval_requirement(&name, session.workspace_id.as_deref());
                 (
                     decision.risk_level,
                     decision.risk_score,
                     decision.reason,
                     decision.matched_rule,
                     decision.policy_version,
+                    approval,
+                    decision.shadow,
                 )
             } else {
                 // Fallback to legacy behavior if no engine is configured
@@ -445,6 +711,8 @@ Always think before acting. Be concise and focused on the goal."#
                     "Default policy (no engine)".to_string(),
                     None,
                     "0.0.0".to_string(),
+                    ApprovalRequirement::default(),
+                    Vec::new(),
                 )
             };
 
@@ -460,19 +728,75 @@ Always think before acting. Be concise and focused on the goal."#
                     matched_rule,
                     reason: reason.clone(),
                     policy_version,
+                    shadow: shadow
+                        .iter()
+                        .filter_map(|m| serde_json::to_value(m).ok())
+                        .collect(),
                 })
                 .unwrap_or_default(),
             )
             .with_trace(&session.trace_id)
-            .with_session(&session.id);
+            .with_session(&session.id)
+            .with_actor(session.user_id.as_deref().unwrap_or("system"));
             emitter.emit(event).await;
         }
 
         // 3. Approval Check
+        // Tools may ask to be approved on every call; the policy can force
+        // approval or lower the risk threshold per tool and per workspace.
+        let tool_requires = match self.tools {
+            Some(ref tools) => tools.requires_approval_for(&name, &effective_args).await,
+            None => false,
+        };
+
+        // Dry runs simulate mutating calls; nothing runs, so nothing to approve.
+        if session.dry_run {
+            let declared = match self.tools {
+                Some(ref tools) => tools.get_risk_level(&name).await,
+                None => ToolRiskLevel::Low,
+            };
+            let risk = risk.max(declared);
+            if dry_run::is_simulated(risk, tool_requires) {
+                tracing::info!(tool = %name, risk = ?risk, "Dry run: simulating tool call");
+                dry_run::record_simulated_call(session, &name, effective_args, risk);
+                return Ok(None);
+            }
+        }
+        // `Never` only turns off the risk threshold; a tool's own requirement stands.
+        let always_approve = match approval.mode {
+            ApprovalMode::Always => true,
+            ApprovalMode::Never | ApprovalMode::Threshold => tool_requires,
+        };
+        if always_approve && self.approval_gate.is_none() {
+            tracing::warn!(tool = %name, "Tool requires approval but no approval gate is configured");
+            let observation = self
+                .prompts
+                .render_current(prompt_keys::APPROVAL_UNAVAILABLE, &[("tool", &name)]);
+            AgentStreamEvent::Observation {
+                tool: Some(name.clone()),
+                content: observation.clone(),
+            }
+            .emit();
+            session.history.push(HistoryEntry {
+                role: "user".to_string(),
+                content: Arc::new(format!("OBSERVATION: {}", observation)),
+                tool_call: Some(ToolCallInfo {
+                    name: name.clone(),
+                    arguments: effective_args,
+                    result: Some(Arc::new(observation.clone())),
+                }),
+                timestamp: chrono_timestamp(),
+            });
+            if let Some(ref mut task_state) = session.task_state {
+                task_state.observations.push(Arc::new(observation));
+            }
+            return Ok(None);
+        }
+
         if let Some(ref gate) = self.approval_gate {
-            let threshold_score = 50; // TODO: Make configurable via policy thresholds
+            let threshold_score = approval.threshold;
 
-            if risk_score >= threshold_score {
+            if approval.requires_approval(risk_score, tool_requires) {
                 tracing::info!(
                     tool = %name,
                     risk_score = risk_score,
@@ -487,14 +811,19 @@ Always think before acting. Be concise and focused on the goal."#
                     tool_name: name.clone(),
                     args: effective_args.clone(),
                     risk_level: risk,
-                    context: format!(
-                        "Policy Reason: {}. Session Goal: {}",
-                        reason,
-                        session
-                            .task_state
-                            .as_ref()
-                            .map(|t| t.goal.clone())
-                            .unwrap_or_default()
+                    context: self.prompts.render_current(
+                        prompt_keys::APPROVAL_CONTEXT,
+                        &[
+                            ("reason", &reason),
+                            (
+                                "goal",
+                                session
+                                    .task_state
+                                    .as_ref()
+                                    .map(|t| t.goal.as_str())
+                                    .unwrap_or_default(),
+                            ),
+                        ],
                     ),
                     timeout_secs: None,
                     nonce: uuid::Uuid::new_v4().to_string(),
@@ -503,17 +832,20 @@ Always think before acting. Be concise and focused on the goal."#
                         .unwrap()
                         .as_secs() as i64
                         + 300, // 5 minutes default expiry
+                    mandatory: tool_requires,
                 };
 
                 match gate.request_approval(&approval_req).await? {
                     ApprovalResponse::Approved {
                         reason,
                         reason_code,
+                        scope,
                     } => {
                         tracing::info!(
                             tool = %name,
                             reason = ?reason,
                             reason_code = %reason_code,
+                            scope = ?scope,
                             "Tool call APPROVED by human"
                         );
                         // Reset deadlock counter
@@ -537,10 +869,15 @@ Always think before acting. Be concise and focused on the goal."#
                             task_state.consecutive_rejections += 1;
                         }
 
-                        let observation = format!(
-                            "Tool '{}' was DENIED by human reviewer ({}): {}",
-                            name, reason_code, reason
+                        let observation = self.prompts.render_current(
+                            prompt_keys::APPROVAL_DENIED,
+                            &[("tool", &name), ("code", &reason_code), ("reason", &reason)],
                         );
+                        AgentStreamEvent::Observation {
+                            tool: Some(name.clone()),
+                            content: observation.clone(),
+                        }
+                        .emit();
                         session.history.push(HistoryEntry {
                             role: "user".to_string(),
                             content: Arc::new(format!("OBSERVATION: {}", observation)),
@@ -583,23 +920,53 @@ Always think before acting. Be concise and focused on the goal."#
         let observation = if let Some(ref tools) = self.tools {
             // Emit TOOL_EXEC_STARTED
             if let Some(emitter) = &self.event_emitter {
-                use multi_agent_core::events::{EventEnvelope, EventType};
+                use multi_agent_core::events::{EventEnvelope, EventType, ToolExecStartedPayload};
                 let event = EventEnvelope::new(
                     EventType::ToolExecStarted,
-                    serde_json::json!({ "tool_name": name }),
+                    serde_json::to_value(ToolExecStartedPayload {
+                        tool_name: name.clone(),
+                    })
+                    .unwrap_or_default(),
                 )
                 .with_trace(&session.trace_id)
-                .with_session(&session.id);
+                .with_session(&session.id)
+                .with_actor(session.user_id.as_deref().unwrap_or("system"));
                 emitter.emit(event).await;
             }
 
+            // Tools that wait for the human pause the session for the duration,
+            // so observers see it is blocked on input rather than working.
+            let awaits_input = tools.awaits_human_input(&name).await;
+            if awaits_input {
+                session.status = SessionStatus::Paused;
+                self.persist_session(session).await;
+            }
+
             let start_time = std::time::Instant::now();
-            let result = tools.execute(&name, effective_args.clone()).await;
+            // Artifacts saved by the tool are attributed to this session's user.
+            let owner = ArtifactOwner::new(session.user_id.clone(), Some(session.id.clone()));
+            // Audit entries written by the tool carry this session's trace.
+            let audit_context = AuditContext {
+                user_id: session.user_id.clone(),
+                trace_id: Some(session.trace_id.clone()),
+                session_id: Some(session.id.clone()),
+            };
+            let result = owner
+                .scope(audit_context.scope(tools.execute(&name, effective_args.clone())))
+                .await;
             let duration = start_time.elapsed().as_millis() as u64;
+            if let Some(ledger) = &self.usage_ledger {
+                ledger.record_tool(&name);
+            }
+
+            if awaits_input {
+                session.status = SessionStatus::Running;
+                self.persist_session(session).await;
+            }
 
             // Emit TOOL_EXEC_FINISHED
             if let Some(emitter) = &self.event_emitter {
-                use multi_agent_core::events::{EventEnvelope, EventType};
+                use multi_agent_core::events::{EventEnvelope, EventType, ToolExecPayload};
                 let (success, output) = match &result {
                     Ok(o) => (o.success, o.content.clone()),
                     Err(e) => (false, e.to_string()),
@@ -607,15 +974,20 @@ Always think before acting. Be concise and focused on the goal."#
 
                 let event = EventEnvelope::new(
                     EventType::ToolExecFinished,
-                    serde_json::json!({
-                        "tool_name": name,
-                        "success": success,
-                        "duration_ms": duration,
-                        "output_len": output.len()
-                    }),
+                    serde_json::to_value(ToolExecPayload {
+                        tool_name: name.clone(),
+                        input: None,
+                        output: None,
+                        duration_ms: Some(duration),
+                        error: None,
+                        success: Some(success),
+                        output_len: Some(output.len()),
+                    })
+                    .unwrap_or_default(),
                 )
                 .with_trace(&session.trace_id)
-                .with_session(&session.id);
+                .with_session(&session.id)
+                .with_actor(session.user_id.as_deref().unwrap_or("system"));
                 emitter.emit(event).await;
             }
 
@@ -632,6 +1004,11 @@ Always think before acting. Be concise and focused on the goal."#
         } else {
             format!("Tool '{}' not available (no tools configured)", name)
         };
+        AgentStreamEvent::Observation {
+            tool: Some(name.clone()),
+            content: observation.clone(),
+        }
+        .emit();
 
         session.history.push(HistoryEntry {
             role: "user".to_string(),
@@ -648,7 +1025,7 @@ Always think before acting. Be concise and focused on the goal."#
             task_state.observations.push(Arc::new(observation));
         }
 
-        for cap in &self.capabilities {
+        for cap in self.active_capabilities() {
             cap.on_post_execute(session)
                 .await
                 .map_err(|e| Error::controller(e.to_string()))?;
@@ -658,7 +1035,30 @@ Always think before acting. Be concise and focused on the goal."#
     }
 
     /// Run the ReAct loop for a session.
+    ///
+    /// LLM calls inside are attributed to the session's workspace, so
+    /// provider spend caps can exempt privileged workspaces.
     async fn run_loop(&self, session: &mut Session) -> Result<AgentResult> {
+        let workspace = session.workspace_id.clone();
+        let result = multi_agent_model_gateway::spend_caps::in_workspace(
+            workspace,
+            self.run_iterations(session),
+        )
+        .await;
+
+        // Running and paused sessions may still resume.
+        if matches!(
+            session.status,
+            SessionStatus::Completed | SessionStatus::Degraded | SessionStatus::Failed
+        ) {
+            if let Some(gate) = &self.approval_gate {
+                gate.end_session(&session.id).await;
+            }
+        }
+        result
+    }
+
+    async fn run_iterations(&self, session: &mut Session) -> Result<AgentResult> {
         let start_iteration = session
             .task_state
             .as_ref()
@@ -673,7 +1073,7 @@ Always think before acting. Be concise and focused on the goal."#
 
         // Run on_start capabilities for fresh sessions (not resuming)
         if start_iteration == 0 {
-            for cap in &self.capabilities {
+            for cap in self.active_capabilities() {
                 cap.on_start(session)
                     .await
                     .map_err(|e| Error::controller(e.to_string()))?;
@@ -696,15 +1096,35 @@ Always think before acting. Be concise and focused on the goal."#
                 });
             }
 
-            // 2. Check Deadlock Circuit Breaker
-            if let Some(ref task_state) = session.task_state {
-                if task_state.consecutive_rejections >= 3 {
-                    tracing::error!(session_id = %session.id, "Deadlock detected: too many consecutive rejections");
-                    session.status = SessionStatus::Failed;
-                    self.persist_session(session).await;
-                    return Err(Error::controller(
-                        "Deadlock: Too many consecutive human rejections (3). Terminating session.",
-                    ));
+            // 2. Stop at the caller's deadline, leaving the session resumable
+            if RequestContext::current().is_some_and(|context| context.deadline_passed()) {
+                tracing::warn!(session_id = %session.id, iteration, "Request deadline passed");
+                session.status = SessionStatus::Paused;
+                self.persist_session(session).await;
+                return Err(Error::Timeout(self.prompts.render_current(
+                    prompt_keys::ERROR_DEADLINE,
+                    &[
+                        ("iterations", &iteration.to_string()),
+                        ("session", &session.id),
+                    ],
+                )));
+            }
+
+            // 3. Check Deadlock Circuit Breaker
+            if self.is_deadlocked(session) {
+                match self.recover_deadlock(session).await {
+                    Ok(Recovery::Resume) => self.persist_session(session).await,
+                    Ok(Recovery::Report(result)) => {
+                        session.updated_at = chrono_timestamp();
+                        session.status = SessionStatus::Degraded;
+                        self.persist_session(session).await;
+                        return Ok(result);
+                    }
+                    Err(e) => {
+                        session.status = SessionStatus::Failed;
+                        self.persist_session(session).await;
+                        return Err(e);
+                    }
                 }
             }
 
@@ -746,7 +1166,7 @@ impl Controller for ReActController {
             UserIntent::FastAction {
                 tool_name,
                 args,
-                user_id: _,
+                user_id,
             } => {
                 self.validate_fast_action_security(&args).await?;
 
@@ -754,7 +1174,11 @@ impl Controller for ReActController {
                 tracing::info!(tool = %tool_name, "Fast path execution");
 
                 if let Some(ref tools) = self.tools {
-                    match tools.execute(&tool_name, args).await {
+                    if let Some(ledger) = &self.usage_ledger {
+                        ledger.record_tool(&tool_name);
+                    }
+                    let owner = ArtifactOwner::new(user_id, None);
+                    match owner.scope(tools.execute(&tool_name, args)).await {
                         Ok(output) => {
                             if output.success {
                                 Ok(AgentResult::Text(output.content))
@@ -783,8 +1207,12 @@ impl Controller for ReActController {
                 context_summary: _,
                 visual_refs: _,
                 user_id,
+                workspace_id,
+                dry_run,
             } => {
                 let mut session = self.create_session(&goal, &trace_id, user_id);
+                session.workspace_id = workspace_id;
+                session.dry_run = dry_run;
                 // Run the loop
                 self.run_loop(&mut session).await
             }
@@ -805,7 +1233,7 @@ impl Controller for ReActController {
         tracing::info!(session_id = %session_id, status = ?session.status, "Resuming session");
 
         match session.status {
-            SessionStatus::Completed => {
+            SessionStatus::Completed | SessionStatus::Degraded => {
                 // If completed, find the final answer in history
                 // We search backwards for the first assistant message that looks like a final answer
                 // OR we can just return the last message content if it was a final answer
@@ -894,6 +1322,89 @@ ARGS: {"operation": "add", "a": 5, "b": 3}"#;
         }
     }
 
+    #[tokio::test]
+    async fn test_system_prompt_follows_request_locale() {
+        let prompts = PromptRegistry::default().with_texts(
+            "de",
+            [(
+                prompt_keys::REACT_SYSTEM.to_string(),
+                "Du bist ein Assistent. ZIEL: {goal}".to_string(),
+            )],
+        );
+        let controller = ReActController::builder()
+            .with_prompts(Arc::new(prompts))
+            .build();
+
+        let context = RequestContext {
+            locale: Some("de-CH".into()),
+            ..Default::default()
+        };
+        let session = context
+            .scope(async { controller.create_session("Berichte lesen", "t1", None) })
+            .await;
+        assert_eq!(
+            session.history[0].content.as_str(),
+            "Du bist ein Assistent. ZIEL: Berichte lesen"
+        );
+
+        let session = controller.create_session("Read reports", "t2", None);
+        assert!(session.history[0]
+            .content
+            .starts_with("You are an AI assistant"));
+    }
+
+    struct NoopDelegator;
+
+    #[async_trait::async_trait]
+    impl crate::delegation::Delegator for NoopDelegator {
+        async fn delegate(
+            &self,
+            request: crate::delegation::DelegationRequest,
+        ) -> Result<crate::delegation::DelegationResult> {
+            Ok(crate::delegation::DelegationResult::success(
+                request.id,
+                String::new(),
+                0,
+            ))
+        }
+
+        async fn check_delegation(
+            &self,
+            _id: &str,
+        ) -> Result<Option<crate::delegation::DelegationResult>> {
+            Ok(None)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_capability_disabled_by_feature_flag() {
+        let mut controller = ReActController::new(ReActConfig::default());
+        controller
+            .capabilities
+            .push(Arc::new(crate::capability::DelegationCapability::new(
+                Arc::new(NoopDelegator),
+            )));
+        let response = "DELEGATE: Summarize the logs";
+        assert!(matches!(
+            controller.parse_action(response),
+            ReActAction::Delegate { .. }
+        ));
+
+        let context = RequestContext {
+            feature_flags: [(
+                multi_agent_core::types::flags::DELEGATION.to_string(),
+                false,
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+        let action = context
+            .scope(async { controller.parse_action(response) })
+            .await;
+        assert!(!matches!(action, ReActAction::Delegate { .. }));
+    }
+
     #[tokio::test]
     async fn test_fast_action() {
         let controller = ReActController::new(ReActConfig::default());
@@ -925,6 +1436,8 @@ ARGS: {"operation": "add", "a": 5, "b": 3}"#;
             context_summary: "Test context".to_string(),
             visual_refs: vec![],
             user_id: None,
+            workspace_id: None,
+            dry_run: false,
         };
 
         let result = controller
@@ -9,7 +9,7 @@ use std::collections::HashMap;
 
 use multi_agent_core::{
     traits::{SopDefinition, SopEngine, SopStep, ToolRegistry},
-    types::AgentResult,
+    types::{flags, AgentResult, RequestContext},
     Error, Result,
 };
 
@@ -143,7 +143,9 @@ impl SopEngine for DefaultSopEngine {
         sop: &SopDefinition,
         _context: serde_json::Value,
     ) -> Result<AgentResult> {
-        tracing::info!(sop = %sop.name, parallel = sop.allow_parallel, "Executing SOP");
+        // The parallel_tools flag can force plans to run sequentially.
+        let parallel = sop.allow_parallel && !RequestContext::flag_off(flags::PARALLEL_TOOLS);
+        tracing::info!(sop = %sop.name, parallel, "Executing SOP");
 
         let tools = self
             .tools
@@ -162,7 +164,7 @@ impl SopEngine for DefaultSopEngine {
             })
             .collect();
 
-        let executor = crate::dag::DagExecutor::new(sop.allow_parallel);
+        let executor = crate::dag::DagExecutor::new(parallel);
         let results = executor.execute(tasks).await?;
 
         Ok(AgentResult::Data(serde_json::json!({
@@ -0,0 +1,399 @@
+//! Capability for speculative drafting (two-tier reasoning).
+//!
+//! A cheap model drafts the next action several times. When the drafts agree
+//! on a well-formed action it is used as-is; when they disagree (or none of
+//! them parses into an action), the expensive model is asked to verify the
+//! candidates and pick or override them. Only workspaces that opt in use this
+//! mode, through the `speculative` feature flag or
+//! [`SpeculativeCapability::set_workspace_enabled`]; everything else falls
+//! through to the controller's own LLM.
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use serde::Serialize;
+
+use crate::capability::AgentCapability;
+use crate::parser::{ActionParser, ReActAction};
+use multi_agent_core::{
+    config::SpeculativeDraftingConfig,
+    traits::{ChatMessage, LlmClient, LlmResponse, LlmUsage},
+    types::{flags, RequestContext, Session},
+    Result,
+};
+
+/// Configuration for speculative drafting.
+#[derive(Debug, Clone)]
+pub struct SpeculativeConfig {
+    /// Drafts sampled per iteration; they must all agree to skip verification.
+    pub draft_samples: usize,
+    /// Whether workspaces without an explicit setting use speculative drafting.
+    pub enabled_by_default: bool,
+}
+
+impl SpeculativeConfig {
+    pub fn from_config(config: &SpeculativeDraftingConfig) -> Self {
+        Self {
+            draft_samples: config.draft_samples,
+            enabled_by_default: config.enabled_by_default,
+        }
+    }
+}
+
+impl Default for SpeculativeConfig {
+    fn default() -> Self {
+        Self {
+            draft_samples: 2,
+            enabled_by_default: false,
+        }
+    }
+}
+
+/// Counters for how often drafts were accepted.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SpeculativeStats {
+    pub iterations: u64,
+    /// Drafts that agreed and were used without verification.
+    pub accepted: u64,
+    /// Iterations escalated to the verifier.
+    pub verified: u64,
+    /// Verified iterations where the verifier chose a different action.
+    pub overridden: u64,
+}
+
+/// Capability that drafts actions with a small model and verifies them with
+/// a large one when the drafts disagree.
+pub struct SpeculativeCapability {
+    drafter: Arc<dyn LlmClient>,
+    verifier: Arc<dyn LlmClient>,
+    config: SpeculativeConfig,
+    workspaces: RwLock<HashMap<String, bool>>,
+    iterations: AtomicU64,
+    accepted: AtomicU64,
+    verified: AtomicU64,
+    overridden: AtomicU64,
+}
+
+impl SpeculativeCapability {
+    pub fn new(drafter: Arc<dyn LlmClient>, verifier: Arc<dyn LlmClient>) -> Self {
+        Self {
+            drafter,
+            verifier,
+            config: SpeculativeConfig::default(),
+            workspaces: RwLock::new(HashMap::new()),
+            iterations: AtomicU64::new(0),
+            accepted: AtomicU64::new(0),
+            verified: AtomicU64::new(0),
+            overridden: AtomicU64::new(0),
+        }
+    }
+
+    pub fn with_config(mut self, config: SpeculativeConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Enable or disable speculative drafting for one workspace.
+    pub fn set_workspace_enabled(&self, workspace_id: &str, enabled: bool) {
+        self.workspaces
+            .write()
+            .unwrap()
+            .insert(workspace_id.to_string(), enabled);
+    }
+
+    /// Whether a session in this workspace uses speculative drafting. The
+    /// request's `speculative` flag wins over workspace settings made here.
+    pub fn is_enabled(&self, workspace_id: Option<&str>) -> bool {
+        RequestContext::current()
+            .and_then(|context| context.flag(flags::SPECULATIVE))
+            .or_else(|| {
+                workspace_id.and_then(|id| self.workspaces.read().unwrap().get(id).copied())
+            })
+            .unwrap_or(self.config.enabled_by_default)
+    }
+
+    pub fn stats(&self) -> SpeculativeStats {
+        SpeculativeStats {
+            iterations: self.iterations.load(Ordering::Relaxed),
+            accepted: self.accepted.load(Ordering::Relaxed),
+            verified: self.verified.load(Ordering::Relaxed),
+            overridden: self.overridden.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Ask the verifier to pick among (or replace) the drafted actions.
+    async fn verify(
+        &self,
+        messages: &[ChatMessage],
+        drafts: &[LlmResponse],
+    ) -> Result<LlmResponse> {
+        let candidates = drafts
+            .iter()
+            .enumerate()
+            .map(|(i, d)| format!("Candidate {}:\n{}", i + 1, d.content.trim()))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let mut messages = messages.to_vec();
+        messages.push(ChatMessage {
+            role: "user".to_string(),
+            content: format!(
+                "A draft model proposed these next steps:\n\n{}\n\n\
+                Reply with the correct next step in the required format. \
+                Repeat a candidate if it is right, otherwise give your own.",
+                candidates
+            ),
+            tool_calls: None,
+        });
+        self.verifier.chat(&messages).await
+    }
+}
+
+/// Comparable form of an action, used to detect disagreement between drafts.
+/// `None` means the draft is not a usable action.
+fn signature(action: &ReActAction) -> Option<String> {
+    match action {
+        ReActAction::ToolCall { name, args } => Some(format!("tool:{}:{}", name, args)),
+        ReActAction::FinalAnswer(answer) => Some(format!("answer:{}", answer.trim())),
+        ReActAction::Delegate { objective, .. } => Some(format!("delegate:{}", objective)),
+        ReActAction::McpSelect { task_description } => Some(format!("mcp:{}", task_description)),
+        ReActAction::Think(_) => None,
+    }
+}
+
+fn add_usage(total: &mut LlmUsage, usage: &LlmUsage) {
+    total.prompt_tokens += usage.prompt_tokens;
+    total.completion_tokens += usage.completion_tokens;
+    total.total_tokens += usage.total_tokens;
+}
+
+#[async_trait]
+impl AgentCapability for SpeculativeCapability {
+    fn name(&self) -> &str {
+        "speculative"
+    }
+
+    async fn on_reasoning(
+        &self,
+        session: &Session,
+        messages: &[ChatMessage],
+    ) -> Result<Option<LlmResponse>> {
+        if !self.is_enabled(session.workspace_id.as_deref()) {
+            return Ok(None);
+        }
+        self.iterations.fetch_add(1, Ordering::Relaxed);
+
+        let mut drafts = Vec::with_capacity(self.config.draft_samples.max(1));
+        for _ in 0..self.config.draft_samples.max(1) {
+            match self.drafter.chat(messages).await {
+                Ok(draft) => drafts.push(draft),
+                Err(e) => tracing::warn!(error = %e, "Draft model failed"),
+            }
+        }
+
+        let mut usage = LlmUsage::default();
+        for draft in &drafts {
+            add_usage(&mut usage, &draft.usage);
+        }
+
+        // Drafts count as confident only if they all parse to the same action
+        let parser = ActionParser::new(Vec::new());
+        let signatures: Vec<Option<String>> = drafts
+            .iter()
+            .map(|d| signature(&parser.parse(&d.content)))
+            .collect();
+        let agreed = match signatures.first() {
+            Some(Some(first)) => signatures.iter().all(|s| s.as_ref() == Some(first)),
+            _ => false,
+        };
+
+        if agreed {
+            self.accepted.fetch_add(1, Ordering::Relaxed);
+            tracing::debug!(
+                samples = drafts.len(),
+                "Drafts agree, skipping verification"
+            );
+            let mut response = drafts.swap_remove(0);
+            response.usage = usage;
+            return Ok(Some(response));
+        }
+
+        self.verified.fetch_add(1, Ordering::Relaxed);
+        tracing::debug!(
+            samples = drafts.len(),
+            "Drafts disagree, escalating to verifier"
+        );
+        let mut response = self.verify(messages, &drafts).await?;
+        let chosen = signature(&parser.parse(&response.content));
+        if chosen.is_none() || !signatures.contains(&chosen) {
+            self.overridden.fetch_add(1, Ordering::Relaxed);
+        }
+        add_usage(&mut usage, &response.usage);
+        response.usage = usage;
+        Ok(Some(response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multi_agent_core::types::{SessionStatus, TokenUsage};
+    use std::sync::Mutex;
+
+    /// Returns scripted responses in order, repeating the last one.
+    struct ScriptedLlm {
+        responses: Mutex<Vec<&'static str>>,
+        calls: AtomicU64,
+    }
+
+    impl ScriptedLlm {
+        fn new(responses: Vec<&'static str>) -> Arc<Self> {
+            Arc::new(Self {
+                responses: Mutex::new(responses),
+                calls: AtomicU64::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl LlmClient for ScriptedLlm {
+        async fn complete(&self, _prompt: &str) -> Result<LlmResponse> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let mut responses = self.responses.lock().unwrap();
+            let content = if responses.len() > 1 {
+                responses.remove(0)
+            } else {
+                responses[0]
+            };
+            Ok(LlmResponse {
+                content: content.to_string(),
+                finish_reason: "stop".to_string(),
+                usage: LlmUsage {
+                    prompt_tokens: 10,
+                    completion_tokens: 5,
+                    total_tokens: 15,
+                },
+                tool_calls: None,
+            })
+        }
+        async fn chat(&self, _messages: &[ChatMessage]) -> Result<LlmResponse> {
+            self.complete("").await
+        }
+        async fn embed(&self, _text: &str) -> Result<Vec<f32>> {
+            Ok(vec![])
+        }
+    }
+
+    fn session(workspace: Option<&str>) -> Session {
+        Session {
+            id: "s".to_string(),
+            trace_id: "t".to_string(),
+            user_id: None,
+            workspace_id: workspace.map(String::from),
+            dry_run: false,
+            status: SessionStatus::Running,
+            history: Vec::new(),
+            task_state: None,
+            token_usage: TokenUsage::default(),
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_agreeing_drafts_skip_verifier() {
+        let drafter = ScriptedLlm::new(vec!["FINAL ANSWER: 42"]);
+        let verifier = ScriptedLlm::new(vec!["FINAL ANSWER: 43"]);
+        let cap = SpeculativeCapability::new(drafter.clone(), verifier.clone());
+        cap.set_workspace_enabled("ws", true);
+
+        let response = cap
+            .on_reasoning(&session(Some("ws")), &[])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(response.content, "FINAL ANSWER: 42");
+        assert_eq!(response.usage.total_tokens, 30);
+        assert_eq!(drafter.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(verifier.calls.load(Ordering::SeqCst), 0);
+        assert_eq!(cap.stats().accepted, 1);
+    }
+
+    #[tokio::test]
+    async fn test_disagreement_escalates_to_verifier() {
+        let drafter = ScriptedLlm::new(vec!["ACTION: search\nARGS: {}", "FINAL ANSWER: done"]);
+        let verifier = ScriptedLlm::new(vec!["ACTION: read_file\nARGS: {}"]);
+        let cap = SpeculativeCapability::new(drafter, verifier.clone());
+        cap.set_workspace_enabled("ws", true);
+
+        let response = cap
+            .on_reasoning(&session(Some("ws")), &[])
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(response.content.contains("read_file"));
+        assert_eq!(response.usage.total_tokens, 45);
+        assert_eq!(verifier.calls.load(Ordering::SeqCst), 1);
+        let stats = cap.stats();
+        assert_eq!((stats.verified, stats.overridden), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_only_enabled_workspaces() {
+        let drafter = ScriptedLlm::new(vec!["FINAL ANSWER: 42"]);
+        let cap = SpeculativeCapability::new(drafter.clone(), drafter.clone());
+        cap.set_workspace_enabled("off", false);
+
+        assert!(cap
+            .on_reasoning(&session(None), &[])
+            .await
+            .unwrap()
+            .is_none());
+        assert!(cap
+            .on_reasoning(&session(Some("off")), &[])
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(drafter.calls.load(Ordering::SeqCst), 0);
+
+        let cap = cap.with_config(SpeculativeConfig {
+            enabled_by_default: true,
+            ..SpeculativeConfig::default()
+        });
+        assert!(cap
+            .on_reasoning(&session(None), &[])
+            .await
+            .unwrap()
+            .is_some());
+        assert!(cap
+            .on_reasoning(&session(Some("off")), &[])
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_feature_flag_enables_workspace() {
+        let drafter = ScriptedLlm::new(vec!["FINAL ANSWER: 42"]);
+        let cap = SpeculativeCapability::new(drafter.clone(), drafter.clone());
+        cap.set_workspace_enabled("ws", false);
+        let flagged = |enabled: bool| RequestContext {
+            feature_flags: [(flags::SPECULATIVE.to_string(), enabled)].into(),
+            ..Default::default()
+        };
+
+        let response = flagged(true)
+            .scope(cap.on_reasoning(&session(Some("ws")), &[]))
+            .await
+            .unwrap();
+        assert!(response.is_some());
+
+        cap.set_workspace_enabled("ws", true);
+        let response = flagged(false)
+            .scope(cap.on_reasoning(&session(Some("ws")), &[]))
+            .await
+            .unwrap();
+        assert!(response.is_none());
+    }
+}
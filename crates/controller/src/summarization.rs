@@ -257,6 +257,10 @@ impl AgentCapability for SummarizationCapability {
             embedding,
             tags,
             created_at: chrono::Utc::now().timestamp(),
+            content_hash: None,
+            source_url: None,
+            refreshed_at: None,
+            hits: 0,
         };
 
         match self.knowledge_store.store(entry).await {
@@ -320,6 +324,8 @@ mod tests {
             id: "test-session-42".to_string(),
             trace_id: "test-trace-42".to_string(),
             user_id: None,
+            workspace_id: None,
+            dry_run: false,
             status: SessionStatus::Running,
             history: vec![],
             task_state: Some(TaskState {
@@ -370,6 +376,8 @@ mod tests {
             id: "test-session-43".to_string(),
             trace_id: "test-trace-43".to_string(),
             user_id: None,
+            workspace_id: None,
+            dry_run: false,
             status: SessionStatus::Running,
             history: vec![],
             task_state: Some(TaskState {
@@ -31,6 +31,8 @@ fn make_session_with_history(count: usize) -> Session {
         id: format!("sess-{}", Uuid::new_v4()),
         trace_id: Uuid::new_v4().to_string(),
         user_id: Some("tester".to_string()),
+        workspace_id: None,
+        dry_run: false,
         history,
         created_at: Utc::now().timestamp(),
         updated_at: Utc::now().timestamp(),
@@ -0,0 +1,155 @@
+//! Dry-run missions: mutating tools are simulated and an action plan is returned.
+
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use multi_agent_controller::{ReActConfig, ReActController};
+use multi_agent_core::{
+    traits::{ChatMessage, Controller, LlmClient, LlmResponse, LlmUsage, Tool, ToolRegistry},
+    types::{AgentResult, ToolOutput, ToolRiskLevel, UserIntent},
+};
+use multi_agent_skills::DefaultToolRegistry;
+
+/// Lists files, deletes one, then answers.
+struct CleanupLlm;
+
+#[async_trait]
+impl LlmClient for CleanupLlm {
+    async fn complete(&self, prompt: &str) -> multi_agent_core::Result<LlmResponse> {
+        let content = if prompt.contains("delete_file") {
+            "FINAL ANSWER: Removed old.log"
+        } else if prompt.contains("old.log") {
+            "ACTION: delete_file\nARGS: {\"path\": \"old.log\"}"
+        } else {
+            "ACTION: list_files\nARGS: {}"
+        };
+        Ok(LlmResponse {
+            content: content.to_string(),
+            finish_reason: "stop".to_string(),
+            usage: LlmUsage {
+                prompt_tokens: 10,
+                completion_tokens: 10,
+                total_tokens: 20,
+            },
+            tool_calls: None,
+        })
+    }
+    async fn chat(&self, messages: &[ChatMessage]) -> multi_agent_core::Result<LlmResponse> {
+        // Only look at observations, not the system prompt
+        let prompt: String = messages
+            .iter()
+            .skip(1)
+            .map(|m| m.content.as_str())
+            .collect();
+        self.complete(&prompt).await
+    }
+    async fn embed(&self, _text: &str) -> multi_agent_core::Result<Vec<f32>> {
+        Ok(vec![])
+    }
+}
+
+struct CountingTool {
+    name: &'static str,
+    risk: ToolRiskLevel,
+    output: &'static str,
+    calls: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Tool for CountingTool {
+    fn name(&self) -> &str {
+        self.name
+    }
+    fn description(&self) -> &str {
+        "test tool"
+    }
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({"type": "object"})
+    }
+    fn risk_level(&self) -> ToolRiskLevel {
+        self.risk
+    }
+    async fn execute(&self, _args: serde_json::Value) -> multi_agent_core::Result<ToolOutput> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(ToolOutput::text(self.output))
+    }
+}
+
+async fn run(dry_run: bool) -> (AgentResult, usize, usize) {
+    let listed = Arc::new(AtomicUsize::new(0));
+    let deleted = Arc::new(AtomicUsize::new(0));
+    let registry = DefaultToolRegistry::new();
+    registry
+        .register(Box::new(CountingTool {
+            name: "list_files",
+            risk: ToolRiskLevel::Low,
+            output: "old.log new.log",
+            calls: listed.clone(),
+        }))
+        .await
+        .unwrap();
+    registry
+        .register(Box::new(CountingTool {
+            name: "delete_file",
+            risk: ToolRiskLevel::High,
+            output: "deleted",
+            calls: deleted.clone(),
+        }))
+        .await
+        .unwrap();
+
+    let controller = ReActController::builder()
+        .with_config(ReActConfig {
+            max_iterations: 5,
+            ..ReActConfig::default()
+        })
+        .with_llm(Arc::new(CleanupLlm))
+        .with_tools(Arc::new(registry))
+        .build();
+
+    let intent = UserIntent::ComplexMission {
+        goal: "Remove old logs".into(),
+        context_summary: "test".into(),
+        visual_refs: vec![],
+        user_id: None,
+        workspace_id: None,
+        dry_run,
+    };
+    let result = controller
+        .execute(intent, "trace".to_string())
+        .await
+        .unwrap();
+    (
+        result,
+        listed.load(Ordering::SeqCst),
+        deleted.load(Ordering::SeqCst),
+    )
+}
+
+#[tokio::test]
+async fn test_dry_run_simulates_mutating_tools() {
+    let (result, listed, deleted) = run(true).await;
+    assert_eq!(listed, 1, "read-only tools still run");
+    assert_eq!(deleted, 0, "mutating tools are simulated");
+
+    let AgentResult::Data(report) = result else {
+        panic!("Expected a dry-run report, got {:?}", result);
+    };
+    assert_eq!(report["dry_run"], true);
+    assert_eq!(report["goal"], "Remove old logs");
+    assert_eq!(report["simulated"], 1);
+    assert_eq!(report["actions"][0]["tool"], "list_files");
+    assert_eq!(report["actions"][0]["executed"], true);
+    assert_eq!(report["actions"][1]["tool"], "delete_file");
+    assert_eq!(report["actions"][1]["executed"], false);
+    assert_eq!(report["actions"][1]["args"]["path"], "old.log");
+    assert_eq!(report["answer"], "Removed old.log");
+}
+
+#[tokio::test]
+async fn test_without_dry_run_tools_execute() {
+    let (result, listed, deleted) = run(false).await;
+    assert_eq!((listed, deleted), (1, 1));
+    assert!(matches!(result, AgentResult::Text(ref t) if t == "Removed old.log"));
+}
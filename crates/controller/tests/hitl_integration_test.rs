@@ -75,6 +75,7 @@ impl ApprovalGate for ApproveGate {
         Ok(ApprovalResponse::Approved {
             reason: None,
             reason_code: "TEST_APPROVED".to_string(),
+            scope: multi_agent_core::types::ApprovalScope::Once,
         })
     }
     fn threshold(&self) -> ToolRiskLevel {
@@ -136,6 +137,8 @@ async fn test_deny_gate_triggers_deadlock_breaker() {
         context_summary: "test".into(),
         visual_refs: vec![],
         user_id: None,
+        workspace_id: None,
+        dry_run: false,
     };
 
     let result = controller.execute(intent, "test-trace".to_string()).await;
@@ -211,6 +214,8 @@ async fn test_approve_gate_allows_execution() {
         context_summary: "test".into(),
         visual_refs: vec![],
         user_id: None,
+        workspace_id: None,
+        dry_run: false,
     };
 
     // Should NOT fail with Denied
@@ -250,6 +255,7 @@ async fn test_channel_gate_timeout_auto_deny() {
         timeout_secs: None,
         nonce: "timeout-nonce".into(),
         expires_at: chrono::Utc::now().timestamp() + 60,
+        mandatory: false,
     };
 
     // No response submitted → should timeout and auto-deny
@@ -290,6 +296,7 @@ async fn test_channel_gate_async_approve() {
         timeout_secs: None,
         nonce: "test-nonce-4".into(),
         expires_at: 0,
+        mandatory: false,
     };
 
     // Spawn the approval request
@@ -305,6 +312,7 @@ async fn test_channel_gate_async_approve() {
         ApprovalResponse::Approved {
             reason: None,
             reason_code: "TEST_APPROVED".to_string(),
+            scope: multi_agent_core::types::ApprovalScope::Once,
         },
     )
     .await
@@ -313,3 +321,393 @@ async fn test_channel_gate_async_approve() {
     let response = handle.await.unwrap().unwrap();
     assert!(matches!(response, ApprovalResponse::Approved { .. }));
 }
+
+// =============================================================================
+// 5. requires_approval 工具无论风险等级都必须经过审批
+// =============================================================================
+
+/// Low-risk tool that must still be approved on every call.
+struct NotifyTool {
+    calls: Arc<std::sync::atomic::AtomicUsize>,
+}
+
+#[async_trait]
+impl multi_agent_core::traits::Tool for NotifyTool {
+    fn name(&self) -> &str {
+        "notify"
+    }
+    fn description(&self) -> &str {
+        "Send a notification"
+    }
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({"type": "object"})
+    }
+    async fn execute(
+        &self,
+        _args: serde_json::Value,
+    ) -> multi_agent_core::Result<multi_agent_core::types::ToolOutput> {
+        self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Ok(multi_agent_core::types::ToolOutput::text("sent"))
+    }
+    fn requires_approval(&self) -> bool {
+        true
+    }
+}
+
+struct NotifyLlm;
+
+#[async_trait]
+impl LlmClient for NotifyLlm {
+    async fn complete(&self, _prompt: &str) -> multi_agent_core::Result<LlmResponse> {
+        Ok(LlmResponse {
+            content: "THOUGHT: Notify the team.\nACTION: notify\nARGS: {}".to_string(),
+            finish_reason: "stop".to_string(),
+            usage: LlmUsage {
+                prompt_tokens: 10,
+                completion_tokens: 20,
+                total_tokens: 30,
+            },
+            tool_calls: None,
+        })
+    }
+    async fn chat(&self, _messages: &[ChatMessage]) -> multi_agent_core::Result<LlmResponse> {
+        self.complete("").await
+    }
+    async fn embed(&self, _text: &str) -> multi_agent_core::Result<Vec<f32>> {
+        Ok(vec![])
+    }
+}
+
+/// Denies everything and counts requests.
+struct CountingDenyGate {
+    requests: Arc<std::sync::atomic::AtomicUsize>,
+}
+
+#[async_trait]
+impl ApprovalGate for CountingDenyGate {
+    async fn request_approval(
+        &self,
+        _req: &ApprovalRequest,
+    ) -> multi_agent_core::Result<ApprovalResponse> {
+        self.requests
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Ok(ApprovalResponse::Denied {
+            reason: "Not now".to_string(),
+            reason_code: "TEST_DENIED".to_string(),
+        })
+    }
+}
+
+async fn run_notify_mission(
+    gate: Option<Arc<dyn ApprovalGate>>,
+    policy: Option<multi_agent_governance::PolicyEngine>,
+    workspace_id: Option<&str>,
+) -> usize {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let registry = DefaultToolRegistry::new();
+    registry
+        .register(Box::new(NotifyTool {
+            calls: calls.clone(),
+        }))
+        .await
+        .unwrap();
+    assert!(registry.requires_approval("notify").await);
+
+    let mut builder = ReActController::builder()
+        .with_config(ReActConfig {
+            max_iterations: 2,
+            ..ReActConfig::default()
+        })
+        .with_llm(Arc::new(NotifyLlm))
+        .with_tools(Arc::new(registry))
+        .with_session_store(Arc::new(InMemorySessionStore::new()));
+    if let Some(gate) = gate {
+        builder = builder.with_approval_gate(gate);
+    }
+    if let Some(policy) = policy {
+        builder = builder.with_policy_engine(Arc::new(tokio::sync::RwLock::new(policy)));
+    }
+
+    let intent = multi_agent_core::types::UserIntent::ComplexMission {
+        goal: "Notify the team".into(),
+        context_summary: "test".into(),
+        visual_refs: vec![],
+        user_id: None,
+        workspace_id: workspace_id.map(str::to_string),
+        dry_run: false,
+    };
+    let _ = builder
+        .build()
+        .execute(intent, "test-trace".to_string())
+        .await;
+    calls.load(Ordering::SeqCst)
+}
+
+#[tokio::test]
+async fn test_requires_approval_bypasses_risk_threshold() {
+    let requests = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let executed = run_notify_mission(
+        Some(Arc::new(CountingDenyGate {
+            requests: requests.clone(),
+        })),
+        None,
+        None,
+    )
+    .await;
+
+    assert!(requests.load(std::sync::atomic::Ordering::SeqCst) > 0);
+    assert_eq!(executed, 0, "denied tool must not run");
+}
+
+#[tokio::test]
+async fn test_requires_approval_without_gate_is_not_executed() {
+    assert_eq!(run_notify_mission(None, None, None).await, 0);
+}
+
+#[tokio::test]
+async fn test_workspace_never_override_keeps_tool_approval() {
+    use multi_agent_governance::{
+        ApprovalMode, PolicyEngine, PolicyFile, ToolApprovalOverride, WorkspaceApprovalOverride,
+    };
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let policy = || {
+        PolicyEngine::from_file(PolicyFile {
+            version: "1".into(),
+            name: "overrides".into(),
+            rules: vec![],
+            thresholds: Default::default(),
+            tool_overrides: HashMap::new(),
+            workspace_overrides: HashMap::from([(
+                "trusted".to_string(),
+                WorkspaceApprovalOverride {
+                    approval_required: None,
+                    tools: HashMap::from([(
+                        "notify".to_string(),
+                        ToolApprovalOverride {
+                            approval: Some(ApprovalMode::Never),
+                            threshold: None,
+                        },
+                    )]),
+                },
+            )]),
+        })
+    };
+
+    let requests = Arc::new(AtomicUsize::new(0));
+    let gate = || -> Option<Arc<dyn ApprovalGate>> {
+        Some(Arc::new(CountingDenyGate {
+            requests: requests.clone(),
+        }))
+    };
+
+    // `never` lowers the risk threshold but cannot waive the tool's own
+    // requirement, in this workspace or any other
+    for workspace in ["other", "trusted"] {
+        requests.store(0, Ordering::SeqCst);
+        assert_eq!(
+            run_notify_mission(gate(), Some(policy()), Some(workspace)).await,
+            0
+        );
+        assert!(requests.load(Ordering::SeqCst) > 0);
+    }
+}
+
+// =============================================================================
+// ask_user pauses the session and returns the answer as an observation
+// =============================================================================
+
+struct AskingLlm;
+
+#[async_trait]
+impl LlmClient for AskingLlm {
+    async fn complete(&self, prompt: &str) -> multi_agent_core::Result<LlmResponse> {
+        let content = if prompt.contains("User answered: staging") {
+            "FINAL ANSWER: Deploying to staging"
+        } else {
+            "THOUGHT: The target is ambiguous.\nACTION: ask_user\nARGS: {\"question\": \"Which environment?\"}"
+        };
+        Ok(LlmResponse {
+            content: content.to_string(),
+            finish_reason: "stop".to_string(),
+            usage: LlmUsage {
+                prompt_tokens: 10,
+                completion_tokens: 20,
+                total_tokens: 30,
+            },
+            tool_calls: None,
+        })
+    }
+    async fn chat(&self, messages: &[ChatMessage]) -> multi_agent_core::Result<LlmResponse> {
+        let prompt: String = messages.iter().map(|m| m.content.as_str()).collect();
+        self.complete(&prompt).await
+    }
+    async fn embed(&self, _text: &str) -> multi_agent_core::Result<Vec<f32>> {
+        Ok(vec![])
+    }
+}
+
+/// Answers every question, recording the session status seen while waiting.
+struct StatusCheckingInput {
+    store: Arc<InMemorySessionStore>,
+    seen: tokio::sync::Mutex<Vec<multi_agent_core::types::SessionStatus>>,
+}
+
+#[async_trait]
+impl multi_agent_core::traits::HumanInputChannel for StatusCheckingInput {
+    async fn ask(
+        &self,
+        question: &multi_agent_core::types::HumanQuestion,
+    ) -> multi_agent_core::Result<multi_agent_core::types::HumanAnswer> {
+        use multi_agent_core::traits::SessionStore;
+        if let Some(session) = self.store.load(&question.session_id).await? {
+            self.seen.lock().await.push(session.status);
+        }
+        Ok(multi_agent_core::types::HumanAnswer::Answered {
+            answer: "staging".into(),
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_ask_user_pauses_session_until_answered() {
+    let store = Arc::new(InMemorySessionStore::new());
+    let input = Arc::new(StatusCheckingInput {
+        store: store.clone(),
+        seen: tokio::sync::Mutex::new(Vec::new()),
+    });
+    let registry = DefaultToolRegistry::new();
+    registry
+        .register(Box::new(multi_agent_skills::AskUserTool::new(
+            input.clone(),
+        )))
+        .await
+        .unwrap();
+
+    let controller = ReActController::builder()
+        .with_config(ReActConfig {
+            max_iterations: 4,
+            ..ReActConfig::default()
+        })
+        .with_llm(Arc::new(AskingLlm))
+        .with_tools(Arc::new(registry))
+        .with_session_store(store)
+        .build();
+
+    let intent = multi_agent_core::types::UserIntent::ComplexMission {
+        goal: "Deploy the service".into(),
+        context_summary: "test".into(),
+        visual_refs: vec![],
+        user_id: None,
+        workspace_id: None,
+        dry_run: false,
+    };
+    let result = controller
+        .execute(intent, "test-trace".to_string())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        *input.seen.lock().await,
+        vec![multi_agent_core::types::SessionStatus::Paused]
+    );
+    assert!(format!("{:?}", result).contains("Deploying to staging"));
+}
+
+// =============================================================================
+// 7. 死锁恢复策略：询问人类 / 仅报告
+// =============================================================================
+
+/// Answers every question with revised instructions.
+struct RevisingInput {
+    questions: Arc<std::sync::atomic::AtomicUsize>,
+}
+
+#[async_trait]
+impl multi_agent_core::traits::HumanInputChannel for RevisingInput {
+    async fn ask(
+        &self,
+        _question: &multi_agent_core::types::HumanQuestion,
+    ) -> multi_agent_core::Result<multi_agent_core::types::HumanAnswer> {
+        self.questions
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Ok(multi_agent_core::types::HumanAnswer::Answered {
+            answer: "Just summarise instead".to_string(),
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_deadlock_recovery_strategies() {
+    use multi_agent_core::config::{DeadlockConfig, DeadlockStrategy};
+    use multi_agent_core::traits::SessionStore;
+    use multi_agent_core::types::{AgentResult, SessionStatus};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let store = Arc::new(InMemorySessionStore::new());
+    let questions = Arc::new(AtomicUsize::new(0));
+    let registry = DefaultToolRegistry::new();
+    registry
+        .register(Box::new(NotifyTool {
+            calls: Arc::new(AtomicUsize::new(0)),
+        }))
+        .await
+        .unwrap();
+    let controller = ReActController::builder()
+        .with_config(ReActConfig {
+            max_iterations: 4,
+            deadlock: DeadlockConfig {
+                max_rejections: 1,
+                strategy: DeadlockStrategy::AskHuman,
+                workspaces: [("audit".to_string(), DeadlockStrategy::ReportOnly)].into(),
+            },
+            ..ReActConfig::default()
+        })
+        .with_llm(Arc::new(NotifyLlm))
+        .with_tools(Arc::new(registry))
+        .with_approval_gate(Arc::new(CountingDenyGate {
+            requests: Arc::new(AtomicUsize::new(0)),
+        }))
+        .with_human_input(Arc::new(RevisingInput {
+            questions: questions.clone(),
+        }))
+        .with_session_store(store.clone())
+        .build();
+    let mission =
+        |workspace_id: Option<&str>| multi_agent_core::types::UserIntent::ComplexMission {
+            goal: "Notify the team".into(),
+            context_summary: "test".into(),
+            visual_refs: vec![],
+            user_id: None,
+            workspace_id: workspace_id.map(str::to_string),
+            dry_run: false,
+        };
+
+    // Each denial is followed by a question instead of a hard failure
+    let err = controller
+        .execute(mission(None), "t1".to_string())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, multi_agent_core::Error::MaxIterationsExceeded(4)),
+        "{}",
+        err
+    );
+    assert!(questions.load(Ordering::SeqCst) >= 1);
+
+    // The audit workspace ends with a report and a distinct status
+    let result = controller
+        .execute(mission(Some("audit")), "t2".to_string())
+        .await
+        .unwrap();
+    assert!(matches!(result, AgentResult::Text(_)));
+    let degraded = store
+        .list_sessions(Some(SessionStatus::Degraded), None)
+        .await
+        .unwrap();
+    assert_eq!(degraded.len(), 1);
+    assert_eq!(degraded[0].workspace_id.as_deref(), Some("audit"));
+}
@@ -53,6 +53,8 @@ fn make_session(goal: &str) -> Session {
         id: Uuid::new_v4().to_string(),
         trace_id: Uuid::new_v4().to_string(),
         user_id: None,
+        workspace_id: None,
+        dry_run: false,
         status: SessionStatus::Running,
         history: Vec::new(),
         task_state: Some(TaskState {
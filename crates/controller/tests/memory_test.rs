@@ -46,6 +46,8 @@ async fn test_memory_context_injection() -> Result<()> {
         id: Uuid::new_v4().to_string(),
         trace_id: Uuid::new_v4().to_string(),
         user_id: None,
+        workspace_id: None,
+        dry_run: false,
         history: Vec::new(),
         created_at: Utc::now().timestamp(),
         updated_at: Utc::now().timestamp(),
@@ -86,6 +88,8 @@ async fn test_memory_archival() -> Result<()> {
         id: "sess1".to_string(),
         trace_id: "trace-sess1".to_string(),
         user_id: None,
+        workspace_id: None,
+        dry_run: false,
         history: Vec::new(),
         created_at: Utc::now().timestamp(),
         updated_at: Utc::now().timestamp(),
@@ -12,6 +12,8 @@ fn make_session(session_id: &str, goal: &str) -> Session {
         id: session_id.to_string(),
         trace_id: Uuid::new_v4().to_string(),
         user_id: Some("tester".to_string()),
+        workspace_id: None,
+        dry_run: false,
         history: Vec::new(),
         created_at: Utc::now().timestamp(),
         updated_at: Utc::now().timestamp(),
@@ -0,0 +1,88 @@
+//! Adaptive model selection: iterations are routed per task class and the
+//! decision is reported as a MODEL_SELECTED event.
+
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+use multi_agent_controller::{ReActConfig, ReActController};
+use multi_agent_core::{
+    events::{EventEnvelope, EventType},
+    traits::{Controller, EventEmitter},
+    types::{AgentResult, UserIntent},
+};
+use multi_agent_model_gateway::{
+    AdaptiveModelSelector, MockLlmClient, ProviderRegistry, TaskClass,
+};
+
+#[derive(Default)]
+struct CollectingEmitter {
+    events: Mutex<Vec<EventEnvelope>>,
+}
+
+#[async_trait]
+impl EventEmitter for CollectingEmitter {
+    async fn emit(&self, event: EventEnvelope) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+#[tokio::test]
+async fn test_iterations_use_selected_model() {
+    let registry = Arc::new(ProviderRegistry::new());
+    registry.register(
+        "openai",
+        "gpt-4o",
+        Arc::new(MockLlmClient::new("FINAL ANSWER: planned by gpt-4o")),
+    );
+    registry.register(
+        "openai",
+        "gpt-4o-mini",
+        Arc::new(MockLlmClient::new("FINAL ANSWER: planned by mini")),
+    );
+    let selector = Arc::new(AdaptiveModelSelector::new(registry).with_exploration_interval(0));
+    let emitter = Arc::new(CollectingEmitter::default());
+
+    let controller = ReActController::builder()
+        .with_config(ReActConfig {
+            max_iterations: 3,
+            ..ReActConfig::default()
+        })
+        .with_model_selector(selector.clone())
+        .with_event_emitter(emitter.clone())
+        .build();
+
+    let intent = UserIntent::ComplexMission {
+        goal: "Plan the release".into(),
+        context_summary: "test".into(),
+        visual_refs: vec![],
+        user_id: None,
+        workspace_id: None,
+        dry_run: false,
+    };
+    let result = controller
+        .execute(intent, "trace".to_string())
+        .await
+        .unwrap();
+
+    // Planning needs a high-quality model until a cheaper one proves itself
+    let AgentResult::Text(answer) = result else {
+        panic!("Expected a text answer, got {:?}", result);
+    };
+    assert!(answer.contains("gpt-4o"));
+    assert!(!answer.contains("mini"));
+
+    let events = emitter.events.lock().unwrap();
+    let selected = events
+        .iter()
+        .find(|e| e.event_type == EventType::ModelSelected)
+        .expect("MODEL_SELECTED emitted");
+    assert_eq!(selected.payload["class"], "planning");
+    assert_eq!(selected.payload["model"], "openai:gpt-4o");
+    assert_eq!(selected.payload["reason"], "default_quality");
+    assert_eq!(selected.payload["iteration"], 0);
+
+    // The outcome was recorded against the chosen model
+    let (_, decision) = selector.select_for_class(TaskClass::Planning).unwrap();
+    assert_eq!(decision.samples, 1);
+    assert_eq!(decision.success_rate, Some(1.0));
+}
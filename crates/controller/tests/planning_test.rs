@@ -2,8 +2,11 @@ use async_trait::async_trait;
 use chrono::Utc;
 use multi_agent_controller::capability::AgentCapability;
 use multi_agent_controller::planning::PlanningCapability;
-use multi_agent_core::traits::{ChatMessage, LlmClient, LlmResponse};
-use multi_agent_core::types::{Session, SessionStatus, TaskState};
+use multi_agent_controller::{PlanApprovalThreshold, PlanEstimator};
+use multi_agent_core::traits::{ApprovalGate, ChatMessage, LlmClient, LlmResponse};
+use multi_agent_core::types::{
+    AgentResult, ApprovalRequest, ApprovalResponse, Session, SessionStatus, TaskState,
+};
 use multi_agent_core::LlmUsage;
 use multi_agent_core::Result;
 use std::sync::Arc;
@@ -51,6 +54,8 @@ async fn test_planning_generation() -> Result<()> {
         id: Uuid::new_v4().to_string(),
         trace_id: Uuid::new_v4().to_string(),
         user_id: None,
+        workspace_id: None,
+        dry_run: false,
         history: Vec::new(),
         created_at: Utc::now().timestamp(),
         updated_at: Utc::now().timestamp(),
@@ -86,3 +91,84 @@ async fn test_planning_generation() -> Result<()> {
 
     Ok(())
 }
+
+struct DenyGate(std::sync::Mutex<Vec<ApprovalRequest>>);
+#[async_trait]
+impl ApprovalGate for DenyGate {
+    async fn request_approval(&self, req: &ApprovalRequest) -> Result<ApprovalResponse> {
+        self.0.lock().unwrap().push(req.clone());
+        Ok(ApprovalResponse::Denied {
+            reason: "too expensive".to_string(),
+            reason_code: "USER_DENIED".to_string(),
+        })
+    }
+}
+
+fn new_session(goal: &str) -> Session {
+    Session {
+        id: Uuid::new_v4().to_string(),
+        trace_id: Uuid::new_v4().to_string(),
+        user_id: None,
+        workspace_id: None,
+        dry_run: false,
+        history: Vec::new(),
+        created_at: Utc::now().timestamp(),
+        updated_at: Utc::now().timestamp(),
+        status: SessionStatus::Running,
+        token_usage: Default::default(),
+        task_state: Some(TaskState {
+            goal: goal.to_string(),
+            iteration: 0,
+            observations: Vec::new(),
+            pending_actions: Vec::new(),
+            consecutive_rejections: 0,
+        }),
+    }
+}
+
+#[tokio::test]
+async fn test_plan_estimate_approval_and_accuracy() -> Result<()> {
+    let estimator = Arc::new(
+        PlanEstimator::new().with_approval_threshold(PlanApprovalThreshold {
+            tokens: 10_000,
+            ..Default::default()
+        }),
+    );
+
+    // Two steps fit under the threshold; the estimate is tracked on finish
+    let planner =
+        PlanningCapability::new(Arc::new(MockPlannerLlm)).with_estimator(estimator.clone());
+    let mut session = new_session("Build a house");
+    planner.on_start(&mut session).await?;
+    assert!(session.history[0].content.contains("Estimated budget"));
+    session.token_usage.prompt_tokens = 3_000;
+    session.token_usage.completion_tokens = 1_000;
+    planner
+        .on_finish(&mut session, &AgentResult::Text("done".into()))
+        .await?;
+    let accuracy = estimator.accuracy();
+    assert_eq!(accuracy.samples, 1);
+    assert!((accuracy.mean_ratio - 1.0).abs() < 1e-9);
+
+    // Learned 2k tokens per step; a tighter threshold now needs approval
+    let estimator = Arc::new(
+        PlanEstimator::new().with_approval_threshold(PlanApprovalThreshold {
+            tokens: 1_000,
+            ..Default::default()
+        }),
+    );
+    let gate = Arc::new(DenyGate(Default::default()));
+    let planner = PlanningCapability::new(Arc::new(MockPlannerLlm))
+        .with_estimator(estimator)
+        .with_approval_gate(gate.clone());
+    let err = planner
+        .on_start(&mut new_session("Build a house"))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("DENIED"), "{}", err);
+    let requests = gate.0.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].tool_name, "execute_plan");
+    assert_eq!(requests[0].args["estimate"]["steps"], 2);
+    Ok(())
+}
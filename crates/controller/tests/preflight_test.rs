@@ -0,0 +1,78 @@
+//! Preflight token checks stop a session before the LLM is called.
+
+use std::sync::Arc;
+
+use multi_agent_controller::{ReActConfig, ReActController};
+use multi_agent_core::{traits::Controller, types::UserIntent, Error};
+use multi_agent_model_gateway::{MockLlmClient, TokenCounter};
+
+/// Pre-compaction flushes go to a temp dir instead of the crate's `.memory/`.
+fn isolate_memory_dir() {
+    let dir = std::env::temp_dir().join(format!("ma_preflight_{}", std::process::id()));
+    unsafe {
+        std::env::set_var("MULTI_AGENT_MEMORY_DIR", dir);
+    }
+}
+
+fn intent() -> UserIntent {
+    UserIntent::ComplexMission {
+        goal: "Summarize the quarterly report".into(),
+        context_summary: "test".into(),
+        visual_refs: vec![],
+        user_id: None,
+        workspace_id: None,
+        dry_run: false,
+    }
+}
+
+#[tokio::test]
+async fn test_prompt_exceeding_budget_is_rejected() {
+    isolate_memory_dir();
+    let controller = ReActController::builder()
+        .with_config(ReActConfig {
+            default_budget: 20,
+            ..ReActConfig::default()
+        })
+        .with_llm(Arc::new(MockLlmClient::new("FINAL ANSWER: done")))
+        .with_token_counter(Arc::new(TokenCounter::for_model("gpt-4o")))
+        .build();
+
+    let err = controller
+        .execute(intent(), "trace".to_string())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, Error::BudgetExceeded { limit: 20, .. }),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn test_context_window_overflow_is_rejected() {
+    isolate_memory_dir();
+    let controller = ReActController::builder()
+        .with_llm(Arc::new(MockLlmClient::new("FINAL ANSWER: done")))
+        .with_token_counter(Arc::new(
+            TokenCounter::for_model("gpt-4o").with_context_window(1_100),
+        ))
+        .build();
+
+    let err = controller
+        .execute(intent(), "trace".to_string())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, Error::ContextWindowExceeded { limit: 1_100, .. }),
+        "{err}"
+    );
+
+    // The same prompt fits a real GPT-4o window
+    let controller = ReActController::builder()
+        .with_llm(Arc::new(MockLlmClient::new("FINAL ANSWER: done")))
+        .with_token_counter(Arc::new(TokenCounter::for_model("gpt-4o")))
+        .build();
+    assert!(controller
+        .execute(intent(), "trace".to_string())
+        .await
+        .is_ok());
+}
@@ -21,6 +21,8 @@ async fn test_reflection_loop_detection() -> anyhow::Result<()> {
         id: Uuid::new_v4().to_string(),
         trace_id: Uuid::new_v4().to_string(),
         user_id: None,
+        workspace_id: None,
+        dry_run: false,
         history: Vec::new(),
         created_at: Utc::now().timestamp(),
         updated_at: Utc::now().timestamp(),
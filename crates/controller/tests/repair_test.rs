@@ -0,0 +1,110 @@
+//! Malformed actions are repaired by re-prompting with the parse error.
+
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+use multi_agent_controller::{ReActConfig, ReActController};
+use multi_agent_core::{
+    traits::{ChatMessage, Controller, LlmClient, LlmResponse, LlmUsage},
+    types::{AgentResult, UserIntent},
+    Result,
+};
+
+/// Returns scripted responses in order and records every prompt it sees.
+struct ScriptedLlm {
+    responses: Mutex<Vec<&'static str>>,
+    prompts: Mutex<Vec<Vec<ChatMessage>>>,
+}
+
+impl ScriptedLlm {
+    fn new(responses: Vec<&'static str>) -> Arc<Self> {
+        Arc::new(Self {
+            responses: Mutex::new(responses),
+            prompts: Mutex::new(Vec::new()),
+        })
+    }
+}
+
+#[async_trait]
+impl LlmClient for ScriptedLlm {
+    async fn complete(&self, _prompt: &str) -> Result<LlmResponse> {
+        self.chat(&[]).await
+    }
+    async fn chat(&self, messages: &[ChatMessage]) -> Result<LlmResponse> {
+        self.prompts.lock().unwrap().push(messages.to_vec());
+        let mut responses = self.responses.lock().unwrap();
+        let content = if responses.len() > 1 {
+            responses.remove(0)
+        } else {
+            responses[0]
+        };
+        Ok(LlmResponse {
+            content: content.to_string(),
+            finish_reason: "stop".to_string(),
+            usage: LlmUsage {
+                prompt_tokens: 10,
+                completion_tokens: 5,
+                total_tokens: 15,
+            },
+            tool_calls: None,
+        })
+    }
+    async fn embed(&self, _text: &str) -> Result<Vec<f32>> {
+        Ok(vec![])
+    }
+}
+
+fn intent() -> UserIntent {
+    UserIntent::ComplexMission {
+        goal: "Look up the weather".into(),
+        context_summary: "test".into(),
+        visual_refs: vec![],
+        user_id: None,
+        workspace_id: None,
+        dry_run: false,
+    }
+}
+
+#[tokio::test]
+async fn test_malformed_args_are_repaired() {
+    let llm = ScriptedLlm::new(vec![
+        "ACTION: weather\nARGS: {city: Paris",
+        "FINAL ANSWER: sunny",
+    ]);
+    let controller = ReActController::builder()
+        .with_config(ReActConfig {
+            max_iterations: 1,
+            ..ReActConfig::default()
+        })
+        .with_llm(llm.clone())
+        .build();
+
+    let result = controller
+        .execute(intent(), "trace".to_string())
+        .await
+        .unwrap();
+    assert!(matches!(result, AgentResult::Text(ref t) if t.contains("sunny")));
+
+    let prompts = llm.prompts.lock().unwrap();
+    assert_eq!(prompts.len(), 2);
+    let repair = prompts[1].last().unwrap();
+    assert!(repair
+        .content
+        .contains("ARGS for 'weather' is not valid JSON"));
+}
+
+#[tokio::test]
+async fn test_repair_attempts_are_bounded() {
+    let llm = ScriptedLlm::new(vec!["ACTION: weather"]);
+    let controller = ReActController::builder()
+        .with_config(ReActConfig {
+            max_iterations: 1,
+            max_repair_attempts: 2,
+            ..ReActConfig::default()
+        })
+        .with_llm(llm.clone())
+        .build();
+
+    let _ = controller.execute(intent(), "trace".to_string()).await;
+    assert_eq!(llm.prompts.lock().unwrap().len(), 3);
+}
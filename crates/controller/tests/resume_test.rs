@@ -20,6 +20,8 @@ async fn test_resume_session() -> anyhow::Result<()> {
         id: session_id.to_string(),
         trace_id: "test-trace-resume".to_string(),
         user_id: None,
+        workspace_id: None,
+        dry_run: false,
         status: SessionStatus::Running,
         history: vec![
             HistoryEntry {
@@ -68,3 +70,38 @@ async fn test_resume_session() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_deadline_pauses_mission() -> anyhow::Result<()> {
+    use multi_agent_core::types::{RequestContext, UserIntent};
+
+    let session_store = Arc::new(InMemorySessionStore::new());
+    let controller = ReActController::builder()
+        .with_session_store(session_store.clone())
+        .build();
+
+    let context = RequestContext {
+        deadline: Some(chrono::Utc::now() - chrono::Duration::seconds(1)),
+        ..Default::default()
+    };
+    let intent = UserIntent::ComplexMission {
+        goal: "Summarize the report".to_string(),
+        context_summary: String::new(),
+        visual_refs: vec![],
+        user_id: None,
+        workspace_id: None,
+        dry_run: false,
+    };
+    let err = context
+        .scope(controller.execute(intent, "trace-deadline".to_string()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, multi_agent_core::Error::Timeout(_)));
+
+    let paused = session_store
+        .list_sessions(Some(SessionStatus::Paused), None)
+        .await?;
+    assert_eq!(paused.len(), 1);
+    assert_eq!(paused[0].trace_id, "trace-deadline");
+    Ok(())
+}
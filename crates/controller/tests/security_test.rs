@@ -5,7 +5,6 @@ use multi_agent_core::types::UserIntent;
 use multi_agent_core::LlmUsage;
 use multi_agent_governance::guardrails::{CompositeGuardrail, PiiScanner};
 use std::sync::Arc;
-use tokio;
 
 // Mock LLM Client
 struct MockLlm;
@@ -55,6 +54,8 @@ async fn test_security_pii_violation() {
         context_summary: "".to_string(),
         visual_refs: vec![],
         user_id: None,
+        workspace_id: None,
+        dry_run: false,
     };
 
     // 3. Execute should fail (Security Block)
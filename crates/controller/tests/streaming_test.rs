@@ -0,0 +1,107 @@
+//! Streamed execution: thoughts, tool calls and observations arrive before the answer.
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::sync::Arc;
+
+use multi_agent_controller::{ReActConfig, ReActController};
+use multi_agent_core::{
+    traits::{ChatMessage, Controller, LlmClient, LlmResponse, LlmUsage, Tool, ToolRegistry},
+    types::{AgentResult, AgentStreamEvent, ToolOutput, UserIntent},
+};
+use multi_agent_skills::DefaultToolRegistry;
+
+/// Looks up the time, then answers with it.
+struct ClockLlm;
+
+#[async_trait]
+impl LlmClient for ClockLlm {
+    async fn complete(&self, prompt: &str) -> multi_agent_core::Result<LlmResponse> {
+        let content = if prompt.contains("12:00") {
+            "FINAL ANSWER: It is noon"
+        } else {
+            "THOUGHT: I need the current time.\nACTION: clock\nARGS: {}"
+        };
+        Ok(LlmResponse {
+            content: content.to_string(),
+            finish_reason: "stop".to_string(),
+            usage: LlmUsage {
+                prompt_tokens: 10,
+                completion_tokens: 10,
+                total_tokens: 20,
+            },
+            tool_calls: None,
+        })
+    }
+    async fn chat(&self, messages: &[ChatMessage]) -> multi_agent_core::Result<LlmResponse> {
+        let prompt: String = messages
+            .iter()
+            .skip(1)
+            .map(|m| m.content.as_str())
+            .collect();
+        self.complete(&prompt).await
+    }
+    async fn embed(&self, _text: &str) -> multi_agent_core::Result<Vec<f32>> {
+        Ok(vec![])
+    }
+}
+
+struct ClockTool;
+
+#[async_trait]
+impl Tool for ClockTool {
+    fn name(&self) -> &str {
+        "clock"
+    }
+    fn description(&self) -> &str {
+        "current time"
+    }
+    fn parameters(&self) -> serde_json::Value {
+        serde_json::json!({"type": "object"})
+    }
+    async fn execute(&self, _args: serde_json::Value) -> multi_agent_core::Result<ToolOutput> {
+        Ok(ToolOutput::text("12:00"))
+    }
+}
+
+#[tokio::test]
+async fn test_execute_streaming_reports_each_step() {
+    let registry = DefaultToolRegistry::new();
+    registry.register(Box::new(ClockTool)).await.unwrap();
+    let controller = ReActController::builder()
+        .with_config(ReActConfig {
+            max_iterations: 5,
+            ..ReActConfig::default()
+        })
+        .with_llm(Arc::new(ClockLlm))
+        .with_tools(Arc::new(registry))
+        .build();
+
+    let intent = UserIntent::ComplexMission {
+        goal: "What time is it?".into(),
+        context_summary: "test".into(),
+        visual_refs: vec![],
+        user_id: None,
+        workspace_id: None,
+        dry_run: false,
+    };
+    let events: Vec<AgentStreamEvent> = controller
+        .execute_streaming(intent, "trace".to_string())
+        .collect()
+        .await;
+
+    assert_eq!(
+        events.iter().map(|e| e.name()).collect::<Vec<_>>(),
+        vec!["thought", "tool_call", "observation", "final"]
+    );
+    assert!(
+        matches!(&events[0], AgentStreamEvent::Thought { content } if content == "I need the current time.")
+    );
+    assert!(
+        matches!(&events[2], AgentStreamEvent::Observation { tool: Some(tool), content } if tool == "clock" && content.contains("12:00"))
+    );
+    assert!(matches!(
+        &events[3],
+        AgentStreamEvent::Final { result: AgentResult::Text(text) } if text == "It is noon"
+    ));
+}
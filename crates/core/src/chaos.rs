@@ -0,0 +1,524 @@
+//! Fault injection for resilience testing.
+//!
+//! Compiled only with the `chaos` feature. A [`FaultInjector`] draws faults
+//! for one kind of call from its [`FaultConfig`]; the `Chaos*` wrappers put
+//! one in front of an LLM client, artifact store, session store or tool
+//! registry so failover, retries and circuit breakers can be exercised in
+//! CI and staging.
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::Value;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::config::{ChaosConfig, FaultConfig};
+use crate::error::{Error, Result};
+use crate::traits::{
+    ArtifactFilter, ArtifactMetadata, ArtifactStore, ChatMessage, Erasable, LlmClient, LlmResponse,
+    ProviderParams, SessionStore, SessionWatch, Tool, ToolRegistry,
+};
+use crate::types::{RefId, Session, SessionStatus, ToolDefinition, ToolOutput, ToolRiskLevel};
+
+/// A fault drawn for one call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// The call fails without taking effect.
+    Error,
+    /// The call fails partway; what that means depends on the call.
+    Partial,
+}
+
+/// Draws faults for one kind of call.
+pub struct FaultInjector {
+    target: &'static str,
+    config: FaultConfig,
+    rng: Mutex<StdRng>,
+    injected: AtomicU64,
+}
+
+impl FaultInjector {
+    pub fn new(target: &'static str, config: FaultConfig, seed: Option<u64>) -> Self {
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Self {
+            target,
+            config,
+            rng: Mutex::new(rng),
+            injected: AtomicU64::new(0),
+        }
+    }
+
+    /// Injector for `target` from `config.<target>`'s settings, or `None` if
+    /// chaos is disabled or the target injects nothing.
+    pub fn from_config(
+        target: &'static str,
+        config: &ChaosConfig,
+        faults: &FaultConfig,
+    ) -> Option<Arc<Self>> {
+        let active = faults.error_rate > 0.0
+            || faults.partial_rate > 0.0
+            || (faults.latency_rate > 0.0 && faults.latency_ms > 0);
+        (config.enabled && active).then(|| Arc::new(Self::new(target, faults.clone(), config.seed)))
+    }
+
+    /// Kind of call this injector is for, e.g. `llm`.
+    pub fn target(&self) -> &'static str {
+        self.target
+    }
+
+    /// Faults and delays injected so far.
+    pub fn injected(&self) -> u64 {
+        self.injected.load(Ordering::Relaxed)
+    }
+
+    fn applies(&self, op: &str) -> bool {
+        self.config.only.is_empty()
+            || self
+                .config
+                .only
+                .iter()
+                .any(|prefix| op.starts_with(prefix.as_str()))
+    }
+
+    /// Delay a call of `op` if drawn, then draw its fault.
+    pub async fn inject(&self, op: &str) -> Option<Fault> {
+        if !self.applies(op) {
+            return None;
+        }
+        let (delay, fault) = {
+            let mut rng = self.rng.lock().unwrap_or_else(|e| e.into_inner());
+            let delay = rng.gen::<f64>() < self.config.latency_rate;
+            let roll = rng.gen::<f64>();
+            let fault = if roll < self.config.error_rate {
+                Some(Fault::Error)
+            } else if roll < self.config.error_rate + self.config.partial_rate {
+                Some(Fault::Partial)
+            } else {
+                None
+            };
+            (delay, fault)
+        };
+        if delay || fault.is_some() {
+            self.injected.fetch_add(1, Ordering::Relaxed);
+        }
+        if delay {
+            tokio::time::sleep(Duration::from_millis(self.config.latency_ms)).await;
+        }
+        fault
+    }
+
+    /// Message of an injected failure of `op`.
+    pub fn message(&self, op: &str) -> String {
+        format!("chaos: injected {} failure in {}", self.target, op)
+    }
+}
+
+/// First half of `text`, cut at a character boundary.
+fn truncated(text: &str) -> String {
+    let mut end = text.len() / 2;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text[..end].to_string()
+}
+
+/// LLM client failing like a flaky provider.
+///
+/// Errors are provider errors so failover and circuit breakers trip;
+/// partial failures cut the reply in half with finish reason `length`.
+pub struct ChaosLlmClient {
+    inner: Arc<dyn LlmClient>,
+    injector: Arc<FaultInjector>,
+}
+
+impl ChaosLlmClient {
+    pub fn new(inner: Arc<dyn LlmClient>, injector: Arc<FaultInjector>) -> Self {
+        Self { inner, injector }
+    }
+
+    async fn reply(
+        &self,
+        op: &str,
+        call: impl std::future::Future<Output = Result<LlmResponse>>,
+    ) -> Result<LlmResponse> {
+        match self.injector.inject(op).await {
+            Some(Fault::Error) => Err(Error::ModelProvider(self.injector.message(op))),
+            Some(Fault::Partial) => call.await.map(|mut response| {
+                response.content = truncated(&response.content);
+                response.finish_reason = "length".to_string();
+                response.tool_calls = None;
+                response
+            }),
+            None => call.await,
+        }
+    }
+}
+
+#[async_trait]
+impl LlmClient for ChaosLlmClient {
+    async fn complete(&self, prompt: &str) -> Result<LlmResponse> {
+        self.reply("complete", self.inner.complete(prompt)).await
+    }
+
+    async fn chat(&self, messages: &[ChatMessage]) -> Result<LlmResponse> {
+        self.reply("chat", self.inner.chat(messages)).await
+    }
+
+    async fn chat_with_params(
+        &self,
+        messages: &[ChatMessage],
+        params: &ProviderParams,
+    ) -> Result<LlmResponse> {
+        self.reply("chat", self.inner.chat_with_params(messages, params))
+            .await
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        match self.injector.inject("embed").await {
+            Some(_) => Err(Error::ModelProvider(self.injector.message("embed"))),
+            None => self.inner.embed(text).await,
+        }
+    }
+}
+
+/// Artifact store failing like an unreliable object store.
+///
+/// Partial writes are stored but reported as failed; partial reads return
+/// the first half of the artifact.
+pub struct ChaosArtifactStore {
+    inner: Arc<dyn ArtifactStore>,
+    injector: Arc<FaultInjector>,
+}
+
+impl ChaosArtifactStore {
+    pub fn new(inner: Arc<dyn ArtifactStore>, injector: Arc<FaultInjector>) -> Self {
+        Self { inner, injector }
+    }
+
+    async fn write<T>(
+        &self,
+        op: &str,
+        call: impl std::future::Future<Output = Result<T>>,
+    ) -> Result<T> {
+        match self.injector.inject(op).await {
+            Some(Fault::Error) => Err(Error::storage(self.injector.message(op))),
+            Some(Fault::Partial) => {
+                call.await?;
+                Err(Error::storage(self.injector.message(op)))
+            }
+            None => call.await,
+        }
+    }
+
+    async fn read<T>(
+        &self,
+        op: &str,
+        call: impl std::future::Future<Output = Result<T>>,
+    ) -> Result<T> {
+        match self.injector.inject(op).await {
+            Some(_) => Err(Error::storage(self.injector.message(op))),
+            None => call.await,
+        }
+    }
+
+    async fn read_data(
+        &self,
+        op: &str,
+        call: impl std::future::Future<Output = Result<Option<Bytes>>>,
+    ) -> Result<Option<Bytes>> {
+        match self.injector.inject(op).await {
+            Some(Fault::Error) => Err(Error::storage(self.injector.message(op))),
+            Some(Fault::Partial) => Ok(call.await?.map(|data| data.slice(..data.len() / 2))),
+            None => call.await,
+        }
+    }
+}
+
+#[async_trait]
+impl Erasable for ChaosArtifactStore {
+    async fn erase_user(&self, user_id: &str) -> Result<usize> {
+        self.inner.erase_user(user_id).await
+    }
+
+    fn store_name(&self) -> &'static str {
+        self.inner.store_name()
+    }
+}
+
+#[async_trait]
+impl ArtifactStore for ChaosArtifactStore {
+    async fn save(&self, data: Bytes) -> Result<RefId> {
+        self.write("save", self.inner.save(data)).await
+    }
+
+    async fn save_with_id(&self, id: &RefId, data: Bytes) -> Result<()> {
+        self.write("save", self.inner.save_with_id(id, data)).await
+    }
+
+    async fn save_with_type(&self, data: Bytes, content_type: &str) -> Result<RefId> {
+        self.write("save", self.inner.save_with_type(data, content_type))
+            .await
+    }
+
+    async fn load(&self, id: &RefId) -> Result<Option<Bytes>> {
+        self.read_data("load", self.inner.load(id)).await
+    }
+
+    async fn load_range(&self, id: &RefId, start: u64, end: u64) -> Result<Option<Bytes>> {
+        self.read_data("load", self.inner.load_range(id, start, end))
+            .await
+    }
+
+    async fn presigned_url(&self, id: &RefId, expires_in: Duration) -> Result<Option<String>> {
+        self.read("presigned_url", self.inner.presigned_url(id, expires_in))
+            .await
+    }
+
+    async fn delete(&self, id: &RefId) -> Result<()> {
+        self.write("delete", self.inner.delete(id)).await
+    }
+
+    async fn exists(&self, id: &RefId) -> Result<bool> {
+        self.read("exists", self.inner.exists(id)).await
+    }
+
+    async fn metadata(&self, id: &RefId) -> Result<Option<ArtifactMetadata>> {
+        self.read("metadata", self.inner.metadata(id)).await
+    }
+
+    async fn list(&self, filter: ArtifactFilter) -> Result<Vec<ArtifactMetadata>> {
+        self.read("list", self.inner.list(filter)).await
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        self.inner.health_check().await
+    }
+}
+
+/// Session store failing like an unreliable Redis.
+///
+/// Partial writes are stored but reported as failed; partial listings miss
+/// half of their sessions.
+pub struct ChaosSessionStore {
+    inner: Arc<dyn SessionStore>,
+    injector: Arc<FaultInjector>,
+}
+
+impl ChaosSessionStore {
+    pub fn new(inner: Arc<dyn SessionStore>, injector: Arc<FaultInjector>) -> Self {
+        Self { inner, injector }
+    }
+
+    async fn write(
+        &self,
+        op: &str,
+        call: impl std::future::Future<Output = Result<()>>,
+    ) -> Result<()> {
+        match self.injector.inject(op).await {
+            Some(Fault::Error) => Err(Error::storage(self.injector.message(op))),
+            Some(Fault::Partial) => {
+                call.await?;
+                Err(Error::storage(self.injector.message(op)))
+            }
+            None => call.await,
+        }
+    }
+
+    async fn list<T>(
+        &self,
+        op: &str,
+        call: impl std::future::Future<Output = Result<Vec<T>>>,
+    ) -> Result<Vec<T>> {
+        match self.injector.inject(op).await {
+            Some(Fault::Error) => Err(Error::storage(self.injector.message(op))),
+            Some(Fault::Partial) => call.await.map(|mut items| {
+                items.truncate(items.len() / 2);
+                items
+            }),
+            None => call.await,
+        }
+    }
+}
+
+#[async_trait]
+impl SessionStore for ChaosSessionStore {
+    async fn save(&self, session: &Session) -> Result<()> {
+        self.write("save", self.inner.save(session)).await
+    }
+
+    async fn load(&self, session_id: &str) -> Result<Option<Session>> {
+        match self.injector.inject("load").await {
+            Some(_) => Err(Error::storage(self.injector.message("load"))),
+            None => self.inner.load(session_id).await,
+        }
+    }
+
+    async fn delete(&self, session_id: &str) -> Result<()> {
+        self.write("delete", self.inner.delete(session_id)).await
+    }
+
+    async fn list_running(&self) -> Result<Vec<String>> {
+        self.list("list", self.inner.list_running()).await
+    }
+
+    async fn list_sessions(
+        &self,
+        status: Option<SessionStatus>,
+        user_id: Option<&str>,
+    ) -> Result<Vec<Session>> {
+        self.list("list", self.inner.list_sessions(status, user_id))
+            .await
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        self.inner.health_check().await
+    }
+
+    async fn watch(self: Arc<Self>, session_id: &str) -> Result<SessionWatch> {
+        match self.injector.inject("watch").await {
+            Some(_) => Err(Error::storage(self.injector.message("watch"))),
+            None => self.inner.clone().watch(session_id).await,
+        }
+    }
+}
+
+/// Tool registry whose executions fail or return truncated output.
+///
+/// Operations are tool names, so `only` can single out flaky tools.
+pub struct ChaosToolRegistry {
+    inner: Arc<dyn ToolRegistry>,
+    injector: Arc<FaultInjector>,
+}
+
+impl ChaosToolRegistry {
+    pub fn new(inner: Arc<dyn ToolRegistry>, injector: Arc<FaultInjector>) -> Self {
+        Self { inner, injector }
+    }
+}
+
+#[async_trait]
+impl ToolRegistry for ChaosToolRegistry {
+    async fn register(&self, tool: Box<dyn Tool>) -> Result<()> {
+        self.inner.register(tool).await
+    }
+
+    async fn get(&self, name: &str) -> Result<Option<Box<dyn Tool>>> {
+        self.inner.get(name).await
+    }
+
+    async fn list(&self) -> Result<Vec<ToolDefinition>> {
+        self.inner.list().await
+    }
+
+    async fn execute(&self, name: &str, args: Value) -> Result<ToolOutput> {
+        match self.injector.inject(name).await {
+            Some(Fault::Error) => Err(Error::tool_execution(self.injector.message(name))),
+            Some(Fault::Partial) => self.inner.execute(name, args).await.map(|mut output| {
+                output.content = truncated(&output.content);
+                output
+            }),
+            None => self.inner.execute(name, args).await,
+        }
+    }
+
+    async fn get_risk_level(&self, name: &str) -> ToolRiskLevel {
+        self.inner.get_risk_level(name).await
+    }
+
+    async fn requires_approval(&self, name: &str) -> bool {
+        self.inner.requires_approval(name).await
+    }
+
+    async fn requires_approval_for(&self, name: &str, args: &Value) -> bool {
+        self.inner.requires_approval_for(name, args).await
+    }
+
+    async fn awaits_human_input(&self, name: &str) -> bool {
+        self.inner.awaits_human_input(name).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mocks::MockLlm;
+
+    fn faults(error_rate: f64, partial_rate: f64) -> FaultConfig {
+        FaultConfig {
+            error_rate,
+            partial_rate,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_injector_rates_and_filter() {
+        let injector = FaultInjector::new("llm", faults(1.0, 0.0), Some(7));
+        assert_eq!(injector.inject("chat").await, Some(Fault::Error));
+
+        let injector = FaultInjector::new("llm", faults(0.0, 1.0), Some(7));
+        assert_eq!(injector.inject("chat").await, Some(Fault::Partial));
+
+        let injector = FaultInjector::new(
+            "tools",
+            FaultConfig {
+                only: vec!["fetch".into()],
+                ..faults(1.0, 0.0)
+            },
+            Some(7),
+        );
+        assert_eq!(injector.inject("echo").await, None);
+        assert_eq!(injector.inject("fetch").await, Some(Fault::Error));
+        assert_eq!(injector.injected(), 1);
+
+        // Same seed, same sequence
+        let draws = |seed| async move {
+            let injector = FaultInjector::new("llm", faults(0.3, 0.3), Some(seed));
+            let mut draws = Vec::new();
+            for _ in 0..32 {
+                draws.push(injector.inject("chat").await);
+            }
+            draws
+        };
+        assert_eq!(draws(42).await, draws(42).await);
+
+        let disabled = ChaosConfig {
+            llm: faults(1.0, 0.0),
+            ..Default::default()
+        };
+        assert!(FaultInjector::from_config("llm", &disabled, &disabled.llm).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_llm_faults() {
+        let inner: Arc<dyn LlmClient> = Arc::new(MockLlm::constant("abcdef"));
+        let failing = ChaosLlmClient::new(
+            inner.clone(),
+            Arc::new(FaultInjector::new("llm", faults(1.0, 0.0), None)),
+        );
+        assert!(matches!(
+            failing.complete("hi").await,
+            Err(Error::ModelProvider(_))
+        ));
+
+        let partial = ChaosLlmClient::new(
+            inner,
+            Arc::new(FaultInjector::new("llm", faults(0.0, 1.0), None)),
+        );
+        let response = partial.complete("hi").await.unwrap();
+        assert_eq!(response.content, "abc");
+        assert_eq!(response.finish_reason, "length");
+    }
+
+    #[test]
+    fn test_truncated_respects_char_boundaries() {
+        assert_eq!(truncated("héllo"), "hé");
+        assert_eq!(truncated(""), "");
+    }
+}
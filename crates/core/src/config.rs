@@ -1,6 +1,6 @@
 use config::{Config, ConfigError, Environment, File};
 use secrecy::Secret;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct AppConfig {
@@ -11,12 +11,195 @@ pub struct AppConfig {
     pub governance: GovernanceConfig,
     pub model_gateway: ModelGatewayConfig,
     pub safety: SafetyConfig,
+    #[serde(default)]
+    pub email: EmailConfig,
+    #[serde(default)]
+    pub events: EventBusConfig,
+    #[serde(default)]
+    pub i18n: I18nConfig,
+    #[serde(default)]
+    pub chaos: ChaosConfig,
+    #[serde(default)]
+    pub sandbox: ExecSandboxConfig,
+}
+
+/// Localization of prompts and user-facing messages; see
+/// [`crate::prompts::PromptRegistry`].
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct I18nConfig {
+    /// Locale used when a request names none, or none of its fallbacks.
+    pub default_locale: String,
+    /// Directory with one `<locale>.yaml` prompt catalog per locale.
+    pub prompts_dir: Option<String>,
+}
+
+impl Default for I18nConfig {
+    fn default() -> Self {
+        Self {
+            default_locale: "en".into(),
+            prompts_dir: None,
+        }
+    }
+}
+
+/// Containers that run sandbox tool commands.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ExecSandboxConfig {
+    /// Profile of the throwaway container each high-risk command runs in.
+    pub isolated: IsolatedProfileConfig,
+    /// Command patterns `sandbox_shell` refuses to run.
+    pub commands: CommandPolicyConfig,
+}
+
+/// Overrides for the isolated profile. Unset fields keep the values derived
+/// from the pooled sandbox: half its memory and CPU, a quarter of its pids
+/// and no network.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct IsolatedProfileConfig {
+    pub image: Option<String>,
+    pub memory_limit_mb: Option<u64>,
+    /// CPU quota in microseconds per 100ms period (100000 = one core).
+    pub cpu_quota: Option<i64>,
+    pub pids_limit: Option<i64>,
+    pub timeout_secs: Option<u64>,
+}
+
+/// How a rule matches a command.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CommandMatcher {
+    /// Regex over the whole command line.
+    Regex { pattern: String },
+    /// Program name regex (matched against the basename, anchored), with an
+    /// optional regex over the joined arguments.
+    Program {
+        program: String,
+        #[serde(default)]
+        args: Option<String>,
+    },
+    /// A stage running `from` piped directly into a stage running `into`.
+    Pipe {
+        from: Vec<String>,
+        into: Vec<String>,
+    },
+}
+
+/// A deny rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandRule {
+    pub id: String,
+    #[serde(flatten)]
+    pub matcher: CommandMatcher,
+    /// Shown to the model so it can rewrite the command.
+    pub reason: String,
+}
+
+/// Allow/deny rules for `sandbox_shell` commands, checked before they reach
+/// the sandbox.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommandPolicyConfig {
+    /// Extra deny rules.
+    pub deny: Vec<CommandRule>,
+    /// Program name regexes; when non-empty, any other program is denied.
+    pub allow: Vec<String>,
+    /// Keep the built-in deny rules (default: true).
+    pub include_defaults: bool,
+}
+
+impl Default for CommandPolicyConfig {
+    fn default() -> Self {
+        Self {
+            deny: Vec::new(),
+            allow: Vec::new(),
+            include_defaults: true,
+        }
+    }
+}
+
+/// Fault injection for resilience testing. Only builds with the `chaos`
+/// feature honor it; release images are built without it.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ChaosConfig {
+    pub enabled: bool,
+    /// Seed for reproducible fault sequences; random when unset.
+    pub seed: Option<u64>,
+    /// Calls to the default LLM client.
+    pub llm: FaultConfig,
+    /// Artifact store calls; the cold tier (S3, GCS, Azure) when configured.
+    pub artifacts: FaultConfig,
+    /// Session store calls (Redis when configured).
+    pub sessions: FaultConfig,
+    /// Tool executions.
+    pub tools: FaultConfig,
+    /// Requests to the gateway's HTTP routes.
+    pub http: FaultConfig,
+}
+
+/// Faults injected into one kind of call. Rates are probabilities per call.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct FaultConfig {
+    /// Share of calls delayed by `latency_ms`.
+    pub latency_rate: f64,
+    pub latency_ms: u64,
+    /// Share of calls failing without taking effect.
+    pub error_rate: f64,
+    /// Share of calls failing partway: LLM replies and tool outputs are
+    /// truncated, store reads return truncated data, and store writes and
+    /// HTTP requests take effect but report a failure.
+    pub partial_rate: f64,
+    /// Prefixes of the operations (`chat`, `save`, ...), tool names or
+    /// request paths to inject into; empty means all.
+    pub only: Vec<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct SafetyConfig {
     pub max_download_size_bytes: u64,
     pub allowed_content_types: Vec<String>,
+    /// Refuse plain-HTTP fetches.
+    #[serde(default)]
+    pub require_https: bool,
+    /// Refuse known URL shorteners, whose destination cannot be checked up front.
+    #[serde(default)]
+    pub block_url_shorteners: bool,
+    /// File of blocked domains, one rule per line (`#` comments, `*.` wildcards).
+    #[serde(default)]
+    pub blocklist_path: Option<String>,
+    /// Accepted SHA-256 fingerprints of the leaf certificate, per domain.
+    #[serde(default)]
+    pub pinned_certificates: std::collections::HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub quarantine: QuarantineConfig,
+}
+
+/// Checks applied to downloads before they are written to the sandbox.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct QuarantineConfig {
+    /// Content types a download may declare; empty allows any.
+    pub allowed_content_types: Vec<String>,
+    /// External scanner run on the quarantined file, `{path}` being replaced
+    /// by its location. Exit status 0 means clean and 1 means a threat was
+    /// found (the `clamscan` convention); any other status fails the download.
+    /// Empty disables scanning.
+    pub scan_command: Vec<String>,
+    pub scan_timeout_secs: u64,
+}
+
+impl Default for QuarantineConfig {
+    fn default() -> Self {
+        Self {
+            allowed_content_types: Vec::new(),
+            scan_command: Vec::new(),
+            scan_timeout_secs: 60,
+        }
+    }
 }
 
 impl Default for SafetyConfig {
@@ -32,14 +215,114 @@ impl Default for SafetyConfig {
                 "text/xml".into(),
                 "application/xhtml+xml".into(),
             ],
+            require_https: false,
+            block_url_shorteners: false,
+            blocklist_path: None,
+            pinned_certificates: Default::default(),
+            quarantine: QuarantineConfig::default(),
+        }
+    }
+}
+
+/// Settings for the `send_email` tool.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct EmailConfig {
+    /// Admin provider (vendor `smtp`) to send through. Defaults to the first active SMTP provider.
+    pub provider_id: Option<String>,
+    /// Maximum number of recipients (to + cc + bcc) per message.
+    pub max_recipients: usize,
+    /// Named templates; `{{variable}}` placeholders are filled from the tool arguments.
+    pub templates: std::collections::HashMap<String, EmailTemplate>,
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self {
+            provider_id: None,
+            max_recipients: 20,
+            templates: std::collections::HashMap::new(),
         }
     }
 }
 
+/// Where structured events and dashboard log lines are fanned out.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct EventBusConfig {
+    pub backend: EventBusBackend,
+    /// Subject (NATS) or record key (Kafka) prefix; the event type is appended.
+    pub subject_prefix: String,
+    pub nats: Option<NatsBusConfig>,
+    pub kafka: Option<KafkaBusConfig>,
+}
+
+impl Default for EventBusConfig {
+    fn default() -> Self {
+        Self {
+            backend: EventBusBackend::InProcess,
+            subject_prefix: "opencoordex.events".into(),
+            nats: None,
+            kafka: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventBusBackend {
+    /// Broadcast within this process only.
+    InProcess,
+    Nats,
+    Kafka,
+}
+
+/// NATS server, optionally with a JetStream stream for durability.
+#[derive(Debug, Deserialize, Clone)]
+pub struct NatsBusConfig {
+    /// `nats://host:port`
+    pub url: String,
+    #[serde(default)]
+    pub token: Option<Secret<String>>,
+    /// JetStream stream capturing `<subject_prefix>.>`; created if missing.
+    #[serde(default)]
+    pub stream: Option<String>,
+    /// On startup, replay events from the stream this far back.
+    #[serde(default)]
+    pub replay_secs: u64,
+}
+
+/// Kafka reached through a Confluent-compatible REST proxy. Brokers are not
+/// spoken to directly, so deployments without a REST proxy (or with one that
+/// lacks the v2 consumer API) cannot use this backend.
+#[derive(Debug, Deserialize, Clone)]
+pub struct KafkaBusConfig {
+    pub rest_url: String,
+    pub topic: String,
+    /// Consumer group prefix (default `opencoordex-events`); each replica
+    /// consumes as `<group>-<replica>` so every replica sees every event and
+    /// resumes from its committed offset.
+    #[serde(default)]
+    pub consumer_group: Option<String>,
+}
+
+/// A reusable email template.
+#[derive(Debug, Deserialize, Clone)]
+pub struct EmailTemplate {
+    pub subject: String,
+    pub body: String,
+    /// Send the body as `text/html` instead of `text/plain`.
+    #[serde(default)]
+    pub html: bool,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
+    /// Serve on this Unix domain socket instead of `host:port`.
+    #[serde(default)]
+    pub unix_socket: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -48,6 +331,209 @@ pub struct GatewayConfig {
     pub semantic_cache_threshold: f64,
     pub allowed_origins: Vec<String>,
     pub tls: TlsConfig,
+    #[serde(default)]
+    pub response: ResponseLimitConfig,
+    #[serde(default)]
+    pub cache: SemanticCacheConfig,
+    #[serde(default)]
+    pub body_limits: BodyLimitConfig,
+    #[serde(default)]
+    pub security_headers: SecurityHeadersConfig,
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    #[serde(default)]
+    pub concurrency: RouteConcurrencyConfig,
+}
+
+/// Header in which trusted proxies report the client address.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ForwardedHeader {
+    /// `X-Forwarded-For`, as set by nginx, HAProxy and AWS ALB.
+    #[default]
+    XForwardedFor,
+    /// RFC 7239 `Forwarded`.
+    Forwarded,
+}
+
+/// How the rate limiter identifies clients.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    /// Reverse proxies or load balancers, as IPs or CIDR ranges, whose
+    /// forwarding header is believed.
+    pub trusted_proxies: Vec<String>,
+    /// The one header the trusted proxies set. The other is ignored, since
+    /// a proxy passes it through from the client untouched.
+    pub forwarded_header: ForwardedHeader,
+    /// Key authenticated requests on the caller's identity instead of their
+    /// IP; anonymous requests are still keyed on IP.
+    pub key_by_identity: bool,
+}
+
+/// Semantic cache lifetime and per-workspace switches.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct SemanticCacheConfig {
+    /// Seconds a cached answer stays valid.
+    pub ttl_secs: u64,
+    /// Workspaces that never read from or write to the cache.
+    pub disabled_workspaces: Vec<String>,
+}
+
+impl Default for SemanticCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl_secs: 3600,
+            disabled_workspaces: Vec::new(),
+        }
+    }
+}
+
+/// Ceiling on results returned inline by the gateway.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ResponseLimitConfig {
+    /// Results larger than this many bytes are stored as artifacts and
+    /// returned by reference; 0 disables the limit.
+    pub max_bytes: usize,
+    /// Characters of an oversized result returned inline as a preview.
+    pub preview_chars: usize,
+}
+
+impl Default for ResponseLimitConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: 256 * 1024,
+            preview_chars: 2000,
+        }
+    }
+}
+
+/// Ceilings on request bodies accepted by the gateway.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct BodyLimitConfig {
+    /// Bytes accepted for a request body unless a route overrides it.
+    pub max_bytes: usize,
+    /// Bytes accepted by the multipart artifact upload endpoint.
+    pub upload_max_bytes: usize,
+    /// Per-route ceilings keyed by path prefix, e.g. `/v1/agent/research`.
+    pub routes: std::collections::HashMap<String, usize>,
+}
+
+impl BodyLimitConfig {
+    /// Ceiling for `path`: the longest matching route prefix, else the default.
+    pub fn limit_for(&self, path: &str) -> usize {
+        self.routes
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(self.max_bytes, |(_, limit)| *limit)
+    }
+}
+
+impl Default for BodyLimitConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: 1024 * 1024,
+            upload_max_bytes: 50 * 1024 * 1024,
+            routes: std::collections::HashMap::new(),
+        }
+    }
+}
+
+/// Ceilings on requests in flight per route group, with load shedding.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct RouteConcurrencyConfig {
+    pub enabled: bool,
+    /// Route groups keyed by name, e.g. `chat`; the name labels metrics.
+    pub routes: std::collections::HashMap<String, RouteConcurrency>,
+    /// `Retry-After` sent with shed requests.
+    pub retry_after_secs: u64,
+}
+
+impl Default for RouteConcurrencyConfig {
+    fn default() -> Self {
+        let group = |prefixes: &[&str], max_in_flight, max_queued| RouteConcurrency {
+            prefixes: prefixes.iter().map(|p| p.to_string()).collect(),
+            max_in_flight,
+            max_queued,
+            queue_timeout_secs: 30,
+        };
+        Self {
+            enabled: true,
+            routes: [
+                ("chat", group(&["/v1/chat", "/v1/agent/chat"], 32, 64)),
+                ("research", group(&["/v1/agent/research"], 8, 16)),
+                ("admin", group(&["/v1/admin"], 16, 32)),
+            ]
+            .into_iter()
+            .map(|(name, group)| (name.to_string(), group))
+            .collect(),
+            retry_after_secs: 5,
+        }
+    }
+}
+
+/// Concurrency ceiling shared by the routes under `prefixes`.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct RouteConcurrency {
+    pub prefixes: Vec<String>,
+    /// Requests handled at once; 0 disables the ceiling.
+    pub max_in_flight: usize,
+    /// Requests waiting for a slot before further ones are shed.
+    pub max_queued: usize,
+    /// Seconds a queued request waits for a slot before it is shed.
+    pub queue_timeout_secs: u64,
+}
+
+impl Default for RouteConcurrency {
+    fn default() -> Self {
+        Self {
+            prefixes: Vec::new(),
+            max_in_flight: 0,
+            max_queued: 0,
+            queue_timeout_secs: 30,
+        }
+    }
+}
+
+/// Security headers added to gateway responses. An empty value omits the
+/// header; a header set by the handler itself is left untouched.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct SecurityHeadersConfig {
+    pub enabled: bool,
+    pub content_security_policy: String,
+    pub frame_options: String,
+    pub referrer_policy: String,
+    /// `max-age` of `Strict-Transport-Security`, sent only when TLS is on.
+    pub hsts_max_age_secs: u64,
+    /// Reject state-changing requests authenticated by cookie whose `Origin`
+    /// is neither the gateway itself nor an allowed origin.
+    pub csrf_protection: bool,
+}
+
+impl Default for SecurityHeadersConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            content_security_policy: "default-src 'self'; \
+                script-src 'self' 'unsafe-inline'; \
+                style-src 'self' 'unsafe-inline' https://fonts.googleapis.com https://cdnjs.cloudflare.com; \
+                font-src 'self' https://fonts.gstatic.com https://cdnjs.cloudflare.com; \
+                img-src 'self' data:; connect-src 'self' ws: wss:; \
+                frame-ancestors 'none'; base-uri 'self'; form-action 'self'"
+                .into(),
+            frame_options: "DENY".into(),
+            referrer_policy: "strict-origin-when-cross-origin".into(),
+            hsts_max_age_secs: 31_536_000,
+            csrf_protection: true,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -55,13 +541,203 @@ pub struct TlsConfig {
     pub enabled: bool,
     pub cert_path: Option<String>,
     pub key_path: Option<String>,
+    /// CA bundle that client certificates are verified against. Setting it
+    /// enables mutual TLS.
     pub ca_path: Option<String>,
+    /// Refuse connections without a client certificate; otherwise clients
+    /// without one fall back to token authentication.
+    #[serde(default)]
+    pub require_client_cert: bool,
+    /// Identities of client certificates, keyed by subject common name.
+    /// Certificates without an entry authenticate as their common name with
+    /// no roles.
+    #[serde(default)]
+    pub client_identities: std::collections::HashMap<String, ClientCertIdentity>,
+}
+
+/// RBAC identity granted to a client certificate.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ClientCertIdentity {
+    /// User id; defaults to the certificate's common name.
+    pub user_id: Option<String>,
+    pub roles: Vec<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct ControllerConfig {
     pub max_react_iterations: u32,
     pub state_persistence: bool,
+    #[serde(default)]
+    pub concurrency: MissionConcurrencyConfig,
+    #[serde(default)]
+    pub idle: SessionIdleConfig,
+    #[serde(default)]
+    pub deadlock: DeadlockConfig,
+    #[serde(default)]
+    pub observations: ObservationConfig,
+    #[serde(default)]
+    pub planning: PlanningConfig,
+    #[serde(default)]
+    pub speculative: SpeculativeDraftingConfig,
+}
+
+/// Speculative drafting: a cheap model drafts each step and an expensive one
+/// verifies when the drafts disagree. Workspaces opt in through the
+/// `speculative` feature flag.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct SpeculativeDraftingConfig {
+    /// Provider key (`provider:model`) of the drafting model; unset disables
+    /// speculative drafting.
+    pub drafter: Option<String>,
+    /// Provider key of the verifying model; the default client when unset.
+    pub verifier: Option<String>,
+    /// Drafts sampled per step; they must all agree to skip verification.
+    pub draft_samples: usize,
+    /// Whether workspaces without a `speculative` flag value use it.
+    pub enabled_by_default: bool,
+}
+
+impl Default for SpeculativeDraftingConfig {
+    fn default() -> Self {
+        Self {
+            drafter: None,
+            verifier: None,
+            draft_samples: 2,
+            enabled_by_default: false,
+        }
+    }
+}
+
+/// Plan-and-solve: the agent drafts a plan, which is priced before it runs.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct PlanningConfig {
+    pub enabled: bool,
+    /// Plans estimated above this many tokens need approval; 0 disables.
+    pub approval_tokens: u64,
+    /// Plans estimated above this cost need approval; 0 disables.
+    pub approval_cost_usd: f64,
+    /// Plans mentioning a tool at or above this risk need approval.
+    pub approval_risk: Option<crate::types::ToolRiskLevel>,
+}
+
+/// Shortening of long tool observations.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ObservationConfig {
+    /// Observations longer than this many characters are summarized and the
+    /// full output stored as an artifact; 0 keeps them verbatim.
+    pub max_chars: usize,
+}
+
+impl Default for ObservationConfig {
+    fn default() -> Self {
+        Self { max_chars: 4_000 }
+    }
+}
+
+/// What a session does after too many consecutive approval denials.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DeadlockStrategy {
+    /// Terminate the session with an error.
+    #[default]
+    Fail,
+    /// Ask the human for revised instructions and carry on with them.
+    AskHuman,
+    /// Have the agent propose a plan and continue once it is approved.
+    PlanApproval,
+    /// Stop acting and answer with a report of what was found so far.
+    ReportOnly,
+}
+
+/// Recovery from approval deadlocks.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct DeadlockConfig {
+    /// Consecutive denials that count as a deadlock.
+    pub max_rejections: usize,
+    pub strategy: DeadlockStrategy,
+    /// Per-workspace overrides of `strategy`.
+    pub workspaces: std::collections::HashMap<String, DeadlockStrategy>,
+}
+
+impl DeadlockConfig {
+    /// Strategy for sessions in `workspace`.
+    pub fn strategy_for(&self, workspace: Option<&str>) -> DeadlockStrategy {
+        workspace
+            .and_then(|w| self.workspaces.get(w))
+            .copied()
+            .unwrap_or(self.strategy)
+    }
+}
+
+impl Default for DeadlockConfig {
+    fn default() -> Self {
+        Self {
+            max_rejections: 3,
+            strategy: DeadlockStrategy::Fail,
+            workspaces: std::collections::HashMap::new(),
+        }
+    }
+}
+
+/// Pausing sessions that wait on a human, and freeing their sandbox.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct SessionIdleConfig {
+    /// Seconds a session may wait on an approval or an answer before it is
+    /// paused and the sandbox hibernated; 0 disables the detector.
+    pub pause_after_secs: u64,
+    /// Seconds between idle checks.
+    pub check_interval_secs: u64,
+}
+
+impl Default for SessionIdleConfig {
+    fn default() -> Self {
+        Self {
+            pause_after_secs: 900,
+            check_interval_secs: 60,
+        }
+    }
+}
+
+/// Limits on missions running at the same time.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct MissionConcurrencyConfig {
+    /// Missions one user may run at once; 0 means unlimited.
+    pub max_per_user: usize,
+    /// Missions one workspace may run at once; 0 means unlimited.
+    pub max_per_workspace: usize,
+    /// Per-workspace overrides of `max_per_workspace`.
+    pub workspaces: std::collections::HashMap<String, usize>,
+    /// Seconds an excess mission waits for a slot before it is rejected;
+    /// 0 rejects immediately.
+    pub queue_timeout_secs: u64,
+}
+
+impl Default for MissionConcurrencyConfig {
+    fn default() -> Self {
+        Self {
+            max_per_user: 0,
+            max_per_workspace: 0,
+            workspaces: std::collections::HashMap::new(),
+            queue_timeout_secs: 30,
+        }
+    }
+}
+
+impl MissionConcurrencyConfig {
+    /// Limit for `workspace_id`, after overrides.
+    pub fn workspace_limit(&self, workspace_id: &str) -> usize {
+        self.workspaces
+            .get(workspace_id)
+            .copied()
+            .unwrap_or(self.max_per_workspace)
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -71,7 +747,260 @@ pub struct StoreConfig {
     pub s3_bucket: Option<String>,
     pub s3_endpoint: Option<String>,
     pub redis_url: Option<String>,
+    /// Postgres connection URL. When set, sessions and providers are kept
+    /// in Postgres instead of Redis or memory.
+    #[serde(default)]
+    pub postgres_url: Option<String>,
     pub encryption: EncryptionConfig,
+    #[serde(default)]
+    pub s3_retry: S3RetryConfig,
+    #[serde(default)]
+    pub s3_sse: S3SseConfig,
+    /// Native Google Cloud Storage cold tier (used when no S3 bucket is set).
+    #[serde(default)]
+    pub gcs: Option<GcsStoreConfig>,
+    /// Native Azure Blob Storage cold tier (used when neither S3 nor GCS is set).
+    #[serde(default)]
+    pub azure_blob: Option<AzureBlobStoreConfig>,
+    #[serde(default)]
+    pub artifact_download: ArtifactDownloadConfig,
+    #[serde(default)]
+    pub file_policy: FileTypePolicyConfig,
+    /// Directory for runtime state files (see [`StateFile`]). When unset, files
+    /// keep their legacy locations in the working directory, or are disabled
+    /// if an external backend is configured.
+    #[serde(default)]
+    pub data_dir: Option<String>,
+    #[serde(default)]
+    pub leader_election: LeaderElectionConfig,
+    #[serde(default)]
+    pub session_encoding: SessionEncodingConfig,
+}
+
+/// Runtime state kept in local files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateFile {
+    Providers,
+    NetworkPolicy,
+    Secrets,
+    Policy,
+    RoutingPolicies,
+    Workspaces,
+    Onboarding,
+    FeatureFlags,
+}
+
+impl StateFile {
+    /// Path relative to `store.data_dir`.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::Providers => "providers.json",
+            Self::NetworkPolicy => "network_policy.json",
+            Self::Secrets => "secrets.json",
+            Self::Policy => "policies/default.yaml",
+            Self::RoutingPolicies => "routing/policies.json",
+            Self::Workspaces => "workspaces/store.json",
+            Self::Onboarding => "onboarding.json",
+            Self::FeatureFlags => "feature_flags.json",
+        }
+    }
+
+    /// Location used when `store.data_dir` is unset.
+    pub fn legacy_path(self) -> &'static str {
+        match self {
+            Self::Providers | Self::NetworkPolicy | Self::Secrets => self.file_name(),
+            Self::Policy => ".sovereign_claw/policies/default.yaml",
+            Self::RoutingPolicies => ".sovereign_claw/routing/policies.json",
+            Self::Workspaces => ".sovereign_claw/workspaces/store.json",
+            Self::Onboarding => ".sovereign_claw/onboarding.json",
+            Self::FeatureFlags => ".sovereign_claw/feature_flags.json",
+        }
+    }
+}
+
+/// Which artifacts may be stored, enforced on every artifact store write.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct FileTypePolicyConfig {
+    /// Content types that may be stored, `image/*` style wildcards allowed.
+    /// Untyped writes count as `application/octet-stream`. Empty allows any.
+    pub allowed_types: Vec<String>,
+    /// File extensions whose content types are allowed as well.
+    pub allowed_extensions: Vec<String>,
+    /// Largest artifact stored, in bytes; 0 means unlimited.
+    pub max_bytes: u64,
+    /// Caps for specific content types (or `type/*`), overriding `max_bytes`.
+    pub type_max_bytes: std::collections::HashMap<String, u64>,
+}
+
+impl Default for FileTypePolicyConfig {
+    fn default() -> Self {
+        Self {
+            allowed_types: Vec::new(),
+            allowed_extensions: Vec::new(),
+            max_bytes: 512 * 1024 * 1024,
+            type_max_bytes: Default::default(),
+        }
+    }
+}
+
+/// Settings for the artifact retrieval API.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ArtifactDownloadConfig {
+    /// Artifacts at least this large are served via a pre-signed URL redirect when the
+    /// backing store supports it, instead of being streamed through the gateway.
+    pub presign_threshold_bytes: u64,
+    /// Lifetime of generated pre-signed URLs in seconds.
+    pub presign_expiry_secs: u64,
+}
+
+impl Default for ArtifactDownloadConfig {
+    fn default() -> Self {
+        Self {
+            presign_threshold_bytes: 64 * 1024 * 1024,
+            presign_expiry_secs: 900,
+        }
+    }
+}
+
+/// Lease settings for singleton background jobs (alerts, anchoring, spend caps, re-crawl).
+/// Leases are held in Redis when `store.redis_url` is set, otherwise per process.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct LeaderElectionConfig {
+    /// How long a lease survives without renewal; bounds failover time.
+    pub lease_ttl_secs: u64,
+    /// Redis key prefix for leases.
+    pub key_prefix: String,
+}
+
+impl Default for LeaderElectionConfig {
+    fn default() -> Self {
+        Self {
+            lease_ttl_secs: 15,
+            key_prefix: "opencoordex:leader".into(),
+        }
+    }
+}
+
+/// Serialization format of sessions stored in Redis.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionFormat {
+    #[default]
+    Json,
+    #[serde(alias = "messagepack")]
+    Msgpack,
+    Cbor,
+}
+
+/// Compression applied to serialized sessions.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionCompression {
+    #[default]
+    None,
+    Zstd,
+}
+
+/// How `RedisSessionStore` encodes sessions. Every value is tagged with its
+/// encoding, so the settings can change at any time: sessions written
+/// earlier, including legacy untagged JSON, stay readable. Plain JSON is
+/// written untagged so that older instances can read it during a rollout.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct SessionEncodingConfig {
+    pub format: SessionFormat,
+    pub compression: SessionCompression,
+    /// zstd level, 1 (fastest) to 22 (smallest).
+    pub zstd_level: i32,
+    /// Sessions serializing smaller than this are stored uncompressed.
+    pub min_compress_bytes: usize,
+}
+
+impl Default for SessionEncodingConfig {
+    fn default() -> Self {
+        Self {
+            format: SessionFormat::Json,
+            compression: SessionCompression::None,
+            zstd_level: 3,
+            min_compress_bytes: 1024,
+        }
+    }
+}
+
+/// Google Cloud Storage backend settings.
+#[derive(Debug, Deserialize, Clone)]
+pub struct GcsStoreConfig {
+    pub bucket: String,
+    /// Path to a service account JSON key. Falls back to application default credentials.
+    #[serde(default)]
+    pub service_account_path: Option<String>,
+    #[serde(default)]
+    pub prefix: String,
+}
+
+/// Azure Blob Storage backend settings.
+#[derive(Debug, Deserialize, Clone)]
+pub struct AzureBlobStoreConfig {
+    pub account: String,
+    pub container: String,
+    /// Storage account access key. Falls back to managed identity / environment credentials.
+    #[serde(default)]
+    pub access_key: Option<Secret<String>>,
+    /// Custom endpoint (e.g. Azurite).
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    #[serde(default)]
+    pub prefix: String,
+}
+
+/// Timeout and retry policy for S3 operations.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct S3RetryConfig {
+    /// Per-attempt timeout in milliseconds.
+    pub timeout_ms: u64,
+    /// Maximum number of retries for retryable failures (throttling, timeouts, 5xx).
+    pub max_retries: u32,
+    /// Base delay for exponential backoff in milliseconds.
+    pub base_delay_ms: u64,
+    /// Upper bound on a single backoff delay in milliseconds.
+    pub max_delay_ms: u64,
+}
+
+impl Default for S3RetryConfig {
+    fn default() -> Self {
+        Self {
+            timeout_ms: 30_000,
+            max_retries: 3,
+            base_delay_ms: 100,
+            max_delay_ms: 5_000,
+        }
+    }
+}
+
+/// Server-side encryption mode applied to S3 writes.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum S3SseMode {
+    /// Rely on the bucket default (no per-object header).
+    #[default]
+    None,
+    /// SSE-S3 (AES256, S3-managed keys).
+    SseS3,
+    /// SSE-KMS (AWS KMS-managed keys).
+    SseKms,
+}
+
+/// Server-side encryption settings for S3 writes.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct S3SseConfig {
+    pub mode: S3SseMode,
+    /// KMS key ID or ARN for `sse-kms`. The bucket/account default key is used when unset.
+    pub kms_key_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -94,6 +1023,311 @@ pub struct GovernanceConfig {
     pub json_logs: bool,
     #[serde(default)]
     pub admin_allow_external_access: bool,
+    #[serde(default)]
+    pub research: ResearchBudgetConfig,
+    #[serde(default)]
+    pub audit_worm: AuditWormConfig,
+    #[serde(default)]
+    pub log_stream: LogStreamConfig,
+    /// Default presentation policy; workspaces may override it.
+    #[serde(default)]
+    pub presentation: PresentationPolicy,
+    #[serde(default)]
+    pub remote_admin: RemoteAdminConfig,
+    /// Feature flags by name; the admin API can override them at runtime.
+    #[serde(default)]
+    pub feature_flags: std::collections::BTreeMap<String, FeatureFlagDefinition>,
+    #[serde(default)]
+    pub moderation: ModerationConfig,
+}
+
+/// External content moderation service.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ModerationProviderKind {
+    /// OpenAI moderation endpoint.
+    #[default]
+    Openai,
+    /// Azure AI Content Safety text analysis.
+    AzureContentSafety,
+}
+
+/// Score thresholds, from 0 to 1, at which content is flagged or blocked.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub struct ModerationThresholds {
+    pub flag: f32,
+    pub block: f32,
+}
+
+/// Moderation of inbound requests and outbound answers.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ModerationConfig {
+    pub enabled: bool,
+    pub provider: ModerationProviderKind,
+    /// Service URL; the provider's public endpoint when unset (required for
+    /// Azure, e.g. `https://<resource>.cognitiveservices.azure.com`).
+    pub endpoint: Option<String>,
+    pub api_key: Option<Secret<String>>,
+    /// OpenAI moderation model.
+    pub model: String,
+    pub check_input: bool,
+    pub check_output: bool,
+    /// Thresholds for categories without their own.
+    pub thresholds: ModerationThresholds,
+    /// Thresholds by category name, e.g. `violence` or `Hate`.
+    pub categories: std::collections::HashMap<String, ModerationThresholds>,
+    /// Let content through when the provider fails instead of rejecting it.
+    pub fail_open: bool,
+    pub timeout_secs: u64,
+}
+
+impl Default for ModerationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: ModerationProviderKind::Openai,
+            endpoint: None,
+            api_key: None,
+            model: "omni-moderation-latest".into(),
+            check_input: true,
+            check_output: true,
+            thresholds: ModerationThresholds {
+                flag: 0.5,
+                block: 0.8,
+            },
+            categories: Default::default(),
+            fail_open: true,
+            timeout_secs: 5,
+        }
+    }
+}
+
+/// Static definition of a feature flag.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FeatureFlagDefinition {
+    /// Whether the flag is on for workspaces not listed in `workspaces`.
+    pub enabled: bool,
+    /// Per-workspace values, e.g. to enable a feature for one tenant.
+    pub workspaces: std::collections::BTreeMap<String, bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Remote administration of headless servers: admin routes accept clients
+/// from `allowed_ips` that present an elevated token issued by
+/// `POST /v1/admin/auth/elevate` against a TOTP code.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct RemoteAdminConfig {
+    pub enabled: bool,
+    /// Client IPs or CIDR ranges, e.g. `10.0.0.0/8`.
+    pub allowed_ips: Vec<String>,
+    /// Base32 TOTP secret of each administrator's authenticator, by user id.
+    pub totp_secrets: std::collections::HashMap<String, Secret<String>>,
+    /// Seconds an elevated token stays valid.
+    pub elevation_ttl_secs: u64,
+    /// Wrong codes a user may send from one IP before elevation is locked.
+    pub max_failed_attempts: u32,
+    /// Seconds a locked user and IP must wait before trying again.
+    pub lockout_secs: u64,
+}
+
+impl Default for RemoteAdminConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            allowed_ips: Vec::new(),
+            totp_secrets: std::collections::HashMap::new(),
+            elevation_ttl_secs: 900,
+            max_failed_attempts: 5,
+            lockout_secs: 300,
+        }
+    }
+}
+
+/// Which streamed events end users see; the rest are operator-only.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PresentationPolicy {
+    /// Event types shown to end users, e.g. `TOOL_EXEC_STARTED`.
+    pub user_visible: Vec<String>,
+    /// Payload fields masked in the events end users see, at any depth.
+    pub redacted_fields: Vec<String>,
+}
+
+impl Default for PresentationPolicy {
+    fn default() -> Self {
+        Self {
+            user_visible: [
+                "REQUEST_RECEIVED",
+                "INTENT_RESOLVED",
+                "PLAN_PROPOSED",
+                "TOOL_EXEC_STARTED",
+                "TOOL_EXEC_FINISHED",
+                "APPROVAL_REQUESTED",
+                "APPROVAL_DECIDED",
+                "RESEARCH_SOURCE_PROGRESS",
+                "REPORT_SECTION_GENERATED",
+                "REPORT_GENERATED",
+                "BUDGET_EXCEEDED",
+                "SYSTEM_ERROR",
+                "USER_QUESTION",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            redacted_fields: vec!["args".into(), "input".into(), "output".into()],
+        }
+    }
+}
+
+impl PresentationPolicy {
+    /// Whether end users see events of `event_type` (case-insensitive).
+    pub fn shows(&self, event_type: &str) -> bool {
+        self.user_visible
+            .iter()
+            .any(|t| t.eq_ignore_ascii_case(event_type))
+    }
+}
+
+/// Visibility and backpressure for the `/ws/logs` stream.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct LogStreamConfig {
+    /// Roles that see every event unfiltered by the presentation policy.
+    pub operator_roles: Vec<String>,
+    /// Roles whose subscribers get `redacted_fields` masked (admins never are).
+    pub redact_for_roles: Vec<String>,
+    /// Payload fields masked for those roles, at any depth.
+    pub redacted_fields: Vec<String>,
+    /// Lag notices a subscriber may accumulate before it is disconnected.
+    pub max_lag_notices: u32,
+    /// Seconds a single send may block before the subscriber is disconnected.
+    pub send_timeout_secs: u64,
+    /// Recent events kept for `?since=<event_id>` resume; 0 disables replay.
+    pub replay_capacity: usize,
+}
+
+impl Default for LogStreamConfig {
+    fn default() -> Self {
+        Self {
+            operator_roles: vec!["admin".into(), "operator".into()],
+            redact_for_roles: vec!["viewer".into()],
+            redacted_fields: vec![
+                "args".into(),
+                "input".into(),
+                "output".into(),
+                "plan".into(),
+            ],
+            max_lag_notices: 5,
+            send_timeout_secs: 5,
+            replay_capacity: 1000,
+        }
+    }
+}
+
+/// External notary used to anchor the audit chain head.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuditNotaryKind {
+    /// Append-only table without external anchors.
+    #[default]
+    None,
+    /// S3 object-lock (compliance mode) write.
+    S3,
+    /// RFC 3161 timestamp authority.
+    Rfc3161,
+}
+
+/// Append-only audit mode with periodic external anchoring.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct AuditWormConfig {
+    pub enabled: bool,
+    /// Seconds between anchors of the chain head.
+    pub anchor_interval_secs: u64,
+    pub notary: AuditNotaryKind,
+    /// Timestamp authority URL for `rfc3161`.
+    pub tsa_url: Option<String>,
+    /// Path to the TSA signing certificate (PEM or DER) for `rfc3161`;
+    /// tokens not signed with it are rejected.
+    pub tsa_certificate: Option<String>,
+    /// Object-lock enabled bucket for `s3`.
+    pub s3_bucket: Option<String>,
+    pub s3_prefix: String,
+    pub s3_endpoint: Option<String>,
+    /// Object-lock retention for `s3` anchors, in days.
+    pub retention_days: u32,
+}
+
+impl Default for AuditWormConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            anchor_interval_secs: 3600,
+            notary: AuditNotaryKind::None,
+            tsa_url: None,
+            tsa_certificate: None,
+            s3_bucket: None,
+            s3_prefix: "audit-anchors/".into(),
+            s3_endpoint: None,
+            retention_days: 2555,
+        }
+    }
+}
+
+/// Upper bounds for research runs; requests asking for more are rejected.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ResearchBudgetConfig {
+    /// Maximum number of sources fetched per run.
+    pub max_sources: u32,
+    /// Maximum link-following depth from each seed domain (0 = seed pages only).
+    pub max_depth: u32,
+    /// Maximum wall-clock time for the fetch phase, in seconds.
+    pub max_time_budget_secs: u64,
+    /// Background refresh of stale research sources in the knowledge store.
+    pub recrawl: RecrawlConfig,
+}
+
+impl Default for ResearchBudgetConfig {
+    fn default() -> Self {
+        Self {
+            max_sources: 20,
+            max_depth: 2,
+            max_time_budget_secs: 600,
+            recrawl: RecrawlConfig::default(),
+        }
+    }
+}
+
+/// Freshness-aware re-crawl of knowledge sources.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct RecrawlConfig {
+    pub enabled: bool,
+    /// Seconds between re-crawl passes.
+    pub interval_secs: u64,
+    /// Sources not refreshed for this many seconds are considered stale.
+    pub stale_after_secs: u64,
+    /// Maximum sources refreshed per pass.
+    pub batch_size: usize,
+    /// Only refresh sources ingested again at least this many times.
+    pub min_hits: u32,
+}
+
+impl Default for RecrawlConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: 3600,
+            stale_after_secs: 7 * 24 * 3600,
+            batch_size: 10,
+            min_hits: 0,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -104,6 +1338,131 @@ pub struct ModelGatewayConfig {
 
     pub openai_api_key: Option<Secret<String>>,
     pub anthropic_api_key: Option<Secret<String>>,
+    #[serde(default)]
+    pub queue: InferenceQueueConfig,
+    #[serde(default)]
+    pub alerts: UsageAlertsConfig,
+    #[serde(default)]
+    pub spend_caps: SpendCapsConfig,
+    #[serde(default)]
+    pub canary: ModelCanaryConfig,
+}
+
+/// Priority queue in front of the model providers.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct InferenceQueueConfig {
+    /// Requests in flight across all priority classes.
+    pub max_concurrent: usize,
+    /// Per-class caps on requests in flight.
+    pub interactive_limit: usize,
+    pub background_limit: usize,
+    pub eval_limit: usize,
+}
+
+impl Default for InferenceQueueConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 16,
+            interactive_limit: 16,
+            background_limit: 8,
+            eval_limit: 4,
+        }
+    }
+}
+
+/// Cost and usage alert rules plus the daily usage digest.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct UsageAlertsConfig {
+    pub enabled: bool,
+    /// How often the rules are evaluated.
+    pub interval_secs: u64,
+    /// Alert when today's spend exceeds this many USD.
+    pub daily_spend_usd: Option<f64>,
+    /// Alert when a single session uses more tokens than this.
+    pub session_tokens: Option<u64>,
+    /// Alert when a model's error rate today exceeds this fraction.
+    pub provider_error_rate: Option<f64>,
+    /// Calls needed before the error rate is judged.
+    pub min_provider_calls: u64,
+    /// UTC hour at which the previous day's digest is sent; `None` disables it.
+    pub digest_hour_utc: Option<u32>,
+    /// Webhooks receiving alerts and digests (Slack-compatible payload).
+    pub webhook_urls: Vec<String>,
+}
+
+impl Default for UsageAlertsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: 300,
+            daily_spend_usd: None,
+            session_tokens: None,
+            provider_error_rate: None,
+            min_provider_calls: 20,
+            digest_hour_utc: Some(8),
+            webhook_urls: Vec::new(),
+        }
+    }
+}
+
+/// Enforcement of the monthly spend caps set on admin provider entries.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct SpendCapsConfig {
+    /// How often month-to-date spend is compared against the caps.
+    pub check_interval_secs: u64,
+    /// Workspaces that keep using providers over their cap.
+    pub privileged_workspaces: Vec<String>,
+}
+
+impl Default for SpendCapsConfig {
+    fn default() -> Self {
+        Self {
+            check_interval_secs: 60,
+            privileged_workspaces: Vec::new(),
+        }
+    }
+}
+
+/// Evaluation of a provider's new model before the upgrade is kept.
+///
+/// When an admin changes a provider's `model_id`, the cases run against the
+/// previous and the new model; the change is rolled back when the new model
+/// passes fewer cases or costs more than allowed.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ModelCanaryConfig {
+    pub enabled: bool,
+    pub cases: Vec<CanaryCase>,
+    /// Largest tolerated drop in the fraction of passed cases.
+    pub max_success_drop: f64,
+    /// Largest tolerated relative cost increase (0.5 = 50% more).
+    pub max_cost_increase: f64,
+    /// Time limit per case and model.
+    pub timeout_secs: u64,
+}
+
+impl Default for ModelCanaryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cases: Vec::new(),
+            max_success_drop: 0.0,
+            max_cost_increase: 0.5,
+            timeout_secs: 60,
+        }
+    }
+}
+
+/// A prompt whose answer must contain every `expect` string (ignoring case).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CanaryCase {
+    pub name: String,
+    pub prompt: String,
+    #[serde(default)]
+    pub expect: Vec<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -113,6 +1472,26 @@ pub struct ProviderConfig {
 }
 
 impl AppConfig {
+    /// Whether Redis, Postgres or an object store backs shared state.
+    pub fn has_external_state(&self) -> bool {
+        self.store.redis_url.is_some()
+            || self.store.postgres_url.is_some()
+            || self.store.s3_bucket.is_some()
+            || self.store.gcs.is_some()
+            || self.store.azure_blob.is_some()
+    }
+
+    /// Where to keep `file`. `None` when local state files are disabled: no
+    /// `store.data_dir` is set and an external backend is configured, as in
+    /// containers with a read-only root filesystem.
+    pub fn state_path(&self, file: StateFile) -> Option<std::path::PathBuf> {
+        match &self.store.data_dir {
+            Some(dir) => Some(std::path::Path::new(dir).join(file.file_name())),
+            None if self.has_external_state() => None,
+            None => Some(file.legacy_path().into()),
+        }
+    }
+
     pub fn load() -> Result<Self, ConfigError> {
         let env = std::env::var("MULTIAGENT_ENV").unwrap_or_else(|_| "development".into());
 
@@ -134,6 +1513,7 @@ impl Default for AppConfig {
             server: ServerConfig {
                 host: "0.0.0.0".into(),
                 port: 3000,
+                unix_socket: None,
             },
             gateway: GatewayConfig {
                 routing_timeout_ms: 5000,
@@ -144,11 +1524,25 @@ impl Default for AppConfig {
                     cert_path: None,
                     key_path: None,
                     ca_path: None,
+                    require_client_cert: false,
+                    client_identities: Default::default(),
                 },
+                response: ResponseLimitConfig::default(),
+                cache: SemanticCacheConfig::default(),
+                body_limits: BodyLimitConfig::default(),
+                security_headers: SecurityHeadersConfig::default(),
+                rate_limit: RateLimitConfig::default(),
+                concurrency: RouteConcurrencyConfig::default(),
             },
             controller: ControllerConfig {
                 max_react_iterations: 10,
                 state_persistence: false,
+                concurrency: MissionConcurrencyConfig::default(),
+                idle: SessionIdleConfig::default(),
+                deadlock: DeadlockConfig::default(),
+                observations: ObservationConfig::default(),
+                planning: PlanningConfig::default(),
+                speculative: SpeculativeDraftingConfig::default(),
             },
             store: StoreConfig {
                 large_content_threshold: 1048576,
@@ -156,10 +1550,20 @@ impl Default for AppConfig {
                 s3_bucket: None,
                 s3_endpoint: None,
                 redis_url: None,
+                postgres_url: None,
                 encryption: EncryptionConfig {
                     enabled: false,
                     master_key: None,
                 },
+                s3_retry: S3RetryConfig::default(),
+                s3_sse: S3SseConfig::default(),
+                gcs: None,
+                azure_blob: None,
+                artifact_download: ArtifactDownloadConfig::default(),
+                file_policy: FileTypePolicyConfig::default(),
+                data_dir: None,
+                leader_election: LeaderElectionConfig::default(),
+                session_encoding: SessionEncodingConfig::default(),
             },
             governance: GovernanceConfig {
                 default_token_budget: 100000,
@@ -173,6 +1577,13 @@ impl Default for AppConfig {
                 deny_domains: vec![],
                 json_logs: false,
                 admin_allow_external_access: false,
+                research: ResearchBudgetConfig::default(),
+                audit_worm: AuditWormConfig::default(),
+                log_stream: LogStreamConfig::default(),
+                presentation: PresentationPolicy::default(),
+                remote_admin: RemoteAdminConfig::default(),
+                feature_flags: std::collections::BTreeMap::new(),
+                moderation: ModerationConfig::default(),
             },
             model_gateway: ModelGatewayConfig {
                 default_provider: "openai".into(),
@@ -180,8 +1591,40 @@ impl Default for AppConfig {
                 providers: std::collections::HashMap::new(),
                 openai_api_key: None,
                 anthropic_api_key: None,
+                queue: InferenceQueueConfig::default(),
+                alerts: UsageAlertsConfig::default(),
+                spend_caps: SpendCapsConfig::default(),
+                canary: ModelCanaryConfig::default(),
             },
             safety: SafetyConfig::default(),
+            email: EmailConfig::default(),
+            events: EventBusConfig::default(),
+            i18n: I18nConfig::default(),
+            chaos: ChaosConfig::default(),
+            sandbox: ExecSandboxConfig::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_path_resolution() {
+        let mut config = AppConfig::default();
+        assert_eq!(
+            config.state_path(StateFile::RoutingPolicies),
+            Some(".sovereign_claw/routing/policies.json".into())
+        );
+
+        config.store.redis_url = Some("redis://redis:6379".into());
+        assert_eq!(config.state_path(StateFile::Secrets), None);
+
+        config.store.data_dir = Some("/app/data".into());
+        assert_eq!(
+            config.state_path(StateFile::Policy),
+            Some("/app/data/policies/default.yaml".into())
+        );
+    }
+}
@@ -38,6 +38,13 @@ pub enum Error {
     #[error("SOP execution error: {0}")]
     SopExecution(String),
 
+    #[error("Concurrency limit reached: {scope} '{key}' is already running {limit} missions")]
+    ConcurrencyLimit {
+        scope: String,
+        key: String,
+        limit: usize,
+    },
+
     // =========================================================================
     // Skills Errors (L2)
     // =========================================================================
@@ -59,6 +66,32 @@ pub enum Error {
     #[error("Storage error: {0}")]
     Storage(String),
 
+    #[error("Access denied: {0}")]
+    AccessDenied(String),
+
+    #[error("Throttled: {0}")]
+    Throttled(String),
+
+    /// The store does not implement the requested operation.
+    #[error("Unsupported operation: {0}")]
+    Unsupported(String),
+
+    /// Erasure that removed data from some parts of a store but failed in others.
+    #[error("Erased {erased} items, but failed on: {}", failures.join("; "))]
+    PartialErasure {
+        /// Items deleted despite the failures.
+        erased: usize,
+        /// One entry per part of the store (e.g. a tier) that could not be erased.
+        failures: Vec<String>,
+    },
+
+    #[error("File type policy rejected {content_type} ({size} bytes): {reason}")]
+    FileTypeRejected {
+        content_type: String,
+        size: usize,
+        reason: String,
+    },
+
     // =========================================================================
     // Governance Errors (L4)
     // =========================================================================
@@ -83,6 +116,12 @@ pub enum Error {
     #[error("Model selection failed: {0}")]
     ModelSelection(String),
 
+    #[error("Context window exceeded: {tokens} tokens, window {limit}")]
+    ContextWindowExceeded { tokens: u64, limit: u64 },
+
+    #[error("Monthly spend cap of ${limit_usd:.2} reached for {model}")]
+    SpendCapExceeded { model: String, limit_usd: f64 },
+
     // =========================================================================
     // Template Errors (L-T)
     // =========================================================================
@@ -141,6 +180,21 @@ impl Error {
         Self::Storage(msg.into())
     }
 
+    /// Create an access denied error.
+    pub fn access_denied(msg: impl Into<String>) -> Self {
+        Self::AccessDenied(msg.into())
+    }
+
+    /// Create a throttled error.
+    pub fn throttled(msg: impl Into<String>) -> Self {
+        Self::Throttled(msg.into())
+    }
+
+    /// Create an unsupported operation error.
+    pub fn unsupported(msg: impl Into<String>) -> Self {
+        Self::Unsupported(msg.into())
+    }
+
     /// Create a governance error.
     pub fn governance(msg: impl Into<String>) -> Self {
         Self::Governance(msg.into())
@@ -150,4 +204,12 @@ impl Error {
     pub fn internal(msg: impl Into<String>) -> Self {
         Self::Internal(msg.into())
     }
+
+    /// Whether the failure is transient and the operation may succeed if retried.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::Throttled(_) | Self::Timeout(_) | Self::ConcurrencyLimit { .. }
+        )
+    }
 }
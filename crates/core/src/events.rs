@@ -1,7 +1,14 @@
+use crate::types::research::{ResearchPlan, ResearchStyle};
+use crate::types::RequestContext;
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// Version of the typed payload schemas below. Bump on any breaking payload
+/// change; consumers can branch on [`EventEnvelope::schema_version`].
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
 /// Structured Event Envelope
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventEnvelope {
@@ -21,23 +28,46 @@ pub struct EventEnvelope {
     pub event_type: EventType,
     /// Event severity level
     pub severity: EventSeverity,
-    /// Structured payload (event-specific data)
+    /// Structured payload; its shape is given by [`payload_schema`]
     pub payload: serde_json::Value,
+    /// Payload schema version (0 for events recorded before versioning)
+    #[serde(default)]
+    pub schema_version: u32,
 }
 
 impl EventEnvelope {
+    /// Create an event. Trace, session, workspace and actor default to the
+    /// current [`RequestContext`]. Debug builds panic if `payload` does not
+    /// match the typed payload for `event_type`.
     pub fn new(event_type: EventType, payload: serde_json::Value) -> Self {
-        Self {
+        let context = RequestContext::current().unwrap_or_default();
+        let envelope = Self {
             id: Uuid::new_v4().to_string(),
-            trace_id: Uuid::new_v4().to_string(), // Default, should be overwritten by context
-            session_id: None,
-            workspace_id: None,
-            actor: "system".to_string(),
+            trace_id: context
+                .trace_id
+                .unwrap_or_else(|| Uuid::new_v4().to_string()),
+            session_id: context.session_id,
+            workspace_id: context.workspace_id,
+            actor: context.user_id.unwrap_or_else(|| "system".to_string()),
             timestamp: Utc::now(),
             event_type,
             severity: EventSeverity::Info,
             payload,
+            schema_version: EVENT_SCHEMA_VERSION,
+        };
+        #[cfg(debug_assertions)]
+        if let Err(e) = envelope.validate() {
+            panic!(
+                "{:?} event payload does not match its schema: {}",
+                envelope.event_type, e
+            );
         }
+        envelope
+    }
+
+    /// Check the payload against the typed payload for its event type.
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        validate_payload(&self.event_type, &self.payload)
     }
 
     pub fn with_trace(mut self, trace_id: &str) -> Self {
@@ -79,8 +109,12 @@ pub enum EventType {
     ToolCallProposed,
     /// Research plan proposed by LLM
     PlanProposed,
+    /// Model chosen for a ReAct iteration by adaptive selection
+    ModelSelected,
     /// Policy engine evaluation result
     PolicyEvaluated,
+    /// Content moderation verdict on a request or answer
+    ModerationEvaluated,
     /// Manual approval requested
     ApprovalRequested,
     /// Manual approval decided (Approved/Rejected)
@@ -93,6 +127,8 @@ pub enum EventType {
     EgressRequest,
     /// Egress (network) result received
     EgressResult,
+    /// Research source fetched, failed or skipped (per-source progress)
+    ResearchSourceProgress,
     /// Filesystem read operation
     FsRead,
     /// Filesystem write operation
@@ -103,6 +139,8 @@ pub enum EventType {
     BudgetExceeded,
     /// Audit log entry appended
     AuditAppended,
+    /// Research report section written (partial report)
+    ReportSectionGenerated,
     /// Research report summary generated
     ReportGenerated,
     /// Audit export bundle generated
@@ -117,7 +155,8 @@ pub enum EventType {
     Other(String),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// Ordered from least to most severe.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum EventSeverity {
     Debug,
@@ -127,9 +166,66 @@ pub enum EventSeverity {
     Critical,
 }
 
-// Helper structs for common payloads
+// Typed payloads, one per event type
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RequestReceivedPayload {
+    pub message_len: usize,
+    pub has_session: bool,
+    pub has_user: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ResearchCreatedPayload {
+    pub query: String,
+    pub orchestrator_version: String,
+    pub max_sources: u32,
+    pub max_depth: u32,
+    pub time_budget_secs: u64,
+    pub allowed_domains: Vec<String>,
+    pub style: ResearchStyle,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct IntentResolvedPayload {
+    pub intent_type: String,
+    /// Routing diagnostics (matched rule, release, source).
+    pub routing: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ToolCallProposedPayload {
+    pub tool_name: String,
+    pub args: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PlanProposedPayload {
+    pub query: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ModelSelectedPayload {
+    /// Task class, e.g. `tool_use`.
+    pub class: String,
+    /// Provider key, e.g. `openai:gpt-4o-mini`.
+    pub model: String,
+    /// `proven`, `default_quality`, `exploring` or `fallback`.
+    pub reason: String,
+    pub success_rate: Option<f64>,
+    pub samples: u64,
+    pub iteration: usize,
+}
+
+/// POLICY_EVALUATED for a tool call or a research plan.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum PolicyEvaluatedPayload {
+    Tool(PolicyEvaluationPayload),
+    Network(NetworkPolicyPayload),
+}
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PolicyEvaluationPayload {
     pub tool_name: String,
     pub risk_level: String,
@@ -137,18 +233,99 @@ pub struct PolicyEvaluationPayload {
     pub matched_rule: Option<String>,
     pub reason: String,
     pub policy_version: String,
+    /// Shadow rules that matched but were not enforced.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shadow: Vec<serde_json::Value>,
 }
 
-#[derive(Serialize, Deserialize)]
+/// Network policy check of a research plan.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NetworkPolicyPayload {
+    /// `ALLOWED` or `DENIED`.
+    pub decision: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub plan_summary: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ApprovalRequestedPayload {
+    pub plan: ResearchPlan,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ApprovalDecidedPayload {
+    /// `APPROVED` or `DENIED`.
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ToolExecStartedPayload {
+    pub tool_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ToolExecPayload {
     pub tool_name: String,
     pub input: Option<serde_json::Value>,
     pub output: Option<String>,
     pub duration_ms: Option<u64>,
     pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+    /// Output size when the output itself is not included.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_len: Option<usize>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EgressRequestPayload {
+    pub url: String,
+    pub method: String,
+}
+
+/// EGRESS_RESULT: a stored response or a failed fetch.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum EgressResultPayload {
+    Fetched {
+        url: String,
+        /// HTTP status code.
+        status: u16,
+        content_type: String,
+        body_len: usize,
+        body_hash: String,
+        artifact_id: String,
+    },
+    Failed {
+        url: String,
+        /// Always `ERROR`.
+        status: String,
+        error: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ResearchSourceProgressPayload {
+    /// `FETCHED`, `FAILED`, `DUPLICATE` or `TIME_BUDGET_EXHAUSTED`.
+    pub status: String,
+    pub sources_fetched: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub citation_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_sources: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sources_pending: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub elapsed_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FsPayload {
     pub path: String,
     pub operation: String, // "read", "write", "list", "delete"
@@ -156,3 +333,204 @@ pub struct FsPayload {
     pub success: bool,
     pub error: Option<String>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BudgetPayload {
+    pub used: u64,
+    pub limit: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AuditAppendedPayload {
+    /// Audit action, e.g. `ROUTING_POLICY_PUBLISH`.
+    pub action: String,
+    /// Action-specific details.
+    #[serde(flatten)]
+    pub details: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ReportSectionGeneratedPayload {
+    pub index: usize,
+    pub title: String,
+    pub sections: usize,
+    pub content_len: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ReportGeneratedPayload {
+    pub report_len: usize,
+    pub citations: usize,
+    pub sources: usize,
+    /// Cited source ids that match no fetched source.
+    pub unresolved_citations: Vec<String>,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExportGeneratedPayload {
+    pub format: String,
+    pub entries: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artifact_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DataDeletionInitiatedPayload {
+    pub user_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DataDeletionCompletedPayload {
+    pub user_id: String,
+    pub total_deleted: usize,
+    pub per_store: Vec<StoreDeletion>,
+    pub errors: Vec<String>,
+}
+
+/// Deletion outcome for a single store.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StoreDeletion {
+    pub store: String,
+    pub deleted: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ModerationPayload {
+    /// `INPUT` for inbound requests, `OUTPUT` for answers.
+    pub direction: String,
+    pub provider: String,
+    /// `ALLOW`, `FLAG` or `BLOCK`.
+    pub action: String,
+    /// Categories at or above their flag threshold.
+    pub categories: Vec<String>,
+    /// Provider scores by category, from 0 to 1.
+    pub scores: std::collections::BTreeMap<String, f32>,
+    /// Set when the provider failed; the action then follows `fail_open`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SystemErrorPayload {
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component: Option<String>,
+}
+
+/// Schema registry entry for one event type.
+#[derive(Debug, Clone, Serialize)]
+pub struct EventSchema {
+    pub event_type: EventType,
+    pub version: u32,
+    pub schema: schemars::schema::RootSchema,
+}
+
+macro_rules! event_payloads {
+    ($($variant:ident => $payload:ty),* $(,)?) => {
+        /// JSON schema of the payload for `event_type`; `None` for free-form
+        /// [`EventType::Other`] events.
+        pub fn payload_schema(event_type: &EventType) -> Option<schemars::schema::RootSchema> {
+            match event_type {
+                $(EventType::$variant => Some(schemars::schema_for!($payload)),)*
+                EventType::Other(_) => None,
+            }
+        }
+
+        fn validate_payload(
+            event_type: &EventType,
+            payload: &serde_json::Value,
+        ) -> std::result::Result<(), String> {
+            match event_type {
+                $(EventType::$variant => <$payload>::deserialize(payload)
+                    .map(|_| ())
+                    .map_err(|e| e.to_string()),)*
+                EventType::Other(_) => Ok(()),
+            }
+        }
+
+        /// Payload schemas for every typed event.
+        pub fn event_schemas() -> Vec<EventSchema> {
+            [$(EventType::$variant),*]
+                .into_iter()
+                .filter_map(|event_type| {
+                    let schema = payload_schema(&event_type)?;
+                    Some(EventSchema { event_type, version: EVENT_SCHEMA_VERSION, schema })
+                })
+                .collect()
+        }
+    };
+}
+
+event_payloads! {
+    RequestReceived => RequestReceivedPayload,
+    ResearchCreated => ResearchCreatedPayload,
+    IntentResolved => IntentResolvedPayload,
+    ToolCallProposed => ToolCallProposedPayload,
+    PlanProposed => PlanProposedPayload,
+    ModelSelected => ModelSelectedPayload,
+    PolicyEvaluated => PolicyEvaluatedPayload,
+    ModerationEvaluated => ModerationPayload,
+    ApprovalRequested => ApprovalRequestedPayload,
+    ApprovalDecided => ApprovalDecidedPayload,
+    ToolExecStarted => ToolExecStartedPayload,
+    ToolExecFinished => ToolExecPayload,
+    EgressRequest => EgressRequestPayload,
+    EgressResult => EgressResultPayload,
+    ResearchSourceProgress => ResearchSourceProgressPayload,
+    FsRead => FsPayload,
+    FsWrite => FsPayload,
+    BudgetUpdated => BudgetPayload,
+    BudgetExceeded => BudgetPayload,
+    AuditAppended => AuditAppendedPayload,
+    ReportSectionGenerated => ReportSectionGeneratedPayload,
+    ReportGenerated => ReportGeneratedPayload,
+    ExportGenerated => ExportGeneratedPayload,
+    DataDeletionInitiated => DataDeletionInitiatedPayload,
+    DataDeletionCompleted => DataDeletionCompletedPayload,
+    SystemError => SystemErrorPayload,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_validation() {
+        let event = EventEnvelope::new(
+            EventType::ToolExecStarted,
+            serde_json::json!({"tool_name": "read_file"}),
+        );
+        assert_eq!(event.schema_version, EVENT_SCHEMA_VERSION);
+        assert!(validate_payload(
+            &EventType::ToolExecStarted,
+            &serde_json::json!({"tool": "x"})
+        )
+        .is_err());
+        assert!(validate_payload(
+            &EventType::EgressResult,
+            &serde_json::json!({"url": "https://a.test", "status": "ERROR", "error": "timeout"})
+        )
+        .is_ok());
+        assert!(validate_payload(
+            &EventType::Other("PLUGIN_ENABLED".into()),
+            &serde_json::json!(1)
+        )
+        .is_ok());
+
+        // Events recorded before versioning still deserialize
+        let mut legacy = serde_json::to_value(&event).unwrap();
+        legacy.as_object_mut().unwrap().remove("schema_version");
+        let legacy: EventEnvelope = serde_json::from_value(legacy).unwrap();
+        assert_eq!(legacy.schema_version, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match its schema")]
+    #[cfg(debug_assertions)]
+    fn test_invalid_payload_panics_in_debug() {
+        EventEnvelope::new(EventType::FsWrite, serde_json::json!({"path": "/tmp/x"}));
+    }
+}
@@ -4,12 +4,15 @@
 //! This crate provides the foundational building blocks shared across all layers
 //! of the multi-agent system.
 
+#[cfg(feature = "chaos")]
+pub mod chaos;
 pub mod config;
 pub mod error;
 pub mod events;
 pub mod evidence;
 pub mod fs_policy;
 pub mod mocks;
+pub mod prompts;
 pub mod template;
 pub mod traits;
 pub mod types;
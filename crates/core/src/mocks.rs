@@ -300,6 +300,8 @@ impl MockRouter {
             context_summary: String::new(),
             visual_refs: Vec::new(),
             user_id: None,
+            workspace_id: None,
+            dry_run: false,
         })
     }
 
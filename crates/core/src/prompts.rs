@@ -0,0 +1,354 @@
+//! Localized prompts and messages.
+//!
+//! The [`PromptRegistry`] holds agent-facing prompts (the ReAct system
+//! prompt, approval texts) and user-facing error messages per locale. English
+//! texts are built in; deployments add or replace texts with one YAML or JSON
+//! file per locale in `i18n.prompts_dir` (e.g. `de.yaml`, `pt-BR.yaml`), each
+//! a flat map from prompt key to text. Texts use `{name}` placeholders.
+//!
+//! A lookup for `pt-BR` tries `pt-BR`, then `pt`, then the configured default
+//! locale, then the built-in English text.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::config::I18nConfig;
+use crate::error::{Error, Result};
+use crate::types::RequestContext;
+
+/// Locale of the built-in texts.
+pub const BUILTIN_LOCALE: &str = "en";
+
+/// Prompt keys with built-in texts.
+pub mod keys {
+    /// ReAct system prompt; `{goal}`, `{tools}`.
+    pub const REACT_SYSTEM: &str = "react.system";
+    /// Tool list placeholder before tools are loaded.
+    pub const REACT_TOOLS_PENDING: &str = "react.tools_pending";
+    /// Context shown to approvers; `{reason}`, `{goal}`.
+    pub const APPROVAL_CONTEXT: &str = "approval.context";
+    /// Observation after a denied call; `{tool}`, `{code}`, `{reason}`.
+    pub const APPROVAL_DENIED: &str = "approval.denied";
+    /// Observation when approval is required but impossible; `{tool}`.
+    pub const APPROVAL_UNAVAILABLE: &str = "approval.unavailable";
+    /// Deadline error; `{iterations}`, `{session}`.
+    pub const ERROR_DEADLINE: &str = "error.deadline";
+    /// Request blocked by a guardrail without a reason.
+    pub const ERROR_GUARDRAIL: &str = "error.guardrail";
+    /// Request or answer rejected by content moderation.
+    pub const ERROR_MODERATION: &str = "error.moderation";
+    /// Intent routing failed; `{error}`.
+    pub const ERROR_ROUTING: &str = "error.routing";
+    /// Too many concurrent missions; `{error}`.
+    pub const ERROR_CONCURRENCY: &str = "error.concurrency";
+    /// Mission failed; `{error}`.
+    pub const ERROR_EXECUTION: &str = "error.execution";
+}
+
+const REACT_SYSTEM_EN: &str = r#"You are an AI assistant that uses the ReAct (Reasoning + Acting) pattern.
+
+GOAL: {goal}
+
+AVAILABLE TOOLS:
+{tools}
+
+INSTRUCTIONS:
+1. Think step by step about what needs to be done
+2. Use tools when needed by responding with ACTION
+3. After receiving tool results, continue reasoning
+4. When done, provide your FINAL ANSWER
+
+RESPONSE FORMAT:
+Use exactly one of these formats in each response:
+
+For thinking/reasoning:
+THOUGHT: <your reasoning here>
+
+For tool calls:
+ACTION: <tool_name>
+ARGS: <json arguments>
+
+For final answer (when task is complete):
+FINAL ANSWER: <your complete answer>
+
+Tables, file lists and sourced answers may be given as a JSON final answer:
+FINAL ANSWER: {"type": "Table", "payload": {"columns": [...], "rows": [[...]]}}
+FINAL ANSWER: {"type": "CitedText", "payload": {"text": "... [1]", "citations": [{"url": "...", "hash": "", "context": "..."}]}}
+
+Always think before acting. Be concise and focused on the goal."#;
+
+fn builtin() -> HashMap<String, String> {
+    [
+        (keys::REACT_SYSTEM, REACT_SYSTEM_EN),
+        (
+            keys::REACT_TOOLS_PENDING,
+            "Tools will be loaded when execution starts.",
+        ),
+        (
+            keys::APPROVAL_CONTEXT,
+            "Policy Reason: {reason}. Session Goal: {goal}",
+        ),
+        (
+            keys::APPROVAL_DENIED,
+            "Tool '{tool}' was DENIED by human reviewer ({code}): {reason}",
+        ),
+        (
+            keys::APPROVAL_UNAVAILABLE,
+            "Tool '{tool}' requires human approval, but no approval gate is configured. The call was not executed.",
+        ),
+        (
+            keys::ERROR_DEADLINE,
+            "Request deadline passed after {iterations} iterations; resume session {session} to continue",
+        ),
+        (keys::ERROR_GUARDRAIL, "Blocked by guardrail policy"),
+        (keys::ERROR_MODERATION, "Blocked by content moderation"),
+        (keys::ERROR_ROUTING, "{error}"),
+        (keys::ERROR_CONCURRENCY, "{error}"),
+        (keys::ERROR_EXECUTION, "{error}"),
+    ]
+    .into_iter()
+    .map(|(key, text)| (key.to_string(), text.to_string()))
+    .collect()
+}
+
+/// Prompt and message texts by locale.
+#[derive(Debug, Clone)]
+pub struct PromptRegistry {
+    default_locale: String,
+    /// Texts by lowercase locale, then key.
+    catalogs: HashMap<String, HashMap<String, String>>,
+}
+
+impl Default for PromptRegistry {
+    fn default() -> Self {
+        Self::new(BUILTIN_LOCALE)
+    }
+}
+
+impl PromptRegistry {
+    /// Registry with the built-in texts, falling back to `default_locale`.
+    pub fn new(default_locale: impl Into<String>) -> Self {
+        Self {
+            default_locale: normalize(&default_locale.into()),
+            catalogs: HashMap::from([(BUILTIN_LOCALE.to_string(), builtin())]),
+        }
+    }
+
+    /// Registry for the `[i18n]` configuration, loading `prompts_dir`.
+    pub fn from_config(config: &I18nConfig) -> Result<Self> {
+        let registry = Self::new(&config.default_locale);
+        match &config.prompts_dir {
+            Some(dir) => registry.load_dir(dir),
+            None => Ok(registry),
+        }
+    }
+
+    /// Add or replace texts for `locale`.
+    pub fn with_texts(
+        mut self,
+        locale: &str,
+        texts: impl IntoIterator<Item = (String, String)>,
+    ) -> Self {
+        self.catalogs
+            .entry(normalize(locale))
+            .or_default()
+            .extend(texts);
+        self
+    }
+
+    /// Load one catalog per `<locale>.yaml`, `.yml` or `.json` file in `dir`.
+    pub fn load_dir(mut self, dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        let entries = std::fs::read_dir(dir).map_err(|e| {
+            Error::invalid_request(format!("Read prompts dir {} failed: {}", dir.display(), e))
+        })?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_catalog = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| matches!(ext, "yaml" | "yml" | "json"));
+            let Some(locale) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            if !is_catalog {
+                continue;
+            }
+            let content = std::fs::read_to_string(&path).map_err(|e| {
+                Error::invalid_request(format!("Read {} failed: {}", path.display(), e))
+            })?;
+            let texts: HashMap<String, String> = serde_yaml::from_str(&content).map_err(|e| {
+                Error::invalid_request(format!("Parse {} failed: {}", path.display(), e))
+            })?;
+            self = self.with_texts(locale, texts);
+        }
+        Ok(self)
+    }
+
+    /// Locales with a catalog.
+    pub fn locales(&self) -> Vec<String> {
+        let mut locales: Vec<String> = self.catalogs.keys().cloned().collect();
+        locales.sort();
+        locales
+    }
+
+    /// Locales tried, in order, for `locale`.
+    pub fn fallback_chain(&self, locale: Option<&str>) -> Vec<String> {
+        let mut chain: Vec<String> = Vec::new();
+        for tag in locale
+            .into_iter()
+            .chain([self.default_locale.as_str(), BUILTIN_LOCALE])
+        {
+            let tag = normalize(tag);
+            let mut candidate = tag.as_str();
+            loop {
+                if !candidate.is_empty() && !chain.iter().any(|c| c == candidate) {
+                    chain.push(candidate.to_string());
+                }
+                match candidate.rsplit_once('-') {
+                    Some((parent, _)) => candidate = parent,
+                    None => break,
+                }
+            }
+        }
+        chain
+    }
+
+    /// Text of `key` in the best available locale.
+    pub fn text(&self, key: &str, locale: Option<&str>) -> Option<&str> {
+        self.fallback_chain(locale).iter().find_map(|locale| {
+            self.catalogs
+                .get(locale)
+                .and_then(|texts| texts.get(key))
+                .map(String::as_str)
+        })
+    }
+
+    /// Text of `key` for `locale` with `{name}` placeholders filled in.
+    /// Unknown keys render as the key itself.
+    pub fn render(&self, key: &str, locale: Option<&str>, args: &[(&str, &str)]) -> String {
+        let mut text = self.text(key, locale).unwrap_or(key);
+        let mut rendered = String::with_capacity(text.len());
+        // One pass, so placeholders inside substituted values stay literal.
+        while let Some(start) = text.find('{') {
+            rendered.push_str(&text[..start]);
+            let rest = &text[start + 1..];
+            let value = rest.find('}').and_then(|end| {
+                args.iter()
+                    .find(|(name, _)| *name == &rest[..end])
+                    .map(|(_, value)| (end, value))
+            });
+            match value {
+                Some((end, value)) => {
+                    rendered.push_str(value);
+                    text = &rest[end + 1..];
+                }
+                None => {
+                    rendered.push('{');
+                    text = rest;
+                }
+            }
+        }
+        rendered.push_str(text);
+        rendered
+    }
+
+    /// [`Self::render`] in the locale of the current request.
+    pub fn render_current(&self, key: &str, args: &[(&str, &str)]) -> String {
+        let locale = RequestContext::current().and_then(|context| context.locale);
+        self.render(key, locale.as_deref(), args)
+    }
+}
+
+/// Lowercase `pt_BR` style tags to `pt-br`.
+fn normalize(locale: &str) -> String {
+    locale.trim().replace('_', "-").to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fallback_chain() {
+        let registry = PromptRegistry::new("de");
+        assert_eq!(
+            registry.fallback_chain(Some("pt_BR")),
+            vec!["pt-br", "pt", "de", "en"]
+        );
+        assert_eq!(registry.fallback_chain(None), vec!["de", "en"]);
+    }
+
+    #[test]
+    fn test_render_with_fallback() {
+        let registry = PromptRegistry::default()
+            .with_texts(
+                "de",
+                [(
+                    keys::APPROVAL_DENIED.to_string(),
+                    "Werkzeug '{tool}' wurde abgelehnt ({code}): {reason}".to_string(),
+                )],
+            )
+            .with_texts(
+                "de-AT",
+                [(
+                    keys::ERROR_GUARDRAIL.to_string(),
+                    "Durch Richtlinie blockiert".to_string(),
+                )],
+            );
+        let args = [("tool", "rm"), ("code", "RISK"), ("reason", "nein")];
+
+        assert_eq!(
+            registry.render(keys::APPROVAL_DENIED, Some("de-AT"), &args),
+            "Werkzeug 'rm' wurde abgelehnt (RISK): nein"
+        );
+        assert_eq!(
+            registry.render(keys::ERROR_GUARDRAIL, Some("de-AT"), &[]),
+            "Durch Richtlinie blockiert"
+        );
+        assert_eq!(
+            registry
+                .render(
+                    keys::REACT_SYSTEM,
+                    None,
+                    &[("goal", "{tools}"), ("tools", "none")]
+                )
+                .lines()
+                .nth(2),
+            Some("GOAL: {tools}")
+        );
+        // Missing in every German catalog: built-in English.
+        assert_eq!(
+            registry.render(keys::ERROR_GUARDRAIL, Some("de-DE"), &[]),
+            "Blocked by guardrail policy"
+        );
+        assert_eq!(
+            registry.render(keys::APPROVAL_DENIED, Some("fr"), &args),
+            "Tool 'rm' was DENIED by human reviewer (RISK): nein"
+        );
+    }
+
+    #[test]
+    fn test_load_dir() {
+        let dir = std::env::temp_dir().join(format!("prompts-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("es.yaml"),
+            "error.guardrail: \"Bloqueado por la política\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("README.md"), "not a catalog").unwrap();
+
+        let registry = PromptRegistry::from_config(&I18nConfig {
+            default_locale: "es".into(),
+            prompts_dir: Some(dir.display().to_string()),
+        })
+        .unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(registry.locales(), vec!["en", "es"]);
+        assert_eq!(
+            registry.render(keys::ERROR_GUARDRAIL, None, &[]),
+            "Bloqueado por la política"
+        );
+    }
+}
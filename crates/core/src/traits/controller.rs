@@ -1,9 +1,12 @@
 //! L1 Controller traits.
 
 use crate::error::Result;
-use crate::types::AgentResult;
+use crate::types::{stream_execution, AgentEventStream, AgentResult, Session, SessionChange};
 use async_trait::async_trait;
+use futures::stream::{BoxStream, StreamExt};
 use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Controller for orchestrating complex tasks.
 #[async_trait]
@@ -15,6 +18,19 @@ pub trait Controller: Send + Sync {
         trace_id: String,
     ) -> Result<AgentResult>;
 
+    /// Execute a mission, streaming its thoughts, tool calls and observations
+    /// as they happen, followed by the outcome.
+    ///
+    /// The default streams whatever `execute` emits as
+    /// [`AgentStreamEvent`](crate::types::AgentStreamEvent)s.
+    fn execute_streaming<'a>(
+        &'a self,
+        intent: crate::types::UserIntent,
+        trace_id: String,
+    ) -> AgentEventStream<'a> {
+        stream_execution(self.execute(intent, trace_id))
+    }
+
     /// Resume a previously interrupted task.
     async fn resume(&self, session_id: &str, user_id: Option<&str>) -> Result<AgentResult>;
 
@@ -58,6 +74,68 @@ pub trait SessionStore: Send + Sync {
     async fn health_check(&self) -> Result<()> {
         Ok(())
     }
+
+    /// Changes to `session_id` from now on, until the stream is dropped.
+    ///
+    /// The default polls [`load`](Self::load) every
+    /// [`SESSION_POLL_INTERVAL`]; stores with change notifications push
+    /// changes as they happen.
+    async fn watch(self: Arc<Self>, session_id: &str) -> Result<SessionWatch>
+    where
+        Self: 'static,
+    {
+        poll_session(self, session_id, SESSION_POLL_INTERVAL).await
+    }
+}
+
+/// Stream of changes to one session.
+pub type SessionWatch = BoxStream<'static, SessionChange>;
+
+/// Interval of the polling fallback of [`SessionStore::watch`].
+pub const SESSION_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Watch a session by loading it every `interval` and reporting when it
+/// differs from the last load. Changes in between polls are coalesced, and
+/// failed loads are retried on the next poll.
+pub async fn poll_session<S>(
+    store: Arc<S>,
+    session_id: &str,
+    interval: Duration,
+) -> Result<SessionWatch>
+where
+    S: SessionStore + ?Sized + 'static,
+{
+    // Serialized, as sessions are not comparable
+    fn snapshot(session: &Option<Session>) -> Option<Value> {
+        session
+            .as_ref()
+            .and_then(|session| serde_json::to_value(session).ok())
+    }
+
+    let session_id = session_id.to_string();
+    let last = snapshot(&store.load(&session_id).await?);
+    let stream = futures::stream::unfold(
+        (store, session_id, last),
+        move |(store, session_id, mut last)| async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let Ok(current) = store.load(&session_id).await else {
+                    continue;
+                };
+                let next = snapshot(&current);
+                if next == last {
+                    continue;
+                }
+                last = next;
+                let change = match current {
+                    Some(session) => SessionChange::Saved(Box::new(session)),
+                    None => SessionChange::Deleted,
+                };
+                return Some((change, (store, session_id, last)));
+            }
+        },
+    );
+    Ok(stream.boxed())
 }
 
 /// SOP definition structure.
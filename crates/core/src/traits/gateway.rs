@@ -1,8 +1,9 @@
 //! L0 Gateway traits.
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::types::{NormalizedRequest, UserIntent};
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 
 /// Intent router for classifying incoming requests.
 #[async_trait]
@@ -48,6 +49,76 @@ pub trait SemanticCache: Send + Sync {
         response: &str,
     ) -> Result<()>;
 
+    /// Store a query-response pair attributed to a user, so it can be erased on request.
+    async fn set_for_user(
+        &self,
+        workspace_id: &str,
+        session_id: &str,
+        query: &str,
+        response: &str,
+        _user_id: Option<&str>,
+    ) -> Result<()> {
+        self.set(workspace_id, session_id, query, response).await
+    }
+
     /// Invalidate cache entries matching a pattern.
     async fn invalidate(&self, workspace_id: &str, session_id: &str, pattern: &str) -> Result<()>;
+
+    /// List cached entries matching the selector.
+    async fn entries(&self, _selector: &CacheSelector) -> Result<Vec<CacheEntryInfo>> {
+        Ok(Vec::new())
+    }
+
+    /// Remove cached entries matching the selector, returning how many were removed.
+    async fn purge(&self, _selector: &CacheSelector) -> Result<usize> {
+        Err(Error::SemanticCache(
+            "Cache does not support purging".to_string(),
+        ))
+    }
+
+    /// Enable or disable caching for a workspace.
+    async fn set_workspace_enabled(&self, _workspace_id: &str, _enabled: bool) -> Result<()> {
+        Err(Error::SemanticCache(
+            "Cache does not support per-workspace switches".to_string(),
+        ))
+    }
+
+    /// Workspaces with caching disabled.
+    async fn disabled_workspaces(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Selects semantic cache entries; unset fields match everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheSelector {
+    #[serde(default, alias = "workspace")]
+    pub workspace_id: Option<String>,
+    #[serde(default, alias = "session")]
+    pub session_id: Option<String>,
+    /// Hash of the normalized query, as reported by [`CacheEntryInfo`].
+    #[serde(default)]
+    pub query_hash: Option<String>,
+}
+
+impl CacheSelector {
+    /// Whether an entry with these keys is selected.
+    pub fn matches(&self, workspace_id: &str, session_id: &str, query_hash: &str) -> bool {
+        self.workspace_id
+            .as_deref()
+            .is_none_or(|w| w == workspace_id)
+            && self.session_id.as_deref().is_none_or(|s| s == session_id)
+            && self.query_hash.as_deref().is_none_or(|h| h == query_hash)
+    }
+}
+
+/// Summary of a cached entry; the query text itself is not exposed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntryInfo {
+    pub workspace_id: String,
+    pub session_id: String,
+    pub query_hash: String,
+    pub hit_count: u64,
+    pub age_secs: u64,
+    pub expires_in_secs: u64,
 }
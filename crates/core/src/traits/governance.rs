@@ -57,4 +57,18 @@ pub trait ApprovalGate: Send + Sync {
     fn threshold(&self) -> crate::types::ToolRiskLevel {
         crate::types::ToolRiskLevel::High
     }
+
+    /// Called when a session completes or fails, so the gate can drop any
+    /// state it keeps for it (e.g., standing approvals).
+    async fn end_session(&self, _session_id: &str) {}
+}
+
+/// Channel for asking the human clarification questions mid-mission.
+#[async_trait]
+pub trait HumanInputChannel: Send + Sync {
+    /// Ask a question and wait for the answer (or the timeout).
+    async fn ask(
+        &self,
+        question: &crate::types::HumanQuestion,
+    ) -> Result<crate::types::HumanAnswer>;
 }
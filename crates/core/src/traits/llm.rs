@@ -14,10 +14,26 @@ pub trait LlmClient: Send + Sync {
     /// Generate a chat completion.
     async fn chat(&self, messages: &[ChatMessage]) -> Result<LlmResponse>;
 
+    /// Generate a chat completion with provider-specific parameters.
+    ///
+    /// Clients without parameter support ignore `params`.
+    async fn chat_with_params(
+        &self,
+        messages: &[ChatMessage],
+        params: &ProviderParams,
+    ) -> Result<LlmResponse> {
+        let _ = params;
+        self.chat(messages).await
+    }
+
     /// Generate embeddings for text.
     async fn embed(&self, text: &str) -> Result<Vec<f32>>;
 }
 
+/// Provider-specific request parameters keyed by their API names
+/// (`top_p`, `max_tokens`, `stop`, `seed`, `reasoning_effort`, ...).
+pub type ProviderParams = serde_json::Map<String, Value>;
+
 /// Chat message for LLM interactions.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatMessage {
@@ -7,6 +7,7 @@
 //! - `store`: L3 Store traits (ArtifactStore, MemoryStore)
 //! - `governance`: L4 Governance traits (BudgetController, SecurityProxy)
 //! - `llm`: L-M Model Gateway traits (LlmClient, ModelSelector)
+//! - `notify`: Operator notifications (NotificationSink)
 //! - `state_store`: Stateless architecture traits (StateStore, DistributedRateLimiter)
 
 pub mod controller;
@@ -14,6 +15,7 @@ pub mod events;
 pub mod gateway;
 pub mod governance;
 pub mod llm;
+pub mod notify;
 pub mod skills;
 pub mod state_store;
 pub mod store;
@@ -24,6 +26,7 @@ pub use events::*;
 pub use gateway::*;
 pub use governance::*;
 pub use llm::*;
+pub use notify::*;
 pub use skills::*;
 pub use state_store::*;
 pub use store::*;
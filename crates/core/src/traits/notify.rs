@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+
+/// How urgent a notification is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+/// A message for operators (alerts, digests).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    pub title: String,
+    pub body: String,
+    pub severity: NotificationSeverity,
+}
+
+impl Notification {
+    pub fn new(
+        severity: NotificationSeverity,
+        title: impl Into<String>,
+        body: impl Into<String>,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            body: body.into(),
+            severity,
+        }
+    }
+}
+
+/// Delivers notifications to an external channel (log, webhook, chat).
+#[async_trait]
+pub trait NotificationSink: Send + Sync {
+    /// Sink name for logs.
+    fn name(&self) -> &str;
+
+    /// Deliver one notification.
+    async fn send(&self, notification: &Notification) -> Result<()>;
+}
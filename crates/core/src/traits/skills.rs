@@ -25,6 +25,25 @@ pub trait Tool: Send + Sync {
     fn risk_level(&self) -> crate::types::ToolRiskLevel {
         crate::types::ToolRiskLevel::Low
     }
+
+    /// Whether every call must be approved by a human, regardless of risk
+    /// level or policy thresholds. Override this for tools with external,
+    /// irreversible side effects (e.g. sending email).
+    fn requires_approval(&self) -> bool {
+        false
+    }
+
+    /// Whether this particular call must be approved. Override this when only
+    /// some arguments are dangerous; defaults to [`Tool::requires_approval`].
+    fn requires_approval_for(&self, _args: &Value) -> bool {
+        self.requires_approval()
+    }
+
+    /// Whether the tool blocks on human input. The controller marks the
+    /// session as paused while such a tool runs.
+    fn awaits_human_input(&self) -> bool {
+        false
+    }
 }
 
 /// Tool registry for managing available tools.
@@ -50,6 +69,33 @@ pub trait ToolRegistry: Send + Sync {
             _ => crate::types::ToolRiskLevel::Low,
         }
     }
+
+    /// Whether a tool must always go through the approval gate.
+    /// Returns `false` if the tool is not found.
+    async fn requires_approval(&self, name: &str) -> bool {
+        match self.get(name).await {
+            Ok(Some(tool)) => tool.requires_approval(),
+            _ => false,
+        }
+    }
+
+    /// Whether a call to a tool with `args` must go through the approval gate.
+    /// Returns `false` if the tool is not found.
+    async fn requires_approval_for(&self, name: &str, args: &Value) -> bool {
+        match self.get(name).await {
+            Ok(Some(tool)) => tool.requires_approval_for(args),
+            _ => false,
+        }
+    }
+
+    /// Whether a tool blocks on human input.
+    /// Returns `false` if the tool is not found.
+    async fn awaits_human_input(&self, name: &str) -> bool {
+        match self.get(name).await {
+            Ok(Some(tool)) => tool.awaits_human_input(),
+            _ => false,
+        }
+    }
 }
 
 /// MCP (Model Context Protocol) adapter.
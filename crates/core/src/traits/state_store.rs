@@ -88,9 +88,15 @@ pub struct ProviderEntry {
     pub model_id: String,
     pub description: Option<String>,
     pub base_url: String,
+    /// Additional regional base URLs.
+    #[serde(default)]
+    pub regional_urls: Vec<String>,
     pub version: Option<String>,
     /// Reference to encrypted API key in secrets manager.
     pub api_key_id: String,
     pub capabilities: Vec<String>,
     pub status: String,
+    /// Monthly spend cap in USD; the provider is disabled once it is reached.
+    #[serde(default)]
+    pub monthly_spend_cap_usd: Option<f64>,
 }
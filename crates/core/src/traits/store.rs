@@ -8,8 +8,11 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Artifact store for managing large content.
+///
+/// Every artifact store must support user erasure so that GDPR deletion
+/// cascades through wrappers and tiers.
 #[async_trait]
-pub trait ArtifactStore: Send + Sync {
+pub trait ArtifactStore: Erasable + Send + Sync {
     /// Save data and return a reference ID.
     async fn save(&self, data: Bytes) -> Result<RefId>;
 
@@ -22,6 +25,26 @@ pub trait ArtifactStore: Send + Sync {
     /// Load data by reference ID.
     async fn load(&self, id: &RefId) -> Result<Option<Bytes>>;
 
+    /// Load the inclusive byte range `start..=end` of an artifact.
+    /// The range is clamped to the artifact's size.
+    async fn load_range(&self, id: &RefId, start: u64, end: u64) -> Result<Option<Bytes>> {
+        Ok(self.load(id).await?.map(|data| {
+            let len = data.len() as u64;
+            let (start, end) = (start.min(len), end.saturating_add(1).min(len));
+            data.slice(start as usize..end.max(start) as usize)
+        }))
+    }
+
+    /// Create a time-limited URL that downloads the artifact directly from the
+    /// backing store, bypassing the gateway. Returns `None` when unsupported.
+    async fn presigned_url(
+        &self,
+        _id: &RefId,
+        _expires_in: std::time::Duration,
+    ) -> Result<Option<String>> {
+        Ok(None)
+    }
+
     /// Delete an artifact.
     async fn delete(&self, id: &RefId) -> Result<()>;
 
@@ -31,6 +54,17 @@ pub trait ArtifactStore: Send + Sync {
     /// Get metadata about an artifact.
     async fn metadata(&self, id: &RefId) -> Result<Option<ArtifactMetadata>>;
 
+    /// Metadata of the artifacts matching `filter`, in ascending id order.
+    ///
+    /// Stores that cannot enumerate their contents return
+    /// [`Error::Unsupported`](crate::Error::Unsupported).
+    async fn list(&self, _filter: ArtifactFilter) -> Result<Vec<ArtifactMetadata>> {
+        Err(crate::Error::unsupported(format!(
+            "{} does not support listing artifacts",
+            self.store_name()
+        )))
+    }
+
     /// Perform a health check on the store.
     async fn health_check(&self) -> Result<()> {
         Ok(())
@@ -38,8 +72,10 @@ pub trait ArtifactStore: Send + Sync {
 }
 
 /// Metadata for stored artifacts.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ArtifactMetadata {
+    /// Reference ID of the artifact.
+    pub id: RefId,
     /// Size in bytes.
     pub size: usize,
     /// Content type.
@@ -48,10 +84,56 @@ pub struct ArtifactMetadata {
     pub created_at: i64,
     /// Storage tier.
     pub tier: StorageTier,
+    /// User that created the artifact, if known.
+    pub owner_user_id: Option<String>,
+    /// Session the artifact was created in, if known.
+    pub session_id: Option<String>,
+}
+
+/// Selects artifacts for [`ArtifactStore::list`].
+///
+/// Listings are ordered by id, so a page continues from the last id of the
+/// previous one via `after_id`.
+#[derive(Debug, Clone, Default)]
+pub struct ArtifactFilter {
+    pub owner_user_id: Option<String>,
+    pub session_id: Option<String>,
+    /// Only artifacts created at or after this Unix timestamp.
+    pub created_after: Option<i64>,
+    /// Only artifacts created before this Unix timestamp.
+    pub created_before: Option<i64>,
+    /// Only artifacts whose id sorts after this one.
+    pub after_id: Option<RefId>,
+    /// Maximum number of artifacts returned.
+    pub limit: Option<usize>,
+}
+
+impl ArtifactFilter {
+    /// Whether `meta` passes every condition except the limit.
+    pub fn matches(&self, meta: &ArtifactMetadata) -> bool {
+        self.after_id
+            .as_ref()
+            .is_none_or(|after| meta.id.as_str() > after.as_str())
+            && self.created_in_range(meta.created_at)
+            && self
+                .owner_user_id
+                .as_ref()
+                .is_none_or(|user| meta.owner_user_id.as_ref() == Some(user))
+            && self
+                .session_id
+                .as_ref()
+                .is_none_or(|session| meta.session_id.as_ref() == Some(session))
+    }
+
+    /// Whether `created_at` lies within the creation time bounds.
+    pub fn created_in_range(&self, created_at: i64) -> bool {
+        self.created_after.is_none_or(|after| created_at >= after)
+            && self.created_before.is_none_or(|before| created_at < before)
+    }
 }
 
 /// Storage tier for tiered storage.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum StorageTier {
     /// Hot storage (in-memory, fastest).
     Hot,
@@ -110,12 +192,64 @@ pub struct KnowledgeEntry {
     pub tags: Vec<String>,
     /// Unix timestamp of creation.
     pub created_at: i64,
+    /// SHA-256 of the content, used to deduplicate ingestion.
+    /// Stores compute it from `summary` when absent.
+    #[serde(default)]
+    pub content_hash: Option<String>,
+    /// URL the content was retrieved from, if it can be re-crawled.
+    #[serde(default)]
+    pub source_url: Option<String>,
+    /// Unix timestamp the content was last confirmed current.
+    #[serde(default)]
+    pub refreshed_at: Option<i64>,
+    /// Number of times the same content was ingested again.
+    #[serde(default)]
+    pub hits: u32,
+}
+
+impl KnowledgeEntry {
+    /// Hash used for deduplication: SHA-256 of the whitespace-normalised text.
+    pub fn hash_content(text: &str) -> String {
+        use sha2::{Digest, Sha256};
+        let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        format!("{:x}", Sha256::digest(normalized.as_bytes()))
+    }
+
+    /// Stored hash, or one computed from the summary.
+    pub fn effective_hash(&self) -> String {
+        self.content_hash
+            .clone()
+            .unwrap_or_else(|| Self::hash_content(&self.summary))
+    }
+
+    /// When the content was last known to be current.
+    pub fn last_refreshed(&self) -> i64 {
+        self.refreshed_at.unwrap_or(self.created_at)
+    }
+
+    /// Fold a duplicate ingestion into this entry: merge tags, count the hit
+    /// and mark the content fresh.
+    pub fn absorb(&mut self, duplicate: &KnowledgeEntry) {
+        for tag in &duplicate.tags {
+            if !self.tags.contains(tag) {
+                self.tags.push(tag.clone());
+            }
+        }
+        if self.source_url.is_none() {
+            self.source_url = duplicate.source_url.clone();
+        }
+        self.hits = self.hits.saturating_add(1);
+        self.refreshed_at = Some(duplicate.last_refreshed().max(self.last_refreshed()));
+    }
 }
 
 /// Interface for persistent knowledge storage with semantic search.
 #[async_trait]
 pub trait KnowledgeStore: Send + Sync {
     /// Store a knowledge entry. Returns the entry ID.
+    ///
+    /// An entry whose content hash matches an existing entry of the same user
+    /// (under a different ID) is merged into it and the existing ID returned.
     async fn store(&self, entry: KnowledgeEntry) -> Result<String>;
 
     /// Search for relevant knowledge by embedding similarity.
@@ -124,11 +258,27 @@ pub trait KnowledgeStore: Send + Sync {
     /// Search by tags.
     async fn search_by_tags(&self, tags: &[String], limit: usize) -> Result<Vec<KnowledgeEntry>>;
 
+    /// Keyword search over summaries, source tasks and tags, best matches
+    /// first. When `user_id` is given only that user's entries are searched.
+    async fn search_text(
+        &self,
+        query: &str,
+        user_id: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<KnowledgeEntry>>;
+
     /// Delete a knowledge entry.
     async fn delete(&self, id: &str) -> Result<()>;
 
     /// Get the total number of knowledge entries.
     async fn count(&self) -> Result<usize>;
+
+    /// Re-crawlable entries (with a `source_url`) last refreshed before
+    /// `older_than`, most frequently ingested first.
+    async fn stale(&self, older_than: i64, limit: usize) -> Result<Vec<KnowledgeEntry>>;
+
+    /// Number of entries per tag, for coverage reporting.
+    async fn tag_counts(&self) -> Result<HashMap<String, usize>>;
 }
 
 /// Trait for stores that support data erasure (GDPR/Privacy).
@@ -137,4 +287,11 @@ pub trait Erasable: Send + Sync {
     /// Delete all data associated with a user.
     /// Returns the number of items deleted.
     async fn erase_user(&self, user_id: &str) -> Result<usize>;
+
+    /// Short name used to attribute deletions in reports.
+    fn store_name(&self) -> &'static str {
+        let full = std::any::type_name::<Self>();
+        let base = full.split('<').next().unwrap_or(full);
+        base.rsplit("::").next().unwrap_or(base)
+    }
 }
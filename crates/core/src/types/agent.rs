@@ -1,4 +1,5 @@
 use super::refs::RefId;
+use super::research::Citation;
 use serde::{Deserialize, Serialize};
 
 // =============================================================================
@@ -22,9 +23,49 @@ pub enum AgentResult {
         mime_type: String,
     },
 
+    /// Several file artifacts produced by the task.
+    Artifacts {
+        /// The artifacts, in the order they were produced.
+        artifacts: Vec<ResultArtifact>,
+    },
+
     /// Structured data response.
     Data(serde_json::Value),
 
+    /// Tabular data.
+    Table {
+        /// Column headers.
+        columns: Vec<String>,
+        /// Rows; each row has one cell per column.
+        rows: Vec<Vec<serde_json::Value>>,
+        /// Optional caption shown above the table.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        caption: Option<String>,
+    },
+
+    /// Text annotated with the sources it cites.
+    ///
+    /// Markers in `text` are citation IDs (`[src-1a2b3c4d]`); citations
+    /// without an ID are referred to by position (`[1]` is `citations[0]`).
+    CitedText {
+        /// The answer text.
+        text: String,
+        /// Sources referenced by the text.
+        citations: Vec<Citation>,
+    },
+
+    /// A result too large to return inline; the full content is in L3.
+    Reference {
+        /// Reference to the full result in L3.
+        ref_id: RefId,
+        /// MIME type of the stored content.
+        mime_type: String,
+        /// Size of the full result in bytes.
+        size: usize,
+        /// Leading part of the result.
+        preview: String,
+    },
+
     /// Interactive UI component (React/JSON).
     UiComponent {
         /// Component type.
@@ -42,6 +83,104 @@ pub enum AgentResult {
     },
 }
 
+/// A file artifact listed in an [`AgentResult::Artifacts`] result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultArtifact {
+    /// Reference to the file in L3.
+    pub ref_id: RefId,
+    /// File name.
+    pub filename: String,
+    /// MIME type.
+    pub mime_type: String,
+}
+
+impl AgentResult {
+    /// Interpret a ReAct final answer.
+    ///
+    /// A final answer that is a JSON object tagged with one of the structured
+    /// result types (`Artifacts`, `Data`, `Table`, `CitedText`, `File`) is
+    /// returned as that variant; anything else is plain text.
+    pub fn from_final_answer(answer: &str) -> Self {
+        let trimmed = answer.trim();
+        if trimmed.starts_with('{') {
+            if let Ok(result) = serde_json::from_str::<AgentResult>(trimmed) {
+                if matches!(
+                    result,
+                    Self::File { .. }
+                        | Self::Artifacts { .. }
+                        | Self::Data(_)
+                        | Self::Table { .. }
+                        | Self::CitedText { .. }
+                ) {
+                    return result;
+                }
+            }
+        }
+        Self::Text(answer.to_string())
+    }
+
+    /// Flatten the result to plain text, for caches, memory and logs.
+    pub fn to_text(&self) -> String {
+        match self {
+            Self::Text(text) => text.clone(),
+            Self::File { filename, .. } => format!("file: {}", filename),
+            Self::Artifacts { artifacts } => artifacts
+                .iter()
+                .map(|a| format!("file: {}", a.filename))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Self::Data(value) => value.to_string(),
+            Self::Table {
+                columns,
+                rows,
+                caption,
+            } => {
+                let mut lines = Vec::with_capacity(rows.len() + 2);
+                if let Some(caption) = caption {
+                    lines.push(caption.clone());
+                }
+                lines.push(columns.join("\t"));
+                for row in rows {
+                    let cells: Vec<String> = row
+                        .iter()
+                        .map(|cell| match cell {
+                            serde_json::Value::String(s) => s.clone(),
+                            other => other.to_string(),
+                        })
+                        .collect();
+                    lines.push(cells.join("\t"));
+                }
+                lines.join("\n")
+            }
+            Self::CitedText { text, citations } => {
+                let mut out = text.clone();
+                if !citations.is_empty() {
+                    out.push_str("\n\nSources:");
+                    for (i, citation) in citations.iter().enumerate() {
+                        let marker = if citation.id.is_empty() {
+                            (i + 1).to_string()
+                        } else {
+                            citation.id.clone()
+                        };
+                        out.push_str(&format!("\n[{}] {}", marker, citation.url));
+                    }
+                }
+                out
+            }
+            Self::Reference {
+                ref_id,
+                size,
+                preview,
+                ..
+            } => format!("{}\n[{} bytes; full result: {}]", preview, size, ref_id),
+            Self::UiComponent { component_type, .. } => {
+                format!("ui_component: {}", component_type)
+            }
+            Self::Error { message, .. } => format!("error: {}", message),
+        }
+    }
+}
+
 // =============================================================================
 // Tool Risk Levels & Approval Types (L4 Governance)
 // =============================================================================
@@ -96,6 +235,10 @@ pub struct ApprovalRequest {
     pub nonce: String,
     /// Expiration timestamp (Unix epoch).
     pub expires_at: i64,
+    /// The tool itself requires approval for this call, whatever the policy.
+    /// Such calls are never covered by session-wide pre-authorization.
+    #[serde(default)]
+    pub mandatory: bool,
 }
 
 /// Human's response to an approval request.
@@ -108,6 +251,9 @@ pub enum ApprovalResponse {
         reason: Option<String>,
         /// Mandatory reason code for auditing (e.g., "USER_APPROVED", "AUTO_APPROVED").
         reason_code: String,
+        /// Which later calls this approval also covers.
+        #[serde(default)]
+        scope: ApprovalScope,
     },
     /// Denied — do not execute.
     Denied {
@@ -126,3 +272,104 @@ pub enum ApprovalResponse {
         reason_code: String,
     },
 }
+
+/// How far an approval extends beyond the call it was given for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ApprovalScope {
+    /// Only the call under review.
+    #[default]
+    Once,
+    /// This call and further calls with identical arguments, `count` in total.
+    Calls { count: u32 },
+    /// Every call to the same tool for the rest of the session.
+    Session,
+}
+
+/// Clarification question the agent asks the human mid-mission.
+///
+/// Unlike an [`ApprovalRequest`], nothing is being authorised: the session
+/// waits for free-form input and continues with the answer as an observation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HumanQuestion {
+    /// Unique ID for this question.
+    pub request_id: String,
+    /// Session asking the question.
+    pub session_id: String,
+    /// The question, as shown to the human.
+    pub question: String,
+    /// Suggested answers; the human may still answer freely.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+    /// Timeout for this specific question.
+    pub timeout_secs: Option<u64>,
+    /// Nonce that must accompany the answer.
+    pub nonce: String,
+    /// Expiration timestamp (Unix epoch).
+    pub expires_at: i64,
+}
+
+/// Human's reply to a [`HumanQuestion`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "payload")]
+pub enum HumanAnswer {
+    /// The human answered.
+    Answered {
+        /// The answer text.
+        answer: String,
+    },
+    /// Nobody answered before the timeout.
+    TimedOut,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_final_answer_parses_structured_results() {
+        let table = AgentResult::from_final_answer(
+            r#"{"type": "Table", "payload": {"columns": ["city", "pop"], "rows": [["Oslo", 709000]]}}"#,
+        );
+        match &table {
+            AgentResult::Table { columns, rows, .. } => {
+                assert_eq!(columns, &vec!["city".to_string(), "pop".to_string()]);
+                assert_eq!(rows[0][1], serde_json::json!(709000));
+            }
+            other => panic!("expected table, got {:?}", other),
+        }
+        assert_eq!(table.to_text(), "city\tpop\nOslo\t709000");
+
+        // Plain text, malformed JSON and error payloads all stay text.
+        for answer in [
+            "The answer is 42.",
+            "{not json",
+            r#"{"type": "Error", "payload": {"message": "x", "code": "y"}}"#,
+        ] {
+            assert!(matches!(
+                AgentResult::from_final_answer(answer),
+                AgentResult::Text(ref t) if t == answer
+            ));
+        }
+    }
+
+    #[test]
+    fn test_cited_text_round_trip() {
+        let result = AgentResult::CitedText {
+            text: "Rust 1.0 shipped in 2015 [1].".into(),
+            citations: vec![Citation {
+                id: String::new(),
+                url: "https://blog.rust-lang.org/2015/05/15/Rust-1.0.html".into(),
+                hash: "abc".into(),
+                context: "Rust blog".into(),
+                artifact_id: None,
+            }],
+        };
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["type"], "CitedText");
+        assert_eq!(json["payload"]["citations"][0]["hash"], "abc");
+
+        let text = result.to_text();
+        assert!(text.ends_with("[1] https://blog.rust-lang.org/2015/05/15/Rust-1.0.html"));
+    }
+}
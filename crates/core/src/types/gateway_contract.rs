@@ -32,6 +32,8 @@ pub enum ApiErrorCode {
     Unauthorized,
     Forbidden,
     Conflict,
+    /// Too many concurrent missions for the user or workspace.
+    ConcurrencyLimited,
     InternalError,
 }
 
@@ -32,5 +32,11 @@ pub enum UserIntent {
         /// User ID for isolation.
         #[serde(default)]
         user_id: Option<String>,
+        /// Workspace the request was made in.
+        #[serde(default)]
+        workspace_id: Option<String>,
+        /// Simulate mutating tool calls instead of running them.
+        #[serde(default)]
+        dry_run: bool,
     },
 }
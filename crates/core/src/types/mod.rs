@@ -13,6 +13,7 @@ pub mod refs;
 pub mod request;
 pub mod research;
 pub mod session;
+pub mod stream;
 pub mod tool;
 
 // Re-export everything to maintain backward compatibility
@@ -23,4 +24,5 @@ pub use model::*;
 pub use refs::*;
 pub use request::*;
 pub use session::*;
+pub use stream::*;
 pub use tool::*;
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::future::Future;
 use uuid::Uuid;
 
 // =============================================================================
@@ -38,3 +39,74 @@ impl std::fmt::Display for RefId {
         write!(f, "{}", self.0)
     }
 }
+
+// =============================================================================
+// Artifact Ownership
+// =============================================================================
+
+tokio::task_local! {
+    static CURRENT_OWNER: ArtifactOwner;
+}
+
+/// The user and session an artifact was created for.
+///
+/// Stores record this alongside each artifact so that `erase_user` can find
+/// everything a user created, regardless of how the artifact was keyed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactOwner {
+    /// Owning user.
+    pub user_id: Option<String>,
+    /// Session the artifact was created in.
+    pub session_id: Option<String>,
+}
+
+impl ArtifactOwner {
+    /// Create a new owner.
+    pub fn new(user_id: Option<String>, session_id: Option<String>) -> Self {
+        Self {
+            user_id,
+            session_id,
+        }
+    }
+
+    /// Whether neither a user nor a session is set.
+    pub fn is_empty(&self) -> bool {
+        self.user_id.is_none() && self.session_id.is_none()
+    }
+
+    /// Run `fut` with this owner as the execution context.
+    /// Artifacts saved inside the future are attributed to this owner.
+    pub async fn scope<F: Future>(self, fut: F) -> F::Output {
+        CURRENT_OWNER.scope(self, fut).await
+    }
+
+    /// The owner of the current execution context, if any.
+    pub fn current() -> Option<Self> {
+        CURRENT_OWNER
+            .try_with(|owner| owner.clone())
+            .ok()
+            .filter(|owner| !owner.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_owner_scope() {
+        assert_eq!(ArtifactOwner::current(), None);
+
+        let owner = ArtifactOwner::new(Some("alice".into()), Some("s1".into()));
+        let seen = owner
+            .clone()
+            .scope(async { ArtifactOwner::current() })
+            .await;
+        assert_eq!(seen, Some(owner));
+
+        let empty = ArtifactOwner::default()
+            .scope(async { ArtifactOwner::current() })
+            .await;
+        assert_eq!(empty, None);
+    }
+}
@@ -1,7 +1,14 @@
 use super::refs::RefId;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::future::Future;
 use uuid::Uuid;
 
+tokio::task_local! {
+    static CURRENT_CONTEXT: RequestContext;
+}
+
 // =============================================================================
 // Request Types
 // =============================================================================
@@ -53,13 +60,22 @@ pub struct NormalizedRequest {
     /// References to artifacts in L3.
     pub refs: Vec<RefId>,
 
-    /// Request metadata.
-    pub metadata: RequestMetadata,
+    /// Who sent the request and how it should be handled.
+    #[serde(alias = "metadata")]
+    pub context: RequestContext,
 }
 
-/// Metadata associated with a request.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
-pub struct RequestMetadata {
+/// Typed context of a request, propagated from the gateway through routing,
+/// the controller, tools and events.
+///
+/// The gateway runs each request inside its context (see [`Self::scope`]), so
+/// code far from the handler can read it with [`Self::current`]. Contexts
+/// serialized before the typed fields existed keep routing keys in a `custom`
+/// string map; those keys are read into the typed fields and anything else is
+/// kept in [`Self::extra`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "RawRequestContext")]
+pub struct RequestContext {
     /// User identifier.
     pub user_id: Option<String>,
 
@@ -72,8 +88,137 @@ pub struct RequestMetadata {
     /// Trace identifier for distributed tracing.
     pub trace_id: Option<String>,
 
-    /// Custom key-value metadata.
-    pub custom: std::collections::HashMap<String, String>,
+    /// Channel the request arrived on (e.g. `slack`, `support`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+
+    /// Account the request belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+
+    /// Peer (end user on the channel) the request came from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peer: Option<String>,
+
+    /// Routing policy release to resolve against (`stable` or `canary`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub routing_channel: Option<String>,
+
+    /// Preferred locale (BCP 47, e.g. `de-DE`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+
+    /// Time by which the request must be answered.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<DateTime<Utc>>,
+
+    /// Feature flags resolved for this request.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub feature_flags: BTreeMap<String, bool>,
+
+    /// Untyped key-value metadata.
+    #[serde(rename = "custom", skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra: BTreeMap<String, String>,
+}
+
+/// Former name of [`RequestContext`].
+pub type RequestMetadata = RequestContext;
+
+/// Wire form of [`RequestContext`], accepting the legacy `custom` map.
+#[derive(Deserialize)]
+struct RawRequestContext {
+    #[serde(default)]
+    user_id: Option<String>,
+    #[serde(default)]
+    workspace_id: Option<String>,
+    #[serde(default)]
+    session_id: Option<String>,
+    #[serde(default)]
+    trace_id: Option<String>,
+    #[serde(default)]
+    channel: Option<String>,
+    #[serde(default)]
+    account: Option<String>,
+    #[serde(default)]
+    peer: Option<String>,
+    #[serde(default)]
+    routing_channel: Option<String>,
+    #[serde(default)]
+    locale: Option<String>,
+    #[serde(default)]
+    deadline: Option<DateTime<Utc>>,
+    #[serde(default)]
+    feature_flags: BTreeMap<String, bool>,
+    #[serde(default)]
+    custom: BTreeMap<String, String>,
+}
+
+impl From<RawRequestContext> for RequestContext {
+    fn from(raw: RawRequestContext) -> Self {
+        let mut custom = raw.custom;
+        Self {
+            user_id: raw.user_id,
+            workspace_id: raw.workspace_id,
+            session_id: raw.session_id,
+            trace_id: raw.trace_id,
+            channel: raw.channel.or_else(|| custom.remove("channel")),
+            account: raw.account.or_else(|| custom.remove("account")),
+            peer: raw.peer.or_else(|| custom.remove("peer")),
+            routing_channel: raw
+                .routing_channel
+                .or_else(|| custom.remove("routing_channel")),
+            locale: raw.locale.or_else(|| custom.remove("locale")),
+            deadline: raw.deadline,
+            feature_flags: raw.feature_flags,
+            extra: custom,
+        }
+    }
+}
+
+impl RequestContext {
+    /// Run `fut` with this context as the current request context.
+    pub async fn scope<F: Future>(self, fut: F) -> F::Output {
+        CURRENT_CONTEXT.scope(self, fut).await
+    }
+
+    /// The request context of the current execution context, if any.
+    pub fn current() -> Option<Self> {
+        CURRENT_CONTEXT.try_with(|context| context.clone()).ok()
+    }
+
+    /// Whether the deadline, if any, has passed.
+    pub fn deadline_passed(&self) -> bool {
+        self.deadline.is_some_and(|deadline| Utc::now() >= deadline)
+    }
+
+    /// Whether `flag` is enabled for this request; `None` if it was not
+    /// resolved.
+    pub fn flag(&self, flag: &str) -> Option<bool> {
+        self.feature_flags.get(flag).copied()
+    }
+
+    /// Whether the current request has `flag` turned off. Unresolved flags,
+    /// and code running outside a request, count as on.
+    pub fn flag_off(flag: &str) -> bool {
+        CURRENT_CONTEXT
+            .try_with(|context| context.flag(flag) == Some(false))
+            .unwrap_or(false)
+    }
+}
+
+/// Names of the feature flags consulted across the crates.
+pub mod flags {
+    /// Parallel tool execution in SOP plans.
+    pub const PARALLEL_TOOLS: &str = "parallel_tools";
+    /// Delegating subtasks to other agents.
+    pub const DELEGATION: &str = "delegation";
+    /// Drafting actions with a small model, verified by a large one.
+    pub const SPECULATIVE: &str = "speculative";
+
+    /// Flag gating the model provider registered under `key`.
+    pub fn provider(key: &str) -> String {
+        format!("provider.{}", key)
+    }
 }
 
 impl NormalizedRequest {
@@ -85,7 +230,7 @@ impl NormalizedRequest {
             content: content.clone(),
             original_content: RequestContent::Text(content),
             refs: Vec::new(),
-            metadata: RequestMetadata::default(),
+            context: RequestContext::default(),
         }
     }
 
@@ -95,9 +240,65 @@ impl NormalizedRequest {
         self
     }
 
-    /// Add metadata to the request.
-    pub fn with_metadata(mut self, metadata: RequestMetadata) -> Self {
-        self.metadata = metadata;
+    /// Set the request context.
+    pub fn with_context(mut self, context: RequestContext) -> Self {
+        self.context = context;
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_custom_map() {
+        let legacy = serde_json::json!({
+            "user_id": "u1",
+            "workspace_id": null,
+            "session_id": null,
+            "trace_id": "t1",
+            "custom": { "channel": "support", "peer": "vip", "ticket": "42" }
+        });
+        let context: RequestContext = serde_json::from_value(legacy).unwrap();
+        assert_eq!(context.user_id.as_deref(), Some("u1"));
+        assert_eq!(context.channel.as_deref(), Some("support"));
+        assert_eq!(context.peer.as_deref(), Some("vip"));
+        assert_eq!(context.extra.get("ticket").map(String::as_str), Some("42"));
+        assert!(!context.extra.contains_key("channel"));
+
+        let round_trip: RequestContext =
+            serde_json::from_value(serde_json::to_value(&context).unwrap()).unwrap();
+        assert_eq!(round_trip, context);
+
+        let request: NormalizedRequest = serde_json::from_value(serde_json::json!({
+            "trace_id": "t1",
+            "content": "hi",
+            "original_content": { "type": "Text", "data": "hi" },
+            "refs": [],
+            "metadata": { "custom": { "routing_channel": "canary" } }
+        }))
+        .unwrap();
+        assert_eq!(request.context.routing_channel.as_deref(), Some("canary"));
+    }
+
+    #[tokio::test]
+    async fn test_scope_and_deadline() {
+        assert!(RequestContext::current().is_none());
+        let context = RequestContext {
+            locale: Some("de-DE".into()),
+            deadline: Some(Utc::now() - chrono::Duration::seconds(1)),
+            feature_flags: BTreeMap::from([("parallel_tools".to_string(), true)]),
+            ..Default::default()
+        };
+        context
+            .scope(async {
+                let current = RequestContext::current().unwrap();
+                assert_eq!(current.locale.as_deref(), Some("de-DE"));
+                assert!(current.deadline_passed());
+                assert_eq!(current.flag("parallel_tools"), Some(true));
+                assert_eq!(current.flag("delegation"), None);
+            })
+            .await;
+    }
+}
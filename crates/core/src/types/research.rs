@@ -18,13 +18,102 @@ pub struct ResearchPlan {
     pub budget_tokens: Option<u32>,
 }
 
+/// Output style of the research report.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ResearchStyle {
+    /// A few paragraphs answering the query.
+    Brief,
+    /// A comprehensive report.
+    #[default]
+    Deep,
+}
+
+/// Caller-supplied limits for a research run.
+///
+/// Unset limits fall back to the governance research budget.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ResearchOptions {
+    /// Maximum number of sources to fetch.
+    pub max_sources: Option<u32>,
+    /// Link-following depth from each seed domain (0 = seed pages only).
+    pub max_depth: Option<u32>,
+    /// Time budget for fetching sources, in seconds.
+    pub time_budget_secs: Option<u64>,
+    /// Restrict crawling to these domains (a subset of the network allowlist).
+    pub allowed_domains: Vec<String>,
+    /// Report style.
+    pub style: ResearchStyle,
+    /// Extra tags stored with the report in the knowledge base.
+    pub tags: Vec<String>,
+}
+
+/// One section of a research report, delivered as soon as it is written.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ReportSection {
+    /// Position of the section in the report.
+    pub index: usize,
+    /// Section title (the research goal it covers).
+    pub title: String,
+    /// Section text, with `[src-…]` citation markers.
+    pub content: String,
+}
+
 /// A citation in the final research report.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Citation {
+    /// Stable citation ID used as the `[id]` marker in the report text.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
     /// Source URL.
     pub url: String,
     /// The specific snippet or content hash.
     pub hash: String,
     /// Brief context or title.
     pub context: String,
+    /// Artifact holding the snapshot of the source as it was fetched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artifact_id: Option<String>,
+}
+
+/// A source fetched during a research run, snapshotted in the artifact store.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SourceSnapshot {
+    /// Stable citation ID, derived from the content hash.
+    pub id: String,
+    /// URL the source was fetched from.
+    pub url: String,
+    /// Brief context or title (the host, by default).
+    pub context: String,
+    /// SHA-256 of the fetched bytes.
+    pub hash: String,
+    /// Artifact holding the fetched bytes.
+    pub artifact_id: String,
+    /// Content type reported by the server.
+    pub content_type: String,
+    /// Size of the snapshot in bytes.
+    pub size_bytes: u64,
+    /// Crawl depth the source was found at (0 = seed page).
+    pub depth: u32,
+    /// Fetch time (Unix epoch seconds).
+    pub retrieved_at: i64,
+    /// Whether the report cites this source.
+    pub cited: bool,
+    /// Whether the stored snapshot still matches `hash` (set when served for review).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified: Option<bool>,
+}
+
+impl SourceSnapshot {
+    /// Citation for this source.
+    pub fn citation(&self) -> Citation {
+        Citation {
+            id: self.id.clone(),
+            url: self.url.clone(),
+            hash: self.hash.clone(),
+            context: self.context.clone(),
+            artifact_id: Some(self.artifact_id.clone()),
+        }
+    }
 }
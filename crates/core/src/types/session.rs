@@ -17,6 +17,14 @@ pub struct Session {
     /// User ID of the session owner (for isolation).
     pub user_id: Option<String>,
 
+    /// Workspace the session runs in (selects approval overrides).
+    #[serde(default)]
+    pub workspace_id: Option<String>,
+
+    /// Dry-run sessions simulate mutating tool calls (see the controller).
+    #[serde(default)]
+    pub dry_run: bool,
+
     /// Current status.
     pub status: SessionStatus,
 
@@ -47,6 +55,17 @@ pub enum SessionStatus {
     Completed,
     /// Session failed with error.
     Failed,
+    /// Session ended with a report-only answer after an approval deadlock.
+    Degraded,
+}
+
+/// Change to a watched session.
+#[derive(Debug, Clone)]
+pub enum SessionChange {
+    /// The session was saved; carries its new state.
+    Saved(Box<Session>),
+    /// The session was deleted or expired.
+    Deleted,
 }
 
 /// Entry in conversation history.
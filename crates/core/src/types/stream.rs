@@ -0,0 +1,132 @@
+//! Streamed progress of an agent execution.
+//!
+//! A controller reports progress by emitting [`AgentStreamEvent`]s while it
+//! runs; [`stream_execution`] collects the events emitted inside an
+//! execution into a stream that ends with the execution's outcome. Emitting
+//! outside a streamed execution does nothing, so controllers emit
+//! unconditionally.
+
+use super::agent::AgentResult;
+use crate::error::Result;
+use futures::stream::{BoxStream, StreamExt};
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+tokio::task_local! {
+    static CURRENT_STREAM: UnboundedSender<AgentStreamEvent>;
+}
+
+/// Stream of events from one execution.
+pub type AgentEventStream<'a> = BoxStream<'a, AgentStreamEvent>;
+
+/// One step of an agent execution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AgentStreamEvent {
+    /// Reasoning the model gave before acting.
+    Thought { content: String },
+    /// A tool the agent decided to call.
+    ToolCall {
+        tool: String,
+        args: serde_json::Value,
+    },
+    /// What an action returned, as shown to the model.
+    Observation {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tool: Option<String>,
+        content: String,
+    },
+    /// The execution finished; always the last event on success.
+    Final { result: AgentResult },
+    /// The execution failed; always the last event on failure.
+    Error { message: String },
+}
+
+impl AgentStreamEvent {
+    /// Event name, as used for the SSE `event` field.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Thought { .. } => "thought",
+            Self::ToolCall { .. } => "tool_call",
+            Self::Observation { .. } => "observation",
+            Self::Final { .. } => "final",
+            Self::Error { .. } => "error",
+        }
+    }
+
+    /// Terminal event for an execution's outcome.
+    pub fn finished(outcome: Result<AgentResult>) -> Self {
+        match outcome {
+            Ok(result) => Self::Final { result },
+            Err(e) => Self::Error {
+                message: e.to_string(),
+            },
+        }
+    }
+
+    /// Send this event to the stream of the current execution, if any.
+    pub fn emit(self) {
+        let _ = CURRENT_STREAM.try_with(|stream| stream.send(self));
+    }
+}
+
+/// Run `execution`, streaming the events it emits followed by its outcome.
+///
+/// The execution runs as the stream is polled; dropping the stream cancels
+/// it.
+pub fn stream_execution<'a>(
+    execution: impl Future<Output = Result<AgentResult>> + Send + 'a,
+) -> AgentEventStream<'a> {
+    let (sender, receiver) = unbounded_channel();
+    let outcome = sender.clone();
+    let run = CURRENT_STREAM.scope(sender, async move {
+        let _ = outcome.send(AgentStreamEvent::finished(execution.await));
+    });
+    // Ends once the execution is done and its events are drained
+    let events = futures::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|event| (event, receiver))
+    });
+    let run = run
+        .into_stream()
+        .filter_map(|()| futures::future::ready(None));
+    futures::stream::select(events, run).boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    #[tokio::test]
+    async fn test_stream_execution() {
+        let events: Vec<_> = stream_execution(async {
+            AgentStreamEvent::Thought {
+                content: "look it up".into(),
+            }
+            .emit();
+            Ok(AgentResult::Text("42".into()))
+        })
+        .collect()
+        .await;
+        assert_eq!(
+            events.iter().map(|e| e.name()).collect::<Vec<_>>(),
+            vec!["thought", "final"]
+        );
+
+        let events: Vec<_> = stream_execution(async { Err(Error::controller("boom")) })
+            .collect()
+            .await;
+        assert_eq!(events.len(), 1);
+        assert!(
+            matches!(&events[0], AgentStreamEvent::Error { message } if message.contains("boom"))
+        );
+
+        // Outside a streamed execution, emitting is a no-op
+        AgentStreamEvent::Thought {
+            content: "unheard".into(),
+        }
+        .emit();
+    }
+}
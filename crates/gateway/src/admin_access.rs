@@ -0,0 +1,463 @@
+//! Network and step-up checks for admin routes.
+//!
+//! Admin routes are served to loopback clients. With
+//! `governance.remote_admin.enabled`, clients whose IP is in `allowed_ips`
+//! may use them too, provided they also send an `x-admin-elevation` token.
+//! Such a token is issued by `POST /v1/admin/auth/elevate` to an authenticated
+//! admin who proves a second factor — a TOTP code from their own secret in
+//! `remote_admin.totp_secrets` — and is bound to that user and client IP for
+//! `elevation_ttl_secs`. A code is accepted once per user, and repeated wrong
+//! codes lock the user out from that IP for a while.
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use dashmap::DashMap;
+use hmac::{Hmac, Mac};
+use secrecy::ExposeSecret;
+use sha1::Sha1;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use multi_agent_core::config::RemoteAdminConfig;
+use multi_agent_governance::rbac::{UserContext, UserRoles};
+use multi_agent_governance::{AuditEntry, AuditOutcome};
+
+use crate::server::AppState;
+
+/// Header carrying an elevated token on remote admin requests.
+pub const ELEVATION_HEADER: &str = "x-admin-elevation";
+
+/// TOTP step in seconds.
+const TOTP_STEP_SECS: u64 = 30;
+
+#[derive(Debug, Clone)]
+struct Elevation {
+    user_id: String,
+    ip: IpAddr,
+    expires_at: SystemTime,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FailedAttempts {
+    count: u32,
+    locked_until: Option<SystemTime>,
+}
+
+/// Elevated tokens issued to remote administrators, with the state guarding
+/// the elevation endpoint: failed attempts per user and IP, and the last TOTP
+/// step each user redeemed.
+#[derive(Default)]
+pub struct ElevationStore {
+    tokens: DashMap<String, Elevation>,
+    failures: DashMap<(String, IpAddr), FailedAttempts>,
+    last_counters: DashMap<String, u64>,
+}
+
+impl ElevationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Issue a token for `user_id` connecting from `ip`.
+    pub fn issue(&self, user_id: &str, ip: IpAddr, ttl: Duration) -> (String, SystemTime) {
+        let now = SystemTime::now();
+        self.tokens.retain(|_, e| e.expires_at > now);
+        let token = format!("elev_{}", uuid::Uuid::new_v4().simple());
+        let expires_at = now + ttl;
+        self.tokens.insert(
+            token.clone(),
+            Elevation {
+                user_id: user_id.to_string(),
+                ip,
+                expires_at,
+            },
+        );
+        (token, expires_at)
+    }
+
+    /// Whether `token` is unexpired and was issued to `user_id` at `ip`.
+    pub fn verify(&self, token: &str, user_id: &str, ip: IpAddr) -> bool {
+        let Some(elevation) = self.tokens.get(token).map(|e| e.clone()) else {
+            return false;
+        };
+        if elevation.expires_at <= SystemTime::now() {
+            self.tokens.remove(token);
+            return false;
+        }
+        elevation.user_id == user_id && elevation.ip == ip
+    }
+
+    /// When `user_id` at `ip` is locked out, the time the lockout ends.
+    pub fn locked_until(&self, user_id: &str, ip: IpAddr) -> Option<SystemTime> {
+        let key = (user_id.to_string(), ip);
+        let until = self.failures.get(&key)?.locked_until?;
+        if until > SystemTime::now() {
+            return Some(until);
+        }
+        self.failures.remove(&key);
+        None
+    }
+
+    /// Count a wrong code; the `max_attempts`th in a row locks `user_id` at
+    /// `ip` out for `lockout`. Returns the end of the lockout if one started.
+    pub fn record_failure(
+        &self,
+        user_id: &str,
+        ip: IpAddr,
+        max_attempts: u32,
+        lockout: Duration,
+    ) -> Option<SystemTime> {
+        let mut entry = self
+            .failures
+            .entry((user_id.to_string(), ip))
+            .or_insert(FailedAttempts {
+                count: 0,
+                locked_until: None,
+            });
+        entry.count += 1;
+        if max_attempts == 0 || entry.count < max_attempts {
+            return None;
+        }
+        let until = SystemTime::now() + lockout;
+        *entry = FailedAttempts {
+            count: 0,
+            locked_until: Some(until),
+        };
+        Some(until)
+    }
+
+    /// Forget the failed attempts of `user_id` at `ip`.
+    pub fn clear_failures(&self, user_id: &str, ip: IpAddr) {
+        self.failures.remove(&(user_id.to_string(), ip));
+    }
+
+    /// Redeem the TOTP step `counter` for `user_id`. Fails when the user
+    /// already redeemed that step or a later one, so a code cannot be replayed.
+    pub fn redeem_counter(&self, user_id: &str, counter: u64) -> bool {
+        let mut last = self.last_counters.entry(user_id.to_string()).or_insert(0);
+        if counter <= *last {
+            return false;
+        }
+        *last = counter;
+        true
+    }
+}
+
+/// Whether `ip` matches one of `allowed`, given as addresses or CIDR ranges.
+pub fn ip_allowed(allowed: &[String], ip: IpAddr) -> bool {
+    allowed.iter().any(|entry| {
+        if let Ok(net) = entry.parse::<ipnet::IpNet>() {
+            net.contains(&ip)
+        } else {
+            entry.parse::<IpAddr>().is_ok_and(|addr| addr == ip)
+        }
+    })
+}
+
+fn totp_code(secret: &[u8], counter: u64) -> u32 {
+    let mut mac = Hmac::<Sha1>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(&counter.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let value = u32::from_be_bytes([
+        hash[offset] & 0x7f,
+        hash[offset + 1],
+        hash[offset + 2],
+        hash[offset + 3],
+    ]);
+    value % 1_000_000
+}
+
+fn decode_secret(secret_base32: &str) -> Option<Vec<u8>> {
+    let normalized = secret_base32
+        .trim()
+        .trim_end_matches('=')
+        .replace(' ', "")
+        .to_ascii_uppercase();
+    data_encoding::BASE32_NOPAD
+        .decode(normalized.as_bytes())
+        .ok()
+}
+
+fn totp_counter(now: SystemTime) -> u64 {
+    now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() / TOTP_STEP_SECS
+}
+
+/// Six-digit TOTP code of a base32 secret at `now`.
+pub fn totp_at(secret_base32: &str, now: SystemTime) -> Option<String> {
+    let secret = decode_secret(secret_base32)?;
+    Some(format!("{:06}", totp_code(&secret, totp_counter(now))))
+}
+
+/// Check a six-digit TOTP `code` against a base32 secret, allowing one step
+/// of clock drift either way.
+pub fn verify_totp(secret_base32: &str, code: &str, now: SystemTime) -> bool {
+    totp_step(secret_base32, code, now).is_some()
+}
+
+/// The TOTP step `code` belongs to, if it is valid at `now` (with one step of
+/// clock drift either way).
+pub fn totp_step(secret_base32: &str, code: &str, now: SystemTime) -> Option<u64> {
+    let secret = decode_secret(secret_base32)?;
+    let code = code.trim().parse::<u32>().ok()?;
+    let counter = totp_counter(now);
+    [counter.saturating_sub(1), counter, counter + 1]
+        .into_iter()
+        .find(|c| totp_code(&secret, *c) == code)
+}
+
+fn caller(req: &Request) -> Option<(String, bool)> {
+    crate::identity::caller(req.extensions().get(), req.extensions().get())
+}
+
+fn forbidden(message: &str) -> Response {
+    (
+        StatusCode::FORBIDDEN,
+        Json(serde_json::json!({ "error": message })),
+    )
+        .into_response()
+}
+
+/// Decide whether `req` from `ip` may reach an admin route. `require_elevation`
+/// is false only for the elevation endpoint itself.
+pub(crate) fn check_admin_access(
+    state: &AppState,
+    ip: IpAddr,
+    req: &Request,
+    require_elevation: bool,
+) -> Result<(), Box<Response>> {
+    if ip.is_loopback() || state.app_config.governance.admin_allow_external_access {
+        return Ok(());
+    }
+
+    let remote: &RemoteAdminConfig = &state.app_config.governance.remote_admin;
+    if !remote.enabled {
+        tracing::warn!(client_ip = %ip, "Blocked non-localhost access to Admin API");
+        return Err(Box::new(forbidden("Admin API restricted to localhost")));
+    }
+    if !ip_allowed(&remote.allowed_ips, ip) {
+        tracing::warn!(client_ip = %ip, "Blocked admin access from outside the allowlist");
+        return Err(Box::new(forbidden(
+            "Client IP is not allowed to use the Admin API",
+        )));
+    }
+    if !require_elevation {
+        return Ok(());
+    }
+
+    let token = req
+        .headers()
+        .get(ELEVATION_HEADER)
+        .and_then(|v| v.to_str().ok());
+    let elevated = match (token, caller(req)) {
+        (Some(token), Some((user_id, _))) => state.elevations.verify(token, &user_id, ip),
+        _ => false,
+    };
+    if elevated {
+        Ok(())
+    } else {
+        Err(Box::new(
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({
+                    "error": "Step-up authentication required",
+                    "guidance": "POST /v1/admin/auth/elevate with a TOTP code and send the \
+                                 returned token in the x-admin-elevation header",
+                })),
+            )
+                .into_response(),
+        ))
+    }
+}
+
+/// Middleware for the elevation endpoint: network checks only.
+pub(crate) async fn admin_network_middleware(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    req: Request,
+    next: axum::middleware::Next,
+) -> Response {
+    match check_admin_access(&state, addr.ip(), &req, false) {
+        Ok(()) => next.run(req).await,
+        Err(response) => *response,
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct ElevateRequest {
+    /// Current TOTP code.
+    pub code: String,
+}
+
+async fn audit_elevation(state: &AppState, user_id: &str, ip: IpAddr, outcome: AuditOutcome) {
+    let Some(admin) = &state.admin_state else {
+        return;
+    };
+    let _ = admin
+        .audit_store
+        .log(AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            user_id: user_id.to_string(),
+            action: "ADMIN_ELEVATE".to_string(),
+            resource: "admin_session".to_string(),
+            outcome,
+            metadata: Some(serde_json::json!({ "client_ip": ip.to_string() })),
+            previous_hash: None,
+            hash: None,
+            trace_id: None,
+            session_id: None,
+        })
+        .await;
+}
+
+fn locked_out(until: SystemTime) -> Response {
+    let retry_after = until
+        .duration_since(SystemTime::now())
+        .unwrap_or_default()
+        .as_secs()
+        .max(1);
+    (
+        StatusCode::TOO_MANY_REQUESTS,
+        [(axum::http::header::RETRY_AFTER, retry_after.to_string())],
+        Json(serde_json::json!({ "error": "Too many failed attempts; try again later" })),
+    )
+        .into_response()
+}
+
+/// `POST /v1/admin/auth/elevate`
+///
+/// Exchanges a TOTP code for a short-lived elevated token.
+pub(crate) async fn elevate_handler(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    context: Option<Extension<UserContext>>,
+    roles: Option<Extension<UserRoles>>,
+    Json(body): Json<ElevateRequest>,
+) -> Response {
+    let remote = &state.app_config.governance.remote_admin;
+    if !remote.enabled || remote.totp_secrets.is_empty() {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "Remote admin is not enabled" })),
+        )
+            .into_response();
+    }
+
+    let Some((user_id, true)) = crate::identity::caller(context.as_deref(), roles.as_deref())
+    else {
+        return forbidden("Only administrators can elevate");
+    };
+
+    let ip = addr.ip();
+    let Some(secret) = remote.totp_secrets.get(&user_id) else {
+        tracing::warn!(user_id = %user_id, client_ip = %ip, "No TOTP secret enrolled for administrator");
+        audit_elevation(&state, &user_id, ip, AuditOutcome::Denied).await;
+        return forbidden("No TOTP secret is enrolled for this administrator");
+    };
+    if let Some(until) = state.elevations.locked_until(&user_id, ip) {
+        audit_elevation(&state, &user_id, ip, AuditOutcome::Denied).await;
+        return locked_out(until);
+    }
+
+    let step = totp_step(secret.expose_secret(), &body.code, SystemTime::now());
+    let valid = step.is_some_and(|step| state.elevations.redeem_counter(&user_id, step));
+    if !valid {
+        tracing::warn!(user_id = %user_id, client_ip = %ip, "Rejected admin elevation code");
+        audit_elevation(&state, &user_id, ip, AuditOutcome::Denied).await;
+        let lockout = Duration::from_secs(remote.lockout_secs);
+        if let Some(until) =
+            state
+                .elevations
+                .record_failure(&user_id, ip, remote.max_failed_attempts, lockout)
+        {
+            tracing::warn!(user_id = %user_id, client_ip = %ip, "Admin elevation locked after repeated failures");
+            return locked_out(until);
+        }
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({ "error": "Invalid code" })),
+        )
+            .into_response();
+    }
+    state.elevations.clear_failures(&user_id, ip);
+
+    let ttl = Duration::from_secs(remote.elevation_ttl_secs);
+    let (token, expires_at) = state.elevations.issue(&user_id, ip, ttl);
+    audit_elevation(&state, &user_id, ip, AuditOutcome::Success).await;
+    Json(serde_json::json!({
+        "token": token,
+        "header": ELEVATION_HEADER,
+        "expires_at": chrono::DateTime::<chrono::Utc>::from(expires_at).to_rfc3339(),
+    }))
+    .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_totp_rfc6238_vector() {
+        // RFC 6238 SHA-1 secret "12345678901234567890"; 94287082 at T=59.
+        let secret = data_encoding::BASE32_NOPAD.encode(b"12345678901234567890");
+        let at = UNIX_EPOCH + Duration::from_secs(59);
+        assert_eq!(totp_at(&secret, at).as_deref(), Some("287082"));
+        assert!(verify_totp(&secret, "287082", at));
+        assert!(verify_totp(&secret, "287082", at + Duration::from_secs(30)));
+        assert!(!verify_totp(
+            &secret,
+            "287082",
+            at + Duration::from_secs(120)
+        ));
+        assert!(!verify_totp(&secret, "000000", at));
+        assert!(!verify_totp("not base32!", "287082", at));
+    }
+
+    #[test]
+    fn test_ip_allowlist() {
+        let allowed = vec!["10.0.0.0/8".to_string(), "192.168.1.7".to_string()];
+        assert!(ip_allowed(&allowed, "10.2.3.4".parse().unwrap()));
+        assert!(ip_allowed(&allowed, "192.168.1.7".parse().unwrap()));
+        assert!(!ip_allowed(&allowed, "192.168.1.8".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_elevation_bound_to_user_and_ip() {
+        let store = ElevationStore::new();
+        let ip: IpAddr = "10.0.0.5".parse().unwrap();
+        let (token, _) = store.issue("alice", ip, Duration::from_secs(60));
+        assert!(store.verify(&token, "alice", ip));
+        assert!(!store.verify(&token, "bob", ip));
+        assert!(!store.verify(&token, "alice", "10.0.0.6".parse().unwrap()));
+
+        let (expired, _) = store.issue("alice", ip, Duration::ZERO);
+        assert!(!store.verify(&expired, "alice", ip));
+    }
+
+    #[test]
+    fn test_lockout_and_replay() {
+        let store = ElevationStore::new();
+        let ip: IpAddr = "10.0.0.5".parse().unwrap();
+        let lockout = Duration::from_secs(60);
+        assert!(store.record_failure("alice", ip, 3, lockout).is_none());
+        assert!(store.record_failure("alice", ip, 3, lockout).is_none());
+        assert!(store.record_failure("alice", ip, 3, lockout).is_some());
+        assert!(store.locked_until("alice", ip).is_some());
+        assert!(store
+            .locked_until("alice", "10.0.0.6".parse().unwrap())
+            .is_none());
+        assert!(store.locked_until("bob", ip).is_none());
+
+        assert!(store.redeem_counter("alice", 100));
+        assert!(!store.redeem_counter("alice", 100));
+        assert!(!store.redeem_counter("alice", 99));
+        assert!(store.redeem_counter("alice", 101));
+        assert!(store.redeem_counter("bob", 100));
+    }
+}
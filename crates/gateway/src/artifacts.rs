@@ -0,0 +1,542 @@
+//! Artifact retrieval endpoints.
+//!
+//! `GET /v1/agent/artifacts/:ref_id` streams an artifact with HTTP range support
+//! and content-type negotiation. Large artifacts held in an object store that can
+//! sign URLs are served via a temporary redirect to a pre-signed URL instead of
+//! being proxied through the gateway.
+//!
+//! `POST /v1/agent/artifacts` stores the files of a multipart upload, read
+//! chunk by chunk under `gateway.body_limits.upload_max_bytes`.
+//!
+//! `GET /v1/agent/sessions/:id/artifacts` lists the artifacts a session saved,
+//! as recorded by the artifact catalog.
+//!
+//! Results larger than `gateway.response.max_bytes` are saved here by
+//! [`limit_result`] and returned as a [`AgentResult::Reference`] with a preview.
+
+use axum::{
+    extract::{multipart::MultipartError, Multipart, Path, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use bytes::{Bytes, BytesMut};
+use std::sync::Arc;
+use std::time::Duration;
+
+use multi_agent_core::traits::{ArtifactMetadata, ArtifactStore};
+use multi_agent_core::types::{AgentResult, ArtifactOwner, RefId};
+use multi_agent_governance::rbac::{UserContext, UserRoles};
+
+use crate::identity::caller;
+use crate::server::AppState;
+
+/// A parsed `Range` header, resolved against the artifact size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RangeRequest {
+    /// No usable range; serve the whole artifact.
+    Full,
+    /// Inclusive byte range.
+    Partial(u64, u64),
+    /// The range lies outside the artifact.
+    Unsatisfiable,
+}
+
+/// Parse a single-range `bytes=` header. Multi-range and malformed headers are
+/// ignored, which RFC 9110 permits by serving the full representation.
+pub(crate) fn parse_range(value: &str, total: u64) -> RangeRequest {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return RangeRequest::Full;
+    };
+    if spec.contains(',') {
+        return RangeRequest::Full;
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return RangeRequest::Full;
+    };
+
+    match (start.trim(), end.trim()) {
+        ("", "") => RangeRequest::Full,
+        // Suffix range: the last `n` bytes.
+        ("", suffix) => match suffix.parse::<u64>() {
+            Ok(0) => RangeRequest::Unsatisfiable,
+            Ok(_) if total == 0 => RangeRequest::Unsatisfiable,
+            Ok(n) => RangeRequest::Partial(total.saturating_sub(n), total - 1),
+            Err(_) => RangeRequest::Full,
+        },
+        (start, end) => {
+            let Ok(start) = start.parse::<u64>() else {
+                return RangeRequest::Full;
+            };
+            let end = if end.is_empty() {
+                u64::MAX
+            } else {
+                match end.parse::<u64>() {
+                    Ok(end) if end >= start => end,
+                    _ => return RangeRequest::Full,
+                }
+            };
+            if start >= total {
+                RangeRequest::Unsatisfiable
+            } else {
+                RangeRequest::Partial(start, end.min(total - 1))
+            }
+        }
+    }
+}
+
+/// Whether an `Accept` header admits the given content type.
+pub(crate) fn accepts(accept: &str, content_type: &str) -> bool {
+    let content_type = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let major = content_type.split('/').next().unwrap_or_default();
+
+    accept.split(',').any(|range| {
+        let mut parts = range.split(';');
+        let media = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+        let refused = parts.any(|p| {
+            p.trim()
+                .strip_prefix("q=")
+                .and_then(|q| q.trim().parse::<f32>().ok())
+                .is_some_and(|q| q <= 0.0)
+        });
+        if refused {
+            return false;
+        }
+        media == "*/*"
+            || media == content_type
+            || media
+                .strip_suffix("/*")
+                .is_some_and(|prefix| prefix == major)
+    })
+}
+
+/// Keep `result` inline if it fits the response ceiling; otherwise save it as
+/// an artifact owned by `owner` and return a reference with a preview.
+pub(crate) async fn limit_result(
+    state: &AppState,
+    result: AgentResult,
+    owner: ArtifactOwner,
+) -> AgentResult {
+    let limits = &state.app_config.gateway.response;
+    if limits.max_bytes == 0 {
+        return result;
+    }
+    let (body, mime_type) = match &result {
+        AgentResult::Text(text) => (text.clone().into_bytes(), "text/plain"),
+        other => match serde_json::to_vec(other) {
+            Ok(json) => (json, "application/json"),
+            Err(_) => return result,
+        },
+    };
+    if body.len() <= limits.max_bytes {
+        return result;
+    }
+
+    let size = body.len();
+    let Some(store) = &state.artifact_store else {
+        tracing::warn!(
+            size,
+            "Oversized result dropped: artifact store not configured"
+        );
+        return AgentResult::Error {
+            message: format!(
+                "Result of {} bytes exceeds the {} byte response limit",
+                size, limits.max_bytes
+            ),
+            code: "RESPONSE_TOO_LARGE".to_string(),
+        };
+    };
+    match owner
+        .scope(store.save_with_type(Bytes::from(body), mime_type))
+        .await
+    {
+        Ok(ref_id) => {
+            metrics::counter!("gateway_results_by_reference_total").increment(1);
+            tracing::info!(ref_id = %ref_id, size, "Oversized result stored by reference");
+            AgentResult::Reference {
+                ref_id,
+                mime_type: mime_type.to_string(),
+                size,
+                preview: result
+                    .to_text()
+                    .chars()
+                    .take(limits.preview_chars)
+                    .collect(),
+            }
+        }
+        Err(e) => {
+            tracing::error!(size, error = %e, "Failed to store oversized result");
+            AgentResult::Error {
+                message: format!(
+                    "Result of {} bytes exceeds the {} byte response limit and could not be stored",
+                    size, limits.max_bytes
+                ),
+                code: "RESPONSE_TOO_LARGE".to_string(),
+            }
+        }
+    }
+}
+
+/// Look up an artifact the caller is allowed to read. Artifacts owned by another
+/// user, or owned at all when the caller is anonymous, are reported as missing
+/// so their existence is not disclosed.
+async fn authorized_metadata(
+    store: &Arc<dyn ArtifactStore>,
+    id: &RefId,
+    caller: Option<(String, bool)>,
+) -> Result<ArtifactMetadata, Box<Response>> {
+    let meta = match store.metadata(id).await {
+        Ok(Some(meta)) => meta,
+        Ok(None) => {
+            return Err(Box::new(error_response(
+                StatusCode::NOT_FOUND,
+                "Artifact not found",
+            )))
+        }
+        Err(e) => {
+            tracing::error!(ref_id = %id, error = %e, "Failed to read artifact metadata");
+            return Err(Box::new(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to read artifact",
+            )));
+        }
+    };
+
+    if !may_read(meta.owner_user_id.as_deref(), caller.as_ref()) {
+        return Err(Box::new(error_response(
+            StatusCode::NOT_FOUND,
+            "Artifact not found",
+        )));
+    }
+
+    Ok(meta)
+}
+
+/// Whether `caller` may see an artifact owned by `owner`: unowned artifacts
+/// are visible to everyone, owned ones only to their owner or an admin.
+fn may_read(owner: Option<&str>, caller: Option<&(String, bool)>) -> bool {
+    match owner {
+        None => true,
+        Some(owner) => caller.is_some_and(|(user_id, is_admin)| *is_admin || owner == user_id),
+    }
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (status, Json(serde_json::json!({ "error": message }))).into_response()
+}
+
+fn store_or_unavailable(state: &AppState) -> Result<&Arc<dyn ArtifactStore>, Box<Response>> {
+    state.artifact_store.as_ref().ok_or_else(|| {
+        Box::new(error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Artifact store not configured",
+        ))
+    })
+}
+
+/// `GET /artifacts/:ref_id`
+pub(crate) async fn get_artifact_handler(
+    State(state): State<Arc<AppState>>,
+    Path(ref_id): Path<String>,
+    context: Option<Extension<UserContext>>,
+    roles: Option<Extension<UserRoles>>,
+    headers: HeaderMap,
+) -> Response {
+    let store = match store_or_unavailable(&state) {
+        Ok(store) => store,
+        Err(resp) => return *resp,
+    };
+    let id = RefId::from_string(ref_id);
+    let meta =
+        match authorized_metadata(store, &id, caller(context.as_deref(), roles.as_deref())).await {
+            Ok(meta) => meta,
+            Err(resp) => return *resp,
+        };
+
+    if let Some(accept) = headers.get(header::ACCEPT).and_then(|v| v.to_str().ok()) {
+        if !accepts(accept, &meta.content_type) {
+            return error_response(
+                StatusCode::NOT_ACCEPTABLE,
+                &format!("Artifact is only available as {}", meta.content_type),
+            );
+        }
+    }
+
+    let total = meta.size as u64;
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| parse_range(v, total))
+        .unwrap_or(RangeRequest::Full);
+
+    let download = &state.app_config.store.artifact_download;
+    if range == RangeRequest::Full && total >= download.presign_threshold_bytes {
+        let expires_in = Duration::from_secs(download.presign_expiry_secs);
+        match store.presigned_url(&id, expires_in).await {
+            Ok(Some(url)) => {
+                if let Ok(location) = HeaderValue::from_str(&url) {
+                    return (
+                        StatusCode::TEMPORARY_REDIRECT,
+                        [(header::LOCATION, location)],
+                    )
+                        .into_response();
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                // Fall back to proxying the bytes.
+                tracing::warn!(ref_id = %id, error = %e, "Failed to pre-sign artifact URL");
+            }
+        }
+    }
+
+    let content_type = HeaderValue::from_str(&meta.content_type)
+        .unwrap_or_else(|_| HeaderValue::from_static("application/octet-stream"));
+
+    let (status, data, content_range) = match range {
+        RangeRequest::Unsatisfiable => {
+            return (
+                StatusCode::RANGE_NOT_SATISFIABLE,
+                [(header::CONTENT_RANGE, format!("bytes */{}", total))],
+            )
+                .into_response();
+        }
+        RangeRequest::Partial(start, end) => (
+            StatusCode::PARTIAL_CONTENT,
+            store.load_range(&id, start, end).await,
+            Some(format!("bytes {}-{}/{}", start, end, total)),
+        ),
+        RangeRequest::Full => (StatusCode::OK, store.load(&id).await, None),
+    };
+
+    let data = match data {
+        Ok(Some(data)) => data,
+        Ok(None) => return error_response(StatusCode::NOT_FOUND, "Artifact not found"),
+        Err(e) => {
+            tracing::error!(ref_id = %id, error = %e, "Failed to load artifact");
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR, "Failed to read artifact");
+        }
+    };
+
+    let mut response = (status, data).into_response();
+    let headers = response.headers_mut();
+    headers.insert(header::CONTENT_TYPE, content_type);
+    headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    if let Some(content_range) = content_range.and_then(|v| HeaderValue::from_str(&v).ok()) {
+        headers.insert(header::CONTENT_RANGE, content_range);
+    }
+    response
+}
+
+fn multipart_error(e: MultipartError) -> Response {
+    error_response(e.status(), &e.body_text())
+}
+
+/// `POST /artifacts`
+///
+/// Saves each file field of a multipart upload as an artifact owned by the
+/// caller. Non-file fields are ignored.
+pub(crate) async fn upload_artifact_handler(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<UserContext>>,
+    roles: Option<Extension<UserRoles>>,
+    mut multipart: Multipart,
+) -> Response {
+    let store = match store_or_unavailable(&state) {
+        Ok(store) => store,
+        Err(resp) => return *resp,
+    };
+    let owner = ArtifactOwner::new(
+        caller(context.as_deref(), roles.as_deref()).map(|(user_id, _)| user_id),
+        None,
+    );
+
+    let mut uploaded = Vec::new();
+    loop {
+        let mut field = match multipart.next_field().await {
+            Ok(Some(field)) => field,
+            Ok(None) => break,
+            Err(e) => return multipart_error(e),
+        };
+        let Some(file_name) = field.file_name().map(str::to_string) else {
+            continue;
+        };
+        let content_type = field
+            .content_type()
+            .unwrap_or("application/octet-stream")
+            .to_string();
+
+        let mut data = BytesMut::new();
+        loop {
+            match field.chunk().await {
+                Ok(Some(chunk)) => data.extend_from_slice(&chunk),
+                Ok(None) => break,
+                Err(e) => return multipart_error(e),
+            }
+        }
+        let size = data.len();
+        match owner
+            .clone()
+            .scope(store.save_with_type(data.freeze(), &content_type))
+            .await
+        {
+            Ok(id) => uploaded.push(serde_json::json!({
+                "ref_id": id.0,
+                "file_name": file_name,
+                "content_type": content_type,
+                "size": size,
+            })),
+            Err(e) => {
+                tracing::error!(file_name = %file_name, error = %e, "Failed to store upload");
+                return error_response(StatusCode::INTERNAL_SERVER_ERROR, "Failed to store upload");
+            }
+        }
+    }
+
+    if uploaded.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "Upload contains no files");
+    }
+    (
+        StatusCode::CREATED,
+        Json(serde_json::json!({ "artifacts": uploaded })),
+    )
+        .into_response()
+}
+
+/// `GET /artifacts/:ref_id/url`
+///
+/// Returns a pre-signed download URL when the backing store supports one.
+pub(crate) async fn get_artifact_url_handler(
+    State(state): State<Arc<AppState>>,
+    Path(ref_id): Path<String>,
+    context: Option<Extension<UserContext>>,
+    roles: Option<Extension<UserRoles>>,
+) -> Response {
+    let store = match store_or_unavailable(&state) {
+        Ok(store) => store,
+        Err(resp) => return *resp,
+    };
+    let id = RefId::from_string(ref_id);
+    if let Err(resp) =
+        authorized_metadata(store, &id, caller(context.as_deref(), roles.as_deref())).await
+    {
+        return *resp;
+    }
+
+    let expires_in_secs = state.app_config.store.artifact_download.presign_expiry_secs;
+    match store
+        .presigned_url(&id, Duration::from_secs(expires_in_secs))
+        .await
+    {
+        Ok(Some(url)) => Json(serde_json::json!({
+            "url": url,
+            "expires_in_secs": expires_in_secs,
+        }))
+        .into_response(),
+        Ok(None) => error_response(
+            StatusCode::NOT_FOUND,
+            "No pre-signed URL available for this artifact",
+        ),
+        Err(e) => {
+            tracing::error!(ref_id = %id, error = %e, "Failed to pre-sign artifact URL");
+            error_response(StatusCode::INTERNAL_SERVER_ERROR, "Failed to pre-sign URL")
+        }
+    }
+}
+
+/// `GET /sessions/:id/artifacts`
+pub(crate) async fn list_session_artifacts_handler(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+    context: Option<Extension<UserContext>>,
+    roles: Option<Extension<UserRoles>>,
+) -> Response {
+    let Some(catalog) = &state.artifact_catalog else {
+        return error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Artifact catalog not configured",
+        );
+    };
+    let caller = caller(context.as_deref(), roles.as_deref());
+    // Same rule as single artifacts: other users' artifacts are not disclosed.
+    let artifacts: Vec<_> = catalog
+        .for_session(&session_id)
+        .into_iter()
+        .filter(|entry| may_read(entry.user_id.as_deref(), caller.as_ref()))
+        .collect();
+    Json(serde_json::json!({
+        "session_id": session_id,
+        "artifacts": artifacts,
+    }))
+    .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_may_read() {
+        let alice = ("alice".to_string(), false);
+        let admin = ("root".to_string(), true);
+        assert!(may_read(None, None));
+        assert!(may_read(Some("alice"), Some(&alice)));
+        assert!(may_read(Some("alice"), Some(&admin)));
+        assert!(!may_read(Some("bob"), Some(&alice)));
+        assert!(!may_read(Some("alice"), None));
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("bytes=0-3", 10), RangeRequest::Partial(0, 3));
+        assert_eq!(parse_range("bytes=4-", 10), RangeRequest::Partial(4, 9));
+        assert_eq!(parse_range("bytes=-3", 10), RangeRequest::Partial(7, 9));
+        assert_eq!(parse_range("bytes=-30", 10), RangeRequest::Partial(0, 9));
+        assert_eq!(parse_range("bytes=5-100", 10), RangeRequest::Partial(5, 9));
+        assert_eq!(parse_range("bytes=10-", 10), RangeRequest::Unsatisfiable);
+        assert_eq!(parse_range("bytes=-0", 10), RangeRequest::Unsatisfiable);
+        assert_eq!(parse_range("bytes=0-1,4-5", 10), RangeRequest::Full);
+        assert_eq!(parse_range("bytes=5-2", 10), RangeRequest::Full);
+        assert_eq!(parse_range("items=0-1", 10), RangeRequest::Full);
+    }
+
+    #[test]
+    fn test_accepts() {
+        assert!(accepts("*/*", "application/pdf"));
+        assert!(accepts("application/pdf", "application/pdf"));
+        assert!(accepts("text/*", "text/plain; charset=utf-8"));
+        assert!(accepts(
+            "image/png, application/*;q=0.5",
+            "application/json"
+        ));
+        assert!(!accepts("image/png", "application/json"));
+        assert!(!accepts("application/json;q=0", "application/json"));
+    }
+
+    #[tokio::test]
+    async fn test_owned_artifact_hidden_from_anonymous_callers() {
+        let store = multi_agent_store::InMemoryStore::new();
+        let id = ArtifactOwner::new(Some("alice".into()), None)
+            .scope(store.save(Bytes::from("private")))
+            .await
+            .unwrap();
+        let store: Arc<dyn ArtifactStore> = Arc::new(store);
+
+        let err = authorized_metadata(&store, &id, None).await.unwrap_err();
+        assert_eq!(err.status(), StatusCode::NOT_FOUND);
+        let err = authorized_metadata(&store, &id, Some(("bob".into(), false)))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), StatusCode::NOT_FOUND);
+        assert!(
+            authorized_metadata(&store, &id, Some(("alice".into(), false)))
+                .await
+                .is_ok()
+        );
+    }
+}
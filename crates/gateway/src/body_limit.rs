@@ -0,0 +1,105 @@
+//! Request body ceilings.
+//!
+//! Every route reads its body through a ceiling from `gateway.body_limits`:
+//! the longest matching route prefix, `upload_max_bytes` for the multipart
+//! artifact upload, and `max_bytes` otherwise. A declared `Content-Length`
+//! above the ceiling is rejected before the body is read; chunked bodies are
+//! cut off as soon as they cross it. Rejections are 413 responses that say
+//! how to send the content instead.
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use http_body_util::Limited;
+use std::sync::Arc;
+
+use multi_agent_core::config::BodyLimitConfig;
+
+use crate::server::AppState;
+
+/// Path of the multipart artifact upload endpoint.
+pub const UPLOAD_PATH: &str = "/v1/agent/artifacts";
+
+/// Body ceiling for `path` in bytes; 0 means unlimited.
+pub fn limit_for(limits: &BodyLimitConfig, path: &str) -> usize {
+    if path == UPLOAD_PATH {
+        limits.upload_max_bytes
+    } else {
+        limits.limit_for(path)
+    }
+}
+
+fn payload_too_large(limit: usize, path: &str) -> Response {
+    let guidance = if path == UPLOAD_PATH {
+        "Split the upload into smaller files, or ask an administrator to raise \
+         gateway.body_limits.upload_max_bytes."
+    } else {
+        "Upload large content as a file with multipart/form-data to POST \
+         /v1/agent/artifacts and pass the returned ref_id instead of inlining it."
+    };
+    metrics::counter!("gateway_body_limit_rejections_total").increment(1);
+    (
+        StatusCode::PAYLOAD_TOO_LARGE,
+        Json(serde_json::json!({
+            "error": format!("Request body exceeds the {} byte limit for this route", limit),
+            "limit_bytes": limit,
+            "guidance": guidance,
+        })),
+    )
+        .into_response()
+}
+
+/// Middleware enforcing the route's body ceiling.
+pub(crate) async fn body_limit_middleware(
+    State(state): State<Arc<AppState>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let path = req.uri().path().to_string();
+    let limit = limit_for(&state.app_config.gateway.body_limits, &path);
+    if limit == 0 {
+        return next.run(req).await;
+    }
+
+    let declared = req
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+    if declared.is_some_and(|len| len > limit as u64) {
+        tracing::warn!(path = %path, limit, "Rejected request body above the route limit");
+        return payload_too_large(limit, &path);
+    }
+
+    let (parts, body) = req.into_parts();
+    let req = Request::from_parts(parts, Body::new(Limited::new(body, limit)));
+    let response = next.run(req).await;
+    // Extractors report a body cut off by `Limited` as a bare 413.
+    if response.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        tracing::warn!(path = %path, limit, "Streamed request body crossed the route limit");
+        return payload_too_large(limit, &path);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_for_route() {
+        let mut limits = BodyLimitConfig::default();
+        limits.routes.insert("/v1/agent".into(), 2048);
+        limits.routes.insert("/v1/agent/research".into(), 512);
+
+        assert_eq!(limit_for(&limits, "/v1/agent/research/jobs"), 512);
+        assert_eq!(limit_for(&limits, "/v1/agent/chat"), 2048);
+        assert_eq!(limit_for(&limits, "/v1/chat"), limits.max_bytes);
+        assert_eq!(limit_for(&limits, UPLOAD_PATH), limits.upload_max_bytes);
+    }
+}
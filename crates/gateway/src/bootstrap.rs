@@ -0,0 +1,676 @@
+//! Declarative bootstrap (infrastructure as code).
+//!
+//! A single YAML file describes the desired providers, MCP servers, policy,
+//! routing rules, personas (environment templates) and workspaces. [`reconcile`]
+//! diffs it against the running instance and returns a [`BootstrapPlan`];
+//! with `apply` set the changes are made as they are planned. Run it at
+//! startup with `--apply config/bootstrap.yaml` (add `--dry-run` to only print
+//! the plan) or via `POST /v1/admin/bootstrap?dry_run=true`.
+//!
+//! Resources missing from the file are left alone unless `prune: true`, which
+//! deletes providers, MCP servers and templates not listed. Workspaces are
+//! only ever created.
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use multi_agent_admin::ProviderEntry;
+use multi_agent_core::{Error, Result};
+use multi_agent_governance::{
+    AuditContext, AuditEntry, AuditOutcome, PolicyRule, PolicyThresholds, ToolApprovalOverride,
+    WorkspaceApprovalOverride,
+};
+use multi_agent_skills::McpServerInfo;
+
+use crate::routing_policy::{RoutingPolicyChannel, RoutingPolicyRelease, RoutingRule};
+use crate::server::AppState;
+use crate::workspaces::{self, EnvironmentTemplate};
+
+/// Desired state of an instance.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Bootstrap {
+    /// Delete resources that are not listed.
+    #[serde(default)]
+    pub prune: bool,
+    #[serde(default)]
+    pub providers: Vec<ProviderSpec>,
+    #[serde(default)]
+    pub mcp_servers: Vec<McpServerInfo>,
+    #[serde(default)]
+    pub policy: Option<PolicySpec>,
+    #[serde(default)]
+    pub routing: Option<RoutingSpec>,
+    /// Personas: reusable prompts, tools and approval policy.
+    #[serde(default, alias = "templates")]
+    pub personas: Vec<EnvironmentTemplate>,
+    #[serde(default)]
+    pub workspaces: Vec<WorkspaceSpec>,
+}
+
+impl Bootstrap {
+    /// Parse a bootstrap document (YAML or JSON).
+    pub fn parse(content: &str) -> Result<Self> {
+        serde_yaml::from_str(content)
+            .map_err(|e| Error::invalid_request(format!("Invalid bootstrap file: {}", e)))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            Error::invalid_request(format!("Read bootstrap file {:?} failed: {}", path, e))
+        })?;
+        Self::parse(&content)
+    }
+}
+
+/// A provider entry; the API key is given inline or read from the environment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderSpec {
+    pub id: String,
+    pub vendor: String,
+    pub model_id: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub base_url: String,
+    #[serde(default)]
+    pub regional_urls: Vec<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub api_key: Option<String>,
+    /// Environment variable holding the API key.
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    #[serde(default)]
+    pub monthly_spend_cap_usd: Option<f64>,
+}
+
+impl ProviderSpec {
+    fn api_key(&self) -> Result<Option<String>> {
+        if let Some(key) = &self.api_key {
+            return Ok(Some(key.clone()));
+        }
+        match &self.api_key_env {
+            Some(var) => std::env::var(var).map(Some).map_err(|_| {
+                Error::invalid_request(format!(
+                    "Provider {}: environment variable {} is not set",
+                    self.id, var
+                ))
+            }),
+            None => Ok(None),
+        }
+    }
+
+    fn matches(&self, entry: &ProviderEntry) -> bool {
+        entry.vendor == self.vendor
+            && entry.model_id == self.model_id
+            && entry.description == self.description
+            && entry.base_url == self.base_url
+            && entry.regional_urls == self.regional_urls
+            && entry.version == self.version
+            && entry.capabilities == self.capabilities
+            && entry.monthly_spend_cap_usd == self.monthly_spend_cap_usd
+    }
+}
+
+/// Policy sections to enforce; omitted sections are left unchanged.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PolicySpec {
+    #[serde(default)]
+    pub rules: Option<Vec<PolicyRule>>,
+    #[serde(default)]
+    pub thresholds: Option<PolicyThresholds>,
+    #[serde(default)]
+    pub tool_overrides: Option<HashMap<String, ToolApprovalOverride>>,
+    #[serde(default)]
+    pub workspace_overrides: Option<HashMap<String, WorkspaceApprovalOverride>>,
+}
+
+/// Routing rules, published as a stable release when they differ from the
+/// active one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutingSpec {
+    /// Semver release version; must be newer than the latest release.
+    pub version: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub rules: Vec<RoutingRule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceSpec {
+    pub id: String,
+    /// Persona the workspace is created from.
+    pub persona: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceKind {
+    Provider,
+    McpServer,
+    Policy,
+    Routing,
+    Persona,
+    Workspace,
+}
+
+impl fmt::Display for ResourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Provider => "provider",
+            Self::McpServer => "mcp_server",
+            Self::Policy => "policy",
+            Self::Routing => "routing",
+            Self::Persona => "persona",
+            Self::Workspace => "workspace",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeAction {
+    Create,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedChange {
+    pub kind: ResourceKind,
+    pub id: String,
+    pub action: ChangeAction,
+    /// Fields that differ, for updates.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<String>,
+}
+
+/// Changes needed to reach the desired state.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BootstrapPlan {
+    pub changes: Vec<PlannedChange>,
+    /// Sections that could not be reconciled on this instance.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Whether the changes were made.
+    pub applied: bool,
+}
+
+impl BootstrapPlan {
+    fn push(&mut self, kind: ResourceKind, id: &str, action: ChangeAction, fields: Vec<String>) {
+        self.changes.push(PlannedChange {
+            kind,
+            id: id.to_string(),
+            action,
+            fields,
+        });
+    }
+}
+
+impl fmt::Display for BootstrapPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            writeln!(f, "No changes. The instance matches the bootstrap file.")?;
+        }
+        for change in &self.changes {
+            let sign = match change.action {
+                ChangeAction::Create => '+',
+                ChangeAction::Update => '~',
+                ChangeAction::Delete => '-',
+            };
+            write!(f, "{} {} {}", sign, change.kind, change.id)?;
+            if !change.fields.is_empty() {
+                write!(f, " ({})", change.fields.join(", "))?;
+            }
+            writeln!(f)?;
+        }
+        for warning in &self.warnings {
+            writeln!(f, "! {}", warning)?;
+        }
+        let verb = if self.applied { "applied" } else { "planned" };
+        write!(f, "{} change(s) {}.", self.changes.len(), verb)
+    }
+}
+
+/// Top-level keys of two serialized values that differ.
+fn changed_fields<T: Serialize>(current: &T, desired: &T) -> Vec<String> {
+    let (Ok(serde_json::Value::Object(current)), Ok(serde_json::Value::Object(desired))) =
+        (serde_json::to_value(current), serde_json::to_value(desired))
+    else {
+        return Vec::new();
+    };
+    let mut fields: Vec<String> = desired
+        .iter()
+        .filter(|(key, value)| current.get(*key) != Some(value))
+        .map(|(key, _)| key.clone())
+        .chain(
+            current
+                .keys()
+                .filter(|key| !desired.contains_key(*key))
+                .cloned(),
+        )
+        .collect();
+    fields.sort();
+    fields
+}
+
+/// Diff `desired` against the instance behind `state`; with `apply`, make the
+/// changes. Changes are applied in plan order, so an error may leave earlier
+/// ones in place; re-running converges.
+pub async fn reconcile(
+    state: &AppState,
+    desired: &Bootstrap,
+    apply: bool,
+) -> Result<BootstrapPlan> {
+    let mut plan = BootstrapPlan {
+        applied: apply,
+        ..BootstrapPlan::default()
+    };
+    reconcile_providers(state, desired, apply, &mut plan).await?;
+    reconcile_mcp_servers(state, desired, apply, &mut plan);
+    reconcile_policy(state, desired, apply, &mut plan).await?;
+    reconcile_routing(state, desired, apply, &mut plan).await?;
+    reconcile_personas(state, desired, apply, &mut plan).await?;
+    reconcile_workspaces(state, desired, apply, &mut plan).await?;
+    Ok(plan)
+}
+
+async fn reconcile_providers(
+    state: &AppState,
+    desired: &Bootstrap,
+    apply: bool,
+    plan: &mut BootstrapPlan,
+) -> Result<()> {
+    if desired.providers.is_empty() && !desired.prune {
+        return Ok(());
+    }
+    let Some(admin) = &state.admin_state else {
+        plan.warnings
+            .push("providers: admin API not configured".to_string());
+        return Ok(());
+    };
+    let current = multi_agent_admin::spend_caps::load_providers(admin).await;
+
+    for spec in &desired.providers {
+        let api_key = spec.api_key()?;
+        let existing = current.iter().find(|p| p.id == spec.id);
+        let api_key_id = existing
+            .map(|p| p.api_key_id.clone())
+            .unwrap_or_else(|| format!("api_key:{}", spec.id));
+        let key_changed = match (&api_key, existing) {
+            (Some(key), Some(_)) => {
+                admin.secrets.retrieve(&api_key_id).await?.as_ref() != Some(key)
+            }
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (None, None) => {
+                return Err(Error::invalid_request(format!(
+                    "Provider {}: api_key or api_key_env is required",
+                    spec.id
+                )))
+            }
+        };
+        let action = match existing {
+            None => ChangeAction::Create,
+            Some(entry) if !spec.matches(entry) || key_changed => ChangeAction::Update,
+            Some(_) => continue,
+        };
+        let entry = ProviderEntry {
+            id: spec.id.clone(),
+            vendor: spec.vendor.clone(),
+            model_id: spec.model_id.clone(),
+            description: spec.description.clone(),
+            base_url: spec.base_url.clone(),
+            regional_urls: spec.regional_urls.clone(),
+            version: spec.version.clone(),
+            api_key_id,
+            capabilities: spec.capabilities.clone(),
+            // Keep a status set by spend caps
+            status: existing
+                .map(|p| p.status.clone())
+                .unwrap_or_else(|| "active".to_string()),
+            monthly_spend_cap_usd: spec.monthly_spend_cap_usd,
+            endpoints: Vec::new(),
+        };
+        let mut fields = existing
+            .map(|p| changed_fields(p, &entry))
+            .unwrap_or_default();
+        if key_changed && existing.is_some() {
+            fields.push("api_key".to_string());
+        }
+        plan.push(ResourceKind::Provider, &spec.id, action, fields);
+        if apply {
+            if key_changed {
+                if let Some(key) = &api_key {
+                    admin.secrets.store(&entry.api_key_id, key).await?;
+                }
+            }
+            multi_agent_admin::save_provider(admin, &entry).await?;
+        }
+    }
+
+    if desired.prune {
+        for provider in &current {
+            if desired.providers.iter().any(|p| p.id == provider.id) {
+                continue;
+            }
+            plan.push(
+                ResourceKind::Provider,
+                &provider.id,
+                ChangeAction::Delete,
+                Vec::new(),
+            );
+            if apply {
+                multi_agent_admin::remove_provider(admin, &provider.id).await;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn reconcile_mcp_servers(
+    state: &AppState,
+    desired: &Bootstrap,
+    apply: bool,
+    plan: &mut BootstrapPlan,
+) {
+    if desired.mcp_servers.is_empty() && !desired.prune {
+        return;
+    }
+    let Some(admin) = &state.admin_state else {
+        plan.warnings
+            .push("mcp_servers: admin API not configured".to_string());
+        return;
+    };
+    let current = admin.mcp_registry.list_all();
+
+    for server in &desired.mcp_servers {
+        let (action, fields) = match current.iter().find(|s| s.id == server.id) {
+            None => (ChangeAction::Create, Vec::new()),
+            Some(existing) => {
+                let fields = changed_fields(existing, server);
+                if fields.is_empty() {
+                    continue;
+                }
+                (ChangeAction::Update, fields)
+            }
+        };
+        plan.push(ResourceKind::McpServer, &server.id, action, fields);
+        if apply {
+            admin.mcp_registry.register(server.clone());
+        }
+    }
+
+    if desired.prune {
+        for server in &current {
+            if desired.mcp_servers.iter().any(|s| s.id == server.id) {
+                continue;
+            }
+            plan.push(
+                ResourceKind::McpServer,
+                &server.id,
+                ChangeAction::Delete,
+                Vec::new(),
+            );
+            if apply {
+                admin.mcp_registry.unregister(&server.id);
+            }
+        }
+    }
+}
+
+async fn reconcile_policy(
+    state: &AppState,
+    desired: &Bootstrap,
+    apply: bool,
+    plan: &mut BootstrapPlan,
+) -> Result<()> {
+    let Some(spec) = &desired.policy else {
+        return Ok(());
+    };
+    let Some(engine) = &state.policy_engine else {
+        plan.warnings
+            .push("policy: policy engine not configured".to_string());
+        return Ok(());
+    };
+    let mut engine = engine.write().await;
+    let mut policy = engine.policy.clone();
+    if let Some(rules) = &spec.rules {
+        policy.rules = rules.clone();
+    }
+    if let Some(thresholds) = &spec.thresholds {
+        policy.thresholds = thresholds.clone();
+    }
+    if let Some(overrides) = &spec.tool_overrides {
+        policy.tool_overrides = overrides.clone();
+    }
+    if let Some(overrides) = &spec.workspace_overrides {
+        policy.workspace_overrides = overrides.clone();
+    }
+    let fields = changed_fields(&engine.policy, &policy);
+    if fields.is_empty() {
+        return Ok(());
+    }
+    crate::server::check_mandatory_approval(state, &policy).await?;
+    plan.push(
+        ResourceKind::Policy,
+        &policy.name,
+        ChangeAction::Update,
+        fields,
+    );
+    if apply {
+        if !crate::server::persist_policy(state, &policy) {
+            return Err(Error::internal("Failed to persist policy"));
+        }
+        engine.policy = policy;
+    }
+    Ok(())
+}
+
+async fn reconcile_routing(
+    state: &AppState,
+    desired: &Bootstrap,
+    apply: bool,
+    plan: &mut BootstrapPlan,
+) -> Result<()> {
+    let Some(spec) = &desired.routing else {
+        return Ok(());
+    };
+    let Some(store) = &state.routing_policy_store else {
+        plan.warnings
+            .push("routing: routing policy store not configured".to_string());
+        return Ok(());
+    };
+    let active = store
+        .active_release_for_channel(RoutingPolicyChannel::Stable)
+        .await;
+    if active.as_ref().is_some_and(|r| r.rules == spec.rules) {
+        return Ok(());
+    }
+    let action = if active.is_some() {
+        ChangeAction::Update
+    } else {
+        ChangeAction::Create
+    };
+    plan.push(
+        ResourceKind::Routing,
+        &spec.version,
+        action,
+        vec!["rules".to_string()],
+    );
+    if apply {
+        store
+            .publish(RoutingPolicyRelease {
+                version: spec.version.clone(),
+                name: spec.name.clone(),
+                published_at: chrono::Utc::now().timestamp(),
+                channel: RoutingPolicyChannel::Stable,
+                rules: spec.rules.clone(),
+            })
+            .await?;
+    }
+    Ok(())
+}
+
+async fn reconcile_personas(
+    state: &AppState,
+    desired: &Bootstrap,
+    apply: bool,
+    plan: &mut BootstrapPlan,
+) -> Result<()> {
+    if desired.personas.is_empty() && !desired.prune {
+        return Ok(());
+    }
+    let Some(store) = &state.workspace_store else {
+        plan.warnings
+            .push("personas: workspace store not configured".to_string());
+        return Ok(());
+    };
+    let current = store.list_templates().await;
+
+    for persona in &desired.personas {
+        let (action, fields) = match current.iter().find(|t| t.name == persona.name) {
+            None => (ChangeAction::Create, Vec::new()),
+            Some(existing) => {
+                let persona = EnvironmentTemplate {
+                    created_at: existing.created_at,
+                    ..persona.clone()
+                };
+                let fields = changed_fields(existing, &persona);
+                if fields.is_empty() {
+                    continue;
+                }
+                (ChangeAction::Update, fields)
+            }
+        };
+        plan.push(ResourceKind::Persona, &persona.name, action, fields);
+        if apply {
+            store.put_template(persona.clone()).await?;
+        }
+    }
+
+    if desired.prune {
+        for template in &current {
+            if desired.personas.iter().any(|p| p.name == template.name) {
+                continue;
+            }
+            plan.push(
+                ResourceKind::Persona,
+                &template.name,
+                ChangeAction::Delete,
+                Vec::new(),
+            );
+            if apply {
+                store.remove_template(&template.name).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn reconcile_workspaces(
+    state: &AppState,
+    desired: &Bootstrap,
+    apply: bool,
+    plan: &mut BootstrapPlan,
+) -> Result<()> {
+    if desired.workspaces.is_empty() {
+        return Ok(());
+    }
+    let Some(store) = &state.workspace_store else {
+        plan.warnings
+            .push("workspaces: workspace store not configured".to_string());
+        return Ok(());
+    };
+    for spec in &desired.workspaces {
+        if store.get_workspace(&spec.id).await.is_some() {
+            continue;
+        }
+        // The persona may only be created by this run
+        let persona = match desired.personas.iter().find(|p| p.name == spec.persona) {
+            Some(persona) => persona.clone(),
+            None => store.get_template(&spec.persona).await.ok_or_else(|| {
+                Error::invalid_request(format!(
+                    "Workspace {}: unknown persona {}",
+                    spec.id, spec.persona
+                ))
+            })?,
+        };
+        plan.push(
+            ResourceKind::Workspace,
+            &spec.id,
+            ChangeAction::Create,
+            Vec::new(),
+        );
+        if apply {
+            workspaces::instantiate(state, &persona, &spec.id).await?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct BootstrapQuery {
+    /// Only return the plan.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// `POST /v1/admin/bootstrap` reconciles the instance with a bootstrap
+/// document in the request body.
+pub(crate) async fn bootstrap_handler(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<BootstrapQuery>,
+    body: String,
+) -> Response {
+    let desired = match Bootstrap::parse(&body) {
+        Ok(desired) => desired,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": e.to_string() })),
+            )
+                .into_response()
+        }
+    };
+    match reconcile(&state, &desired, !query.dry_run).await {
+        Ok(plan) => {
+            if let (Some(admin), true) = (&state.admin_state, plan.applied) {
+                let _ = admin
+                    .audit_store
+                    .log(AuditEntry {
+                        id: uuid::Uuid::new_v4().to_string(),
+                        timestamp: chrono::Utc::now().to_rfc3339(),
+                        user_id: AuditContext::user_or("admin"),
+                        action: "APPLY_BOOTSTRAP".to_string(),
+                        resource: "bootstrap".to_string(),
+                        outcome: AuditOutcome::Success,
+                        metadata: Some(serde_json::json!({ "changes": plan.changes })),
+                        previous_hash: None,
+                        hash: None,
+                        trace_id: None,
+                        session_id: None,
+                    })
+                    .await;
+            }
+            Json(plan).into_response()
+        }
+        Err(e) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
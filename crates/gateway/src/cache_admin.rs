@@ -0,0 +1,94 @@
+//! Semantic cache management.
+//!
+//! Mounted under `/v1/admin/cache`: `GET /` lists cached entries (by query
+//! hash, never the query text), `DELETE /?workspace=&session=&query_hash=`
+//! purges the matching entries, and `PUT /workspaces/:id` turns caching on or
+//! off for one workspace.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use multi_agent_core::traits::CacheSelector;
+
+use crate::server::AppState;
+use crate::workspaces::audit;
+
+/// Body of `PUT /workspaces/:id`.
+#[derive(Debug, Deserialize)]
+pub struct CacheSwitch {
+    pub enabled: bool,
+}
+
+fn error(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(serde_json::json!({ "error": message.into() }))).into_response()
+}
+
+/// `GET /`
+pub(crate) async fn list_cache_handler(
+    State(state): State<Arc<AppState>>,
+    Query(selector): Query<CacheSelector>,
+) -> Response {
+    match state.cache.entries(&selector).await {
+        Ok(entries) => Json(serde_json::json!({
+            "entries": entries,
+            "disabled_workspaces": state.cache.disabled_workspaces().await,
+        }))
+        .into_response(),
+        Err(e) => error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
+/// `DELETE /`
+pub(crate) async fn purge_cache_handler(
+    State(state): State<Arc<AppState>>,
+    Query(selector): Query<CacheSelector>,
+) -> Response {
+    match state.cache.purge(&selector).await {
+        Ok(purged) => {
+            audit(
+                &state,
+                "PURGE_SEMANTIC_CACHE",
+                selector.workspace_id.as_deref().unwrap_or("*"),
+                serde_json::json!({ "selector": selector, "purged": purged }),
+            )
+            .await;
+            Json(serde_json::json!({ "purged": purged })).into_response()
+        }
+        Err(e) => error(StatusCode::NOT_IMPLEMENTED, e.to_string()),
+    }
+}
+
+/// `PUT /workspaces/:id`
+pub(crate) async fn set_workspace_cache_handler(
+    State(state): State<Arc<AppState>>,
+    Path(workspace_id): Path<String>,
+    Json(switch): Json<CacheSwitch>,
+) -> Response {
+    match state
+        .cache
+        .set_workspace_enabled(&workspace_id, switch.enabled)
+        .await
+    {
+        Ok(()) => {
+            audit(
+                &state,
+                "SET_WORKSPACE_CACHE",
+                &workspace_id,
+                serde_json::json!({ "enabled": switch.enabled }),
+            )
+            .await;
+            Json(serde_json::json!({
+                "workspace_id": workspace_id,
+                "enabled": switch.enabled,
+            }))
+            .into_response()
+        }
+        Err(e) => error(StatusCode::NOT_IMPLEMENTED, e.to_string()),
+    }
+}
@@ -0,0 +1,42 @@
+//! Fault injection into HTTP requests, from `chaos.http`.
+//!
+//! Compiled only with the `chaos` feature. Requests may be delayed, fail
+//! with 503 before reaching their handler, or be handled and then answered
+//! with 502 so clients see a failure for work that was done.
+
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::sync::Arc;
+
+use multi_agent_core::chaos::{Fault, FaultInjector};
+
+fn injected(injector: &FaultInjector, status: StatusCode, path: &str) -> Response {
+    metrics::counter!("chaos_faults_injected_total", "target" => injector.target()).increment(1);
+    (
+        status,
+        Json(serde_json::json!({ "error": injector.message(path) })),
+    )
+        .into_response()
+}
+
+/// Middleware injecting the faults drawn for each request path.
+pub(crate) async fn chaos_middleware(
+    State(injector): State<Arc<FaultInjector>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let path = req.uri().path().to_string();
+    match injector.inject(&path).await {
+        Some(Fault::Error) => injected(&injector, StatusCode::SERVICE_UNAVAILABLE, &path),
+        Some(Fault::Partial) => {
+            let _ = next.run(req).await;
+            injected(&injector, StatusCode::BAD_GATEWAY, &path)
+        }
+        None => next.run(req).await,
+    }
+}
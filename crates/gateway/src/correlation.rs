@@ -0,0 +1,112 @@
+//! Request correlation for audit entries.
+//!
+//! Authenticated requests run inside an [`AuditContext`] holding the caller,
+//! a trace ID and the session ID, so audit entries logged while handling them
+//! can be joined with the events and traces of the same request. The trace ID
+//! comes from an `x-trace-id` or W3C `traceparent` header, or is generated,
+//! and is echoed in the `x-trace-id` response header. The session ID comes from
+//! `x-session-id` or the caller's token.
+
+use axum::{
+    extract::Request,
+    http::{HeaderMap, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+
+use multi_agent_governance::AuditContext;
+
+/// Header carrying the trace ID on requests and responses.
+pub const TRACE_ID_HEADER: &str = "x-trace-id";
+
+/// Header naming the session a request belongs to.
+pub const SESSION_ID_HEADER: &str = "x-session-id";
+
+/// Longest client-supplied ID accepted.
+const MAX_ID_LEN: usize = 128;
+
+fn valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_ID_LEN
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+fn header_id<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|id| valid_id(id))
+}
+
+/// Trace ID of a request: `x-trace-id`, else the trace of a `traceparent`
+/// header, else a new UUID.
+pub fn trace_id(headers: &HeaderMap) -> String {
+    if let Some(id) = header_id(headers, TRACE_ID_HEADER) {
+        return id.to_string();
+    }
+    let traceparent = headers
+        .get("traceparent")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split('-').nth(1))
+        .filter(|id| id.len() == 32 && id.chars().all(|c| c.is_ascii_hexdigit()));
+    match traceparent {
+        Some(id) => id.to_string(),
+        None => uuid::Uuid::new_v4().to_string(),
+    }
+}
+
+/// Run the rest of the stack for an authenticated request inside its
+/// [`AuditContext`].
+pub(crate) async fn run_correlated(
+    req: Request,
+    user_id: String,
+    session_id: Option<String>,
+    next: Next,
+) -> Response {
+    let context = AuditContext {
+        user_id: Some(user_id),
+        trace_id: Some(trace_id(req.headers())),
+        session_id: header_id(req.headers(), SESSION_ID_HEADER)
+            .map(str::to_string)
+            .or(session_id),
+    };
+    let trace = context.trace_id.clone().unwrap_or_default();
+
+    let mut response = context.scope(next.run(req)).await;
+    if let Ok(value) = HeaderValue::from_str(&trace) {
+        response
+            .headers_mut()
+            .entry(TRACE_ID_HEADER)
+            .or_insert(value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_id_sources() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(trace_id(&headers), "4bf92f3577b34da6a3ce929d0e0e4736");
+
+        headers.insert(TRACE_ID_HEADER, "req-42".parse().unwrap());
+        assert_eq!(trace_id(&headers), "req-42");
+
+        let mut invalid = HeaderMap::new();
+        invalid.insert(TRACE_ID_HEADER, "has spaces".parse().unwrap());
+        invalid.insert("traceparent", "00-short-00-01".parse().unwrap());
+        let generated = trace_id(&invalid);
+        assert!(uuid::Uuid::parse_str(&generated).is_ok());
+    }
+}
@@ -0,0 +1,113 @@
+//! Feature flag management.
+//!
+//! Mounted under `/v1/admin/flags`: `GET /` lists every flag with its
+//! configured definition and runtime overrides, `PUT /:name` overrides a flag
+//! globally or for one workspace, and `DELETE /:name?workspace_id=` removes
+//! an override. Changes apply to the next request without a restart.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::server::AppState;
+use crate::workspaces::audit;
+
+/// Body of `PUT /:name`.
+#[derive(Debug, Deserialize)]
+pub struct FlagSwitch {
+    pub enabled: bool,
+    /// Override only this workspace; all workspaces when absent.
+    #[serde(default)]
+    pub workspace_id: Option<String>,
+}
+
+/// Query of `DELETE /:name`.
+#[derive(Debug, Default, Deserialize)]
+pub struct FlagScope {
+    #[serde(default)]
+    pub workspace_id: Option<String>,
+}
+
+fn error(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(serde_json::json!({ "error": message.into() }))).into_response()
+}
+
+fn unavailable() -> Response {
+    error(
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Feature flags not configured",
+    )
+}
+
+/// `GET /`
+pub(crate) async fn list_flags_handler(State(state): State<Arc<AppState>>) -> Response {
+    match &state.feature_flags {
+        Some(flags) => Json(flags.list()).into_response(),
+        None => unavailable(),
+    }
+}
+
+/// `PUT /:name`
+pub(crate) async fn set_flag_handler(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Json(switch): Json<FlagSwitch>,
+) -> Response {
+    let Some(flags) = &state.feature_flags else {
+        return unavailable();
+    };
+    match flags.set_override(&name, switch.workspace_id.as_deref(), switch.enabled) {
+        Ok(()) => {
+            audit(
+                &state,
+                "SET_FEATURE_FLAG",
+                &name,
+                serde_json::json!({
+                    "enabled": switch.enabled,
+                    "workspace_id": switch.workspace_id,
+                }),
+            )
+            .await;
+            Json(serde_json::json!({
+                "name": name,
+                "workspace_id": switch.workspace_id,
+                "enabled": flags.is_enabled(&name, switch.workspace_id.as_deref()),
+            }))
+            .into_response()
+        }
+        Err(e @ multi_agent_core::Error::InvalidRequest(_)) => {
+            error(StatusCode::BAD_REQUEST, e.to_string())
+        }
+        Err(e) => error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
+/// `DELETE /:name`
+pub(crate) async fn clear_flag_handler(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Query(scope): Query<FlagScope>,
+) -> Response {
+    let Some(flags) = &state.feature_flags else {
+        return unavailable();
+    };
+    match flags.clear_override(&name, scope.workspace_id.as_deref()) {
+        Ok(true) => {
+            audit(
+                &state,
+                "CLEAR_FEATURE_FLAG",
+                &name,
+                serde_json::json!({ "workspace_id": scope.workspace_id }),
+            )
+            .await;
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Ok(false) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
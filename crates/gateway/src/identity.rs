@@ -0,0 +1,56 @@
+//! Identity of the authenticated caller.
+//!
+//! The bearer auth middleware inserts a [`UserContext`] for the admin token
+//! and [`UserRoles`] for RBAC tokens and mTLS client certificates; handlers
+//! read whichever is present through [`caller`].
+
+use multi_agent_governance::rbac::{UserContext, UserRoles};
+
+/// User id of the authenticated caller and whether they are an admin: they
+/// hold the `admin` role or, as RBAC treats it, the `*` permission.
+pub(crate) fn caller(
+    context: Option<&UserContext>,
+    roles: Option<&UserRoles>,
+) -> Option<(String, bool)> {
+    if let Some(ctx) = context {
+        let is_admin =
+            ctx.roles.iter().any(|r| r == "admin") || ctx.permissions.iter().any(|p| p == "*");
+        return Some((ctx.user_id.clone(), is_admin));
+    }
+    roles.map(|r| (r.user_id.clone(), r.is_admin))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(roles: &[&str], permissions: &[&str]) -> UserContext {
+        UserContext {
+            user_id: "alice".into(),
+            roles: roles.iter().map(|r| r.to_string()).collect(),
+            permissions: permissions.iter().map(|p| p.to_string()).collect(),
+            session_id: None,
+        }
+    }
+
+    #[test]
+    fn test_caller_admin_by_role_or_wildcard() {
+        let by_role = context(&["admin"], &[]);
+        let by_permission = context(&["user"], &["*"]);
+        let plain = context(&["user"], &["read"]);
+        assert_eq!(caller(Some(&by_role), None), Some(("alice".into(), true)));
+        assert_eq!(
+            caller(Some(&by_permission), None),
+            Some(("alice".into(), true))
+        );
+        assert_eq!(caller(Some(&plain), None), Some(("alice".into(), false)));
+
+        let roles = UserRoles {
+            user_id: "bob".into(),
+            roles: vec!["admin".into()],
+            is_admin: true,
+        };
+        assert_eq!(caller(None, Some(&roles)), Some(("bob".into(), true)));
+        assert_eq!(caller(None, None), None);
+    }
+}
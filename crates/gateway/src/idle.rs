@@ -0,0 +1,215 @@
+//! Idle session detection.
+//!
+//! Sessions blocked on an approval or a clarification answer keep the sandbox
+//! alive for as long as the human takes. [`IdleReclaimer`] pauses sessions
+//! that have waited longer than `controller.idle.pause_after_secs` and, once
+//! no running session needs it, hibernates the sandbox. When the answer
+//! arrives the controller marks the session running again, and the sandbox
+//! is restored from its snapshot on the next command.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use multi_agent_core::traits::SessionStore;
+use multi_agent_core::types::SessionStatus;
+use multi_agent_governance::{ChannelApprovalGate, ChannelHumanInput};
+use multi_agent_sandbox::SandboxManager;
+
+/// What one [`IdleReclaimer::reclaim`] pass did.
+#[derive(Debug, Default, Serialize)]
+pub struct ReclaimReport {
+    /// Sessions paused in this pass.
+    pub paused: Vec<String>,
+    pub sandbox_hibernated: bool,
+}
+
+/// Pauses sessions idling on human input and frees their sandbox.
+pub struct IdleReclaimer {
+    sessions: Arc<dyn SessionStore>,
+    pause_after: Duration,
+    sandbox: Option<Arc<SandboxManager>>,
+    approvals: Option<Arc<ChannelApprovalGate>>,
+    questions: Option<Arc<ChannelHumanInput>>,
+}
+
+impl IdleReclaimer {
+    pub fn new(sessions: Arc<dyn SessionStore>, pause_after: Duration) -> Self {
+        Self {
+            sessions,
+            pause_after,
+            sandbox: None,
+            approvals: None,
+            questions: None,
+        }
+    }
+
+    /// Hibernate this sandbox when every running session is idle.
+    pub fn with_sandbox(mut self, sandbox: Arc<SandboxManager>) -> Self {
+        self.sandbox = Some(sandbox);
+        self
+    }
+
+    /// Treat sessions with pending approvals as waiting.
+    pub fn with_approval_gate(mut self, gate: Arc<ChannelApprovalGate>) -> Self {
+        self.approvals = Some(gate);
+        self
+    }
+
+    /// Treat sessions with unanswered questions as waiting.
+    pub fn with_human_input(mut self, channel: Arc<ChannelHumanInput>) -> Self {
+        self.questions = Some(channel);
+        self
+    }
+
+    /// Pause sessions that have waited too long and hibernate an unused sandbox.
+    pub async fn reclaim(&self) -> ReclaimReport {
+        let mut report = ReclaimReport::default();
+        let mut waiting = HashSet::new();
+        if let Some(gate) = &self.approvals {
+            waiting.extend(gate.pending_sessions().await);
+        }
+        if let Some(channel) = &self.questions {
+            waiting.extend(channel.pending_sessions().await);
+        }
+
+        let running = match self.sessions.list_running().await {
+            Ok(ids) => ids,
+            Err(e) => {
+                tracing::warn!(error = %e, "Idle check could not list running sessions");
+                return report;
+            }
+        };
+        let cutoff = chrono::Utc::now().timestamp() - self.pause_after.as_secs() as i64;
+        let mut busy = false;
+        for id in running {
+            if !waiting.contains(&id) {
+                busy = true;
+                continue;
+            }
+            let mut session = match self.sessions.load(&id).await {
+                Ok(Some(session)) if session.status == SessionStatus::Running => session,
+                _ => continue,
+            };
+            if session.updated_at > cutoff {
+                continue;
+            }
+            session.status = SessionStatus::Paused;
+            if let Err(e) = self.sessions.save(&session).await {
+                tracing::warn!(session_id = %id, error = %e, "Failed to pause idle session");
+                continue;
+            }
+            tracing::info!(session_id = %id, "Paused session idling on human input");
+            metrics::counter!("sessions_idle_paused_total").increment(1);
+            report.paused.push(id);
+        }
+
+        if let Some(sandbox) = &self.sandbox {
+            if !busy && sandbox.idle_for() >= self.pause_after {
+                match sandbox.hibernate().await {
+                    Ok(freed) => {
+                        if freed {
+                            metrics::counter!("sandboxes_hibernated_total").increment(1);
+                        }
+                        report.sandbox_hibernated = freed;
+                    }
+                    Err(e) => tracing::warn!(error = %e, "Failed to hibernate idle sandbox"),
+                }
+            }
+        }
+        report
+    }
+
+    /// Run [`Self::reclaim`] every `interval`.
+    pub fn spawn(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                self.reclaim().await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multi_agent_core::traits::ApprovalGate;
+    use multi_agent_core::types::{ApprovalRequest, Session, ToolRiskLevel};
+    use multi_agent_sandbox::{MockSandbox, SandboxConfig};
+    use multi_agent_store::InMemorySessionStore;
+
+    fn session(id: &str, updated_at: i64) -> Session {
+        Session {
+            id: id.to_string(),
+            trace_id: "t".to_string(),
+            user_id: None,
+            workspace_id: None,
+            dry_run: false,
+            status: SessionStatus::Running,
+            history: Vec::new(),
+            task_state: None,
+            token_usage: Default::default(),
+            created_at: updated_at,
+            updated_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pauses_waiting_sessions_and_hibernates_sandbox() {
+        let store = Arc::new(InMemorySessionStore::new());
+        let old = chrono::Utc::now().timestamp() - 3600;
+        store.save(&session("waiting", old)).await.unwrap();
+        store.save(&session("working", old)).await.unwrap();
+
+        let gate = Arc::new(ChannelApprovalGate::new(ToolRiskLevel::High));
+        let pending = {
+            let gate = gate.clone();
+            tokio::spawn(async move {
+                gate.request_approval(&ApprovalRequest {
+                    request_id: "r1".to_string(),
+                    session_id: "waiting".to_string(),
+                    tool_name: "shell".to_string(),
+                    args: serde_json::json!({}),
+                    risk_level: ToolRiskLevel::High,
+                    context: String::new(),
+                    timeout_secs: None,
+                    nonce: "n".to_string(),
+                    expires_at: 0,
+                    mandatory: false,
+                })
+                .await
+            })
+        };
+        while gate.pending_sessions().await.is_empty() {
+            tokio::task::yield_now().await;
+        }
+
+        let sandbox = Arc::new(SandboxManager::new(
+            Arc::new(MockSandbox::default()),
+            SandboxConfig::default(),
+        ));
+        sandbox.get_or_create().await.unwrap();
+        let reclaimer = IdleReclaimer::new(store.clone(), Duration::ZERO)
+            .with_approval_gate(gate)
+            .with_sandbox(sandbox.clone());
+
+        // Another session is still working, so the sandbox stays up
+        let report = reclaimer.reclaim().await;
+        assert_eq!(report.paused, vec!["waiting".to_string()]);
+        assert!(!report.sandbox_hibernated);
+        let paused = store.load("waiting").await.unwrap().unwrap();
+        assert_eq!(paused.status, SessionStatus::Paused);
+
+        store.delete("working").await.unwrap();
+        let report = reclaimer.reclaim().await;
+        assert!(report.paused.is_empty());
+        assert!(report.sandbox_hibernated);
+        assert!(sandbox.is_hibernated().await);
+        pending.abort();
+    }
+}
@@ -4,15 +4,37 @@
 //! This crate provides the HTTP entry point for the system,
 //! including semantic caching and intent routing.
 
+pub mod admin_access;
+pub mod artifacts;
 pub mod audio;
+pub mod body_limit;
+pub mod bootstrap;
+pub mod cache_admin;
+#[cfg(feature = "chaos")]
+pub mod chaos;
+pub mod correlation;
+pub mod flags_admin;
 pub mod idempotency;
+pub(crate) mod identity;
+pub mod idle;
+pub mod load_shed;
+pub mod logs;
+pub mod memory;
+pub mod mtls;
+pub mod rate_limit;
 pub mod research;
+pub mod research_jobs;
 pub mod router;
 pub mod routing_policy;
 pub mod scheduler;
+pub mod security_headers;
 pub mod semantic_cache;
 pub mod server;
+pub mod sessions;
+pub mod terminal;
+pub mod unix_socket;
 pub mod vision;
+pub mod workspaces;
 
 pub use audio::{AudioFormat, AudioProcessor, TranscriptionResult};
 pub use router::DefaultRouter;
@@ -0,0 +1,194 @@
+//! Per-route concurrency ceilings and load shedding.
+//!
+//! Each group in `gateway.concurrency.routes` admits `max_in_flight`
+//! requests at once across its path prefixes. Further requests queue for a
+//! slot; once `max_queued` are waiting, or a queued request has waited
+//! `queue_timeout_secs`, requests are shed with 503 and `Retry-After` so
+//! spikes do not pile up on the LLM backends and the sandbox host.
+//! Streamed responses hold their slot until the stream ends.
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures::StreamExt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use multi_agent_core::config::{RouteConcurrency, RouteConcurrencyConfig};
+
+/// Slots of one route group.
+struct RouteGate {
+    name: String,
+    limit: RouteConcurrency,
+    permits: Arc<Semaphore>,
+    queued: AtomicUsize,
+}
+
+impl RouteGate {
+    /// A slot, waiting in the queue if needed; `None` means shed.
+    async fn admit(&self) -> Option<OwnedSemaphorePermit> {
+        if let Ok(permit) = self.permits.clone().try_acquire_owned() {
+            return Some(permit);
+        }
+        let queued = self.queued.fetch_add(1, Ordering::SeqCst);
+        let permit = if queued < self.limit.max_queued {
+            tokio::time::timeout(
+                Duration::from_secs(self.limit.queue_timeout_secs),
+                self.permits.clone().acquire_owned(),
+            )
+            .await
+            .ok()
+            .and_then(Result::ok)
+        } else {
+            None
+        };
+        self.queued.fetch_sub(1, Ordering::SeqCst);
+        permit
+    }
+}
+
+/// Concurrency ceilings of the configured route groups.
+pub struct RouteLimiter {
+    gates: Vec<RouteGate>,
+    retry_after_secs: u64,
+}
+
+impl RouteLimiter {
+    pub fn new(config: &RouteConcurrencyConfig) -> Self {
+        let gates = if config.enabled {
+            config
+                .routes
+                .iter()
+                .filter(|(_, limit)| limit.max_in_flight > 0)
+                .map(|(name, limit)| RouteGate {
+                    name: name.clone(),
+                    limit: limit.clone(),
+                    permits: Arc::new(Semaphore::new(limit.max_in_flight)),
+                    queued: AtomicUsize::new(0),
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        Self {
+            gates,
+            retry_after_secs: config.retry_after_secs,
+        }
+    }
+
+    /// Group of `path`: the one with the longest matching prefix.
+    fn gate_for(&self, path: &str) -> Option<&RouteGate> {
+        self.gates
+            .iter()
+            .filter_map(|gate| {
+                gate.limit
+                    .prefixes
+                    .iter()
+                    .filter(|prefix| path.starts_with(prefix.as_str()))
+                    .map(|prefix| prefix.len())
+                    .max()
+                    .map(|len| (gate, len))
+            })
+            .max_by_key(|(_, len)| *len)
+            .map(|(gate, _)| gate)
+    }
+
+    /// Name of the group `path` belongs to, if it has a ceiling.
+    pub fn route_for(&self, path: &str) -> Option<&str> {
+        self.gate_for(path).map(|gate| gate.name.as_str())
+    }
+
+    fn shed(&self, route: &str) -> Response {
+        metrics::counter!("gateway_load_shed_total", "route" => route.to_string()).increment(1);
+        let mut response = (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({
+                "error": format!("The {} routes are at capacity; retry later", route),
+                "route": route,
+                "retry_after_secs": self.retry_after_secs,
+            })),
+        )
+            .into_response();
+        response.headers_mut().insert(
+            header::RETRY_AFTER,
+            HeaderValue::from(self.retry_after_secs),
+        );
+        response
+    }
+}
+
+/// Middleware holding a slot of the route's group for the request.
+pub(crate) async fn route_concurrency_middleware(
+    State(limiter): State<Arc<RouteLimiter>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Some(gate) = limiter.gate_for(req.uri().path()) else {
+        return next.run(req).await;
+    };
+    let Some(permit) = gate.admit().await else {
+        tracing::warn!(route = %gate.name, path = %req.uri().path(), "Shed request at route capacity");
+        return limiter.shed(&gate.name);
+    };
+    let response = next.run(req).await;
+
+    let streamed = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|v| v.as_bytes().starts_with(b"text/event-stream"));
+    if !streamed {
+        return response;
+    }
+    let (parts, body) = response.into_parts();
+    let body = body.into_data_stream().map(move |chunk| {
+        let _slot = &permit;
+        chunk
+    });
+    Response::from_parts(parts, Body::from_stream(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_for_longest_prefix() {
+        let mut config = RouteConcurrencyConfig::default();
+        config.routes.insert(
+            "research_jobs".into(),
+            RouteConcurrency {
+                prefixes: vec!["/v1/agent/research/jobs".into()],
+                max_in_flight: 2,
+                ..Default::default()
+            },
+        );
+        config.routes.insert(
+            "disabled".into(),
+            RouteConcurrency {
+                prefixes: vec!["/v1/intent".into()],
+                ..Default::default()
+            },
+        );
+        let limiter = RouteLimiter::new(&config);
+
+        assert_eq!(limiter.route_for("/v1/chat/stream"), Some("chat"));
+        assert_eq!(limiter.route_for("/v1/agent/chat"), Some("chat"));
+        assert_eq!(limiter.route_for("/v1/agent/research"), Some("research"));
+        assert_eq!(
+            limiter.route_for("/v1/agent/research/jobs/1"),
+            Some("research_jobs")
+        );
+        assert_eq!(limiter.route_for("/v1/intent"), None);
+        assert_eq!(limiter.route_for("/health"), None);
+
+        config.enabled = false;
+        assert_eq!(RouteLimiter::new(&config).route_for("/v1/chat"), None);
+    }
+}
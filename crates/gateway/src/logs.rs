@@ -0,0 +1,619 @@
+//! Live log stream over WebSocket.
+//!
+//! `GET /v1/agent/ws/logs` streams event lines to authenticated callers.
+//! Subscribers may narrow the stream with `session_id`, `trace_id`, `types`
+//! (comma-separated event types) and `min_severity`. Callers holding one of
+//! `governance.log_stream.redact_for_roles` get sensitive payload fields
+//! masked. Slow consumers receive `lagged` notices and are disconnected once
+//! they fall behind too often or a send blocks too long.
+//!
+//! Callers without an operator role only see the event types allowed by the
+//! presentation policy (`governance.presentation`, or their workspace's
+//! override), with its fields masked.
+//!
+//! Recent events are kept in a [`LogBuffer`] so a reconnecting client can pass
+//! `since=<event_id>` and receive what it missed before the live stream.
+
+use axum::{
+    extract::{
+        ws::{CloseFrame, Message, WebSocket},
+        Query, State, WebSocketUpgrade,
+    },
+    response::IntoResponse,
+    Extension,
+};
+use serde::Deserialize;
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+
+use multi_agent_core::config::{LogStreamConfig, PresentationPolicy};
+use multi_agent_core::events::EventSeverity;
+use multi_agent_governance::rbac::{UserContext, UserRoles};
+
+use crate::identity::caller;
+use crate::server::{recv_optional, AppState};
+use crate::workspaces::WorkspaceStore;
+
+/// Close code sent to subscribers that cannot keep up ("try again later").
+const CLOSE_SLOW_CONSUMER: u16 = 1013;
+
+/// Query parameters for the log stream.
+#[derive(Debug, Default, Deserialize)]
+pub struct LogsQuery {
+    /// Only events for this session.
+    pub session_id: Option<String>,
+    /// Only events for this trace.
+    pub trace_id: Option<String>,
+    /// Comma-separated event types, e.g. `TOOL_EXEC_STARTED,USER_QUESTION`.
+    pub types: Option<String>,
+    /// Drop events below this severity (`debug`, `info`, `warning`, ...).
+    pub min_severity: Option<String>,
+    /// Replay buffered events after this event id before streaming live.
+    pub since: Option<String>,
+}
+
+/// Ring buffer of recent event lines, keyed by envelope `id`.
+pub struct LogBuffer {
+    capacity: usize,
+    lines: Mutex<VecDeque<(String, String)>>,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            lines: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Buffer a line; lines without an event `id` cannot be resumed from and
+    /// are skipped.
+    pub fn push(&self, line: &str) {
+        if self.capacity == 0 {
+            return;
+        }
+        let Some(id) = event_id(line) else {
+            return;
+        };
+        let mut lines = self.lines.lock().unwrap_or_else(|e| e.into_inner());
+        if lines.len() >= self.capacity {
+            lines.pop_front();
+        }
+        lines.push_back((id, line.to_string()));
+    }
+
+    /// Lines after event `id`, oldest first. `None` when `id` has been evicted
+    /// or was never seen; the caller decides how to report the gap.
+    pub fn since(&self, id: &str) -> Option<Vec<(String, String)>> {
+        let lines = self.lines.lock().unwrap_or_else(|e| e.into_inner());
+        let pos = lines.iter().position(|(line_id, _)| line_id == id)?;
+        Some(lines.iter().skip(pos + 1).cloned().collect())
+    }
+
+    /// Every buffered line, oldest first.
+    pub fn all(&self) -> Vec<(String, String)> {
+        let lines = self.lines.lock().unwrap_or_else(|e| e.into_inner());
+        lines.iter().cloned().collect()
+    }
+
+    /// Record every line from `rx` until the channel closes.
+    pub fn record(self: &Arc<Self>, mut rx: broadcast::Receiver<String>) -> JoinHandle<()> {
+        let buffer = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match rx.recv().await {
+                    Ok(line) => buffer.push(&line),
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!(skipped, "Log replay buffer lagged");
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+}
+
+fn event_id(line: &str) -> Option<String> {
+    serde_json::from_str::<serde_json::Value>(line)
+        .ok()?
+        .get("id")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Line filter built from [`LogsQuery`].
+#[derive(Debug, Default)]
+pub struct LogFilter {
+    session_id: Option<String>,
+    trace_id: Option<String>,
+    types: Vec<String>,
+    min_severity: Option<EventSeverity>,
+}
+
+impl LogFilter {
+    /// Build a filter, rejecting an unknown severity.
+    pub fn from_query(query: &LogsQuery) -> Result<Self, String> {
+        let min_severity = query
+            .min_severity
+            .as_deref()
+            .map(|s| {
+                serde_json::from_value(serde_json::Value::String(s.trim().to_uppercase()))
+                    .map_err(|_| format!("Unknown severity '{}'", s))
+            })
+            .transpose()?;
+        let types = query
+            .types
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(|t| t.trim().to_uppercase())
+            .filter(|t| !t.is_empty())
+            .collect();
+        Ok(Self {
+            session_id: query.session_id.clone(),
+            trace_id: query.trace_id.clone(),
+            types,
+            min_severity,
+        })
+    }
+
+    /// Whether a parsed line passes the filter. Lines that are not event
+    /// envelopes (e.g. `user_question`) match on `type` and count as info.
+    pub fn matches(&self, line: &serde_json::Value) -> bool {
+        if let Some(session_id) = &self.session_id {
+            let found = line
+                .get("session_id")
+                .or_else(|| line.pointer("/data/session_id"))
+                .and_then(|v| v.as_str());
+            if found != Some(session_id.as_str()) {
+                return false;
+            }
+        }
+        if let Some(trace_id) = &self.trace_id {
+            if line.get("trace_id").and_then(|v| v.as_str()) != Some(trace_id.as_str()) {
+                return false;
+            }
+        }
+        if !self.types.is_empty() {
+            let event_type = line
+                .get("event_type")
+                .or_else(|| line.get("type"))
+                .and_then(|v| v.as_str())
+                .map(str::to_uppercase);
+            if !event_type.is_some_and(|t| self.types.contains(&t)) {
+                return false;
+            }
+        }
+        if let Some(min) = self.min_severity {
+            let severity = line
+                .get("severity")
+                .and_then(|v| serde_json::from_value::<EventSeverity>(v.clone()).ok())
+                .unwrap_or(EventSeverity::Info);
+            if severity < min {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Whether a caller's lines need redaction under `config`.
+pub fn redacts_for(config: &LogStreamConfig, roles: &[String], is_admin: bool) -> bool {
+    !is_admin && roles.iter().any(|r| config.redact_for_roles.contains(r))
+}
+
+/// Mask `fields` at any depth of `value`.
+pub fn redact(value: &mut serde_json::Value, fields: &[String]) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if fields.contains(key) {
+                    *child = serde_json::Value::String("[REDACTED]".into());
+                } else {
+                    redact(child, fields);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                redact(item, fields);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn caller_roles(
+    context: Option<Extension<UserContext>>,
+    roles: Option<Extension<UserRoles>>,
+) -> (Vec<String>, bool) {
+    let is_admin = caller(context.as_deref(), roles.as_deref()).is_some_and(|(_, admin)| admin);
+    let roles = match (context, roles) {
+        (Some(Extension(ctx)), _) => ctx.roles,
+        (None, Some(Extension(r))) => r.roles,
+        (None, None) => Vec::new(),
+    };
+    (roles, is_admin)
+}
+
+/// Whether `roles` see every event regardless of the presentation policy.
+pub fn is_operator(config: &LogStreamConfig, roles: &[String], is_admin: bool) -> bool {
+    is_admin || roles.iter().any(|r| config.operator_roles.contains(r))
+}
+
+/// Apply a presentation policy to a parsed line: `false` if end users must
+/// not see it, otherwise its policy fields are masked.
+pub fn present(line: &mut serde_json::Value, policy: &PresentationPolicy) -> bool {
+    let event_type = line
+        .get("event_type")
+        .or_else(|| line.get("type"))
+        .and_then(|v| v.as_str());
+    if !event_type.is_some_and(|t| policy.shows(t)) {
+        return false;
+    }
+    redact(line, &policy.redacted_fields);
+    true
+}
+
+/// What a caller is allowed to see of event payloads: the presentation
+/// policy and role-based redaction.
+pub(crate) struct EventPresenter {
+    /// Fields masked for restricted roles.
+    redacted: Option<Vec<String>>,
+    /// Global presentation policy; `None` for operators.
+    presentation: Option<PresentationPolicy>,
+    workspaces: Option<Arc<WorkspaceStore>>,
+}
+
+impl EventPresenter {
+    /// Presenter for the authenticated caller of a request.
+    pub(crate) fn for_caller(
+        state: &AppState,
+        context: Option<Extension<UserContext>>,
+        roles: Option<Extension<UserRoles>>,
+    ) -> Self {
+        let config = &state.app_config.governance;
+        let (roles, is_admin) = caller_roles(context, roles);
+        Self {
+            redacted: redacts_for(&config.log_stream, &roles, is_admin)
+                .then(|| config.log_stream.redacted_fields.clone()),
+            presentation: (!is_operator(&config.log_stream, &roles, is_admin))
+                .then(|| config.presentation.clone()),
+            workspaces: state.workspace_store.clone(),
+        }
+    }
+
+    /// Whether the caller may see less than the raw events.
+    fn restricted(&self) -> bool {
+        self.redacted.is_some() || self.presentation.is_some()
+    }
+
+    /// The policy for a workspace, falling back to the global one.
+    async fn policy_for(&self, workspace_id: Option<&str>) -> Option<PresentationPolicy> {
+        let global = self.presentation.as_ref()?;
+        if let (Some(id), Some(store)) = (workspace_id, &self.workspaces) {
+            if let Some(policy) = store
+                .get_workspace(id)
+                .await
+                .and_then(|workspace| workspace.presentation)
+            {
+                return Some(policy);
+            }
+        }
+        Some(global.clone())
+    }
+
+    /// Apply presentation and redaction to an event of `workspace_id`;
+    /// `false` if the caller must not see it.
+    pub(crate) async fn apply(
+        &self,
+        event: &mut serde_json::Value,
+        workspace_id: Option<&str>,
+    ) -> bool {
+        if let Some(policy) = self.policy_for(workspace_id).await {
+            if !present(event, &policy) {
+                return false;
+            }
+        }
+        if let Some(fields) = &self.redacted {
+            redact(event, fields);
+        }
+        true
+    }
+}
+
+/// What one subscriber is allowed to see and asked to receive.
+struct LogView {
+    filter: LogFilter,
+    presenter: EventPresenter,
+}
+
+impl LogView {
+    /// Apply filter, presentation and redaction to a line; `None` drops it.
+    async fn render(&self, line: String) -> Option<String> {
+        let Ok(mut value) = serde_json::from_str::<serde_json::Value>(&line) else {
+            // Unstructured lines carry no fields to filter or redact on.
+            return (!self.presenter.restricted() && self.filter.types.is_empty()).then_some(line);
+        };
+        if !self.filter.matches(&value) {
+            return None;
+        }
+        if !self.presenter.restricted() {
+            return Some(line);
+        }
+        let workspace_id = value
+            .get("workspace_id")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        self.presenter
+            .apply(&mut value, workspace_id.as_deref())
+            .await
+            .then(|| value.to_string())
+    }
+}
+
+/// WebSocket handler for the filtered log stream.
+pub async fn logs_ws_handler(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<LogsQuery>,
+    context: Option<Extension<UserContext>>,
+    roles: Option<Extension<UserRoles>>,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    let presenter = EventPresenter::for_caller(&state, context, roles);
+    let view = LogFilter::from_query(&query).map(|filter| LogView { filter, presenter });
+    let since = query.since.filter(|s| !s.is_empty());
+    ws.on_upgrade(move |socket| handle_logs_ws(state, socket, view, since))
+}
+
+async fn send_json(socket: &mut WebSocket, value: serde_json::Value, timeout: Duration) -> bool {
+    matches!(
+        tokio::time::timeout(timeout, socket.send(Message::Text(value.to_string()))).await,
+        Ok(Ok(()))
+    )
+}
+
+async fn close(socket: &mut WebSocket, code: u16, reason: &str) {
+    let _ = socket
+        .send(Message::Close(Some(CloseFrame {
+            code,
+            reason: reason.to_string().into(),
+        })))
+        .await;
+}
+
+async fn handle_logs_ws(
+    state: Arc<AppState>,
+    mut socket: WebSocket,
+    view: Result<LogView, String>,
+    since: Option<String>,
+) {
+    let config = state.app_config.governance.log_stream.clone();
+    let timeout = Duration::from_secs(config.send_timeout_secs.max(1));
+
+    let view = match view {
+        Ok(view) => view,
+        Err(message) => {
+            let _ = send_json(
+                &mut socket,
+                serde_json::json!({"type": "error", "message": message}),
+                timeout,
+            )
+            .await;
+            return;
+        }
+    };
+
+    let mut rx = match state.event_stream.as_ref().or(state.logs_channel.as_ref()) {
+        Some(tx) => tx.subscribe(),
+        None => {
+            let _ = send_json(
+                &mut socket,
+                serde_json::json!({"type": "error", "message": "Logs channel not configured"}),
+                timeout,
+            )
+            .await;
+            return;
+        }
+    };
+
+    // Clarification questions are surfaced in the log stream too, so a
+    // client watching only the logs can see the agent is waiting on a human.
+    let mut questions = state.human_input.as_ref().map(|input| input.subscribe());
+    let mut lag_notices = 0u32;
+
+    // Subscribed before reading the buffer, so nothing falls between the two;
+    // live lines already replayed are skipped by id.
+    let mut replayed = HashSet::new();
+    if let (Some(since), Some(buffer)) = (since, state.log_buffer.as_ref()) {
+        let lines = match buffer.since(&since) {
+            Some(lines) => lines,
+            None => {
+                let notice = serde_json::json!({"type": "replay_gap", "since": since});
+                if !send_json(&mut socket, notice, timeout).await {
+                    return;
+                }
+                buffer.all()
+            }
+        };
+        for (id, line) in lines {
+            replayed.insert(id);
+            let Some(message) = view.render(line).await else {
+                continue;
+            };
+            if !matches!(
+                tokio::time::timeout(timeout, socket.send(Message::Text(message))).await,
+                Ok(Ok(()))
+            ) {
+                return;
+            }
+        }
+    }
+
+    loop {
+        let line = tokio::select! {
+            result = rx.recv() => result,
+            result = recv_optional(&mut questions) => result.map(|question| {
+                serde_json::json!({"type": "user_question", "data": question}).to_string()
+            }),
+        };
+        match line {
+            Ok(log_line) => {
+                if !replayed.is_empty() {
+                    match event_id(&log_line) {
+                        Some(id) if replayed.contains(&id) => continue,
+                        // Past the overlap with the replay; later lines are new.
+                        _ => replayed.clear(),
+                    }
+                }
+                let Some(message) = view.render(log_line).await else {
+                    continue;
+                };
+                match tokio::time::timeout(timeout, socket.send(Message::Text(message))).await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(_)) => break,
+                    Err(_) => {
+                        tracing::warn!("Log stream send timed out; disconnecting subscriber");
+                        metrics::counter!("logs_ws_disconnects_total", "reason" => "timeout")
+                            .increment(1);
+                        break;
+                    }
+                }
+            }
+            Err(RecvError::Lagged(skipped)) => {
+                metrics::counter!("logs_ws_lagged_total").increment(skipped);
+                lag_notices += 1;
+                if lag_notices > config.max_lag_notices {
+                    tracing::warn!(skipped, "Log stream subscriber too slow; disconnecting");
+                    metrics::counter!("logs_ws_disconnects_total", "reason" => "lagged")
+                        .increment(1);
+                    close(&mut socket, CLOSE_SLOW_CONSUMER, "slow consumer").await;
+                    break;
+                }
+                let notice = serde_json::json!({"type": "lagged", "skipped": skipped});
+                if !send_json(&mut socket, notice, timeout).await {
+                    break;
+                }
+            }
+            Err(RecvError::Closed) => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(query: LogsQuery) -> LogFilter {
+        LogFilter::from_query(&query).unwrap()
+    }
+
+    #[test]
+    fn test_filter_by_session_type_and_severity() {
+        let line = serde_json::json!({
+            "trace_id": "t1",
+            "session_id": "s1",
+            "event_type": "TOOL_EXEC_STARTED",
+            "severity": "WARNING",
+        });
+
+        assert!(filter(LogsQuery::default()).matches(&line));
+        assert!(filter(LogsQuery {
+            session_id: Some("s1".into()),
+            types: Some("tool_exec_started, tool_exec_finished".into()),
+            min_severity: Some("warning".into()),
+            ..Default::default()
+        })
+        .matches(&line));
+        assert!(!filter(LogsQuery {
+            session_id: Some("s2".into()),
+            ..Default::default()
+        })
+        .matches(&line));
+        assert!(!filter(LogsQuery {
+            trace_id: Some("t2".into()),
+            ..Default::default()
+        })
+        .matches(&line));
+        assert!(!filter(LogsQuery {
+            min_severity: Some("error".into()),
+            ..Default::default()
+        })
+        .matches(&line));
+
+        let question = serde_json::json!({"type": "user_question", "data": {"session_id": "s1"}});
+        assert!(filter(LogsQuery {
+            session_id: Some("s1".into()),
+            types: Some("USER_QUESTION".into()),
+            min_severity: Some("info".into()),
+            ..Default::default()
+        })
+        .matches(&question));
+
+        assert!(LogFilter::from_query(&LogsQuery {
+            min_severity: Some("loud".into()),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_buffer_since_and_eviction() {
+        let buffer = LogBuffer::new(3);
+        for id in ["e1", "e2", "e3", "e4"] {
+            buffer.push(&serde_json::json!({"id": id, "event_type": "SYSTEM_ERROR"}).to_string());
+        }
+        buffer.push("plain text without an id");
+
+        let ids = |lines: Vec<(String, String)>| -> Vec<String> {
+            lines.into_iter().map(|(id, _)| id).collect()
+        };
+        assert_eq!(ids(buffer.since("e2").unwrap()), vec!["e3", "e4"]);
+        assert!(buffer.since("e4").unwrap().is_empty());
+        // e1 was evicted
+        assert!(buffer.since("e1").is_none());
+        assert_eq!(ids(buffer.all()), vec!["e2", "e3", "e4"]);
+    }
+
+    #[test]
+    fn test_presentation_policy() {
+        let config = LogStreamConfig::default();
+        assert!(is_operator(&config, &["operator".into()], false));
+        assert!(is_operator(&config, &[], true));
+        assert!(!is_operator(&config, &["user".into()], false));
+
+        let policy = PresentationPolicy::default();
+        let mut finished = serde_json::json!({
+            "event_type": "TOOL_EXEC_FINISHED",
+            "payload": {"tool_name": "read_file", "output": "API_KEY=secret", "duration_ms": 3},
+        });
+        assert!(present(&mut finished, &policy));
+        assert_eq!(finished["payload"]["output"], "[REDACTED]");
+        assert_eq!(finished["payload"]["duration_ms"], 3);
+
+        let mut policy_event = serde_json::json!({"event_type": "POLICY_EVALUATED", "payload": {}});
+        assert!(!present(&mut policy_event, &policy));
+        let mut question = serde_json::json!({"type": "user_question", "data": {}});
+        assert!(present(&mut question, &policy));
+    }
+
+    #[test]
+    fn test_redaction_by_role() {
+        let config = LogStreamConfig::default();
+        assert!(redacts_for(&config, &["viewer".into()], false));
+        assert!(!redacts_for(&config, &["viewer".into()], true));
+        assert!(!redacts_for(&config, &["user".into()], false));
+
+        let mut line = serde_json::json!({
+            "event_type": "TOOL_EXEC_STARTED",
+            "payload": {"tool_name": "shell", "args": {"cmd": "cat secrets"}, "steps": [{"input": "x"}]},
+        });
+        redact(&mut line, &config.redacted_fields);
+        assert_eq!(line["payload"]["tool_name"], "shell");
+        assert_eq!(line["payload"]["args"], "[REDACTED]");
+        assert_eq!(line["payload"]["steps"][0]["input"], "[REDACTED]");
+    }
+}
@@ -0,0 +1,212 @@
+//! Memory search endpoint.
+//!
+//! `GET /v1/agent/memory/search?q=` lets a user query the knowledge accumulated
+//! across their missions and research runs. Results are scoped to the caller;
+//! admins may search another user's memories or all of them.
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use multi_agent_core::traits::KnowledgeEntry;
+use multi_agent_governance::rbac::{UserContext, UserRoles};
+
+use crate::identity::caller;
+use crate::server::AppState;
+
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 50;
+/// Characters of context kept around the first match in a snippet.
+const SNIPPET_CHARS: usize = 240;
+
+/// Query parameters for memory search.
+#[derive(Debug, Deserialize)]
+pub struct MemorySearchQuery {
+    /// Free-text query.
+    pub q: String,
+    /// Maximum results (default 10, capped at 50).
+    pub limit: Option<usize>,
+    /// Restrict to entries tagged `workspace:<id>`.
+    pub workspace: Option<String>,
+    /// Admin only: search this user's memories, or `*` for all users.
+    pub user_id: Option<String>,
+}
+
+/// Where a memory came from.
+#[derive(Debug, Serialize)]
+pub struct MemoryProvenance {
+    pub session_id: String,
+    pub source_task: String,
+    pub source_url: Option<String>,
+    pub tags: Vec<String>,
+    pub created_at: i64,
+    pub refreshed_at: Option<i64>,
+}
+
+/// One memory search hit.
+#[derive(Debug, Serialize)]
+pub struct MemoryHit {
+    pub id: String,
+    pub user_id: String,
+    pub snippet: String,
+    pub provenance: MemoryProvenance,
+}
+
+impl MemoryHit {
+    fn from_entry(entry: KnowledgeEntry, query: &str) -> Self {
+        Self {
+            snippet: snippet(&entry.summary, query),
+            id: entry.id,
+            user_id: entry.user_id,
+            provenance: MemoryProvenance {
+                session_id: entry.session_id,
+                source_task: entry.source_task,
+                source_url: entry.source_url,
+                tags: entry.tags,
+                created_at: entry.created_at,
+                refreshed_at: entry.refreshed_at,
+            },
+        }
+    }
+}
+
+/// Text around the first query term found in `text`, on char boundaries.
+pub(crate) fn snippet(text: &str, query: &str) -> String {
+    let lower = text.to_lowercase();
+    // Lowercasing can change byte lengths; only trust positions when it did not.
+    let start = if lower.len() == text.len() {
+        query
+            .split_whitespace()
+            .filter_map(|term| lower.find(&term.to_lowercase()))
+            .min()
+            .unwrap_or(0)
+    } else {
+        0
+    };
+
+    let chars_before = text[..start].chars().count();
+    let skip = chars_before.saturating_sub(SNIPPET_CHARS / 4);
+    let body: String = text.chars().skip(skip).take(SNIPPET_CHARS).collect();
+    let body = body.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let mut out = String::new();
+    if skip > 0 {
+        out.push('…');
+    }
+    out.push_str(&body);
+    if text.chars().count() > skip + SNIPPET_CHARS {
+        out.push('…');
+    }
+    out
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (status, Json(serde_json::json!({ "error": message }))).into_response()
+}
+
+/// `GET /memory/search?q=`
+pub(crate) async fn memory_search_handler(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<UserContext>>,
+    roles: Option<Extension<UserRoles>>,
+    Query(params): Query<MemorySearchQuery>,
+) -> Response {
+    let Some(store) = state.knowledge_store.as_ref() else {
+        return error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Knowledge store not configured",
+        );
+    };
+    let Some((user_id, is_admin)) = caller(context.as_deref(), roles.as_deref()) else {
+        return error_response(StatusCode::UNAUTHORIZED, "Authentication required");
+    };
+
+    let query = params.q.trim();
+    if query.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "Query parameter 'q' is required");
+    }
+
+    let scope = match params.user_id.as_deref() {
+        None => Some(user_id.clone()),
+        Some(requested) if requested == user_id => Some(user_id.clone()),
+        Some(_) if !is_admin => {
+            return error_response(
+                StatusCode::FORBIDDEN,
+                "Only admins can search other users' memories",
+            )
+        }
+        Some("*") => None,
+        Some(requested) => Some(requested.to_string()),
+    };
+
+    let limit = params.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let workspace_tag = params
+        .workspace
+        .as_deref()
+        .map(|w| format!("workspace:{}", w));
+    // Over-fetch so the workspace filter still fills the page.
+    let fetch = if workspace_tag.is_some() {
+        MAX_LIMIT * 4
+    } else {
+        limit
+    };
+
+    let entries = match store.search_text(query, scope.as_deref(), fetch).await {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::error!(error = %e, "Memory search failed");
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR, "Memory search failed");
+        }
+    };
+
+    let results: Vec<MemoryHit> = entries
+        .into_iter()
+        .filter(|e| {
+            workspace_tag
+                .as_ref()
+                .is_none_or(|tag| e.tags.contains(tag))
+        })
+        .take(limit)
+        .map(|e| MemoryHit::from_entry(e, query))
+        .collect();
+
+    tracing::info!(
+        user_id = %user_id,
+        scope = scope.as_deref().unwrap_or("*"),
+        results = results.len(),
+        "Memory search"
+    );
+
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "query": query,
+            "results": results,
+        })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snippet_centres_on_match() {
+        let text = format!("{} needle here {}", "a ".repeat(300), "b ".repeat(300));
+        let s = snippet(&text, "NEEDLE");
+        assert!(s.starts_with('…'));
+        assert!(s.ends_with('…'));
+        assert!(s.contains("needle here"));
+
+        assert_eq!(snippet("short text", "missing"), "short text");
+        // Multi-byte text never splits a char
+        let s = snippet(&"é".repeat(500), "é");
+        assert!(s.chars().count() <= SNIPPET_CHARS + 1);
+    }
+}
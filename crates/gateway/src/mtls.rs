@@ -0,0 +1,215 @@
+//! Mutual TLS client authentication.
+//!
+//! When `gateway.tls.ca_path` is set, the TLS listener asks clients for a
+//! certificate and verifies it against that CA bundle. [`MtlsAcceptor`] maps
+//! the verified certificate's subject common name to an RBAC identity through
+//! `gateway.tls.client_identities` and attaches it to every request on the
+//! connection as a [`PeerIdentity`]; `bearer_auth_middleware` accepts it in
+//! place of a bearer token. With `require_client_cert` unset, clients without a
+//! certificate still connect and authenticate with tokens.
+
+use axum::middleware::AddExtension;
+use axum::Extension;
+use axum_server::accept::Accept;
+use axum_server::tls_rustls::{RustlsAcceptor, RustlsConfig};
+use futures::future::BoxFuture;
+use rustls::pki_types::CertificateDer;
+use rustls::server::WebPkiClientVerifier;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::server::TlsStream;
+use tower::Layer;
+
+use multi_agent_core::config::{ClientCertIdentity, TlsConfig};
+use multi_agent_core::{Error, Result};
+use multi_agent_governance::rbac::UserRoles;
+
+/// Identity established by the client certificate of a TLS connection.
+#[derive(Debug, Clone, Default)]
+pub struct PeerIdentity {
+    /// Subject common name of the verified certificate, if one was presented.
+    pub common_name: Option<String>,
+    /// RBAC identity mapped from the certificate.
+    pub user: Option<UserRoles>,
+}
+
+/// RBAC identity of a certificate with the given common name.
+pub fn identity_for(
+    identities: &HashMap<String, ClientCertIdentity>,
+    common_name: &str,
+) -> UserRoles {
+    let mapped = identities.get(common_name);
+    let roles = mapped.map(|i| i.roles.clone()).unwrap_or_default();
+    UserRoles {
+        user_id: mapped
+            .and_then(|i| i.user_id.clone())
+            .unwrap_or_else(|| common_name.to_string()),
+        is_admin: roles.iter().any(|r| r == "admin"),
+        roles,
+    }
+}
+
+fn common_name(der: &[u8]) -> Option<String> {
+    let (_, cert) = x509_parser::parse_x509_certificate(der).ok()?;
+    let name = cert.subject().iter_common_name().next()?;
+    name.as_str().ok().map(str::to_string)
+}
+
+fn read_certs(path: &str) -> Result<Vec<CertificateDer<'static>>> {
+    let pem = std::fs::read(path)
+        .map_err(|e| Error::gateway(format!("Failed to read {}: {}", path, e)))?;
+    let certs = rustls_pemfile::certs(&mut pem.as_slice())
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| Error::gateway(format!("Invalid certificate in {}: {}", path, e)))?;
+    if certs.is_empty() {
+        return Err(Error::gateway(format!("No certificates in {}", path)));
+    }
+    Ok(certs)
+}
+
+/// Build the rustls server configuration, verifying client certificates
+/// against `ca_path` when it is set.
+pub fn server_config(tls: &TlsConfig) -> Result<rustls::ServerConfig> {
+    let cert_path = tls
+        .cert_path
+        .as_deref()
+        .ok_or_else(|| Error::gateway("TLS enabled but cert_path missing"))?;
+    let key_path = tls
+        .key_path
+        .as_deref()
+        .ok_or_else(|| Error::gateway("TLS enabled but key_path missing"))?;
+
+    let certs = read_certs(cert_path)?;
+    let key_pem = std::fs::read(key_path)
+        .map_err(|e| Error::gateway(format!("Failed to read {}: {}", key_path, e)))?;
+    let key = rustls_pemfile::private_key(&mut key_pem.as_slice())
+        .map_err(|e| Error::gateway(format!("Invalid private key in {}: {}", key_path, e)))?
+        .ok_or_else(|| Error::gateway(format!("No private key in {}", key_path)))?;
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let builder = rustls::ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(|e| Error::gateway(format!("TLS config error: {}", e)))?;
+
+    let builder = match &tls.ca_path {
+        Some(ca_path) => {
+            let mut roots = rustls::RootCertStore::empty();
+            for cert in read_certs(ca_path)? {
+                roots
+                    .add(cert)
+                    .map_err(|e| Error::gateway(format!("Invalid client CA: {}", e)))?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider);
+            let verifier = if tls.require_client_cert {
+                verifier
+            } else {
+                verifier.allow_unauthenticated()
+            };
+            let verifier = verifier
+                .build()
+                .map_err(|e| Error::gateway(format!("Invalid client CA: {}", e)))?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    let mut config = builder
+        .with_single_cert(certs, key)
+        .map_err(|e| Error::gateway(format!("TLS config error: {}", e)))?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(config)
+}
+
+/// TLS acceptor attaching a [`PeerIdentity`] to each connection's requests.
+#[derive(Clone)]
+pub struct MtlsAcceptor {
+    inner: RustlsAcceptor,
+    identities: Arc<HashMap<String, ClientCertIdentity>>,
+}
+
+impl MtlsAcceptor {
+    /// Create an acceptor for `tls`.
+    pub fn new(tls: &TlsConfig) -> Result<Self> {
+        let config = RustlsConfig::from_config(Arc::new(server_config(tls)?));
+        Ok(Self {
+            inner: RustlsAcceptor::new(config),
+            identities: Arc::new(tls.client_identities.clone()),
+        })
+    }
+}
+
+impl<I, S> Accept<I, S> for MtlsAcceptor
+where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    S: Send + 'static,
+{
+    type Stream = TlsStream<I>;
+    type Service = AddExtension<S, PeerIdentity>;
+    type Future = BoxFuture<'static, std::io::Result<(Self::Stream, Self::Service)>>;
+
+    fn accept(&self, stream: I, service: S) -> Self::Future {
+        let acceptor = self.inner.clone();
+        let identities = self.identities.clone();
+
+        Box::pin(async move {
+            let (stream, service) = acceptor.accept(stream, service).await?;
+            let common_name = stream
+                .get_ref()
+                .1
+                .peer_certificates()
+                .and_then(|certs| certs.first())
+                .and_then(|cert| common_name(cert));
+            if let Some(cn) = &common_name {
+                tracing::debug!(common_name = %cn, "Client certificate verified");
+            }
+            let peer = PeerIdentity {
+                user: common_name
+                    .as_deref()
+                    .map(|cn| identity_for(&identities, cn)),
+                common_name,
+            };
+            Ok((stream, Extension(peer).layer(service)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity_mapping() {
+        let mut identities = HashMap::new();
+        identities.insert(
+            "billing-service".to_string(),
+            ClientCertIdentity {
+                user_id: Some("svc-billing".into()),
+                roles: vec!["operator".into(), "admin".into()],
+            },
+        );
+
+        let mapped = identity_for(&identities, "billing-service");
+        assert_eq!(mapped.user_id, "svc-billing");
+        assert_eq!(mapped.roles, vec!["operator", "admin"]);
+        assert!(mapped.is_admin);
+
+        let unmapped = identity_for(&identities, "reports");
+        assert_eq!(unmapped.user_id, "reports");
+        assert!(unmapped.roles.is_empty());
+        assert!(!unmapped.is_admin);
+    }
+
+    #[test]
+    fn test_server_config_requires_readable_files() {
+        let tls = TlsConfig {
+            enabled: true,
+            cert_path: Some("/nonexistent/cert.pem".into()),
+            key_path: Some("/nonexistent/key.pem".into()),
+            ca_path: Some("/nonexistent/ca.pem".into()),
+            ..TlsConfig::default()
+        };
+        assert!(server_config(&tls).is_err());
+        assert!(server_config(&TlsConfig::default()).is_err());
+    }
+}
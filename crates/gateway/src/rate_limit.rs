@@ -0,0 +1,257 @@
+//! Client identification for rate limiting.
+//!
+//! Requests are limited per client. The client is the connecting peer,
+//! unless that peer is one of `gateway.rate_limit.trusted_proxies`: then the
+//! address it forwarded in `gateway.rate_limit.forwarded_header` is used,
+//! walking the chain from the right past every trusted hop so a client cannot
+//! spoof its address by prepending entries. The other forwarding header is
+//! never read, as proxies pass it through from the client. With `key_by_identity`, requests
+//! carrying valid credentials are keyed on the authenticated user instead,
+//! and anonymous requests fall back to the client IP.
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, Extensions, HeaderMap, Request as HttpRequest},
+    middleware::Next,
+    response::Response,
+};
+use axum_extra::extract::cookie::CookieJar;
+use multi_agent_core::config::{ForwardedHeader, RateLimitConfig};
+use secrecy::ExposeSecret;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use tower_governor::{key_extractor::KeyExtractor, GovernorError};
+
+use crate::admin_access::ip_allowed;
+use crate::server::AppState;
+
+/// Rate limit key of a request, set by [`client_key_middleware`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClientKey(pub String);
+
+impl ClientKey {
+    fn ip(ip: IpAddr) -> Self {
+        Self(format!("ip:{}", ip))
+    }
+
+    fn user(user_id: &str) -> Self {
+        Self(format!("user:{}", user_id))
+    }
+}
+
+/// Parse one `X-Forwarded-For` entry or `Forwarded` `for=` value.
+///
+/// Accepts bare addresses, `v4:port` and `[v6]:port`; obfuscated
+/// identifiers and `unknown` yield `None`.
+fn parse_node(node: &str) -> Option<IpAddr> {
+    let node = node.trim().trim_matches('"');
+    if let Ok(ip) = node.parse::<IpAddr>() {
+        return Some(ip);
+    }
+    if let Some(rest) = node.strip_prefix('[') {
+        return rest.split(']').next()?.parse().ok();
+    }
+    node.parse::<SocketAddr>().ok().map(|addr| addr.ip())
+}
+
+/// Forwarded hops in `source`, in order from the original client to the
+/// last proxy. An unparseable hop is kept as `None` so the chain cannot be
+/// walked past it.
+fn forwarded_chain(headers: &HeaderMap, source: ForwardedHeader) -> Vec<Option<IpAddr>> {
+    match source {
+        ForwardedHeader::Forwarded => headers
+            .get_all(header::FORWARDED)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(|element| {
+                element.split(';').find_map(|pair| {
+                    let (name, value) = pair.split_once('=')?;
+                    name.trim()
+                        .eq_ignore_ascii_case("for")
+                        .then(|| parse_node(value))
+                })
+            })
+            .collect(),
+        ForwardedHeader::XForwardedFor => headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(parse_node)
+            .collect(),
+    }
+}
+
+/// Address of the client behind `peer`, believing the forwarding header in
+/// `config` only from its trusted proxies.
+pub fn client_ip(peer: IpAddr, headers: &HeaderMap, config: &RateLimitConfig) -> IpAddr {
+    let trusted_proxies = &config.trusted_proxies;
+    let mut client = peer;
+    for hop in forwarded_chain(headers, config.forwarded_header)
+        .into_iter()
+        .rev()
+    {
+        if !ip_allowed(trusted_proxies, client) {
+            break;
+        }
+        match hop {
+            Some(ip) => client = ip,
+            None => break,
+        }
+    }
+    client
+}
+
+/// Authenticated user of the request, if its credentials are valid.
+async fn authenticated_user(
+    state: &AppState,
+    headers: &HeaderMap,
+    extensions: &Extensions,
+) -> Option<String> {
+    if let Some(admin_token) = &state.app_config.governance.admin_token {
+        let header_token = headers
+            .get("x-admin-token")
+            .and_then(|h| h.to_str().ok())
+            .map(str::to_string);
+        let token = header_token.or_else(|| {
+            CookieJar::from_headers(headers)
+                .get("admin_token")
+                .map(|c| c.value().to_string())
+        });
+        if token.as_deref() == Some(admin_token.expose_secret().as_str()) {
+            return Some("admin".to_string());
+        }
+    }
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "));
+    let Some(token) = bearer else {
+        return extensions
+            .get::<crate::mtls::PeerIdentity>()
+            .and_then(|peer| peer.user.as_ref())
+            .map(|user| user.user_id.clone());
+    };
+    let rbac = state.admin_state.as_ref()?.rbac.clone();
+    rbac.validate(token).await.ok().map(|user| user.user_id)
+}
+
+/// Middleware attaching the request's [`ClientKey`].
+pub(crate) async fn client_key_middleware(
+    State(state): State<Arc<AppState>>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let config = &state.app_config.gateway.rate_limit;
+    let user = if config.key_by_identity {
+        authenticated_user(&state, req.headers(), req.extensions()).await
+    } else {
+        None
+    };
+    let key = match user {
+        Some(user_id) => ClientKey::user(&user_id),
+        None => {
+            let peer = req
+                .extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ci| ci.0.ip())
+                .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST));
+            ClientKey::ip(client_ip(peer, req.headers(), config))
+        }
+    };
+    req.extensions_mut().insert(key);
+    next.run(req).await
+}
+
+/// Keys the local limiter on the request's [`ClientKey`].
+#[derive(Debug, Clone, Copy)]
+pub struct ClientKeyExtractor;
+
+impl KeyExtractor for ClientKeyExtractor {
+    type Key = ClientKey;
+
+    fn extract<T>(&self, req: &HttpRequest<T>) -> Result<Self::Key, GovernorError> {
+        req.extensions()
+            .get::<ClientKey>()
+            .cloned()
+            .ok_or(GovernorError::UnableToExtractKey)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn headers(name: &'static str, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    fn trusting(proxies: &str, forwarded_header: ForwardedHeader) -> RateLimitConfig {
+        RateLimitConfig {
+            trusted_proxies: vec![proxies.to_string()],
+            forwarded_header,
+            key_by_identity: false,
+        }
+    }
+
+    #[test]
+    fn test_client_ip_behind_trusted_proxies() {
+        let trusted = trusting("10.0.0.0/8", ForwardedHeader::XForwardedFor);
+        let xff = headers("x-forwarded-for", "198.51.100.7, 203.0.113.5, 10.0.0.3");
+
+        // Trusted hops are skipped; the spoofable leftmost entry is not reached
+        assert_eq!(client_ip(ip("10.0.0.1"), &xff, &trusted), ip("203.0.113.5"));
+        // Headers from untrusted peers are ignored
+        let untrusted = RateLimitConfig::default();
+        assert_eq!(
+            client_ip(ip("192.0.2.1"), &xff, &untrusted),
+            ip("192.0.2.1")
+        );
+        assert_eq!(client_ip(ip("192.0.2.1"), &xff, &trusted), ip("192.0.2.1"));
+
+        let trusted = trusting("10.0.0.0/8", ForwardedHeader::Forwarded);
+        let forwarded = headers(
+            "forwarded",
+            "for=\"[2001:db8::1]:4711\";proto=https, For=10.0.0.9",
+        );
+        assert_eq!(
+            client_ip(ip("10.0.0.1"), &forwarded, &trusted),
+            ip("2001:db8::1")
+        );
+
+        // An obfuscated hop stops the walk at the proxy that reported it
+        let obfuscated = headers("forwarded", "for=_hidden, for=10.0.0.9");
+        assert_eq!(
+            client_ip(ip("10.0.0.1"), &obfuscated, &trusted),
+            ip("10.0.0.9")
+        );
+    }
+
+    #[test]
+    fn test_client_ip_reads_only_the_configured_header() {
+        // A proxy appending X-Forwarded-For passes the client's own
+        // Forwarded header through; it must not pick the key
+        let mut spoofed = headers("x-forwarded-for", "203.0.113.5");
+        spoofed.insert(
+            header::FORWARDED,
+            HeaderValue::from_static("for=198.51.100.1"),
+        );
+        let xff = trusting("10.0.0.0/8", ForwardedHeader::XForwardedFor);
+        assert_eq!(client_ip(ip("10.0.0.1"), &spoofed, &xff), ip("203.0.113.5"));
+
+        let forwarded = trusting("10.0.0.0/8", ForwardedHeader::Forwarded);
+        let xff_only = headers("x-forwarded-for", "203.0.113.5");
+        assert_eq!(
+            client_ip(ip("10.0.0.1"), &xff_only, &forwarded),
+            ip("10.0.0.1")
+        );
+    }
+}
@@ -1,9 +1,18 @@
 use chrono::Utc;
+use dashmap::DashMap;
 use multi_agent_admin::AdminState;
 use multi_agent_core::{
-    events::{EventEnvelope, EventType},
+    events::{
+        ApprovalDecidedPayload, ApprovalRequestedPayload, EgressRequestPayload,
+        EgressResultPayload, EventEnvelope, EventType, NetworkPolicyPayload, PlanProposedPayload,
+        ReportGeneratedPayload, ReportSectionGeneratedPayload, ResearchCreatedPayload,
+        ResearchSourceProgressPayload,
+    },
     traits::{ApprovalGate, ArtifactStore, KnowledgeEntry, KnowledgeStore},
-    types::research::ResearchPlan,
+    types::{
+        research::{ReportSection, ResearchOptions, ResearchPlan, ResearchStyle, SourceSnapshot},
+        AgentResult, ArtifactOwner, RefId,
+    },
     Error, Result,
 };
 use multi_agent_governance::{
@@ -15,8 +24,11 @@ use rig::completion::Prompt;
 use rig::prelude::*;
 use rig::providers::openai;
 use sha2::{Digest, Sha256};
+use std::collections::{HashSet, VecDeque};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::{mpsc, RwLock};
+use tokio::time::Instant;
 use uuid::Uuid;
 
 /// State of a research task.
@@ -31,9 +43,49 @@ pub enum ResearchStatus {
     Failed(String),
 }
 
-use multi_agent_core::config::SafetyConfig;
+use multi_agent_core::config::{RecrawlConfig, ResearchBudgetConfig, SafetyConfig};
 use multi_agent_governance::PolicyEngine;
 
+/// Effective limits for one research run, after validation against the budget.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResearchLimits {
+    /// Maximum number of sources to fetch.
+    pub max_sources: u32,
+    /// Link-following depth from each seed page.
+    pub max_depth: u32,
+    /// Time budget for the fetch phase.
+    pub time_budget: Duration,
+    /// Domains crawling is restricted to (empty = the plan's domains).
+    pub allowed_domains: Vec<String>,
+    /// Report style.
+    pub style: ResearchStyle,
+}
+
+/// Longest source excerpt kept in the knowledge store.
+const MAX_SOURCE_EXCERPT_CHARS: usize = 4000;
+
+/// Outcome of one re-crawl pass over stale knowledge sources.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct RecrawlReport {
+    /// Stale sources considered.
+    pub checked: usize,
+    /// Sources whose content was unchanged; only their freshness was bumped.
+    pub unchanged: usize,
+    /// Sources whose content changed and was replaced.
+    pub updated: usize,
+    /// Sources that could not be fetched.
+    pub failed: usize,
+}
+
+/// A fetched and persisted research source.
+struct FetchedSource {
+    content_type: String,
+    body: String,
+    hash: String,
+    artifact_id: RefId,
+    size_bytes: u64,
+}
+
 /// Orchestrator for the Research Workflow.
 pub struct ResearchOrchestrator {
     _admin_state: Arc<AdminState>,
@@ -44,6 +96,9 @@ pub struct ResearchOrchestrator {
     artifact_store: Arc<dyn ArtifactStore>,
     knowledge_store: Arc<dyn KnowledgeStore>,
     logs_channel: Option<tokio::sync::broadcast::Sender<String>>,
+    budgets: ResearchBudgetConfig,
+    /// Snapshotted sources per research session, for citation review.
+    sources: DashMap<String, Vec<SourceSnapshot>>,
 }
 
 impl ResearchOrchestrator {
@@ -67,33 +122,153 @@ impl ResearchOrchestrator {
             artifact_store,
             knowledge_store,
             logs_channel,
+            budgets: ResearchBudgetConfig::default(),
+            sources: DashMap::new(),
+        }
+    }
+
+    /// Sources snapshotted for a research session, with each snapshot
+    /// re-hashed so reviewers can tell whether it still matches its citation.
+    ///
+    /// Returns `None` for unknown sessions.
+    pub async fn sources(&self, session_id: &str) -> Option<Vec<SourceSnapshot>> {
+        let mut sources = self.sources.get(session_id)?.clone();
+        for source in &mut sources {
+            let stored = self
+                .artifact_store
+                .load(&RefId::from_string(source.artifact_id.clone()))
+                .await;
+            source.verified = Some(match stored {
+                Ok(Some(bytes)) => sha256_hex(&bytes) == source.hash,
+                _ => false,
+            });
+        }
+        Some(sources)
+    }
+
+    /// Set the governance budgets research options are validated against.
+    pub fn with_budgets(mut self, budgets: ResearchBudgetConfig) -> Self {
+        self.budgets = budgets;
+        self
+    }
+
+    /// Validate caller options against the governance budget and network policy.
+    ///
+    /// Limits above the budget are rejected rather than silently clamped, so
+    /// callers learn what they are allowed to ask for.
+    pub async fn resolve_limits(&self, options: &ResearchOptions) -> Result<ResearchLimits> {
+        let budgets = &self.budgets;
+
+        let max_sources = options.max_sources.unwrap_or(budgets.max_sources);
+        if max_sources == 0 || max_sources > budgets.max_sources {
+            return Err(Error::invalid_request(format!(
+                "max_sources must be between 1 and {}",
+                budgets.max_sources
+            )));
+        }
+
+        let max_depth = options.max_depth.unwrap_or(0);
+        if max_depth > budgets.max_depth {
+            return Err(Error::invalid_request(format!(
+                "max_depth must be at most {}",
+                budgets.max_depth
+            )));
+        }
+
+        let time_budget_secs = options
+            .time_budget_secs
+            .unwrap_or(budgets.max_time_budget_secs);
+        if time_budget_secs == 0 || time_budget_secs > budgets.max_time_budget_secs {
+            return Err(Error::invalid_request(format!(
+                "time_budget_secs must be between 1 and {}",
+                budgets.max_time_budget_secs
+            )));
         }
+
+        let mut allowed_domains = Vec::new();
+        {
+            let policy = self.policy.read().await;
+            for domain in &options.allowed_domains {
+                let domain = normalize_domain(domain);
+                if domain.is_empty() {
+                    continue;
+                }
+                match policy.check(&seed_url(&domain)) {
+                    Ok(NetworkDecision::Allowed) => allowed_domains.push(domain),
+                    Ok(NetworkDecision::Denied(reason)) => {
+                        return Err(Error::governance(format!(
+                            "Domain '{}' is not allowed: {}",
+                            domain, reason
+                        )))
+                    }
+                    Err(e) => {
+                        return Err(Error::invalid_request(format!(
+                            "Invalid domain '{}': {}",
+                            domain, e
+                        )))
+                    }
+                }
+            }
+        }
+
+        Ok(ResearchLimits {
+            max_sources,
+            max_depth,
+            time_budget: Duration::from_secs(time_budget_secs),
+            allowed_domains,
+            style: options.style,
+        })
     }
 
     /// Execute the full research workflow.
+    ///
+    /// Returns the report as [`AgentResult::CitedText`], citing every source
+    /// that was fetched.
     pub async fn run_research(
         &self,
         session_id: &str,
         user_id: &str,
         query: &str,
-    ) -> Result<String> {
+        options: &ResearchOptions,
+    ) -> Result<AgentResult> {
+        self.run_research_with_sections(session_id, user_id, query, options, None)
+            .await
+    }
+
+    /// Execute the research workflow, sending each report section to
+    /// `sections` as soon as it is written.
+    pub async fn run_research_with_sections(
+        &self,
+        session_id: &str,
+        user_id: &str,
+        query: &str,
+        options: &ResearchOptions,
+        sections: Option<&mpsc::UnboundedSender<ReportSection>>,
+    ) -> Result<AgentResult> {
         let trace_id = Uuid::new_v4().to_string();
+        let limits = self.resolve_limits(options).await?;
 
         self.emit_audit(
             session_id,
             &trace_id,
             EventType::ResearchCreated,
-            serde_json::json!({
-                "query": query,
-                "orchestrator_version": "P0"
-            }),
+            ResearchCreatedPayload {
+                query: query.to_string(),
+                orchestrator_version: "P0".to_string(),
+                max_sources: limits.max_sources,
+                max_depth: limits.max_depth,
+                time_budget_secs: limits.time_budget.as_secs(),
+                allowed_domains: limits.allowed_domains.clone(),
+                style: limits.style,
+            },
         );
 
         // 1. Planning State
         tracing::info!(trace_id, "Transitioning to PLANNING");
-        let plan = self
+        let mut plan = self
             .plan_research(session_id, user_id, &trace_id, query)
             .await?;
+        apply_limits(&mut plan, &limits);
 
         // 2. Policy Evaluation
         tracing::info!(trace_id, "Transitioning to GOVERNANCE");
@@ -105,11 +280,11 @@ impl ResearchOrchestrator {
                 session_id,
                 &trace_id,
                 EventType::PolicyEvaluated,
-                serde_json::json!({
-                    "decision": "DENIED",
-                    "reason": reason,
-                    "plan_summary": plan.goals
-                }),
+                NetworkPolicyPayload {
+                    decision: "DENIED".to_string(),
+                    reason: Some(reason.clone()),
+                    plan_summary: plan.goals.clone(),
+                },
             );
             return Err(Error::governance(format!(
                 "Research blocked by network policy: {}",
@@ -121,10 +296,11 @@ impl ResearchOrchestrator {
             session_id,
             &trace_id,
             EventType::PolicyEvaluated,
-            serde_json::json!({
-                "decision": decision,
-                "plan_summary": plan.goals
-            }),
+            NetworkPolicyPayload {
+                decision: "ALLOWED".to_string(),
+                reason: None,
+                plan_summary: plan.goals.clone(),
+            },
         );
 
         // 3. Approval Gate (Risk-Based)
@@ -135,10 +311,10 @@ impl ResearchOrchestrator {
                 session_id,
                 &trace_id,
                 EventType::ApprovalRequested,
-                serde_json::json!({
-                    "plan": plan,
-                    "reason": "Risk score exceeds approval threshold"
-                }),
+                ApprovalRequestedPayload {
+                    plan: plan.clone(),
+                    reason: "Risk score exceeds approval threshold".to_string(),
+                },
             );
 
             let approval_req = multi_agent_core::types::ApprovalRequest {
@@ -152,6 +328,7 @@ impl ResearchOrchestrator {
                 timeout_secs: Some(600),
                 nonce: Uuid::new_v4().to_string(),
                 expires_at: (Utc::now() + chrono::Duration::seconds(600)).timestamp(),
+                mandatory: false,
             };
 
             let response = self.approval_gate.request_approval(&approval_req).await?;
@@ -162,7 +339,9 @@ impl ResearchOrchestrator {
                         session_id,
                         &trace_id,
                         EventType::ApprovalDecided,
-                        serde_json::json!({"status": "APPROVED"}),
+                        ApprovalDecidedPayload {
+                            status: "APPROVED".to_string(),
+                        },
                     );
                 }
                 _ => {
@@ -170,7 +349,9 @@ impl ResearchOrchestrator {
                         session_id,
                         &trace_id,
                         EventType::ApprovalDecided,
-                        serde_json::json!({"status": "DENIED"}),
+                        ApprovalDecidedPayload {
+                            status: "DENIED".to_string(),
+                        },
                     );
                     return Err(Error::governance(
                         "Research task denied by administrator".to_string(),
@@ -181,25 +362,104 @@ impl ResearchOrchestrator {
 
         // 4. Execution State (Airlock)
         tracing::info!(trace_id, "Transitioning to EXECUTION");
-        let findings = self.execute_research(session_id, &trace_id, &plan).await?;
+        // Attribute fetched artifacts to the requesting user for targeted deletion.
+        let owner = ArtifactOwner::new(Some(user_id.to_string()), Some(session_id.to_string()));
+        let (findings, mut sources) = owner
+            .scope(self.execute_research(session_id, &trace_id, &plan, &limits))
+            .await?;
+
+        let excerpts: Vec<String> = sources
+            .iter()
+            .zip(&findings)
+            .map(|(source, finding)| source_excerpt(&source.url, finding))
+            .collect();
 
         // 5. Synthesis State
         tracing::info!(trace_id, "Transitioning to SYNTHESIS");
         let report = self
-            .synthesize_findings(session_id, user_id, &trace_id, query, findings)
+            .synthesize_findings(
+                session_id,
+                &trace_id,
+                query,
+                &plan.goals,
+                limits.style,
+                findings,
+                sections,
+            )
             .await?;
 
+        // Check every [src-…] marker in the report against the fetched sources.
+        let cited = cited_source_ids(&report);
+        for source in &mut sources {
+            source.cited = cited.contains(&source.id);
+        }
+        let unresolved: Vec<&String> = cited
+            .iter()
+            .filter(|id| !sources.iter().any(|s| &s.id == *id))
+            .collect();
+        if !unresolved.is_empty() {
+            tracing::warn!(trace_id, ?unresolved, "Report cites unknown sources");
+        }
+
         self.emit_audit(
             session_id,
             &trace_id,
             EventType::ReportGenerated,
-            serde_json::json!({
-                 "report_len": report.len(),
-                 "status": "COMPLETED"
-            }),
+            ReportGeneratedPayload {
+                report_len: report.len(),
+                citations: sources.iter().filter(|s| s.cited).count(),
+                sources: sources.len(),
+                unresolved_citations: unresolved.iter().map(|id| id.to_string()).collect(),
+                status: "COMPLETED".to_string(),
+            },
         );
 
-        Ok(report)
+        // M10.3: Store in Knowledge Base, tagged for later retrieval
+        let mut tags = vec![
+            "research".to_string(),
+            format!("style:{}", style_name(limits.style)),
+            format!("session:{}", session_id),
+        ];
+        tags.extend(options.tags.iter().map(|t| t.trim().to_string()));
+        for source in sources.iter().filter(|s| s.cited) {
+            tags.push(format!("source:{}", source.context));
+        }
+        let mut seen = HashSet::new();
+        tags.retain(|t| !t.is_empty() && seen.insert(t.clone()));
+
+        let entry = KnowledgeEntry {
+            id: Uuid::new_v4().to_string(),
+            summary: report.clone(),
+            source_task: query.to_string(),
+            user_id: user_id.to_string(),
+            session_id: session_id.to_string(),
+            embedding: vec![0.0; 1536], // Mock embedding for now, real systems would call an embedding model
+            tags,
+            created_at: Utc::now().timestamp(),
+            content_hash: None,
+            source_url: None,
+            refreshed_at: None,
+            hits: 0,
+        };
+        self.knowledge_store.store(entry).await?;
+        self.ingest_sources(
+            session_id,
+            user_id,
+            query,
+            &options.tags,
+            &sources,
+            &excerpts,
+        )
+        .await;
+        self.record_coverage().await;
+
+        let citations = sources.iter().map(SourceSnapshot::citation).collect();
+        self.sources.insert(session_id.to_string(), sources);
+
+        Ok(AgentResult::CitedText {
+            text: report,
+            citations,
+        })
     }
 
     async fn plan_research(
@@ -213,7 +473,9 @@ impl ResearchOrchestrator {
             session_id,
             trace_id,
             EventType::PlanProposed,
-            serde_json::json!({"query": query}),
+            PlanProposedPayload {
+                query: query.to_string(),
+            },
         );
 
         // Use Rig for planning (M10.1)
@@ -241,7 +503,7 @@ impl ResearchOrchestrator {
         let p = self.policy.read().await;
         for domain in &plan.candidate_domains {
             // Ensure we handle the Result from check()
-            match p.check(domain) {
+            match p.check(&seed_url(domain)) {
                 Ok(NetworkDecision::Denied(reason)) => return NetworkDecision::Denied(reason),
                 Err(e) => return NetworkDecision::Denied(format!("Invalid URL: {}", e)),
                 _ => {}
@@ -276,203 +538,496 @@ impl ResearchOrchestrator {
         session_id: &str,
         trace_id: &str,
         plan: &ResearchPlan,
-    ) -> Result<Vec<String>> {
+        limits: &ResearchLimits,
+    ) -> Result<(Vec<String>, Vec<SourceSnapshot>)> {
         let mut results = Vec::new();
-        // Client for fetch_with_policy
-        let client = reqwest::Client::builder()
-            .user_agent("MultiAgent-Research/1.0")
-            .redirect(reqwest::redirect::Policy::none()) // Important: manual redirect handling
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .map_err(|e| Error::internal(format!("Failed to build HTTP client: {}", e)))?;
+        let mut citations: Vec<SourceSnapshot> = Vec::new();
+        let client = research_client()?;
 
-        for domain in &plan.candidate_domains {
-            let url_str = if domain.starts_with("http") {
-                domain.clone()
-            } else {
-                format!("https://{}", domain)
-            };
+        let started = Instant::now();
+        let deadline = started + limits.time_budget;
+        let max_sources = plan.max_pages.min(limits.max_sources) as usize;
 
-            let url = match url::Url::parse(&url_str) {
-                Ok(u) => u,
-                Err(e) => {
-                    tracing::warn!("Skipping invalid URL {}: {}", url_str, e);
-                    continue;
+        // Breadth-first crawl: seed pages first, then links found on them.
+        let mut queue = VecDeque::new();
+        let mut visited = HashSet::new();
+        for domain in &plan.candidate_domains {
+            match url::Url::parse(&seed_url(domain)) {
+                Ok(url) => {
+                    if visited.insert(url.to_string()) {
+                        queue.push_back((url, 0u32));
+                    }
                 }
+                Err(e) => tracing::warn!("Skipping invalid URL {}: {}", domain, e),
+            }
+        }
+
+        while let Some((url, depth)) = queue.pop_front() {
+            if citations.len() >= max_sources {
+                break;
+            }
+            if Instant::now() >= deadline {
+                self.emit_progress(
+                    session_id,
+                    trace_id,
+                    ResearchSourceProgressPayload {
+                        status: "TIME_BUDGET_EXHAUSTED".to_string(),
+                        sources_fetched: citations.len(),
+                        url: None,
+                        depth: None,
+                        citation_id: None,
+                        max_sources: None,
+                        sources_pending: Some(queue.len() + 1),
+                        elapsed_ms: None,
+                    },
+                );
+                break;
+            }
+
+            let fetched = match tokio::time::timeout_at(
+                deadline,
+                self.fetch_source(&client, session_id, trace_id, &url),
+            )
+            .await
+            {
+                Ok(fetched) => fetched?,
+                Err(_) => None,
+            };
+            let Some(source) = fetched else {
+                self.emit_progress(
+                    session_id,
+                    trace_id,
+                    ResearchSourceProgressPayload {
+                        status: "FAILED".to_string(),
+                        sources_fetched: citations.len(),
+                        url: Some(url.to_string()),
+                        depth: Some(depth),
+                        citation_id: None,
+                        max_sources: Some(max_sources),
+                        sources_pending: None,
+                        elapsed_ms: None,
+                    },
+                );
+                continue;
             };
 
-            // Emit EGRESS_REQUEST
-            self.emit_audit(
+            // Identical content served under several URLs is cited once.
+            let id = citation_id(&source.hash);
+            if citations.iter().any(|c| c.id == id) {
+                self.emit_progress(
+                    session_id,
+                    trace_id,
+                    ResearchSourceProgressPayload {
+                        status: "DUPLICATE".to_string(),
+                        sources_fetched: citations.len(),
+                        url: Some(url.to_string()),
+                        depth: Some(depth),
+                        citation_id: Some(id.to_string()),
+                        max_sources: Some(max_sources),
+                        sources_pending: None,
+                        elapsed_ms: None,
+                    },
+                );
+                continue;
+            }
+
+            let host = url.host_str().unwrap_or_default().to_string();
+            results.push(format!(
+                "Source [{}]: {}\nURL: {}\nContent:\n{}",
+                id, host, url, source.body
+            ));
+            citations.push(SourceSnapshot {
+                id: id.clone(),
+                url: url.to_string(),
+                context: host,
+                hash: source.hash.clone(),
+                artifact_id: source.artifact_id.to_string(),
+                content_type: source.content_type.clone(),
+                size_bytes: source.size_bytes,
+                depth,
+                retrieved_at: Utc::now().timestamp(),
+                cited: false,
+                verified: None,
+            });
+
+            self.emit_progress(
                 session_id,
                 trace_id,
-                multi_agent_core::events::EventType::EgressRequest,
-                serde_json::json!({
-                    "url": url_str,
-                    "method": "GET"
-                }),
+                ResearchSourceProgressPayload {
+                    status: "FETCHED".to_string(),
+                    sources_fetched: citations.len(),
+                    url: Some(url.to_string()),
+                    depth: Some(depth),
+                    citation_id: Some(id.to_string()),
+                    max_sources: Some(max_sources),
+                    sources_pending: None,
+                    elapsed_ms: Some(started.elapsed().as_millis() as u64),
+                },
             );
 
-            // Use unified egress (fetch_with_policy)
-            // We need to read policy lock
-            let policy_guard = self.policy.read().await;
+            if depth < limits.max_depth && source.content_type.contains("html") {
+                for link in extract_links(&url, &source.body) {
+                    if visited.insert(link.to_string()) {
+                        queue.push_back((link, depth + 1));
+                    }
+                }
+            }
+        }
+
+        Ok((results, citations))
+    }
 
-            use multi_agent_governance::network::fetch_with_policy;
+    /// Fetch one source through the egress policy and persist it as an artifact.
+    ///
+    /// Returns `None` when the fetch fails or the body exceeds the size limit.
+    async fn fetch_source(
+        &self,
+        client: &reqwest::Client,
+        session_id: &str,
+        trace_id: &str,
+        url: &url::Url,
+    ) -> Result<Option<FetchedSource>> {
+        let url_str = url.to_string();
 
-            let response_result = fetch_with_policy(
-                &client,
-                &policy_guard,
-                &self.safety,
-                reqwest::Method::GET,
-                url.clone(),
-                None, // No headers
-                None, // No body
-            )
-            .await;
+        // Emit EGRESS_REQUEST
+        self.emit_audit(
+            session_id,
+            trace_id,
+            EventType::EgressRequest,
+            EgressRequestPayload {
+                url: url_str.clone(),
+                method: "GET".to_string(),
+            },
+        );
 
-            let response = match response_result {
-                Ok(resp) => resp,
-                Err(e) => {
-                    self.emit_audit(
-                        session_id,
-                        trace_id,
-                        multi_agent_core::events::EventType::EgressResult,
-                        serde_json::json!({
-                            "url": url_str,
-                            "status": "ERROR",
-                            "error": e.to_string()
-                        }),
-                    );
-                    continue;
-                }
-            };
+        // Use unified egress (fetch_with_policy)
+        // We need to read policy lock
+        let policy_guard = self.policy.read().await;
 
-            let status = response.status();
-            let headers = response.headers().clone();
-            let content_type = headers
-                .get("content-type")
-                .and_then(|h: &reqwest::header::HeaderValue| h.to_str().ok())
-                .unwrap_or("unknown")
-                .to_string();
-
-            // Read body with safety limit
-            use futures::StreamExt;
-            let mut stream = response.bytes_stream();
-            let mut buffer = Vec::new();
-            let mut total_size = 0;
-            let limit = self.safety.max_download_size_bytes;
-            let mut failed = false;
-
-            while let Some(chunk) = stream.next().await {
-                match chunk {
-                    Ok(bytes) => {
-                        total_size += bytes.len() as u64;
-                        if total_size > limit {
-                            tracing::warn!("Response size exceeded limit for {}", url_str);
-                            failed = true;
-                            break;
-                        }
-                        buffer.extend_from_slice(&bytes);
-                    }
-                    Err(e) => {
-                        tracing::warn!("Failed to read body chunk from {}: {}", url_str, e);
-                        failed = true;
-                        break;
+        use multi_agent_governance::network::fetch_with_policy;
+
+        let response_result = fetch_with_policy(
+            client,
+            &policy_guard,
+            &self.safety,
+            reqwest::Method::GET,
+            url.clone(),
+            None, // No headers
+            None, // No body
+        )
+        .await;
+        drop(policy_guard);
+
+        let response = match response_result {
+            Ok(resp) => resp,
+            Err(e) => {
+                self.emit_audit(
+                    session_id,
+                    trace_id,
+                    EventType::EgressResult,
+                    EgressResultPayload::Failed {
+                        url: url_str.clone(),
+                        status: "ERROR".to_string(),
+                        error: e.to_string(),
+                    },
+                );
+                return Ok(None);
+            }
+        };
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        let content_type = headers
+            .get("content-type")
+            .and_then(|h: &reqwest::header::HeaderValue| h.to_str().ok())
+            .unwrap_or("unknown")
+            .to_string();
+
+        // Read body with safety limit
+        use futures::StreamExt;
+        let mut stream = response.bytes_stream();
+        let mut buffer = Vec::new();
+        let mut total_size = 0;
+        let limit = self.safety.max_download_size_bytes;
+
+        while let Some(chunk) = stream.next().await {
+            match chunk {
+                Ok(bytes) => {
+                    total_size += bytes.len() as u64;
+                    if total_size > limit {
+                        tracing::warn!("Response size exceeded limit for {}", url_str);
+                        return Ok(None);
                     }
+                    buffer.extend_from_slice(&bytes);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to read body chunk from {}: {}", url_str, e);
+                    return Ok(None);
                 }
             }
+        }
 
-            if failed {
-                continue;
-            }
+        let body = String::from_utf8_lossy(&buffer).to_string();
+        let size_bytes = buffer.len() as u64;
 
-            let body = String::from_utf8_lossy(&buffer).to_string();
+        // Hash the raw bytes so the snapshot can be re-verified later
+        let body_hash = sha256_hex(&buffer);
 
-            // Calculate hash for audit
-            let mut hasher = Sha256::new();
-            hasher.update(body.as_bytes());
-            let body_hash = format!("{:x}", hasher.finalize());
+        // Snapshot the source in the ArtifactStore exactly as fetched
+        let ref_id = self
+            .artifact_store
+            .save_with_type(bytes::Bytes::from(buffer), &content_type)
+            .await?;
 
-            // Persist finding to ArtifactStore
-            // In a real system we'd parse HTML to text, but for now we store raw or simple text
-            let ref_id = self
-                .artifact_store
-                .save_with_type(
-                    bytes::Bytes::from(buffer.clone()), // Use buffer directly
-                    &content_type,
+        // Emit EGRESS_RESULT with reference to artifact and metadata
+        self.emit_audit(
+            session_id,
+            trace_id,
+            EventType::EgressResult,
+            EgressResultPayload::Fetched {
+                url: url_str.clone(),
+                status: status.as_u16(),
+                content_type: content_type.clone(),
+                body_len: body.len(),
+                body_hash: body_hash.clone(),
+                artifact_id: ref_id.to_string(),
+            },
+        );
+
+        Ok(Some(FetchedSource {
+            content_type,
+            body,
+            hash: body_hash,
+            artifact_id: ref_id,
+            size_bytes,
+        }))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn synthesize_findings(
+        &self,
+        session_id: &str,
+        trace_id: &str,
+        query: &str,
+        goals: &[String],
+        style: ResearchStyle,
+        findings: Vec<String>,
+        sections: Option<&mpsc::UnboundedSender<ReportSection>>,
+    ) -> Result<String> {
+        // M10.5: Synthesis (Rig based)
+        let preamble = match style {
+            ResearchStyle::Brief => "You are a research analyst. Answer the query in a brief summary of at most three paragraphs based on the provided findings. Support every claim with the ID of the source it comes from, in brackets exactly as given (e.g. [src-1a2b3c4d]). Do not cite anything else.",
+            ResearchStyle::Deep => "You are a research analyst. Consolidate the provided findings into a comprehensive research report. Support every claim with the ID of the source it comes from, in brackets exactly as given (e.g. [src-1a2b3c4d]). Do not cite anything else.",
+        };
+        let client = openai::Client::from_env();
+        let synthesis_agent = client.agent("gpt-4o").preamble(preamble).build();
+
+        let context = findings.join("\n\n---\n\n");
+        let topics = section_topics(query, goals, style);
+
+        // Deep reports are written one section per research goal so that
+        // partial results can be delivered while later sections are pending.
+        let mut report = Vec::with_capacity(topics.len());
+        for (index, topic) in topics.iter().enumerate() {
+            let prompt = if topics.len() == 1 {
+                format!("Research Query: {}\n\nFindings:\n{}", query, context)
+            } else {
+                format!(
+                    "Research Query: {}\n\nWrite only the section of the report covering: {}\n\nFindings:\n{}",
+                    query, topic, context
                 )
-                .await?;
+            };
+
+            let content: String = synthesis_agent
+                .prompt(prompt)
+                .await
+                .map_err(|e| Error::internal(format!("Synthesis error: {}", e)))?;
 
-            // Emit EGRESS_RESULT with reference to artifact and metadata
             self.emit_audit(
                 session_id,
                 trace_id,
-                multi_agent_core::events::EventType::EgressResult,
-                serde_json::json!({
-                    "url": url_str,
-                    "status": status.as_u16(),
-                    "content_type": content_type,
-                    "body_len": body.len(),
-                    "body_hash": body_hash,
-                    "artifact_id": ref_id
-                }),
+                EventType::ReportSectionGenerated,
+                ReportSectionGeneratedPayload {
+                    index,
+                    title: topic.clone(),
+                    sections: topics.len(),
+                    content_len: content.len(),
+                },
             );
-
-            // Use simplified content for the results passed to synthesis
-            results.push(format!(
-                "Source: {}\nURL: {}\nContent:\n{}",
-                domain, url_str, body
-            ));
+            let section = ReportSection {
+                index,
+                title: topic.clone(),
+                content,
+            };
+            if let Some(tx) = sections {
+                let _ = tx.send(section.clone());
+            }
+            report.push(section);
         }
 
-        Ok(results)
+        Ok(assemble_report(&report))
     }
 
-    async fn synthesize_findings(
+    /// Store every fetched source as its own knowledge entry so repeated
+    /// fetches of the same page are deduplicated and can be re-crawled.
+    async fn ingest_sources(
         &self,
         session_id: &str,
         user_id: &str,
-        _trace_id: &str,
         query: &str,
-        findings: Vec<String>,
-    ) -> Result<String> {
-        // M10.5: Synthesis (Rig based)
-        let client = openai::Client::from_env();
-        let synthesis_agent = client.agent("gpt-4o")
-            .preamble("You are a research analyst. Consolidate the provided findings into a comprehensive research report.")
-            .build();
+        topics: &[String],
+        sources: &[SourceSnapshot],
+        excerpts: &[String],
+    ) {
+        let now = Utc::now().timestamp();
+        for (source, excerpt) in sources.iter().zip(excerpts) {
+            let mut tags = vec![
+                "research-source".to_string(),
+                format!("source:{}", source.context),
+            ];
+            tags.extend(topics.iter().map(|t| t.trim().to_string()));
+            let mut seen = HashSet::new();
+            tags.retain(|t| !t.is_empty() && seen.insert(t.clone()));
+            let entry = KnowledgeEntry {
+                id: Uuid::new_v4().to_string(),
+                summary: excerpt.clone(),
+                source_task: query.to_string(),
+                user_id: user_id.to_string(),
+                session_id: session_id.to_string(),
+                embedding: vec![0.0; 1536],
+                tags,
+                created_at: now,
+                content_hash: Some(source.hash.clone()),
+                source_url: Some(source.url.clone()),
+                refreshed_at: Some(now),
+                hits: 0,
+            };
+            if let Err(e) = self.knowledge_store.store(entry).await {
+                tracing::warn!(url = %source.url, error = %e, "Failed to store research source");
+            }
+        }
+    }
 
-        let context = findings.join("\n\n---\n\n");
-        let prompt = format!("Research Query: {}\n\nFindings:\n{}", query, context);
+    /// Refresh stale, re-crawlable knowledge sources.
+    ///
+    /// Sources are fetched again through the egress policy; unchanged content
+    /// only has its freshness bumped, changed content replaces the excerpt.
+    pub async fn recrawl_stale(&self, config: &RecrawlConfig) -> Result<RecrawlReport> {
+        let now = Utc::now().timestamp();
+        let older_than = now - config.stale_after_secs as i64;
+        let stale = self
+            .knowledge_store
+            .stale(older_than, config.batch_size)
+            .await?;
+        let client = research_client()?;
+        let trace_id = Uuid::new_v4().to_string();
+        let mut report = RecrawlReport::default();
 
-        let report: String = synthesis_agent
-            .prompt(prompt)
-            .await
-            .map_err(|e| Error::internal(format!("Synthesis error: {}", e)))?;
+        for mut entry in stale.into_iter().filter(|e| e.hits >= config.min_hits) {
+            report.checked += 1;
+            let Some(url) = entry
+                .source_url
+                .as_deref()
+                .and_then(|u| url::Url::parse(u).ok())
+            else {
+                report.failed += 1;
+                continue;
+            };
 
-        // M10.3: Store in Knowledge Base
-        let entry = KnowledgeEntry {
-            id: Uuid::new_v4().to_string(),
-            summary: report.clone(),
-            source_task: query.to_string(),
-            user_id: user_id.to_string(),
-            session_id: session_id.to_string(),
-            embedding: vec![0.0; 1536], // Mock embedding for now, real systems would call an embedding model
-            tags: vec!["research".to_string()],
-            created_at: Utc::now().timestamp(),
-        };
+            let owner =
+                ArtifactOwner::new(Some(entry.user_id.clone()), Some(entry.session_id.clone()));
+            let fetched = owner
+                .scope(self.fetch_source(&client, &entry.session_id, &trace_id, &url))
+                .await;
+            let outcome = match fetched {
+                Ok(Some(source)) => {
+                    if entry.content_hash.as_deref() == Some(source.hash.as_str()) {
+                        report.unchanged += 1;
+                        "unchanged"
+                    } else {
+                        entry.summary = source_excerpt(url.as_str(), &source.body);
+                        entry.content_hash = Some(source.hash);
+                        report.updated += 1;
+                        "updated"
+                    }
+                }
+                Ok(None) | Err(_) => {
+                    report.failed += 1;
+                    "failed"
+                }
+            };
+            metrics::counter!("knowledge_recrawl_total", "outcome" => outcome).increment(1);
+            if outcome == "failed" {
+                continue;
+            }
 
-        self.knowledge_store.store(entry).await?;
+            entry.refreshed_at = Some(Utc::now().timestamp());
+            self.knowledge_store.store(entry).await?;
+        }
 
+        self.record_coverage().await;
+        tracing::info!(trace_id, ?report, "Knowledge re-crawl pass finished");
         Ok(report)
     }
 
+    /// Run [`recrawl_stale`](Self::recrawl_stale) every `interval_secs`.
+    ///
+    /// Returns `None` when re-crawling is disabled.
+    pub fn spawn_recrawl(
+        self: Arc<Self>,
+        config: RecrawlConfig,
+    ) -> Option<tokio::task::JoinHandle<()>> {
+        if !config.enabled {
+            return None;
+        }
+        Some(tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_secs(config.interval_secs.max(60)));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.recrawl_stale(&config).await {
+                    tracing::warn!(error = %e, "Knowledge re-crawl pass failed");
+                }
+            }
+        }))
+    }
+
+    /// Publish knowledge coverage per topic tag as gauges.
+    async fn record_coverage(&self) {
+        match self.knowledge_store.tag_counts().await {
+            Ok(counts) => {
+                for (topic, count) in counts.into_iter().filter(|(t, _)| is_topic_tag(t)) {
+                    metrics::gauge!("knowledge_coverage_entries", "topic" => topic)
+                        .set(count as f64);
+                }
+            }
+            Err(e) => tracing::warn!(error = %e, "Failed to compute knowledge coverage"),
+        }
+    }
+
+    fn emit_progress(
+        &self,
+        session_id: &str,
+        trace_id: &str,
+        payload: ResearchSourceProgressPayload,
+    ) {
+        self.emit_audit(
+            session_id,
+            trace_id,
+            EventType::ResearchSourceProgress,
+            payload,
+        );
+    }
+
     fn emit_audit(
         &self,
         session_id: &str,
         trace_id: &str,
         event_type: EventType,
-        payload: serde_json::Value,
+        payload: impl serde::Serialize,
     ) {
+        let payload = serde_json::to_value(payload).unwrap_or_default();
         let envelope = EventEnvelope::new(event_type, payload)
             .with_session(session_id)
             .with_trace(trace_id);
@@ -485,3 +1040,451 @@ impl ResearchOrchestrator {
         tracing::info!(?envelope, "Audit Event");
     }
 }
+
+fn style_name(style: ResearchStyle) -> &'static str {
+    match style {
+        ResearchStyle::Brief => "brief",
+        ResearchStyle::Deep => "deep",
+    }
+}
+
+/// Report sections to write: one per goal for deep multi-goal reports,
+/// otherwise a single section answering the query.
+fn section_topics(query: &str, goals: &[String], style: ResearchStyle) -> Vec<String> {
+    let goals: Vec<String> = goals
+        .iter()
+        .map(|g| g.trim().to_string())
+        .filter(|g| !g.is_empty())
+        .collect();
+    if style == ResearchStyle::Deep && goals.len() > 1 {
+        goals
+    } else {
+        vec![query.to_string()]
+    }
+}
+
+/// Join sections into the final report; multi-section reports get headings.
+fn assemble_report(sections: &[ReportSection]) -> String {
+    if let [only] = sections {
+        return only.content.trim().to_string();
+    }
+    sections
+        .iter()
+        .map(|s| format!("## {}\n\n{}", s.title, s.content.trim()))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Client for fetch_with_policy.
+fn research_client() -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .user_agent("MultiAgent-Research/1.0")
+        .redirect(reqwest::redirect::Policy::none()) // Important: manual redirect handling
+        .tls_info(true) // Certificate pinning
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .map_err(|e| Error::internal(format!("Failed to build HTTP client: {}", e)))
+}
+
+/// Knowledge-store excerpt of a fetched source, cut on a char boundary.
+fn source_excerpt(url: &str, body: &str) -> String {
+    let excerpt: String = body.chars().take(MAX_SOURCE_EXCERPT_CHARS).collect();
+    format!("URL: {}\n\n{}", url, excerpt)
+}
+
+/// Tags counted towards topic coverage (per-session tags are not topics).
+fn is_topic_tag(tag: &str) -> bool {
+    !tag.starts_with("session:") && !tag.starts_with("style:")
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Stable citation ID for a source, derived from its content hash.
+fn citation_id(hash: &str) -> String {
+    format!("src-{}", &hash[..hash.len().min(8)])
+}
+
+/// Citation IDs referenced as `[src-…]` in a report, in order of first use.
+fn cited_source_ids(report: &str) -> Vec<String> {
+    let mut ids = Vec::new();
+    let mut rest = report;
+    while let Some(start) = rest.find("[src-") {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find(']') else { break };
+        // Markers may group several IDs: [src-aaaa, src-bbbb]
+        for id in rest[..end].split(',').map(str::trim) {
+            if id.starts_with("src-") && !ids.iter().any(|known| known == id) {
+                ids.push(id.to_string());
+            }
+        }
+        rest = &rest[end..];
+    }
+    ids
+}
+
+/// URL fetched for a plan domain (bare domains are fetched over HTTPS).
+fn seed_url(domain: &str) -> String {
+    if domain.starts_with("http") {
+        domain.to_string()
+    } else {
+        format!("https://{}", domain)
+    }
+}
+
+/// Lowercase host for an allowed-domain entry, accepting bare domains or URLs.
+fn normalize_domain(domain: &str) -> String {
+    let domain = domain.trim().to_ascii_lowercase();
+    match url::Url::parse(&domain) {
+        Ok(url) if url.has_host() => url.host_str().unwrap_or_default().to_string(),
+        _ => domain.trim_end_matches('/').to_string(),
+    }
+}
+
+/// Whether `host` is `domain` or one of its subdomains.
+fn host_in_domain(host: &str, domain: &str) -> bool {
+    host == domain || host.ends_with(&format!(".{}", domain))
+}
+
+/// Narrow the LLM's plan to the caller's limits.
+fn apply_limits(plan: &mut ResearchPlan, limits: &ResearchLimits) {
+    if !limits.allowed_domains.is_empty() {
+        plan.candidate_domains.retain(|domain| {
+            let host = normalize_domain(domain);
+            limits
+                .allowed_domains
+                .iter()
+                .any(|allowed| host_in_domain(&host, allowed))
+        });
+    }
+    plan.candidate_domains.truncate(limits.max_sources as usize);
+    plan.max_pages = if plan.max_pages == 0 {
+        limits.max_sources
+    } else {
+        plan.max_pages.min(limits.max_sources)
+    };
+}
+
+/// Same-host HTTP(S) links in an HTML page, resolved against `base`.
+fn extract_links(base: &url::Url, html: &str) -> Vec<url::Url> {
+    let mut links = Vec::new();
+    let lower = html.to_ascii_lowercase();
+    let mut rest = lower.as_str();
+    let mut offset = 0;
+    while let Some(pos) = rest.find("href=") {
+        let start = offset + pos + "href=".len();
+        let value = &html[start..];
+        let (quote, value) = match value.chars().next() {
+            Some(q @ ('"' | '\'')) => (Some(q), &value[1..]),
+            _ => (None, value),
+        };
+        let end = match quote {
+            Some(q) => value.find(q),
+            None => value.find(|c: char| c.is_whitespace() || c == '>'),
+        }
+        .unwrap_or(value.len());
+        if let Ok(mut link) = base.join(value[..end].trim()) {
+            link.set_fragment(None);
+            if matches!(link.scheme(), "http" | "https") && link.host_str() == base.host_str() {
+                links.push(link);
+            }
+        }
+        offset = start;
+        rest = &lower[offset..];
+    }
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_links_keeps_same_host_only() {
+        let base = url::Url::parse("https://docs.example.com/guide/").unwrap();
+        let html = r#"<a href="intro.html#top">Intro</a>
+            <A HREF='/api'>API</A>
+            <a href=https://docs.example.com/faq>FAQ</a>
+            <a href="https://other.example.org/">Other</a>
+            <a href="mailto:team@example.com">Mail</a>"#;
+        let links: Vec<String> = extract_links(&base, html)
+            .into_iter()
+            .map(|u| u.to_string())
+            .collect();
+        assert_eq!(
+            links,
+            vec![
+                "https://docs.example.com/guide/intro.html",
+                "https://docs.example.com/api",
+                "https://docs.example.com/faq",
+            ]
+        );
+    }
+
+    fn orchestrator(allow: Vec<String>) -> ResearchOrchestrator {
+        orchestrator_with_knowledge(
+            allow,
+            Arc::new(multi_agent_store::InMemoryKnowledgeStore::new()),
+        )
+    }
+
+    fn orchestrator_with_knowledge(
+        allow: Vec<String>,
+        knowledge_store: Arc<dyn KnowledgeStore>,
+    ) -> ResearchOrchestrator {
+        let policy = Arc::new(RwLock::new(NetworkPolicy::new(allow, vec![], vec![443])));
+        let admin_state = Arc::new(AdminState {
+            audit_store: Arc::new(multi_agent_governance::InMemoryAuditStore::new()),
+            rbac: Arc::new(multi_agent_governance::NoOpRbacConnector),
+            metrics: None,
+            mcp_registry: Arc::new(multi_agent_skills::McpRegistry::new()),
+            openapi_registry: None,
+            providers: Arc::new(RwLock::new(Vec::new())),
+            provider_store: None,
+            secrets: Arc::new(multi_agent_governance::AesGcmSecretsManager::new(None)),
+            privacy_controller: None,
+            artifact_store: None,
+            session_store: None,
+            app_config: multi_agent_core::config::AppConfig::default(),
+            network_policy: policy.clone(),
+            audit_presets: Arc::new(multi_agent_admin::audit_view::AuditPresetStore::new()),
+            audit_anchorer: None,
+            guardrails: Arc::new(multi_agent_governance::RouteGuardrails::default()),
+            tool_analytics: None,
+            endpoint_registry: None,
+            active_client: None,
+            provider_sync: None,
+            model_canary: None,
+        });
+        ResearchOrchestrator::new(
+            admin_state,
+            Arc::new(ChannelApprovalGate::new(
+                multi_agent_core::types::ToolRiskLevel::High,
+            )),
+            policy,
+            None,
+            SafetyConfig::default(),
+            Arc::new(multi_agent_store::InMemoryStore::new()),
+            knowledge_store,
+            None,
+        )
+        .with_budgets(ResearchBudgetConfig {
+            max_sources: 10,
+            max_depth: 1,
+            max_time_budget_secs: 120,
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_resolve_limits_against_budget() {
+        let orchestrator = orchestrator(vec!["*.rust-lang.org".into(), "rust-lang.org".into()]);
+
+        let limits = orchestrator
+            .resolve_limits(&ResearchOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(limits.max_sources, 10);
+        assert_eq!(limits.max_depth, 0);
+        assert_eq!(limits.time_budget, Duration::from_secs(120));
+        assert_eq!(limits.style, ResearchStyle::Deep);
+
+        let limits = orchestrator
+            .resolve_limits(&ResearchOptions {
+                max_sources: Some(3),
+                max_depth: Some(1),
+                time_budget_secs: Some(30),
+                allowed_domains: vec!["https://Doc.Rust-Lang.org/".into()],
+                style: ResearchStyle::Brief,
+                tags: vec![],
+            })
+            .await
+            .unwrap();
+        assert_eq!(limits.allowed_domains, vec!["doc.rust-lang.org"]);
+
+        for options in [
+            ResearchOptions {
+                max_sources: Some(11),
+                ..Default::default()
+            },
+            ResearchOptions {
+                max_depth: Some(2),
+                ..Default::default()
+            },
+            ResearchOptions {
+                time_budget_secs: Some(0),
+                ..Default::default()
+            },
+        ] {
+            let err = orchestrator.resolve_limits(&options).await.unwrap_err();
+            assert!(matches!(err, Error::InvalidRequest(_)), "{:?}", err);
+        }
+
+        let err = orchestrator
+            .resolve_limits(&ResearchOptions {
+                allowed_domains: vec!["evil.example.com".into()],
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Governance(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn test_sections_follow_goals_for_deep_reports() {
+        let goals = vec![
+            "History".to_string(),
+            " ".to_string(),
+            "Adoption".to_string(),
+        ];
+        assert_eq!(
+            section_topics("rust", &goals, ResearchStyle::Deep),
+            vec!["History", "Adoption"]
+        );
+        assert_eq!(
+            section_topics("rust", &goals, ResearchStyle::Brief),
+            vec!["rust"]
+        );
+
+        let sections = vec![
+            ReportSection {
+                index: 0,
+                title: "History".into(),
+                content: "Began in 2006 [src-1]. ".into(),
+            },
+            ReportSection {
+                index: 1,
+                title: "Adoption".into(),
+                content: "Widely used [src-2].".into(),
+            },
+        ];
+        assert_eq!(
+            assemble_report(&sections),
+            "## History\n\nBegan in 2006 [src-1].\n\n## Adoption\n\nWidely used [src-2]."
+        );
+        assert_eq!(assemble_report(&sections[..1]), "Began in 2006 [src-1].");
+    }
+
+    #[test]
+    fn test_cited_source_ids() {
+        let report = "Rust is fast [src-1a2b3c4d]. It is safe [src-1a2b3c4d, src-99aa00bb]; \
+                      see [1] and [src-ffff0000";
+        assert_eq!(
+            cited_source_ids(report),
+            vec!["src-1a2b3c4d", "src-99aa00bb"]
+        );
+        assert_eq!(citation_id(&sha256_hex(b"hello")), "src-2cf24dba");
+    }
+
+    #[tokio::test]
+    async fn test_sources_are_verified_against_snapshots() {
+        let orchestrator = orchestrator(vec![]);
+        let intact = orchestrator
+            .artifact_store
+            .save(bytes::Bytes::from_static(b"hello"))
+            .await
+            .unwrap();
+        let tampered = orchestrator
+            .artifact_store
+            .save(bytes::Bytes::from_static(b"changed"))
+            .await
+            .unwrap();
+        let snapshot = |artifact_id: &RefId| SourceSnapshot {
+            id: citation_id(&sha256_hex(b"hello")),
+            url: "https://example.com/".into(),
+            context: "example.com".into(),
+            hash: sha256_hex(b"hello"),
+            artifact_id: artifact_id.to_string(),
+            content_type: "text/plain".into(),
+            size_bytes: 5,
+            depth: 0,
+            retrieved_at: 0,
+            cited: true,
+            verified: None,
+        };
+        orchestrator
+            .sources
+            .insert("rs-1".into(), vec![snapshot(&intact), snapshot(&tampered)]);
+
+        let sources = orchestrator.sources("rs-1").await.unwrap();
+        assert_eq!(sources[0].verified, Some(true));
+        assert_eq!(sources[1].verified, Some(false));
+        assert!(orchestrator.sources("unknown").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_recrawl_skips_fresh_and_low_value_sources() {
+        let knowledge = Arc::new(multi_agent_store::InMemoryKnowledgeStore::new());
+        let orchestrator = orchestrator_with_knowledge(vec![], knowledge.clone());
+
+        let now = Utc::now().timestamp();
+        for (id, url, refreshed_at, hits) in [
+            ("stale", "http://127.0.0.1:9/page", now - 10_000, 3),
+            ("fresh", "http://127.0.0.1:9/fresh", now, 3),
+            ("cold", "http://127.0.0.1:9/cold", now - 10_000, 0),
+        ] {
+            knowledge
+                .store(KnowledgeEntry {
+                    id: id.into(),
+                    summary: format!("content of {}", id),
+                    source_task: "q".into(),
+                    user_id: "u".into(),
+                    session_id: "s".into(),
+                    embedding: vec![1.0],
+                    tags: vec!["research-source".into()],
+                    created_at: refreshed_at,
+                    content_hash: None,
+                    source_url: Some(url.into()),
+                    refreshed_at: Some(refreshed_at),
+                    hits,
+                })
+                .await
+                .unwrap();
+        }
+
+        let config = RecrawlConfig {
+            stale_after_secs: 3600,
+            min_hits: 1,
+            ..Default::default()
+        };
+        let report = orchestrator.recrawl_stale(&config).await.unwrap();
+        // Only the stale, frequently ingested source is fetched; the fetch is
+        // refused by the egress policy and left for the next pass.
+        assert_eq!(report.checked, 1);
+        assert_eq!(report.failed, 1);
+        assert_eq!(knowledge.stale(now - 3600, 10).await.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_apply_limits_filters_and_caps_plan() {
+        let mut plan = ResearchPlan {
+            user_id: None,
+            goals: vec![],
+            candidate_domains: vec![
+                "https://blog.rust-lang.org".into(),
+                "en.wikipedia.org".into(),
+                "rust-lang.org".into(),
+                "doc.rust-lang.org".into(),
+            ],
+            max_pages: 50,
+            allow_redirects: false,
+            budget_tokens: None,
+        };
+        let limits = ResearchLimits {
+            max_sources: 2,
+            max_depth: 0,
+            time_budget: Duration::from_secs(60),
+            allowed_domains: vec!["rust-lang.org".into()],
+            style: ResearchStyle::Brief,
+        };
+        apply_limits(&mut plan, &limits);
+        assert_eq!(
+            plan.candidate_domains,
+            vec!["https://blog.rust-lang.org", "rust-lang.org"]
+        );
+        assert_eq!(plan.max_pages, 2);
+    }
+}
@@ -0,0 +1,369 @@
+//! Asynchronous research jobs.
+//!
+//! Long research runs should not hold an HTTP request open. A job is submitted,
+//! optionally scheduled for later, and executed on the [`ControllerScheduler`]
+//! so it shares the global concurrency limit with chat missions. Report
+//! sections are published to subscribers as they are written; the final
+//! report is persisted to the knowledge store by the orchestrator.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::{broadcast, mpsc};
+use uuid::Uuid;
+
+use crate::research::ResearchOrchestrator;
+use crate::scheduler::ControllerScheduler;
+use multi_agent_core::types::research::{ReportSection, ResearchOptions};
+use multi_agent_core::types::AgentResult;
+use multi_agent_core::Result;
+
+/// Buffered events per job before slow subscribers start lagging.
+const EVENT_BUFFER: usize = 64;
+
+/// Executes a research run, streaming sections as they are written.
+#[async_trait]
+pub trait ResearchRunner: Send + Sync {
+    async fn run(
+        &self,
+        session_id: &str,
+        user_id: &str,
+        query: &str,
+        options: &ResearchOptions,
+        sections: mpsc::UnboundedSender<ReportSection>,
+    ) -> Result<AgentResult>;
+}
+
+#[async_trait]
+impl ResearchRunner for ResearchOrchestrator {
+    async fn run(
+        &self,
+        session_id: &str,
+        user_id: &str,
+        query: &str,
+        options: &ResearchOptions,
+        sections: mpsc::UnboundedSender<ReportSection>,
+    ) -> Result<AgentResult> {
+        self.run_research_with_sections(session_id, user_id, query, options, Some(&sections))
+            .await
+    }
+}
+
+/// Lifecycle of a research job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResearchJobStatus {
+    /// Waiting for a scheduler slot.
+    Queued,
+    /// Waiting for its `run_at` time.
+    Scheduled,
+    Running,
+    Completed,
+    Failed,
+}
+
+impl ResearchJobStatus {
+    pub fn is_terminal(self) -> bool {
+        matches!(self, Self::Completed | Self::Failed)
+    }
+}
+
+/// Progress event published to job subscribers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResearchJobEvent {
+    Status { status: ResearchJobStatus },
+    Section { section: ReportSection },
+    Completed { result: AgentResult },
+    Failed { error: String },
+}
+
+impl ResearchJobEvent {
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Completed { .. } | Self::Failed { .. })
+    }
+}
+
+/// Snapshot of a research job.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResearchJob {
+    pub id: String,
+    /// Session the research run executes under (used for sources review).
+    pub session_id: String,
+    pub query: String,
+    pub user_id: String,
+    pub status: ResearchJobStatus,
+    /// Sections delivered so far, in order.
+    pub sections: Vec<ReportSection>,
+    pub result: Option<AgentResult>,
+    pub error: Option<String>,
+    pub run_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+struct JobEntry {
+    job: ResearchJob,
+    events: broadcast::Sender<ResearchJobEvent>,
+}
+
+/// Submits research jobs to the controller scheduler and tracks their progress.
+pub struct ResearchJobManager {
+    runner: Arc<dyn ResearchRunner>,
+    scheduler: Arc<ControllerScheduler>,
+    jobs: Arc<DashMap<String, JobEntry>>,
+}
+
+impl ResearchJobManager {
+    pub fn new(runner: Arc<dyn ResearchRunner>, scheduler: Arc<ControllerScheduler>) -> Self {
+        Self {
+            runner,
+            scheduler,
+            jobs: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Submit a research job. It starts immediately, or at `run_at` when given.
+    pub fn submit(
+        &self,
+        query: String,
+        user_id: String,
+        options: ResearchOptions,
+        run_at: Option<DateTime<Utc>>,
+    ) -> ResearchJob {
+        let now = Utc::now();
+        let id = Uuid::new_v4().to_string();
+        let run_at = run_at.filter(|at| *at > now);
+        let job = ResearchJob {
+            id: id.clone(),
+            session_id: format!("job-rs-{}", id),
+            query,
+            user_id,
+            status: if run_at.is_some() {
+                ResearchJobStatus::Scheduled
+            } else {
+                ResearchJobStatus::Queued
+            },
+            sections: Vec::new(),
+            result: None,
+            error: None,
+            run_at,
+            created_at: now,
+            updated_at: now,
+        };
+        let (events, _) = broadcast::channel(EVENT_BUFFER);
+        self.jobs.insert(
+            id.clone(),
+            JobEntry {
+                job: job.clone(),
+                events,
+            },
+        );
+
+        let runner = self.runner.clone();
+        let scheduler = self.scheduler.clone();
+        let jobs = self.jobs.clone();
+        let snapshot = job.clone();
+        tokio::spawn(async move {
+            if let Some(at) = snapshot.run_at {
+                if let Ok(delay) = (at - Utc::now()).to_std() {
+                    tokio::time::sleep(delay).await;
+                }
+                update(&jobs, &snapshot.id, |job| {
+                    job.status = ResearchJobStatus::Queued;
+                    Some(ResearchJobEvent::Status { status: job.status })
+                });
+            }
+
+            let result = scheduler
+                .run(Some(&snapshot.session_id), || async {
+                    update(&jobs, &snapshot.id, |job| {
+                        job.status = ResearchJobStatus::Running;
+                        Some(ResearchJobEvent::Status { status: job.status })
+                    });
+
+                    let (tx, mut rx) = mpsc::unbounded_channel();
+                    let run = runner.run(
+                        &snapshot.session_id,
+                        &snapshot.user_id,
+                        &snapshot.query,
+                        &options,
+                        tx,
+                    );
+                    let forward = async {
+                        while let Some(section) = rx.recv().await {
+                            update(&jobs, &snapshot.id, |job| {
+                                job.sections.push(section.clone());
+                                Some(ResearchJobEvent::Section { section })
+                            });
+                        }
+                    };
+                    let (result, _) = tokio::join!(run, forward);
+                    result
+                })
+                .await;
+
+            update(&jobs, &snapshot.id, |job| match result {
+                Ok(result) => {
+                    job.status = ResearchJobStatus::Completed;
+                    job.result = Some(result.clone());
+                    Some(ResearchJobEvent::Completed { result })
+                }
+                Err(e) => {
+                    tracing::warn!(job_id = %job.id, error = %e, "Research job failed");
+                    job.status = ResearchJobStatus::Failed;
+                    job.error = Some(e.to_string());
+                    Some(ResearchJobEvent::Failed {
+                        error: e.to_string(),
+                    })
+                }
+            });
+        });
+
+        job
+    }
+
+    /// Current snapshot of a job.
+    pub fn get(&self, job_id: &str) -> Option<ResearchJob> {
+        self.jobs.get(job_id).map(|entry| entry.job.clone())
+    }
+
+    /// Snapshot of a job together with a receiver for events after it.
+    ///
+    /// Both are taken under the same lock, so replaying the snapshot and then
+    /// the receiver never skips or duplicates a section.
+    pub fn subscribe(
+        &self,
+        job_id: &str,
+    ) -> Option<(ResearchJob, broadcast::Receiver<ResearchJobEvent>)> {
+        self.jobs
+            .get(job_id)
+            .map(|entry| (entry.job.clone(), entry.events.subscribe()))
+    }
+}
+
+/// Apply `change` to a job and publish the event it returns.
+fn update<F>(jobs: &DashMap<String, JobEntry>, job_id: &str, change: F)
+where
+    F: FnOnce(&mut ResearchJob) -> Option<ResearchJobEvent>,
+{
+    if let Some(mut entry) = jobs.get_mut(job_id) {
+        let event = change(&mut entry.job);
+        entry.job.updated_at = Utc::now();
+        if let Some(event) = event {
+            // No subscribers is fine; the snapshot keeps the state.
+            let _ = entry.events.send(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multi_agent_core::Error;
+
+    struct SectionRunner;
+
+    #[async_trait]
+    impl ResearchRunner for SectionRunner {
+        async fn run(
+            &self,
+            _session_id: &str,
+            _user_id: &str,
+            query: &str,
+            _options: &ResearchOptions,
+            sections: mpsc::UnboundedSender<ReportSection>,
+        ) -> Result<AgentResult> {
+            if query == "fail" {
+                return Err(Error::internal("boom"));
+            }
+            for (index, title) in ["Overview", "Details"].into_iter().enumerate() {
+                let _ = sections.send(ReportSection {
+                    index,
+                    title: title.to_string(),
+                    content: format!("{} of {}", title, query),
+                });
+                tokio::task::yield_now().await;
+            }
+            Ok(AgentResult::Text(format!("report on {}", query)))
+        }
+    }
+
+    fn manager() -> ResearchJobManager {
+        ResearchJobManager::new(
+            Arc::new(SectionRunner),
+            Arc::new(ControllerScheduler::new(2)),
+        )
+    }
+
+    async fn drain(mut rx: broadcast::Receiver<ResearchJobEvent>) -> Vec<ResearchJobEvent> {
+        let mut events = Vec::new();
+        while let Ok(event) = rx.recv().await {
+            let done = event.is_terminal();
+            events.push(event);
+            if done {
+                break;
+            }
+        }
+        events
+    }
+
+    #[tokio::test]
+    async fn test_job_streams_sections_then_completes() {
+        let manager = manager();
+        let run_at = Utc::now() + chrono::Duration::milliseconds(50);
+        let job = manager.submit(
+            "rust".into(),
+            "alice".into(),
+            ResearchOptions::default(),
+            Some(run_at),
+        );
+        assert_eq!(job.status, ResearchJobStatus::Scheduled);
+
+        let (snapshot, rx) = manager.subscribe(&job.id).unwrap();
+        assert!(snapshot.sections.is_empty());
+        let events = drain(rx).await;
+
+        let titles: Vec<_> = events
+            .iter()
+            .filter_map(|e| match e {
+                ResearchJobEvent::Section { section } => Some(section.title.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(titles, vec!["Overview", "Details"]);
+        assert!(matches!(
+            events.last(),
+            Some(ResearchJobEvent::Completed { .. })
+        ));
+
+        let done = manager.get(&job.id).unwrap();
+        assert_eq!(done.status, ResearchJobStatus::Completed);
+        assert_eq!(done.sections.len(), 2);
+        assert_eq!(done.result.unwrap().to_text(), "report on rust".to_string());
+    }
+
+    #[tokio::test]
+    async fn test_failed_job_records_error() {
+        let manager = manager();
+        let job = manager.submit(
+            "fail".into(),
+            "alice".into(),
+            ResearchOptions::default(),
+            None,
+        );
+        let (_, rx) = manager.subscribe(&job.id).unwrap();
+        let events = drain(rx).await;
+        assert!(matches!(
+            events.last(),
+            Some(ResearchJobEvent::Failed { .. })
+        ));
+
+        let failed = manager.get(&job.id).unwrap();
+        assert_eq!(failed.status, ResearchJobStatus::Failed);
+        assert!(failed.error.unwrap().contains("boom"));
+        assert!(manager.get("missing").is_none());
+    }
+}
@@ -275,7 +275,7 @@ impl DefaultRouter {
             return Err("llm_low_confidence");
         }
 
-        let user_id = request.metadata.user_id.clone();
+        let user_id = request.context.user_id.clone();
         match decision.intent_type.as_str() {
             "fast_action" => {
                 let tool_name = decision.tool_name.ok_or("llm_missing_tool_name")?;
@@ -308,6 +308,8 @@ impl DefaultRouter {
                     context_summary: request.content.clone(),
                     visual_refs: request.refs.iter().map(|r| r.0.clone()).collect(),
                     user_id,
+                    workspace_id: request.context.workspace_id.clone(),
+                    dry_run: false,
                 },
                 serde_json::json!({
                     "routing": {
@@ -323,7 +325,7 @@ impl DefaultRouter {
 
     fn classify_with_rules(&self, request: &NormalizedRequest) -> UserIntent {
         let content = &request.content;
-        let user_id = request.metadata.user_id.clone();
+        let user_id = request.context.user_id.clone();
 
         if !request.refs.is_empty() {
             return UserIntent::ComplexMission {
@@ -331,6 +333,8 @@ impl DefaultRouter {
                 context_summary: content.clone(),
                 visual_refs: request.refs.iter().map(|r| r.0.clone()).collect(),
                 user_id,
+                workspace_id: request.context.workspace_id.clone(),
+                dry_run: false,
             };
         }
 
@@ -340,6 +344,8 @@ impl DefaultRouter {
                 context_summary: content.clone(),
                 visual_refs: Vec::new(),
                 user_id,
+                workspace_id: request.context.workspace_id.clone(),
+                dry_run: false,
             };
         }
 
@@ -356,14 +362,16 @@ impl DefaultRouter {
             context_summary: content.clone(),
             visual_refs: Vec::new(),
             user_id,
+            workspace_id: request.context.workspace_id.clone(),
+            dry_run: false,
         }
     }
 
     fn routing_context_from_request(request: &NormalizedRequest) -> RoutingContext {
         RoutingContext {
-            channel: request.metadata.custom.get("channel").cloned(),
-            account: request.metadata.custom.get("account").cloned(),
-            peer: request.metadata.custom.get("peer").cloned(),
+            channel: request.context.channel.clone(),
+            account: request.context.account.clone(),
+            peer: request.context.peer.clone(),
         }
     }
 
@@ -381,9 +389,9 @@ impl DefaultRouter {
     ) -> Option<(UserIntent, serde_json::Value)> {
         let context = Self::routing_context_from_request(request);
         let requested_channel = request
-            .metadata
-            .custom
-            .get("routing_channel")
+            .context
+            .routing_channel
+            .as_ref()
             .map(|v| v.to_ascii_lowercase());
         let decision = if let Some(store) = &self.routing_policy_store {
             match requested_channel.as_deref() {
@@ -404,7 +412,7 @@ impl DefaultRouter {
                 .as_ref()
                 .and_then(|policy| policy.resolve(&context))
         }?;
-        let user_id = request.metadata.user_id.clone();
+        let user_id = request.context.user_id.clone();
 
         let intent = match decision.target {
             RouteTarget::FastAction { tool_name } => UserIntent::FastAction {
@@ -417,6 +425,8 @@ impl DefaultRouter {
                 context_summary: request.content.clone(),
                 visual_refs: request.refs.iter().map(|r| r.0.clone()).collect(),
                 user_id,
+                workspace_id: request.context.workspace_id.clone(),
+                dry_run: false,
             },
         };
 
@@ -455,7 +465,7 @@ impl IntentRouter for DefaultRouter {
         tracing::debug!(
             trace_id = %request.trace_id,
             content_length = request.content.len(),
-            user_id = ?request.metadata.user_id,
+            user_id = ?request.context.user_id,
             "Classifying intent"
         );
 
@@ -745,10 +755,7 @@ mod tests {
             .with_llm_classifier(llm, registry)
             .with_routing_policy(policy);
         let mut request = NormalizedRequest::text("please help");
-        request
-            .metadata
-            .custom
-            .insert("channel".to_string(), "support".to_string());
+        request.context.channel = Some("support".to_string());
 
         let (intent, diagnostics) = router.classify_detailed(&request).await.unwrap();
         match intent {
@@ -772,10 +779,7 @@ mod tests {
         let router = DefaultRouter::new().with_routing_policy(policy);
 
         let mut request = NormalizedRequest::text("search docs");
-        request
-            .metadata
-            .custom
-            .insert("peer".to_string(), "vip-user".to_string());
+        request.context.peer = Some("vip-user".to_string());
 
         let (intent, diagnostics) = router.classify_detailed(&request).await.unwrap();
         match intent {
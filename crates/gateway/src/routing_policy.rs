@@ -1,5 +1,7 @@
+use multi_agent_governance::{AuditContext, AuditEntry, AuditOutcome, AuditStore};
 use semver::Version;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -168,7 +170,7 @@ pub struct RoutingPolicyRelease {
     pub rules: Vec<RoutingRule>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum RoutingPolicyChannel {
     Canary,
@@ -176,11 +178,38 @@ pub enum RoutingPolicyChannel {
     Stable,
 }
 
+/// How often a rule of an active release matched live traffic since the
+/// store was created.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RuleHitStats {
+    pub rule_id: String,
+    pub version: String,
+    pub channel: RoutingPolicyChannel,
+    pub hits: u64,
+    /// Unix timestamp of the latest match.
+    pub last_hit_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct RuleHits {
+    hits: u64,
+    last_hit_at: Option<i64>,
+}
+
+/// Versioned routing policy releases on a stable and a canary channel.
+///
+/// Every publish, promotion and rollback is recorded in the audit store set
+/// with [`RoutingPolicyStore::set_audit_store`], and every rule match is
+/// counted in `routing_policy_rule_hits_total` so rules that no longer match
+/// any traffic can be retired.
 pub struct RoutingPolicyStore {
     active_stable: RwLock<Option<RoutingPolicyRelease>>,
     active_canary: RwLock<Option<RoutingPolicyRelease>>,
     history: RwLock<Vec<RoutingPolicyRelease>>,
     persistence_path: Option<PathBuf>,
+    audit_store: std::sync::RwLock<Option<Arc<dyn AuditStore>>>,
+    /// Keyed by (channel, version, rule id).
+    hits: std::sync::Mutex<HashMap<(RoutingPolicyChannel, String, String), RuleHits>>,
 }
 
 impl Default for RoutingPolicyStore {
@@ -196,16 +225,16 @@ impl RoutingPolicyStore {
             active_canary: RwLock::new(None),
             history: RwLock::new(Vec::new()),
             persistence_path: None,
+            audit_store: std::sync::RwLock::new(None),
+            hits: std::sync::Mutex::new(HashMap::new()),
         }
     }
 
     pub fn new_persistent(path: impl AsRef<Path>) -> multi_agent_core::Result<Self> {
         let path = path.as_ref().to_path_buf();
         let mut store = Self {
-            active_stable: RwLock::new(None),
-            active_canary: RwLock::new(None),
-            history: RwLock::new(Vec::new()),
             persistence_path: Some(path.clone()),
+            ..Self::new()
         };
         if path.exists() {
             let content = std::fs::read_to_string(&path).map_err(|e| {
@@ -262,7 +291,71 @@ impl RoutingPolicyStore {
         Ok(())
     }
 
-    pub async fn publish(&self, mut release: RoutingPolicyRelease) -> multi_agent_core::Result<()> {
+    /// Record policy mutations in `audit_store`.
+    pub fn set_audit_store(&self, audit_store: Arc<dyn AuditStore>) {
+        *self.audit_store.write().unwrap_or_else(|e| e.into_inner()) = Some(audit_store);
+    }
+
+    async fn audit(
+        &self,
+        action: &str,
+        mut metadata: serde_json::Value,
+        result: &multi_agent_core::Result<()>,
+    ) {
+        let audit_store = self
+            .audit_store
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        let Some(audit_store) = audit_store else {
+            return;
+        };
+        let outcome = match result {
+            Ok(()) => {
+                metadata["active_stable"] =
+                    serde_json::json!(self.active_stable.read().await.clone());
+                metadata["active_canary"] =
+                    serde_json::json!(self.active_canary.read().await.clone());
+                AuditOutcome::Success
+            }
+            Err(e) => {
+                metadata["error"] = serde_json::json!(e.to_string());
+                AuditOutcome::Error(e.to_string())
+            }
+        };
+        let _ = audit_store
+            .log(AuditEntry {
+                id: uuid::Uuid::new_v4().to_string(),
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                user_id: AuditContext::user_or("system"),
+                action: action.to_string(),
+                resource: "routing_policy".to_string(),
+                outcome,
+                metadata: Some(metadata),
+                previous_hash: None,
+                hash: None,
+                trace_id: None,
+                session_id: None,
+            })
+            .await;
+    }
+
+    pub async fn publish(&self, release: RoutingPolicyRelease) -> multi_agent_core::Result<()> {
+        let metadata = serde_json::json!({
+            "version": release.version,
+            "channel": release.channel,
+            "rules": release.rules.len(),
+        });
+        let result = self.apply_publish(release).await;
+        self.audit("ROUTING_POLICY_PUBLISH", metadata, &result)
+            .await;
+        result
+    }
+
+    async fn apply_publish(
+        &self,
+        mut release: RoutingPolicyRelease,
+    ) -> multi_agent_core::Result<()> {
         let version = Version::parse(&release.version).map_err(|e| {
             multi_agent_core::Error::invalid_request(format!("Invalid policy version: {}", e))
         })?;
@@ -309,6 +402,17 @@ impl RoutingPolicyStore {
     }
 
     pub async fn rollback_to(&self, version: &str) -> multi_agent_core::Result<()> {
+        let result = self.apply_rollback(version).await;
+        self.audit(
+            "ROUTING_POLICY_ROLLBACK",
+            serde_json::json!({ "version": version }),
+            &result,
+        )
+        .await;
+        result
+    }
+
+    async fn apply_rollback(&self, version: &str) -> multi_agent_core::Result<()> {
         let history = self.history.read().await;
         let Some(found) = history.iter().find(|r| r.version == version).cloned() else {
             return Err(multi_agent_core::Error::invalid_request(format!(
@@ -335,6 +439,17 @@ impl RoutingPolicyStore {
         &self,
         version: Option<&str>,
     ) -> multi_agent_core::Result<()> {
+        let result = self.apply_promote(version).await;
+        self.audit(
+            "ROUTING_POLICY_PROMOTE",
+            serde_json::json!({ "version": version }),
+            &result,
+        )
+        .await;
+        result
+    }
+
+    async fn apply_promote(&self, version: Option<&str>) -> multi_agent_core::Result<()> {
         let canary = self.active_canary.read().await.clone();
         let Some(mut release) = canary else {
             return Err(multi_agent_core::Error::invalid_request(
@@ -389,8 +504,7 @@ impl RoutingPolicyStore {
 
     pub async fn resolve(&self, context: &RoutingContext) -> Option<RoutingDecision> {
         let active = self.active_release().await?;
-        let engine = RoutingPolicyEngine::new(active.rules);
-        engine.resolve(context)
+        self.resolve_in(active, context)
     }
 
     pub async fn resolve_for_channel(
@@ -399,8 +513,67 @@ impl RoutingPolicyStore {
         channel: RoutingPolicyChannel,
     ) -> Option<RoutingDecision> {
         let active = self.active_release_for_channel(channel).await?;
-        let engine = RoutingPolicyEngine::new(active.rules);
-        engine.resolve(context)
+        self.resolve_in(active, context)
+    }
+
+    fn resolve_in(
+        &self,
+        release: RoutingPolicyRelease,
+        context: &RoutingContext,
+    ) -> Option<RoutingDecision> {
+        let decision = RoutingPolicyEngine::new(release.rules).resolve(context)?;
+        let channel = match release.channel {
+            RoutingPolicyChannel::Stable => "stable",
+            RoutingPolicyChannel::Canary => "canary",
+        };
+        metrics::counter!(
+            "routing_policy_rule_hits_total",
+            "rule_id" => decision.rule_id.clone(),
+            "version" => release.version.clone(),
+            "channel" => channel
+        )
+        .increment(1);
+        let mut hits = self.hits.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = hits
+            .entry((release.channel, release.version, decision.rule_id.clone()))
+            .or_default();
+        entry.hits += 1;
+        entry.last_hit_at = Some(chrono::Utc::now().timestamp());
+        Some(decision)
+    }
+
+    /// Hit counts of every rule in the active releases, least used first.
+    pub async fn rule_hits(&self) -> Vec<RuleHitStats> {
+        let (stable, canary) = self.active_channels().await;
+        let hits = self.hits.lock().unwrap_or_else(|e| e.into_inner());
+        let mut stats: Vec<RuleHitStats> = stable
+            .into_iter()
+            .chain(canary)
+            .flat_map(|release| {
+                release
+                    .rules
+                    .iter()
+                    .map(|rule| {
+                        let key = (release.channel, release.version.clone(), rule.id.clone());
+                        let counted = hits.get(&key).copied().unwrap_or_default();
+                        RuleHitStats {
+                            rule_id: rule.id.clone(),
+                            version: release.version.clone(),
+                            channel: release.channel,
+                            hits: counted.hits,
+                            last_hit_at: counted.last_hit_at,
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        stats.sort_by(|a, b| {
+            a.hits
+                .cmp(&b.hits)
+                .then_with(|| a.last_hit_at.cmp(&b.last_hit_at))
+                .then_with(|| a.rule_id.cmp(&b.rule_id))
+        });
+        stats
     }
 
     pub async fn simulate_active(
@@ -525,4 +698,51 @@ mod tests {
             .expect("stable");
         assert_eq!(stable.version, "1.2.0");
     }
+
+    #[tokio::test]
+    async fn test_mutations_are_audited_and_hits_counted() {
+        use multi_agent_governance::{AuditFilter, InMemoryAuditStore};
+
+        let audit_store = Arc::new(InMemoryAuditStore::new());
+        let store = RoutingPolicyStore::new();
+        store.set_audit_store(audit_store.clone());
+        let release = RoutingPolicyRelease {
+            version: "1.0.0".to_string(),
+            name: None,
+            published_at: 0,
+            channel: RoutingPolicyChannel::Stable,
+            rules: vec![
+                RoutingRule::force_fast("hot", RouteScope::Channel, "slack", "search", 1),
+                RoutingRule::force_fast("stale", RouteScope::Channel, "fax", "search", 1),
+            ],
+        };
+        store.publish(release.clone()).await.expect("publish");
+        assert!(store.publish(release).await.is_err());
+
+        let entries = audit_store
+            .query(AuditFilter {
+                action: Some("ROUTING_POLICY_PUBLISH".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries
+            .iter()
+            .any(|e| matches!(e.outcome, AuditOutcome::Error(_))));
+
+        let context = RoutingContext {
+            channel: Some("slack".to_string()),
+            ..Default::default()
+        };
+        store.resolve(&context).await.expect("match");
+        store.resolve(&context).await.expect("match");
+
+        let hits = store.rule_hits().await;
+        assert_eq!(hits[0].rule_id, "stale");
+        assert_eq!(hits[0].hits, 0);
+        assert_eq!(hits[1].rule_id, "hot");
+        assert_eq!(hits[1].hits, 2);
+        assert!(hits[1].last_hit_at.is_some());
+    }
 }
@@ -1,14 +1,84 @@
 use dashmap::DashMap;
+use std::collections::HashMap;
 use std::future::Future;
 use std::sync::Arc;
-use tokio::sync::{Mutex, Semaphore};
+use std::time::Duration;
+use tokio::sync::{Mutex, Notify, Semaphore};
+use tokio::time::Instant;
+
+use multi_agent_core::config::MissionConcurrencyConfig;
+use multi_agent_core::{Error, Result};
 
 /// Dual-lane scheduler for controller execution.
 /// - Global lane: limits total concurrent executions.
 /// - Session lane: serializes executions per session ID.
+///
+/// Missions are additionally admitted against per-user and per-workspace
+/// limits with [`ControllerScheduler::admit`].
 pub struct ControllerScheduler {
     global: Arc<Semaphore>,
     session_lanes: DashMap<String, Arc<Mutex<()>>>,
+    missions: Arc<MissionSlots>,
+}
+
+/// Running missions per `user:<id>` and `workspace:<id>` key.
+#[derive(Default)]
+struct MissionSlots {
+    active: std::sync::Mutex<HashMap<String, usize>>,
+    released: Notify,
+}
+
+/// Slot held by a running mission; released on drop.
+pub struct MissionPermit {
+    slots: Arc<MissionSlots>,
+    keys: Vec<String>,
+}
+
+impl Drop for MissionPermit {
+    fn drop(&mut self) {
+        let mut active = self.slots.active.lock().unwrap();
+        for key in &self.keys {
+            if let Some(count) = active.get_mut(key) {
+                *count -= 1;
+                if *count == 0 {
+                    active.remove(key);
+                }
+            }
+        }
+        drop(active);
+        self.slots.released.notify_waiters();
+    }
+}
+
+impl MissionSlots {
+    /// Take a slot under every limit, or report the first one that is full.
+    fn try_acquire(self: &Arc<Self>, limits: &[(&str, &str, usize)]) -> Result<MissionPermit> {
+        let mut active = self.active.lock().unwrap();
+        for (scope, key, limit) in limits {
+            let running = active
+                .get(&format!("{}:{}", scope, key))
+                .copied()
+                .unwrap_or(0);
+            if *limit > 0 && running >= *limit {
+                return Err(Error::ConcurrencyLimit {
+                    scope: scope.to_string(),
+                    key: key.to_string(),
+                    limit: *limit,
+                });
+            }
+        }
+        let keys: Vec<String> = limits
+            .iter()
+            .map(|(scope, key, _)| format!("{}:{}", scope, key))
+            .collect();
+        for key in &keys {
+            *active.entry(key.clone()).or_default() += 1;
+        }
+        Ok(MissionPermit {
+            slots: self.clone(),
+            keys,
+        })
+    }
 }
 
 impl ControllerScheduler {
@@ -16,6 +86,52 @@ impl ControllerScheduler {
         Self {
             global: Arc::new(Semaphore::new(global_limit.max(1))),
             session_lanes: DashMap::new(),
+            missions: Arc::new(MissionSlots::default()),
+        }
+    }
+
+    /// Admit a mission for `user_id` in `workspace_id`.
+    ///
+    /// When a limit is reached the call waits for a running mission to finish,
+    /// up to `queue_timeout_secs`, and then fails with
+    /// [`Error::ConcurrencyLimit`].
+    pub async fn admit(
+        &self,
+        config: &MissionConcurrencyConfig,
+        user_id: &str,
+        workspace_id: &str,
+    ) -> Result<MissionPermit> {
+        let limits = [
+            ("user", user_id, config.max_per_user),
+            (
+                "workspace",
+                workspace_id,
+                config.workspace_limit(workspace_id),
+            ),
+        ];
+        let deadline = Instant::now() + Duration::from_secs(config.queue_timeout_secs);
+        let mut queued = false;
+        loop {
+            // Register for wake-ups before checking so a release is not missed
+            let released = self.missions.released.notified();
+            tokio::pin!(released);
+            released.as_mut().enable();
+
+            let error = match self.missions.try_acquire(&limits) {
+                Ok(permit) => return Ok(permit),
+                Err(e) => e,
+            };
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() || tokio::time::timeout(remaining, released).await.is_err() {
+                metrics::counter!("missions_rejected_total").increment(1);
+                tracing::warn!(user = %user_id, workspace = %workspace_id, error = %error, "Mission rejected");
+                return Err(error);
+            }
+            if !queued {
+                queued = true;
+                metrics::counter!("missions_queued_total").increment(1);
+                tracing::info!(user = %user_id, workspace = %workspace_id, "Mission queued for a concurrency slot");
+            }
         }
     }
 
@@ -44,3 +160,62 @@ impl Default for ControllerScheduler {
         Self::new(32)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(queue_timeout_secs: u64) -> MissionConcurrencyConfig {
+        MissionConcurrencyConfig {
+            max_per_user: 1,
+            max_per_workspace: 2,
+            workspaces: HashMap::from([("big".to_string(), 3)]),
+            queue_timeout_secs,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_admit_rejects_over_limit() {
+        let scheduler = ControllerScheduler::default();
+        let config = config(0);
+
+        let alice = scheduler.admit(&config, "alice", "w1").await.unwrap();
+        let err = scheduler.admit(&config, "alice", "w2").await.err().unwrap();
+        assert!(
+            matches!(&err, Error::ConcurrencyLimit { scope, limit: 1, .. } if scope == "user"),
+            "{}",
+            err
+        );
+
+        let _bob = scheduler.admit(&config, "bob", "w1").await.unwrap();
+        let err = scheduler.admit(&config, "carol", "w1").await.err().unwrap();
+        assert!(
+            matches!(&err, Error::ConcurrencyLimit { scope, key, .. } if scope == "workspace" && key == "w1")
+        );
+        // Overrides raise the limit for one workspace
+        let _c1 = scheduler.admit(&config, "carol", "big").await.unwrap();
+
+        drop(alice);
+        assert!(scheduler.admit(&config, "alice", "w2").await.is_ok());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_admit_queues_until_released() {
+        let scheduler = Arc::new(ControllerScheduler::default());
+        let config = config(5);
+        let first = scheduler.admit(&config, "alice", "w1").await.unwrap();
+
+        let waiting = {
+            let scheduler = scheduler.clone();
+            let config = config.clone();
+            tokio::spawn(async move { scheduler.admit(&config, "alice", "w1").await.is_ok() })
+        };
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        drop(first);
+        assert!(waiting.await.unwrap());
+
+        // Nothing is released this time: the wait times out
+        let _held = scheduler.admit(&config, "alice", "w1").await.unwrap();
+        assert!(scheduler.admit(&config, "alice", "w1").await.is_err());
+    }
+}
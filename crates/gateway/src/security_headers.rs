@@ -0,0 +1,233 @@
+//! Security headers and CSRF protection.
+//!
+//! Every response gets `Content-Security-Policy`, `X-Frame-Options`,
+//! `Referrer-Policy` and `X-Content-Type-Options` from
+//! `gateway.security_headers`, plus `Strict-Transport-Security` when the
+//! gateway terminates TLS. Headers a handler set itself are kept.
+//!
+//! Browsers attach the dashboard's `admin_token` cookie to cross-site requests,
+//! so state-changing requests that carry cookies but no `Authorization` or
+//! `x-admin-token` header must come from the gateway's own origin or one of
+//! `allowed_origins`. Only requests with neither `Origin` nor `Referer` are
+//! let through unchecked. Header-authenticated clients are not affected.
+
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+
+use multi_agent_core::config::SecurityHeadersConfig;
+
+/// Headers and CSRF rules resolved when the router is built.
+pub struct SecurityPolicy {
+    headers: Vec<(HeaderName, HeaderValue)>,
+    csrf_protection: bool,
+    allowed_origins: Vec<String>,
+}
+
+impl SecurityPolicy {
+    /// Resolve the policy; `tls` enables HSTS.
+    pub fn new(config: &SecurityHeadersConfig, tls: bool, allowed_origins: &[String]) -> Self {
+        let mut headers = Vec::new();
+        if config.enabled {
+            let mut push = |name: HeaderName, value: &str| {
+                if value.is_empty() {
+                    return;
+                }
+                match HeaderValue::from_str(value) {
+                    Ok(value) => headers.push((name, value)),
+                    Err(_) => {
+                        tracing::warn!(header = %name, "Ignoring invalid security header value")
+                    }
+                }
+            };
+            push(
+                header::CONTENT_SECURITY_POLICY,
+                &config.content_security_policy,
+            );
+            push(header::X_FRAME_OPTIONS, &config.frame_options);
+            push(header::REFERRER_POLICY, &config.referrer_policy);
+            push(header::X_CONTENT_TYPE_OPTIONS, "nosniff");
+            if tls && config.hsts_max_age_secs > 0 {
+                push(
+                    header::STRICT_TRANSPORT_SECURITY,
+                    &format!("max-age={}; includeSubDomains", config.hsts_max_age_secs),
+                );
+            }
+        }
+        Self {
+            headers,
+            csrf_protection: config.csrf_protection,
+            allowed_origins: allowed_origins
+                .iter()
+                .filter(|o| o.as_str() != "*")
+                .map(|o| o.trim_end_matches('/').to_string())
+                .collect(),
+        }
+    }
+
+    /// Whether a cookie-authenticated request may change state.
+    fn origin_allowed(&self, method: &Method, headers: &HeaderMap) -> bool {
+        if !self.csrf_protection
+            || matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+            || !headers.contains_key(header::COOKIE)
+            || headers.contains_key(header::AUTHORIZATION)
+            || headers.contains_key("x-admin-token")
+        {
+            return true;
+        }
+
+        // Non-browser clients send neither header. A browser sends
+        // `Origin: null` from sandboxed frames and some cross-site
+        // redirects, so a present but unusable value is rejected.
+        let Some(value) = headers
+            .get(header::ORIGIN)
+            .or_else(|| headers.get(header::REFERER))
+        else {
+            return true;
+        };
+        let origin = value
+            .to_str()
+            .ok()
+            .and_then(|v| url::Url::parse(v).ok())
+            .map(|url| url.origin())
+            .filter(|origin| origin.is_tuple());
+        let Some(origin) = origin else {
+            return false;
+        };
+        let origin = origin.ascii_serialization();
+
+        let host = headers.get(header::HOST).and_then(|v| v.to_str().ok());
+        let same_origin = host.is_some_and(|host| {
+            origin
+                .split_once("://")
+                .is_some_and(|(_, authority)| authority == host)
+        });
+        same_origin || self.allowed_origins.contains(&origin)
+    }
+}
+
+/// Middleware applying [`SecurityPolicy`] to every request and response.
+pub(crate) async fn security_headers_middleware(
+    State(policy): State<Arc<SecurityPolicy>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if !policy.origin_allowed(req.method(), req.headers()) {
+        tracing::warn!(
+            method = %req.method(),
+            path = %req.uri().path(),
+            "Blocked cross-origin cookie-authenticated request"
+        );
+        return (StatusCode::FORBIDDEN, "Cross-origin request rejected").into_response();
+    }
+
+    let mut response = next.run(req).await;
+    let headers = response.headers_mut();
+    for (name, value) in &policy.headers {
+        if !headers.contains_key(name) {
+            headers.insert(name.clone(), value.clone());
+        }
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request_headers(pairs: &[(HeaderName, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(name.clone(), HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_hsts_only_with_tls() {
+        let config = SecurityHeadersConfig::default();
+        let plain = SecurityPolicy::new(&config, false, &[]);
+        let tls = SecurityPolicy::new(&config, true, &[]);
+        let has_hsts = |p: &SecurityPolicy| {
+            p.headers
+                .iter()
+                .any(|(n, _)| *n == header::STRICT_TRANSPORT_SECURITY)
+        };
+        assert!(!has_hsts(&plain));
+        assert!(has_hsts(&tls));
+
+        let config = SecurityHeadersConfig {
+            frame_options: String::new(),
+            ..SecurityHeadersConfig::default()
+        };
+        let policy = SecurityPolicy::new(&config, false, &[]);
+        assert!(!policy
+            .headers
+            .iter()
+            .any(|(n, _)| *n == header::X_FRAME_OPTIONS));
+    }
+
+    #[test]
+    fn test_csrf_origin_check() {
+        let policy = SecurityPolicy::new(
+            &SecurityHeadersConfig::default(),
+            false,
+            &["https://console.example.com".to_string()],
+        );
+        let cookie = (header::COOKIE, "admin_token=secret");
+        let host = (header::HOST, "gateway.local:3000");
+
+        let cross = request_headers(&[
+            cookie.clone(),
+            host.clone(),
+            (header::ORIGIN, "https://evil.example.com"),
+        ]);
+        assert!(!policy.origin_allowed(&Method::POST, &cross));
+        assert!(policy.origin_allowed(&Method::GET, &cross));
+
+        let same = request_headers(&[
+            cookie.clone(),
+            host.clone(),
+            (header::ORIGIN, "http://gateway.local:3000"),
+        ]);
+        assert!(policy.origin_allowed(&Method::DELETE, &same));
+
+        let listed = request_headers(&[
+            cookie.clone(),
+            host.clone(),
+            (header::REFERER, "https://console.example.com/providers"),
+        ]);
+        assert!(policy.origin_allowed(&Method::PUT, &listed));
+
+        let absent = request_headers(&[cookie.clone(), host.clone()]);
+        assert!(policy.origin_allowed(&Method::POST, &absent));
+        for opaque in ["null", "not a url", "data:text/html,hi"] {
+            let headers =
+                request_headers(&[cookie.clone(), host.clone(), (header::ORIGIN, opaque)]);
+            assert!(
+                !policy.origin_allowed(&Method::POST, &headers),
+                "{}",
+                opaque
+            );
+        }
+        let null_with_referer = request_headers(&[
+            cookie.clone(),
+            host.clone(),
+            (header::ORIGIN, "null"),
+            (header::REFERER, "http://gateway.local:3000/"),
+        ]);
+        assert!(!policy.origin_allowed(&Method::POST, &null_with_referer));
+
+        let bearer = request_headers(&[
+            cookie,
+            host,
+            (header::ORIGIN, "https://evil.example.com"),
+            (header::AUTHORIZATION, "Bearer t"),
+        ]);
+        assert!(policy.origin_allowed(&Method::POST, &bearer));
+    }
+}
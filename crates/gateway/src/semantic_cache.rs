@@ -1,12 +1,16 @@
 //! Semantic cache for high-frequency queries.
+//!
+//! Lookups are counted in `semantic_cache_lookups_total`, labelled by outcome
+//! and by the bucket of the best similarity score seen.
 
 use async_trait::async_trait;
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use multi_agent_core::{
-    traits::{LlmClient, SemanticCache},
+    traits::{CacheEntryInfo, CacheSelector, Erasable, LlmClient, SemanticCache},
     Result,
 };
 
@@ -23,12 +27,51 @@ struct CacheEntry {
     ttl: Duration,
     /// Hit count for analytics.
     hit_count: u64,
+    /// User the response was generated for, if known.
+    owner: Option<String>,
+    workspace_id: String,
+    session_id: String,
+    /// Hash of the normalized query, used to address the entry.
+    query_hash: String,
 }
 
 impl CacheEntry {
     fn is_expired(&self) -> bool {
         self.created_at.elapsed() > self.ttl
     }
+
+    fn info(&self) -> CacheEntryInfo {
+        let age = self.created_at.elapsed();
+        CacheEntryInfo {
+            workspace_id: self.workspace_id.clone(),
+            session_id: self.session_id.clone(),
+            query_hash: self.query_hash.clone(),
+            hit_count: self.hit_count,
+            age_secs: age.as_secs(),
+            expires_in_secs: self.ttl.saturating_sub(age).as_secs(),
+        }
+    }
+}
+
+/// Label for the best similarity score of a lookup.
+fn similarity_bucket(similarity: Option<f32>) -> &'static str {
+    match similarity {
+        None => "none",
+        Some(s) if s >= 0.95 => "0.95-1.00",
+        Some(s) if s >= 0.90 => "0.90-0.95",
+        Some(s) if s >= 0.80 => "0.80-0.90",
+        Some(s) if s >= 0.50 => "0.50-0.80",
+        Some(_) => "0.00-0.50",
+    }
+}
+
+fn record_lookup(outcome: &'static str, bucket: &'static str) {
+    metrics::counter!(
+        "semantic_cache_lookups_total",
+        "outcome" => outcome,
+        "similarity" => bucket
+    )
+    .increment(1);
 }
 
 /// In-memory semantic cache.
@@ -44,6 +87,8 @@ pub struct InMemorySemanticCache {
     threshold: f32,
     /// Default TTL.
     default_ttl: Duration,
+    /// Workspaces that bypass the cache.
+    disabled: DashSet<String>,
 }
 
 impl InMemorySemanticCache {
@@ -54,6 +99,7 @@ impl InMemorySemanticCache {
             llm_client,
             threshold: 0.90, // Higher threshold for semantic match
             default_ttl: Duration::from_secs(3600), // 1 hour
+            disabled: DashSet::new(),
         }
     }
 
@@ -69,13 +115,24 @@ impl InMemorySemanticCache {
         self
     }
 
+    /// Disable caching for the given workspaces.
+    pub fn with_disabled_workspaces(self, workspaces: impl IntoIterator<Item = String>) -> Self {
+        for workspace in workspaces {
+            self.disabled.insert(workspace);
+        }
+        self
+    }
+
+    /// Hash identifying a query in the management API.
+    pub fn query_hash(query: &str) -> String {
+        let normalized = normalize(query);
+        let digest = Sha256::digest(normalized.as_bytes());
+        digest[..8].iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
     /// Normalize a query for caching.
     fn normalize_query(&self, query: &str) -> String {
-        query
-            .to_lowercase()
-            .split_whitespace()
-            .collect::<Vec<_>>()
-            .join(" ")
+        normalize(query)
     }
 
     /// Calculate cosine similarity between two vectors.
@@ -118,6 +175,14 @@ impl InMemorySemanticCache {
     }
 }
 
+fn normalize(query: &str) -> String {
+    query
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Cache statistics.
 #[derive(Debug, Clone)]
 pub struct CacheStats {
@@ -135,6 +200,10 @@ impl SemanticCache for InMemorySemanticCache {
         session_id: &str,
         query: &str,
     ) -> Result<Option<String>> {
+        if self.disabled.contains(workspace_id) {
+            record_lookup("bypass", "none");
+            return Ok(None);
+        }
         let key = self.cache_key(workspace_id, session_id, query);
 
         // 1. Exact match (Fast path)
@@ -146,6 +215,7 @@ impl SemanticCache for InMemorySemanticCache {
                     session = session_id,
                     "Semantic cache exact hit"
                 );
+                record_lookup("hit", similarity_bucket(Some(1.0)));
                 return Ok(Some(entry.response.clone()));
             }
         }
@@ -156,6 +226,7 @@ impl SemanticCache for InMemorySemanticCache {
             Ok(emb) => emb,
             Err(e) => {
                 tracing::warn!("Failed to generate embedding for cache query: {}", e);
+                record_lookup("miss", "none");
                 return Ok(None);
             }
         };
@@ -163,7 +234,7 @@ impl SemanticCache for InMemorySemanticCache {
         // Iterate and find best match - restrict to current workspace/session prefix
         let prefix = format!("{}:{}:", workspace_id, session_id);
         let mut best_match: Option<String> = None;
-        let mut max_similarity = 0.0;
+        let mut max_similarity: Option<f32> = None;
 
         for entry in self.cache.iter() {
             if entry.is_expired() || !entry.key().starts_with(&prefix) {
@@ -172,8 +243,8 @@ impl SemanticCache for InMemorySemanticCache {
 
             if let Some(ref stored_embedding) = entry.value().query_embedding {
                 let sim = self.cosine_similarity(&query_embedding, stored_embedding);
-                if sim > max_similarity {
-                    max_similarity = sim;
+                if max_similarity.is_none_or(|max| sim > max) {
+                    max_similarity = Some(sim);
                     if sim >= self.threshold {
                         best_match = Some(entry.value().response.clone());
                     }
@@ -181,16 +252,19 @@ impl SemanticCache for InMemorySemanticCache {
             }
         }
 
+        let bucket = similarity_bucket(max_similarity);
         if let Some(response) = best_match {
             tracing::debug!(
                 query = query,
                 similarity = max_similarity,
                 "Semantic cache fuzzy hit"
             );
+            record_lookup("hit", bucket);
             return Ok(Some(response));
         }
 
         tracing::debug!(query = query, "Semantic cache miss");
+        record_lookup("miss", bucket);
         Ok(None)
     }
 
@@ -201,6 +275,21 @@ impl SemanticCache for InMemorySemanticCache {
         query: &str,
         response: &str,
     ) -> Result<()> {
+        self.set_for_user(workspace_id, session_id, query, response, None)
+            .await
+    }
+
+    async fn set_for_user(
+        &self,
+        workspace_id: &str,
+        session_id: &str,
+        query: &str,
+        response: &str,
+        user_id: Option<&str>,
+    ) -> Result<()> {
+        if self.disabled.contains(workspace_id) {
+            return Ok(());
+        }
         let key = self.cache_key(workspace_id, session_id, query);
 
         let query_embedding = match self.llm_client.embed(query).await {
@@ -217,6 +306,10 @@ impl SemanticCache for InMemorySemanticCache {
             created_at: Instant::now(),
             ttl: self.default_ttl,
             hit_count: 0,
+            owner: user_id.map(str::to_string),
+            workspace_id: workspace_id.to_string(),
+            session_id: session_id.to_string(),
+            query_hash: Self::query_hash(query),
         };
 
         tracing::debug!(
@@ -248,6 +341,58 @@ impl SemanticCache for InMemorySemanticCache {
         );
         Ok(())
     }
+
+    async fn entries(&self, selector: &CacheSelector) -> Result<Vec<CacheEntryInfo>> {
+        let mut entries: Vec<_> = self
+            .cache
+            .iter()
+            .filter(|entry| {
+                !entry.is_expired()
+                    && selector.matches(&entry.workspace_id, &entry.session_id, &entry.query_hash)
+            })
+            .map(|entry| entry.info())
+            .collect();
+        entries.sort_by_key(|e| e.age_secs);
+        Ok(entries)
+    }
+
+    async fn purge(&self, selector: &CacheSelector) -> Result<usize> {
+        let before = self.cache.len();
+        self.cache.retain(|_, entry| {
+            !selector.matches(&entry.workspace_id, &entry.session_id, &entry.query_hash)
+        });
+        let purged = before.saturating_sub(self.cache.len());
+        metrics::counter!("semantic_cache_purged_total").increment(purged as u64);
+        Ok(purged)
+    }
+
+    async fn set_workspace_enabled(&self, workspace_id: &str, enabled: bool) -> Result<()> {
+        if enabled {
+            self.disabled.remove(workspace_id);
+        } else {
+            self.disabled.insert(workspace_id.to_string());
+            // Answers cached before the switch must not outlive it
+            self.cache
+                .retain(|_, entry| entry.workspace_id != workspace_id);
+        }
+        Ok(())
+    }
+
+    async fn disabled_workspaces(&self) -> Vec<String> {
+        let mut workspaces: Vec<_> = self.disabled.iter().map(|w| w.clone()).collect();
+        workspaces.sort();
+        workspaces
+    }
+}
+
+#[async_trait]
+impl Erasable for InMemorySemanticCache {
+    async fn erase_user(&self, user_id: &str) -> Result<usize> {
+        let before = self.cache.len();
+        self.cache
+            .retain(|_, entry| entry.owner.as_deref() != Some(user_id));
+        Ok(before.saturating_sub(self.cache.len()))
+    }
 }
 
 // Mock LlmClient for testing
@@ -284,4 +429,61 @@ mod tests {
         let miss = cache.get("w2", "s1", "Rust").await.unwrap();
         assert_eq!(miss, None);
     }
+
+    #[tokio::test]
+    async fn test_erase_user() {
+        let cache = InMemorySemanticCache::new(Arc::new(MockLlm));
+
+        cache
+            .set_for_user("w1", "s1", "Rust", "Language", Some("alice"))
+            .await
+            .unwrap();
+        cache
+            .set_for_user("w1", "s2", "Go", "Language", Some("bob"))
+            .await
+            .unwrap();
+
+        assert_eq!(cache.erase_user("alice").await.unwrap(), 1);
+        assert_eq!(cache.get("w1", "s1", "Rust").await.unwrap(), None);
+        assert!(cache.get("w1", "s2", "Go").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_purge_and_workspace_switch() {
+        let cache = InMemorySemanticCache::new(Arc::new(MockLlm));
+        cache.set("w1", "s1", "Rust", "Language").await.unwrap();
+        cache.set("w1", "s2", "Go", "Language").await.unwrap();
+        cache.set("w2", "s1", "Rust", "Language").await.unwrap();
+
+        let hash = InMemorySemanticCache::query_hash("  rust ");
+        let selector = CacheSelector {
+            query_hash: Some(hash.clone()),
+            ..Default::default()
+        };
+        assert_eq!(cache.entries(&selector).await.unwrap().len(), 2);
+        let selector = CacheSelector {
+            workspace_id: Some("w1".into()),
+            query_hash: Some(hash),
+            ..Default::default()
+        };
+        assert_eq!(cache.purge(&selector).await.unwrap(), 1);
+        assert_eq!(cache.get("w1", "s1", "Rust").await.unwrap(), None);
+        assert!(cache.get("w2", "s1", "Rust").await.unwrap().is_some());
+
+        // Disabling drops the workspace's entries and stops new ones
+        cache.set_workspace_enabled("w1", false).await.unwrap();
+        assert_eq!(cache.get("w1", "s2", "Go").await.unwrap(), None);
+        cache.set("w1", "s2", "Go", "Language").await.unwrap();
+        cache.set_workspace_enabled("w1", true).await.unwrap();
+        assert_eq!(cache.get("w1", "s2", "Go").await.unwrap(), None);
+        assert_eq!(cache.disabled_workspaces().await, Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_similarity_buckets() {
+        assert_eq!(similarity_bucket(None), "none");
+        assert_eq!(similarity_bucket(Some(1.0)), "0.95-1.00");
+        assert_eq!(similarity_bucket(Some(0.92)), "0.90-0.95");
+        assert_eq!(similarity_bucket(Some(0.1)), "0.00-0.50");
+    }
 }
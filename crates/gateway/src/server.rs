@@ -6,34 +6,49 @@ use axum::{
         Json, Path, Query, State, WebSocketUpgrade,
     },
     http::{HeaderMap, StatusCode},
-    response::{IntoResponse, Response},
-    routing::{get, post},
-    Router,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::{get, post, put},
+    Extension, Router,
 };
+use futures::StreamExt;
 use schemars::schema_for;
 use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
 use std::sync::Arc;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 use uuid::Uuid;
 
 use crate::idempotency::{IdempotencyLookup, IdempotencyStore};
+use crate::research_jobs::{ResearchJobEvent, ResearchJobManager};
 use crate::routing_policy::{
     RoutingContext, RoutingPolicyChannel, RoutingPolicyEngine, RoutingPolicyRelease,
     RoutingPolicyStore, RoutingRule,
 };
 use crate::scheduler::ControllerScheduler;
+use crate::workspaces::{self, WorkspaceStore};
 use multi_agent_core::{
-    config::TlsConfig,
-    traits::{Controller, IntentRouter, SemanticCache},
+    config::{StateFile, TlsConfig},
+    prompts::{keys as prompt_keys, PromptRegistry},
+    traits::{
+        ArtifactStore, Controller, IntentRouter, KnowledgeStore, SemanticCache, ToolRegistry,
+    },
     types::{
-        AgentResult, ApiEnvelope, ApiErrorBody, ApiErrorCode, ApprovalResponse, NormalizedRequest,
-        RequestContent, RequestMetadata, UserIntent, GATEWAY_CONTRACT_VERSION,
+        AgentResult, AgentStreamEvent, ApiEnvelope, ApiErrorBody, ApiErrorCode, ApprovalResponse,
+        ApprovalScope, ArtifactOwner, NormalizedRequest, RequestContent, RequestContext,
+        UserIntent, GATEWAY_CONTRACT_VERSION,
     },
     Result,
 };
-use multi_agent_governance::approval::ChannelApprovalGate;
-use multi_agent_governance::{AuditEntry, AuditFilter, AuditOutcome};
+use multi_agent_governance::approval::{ChannelApprovalGate, ChannelHumanInput};
+use multi_agent_governance::rbac::{UserContext, UserRoles};
+use multi_agent_governance::{
+    AuditContext, AuditFilter, GuardrailChannel, ModerationAction, ModerationDirection,
+    ModerationOutcome, Moderator, RouteGuardrails,
+};
 
 /// Gateway configuration.
 #[derive(Debug, Clone)]
@@ -50,6 +65,8 @@ pub struct GatewayConfig {
     pub allowed_origins: Vec<String>,
     /// TLS Configuration.
     pub tls: TlsConfig,
+    /// Serve on this Unix domain socket instead of `host:port`.
+    pub unix_socket: Option<String>,
 }
 
 impl Default for GatewayConfig {
@@ -65,7 +82,10 @@ impl Default for GatewayConfig {
                 cert_path: None,
                 key_path: None,
                 ca_path: None,
+                require_client_cert: false,
+                client_identities: Default::default(),
             },
+            unix_socket: None,
         }
     }
 }
@@ -83,8 +103,15 @@ pub struct AppState {
     /// Approval gate for HITL flow (optional).
     /// Approval gate for HITL flow (optional).
     pub approval_gate: Option<Arc<ChannelApprovalGate>>,
+    /// Human-input channel for `ask_user` clarification questions (optional).
+    pub human_input: Option<Arc<ChannelHumanInput>>,
     /// Logs broadcast channel for "Fog of War" UI.
     pub logs_channel: Option<tokio::sync::broadcast::Sender<String>>,
+    /// Event stream shared across replicas via the event bus; the logs
+    /// WebSocket reads this instead of `logs_channel` when set.
+    pub event_stream: Option<tokio::sync::broadcast::Sender<String>>,
+    /// Recent events replayed to log subscribers that resume with `since`.
+    pub log_buffer: Option<Arc<crate::logs::LogBuffer>>,
     /// Policy engine for rule-based risk assessment.
     pub policy_engine: Option<Arc<tokio::sync::RwLock<multi_agent_governance::PolicyEngine>>>,
     /// Admin state for configuration persistence.
@@ -95,12 +122,36 @@ pub struct AppState {
     pub app_config: multi_agent_core::config::AppConfig,
     /// Research orchestrator for P0 workflow.
     pub research_orchestrator: Option<Arc<crate::research::ResearchOrchestrator>>,
+    /// Asynchronous research jobs (set together with the orchestrator).
+    pub research_jobs: Option<Arc<ResearchJobManager>>,
     /// Idempotency store for side-effect endpoints.
     pub idempotency_store: Arc<IdempotencyStore>,
     /// Scheduler for controller execution lanes.
     pub controller_scheduler: Arc<ControllerScheduler>,
     /// Shared versioned routing policy store.
     pub routing_policy_store: Option<Arc<RoutingPolicyStore>>,
+    /// Artifact store backing the artifact retrieval API.
+    pub artifact_store: Option<Arc<dyn ArtifactStore>>,
+    /// Catalog backing the session artifact listing.
+    pub artifact_catalog: Option<Arc<multi_agent_store::ArtifactCatalog>>,
+    /// Knowledge store backing the memory search API.
+    pub knowledge_store: Option<Arc<dyn KnowledgeStore>>,
+    /// Per-channel guardrails for chat, research and webhook input.
+    pub guardrails: Option<Arc<RouteGuardrails>>,
+    /// Sandbox backing the interactive terminal endpoint.
+    pub sandbox_manager: Option<Arc<multi_agent_sandbox::SandboxManager>>,
+    /// Tools available to agents, consulted when validating policy updates.
+    pub tool_registry: Option<Arc<dyn ToolRegistry>>,
+    /// Workspace environment templates and instantiated workspaces.
+    pub workspace_store: Option<Arc<WorkspaceStore>>,
+    /// Elevated tokens of remote administrators.
+    pub elevations: Arc<crate::admin_access::ElevationStore>,
+    /// Feature flags resolved into every chat request's context.
+    pub feature_flags: Option<Arc<multi_agent_governance::FeatureFlagService>>,
+    /// Localized user-facing messages.
+    pub prompts: Arc<PromptRegistry>,
+    /// Content moderation of chat requests and answers.
+    pub moderator: Option<Arc<Moderator>>,
 }
 
 impl AppState {
@@ -142,15 +193,30 @@ impl GatewayServer {
                 controller: None,
                 rate_limiter: None,
                 approval_gate: None,
+                human_input: None,
                 logs_channel: None,
+                event_stream: None,
+                log_buffer: None,
                 policy_engine: None,
                 admin_state: None,
                 plugin_manager: None,
                 app_config: multi_agent_core::config::AppConfig::load().unwrap_or_default(),
                 research_orchestrator: None,
+                research_jobs: None,
                 idempotency_store: Arc::new(IdempotencyStore::new()),
                 controller_scheduler: Arc::new(ControllerScheduler::default()),
                 routing_policy_store: None,
+                artifact_store: None,
+                artifact_catalog: None,
+                knowledge_store: None,
+                guardrails: None,
+                sandbox_manager: None,
+                tool_registry: None,
+                workspace_store: None,
+                elevations: Arc::new(crate::admin_access::ElevationStore::new()),
+                feature_flags: None,
+                prompts: Arc::new(PromptRegistry::default()),
+                moderator: None,
             }),
             metrics_handle: None,
             admin_state: None,
@@ -168,6 +234,14 @@ impl GatewayServer {
         self
     }
 
+    /// Set the tool registry used to validate policy updates.
+    pub fn with_tool_registry(mut self, registry: Arc<dyn ToolRegistry>) -> Self {
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.tool_registry = Some(registry);
+        }
+        self
+    }
+
     /// Set the controller.
     pub fn with_controller(mut self, controller: Arc<dyn Controller>) -> Self {
         if let Some(state) = Arc::get_mut(&mut self.state) {
@@ -200,9 +274,19 @@ impl GatewayServer {
             s.app_config = state.app_config.clone();
             s.admin_state = Some(state);
         }
+        self.link_routing_audit();
         self
     }
 
+    /// Audit routing policy changes in the admin audit store.
+    fn link_routing_audit(&self) {
+        if let (Some(admin_state), Some(store)) =
+            (&self.admin_state, &self.state.routing_policy_store)
+        {
+            store.set_audit_store(admin_state.audit_store.clone());
+        }
+    }
+
     /// Set distributed rate limiter (e.g., Redis-backed).
     pub fn with_rate_limiter(mut self, limiter: Arc<dyn DistributedRateLimiter>) -> Self {
         if let Some(state) = Arc::get_mut(&mut self.state) {
@@ -219,12 +303,24 @@ impl GatewayServer {
         self
     }
 
-    /// Set the research orchestrator.
+    /// Set the human-input channel for clarification questions.
+    pub fn with_human_input(mut self, channel: Arc<ChannelHumanInput>) -> Self {
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.human_input = Some(channel);
+        }
+        self
+    }
+
+    /// Set the research orchestrator and enable asynchronous research jobs.
     pub fn with_research_orchestrator(
         mut self,
         orchestrator: Arc<crate::research::ResearchOrchestrator>,
     ) -> Self {
         if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.research_jobs = Some(Arc::new(ResearchJobManager::new(
+                orchestrator.clone(),
+                state.controller_scheduler.clone(),
+            )));
             state.research_orchestrator = Some(orchestrator);
         }
         self
@@ -238,14 +334,122 @@ impl GatewayServer {
         self
     }
 
+    /// Set the bus-backed event stream served to dashboard log subscribers.
+    pub fn with_event_stream(mut self, sender: tokio::sync::broadcast::Sender<String>) -> Self {
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.event_stream = Some(sender);
+        }
+        self
+    }
+
+    /// Set the buffer that log subscribers resume from.
+    pub fn with_log_buffer(mut self, buffer: Arc<crate::logs::LogBuffer>) -> Self {
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.log_buffer = Some(buffer);
+        }
+        self
+    }
+
     /// Set shared versioned routing policy store.
     pub fn with_routing_policy_store(mut self, store: Arc<RoutingPolicyStore>) -> Self {
         if let Some(state) = Arc::get_mut(&mut self.state) {
             state.routing_policy_store = Some(store);
         }
+        self.link_routing_audit();
+        self
+    }
+
+    /// Set the artifact store served by the artifact retrieval API.
+    pub fn with_artifact_store(mut self, store: Arc<dyn ArtifactStore>) -> Self {
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.artifact_store = Some(store);
+        }
         self
     }
 
+    /// Set the catalog listing each session's artifacts.
+    pub fn with_artifact_catalog(
+        mut self,
+        catalog: Arc<multi_agent_store::ArtifactCatalog>,
+    ) -> Self {
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.artifact_catalog = Some(catalog);
+        }
+        self
+    }
+
+    /// Set the knowledge store backing memory search.
+    pub fn with_knowledge_store(mut self, store: Arc<dyn KnowledgeStore>) -> Self {
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.knowledge_store = Some(store);
+        }
+        self
+    }
+
+    /// Enforce per-channel guardrails on inbound content.
+    pub fn with_guardrails(mut self, guardrails: Arc<RouteGuardrails>) -> Self {
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.guardrails = Some(guardrails);
+        }
+        self
+    }
+
+    /// Set the sandbox manager used by the interactive terminal endpoint.
+    pub fn with_sandbox_manager(
+        mut self,
+        manager: Arc<multi_agent_sandbox::SandboxManager>,
+    ) -> Self {
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.sandbox_manager = Some(manager);
+        }
+        self
+    }
+
+    /// Set the store backing workspace templates.
+    pub fn with_workspace_store(mut self, store: Arc<WorkspaceStore>) -> Self {
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.workspace_store = Some(store);
+        }
+        self
+    }
+
+    /// Set the feature flags resolved for each request.
+    pub fn with_feature_flags(
+        mut self,
+        flags: Arc<multi_agent_governance::FeatureFlagService>,
+    ) -> Self {
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.feature_flags = Some(flags);
+        }
+        self
+    }
+
+    /// Set the catalog localizing user-facing messages.
+    pub fn with_prompts(mut self, prompts: Arc<PromptRegistry>) -> Self {
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.prompts = prompts;
+        }
+        self
+    }
+
+    /// Moderate chat requests and answers.
+    pub fn with_moderator(mut self, moderator: Arc<Moderator>) -> Self {
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.moderator = Some(moderator);
+        }
+        self
+    }
+
+    /// Reconcile the instance with a bootstrap document; see
+    /// [`crate::bootstrap`].
+    pub async fn reconcile(
+        &self,
+        desired: &crate::bootstrap::Bootstrap,
+        apply: bool,
+    ) -> Result<crate::bootstrap::BootstrapPlan> {
+        crate::bootstrap::reconcile(&self.state, desired, apply).await
+    }
+
     /// Build the Axum router.
     pub fn build_router(&self) -> Router {
         // System Routes
@@ -255,6 +459,7 @@ impl GatewayServer {
             .route("/healthz", get(healthz_handler))
             .route("/readyz", get(readyz_handler))
             .route("/schema/gateway", get(gateway_schema_handler))
+            .route("/events/schema", get(event_schema_handler))
             .route(
                 "/metrics",
                 get(move || {
@@ -272,15 +477,53 @@ impl GatewayServer {
         // Agent Routes
         let agent_router = Router::new()
             .route("/chat", post(chat_handler))
+            .route("/chat/stream", post(chat_stream_handler))
             .route("/intent", post(intent_handler))
             .route("/webhook/:event_type", post(webhook_handler))
             .route("/ws/approval", get(approval_ws_handler))
-            .route("/ws/logs", get(logs_ws_handler))
+            .route("/ws/logs", get(crate::logs::logs_ws_handler))
             .route("/approve/:request_id", post(approve_rest_handler))
+            .route("/answer/:request_id", post(answer_rest_handler))
             .route("/onboarding/status", get(onboarding_status_handler))
             .route("/onboarding/setup", post(onboarding_setup_handler))
             .route("/research", post(research_handler))
+            .route("/research/jobs", post(research_job_submit_handler))
+            .route("/research/jobs/:job_id", get(research_job_handler))
+            .route(
+                "/research/jobs/:job_id/events",
+                get(research_job_events_handler),
+            )
+            .route(
+                "/research/:session_id/sources",
+                get(research_sources_handler),
+            )
+            .route(
+                "/artifacts",
+                post(crate::artifacts::upload_artifact_handler),
+            )
+            .route(
+                "/artifacts/:ref_id",
+                get(crate::artifacts::get_artifact_handler),
+            )
+            .route(
+                "/artifacts/:ref_id/url",
+                get(crate::artifacts::get_artifact_url_handler),
+            )
+            .route("/memory/search", get(crate::memory::memory_search_handler))
+            .route(
+                "/sessions/:id/progress",
+                get(crate::sessions::session_progress_handler),
+            )
+            .route(
+                "/sessions/:id/watch",
+                get(crate::sessions::session_watch_handler),
+            )
+            .route(
+                "/sessions/:id/artifacts",
+                get(crate::artifacts::list_session_artifacts_handler),
+            )
             .route("/policy", get(get_policy_handler).put(put_policy_handler))
+            .route("/policy/rules/:rule_id/mode", put(put_rule_mode_handler))
             .route("/plugins", get(get_plugins_handler))
             .route("/plugins/{plugin_id}", get(get_plugin_details_handler))
             .route("/plugins/{plugin_id}/toggle", post(toggle_plugin_handler))
@@ -299,6 +542,7 @@ impl GatewayServer {
             .route("/v1/intent", post(intent_handler))
             .route("/v1/webhook/:event_type", post(webhook_handler))
             .route("/v1/approve/:request_id", post(approve_rest_handler))
+            .route("/v1/answer/:request_id", post(answer_rest_handler))
             .with_state(self.state.clone());
 
         // Admin API
@@ -321,6 +565,7 @@ impl GatewayServer {
                 .route("/rollback", post(admin_routing_rollback_handler))
                 .route("/audits", get(admin_routing_audits_handler))
                 .route("/policies", get(admin_routing_policies_handler))
+                .route("/hits", get(admin_routing_hits_handler))
                 .route_layer(axum::middleware::from_fn_with_state(
                     self.state.clone(),
                     restrict_to_localhost,
@@ -332,10 +577,155 @@ impl GatewayServer {
                 .with_state(self.state.clone());
             router = router.nest("/v1/admin/routing", routing_admin_api);
 
+            let sandbox_admin_api = Router::new()
+                .route("/terminal", get(crate::terminal::sandbox_terminal_handler))
+                .route_layer(axum::middleware::from_fn_with_state(
+                    self.state.clone(),
+                    restrict_to_localhost,
+                ))
+                .route_layer(axum::middleware::from_fn_with_state(
+                    self.state.clone(),
+                    bearer_auth_middleware,
+                ))
+                .with_state(self.state.clone());
+            router = router.nest("/v1/admin/sandbox", sandbox_admin_api);
+
+            let workspace_admin_api = Router::new()
+                .route("/", get(workspaces::list_workspaces_handler))
+                .route(
+                    "/templates",
+                    get(workspaces::list_templates_handler).post(workspaces::put_template_handler),
+                )
+                .route(
+                    "/templates/:name",
+                    get(workspaces::get_template_handler)
+                        .delete(workspaces::delete_template_handler),
+                )
+                .route(
+                    "/templates/:name/instantiate",
+                    post(workspaces::instantiate_template_handler),
+                )
+                .route("/:id", get(workspaces::get_workspace_handler))
+                .route(
+                    "/:id/snapshot",
+                    post(workspaces::snapshot_workspace_handler),
+                )
+                .route(
+                    "/:id/presentation",
+                    put(workspaces::put_presentation_handler),
+                )
+                .route_layer(axum::middleware::from_fn_with_state(
+                    self.state.clone(),
+                    restrict_to_localhost,
+                ))
+                .route_layer(axum::middleware::from_fn_with_state(
+                    self.state.clone(),
+                    bearer_auth_middleware,
+                ))
+                .with_state(self.state.clone());
+            router = router.nest("/v1/admin/workspaces", workspace_admin_api);
+
+            let bootstrap_admin_api = Router::new()
+                .route("/", post(crate::bootstrap::bootstrap_handler))
+                .route_layer(axum::middleware::from_fn_with_state(
+                    self.state.clone(),
+                    restrict_to_localhost,
+                ))
+                .route_layer(axum::middleware::from_fn_with_state(
+                    self.state.clone(),
+                    bearer_auth_middleware,
+                ))
+                .with_state(self.state.clone());
+            router = router.nest("/v1/admin/bootstrap", bootstrap_admin_api);
+
+            let cache_admin_api = Router::new()
+                .route(
+                    "/",
+                    get(crate::cache_admin::list_cache_handler)
+                        .delete(crate::cache_admin::purge_cache_handler),
+                )
+                .route(
+                    "/workspaces/:id",
+                    put(crate::cache_admin::set_workspace_cache_handler),
+                )
+                .route_layer(axum::middleware::from_fn_with_state(
+                    self.state.clone(),
+                    restrict_to_localhost,
+                ))
+                .route_layer(axum::middleware::from_fn_with_state(
+                    self.state.clone(),
+                    bearer_auth_middleware,
+                ))
+                .with_state(self.state.clone());
+            router = router.nest("/v1/admin/cache", cache_admin_api);
+
+            let flags_admin_api = Router::new()
+                .route("/", get(crate::flags_admin::list_flags_handler))
+                .route(
+                    "/:name",
+                    put(crate::flags_admin::set_flag_handler)
+                        .delete(crate::flags_admin::clear_flag_handler),
+                )
+                .route_layer(axum::middleware::from_fn_with_state(
+                    self.state.clone(),
+                    restrict_to_localhost,
+                ))
+                .route_layer(axum::middleware::from_fn_with_state(
+                    self.state.clone(),
+                    bearer_auth_middleware,
+                ))
+                .with_state(self.state.clone());
+            router = router.nest("/v1/admin/flags", flags_admin_api);
+
+            let auth_admin_api = Router::new()
+                .route("/elevate", post(crate::admin_access::elevate_handler))
+                .route_layer(axum::middleware::from_fn_with_state(
+                    self.state.clone(),
+                    crate::admin_access::admin_network_middleware,
+                ))
+                .route_layer(axum::middleware::from_fn_with_state(
+                    self.state.clone(),
+                    bearer_auth_middleware,
+                ))
+                .with_state(self.state.clone());
+            router = router.nest("/v1/admin/auth", auth_admin_api);
+
             // Management Console (Static assets)
             router = router.nest("/console", multi_agent_admin::admin_static_router());
         }
 
+        // Body ceilings come from gateway.body_limits instead of axum's default.
+        router = router
+            .layer(axum::extract::DefaultBodyLimit::disable())
+            .layer(axum::middleware::from_fn_with_state(
+                self.state.clone(),
+                crate::body_limit::body_limit_middleware,
+            ));
+
+        // Test builds only: injected latency holds a route slot like a slow
+        // backend would.
+        #[cfg(feature = "chaos")]
+        if let Some(injector) = multi_agent_core::chaos::FaultInjector::from_config(
+            "http",
+            &self.state.app_config.chaos,
+            &self.state.app_config.chaos.http,
+        ) {
+            tracing::warn!("Chaos mode: injecting faults into HTTP requests");
+            router = router.layer(axum::middleware::from_fn_with_state(
+                injector,
+                crate::chaos::chaos_middleware,
+            ));
+        }
+
+        // Per-route ceilings from gateway.concurrency; excess load is shed.
+        let route_limiter = Arc::new(crate::load_shed::RouteLimiter::new(
+            &self.state.app_config.gateway.concurrency,
+        ));
+        router = router.layer(axum::middleware::from_fn_with_state(
+            route_limiter,
+            crate::load_shed::route_concurrency_middleware,
+        ));
+
         // Apply rate limiting: Distributed (Redis) or Local (Governor)
         if self.state.rate_limiter.is_some() {
             tracing::info!("Using Distributed Rate Limiter (Redis)");
@@ -347,10 +737,11 @@ impl GatewayServer {
             tracing::info!("Using Local Rate Limiter (Tower Governor)");
             use tower_governor::{governor::GovernorConfigBuilder, GovernorLayer};
 
-            // Rate limit: ~120 requests per minute per IP
+            // Rate limit: ~120 requests per minute per client
             let governor_conf = GovernorConfigBuilder::default()
                 .per_second(2) // ~120/min
                 .burst_size(30) // Allow bursts
+                .key_extractor(crate::rate_limit::ClientKeyExtractor)
                 .finish()
                 .expect("Failed to build rate limiter config");
 
@@ -359,6 +750,21 @@ impl GatewayServer {
             };
             router = router.layer(governor_limiter);
         }
+        // Runs before either limiter to resolve who the client is
+        router = router.layer(axum::middleware::from_fn_with_state(
+            self.state.clone(),
+            crate::rate_limit::client_key_middleware,
+        ));
+
+        let security_policy = Arc::new(crate::security_headers::SecurityPolicy::new(
+            &self.state.app_config.gateway.security_headers,
+            self.config.tls.enabled,
+            &self.config.allowed_origins,
+        ));
+        router = router.layer(axum::middleware::from_fn_with_state(
+            security_policy,
+            crate::security_headers::security_headers_middleware,
+        ));
 
         if self.config.enable_cors {
             // CORS: Use configured allowed origins
@@ -392,26 +798,23 @@ impl GatewayServer {
 
     /// Run the server.
     pub async fn run(self) -> Result<()> {
+        if let Some(path) = &self.config.unix_socket {
+            tracing::info!(socket = %path, "Gateway server starting (UNIX SOCKET)");
+            return crate::unix_socket::serve(self.build_router(), path).await;
+        }
+
         let addr = format!("{}:{}", self.config.host, self.config.port);
         if self.config.tls.enabled {
-            use axum_server::tls_rustls::RustlsConfig;
-
-            let cert_path = self.config.tls.cert_path.as_ref().ok_or_else(|| {
-                multi_agent_core::Error::gateway("TLS enabled but cert_path missing")
-            })?;
-            let key_path = self.config.tls.key_path.as_ref().ok_or_else(|| {
-                multi_agent_core::Error::gateway("TLS enabled but key_path missing")
-            })?;
-
-            tracing::info!(addr = %addr, "Gateway server starting (TLS ENABLED)");
+            let acceptor = crate::mtls::MtlsAcceptor::new(&self.config.tls)?;
 
-            let config = RustlsConfig::from_pem_file(cert_path, key_path)
-                .await
-                .map_err(|e| {
-                    multi_agent_core::Error::gateway(format!("TLS config error: {}", e))
-                })?;
+            tracing::info!(
+                addr = %addr,
+                client_auth = self.config.tls.ca_path.is_some(),
+                "Gateway server starting (TLS ENABLED)"
+            );
 
-            axum_server::bind_rustls(addr.parse::<std::net::SocketAddr>().unwrap(), config)
+            axum_server::bind(addr.parse::<std::net::SocketAddr>().unwrap())
+                .acceptor(acceptor)
                 .serve(
                     self.build_router()
                         .into_make_service_with_connect_info::<std::net::SocketAddr>(),
@@ -454,6 +857,42 @@ pub struct ChatRequest {
     pub user_id: Option<String>,
     /// Optional workspace ID for isolation.
     pub workspace_id: Option<String>,
+    /// Simulate mutating tool calls and return an action plan report.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// How the semantic cache is used for this request.
+    #[serde(default)]
+    pub cache: CacheMode,
+    /// Channel the message arrived on, matched by routing policy scopes.
+    pub channel: Option<String>,
+    /// Preferred response locale; defaults to the `Accept-Language` header.
+    pub locale: Option<String>,
+    /// Seconds the caller will wait for an answer; missions still running
+    /// then are paused and can be resumed.
+    pub timeout_secs: Option<u64>,
+}
+
+/// Per-request semantic cache behaviour.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheMode {
+    /// Answer from the cache when possible and cache the result.
+    #[default]
+    Prefer,
+    /// Recompute and overwrite the cached answer.
+    Refresh,
+    /// Neither read nor write the cache.
+    Bypass,
+}
+
+impl CacheMode {
+    fn reads(self) -> bool {
+        self == CacheMode::Prefer
+    }
+
+    fn writes(self) -> bool {
+        self != CacheMode::Bypass
+    }
 }
 
 /// Chat response.
@@ -483,6 +922,18 @@ pub struct ResearchRequest {
     pub query: String,
     /// User ID (optional, normally from JWT).
     pub user_id: Option<String>,
+    /// Source, depth, time and style limits for the run.
+    #[serde(flatten)]
+    pub options: multi_agent_core::types::research::ResearchOptions,
+}
+
+/// Asynchronous research job request.
+#[derive(Debug, Deserialize)]
+pub struct ResearchJobRequest {
+    #[serde(flatten)]
+    pub research: ResearchRequest,
+    /// Start the job at this time instead of immediately.
+    pub run_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 /// Intent response.
@@ -535,6 +986,14 @@ async fn gateway_schema_handler() -> impl IntoResponse {
     }))
 }
 
+async fn event_schema_handler() -> impl IntoResponse {
+    Json(serde_json::json!({
+        "name": "event_payloads",
+        "version": multi_agent_core::events::EVENT_SCHEMA_VERSION,
+        "events": multi_agent_core::events::event_schemas(),
+    }))
+}
+
 /// Liveness check handler (k8s style).
 async fn healthz_handler() -> impl IntoResponse {
     StatusCode::OK
@@ -674,18 +1133,17 @@ async fn put_policy_handler(
 ) -> impl IntoResponse {
     match &state.policy_engine {
         Some(engine) => {
+            if let Err(e) = check_mandatory_approval(&state, &payload).await {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({"error": e.to_string()})),
+                )
+                    .into_response();
+            }
             let mut engine = engine.write().await;
 
-            // Persist to disk
-            let policy_path = std::path::Path::new(".sovereign_claw/policies/default.yaml");
-            if let Some(parent) = policy_path.parent() {
-                let _ = std::fs::create_dir_all(parent);
-            }
-            if let Ok(content) = serde_yaml::to_string(&payload) {
-                if let Err(e) = std::fs::write(policy_path, content) {
-                    tracing::error!("Failed to persist policy: {}", e);
-                    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-                }
+            if !persist_policy(&state, &payload) {
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
             }
 
             engine.policy = payload;
@@ -699,6 +1157,87 @@ async fn put_policy_handler(
     }
 }
 
+/// Reject policies that set `never` for a tool that requires approval on
+/// every call. Without a tool registry there is nothing to check against.
+pub(crate) async fn check_mandatory_approval(
+    state: &AppState,
+    policy: &multi_agent_governance::PolicyFile,
+) -> multi_agent_core::Result<()> {
+    let Some(tools) = &state.tool_registry else {
+        return Ok(());
+    };
+    let mut mandatory = Vec::new();
+    for tool in tools.list().await? {
+        if tools.requires_approval(&tool.name).await {
+            mandatory.push(tool.name);
+        }
+    }
+    multi_agent_governance::PolicyEngine::from_file(policy.clone())
+        .check_mandatory_approval(&mandatory)
+        .map_err(|e| multi_agent_core::Error::invalid_request(e.to_string()))
+}
+
+/// Write the policy to disk so it survives restarts. A no-op when local
+/// state files are disabled.
+pub(crate) fn persist_policy(
+    state: &AppState,
+    policy: &multi_agent_governance::PolicyFile,
+) -> bool {
+    let Some(policy_path) = state.app_config.state_path(StateFile::Policy) else {
+        return true;
+    };
+    if let Some(parent) = policy_path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(content) = serde_yaml::to_string(policy) {
+        if let Err(e) = std::fs::write(&policy_path, content) {
+            tracing::error!("Failed to persist policy: {}", e);
+            return false;
+        }
+    }
+    true
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RuleModeRequest {
+    pub mode: multi_agent_governance::RuleMode,
+}
+
+/// `PUT /policy/rules/:rule_id/mode` switches a rule between shadow and enforcing.
+async fn put_rule_mode_handler(
+    State(state): State<Arc<AppState>>,
+    Path(rule_id): Path<String>,
+    Json(payload): Json<RuleModeRequest>,
+) -> impl IntoResponse {
+    let Some(engine) = &state.policy_engine else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "Policy engine not configured"})),
+        )
+            .into_response();
+    };
+    let mut engine = engine.write().await;
+    let previous = engine.policy.clone();
+    if !engine.set_rule_mode(&rule_id, payload.mode) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("No policy rule with ID: {}", rule_id)})),
+        )
+            .into_response();
+    }
+    if !persist_policy(&state, &engine.policy) {
+        engine.policy = previous;
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+
+    tracing::info!(rule = %rule_id, mode = ?payload.mode, "Policy rule mode changed");
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({"rule_id": rule_id, "mode": payload.mode})),
+    )
+        .into_response()
+}
+
 #[derive(Debug, Deserialize)]
 pub struct RoutingPublishRequest {
     pub version: String,
@@ -731,31 +1270,6 @@ pub struct RoutingAuditQuery {
     pub limit: Option<usize>,
 }
 
-async fn log_routing_admin_audit(
-    state: &Arc<AppState>,
-    action: &str,
-    metadata: serde_json::Value,
-    outcome: AuditOutcome,
-) {
-    let Some(admin_state) = &state.admin_state else {
-        return;
-    };
-    let _ = admin_state
-        .audit_store
-        .log(AuditEntry {
-            id: uuid::Uuid::new_v4().to_string(),
-            timestamp: chrono::Utc::now().to_rfc3339(),
-            user_id: "admin".to_string(),
-            action: action.to_string(),
-            resource: "routing_policy".to_string(),
-            outcome,
-            metadata: Some(metadata),
-            previous_hash: None,
-            hash: None,
-        })
-        .await;
-}
-
 async fn admin_routing_publish_handler(
     State(state): State<Arc<AppState>>,
     Json(payload): Json<RoutingPublishRequest>,
@@ -768,13 +1282,11 @@ async fn admin_routing_publish_handler(
             .into_response();
     };
 
-    let publish_version = payload.version.clone();
-    let publish_channel = payload.channel.unwrap_or(RoutingPolicyChannel::Canary);
     let release = RoutingPolicyRelease {
-        version: publish_version.clone(),
+        version: payload.version,
         name: payload.name,
         published_at: chrono::Utc::now().timestamp(),
-        channel: publish_channel,
+        channel: payload.channel.unwrap_or(RoutingPolicyChannel::Canary),
         rules: payload.rules,
     };
 
@@ -790,17 +1302,6 @@ async fn admin_routing_publish_handler(
                 )
                 .with_actor("admin"),
             );
-            log_routing_admin_audit(
-                &state,
-                "ROUTING_POLICY_PUBLISH",
-                serde_json::json!({
-                    "version": publish_version,
-                    "channel": publish_channel,
-                    "active": store.active_release().await
-                }),
-                AuditOutcome::Success,
-            )
-            .await;
             let active = store.active_release().await;
             (
                 StatusCode::OK,
@@ -808,24 +1309,11 @@ async fn admin_routing_publish_handler(
             )
                 .into_response()
         }
-        Err(e) => {
-            log_routing_admin_audit(
-                &state,
-                "ROUTING_POLICY_PUBLISH",
-                serde_json::json!({
-                    "error": e.to_string(),
-                    "version": publish_version,
-                    "channel": publish_channel
-                }),
-                AuditOutcome::Error(e.to_string()),
-            )
-            .await;
-            (
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({"published": false, "error": e.to_string()})),
-            )
-                .into_response()
-        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"published": false, "error": e.to_string()})),
+        )
+            .into_response(),
     }
 }
 
@@ -899,16 +1387,6 @@ async fn admin_routing_promote_handler(
                 )
                 .with_actor("admin"),
             );
-            log_routing_admin_audit(
-                &state,
-                "ROUTING_POLICY_PROMOTE",
-                serde_json::json!({
-                    "version": payload.version,
-                    "active_stable": store.active_release_for_channel(RoutingPolicyChannel::Stable).await
-                }),
-                AuditOutcome::Success,
-            )
-            .await;
             (
                 StatusCode::OK,
                 Json(serde_json::json!({
@@ -918,26 +1396,14 @@ async fn admin_routing_promote_handler(
             )
                 .into_response()
         }
-        Err(e) => {
-            log_routing_admin_audit(
-                &state,
-                "ROUTING_POLICY_PROMOTE",
-                serde_json::json!({
-                    "version": payload.version,
-                    "error": e.to_string(),
-                }),
-                AuditOutcome::Error(e.to_string()),
-            )
-            .await;
-            (
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({
-                    "promoted": false,
-                    "error": e.to_string()
-                })),
-            )
-                .into_response()
-        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "promoted": false,
+                "error": e.to_string()
+            })),
+        )
+            .into_response(),
     }
 }
 
@@ -966,16 +1432,6 @@ async fn admin_routing_rollback_handler(
                 )
                 .with_actor("admin"),
             );
-            log_routing_admin_audit(
-                &state,
-                "ROUTING_POLICY_ROLLBACK",
-                serde_json::json!({
-                    "version": payload.version,
-                    "active": store.active_release().await
-                }),
-                AuditOutcome::Success,
-            )
-            .await;
             (
                 StatusCode::OK,
                 Json(serde_json::json!({
@@ -985,26 +1441,14 @@ async fn admin_routing_rollback_handler(
             )
                 .into_response()
         }
-        Err(e) => {
-            log_routing_admin_audit(
-                &state,
-                "ROUTING_POLICY_ROLLBACK",
-                serde_json::json!({
-                    "version": payload.version,
-                    "error": e.to_string()
-                }),
-                AuditOutcome::Error(e.to_string()),
-            )
-            .await;
-            (
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({
-                    "rolled_back": false,
-                    "error": e.to_string()
-                })),
-            )
-                .into_response()
-        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "rolled_back": false,
+                "error": e.to_string()
+            })),
+        )
+            .into_response(),
     }
 }
 
@@ -1099,6 +1543,26 @@ async fn admin_routing_policies_handler(State(state): State<Arc<AppState>>) -> i
         .into_response()
 }
 
+/// Hit counts of the active rules, least used first.
+async fn admin_routing_hits_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let Some(store) = &state.routing_policy_store else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({"error":"Routing policy store not configured"})),
+        )
+            .into_response();
+    };
+    let rules = store.rule_hits().await;
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "count": rules.len(),
+            "rules": rules
+        })),
+    )
+        .into_response()
+}
+
 /// Research agent handler.
 async fn research_handler(
     State(state): State<Arc<AppState>>,
@@ -1116,36 +1580,391 @@ async fn research_handler(
     };
 
     let session_id = format!("sync-rs-{}", Uuid::new_v4());
+    if let Some(rejection) = guardrail_rejection(
+        &state,
+        GuardrailChannel::Research,
+        &req.query,
+        &session_id,
+        None,
+    )
+    .await
+    {
+        return rejection;
+    }
     let user_id = req.user_id.unwrap_or_else(|| "anonymous".to_string());
 
     match orchestrator
-        .run_research(&session_id, &user_id, &req.query)
+        .run_research(&session_id, &user_id, &req.query, &req.options)
         .await
     {
-        Ok(report) => (
+        Ok(result) => {
+            let owner = ArtifactOwner::new(Some(user_id), Some(session_id.clone()));
+            let result = crate::artifacts::limit_result(&state, result, owner).await;
+            (
+                StatusCode::OK,
+                Json(serde_json::json!({
+                    "report": result.to_text(),
+                    "result": result,
+                    "session_id": session_id,
+                })),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            let status = match e {
+                multi_agent_core::Error::InvalidRequest(_) => StatusCode::BAD_REQUEST,
+                multi_agent_core::Error::Governance(_) => StatusCode::FORBIDDEN,
+                multi_agent_core::Error::FileTypeRejected { .. } => {
+                    StatusCode::UNPROCESSABLE_ENTITY
+                }
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (
+                status,
+                Json(serde_json::json!({
+                    "error": format!("Research failed: {}", e)
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Submit an asynchronous research job.
+///
+/// `POST /v1/agent/research/jobs` returns `202 Accepted` with the job; follow
+/// progress at `/research/jobs/:job_id/events`.
+async fn research_job_submit_handler(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<ResearchJobRequest>,
+) -> impl IntoResponse {
+    let (jobs, orchestrator) = match (&state.research_jobs, &state.research_orchestrator) {
+        (Some(j), Some(o)) => (j, o),
+        _ => {
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": "Research orchestrator not enabled"})),
+            )
+                .into_response()
+        }
+    };
+
+    let trace_id = Uuid::new_v4().to_string();
+    if let Some(rejection) = guardrail_rejection(
+        &state,
+        GuardrailChannel::Research,
+        &req.research.query,
+        &trace_id,
+        None,
+    )
+    .await
+    {
+        return rejection;
+    }
+
+    // Reject bad limits up front instead of failing the job later.
+    if let Err(e) = orchestrator.resolve_limits(&req.research.options).await {
+        let status = match e {
+            multi_agent_core::Error::Governance(_) => StatusCode::FORBIDDEN,
+            _ => StatusCode::BAD_REQUEST,
+        };
+        return (
+            status,
+            Json(serde_json::json!({"error": format!("Research rejected: {}", e)})),
+        )
+            .into_response();
+    }
+
+    let user_id = req
+        .research
+        .user_id
+        .unwrap_or_else(|| "anonymous".to_string());
+    let job = jobs.submit(
+        req.research.query,
+        user_id,
+        req.research.options,
+        req.run_at,
+    );
+    (StatusCode::ACCEPTED, Json(serde_json::json!(job))).into_response()
+}
+
+/// Current state of a research job, including sections delivered so far.
+///
+/// `GET /v1/agent/research/jobs/:job_id`
+async fn research_job_handler(
+    State(state): State<Arc<AppState>>,
+    Path(job_id): Path<String>,
+) -> impl IntoResponse {
+    let Some(jobs) = &state.research_jobs else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({"error": "Research orchestrator not enabled"})),
+        )
+            .into_response();
+    };
+    match jobs.get(&job_id) {
+        Some(job) => (StatusCode::OK, Json(serde_json::json!(job))).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("Research job '{}' not found", job_id)})),
+        )
+            .into_response(),
+    }
+}
+
+/// Server-sent events for a research job.
+///
+/// `GET /v1/agent/research/jobs/:job_id/events` replays the sections already
+/// written, then streams live `status`, `section`, `completed` and `failed`
+/// events until the job finishes.
+async fn research_job_events_handler(
+    State(state): State<Arc<AppState>>,
+    Path(job_id): Path<String>,
+) -> Response {
+    let Some(jobs) = &state.research_jobs else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({"error": "Research orchestrator not enabled"})),
+        )
+            .into_response();
+    };
+    let Some((job, rx)) = jobs.subscribe(&job_id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("Research job '{}' not found", job_id)})),
+        )
+            .into_response();
+    };
+
+    let mut replay = vec![ResearchJobEvent::Status { status: job.status }];
+    replay.extend(
+        job.sections
+            .into_iter()
+            .map(|section| ResearchJobEvent::Section { section }),
+    );
+    if let Some(result) = job.result {
+        replay.push(ResearchJobEvent::Completed { result });
+    } else if let Some(error) = job.error {
+        replay.push(ResearchJobEvent::Failed { error });
+    }
+    let finished = job.status.is_terminal();
+
+    let live = futures::stream::unfold((rx, finished), |(mut rx, finished)| async move {
+        if finished {
+            return None;
+        }
+        loop {
+            match rx.recv().await {
+                Ok(event) => {
+                    let done = event.is_terminal();
+                    return Some((event, (rx, done)));
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!(skipped, "Research job subscriber lagged");
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+    let stream = futures::stream::iter(replay).chain(live).map(|event| {
+        let name = match &event {
+            ResearchJobEvent::Status { .. } => "status",
+            ResearchJobEvent::Section { .. } => "section",
+            ResearchJobEvent::Completed { .. } => "completed",
+            ResearchJobEvent::Failed { .. } => "failed",
+        };
+        Event::default().event(name).json_data(&event)
+    });
+
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// Sources snapshotted for a research run, for reviewing its citations.
+///
+/// `GET /v1/agent/research/:session_id/sources`
+async fn research_sources_handler(
+    State(state): State<Arc<AppState>>,
+    Path(session_id): Path<String>,
+) -> impl IntoResponse {
+    let orchestrator = match &state.research_orchestrator {
+        Some(o) => o,
+        None => {
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({"error": "Research orchestrator not enabled"})),
+            )
+                .into_response()
+        }
+    };
+
+    match orchestrator.sources(&session_id).await {
+        Some(sources) => (
             StatusCode::OK,
             Json(serde_json::json!({
-                "report": report,
                 "session_id": session_id,
+                "sources": sources,
             })),
         )
             .into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
+        None => (
+            StatusCode::NOT_FOUND,
             Json(serde_json::json!({
-                "error": format!("Research failed: {}", e)
+                "error": format!("No research sources for session '{}'", session_id)
             })),
         )
             .into_response(),
     }
 }
 
-/// Chat handler.
-async fn chat_handler(
-    State(state): State<Arc<AppState>>,
-    Json(payload): Json<ChatRequest>,
-) -> impl IntoResponse {
-    let trace_id = Uuid::new_v4().to_string();
+/// Chat handler.
+/// Check inbound text against the channel's guardrails, returning the
+/// rejection to send when it is blocked.
+async fn guardrail_rejection(
+    state: &AppState,
+    channel: GuardrailChannel,
+    text: &str,
+    trace_id: &str,
+    locale: Option<&str>,
+) -> Option<axum::response::Response> {
+    let guardrails = state.guardrails.as_ref()?;
+    let (status, body) = match guardrails.check_input(channel, text).await {
+        Ok(result) if result.passed => return None,
+        Ok(result) => {
+            tracing::warn!(
+                trace_id = %trace_id,
+                channel = channel.as_str(),
+                reason = ?result.reason,
+                "Guardrail blocked request"
+            );
+            (
+                StatusCode::FORBIDDEN,
+                ApiErrorBody::new(
+                    ApiErrorCode::Forbidden,
+                    result.reason.unwrap_or_else(|| {
+                        state
+                            .prompts
+                            .render(prompt_keys::ERROR_GUARDRAIL, locale, &[])
+                    }),
+                    false,
+                )
+                .with_details(serde_json::json!({
+                    "channel": channel,
+                    "violation_type": result.violation_type,
+                })),
+            )
+        }
+        Err(e) => {
+            tracing::error!(trace_id = %trace_id, error = %e, "Guardrail check failed");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                ApiErrorBody::new(ApiErrorCode::InternalError, e.to_string(), true),
+            )
+        }
+    };
+    Some(
+        (
+            status,
+            Json(ApiEnvelope::success(trace_id.to_string(), body)),
+        )
+            .into_response(),
+    )
+}
+
+/// Moderate `text` when the moderator checks `direction`, recording the
+/// verdict as a `MODERATION_EVALUATED` event.
+async fn moderate(
+    state: &AppState,
+    direction: ModerationDirection,
+    text: &str,
+    context: &RequestContext,
+) -> Option<ModerationOutcome> {
+    let moderator = state.moderator.as_ref()?;
+    if !moderator.checks(direction) {
+        return None;
+    }
+    let outcome = moderator.moderate(direction, text).await;
+
+    use multi_agent_core::events::{EventEnvelope, EventSeverity, EventType};
+    let mut event = EventEnvelope::new(
+        EventType::ModerationEvaluated,
+        serde_json::to_value(outcome.payload()).unwrap_or_default(),
+    )
+    .with_actor(context.user_id.as_deref().unwrap_or("anonymous"));
+    if outcome.action != ModerationAction::Allow {
+        event = event.with_severity(EventSeverity::Warning);
+    }
+    if let Some(trace_id) = &context.trace_id {
+        event = event.with_trace(trace_id);
+    }
+    if let Some(session_id) = &context.session_id {
+        event = event.with_session(session_id);
+    }
+    state.emit_event(event);
+    Some(outcome)
+}
+
+/// First language tag of an `Accept-Language` header.
+fn accept_language(headers: &axum::http::HeaderMap) -> Option<String> {
+    headers
+        .get(axum::http::header::ACCEPT_LANGUAGE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(',').next())
+        .map(|tag| tag.split(';').next().unwrap_or(tag).trim().to_string())
+        .filter(|tag| !tag.is_empty() && tag != "*")
+}
+
+/// A chat request that passed the checks and is ready to execute.
+struct PreparedChat {
+    trace_id: String,
+    context: RequestContext,
+    request: NormalizedRequest,
+    intent: UserIntent,
+    owner: ArtifactOwner,
+    cache_mode: CacheMode,
+    locale: Option<String>,
+    /// Held until the mission finishes.
+    _mission_permit: Option<crate::scheduler::MissionPermit>,
+}
+
+/// How a chat request proceeds after [`prepare_chat`].
+enum ChatPlan {
+    /// Answered from the semantic cache.
+    Cached(Box<ChatResponse>),
+    Execute(Box<PreparedChat>),
+}
+
+/// Run everything in front of the controller: events, guardrails and
+/// moderation, the cache, intent classification and mission admission.
+/// Returns the response to send when the request stops here.
+async fn prepare_chat(
+    state: &Arc<AppState>,
+    headers: &axum::http::HeaderMap,
+    payload: ChatRequest,
+) -> std::result::Result<ChatPlan, Response> {
+    let trace_id = AuditContext::current()
+        .and_then(|context| context.trace_id)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+    let context = RequestContext {
+        user_id: payload.user_id.clone(),
+        workspace_id: payload.workspace_id.clone(),
+        session_id: payload.session_id.clone(),
+        trace_id: Some(trace_id.clone()),
+        channel: payload.channel.clone(),
+        locale: payload.locale.clone().or_else(|| accept_language(headers)),
+        deadline: payload
+            .timeout_secs
+            .map(|secs| chrono::Utc::now() + chrono::Duration::seconds(secs as i64)),
+        feature_flags: state
+            .feature_flags
+            .as_ref()
+            .map(|flags| flags.resolve(payload.workspace_id.as_deref()))
+            .unwrap_or_default(),
+        ..Default::default()
+    };
+
+    let locale = context.locale.clone();
 
     tracing::info!(
         trace_id = %trace_id,
@@ -1155,14 +1974,15 @@ async fn chat_handler(
 
     // Emit REQUEST_RECEIVED event
     {
-        use multi_agent_core::events::{EventEnvelope, EventType};
+        use multi_agent_core::events::{EventEnvelope, EventType, RequestReceivedPayload};
         let event = EventEnvelope::new(
             EventType::RequestReceived,
-            serde_json::json!({
-                "message_len": payload.message.len(),
-                "has_session": payload.session_id.is_some(),
-                "has_user": payload.user_id.is_some()
-            }),
+            serde_json::to_value(RequestReceivedPayload {
+                message_len: payload.message.len(),
+                has_session: payload.session_id.is_some(),
+                has_user: payload.user_id.is_some(),
+            })
+            .unwrap_or_default(),
         )
         .with_trace(&trace_id)
         .with_actor(payload.user_id.as_deref().unwrap_or("anonymous"));
@@ -1174,34 +1994,92 @@ async fn chat_handler(
         }
     }
 
-    let workspace_id = payload.workspace_id.as_deref().unwrap_or("default");
-    let session_id = payload.session_id.as_deref().unwrap_or("default");
+    if let Some(rejection) = guardrail_rejection(
+        state,
+        GuardrailChannel::Chat,
+        &payload.message,
+        &trace_id,
+        locale.as_deref(),
+    )
+    .await
+    {
+        return Err(rejection);
+    }
 
-    // Check semantic cache first
-    match state
-        .cache
-        .get(workspace_id, session_id, &payload.message)
-        .await
+    if let Some(outcome) = moderate(
+        state,
+        ModerationDirection::Input,
+        &payload.message,
+        &context,
+    )
+    .await
     {
-        Ok(Some(cached_response)) => {
-            tracing::info!(trace_id = %trace_id, workspace = %workspace_id, session = %session_id, "Cache hit");
-            return (
-                StatusCode::OK,
+        if outcome.blocked() {
+            tracing::warn!(
+                trace_id = %trace_id,
+                categories = ?outcome.categories,
+                "Moderation blocked request"
+            );
+            return Err((
+                StatusCode::FORBIDDEN,
                 Json(ApiEnvelope::success(
                     trace_id.clone(),
-                    ChatResponse {
-                        trace_id,
-                        intent: UserIntent::FastAction {
-                            tool_name: "cache".to_string(),
-                            args: serde_json::json!({}),
-                            user_id: payload.user_id.clone(),
-                        },
-                        result: Some(AgentResult::Text(cached_response)),
-                        cached: true,
-                    },
+                    ApiErrorBody::new(
+                        ApiErrorCode::Forbidden,
+                        state
+                            .prompts
+                            .render(prompt_keys::ERROR_MODERATION, locale.as_deref(), &[]),
+                        false,
+                    )
+                    .with_details(serde_json::json!({
+                        "categories": outcome.categories,
+                    })),
                 )),
             )
-                .into_response();
+                .into_response());
+        }
+    }
+
+    let workspace_id = payload.workspace_id.as_deref().unwrap_or("default");
+    let session_id = payload.session_id.as_deref().unwrap_or("default");
+    let owner = ArtifactOwner::new(payload.user_id.clone(), payload.session_id.clone());
+
+    // Dry runs never touch the cache
+    let cache_mode = if payload.dry_run {
+        CacheMode::Bypass
+    } else {
+        payload.cache
+    };
+
+    // Check semantic cache first
+    let cached = if !cache_mode.reads() {
+        Ok(None)
+    } else {
+        state
+            .cache
+            .get(workspace_id, session_id, &payload.message)
+            .await
+    };
+    match cached {
+        Ok(Some(cached_response)) => {
+            tracing::info!(trace_id = %trace_id, workspace = %workspace_id, session = %session_id, "Cache hit");
+            return Ok(ChatPlan::Cached(Box::new(ChatResponse {
+                trace_id,
+                intent: UserIntent::FastAction {
+                    tool_name: "cache".to_string(),
+                    args: serde_json::json!({}),
+                    user_id: payload.user_id.clone(),
+                },
+                result: Some(
+                    crate::artifacts::limit_result(
+                        state,
+                        AgentResult::Text(cached_response),
+                        owner,
+                    )
+                    .await,
+                ),
+                cached: true,
+            })));
         }
         Ok(None) => {
             tracing::debug!(trace_id = %trace_id, "Cache miss");
@@ -1217,13 +2095,7 @@ async fn chat_handler(
         content: payload.message.clone(),
         original_content: multi_agent_core::types::RequestContent::Text(payload.message.clone()),
         refs: Vec::new(),
-        metadata: RequestMetadata {
-            user_id: payload.user_id.clone(),
-            workspace_id: payload.workspace_id.clone(),
-            session_id: payload.session_id.clone(),
-            trace_id: Some(trace_id.clone()),
-            custom: Default::default(),
-        },
+        context: context.clone(),
     };
 
     // Classify intent
@@ -1231,87 +2103,219 @@ async fn chat_handler(
         Ok((intent, routing_diagnostics)) => {
             // Emit INTENT_RESOLVED
             {
-                use multi_agent_core::events::{EventEnvelope, EventType};
+                use multi_agent_core::events::{EventEnvelope, EventType, IntentResolvedPayload};
                 let event = EventEnvelope::new(
                     EventType::IntentResolved,
-                    serde_json::json!({
-                        "intent_type": format!("{:?}", intent),
-                        "routing": routing_diagnostics.get("routing").cloned().unwrap_or_else(|| serde_json::json!({"source":"unknown"}))
-                    }),
+                    serde_json::to_value(IntentResolvedPayload {
+                        intent_type: format!("{:?}", intent),
+                        routing: routing_diagnostics
+                            .get("routing")
+                            .cloned()
+                            .unwrap_or_else(|| serde_json::json!({"source": "unknown"})),
+                    })
+                    .unwrap_or_default(),
                 )
                 .with_trace(&trace_id);
                 state.emit_event(event);
             }
-            intent
+            if payload.dry_run {
+                dry_run_intent(intent, &request)
+            } else {
+                intent
+            }
         }
         Err(e) => {
             tracing::error!(trace_id = %trace_id, error = %e, "Failed to classify intent");
-            return (
+            return Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiEnvelope::success(
                     trace_id.clone(),
-                    ApiErrorBody::new(ApiErrorCode::RoutingFailed, e.to_string(), true)
-                        .with_details(serde_json::json!({
-                            "user_id": payload.user_id,
-                        })),
+                    ApiErrorBody::new(
+                        ApiErrorCode::RoutingFailed,
+                        state.prompts.render(
+                            prompt_keys::ERROR_ROUTING,
+                            locale.as_deref(),
+                            &[("error", &e.to_string())],
+                        ),
+                        true,
+                    )
+                    .with_details(serde_json::json!({
+                        "user_id": payload.user_id,
+                    })),
                 )),
+            )
+                .into_response());
+        }
+    };
+
+    // Missions count against the user's and workspace's concurrency limits
+    let mission_permit =
+        if state.controller.is_some() && matches!(intent, UserIntent::ComplexMission { .. }) {
+            let user_id = payload.user_id.as_deref().unwrap_or("anonymous");
+            match state
+                .controller_scheduler
+                .admit(
+                    &state.app_config.controller.concurrency,
+                    user_id,
+                    workspace_id,
+                )
+                .await
+            {
+                Ok(permit) => Some(permit),
+                Err(e) => {
+                    let details = match &e {
+                        multi_agent_core::Error::ConcurrencyLimit { scope, key, limit } => {
+                            serde_json::json!({ "scope": scope, "key": key, "limit": limit })
+                        }
+                        _ => serde_json::Value::Null,
+                    };
+                    return Err((
+                        StatusCode::TOO_MANY_REQUESTS,
+                        Json(ApiEnvelope::success(
+                            trace_id.clone(),
+                            ApiErrorBody::new(
+                                ApiErrorCode::ConcurrencyLimited,
+                                state.prompts.render(
+                                    prompt_keys::ERROR_CONCURRENCY,
+                                    locale.as_deref(),
+                                    &[("error", &e.to_string())],
+                                ),
+                                true,
+                            )
+                            .with_details(details),
+                        )),
+                    )
+                        .into_response());
+                }
+            }
+        } else {
+            None
+        };
+
+    Ok(ChatPlan::Execute(Box::new(PreparedChat {
+        trace_id,
+        context,
+        request,
+        intent,
+        owner,
+        cache_mode,
+        locale,
+        _mission_permit: mission_permit,
+    })))
+}
+
+/// Turn the controller's outcome into the result returned to the caller:
+/// oversized results go by reference, blocked answers are replaced and
+/// successful text is cached.
+async fn finish_chat(
+    state: &AppState,
+    chat: &PreparedChat,
+    execution: std::result::Result<AgentResult, String>,
+) -> AgentResult {
+    let locale = chat.locale.as_deref();
+    let result = match execution {
+        Ok(result) => result,
+        Err(error) => {
+            tracing::error!(trace_id = %chat.trace_id, error = %error, "Controller execution failed");
+            return AgentResult::Error {
+                message: state.prompts.render(
+                    prompt_keys::ERROR_EXECUTION,
+                    locale,
+                    &[("error", &error)],
+                ),
+                code: "EXECUTION_ERROR".to_string(),
+            };
+        }
+    };
+    // Oversized results come back by reference and are not cached
+    let result = crate::artifacts::limit_result(state, result, chat.owner.clone()).await;
+    // Blocked answers are neither returned nor cached
+    let result = match result {
+        AgentResult::Text(text) => {
+            match moderate(state, ModerationDirection::Output, &text, &chat.context).await {
+                Some(outcome) if outcome.blocked() => AgentResult::Error {
+                    message: state
+                        .prompts
+                        .render(prompt_keys::ERROR_MODERATION, locale, &[]),
+                    code: "MODERATION_BLOCKED".to_string(),
+                },
+                _ => AgentResult::Text(text),
+            }
+        }
+        other => other,
+    };
+    // Cache successful text responses
+    if let (AgentResult::Text(ref text), true) = (&result, chat.cache_mode.writes()) {
+        let request = &chat.request;
+        let w_id = request.context.workspace_id.as_deref().unwrap_or("default");
+        let s_id = request.context.session_id.as_deref().unwrap_or("default");
+        let _ = state
+            .cache
+            .set_for_user(
+                w_id,
+                s_id,
+                &request.content,
+                text,
+                request.context.user_id.as_deref(),
+            )
+            .await;
+    }
+    result
+}
+
+/// Result when no controller is configured.
+fn intent_only_result(chat: &PreparedChat) -> AgentResult {
+    tracing::debug!(trace_id = %chat.trace_id, "No controller, returning intent only");
+    AgentResult::Text(format!(
+        "Intent classified. Controller not available in Phase 1. Intent: {:?}",
+        chat.intent
+    ))
+}
+
+async fn chat_handler(
+    State(state): State<Arc<AppState>>,
+    headers: axum::http::HeaderMap,
+    Json(payload): Json<ChatRequest>,
+) -> impl IntoResponse {
+    let chat = match prepare_chat(&state, &headers, payload).await {
+        Ok(ChatPlan::Execute(chat)) => chat,
+        Ok(ChatPlan::Cached(response)) => {
+            return (
+                StatusCode::OK,
+                Json(ApiEnvelope::success(response.trace_id.clone(), response)),
             )
                 .into_response();
         }
+        Err(response) => return response,
     };
 
     // Execute via controller if available
     let result = if let Some(ref controller) = state.controller {
         let controller = controller.clone();
-        let intent_for_exec = intent.clone();
-        let trace_for_exec = trace_id.clone();
-        let session_lane = request.metadata.session_id.clone();
+        let context = chat.context.clone();
+        let intent_for_exec = chat.intent.clone();
+        let trace_for_exec = chat.trace_id.clone();
         let execution = state
             .controller_scheduler
-            .run(session_lane.as_deref(), move || async move {
-                controller.execute(intent_for_exec, trace_for_exec).await
+            .run(chat.context.session_id.as_deref(), move || async move {
+                context
+                    .scope(controller.execute(intent_for_exec, trace_for_exec))
+                    .await
             })
             .await;
-        match execution {
-            Ok(result) => {
-                // Cache successful text responses
-                if let AgentResult::Text(ref text) = result {
-                    // Extract IDs again as payload was moved or use references
-                    let w_id = request
-                        .metadata
-                        .workspace_id
-                        .as_deref()
-                        .unwrap_or("default");
-                    let s_id = request.metadata.session_id.as_deref().unwrap_or("default");
-                    let _ = state.cache.set(w_id, s_id, &request.content, text).await;
-                }
-                Some(result)
-            }
-            Err(e) => {
-                tracing::error!(trace_id = %trace_id, error = %e, "Controller execution failed");
-                Some(AgentResult::Error {
-                    message: e.to_string(),
-                    code: "EXECUTION_ERROR".to_string(),
-                })
-            }
-        }
+        finish_chat(&state, &chat, execution.map_err(|e| e.to_string())).await
     } else {
-        // No controller - return intent classification only
-        tracing::debug!(trace_id = %trace_id, "No controller, returning intent only");
-        Some(AgentResult::Text(format!(
-            "Intent classified. Controller not available in Phase 1. Intent: {:?}",
-            intent
-        )))
+        intent_only_result(&chat)
     };
 
     (
         StatusCode::OK,
         Json(ApiEnvelope::success(
-            trace_id.clone(),
+            chat.trace_id.clone(),
             ChatResponse {
-                trace_id,
-                intent,
-                result,
+                trace_id: chat.trace_id,
+                intent: chat.intent,
+                result: Some(result),
                 cached: false,
             },
         )),
@@ -1319,6 +2323,159 @@ async fn chat_handler(
         .into_response()
 }
 
+/// Events buffered for a streaming chat client before the agent waits on it.
+const CHAT_STREAM_BUFFER: usize = 64;
+
+/// Streaming chat.
+///
+/// `POST /v1/agent/chat/stream` takes the same body as `/v1/agent/chat` and
+/// answers with Server-Sent Events: `thought`, `tool_call` and `observation`
+/// events while the agent works, then one `final` event carrying the
+/// [`ChatResponse`]. Steps pass through the caller's presentation policy and
+/// redaction, as on the log stream. Requests rejected before execution get
+/// the same error responses as `/v1/agent/chat`.
+async fn chat_stream_handler(
+    State(state): State<Arc<AppState>>,
+    headers: axum::http::HeaderMap,
+    context: Option<Extension<UserContext>>,
+    roles: Option<Extension<UserRoles>>,
+    Json(payload): Json<ChatRequest>,
+) -> Response {
+    let chat = match prepare_chat(&state, &headers, payload).await {
+        Ok(ChatPlan::Execute(chat)) => chat,
+        Ok(ChatPlan::Cached(response)) => {
+            let event = json_event("final", &response);
+            return Sse::new(futures::stream::iter(event.map(Ok::<_, Infallible>))).into_response();
+        }
+        Err(response) => return response,
+    };
+
+    let presenter = crate::logs::EventPresenter::for_caller(&state, context, roles);
+    let (sender, receiver) = tokio::sync::mpsc::channel::<Event>(CHAT_STREAM_BUFFER);
+    tokio::spawn(async move {
+        let result = match state.controller.clone() {
+            Some(controller) => {
+                let context = chat.context.clone();
+                let intent = chat.intent.clone();
+                let trace_id = chat.trace_id.clone();
+                let workspace_id = chat.context.workspace_id.as_deref();
+                let (progress, presenter) = (&sender, &presenter);
+                let execution = state
+                    .controller_scheduler
+                    .run(chat.context.session_id.as_deref(), move || async move {
+                        context
+                            .scope(async {
+                                let mut events = controller.execute_streaming(intent, trace_id);
+                                let mut outcome =
+                                    Err("Execution ended without a result".to_string());
+                                while let Some(event) = events.next().await {
+                                    match event {
+                                        AgentStreamEvent::Final { result } => outcome = Ok(result),
+                                        AgentStreamEvent::Error { message } => {
+                                            outcome = Err(message)
+                                        }
+                                        // The mission runs to completion even once the
+                                        // client is gone; its steps are dropped.
+                                        _ if progress.is_closed() => {}
+                                        event => {
+                                            if let Some(step) =
+                                                present_step(presenter, workspace_id, &event).await
+                                            {
+                                                let _ = progress.send(step).await;
+                                            }
+                                        }
+                                    }
+                                }
+                                outcome
+                            })
+                            .await
+                    })
+                    .await;
+                finish_chat(&state, &chat, execution).await
+            }
+            None => intent_only_result(&chat),
+        };
+        let response = ChatResponse {
+            trace_id: chat.trace_id,
+            intent: chat.intent,
+            result: Some(result),
+            cached: false,
+        };
+        if let Some(event) = json_event("final", &response) {
+            let _ = sender.send(event).await;
+        }
+    });
+
+    let events = futures::stream::unfold(receiver, |mut receiver| async move {
+        receiver
+            .recv()
+            .await
+            .map(|event| (Ok::<_, Infallible>(event), receiver))
+    });
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// SSE event for an execution step as the caller may see it; `None` if the
+/// presentation policy hides it or it does not serialize.
+async fn present_step(
+    presenter: &crate::logs::EventPresenter,
+    workspace_id: Option<&str>,
+    event: &AgentStreamEvent,
+) -> Option<Event> {
+    let mut value = serde_json::to_value(event)
+        .inspect_err(|e| {
+            tracing::warn!(event = event.name(), error = %e, "Skipping unserializable SSE event")
+        })
+        .ok()?;
+    presenter
+        .apply(&mut value, workspace_id)
+        .await
+        .then(|| Event::default().event(event.name()).data(value.to_string()))
+}
+
+/// SSE event carrying `data` as JSON; `None`, logged, if it does not serialize.
+fn json_event(name: &'static str, data: &impl Serialize) -> Option<Event> {
+    Event::default()
+        .event(name)
+        .json_data(data)
+        .inspect_err(
+            |e| tracing::warn!(event = name, error = %e, "Skipping unserializable SSE event"),
+        )
+        .ok()
+}
+
+/// Dry runs always go through the ReAct loop, which simulates mutating calls;
+/// a fast action becomes a one-goal mission.
+fn dry_run_intent(intent: UserIntent, request: &NormalizedRequest) -> UserIntent {
+    match intent {
+        UserIntent::ComplexMission {
+            goal,
+            context_summary,
+            visual_refs,
+            user_id,
+            workspace_id,
+            ..
+        } => UserIntent::ComplexMission {
+            goal,
+            context_summary,
+            visual_refs,
+            user_id,
+            workspace_id,
+            dry_run: true,
+        },
+        UserIntent::FastAction { user_id, .. } => UserIntent::ComplexMission {
+            goal: request.content.clone(),
+            context_summary: request.content.clone(),
+            visual_refs: Vec::new(),
+            user_id,
+            workspace_id: request.context.workspace_id.clone(),
+            dry_run: true,
+        },
+    }
+}
+
 /// Intent classification handler (for debugging/testing).
 async fn intent_handler(
     State(state): State<Arc<AppState>>,
@@ -1423,6 +2580,20 @@ async fn webhook_handler(
         }
     }
 
+    // Webhook payloads are untrusted, machine-sourced content
+    let payload_text = serde_json::to_string(&request_fingerprint["payload"]).unwrap_or_default();
+    if let Some(rejection) = guardrail_rejection(
+        &state,
+        GuardrailChannel::Webhook,
+        &payload_text,
+        &trace_id,
+        None,
+    )
+    .await
+    {
+        return rejection;
+    }
+
     // Create a normalized request from the system event
     let event_summary = format!(
         "System event: {} - {}",
@@ -1442,7 +2613,7 @@ async fn webhook_handler(
             payload: request_fingerprint["payload"].clone(),
         },
         refs: Vec::new(),
-        metadata: RequestMetadata::default(),
+        context: RequestContext::default(),
     };
 
     // Classify the event
@@ -1525,6 +2696,40 @@ struct WsApprovalResponse {
     reason_code: Option<String>,
     /// Modified args (for modified).
     modified_args: Option<serde_json::Value>,
+    /// Pre-authorization scope (for approved), e.g. `{"kind": "calls", "count": 20}`.
+    #[serde(default)]
+    scope: ApprovalScope,
+}
+
+/// WebSocket clarification question message (sent to client).
+#[derive(Debug, Serialize)]
+struct WsUserQuestion {
+    /// Message type ("user_question").
+    #[serde(rename = "type")]
+    msg_type: String,
+    /// The question data.
+    data: multi_agent_core::types::HumanQuestion,
+}
+
+/// WebSocket answer to a clarification question (received from client).
+#[derive(Debug, Deserialize)]
+struct WsUserAnswer {
+    /// Request ID being answered.
+    request_id: String,
+    /// Nonce for security validation.
+    nonce: String,
+    /// The answer text.
+    answer: String,
+}
+
+/// Receive from an optional broadcast channel; pends forever when absent.
+pub(crate) async fn recv_optional<T: Clone>(
+    rx: &mut Option<tokio::sync::broadcast::Receiver<T>>,
+) -> std::result::Result<T, tokio::sync::broadcast::error::RecvError> {
+    match rx {
+        Some(rx) => rx.recv().await,
+        None => std::future::pending().await,
+    }
 }
 
 /// REST approval request body.
@@ -1538,6 +2743,9 @@ pub struct ApproveRequest {
     pub reason: Option<String>,
     /// Reason code (e.g., "USER_APPROVED", "USER_DENIED").
     pub reason_code: Option<String>,
+    /// Pre-authorization scope (for approved); defaults to this call only.
+    #[serde(default)]
+    pub scope: ApprovalScope,
 }
 
 /// REST approval response.
@@ -1561,28 +2769,24 @@ async fn approval_ws_handler(
 }
 
 async fn handle_approval_ws(state: Arc<AppState>, mut socket: WebSocket) {
-    let gate = match &state.approval_gate {
-        Some(gate) => gate.clone(),
-        None => {
-            tracing::warn!(
-                "WebSocket approval connection attempted but no approval gate configured"
-            );
-            let _ = socket
-                .send(Message::Text(
-                    serde_json::json!({"type": "error", "message": "Approval gate not configured"})
-                        .to_string(),
-                ))
-                .await;
-            return;
-        }
-    };
+    if state.approval_gate.is_none() && state.human_input.is_none() {
+        tracing::warn!("WebSocket approval connection attempted but no approval gate configured");
+        let _ = socket
+            .send(Message::Text(
+                serde_json::json!({"type": "error", "message": "Approval gate not configured"})
+                    .to_string(),
+            ))
+            .await;
+        return;
+    }
 
-    let mut rx = gate.subscribe();
+    let mut approvals = state.approval_gate.as_ref().map(|gate| gate.subscribe());
+    let mut questions = state.human_input.as_ref().map(|input| input.subscribe());
 
     loop {
         tokio::select! {
             // Forward approval requests from broadcast channel to WebSocket
-            result = rx.recv() => {
+            result = recv_optional(&mut approvals) => {
                 match result {
                     Ok(req) => {
                         let msg = WsApprovalRequest {
@@ -1598,16 +2802,56 @@ async fn handle_approval_ws(state: Arc<AppState>, mut socket: WebSocket) {
                     Err(_) => break, // Broadcast sender dropped
                 }
             }
-            // Receive approval responses from WebSocket client
+            // Forward clarification questions from ask_user
+            result = recv_optional(&mut questions) => {
+                match result {
+                    Ok(question) => {
+                        let msg = WsUserQuestion {
+                            msg_type: "user_question".to_string(),
+                            data: question,
+                        };
+                        if let Ok(json) = serde_json::to_string(&msg) {
+                            if socket.send(Message::Text(json)).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    Err(_) => break,
+                }
+            }
+            // Receive approval responses and answers from WebSocket client
             result = socket.recv() => {
                 match result {
                     Some(Ok(Message::Text(text))) => {
+                        let msg_type = serde_json::from_str::<serde_json::Value>(&text)
+                            .ok()
+                            .and_then(|v| v.get("type").and_then(|t| t.as_str()).map(str::to_string));
+                        if msg_type.as_deref() == Some("user_answer") {
+                            let Some(input) = &state.human_input else {
+                                tracing::warn!("Received user_answer but no human-input channel configured");
+                                continue;
+                            };
+                            match serde_json::from_str::<WsUserAnswer>(&text) {
+                                Ok(ans) => {
+                                    if let Err(e) = input.submit_answer(&ans.request_id, &ans.nonce, ans.answer).await {
+                                        tracing::warn!("Failed to submit answer: {}", e);
+                                    }
+                                }
+                                Err(e) => tracing::warn!("Invalid user answer JSON: {}", e),
+                            }
+                            continue;
+                        }
+                        let Some(gate) = &state.approval_gate else {
+                            tracing::warn!("Received approval response but no approval gate configured");
+                            continue;
+                        };
                         match serde_json::from_str::<WsApprovalResponse>(&text) {
                             Ok(resp) => {
                                 let approval_response = match resp.decision.as_str() {
                                     "approved" => ApprovalResponse::Approved {
                                         reason: resp.reason.clone(),
                                         reason_code: resp.reason_code.clone().unwrap_or_else(|| "USER_APPROVED".to_string()),
+                                        scope: resp.scope,
                                     },
                                     "denied" => ApprovalResponse::Denied {
                                         reason: resp.reason.clone().unwrap_or_else(|| "Denied via WebSocket".into()),
@@ -1653,45 +2897,6 @@ async fn handle_approval_ws(state: Arc<AppState>, mut socket: WebSocket) {
     tracing::info!("Approval WebSocket session ended");
 }
 
-async fn logs_ws_handler(
-    State(state): State<Arc<AppState>>,
-    ws: WebSocketUpgrade,
-) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_logs_ws(state, socket))
-}
-
-async fn handle_logs_ws(state: Arc<AppState>, mut socket: WebSocket) {
-    let mut rx = match &state.logs_channel {
-        Some(tx) => tx.subscribe(),
-        None => {
-            let _ = socket
-                .send(Message::Text(
-                    serde_json::json!({"type": "error", "message": "Logs channel not configured"})
-                        .to_string(),
-                ))
-                .await;
-            return;
-        }
-    };
-
-    loop {
-        match rx.recv().await {
-            Ok(log_line) => {
-                if socket.send(Message::Text(log_line)).await.is_err() {
-                    break;
-                }
-            }
-            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {
-                // Skip lagged messages
-                continue;
-            }
-            Err(tokio::sync::broadcast::error::RecvError::Closed) => {
-                break;
-            }
-        }
-    }
-}
-
 /// REST endpoint for submitting approval decisions.
 ///
 /// `POST /v1/approve/:request_id`
@@ -1776,6 +2981,7 @@ async fn approve_rest_handler(
                 .reason_code
                 .clone()
                 .unwrap_or_else(|| "USER_APPROVED".to_string()),
+            scope: payload.scope,
         },
         "denied" => ApprovalResponse::Denied {
             reason: payload
@@ -1822,6 +3028,55 @@ async fn approve_rest_handler(
     }
 }
 
+/// REST answer body for clarification questions.
+#[derive(Debug, Deserialize)]
+pub struct AnswerRequest {
+    /// Nonce for security validation.
+    pub nonce: String,
+    /// The answer text.
+    pub answer: String,
+}
+
+/// REST endpoint for answering `ask_user` clarification questions.
+///
+/// `POST /v1/answer/:request_id`
+async fn answer_rest_handler(
+    State(state): State<Arc<AppState>>,
+    Path(request_id): Path<String>,
+    Json(payload): Json<AnswerRequest>,
+) -> impl IntoResponse {
+    let trace_id = Uuid::new_v4().to_string();
+    let (status, body) = match &state.human_input {
+        None => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            ApproveResponse {
+                accepted: false,
+                message: "Human input channel not configured".into(),
+            },
+        ),
+        Some(input) => match input
+            .submit_answer(&request_id, &payload.nonce, payload.answer)
+            .await
+        {
+            Ok(()) => (
+                StatusCode::OK,
+                ApproveResponse {
+                    accepted: true,
+                    message: format!("Answer submitted for request '{}'", request_id),
+                },
+            ),
+            Err(e) => (
+                StatusCode::NOT_FOUND,
+                ApproveResponse {
+                    accepted: false,
+                    message: e,
+                },
+            ),
+        },
+    };
+    (status, Json(ApiEnvelope::success(trace_id, body))).into_response()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1832,6 +3087,33 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[test]
+    fn test_dry_run_intent_routes_through_mission() {
+        let mut request = NormalizedRequest::text("restart the web server");
+        request.context.workspace_id = Some("ops".into());
+        let fast = UserIntent::FastAction {
+            tool_name: "restart".into(),
+            args: serde_json::json!({}),
+            user_id: Some("alice".into()),
+        };
+
+        match dry_run_intent(fast, &request) {
+            UserIntent::ComplexMission {
+                goal,
+                user_id,
+                workspace_id,
+                dry_run,
+                ..
+            } => {
+                assert_eq!(goal, "restart the web server");
+                assert_eq!(user_id.as_deref(), Some("alice"));
+                assert_eq!(workspace_id.as_deref(), Some("ops"));
+                assert!(dry_run);
+            }
+            other => panic!("Expected ComplexMission, got {:?}", other),
+        }
+    }
+
     use axum::body::Body;
     use axum::middleware::from_fn_with_state;
     use multi_agent_governance::StaticTokenRbacConnector;
@@ -1847,13 +3129,17 @@ mod tests {
             controller: None,
             rate_limiter: None,
             approval_gate: None,
+            human_input: None,
             logs_channel: None,
+            event_stream: None,
+            log_buffer: None,
             policy_engine: None,
             admin_state: Some(Arc::new(multi_agent_admin::AdminState {
                 audit_store: Arc::new(multi_agent_governance::InMemoryAuditStore::new()),
                 rbac: rbac.clone(),
                 metrics: None,
                 mcp_registry: Arc::new(multi_agent_skills::mcp_registry::McpRegistry::new()),
+                openapi_registry: None,
                 providers: Arc::new(tokio::sync::RwLock::new(vec![])),
                 provider_store: None,
                 secrets: Arc::new(multi_agent_governance::AesGcmSecretsManager::new(None)),
@@ -1864,13 +3150,33 @@ mod tests {
                 network_policy: Arc::new(tokio::sync::RwLock::new(
                     multi_agent_governance::network::NetworkPolicy::default(),
                 )),
+                audit_presets: Arc::new(multi_agent_admin::audit_view::AuditPresetStore::new()),
+                audit_anchorer: None,
+                guardrails: Arc::new(multi_agent_governance::RouteGuardrails::default()),
+                tool_analytics: None,
+                endpoint_registry: None,
+                active_client: None,
+                provider_sync: None,
+                model_canary: None,
             })),
             plugin_manager: None,
             app_config: multi_agent_core::config::AppConfig::default(),
             research_orchestrator: None,
+            research_jobs: None,
             idempotency_store: Arc::new(IdempotencyStore::new()),
             controller_scheduler: Arc::new(ControllerScheduler::default()),
             routing_policy_store: None,
+            artifact_store: None,
+            artifact_catalog: None,
+            knowledge_store: None,
+            guardrails: None,
+            sandbox_manager: None,
+            tool_registry: None,
+            workspace_store: None,
+            elevations: Arc::new(crate::admin_access::ElevationStore::new()),
+            feature_flags: None,
+            prompts: Arc::new(PromptRegistry::default()),
+            moderator: None,
         });
 
         let app = Router::new()
@@ -1895,6 +3201,21 @@ mod tests {
         let response = app.clone().oneshot(req).await.unwrap();
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
 
+        // Verified client certificate without a token
+        let req = Request::builder()
+            .uri("/")
+            .extension(crate::mtls::PeerIdentity {
+                common_name: Some("billing-service".into()),
+                user: Some(multi_agent_governance::rbac::UserRoles {
+                    user_id: "billing-service".into(),
+                    ..Default::default()
+                }),
+            })
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
         // Valid token
         let req = Request::builder()
             .uri("/")
@@ -1916,15 +3237,13 @@ async fn distributed_rate_limit(
     req: axum::extract::Request,
     next: axum::middleware::Next,
 ) -> axum::response::Response {
-    // Extract ConnectInfo manually
-    let addr = req
-        .extensions()
-        .get::<axum::extract::ConnectInfo<std::net::SocketAddr>>()
-        .map(|ci| ci.0.ip())
-        .unwrap_or_else(|| std::net::IpAddr::V4(std::net::Ipv4Addr::new(127, 0, 0, 1)));
-
     if let Some(limiter) = &state.rate_limiter {
-        let key = format!("rate_limit:{}", addr);
+        let client = req
+            .extensions()
+            .get::<crate::rate_limit::ClientKey>()
+            .map(|key| key.0.as_str())
+            .unwrap_or("ip:127.0.0.1");
+        let key = format!("rate_limit:{}", client);
 
         // 120 requests per minute
         match limiter
@@ -1946,6 +3265,9 @@ async fn distributed_rate_limit(
 }
 
 /// Middleware for bearer token authentication.
+///
+/// Authenticated requests run inside their audit context; see
+/// [`crate::correlation`].
 async fn bearer_auth_middleware(
     State(state): State<Arc<AppState>>,
     req: axum::extract::Request,
@@ -1985,9 +3307,10 @@ async fn bearer_auth_middleware(
                     session_id: None,
                 };
 
+                let (user_id, session_id) = (user.user_id.clone(), user.session_id.clone());
                 let mut req = req;
                 req.extensions_mut().insert(user);
-                return next.run(req).await;
+                return crate::correlation::run_correlated(req, user_id, session_id, next).await;
             }
         }
     }
@@ -1998,6 +3321,20 @@ async fn bearer_auth_middleware(
         .get(axum::http::header::AUTHORIZATION)
         .and_then(|h| h.to_str().ok());
 
+    // 4. Without a token, a verified mTLS client certificate identifies the caller
+    if auth_header.is_none() {
+        let peer_user = req
+            .extensions()
+            .get::<crate::mtls::PeerIdentity>()
+            .and_then(|peer| peer.user.clone());
+        if let Some(user) = peer_user {
+            let user_id = user.user_id.clone();
+            let mut req = req;
+            req.extensions_mut().insert(user);
+            return crate::correlation::run_correlated(req, user_id, None, next).await;
+        }
+    }
+
     let token = match auth_header {
         Some(h) if h.starts_with("Bearer ") => &h[7..],
         _ => {
@@ -2015,9 +3352,10 @@ async fn bearer_auth_middleware(
     match rbac {
         Some(rbac) => match rbac.validate(token).await {
             Ok(user) => {
+                let user_id = user.user_id.clone();
                 let mut req = req;
                 req.extensions_mut().insert(user);
-                next.run(req).await
+                crate::correlation::run_correlated(req, user_id, None, next).await
             }
             Err(e) => {
                 tracing::warn!("Auth validation failed: {}", e);
@@ -2035,18 +3373,18 @@ async fn bearer_auth_middleware(
     }
 }
 
-/// Middleware to restrict access to localhost.
+/// Middleware to restrict admin routes to localhost, or to allowlisted remote
+/// clients holding an elevated token when remote admin is enabled. Unix socket
+/// connections are always local and report a loopback peer.
 async fn restrict_to_localhost(
     State(state): State<Arc<AppState>>,
     axum::extract::ConnectInfo(addr): axum::extract::ConnectInfo<std::net::SocketAddr>,
     req: axum::extract::Request,
     next: axum::middleware::Next,
 ) -> axum::response::Response {
-    if state.app_config.governance.admin_allow_external_access || addr.ip().is_loopback() {
-        next.run(req).await
-    } else {
-        tracing::warn!(client_ip = %addr.ip(), "Blocked non-localhost access to Admin API");
-        (StatusCode::FORBIDDEN, "Admin API restricted to localhost").into_response()
+    match crate::admin_access::check_admin_access(&state, addr.ip(), &req, true) {
+        Ok(()) => next.run(req).await,
+        Err(response) => *response,
     }
 }
 
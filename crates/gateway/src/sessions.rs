@@ -0,0 +1,382 @@
+//! Session progress endpoints.
+//!
+//! `GET /v1/agent/sessions/:id/progress` summarises a running session for
+//! clients that poll instead of holding a WebSocket: the current iteration,
+//! the agent's latest thought, any pending approval, token usage and cost so
+//! far, and a rough ETA. `GET /v1/agent/sessions/:id/watch` streams the same
+//! summary as Server-Sent Events whenever the session changes. Sessions are
+//! visible to their owner and to admins.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    Extension, Json,
+};
+use futures::StreamExt;
+use serde::Serialize;
+use std::sync::Arc;
+
+use multi_agent_core::config::AppConfig;
+use multi_agent_core::traits::SessionStore;
+use multi_agent_core::types::{Session, SessionChange, SessionStatus, ToolRiskLevel};
+use multi_agent_governance::rbac::{UserContext, UserRoles};
+use multi_agent_model_gateway::PricingRegistry;
+
+use crate::identity::caller;
+use crate::server::AppState;
+
+/// Characters of the latest thought returned.
+const THOUGHT_CHARS: usize = 280;
+
+/// Progress snapshot of one session.
+#[derive(Debug, Serialize)]
+pub struct SessionProgress {
+    pub session_id: String,
+    pub status: SessionStatus,
+    /// Zero-based ReAct iteration currently running.
+    pub iteration: usize,
+    pub max_iterations: usize,
+    /// Truncated text of the agent's latest response.
+    pub last_thought: Option<String>,
+    pub pending_approval: Option<PendingApproval>,
+    pub tokens: TokenProgress,
+    /// Estimated from the default model's price; absent when it is unpriced.
+    pub cost_usd: Option<f64>,
+    pub eta: Option<EtaEstimate>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Approval the session is blocked on.
+#[derive(Debug, Serialize)]
+pub struct PendingApproval {
+    pub request_id: String,
+    pub tool_name: String,
+    pub risk_level: ToolRiskLevel,
+    pub expires_at: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TokenProgress {
+    pub prompt: u64,
+    pub completion: u64,
+    pub total: u64,
+    pub budget: u64,
+    pub remaining: u64,
+}
+
+/// Heuristic time left; sessions usually finish before the upper bound.
+#[derive(Debug, Serialize)]
+pub struct EtaEstimate {
+    pub avg_iteration_secs: f64,
+    /// Iterations left before the iteration cap or the token budget stops the run.
+    pub remaining_iterations: usize,
+    pub max_remaining_secs: u64,
+}
+
+impl SessionProgress {
+    /// Build a snapshot from a stored session run under `max_iterations`.
+    pub fn from_session(session: &Session, max_iterations: usize, cost_usd: Option<f64>) -> Self {
+        let iteration = session
+            .task_state
+            .as_ref()
+            .map(|t| t.iteration)
+            .unwrap_or_default();
+        let last_thought = session
+            .history
+            .iter()
+            .rev()
+            .find(|entry| entry.role == "assistant")
+            .map(|entry| truncate(&entry.content, THOUGHT_CHARS));
+        let usage = &session.token_usage;
+        let eta = (session.status == SessionStatus::Running)
+            .then(|| estimate_eta(session, iteration, max_iterations));
+        Self {
+            session_id: session.id.clone(),
+            status: session.status,
+            iteration,
+            max_iterations,
+            last_thought,
+            pending_approval: None,
+            tokens: TokenProgress {
+                prompt: usage.prompt_tokens,
+                completion: usage.completion_tokens,
+                total: usage.total_tokens,
+                budget: usage.budget_limit,
+                remaining: usage.remaining(),
+            },
+            cost_usd,
+            eta,
+            created_at: session.created_at,
+            updated_at: session.updated_at,
+        }
+    }
+}
+
+fn estimate_eta(session: &Session, iteration: usize, max_iterations: usize) -> EtaEstimate {
+    let completed = iteration + 1;
+    let elapsed = (session.updated_at - session.created_at).max(0) as f64;
+    let avg_iteration_secs = elapsed / completed as f64;
+    let by_cap = max_iterations.saturating_sub(completed);
+    let usage = &session.token_usage;
+    let by_budget = match usage.total_tokens / completed as u64 {
+        0 => by_cap,
+        per_iteration => (usage.remaining() / per_iteration) as usize,
+    };
+    let remaining_iterations = by_cap.min(by_budget);
+    EtaEstimate {
+        avg_iteration_secs,
+        remaining_iterations,
+        max_remaining_secs: (avg_iteration_secs * remaining_iterations as f64).ceil() as u64,
+    }
+}
+
+/// Token cost at the price of the default provider's first model.
+fn estimate_cost(config: &AppConfig, session: &Session) -> Option<f64> {
+    let provider = &config.model_gateway.default_provider;
+    let model = config
+        .model_gateway
+        .providers
+        .get(provider)?
+        .models
+        .first()?;
+    let pricing = PricingRegistry::with_defaults();
+    let price = pricing.get(&format!("{}:{}", provider, model))?;
+    Some(price.estimate_cost(
+        session.token_usage.prompt_tokens,
+        session.token_usage.completion_tokens,
+    ))
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    let mut chars = text.chars();
+    let mut out: String = chars.by_ref().take(max_chars).collect();
+    if chars.next().is_some() {
+        out.push('…');
+    }
+    out
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (status, Json(serde_json::json!({ "error": message }))).into_response()
+}
+
+fn session_store(state: &AppState) -> Option<Arc<dyn SessionStore>> {
+    state
+        .admin_state
+        .as_ref()
+        .and_then(|admin| admin.session_store.clone())
+}
+
+/// Load a session the caller may see.
+async fn load_visible(
+    store: &dyn SessionStore,
+    session_id: &str,
+    user_id: &str,
+    is_admin: bool,
+) -> Result<Session, Response> {
+    let session = match store.load(session_id).await {
+        Ok(Some(session)) => session,
+        Ok(None) => return Err(error_response(StatusCode::NOT_FOUND, "Session not found")),
+        Err(e) => {
+            tracing::error!(session_id = %session_id, error = %e, "Failed to load session");
+            return Err(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to load session",
+            ));
+        }
+    };
+    // Other users' sessions are reported as missing rather than forbidden.
+    if !is_admin && session.user_id.as_deref() != Some(user_id) {
+        return Err(error_response(StatusCode::NOT_FOUND, "Session not found"));
+    }
+    Ok(session)
+}
+
+async fn progress(state: &AppState, session: &Session) -> SessionProgress {
+    let max_iterations = state.app_config.controller.max_react_iterations as usize;
+    let cost_usd = estimate_cost(&state.app_config, session);
+    let mut progress = SessionProgress::from_session(session, max_iterations, cost_usd);
+    if let Some(gate) = &state.approval_gate {
+        progress.pending_approval = gate
+            .pending_for_session(&session.id)
+            .await
+            .into_iter()
+            .min_by_key(|request| request.expires_at)
+            .map(|request| PendingApproval {
+                request_id: request.request_id,
+                tool_name: request.tool_name,
+                risk_level: request.risk_level,
+                expires_at: request.expires_at,
+            });
+    }
+    progress
+}
+
+/// `GET /sessions/:id/progress`
+pub(crate) async fn session_progress_handler(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<UserContext>>,
+    roles: Option<Extension<UserRoles>>,
+    Path(session_id): Path<String>,
+) -> Response {
+    let Some(store) = session_store(&state) else {
+        return error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Session store not configured",
+        );
+    };
+    let Some((user_id, is_admin)) = caller(context.as_deref(), roles.as_deref()) else {
+        return error_response(StatusCode::UNAUTHORIZED, "Authentication required");
+    };
+    match load_visible(store.as_ref(), &session_id, &user_id, is_admin).await {
+        Ok(session) => Json(progress(&state, &session).await).into_response(),
+        Err(response) => response,
+    }
+}
+
+/// `GET /sessions/:id/watch`
+///
+/// Sends a `progress` event with the current state, another after every
+/// change, and a final `deleted` event if the session goes away.
+pub(crate) async fn session_watch_handler(
+    State(state): State<Arc<AppState>>,
+    context: Option<Extension<UserContext>>,
+    roles: Option<Extension<UserRoles>>,
+    Path(session_id): Path<String>,
+) -> Response {
+    let Some(store) = session_store(&state) else {
+        return error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Session store not configured",
+        );
+    };
+    let Some((user_id, is_admin)) = caller(context.as_deref(), roles.as_deref()) else {
+        return error_response(StatusCode::UNAUTHORIZED, "Authentication required");
+    };
+    // Watch before loading so no change slips in between.
+    let changes = match store.clone().watch(&session_id).await {
+        Ok(changes) => changes,
+        Err(e) => {
+            tracing::error!(session_id = %session_id, error = %e, "Failed to watch session");
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR, "Failed to watch session");
+        }
+    };
+    let session = match load_visible(store.as_ref(), &session_id, &user_id, is_admin).await {
+        Ok(session) => session,
+        Err(response) => return response,
+    };
+
+    let changes = futures::stream::iter([SessionChange::Saved(Box::new(session))]).chain(changes);
+    // End the stream right after the deletion event.
+    let events = futures::stream::unfold(Some(changes), |changes| async move {
+        let mut changes = changes?;
+        let change = changes.next().await?;
+        let deleted = matches!(change, SessionChange::Deleted);
+        Some((change, (!deleted).then_some(changes)))
+    })
+    .then(move |change| {
+        let state = state.clone();
+        async move {
+            match change {
+                SessionChange::Saved(session) => Event::default()
+                    .event("progress")
+                    .json_data(progress(&state, &session).await),
+                SessionChange::Deleted => Ok(Event::default().event("deleted").data("{}")),
+            }
+        }
+    });
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multi_agent_core::types::{HistoryEntry, TaskState, TokenUsage};
+
+    fn session(iteration: usize, total_tokens: u64, budget: u64) -> Session {
+        let mut token_usage = TokenUsage::with_budget(budget);
+        token_usage.add(total_tokens / 2, total_tokens - total_tokens / 2);
+        Session {
+            id: "s1".into(),
+            trace_id: "t1".into(),
+            user_id: Some("alice".into()),
+            workspace_id: None,
+            dry_run: false,
+            status: SessionStatus::Running,
+            history: vec![
+                HistoryEntry {
+                    role: "assistant".into(),
+                    content: Arc::new("Listing the repository".into()),
+                    tool_call: None,
+                    timestamp: 0,
+                },
+                HistoryEntry {
+                    role: "user".into(),
+                    content: Arc::new("OBSERVATION: ok".into()),
+                    tool_call: None,
+                    timestamp: 0,
+                },
+            ],
+            task_state: Some(TaskState {
+                iteration,
+                goal: "Summarise".into(),
+                observations: vec![],
+                pending_actions: vec![],
+                consecutive_rejections: 0,
+            }),
+            token_usage,
+            created_at: 1_000,
+            updated_at: 1_030,
+        }
+    }
+
+    #[test]
+    fn test_progress_eta_bounded_by_cap_and_budget() {
+        // 3 iterations in 30s, 1000 tokens each, plenty of budget: capped at 10
+        let progress = SessionProgress::from_session(&session(2, 3_000, 100_000), 10, None);
+        assert_eq!(
+            progress.last_thought.as_deref(),
+            Some("Listing the repository")
+        );
+        let eta = progress.eta.unwrap();
+        assert_eq!(eta.avg_iteration_secs, 10.0);
+        assert_eq!(eta.remaining_iterations, 7);
+        assert_eq!(eta.max_remaining_secs, 70);
+
+        // Budget for only two more iterations
+        let eta = SessionProgress::from_session(&session(2, 3_000, 5_000), 10, None)
+            .eta
+            .unwrap();
+        assert_eq!(eta.remaining_iterations, 2);
+
+        let mut done = session(2, 3_000, 5_000);
+        done.status = SessionStatus::Completed;
+        assert!(SessionProgress::from_session(&done, 10, None).eta.is_none());
+    }
+
+    #[test]
+    fn test_cost_uses_default_model_price() {
+        let mut config = AppConfig::default();
+        config.model_gateway.default_provider = "openai".into();
+        config.model_gateway.providers.insert(
+            "openai".into(),
+            multi_agent_core::config::ProviderConfig {
+                enabled: true,
+                models: vec!["gpt-4o".into()],
+            },
+        );
+        let cost = estimate_cost(&config, &session(0, 2_000, 10_000)).unwrap();
+        // 1k prompt tokens at $5 + 1k completion tokens at $15
+        assert!((cost - 20.0).abs() < 1e-9);
+
+        config.model_gateway.providers.clear();
+        assert!(estimate_cost(&config, &session(0, 10, 100)).is_none());
+    }
+}
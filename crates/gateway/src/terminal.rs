@@ -0,0 +1,227 @@
+//! Interactive sandbox terminal.
+//!
+//! `GET /v1/admin/sandbox/terminal?cols=&rows=` upgrades to a WebSocket bound
+//! to a PTY shell in the agent's active sandbox, so an operator can inspect
+//! or unstick a workspace from the dashboard. Admin role required.
+//!
+//! Client → server: binary frames are keystrokes; text frames are JSON
+//! `{"type":"input","data":"..."}` or `{"type":"resize","cols":N,"rows":N}`.
+//! Server → client: binary frames are terminal output, followed by a final
+//! `{"type":"closed","transcript_ref":"..."}` text frame.
+//!
+//! The whole session is saved to the artifact store as an asciicast v2
+//! transcript and both ends of it are written to the audit log.
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket},
+        Query, State, WebSocketUpgrade,
+    },
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use multi_agent_governance::rbac::{UserContext, UserRoles};
+use multi_agent_governance::{AuditEntry, AuditOutcome};
+use multi_agent_sandbox::{SandboxId, TerminalInput, TerminalSize, TranscriptRecorder};
+
+use crate::identity::caller;
+use crate::server::AppState;
+
+/// Content type of stored transcripts.
+const TRANSCRIPT_CONTENT_TYPE: &str = "application/x-asciicast";
+
+/// Query parameters for opening a terminal.
+#[derive(Debug, Deserialize)]
+pub struct TerminalQuery {
+    pub cols: Option<u16>,
+    pub rows: Option<u16>,
+}
+
+/// Control messages sent as text frames.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    Input { data: String },
+    Resize { cols: u16, rows: u16 },
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (status, Json(serde_json::json!({ "error": message }))).into_response()
+}
+
+/// `GET /v1/admin/sandbox/terminal`
+pub(crate) async fn sandbox_terminal_handler(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<TerminalQuery>,
+    context: Option<Extension<UserContext>>,
+    roles: Option<Extension<UserRoles>>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let user_id = match caller(context.as_deref(), roles.as_deref()) {
+        Some((user_id, true)) => user_id,
+        _ => {
+            return error_response(
+                StatusCode::FORBIDDEN,
+                "Sandbox terminal requires admin role",
+            )
+        }
+    };
+    let Some(manager) = state.sandbox_manager.clone() else {
+        return error_response(StatusCode::SERVICE_UNAVAILABLE, "Sandbox not configured");
+    };
+
+    let default = TerminalSize::default();
+    let size = TerminalSize {
+        cols: query.cols.unwrap_or(default.cols).clamp(10, 500),
+        rows: query.rows.unwrap_or(default.rows).clamp(5, 200),
+    };
+
+    let (sandbox_id, session) = match manager.open_terminal(size).await {
+        Ok(opened) => opened,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to open sandbox terminal");
+            audit(
+                &state,
+                &user_id,
+                "sandbox_terminal_opened",
+                None,
+                AuditOutcome::Error(e.to_string()),
+                serde_json::json!({}),
+            )
+            .await;
+            return error_response(StatusCode::BAD_GATEWAY, "Failed to open sandbox terminal");
+        }
+    };
+    audit(
+        &state,
+        &user_id,
+        "sandbox_terminal_opened",
+        Some(&sandbox_id),
+        AuditOutcome::Success,
+        serde_json::json!({ "cols": size.cols, "rows": size.rows }),
+    )
+    .await;
+
+    ws.on_upgrade(move |socket| handle_terminal(state, socket, user_id, sandbox_id, session, size))
+}
+
+async fn handle_terminal(
+    state: Arc<AppState>,
+    mut socket: WebSocket,
+    user_id: String,
+    sandbox_id: SandboxId,
+    session: multi_agent_sandbox::TerminalSession,
+    size: TerminalSize,
+) {
+    let multi_agent_sandbox::TerminalSession { input, mut output } = session;
+    let mut recorder =
+        TranscriptRecorder::new(size).with_title(format!("{} @ {}", user_id, sandbox_id));
+
+    loop {
+        tokio::select! {
+            chunk = output.recv() => {
+                let Some(chunk) = chunk else { break };
+                recorder.output(&chunk);
+                if socket.send(Message::Binary(chunk)).await.is_err() {
+                    break;
+                }
+            }
+            msg = socket.recv() => {
+                let msg = match msg {
+                    Some(Ok(Message::Binary(data))) => TerminalInput::Data(data),
+                    Some(Ok(Message::Text(text))) => match serde_json::from_str(&text) {
+                        Ok(ClientMessage::Input { data }) => TerminalInput::Data(data.into_bytes()),
+                        Ok(ClientMessage::Resize { cols, rows }) => {
+                            TerminalInput::Resize(TerminalSize { cols, rows })
+                        }
+                        Err(_) => continue,
+                    },
+                    Some(Ok(Message::Close(_))) | None | Some(Err(_)) => break,
+                    Some(Ok(_)) => continue,
+                };
+                match &msg {
+                    TerminalInput::Data(data) => recorder.input(data),
+                    TerminalInput::Resize(size) => recorder.resize(*size),
+                }
+                if input.send(msg).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+    // Dropping the input channel ends the shell
+    drop(input);
+
+    let transcript_ref = match &state.artifact_store {
+        Some(store) => match store
+            .save_with_type(
+                bytes::Bytes::from(recorder.to_asciicast()),
+                TRANSCRIPT_CONTENT_TYPE,
+            )
+            .await
+        {
+            Ok(id) => Some(id.to_string()),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to store sandbox terminal transcript");
+                None
+            }
+        },
+        None => None,
+    };
+
+    let _ = socket
+        .send(Message::Text(
+            serde_json::json!({ "type": "closed", "transcript_ref": transcript_ref }).to_string(),
+        ))
+        .await;
+
+    audit(
+        &state,
+        &user_id,
+        "sandbox_terminal_closed",
+        Some(&sandbox_id),
+        AuditOutcome::Success,
+        serde_json::json!({
+            "events": recorder.len(),
+            "transcript_ref": transcript_ref,
+        }),
+    )
+    .await;
+    tracing::info!(sandbox_id = %sandbox_id, user_id = %user_id, "Sandbox terminal closed");
+}
+
+async fn audit(
+    state: &AppState,
+    user_id: &str,
+    action: &str,
+    sandbox_id: Option<&SandboxId>,
+    outcome: AuditOutcome,
+    metadata: serde_json::Value,
+) {
+    let Some(admin_state) = &state.admin_state else {
+        return;
+    };
+    let _ = admin_state
+        .audit_store
+        .log(AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            user_id: user_id.to_string(),
+            action: action.to_string(),
+            resource: match sandbox_id {
+                Some(id) => format!("sandbox:{}", id),
+                None => "sandbox".to_string(),
+            },
+            outcome,
+            metadata: Some(metadata),
+            previous_hash: None,
+            hash: None,
+            trace_id: None,
+            session_id: None,
+        })
+        .await;
+}
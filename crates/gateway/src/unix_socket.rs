@@ -0,0 +1,119 @@
+//! Unix domain socket listener.
+//!
+//! Local-only deployments (the desktop app, sidecars) can serve the gateway on
+//! a Unix socket instead of a TCP port. The socket file is created with mode
+//! `0600`, so only the owning user can connect. Connections are reported to the
+//! router as coming from `127.0.0.1`, which keeps the localhost-only admin
+//! routes and per-IP rate limiting working unchanged.
+//!
+//! Windows named pipes are not supported; configuring a socket there fails at
+//! startup.
+
+use axum::Router;
+use std::path::Path;
+
+use multi_agent_core::{Error, Result};
+
+/// Peer address reported for Unix socket connections.
+pub fn local_peer() -> std::net::SocketAddr {
+    std::net::SocketAddr::from(([127, 0, 0, 1], 0))
+}
+
+/// Serve `app` on the Unix socket at `path` until the listener fails.
+#[cfg(unix)]
+pub async fn serve(app: Router, path: impl AsRef<Path>) -> Result<()> {
+    use hyper_util::rt::{TokioExecutor, TokioIo};
+    use hyper_util::server::conn::auto;
+    use hyper_util::service::TowerToHyperService;
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+    let path = path.as_ref();
+    // A socket left behind by a previous run would make bind fail.
+    if let Ok(meta) = std::fs::symlink_metadata(path) {
+        if !meta.file_type().is_socket() {
+            return Err(Error::gateway(format!(
+                "{} exists and is not a socket",
+                path.display()
+            )));
+        }
+        std::fs::remove_file(path)
+            .map_err(|e| Error::gateway(format!("Failed to remove stale socket: {}", e)))?;
+    }
+
+    let listener = tokio::net::UnixListener::bind(path)
+        .map_err(|e| Error::gateway(format!("Failed to bind {}: {}", path.display(), e)))?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+        .map_err(|e| Error::gateway(format!("Failed to restrict socket permissions: {}", e)))?;
+
+    let app = app.layer(axum::Extension(axum::extract::ConnectInfo(local_peer())));
+
+    loop {
+        let (stream, _) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to accept Unix socket connection");
+                continue;
+            }
+        };
+        let service = TowerToHyperService::new(app.clone());
+        tokio::spawn(async move {
+            if let Err(e) = auto::Builder::new(TokioExecutor::new())
+                .serve_connection_with_upgrades(TokioIo::new(stream), service)
+                .await
+            {
+                tracing::debug!(error = %e, "Unix socket connection closed with error");
+            }
+        });
+    }
+}
+
+/// Serve `app` on a Unix socket; unsupported on this platform.
+#[cfg(not(unix))]
+pub async fn serve(_app: Router, path: impl AsRef<Path>) -> Result<()> {
+    Err(Error::gateway(format!(
+        "Cannot listen on {}: Unix sockets and named pipes are not supported on this platform",
+        path.as_ref().display()
+    )))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn test_serves_over_unix_socket() {
+        let path = std::env::temp_dir().join(format!("gateway-{}.sock", uuid::Uuid::new_v4()));
+        let app = Router::new().route(
+            "/peer",
+            get(
+                |axum::extract::ConnectInfo(addr): axum::extract::ConnectInfo<
+                    std::net::SocketAddr,
+                >| async move { addr.ip().to_string() },
+            ),
+        );
+        tokio::spawn(serve(app, path.clone()));
+
+        let mut stream = loop {
+            match tokio::net::UnixStream::connect(&path).await {
+                Ok(stream) => break stream,
+                Err(_) => tokio::time::sleep(std::time::Duration::from_millis(10)).await,
+            }
+        };
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        stream
+            .write_all(b"GET /peer HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.ends_with("127.0.0.1"));
+
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,589 @@
+//! Workspace environment templates.
+//!
+//! A template bundles what a team needs to start working: pre-registered
+//! tools, MCP servers, prompts and an approval policy override. Templates are
+//! managed under `/v1/admin/workspaces/templates`;
+//! `POST /templates/:name/instantiate` creates a workspace from one in a
+//! single call (registering missing MCP servers and installing the approval
+//! override), and `POST /:id/snapshot` captures an existing workspace's
+//! configuration as a new template. `PUT /:id/presentation` sets which
+//! streamed events the workspace's end users see.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path as FsPath, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use multi_agent_core::config::PresentationPolicy;
+use multi_agent_governance::{AuditContext, AuditEntry, AuditOutcome, WorkspaceApprovalOverride};
+use multi_agent_skills::McpServerInfo;
+
+use crate::server::AppState;
+
+/// Reusable workspace configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentTemplate {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Tools available in workspaces created from this template.
+    #[serde(default)]
+    pub tools: Vec<String>,
+    /// MCP servers registered on instantiation if missing.
+    #[serde(default)]
+    pub mcp_servers: Vec<McpServerInfo>,
+    /// Named prompts, e.g. `system` or `onboarding`.
+    #[serde(default)]
+    pub prompts: BTreeMap<String, String>,
+    /// Approval policy installed for the workspace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval: Option<WorkspaceApprovalOverride>,
+    /// Presentation policy for the workspace's streamed events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presentation: Option<PresentationPolicy>,
+    /// Unix timestamp; set by the store.
+    #[serde(default)]
+    pub created_at: i64,
+}
+
+/// A workspace and the configuration it was created with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workspace {
+    pub id: String,
+    /// Template the workspace was instantiated from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(default)]
+    pub tools: Vec<String>,
+    /// IDs of the MCP servers registered for the workspace.
+    #[serde(default)]
+    pub mcp_servers: Vec<String>,
+    #[serde(default)]
+    pub prompts: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval: Option<WorkspaceApprovalOverride>,
+    /// Overrides `governance.presentation` for this workspace's events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presentation: Option<PresentationPolicy>,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct WorkspaceSnapshot {
+    #[serde(default)]
+    templates: Vec<EnvironmentTemplate>,
+    #[serde(default)]
+    workspaces: Vec<Workspace>,
+}
+
+/// Templates and workspaces, optionally persisted to a JSON file.
+pub struct WorkspaceStore {
+    templates: RwLock<HashMap<String, EnvironmentTemplate>>,
+    workspaces: RwLock<HashMap<String, Workspace>>,
+    persistence_path: Option<PathBuf>,
+}
+
+impl Default for WorkspaceStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WorkspaceStore {
+    pub fn new() -> Self {
+        Self {
+            templates: RwLock::new(HashMap::new()),
+            workspaces: RwLock::new(HashMap::new()),
+            persistence_path: None,
+        }
+    }
+
+    pub fn new_persistent(path: impl AsRef<FsPath>) -> multi_agent_core::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut snapshot = WorkspaceSnapshot::default();
+        if path.exists() {
+            let content = std::fs::read_to_string(&path).map_err(|e| {
+                multi_agent_core::Error::invalid_request(format!(
+                    "Read workspace store failed: {}",
+                    e
+                ))
+            })?;
+            if !content.trim().is_empty() {
+                snapshot = serde_json::from_str(&content).map_err(|e| {
+                    multi_agent_core::Error::invalid_request(format!(
+                        "Parse workspace store failed: {}",
+                        e
+                    ))
+                })?;
+            }
+        }
+        Ok(Self {
+            templates: RwLock::new(
+                snapshot
+                    .templates
+                    .into_iter()
+                    .map(|t| (t.name.clone(), t))
+                    .collect(),
+            ),
+            workspaces: RwLock::new(
+                snapshot
+                    .workspaces
+                    .into_iter()
+                    .map(|w| (w.id.clone(), w))
+                    .collect(),
+            ),
+            persistence_path: Some(path),
+        })
+    }
+
+    async fn persist_snapshot(&self) -> multi_agent_core::Result<()> {
+        let Some(path) = &self.persistence_path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                multi_agent_core::Error::invalid_request(format!(
+                    "Create workspace store dir failed: {}",
+                    e
+                ))
+            })?;
+        }
+        let mut snapshot = WorkspaceSnapshot {
+            templates: self.templates.read().await.values().cloned().collect(),
+            workspaces: self.workspaces.read().await.values().cloned().collect(),
+        };
+        snapshot.templates.sort_by(|a, b| a.name.cmp(&b.name));
+        snapshot.workspaces.sort_by(|a, b| a.id.cmp(&b.id));
+        let content = serde_json::to_string_pretty(&snapshot).map_err(|e| {
+            multi_agent_core::Error::invalid_request(format!(
+                "Serialize workspace store failed: {}",
+                e
+            ))
+        })?;
+        std::fs::write(path, content).map_err(|e| {
+            multi_agent_core::Error::invalid_request(format!(
+                "Persist workspace store failed: {}",
+                e
+            ))
+        })
+    }
+
+    /// Add or replace a template.
+    pub async fn put_template(
+        &self,
+        mut template: EnvironmentTemplate,
+    ) -> multi_agent_core::Result<EnvironmentTemplate> {
+        if template.name.trim().is_empty() {
+            return Err(multi_agent_core::Error::invalid_request(
+                "Template name must not be empty",
+            ));
+        }
+        template.created_at = chrono::Utc::now().timestamp();
+        self.templates
+            .write()
+            .await
+            .insert(template.name.clone(), template.clone());
+        self.persist_snapshot().await?;
+        Ok(template)
+    }
+
+    pub async fn get_template(&self, name: &str) -> Option<EnvironmentTemplate> {
+        self.templates.read().await.get(name).cloned()
+    }
+
+    pub async fn list_templates(&self) -> Vec<EnvironmentTemplate> {
+        let mut templates: Vec<_> = self.templates.read().await.values().cloned().collect();
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+        templates
+    }
+
+    /// Returns false if no template has this name.
+    pub async fn remove_template(&self, name: &str) -> multi_agent_core::Result<bool> {
+        if self.templates.write().await.remove(name).is_none() {
+            return Ok(false);
+        }
+        self.persist_snapshot().await?;
+        Ok(true)
+    }
+
+    pub async fn get_workspace(&self, id: &str) -> Option<Workspace> {
+        self.workspaces.read().await.get(id).cloned()
+    }
+
+    pub async fn list_workspaces(&self) -> Vec<Workspace> {
+        let mut workspaces: Vec<_> = self.workspaces.read().await.values().cloned().collect();
+        workspaces.sort_by(|a, b| a.id.cmp(&b.id));
+        workspaces
+    }
+
+    /// Record a new workspace; fails if the ID is taken.
+    pub async fn create_workspace(&self, workspace: Workspace) -> multi_agent_core::Result<()> {
+        {
+            let mut workspaces = self.workspaces.write().await;
+            if workspaces.contains_key(&workspace.id) {
+                return Err(multi_agent_core::Error::invalid_request(format!(
+                    "Workspace {} already exists",
+                    workspace.id
+                )));
+            }
+            workspaces.insert(workspace.id.clone(), workspace);
+        }
+        self.persist_snapshot().await
+    }
+
+    /// Replace a workspace's presentation policy; `None` if no such workspace.
+    pub async fn set_presentation(
+        &self,
+        id: &str,
+        presentation: Option<PresentationPolicy>,
+    ) -> multi_agent_core::Result<Option<Workspace>> {
+        let workspace = {
+            let mut workspaces = self.workspaces.write().await;
+            let Some(workspace) = workspaces.get_mut(id) else {
+                return Ok(None);
+            };
+            workspace.presentation = presentation;
+            workspace.clone()
+        };
+        self.persist_snapshot().await?;
+        Ok(Some(workspace))
+    }
+}
+
+/// Create workspace `workspace_id` from `template`: register its missing MCP
+/// servers, install its approval override and record the workspace. Returns
+/// the workspace and the IDs of the MCP servers registered.
+pub async fn instantiate(
+    state: &AppState,
+    template: &EnvironmentTemplate,
+    workspace_id: &str,
+) -> multi_agent_core::Result<(Workspace, Vec<String>)> {
+    let Some(store) = &state.workspace_store else {
+        return Err(multi_agent_core::Error::invalid_request(
+            "Workspace store not configured",
+        ));
+    };
+
+    let mut registered = Vec::new();
+    if let Some(admin_state) = &state.admin_state {
+        for server in &template.mcp_servers {
+            if !admin_state.mcp_registry.contains(&server.id) {
+                admin_state.mcp_registry.register(server.clone());
+                registered.push(server.id.clone());
+            }
+        }
+    }
+
+    if let (Some(approval), Some(engine)) = (&template.approval, &state.policy_engine) {
+        let mut engine = engine.write().await;
+        let previous = engine.policy.clone();
+        engine
+            .policy
+            .workspace_overrides
+            .insert(workspace_id.to_string(), approval.clone());
+        if let Err(e) = crate::server::check_mandatory_approval(state, &engine.policy).await {
+            engine.policy = previous;
+            return Err(e);
+        }
+        if !crate::server::persist_policy(state, &engine.policy) {
+            engine.policy = previous;
+            return Err(multi_agent_core::Error::invalid_request(
+                "Failed to persist workspace approval policy",
+            ));
+        }
+    }
+
+    let workspace = Workspace {
+        id: workspace_id.to_string(),
+        template: Some(template.name.clone()),
+        tools: template.tools.clone(),
+        mcp_servers: template.mcp_servers.iter().map(|s| s.id.clone()).collect(),
+        prompts: template.prompts.clone(),
+        approval: template.approval.clone(),
+        presentation: template.presentation.clone(),
+        created_at: chrono::Utc::now().timestamp(),
+    };
+    store.create_workspace(workspace.clone()).await?;
+    tracing::info!(workspace = %workspace.id, template = %template.name, "Workspace created from template");
+    Ok((workspace, registered))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InstantiateRequest {
+    pub workspace_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SnapshotRequest {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+fn error(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(serde_json::json!({ "error": message.into() }))).into_response()
+}
+
+fn unavailable() -> Response {
+    error(
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Workspace store not configured",
+    )
+}
+
+pub(crate) async fn audit(
+    state: &AppState,
+    action: &str,
+    resource: &str,
+    metadata: serde_json::Value,
+) {
+    let Some(admin_state) = &state.admin_state else {
+        return;
+    };
+    let _ = admin_state
+        .audit_store
+        .log(AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            user_id: AuditContext::user_or("admin"),
+            action: action.to_string(),
+            resource: resource.to_string(),
+            outcome: AuditOutcome::Success,
+            metadata: Some(metadata),
+            previous_hash: None,
+            hash: None,
+            trace_id: None,
+            session_id: None,
+        })
+        .await;
+}
+
+/// `GET /templates`
+pub(crate) async fn list_templates_handler(State(state): State<Arc<AppState>>) -> Response {
+    match &state.workspace_store {
+        Some(store) => Json(store.list_templates().await).into_response(),
+        None => unavailable(),
+    }
+}
+
+/// `POST /templates` adds or replaces a template.
+pub(crate) async fn put_template_handler(
+    State(state): State<Arc<AppState>>,
+    Json(template): Json<EnvironmentTemplate>,
+) -> Response {
+    let Some(store) = &state.workspace_store else {
+        return unavailable();
+    };
+    match store.put_template(template).await {
+        Ok(template) => {
+            audit(
+                &state,
+                "PUT_WORKSPACE_TEMPLATE",
+                &template.name,
+                serde_json::json!({
+                    "tools": template.tools.len(),
+                    "mcp_servers": template.mcp_servers.len(),
+                    "prompts": template.prompts.len(),
+                }),
+            )
+            .await;
+            (StatusCode::CREATED, Json(template)).into_response()
+        }
+        Err(e) => error(StatusCode::BAD_REQUEST, e.to_string()),
+    }
+}
+
+/// `GET /templates/:name`
+pub(crate) async fn get_template_handler(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Response {
+    let Some(store) = &state.workspace_store else {
+        return unavailable();
+    };
+    match store.get_template(&name).await {
+        Some(template) => Json(template).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// `DELETE /templates/:name`
+pub(crate) async fn delete_template_handler(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Response {
+    let Some(store) = &state.workspace_store else {
+        return unavailable();
+    };
+    match store.remove_template(&name).await {
+        Ok(true) => {
+            audit(
+                &state,
+                "DELETE_WORKSPACE_TEMPLATE",
+                &name,
+                serde_json::json!({}),
+            )
+            .await;
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Ok(false) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
+/// `POST /templates/:name/instantiate` creates a workspace from a template.
+pub(crate) async fn instantiate_template_handler(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Json(req): Json<InstantiateRequest>,
+) -> Response {
+    let Some(store) = &state.workspace_store else {
+        return unavailable();
+    };
+    if req.workspace_id.trim().is_empty() {
+        return error(StatusCode::BAD_REQUEST, "workspace_id must not be empty");
+    }
+    let Some(template) = store.get_template(&name).await else {
+        return error(
+            StatusCode::NOT_FOUND,
+            format!("No workspace template named {}", name),
+        );
+    };
+    if store.get_workspace(&req.workspace_id).await.is_some() {
+        return error(
+            StatusCode::CONFLICT,
+            format!("Workspace {} already exists", req.workspace_id),
+        );
+    }
+
+    let (workspace, registered) = match instantiate(&state, &template, &req.workspace_id).await {
+        Ok(created) => created,
+        Err(e) => return error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    };
+    audit(
+        &state,
+        "INSTANTIATE_WORKSPACE_TEMPLATE",
+        &workspace.id,
+        serde_json::json!({
+            "template": template.name,
+            "registered_mcp_servers": registered,
+        }),
+    )
+    .await;
+    (StatusCode::CREATED, Json(workspace)).into_response()
+}
+
+/// `GET /`
+pub(crate) async fn list_workspaces_handler(State(state): State<Arc<AppState>>) -> Response {
+    match &state.workspace_store {
+        Some(store) => Json(store.list_workspaces().await).into_response(),
+        None => unavailable(),
+    }
+}
+
+/// `GET /:id`
+pub(crate) async fn get_workspace_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Response {
+    let Some(store) = &state.workspace_store else {
+        return unavailable();
+    };
+    match store.get_workspace(&id).await {
+        Some(workspace) => Json(workspace).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// `POST /:id/snapshot` saves a workspace's current configuration as a
+/// template. The live approval override and MCP server definitions are used,
+/// so changes made after instantiation are captured.
+pub(crate) async fn snapshot_workspace_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(req): Json<SnapshotRequest>,
+) -> Response {
+    let Some(store) = &state.workspace_store else {
+        return unavailable();
+    };
+    let Some(workspace) = store.get_workspace(&id).await else {
+        return error(StatusCode::NOT_FOUND, format!("No workspace {}", id));
+    };
+
+    let approval = match &state.policy_engine {
+        Some(engine) => engine
+            .read()
+            .await
+            .policy
+            .workspace_overrides
+            .get(&id)
+            .cloned(),
+        None => workspace.approval.clone(),
+    };
+    let mcp_servers = match &state.admin_state {
+        Some(admin_state) => admin_state
+            .mcp_registry
+            .list_all()
+            .into_iter()
+            .filter(|s| workspace.mcp_servers.contains(&s.id))
+            .collect(),
+        None => Vec::new(),
+    };
+    let template = EnvironmentTemplate {
+        name: req.name,
+        description: req
+            .description
+            .or_else(|| Some(format!("Snapshot of workspace {}", id))),
+        tools: workspace.tools,
+        mcp_servers,
+        prompts: workspace.prompts,
+        approval,
+        presentation: workspace.presentation,
+        created_at: 0,
+    };
+    match store.put_template(template).await {
+        Ok(template) => {
+            audit(
+                &state,
+                "SNAPSHOT_WORKSPACE",
+                &id,
+                serde_json::json!({ "template": template.name }),
+            )
+            .await;
+            (StatusCode::CREATED, Json(template)).into_response()
+        }
+        Err(e) => error(StatusCode::BAD_REQUEST, e.to_string()),
+    }
+}
+
+/// `PUT /:id/presentation` sets the workspace's presentation policy; a `null`
+/// body restores the global default.
+pub(crate) async fn put_presentation_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(presentation): Json<Option<PresentationPolicy>>,
+) -> Response {
+    let Some(store) = &state.workspace_store else {
+        return unavailable();
+    };
+    match store.set_presentation(&id, presentation).await {
+        Ok(Some(workspace)) => {
+            audit(
+                &state,
+                "SET_WORKSPACE_PRESENTATION",
+                &id,
+                serde_json::json!({ "presentation": workspace.presentation }),
+            )
+            .await;
+            Json(workspace).into_response()
+        }
+        Ok(None) => error(StatusCode::NOT_FOUND, format!("No workspace {}", id)),
+        Err(e) => error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
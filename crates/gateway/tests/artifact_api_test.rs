@@ -0,0 +1,333 @@
+use async_trait::async_trait;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use bytes::Bytes;
+use multi_agent_core::{
+    traits::{ArtifactMetadata, ArtifactStore, Erasable},
+    types::{ArtifactOwner, RefId},
+    Result,
+};
+use multi_agent_store::{ArtifactCatalog, CatalogArtifactStore, InMemoryStore};
+use multi_agent_testkit::{TestApp, TestAppBuilder};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+/// In-memory store that pretends to support pre-signed URLs.
+struct SigningStore(InMemoryStore);
+
+#[async_trait]
+impl ArtifactStore for SigningStore {
+    async fn save(&self, data: Bytes) -> Result<RefId> {
+        self.0.save(data).await
+    }
+
+    async fn save_with_id(&self, id: &RefId, data: Bytes) -> Result<()> {
+        self.0.save_with_id(id, data).await
+    }
+
+    async fn save_with_type(&self, data: Bytes, content_type: &str) -> Result<RefId> {
+        self.0.save_with_type(data, content_type).await
+    }
+
+    async fn load(&self, id: &RefId) -> Result<Option<Bytes>> {
+        self.0.load(id).await
+    }
+
+    async fn delete(&self, id: &RefId) -> Result<()> {
+        self.0.delete(id).await
+    }
+
+    async fn exists(&self, id: &RefId) -> Result<bool> {
+        self.0.exists(id).await
+    }
+
+    async fn metadata(&self, id: &RefId) -> Result<Option<ArtifactMetadata>> {
+        self.0.metadata(id).await
+    }
+
+    async fn presigned_url(
+        &self,
+        id: &RefId,
+        _expires_in: std::time::Duration,
+    ) -> Result<Option<String>> {
+        Ok(Some(format!("https://objects.example.com/{}?sig=abc", id)))
+    }
+}
+
+#[async_trait]
+impl Erasable for SigningStore {
+    async fn erase_user(&self, user_id: &str) -> Result<usize> {
+        self.0.erase_user(user_id).await
+    }
+}
+
+fn build_app(store: Arc<dyn ArtifactStore>) -> axum::Router {
+    app(store).build().router()
+}
+
+fn app(store: Arc<dyn ArtifactStore>) -> TestAppBuilder {
+    TestApp::builder().server(|server| server.with_artifact_store(store))
+}
+
+fn request(uri: &str, token: &str) -> axum::http::request::Builder {
+    Request::builder()
+        .uri(uri)
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+            [127, 0, 0, 1],
+            12345,
+        ))))
+}
+
+fn get(uri: &str) -> axum::http::request::Builder {
+    request(uri, "user-token")
+}
+
+async fn body_bytes(response: axum::response::Response) -> Bytes {
+    axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_artifact_full_and_range_requests() {
+    let store = Arc::new(InMemoryStore::new());
+    let id = store
+        .save_with_type(Bytes::from("0123456789"), "text/plain")
+        .await
+        .unwrap();
+    let app = build_app(store);
+    let uri = format!("/v1/agent/artifacts/{}", id);
+
+    let response = app
+        .clone()
+        .oneshot(get(&uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/plain");
+    assert_eq!(response.headers()[header::ACCEPT_RANGES], "bytes");
+    assert_eq!(body_bytes(response).await, Bytes::from("0123456789"));
+
+    let response = app
+        .clone()
+        .oneshot(
+            get(&uri)
+                .header(header::RANGE, "bytes=2-5")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes 2-5/10");
+    assert_eq!(body_bytes(response).await, Bytes::from("2345"));
+
+    let response = app
+        .clone()
+        .oneshot(
+            get(&uri)
+                .header(header::RANGE, "bytes=20-")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+    assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes */10");
+
+    let response = app
+        .clone()
+        .oneshot(
+            get(&uri)
+                .header(header::ACCEPT, "application/json")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+
+    let response = app
+        .oneshot(
+            get("/v1/agent/artifacts/missing")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_artifact_owned_by_other_user_is_hidden() {
+    let store = Arc::new(InMemoryStore::new());
+    let id = ArtifactOwner::new(Some("alice".into()), None)
+        .scope(store.save(Bytes::from("private")))
+        .await
+        .unwrap();
+    let app = build_app(store);
+    let uri = format!("/v1/agent/artifacts/{}", id);
+
+    // NoOpRbacConnector maps any non-admin token to "anonymous".
+    let response = app
+        .clone()
+        .oneshot(get(&uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = app
+        .oneshot(request(&uri, "admin").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_artifact_presigned_url_endpoint() {
+    let store = Arc::new(SigningStore(InMemoryStore::new()));
+    let id = store.save(Bytes::from("large")).await.unwrap();
+    let app = build_app(store);
+
+    let response = app
+        .oneshot(
+            get(&format!("/v1/agent/artifacts/{}/url", id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let json: serde_json::Value = serde_json::from_slice(&body_bytes(response).await).unwrap();
+    assert!(json["url"].as_str().unwrap().contains("sig=abc"));
+    assert!(json["expires_in_secs"].as_u64().is_some());
+}
+
+#[tokio::test]
+async fn test_session_artifact_listing() {
+    let catalog = Arc::new(ArtifactCatalog::new());
+    let store = Arc::new(CatalogArtifactStore::new(
+        Arc::new(InMemoryStore::new()),
+        catalog.clone(),
+    ));
+    let shared = ArtifactOwner::new(None, Some("s1".into()))
+        .scope(store.save_with_type(Bytes::from("Summary\nbody"), "text/plain"))
+        .await
+        .unwrap();
+    ArtifactOwner::new(Some("alice".into()), Some("s1".into()))
+        .scope(store.save(Bytes::from("private")))
+        .await
+        .unwrap();
+    let app = app(store)
+        .server(|server| server.with_artifact_catalog(catalog))
+        .build()
+        .router();
+
+    let response = app
+        .clone()
+        .oneshot(
+            get("/v1/agent/sessions/s1/artifacts")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let json: serde_json::Value = serde_json::from_slice(&body_bytes(response).await).unwrap();
+    let artifacts = json["artifacts"].as_array().unwrap();
+    assert_eq!(artifacts.len(), 1);
+    assert_eq!(artifacts[0]["ref_id"], shared.as_str());
+    assert_eq!(artifacts[0]["description"], "Summary");
+    assert_eq!(artifacts[0]["content_type"], "text/plain");
+
+    let response = app
+        .oneshot(
+            request("/v1/agent/sessions/s1/artifacts", "admin")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body_bytes(response).await).unwrap();
+    assert_eq!(json["artifacts"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_multipart_upload_and_body_limits() {
+    let store = Arc::new(InMemoryStore::new());
+    let app = build_app(store.clone());
+
+    let multipart = "--XBOUNDARY\r\n\
+         Content-Disposition: form-data; name=\"note\"\r\n\r\n\
+         ignored\r\n\
+         --XBOUNDARY\r\n\
+         Content-Disposition: form-data; name=\"file\"; filename=\"data.csv\"\r\n\
+         Content-Type: text/csv\r\n\r\n\
+         a,b\n1,2\n\r\n\
+         --XBOUNDARY--\r\n";
+    let response = app
+        .clone()
+        .oneshot(
+            get("/v1/agent/artifacts")
+                .method("POST")
+                .header(
+                    header::CONTENT_TYPE,
+                    "multipart/form-data; boundary=XBOUNDARY",
+                )
+                .body(Body::from(multipart))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let json: serde_json::Value = serde_json::from_slice(&body_bytes(response).await).unwrap();
+    let artifacts = json["artifacts"].as_array().unwrap();
+    assert_eq!(artifacts.len(), 1);
+    assert_eq!(artifacts[0]["file_name"], "data.csv");
+    assert_eq!(artifacts[0]["content_type"], "text/csv");
+    let id = RefId::from_string(artifacts[0]["ref_id"].as_str().unwrap());
+    assert_eq!(store.load(&id).await.unwrap().unwrap(), "a,b\n1,2\n");
+
+    // Declared oversized bodies are rejected before they are read.
+    let oversized = vec![b' '; 2 * 1024 * 1024];
+    let response = app
+        .clone()
+        .oneshot(
+            get("/v1/agent/chat")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::CONTENT_LENGTH, oversized.len())
+                .body(Body::from(oversized.clone()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    let json: serde_json::Value = serde_json::from_slice(&body_bytes(response).await).unwrap();
+    assert!(json["guidance"]
+        .as_str()
+        .unwrap()
+        .contains("/v1/agent/artifacts"));
+
+    // Streamed bodies without a length are cut off at the limit.
+    let chunks = oversized
+        .chunks(64 * 1024)
+        .map(|c| Ok::<_, std::io::Error>(Bytes::copy_from_slice(c)))
+        .collect::<Vec<_>>();
+    let response = app
+        .oneshot(
+            get("/v1/agent/chat")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from_stream(futures::stream::iter(chunks)))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    let json: serde_json::Value = serde_json::from_slice(&body_bytes(response).await).unwrap();
+    assert!(json["limit_bytes"].as_u64().is_some());
+}
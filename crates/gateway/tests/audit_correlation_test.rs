@@ -0,0 +1,118 @@
+use async_trait::async_trait;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use multi_agent_core::Result;
+use multi_agent_gateway::workspaces::WorkspaceStore;
+use multi_agent_governance::{
+    AuditFilter, AuditStore, InMemoryAuditStore, RbacConnector, UserRoles,
+};
+use multi_agent_testkit::TestApp;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+/// Every token belongs to the admin user "alice".
+struct AliceRbac;
+
+#[async_trait]
+impl RbacConnector for AliceRbac {
+    async fn validate(&self, _token: &str) -> Result<UserRoles> {
+        Ok(UserRoles {
+            user_id: "alice".to_string(),
+            roles: vec!["admin".to_string()],
+            is_admin: true,
+        })
+    }
+
+    async fn check_permission(&self, _token: &str, _resource: &str, _action: &str) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+fn build_app(audit_store: Arc<InMemoryAuditStore>) -> axum::Router {
+    TestApp::builder()
+        .admin(|state| {
+            state.audit_store = audit_store;
+            state.rbac = Arc::new(AliceRbac);
+        })
+        .server(|server| server.with_workspace_store(Arc::new(WorkspaceStore::new())))
+        .build()
+        .router()
+}
+
+fn request(method: &str, uri: &str, body: Option<serde_json::Value>) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::AUTHORIZATION, "Bearer alice-token")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        )
+        .header("x-session-id", "sess-7")
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+            [127, 0, 0, 1],
+            12345,
+        ))))
+        .body(match body {
+            Some(body) => Body::from(body.to_string()),
+            None => Body::empty(),
+        })
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_admin_audit_entries_carry_caller_and_trace() {
+    let audit_store = Arc::new(InMemoryAuditStore::new());
+    let app = build_app(audit_store.clone());
+
+    let response = app
+        .clone()
+        .oneshot(request(
+            "POST",
+            "/v1/admin/workspaces/templates",
+            Some(serde_json::json!({ "name": "data-team", "tools": ["read_file"] })),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(
+        response.headers()["x-trace-id"],
+        "4bf92f3577b34da6a3ce929d0e0e4736"
+    );
+
+    let entries = audit_store
+        .query(AuditFilter {
+            action: Some("PUT_WORKSPACE_TEMPLATE".into()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].user_id, "alice");
+    assert_eq!(
+        entries[0].trace_id.as_deref(),
+        Some("4bf92f3577b34da6a3ce929d0e0e4736")
+    );
+    assert_eq!(entries[0].session_id.as_deref(), Some("sess-7"));
+
+    // Investigators can pivot from a trace to its audit entries.
+    let response = app
+        .clone()
+        .oneshot(request(
+            "GET",
+            "/v1/admin/audit?trace_id=4bf92f3577b34da6a3ce929d0e0e4736",
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let entries: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(entries.as_array().unwrap().len(), 1);
+    assert_eq!(entries[0]["action"], "PUT_WORKSPACE_TEMPLATE");
+}
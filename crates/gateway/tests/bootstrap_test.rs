@@ -0,0 +1,131 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use multi_agent_gateway::bootstrap::Bootstrap;
+use multi_agent_gateway::routing_policy::RoutingPolicyStore;
+use multi_agent_gateway::workspaces::WorkspaceStore;
+use multi_agent_testkit::TestApp;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+fn build_app() -> TestApp {
+    TestApp::builder()
+        .server(|server| {
+            server
+                .with_routing_policy_store(Arc::new(RoutingPolicyStore::new()))
+                .with_workspace_store(Arc::new(WorkspaceStore::new()))
+        })
+        .build()
+}
+
+async fn post_bootstrap(
+    app: &axum::Router,
+    query: &str,
+    body: &str,
+) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("/v1/admin/bootstrap{}", query))
+        .header(header::AUTHORIZATION, "Bearer admin")
+        .header(header::CONTENT_TYPE, "application/yaml")
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+            [127, 0, 0, 1],
+            12345,
+        ))))
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or_default())
+}
+
+const BOOTSTRAP: &str = r#"
+providers:
+  - id: openai-main
+    vendor: openai
+    model_id: gpt-4o
+    base_url: https://api.openai.com/v1
+    api_key: sk-test
+routing:
+  version: 1.0.0
+  rules:
+    - id: ops-channel
+      scope: channel
+      scope_value: ops
+      target: { type: complex_mission, payload: { goal_hint: operations } }
+      priority: 10
+personas:
+  - name: analyst
+    tools: [read_file]
+workspaces:
+  - id: analytics
+    persona: analyst
+"#;
+
+fn actions(plan: &serde_json::Value) -> Vec<String> {
+    plan["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| format!("{} {} {}", c["action"], c["kind"], c["id"]).replace('"', ""))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_bootstrap_plan_apply_and_converge() {
+    let test_app = build_app();
+    let state = test_app.admin.clone();
+    let app = test_app.router();
+
+    let (status, plan) = post_bootstrap(&app, "?dry_run=true", BOOTSTRAP).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(plan["applied"], false);
+    assert_eq!(
+        actions(&plan),
+        vec![
+            "create provider openai-main",
+            "create routing 1.0.0",
+            "create persona analyst",
+            "create workspace analytics",
+        ]
+    );
+    assert!(state.providers.read().await.is_empty());
+
+    let (status, plan) = post_bootstrap(&app, "", BOOTSTRAP).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(plan["applied"], true);
+    assert_eq!(state.providers.read().await[0].id, "openai-main");
+    assert_eq!(
+        state
+            .secrets
+            .retrieve("api_key:openai-main")
+            .await
+            .unwrap()
+            .as_deref(),
+        Some("sk-test")
+    );
+
+    // Applying the same file again is a no-op
+    let (_, plan) = post_bootstrap(&app, "", BOOTSTRAP).await;
+    assert!(actions(&plan).is_empty());
+
+    // Drift is reported field by field
+    let changed = BOOTSTRAP.replace("model_id: gpt-4o", "model_id: gpt-4o-mini");
+    let (_, plan) = post_bootstrap(&app, "?dry_run=true", &changed).await;
+    assert_eq!(actions(&plan), vec!["update provider openai-main"]);
+    assert_eq!(
+        plan["changes"][0]["fields"],
+        serde_json::json!(["model_id"])
+    );
+}
+
+#[test]
+fn test_example_bootstrap_parses() {
+    let bootstrap = Bootstrap::load("../../config/bootstrap.example.yaml").unwrap();
+    assert_eq!(bootstrap.providers[0].id, "openai-main");
+    assert_eq!(bootstrap.workspaces[0].persona, "analyst");
+}
@@ -0,0 +1,65 @@
+use axum::http::StatusCode;
+use multi_agent_core::traits::SemanticCache;
+use multi_agent_gateway::InMemorySemanticCache;
+use multi_agent_testkit::TestApp;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn test_cache_purge_and_workspace_switch() {
+    let llm_client = Arc::new(multi_agent_model_gateway::MockLlmClient::new("dummy"));
+    let cache = Arc::new(InMemorySemanticCache::new(llm_client));
+    cache
+        .set("w1", "s1", "What is Rust?", "A language")
+        .await
+        .unwrap();
+    cache
+        .set("w1", "s2", "What is Go?", "A language")
+        .await
+        .unwrap();
+    cache
+        .set("w2", "s1", "What is Rust?", "A language")
+        .await
+        .unwrap();
+    let app = TestApp::builder().cache(cache.clone()).build();
+
+    let response = app.get("/v1/admin/cache?workspace=w1").admin().send().await;
+    assert_eq!(response.status, StatusCode::OK);
+    let body = response.json();
+    assert_eq!(body["entries"].as_array().unwrap().len(), 2);
+    assert!(body.to_string().find("What is").is_none());
+
+    let hash = InMemorySemanticCache::query_hash("what is rust?");
+    let response = app
+        .delete(&format!("/v1/admin/cache?workspace=w1&query_hash={}", hash))
+        .admin()
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.json()["purged"], 1);
+    assert!(cache
+        .get("w1", "s1", "What is Rust?")
+        .await
+        .unwrap()
+        .is_none());
+    assert!(cache
+        .get("w2", "s1", "What is Rust?")
+        .await
+        .unwrap()
+        .is_some());
+
+    let response = app
+        .put("/v1/admin/cache/workspaces/w2")
+        .admin()
+        .json(&serde_json::json!({ "enabled": false }))
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert!(cache
+        .get("w2", "s1", "What is Rust?")
+        .await
+        .unwrap()
+        .is_none());
+    let body = app.get("/v1/admin/cache").admin().send().await.json();
+    assert_eq!(body["disabled_workspaces"], serde_json::json!(["w2"]));
+    assert_eq!(body["entries"].as_array().unwrap().len(), 1);
+}
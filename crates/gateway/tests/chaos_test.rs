@@ -0,0 +1,126 @@
+#![cfg(feature = "chaos")]
+
+use async_trait::async_trait;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use multi_agent_core::config::{ChaosConfig, FaultConfig};
+use multi_agent_core::traits::Controller;
+use multi_agent_core::types::{AgentResult, UserIntent};
+use multi_agent_testkit::TestApp;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+/// Counts the missions it runs.
+struct CountingController {
+    runs: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Controller for CountingController {
+    async fn execute(
+        &self,
+        _intent: UserIntent,
+        _trace_id: String,
+    ) -> multi_agent_core::Result<AgentResult> {
+        self.runs.fetch_add(1, Ordering::SeqCst);
+        Ok(AgentResult::Text("done".to_string()))
+    }
+    async fn resume(
+        &self,
+        _session_id: &str,
+        _user_id: Option<&str>,
+    ) -> multi_agent_core::Result<AgentResult> {
+        unimplemented!()
+    }
+    async fn cancel(&self, _session_id: &str) -> multi_agent_core::Result<()> {
+        Ok(())
+    }
+}
+
+fn build_app(http: FaultConfig, runs: Arc<AtomicUsize>) -> axum::Router {
+    TestApp::builder()
+        .config(|config| {
+            config.chaos = ChaosConfig {
+                enabled: true,
+                seed: Some(1),
+                http,
+                ..Default::default()
+            }
+        })
+        .controller(Arc::new(CountingController { runs }))
+        .build()
+        .router()
+}
+
+fn request(method: &str, uri: &str, body: Body) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/json")
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+            [127, 0, 0, 1],
+            12345,
+        ))))
+        .body(body)
+        .unwrap()
+}
+
+fn chat_request() -> Request<Body> {
+    request(
+        "POST",
+        "/v1/chat",
+        Body::from(
+            serde_json::json!({ "message": "Summarize the deployment logs", "cache": "bypass" })
+                .to_string(),
+        ),
+    )
+}
+
+#[tokio::test]
+async fn test_injected_errors_fail_before_the_handler() {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let app = build_app(
+        FaultConfig {
+            error_rate: 1.0,
+            only: vec!["/v1/chat".to_string()],
+            ..Default::default()
+        },
+        runs.clone(),
+    );
+
+    let response = app.clone().oneshot(chat_request()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(body["error"].as_str().unwrap().starts_with("chaos:"));
+    assert_eq!(runs.load(Ordering::SeqCst), 0);
+
+    // Paths outside `only` are untouched
+    let health = app
+        .oneshot(request("GET", "/health", Body::empty()))
+        .await
+        .unwrap();
+    assert_eq!(health.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_partial_failures_run_the_handler() {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let app = build_app(
+        FaultConfig {
+            partial_rate: 1.0,
+            only: vec!["/v1/chat".to_string()],
+            ..Default::default()
+        },
+        runs.clone(),
+    );
+
+    let response = app.oneshot(chat_request()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+}
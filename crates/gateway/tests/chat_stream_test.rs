@@ -0,0 +1,129 @@
+use async_trait::async_trait;
+use axum::http::{header, StatusCode};
+use multi_agent_core::config::AppConfig;
+use multi_agent_core::traits::Controller;
+use multi_agent_core::types::{AgentResult, AgentStreamEvent, UserIntent};
+use multi_agent_testkit::TestApp;
+use std::sync::Arc;
+
+/// Reports one step of each kind, then answers.
+struct SteppingController;
+
+#[async_trait]
+impl Controller for SteppingController {
+    async fn execute(
+        &self,
+        _intent: UserIntent,
+        _trace_id: String,
+    ) -> multi_agent_core::Result<AgentResult> {
+        AgentStreamEvent::Thought {
+            content: "Check the disk".into(),
+        }
+        .emit();
+        AgentStreamEvent::ToolCall {
+            tool: "df".into(),
+            args: serde_json::json!({"path": "/"}),
+        }
+        .emit();
+        AgentStreamEvent::Observation {
+            tool: Some("df".into()),
+            content: "42% used".into(),
+        }
+        .emit();
+        Ok(AgentResult::Text("The disk is 42% full".to_string()))
+    }
+    async fn resume(
+        &self,
+        _session_id: &str,
+        _user_id: Option<&str>,
+    ) -> multi_agent_core::Result<AgentResult> {
+        unimplemented!()
+    }
+    async fn cancel(&self, _session_id: &str) -> multi_agent_core::Result<()> {
+        Ok(())
+    }
+}
+
+fn build_app(configure: impl FnOnce(&mut AppConfig)) -> TestApp {
+    TestApp::builder()
+        .config(configure)
+        .controller(Arc::new(SteppingController))
+        .build()
+}
+
+/// `(event, data)` pairs of an SSE body.
+fn parse_sse(body: &str) -> Vec<(String, serde_json::Value)> {
+    body.split("\n\n")
+        .filter_map(|frame| {
+            let event = frame.lines().find_map(|l| l.strip_prefix("event: "))?;
+            let data = frame.lines().find_map(|l| l.strip_prefix("data: "))?;
+            Some((event.to_string(), serde_json::from_str(data).unwrap()))
+        })
+        .collect()
+}
+
+fn chat_body() -> serde_json::Value {
+    serde_json::json!({
+        "message": "Investigate why the disk on the build server keeps filling up and summarize the cause",
+        "cache": "bypass",
+    })
+}
+
+#[tokio::test]
+async fn test_chat_stream_sends_steps_then_answer() {
+    let app = build_app(|_| {});
+    let response = app
+        .post("/v1/agent/chat/stream")
+        .admin()
+        .json(&chat_body())
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.headers[header::CONTENT_TYPE], "text/event-stream");
+
+    let events = parse_sse(&response.text());
+    let names: Vec<&str> = events.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["thought", "tool_call", "observation", "final"]);
+
+    assert_eq!(events[1].1["tool"], "df");
+    assert_eq!(events[1].1["args"]["path"], "/");
+    assert_eq!(events[2].1["content"], "42% used");
+    let answer = &events[3].1;
+    assert_eq!(answer["cached"], false);
+    assert_eq!(answer["result"]["type"], "Text");
+    assert_eq!(answer["result"]["payload"], "The disk is 42% full");
+}
+
+#[tokio::test]
+async fn test_chat_stream_applies_presentation_policy() {
+    let app = build_app(|config| {
+        config.governance.presentation.user_visible = vec!["tool_call".into()];
+    });
+    let response = app
+        .post("/v1/agent/chat/stream")
+        .bearer("user-token")
+        .json(&chat_body())
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+
+    let events = parse_sse(&response.text());
+    let names: Vec<&str> = events.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["tool_call", "final"]);
+    assert_eq!(events[0].1["tool"], "df");
+    assert_eq!(events[0].1["args"], "[REDACTED]");
+}
+
+#[tokio::test]
+async fn test_chat_stream_requires_authentication() {
+    let app = build_app(|_| {});
+    let response = app
+        .post("/v1/agent/chat/stream")
+        .json(&chat_body())
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+
+    let legacy = app.post("/v1/chat/stream").json(&chat_body()).send().await;
+    assert_eq!(legacy.status, StatusCode::NOT_FOUND);
+}
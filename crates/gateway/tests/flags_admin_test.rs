@@ -0,0 +1,57 @@
+use axum::http::StatusCode;
+use multi_agent_core::config::FeatureFlagDefinition;
+use multi_agent_governance::FeatureFlagService;
+use multi_agent_testkit::TestApp;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn test_flag_overrides_apply_without_restart() {
+    let flags = Arc::new(FeatureFlagService::new(
+        [(
+            "delegation".to_string(),
+            FeatureFlagDefinition {
+                enabled: true,
+                ..Default::default()
+            },
+        )]
+        .into_iter()
+        .collect(),
+    ));
+    let server_flags = flags.clone();
+    let app = TestApp::builder()
+        .server(|server| server.with_feature_flags(server_flags))
+        .build();
+
+    let response = app.get("/v1/admin/flags").admin().send().await;
+    assert_eq!(response.status, StatusCode::OK);
+    let body = response.json();
+    assert_eq!(body[0]["name"], "delegation");
+    assert_eq!(body[0]["enabled"], true);
+
+    // Roll delegation back for one tenant only.
+    let response = app
+        .put("/v1/admin/flags/delegation")
+        .admin()
+        .json(&serde_json::json!({ "enabled": false, "workspace_id": "acme" }))
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.json()["enabled"], false);
+    assert!(!flags.is_enabled("delegation", Some("acme")));
+    assert!(flags.is_enabled("delegation", Some("beta")));
+
+    let response = app
+        .delete("/v1/admin/flags/delegation?workspace_id=acme")
+        .admin()
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::NO_CONTENT);
+    assert!(flags.is_enabled("delegation", Some("acme")));
+
+    let response = app
+        .delete("/v1/admin/flags/delegation")
+        .admin()
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+}
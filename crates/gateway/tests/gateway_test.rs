@@ -36,6 +36,58 @@ impl Controller for MockController {
     }
 }
 
+/// Answers every request with a table.
+struct TableController;
+
+#[async_trait]
+impl Controller for TableController {
+    async fn execute(
+        &self,
+        _intent: UserIntent,
+        _trace_id: String,
+    ) -> multi_agent_core::Result<AgentResult> {
+        Ok(AgentResult::Table {
+            columns: vec!["region".into(), "revenue".into()],
+            rows: vec![vec![json!("EMEA"), json!(1200)]],
+            caption: Some("Q3".into()),
+        })
+    }
+    async fn resume(
+        &self,
+        _session_id: &str,
+        _user_id: Option<&str>,
+    ) -> multi_agent_core::Result<AgentResult> {
+        Ok(AgentResult::Text("Resumed".to_string()))
+    }
+    async fn cancel(&self, _session_id: &str) -> multi_agent_core::Result<()> {
+        Ok(())
+    }
+}
+
+/// Answers with text larger than the default response ceiling.
+struct LargeTextController;
+
+#[async_trait]
+impl Controller for LargeTextController {
+    async fn execute(
+        &self,
+        _intent: UserIntent,
+        _trace_id: String,
+    ) -> multi_agent_core::Result<AgentResult> {
+        Ok(AgentResult::Text("x".repeat(300 * 1024)))
+    }
+    async fn resume(
+        &self,
+        _session_id: &str,
+        _user_id: Option<&str>,
+    ) -> multi_agent_core::Result<AgentResult> {
+        Ok(AgentResult::Text("Resumed".to_string()))
+    }
+    async fn cancel(&self, _session_id: &str) -> multi_agent_core::Result<()> {
+        Ok(())
+    }
+}
+
 struct ConcurrencyController {
     active: AtomicUsize,
     max_active: AtomicUsize,
@@ -197,6 +249,153 @@ async fn test_chat_endpoint_with_controller() {
     assert_eq!(json["data"]["result"]["payload"], "Mock response");
 }
 
+#[tokio::test]
+async fn test_chat_cache_modes() {
+    use multi_agent_core::traits::SemanticCache;
+
+    let cache = Arc::new(MockSemanticCache::with_entries(vec![(
+        "default:default:hello",
+        "Stale response",
+    )]));
+    let server = GatewayServer::new(
+        GatewayConfig::default(),
+        Arc::new(MockRouter::complex_mission("hello")),
+        cache.clone(),
+    )
+    .with_controller(Arc::new(MockController));
+    let app = server.build_router();
+
+    let chat = |body: Value| {
+        let app = app.clone();
+        async move {
+            let response = app
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri("/v1/chat")
+                        .header("Content-Type", "application/json")
+                        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+                            [127, 0, 0, 1],
+                            12345,
+                        ))))
+                        .body(Body::from(body.to_string()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<Value>(&body).unwrap()["data"].clone()
+        }
+    };
+
+    // Bypass neither reads nor overwrites the cached entry
+    let data = chat(json!({"message": "hello", "cache": "bypass"})).await;
+    assert_eq!(data["cached"], false);
+    assert_eq!(data["result"]["payload"], "Mock response");
+    let data = chat(json!({"message": "hello"})).await;
+    assert_eq!(data["cached"], true);
+    assert_eq!(data["result"]["payload"], "Stale response");
+
+    // Refresh recomputes and replaces it
+    let data = chat(json!({"message": "hello", "cache": "refresh"})).await;
+    assert_eq!(data["cached"], false);
+    assert_eq!(
+        cache.get("default", "default", "hello").await.unwrap(),
+        Some("Mock response".to_string())
+    );
+    let data = chat(json!({"message": "hello", "cache": "prefer"})).await;
+    assert_eq!(data["cached"], true);
+    assert_eq!(data["result"]["payload"], "Mock response");
+}
+
+#[tokio::test]
+async fn test_chat_endpoint_returns_structured_result() {
+    let router = Arc::new(MockRouter::complex_mission("revenue by region"));
+    let cache = Arc::new(MockSemanticCache::new());
+    let server = GatewayServer::new(GatewayConfig::default(), router, cache)
+        .with_controller(Arc::new(TableController));
+
+    let response = server
+        .build_router()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/chat")
+                .header("Content-Type", "application/json")
+                .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+                    [127, 0, 0, 1],
+                    12345,
+                ))))
+                .body(Body::from(json!({"message": "revenue?"}).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    let result = &json["data"]["result"];
+    assert_eq!(result["type"], "Table");
+    assert_eq!(result["payload"]["columns"], json!(["region", "revenue"]));
+    assert_eq!(result["payload"]["rows"][0], json!(["EMEA", 1200]));
+    assert_eq!(result["payload"]["caption"], "Q3");
+}
+
+#[tokio::test]
+async fn test_chat_oversized_result_returned_by_reference() {
+    use multi_agent_core::traits::ArtifactStore;
+    use multi_agent_core::types::RefId;
+
+    let store = Arc::new(multi_agent_store::InMemoryStore::new());
+    let router = Arc::new(MockRouter::complex_mission("dump the logs"));
+    let cache = Arc::new(MockSemanticCache::new());
+    let server = GatewayServer::new(GatewayConfig::default(), router, cache)
+        .with_controller(Arc::new(LargeTextController))
+        .with_artifact_store(store.clone());
+
+    let response = server
+        .build_router()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/chat")
+                .header("Content-Type", "application/json")
+                .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+                    [127, 0, 0, 1],
+                    12345,
+                ))))
+                .body(Body::from(
+                    json!({"message": "dump the logs", "user_id": "alice"}).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(body.len() < 16 * 1024);
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    let result = &json["data"]["result"];
+    assert_eq!(result["type"], "Reference");
+    assert_eq!(result["payload"]["size"], 300 * 1024);
+    assert_eq!(result["payload"]["mime_type"], "text/plain");
+    assert_eq!(result["payload"]["preview"].as_str().unwrap().len(), 2000);
+
+    let ref_id = RefId::from_string(result["payload"]["ref_id"].as_str().unwrap());
+    let stored = store.load(&ref_id).await.unwrap().unwrap();
+    assert_eq!(stored.len(), 300 * 1024);
+    let meta = store.metadata(&ref_id).await.unwrap().unwrap();
+    assert_eq!(meta.owner_user_id.as_deref(), Some("alice"));
+}
+
 #[tokio::test]
 async fn test_gateway_schema_endpoint() {
     let config = GatewayConfig::default();
@@ -230,6 +429,42 @@ async fn test_gateway_schema_endpoint() {
     assert!(json["schema"]["$schema"].is_string());
 }
 
+#[tokio::test]
+async fn test_event_schema_endpoint() {
+    let config = GatewayConfig::default();
+    let router = Arc::new(MockRouter::complex_mission("test"));
+    let cache = Arc::new(MockSemanticCache::new());
+    let app = GatewayServer::new(config, router, cache).build_router();
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/v1/system/events/schema")
+                .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+                    [127, 0, 0, 1],
+                    12345,
+                ))))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["version"], 1);
+    let events = json["events"].as_array().unwrap();
+    let tool_finished = events
+        .iter()
+        .find(|e| e["event_type"] == "TOOL_EXEC_FINISHED")
+        .unwrap();
+    assert!(tool_finished["schema"]["properties"]["tool_name"].is_object());
+}
+
 #[tokio::test]
 async fn test_webhook_idempotency_replay_and_conflict() {
     let config = GatewayConfig::default();
@@ -347,3 +582,48 @@ async fn test_scheduler_serializes_same_session() {
     assert_eq!(r2.unwrap().status(), StatusCode::OK);
     assert_eq!(controller.max_active(), 1);
 }
+
+#[tokio::test]
+async fn test_security_headers_and_csrf() {
+    let config = GatewayConfig::default();
+    let router = Arc::new(MockRouter::complex_mission("test"));
+    let cache = Arc::new(MockSemanticCache::new());
+    let app = GatewayServer::new(config, router, cache).build_router();
+    let loopback = axum::extract::ConnectInfo(std::net::SocketAddr::from(([127, 0, 0, 1], 12345)));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/health")
+                .extension(loopback)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers();
+    assert!(headers.contains_key("content-security-policy"));
+    assert_eq!(headers["x-frame-options"], "DENY");
+    assert_eq!(headers["x-content-type-options"], "nosniff");
+    assert!(!headers.contains_key("strict-transport-security"));
+
+    // A cookie-carrying POST from another site is rejected
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/intent")
+                .header("Content-Type", "application/json")
+                .header("Host", "localhost:3000")
+                .header("Origin", "https://evil.example.com")
+                .header("Cookie", "admin_token=secret")
+                .extension(loopback)
+                .body(Body::from(json!({"message": "test"}).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
@@ -26,6 +26,7 @@ async fn test_v0_8_features_integration() {
         rbac: rbac.clone(),
         metrics: metrics_handle.clone(),
         mcp_registry: Arc::new(multi_agent_skills::McpRegistry::new()),
+        openapi_registry: None,
         providers: Arc::new(tokio::sync::RwLock::new(Vec::new())),
         provider_store: None,
         secrets: Arc::new(multi_agent_governance::AesGcmSecretsManager::new(None)),
@@ -36,6 +37,14 @@ async fn test_v0_8_features_integration() {
         network_policy: Arc::new(tokio::sync::RwLock::new(
             multi_agent_governance::network::NetworkPolicy::default(),
         )),
+        audit_presets: Arc::new(multi_agent_admin::audit_view::AuditPresetStore::new()),
+        audit_anchorer: None,
+        guardrails: Arc::new(multi_agent_governance::RouteGuardrails::default()),
+        tool_analytics: None,
+        endpoint_registry: None,
+        active_client: None,
+        provider_sync: None,
+        model_canary: None,
     });
 
     // Initialize Gateway
@@ -46,6 +55,7 @@ async fn test_v0_8_features_integration() {
         enable_tracing: false,
         allowed_origins: vec![],
         tls: Default::default(),
+        unix_socket: None,
     };
 
     // Mocks for Gateway deps
@@ -173,6 +183,8 @@ async fn test_v0_8_features_integration() {
         metadata: Some(serde_json::json!({"foo": "bar"})),
         previous_hash: None,
         hash: None,
+        trace_id: None,
+        session_id: None,
     };
     audit_store.log(entry).await.unwrap();
 
@@ -0,0 +1,123 @@
+use async_trait::async_trait;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use multi_agent_core::config::RouteConcurrency;
+use multi_agent_core::traits::Controller;
+use multi_agent_core::types::{AgentResult, UserIntent};
+use multi_agent_testkit::TestApp;
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tower::ServiceExt;
+
+/// Holds each mission until released.
+struct GatedController {
+    started: Arc<Notify>,
+    release: Arc<Notify>,
+}
+
+#[async_trait]
+impl Controller for GatedController {
+    async fn execute(
+        &self,
+        _intent: UserIntent,
+        _trace_id: String,
+    ) -> multi_agent_core::Result<AgentResult> {
+        self.started.notify_one();
+        self.release.notified().await;
+        Ok(AgentResult::Text("done".to_string()))
+    }
+    async fn resume(
+        &self,
+        _session_id: &str,
+        _user_id: Option<&str>,
+    ) -> multi_agent_core::Result<AgentResult> {
+        unimplemented!()
+    }
+    async fn cancel(&self, _session_id: &str) -> multi_agent_core::Result<()> {
+        Ok(())
+    }
+}
+
+fn build_app(controller: GatedController) -> axum::Router {
+    TestApp::builder()
+        .config(|config| {
+            config.gateway.concurrency.routes.insert(
+                "chat".to_string(),
+                RouteConcurrency {
+                    prefixes: vec!["/v1/chat".to_string()],
+                    max_in_flight: 1,
+                    max_queued: 0,
+                    queue_timeout_secs: 1,
+                },
+            );
+        })
+        .controller(Arc::new(controller))
+        .build()
+        .router()
+}
+
+fn chat_request() -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/v1/chat")
+        .header(header::CONTENT_TYPE, "application/json")
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+            [127, 0, 0, 1],
+            12345,
+        ))))
+        .body(Body::from(
+            serde_json::json!({ "message": "Summarize the deployment logs", "cache": "bypass" })
+                .to_string(),
+        ))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_requests_over_route_capacity_are_shed() {
+    let started = Arc::new(Notify::new());
+    let release = Arc::new(Notify::new());
+    let app = build_app(GatedController {
+        started: started.clone(),
+        release: release.clone(),
+    });
+
+    let first = tokio::spawn(app.clone().oneshot(chat_request()));
+    started.notified().await;
+
+    let shed = app.clone().oneshot(chat_request()).await.unwrap();
+    assert_eq!(shed.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(shed.headers()[header::RETRY_AFTER], "5");
+    let body = axum::body::to_bytes(shed.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["route"], "chat");
+
+    // Other route groups are unaffected
+    let health = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/health")
+                .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+                    [127, 0, 0, 1],
+                    12345,
+                ))))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(health.status(), StatusCode::OK);
+
+    release.notify_one();
+    assert_eq!(first.await.unwrap().unwrap().status(), StatusCode::OK);
+
+    // The slot is free again
+    let next = tokio::spawn(app.oneshot(chat_request()));
+    started.notified().await;
+    release.notify_one();
+    assert_eq!(next.await.unwrap().unwrap().status(), StatusCode::OK);
+}
@@ -0,0 +1,125 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use multi_agent_core::traits::{KnowledgeEntry, KnowledgeStore};
+use multi_agent_store::InMemoryKnowledgeStore;
+use multi_agent_testkit::TestApp;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+fn build_app(store: Arc<dyn KnowledgeStore>) -> axum::Router {
+    TestApp::builder()
+        .server(|server| server.with_knowledge_store(store))
+        .build()
+        .router()
+}
+
+fn entry(id: &str, user_id: &str, summary: &str, tags: &[&str]) -> KnowledgeEntry {
+    KnowledgeEntry {
+        id: id.to_string(),
+        summary: summary.to_string(),
+        source_task: "Investigate runtimes".to_string(),
+        user_id: user_id.to_string(),
+        session_id: format!("session-{}", id),
+        embedding: vec![],
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        created_at: 1_700_000_000,
+        content_hash: None,
+        source_url: Some(format!("https://docs.example.com/{}", id)),
+        refreshed_at: None,
+        hits: 0,
+    }
+}
+
+async fn search(app: &axum::Router, query: &str, token: &str) -> (StatusCode, serde_json::Value) {
+    let mut request = Request::builder()
+        .uri(format!("/v1/agent/memory/search?{}", query))
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+            [127, 0, 0, 1],
+            12345,
+        ))));
+    if !token.is_empty() {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let response = app
+        .clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or_default())
+}
+
+async fn seeded_app() -> axum::Router {
+    let store = Arc::new(InMemoryKnowledgeStore::new());
+    store
+        .store(entry(
+            "mine",
+            "anonymous",
+            "Tokio uses a work-stealing scheduler.",
+            &["workspace:alpha"],
+        ))
+        .await
+        .unwrap();
+    store
+        .store(entry(
+            "theirs",
+            "bob",
+            "Tokio tuning notes from another team.",
+            &[],
+        ))
+        .await
+        .unwrap();
+    build_app(store)
+}
+
+#[tokio::test]
+async fn test_memory_search_is_scoped_to_caller() {
+    let app = seeded_app().await;
+
+    // NoOpRbacConnector maps any non-admin token to "anonymous"
+    let (status, body) = search(&app, "q=tokio", "user-token").await;
+    assert_eq!(status, StatusCode::OK);
+    let results = body["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["id"], "mine");
+    assert!(results[0]["snippet"]
+        .as_str()
+        .unwrap()
+        .contains("work-stealing"));
+    assert_eq!(results[0]["provenance"]["session_id"], "session-mine");
+    assert_eq!(
+        results[0]["provenance"]["source_url"],
+        "https://docs.example.com/mine"
+    );
+
+    // Workspace filter
+    let (_, body) = search(&app, "q=tokio&workspace=beta", "user-token").await;
+    assert!(body["results"].as_array().unwrap().is_empty());
+
+    // Non-admins cannot widen the scope
+    let (status, _) = search(&app, "q=tokio&user_id=bob", "user-token").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = search(&app, "q=%20", "user-token").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_admin_can_search_other_users() {
+    let app = seeded_app().await;
+
+    let (status, body) = search(&app, "q=tokio&user_id=bob", "admin").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["results"][0]["id"], "theirs");
+
+    let (_, body) = search(&app, "q=tokio&user_id=*", "admin").await;
+    assert_eq!(body["results"].as_array().unwrap().len(), 2);
+
+    let (status, _) = search(&app, "q=tokio", "").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,109 @@
+use async_trait::async_trait;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use multi_agent_core::config::ModerationConfig;
+use multi_agent_core::Result;
+use multi_agent_gateway::{DefaultRouter, GatewayConfig, GatewayServer, InMemorySemanticCache};
+use multi_agent_governance::{ModerationProvider, Moderator};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+/// Scores any text mentioning "attack" as violent.
+struct KeywordModeration;
+
+#[async_trait]
+impl ModerationProvider for KeywordModeration {
+    fn name(&self) -> &str {
+        "keyword"
+    }
+
+    async fn moderate(&self, text: &str) -> Result<BTreeMap<String, f32>> {
+        let score = if text.contains("attack") { 0.95 } else { 0.0 };
+        Ok(BTreeMap::from([("violence".to_string(), score)]))
+    }
+}
+
+fn build_app(logs: tokio::sync::broadcast::Sender<String>) -> axum::Router {
+    let config = GatewayConfig {
+        host: "127.0.0.1".to_string(),
+        port: 0,
+        enable_cors: false,
+        enable_tracing: false,
+        allowed_origins: vec![],
+        tls: Default::default(),
+        unix_socket: None,
+    };
+    let router = Arc::new(DefaultRouter::new());
+    let llm_client = Arc::new(multi_agent_model_gateway::MockLlmClient::new("dummy"));
+    let cache = Arc::new(InMemorySemanticCache::new(llm_client));
+    let moderator = Moderator::new(Arc::new(KeywordModeration), &ModerationConfig::default());
+
+    GatewayServer::new(config, router, cache)
+        .with_logs_channel(logs)
+        .with_moderator(Arc::new(moderator))
+        .build_router()
+}
+
+async fn chat(app: &axum::Router, message: &str) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/v1/chat")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::AUTHORIZATION, "Bearer admin")
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+            [127, 0, 0, 1],
+            12345,
+        ))))
+        .body(Body::from(
+            serde_json::json!({ "message": message, "user_id": "bob" }).to_string(),
+        ))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or_default())
+}
+
+fn moderation_events(
+    logs: &mut tokio::sync::broadcast::Receiver<String>,
+) -> Vec<serde_json::Value> {
+    let mut events = Vec::new();
+    while let Ok(line) = logs.try_recv() {
+        let event: serde_json::Value = serde_json::from_str(&line).unwrap();
+        if event["event_type"] == "MODERATION_EVALUATED" {
+            events.push(event);
+        }
+    }
+    events
+}
+
+#[tokio::test]
+async fn test_blocked_request_is_rejected_and_recorded() {
+    let (logs, mut rx) = tokio::sync::broadcast::channel(64);
+    let app = build_app(logs);
+
+    let (status, body) = chat(&app, "Plan an attack on the server room").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["data"]["code"], "FORBIDDEN");
+    assert_eq!(body["data"]["message"], "Blocked by content moderation");
+    assert_eq!(
+        body["data"]["details"]["categories"],
+        serde_json::json!(["violence"])
+    );
+
+    let events = moderation_events(&mut rx);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["actor"], "bob");
+    assert_eq!(events[0]["payload"]["action"], "BLOCK");
+    assert_eq!(events[0]["payload"]["provider"], "keyword");
+
+    let (status, _) = chat(&app, "Summarize the quarterly report").await;
+    assert_ne!(status, StatusCode::FORBIDDEN);
+    let events = moderation_events(&mut rx);
+    assert_eq!(events[0]["payload"]["action"], "ALLOW");
+}
@@ -2,8 +2,10 @@ use axum::{
     body::Body,
     http::{Request, StatusCode},
 };
+use multi_agent_core::config::{ForwardedHeader, RateLimitConfig};
 use multi_agent_core::mocks::{MockRouter, MockSemanticCache};
 use multi_agent_gateway::{GatewayConfig, GatewayServer};
+use multi_agent_testkit::TestApp;
 use std::sync::Arc;
 use tower::ServiceExt;
 
@@ -57,3 +59,96 @@ async fn test_rate_limiting() {
 
     assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
 }
+
+fn build_app(rate_limit: RateLimitConfig) -> axum::Router {
+    TestApp::builder()
+        .config(|config| config.gateway.rate_limit = rate_limit)
+        .build()
+        .router()
+}
+
+async fn health(app: &axum::Router, peer: [u8; 4], headers: &[(&str, &str)]) -> StatusCode {
+    let mut request = Request::builder()
+        .uri("/health")
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+            peer, 12345,
+        ))));
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    app.clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn test_rate_limiting_behind_trusted_proxy() {
+    let app = build_app(RateLimitConfig {
+        trusted_proxies: vec!["10.0.0.0/8".to_string()],
+        forwarded_header: ForwardedHeader::XForwardedFor,
+        key_by_identity: false,
+    });
+
+    // Clients behind the load balancer each get their own budget
+    for _ in 0..30 {
+        let status = health(
+            &app,
+            [10, 0, 0, 1],
+            &[("x-forwarded-for", "203.0.113.5, 10.0.0.2")],
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    let limited = [("x-forwarded-for", "203.0.113.5")];
+    assert_eq!(
+        health(&app, [10, 0, 0, 1], &limited).await,
+        StatusCode::TOO_MANY_REQUESTS
+    );
+    let other = [("x-forwarded-for", "203.0.113.6")];
+    assert_eq!(health(&app, [10, 0, 0, 1], &other).await, StatusCode::OK);
+
+    // A Forwarded header passed through from the client is not believed
+    let spoofed = [
+        ("x-forwarded-for", "203.0.113.5"),
+        ("forwarded", "for=198.51.100.200"),
+    ];
+    assert_eq!(
+        health(&app, [10, 0, 0, 1], &spoofed).await,
+        StatusCode::TOO_MANY_REQUESTS
+    );
+
+    // Untrusted peers cannot pick a fresh address per request
+    for i in 0..30 {
+        let spoofed = format!("198.51.100.{}", i);
+        let status = health(&app, [192, 0, 2, 1], &[("x-forwarded-for", &spoofed)]).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    let spoofed = [("x-forwarded-for", "198.51.100.99")];
+    assert_eq!(
+        health(&app, [192, 0, 2, 1], &spoofed).await,
+        StatusCode::TOO_MANY_REQUESTS
+    );
+}
+
+#[tokio::test]
+async fn test_rate_limiting_keyed_on_identity() {
+    let app = build_app(RateLimitConfig {
+        trusted_proxies: Vec::new(),
+        forwarded_header: ForwardedHeader::default(),
+        key_by_identity: true,
+    });
+
+    for _ in 0..30 {
+        assert_eq!(health(&app, [127, 0, 0, 1], &[]).await, StatusCode::OK);
+    }
+    assert_eq!(
+        health(&app, [127, 0, 0, 1], &[]).await,
+        StatusCode::TOO_MANY_REQUESTS
+    );
+
+    // An authenticated caller at the same address has its own budget
+    let admin = [("authorization", "Bearer admin")];
+    assert_eq!(health(&app, [127, 0, 0, 1], &admin).await, StatusCode::OK);
+}
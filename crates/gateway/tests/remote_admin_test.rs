@@ -0,0 +1,196 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use multi_agent_testkit::TestApp;
+use tower::ServiceExt;
+
+const TOTP_SECRET: &str = "JBSWY3DPEHPK3PXP";
+/// Secret enrolled for a different administrator.
+const OTHER_TOTP_SECRET: &str = "KRSXG5CTMVRXEZLU";
+
+fn build_app() -> axum::Router {
+    TestApp::builder()
+        .config(|config| {
+            config.governance.remote_admin.enabled = true;
+            config.governance.remote_admin.allowed_ips = vec!["10.0.0.0/8".to_string()];
+            config.governance.remote_admin.totp_secrets =
+                [("admin", TOTP_SECRET), ("other-admin", OTHER_TOTP_SECRET)]
+                    .into_iter()
+                    .map(|(user, secret)| {
+                        (user.to_string(), secrecy::Secret::new(secret.to_string()))
+                    })
+                    .collect();
+        })
+        .build()
+        .router()
+}
+
+fn request(method: &str, uri: &str, client: [u8; 4]) -> axum::http::request::Builder {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::AUTHORIZATION, "Bearer admin")
+        .header(header::CONTENT_TYPE, "application/json")
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+            client, 40000,
+        ))))
+}
+
+async fn json(response: axum::response::Response) -> serde_json::Value {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).unwrap_or_default()
+}
+
+#[tokio::test]
+async fn test_remote_admin_requires_allowlist_and_elevation() {
+    let app = build_app();
+    let remote = [10, 0, 0, 5];
+
+    // Outside the allowlist
+    let response = app
+        .clone()
+        .oneshot(
+            request("GET", "/v1/admin/cache", [203, 0, 113, 9])
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // Allowlisted but not elevated
+    let response = app
+        .clone()
+        .oneshot(
+            request("GET", "/v1/admin/cache", remote)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert!(json(response).await["guidance"]
+        .as_str()
+        .unwrap()
+        .contains("/v1/admin/auth/elevate"));
+
+    // A wrong code is refused
+    let response = app
+        .clone()
+        .oneshot(
+            request("POST", "/v1/admin/auth/elevate", remote)
+                .body(Body::from(r#"{"code":"abcdef"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let code =
+        multi_agent_gateway::admin_access::totp_at(TOTP_SECRET, std::time::SystemTime::now())
+            .unwrap();
+    let response = app
+        .clone()
+        .oneshot(
+            request("POST", "/v1/admin/auth/elevate", remote)
+                .body(Body::from(serde_json::json!({ "code": code }).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let token = json(response).await["token"].as_str().unwrap().to_string();
+
+    // The same code cannot be used twice
+    let response = app
+        .clone()
+        .oneshot(
+            request("POST", "/v1/admin/auth/elevate", remote)
+                .body(Body::from(serde_json::json!({ "code": code }).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = app
+        .clone()
+        .oneshot(
+            request("GET", "/v1/admin/cache", remote)
+                .header("x-admin-elevation", &token)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The token is bound to the client IP
+    let response = app
+        .oneshot(
+            request("GET", "/v1/admin/cache", [10, 0, 0, 6])
+                .header("x-admin-elevation", &token)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_remote_admin_locks_out_after_failed_codes() {
+    let app = build_app();
+    let remote = [10, 0, 0, 7];
+    let elevate = |code: &str| {
+        request("POST", "/v1/admin/auth/elevate", remote)
+            .body(Body::from(serde_json::json!({ "code": code }).to_string()))
+            .unwrap()
+    };
+
+    for _ in 0..4 {
+        let response = app.clone().oneshot(elevate("000000")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+    let response = app.clone().oneshot(elevate("000000")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(response.headers().contains_key(header::RETRY_AFTER));
+
+    // Even a valid code is refused during the lockout
+    let code =
+        multi_agent_gateway::admin_access::totp_at(TOTP_SECRET, std::time::SystemTime::now())
+            .unwrap();
+    let response = app.oneshot(elevate(&code)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[tokio::test]
+async fn test_remote_admin_checks_the_callers_own_secret() {
+    let app = build_app();
+    let remote = [10, 0, 0, 8];
+    let elevate = |code: &str| {
+        request("POST", "/v1/admin/auth/elevate", remote)
+            .body(Body::from(serde_json::json!({ "code": code }).to_string()))
+            .unwrap()
+    };
+
+    // Another administrator's code does not elevate this one
+    let now = std::time::SystemTime::now();
+    let admin_codes = [
+        now - std::time::Duration::from_secs(30),
+        now,
+        now + std::time::Duration::from_secs(30),
+    ]
+    .map(|at| multi_agent_gateway::admin_access::totp_at(TOTP_SECRET, at).unwrap());
+    let other_code = multi_agent_gateway::admin_access::totp_at(OTHER_TOTP_SECRET, now).unwrap();
+    if !admin_codes.contains(&other_code) {
+        let response = app.clone().oneshot(elevate(&other_code)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    let response = app.oneshot(elevate(&admin_codes[1])).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
@@ -0,0 +1,176 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use multi_agent_core::traits::{ApprovalGate, SessionStore};
+use multi_agent_core::types::{
+    ApprovalRequest, HistoryEntry, Session, SessionStatus, TaskState, TokenUsage, ToolRiskLevel,
+};
+use multi_agent_governance::approval::ChannelApprovalGate;
+use multi_agent_store::InMemorySessionStore;
+use multi_agent_testkit::TestApp;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+fn build_app(store: Arc<dyn SessionStore>, gate: Arc<ChannelApprovalGate>) -> axum::Router {
+    TestApp::builder()
+        .admin(|state| state.session_store = Some(store))
+        .server(|server| server.with_approval_gate(gate))
+        .build()
+        .router()
+}
+
+fn session(id: &str, user_id: &str) -> Session {
+    let mut token_usage = TokenUsage::with_budget(10_000);
+    token_usage.add(800, 200);
+    Session {
+        id: id.to_string(),
+        trace_id: format!("trace-{}", id),
+        user_id: Some(user_id.to_string()),
+        workspace_id: None,
+        dry_run: false,
+        status: SessionStatus::Running,
+        history: vec![HistoryEntry {
+            role: "assistant".to_string(),
+            content: Arc::new("I should delete the stale branch.".to_string()),
+            tool_call: None,
+            timestamp: 1_700_000_020,
+        }],
+        task_state: Some(TaskState {
+            iteration: 1,
+            goal: "Clean up branches".to_string(),
+            observations: vec![],
+            pending_actions: vec![],
+            consecutive_rejections: 0,
+        }),
+        token_usage,
+        created_at: 1_700_000_000,
+        updated_at: 1_700_000_020,
+    }
+}
+
+async fn get(app: &axum::Router, uri: &str, token: &str) -> axum::response::Response {
+    let request = Request::builder()
+        .uri(uri)
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+            [127, 0, 0, 1],
+            12345,
+        ))));
+    app.clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+async fn progress(app: &axum::Router, id: &str, token: &str) -> (StatusCode, serde_json::Value) {
+    let response = get(app, &format!("/v1/agent/sessions/{}/progress", id), token).await;
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or_default())
+}
+
+#[tokio::test]
+async fn test_session_progress_reports_pending_approval() {
+    let store = Arc::new(InMemorySessionStore::new());
+    store.save(&session("mine", "anonymous")).await.unwrap();
+    store.save(&session("theirs", "bob")).await.unwrap();
+    let gate = Arc::new(ChannelApprovalGate::new(ToolRiskLevel::High));
+    let app = build_app(store, gate.clone());
+
+    // The agent blocks on an approval for the caller's session
+    let mut requests = gate.subscribe();
+    let waiting = tokio::spawn({
+        let gate = gate.clone();
+        async move {
+            gate.request_approval(&ApprovalRequest {
+                request_id: "req-1".to_string(),
+                session_id: "mine".to_string(),
+                tool_name: "delete_branch".to_string(),
+                args: serde_json::json!({"branch": "stale"}),
+                risk_level: ToolRiskLevel::High,
+                context: "Branch is merged".to_string(),
+                timeout_secs: Some(30),
+                nonce: "n1".to_string(),
+                expires_at: 1_700_000_050,
+                mandatory: false,
+            })
+            .await
+        }
+    });
+    requests.recv().await.unwrap();
+
+    let (status, body) = progress(&app, "mine", "user-token").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "Running");
+    assert_eq!(body["iteration"], 1);
+    assert_eq!(body["last_thought"], "I should delete the stale branch.");
+    assert_eq!(body["tokens"]["total"], 1000);
+    assert_eq!(body["tokens"]["remaining"], 9000);
+    assert_eq!(body["pending_approval"]["request_id"], "req-1");
+    assert_eq!(body["pending_approval"]["tool_name"], "delete_branch");
+    assert!(body["pending_approval"].get("nonce").is_none());
+    assert_eq!(body["eta"]["avg_iteration_secs"], 10.0);
+    assert_eq!(body["eta"]["remaining_iterations"], 8);
+
+    // Other users' sessions are hidden unless the caller is an admin
+    let (status, _) = progress(&app, "theirs", "user-token").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, body) = progress(&app, "theirs", "admin").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["pending_approval"].is_null());
+
+    let (status, _) = progress(&app, "missing", "admin").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    waiting.abort();
+}
+
+#[tokio::test]
+async fn test_session_watch_streams_changes_until_deleted() {
+    let store = Arc::new(InMemorySessionStore::new());
+    store.save(&session("mine", "anonymous")).await.unwrap();
+    store.save(&session("theirs", "bob")).await.unwrap();
+    let gate = Arc::new(ChannelApprovalGate::new(ToolRiskLevel::High));
+    let app = build_app(store.clone(), gate);
+
+    let response = get(&app, "/v1/agent/sessions/theirs/watch", "user-token").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = get(&app, "/v1/agent/sessions/mine/watch", "user-token").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/event-stream"
+    );
+
+    // Changes after the response starts are pushed; deletion ends the stream
+    let mut done = session("mine", "anonymous");
+    done.status = SessionStatus::Completed;
+    store.save(&done).await.unwrap();
+    store.delete("mine").await.unwrap();
+
+    let body = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        axum::body::to_bytes(response.into_body(), usize::MAX),
+    )
+    .await
+    .expect("stream ends after deletion")
+    .unwrap();
+    let events: Vec<(String, serde_json::Value)> = String::from_utf8(body.to_vec())
+        .unwrap()
+        .split("\n\n")
+        .filter_map(|event| {
+            let name = event.lines().find_map(|l| l.strip_prefix("event: "))?;
+            let data = event.lines().find_map(|l| l.strip_prefix("data: "))?;
+            Some((name.to_string(), serde_json::from_str(data).unwrap()))
+        })
+        .collect();
+    let names: Vec<&str> = events.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["progress", "progress", "deleted"]);
+    assert_eq!(events[0].1["status"], "Running");
+    assert_eq!(events[1].1["status"], "Completed");
+    assert!(events[1].1["eta"].is_null());
+}
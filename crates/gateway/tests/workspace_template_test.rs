@@ -0,0 +1,152 @@
+use axum::http::StatusCode;
+use multi_agent_gateway::workspaces::WorkspaceStore;
+use multi_agent_skills::McpRegistry;
+use multi_agent_testkit::TestApp;
+use std::sync::Arc;
+
+fn build_app(mcp_registry: Arc<McpRegistry>) -> TestApp {
+    TestApp::builder()
+        .admin(|state| state.mcp_registry = mcp_registry)
+        .server(|server| server.with_workspace_store(Arc::new(WorkspaceStore::new())))
+        .build()
+}
+
+#[tokio::test]
+async fn test_template_instantiate_and_snapshot() {
+    let mcp_registry = Arc::new(McpRegistry::new());
+    let app = build_app(mcp_registry.clone());
+
+    let response = app
+        .post("/v1/admin/workspaces/templates")
+        .admin()
+        .json(&serde_json::json!({
+            "name": "data-team",
+            "tools": ["read_file", "tabular"],
+            "mcp_servers": [{
+                "id": "mcp-warehouse",
+                "name": "warehouse",
+                "description": "Warehouse queries",
+                "capabilities": ["Database"],
+                "keywords": ["sql"],
+                "connection_uri": "warehouse-mcp",
+                "args": [],
+                "transport_type": "stdio",
+                "priority": 50,
+                "available": true
+            }],
+            "prompts": { "system": "You are the data team's analyst." },
+            "approval": { "approval_required": 30 }
+        }))
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::CREATED);
+
+    let response = app
+        .post("/v1/admin/workspaces/templates/data-team/instantiate")
+        .admin()
+        .json(&serde_json::json!({ "workspace_id": "analytics" }))
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::CREATED);
+    let workspace = response.json();
+    assert_eq!(workspace["template"], "data-team");
+    assert_eq!(workspace["mcp_servers"][0], "mcp-warehouse");
+    assert!(mcp_registry.contains("mcp-warehouse"));
+
+    let response = app
+        .post("/v1/admin/workspaces/templates/data-team/instantiate")
+        .admin()
+        .json(&serde_json::json!({ "workspace_id": "analytics" }))
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::CONFLICT);
+
+    let response = app
+        .post("/v1/admin/workspaces/analytics/snapshot")
+        .admin()
+        .json(&serde_json::json!({ "name": "analytics-v2" }))
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::CREATED);
+    let template = response.json();
+    assert_eq!(
+        template["tools"],
+        serde_json::json!(["read_file", "tabular"])
+    );
+    assert_eq!(template["mcp_servers"][0]["id"], "mcp-warehouse");
+    assert_eq!(template["approval"]["approval_required"], 30);
+
+    let response = app
+        .get("/v1/admin/workspaces/templates")
+        .admin()
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    let templates = response.json();
+    assert_eq!(templates.as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_workspace_presentation_override() {
+    let app = build_app(Arc::new(McpRegistry::new()));
+
+    let response = app
+        .post("/v1/admin/workspaces/templates")
+        .admin()
+        .json(&serde_json::json!({
+            "name": "support",
+            "presentation": { "user_visible": ["REQUEST_RECEIVED", "REPORT_GENERATED"] }
+        }))
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::CREATED);
+
+    let response = app
+        .post("/v1/admin/workspaces/templates/support/instantiate")
+        .admin()
+        .json(&serde_json::json!({ "workspace_id": "helpdesk" }))
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::CREATED);
+    let workspace = response.json();
+    assert_eq!(
+        workspace["presentation"]["user_visible"],
+        serde_json::json!(["REQUEST_RECEIVED", "REPORT_GENERATED"])
+    );
+    // Unset fields take the defaults
+    assert_eq!(
+        workspace["presentation"]["redacted_fields"],
+        serde_json::json!(["args", "input", "output"])
+    );
+
+    let response = app
+        .put("/v1/admin/workspaces/helpdesk/presentation")
+        .admin()
+        .json(&serde_json::json!({ "user_visible": ["SYSTEM_ERROR"], "redacted_fields": [] }))
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    let workspace = response.json();
+    assert_eq!(
+        workspace["presentation"]["user_visible"],
+        serde_json::json!(["SYSTEM_ERROR"])
+    );
+
+    let response = app
+        .put("/v1/admin/workspaces/helpdesk/presentation")
+        .admin()
+        .json(&serde_json::Value::Null)
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    let workspace = response.json();
+    assert!(workspace.get("presentation").is_none());
+
+    let response = app
+        .put("/v1/admin/workspaces/missing/presentation")
+        .admin()
+        .json(&serde_json::Value::Null)
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::NOT_FOUND);
+}
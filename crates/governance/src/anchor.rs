@@ -0,0 +1,783 @@
+//! WORM audit mode with external anchoring.
+//!
+//! The SQLite audit log is hash-chained, but anyone with write access to the
+//! database can rewrite the whole chain consistently. In WORM mode the table is
+//! made append-only and the chain head is periodically anchored with an
+//! external notary (an S3 object-lock write or an RFC 3161 timestamp), so a
+//! rewrite is detectable against a record the operator cannot alter.
+
+use async_trait::async_trait;
+use base64::Engine;
+use cms::content_info::ContentInfo;
+use cms::signed_data::{SignedData, SignerIdentifier, SignerInfo};
+use der::asn1::{BitString, GeneralizedTime, Int, ObjectIdentifier, OctetString};
+use der::{Decode, DecodePem, Encode, Sequence};
+use multi_agent_core::{Error, Result};
+use rsa::pkcs8::DecodePublicKey;
+use rsa::signature::{self, Verifier};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::Duration;
+use x509_cert::ext::pkix::name::GeneralName;
+use x509_cert::ext::pkix::SubjectKeyIdentifier;
+use x509_cert::ext::Extensions;
+use x509_cert::spki::AlgorithmIdentifierOwned;
+use x509_cert::Certificate;
+
+use crate::audit::SqliteAuditStore;
+
+/// A notarised snapshot of the audit chain head.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditAnchor {
+    pub id: String,
+    /// Hash of the newest entry at anchoring time.
+    pub head_hash: String,
+    /// Number of entries in the log at anchoring time.
+    pub entry_count: usize,
+    /// Unix timestamp (seconds).
+    pub anchored_at: i64,
+    /// Name of the notary that holds the anchor.
+    pub notary: String,
+    /// Notary-specific proof (object version, timestamp token, ...).
+    pub receipt: String,
+}
+
+impl AuditAnchor {
+    /// Canonical statement the notary attests to.
+    pub fn statement(&self) -> String {
+        format!("{}:{}", self.entry_count, self.head_hash)
+    }
+
+    /// SHA-256 of [`Self::statement`].
+    pub fn digest(&self) -> [u8; 32] {
+        Sha256::digest(self.statement().as_bytes()).into()
+    }
+}
+
+/// External party that attests to an audit chain head.
+#[async_trait]
+pub trait AuditNotary: Send + Sync {
+    /// Identifier stored with each anchor.
+    fn name(&self) -> &str;
+
+    /// Notarise `anchor` and return the receipt to store with it.
+    async fn anchor(&self, anchor: &AuditAnchor) -> Result<String>;
+
+    /// Check that the notary's record still matches `anchor`.
+    async fn verify(&self, anchor: &AuditAnchor) -> Result<()>;
+}
+
+/// Result of checking one anchor during verification.
+#[derive(Debug, Clone, Serialize)]
+pub struct AnchorCheck {
+    pub anchor_id: String,
+    pub head_hash: String,
+    pub entry_count: usize,
+    pub anchored_at: i64,
+    pub notary: String,
+    pub valid: bool,
+    pub error: Option<String>,
+}
+
+/// Result of verifying the audit chain and its anchors.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainVerification {
+    pub entries: usize,
+    /// Every entry hash recomputes and links to an earlier entry.
+    pub chain_valid: bool,
+    pub errors: Vec<String>,
+    pub anchors: Vec<AnchorCheck>,
+}
+
+impl ChainVerification {
+    /// True when the chain and every anchor check out.
+    pub fn is_valid(&self) -> bool {
+        self.chain_valid && self.anchors.iter().all(|a| a.valid)
+    }
+}
+
+/// Periodically anchors the audit chain head and verifies past anchors.
+pub struct AuditAnchorer {
+    store: Arc<SqliteAuditStore>,
+    notary: Arc<dyn AuditNotary>,
+}
+
+impl AuditAnchorer {
+    pub fn new(store: Arc<SqliteAuditStore>, notary: Arc<dyn AuditNotary>) -> Self {
+        Self { store, notary }
+    }
+
+    /// Anchor the current chain head. Returns `None` when the log is empty or
+    /// the head is already anchored.
+    pub async fn anchor_now(&self) -> Result<Option<AuditAnchor>> {
+        let Some((head_hash, entry_count)) = self.store.chain_head().await? else {
+            return Ok(None);
+        };
+        let anchors = self.store.anchors().await?;
+        if anchors.last().is_some_and(|a| a.head_hash == head_hash) {
+            return Ok(None);
+        }
+
+        let mut anchor = AuditAnchor {
+            id: uuid::Uuid::new_v4().to_string(),
+            head_hash,
+            entry_count,
+            anchored_at: unix_now(),
+            notary: self.notary.name().to_string(),
+            receipt: String::new(),
+        };
+        anchor.receipt = self.notary.anchor(&anchor).await?;
+        self.store.record_anchor(&anchor).await?;
+
+        metrics::counter!("audit_anchors_total", "notary" => anchor.notary.clone()).increment(1);
+        tracing::info!(
+            anchor_id = %anchor.id,
+            entries = anchor.entry_count,
+            notary = %anchor.notary,
+            "Anchored audit chain head"
+        );
+        Ok(Some(anchor))
+    }
+
+    /// Verify the hash chain, then each anchor against the chain and notary.
+    pub async fn verify(&self) -> Result<ChainVerification> {
+        let mut report = self.store.verify_chain().await?;
+        let anchors = self.store.anchors().await?;
+
+        for check in report.anchors.iter_mut().filter(|c| c.valid) {
+            let Some(anchor) = anchors.iter().find(|a| a.id == check.anchor_id) else {
+                continue;
+            };
+            if anchor.notary != self.notary.name() {
+                check.valid = false;
+                check.error = Some(format!(
+                    "Anchored with '{}', but '{}' is configured",
+                    anchor.notary,
+                    self.notary.name()
+                ));
+                continue;
+            }
+            if let Err(e) = self.notary.verify(anchor).await {
+                check.valid = false;
+                check.error = Some(e.to_string());
+            }
+        }
+        Ok(report)
+    }
+
+    /// Anchor the chain head every `interval`.
+    pub fn spawn(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.anchor_now().await {
+                    metrics::counter!("audit_anchor_failures_total").increment(1);
+                    tracing::error!(error = %e, "Failed to anchor audit chain");
+                }
+            }
+        })
+    }
+}
+
+fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// RFC 3161 timestamp authority notary.
+///
+/// The anchor digest is sent as the message imprint and the returned
+/// timestamp token is stored base64-encoded as the receipt. Verification
+/// checks the token's CMS signature against the configured TSA certificate
+/// and that its `TSTInfo` imprint is the SHA-256 of the anchor statement.
+pub struct Rfc3161Notary {
+    tsa_url: String,
+    certificate: Certificate,
+    client: reqwest::Client,
+}
+
+impl Rfc3161Notary {
+    /// Notary for `tsa_url` whose tokens must be signed by `certificate`.
+    pub fn new(tsa_url: impl Into<String>, certificate: Certificate) -> Self {
+        Self {
+            tsa_url: tsa_url.into(),
+            certificate,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Parse the TSA certificate from PEM or DER.
+    pub fn parse_certificate(bytes: &[u8]) -> Result<Certificate> {
+        let parsed = if bytes.starts_with(b"-----") {
+            Certificate::from_pem(bytes)
+        } else {
+            Certificate::from_der(bytes)
+        };
+        parsed.map_err(|e| Error::governance(format!("Invalid TSA certificate: {}", e)))
+    }
+
+    /// DER-encoded `TimeStampReq` for `digest`.
+    pub fn timestamp_request(digest: &[u8; 32], nonce: u64) -> Result<Vec<u8>> {
+        let request = TimeStampReq {
+            version: 1,
+            message_imprint: MessageImprint::sha256(digest)?,
+            req_policy: None,
+            nonce: Some(nonce),
+            // Include the TSA certificate in the token
+            cert_req: true,
+        };
+        request.to_der().map_err(asn1_error)
+    }
+
+    /// Extract the timestamp token from a DER `TimeStampResp`.
+    pub fn parse_response(body: &[u8]) -> Result<Vec<u8>> {
+        let response = TimeStampResp::from_der(body).map_err(asn1_error)?;
+        // PKIStatus: 0 = granted, 1 = grantedWithMods
+        if response.status.status > 1 {
+            return Err(Error::governance(format!(
+                "Timestamp authority rejected the request (status {}{})",
+                response.status.status,
+                response
+                    .status
+                    .status_string
+                    .map(|text| format!(": {}", text.join("; ")))
+                    .unwrap_or_default()
+            )));
+        }
+        let token = response
+            .time_stamp_token
+            .ok_or_else(|| Error::governance("Timestamp response has no token"))?;
+        token.to_der().map_err(asn1_error)
+    }
+
+    /// Check that `token` is signed by the TSA certificate and timestamps
+    /// `digest`.
+    pub fn verify_token(&self, token: &[u8], digest: &[u8; 32]) -> Result<()> {
+        let content_info = ContentInfo::from_der(token).map_err(asn1_error)?;
+        if content_info.content_type != ID_SIGNED_DATA {
+            return Err(Error::governance("Timestamp token is not CMS SignedData"));
+        }
+        let signed_data: SignedData = content_info.content.decode_as().map_err(asn1_error)?;
+        let encap = &signed_data.encap_content_info;
+        if encap.econtent_type != ID_CT_TST_INFO {
+            return Err(Error::governance(
+                "Timestamp token does not contain TSTInfo",
+            ));
+        }
+        let tst_info_der = encap
+            .econtent
+            .as_ref()
+            .ok_or_else(|| Error::governance("Timestamp token has no TSTInfo"))?
+            .decode_as::<OctetString>()
+            .map_err(asn1_error)?;
+        let tst_info = TstInfo::from_der(tst_info_der.as_bytes()).map_err(asn1_error)?;
+
+        let imprint = &tst_info.message_imprint;
+        if imprint.hash_algorithm.oid != ID_SHA_256
+            || imprint.hashed_message.as_bytes() != digest.as_slice()
+        {
+            return Err(Error::governance(
+                "Timestamp token does not cover the anchored chain head",
+            ));
+        }
+
+        let signer = signed_data
+            .signer_infos
+            .0
+            .iter()
+            .find(|signer| self.is_signer(&signer.sid))
+            .ok_or_else(|| {
+                Error::governance("Timestamp token is not signed by the configured TSA")
+            })?;
+        self.verify_signer(signer, tst_info_der.as_bytes())?;
+
+        let validity = &self.certificate.tbs_certificate.validity;
+        let gen_time = tst_info.gen_time.to_unix_duration();
+        if gen_time < validity.not_before.to_unix_duration()
+            || gen_time > validity.not_after.to_unix_duration()
+        {
+            return Err(Error::governance(
+                "Timestamp lies outside the TSA certificate's validity",
+            ));
+        }
+        Ok(())
+    }
+
+    fn is_signer(&self, sid: &SignerIdentifier) -> bool {
+        let tbs = &self.certificate.tbs_certificate;
+        match sid {
+            SignerIdentifier::IssuerAndSerialNumber(id) => {
+                id.issuer == tbs.issuer && id.serial_number == tbs.serial_number
+            }
+            SignerIdentifier::SubjectKeyIdentifier(ski) => tbs
+                .get::<SubjectKeyIdentifier>()
+                .ok()
+                .flatten()
+                .is_some_and(|(_, own)| own == *ski),
+        }
+    }
+
+    /// Verify a signer's signed attributes and their signature.
+    fn verify_signer(&self, signer: &SignerInfo, content: &[u8]) -> Result<()> {
+        let attrs = signer
+            .signed_attrs
+            .as_ref()
+            .ok_or_else(|| Error::governance("Timestamp token has no signed attributes"))?;
+        let attribute = |oid| {
+            attrs
+                .iter()
+                .find(|a| a.oid == oid)
+                .and_then(|a| a.values.get(0))
+        };
+
+        let content_type =
+            attribute(ID_CONTENT_TYPE).and_then(|v| v.decode_as::<ObjectIdentifier>().ok());
+        if content_type != Some(ID_CT_TST_INFO) {
+            return Err(Error::governance(
+                "Timestamp token has a wrong content type",
+            ));
+        }
+        let digest = HashAlgorithm::from_oid(&signer.digest_alg.oid)?;
+        let message_digest = attribute(ID_MESSAGE_DIGEST)
+            .and_then(|v| v.decode_as::<OctetString>().ok())
+            .ok_or_else(|| Error::governance("Timestamp token has no message digest"))?;
+        if message_digest.as_bytes() != digest.hash(content).as_slice() {
+            return Err(Error::governance(
+                "Timestamp token content does not match its signed digest",
+            ));
+        }
+
+        // The signature covers the DER SET OF encoding of the attributes
+        let signed = attrs.to_der().map_err(asn1_error)?;
+        let spki = self
+            .certificate
+            .tbs_certificate
+            .subject_public_key_info
+            .to_der()
+            .map_err(asn1_error)?;
+        let signature = signer.signature.as_bytes();
+        let bad_signature = |e: signature::Error| {
+            Error::governance(format!("Invalid timestamp token signature: {}", e))
+        };
+        match signer.signature_algorithm.oid {
+            RSA_ENCRYPTION
+            | SHA_256_WITH_RSA_ENCRYPTION
+            | SHA_384_WITH_RSA_ENCRYPTION
+            | SHA_512_WITH_RSA_ENCRYPTION => {
+                let key = rsa::RsaPublicKey::from_public_key_der(&spki)
+                    .map_err(|e| Error::governance(format!("TSA key is not RSA: {}", e)))?;
+                let signature =
+                    rsa::pkcs1v15::Signature::try_from(signature).map_err(bad_signature)?;
+                match digest {
+                    HashAlgorithm::Sha256 => {
+                        rsa::pkcs1v15::VerifyingKey::<Sha256>::new(key).verify(&signed, &signature)
+                    }
+                    HashAlgorithm::Sha384 => rsa::pkcs1v15::VerifyingKey::<sha2::Sha384>::new(key)
+                        .verify(&signed, &signature),
+                    HashAlgorithm::Sha512 => rsa::pkcs1v15::VerifyingKey::<sha2::Sha512>::new(key)
+                        .verify(&signed, &signature),
+                }
+                .map_err(bad_signature)
+            }
+            ECDSA_WITH_SHA_256 => {
+                let key = p256::ecdsa::VerifyingKey::from_public_key_der(&spki)
+                    .map_err(|e| Error::governance(format!("TSA key is not P-256: {}", e)))?;
+                let signature =
+                    p256::ecdsa::DerSignature::try_from(signature).map_err(bad_signature)?;
+                key.verify(&signed, &signature).map_err(bad_signature)
+            }
+            ref other => Err(Error::governance(format!(
+                "Unsupported timestamp signature algorithm {}",
+                other
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl AuditNotary for Rfc3161Notary {
+    fn name(&self) -> &str {
+        "rfc3161"
+    }
+
+    async fn anchor(&self, anchor: &AuditAnchor) -> Result<String> {
+        let request = Self::timestamp_request(&anchor.digest(), rand::random())?;
+        let response = self
+            .client
+            .post(&self.tsa_url)
+            .header("Content-Type", "application/timestamp-query")
+            .body(request)
+            .send()
+            .await
+            .map_err(|e| Error::governance(format!("Timestamp request failed: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(Error::governance(format!(
+                "Timestamp authority returned {}",
+                response.status()
+            )));
+        }
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| Error::governance(format!("Timestamp response error: {}", e)))?;
+        let token = Self::parse_response(&body)?;
+        // Refuse tokens that would fail verification later
+        self.verify_token(&token, &anchor.digest())?;
+        Ok(base64::engine::general_purpose::STANDARD.encode(token))
+    }
+
+    async fn verify(&self, anchor: &AuditAnchor) -> Result<()> {
+        let token = base64::engine::general_purpose::STANDARD
+            .decode(&anchor.receipt)
+            .map_err(|e| Error::governance(format!("Invalid timestamp token: {}", e)))?;
+        self.verify_token(&token, &anchor.digest())
+    }
+}
+
+fn asn1_error(e: der::Error) -> Error {
+    Error::governance(format!("Malformed RFC 3161 data: {}", e))
+}
+
+const ID_SIGNED_DATA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.7.2");
+const ID_CT_TST_INFO: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.16.1.4");
+const ID_CONTENT_TYPE: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.3");
+const ID_MESSAGE_DIGEST: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.4");
+const ID_SHA_256: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.1");
+const ID_SHA_384: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.2");
+const ID_SHA_512: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.3");
+const RSA_ENCRYPTION: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.1");
+const SHA_256_WITH_RSA_ENCRYPTION: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.11");
+const SHA_384_WITH_RSA_ENCRYPTION: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.12");
+const SHA_512_WITH_RSA_ENCRYPTION: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.13");
+const ECDSA_WITH_SHA_256: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+
+/// Digest of a signer's content.
+#[derive(Clone, Copy)]
+enum HashAlgorithm {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    fn from_oid(oid: &ObjectIdentifier) -> Result<Self> {
+        match *oid {
+            ID_SHA_256 => Ok(Self::Sha256),
+            ID_SHA_384 => Ok(Self::Sha384),
+            ID_SHA_512 => Ok(Self::Sha512),
+            ref other => Err(Error::governance(format!(
+                "Unsupported timestamp digest algorithm {}",
+                other
+            ))),
+        }
+    }
+
+    fn hash(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Sha256 => Sha256::digest(data).to_vec(),
+            Self::Sha384 => sha2::Sha384::digest(data).to_vec(),
+            Self::Sha512 => sha2::Sha512::digest(data).to_vec(),
+        }
+    }
+}
+
+/// RFC 3161 `MessageImprint`.
+#[derive(Debug, Clone, Sequence)]
+struct MessageImprint {
+    hash_algorithm: AlgorithmIdentifierOwned,
+    hashed_message: OctetString,
+}
+
+impl MessageImprint {
+    fn sha256(digest: &[u8; 32]) -> Result<Self> {
+        Ok(Self {
+            hash_algorithm: AlgorithmIdentifierOwned {
+                oid: ID_SHA_256,
+                parameters: Some(der::asn1::Null.into()),
+            },
+            hashed_message: OctetString::new(digest.as_slice()).map_err(asn1_error)?,
+        })
+    }
+}
+
+/// RFC 3161 `TimeStampReq`, without extensions.
+#[derive(Debug, Sequence)]
+struct TimeStampReq {
+    version: u8,
+    message_imprint: MessageImprint,
+    #[asn1(optional = "true")]
+    req_policy: Option<ObjectIdentifier>,
+    #[asn1(optional = "true")]
+    nonce: Option<u64>,
+    #[asn1(default = "Default::default")]
+    cert_req: bool,
+}
+
+/// RFC 3161 `PKIStatusInfo`.
+#[derive(Debug, Sequence)]
+struct PkiStatusInfo {
+    status: u8,
+    #[asn1(optional = "true")]
+    status_string: Option<Vec<String>>,
+    #[asn1(optional = "true")]
+    fail_info: Option<BitString>,
+}
+
+/// RFC 3161 `TimeStampResp`.
+#[derive(Debug, Sequence)]
+struct TimeStampResp {
+    status: PkiStatusInfo,
+    #[asn1(optional = "true")]
+    time_stamp_token: Option<ContentInfo>,
+}
+
+/// RFC 3161 `Accuracy`.
+#[derive(Debug, Sequence)]
+struct Accuracy {
+    #[asn1(optional = "true")]
+    seconds: Option<u64>,
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT", optional = "true")]
+    millis: Option<u16>,
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+    micros: Option<u16>,
+}
+
+/// RFC 3161 `TSTInfo`, the content signed by the TSA.
+#[derive(Debug, Sequence)]
+struct TstInfo {
+    version: u8,
+    policy: ObjectIdentifier,
+    message_imprint: MessageImprint,
+    serial_number: Int,
+    gen_time: GeneralizedTime,
+    #[asn1(optional = "true")]
+    accuracy: Option<Accuracy>,
+    #[asn1(default = "Default::default")]
+    ordering: bool,
+    #[asn1(optional = "true")]
+    nonce: Option<Int>,
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    tsa: Option<GeneralName>,
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+    extensions: Option<Extensions>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::{AuditEntry, AuditOutcome, AuditStore};
+    use multi_agent_core::traits::Erasable;
+    use std::sync::Mutex;
+    use tempfile::NamedTempFile;
+
+    /// Keeps statements in memory, like a bucket with object lock.
+    #[derive(Default)]
+    struct MemoryNotary {
+        records: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl AuditNotary for MemoryNotary {
+        fn name(&self) -> &str {
+            "memory"
+        }
+
+        async fn anchor(&self, anchor: &AuditAnchor) -> Result<String> {
+            let mut records = self.records.lock().unwrap();
+            records.push(anchor.statement());
+            Ok((records.len() - 1).to_string())
+        }
+
+        async fn verify(&self, anchor: &AuditAnchor) -> Result<()> {
+            let records = self.records.lock().unwrap();
+            let index: usize = anchor.receipt.parse().unwrap();
+            if records.get(index) == Some(&anchor.statement()) {
+                Ok(())
+            } else {
+                Err(Error::governance("statement mismatch"))
+            }
+        }
+    }
+
+    fn entry(i: usize) -> AuditEntry {
+        AuditEntry {
+            id: format!("e{}", i),
+            timestamp: format!("2024-01-01T00:00:{:02}Z", i),
+            user_id: "alice".into(),
+            action: "EXECUTE_TOOL".into(),
+            resource: "shell".into(),
+            outcome: AuditOutcome::Success,
+            metadata: None,
+            previous_hash: None,
+            hash: None,
+            trace_id: None,
+            session_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_anchor_and_verify() {
+        let file = NamedTempFile::new().unwrap();
+        let store = Arc::new(SqliteAuditStore::new(file.path()).unwrap());
+        store.enable_worm().await.unwrap();
+        let anchorer = AuditAnchorer::new(store.clone(), Arc::new(MemoryNotary::default()));
+
+        assert!(anchorer.anchor_now().await.unwrap().is_none());
+        for i in 0..3 {
+            store.log(entry(i)).await.unwrap();
+        }
+        let first = anchorer.anchor_now().await.unwrap().unwrap();
+        assert_eq!(first.entry_count, 3);
+        // Unchanged head is not re-anchored
+        assert!(anchorer.anchor_now().await.unwrap().is_none());
+
+        store.log(entry(3)).await.unwrap();
+        anchorer.anchor_now().await.unwrap().unwrap();
+
+        let report = anchorer.verify().await.unwrap();
+        assert!(report.is_valid(), "{:?}", report);
+        assert_eq!(report.entries, 4);
+        assert_eq!(report.anchors.len(), 2);
+
+        // Append-only: deletes are rejected
+        assert!(store.erase_user("alice").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verify_detects_rewritten_chain() {
+        let file = NamedTempFile::new().unwrap();
+        let store = Arc::new(SqliteAuditStore::new(file.path()).unwrap());
+        let anchorer = AuditAnchorer::new(store.clone(), Arc::new(MemoryNotary::default()));
+        for i in 0..3 {
+            store.log(entry(i)).await.unwrap();
+        }
+        anchorer.anchor_now().await.unwrap().unwrap();
+
+        // Without WORM triggers an attacker can drop and re-log the history
+        store.erase_user("alice").await.unwrap();
+        for i in 0..3 {
+            let mut forged = entry(i);
+            forged.resource = "forged".into();
+            store.log(forged).await.unwrap();
+        }
+
+        let report = anchorer.verify().await.unwrap();
+        assert!(report.chain_valid);
+        assert!(!report.is_valid());
+        assert!(report.anchors[0].error.is_some());
+    }
+
+    /// Anchor matching the digest the fixture tokens were issued for.
+    fn fixture_anchor() -> AuditAnchor {
+        AuditAnchor {
+            id: "a1".into(),
+            head_hash: "ab".repeat(32),
+            entry_count: 3,
+            anchored_at: 0,
+            notary: "rfc3161".into(),
+            receipt: String::new(),
+        }
+    }
+
+    fn notary(cert: &str) -> Rfc3161Notary {
+        let cert = Rfc3161Notary::parse_certificate(cert.as_bytes()).unwrap();
+        Rfc3161Notary::new("http://tsa.invalid", cert)
+    }
+
+    const RSA_CERT: &str = include_str!("../tests/fixtures/tsa_rsa.pem");
+    const EC_CERT: &str = include_str!("../tests/fixtures/tsa_ec.pem");
+    const OTHER_CERT: &str = include_str!("../tests/fixtures/tsa_other.pem");
+    const RSA_RESPONSE: &[u8] = include_bytes!("../tests/fixtures/tsa_rsa.tsr");
+    const EC_RESPONSE: &[u8] = include_bytes!("../tests/fixtures/tsa_ec.tsr");
+
+    #[test]
+    fn test_timestamp_request_encoding() {
+        let digest = [7u8; 32];
+        let request = Rfc3161Notary::timestamp_request(&digest, u64::MAX).unwrap();
+        let decoded = TimeStampReq::from_der(&request).unwrap();
+        assert_eq!(decoded.version, 1);
+        assert_eq!(decoded.message_imprint.hashed_message.as_bytes(), digest);
+        assert_eq!(decoded.nonce, Some(u64::MAX));
+        assert!(decoded.cert_req);
+    }
+
+    #[test]
+    fn test_verify_signed_tokens() {
+        let anchor = fixture_anchor();
+        for (cert, response) in [(RSA_CERT, RSA_RESPONSE), (EC_CERT, EC_RESPONSE)] {
+            let token = Rfc3161Notary::parse_response(response).unwrap();
+            let notary = notary(cert);
+            notary.verify_token(&token, &anchor.digest()).unwrap();
+
+            // A token for another chain head is rejected
+            let other = AuditAnchor {
+                entry_count: 4,
+                ..fixture_anchor()
+            };
+            assert!(notary.verify_token(&token, &other.digest()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_verify_rejects_forged_tokens() {
+        let anchor = fixture_anchor();
+        let token = Rfc3161Notary::parse_response(RSA_RESPONSE).unwrap();
+
+        // Genuine token, but from a TSA other than the configured one
+        let err = notary(OTHER_CERT)
+            .verify_token(&token, &anchor.digest())
+            .unwrap_err();
+        assert!(err.to_string().contains("not signed by the configured TSA"));
+
+        // Rewriting the imprint to cover another chain head breaks the
+        // signed message digest
+        let notary = notary(RSA_CERT);
+        let digest = anchor.digest();
+        let forged_digest = AuditAnchor {
+            head_hash: "cd".repeat(32),
+            ..fixture_anchor()
+        }
+        .digest();
+        let at = token.windows(32).position(|w| w == digest).unwrap();
+        let mut forged = token.clone();
+        forged[at..at + 32].copy_from_slice(&forged_digest);
+        let err = notary.verify_token(&forged, &forged_digest).unwrap_err();
+        assert!(err.to_string().contains("does not match its signed digest"));
+
+        // So does tampering with the signature
+        let mut forged = token.clone();
+        *forged.last_mut().unwrap() ^= 0x01;
+        assert!(notary.verify_token(&forged, &digest).is_err());
+
+        // A bare digest is no longer enough
+        assert!(notary.verify_token(&digest, &digest).is_err());
+    }
+
+    #[test]
+    fn test_parse_response_status() {
+        let rejected = TimeStampResp {
+            status: PkiStatusInfo {
+                status: 2,
+                status_string: Some(vec!["bad digest".into()]),
+                fail_info: None,
+            },
+            time_stamp_token: None,
+        }
+        .to_der()
+        .unwrap();
+        let err = Rfc3161Notary::parse_response(&rejected).unwrap_err();
+        assert!(err.to_string().contains("bad digest"));
+        assert!(Rfc3161Notary::parse_response(&[0x30, 0x05]).is_err());
+    }
+}
@@ -1,19 +1,24 @@
 //! HITL (Human-in-the-Loop) approval gate implementations.
 //!
-//! Provides mechanisms for human review and approval of high-risk tool calls.
+//! Provides mechanisms for human review and approval of high-risk tool calls,
+//! and for answering clarification questions asked by the agent.
 
 use async_trait::async_trait;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::{broadcast, oneshot, Mutex};
 
 use multi_agent_core::{
-    traits::ApprovalGate,
-    types::{ApprovalRequest, ApprovalResponse, ToolRiskLevel},
+    traits::{ApprovalGate, HumanInputChannel},
+    types::{
+        ApprovalRequest, ApprovalResponse, ApprovalScope, HumanAnswer, HumanQuestion, ToolRiskLevel,
+    },
     Error, Result,
 };
 
-type PendingRequest = (oneshot::Sender<ApprovalResponse>, String);
+use crate::audit::{AuditEntry, AuditOutcome, AuditStore};
+
+type PendingRequest = (oneshot::Sender<ApprovalResponse>, ApprovalRequest);
 
 // =============================================================================
 // Channel-Based Approval Gate
@@ -69,8 +74,8 @@ impl ChannelApprovalGate {
     ) -> std::result::Result<(), String> {
         let mut pending = self.pending.lock().await;
         match pending.remove(request_id) {
-            Some((sender, stored_nonce)) => {
-                if stored_nonce != nonce {
+            Some((sender, request)) => {
+                if request.nonce != nonce {
                     return Err("Invalid nonce".to_string());
                 }
                 sender
@@ -85,6 +90,27 @@ impl ChannelApprovalGate {
     pub async fn list_pending(&self) -> Vec<String> {
         self.pending.lock().await.keys().cloned().collect()
     }
+
+    /// Pending approval requests raised by `session_id`.
+    pub async fn pending_for_session(&self, session_id: &str) -> Vec<ApprovalRequest> {
+        self.pending
+            .lock()
+            .await
+            .values()
+            .filter(|(_, request)| request.session_id == session_id)
+            .map(|(_, request)| request.clone())
+            .collect()
+    }
+
+    /// Sessions with at least one pending request.
+    pub async fn pending_sessions(&self) -> HashSet<String> {
+        self.pending
+            .lock()
+            .await
+            .values()
+            .map(|(_, request)| request.session_id.clone())
+            .collect()
+    }
 }
 
 #[async_trait]
@@ -95,7 +121,7 @@ impl ApprovalGate for ChannelApprovalGate {
         // Register the pending request
         {
             let mut pending = self.pending.lock().await;
-            pending.insert(req.request_id.clone(), (tx, req.nonce.clone()));
+            pending.insert(req.request_id.clone(), (tx, req.clone()));
         }
 
         // Notify listeners (WebSocket, etc.)
@@ -141,6 +167,329 @@ impl ApprovalGate for ChannelApprovalGate {
     }
 }
 
+// =============================================================================
+// Channel-Based Human Input
+// =============================================================================
+
+/// Answer channel, nonce and session of a pending question.
+type PendingQuestion = (oneshot::Sender<String>, String, String);
+
+/// Human input channel that broadcasts questions to listeners (WebSocket
+/// handlers, chat connectors) and waits for the first answer submitted.
+pub struct ChannelHumanInput {
+    /// Pending questions, keyed by request_id.
+    pending: Arc<Mutex<HashMap<String, PendingQuestion>>>,
+    /// Broadcast channel for notifying listeners about new questions.
+    question_tx: broadcast::Sender<HumanQuestion>,
+    /// Timeout for waiting for an answer (default: 30 minutes).
+    timeout: std::time::Duration,
+}
+
+impl ChannelHumanInput {
+    /// Create a new channel-based human input channel.
+    pub fn new() -> Self {
+        let (question_tx, _) = broadcast::channel(32);
+        Self {
+            pending: Arc::new(Mutex::new(HashMap::new())),
+            question_tx,
+            timeout: std::time::Duration::from_secs(1800), // 30 minutes
+        }
+    }
+
+    /// Set the default answer timeout.
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Subscribe to question notifications.
+    pub fn subscribe(&self) -> broadcast::Receiver<HumanQuestion> {
+        self.question_tx.subscribe()
+    }
+
+    /// Submit the human's answer to a pending question.
+    pub async fn submit_answer(
+        &self,
+        request_id: &str,
+        nonce: &str,
+        answer: String,
+    ) -> std::result::Result<(), String> {
+        let mut pending = self.pending.lock().await;
+        match pending.get(request_id) {
+            Some((_, stored_nonce, _)) if stored_nonce != nonce => Err("Invalid nonce".to_string()),
+            Some(_) => {
+                let (sender, _, _) = pending.remove(request_id).expect("checked above");
+                sender
+                    .send(answer)
+                    .map_err(|_| "Question channel closed (agent may have timed out)".to_string())
+            }
+            None => Err(format!("No pending question with ID: {}", request_id)),
+        }
+    }
+
+    /// Get the IDs of currently pending questions.
+    pub async fn list_pending(&self) -> Vec<String> {
+        self.pending.lock().await.keys().cloned().collect()
+    }
+
+    /// Sessions waiting for an answer.
+    pub async fn pending_sessions(&self) -> HashSet<String> {
+        self.pending
+            .lock()
+            .await
+            .values()
+            .map(|(_, _, session_id)| session_id.clone())
+            .collect()
+    }
+}
+
+impl Default for ChannelHumanInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl HumanInputChannel for ChannelHumanInput {
+    async fn ask(&self, question: &HumanQuestion) -> Result<HumanAnswer> {
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().await.insert(
+            question.request_id.clone(),
+            (tx, question.nonce.clone(), question.session_id.clone()),
+        );
+
+        let _ = self.question_tx.send(question.clone());
+        tracing::info!(
+            request_id = %question.request_id,
+            session_id = %question.session_id,
+            "Waiting for human answer (timeout: {:?})",
+            self.timeout
+        );
+
+        let timeout = question
+            .timeout_secs
+            .map(std::time::Duration::from_secs)
+            .unwrap_or(self.timeout);
+        let result = tokio::time::timeout(timeout, rx).await;
+        match result {
+            Ok(Ok(answer)) => Ok(HumanAnswer::Answered { answer }),
+            Ok(Err(_)) => {
+                self.pending.lock().await.remove(&question.request_id);
+                Err(Error::governance("Question channel closed unexpectedly"))
+            }
+            Err(_) => {
+                self.pending.lock().await.remove(&question.request_id);
+                tracing::warn!(request_id = %question.request_id, "Question timed out unanswered");
+                Ok(HumanAnswer::TimedOut)
+            }
+        }
+    }
+}
+
+// =============================================================================
+// Pre-Authorization (Approval Batching)
+// =============================================================================
+
+/// Reason code reported for calls covered by an earlier approval.
+pub const PRE_AUTHORIZED: &str = "PRE_AUTHORIZED";
+
+/// A standing approval within one session.
+#[derive(Debug, Clone)]
+struct Grant {
+    tool_name: String,
+    /// Canonical arguments for `Calls` grants; `None` covers any arguments.
+    args: Option<String>,
+    /// Calls still covered; `None` means unlimited.
+    remaining: Option<u32>,
+    scope: ApprovalScope,
+    /// Request that created the grant.
+    granted_by: String,
+}
+
+/// Approval gate that remembers scoped approvals.
+///
+/// Wraps another gate. When the human approves a call with a
+/// [`ApprovalScope::Calls`] or [`ApprovalScope::Session`] scope, matching
+/// later calls in the same session are approved without asking again. Grants
+/// and the calls they cover are written to the audit store, if one is set.
+///
+/// Session-wide grants never cover [`ApprovalRequest::mandatory`] calls, and
+/// all grants are dropped when the session ends.
+pub struct BatchingApprovalGate {
+    inner: Arc<dyn ApprovalGate>,
+    grants: Mutex<HashMap<String, Vec<Grant>>>,
+    audit: Option<Arc<dyn AuditStore>>,
+}
+
+impl BatchingApprovalGate {
+    /// Wrap `inner`, which is consulted whenever no grant applies.
+    pub fn new(inner: Arc<dyn ApprovalGate>) -> Self {
+        Self {
+            inner,
+            grants: Mutex::new(HashMap::new()),
+            audit: None,
+        }
+    }
+
+    /// Record grants and pre-authorized calls in this audit store.
+    pub fn with_audit(mut self, audit: Arc<dyn AuditStore>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Drop all grants for a session (e.g., when it completes).
+    pub async fn revoke_session(&self, session_id: &str) {
+        self.grants.lock().await.remove(session_id);
+    }
+
+    /// Number of grants currently held for a session.
+    pub async fn grant_count(&self, session_id: &str) -> usize {
+        self.grants
+            .lock()
+            .await
+            .get(session_id)
+            .map_or(0, |g| g.len())
+    }
+
+    /// Consume a grant covering `req`, if any.
+    async fn take_grant(&self, req: &ApprovalRequest) -> Option<Grant> {
+        let mut grants = self.grants.lock().await;
+        let session = grants.get_mut(&req.session_id)?;
+        let args = req.args.to_string();
+        // Tools that require approval themselves are only covered by
+        // grants for the exact same call.
+        let index = session.iter().position(|g| {
+            g.tool_name == req.tool_name
+                && match &g.args {
+                    Some(a) => *a == args,
+                    None => !req.mandatory,
+                }
+        })?;
+
+        let grant = &mut session[index];
+        if let Some(remaining) = grant.remaining.as_mut() {
+            *remaining -= 1;
+        }
+        let used = grant.clone();
+        if grant.remaining == Some(0) {
+            session.remove(index);
+        }
+        Some(used)
+    }
+
+    async fn record(&self, req: &ApprovalRequest, scope: ApprovalScope) -> bool {
+        let (args, remaining) = match scope {
+            ApprovalScope::Once => return false,
+            // The approved call itself uses one of the `count` calls.
+            ApprovalScope::Calls { count } if count <= 1 => return false,
+            ApprovalScope::Calls { count } => (Some(req.args.to_string()), Some(count - 1)),
+            ApprovalScope::Session if req.mandatory => {
+                tracing::info!(
+                    request_id = %req.request_id,
+                    tool = %req.tool_name,
+                    "Session-wide approval ignored for a tool that requires approval per call"
+                );
+                return false;
+            }
+            ApprovalScope::Session => (None, None),
+        };
+        self.grants
+            .lock()
+            .await
+            .entry(req.session_id.clone())
+            .or_default()
+            .push(Grant {
+                tool_name: req.tool_name.clone(),
+                args,
+                remaining,
+                scope,
+                granted_by: req.request_id.clone(),
+            });
+        true
+    }
+
+    async fn audit(&self, action: &str, req: &ApprovalRequest, metadata: serde_json::Value) {
+        let Some(audit) = &self.audit else {
+            return;
+        };
+        let entry = AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            user_id: "approval_gate".to_string(),
+            action: action.to_string(),
+            resource: req.tool_name.clone(),
+            outcome: AuditOutcome::Success,
+            metadata: Some(metadata),
+            previous_hash: None,
+            hash: None,
+            trace_id: None,
+            session_id: None,
+        };
+        if let Err(e) = audit.log(entry).await {
+            tracing::warn!(error = %e, "Failed to audit pre-authorization");
+        }
+    }
+}
+
+#[async_trait]
+impl ApprovalGate for BatchingApprovalGate {
+    async fn request_approval(&self, req: &ApprovalRequest) -> Result<ApprovalResponse> {
+        if let Some(grant) = self.take_grant(req).await {
+            tracing::info!(
+                request_id = %req.request_id,
+                tool = %req.tool_name,
+                scope = ?grant.scope,
+                granted_by = %grant.granted_by,
+                "Tool call covered by pre-authorization"
+            );
+            self.audit(
+                "approval.pre_authorized_call",
+                req,
+                serde_json::json!({
+                    "session_id": req.session_id,
+                    "request_id": req.request_id,
+                    "scope": grant.scope,
+                    "granted_by": grant.granted_by,
+                    "remaining": grant.remaining,
+                }),
+            )
+            .await;
+            return Ok(ApprovalResponse::Approved {
+                reason: Some(format!("Pre-authorized by approval {}", grant.granted_by)),
+                reason_code: PRE_AUTHORIZED.to_string(),
+                scope: grant.scope,
+            });
+        }
+
+        let response = self.inner.request_approval(req).await?;
+        if let ApprovalResponse::Approved { scope, .. } = &response {
+            if self.record(req, *scope).await {
+                self.audit(
+                    "approval.pre_authorize",
+                    req,
+                    serde_json::json!({
+                        "session_id": req.session_id,
+                        "request_id": req.request_id,
+                        "scope": scope,
+                        "args": req.args,
+                    }),
+                )
+                .await;
+            }
+        }
+        Ok(response)
+    }
+
+    fn threshold(&self) -> ToolRiskLevel {
+        self.inner.threshold()
+    }
+
+    async fn end_session(&self, session_id: &str) {
+        self.revoke_session(session_id).await;
+        self.inner.end_session(session_id).await;
+    }
+}
+
 // =============================================================================
 // Auto-Approve Gate (for development/testing)
 // =============================================================================
@@ -161,6 +510,7 @@ impl ApprovalGate for AutoApproveGate {
         Ok(ApprovalResponse::Approved {
             reason: Some("Auto-approved in development mode".to_string()),
             reason_code: "AUTO_APPROVED".to_string(),
+            scope: ApprovalScope::Once,
         })
     }
 
@@ -190,6 +540,7 @@ mod tests {
             timeout_secs: None,
             nonce: "test-nonce-1".into(),
             expires_at: 0,
+            mandatory: false,
         };
 
         let response = gate.request_approval(&req).await.unwrap();
@@ -211,6 +562,7 @@ mod tests {
             timeout_secs: None,
             nonce: "test-nonce-2".into(),
             expires_at: 0,
+            mandatory: false,
         };
 
         // Spawn the approval request
@@ -231,6 +583,7 @@ mod tests {
                 ApprovalResponse::Approved {
                     reason: None,
                     reason_code: "USER_APPROVED".into(),
+                    scope: ApprovalScope::Once,
                 },
             )
             .await
@@ -257,6 +610,7 @@ mod tests {
             timeout_secs: None,
             nonce: "test-nonce-3".into(),
             expires_at: 0,
+            mandatory: false,
         };
 
         let gate_for_task = gate.clone();
@@ -299,6 +653,7 @@ mod tests {
             timeout_secs: None,
             nonce: "test-nonce-4".into(),
             expires_at: 0,
+            mandatory: false,
         };
 
         // Don't submit any response — should timeout
@@ -314,4 +669,215 @@ mod tests {
             _ => panic!("Expected Denied due to timeout"),
         }
     }
+
+    /// Approves with a fixed scope and counts how often it was asked.
+    struct ScopedGate {
+        scope: ApprovalScope,
+        asked: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ApprovalGate for ScopedGate {
+        async fn request_approval(&self, _req: &ApprovalRequest) -> Result<ApprovalResponse> {
+            self.asked.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(ApprovalResponse::Approved {
+                reason: None,
+                reason_code: "USER_APPROVED".into(),
+                scope: self.scope,
+            })
+        }
+    }
+
+    fn call(session: &str, tool: &str, args: serde_json::Value) -> ApprovalRequest {
+        ApprovalRequest {
+            request_id: uuid::Uuid::new_v4().to_string(),
+            session_id: session.into(),
+            tool_name: tool.into(),
+            args,
+            risk_level: ToolRiskLevel::Medium,
+            context: "test".into(),
+            timeout_secs: None,
+            nonce: "n".into(),
+            expires_at: 0,
+            mandatory: false,
+        }
+    }
+
+    fn batching(scope: ApprovalScope) -> (Arc<ScopedGate>, BatchingApprovalGate) {
+        let inner = Arc::new(ScopedGate {
+            scope,
+            asked: std::sync::atomic::AtomicUsize::new(0),
+        });
+        (inner.clone(), BatchingApprovalGate::new(inner))
+    }
+
+    fn reason_code(response: ApprovalResponse) -> String {
+        match response {
+            ApprovalResponse::Approved { reason_code, .. } => reason_code,
+            other => panic!("Expected Approved, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batching_covers_n_identical_calls() {
+        let (inner, gate) = batching(ApprovalScope::Calls { count: 3 });
+        let args = serde_json::json!({"url": "https://example.com"});
+
+        for _ in 0..3 {
+            gate.request_approval(&call("s1", "fetch", args.clone()))
+                .await
+                .unwrap();
+        }
+        assert_eq!(inner.asked.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(gate.grant_count("s1").await, 0);
+
+        // Exhausted, different args and other sessions all ask again
+        gate.request_approval(&call("s1", "fetch", args.clone()))
+            .await
+            .unwrap();
+        assert_eq!(inner.asked.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_batching_session_scope_is_per_tool_and_session() {
+        let (inner, gate) = batching(ApprovalScope::Session);
+        let asked = || inner.asked.load(std::sync::atomic::Ordering::SeqCst);
+
+        gate.request_approval(&call("s1", "fetch", serde_json::json!({"n": 1})))
+            .await
+            .unwrap();
+        let response = gate
+            .request_approval(&call("s1", "fetch", serde_json::json!({"n": 2})))
+            .await
+            .unwrap();
+        assert_eq!(reason_code(response), PRE_AUTHORIZED);
+        assert_eq!(asked(), 1);
+
+        gate.request_approval(&call("s1", "write", serde_json::json!({})))
+            .await
+            .unwrap();
+        gate.request_approval(&call("s2", "fetch", serde_json::json!({"n": 1})))
+            .await
+            .unwrap();
+        assert_eq!(asked(), 3);
+
+        gate.revoke_session("s1").await;
+        gate.request_approval(&call("s1", "fetch", serde_json::json!({"n": 3})))
+            .await
+            .unwrap();
+        assert_eq!(asked(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_batching_session_scope_skips_mandatory_tools() {
+        let (inner, gate) = batching(ApprovalScope::Session);
+        let asked = || inner.asked.load(std::sync::atomic::Ordering::SeqCst);
+        let email = |to: &str| ApprovalRequest {
+            mandatory: true,
+            ..call("s1", "send_email", serde_json::json!({"to": to}))
+        };
+
+        gate.request_approval(&email("a@example.com"))
+            .await
+            .unwrap();
+        assert_eq!(gate.grant_count("s1").await, 0);
+        gate.request_approval(&email("b@example.com"))
+            .await
+            .unwrap();
+        assert_eq!(asked(), 2);
+
+        // A grant from a non-mandatory call does not cover mandatory ones
+        gate.request_approval(&call("s1", "send_email", serde_json::json!({})))
+            .await
+            .unwrap();
+        gate.request_approval(&email("c@example.com"))
+            .await
+            .unwrap();
+        assert_eq!(asked(), 4);
+
+        gate.end_session("s1").await;
+        assert_eq!(gate.grant_count("s1").await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_batching_audits_grant_and_use() {
+        let audit = Arc::new(crate::audit::InMemoryAuditStore::new());
+        let (_, gate) = batching(ApprovalScope::Calls { count: 2 });
+        let gate = gate.with_audit(audit.clone());
+
+        let args = serde_json::json!({"q": "x"});
+        gate.request_approval(&call("s1", "search", args.clone()))
+            .await
+            .unwrap();
+        gate.request_approval(&call("s1", "search", args))
+            .await
+            .unwrap();
+
+        let entries = audit.query(Default::default()).await.unwrap();
+        let actions: Vec<_> = entries.iter().map(|e| e.action.as_str()).collect();
+        assert!(actions.contains(&"approval.pre_authorize"));
+        assert!(actions.contains(&"approval.pre_authorized_call"));
+        let grant = entries
+            .iter()
+            .find(|e| e.action == "approval.pre_authorize")
+            .unwrap();
+        assert_eq!(
+            grant.metadata.as_ref().unwrap()["scope"],
+            serde_json::json!({"kind": "calls", "count": 2})
+        );
+    }
+
+    fn question(id: &str, timeout_secs: Option<u64>) -> HumanQuestion {
+        HumanQuestion {
+            request_id: id.into(),
+            session_id: "session-1".into(),
+            question: "Which region?".into(),
+            options: vec!["eu".into(), "us".into()],
+            timeout_secs,
+            nonce: format!("{}-nonce", id),
+            expires_at: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_human_input_answer() {
+        let channel = Arc::new(ChannelHumanInput::new());
+        let mut questions = channel.subscribe();
+
+        let asker = channel.clone();
+        let handle = tokio::spawn(async move { asker.ask(&question("q-1", None)).await });
+
+        let broadcast = questions.recv().await.unwrap();
+        assert_eq!(broadcast.question, "Which region?");
+
+        // A wrong nonce is rejected without dropping the question.
+        assert!(channel
+            .submit_answer("q-1", "bogus", "eu".into())
+            .await
+            .is_err());
+        channel
+            .submit_answer("q-1", "q-1-nonce", "eu".into())
+            .await
+            .unwrap();
+
+        let answer = handle.await.unwrap().unwrap();
+        assert_eq!(
+            answer,
+            HumanAnswer::Answered {
+                answer: "eu".into()
+            }
+        );
+        assert!(channel.list_pending().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_human_input_timeout() {
+        let channel = ChannelHumanInput::new();
+        let answer = channel.ask(&question("q-2", Some(0))).await.unwrap();
+        assert_eq!(answer, HumanAnswer::TimedOut);
+        assert!(channel
+            .submit_answer("q-2", "q-2-nonce", "late".into())
+            .await
+            .is_err());
+    }
 }
@@ -3,6 +3,15 @@
 use async_trait::async_trait;
 use multi_agent_core::{traits::Erasable, Result};
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use tokio::sync::broadcast;
+
+/// Entries buffered per audit stream subscriber before it lags.
+const STREAM_CAPACITY: usize = 256;
+
+tokio::task_local! {
+    static CURRENT_CONTEXT: AuditContext;
+}
 
 /// Outcome of an audited action.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +42,61 @@ pub struct AuditEntry {
     pub previous_hash: Option<String>,
     /// Hash of current entry + previous_hash.
     pub hash: Option<String>,
+    /// Trace of the request that caused the action.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    /// Session the action belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+impl AuditEntry {
+    /// Fill unset trace and session IDs from the current [`AuditContext`].
+    pub fn correlate(&mut self) {
+        if let Some(context) = AuditContext::current() {
+            if self.trace_id.is_none() {
+                self.trace_id = context.trace_id;
+            }
+            if self.session_id.is_none() {
+                self.session_id = context.session_id;
+            }
+        }
+    }
+}
+
+/// The caller, trace and session of the request being served.
+///
+/// The gateway runs each authenticated request inside its context; audit
+/// stores stamp entries logged there with its trace and session IDs, so an
+/// entry can be joined with the events and traces of the same request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditContext {
+    /// Authenticated user.
+    pub user_id: Option<String>,
+    /// Trace ID of the request.
+    pub trace_id: Option<String>,
+    /// Session the request belongs to.
+    pub session_id: Option<String>,
+}
+
+impl AuditContext {
+    /// Run `fut` with this context as the current audit context.
+    pub async fn scope<F: Future>(self, fut: F) -> F::Output {
+        CURRENT_CONTEXT.scope(self, fut).await
+    }
+
+    /// The audit context of the current execution context, if any.
+    pub fn current() -> Option<Self> {
+        CURRENT_CONTEXT.try_with(|context| context.clone()).ok()
+    }
+
+    /// The authenticated user of the current context, or `fallback` outside
+    /// a request.
+    pub fn user_or(fallback: &str) -> String {
+        Self::current()
+            .and_then(|context| context.user_id)
+            .unwrap_or_else(|| fallback.to_string())
+    }
 }
 
 /// Filter for querying audit logs.
@@ -43,7 +107,35 @@ pub struct AuditFilter {
     pub resource: Option<String>,
     pub from_timestamp: Option<String>,
     pub to_timestamp: Option<String>,
+    pub trace_id: Option<String>,
+    pub session_id: Option<String>,
     pub limit: Option<usize>,
+    /// Number of matching entries to skip (for pagination).
+    pub offset: Option<usize>,
+    /// Cursor: only entries after this entry in `order` match. An entry
+    /// that no longer exists yields no entries.
+    pub after_id: Option<String>,
+    pub order: AuditOrder,
+}
+
+/// Order of query results, by timestamp and then by the order entries
+/// were logged in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOrder {
+    #[default]
+    #[serde(alias = "desc")]
+    NewestFirst,
+    #[serde(alias = "asc")]
+    OldestFirst,
+}
+
+/// One page of a cursor-paginated query.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AuditEntryPage {
+    pub entries: Vec<AuditEntry>,
+    /// `after_id` for the next page; `None` on the last page.
+    pub next_cursor: Option<String>,
 }
 
 /// Trait for audit log persistence.
@@ -54,17 +146,68 @@ pub trait AuditStore: Send + Sync {
 
     /// Query audit logs with optional filters.
     async fn query(&self, filter: AuditFilter) -> Result<Vec<AuditEntry>>;
+
+    /// Count entries matching the filter, ignoring `limit`, `offset` and
+    /// `after_id`.
+    async fn count(&self, filter: AuditFilter) -> Result<usize>;
+
+    /// Query up to `page_size` entries, with the cursor of the next page.
+    async fn query_page(
+        &self,
+        mut filter: AuditFilter,
+        page_size: usize,
+    ) -> Result<AuditEntryPage> {
+        // One extra entry tells whether another page follows
+        filter.limit = Some(page_size.saturating_add(1));
+        let mut entries = self.query(filter).await?;
+        let next_cursor = if entries.len() > page_size {
+            entries.truncate(page_size);
+            entries.last().map(|entry| entry.id.clone())
+        } else {
+            None
+        };
+        Ok(AuditEntryPage {
+            entries,
+            next_cursor,
+        })
+    }
+
+    /// Receive entries as they are logged, or `None` if the store cannot
+    /// stream them.
+    fn subscribe(&self) -> Option<broadcast::Receiver<AuditEntry>> {
+        None
+    }
+}
+
+impl AuditFilter {
+    /// Whether `entry` matches the field filters. Timestamps, paging and
+    /// cursors are not considered.
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        self.user_id.as_ref().is_none_or(|u| &entry.user_id == u)
+            && self.action.as_ref().is_none_or(|a| &entry.action == a)
+            && self.resource.as_ref().is_none_or(|r| &entry.resource == r)
+            && self
+                .trace_id
+                .as_ref()
+                .is_none_or(|t| entry.trace_id.as_ref() == Some(t))
+            && self
+                .session_id
+                .as_ref()
+                .is_none_or(|s| entry.session_id.as_ref() == Some(s))
+    }
 }
 
 /// In-memory audit store for testing.
 pub struct InMemoryAuditStore {
     entries: std::sync::Mutex<Vec<AuditEntry>>,
+    events: broadcast::Sender<AuditEntry>,
 }
 
 impl InMemoryAuditStore {
     pub fn new() -> Self {
         Self {
             entries: std::sync::Mutex::new(Vec::new()),
+            events: broadcast::channel(STREAM_CAPACITY).0,
         }
     }
 }
@@ -77,21 +220,33 @@ impl Default for InMemoryAuditStore {
 
 #[async_trait]
 impl AuditStore for InMemoryAuditStore {
-    async fn log(&self, entry: AuditEntry) -> Result<()> {
-        self.entries.lock().unwrap().push(entry);
+    async fn log(&self, mut entry: AuditEntry) -> Result<()> {
+        entry.correlate();
+        self.entries.lock().unwrap().push(entry.clone());
+        // No subscribers is not an error
+        let _ = self.events.send(entry);
         Ok(())
     }
 
     async fn query(&self, filter: AuditFilter) -> Result<Vec<AuditEntry>> {
         let entries = self.entries.lock().unwrap();
-        let mut result: Vec<AuditEntry> = entries
+        // Stable, so entries with equal timestamps stay in logging order
+        let mut matching: Vec<&AuditEntry> = entries.iter().filter(|e| filter.matches(e)).collect();
+        matching.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        if filter.order == AuditOrder::NewestFirst {
+            matching.reverse();
+        }
+        let start = match &filter.after_id {
+            Some(id) => matching
+                .iter()
+                .position(|e| &e.id == id)
+                .map_or(matching.len(), |pos| pos + 1),
+            None => 0,
+        };
+        let mut result: Vec<AuditEntry> = matching[start..]
             .iter()
-            .filter(|e| {
-                filter.user_id.as_ref().is_none_or(|u| &e.user_id == u)
-                    && filter.action.as_ref().is_none_or(|a| &e.action == a)
-                    && filter.resource.as_ref().is_none_or(|r| &e.resource == r)
-            })
-            .cloned()
+            .skip(filter.offset.unwrap_or(0))
+            .map(|e| (*e).clone())
             .collect();
 
         if let Some(limit) = filter.limit {
@@ -100,6 +255,15 @@ impl AuditStore for InMemoryAuditStore {
 
         Ok(result)
     }
+
+    async fn count(&self, filter: AuditFilter) -> Result<usize> {
+        let entries = self.entries.lock().unwrap();
+        Ok(entries.iter().filter(|e| filter.matches(e)).count())
+    }
+
+    fn subscribe(&self) -> Option<broadcast::Receiver<AuditEntry>> {
+        Some(self.events.subscribe())
+    }
 }
 
 #[async_trait]
@@ -112,13 +276,16 @@ impl Erasable for InMemoryAuditStore {
     }
 }
 
+use crate::anchor::{AnchorCheck, AuditAnchor, ChainVerification};
 use rusqlite::{params, Connection, OptionalExtension};
 use sha2::{Digest, Sha256};
+use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 
 /// Secure audit store using SQLite and Hash Chaining.
 pub struct SqliteAuditStore {
     conn: Arc<Mutex<Connection>>,
+    events: broadcast::Sender<AuditEntry>,
 }
 
 impl SqliteAuditStore {
@@ -143,15 +310,313 @@ impl SqliteAuditStore {
         )
         .map_err(|e| multi_agent_core::error::Error::Governance(format!("Schema error: {}", e)))?;
 
+        // Correlation columns were added after the initial schema.
+        for column in ["trace_id", "session_id"] {
+            let exists: bool = conn
+                .query_row(
+                    "SELECT COUNT(*) > 0 FROM pragma_table_info('audit_logs') WHERE name = ?1",
+                    params![column],
+                    |row| row.get(0),
+                )
+                .map_err(|e| {
+                    multi_agent_core::error::Error::Governance(format!("Schema error: {}", e))
+                })?;
+            if !exists {
+                conn.execute(
+                    &format!("ALTER TABLE audit_logs ADD COLUMN {} TEXT", column),
+                    [],
+                )
+                .map_err(|e| {
+                    multi_agent_core::error::Error::Governance(format!("Schema error: {}", e))
+                })?;
+            }
+        }
+
         // Index for performance
+        for (index, column) in [
+            ("idx_audit_user", "user_id"),
+            ("idx_audit_trace", "trace_id"),
+            ("idx_audit_session", "session_id"),
+        ] {
+            conn.execute(
+                &format!(
+                    "CREATE INDEX IF NOT EXISTS {} ON audit_logs ({})",
+                    index, column
+                ),
+                [],
+            )
+            .map_err(|e| {
+                multi_agent_core::error::Error::Governance(format!("Index error: {}", e))
+            })?;
+        }
+
         conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_audit_user ON audit_logs (user_id)",
+            "CREATE TABLE IF NOT EXISTS audit_anchors (
+                id TEXT PRIMARY KEY,
+                head_hash TEXT NOT NULL,
+                entry_count INTEGER NOT NULL,
+                anchored_at INTEGER NOT NULL,
+                notary TEXT NOT NULL,
+                receipt TEXT NOT NULL
+            )",
             [],
         )
-        .map_err(|e| multi_agent_core::error::Error::Governance(format!("Index error: {}", e)))?;
+        .map_err(|e| multi_agent_core::error::Error::Governance(format!("Schema error: {}", e)))?;
 
         Ok(Self {
             conn: Arc::new(Mutex::new(conn)),
+            events: broadcast::channel(STREAM_CAPACITY).0,
+        })
+    }
+
+    /// Append the filter's WHERE clause to `query`, returning its parameters.
+    fn push_conditions(query: &mut String, filter: &AuditFilter) -> Vec<Box<dyn rusqlite::ToSql>> {
+        let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+        query.push_str(" WHERE 1=1");
+        if let Some(uid) = &filter.user_id {
+            query.push_str(" AND user_id = ?");
+            params_vec.push(Box::new(uid.clone()));
+        }
+        if let Some(act) = &filter.action {
+            query.push_str(" AND action = ?");
+            params_vec.push(Box::new(act.clone()));
+        }
+        if let Some(res) = &filter.resource {
+            query.push_str(" AND resource = ?");
+            params_vec.push(Box::new(res.clone()));
+        }
+        if let Some(trace) = &filter.trace_id {
+            query.push_str(" AND trace_id = ?");
+            params_vec.push(Box::new(trace.clone()));
+        }
+        if let Some(session) = &filter.session_id {
+            query.push_str(" AND session_id = ?");
+            params_vec.push(Box::new(session.clone()));
+        }
+        params_vec
+    }
+
+    /// Make the audit and anchor tables append-only (WORM mode).
+    ///
+    /// Updates and deletes abort, including right-to-be-forgotten erasure.
+    pub async fn enable_worm(&self) -> Result<()> {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap();
+            for table in ["audit_logs", "audit_anchors"] {
+                for op in ["UPDATE", "DELETE"] {
+                    conn.execute(
+                        &format!(
+                            "CREATE TRIGGER IF NOT EXISTS {table}_worm_{name} BEFORE {op} ON {table}
+                             BEGIN SELECT RAISE(ABORT, '{table} is append-only'); END",
+                            name = op.to_lowercase(),
+                        ),
+                        [],
+                    )
+                    .map_err(|e| {
+                        multi_agent_core::error::Error::Governance(format!("Trigger error: {}", e))
+                    })?;
+                }
+            }
+            Ok(())
+        })
+        .await
+        .map_err(|e| multi_agent_core::error::Error::Internal(e.to_string()))?
+    }
+
+    /// Hash of the newest entry and the total entry count.
+    pub async fn chain_head(&self) -> Result<Option<(String, usize)>> {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap();
+            let head: Option<String> = conn
+                .query_row(
+                    "SELECT hash FROM audit_logs ORDER BY timestamp DESC, rowid DESC LIMIT 1",
+                    [],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(|e| {
+                    multi_agent_core::error::Error::Governance(format!("Query error: {}", e))
+                })?;
+            let count: usize = conn
+                .query_row("SELECT COUNT(*) FROM audit_logs", [], |row| row.get(0))
+                .map_err(|e| {
+                    multi_agent_core::error::Error::Governance(format!("Count error: {}", e))
+                })?;
+            Ok(head.map(|h| (h, count)))
+        })
+        .await
+        .map_err(|e| multi_agent_core::error::Error::Internal(e.to_string()))?
+    }
+
+    /// Persist a notarised anchor.
+    pub async fn record_anchor(&self, anchor: &AuditAnchor) -> Result<()> {
+        let conn = self.conn.clone();
+        let anchor = anchor.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap();
+            conn.execute(
+                "INSERT INTO audit_anchors (id, head_hash, entry_count, anchored_at, notary, receipt)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    anchor.id,
+                    anchor.head_hash,
+                    anchor.entry_count,
+                    anchor.anchored_at,
+                    anchor.notary,
+                    anchor.receipt
+                ],
+            )
+            .map_err(|e| {
+                multi_agent_core::error::Error::Governance(format!("Insert error: {}", e))
+            })?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| multi_agent_core::error::Error::Internal(e.to_string()))?
+    }
+
+    /// All anchors, oldest first.
+    pub async fn anchors(&self) -> Result<Vec<AuditAnchor>> {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap();
+            let mut stmt = conn
+                .prepare(
+                    "SELECT id, head_hash, entry_count, anchored_at, notary, receipt
+                     FROM audit_anchors ORDER BY anchored_at ASC, rowid ASC",
+                )
+                .map_err(|e| {
+                    multi_agent_core::error::Error::Governance(format!("Prepare error: {}", e))
+                })?;
+            let anchors = stmt
+                .query_map([], |row| {
+                    Ok(AuditAnchor {
+                        id: row.get(0)?,
+                        head_hash: row.get(1)?,
+                        entry_count: row.get(2)?,
+                        anchored_at: row.get(3)?,
+                        notary: row.get(4)?,
+                        receipt: row.get(5)?,
+                    })
+                })
+                .map_err(|e| {
+                    multi_agent_core::error::Error::Governance(format!("Query error: {}", e))
+                })?
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(|e| {
+                    multi_agent_core::error::Error::Governance(format!("Result error: {}", e))
+                })?;
+            Ok(anchors)
+        })
+        .await
+        .map_err(|e| multi_agent_core::error::Error::Internal(e.to_string()))?
+    }
+
+    /// Recompute every entry hash in insertion order and check each anchor's
+    /// head is still in the chain. Notary checks are done by the anchorer.
+    pub async fn verify_chain(&self) -> Result<ChainVerification> {
+        let entries = {
+            let conn = self.conn.clone();
+            tokio::task::spawn_blocking(move || {
+                let conn = conn.lock().unwrap();
+                let mut stmt = conn
+                    .prepare(
+                        "SELECT id, timestamp, user_id, action, resource, outcome, metadata, previous_hash, hash, trace_id, session_id
+                         FROM audit_logs ORDER BY rowid ASC",
+                    )
+                    .map_err(|e| {
+                        multi_agent_core::error::Error::Governance(format!("Prepare error: {}", e))
+                    })?;
+                let entries = stmt
+                    .query_map([], |row| {
+                        Ok(AuditEntry {
+                            id: row.get(0)?,
+                            timestamp: row.get(1)?,
+                            user_id: row.get(2)?,
+                            action: row.get(3)?,
+                            resource: row.get(4)?,
+                            outcome: serde_json::from_str(&row.get::<_, String>(5)?)
+                                .unwrap_or(AuditOutcome::Success),
+                            metadata: row
+                                .get::<_, Option<String>>(6)?
+                                .and_then(|m| serde_json::from_str(&m).ok()),
+                            previous_hash: row.get(7)?,
+                            hash: row.get(8)?,
+                            trace_id: row.get(9)?,
+                            session_id: row.get(10)?,
+                        })
+                    })
+                    .map_err(|e| {
+                        multi_agent_core::error::Error::Governance(format!("Query error: {}", e))
+                    })?
+                    .collect::<std::result::Result<Vec<_>, _>>()
+                    .map_err(|e| {
+                        multi_agent_core::error::Error::Governance(format!("Result error: {}", e))
+                    })?;
+                Ok::<_, multi_agent_core::Error>(entries)
+            })
+            .await
+            .map_err(|e| multi_agent_core::error::Error::Internal(e.to_string()))??
+        };
+
+        let mut errors = Vec::new();
+        let mut seen: HashSet<&str> = HashSet::new();
+        for (i, entry) in entries.iter().enumerate() {
+            let expected = Self::calculate_hash(entry, entry.previous_hash.as_deref());
+            if entry.hash.as_deref() != Some(expected.as_str()) {
+                errors.push(format!(
+                    "Entry {} hash does not match its contents",
+                    entry.id
+                ));
+            }
+            match entry.previous_hash.as_deref() {
+                None if i == 0 => {}
+                None => errors.push(format!("Entry {} is not linked to the chain", entry.id)),
+                Some(prev) if !seen.contains(prev) => {
+                    errors.push(format!("Entry {} links to a missing predecessor", entry.id))
+                }
+                Some(_) => {}
+            }
+            if let Some(hash) = entry.hash.as_deref() {
+                seen.insert(hash);
+            }
+        }
+
+        let anchors = self
+            .anchors()
+            .await?
+            .into_iter()
+            .map(|anchor| {
+                let error = if !seen.contains(anchor.head_hash.as_str()) {
+                    Some("Anchored chain head is missing from the log".to_string())
+                } else if entries.len() < anchor.entry_count {
+                    Some(format!(
+                        "Log has {} entries, fewer than the {} anchored",
+                        entries.len(),
+                        anchor.entry_count
+                    ))
+                } else {
+                    None
+                };
+                AnchorCheck {
+                    anchor_id: anchor.id,
+                    head_hash: anchor.head_hash,
+                    entry_count: anchor.entry_count,
+                    anchored_at: anchor.anchored_at,
+                    notary: anchor.notary,
+                    valid: error.is_none(),
+                    error,
+                }
+            })
+            .collect();
+
+        Ok(ChainVerification {
+            entries: entries.len(),
+            chain_valid: errors.is_empty(),
+            errors,
+            anchors,
         })
     }
 
@@ -170,6 +635,13 @@ impl SqliteAuditStore {
                 .map(|m| m.to_string())
                 .unwrap_or_default(),
         );
+        // Only hashed when set, so entries written before correlation verify.
+        if let Some(trace_id) = &entry.trace_id {
+            hasher.update(trace_id);
+        }
+        if let Some(session_id) = &entry.session_id {
+            hasher.update(session_id);
+        }
         if let Some(ph) = prev_hash {
             hasher.update(ph);
         }
@@ -180,8 +652,9 @@ impl SqliteAuditStore {
 #[async_trait]
 impl AuditStore for SqliteAuditStore {
     async fn log(&self, mut entry: AuditEntry) -> Result<()> {
+        entry.correlate();
         let conn = self.conn.clone();
-        tokio::task::spawn_blocking(move || {
+        let entry = tokio::task::spawn_blocking(move || {
             let mut conn = conn.lock().unwrap();
             let tx = conn.transaction()
                 .map_err(|e| multi_agent_core::error::Error::Governance(format!("Tx error: {}", e)))?;
@@ -198,8 +671,8 @@ impl AuditStore for SqliteAuditStore {
             entry.hash = Some(Self::calculate_hash(&entry, prev_hash.as_deref()));
 
             tx.execute(
-                "INSERT INTO audit_logs (id, timestamp, user_id, action, resource, outcome, metadata, previous_hash, hash)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                "INSERT INTO audit_logs (id, timestamp, user_id, action, resource, outcome, metadata, previous_hash, hash, trace_id, session_id)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
                 params![
                     entry.id,
                     entry.timestamp,
@@ -207,43 +680,50 @@ impl AuditStore for SqliteAuditStore {
                     entry.action,
                     entry.resource,
                     serde_json::to_string(&entry.outcome).unwrap_or_default(),
-                    entry.metadata.map(|m| m.to_string()),
+                    entry.metadata.as_ref().map(|m| m.to_string()),
                     entry.previous_hash,
-                    entry.hash
+                    entry.hash,
+                    entry.trace_id,
+                    entry.session_id
                 ],
             ).map_err(|e| multi_agent_core::error::Error::Governance(format!("Insert error: {}", e)))?;
 
             tx.commit()
                 .map_err(|e| multi_agent_core::error::Error::Governance(format!("Commit error: {}", e)))?;
-            Ok(())
+            Ok::<_, multi_agent_core::error::Error>(entry)
         })
         .await
-        .map_err(|e| multi_agent_core::error::Error::Internal(e.to_string()))?
+        .map_err(|e| multi_agent_core::error::Error::Internal(e.to_string()))??;
+        let _ = self.events.send(entry);
+        Ok(())
     }
 
     async fn query(&self, filter: AuditFilter) -> Result<Vec<AuditEntry>> {
         let conn = self.conn.clone();
         tokio::task::spawn_blocking(move || {
             let conn = conn.lock().unwrap();
-            let mut query = "SELECT id, timestamp, user_id, action, resource, outcome, metadata, previous_hash, hash FROM audit_logs WHERE 1=1".to_string();
-            let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+            let mut query = "SELECT id, timestamp, user_id, action, resource, outcome, metadata, previous_hash, hash, trace_id, session_id FROM audit_logs".to_string();
+            let mut params_vec = Self::push_conditions(&mut query, &filter);
 
-            if let Some(uid) = &filter.user_id {
-                query.push_str(" AND user_id = ?");
-                params_vec.push(Box::new(uid.clone()));
-            }
-            if let Some(act) = &filter.action {
-                query.push_str(" AND action = ?");
-                params_vec.push(Box::new(act.clone()));
+            let (direction, after) = match filter.order {
+                AuditOrder::NewestFirst => ("DESC", "<"),
+                AuditOrder::OldestFirst => ("ASC", ">"),
+            };
+            if let Some(after_id) = &filter.after_id {
+                // No row matches if the cursor entry is gone
+                query.push_str(&format!(
+                    " AND (timestamp, rowid) {} (SELECT timestamp, rowid FROM audit_logs WHERE id = ?)",
+                    after
+                ));
+                params_vec.push(Box::new(after_id.clone()));
             }
-            if let Some(res) = &filter.resource {
-                query.push_str(" AND resource = ?");
-                params_vec.push(Box::new(res.clone()));
-            }
-
-            query.push_str(" ORDER BY timestamp DESC");
-            if let Some(limit) = filter.limit {
-                query.push_str(&format!(" LIMIT {}", limit));
+            query.push_str(&format!(" ORDER BY timestamp {0}, rowid {0}", direction));
+            match (filter.limit, filter.offset) {
+                (Some(limit), Some(offset)) => query.push_str(&format!(" LIMIT {} OFFSET {}", limit, offset)),
+                (Some(limit), None) => query.push_str(&format!(" LIMIT {}", limit)),
+                // SQLite needs a LIMIT before OFFSET; -1 means unbounded.
+                (None, Some(offset)) => query.push_str(&format!(" LIMIT -1 OFFSET {}", offset)),
+                (None, None) => {}
             }
 
             let mut stmt = conn.prepare(&query)
@@ -262,6 +742,8 @@ impl AuditStore for SqliteAuditStore {
                     metadata: row.get::<_, Option<String>>(6)?.and_then(|m| serde_json::from_str(&m).ok()),
                     previous_hash: row.get(7)?,
                     hash: row.get(8)?,
+                    trace_id: row.get(9)?,
+                    session_id: row.get(10)?,
                 })
             }).map_err(|e| multi_agent_core::error::Error::Governance(format!("Query error: {}", e)))?
             .collect::<std::result::Result<Vec<_>, _>>()
@@ -272,6 +754,27 @@ impl AuditStore for SqliteAuditStore {
         .await
         .map_err(|e| multi_agent_core::error::Error::Internal(e.to_string()))?
     }
+
+    async fn count(&self, filter: AuditFilter) -> Result<usize> {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().unwrap();
+            let mut query = "SELECT COUNT(*) FROM audit_logs".to_string();
+            let params_vec = Self::push_conditions(&mut query, &filter);
+            let param_refs: Vec<&dyn rusqlite::ToSql> =
+                params_vec.iter().map(|p| p.as_ref()).collect();
+            conn.query_row(&query, &param_refs[..], |row| row.get::<_, usize>(0))
+                .map_err(|e| {
+                    multi_agent_core::error::Error::Governance(format!("Count error: {}", e))
+                })
+        })
+        .await
+        .map_err(|e| multi_agent_core::error::Error::Internal(e.to_string()))?
+    }
+
+    fn subscribe(&self) -> Option<broadcast::Receiver<AuditEntry>> {
+        Some(self.events.subscribe())
+    }
 }
 
 #[async_trait]
@@ -314,6 +817,8 @@ mod tests {
             metadata: None,
             previous_hash: None,
             hash: None,
+            trace_id: None,
+            session_id: None,
         };
 
         store.log(entry.clone()).await.unwrap();
@@ -328,6 +833,229 @@ mod tests {
         assert!(results[0].hash.is_some());
     }
 
+    #[tokio::test]
+    async fn test_pagination_and_count() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let sqlite = SqliteAuditStore::new(temp_file.path()).unwrap();
+        let memory = InMemoryAuditStore::new();
+        let stores: [&dyn AuditStore; 2] = [&sqlite, &memory];
+
+        for store in stores {
+            for i in 0..5 {
+                store
+                    .log(AuditEntry {
+                        id: format!("e{}", i),
+                        timestamp: format!("2023-01-01T00:00:0{}Z", i),
+                        user_id: if i % 2 == 0 { "even" } else { "odd" }.into(),
+                        action: "ACTION".into(),
+                        resource: "res".into(),
+                        outcome: AuditOutcome::Success,
+                        metadata: None,
+                        previous_hash: None,
+                        hash: None,
+                        trace_id: None,
+                        session_id: None,
+                    })
+                    .await
+                    .unwrap();
+            }
+
+            let page = store
+                .query(AuditFilter {
+                    limit: Some(2),
+                    offset: Some(2),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            assert_eq!(page.len(), 2);
+            let rest = store
+                .query(AuditFilter {
+                    offset: Some(4),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            assert_eq!(rest.len(), 1);
+
+            let even = AuditFilter {
+                user_id: Some("even".into()),
+                limit: Some(1),
+                ..Default::default()
+            };
+            assert_eq!(store.count(even).await.unwrap(), 3);
+            assert_eq!(store.count(AuditFilter::default()).await.unwrap(), 5);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cursor_pagination_and_order() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let sqlite = SqliteAuditStore::new(temp_file.path()).unwrap();
+        let memory = InMemoryAuditStore::new();
+        let stores: [&dyn AuditStore; 2] = [&sqlite, &memory];
+
+        for store in stores {
+            // e3 and e4 share a timestamp; logging order breaks the tie
+            for (i, second) in [0, 1, 2, 3, 3].into_iter().enumerate() {
+                store
+                    .log(AuditEntry {
+                        id: format!("e{}", i),
+                        timestamp: format!("2023-01-01T00:00:0{}Z", second),
+                        user_id: "user".into(),
+                        action: "ACTION".into(),
+                        resource: "res".into(),
+                        outcome: AuditOutcome::Success,
+                        metadata: None,
+                        previous_hash: None,
+                        hash: None,
+                        trace_id: None,
+                        session_id: None,
+                    })
+                    .await
+                    .unwrap();
+            }
+
+            let ids = |page: &AuditEntryPage| -> Vec<String> {
+                page.entries.iter().map(|e| e.id.clone()).collect()
+            };
+            let first = store.query_page(AuditFilter::default(), 2).await.unwrap();
+            assert_eq!(ids(&first), vec!["e4", "e3"]);
+            assert_eq!(first.next_cursor.as_deref(), Some("e3"));
+            let second = store
+                .query_page(
+                    AuditFilter {
+                        after_id: first.next_cursor.clone(),
+                        ..Default::default()
+                    },
+                    2,
+                )
+                .await
+                .unwrap();
+            assert_eq!(ids(&second), vec!["e2", "e1"]);
+            let last = store
+                .query_page(
+                    AuditFilter {
+                        after_id: second.next_cursor.clone(),
+                        ..Default::default()
+                    },
+                    2,
+                )
+                .await
+                .unwrap();
+            assert_eq!(ids(&last), vec!["e0"]);
+            assert_eq!(last.next_cursor, None);
+
+            let oldest = store
+                .query_page(
+                    AuditFilter {
+                        order: AuditOrder::OldestFirst,
+                        after_id: Some("e2".into()),
+                        ..Default::default()
+                    },
+                    5,
+                )
+                .await
+                .unwrap();
+            assert_eq!(ids(&oldest), vec!["e3", "e4"]);
+            assert_eq!(oldest.next_cursor, None);
+
+            let unknown = AuditFilter {
+                after_id: Some("missing".into()),
+                ..Default::default()
+            };
+            assert!(store.query(unknown).await.unwrap().is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_receives_logged_entries() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let sqlite = SqliteAuditStore::new(temp_file.path()).unwrap();
+        let memory = InMemoryAuditStore::new();
+        let stores: [&dyn AuditStore; 2] = [&sqlite, &memory];
+
+        for store in stores {
+            let mut rx = store.subscribe().unwrap();
+            store
+                .log(AuditEntry {
+                    id: "e1".into(),
+                    timestamp: "2023-01-01T00:00:00Z".into(),
+                    user_id: "user".into(),
+                    action: "LOGIN".into(),
+                    resource: "session".into(),
+                    outcome: AuditOutcome::Success,
+                    metadata: None,
+                    previous_hash: None,
+                    hash: None,
+                    trace_id: None,
+                    session_id: None,
+                })
+                .await
+                .unwrap();
+            let entry = rx.recv().await.unwrap();
+            assert_eq!(entry.action, "LOGIN");
+            // Streamed entries carry what was stored, including the chain hash
+            let stored = store.query(AuditFilter::default()).await.unwrap();
+            assert_eq!(entry.hash, stored[0].hash);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_entries_correlated_with_context() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let sqlite = SqliteAuditStore::new(temp_file.path()).unwrap();
+        let memory = InMemoryAuditStore::new();
+        let stores: [&dyn AuditStore; 2] = [&sqlite, &memory];
+
+        for store in stores {
+            let context = AuditContext {
+                user_id: Some("alice".into()),
+                trace_id: Some("trace-1".into()),
+                session_id: Some("sess-1".into()),
+            };
+            context
+                .scope(async {
+                    assert_eq!(AuditContext::user_or("admin"), "alice");
+                    store
+                        .log(AuditEntry {
+                            id: "in-request".into(),
+                            timestamp: "2023-01-01T00:00:00Z".into(),
+                            user_id: AuditContext::user_or("admin"),
+                            action: "ACTION".into(),
+                            resource: "res".into(),
+                            outcome: AuditOutcome::Success,
+                            metadata: None,
+                            previous_hash: None,
+                            hash: None,
+                            trace_id: None,
+                            session_id: None,
+                        })
+                        .await
+                        .unwrap();
+                })
+                .await;
+            assert_eq!(AuditContext::user_or("admin"), "admin");
+
+            let entries = store
+                .query(AuditFilter {
+                    trace_id: Some("trace-1".into()),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries[0].user_id, "alice");
+            assert_eq!(entries[0].session_id.as_deref(), Some("sess-1"));
+            let other_session = AuditFilter {
+                session_id: Some("sess-2".into()),
+                ..Default::default()
+            };
+            assert_eq!(store.count(other_session).await.unwrap(), 0);
+        }
+        assert!(sqlite.verify_chain().await.unwrap().chain_valid);
+    }
+
     #[tokio::test]
     async fn test_hash_chain_integrity() {
         let temp_file = NamedTempFile::new().unwrap();
@@ -344,6 +1072,8 @@ mod tests {
             metadata: None,
             previous_hash: None,
             hash: None,
+            trace_id: None,
+            session_id: None,
         };
 
         let entry2 = AuditEntry {
@@ -356,6 +1086,8 @@ mod tests {
             metadata: None,
             previous_hash: None,
             hash: None,
+            trace_id: None,
+            session_id: None,
         };
 
         store.log(entry1).await.unwrap();
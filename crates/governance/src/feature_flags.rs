@@ -0,0 +1,282 @@
+//! Feature flags with runtime overrides.
+//!
+//! Flags are defined in `[governance.feature_flags]` with a default and
+//! optional per-workspace values. Operators override them through the admin
+//! API, globally or for one workspace, and overrides are persisted so they
+//! survive restarts. A flag resolves, most specific first, to the workspace
+//! override, the global override, the configured workspace value, and
+//! finally the configured default.
+//!
+//! The gateway resolves every flag for the request's workspace into its
+//! [`RequestContext`](multi_agent_core::types::RequestContext), where the
+//! controller, capabilities and model selection read them.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use multi_agent_core::config::FeatureFlagDefinition;
+use multi_agent_core::{Error, Result};
+
+/// Runtime overrides of one flag.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FlagOverride {
+    /// Value for every workspace without a workspace override.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// Values for single workspaces.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub workspaces: BTreeMap<String, bool>,
+}
+
+impl FlagOverride {
+    fn is_empty(&self) -> bool {
+        self.enabled.is_none() && self.workspaces.is_empty()
+    }
+}
+
+/// A flag as listed by the admin API.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FeatureFlagState {
+    pub name: String,
+    /// Configured definition; `None` for flags only created by overrides.
+    pub definition: Option<FeatureFlagDefinition>,
+    #[serde(rename = "override")]
+    pub override_: FlagOverride,
+    /// Value for workspaces without specific settings.
+    pub enabled: bool,
+}
+
+/// Resolves feature flags from configuration and runtime overrides.
+pub struct FeatureFlagService {
+    definitions: BTreeMap<String, FeatureFlagDefinition>,
+    overrides: RwLock<BTreeMap<String, FlagOverride>>,
+    persistence_path: Option<PathBuf>,
+}
+
+impl FeatureFlagService {
+    /// Service over the configured flags, with overrides kept in memory.
+    pub fn new(definitions: BTreeMap<String, FeatureFlagDefinition>) -> Self {
+        Self {
+            definitions,
+            overrides: RwLock::new(BTreeMap::new()),
+            persistence_path: None,
+        }
+    }
+
+    /// Service whose overrides are loaded from and saved to `path`.
+    pub fn new_persistent(
+        definitions: BTreeMap<String, FeatureFlagDefinition>,
+        path: impl AsRef<Path>,
+    ) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut overrides = BTreeMap::new();
+        if path.exists() {
+            let content = std::fs::read_to_string(&path).map_err(|e| {
+                Error::governance(format!("Read feature flag overrides failed: {}", e))
+            })?;
+            if !content.trim().is_empty() {
+                overrides = serde_json::from_str(&content).map_err(|e| {
+                    Error::governance(format!("Parse feature flag overrides failed: {}", e))
+                })?;
+            }
+        }
+        Ok(Self {
+            definitions,
+            overrides: RwLock::new(overrides),
+            persistence_path: Some(path),
+        })
+    }
+
+    /// Whether `flag` is on for `workspace_id`; unknown flags are off.
+    pub fn is_enabled(&self, flag: &str, workspace_id: Option<&str>) -> bool {
+        let overrides = self.overrides.read().unwrap_or_else(|e| e.into_inner());
+        self.resolve_one(flag, overrides.get(flag), workspace_id)
+    }
+
+    /// Every known flag resolved for `workspace_id`.
+    pub fn resolve(&self, workspace_id: Option<&str>) -> BTreeMap<String, bool> {
+        let overrides = self.overrides.read().unwrap_or_else(|e| e.into_inner());
+        self.names(&overrides)
+            .into_iter()
+            .map(|name| {
+                let enabled = self.resolve_one(&name, overrides.get(&name), workspace_id);
+                (name, enabled)
+            })
+            .collect()
+    }
+
+    /// Every known flag with its definition and overrides.
+    pub fn list(&self) -> Vec<FeatureFlagState> {
+        let overrides = self.overrides.read().unwrap_or_else(|e| e.into_inner());
+        self.names(&overrides)
+            .into_iter()
+            .map(|name| FeatureFlagState {
+                definition: self.definitions.get(&name).cloned(),
+                override_: overrides.get(&name).cloned().unwrap_or_default(),
+                enabled: self.resolve_one(&name, overrides.get(&name), None),
+                name,
+            })
+            .collect()
+    }
+
+    /// Override `flag` for `workspace_id`, or for all workspaces.
+    pub fn set_override(
+        &self,
+        flag: &str,
+        workspace_id: Option<&str>,
+        enabled: bool,
+    ) -> Result<()> {
+        if flag.trim().is_empty() {
+            return Err(Error::invalid_request("Feature flag name is empty"));
+        }
+        self.update(|overrides| {
+            let entry = overrides.entry(flag.to_string()).or_default();
+            match workspace_id {
+                Some(workspace) => {
+                    entry.workspaces.insert(workspace.to_string(), enabled);
+                }
+                None => entry.enabled = Some(enabled),
+            }
+            true
+        })
+        .map(|_| ())
+    }
+
+    /// Remove the override of `flag` for `workspace_id`, or the global one.
+    /// Returns whether there was one.
+    pub fn clear_override(&self, flag: &str, workspace_id: Option<&str>) -> Result<bool> {
+        self.update(|overrides| {
+            let Some(entry) = overrides.get_mut(flag) else {
+                return false;
+            };
+            let removed = match workspace_id {
+                Some(workspace) => entry.workspaces.remove(workspace).is_some(),
+                None => entry.enabled.take().is_some(),
+            };
+            if entry.is_empty() {
+                overrides.remove(flag);
+            }
+            removed
+        })
+    }
+
+    fn resolve_one(
+        &self,
+        flag: &str,
+        override_: Option<&FlagOverride>,
+        workspace_id: Option<&str>,
+    ) -> bool {
+        let definition = self.definitions.get(flag);
+        workspace_id
+            .and_then(|w| override_.and_then(|o| o.workspaces.get(w)).copied())
+            .or_else(|| override_.and_then(|o| o.enabled))
+            .or_else(|| {
+                workspace_id.and_then(|w| definition.and_then(|d| d.workspaces.get(w)).copied())
+            })
+            .unwrap_or_else(|| definition.is_some_and(|d| d.enabled))
+    }
+
+    fn names(&self, overrides: &BTreeMap<String, FlagOverride>) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .definitions
+            .keys()
+            .chain(overrides.keys())
+            .cloned()
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    fn update(
+        &self,
+        change: impl FnOnce(&mut BTreeMap<String, FlagOverride>) -> bool,
+    ) -> Result<bool> {
+        let mut overrides = self.overrides.write().unwrap_or_else(|e| e.into_inner());
+        let mut updated = overrides.clone();
+        let changed = change(&mut updated);
+        if changed {
+            self.persist(&updated)?;
+            *overrides = updated;
+        }
+        Ok(changed)
+    }
+
+    fn persist(&self, overrides: &BTreeMap<String, FlagOverride>) -> Result<()> {
+        let Some(path) = &self.persistence_path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| Error::governance(format!("Create feature flag dir failed: {}", e)))?;
+        }
+        let content = serde_json::to_string_pretty(overrides).map_err(|e| {
+            Error::governance(format!("Serialize feature flag overrides failed: {}", e))
+        })?;
+        std::fs::write(path, content)
+            .map_err(|e| Error::governance(format!("Write feature flag overrides failed: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definitions() -> BTreeMap<String, FeatureFlagDefinition> {
+        BTreeMap::from([(
+            "delegation".to_string(),
+            FeatureFlagDefinition {
+                enabled: false,
+                workspaces: BTreeMap::from([("beta".to_string(), true)]),
+                description: None,
+            },
+        )])
+    }
+
+    #[test]
+    fn test_resolution_order() {
+        let flags = FeatureFlagService::new(definitions());
+        assert!(!flags.is_enabled("delegation", None));
+        assert!(!flags.is_enabled("delegation", Some("acme")));
+        assert!(flags.is_enabled("delegation", Some("beta")));
+        assert!(!flags.is_enabled("unknown", Some("beta")));
+
+        // A global override is the kill switch, even for targeted workspaces.
+        flags.set_override("delegation", None, false).unwrap();
+        assert!(!flags.is_enabled("delegation", Some("beta")));
+
+        flags
+            .set_override("delegation", Some("acme"), true)
+            .unwrap();
+        assert!(flags.is_enabled("delegation", Some("acme")));
+        assert_eq!(
+            flags.resolve(Some("acme")),
+            BTreeMap::from([("delegation".to_string(), true)])
+        );
+
+        assert!(flags.clear_override("delegation", None).unwrap());
+        assert!(!flags.clear_override("delegation", None).unwrap());
+        assert!(flags.is_enabled("delegation", Some("beta")));
+    }
+
+    #[test]
+    fn test_overrides_persist() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("flags/feature_flags.json");
+
+        let flags = FeatureFlagService::new_persistent(definitions(), &path).unwrap();
+        flags
+            .set_override("provider.new-model", Some("beta"), true)
+            .unwrap();
+        drop(flags);
+
+        let flags = FeatureFlagService::new_persistent(definitions(), &path).unwrap();
+        assert!(flags.is_enabled("provider.new-model", Some("beta")));
+        assert!(!flags.is_enabled("provider.new-model", Some("acme")));
+        let names: Vec<_> = flags.list().into_iter().map(|f| f.name).collect();
+        assert_eq!(names, vec!["delegation", "provider.new-model"]);
+    }
+}
@@ -0,0 +1,288 @@
+//! File type policy for artifact ingestion.
+//!
+//! [`PolicyArtifactStore`] wraps an artifact store and refuses writes whose
+//! content type is not allowed or whose size exceeds the cap for that type.
+//! Refusals are returned as [`Error::FileTypeRejected`] and audited.
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use multi_agent_core::config::FileTypePolicyConfig;
+use multi_agent_core::traits::{ArtifactFilter, ArtifactMetadata, ArtifactStore, Erasable};
+use multi_agent_core::types::{ArtifactOwner, RefId};
+use multi_agent_core::{Error, Result};
+
+use crate::audit::{AuditEntry, AuditOutcome, AuditStore};
+
+/// Content type assumed for writes that do not declare one.
+const UNTYPED: &str = "application/octet-stream";
+
+/// Allowed content types and size caps.
+#[derive(Debug, Clone, Default)]
+pub struct FileTypePolicy {
+    allowed: Vec<String>,
+    max_bytes: u64,
+    type_max_bytes: HashMap<String, u64>,
+}
+
+/// Whether `content_type` matches a rule such as `image/png` or `image/*`.
+fn type_matches(rule: &str, content_type: &str) -> bool {
+    match rule.strip_suffix("/*") {
+        Some(major) => content_type
+            .split_once('/')
+            .is_some_and(|(m, _)| m == major),
+        None => rule == "*" || rule == content_type,
+    }
+}
+
+impl FileTypePolicy {
+    pub fn from_config(config: &FileTypePolicyConfig) -> Self {
+        let mut allowed: Vec<String> = config
+            .allowed_types
+            .iter()
+            .map(|t| t.to_lowercase())
+            .collect();
+        for ext in &config.allowed_extensions {
+            let ext = ext.trim_start_matches('.');
+            allowed.extend(
+                mime_guess::from_ext(ext)
+                    .iter()
+                    .map(|mime| mime.essence_str().to_string()),
+            );
+        }
+        Self {
+            allowed,
+            max_bytes: config.max_bytes,
+            type_max_bytes: config
+                .type_max_bytes
+                .iter()
+                .map(|(k, v)| (k.to_lowercase(), *v))
+                .collect(),
+        }
+    }
+
+    /// Size cap for a content type; exact entries win over wildcards.
+    fn cap_for(&self, content_type: &str) -> u64 {
+        self.type_max_bytes
+            .get(content_type)
+            .or_else(|| {
+                self.type_max_bytes
+                    .iter()
+                    .find(|(rule, _)| type_matches(rule, content_type))
+                    .map(|(_, cap)| cap)
+            })
+            .copied()
+            .unwrap_or(self.max_bytes)
+    }
+
+    /// Check a write of `size` bytes declared as `content_type`.
+    pub fn check(&self, content_type: &str, size: usize) -> Result<()> {
+        let essence = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase();
+        let reject = |reason: String| {
+            Err(Error::FileTypeRejected {
+                content_type: essence.clone(),
+                size,
+                reason,
+            })
+        };
+        if !self.allowed.is_empty() && !self.allowed.iter().any(|r| type_matches(r, &essence)) {
+            return reject("content type is not on the allowlist".to_string());
+        }
+        let cap = self.cap_for(&essence);
+        if cap > 0 && size as u64 > cap {
+            return reject(format!("exceeds the {} byte limit for this type", cap));
+        }
+        Ok(())
+    }
+}
+
+/// Artifact store wrapper enforcing a [`FileTypePolicy`] on writes.
+pub struct PolicyArtifactStore {
+    inner: Arc<dyn ArtifactStore>,
+    policy: FileTypePolicy,
+    audit: Option<Arc<dyn AuditStore>>,
+}
+
+impl PolicyArtifactStore {
+    pub fn new(inner: Arc<dyn ArtifactStore>, policy: FileTypePolicy) -> Self {
+        Self {
+            inner,
+            policy,
+            audit: None,
+        }
+    }
+
+    /// Record refused writes in the audit log.
+    pub fn with_audit(mut self, audit: Arc<dyn AuditStore>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    async fn check(&self, content_type: &str, size: usize) -> Result<()> {
+        let result = self.policy.check(content_type, size);
+        if let Err(Error::FileTypeRejected {
+            content_type,
+            size,
+            reason,
+        }) = &result
+        {
+            metrics::counter!("artifact_writes_rejected_total").increment(1);
+            tracing::warn!(content_type = %content_type, size, reason = %reason, "Artifact write rejected");
+            if let Some(audit) = &self.audit {
+                let owner = ArtifactOwner::current().unwrap_or_default();
+                let _ = audit
+                    .log(AuditEntry {
+                        id: uuid::Uuid::new_v4().to_string(),
+                        timestamp: chrono::Utc::now().to_rfc3339(),
+                        user_id: owner.user_id.unwrap_or_else(|| "system".to_string()),
+                        action: "ARTIFACT_REJECTED".to_string(),
+                        resource: content_type.clone(),
+                        outcome: AuditOutcome::Denied,
+                        metadata: Some(serde_json::json!({
+                            "size": size,
+                            "reason": reason,
+                            "session_id": owner.session_id,
+                        })),
+                        previous_hash: None,
+                        hash: None,
+                        trace_id: None,
+                        session_id: owner.session_id.clone(),
+                    })
+                    .await;
+            }
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl ArtifactStore for PolicyArtifactStore {
+    async fn save(&self, data: Bytes) -> Result<RefId> {
+        self.check(UNTYPED, data.len()).await?;
+        self.inner.save(data).await
+    }
+
+    async fn save_with_id(&self, id: &RefId, data: Bytes) -> Result<()> {
+        self.check(UNTYPED, data.len()).await?;
+        self.inner.save_with_id(id, data).await
+    }
+
+    async fn save_with_type(&self, data: Bytes, content_type: &str) -> Result<RefId> {
+        self.check(content_type, data.len()).await?;
+        self.inner.save_with_type(data, content_type).await
+    }
+
+    async fn load(&self, id: &RefId) -> Result<Option<Bytes>> {
+        self.inner.load(id).await
+    }
+
+    async fn load_range(&self, id: &RefId, start: u64, end: u64) -> Result<Option<Bytes>> {
+        self.inner.load_range(id, start, end).await
+    }
+
+    async fn presigned_url(
+        &self,
+        id: &RefId,
+        expires_in: std::time::Duration,
+    ) -> Result<Option<String>> {
+        self.inner.presigned_url(id, expires_in).await
+    }
+
+    async fn delete(&self, id: &RefId) -> Result<()> {
+        self.inner.delete(id).await
+    }
+
+    async fn exists(&self, id: &RefId) -> Result<bool> {
+        self.inner.exists(id).await
+    }
+
+    async fn metadata(&self, id: &RefId) -> Result<Option<ArtifactMetadata>> {
+        self.inner.metadata(id).await
+    }
+
+    async fn list(&self, filter: ArtifactFilter) -> Result<Vec<ArtifactMetadata>> {
+        self.inner.list(filter).await
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        self.inner.health_check().await
+    }
+}
+
+#[async_trait]
+impl Erasable for PolicyArtifactStore {
+    async fn erase_user(&self, user_id: &str) -> Result<usize> {
+        self.inner.erase_user(user_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::{AuditFilter, InMemoryAuditStore};
+    use multi_agent_store::InMemoryStore;
+
+    fn policy() -> FileTypePolicy {
+        FileTypePolicy::from_config(&FileTypePolicyConfig {
+            allowed_types: vec!["text/*".into(), "application/json".into()],
+            allowed_extensions: vec![".png".into()],
+            max_bytes: 100,
+            type_max_bytes: HashMap::from([
+                ("text/*".to_string(), 10),
+                ("text/csv".to_string(), 50),
+            ]),
+        })
+    }
+
+    #[test]
+    fn test_allowlist_and_caps() {
+        let policy = policy();
+        assert!(policy.check("text/plain; charset=utf-8", 10).is_ok());
+        assert!(policy.check("image/png", 100).is_ok());
+        assert!(policy.check("text/csv", 50).is_ok());
+        assert!(matches!(
+            policy.check("text/plain", 11),
+            Err(Error::FileTypeRejected { size: 11, .. })
+        ));
+        assert!(matches!(
+            policy.check("application/x-msdownload", 1),
+            Err(Error::FileTypeRejected { ref content_type, .. }) if content_type == "application/x-msdownload"
+        ));
+        assert!(policy.check("application/octet-stream", 1).is_err());
+        assert!(FileTypePolicy::default()
+            .check("anything/else", 1 << 30)
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rejected_writes_are_audited() {
+        let audit = Arc::new(InMemoryAuditStore::new());
+        let store = PolicyArtifactStore::new(Arc::new(InMemoryStore::new()), policy())
+            .with_audit(audit.clone());
+
+        let id = store
+            .save_with_type(Bytes::from("{}"), "application/json")
+            .await
+            .unwrap();
+        assert!(store.exists(&id).await.unwrap());
+
+        let err = ArtifactOwner::new(Some("alice".into()), Some("s1".into()))
+            .scope(store.save(Bytes::from("MZ")))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::FileTypeRejected { .. }));
+
+        let entries = audit.query(AuditFilter::default()).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, "ARTIFACT_REJECTED");
+        assert_eq!(entries[0].user_id, "alice");
+        assert_eq!(entries[0].resource, "application/octet-stream");
+    }
+}
@@ -6,15 +6,23 @@
 //! - Security proxy (request validation)
 //! - Distributed tracing
 //! - RBAC connector for enterprise IAM
-//! - Audit logging
+//! - Audit logging (with optional WORM anchoring)
 //! - Encrypted secrets management
+//! - Notification sinks (log, webhook)
+//! - Feature flags with runtime overrides
+//! - Content moderation through external providers
 
+pub mod anchor;
 pub mod approval;
 pub mod audit;
 pub mod budget;
+pub mod feature_flags;
+pub mod file_policy;
 pub mod guardrails;
 pub mod metrics;
+pub mod moderation;
 pub mod network;
+pub mod notify;
 pub mod policy;
 pub mod privacy;
 pub mod rbac;
@@ -23,18 +31,34 @@ pub mod security;
 pub mod storage_encryption;
 pub mod tracing_layer;
 
-pub use approval::{AutoApproveGate, ChannelApprovalGate};
+pub use anchor::{AuditAnchor, AuditAnchorer, AuditNotary, ChainVerification, Rfc3161Notary};
+pub use approval::{
+    AutoApproveGate, BatchingApprovalGate, ChannelApprovalGate, ChannelHumanInput, PRE_AUTHORIZED,
+};
 pub use audit::{
-    AuditEntry, AuditFilter, AuditOutcome, AuditStore, InMemoryAuditStore, SqliteAuditStore,
+    AuditContext, AuditEntry, AuditEntryPage, AuditFilter, AuditOrder, AuditOutcome, AuditStore,
+    InMemoryAuditStore, SqliteAuditStore,
 };
 pub use budget::TokenBudgetController;
+pub use feature_flags::{FeatureFlagService, FeatureFlagState, FlagOverride};
+pub use file_policy::{FileTypePolicy, PolicyArtifactStore};
 pub use guardrails::{
-    CompositeGuardrail, Guardrail, GuardrailResult, PiiScanner, PromptInjectionDetector,
+    CompositeGuardrail, Guardrail, GuardrailChannel, GuardrailPolicy, GuardrailResult,
+    GuardrailRules, InjectionLevel, PiiScanner, PromptInjectionDetector, RouteGuardrails,
     ViolationType,
 };
 pub use metrics::{setup_metrics_recorder, track_request, track_tokens};
-pub use policy::{PolicyDecision, PolicyEngine, PolicyFile, PolicyRule, RuleAction, RuleMatch};
-pub use privacy::{DeletionReport, PrivacyController};
+pub use moderation::{
+    AzureContentSafety, ModerationAction, ModerationDirection, ModerationOutcome,
+    ModerationProvider, Moderator, OpenAiModeration,
+};
+pub use notify::{LogSink, WebhookSink};
+pub use policy::{
+    ApprovalMode, ApprovalRequirement, PolicyDecision, PolicyEngine, PolicyFile, PolicyRule,
+    PolicyThresholds, RuleAction, RuleMatch, RuleMode, ShadowMatch, ToolApprovalOverride,
+    WorkspaceApprovalOverride,
+};
+pub use privacy::{DeletionReport, PrivacyController, StoreDeletion};
 pub use rbac::{NoOpRbacConnector, RbacConnector, StaticTokenRbacConnector, UserRoles};
 pub use secrets::{AesGcmSecretsManager, EncryptedSecret, SecretsManager};
 pub use security::DefaultSecurityProxy;
@@ -0,0 +1,436 @@
+//! Content moderation through external providers.
+//!
+//! A [`ModerationProvider`] scores text per category (hate, violence, ...)
+//! on a 0 to 1 scale. The [`Moderator`] maps the scores onto the configured
+//! thresholds: content at or above a category's `block` threshold is
+//! rejected, at or above its `flag` threshold it passes but is flagged for
+//! compliance review. Providers:
+//!
+//! - [`OpenAiModeration`]: OpenAI `/v1/moderations`
+//! - [`AzureContentSafety`]: Azure AI Content Safety `text:analyze`, whose
+//!   0-7 severities are scaled to 0-1
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+use multi_agent_core::config::{ModerationConfig, ModerationThresholds};
+use multi_agent_core::events::ModerationPayload;
+use multi_agent_core::{Error, Result};
+
+/// A content moderation service.
+#[async_trait]
+pub trait ModerationProvider: Send + Sync {
+    /// Provider name recorded with each verdict.
+    fn name(&self) -> &str;
+
+    /// Scores of `text` by category, from 0 to 1.
+    async fn moderate(&self, text: &str) -> Result<BTreeMap<String, f32>>;
+}
+
+/// Whether moderated content was a request or an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ModerationDirection {
+    Input,
+    Output,
+}
+
+/// What happens to moderated content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ModerationAction {
+    Allow,
+    /// Passed, but recorded for review.
+    Flag,
+    Block,
+}
+
+impl ModerationDirection {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Input => "INPUT",
+            Self::Output => "OUTPUT",
+        }
+    }
+}
+
+impl ModerationAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Allow => "ALLOW",
+            Self::Flag => "FLAG",
+            Self::Block => "BLOCK",
+        }
+    }
+}
+
+/// Verdict on one piece of content.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModerationOutcome {
+    pub direction: ModerationDirection,
+    pub provider: String,
+    pub action: ModerationAction,
+    /// Categories at or above their flag threshold.
+    pub categories: Vec<String>,
+    pub scores: BTreeMap<String, f32>,
+    /// Provider error, if the verdict comes from the failure mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ModerationOutcome {
+    pub fn blocked(&self) -> bool {
+        self.action == ModerationAction::Block
+    }
+
+    /// Payload of the `MODERATION_EVALUATED` event.
+    pub fn payload(&self) -> ModerationPayload {
+        ModerationPayload {
+            direction: self.direction.as_str().to_string(),
+            provider: self.provider.clone(),
+            action: self.action.as_str().to_string(),
+            categories: self.categories.clone(),
+            scores: self.scores.clone(),
+            error: self.error.clone(),
+        }
+    }
+}
+
+/// Applies a provider's scores to the configured thresholds.
+pub struct Moderator {
+    provider: Arc<dyn ModerationProvider>,
+    thresholds: ModerationThresholds,
+    /// Lowercased category name to thresholds.
+    categories: HashMap<String, ModerationThresholds>,
+    check_input: bool,
+    check_output: bool,
+    fail_open: bool,
+}
+
+impl Moderator {
+    pub fn new(provider: Arc<dyn ModerationProvider>, config: &ModerationConfig) -> Self {
+        Self {
+            provider,
+            thresholds: config.thresholds,
+            categories: config
+                .categories
+                .iter()
+                .map(|(name, thresholds)| (name.to_lowercase(), *thresholds))
+                .collect(),
+            check_input: config.check_input,
+            check_output: config.check_output,
+            fail_open: config.fail_open,
+        }
+    }
+
+    /// Moderator with the provider configured in `config`; `None` when
+    /// moderation is disabled.
+    pub fn from_config(config: &ModerationConfig, api_key: Option<String>) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        let timeout = Duration::from_secs(config.timeout_secs.max(1));
+        let api_key = api_key.ok_or_else(|| Error::governance("Moderation requires an api_key"))?;
+        let provider: Arc<dyn ModerationProvider> = match config.provider {
+            multi_agent_core::config::ModerationProviderKind::Openai => Arc::new(
+                OpenAiModeration::new(api_key, &config.model, timeout)
+                    .with_endpoint(config.endpoint.clone()),
+            ),
+            multi_agent_core::config::ModerationProviderKind::AzureContentSafety => {
+                let endpoint = config.endpoint.clone().ok_or_else(|| {
+                    Error::governance("Azure Content Safety moderation requires an endpoint")
+                })?;
+                Arc::new(AzureContentSafety::new(endpoint, api_key, timeout))
+            }
+        };
+        Ok(Some(Self::new(provider, config)))
+    }
+
+    /// Name of the underlying provider.
+    pub fn provider_name(&self) -> &str {
+        self.provider.name()
+    }
+
+    /// Whether `direction` is moderated at all.
+    pub fn checks(&self, direction: ModerationDirection) -> bool {
+        match direction {
+            ModerationDirection::Input => self.check_input,
+            ModerationDirection::Output => self.check_output,
+        }
+    }
+
+    /// Verdict on `text`. A provider failure blocks unless `fail_open`.
+    pub async fn moderate(&self, direction: ModerationDirection, text: &str) -> ModerationOutcome {
+        let scores = match self.provider.moderate(text).await {
+            Ok(scores) => scores,
+            Err(e) => {
+                tracing::warn!(provider = self.provider.name(), error = %e, "Moderation failed");
+                return ModerationOutcome {
+                    direction,
+                    provider: self.provider.name().to_string(),
+                    action: if self.fail_open {
+                        ModerationAction::Allow
+                    } else {
+                        ModerationAction::Block
+                    },
+                    categories: Vec::new(),
+                    scores: BTreeMap::new(),
+                    error: Some(e.to_string()),
+                };
+            }
+        };
+
+        let mut action = ModerationAction::Allow;
+        let mut categories = Vec::new();
+        for (category, score) in &scores {
+            let thresholds = self
+                .categories
+                .get(&category.to_lowercase())
+                .unwrap_or(&self.thresholds);
+            let verdict = if *score >= thresholds.block {
+                ModerationAction::Block
+            } else if *score >= thresholds.flag {
+                ModerationAction::Flag
+            } else {
+                continue;
+            };
+            categories.push(category.clone());
+            action = action.max(verdict);
+        }
+        if action != ModerationAction::Allow {
+            metrics::counter!(
+                "moderation_verdicts_total",
+                "direction" => direction.as_str(),
+                "action" => action.as_str()
+            )
+            .increment(1);
+        }
+        ModerationOutcome {
+            direction,
+            provider: self.provider.name().to_string(),
+            action,
+            categories,
+            scores,
+            error: None,
+        }
+    }
+}
+
+/// OpenAI moderation endpoint.
+pub struct OpenAiModeration {
+    endpoint: String,
+    api_key: String,
+    model: String,
+    client: reqwest::Client,
+}
+
+#[derive(Deserialize)]
+struct OpenAiModerationResponse {
+    results: Vec<OpenAiModerationResult>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiModerationResult {
+    category_scores: BTreeMap<String, f32>,
+}
+
+impl OpenAiModeration {
+    pub fn new(api_key: impl Into<String>, model: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            endpoint: "https://api.openai.com/v1/moderations".into(),
+            api_key: api_key.into(),
+            model: model.into(),
+            client: reqwest::Client::builder()
+                .timeout(timeout)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Use another OpenAI-compatible moderation URL.
+    pub fn with_endpoint(mut self, endpoint: Option<String>) -> Self {
+        if let Some(endpoint) = endpoint {
+            self.endpoint = endpoint;
+        }
+        self
+    }
+}
+
+#[async_trait]
+impl ModerationProvider for OpenAiModeration {
+    fn name(&self) -> &str {
+        "openai"
+    }
+
+    async fn moderate(&self, text: &str) -> Result<BTreeMap<String, f32>> {
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .bearer_auth(&self.api_key)
+            .json(&serde_json::json!({ "model": self.model, "input": text }))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| Error::governance(format!("OpenAI moderation failed: {}", e)))?;
+        let body: OpenAiModerationResponse = response.json().await.map_err(|e| {
+            Error::governance(format!("Malformed OpenAI moderation response: {}", e))
+        })?;
+        // One result per input; scores for multi-part input are the maxima.
+        let mut scores = BTreeMap::new();
+        for result in body.results {
+            for (category, score) in result.category_scores {
+                let entry = scores.entry(category).or_insert(0.0f32);
+                *entry = entry.max(score);
+            }
+        }
+        Ok(scores)
+    }
+}
+
+/// Azure AI Content Safety text analysis.
+pub struct AzureContentSafety {
+    endpoint: String,
+    api_key: String,
+    client: reqwest::Client,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AzureAnalyzeResponse {
+    categories_analysis: Vec<AzureCategoryAnalysis>,
+}
+
+#[derive(Deserialize)]
+struct AzureCategoryAnalysis {
+    category: String,
+    severity: u8,
+}
+
+/// Highest severity of the eight-level Azure scale.
+const AZURE_MAX_SEVERITY: f32 = 7.0;
+
+impl AzureContentSafety {
+    pub fn new(endpoint: impl Into<String>, api_key: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            api_key: api_key.into(),
+            client: reqwest::Client::builder()
+                .timeout(timeout)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[async_trait]
+impl ModerationProvider for AzureContentSafety {
+    fn name(&self) -> &str {
+        "azure-content-safety"
+    }
+
+    async fn moderate(&self, text: &str) -> Result<BTreeMap<String, f32>> {
+        let response = self
+            .client
+            .post(format!(
+                "{}/contentsafety/text:analyze?api-version=2023-10-01",
+                self.endpoint
+            ))
+            .header("Ocp-Apim-Subscription-Key", &self.api_key)
+            .json(&serde_json::json!({
+                "text": text,
+                "outputType": "EightSeverityLevels",
+            }))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| Error::governance(format!("Azure Content Safety failed: {}", e)))?;
+        let body: AzureAnalyzeResponse = response.json().await.map_err(|e| {
+            Error::governance(format!("Malformed Azure Content Safety response: {}", e))
+        })?;
+        Ok(body
+            .categories_analysis
+            .into_iter()
+            .map(|c| (c.category, f32::from(c.severity) / AZURE_MAX_SEVERITY))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedScores(BTreeMap<String, f32>);
+
+    #[async_trait]
+    impl ModerationProvider for FixedScores {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        async fn moderate(&self, _text: &str) -> Result<BTreeMap<String, f32>> {
+            if self.0.is_empty() {
+                return Err(Error::governance("unavailable"));
+            }
+            Ok(self.0.clone())
+        }
+    }
+
+    fn moderator(scores: &[(&str, f32)], config: ModerationConfig) -> Moderator {
+        let scores = scores.iter().map(|(c, s)| (c.to_string(), *s)).collect();
+        Moderator::new(Arc::new(FixedScores(scores)), &config)
+    }
+
+    #[tokio::test]
+    async fn test_thresholds_map_to_actions() {
+        let mut config = ModerationConfig::default();
+        config.categories.insert(
+            "Violence".into(),
+            ModerationThresholds {
+                flag: 0.2,
+                block: 0.3,
+            },
+        );
+
+        let outcome = moderator(&[("hate", 0.6), ("violence", 0.1)], config.clone())
+            .moderate(ModerationDirection::Input, "text")
+            .await;
+        assert_eq!(outcome.action, ModerationAction::Flag);
+        assert_eq!(outcome.categories, vec!["hate"]);
+
+        let outcome = moderator(&[("hate", 0.6), ("violence", 0.35)], config.clone())
+            .moderate(ModerationDirection::Output, "text")
+            .await;
+        assert!(outcome.blocked());
+        assert_eq!(outcome.categories, vec!["hate", "violence"]);
+        let payload = outcome.payload();
+        assert_eq!(payload.direction, "OUTPUT");
+        assert_eq!(payload.action, "BLOCK");
+
+        let outcome = moderator(&[("hate", 0.1)], config)
+            .moderate(ModerationDirection::Input, "text")
+            .await;
+        assert_eq!(outcome.action, ModerationAction::Allow);
+        assert!(outcome.categories.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_provider_failure_mode() {
+        let outcome = moderator(&[], ModerationConfig::default())
+            .moderate(ModerationDirection::Input, "text")
+            .await;
+        assert_eq!(outcome.action, ModerationAction::Allow);
+        assert!(outcome.error.is_some());
+
+        let config = ModerationConfig {
+            fail_open: false,
+            ..Default::default()
+        };
+        let outcome = moderator(&[], config)
+            .moderate(ModerationDirection::Input, "text")
+            .await;
+        assert!(outcome.blocked());
+    }
+}
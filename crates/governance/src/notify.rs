@@ -0,0 +1,92 @@
+//! Notification sinks for operator alerts and digests.
+
+use async_trait::async_trait;
+use std::time::Duration;
+
+use multi_agent_core::{
+    traits::{Notification, NotificationSeverity, NotificationSink},
+    Error, Result,
+};
+
+/// Writes notifications to the tracing log.
+pub struct LogSink;
+
+#[async_trait]
+impl NotificationSink for LogSink {
+    fn name(&self) -> &str {
+        "log"
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<()> {
+        match notification.severity {
+            NotificationSeverity::Info => {
+                tracing::info!(title = %notification.title, "{}", notification.body)
+            }
+            NotificationSeverity::Warning | NotificationSeverity::Critical => {
+                tracing::warn!(
+                    title = %notification.title,
+                    severity = ?notification.severity,
+                    "{}",
+                    notification.body
+                )
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Posts notifications as JSON to a webhook.
+///
+/// The payload carries a `text` field, so Slack and Mattermost incoming
+/// webhooks render it as is; the structured fields are included for other
+/// receivers.
+pub struct WebhookSink {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl WebhookSink {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// JSON body posted for a notification.
+    pub fn payload(notification: &Notification) -> serde_json::Value {
+        serde_json::json!({
+            "text": format!("*{}*\n{}", notification.title, notification.body),
+            "title": notification.title,
+            "body": notification.body,
+            "severity": notification.severity,
+        })
+    }
+}
+
+#[async_trait]
+impl NotificationSink for WebhookSink {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<()> {
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&Self::payload(notification))
+            .send()
+            .await
+            .map_err(|e| Error::governance(format!("Webhook delivery failed: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(Error::governance(format!(
+                "Webhook returned {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use multi_agent_core::types::ToolRiskLevel;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 
 /// A versioned policy document containing security rules.
@@ -10,6 +11,52 @@ pub struct PolicyFile {
     pub name: String,
     pub rules: Vec<PolicyRule>,
     pub thresholds: PolicyThresholds,
+    /// Approval overrides keyed by tool name or glob (e.g., "sandbox_*").
+    #[serde(default)]
+    pub tool_overrides: HashMap<String, ToolApprovalOverride>,
+    /// Approval overrides keyed by workspace ID.
+    #[serde(default)]
+    pub workspace_overrides: HashMap<String, WorkspaceApprovalOverride>,
+}
+
+/// Policy used when no policy file has been saved yet.
+pub const DEFAULT_POLICY_YAML: &str = r#"version: "1.0"
+name: "Default Security Policy"
+rules:
+  - id: "block-rm-rf"
+    description: "Block recursive force delete"
+    match_rule:
+      tool_glob: "sandbox_*"
+      args_contain: ["rm -rf", "rm -r"]
+    action:
+      risk: Critical
+      reason: "Destructive filesystem operation detected"
+  - id: "high-risk-fs"
+    description: "Elevate risk for sensitive FS operations"
+    match_rule:
+      tool_glob: "fs_write*"
+    action:
+      risk: High
+      reason: "Filesystem write detected"
+  - id: "patch-apply"
+    description: "Patch application modifies workspace files"
+    match_rule:
+      tool: "sandbox_apply_patch"
+    action:
+      risk: High
+      reason: "Filesystem write via patch detected"
+thresholds:
+  low: 10
+  medium: 30
+  high: 60
+  critical: 90
+  approval_required: 50
+"#;
+
+impl Default for PolicyFile {
+    fn default() -> Self {
+        serde_yaml::from_str(DEFAULT_POLICY_YAML).expect("default policy is valid YAML")
+    }
 }
 
 /// A single security rule.
@@ -19,6 +66,18 @@ pub struct PolicyRule {
     pub description: Option<String>,
     pub match_rule: RuleMatch,
     pub action: RuleAction,
+    /// Shadow rules are evaluated and reported but do not affect decisions.
+    #[serde(default)]
+    pub mode: RuleMode,
+}
+
+/// Whether a rule is enforced or only observed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleMode {
+    #[default]
+    Enforce,
+    Shadow,
 }
 
 /// Condition to match a tool call.
@@ -63,6 +122,70 @@ impl Default for PolicyThresholds {
     }
 }
 
+/// How a tool call is gated on human approval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalMode {
+    /// Every call requires approval.
+    Always,
+    /// Calls never require approval for their risk score. Tools that require
+    /// approval on every call (e.g. `send_email`) still do.
+    Never,
+    /// Calls require approval when the risk score reaches the threshold.
+    Threshold,
+}
+
+/// Approval annotation for a single tool (or tool glob).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToolApprovalOverride {
+    /// Approval mode; `None` keeps the threshold behaviour.
+    #[serde(default)]
+    pub approval: Option<ApprovalMode>,
+    /// Risk score threshold for this tool.
+    #[serde(default)]
+    pub threshold: Option<u32>,
+}
+
+/// Approval overrides scoped to one workspace.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkspaceApprovalOverride {
+    /// Replaces `thresholds.approval_required` for this workspace.
+    #[serde(default)]
+    pub approval_required: Option<u32>,
+    /// Per-tool overrides, taking precedence over the global ones.
+    #[serde(default)]
+    pub tools: HashMap<String, ToolApprovalOverride>,
+}
+
+/// Effective approval requirement for a tool call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApprovalRequirement {
+    pub mode: ApprovalMode,
+    /// Risk score at or above which approval is requested (threshold mode).
+    pub threshold: u32,
+}
+
+impl ApprovalRequirement {
+    /// Whether a call with `risk_score` needs approval. `tool_requires` is the
+    /// tool's own annotation, which no mode can waive.
+    pub fn requires_approval(&self, risk_score: u32, tool_requires: bool) -> bool {
+        match self.mode {
+            ApprovalMode::Always => true,
+            ApprovalMode::Never => tool_requires,
+            ApprovalMode::Threshold => tool_requires || risk_score >= self.threshold,
+        }
+    }
+}
+
+impl Default for ApprovalRequirement {
+    fn default() -> Self {
+        Self {
+            mode: ApprovalMode::Threshold,
+            threshold: PolicyThresholds::default().approval_required,
+        }
+    }
+}
+
 /// Merged policy configuration for evaluation.
 pub struct PolicyEngine {
     pub policy: PolicyFile,
@@ -90,7 +213,12 @@ impl PolicyEngine {
         let mut matched_rule_id = None;
         let mut reason = "Default policy (no matching rules)".to_string();
 
-        for rule in &self.policy.rules {
+        let enforced = self
+            .policy
+            .rules
+            .iter()
+            .filter(|r| r.mode == RuleMode::Enforce);
+        for rule in enforced {
             if self.matches(rule, tool, args) {
                 // If this rule has a higher or equal risk than current, update
                 if rule.action.risk > highest_risk || matched_rule_id.is_none() {
@@ -106,6 +234,7 @@ impl PolicyEngine {
         }
 
         let risk_score = self.risk_to_score(highest_risk);
+        let shadow = self.evaluate_shadow(tool, args, highest_risk, risk_score);
 
         PolicyDecision {
             risk_level: highest_risk,
@@ -113,7 +242,139 @@ impl PolicyEngine {
             matched_rule: matched_rule_id,
             reason,
             policy_version: self.policy.version.clone(),
+            shadow,
+        }
+    }
+
+    /// Match shadow rules and report how each would have changed the decision.
+    fn evaluate_shadow(
+        &self,
+        tool: &str,
+        args: &serde_json::Value,
+        enforced_risk: ToolRiskLevel,
+        enforced_score: u32,
+    ) -> Vec<ShadowMatch> {
+        let approval = self.approval_requirement(tool, None);
+        let enforced_approval = approval.requires_approval(enforced_score, false);
+
+        self.policy
+            .rules
+            .iter()
+            .filter(|r| r.mode == RuleMode::Shadow && self.matches(r, tool, args))
+            .map(|rule| {
+                let risk_score = self.risk_to_score(rule.action.risk);
+                let shadow = ShadowMatch {
+                    rule_id: rule.id.clone(),
+                    risk_level: rule.action.risk,
+                    risk_score,
+                    would_escalate: rule.action.risk > enforced_risk,
+                    would_require_approval: !enforced_approval
+                        && approval.requires_approval(risk_score, false),
+                };
+                tracing::info!(
+                    tool = %tool,
+                    rule = %shadow.rule_id,
+                    risk = ?shadow.risk_level,
+                    outcome = shadow.outcome(),
+                    "Shadow policy rule matched"
+                );
+                metrics::counter!(
+                    "policy_shadow_matches_total",
+                    "rule" => shadow.rule_id.clone(),
+                    "outcome" => shadow.outcome()
+                )
+                .increment(1);
+                shadow
+            })
+            .collect()
+    }
+
+    /// Switch a rule between enforcing and shadow mode. Returns `false` if no
+    /// rule has this ID.
+    pub fn set_rule_mode(&mut self, rule_id: &str, mode: RuleMode) -> bool {
+        match self.policy.rules.iter_mut().find(|r| r.id == rule_id) {
+            Some(rule) => {
+                rule.mode = mode;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Resolve the approval requirement for a tool, optionally in a workspace.
+    ///
+    /// Precedence: workspace tool override, global tool override, workspace
+    /// threshold, then `thresholds.approval_required`. Exact tool names win
+    /// over globs.
+    pub fn approval_requirement(&self, tool: &str, workspace: Option<&str>) -> ApprovalRequirement {
+        let workspace = workspace.and_then(|w| self.policy.workspace_overrides.get(w));
+
+        let mut threshold = workspace
+            .and_then(|w| w.approval_required)
+            .unwrap_or(self.policy.thresholds.approval_required);
+        let mut mode = ApprovalMode::Threshold;
+
+        // Apply the least specific layer first so later ones win field by field
+        let layers = [
+            self.find_override(&self.policy.tool_overrides, tool),
+            workspace.and_then(|w| self.find_override(&w.tools, tool)),
+        ];
+        for layer in layers.into_iter().flatten() {
+            if let Some(m) = layer.approval {
+                mode = m;
+            }
+            if let Some(t) = layer.threshold {
+                threshold = t;
+            }
+        }
+
+        ApprovalRequirement { mode, threshold }
+    }
+
+    /// Reject `never` overrides for tools that require approval on every
+    /// call. `mandatory` lists those tools; such an override has no effect
+    /// and would only mislead whoever reads the policy.
+    pub fn check_mandatory_approval(&self, mandatory: &[String]) -> Result<()> {
+        let workspaces = std::iter::once(None).chain(
+            self.policy
+                .workspace_overrides
+                .keys()
+                .map(|w| Some(w.as_str())),
+        );
+        let mut waived = Vec::new();
+        for workspace in workspaces {
+            for tool in mandatory {
+                if self.approval_requirement(tool, workspace).mode == ApprovalMode::Never {
+                    waived.push(match workspace {
+                        Some(w) => format!("{} (workspace {})", tool, w),
+                        None => tool.clone(),
+                    });
+                }
+            }
         }
+        if !waived.is_empty() {
+            anyhow::bail!(
+                "Approval cannot be waived for tools that require it on every call: {}",
+                waived.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    fn find_override<'a>(
+        &self,
+        overrides: &'a HashMap<String, ToolApprovalOverride>,
+        tool: &str,
+    ) -> Option<&'a ToolApprovalOverride> {
+        if let Some(exact) = overrides.get(tool) {
+            return Some(exact);
+        }
+        // Longest matching glob is the most specific
+        overrides
+            .iter()
+            .filter(|(pattern, _)| pattern.contains('*') && self.glob_match(pattern, tool))
+            .max_by_key(|(pattern, _)| pattern.len())
+            .map(|(_, o)| o)
     }
 
     fn matches(&self, rule: &PolicyRule, tool: &str, args: &serde_json::Value) -> bool {
@@ -186,6 +447,10 @@ impl PolicyEngine {
             }
         }
         self.policy.thresholds = other.thresholds;
+        self.policy.tool_overrides.extend(other.tool_overrides);
+        self.policy
+            .workspace_overrides
+            .extend(other.workspace_overrides);
         self.policy.version = other.version;
     }
 }
@@ -212,6 +477,7 @@ mod tests {
                         risk: ToolRiskLevel::Critical,
                         reason: Some("Destructive command detected".to_string()),
                     },
+                    mode: RuleMode::Enforce,
                 },
                 PolicyRule {
                     id: "read-ops".to_string(),
@@ -225,12 +491,26 @@ mod tests {
                         risk: ToolRiskLevel::Low,
                         reason: Some("Read-only operation".to_string()),
                     },
+                    mode: RuleMode::Enforce,
                 },
             ],
             thresholds: PolicyThresholds::default(),
+            tool_overrides: HashMap::new(),
+            workspace_overrides: HashMap::new(),
         }
     }
 
+    #[test]
+    fn test_default_policy_tiers_sandbox_commands() {
+        let engine = PolicyEngine::from_file(PolicyFile::default());
+        let destructive = engine.evaluate("sandbox_shell", &json!({"command": "rm -rf /"}));
+        assert_eq!(destructive.risk_level, ToolRiskLevel::Critical);
+        let patch = engine.evaluate("sandbox_apply_patch", &json!({}));
+        assert_eq!(patch.risk_level, ToolRiskLevel::High);
+        let listing = engine.evaluate("sandbox_shell", &json!({"command": "ls"}));
+        assert_eq!(listing.risk_level, ToolRiskLevel::Low);
+    }
+
     #[test]
     fn test_exact_match_and_args() {
         let engine = PolicyEngine::from_file(test_policy());
@@ -272,11 +552,14 @@ mod tests {
                     risk: ToolRiskLevel::Medium, // Changed risk
                     reason: Some("Elevated read risk".to_string()),
                 },
+                mode: RuleMode::Enforce,
             }],
             thresholds: PolicyThresholds {
                 medium: 10,
                 ..Default::default()
             },
+            tool_overrides: HashMap::new(),
+            workspace_overrides: HashMap::new(),
         };
 
         engine.merge(override_policy);
@@ -285,6 +568,143 @@ mod tests {
         assert_eq!(decision.risk_level, ToolRiskLevel::Medium);
         assert_eq!(decision.risk_score, 10); // From overriden threshold
     }
+
+    #[test]
+    fn test_shadow_rules_are_reported_not_enforced() {
+        let mut policy = test_policy();
+        policy.rules.push(PolicyRule {
+            id: "shadow-shell".to_string(),
+            description: None,
+            match_rule: RuleMatch {
+                tool: Some("sandbox_shell".to_string()),
+                tool_glob: None,
+                args_contain: None,
+            },
+            action: RuleAction {
+                risk: ToolRiskLevel::High,
+                reason: None,
+            },
+            mode: RuleMode::Shadow,
+        });
+        let mut engine = PolicyEngine::from_file(policy);
+
+        let decision = engine.evaluate("sandbox_shell", &json!({"command": "ls"}));
+        assert_eq!(decision.risk_level, ToolRiskLevel::Low);
+        assert_eq!(decision.matched_rule, None);
+        assert_eq!(decision.shadow.len(), 1);
+        let shadow = &decision.shadow[0];
+        assert_eq!(shadow.rule_id, "shadow-shell");
+        assert!(shadow.would_escalate);
+        assert!(shadow.would_require_approval);
+        assert_eq!(shadow.outcome(), "would_require_approval");
+
+        // An enforced critical rule already requires approval
+        let decision = engine.evaluate("sandbox_shell", &json!({"command": "rm -rf /"}));
+        assert_eq!(decision.risk_level, ToolRiskLevel::Critical);
+        assert_eq!(decision.shadow[0].outcome(), "no_change");
+
+        // Flipping to enforcing applies the rule
+        assert!(engine.set_rule_mode("shadow-shell", RuleMode::Enforce));
+        let decision = engine.evaluate("sandbox_shell", &json!({"command": "ls"}));
+        assert_eq!(decision.risk_level, ToolRiskLevel::High);
+        assert!(decision.shadow.is_empty());
+        assert!(!engine.set_rule_mode("missing", RuleMode::Shadow));
+    }
+
+    #[test]
+    fn test_approval_requirement_precedence() {
+        let mut policy = test_policy();
+        policy.thresholds.approval_required = 60;
+        policy.tool_overrides.insert(
+            "sandbox_*".to_string(),
+            ToolApprovalOverride {
+                approval: Some(ApprovalMode::Always),
+                threshold: None,
+            },
+        );
+        policy.tool_overrides.insert(
+            "fs_read".to_string(),
+            ToolApprovalOverride {
+                approval: Some(ApprovalMode::Never),
+                threshold: None,
+            },
+        );
+        policy.workspace_overrides.insert(
+            "lab".to_string(),
+            WorkspaceApprovalOverride {
+                approval_required: Some(90),
+                tools: HashMap::from([(
+                    "sandbox_shell".to_string(),
+                    ToolApprovalOverride {
+                        approval: Some(ApprovalMode::Threshold),
+                        threshold: Some(75),
+                    },
+                )]),
+            },
+        );
+        let engine = PolicyEngine::from_file(policy);
+
+        let req = engine.approval_requirement("web_search", None);
+        assert_eq!(req.mode, ApprovalMode::Threshold);
+        assert_eq!(req.threshold, 60);
+        assert!(!req.requires_approval(50, false));
+        assert!(req.requires_approval(50, true));
+
+        assert_eq!(
+            engine
+                .approval_requirement("web_search", Some("lab"))
+                .threshold,
+            90
+        );
+        assert_eq!(
+            engine.approval_requirement("sandbox_exec", None).mode,
+            ApprovalMode::Always
+        );
+
+        let req = engine.approval_requirement("sandbox_shell", Some("lab"));
+        assert_eq!(req.mode, ApprovalMode::Threshold);
+        assert_eq!(req.threshold, 75);
+
+        let req = engine.approval_requirement("fs_read", Some("lab"));
+        assert_eq!(req.mode, ApprovalMode::Never);
+        assert!(!req.requires_approval(100, false));
+        assert!(req.requires_approval(0, true));
+
+        assert!(engine
+            .check_mandatory_approval(&["web_search".into()])
+            .is_ok());
+        let err = engine
+            .check_mandatory_approval(&["fs_read".into()])
+            .unwrap_err();
+        assert!(err.to_string().contains("fs_read (workspace lab)"));
+    }
+
+    #[test]
+    fn test_overrides_parse_from_yaml() {
+        let yaml = r#"
+version: "2"
+name: overrides
+rules: []
+thresholds: { low: 0, medium: 25, high: 50, critical: 75, approval_required: 40 }
+tool_overrides:
+  delete_*: { approval: always }
+workspace_overrides:
+  sandbox-ws:
+    approval_required: 100
+"#;
+        let policy: PolicyFile = serde_yaml::from_str(yaml).unwrap();
+        let engine = PolicyEngine::from_file(policy);
+        assert_eq!(
+            engine.approval_requirement("delete_file", None).mode,
+            ApprovalMode::Always
+        );
+        assert_eq!(
+            engine
+                .approval_requirement("x", Some("sandbox-ws"))
+                .threshold,
+            100
+        );
+    }
 }
 
 /// Result of a policy evaluation.
@@ -295,4 +715,32 @@ pub struct PolicyDecision {
     pub matched_rule: Option<String>,
     pub reason: String,
     pub policy_version: String,
+    /// Shadow rules that matched; they did not affect this decision.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shadow: Vec<ShadowMatch>,
+}
+
+/// A shadow rule that matched a tool call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShadowMatch {
+    pub rule_id: String,
+    pub risk_level: ToolRiskLevel,
+    pub risk_score: u32,
+    /// The rule would have raised the risk level.
+    pub would_escalate: bool,
+    /// The rule would have required approval where the enforced policy did not.
+    pub would_require_approval: bool,
+}
+
+impl ShadowMatch {
+    /// Metric/log label for what enforcing the rule would have changed.
+    pub fn outcome(&self) -> &'static str {
+        if self.would_require_approval {
+            "would_require_approval"
+        } else if self.would_escalate {
+            "would_escalate"
+        } else {
+            "no_change"
+        }
+    }
 }
@@ -1,8 +1,13 @@
 //! Privacy and GDPR compliance logic.
 
-use multi_agent_core::events::{EventEnvelope, EventSeverity, EventType};
+pub use multi_agent_core::events::StoreDeletion;
+use multi_agent_core::events::{
+    DataDeletionCompletedPayload, DataDeletionInitiatedPayload, EventEnvelope, EventSeverity,
+    EventType,
+};
 use multi_agent_core::traits::events::EventEmitter;
 use multi_agent_core::traits::store::Erasable;
+use multi_agent_core::Error;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
@@ -11,10 +16,12 @@ use std::sync::Arc;
 pub struct DeletionReport {
     pub user_id: String,
     pub total_deleted: usize,
+    /// Deletion outcome for each registered store, in registration order.
+    #[serde(default)]
+    pub per_store: Vec<StoreDeletion>,
     pub errors: Vec<String>,
 }
 
-/// Controller for privacy operations.
 /// Controller for privacy operations.
 pub struct PrivacyController {
     stores: Vec<Arc<dyn Erasable>>,
@@ -37,7 +44,10 @@ impl PrivacyController {
         // Emit initiation event
         let init_event = EventEnvelope::new(
             EventType::DataDeletionInitiated,
-            serde_json::json!({ "user_id": user_id }),
+            serde_json::to_value(DataDeletionInitiatedPayload {
+                user_id: user_id.to_string(),
+            })
+            .unwrap_or_default(),
         )
         .with_severity(EventSeverity::Warning)
         .with_actor("system");
@@ -46,16 +56,34 @@ impl PrivacyController {
         let mut report = DeletionReport {
             user_id: user_id.to_string(),
             total_deleted: 0,
+            per_store: Vec::with_capacity(self.stores.len()),
             errors: Vec::new(),
         };
 
         for store in &self.stores {
+            let name = store.store_name();
             match store.erase_user(user_id).await {
                 Ok(count) => {
                     report.total_deleted += count;
+                    report.per_store.push(StoreDeletion {
+                        store: name.to_string(),
+                        deleted: count,
+                        error: None,
+                    });
                 }
                 Err(e) => {
-                    report.errors.push(format!("Store error: {}", e));
+                    // Data already removed from parts of the store still counts.
+                    let deleted = match &e {
+                        Error::PartialErasure { erased, .. } => *erased,
+                        _ => 0,
+                    };
+                    report.total_deleted += deleted;
+                    report.errors.push(format!("{}: {}", name, e));
+                    report.per_store.push(StoreDeletion {
+                        store: name.to_string(),
+                        deleted,
+                        error: Some(e.to_string()),
+                    });
                 }
             }
         }
@@ -63,11 +91,13 @@ impl PrivacyController {
         // Emit completion event
         let complete_event = EventEnvelope::new(
             EventType::DataDeletionCompleted,
-            serde_json::json!({
-                "user_id": user_id,
-                "total_deleted": report.total_deleted,
-                "errors": report.errors
-            }),
+            serde_json::to_value(DataDeletionCompletedPayload {
+                user_id: user_id.to_string(),
+                total_deleted: report.total_deleted,
+                per_store: report.per_store.clone(),
+                errors: report.errors.clone(),
+            })
+            .unwrap_or_default(),
         )
         .with_severity(EventSeverity::Info)
         .with_actor("system");
@@ -77,5 +107,69 @@ impl PrivacyController {
     }
 }
 
-// For now, I will create this file and then go back to update retention.rs with Erasable trait.
-// And then implement Erasable for stores.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EncryptedArtifactStore;
+    use bytes::Bytes;
+    use multi_agent_core::traits::{ArtifactStore, NoOpEventEmitter};
+    use multi_agent_core::types::RefId;
+    use multi_agent_store::{InMemorySessionStore, InMemoryStore, TieredStore};
+
+    #[tokio::test]
+    async fn test_forget_user_cascades_through_wrappers() {
+        let hot = Arc::new(InMemoryStore::new());
+        let tiered = Arc::new(TieredStore::new(hot.clone()));
+        let encrypted: Arc<dyn ArtifactStore> =
+            Arc::new(EncryptedArtifactStore::new(tiered, &"00".repeat(32)).unwrap());
+
+        encrypted
+            .save_with_id(&RefId::from_string("alice/report"), Bytes::from("secret"))
+            .await
+            .unwrap();
+
+        let controller = PrivacyController::new(
+            vec![encrypted.clone(), Arc::new(InMemorySessionStore::new())],
+            Arc::new(NoOpEventEmitter),
+        );
+        let report = controller.forget_user("alice").await;
+
+        assert_eq!(report.total_deleted, 1);
+        assert!(report.errors.is_empty());
+        assert_eq!(report.per_store.len(), 2);
+        assert_eq!(report.per_store[0].store, "EncryptedArtifactStore");
+        assert_eq!(report.per_store[0].deleted, 1);
+        assert_eq!(report.per_store[1].store, "InMemorySessionStore");
+        assert!(!hot
+            .exists(&RefId::from_string("alice/report"))
+            .await
+            .unwrap());
+    }
+
+    struct PartlyErased;
+
+    #[async_trait::async_trait]
+    impl Erasable for PartlyErased {
+        async fn erase_user(&self, _user_id: &str) -> multi_agent_core::Result<usize> {
+            Err(Error::PartialErasure {
+                erased: 3,
+                failures: vec!["Cold tier (S3ArtifactStore): timed out".into()],
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_forget_user_counts_partial_erasure() {
+        let controller =
+            PrivacyController::new(vec![Arc::new(PartlyErased)], Arc::new(NoOpEventEmitter));
+        let report = controller.forget_user("alice").await;
+
+        assert_eq!(report.total_deleted, 3);
+        assert_eq!(report.per_store[0].deleted, 3);
+        assert!(report.per_store[0]
+            .error
+            .as_deref()
+            .is_some_and(|e| e.contains("Cold tier")));
+        assert_eq!(report.errors.len(), 1);
+    }
+}
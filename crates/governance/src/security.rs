@@ -138,11 +138,11 @@ impl SecurityProxy for DefaultSecurityProxy {
 
         // Check output for sensitive data patterns
         match output {
-            AgentResult::Text(text) => {
-                // Could add PII detection here
-                if text.len() > 1_000_000 {
-                    return Err(Error::SecurityViolation("Output too large".to_string()));
-                }
+            // Could add PII detection here
+            AgentResult::Text(text) | AgentResult::CitedText { text, .. }
+                if text.len() > 1_000_000 =>
+            {
+                return Err(Error::SecurityViolation("Output too large".to_string()));
             }
             AgentResult::Error { message, .. } => {
                 // Ensure errors don't leak sensitive info
@@ -4,12 +4,15 @@ use aes_gcm::{
 };
 use async_trait::async_trait;
 use bytes::Bytes;
-use multi_agent_core::traits::{ArtifactMetadata, ArtifactStore};
+use multi_agent_core::traits::{ArtifactFilter, ArtifactMetadata, ArtifactStore, Erasable};
 use multi_agent_core::types::RefId;
 use multi_agent_core::Result;
 use rand::{RngCore, SeedableRng};
 use std::sync::Arc;
 
+/// Bytes added per artifact: 12-byte nonce plus 16-byte GCM tag.
+const ENCRYPTION_OVERHEAD: usize = 12 + 16;
+
 /// Wrapper that encrypts data before storing and decrypts after loading.
 pub struct EncryptedArtifactStore {
     inner: Arc<dyn ArtifactStore>,
@@ -105,8 +108,20 @@ impl ArtifactStore for EncryptedArtifactStore {
     }
 
     async fn metadata(&self, id: &RefId) -> Result<Option<ArtifactMetadata>> {
-        // Metadata is not encrypted, but size will reflect encrypted size
-        self.inner.metadata(id).await
+        // Metadata is not encrypted; report the plaintext size so byte ranges
+        // computed by callers line up with what `load_range` returns.
+        Ok(self.inner.metadata(id).await?.map(|mut meta| {
+            meta.size = meta.size.saturating_sub(ENCRYPTION_OVERHEAD);
+            meta
+        }))
+    }
+
+    async fn list(&self, filter: ArtifactFilter) -> Result<Vec<ArtifactMetadata>> {
+        let mut artifacts = self.inner.list(filter).await?;
+        for meta in &mut artifacts {
+            meta.size = meta.size.saturating_sub(ENCRYPTION_OVERHEAD);
+        }
+        Ok(artifacts)
     }
 
     async fn health_check(&self) -> Result<()> {
@@ -114,6 +129,13 @@ impl ArtifactStore for EncryptedArtifactStore {
     }
 }
 
+#[async_trait]
+impl Erasable for EncryptedArtifactStore {
+    async fn erase_user(&self, user_id: &str) -> Result<usize> {
+        self.inner.erase_user(user_id).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,4 +161,27 @@ mod tests {
         assert_ne!(raw, data);
         assert!(raw.len() > data.len()); // Nonce + Auth Tag overhead
     }
+
+    #[tokio::test]
+    async fn test_range_and_metadata_use_plaintext() {
+        let base_store = Arc::new(InMemoryStore::new());
+        let key = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+        let store = EncryptedArtifactStore::new(base_store, key).unwrap();
+
+        let id = store.save(Bytes::from("SECRET DATA")).await.unwrap();
+
+        let meta = store.metadata(&id).await.unwrap().unwrap();
+        assert_eq!(meta.size, "SECRET DATA".len());
+        assert_eq!(
+            store.load_range(&id, 7, 10).await.unwrap(),
+            Some(Bytes::from("DATA"))
+        );
+        assert_eq!(
+            store
+                .presigned_url(&id, std::time::Duration::from_secs(60))
+                .await
+                .unwrap(),
+            None
+        );
+    }
 }
@@ -38,6 +38,8 @@ async fn test_audit_store_log_and_query() {
             metadata: None,
             previous_hash: None,
             hash: None,
+            trace_id: None,
+            session_id: None,
         })
         .await
         .unwrap();
@@ -53,6 +55,8 @@ async fn test_audit_store_log_and_query() {
             metadata: None,
             previous_hash: None,
             hash: None,
+            trace_id: None,
+            session_id: None,
         })
         .await
         .unwrap();
@@ -68,6 +72,8 @@ async fn test_audit_store_log_and_query() {
             metadata: None,
             previous_hash: None,
             hash: None,
+            trace_id: None,
+            session_id: None,
         })
         .await
         .unwrap();
@@ -120,6 +126,8 @@ async fn test_audit_outcome_serialization() {
         metadata: Some(serde_json::json!({"retry_count": 3})),
         previous_hash: None,
         hash: None,
+        trace_id: None,
+        session_id: None,
     };
 
     // Serialize to JSON
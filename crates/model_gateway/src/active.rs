@@ -0,0 +1,157 @@
+//! Runtime-switchable default LLM client.
+//!
+//! The default client lives behind an [`ArcSwap`], so activating another
+//! provider takes effect for the next call without a restart. Calls already in
+//! flight finish on the client they started with.
+
+use arc_swap::ArcSwap;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use multi_agent_core::{
+    traits::{ChatMessage, LlmClient, LlmResponse, ProviderParams},
+    Result,
+};
+
+/// Wraps each client swapped in, given its pricing key (metering, spend caps).
+pub type ClientLayer = dyn Fn(Arc<dyn LlmClient>, &str) -> Arc<dyn LlmClient> + Send + Sync;
+
+struct Active {
+    client: Arc<dyn LlmClient>,
+    model: String,
+    provider_id: Option<String>,
+}
+
+/// Default LLM client that can be replaced while the server runs.
+pub struct ActiveLlmClient {
+    current: ArcSwap<Active>,
+    layer: Option<Box<ClientLayer>>,
+}
+
+impl ActiveLlmClient {
+    /// `model` is the pricing key of `client`, e.g. `openai:gpt-4o`.
+    pub fn new(client: Arc<dyn LlmClient>, model: impl Into<String>) -> Self {
+        Self {
+            current: ArcSwap::from_pointee(Active {
+                client,
+                model: model.into(),
+                provider_id: None,
+            }),
+            layer: None,
+        }
+    }
+
+    /// Apply `layer` to the current client and every client activated later.
+    pub fn with_layer(
+        mut self,
+        layer: impl Fn(Arc<dyn LlmClient>, &str) -> Arc<dyn LlmClient> + Send + Sync + 'static,
+    ) -> Self {
+        let current = self.current.load_full();
+        self.current.store(Arc::new(Active {
+            client: layer(current.client.clone(), &current.model),
+            model: current.model.clone(),
+            provider_id: current.provider_id.clone(),
+        }));
+        self.layer = Some(Box::new(layer));
+        self
+    }
+
+    /// Make `client` the default for all subsequent calls.
+    pub fn activate(
+        &self,
+        client: Arc<dyn LlmClient>,
+        model: impl Into<String>,
+        provider_id: Option<String>,
+    ) {
+        let model = model.into();
+        let client = match &self.layer {
+            Some(layer) => layer(client, &model),
+            None => client,
+        };
+        tracing::info!(model = %model, provider = ?provider_id, "Switched default LLM provider");
+        self.current.store(Arc::new(Active {
+            client,
+            model,
+            provider_id,
+        }));
+    }
+
+    /// Pricing key of the active client.
+    pub fn model_id(&self) -> String {
+        self.current.load().model.clone()
+    }
+
+    /// Provider entry the active client was built from; `None` for the
+    /// startup client.
+    pub fn provider_id(&self) -> Option<String> {
+        self.current.load().provider_id.clone()
+    }
+
+    fn client(&self) -> Arc<dyn LlmClient> {
+        self.current.load().client.clone()
+    }
+}
+
+#[async_trait]
+impl LlmClient for ActiveLlmClient {
+    async fn complete(&self, prompt: &str) -> Result<LlmResponse> {
+        self.client().complete(prompt).await
+    }
+
+    async fn chat(&self, messages: &[ChatMessage]) -> Result<LlmResponse> {
+        self.client().chat(messages).await
+    }
+
+    async fn chat_with_params(
+        &self,
+        messages: &[ChatMessage],
+        params: &ProviderParams,
+    ) -> Result<LlmResponse> {
+        self.client().chat_with_params(messages, params).await
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        self.client().embed(text).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::usage::{MeteredLlmClient, UsageLedger};
+    use crate::MockLlmClient;
+
+    #[tokio::test]
+    async fn test_activate_swaps_client_and_reapplies_layer() {
+        let ledger = Arc::new(UsageLedger::new());
+        let layer_ledger = ledger.clone();
+        let active = ActiveLlmClient::new(Arc::new(MockLlmClient::new("first")), "openai:gpt-4o")
+            .with_layer(move |client, model| {
+                Arc::new(MeteredLlmClient::new(client, layer_ledger.clone(), model))
+            });
+
+        assert!(active
+            .complete("hi")
+            .await
+            .unwrap()
+            .content
+            .starts_with("first"));
+        active.activate(
+            Arc::new(MockLlmClient::new("second")),
+            "anthropic:claude-3-5-haiku",
+            Some("prov-2".to_string()),
+        );
+        assert!(active
+            .complete("hi")
+            .await
+            .unwrap()
+            .content
+            .starts_with("second"));
+        assert_eq!(active.model_id(), "anthropic:claude-3-5-haiku");
+        assert_eq!(active.provider_id().as_deref(), Some("prov-2"));
+
+        let models: Vec<String> = ledger.today().models.into_iter().map(|m| m.model).collect();
+        assert!(models.contains(&"openai:gpt-4o".to_string()));
+        assert!(models.contains(&"anthropic:claude-3-5-haiku".to_string()));
+    }
+}
@@ -0,0 +1,364 @@
+//! Cost and usage alerts with a daily digest.
+//!
+//! A background job evaluates [`AlertRule`]s against today's
+//! [`UsageLedger`] summary and delivers each breach once per day to the
+//! configured notification sinks. Once a day, after the configured UTC hour,
+//! it also sends a digest of the previous day.
+
+use chrono::{DateTime, NaiveDate, Timelike, Utc};
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use multi_agent_core::{
+    config::UsageAlertsConfig,
+    traits::{Notification, NotificationSeverity, NotificationSink},
+};
+
+use crate::usage::{DailyUsage, UsageLedger};
+
+/// Entries listed per section of the digest.
+const DIGEST_TOP_N: usize = 5;
+
+/// A condition that triggers an alert.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlertRule {
+    /// Today's estimated spend exceeds `limit_usd`.
+    DailySpend { limit_usd: f64 },
+    /// A session used more than `limit` tokens today.
+    SessionTokens { limit: u64 },
+    /// A model failed more than `max_rate` of at least `min_calls` calls today.
+    ProviderErrorRate { max_rate: f64, min_calls: u64 },
+}
+
+impl AlertRule {
+    /// Rules enabled in the configuration.
+    pub fn from_config(config: &UsageAlertsConfig) -> Vec<Self> {
+        let mut rules = Vec::new();
+        if let Some(limit_usd) = config.daily_spend_usd {
+            rules.push(Self::DailySpend { limit_usd });
+        }
+        if let Some(limit) = config.session_tokens {
+            rules.push(Self::SessionTokens { limit });
+        }
+        if let Some(max_rate) = config.provider_error_rate {
+            rules.push(Self::ProviderErrorRate {
+                max_rate,
+                min_calls: config.min_provider_calls,
+            });
+        }
+        rules
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::DailySpend { .. } => "daily_spend",
+            Self::SessionTokens { .. } => "session_tokens",
+            Self::ProviderErrorRate { .. } => "provider_error_rate",
+        }
+    }
+
+    /// Breaches in a day's usage, keyed so each fires once per day.
+    fn evaluate(&self, usage: &DailyUsage) -> Vec<(String, Notification)> {
+        match *self {
+            Self::DailySpend { limit_usd } if usage.cost_usd > limit_usd => vec![(
+                self.name().to_string(),
+                Notification::new(
+                    NotificationSeverity::Warning,
+                    "Daily spend limit exceeded",
+                    format!(
+                        "Estimated spend on {} is ${:.2}, over the ${:.2} limit.",
+                        usage.date, usage.cost_usd, limit_usd
+                    ),
+                ),
+            )],
+            Self::DailySpend { .. } => Vec::new(),
+            Self::SessionTokens { limit } => usage
+                .sessions
+                .iter()
+                .filter(|s| s.tokens > limit)
+                .map(|s| {
+                    (
+                        format!("{}:{}", self.name(), s.session_id),
+                        Notification::new(
+                            NotificationSeverity::Warning,
+                            "Session token limit exceeded",
+                            format!(
+                                "Session {} used {} tokens today, over the {} limit.",
+                                s.session_id, s.tokens, limit
+                            ),
+                        ),
+                    )
+                })
+                .collect(),
+            Self::ProviderErrorRate {
+                max_rate,
+                min_calls,
+            } => usage
+                .models
+                .iter()
+                .filter(|m| m.calls >= min_calls && m.error_rate() > max_rate)
+                .map(|m| {
+                    (
+                        format!("{}:{}", self.name(), m.model),
+                        Notification::new(
+                            NotificationSeverity::Critical,
+                            "Provider error rate high",
+                            format!(
+                                "{} failed {} of {} calls today ({:.0}%, limit {:.0}%).",
+                                m.model,
+                                m.errors,
+                                m.calls,
+                                m.error_rate() * 100.0,
+                                max_rate * 100.0
+                            ),
+                        ),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Evaluates alert rules and sends the daily digest.
+pub struct UsageAlerter {
+    ledger: Arc<UsageLedger>,
+    rules: Vec<AlertRule>,
+    sinks: Vec<Arc<dyn NotificationSink>>,
+    digest_hour: Option<u32>,
+    /// Alerts already sent, with the day they were sent for.
+    fired: Mutex<HashSet<(NaiveDate, String)>>,
+    last_digest: Mutex<Option<NaiveDate>>,
+}
+
+impl UsageAlerter {
+    pub fn new(ledger: Arc<UsageLedger>, rules: Vec<AlertRule>) -> Self {
+        Self {
+            ledger,
+            rules,
+            sinks: Vec::new(),
+            digest_hour: None,
+            fired: Mutex::new(HashSet::new()),
+            last_digest: Mutex::new(None),
+        }
+    }
+
+    /// Alerter configured from `[model_gateway.alerts]`, without sinks.
+    pub fn from_config(ledger: Arc<UsageLedger>, config: &UsageAlertsConfig) -> Self {
+        Self::new(ledger, AlertRule::from_config(config)).with_digest_hour(config.digest_hour_utc)
+    }
+
+    pub fn with_sink(mut self, sink: Arc<dyn NotificationSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Send the previous day's digest after this UTC hour; `None` disables it.
+    pub fn with_digest_hour(mut self, hour: Option<u32>) -> Self {
+        self.digest_hour = hour.map(|h| h.min(23));
+        self
+    }
+
+    /// New breaches for `date`. Each is reported once per day.
+    pub fn check(&self, date: NaiveDate) -> Vec<Notification> {
+        let usage = self.ledger.summary(date);
+        let mut fired = self.fired.lock().unwrap();
+        fired.retain(|(day, _)| *day >= date);
+        let mut alerts = Vec::new();
+        for rule in &self.rules {
+            for (key, notification) in rule.evaluate(&usage) {
+                if fired.insert((date, key)) {
+                    metrics::counter!("usage_alerts_total", "rule" => rule.name()).increment(1);
+                    alerts.push(notification);
+                }
+            }
+        }
+        alerts
+    }
+
+    /// Digest of one day: sessions, cost, calls per model and top tools.
+    pub fn digest(&self, date: NaiveDate) -> Notification {
+        let usage = self.ledger.summary(date);
+        let mut body = format!(
+            "{} sessions, {} LLM calls ({} failed), {} tokens, estimated cost ${:.2}.",
+            usage.sessions.len(),
+            usage.calls,
+            usage.errors,
+            usage.total_tokens,
+            usage.cost_usd
+        );
+        if !usage.models.is_empty() {
+            body.push_str("\n\nModels:");
+            for m in usage.models.iter().take(DIGEST_TOP_N) {
+                let _ = write!(
+                    body,
+                    "\n- {}: {} calls, {} tokens, ${:.2}",
+                    m.model,
+                    m.calls,
+                    m.prompt_tokens + m.completion_tokens,
+                    m.cost_usd
+                );
+            }
+        }
+        if !usage.sessions.is_empty() {
+            body.push_str("\n\nLargest sessions:");
+            for s in usage.sessions.iter().take(DIGEST_TOP_N) {
+                let _ = write!(body, "\n- {}: {} tokens", s.session_id, s.tokens);
+            }
+        }
+        if !usage.tools.is_empty() {
+            body.push_str("\n\nTop tools:");
+            for t in usage.tools.iter().take(DIGEST_TOP_N) {
+                let _ = write!(body, "\n- {}: {} calls", t.tool, t.calls);
+            }
+        }
+        Notification::new(
+            NotificationSeverity::Info,
+            format!("Usage digest for {}", date),
+            body,
+        )
+    }
+
+    /// The previous day's digest, if it is due at `now` and not yet sent.
+    fn due_digest(&self, now: DateTime<Utc>) -> Option<Notification> {
+        let hour = self.digest_hour?;
+        if now.hour() < hour {
+            return None;
+        }
+        let yesterday = now.date_naive().pred_opt()?;
+        let mut last = self.last_digest.lock().unwrap();
+        if *last == Some(yesterday) {
+            return None;
+        }
+        *last = Some(yesterday);
+        Some(self.digest(yesterday))
+    }
+
+    /// Evaluate rules and the digest schedule once; returns the
+    /// notifications sent.
+    pub async fn tick(&self, now: DateTime<Utc>) -> Vec<Notification> {
+        let mut notifications = self.check(now.date_naive());
+        notifications.extend(self.due_digest(now));
+        for notification in &notifications {
+            for sink in &self.sinks {
+                if let Err(e) = sink.send(notification).await {
+                    tracing::warn!(sink = sink.name(), error = %e, "Notification delivery failed");
+                }
+            }
+        }
+        notifications
+    }
+
+    /// Run [`Self::tick`] every `interval`.
+    pub fn spawn(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                self.tick(Utc::now()).await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pricing::{ModelPricing, PricingRegistry};
+    use async_trait::async_trait;
+    use chrono::TimeZone;
+
+    #[derive(Default)]
+    struct RecordingSink(Mutex<Vec<Notification>>);
+
+    #[async_trait]
+    impl NotificationSink for RecordingSink {
+        fn name(&self) -> &str {
+            "recording"
+        }
+        async fn send(&self, notification: &Notification) -> multi_agent_core::Result<()> {
+            self.0.lock().unwrap().push(notification.clone());
+            Ok(())
+        }
+    }
+
+    fn ledger() -> Arc<UsageLedger> {
+        let mut pricing = PricingRegistry::new();
+        pricing.register(ModelPricing::new("test:model", 10.0, 10.0));
+        Arc::new(UsageLedger::new().with_pricing(pricing))
+    }
+
+    #[tokio::test]
+    async fn test_rules_fire_once_per_day() {
+        let ledger = ledger();
+        let sink = Arc::new(RecordingSink::default());
+        let config = UsageAlertsConfig {
+            daily_spend_usd: Some(5.0),
+            session_tokens: Some(1_000),
+            provider_error_rate: Some(0.25),
+            min_provider_calls: 4,
+            digest_hour_utc: None,
+            ..UsageAlertsConfig::default()
+        };
+        let alerter = UsageAlerter::from_config(ledger.clone(), &config).with_sink(sink.clone());
+        let now = Utc::now();
+
+        ledger.record_call("test:model", 200, 100);
+        ledger.record_session("s1", 300);
+        assert!(alerter.tick(now).await.is_empty());
+
+        ledger.record_call("test:model", 400, 0);
+        ledger.record_error("test:model");
+        ledger.record_error("test:model");
+        ledger.record_session("s1", 900);
+        let titles: Vec<String> = alerter
+            .tick(now)
+            .await
+            .into_iter()
+            .map(|n| n.title)
+            .collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Daily spend limit exceeded",
+                "Session token limit exceeded",
+                "Provider error rate high"
+            ]
+        );
+
+        // Still breached, but already reported today
+        assert!(alerter.tick(now).await.is_empty());
+        assert_eq!(sink.0.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_digest_sent_once_after_hour() {
+        let ledger = ledger();
+        let alerter = UsageAlerter::new(ledger.clone(), Vec::new()).with_digest_hour(Some(8));
+        ledger.record_call("test:model", 1_000, 0);
+        ledger.record_session("s1", 1_000);
+        ledger.record_tool("search");
+
+        let today = Utc::now().date_naive();
+        let digest = alerter.digest(today);
+        assert!(digest
+            .body
+            .starts_with("1 sessions, 1 LLM calls (0 failed), 1000 tokens"));
+        assert!(digest.body.contains("- search: 1 calls"));
+
+        let day = Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap();
+        assert!(alerter
+            .tick(day + chrono::Duration::hours(7))
+            .await
+            .is_empty());
+        let sent = alerter.tick(day + chrono::Duration::hours(8)).await;
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].title, "Usage digest for 2026-03-01");
+        assert!(alerter
+            .tick(day + chrono::Duration::hours(9))
+            .await
+            .is_empty());
+    }
+}
@@ -0,0 +1,240 @@
+//! Canary evaluation of model upgrades.
+//!
+//! A [`CanarySuite`] runs a fixed set of prompts against a client and
+//! records how many answers contain the expected text, the tokens used and,
+//! when the model is priced, the estimated cost. [`CanaryThresholds`] decide
+//! whether a candidate model's metrics regress against the baseline's.
+
+use serde::Serialize;
+use std::time::Duration;
+
+use multi_agent_core::config::{CanaryCase, ModelCanaryConfig};
+use multi_agent_core::traits::LlmClient;
+
+use crate::pricing::ModelPricing;
+
+/// Result of one suite run against one model.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CanaryMetrics {
+    pub model: String,
+    pub cases: usize,
+    pub passed: usize,
+    /// Names of the failed cases.
+    pub failures: Vec<String>,
+    pub tokens: u64,
+    /// Estimated cost; `None` for models without pricing.
+    pub cost_usd: Option<f64>,
+}
+
+impl CanaryMetrics {
+    /// Fraction of passed cases; 1 for an empty suite.
+    pub fn success_rate(&self) -> f64 {
+        if self.cases == 0 {
+            1.0
+        } else {
+            self.passed as f64 / self.cases as f64
+        }
+    }
+}
+
+/// Tolerated regressions of a candidate model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CanaryThresholds {
+    /// Largest tolerated drop in success rate.
+    pub max_success_drop: f64,
+    /// Largest tolerated relative cost increase.
+    pub max_cost_increase: f64,
+}
+
+impl CanaryThresholds {
+    pub fn from_config(config: &ModelCanaryConfig) -> Self {
+        Self {
+            max_success_drop: config.max_success_drop,
+            max_cost_increase: config.max_cost_increase,
+        }
+    }
+
+    /// Regressions of `candidate` against `baseline`; empty if it passes.
+    ///
+    /// Cost is compared in USD when both models are priced and in tokens
+    /// otherwise.
+    pub fn regressions(&self, baseline: &CanaryMetrics, candidate: &CanaryMetrics) -> Vec<String> {
+        let mut regressions = Vec::new();
+        let drop = baseline.success_rate() - candidate.success_rate();
+        if drop > self.max_success_drop + f64::EPSILON {
+            regressions.push(format!(
+                "success rate fell from {:.0}% to {:.0}%",
+                baseline.success_rate() * 100.0,
+                candidate.success_rate() * 100.0
+            ));
+        }
+        let (base, new, unit) = match (baseline.cost_usd, candidate.cost_usd) {
+            (Some(base), Some(new)) => (base, new, "cost"),
+            _ => (
+                baseline.tokens as f64,
+                candidate.tokens as f64,
+                "token usage",
+            ),
+        };
+        if base > 0.0 && (new - base) / base > self.max_cost_increase {
+            regressions.push(format!(
+                "{} rose by {:.0}%",
+                unit,
+                (new - base) / base * 100.0
+            ));
+        }
+        regressions
+    }
+}
+
+/// Prompts with expected answers, run against each model of an upgrade.
+pub struct CanarySuite {
+    cases: Vec<CanaryCase>,
+    timeout: Duration,
+}
+
+impl CanarySuite {
+    pub fn new(cases: Vec<CanaryCase>) -> Self {
+        Self {
+            cases,
+            timeout: Duration::from_secs(60),
+        }
+    }
+
+    pub fn from_config(config: &ModelCanaryConfig) -> Self {
+        Self::new(config.cases.clone())
+            .with_timeout(Duration::from_secs(config.timeout_secs.max(1)))
+    }
+
+    /// Time limit per case.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cases.is_empty()
+    }
+
+    /// Run every case against `client`; errors and timeouts fail the case.
+    pub async fn run(
+        &self,
+        model: &str,
+        client: &dyn LlmClient,
+        pricing: Option<&ModelPricing>,
+    ) -> CanaryMetrics {
+        let mut metrics = CanaryMetrics {
+            model: model.to_string(),
+            cases: self.cases.len(),
+            cost_usd: pricing.map(|_| 0.0),
+            ..Default::default()
+        };
+        for case in &self.cases {
+            let response =
+                match tokio::time::timeout(self.timeout, client.complete(&case.prompt)).await {
+                    Ok(Ok(response)) => response,
+                    Ok(Err(e)) => {
+                        tracing::debug!(model, case = %case.name, error = %e, "Canary case failed");
+                        metrics.failures.push(case.name.clone());
+                        continue;
+                    }
+                    Err(_) => {
+                        tracing::debug!(model, case = %case.name, "Canary case timed out");
+                        metrics.failures.push(case.name.clone());
+                        continue;
+                    }
+                };
+            let usage = &response.usage;
+            metrics.tokens += usage.prompt_tokens + usage.completion_tokens;
+            if let (Some(cost), Some(pricing)) = (metrics.cost_usd.as_mut(), pricing) {
+                *cost += pricing.estimate_cost(usage.prompt_tokens, usage.completion_tokens);
+            }
+            let answer = response.content.to_lowercase();
+            if case
+                .expect
+                .iter()
+                .all(|expected| answer.contains(&expected.to_lowercase()))
+            {
+                metrics.passed += 1;
+            } else {
+                metrics.failures.push(case.name.clone());
+            }
+        }
+        metrics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::MockLlmClient;
+
+    fn case(name: &str, prompt: &str, expect: &str) -> CanaryCase {
+        CanaryCase {
+            name: name.to_string(),
+            prompt: prompt.to_string(),
+            expect: vec![expect.to_string()],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_suite_scores_answers() {
+        let suite = CanarySuite::new(vec![
+            case("echo", "Paris", "paris"),
+            case("missing", "Rome", "Madrid"),
+        ]);
+        let pricing = ModelPricing::new("mock:model", 1.0, 1.0);
+        let metrics = suite
+            .run("mock:model", &MockLlmClient::new("answer"), Some(&pricing))
+            .await;
+        assert_eq!(metrics.passed, 1);
+        assert_eq!(metrics.failures, vec!["missing"]);
+        assert!(metrics.tokens > 0);
+        assert!(metrics.cost_usd.unwrap() > 0.0);
+
+        let failed = suite
+            .run("mock:broken", &MockLlmClient::failing(), None)
+            .await;
+        assert_eq!(failed.passed, 0);
+        assert_eq!(failed.cost_usd, None);
+    }
+
+    #[test]
+    fn test_regressions() {
+        let thresholds = CanaryThresholds {
+            max_success_drop: 0.0,
+            max_cost_increase: 0.5,
+        };
+        let baseline = CanaryMetrics {
+            cases: 4,
+            passed: 4,
+            tokens: 100,
+            ..Default::default()
+        };
+        assert!(thresholds.regressions(&baseline, &baseline).is_empty());
+
+        let worse = CanaryMetrics {
+            passed: 3,
+            tokens: 200,
+            ..baseline.clone()
+        };
+        let regressions = thresholds.regressions(&baseline, &worse);
+        assert_eq!(regressions.len(), 2);
+        assert!(regressions[1].starts_with("token usage"));
+
+        // Priced models are compared by cost rather than tokens
+        let priced = |cost| CanaryMetrics {
+            cost_usd: Some(cost),
+            ..baseline.clone()
+        };
+        assert!(thresholds
+            .regressions(
+                &priced(1.0),
+                &CanaryMetrics {
+                    tokens: 500,
+                    ..priced(1.2)
+                }
+            )
+            .is_empty());
+    }
+}
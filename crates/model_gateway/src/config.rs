@@ -1,8 +1,11 @@
-use multi_agent_core::Result;
+use multi_agent_core::{traits::ProviderParams, Result};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use std::sync::Arc;
 use tokio::fs;
 
+use crate::endpoints::EndpointPool;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProviderConfig {
     pub providers: Vec<ProviderDefinition>,
@@ -12,6 +15,9 @@ pub struct ProviderConfig {
 pub struct ProviderDefinition {
     pub name: String,
     pub base_url: Option<String>,
+    /// Additional regional base URLs; requests go to the fastest healthy one.
+    #[serde(default)]
+    pub regional_urls: Vec<String>,
     pub api_key: Option<String>,
     pub models: Vec<ModelDefinition>,
 }
@@ -23,6 +29,21 @@ pub struct ModelDefinition {
     pub cost_out: Option<f64>,
     pub capabilities: Vec<String>,
     pub max_tokens: Option<u32>,
+    /// Provider-specific request parameters (`top_p`, `stop`, `seed`, ...).
+    #[serde(default)]
+    pub params: ProviderParams,
+}
+
+impl ProviderDefinition {
+    /// Endpoint pool over `base_url` and `regional_urls`, if any are regional.
+    pub fn endpoint_pool(&self) -> Option<Arc<EndpointPool>> {
+        if self.regional_urls.is_empty() {
+            return None;
+        }
+        Some(Arc::new(EndpointPool::new(
+            self.base_url.iter().chain(&self.regional_urls).cloned(),
+        )))
+    }
 }
 
 impl ProviderConfig {
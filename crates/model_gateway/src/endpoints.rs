@@ -0,0 +1,238 @@
+//! Multi-region provider endpoints.
+//!
+//! A provider can be reachable through several base URLs (regions). Each
+//! [`EndpointPool`] is probed periodically; requests go to the fastest healthy
+//! endpoint and fail over to the next one on errors.
+
+use dashmap::DashMap;
+use serde::Serialize;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// Consecutive failures after which an endpoint is considered unhealthy.
+const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+/// Weight of the newest probe in the smoothed latency.
+const LATENCY_SMOOTHING: f64 = 0.3;
+/// Timeout for a single latency probe.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Health of one regional endpoint.
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointStatus {
+    pub base_url: String,
+    pub healthy: bool,
+    /// Smoothed probe latency; `None` until the first successful probe.
+    pub latency_ms: Option<u64>,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    /// Unix timestamp of the last probe.
+    pub last_probe: Option<i64>,
+}
+
+impl EndpointStatus {
+    fn new(base_url: String) -> Self {
+        Self {
+            base_url,
+            healthy: true,
+            latency_ms: None,
+            consecutive_failures: 0,
+            last_error: None,
+            last_probe: None,
+        }
+    }
+}
+
+/// Endpoints of one provider, ordered by health and latency.
+#[derive(Debug)]
+pub struct EndpointPool {
+    endpoints: RwLock<Vec<EndpointStatus>>,
+}
+
+impl EndpointPool {
+    /// Create a pool; the first URL is preferred until probes say otherwise.
+    pub fn new(base_urls: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        let mut endpoints: Vec<EndpointStatus> = Vec::new();
+        for url in base_urls {
+            let url = url.into().trim_end_matches('/').to_string();
+            if !url.is_empty() && !endpoints.iter().any(|e| e.base_url == url) {
+                endpoints.push(EndpointStatus::new(url));
+            }
+        }
+        Self {
+            endpoints: RwLock::new(endpoints),
+        }
+    }
+
+    /// Endpoints in the order requests should try them: healthy ones by
+    /// latency (unprobed last), then unhealthy ones as a last resort.
+    pub fn ordered(&self) -> Vec<String> {
+        let mut endpoints = self.snapshot();
+        endpoints.sort_by_key(|e| (!e.healthy, e.latency_ms.is_none(), e.latency_ms));
+        endpoints.into_iter().map(|e| e.base_url).collect()
+    }
+
+    /// The endpoint the next request will use.
+    pub fn select(&self) -> Option<String> {
+        self.ordered().into_iter().next()
+    }
+
+    pub fn snapshot(&self) -> Vec<EndpointStatus> {
+        self.endpoints.read().unwrap().clone()
+    }
+
+    /// Record a successful request or probe.
+    pub fn record_success(&self, base_url: &str, latency: Option<Duration>) {
+        self.update(base_url, |e| {
+            e.healthy = true;
+            e.consecutive_failures = 0;
+            e.last_error = None;
+            if let Some(latency) = latency {
+                let ms = latency.as_millis() as f64;
+                let smoothed = match e.latency_ms {
+                    Some(prev) => prev as f64 * (1.0 - LATENCY_SMOOTHING) + ms * LATENCY_SMOOTHING,
+                    None => ms,
+                };
+                e.latency_ms = Some(smoothed.round() as u64);
+            }
+        });
+    }
+
+    /// Record a failed request or probe.
+    pub fn record_failure(&self, base_url: &str, error: &str) {
+        self.update(base_url, |e| {
+            e.consecutive_failures += 1;
+            e.last_error = Some(error.to_string());
+            if e.consecutive_failures >= MAX_CONSECUTIVE_FAILURES && e.healthy {
+                e.healthy = false;
+                tracing::warn!(endpoint = %e.base_url, error, "Endpoint marked unhealthy");
+            }
+        });
+    }
+
+    fn update(&self, base_url: &str, f: impl FnOnce(&mut EndpointStatus)) {
+        let mut endpoints = self.endpoints.write().unwrap();
+        if let Some(endpoint) = endpoints.iter_mut().find(|e| e.base_url == base_url) {
+            f(endpoint);
+        }
+    }
+
+    /// Measure the latency of every endpoint. Any HTTP response counts as
+    /// reachable; connection errors and timeouts count as failures.
+    pub async fn probe(&self, client: &reqwest::Client) {
+        let urls: Vec<String> = self.snapshot().into_iter().map(|e| e.base_url).collect();
+        for url in urls {
+            let started = Instant::now();
+            let result = client
+                .get(format!("{}/models", url))
+                .timeout(PROBE_TIMEOUT)
+                .send()
+                .await;
+            let now = unix_now();
+            self.update(&url, |e| e.last_probe = Some(now));
+            match result {
+                Ok(_) => self.record_success(&url, Some(started.elapsed())),
+                Err(e) => self.record_failure(&url, &e.to_string()),
+            }
+        }
+    }
+}
+
+fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// Endpoint pools by provider, probed together.
+#[derive(Debug, Default)]
+pub struct EndpointRegistry {
+    pools: DashMap<String, Arc<EndpointPool>>,
+}
+
+impl EndpointRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&self, provider: &str, pool: Arc<EndpointPool>) {
+        self.pools.insert(provider.to_string(), pool);
+    }
+
+    pub fn remove(&self, provider: &str) {
+        self.pools.remove(provider);
+    }
+
+    pub fn get(&self, provider: &str) -> Option<Arc<EndpointPool>> {
+        self.pools.get(provider).map(|p| p.value().clone())
+    }
+
+    /// Per-endpoint health of a provider.
+    pub fn status(&self, provider: &str) -> Vec<EndpointStatus> {
+        self.get(provider).map(|p| p.snapshot()).unwrap_or_default()
+    }
+
+    /// Probe all pools once.
+    pub async fn probe_all(&self, client: &reqwest::Client) {
+        let pools: Vec<Arc<EndpointPool>> = self.pools.iter().map(|p| p.value().clone()).collect();
+        for pool in pools {
+            pool.probe(client).await;
+        }
+    }
+
+    /// Probe all pools every `interval`.
+    pub fn spawn(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let client = reqwest::Client::new();
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                self.probe_all(&client).await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_to_fastest_healthy_endpoint() {
+        let pool = EndpointPool::new(["https://us.example.com/", "https://eu.example.com"]);
+        assert_eq!(pool.select().unwrap(), "https://us.example.com");
+
+        pool.record_success("https://us.example.com", Some(Duration::from_millis(120)));
+        pool.record_success("https://eu.example.com", Some(Duration::from_millis(40)));
+        assert_eq!(pool.select().unwrap(), "https://eu.example.com");
+
+        for _ in 0..MAX_CONSECUTIVE_FAILURES {
+            pool.record_failure("https://eu.example.com", "connection refused");
+        }
+        assert_eq!(
+            pool.ordered(),
+            vec!["https://us.example.com", "https://eu.example.com"]
+        );
+        let status = pool.snapshot();
+        assert!(!status[1].healthy);
+        assert_eq!(status[1].last_error.as_deref(), Some("connection refused"));
+
+        // A successful probe brings it back
+        pool.record_success("https://eu.example.com", Some(Duration::from_millis(40)));
+        assert_eq!(pool.select().unwrap(), "https://eu.example.com");
+    }
+
+    #[tokio::test]
+    async fn test_probe_marks_unreachable_endpoint() {
+        let pool = EndpointPool::new(["http://127.0.0.1:1"]);
+        let client = reqwest::Client::new();
+        for _ in 0..MAX_CONSECUTIVE_FAILURES {
+            pool.probe(&client).await;
+        }
+        let status = &pool.snapshot()[0];
+        assert!(!status.healthy);
+        assert!(status.last_probe.is_some());
+        assert_eq!(status.latency_ms, None);
+    }
+}
@@ -3,20 +3,46 @@
 //!
 //! This crate provides:
 //! - Model selection and load balancing
+//! - Cost-aware routing by task class and historical outcomes
 //! - Provider health tracking and circuit breaker
 //! - Fallback and retry logic
-//! - Rig LLM client adapter
+//! - Multi-region endpoints with latency-based selection
+//! - Prioritized request queueing (interactive > background > eval)
+//! - Rate-limit tracking from provider headers with adaptive throttling
+//! - Daily usage ledger with cost alerts and digests
+//! - Monthly spend caps per provider, waived for privileged workspaces
+//! - Rig LLM client adapter, switchable at runtime
+//! - Pre-call token counting with per-model tokenizers
+//! - Canary evaluation of model upgrades against the previous model
 
+pub mod active;
+pub mod alerts;
+pub mod canary;
 pub mod config;
+pub mod endpoints;
 pub mod pricing;
 pub mod providers;
+pub mod queue;
+pub mod rate_limit;
 pub mod rig_client;
 pub mod selector;
+pub mod spend_caps;
+pub mod tokenizer;
+pub mod usage;
 
+pub use active::{ActiveLlmClient, ClientLayer};
+pub use alerts::{AlertRule, UsageAlerter};
+pub use canary::{CanaryMetrics, CanarySuite, CanaryThresholds};
+pub use endpoints::{EndpointPool, EndpointRegistry, EndpointStatus};
 pub use pricing::{ModelPricing, PricingRegistry, SessionCostTracker};
-pub use providers::{MockLlmClient, ProviderRegistry};
+pub use providers::{MockLlmClient, ProviderRegistry, RegisteredProviderClient};
+pub use queue::{InferenceQueue, QueueClassStats, QueuePermit, QueuedLlmClient, RequestPriority};
+pub use rate_limit::{RateLimitHeaders, RateLimitTracker, RateLimitedHttp};
 pub use rig_client::{create_default_client, RigConfig, RigLlmClient, RigProvider};
-pub use selector::AdaptiveModelSelector;
+pub use selector::{AdaptiveModelSelector, ModelDecision, TaskClass};
+pub use spend_caps::{SpendCap, SpendCapChange, SpendCappedLlmClient, SpendGuard};
+pub use tokenizer::{PromptEstimate, TokenCounter, TokenizerKind};
+pub use usage::{DailyUsage, MeteredLlmClient, ModelUsage, UsageLedger};
 
 use config::ProviderConfig;
 use secrecy::Secret;
@@ -41,6 +67,13 @@ pub fn create_client_from_config(
                 }
                 if let Some(model) = provider.models.first() {
                     let mut rig_cfg = RigConfig::openai(&model.id);
+                    rig_cfg.params = model.params.clone();
+                    if let Some(pool) = provider.endpoint_pool() {
+                        rig_cfg = rig_cfg.with_endpoints(pool);
+                    }
+                    if let Some(max_tokens) = model.max_tokens {
+                        rig_cfg = rig_cfg.with_max_tokens(max_tokens);
+                    }
                     if let Some(key) = openai_key.clone() {
                         rig_cfg = rig_cfg.with_api_key(key);
                     }
@@ -53,6 +86,13 @@ pub fn create_client_from_config(
                 }
                 if let Some(model) = provider.models.first() {
                     let mut rig_cfg = RigConfig::anthropic(&model.id);
+                    rig_cfg.params = model.params.clone();
+                    if let Some(pool) = provider.endpoint_pool() {
+                        rig_cfg = rig_cfg.with_endpoints(pool);
+                    }
+                    if let Some(max_tokens) = model.max_tokens {
+                        rig_cfg = rig_cfg.with_max_tokens(max_tokens);
+                    }
                     if let Some(key) = anthropic_key.clone() {
                         rig_cfg = rig_cfg.with_api_key(key);
                     }
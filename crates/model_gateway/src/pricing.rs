@@ -19,13 +19,22 @@ pub struct ModelPricing {
     pub quality_score: u8,
     /// Average latency in ms.
     pub avg_latency_ms: u32,
+    /// Maximum context length in tokens.
+    #[serde(default = "default_context_window")]
+    pub context_window: usize,
+}
+
+fn default_context_window() -> usize {
+    crate::tokenizer::DEFAULT_CONTEXT_WINDOW
 }
 
 impl ModelPricing {
     /// Create new pricing info.
     pub fn new(model_id: impl Into<String>, input: f64, output: f64) -> Self {
+        let model_id = model_id.into();
         Self {
-            model_id: model_id.into(),
+            context_window: crate::tokenizer::context_window(&model_id),
+            model_id,
             input_cost_per_1k: input,
             output_cost_per_1k: output,
             quality_score: 5,
@@ -39,6 +48,12 @@ impl ModelPricing {
         self
     }
 
+    /// Set the context window.
+    pub fn with_context_window(mut self, tokens: usize) -> Self {
+        self.context_window = tokens;
+        self
+    }
+
     /// Estimate cost for a request.
     pub fn estimate_cost(&self, input_tokens: u64, output_tokens: u64) -> f64 {
         let input_cost = (input_tokens as f64 / 1000.0) * self.input_cost_per_1k;
@@ -104,7 +119,7 @@ impl PricingRegistry {
     /// Get all models sorted by quality (best first).
     pub fn sorted_by_quality(&self) -> Vec<&ModelPricing> {
         let mut models: Vec<_> = self.models.values().collect();
-        models.sort_by(|a, b| b.quality_score.cmp(&a.quality_score));
+        models.sort_by_key(|m| std::cmp::Reverse(m.quality_score));
         models
     }
 
@@ -7,11 +7,15 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use multi_agent_core::{
-    traits::{ChatMessage, LlmClient, LlmResponse, LlmUsage},
+    traits::{ChatMessage, LlmClient, LlmResponse, LlmUsage, ProviderParams},
     types::ProviderHealth,
     Error, Result,
 };
 
+use crate::rate_limit::RateLimitTracker;
+use crate::spend_caps::{current_workspace, SpendGuard};
+use crate::tokenizer::TokenCounter;
+
 /// Provider status tracking.
 #[derive(Debug)]
 pub struct ProviderStatus {
@@ -29,14 +33,18 @@ pub struct ProviderStatus {
     pub last_failure: Option<Instant>,
     /// Circuit breaker open until.
     pub circuit_open_until: Option<Instant>,
+    /// Maximum context length in tokens.
+    pub context_window: usize,
 }
 
 impl ProviderStatus {
     /// Create a new provider status.
     pub fn new(name: impl Into<String>, model: impl Into<String>) -> Self {
+        let model = model.into();
         Self {
             name: name.into(),
-            model: model.into(),
+            context_window: crate::tokenizer::context_window(&model),
+            model,
             health: ProviderHealth::Healthy,
             total_requests: AtomicU64::new(0),
             failed_requests: AtomicU64::new(0),
@@ -99,6 +107,10 @@ impl ProviderStatus {
 pub struct ProviderRegistry {
     /// Registered providers. Note: using Arc<dyn LlmClient> to support cloning.
     providers: DashMap<String, (Arc<dyn LlmClient>, ProviderStatus)>,
+    /// Providers out of quota are skipped until their limit resets.
+    rate_limits: Option<Arc<RateLimitTracker>>,
+    /// Providers over their monthly spend cap are skipped for non-privileged workspaces.
+    spend_guard: Option<Arc<SpendGuard>>,
 }
 
 impl ProviderRegistry {
@@ -106,9 +118,28 @@ impl ProviderRegistry {
     pub fn new() -> Self {
         Self {
             providers: DashMap::new(),
+            rate_limits: None,
+            spend_guard: None,
         }
     }
 
+    /// Reroute around providers whose rate limit is exhausted.
+    pub fn with_rate_limits(mut self, tracker: Arc<RateLimitTracker>) -> Self {
+        self.rate_limits = Some(tracker);
+        self
+    }
+
+    /// Tracker shared with the clients of registered providers.
+    pub fn rate_limits(&self) -> Option<&Arc<RateLimitTracker>> {
+        self.rate_limits.as_ref()
+    }
+
+    /// Skip providers over their spend cap unless the current workspace is privileged.
+    pub fn with_spend_guard(mut self, guard: Arc<SpendGuard>) -> Self {
+        self.spend_guard = Some(guard);
+        self
+    }
+
     /// Register a provider.
     pub fn register(&self, name: &str, model: &str, client: Arc<dyn LlmClient>) {
         let status = ProviderStatus::new(name, model);
@@ -116,6 +147,31 @@ impl ProviderRegistry {
         self.providers.insert(key, (client, status));
     }
 
+    /// Remove a provider by key.
+    pub fn unregister(&self, key: &str) {
+        self.providers.remove(key);
+    }
+
+    /// Override a provider's context window (e.g. for self-hosted models).
+    pub fn set_context_window(&self, key: &str, tokens: usize) {
+        if let Some(mut entry) = self.providers.get_mut(key) {
+            entry.1.context_window = tokens;
+        }
+    }
+
+    /// Maximum context length of a provider, in tokens.
+    pub fn context_window(&self, key: &str) -> Option<usize> {
+        self.providers
+            .get(key)
+            .map(|entry| entry.value().1.context_window)
+    }
+
+    /// Token counter for a provider, using its configured context window.
+    pub fn token_counter(&self, key: &str) -> Option<TokenCounter> {
+        self.context_window(key)
+            .map(|window| TokenCounter::for_model(key).with_context_window(window))
+    }
+
     /// Get all healthy providers.
     pub fn get_healthy(&self) -> Vec<String> {
         self.providers
@@ -124,6 +180,17 @@ impl ProviderRegistry {
                 let (_, status) = entry.value();
                 !status.is_circuit_open() && status.health != ProviderHealth::Unhealthy
             })
+            .filter(|entry| {
+                !self
+                    .rate_limits
+                    .as_ref()
+                    .is_some_and(|t| t.is_throttled(entry.key()))
+            })
+            .filter(|entry| {
+                self.spend_guard
+                    .as_ref()
+                    .is_none_or(|g| g.allows(entry.key(), current_workspace().as_deref()))
+            })
             .map(|entry| entry.key().clone())
             .collect()
     }
@@ -245,6 +312,25 @@ impl LlmClient for CircuitBreakerClient {
         }
     }
 
+    async fn chat_with_params(
+        &self,
+        messages: &[ChatMessage],
+        params: &ProviderParams,
+    ) -> Result<LlmResponse> {
+        self.check_health()?;
+
+        match self.inner.chat_with_params(messages, params).await {
+            Ok(res) => {
+                self.registry.record_success(&self.key);
+                Ok(res)
+            }
+            Err(e) => {
+                self.registry.record_failure(&self.key);
+                Err(e)
+            }
+        }
+    }
+
     async fn embed(&self, text: &str) -> Result<Vec<f32>> {
         self.check_health()?;
 
@@ -261,6 +347,68 @@ impl LlmClient for CircuitBreakerClient {
     }
 }
 
+/// Client for one registered provider, looked up on every call so that
+/// providers registered after startup are picked up. Calls go through the
+/// circuit breaker; while the provider is not registered they go to the
+/// fallback, or fail without one.
+pub struct RegisteredProviderClient {
+    registry: Arc<ProviderRegistry>,
+    key: String,
+    fallback: Option<Arc<dyn LlmClient>>,
+}
+
+impl RegisteredProviderClient {
+    pub fn new(registry: Arc<ProviderRegistry>, key: impl Into<String>) -> Self {
+        Self {
+            registry,
+            key: key.into(),
+            fallback: None,
+        }
+    }
+
+    /// Client used while the provider is not registered.
+    pub fn with_fallback(mut self, client: Arc<dyn LlmClient>) -> Self {
+        self.fallback = Some(client);
+        self
+    }
+
+    fn resolve(&self) -> Result<Arc<dyn LlmClient>> {
+        match self.registry.get_raw(&self.key) {
+            Some(inner) => Ok(Arc::new(CircuitBreakerClient::new(
+                inner,
+                self.registry.clone(),
+                self.key.clone(),
+            ))),
+            None => self.fallback.clone().ok_or_else(|| {
+                Error::ModelProvider(format!("Provider {} is not registered", self.key))
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl LlmClient for RegisteredProviderClient {
+    async fn complete(&self, prompt: &str) -> Result<LlmResponse> {
+        self.resolve()?.complete(prompt).await
+    }
+
+    async fn chat(&self, messages: &[ChatMessage]) -> Result<LlmResponse> {
+        self.resolve()?.chat(messages).await
+    }
+
+    async fn chat_with_params(
+        &self,
+        messages: &[ChatMessage],
+        params: &ProviderParams,
+    ) -> Result<LlmResponse> {
+        self.resolve()?.chat_with_params(messages, params).await
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        self.resolve()?.embed(text).await
+    }
+}
+
 // =============================================================================
 // Mock LLM Client for Testing
 // =============================================================================
@@ -395,6 +543,30 @@ mod tests {
         assert_eq!(healthy.len(), 1);
     }
 
+    #[test]
+    fn test_context_window_tracking() {
+        let registry = ProviderRegistry::new();
+        registry.register("openai", "gpt-4o", Arc::new(MockLlmClient::new("test")));
+        registry.register("local", "llama3", Arc::new(MockLlmClient::new("test")));
+
+        assert_eq!(registry.context_window("openai:gpt-4o"), Some(128_000));
+        registry.set_context_window("local:llama3", 32_768);
+        let counter = registry.token_counter("local:llama3").unwrap();
+        assert_eq!(counter.context_window(), 32_768);
+        assert!(registry.context_window("missing").is_none());
+    }
+
+    #[test]
+    fn test_rate_limited_providers_are_skipped() {
+        let tracker = Arc::new(RateLimitTracker::new());
+        let registry = ProviderRegistry::new().with_rate_limits(tracker.clone());
+        registry.register("openai", "gpt-4o", Arc::new(MockLlmClient::new("test")));
+        registry.register("anthropic", "claude", Arc::new(MockLlmClient::new("test")));
+
+        tracker.observe_rejection("openai:gpt-4o", Some(Duration::from_secs(60)));
+        assert_eq!(registry.get_healthy(), vec!["anthropic:claude".to_string()]);
+    }
+
     #[tokio::test]
     async fn test_circuit_breaker() {
         let registry = Arc::new(ProviderRegistry::new());
@@ -418,4 +590,20 @@ mod tests {
             matches!(result, Err(Error::ModelProvider(msg)) if msg.contains("Circuit breaker open"))
         );
     }
+
+    #[tokio::test]
+    async fn test_registered_provider_client_resolves_late_registrations() {
+        let registry = Arc::new(ProviderRegistry::new());
+        let client = RegisteredProviderClient::new(registry.clone(), "test:small");
+        assert!(matches!(
+            client.complete("hi").await,
+            Err(Error::ModelProvider(msg)) if msg.contains("not registered")
+        ));
+
+        let client = client.with_fallback(Arc::new(MockLlmClient::new("fallback")));
+        assert_eq!(client.complete("hi").await.unwrap().content, "fallback: hi");
+
+        registry.register("test", "small", Arc::new(MockLlmClient::new("small")));
+        assert_eq!(client.complete("hi").await.unwrap().content, "small: hi");
+    }
 }
@@ -0,0 +1,312 @@
+//! Prioritized inference queue.
+//!
+//! All sessions share the same provider rate limits. Requests are admitted
+//! by priority class (interactive > background > eval), each class with its
+//! own concurrency cap under a global one, so batch jobs cannot starve
+//! interactive chats.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::oneshot;
+
+use multi_agent_core::{
+    config::InferenceQueueConfig,
+    traits::{ChatMessage, LlmClient, LlmResponse, ProviderParams},
+    Result,
+};
+
+/// Priority class of an inference request, highest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequestPriority {
+    /// A user is waiting on the answer.
+    Interactive,
+    /// Research and other batch jobs.
+    Background,
+    /// Evaluation runs.
+    Eval,
+}
+
+impl RequestPriority {
+    pub const ALL: [Self; 3] = [Self::Interactive, Self::Background, Self::Eval];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Interactive => "interactive",
+            Self::Background => "background",
+            Self::Eval => "eval",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Snapshot of one priority class.
+#[derive(Debug, Clone, Serialize)]
+pub struct QueueClassStats {
+    pub class: RequestPriority,
+    pub running: usize,
+    pub waiting: usize,
+    pub limit: usize,
+}
+
+#[derive(Default)]
+struct QueueState {
+    running: [usize; 3],
+    waiting: [VecDeque<oneshot::Sender<QueuePermit>>; 3],
+}
+
+/// Admits inference requests by priority under concurrency caps.
+pub struct InferenceQueue {
+    config: InferenceQueueConfig,
+    state: Mutex<QueueState>,
+}
+
+impl InferenceQueue {
+    pub fn new(config: InferenceQueueConfig) -> Arc<Self> {
+        Arc::new(Self {
+            config,
+            state: Mutex::new(QueueState::default()),
+        })
+    }
+
+    fn limit(&self, priority: RequestPriority) -> usize {
+        match priority {
+            RequestPriority::Interactive => self.config.interactive_limit,
+            RequestPriority::Background => self.config.background_limit,
+            RequestPriority::Eval => self.config.eval_limit,
+        }
+    }
+
+    fn has_capacity(&self, state: &QueueState, priority: RequestPriority) -> bool {
+        state.running.iter().sum::<usize>() < self.config.max_concurrent
+            && state.running[priority.index()] < self.limit(priority)
+    }
+
+    /// Wait for a slot. The slot is held until the permit is dropped.
+    pub async fn acquire(self: &Arc<Self>, priority: RequestPriority) -> QueuePermit {
+        let started = Instant::now();
+        let receiver = {
+            let mut state = self.state.lock().unwrap();
+            // Forget waiters that gave up
+            for waiting in state.waiting.iter_mut() {
+                waiting.retain(|sender| !sender.is_closed());
+            }
+            // Never overtake waiters of the same or a higher class
+            let ahead = state.waiting[..=priority.index()]
+                .iter()
+                .any(|w| !w.is_empty());
+            if !ahead && self.has_capacity(&state, priority) {
+                state.running[priority.index()] += 1;
+                self.publish(&state);
+                return QueuePermit::new(self.clone(), priority);
+            }
+            let (sender, receiver) = oneshot::channel();
+            state.waiting[priority.index()].push_back(sender);
+            self.publish(&state);
+            receiver
+        };
+
+        // The sender is only dropped together with the queue
+        let permit = receiver.await.expect("inference queue dropped");
+        metrics::histogram!("inference_queue_wait_seconds", "class" => priority.as_str())
+            .record(started.elapsed().as_secs_f64());
+        permit
+    }
+
+    fn release(self: &Arc<Self>, priority: RequestPriority) {
+        let mut state = self.state.lock().unwrap();
+        state.running[priority.index()] -= 1;
+        self.dispatch(&mut state);
+        self.publish(&state);
+    }
+
+    /// Hand free slots to waiters, highest class first.
+    fn dispatch(self: &Arc<Self>, state: &mut QueueState) {
+        for priority in RequestPriority::ALL {
+            while self.has_capacity(state, priority) {
+                let Some(sender) = state.waiting[priority.index()].pop_front() else {
+                    break;
+                };
+                state.running[priority.index()] += 1;
+                if let Err(mut permit) = sender.send(QueuePermit::new(self.clone(), priority)) {
+                    // The waiter gave up; reclaim the slot without re-entering the lock
+                    permit.armed = false;
+                    state.running[priority.index()] -= 1;
+                }
+            }
+        }
+    }
+
+    fn publish(&self, state: &QueueState) {
+        for priority in RequestPriority::ALL {
+            let i = priority.index();
+            metrics::gauge!("inference_queue_depth", "class" => priority.as_str())
+                .set(state.waiting[i].len() as f64);
+            metrics::gauge!("inference_queue_running", "class" => priority.as_str())
+                .set(state.running[i] as f64);
+        }
+    }
+
+    pub fn stats(&self) -> Vec<QueueClassStats> {
+        let state = self.state.lock().unwrap();
+        RequestPriority::ALL
+            .iter()
+            .map(|&class| QueueClassStats {
+                class,
+                running: state.running[class.index()],
+                waiting: state.waiting[class.index()].len(),
+                limit: self.limit(class),
+            })
+            .collect()
+    }
+}
+
+/// A slot in the inference queue, released on drop.
+pub struct QueuePermit {
+    queue: Arc<InferenceQueue>,
+    priority: RequestPriority,
+    armed: bool,
+}
+
+impl QueuePermit {
+    fn new(queue: Arc<InferenceQueue>, priority: RequestPriority) -> Self {
+        Self {
+            queue,
+            priority,
+            armed: true,
+        }
+    }
+}
+
+impl Drop for QueuePermit {
+    fn drop(&mut self) {
+        if self.armed {
+            self.queue.release(self.priority);
+        }
+    }
+}
+
+/// LLM client whose calls wait for a slot in an [`InferenceQueue`].
+pub struct QueuedLlmClient {
+    inner: Arc<dyn LlmClient>,
+    queue: Arc<InferenceQueue>,
+    priority: RequestPriority,
+}
+
+impl QueuedLlmClient {
+    pub fn new(
+        inner: Arc<dyn LlmClient>,
+        queue: Arc<InferenceQueue>,
+        priority: RequestPriority,
+    ) -> Self {
+        Self {
+            inner,
+            queue,
+            priority,
+        }
+    }
+
+    /// The same client and queue under another priority class.
+    pub fn with_priority(&self, priority: RequestPriority) -> Self {
+        Self::new(self.inner.clone(), self.queue.clone(), priority)
+    }
+}
+
+#[async_trait]
+impl LlmClient for QueuedLlmClient {
+    async fn complete(&self, prompt: &str) -> Result<LlmResponse> {
+        let _permit = self.queue.acquire(self.priority).await;
+        self.inner.complete(prompt).await
+    }
+
+    async fn chat(&self, messages: &[ChatMessage]) -> Result<LlmResponse> {
+        let _permit = self.queue.acquire(self.priority).await;
+        self.inner.chat(messages).await
+    }
+
+    async fn chat_with_params(
+        &self,
+        messages: &[ChatMessage],
+        params: &ProviderParams,
+    ) -> Result<LlmResponse> {
+        let _permit = self.queue.acquire(self.priority).await;
+        self.inner.chat_with_params(messages, params).await
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let _permit = self.queue.acquire(self.priority).await;
+        self.inner.embed(text).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn queue(max_concurrent: usize) -> Arc<InferenceQueue> {
+        InferenceQueue::new(InferenceQueueConfig {
+            max_concurrent,
+            interactive_limit: max_concurrent,
+            background_limit: 1,
+            eval_limit: 1,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_interactive_is_admitted_before_background() {
+        let queue = queue(1);
+        let held = queue.acquire(RequestPriority::Background).await;
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut tasks = Vec::new();
+        for priority in [RequestPriority::Background, RequestPriority::Interactive] {
+            let (queue, order) = (queue.clone(), order.clone());
+            tasks.push(tokio::spawn(async move {
+                let _permit = queue.acquire(priority).await;
+                order.lock().unwrap().push(priority);
+            }));
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(queue.stats()[1].waiting, 1);
+        assert_eq!(queue.stats()[0].waiting, 1);
+
+        drop(held);
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert_eq!(
+            *order.lock().unwrap(),
+            vec![RequestPriority::Interactive, RequestPriority::Background]
+        );
+        assert!(queue
+            .stats()
+            .iter()
+            .all(|s| s.running == 0 && s.waiting == 0));
+    }
+
+    #[tokio::test]
+    async fn test_class_cap_leaves_room_for_other_classes() {
+        let queue = queue(4);
+        let _eval = queue.acquire(RequestPriority::Eval).await;
+        let blocked = tokio::time::timeout(
+            Duration::from_millis(20),
+            queue.acquire(RequestPriority::Eval),
+        )
+        .await;
+        assert!(blocked.is_err(), "eval is capped at one request");
+
+        // The cancelled waiter neither holds a slot nor blocks the queue
+        drop(_eval);
+        let _eval = queue.acquire(RequestPriority::Eval).await;
+        let _interactive = queue.acquire(RequestPriority::Interactive).await;
+        let stats = queue.stats();
+        assert_eq!((stats[0].running, stats[2].running), (1, 1));
+    }
+}
@@ -0,0 +1,388 @@
+//! Provider rate-limit tracking.
+//!
+//! Providers report their remaining quota in response headers
+//! (`x-ratelimit-*` for OpenAI, `anthropic-ratelimit-*` for Anthropic).
+//! [`RateLimitTracker`] keeps a token bucket per `provider:model` from those
+//! headers, spends it locally for requests in flight, and tells callers how
+//! long to wait before a request would be rejected with a 429.
+//! [`RateLimitedHttp`] is the Rig HTTP backend that feeds it.
+
+use bytes::Bytes;
+use dashmap::DashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use rig::http_client::{
+    self, HeaderMap, HttpClientExt, LazyBody, MultipartForm, Request, Response, StreamingResponse,
+};
+use rig::wasm_compat::WasmCompatSend;
+
+/// Wait assumed after a 429 without a `retry-after` header.
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+/// Reset values above this are Unix timestamps rather than delays.
+const EPOCH_THRESHOLD_SECS: f64 = 1_000_000_000.0;
+
+/// Quota reported by one response.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RateLimitHeaders {
+    pub remaining_requests: Option<u64>,
+    pub remaining_tokens: Option<u64>,
+    /// Time until the request quota refills.
+    pub reset_requests: Option<Duration>,
+    /// Time until the token quota refills.
+    pub reset_tokens: Option<Duration>,
+    pub retry_after: Option<Duration>,
+}
+
+impl RateLimitHeaders {
+    /// Parse OpenAI, Anthropic and generic rate-limit headers.
+    pub fn parse(headers: &HeaderMap) -> Option<Self> {
+        let get = |names: &[&str]| {
+            names
+                .iter()
+                .find_map(|name| headers.get(*name).and_then(|v| v.to_str().ok()))
+                .map(str::trim)
+        };
+        let count = |names: &[&str]| get(names).and_then(|v| v.parse::<u64>().ok());
+        let reset = |names: &[&str]| get(names).and_then(parse_reset);
+
+        let parsed = Self {
+            remaining_requests: count(&[
+                "x-ratelimit-remaining-requests",
+                "anthropic-ratelimit-requests-remaining",
+                "x-ratelimit-remaining",
+            ]),
+            remaining_tokens: count(&[
+                "x-ratelimit-remaining-tokens",
+                "anthropic-ratelimit-tokens-remaining",
+            ]),
+            reset_requests: reset(&[
+                "x-ratelimit-reset-requests",
+                "anthropic-ratelimit-requests-reset",
+                "x-ratelimit-reset",
+            ]),
+            reset_tokens: reset(&[
+                "x-ratelimit-reset-tokens",
+                "anthropic-ratelimit-tokens-reset",
+            ]),
+            retry_after: get(&["retry-after"]).and_then(parse_reset),
+        };
+        (parsed != Self::default()).then_some(parsed)
+    }
+}
+
+/// Parse a reset value: seconds (`"12"`), a Go-style duration (`"6m0s"`,
+/// `"20ms"`) or an RFC 3339 timestamp.
+fn parse_reset(value: &str) -> Option<Duration> {
+    if let Ok(secs) = value.parse::<f64>() {
+        if !secs.is_finite() || secs < 0.0 {
+            return None;
+        }
+        // Some providers send an epoch timestamp instead of a delay
+        if secs > EPOCH_THRESHOLD_SECS {
+            let now = chrono::Utc::now().timestamp() as f64;
+            return Some(Duration::from_secs_f64((secs - now).max(0.0)));
+        }
+        return Some(Duration::from_secs_f64(secs));
+    }
+    if let Ok(at) = chrono::DateTime::parse_from_rfc3339(value) {
+        let ms = (at.with_timezone(&chrono::Utc) - chrono::Utc::now()).num_milliseconds();
+        return Some(Duration::from_millis(ms.max(0) as u64));
+    }
+
+    let mut total = 0.0;
+    let mut number = String::new();
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() || c == '.' {
+            number.push(c);
+            continue;
+        }
+        let value: f64 = number.parse().ok()?;
+        number.clear();
+        total += match c {
+            'h' => value * 3600.0,
+            'm' if chars.peek() == Some(&'s') => {
+                chars.next();
+                value / 1000.0
+            }
+            'm' => value * 60.0,
+            's' => value,
+            _ => return None,
+        };
+    }
+    number.is_empty().then(|| Duration::from_secs_f64(total))
+}
+
+#[derive(Debug, Clone, Default)]
+struct Bucket {
+    requests: Option<u64>,
+    tokens: Option<u64>,
+    requests_reset_at: Option<Instant>,
+    tokens_reset_at: Option<Instant>,
+}
+
+impl Bucket {
+    /// Forget counts whose window has passed; the provider refilled them.
+    fn refill(&mut self, now: Instant) {
+        if self.requests_reset_at.is_some_and(|at| at <= now) {
+            self.requests = None;
+            self.requests_reset_at = None;
+        }
+        if self.tokens_reset_at.is_some_and(|at| at <= now) {
+            self.tokens = None;
+            self.tokens_reset_at = None;
+        }
+    }
+}
+
+/// Token buckets per `provider:model`, filled from response headers.
+#[derive(Debug, Default)]
+pub struct RateLimitTracker {
+    buckets: DashMap<String, Bucket>,
+}
+
+impl RateLimitTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update a bucket from response headers.
+    pub fn observe(&self, key: &str, headers: &RateLimitHeaders) {
+        let now = Instant::now();
+        let mut bucket = self.buckets.entry(key.to_string()).or_default();
+        if let Some(requests) = headers.remaining_requests {
+            bucket.requests = Some(requests);
+            bucket.requests_reset_at = headers.reset_requests.map(|d| now + d);
+        }
+        if let Some(tokens) = headers.remaining_tokens {
+            bucket.tokens = Some(tokens);
+            bucket.tokens_reset_at = headers.reset_tokens.map(|d| now + d);
+        }
+        if let Some(retry_after) = headers.retry_after {
+            bucket.requests = Some(0);
+            bucket.requests_reset_at = Some(now + retry_after);
+        }
+        Self::publish(key, &bucket);
+    }
+
+    /// Record a 429: nothing goes out until `retry_after` has passed.
+    pub fn observe_rejection(&self, key: &str, retry_after: Option<Duration>) {
+        self.observe(
+            key,
+            &RateLimitHeaders {
+                retry_after: Some(retry_after.unwrap_or(DEFAULT_RETRY_AFTER)),
+                ..RateLimitHeaders::default()
+            },
+        );
+        metrics::counter!("llm_rate_limited_total", "provider" => key.to_string()).increment(1);
+    }
+
+    /// How long a request of `estimated_tokens` must wait to avoid a 429.
+    pub fn delay(&self, key: &str, estimated_tokens: u64) -> Option<Duration> {
+        let now = Instant::now();
+        let mut bucket = self.buckets.get_mut(key)?;
+        bucket.refill(now);
+        let mut wait = None;
+        if bucket.requests == Some(0) {
+            wait = bucket.requests_reset_at;
+        }
+        if bucket.tokens.is_some_and(|t| t < estimated_tokens.max(1)) {
+            wait = wait.max(bucket.tokens_reset_at);
+        }
+        wait.map(|at| at.saturating_duration_since(now))
+    }
+
+    /// Spend quota for a request about to be sent, so concurrent requests
+    /// see it before the response headers arrive.
+    pub fn reserve(&self, key: &str, estimated_tokens: u64) {
+        if let Some(mut bucket) = self.buckets.get_mut(key) {
+            bucket.refill(Instant::now());
+            bucket.requests = bucket.requests.map(|r| r.saturating_sub(1));
+            bucket.tokens = bucket.tokens.map(|t| t.saturating_sub(estimated_tokens));
+            Self::publish(key, &bucket);
+        }
+    }
+
+    pub fn is_throttled(&self, key: &str) -> bool {
+        self.delay(key, 0).is_some_and(|d| !d.is_zero())
+    }
+
+    fn publish(key: &str, bucket: &Bucket) {
+        if let Some(requests) = bucket.requests {
+            metrics::gauge!("llm_ratelimit_remaining_requests", "provider" => key.to_string())
+                .set(requests as f64);
+        }
+        if let Some(tokens) = bucket.tokens {
+            metrics::gauge!("llm_ratelimit_remaining_tokens", "provider" => key.to_string())
+                .set(tokens as f64);
+        }
+    }
+}
+
+/// Rig HTTP backend that records rate-limit headers of every response,
+/// including rejected ones.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitedHttp {
+    inner: reqwest::Client,
+    tracker: Arc<RateLimitTracker>,
+    key: String,
+}
+
+impl RateLimitedHttp {
+    pub fn new(tracker: Arc<RateLimitTracker>, key: impl Into<String>) -> Self {
+        Self {
+            inner: reqwest::Client::new(),
+            tracker,
+            key: key.into(),
+        }
+    }
+}
+
+impl HttpClientExt for RateLimitedHttp {
+    fn send<T, U>(
+        &self,
+        req: Request<T>,
+    ) -> impl Future<Output = http_client::Result<Response<LazyBody<U>>>> + WasmCompatSend + 'static
+    where
+        T: Into<Bytes>,
+        T: WasmCompatSend,
+        U: From<Bytes>,
+        U: WasmCompatSend + 'static,
+    {
+        let (parts, body) = req.into_parts();
+        let req = self
+            .inner
+            .request(parts.method, parts.uri.to_string())
+            .headers(parts.headers)
+            .body(body.into());
+        let tracker = self.tracker.clone();
+        let key = self.key.clone();
+
+        async move {
+            let response = req
+                .send()
+                .await
+                .map_err(|e| http_client::Error::Instance(e.into()))?;
+            let headers = RateLimitHeaders::parse(response.headers());
+            if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                tracker.observe_rejection(&key, headers.and_then(|h| h.retry_after));
+            } else if let Some(headers) = headers {
+                tracker.observe(&key, &headers);
+            }
+            if !response.status().is_success() {
+                let status = response.status();
+                let text = response.text().await.unwrap_or_default();
+                return Err(http_client::Error::InvalidStatusCodeWithMessage(
+                    status, text,
+                ));
+            }
+
+            let mut res = Response::builder().status(response.status());
+            if let Some(headers) = res.headers_mut() {
+                *headers = response.headers().clone();
+            }
+            let body: LazyBody<U> = Box::pin(async {
+                let bytes = response
+                    .bytes()
+                    .await
+                    .map_err(|e| http_client::Error::Instance(e.into()))?;
+                Ok(U::from(bytes))
+            });
+            res.body(body).map_err(http_client::Error::Protocol)
+        }
+    }
+
+    fn send_multipart<U>(
+        &self,
+        req: Request<MultipartForm>,
+    ) -> impl Future<Output = http_client::Result<Response<LazyBody<U>>>> + WasmCompatSend + 'static
+    where
+        U: From<Bytes>,
+        U: WasmCompatSend + 'static,
+    {
+        self.inner.send_multipart(req)
+    }
+
+    fn send_streaming<T>(
+        &self,
+        req: Request<T>,
+    ) -> impl Future<Output = http_client::Result<StreamingResponse>> + WasmCompatSend
+    where
+        T: Into<Bytes>,
+    {
+        self.inner.send_streaming(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rig::http_client::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn test_parse_provider_headers() {
+        let openai = RateLimitHeaders::parse(&headers(&[
+            ("x-ratelimit-remaining-requests", "59"),
+            ("x-ratelimit-remaining-tokens", "149984"),
+            ("x-ratelimit-reset-requests", "1m0.5s"),
+            ("x-ratelimit-reset-tokens", "6ms"),
+        ]))
+        .unwrap();
+        assert_eq!(openai.remaining_requests, Some(59));
+        assert_eq!(openai.remaining_tokens, Some(149_984));
+        assert_eq!(openai.reset_requests, Some(Duration::from_millis(60_500)));
+        assert_eq!(openai.reset_tokens, Some(Duration::from_millis(6)));
+
+        let reset = (chrono::Utc::now() + chrono::Duration::seconds(30)).to_rfc3339();
+        let anthropic = RateLimitHeaders::parse(&headers(&[
+            ("anthropic-ratelimit-requests-remaining", "0"),
+            ("anthropic-ratelimit-requests-reset", &reset),
+            ("retry-after", "30"),
+        ]))
+        .unwrap();
+        assert_eq!(anthropic.remaining_requests, Some(0));
+        assert!(anthropic.reset_requests.unwrap() > Duration::from_secs(25));
+        assert_eq!(anthropic.retry_after, Some(Duration::from_secs(30)));
+
+        assert!(RateLimitHeaders::parse(&headers(&[("content-type", "json")])).is_none());
+    }
+
+    #[test]
+    fn test_bucket_delays_until_reset() {
+        let tracker = RateLimitTracker::new();
+        let key = "openai:gpt-4o";
+        assert_eq!(tracker.delay(key, 100), None);
+
+        tracker.observe(
+            key,
+            &RateLimitHeaders {
+                remaining_requests: Some(1),
+                remaining_tokens: Some(500),
+                reset_requests: Some(Duration::from_secs(10)),
+                reset_tokens: Some(Duration::from_secs(2)),
+                retry_after: None,
+            },
+        );
+        assert_eq!(tracker.delay(key, 100), None);
+        // Too many tokens for what is left
+        assert!(tracker.delay(key, 1_000).unwrap() <= Duration::from_secs(2));
+
+        tracker.reserve(key, 100);
+        let wait = tracker.delay(key, 100).unwrap();
+        assert!(wait > Duration::from_secs(2) && wait <= Duration::from_secs(10));
+        assert!(tracker.is_throttled(key));
+
+        tracker.observe_rejection("anthropic:claude", Some(Duration::ZERO));
+        assert!(!tracker.is_throttled("anthropic:claude"));
+    }
+}
@@ -3,9 +3,13 @@
 //! Wraps Rig's Agent for integration with our LlmClient trait.
 
 use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use multi_agent_core::{
-    traits::{ChatMessage, LlmClient, LlmResponse, LlmUsage},
+    traits::{ChatMessage, LlmClient, LlmResponse, LlmUsage, ProviderParams},
     Error, Result,
 };
 
@@ -14,6 +18,10 @@ use rig::client::{CompletionClient, EmbeddingsClient, ProviderClient};
 use rig::completion::Prompt;
 use secrecy::{ExposeSecret, Secret};
 
+use crate::endpoints::EndpointPool;
+use crate::rate_limit::{RateLimitTracker, RateLimitedHttp};
+use crate::tokenizer::TokenCounter;
+
 /// Provider type for Rig clients.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RigProvider {
@@ -21,6 +29,47 @@ pub enum RigProvider {
     Anthropic,
 }
 
+/// Longest rate-limit wait before a request fails so it can be rerouted.
+const MAX_THROTTLE_WAIT: Duration = Duration::from_secs(30);
+
+/// Request parameters accepted by OpenAI chat models.
+const OPENAI_PARAMS: &[&str] = &["top_p", "max_tokens", "stop", "seed"];
+/// Request parameters accepted by Anthropic messages.
+const ANTHROPIC_PARAMS: &[&str] = &["top_p", "top_k", "max_tokens", "stop"];
+
+impl RigProvider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::OpenAI => "openai",
+            Self::Anthropic => "anthropic",
+        }
+    }
+
+    /// Provider for a vendor name as stored on provider entries.
+    pub fn from_vendor(vendor: &str) -> Option<Self> {
+        match vendor.to_lowercase().as_str() {
+            "openai" => Some(Self::OpenAI),
+            "anthropic" => Some(Self::Anthropic),
+            _ => None,
+        }
+    }
+
+    /// Whether the provider accepts `param` for `model`.
+    pub fn supports_param(&self, model: &str, param: &str) -> bool {
+        match self {
+            Self::OpenAI if param == "reasoning_effort" => is_reasoning_model(model),
+            Self::OpenAI => OPENAI_PARAMS.contains(&param),
+            Self::Anthropic => ANTHROPIC_PARAMS.contains(&param),
+        }
+    }
+}
+
+/// OpenAI o-series models (`o1`, `o3-mini`, `o4-mini`, ...).
+fn is_reasoning_model(model: &str) -> bool {
+    let mut chars = model.chars();
+    chars.next() == Some('o') && chars.next().is_some_and(|c| c.is_ascii_digit())
+}
+
 /// Configuration for Rig client.
 #[derive(Debug, Clone)]
 pub struct RigConfig {
@@ -36,6 +85,12 @@ pub struct RigConfig {
     pub max_tokens: Option<u32>,
     /// API key override.
     pub api_key: Option<Secret<String>>,
+    /// Provider-specific parameters sent with every request.
+    pub params: ProviderParams,
+    /// Regional endpoints; when unset the provider's default URL is used.
+    pub endpoints: Option<Arc<EndpointPool>>,
+    /// Rate-limit buckets, shareable across clients and the registry.
+    pub rate_limits: Arc<RateLimitTracker>,
 }
 
 impl Default for RigConfig {
@@ -47,6 +102,9 @@ impl Default for RigConfig {
             temperature: Some(0.7),
             max_tokens: Some(4096),
             api_key: None,
+            params: ProviderParams::new(),
+            endpoints: None,
+            rate_limits: Arc::new(RateLimitTracker::new()),
         }
     }
 }
@@ -87,6 +145,30 @@ impl RigConfig {
         self.temperature = Some(temp);
         self
     }
+
+    /// Set max tokens.
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Route requests through regional endpoints.
+    pub fn with_endpoints(mut self, endpoints: Arc<EndpointPool>) -> Self {
+        self.endpoints = Some(endpoints);
+        self
+    }
+
+    /// Share rate-limit state with other clients and the provider registry.
+    pub fn with_rate_limits(mut self, tracker: Arc<RateLimitTracker>) -> Self {
+        self.rate_limits = tracker;
+        self
+    }
+
+    /// Set a provider-specific parameter (e.g. `top_p`, `seed`).
+    pub fn with_param(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.params.insert(key.into(), value.into());
+        self
+    }
 }
 
 /// Rig-based LLM client.
@@ -95,12 +177,23 @@ impl RigConfig {
 /// providing a unified interface for LLM calls across the system.
 pub struct RigLlmClient {
     config: RigConfig,
+    /// Unsupported parameters already warned about.
+    dropped_params: Mutex<HashSet<String>>,
 }
 
 impl RigLlmClient {
     /// Create a new Rig client with the given configuration.
     pub fn new(config: RigConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            dropped_params: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Share rate-limit state with other clients and the provider registry.
+    pub fn with_rate_limits(mut self, tracker: Arc<RateLimitTracker>) -> Self {
+        self.config.rate_limits = tracker;
+        self
     }
 
     /// Create a client for OpenAI GPT-4o.
@@ -150,22 +243,106 @@ impl RigLlmClient {
         prompt
     }
 
+    /// Rig does not surface provider usage, so count it with the model's tokenizer.
+    fn estimate_usage(&self, prompt: &str, response: &str) -> LlmUsage {
+        let counter = TokenCounter::for_model(&self.config.model);
+        let prompt_tokens = counter.count(prompt) as u64;
+        let completion_tokens = counter.count(response) as u64;
+        LlmUsage {
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens + completion_tokens,
+        }
+    }
+
+    /// Merge request parameters over the configured ones and keep those the
+    /// provider accepts. Each dropped parameter is logged once per client.
+    fn resolve_params(&self, request: &ProviderParams) -> ProviderParams {
+        let RigConfig {
+            provider, model, ..
+        } = &self.config;
+        let mut params = self.config.params.clone();
+        params.extend(request.clone());
+        params.retain(|key, _| {
+            let supported = provider.supports_param(model, key);
+            if !supported && self.dropped_params.lock().unwrap().insert(key.clone()) {
+                tracing::warn!(
+                    provider = ?provider,
+                    model = %model,
+                    param = %key,
+                    "Dropping parameter not supported by provider"
+                );
+            }
+            supported
+        });
+
+        if let Some(max_tokens) = self.config.max_tokens {
+            params
+                .entry("max_tokens")
+                .or_insert_with(|| Value::from(max_tokens));
+        }
+        match provider {
+            // o-series models renamed the completion limit
+            RigProvider::OpenAI if is_reasoning_model(model) => {
+                if let Some(max_tokens) = params.remove("max_tokens") {
+                    params.insert("max_completion_tokens".to_string(), max_tokens);
+                }
+            }
+            RigProvider::Anthropic => {
+                if let Some(stop) = params.remove("stop") {
+                    let stop = match stop {
+                        Value::String(s) => Value::Array(vec![Value::String(s)]),
+                        other => other,
+                    };
+                    params.insert("stop_sequences".to_string(), stop);
+                }
+            }
+            RigProvider::OpenAI => {}
+        }
+        params
+    }
+
     /// Call OpenAI via Rig.
-    async fn call_openai(&self, prompt: &str) -> Result<LlmResponse> {
+    async fn call_openai(
+        &self,
+        prompt: &str,
+        params: ProviderParams,
+        base_url: Option<&str>,
+    ) -> Result<LlmResponse> {
         use rig::providers::openai;
 
-        let client = if let Some(key) = &self.config.api_key {
-            openai::Client::new(key.expose_secret())
-        } else {
-            Ok(openai::Client::from_env())
+        let mut client_builder = openai::Client::<RateLimitedHttp>::builder()
+            .api_key(
+                self.api_key(&self.config.api_key, "OPENAI_API_KEY")?
+                    .as_str(),
+            )
+            .http_client(self.http_client());
+        if let Some(url) = base_url
+            .map(String::from)
+            .or_else(|| std::env::var("OPENAI_BASE_URL").ok())
+        {
+            client_builder = client_builder.base_url(url);
         }
-        .map_err(|e| Error::ModelProvider(format!("OpenAI client error: {}", e)))?;
+        let client = client_builder
+            .build()
+            .map_err(|e| Error::ModelProvider(format!("OpenAI client error: {}", e)))?;
 
         let mut agent_builder = client.agent(&self.config.model);
 
         if let Some(ref system) = self.config.system_prompt {
             agent_builder = agent_builder.preamble(system);
         }
+        // o-series models only accept the default temperature
+        if let Some(temperature) = self.config.temperature {
+            if !is_reasoning_model(&self.config.model) {
+                agent_builder = agent_builder.temperature(temperature as f64);
+            }
+        }
+        // Rig's OpenAI request has no token limit field, so everything goes
+        // into the flattened extra parameters
+        if !params.is_empty() {
+            agent_builder = agent_builder.additional_params(Value::Object(params));
+        }
 
         let agent = agent_builder.build();
 
@@ -177,31 +354,45 @@ impl RigLlmClient {
         Ok(LlmResponse {
             content: response.clone(),
             finish_reason: "stop".to_string(),
-            usage: LlmUsage {
-                prompt_tokens: (prompt.len() / 4) as u64,
-                completion_tokens: (response.len() / 4) as u64,
-                total_tokens: ((prompt.len() + response.len()) / 4) as u64,
-            },
+            usage: self.estimate_usage(prompt, &response),
             tool_calls: None,
         })
     }
 
     /// Call Anthropic via Rig.
-    async fn call_anthropic(&self, prompt: &str) -> Result<LlmResponse> {
+    async fn call_anthropic(
+        &self,
+        prompt: &str,
+        mut params: ProviderParams,
+        base_url: Option<&str>,
+    ) -> Result<LlmResponse> {
         use rig::providers::anthropic;
 
-        let client = if let Some(key) = &self.config.api_key {
-            anthropic::Client::new(key.expose_secret())
-        } else {
-            Ok(anthropic::Client::from_env())
+        let mut client_builder = anthropic::Client::<RateLimitedHttp>::builder()
+            .api_key(self.api_key(&self.config.api_key, "ANTHROPIC_API_KEY")?)
+            .http_client(self.http_client());
+        if let Some(url) = base_url {
+            client_builder = client_builder.base_url(url);
         }
-        .map_err(|e| Error::ModelProvider(format!("Anthropic client error: {}", e)))?;
+        let client = client_builder
+            .build()
+            .map_err(|e| Error::ModelProvider(format!("Anthropic client error: {}", e)))?;
 
         let mut agent_builder = client.agent(&self.config.model);
 
         if let Some(ref system) = self.config.system_prompt {
             agent_builder = agent_builder.preamble(system);
         }
+        if let Some(temperature) = self.config.temperature {
+            agent_builder = agent_builder.temperature(temperature as f64);
+        }
+        // max_tokens is a first-class field of the Messages API
+        if let Some(max_tokens) = params.remove("max_tokens").and_then(|v| v.as_u64()) {
+            agent_builder = agent_builder.max_tokens(max_tokens);
+        }
+        if !params.is_empty() {
+            agent_builder = agent_builder.additional_params(Value::Object(params));
+        }
 
         let agent = agent_builder.build();
 
@@ -213,19 +404,16 @@ impl RigLlmClient {
         Ok(LlmResponse {
             content: response.clone(),
             finish_reason: "stop".to_string(),
-            usage: LlmUsage {
-                prompt_tokens: (prompt.len() / 4) as u64,
-                completion_tokens: (response.len() / 4) as u64,
-                total_tokens: ((prompt.len() + response.len()) / 4) as u64,
-            },
+            usage: self.estimate_usage(prompt, &response),
             tool_calls: None,
         })
     }
-}
 
-#[async_trait]
-impl LlmClient for RigLlmClient {
-    async fn complete(&self, prompt: &str) -> Result<LlmResponse> {
+    async fn complete_with_params(
+        &self,
+        prompt: &str,
+        params: &ProviderParams,
+    ) -> Result<LlmResponse> {
         tracing::debug!(
             provider = ?self.config.provider,
             model = %self.config.model,
@@ -233,15 +421,118 @@ impl LlmClient for RigLlmClient {
             "Calling LLM"
         );
 
+        let params = self.resolve_params(params);
+        self.throttle(prompt).await?;
+        let Some(pool) = &self.config.endpoints else {
+            return self.call(prompt, params, None).await;
+        };
+
+        // Fastest healthy region first, failing over on errors
+        let mut last_error = None;
+        for url in pool.ordered() {
+            let started = Instant::now();
+            match self.call(prompt, params.clone(), Some(&url)).await {
+                Ok(response) => {
+                    pool.record_success(&url, None);
+                    tracing::debug!(endpoint = %url, elapsed_ms = started.elapsed().as_millis() as u64, "LLM call succeeded");
+                    return Ok(response);
+                }
+                Err(e) => {
+                    tracing::warn!(endpoint = %url, error = %e, "Endpoint failed, failing over");
+                    pool.record_failure(&url, &e.to_string());
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error
+            .unwrap_or_else(|| Error::ModelProvider("No endpoints configured".to_string())))
+    }
+
+    async fn call(
+        &self,
+        prompt: &str,
+        params: ProviderParams,
+        base_url: Option<&str>,
+    ) -> Result<LlmResponse> {
         match self.config.provider {
-            RigProvider::OpenAI => self.call_openai(prompt).await,
-            RigProvider::Anthropic => self.call_anthropic(prompt).await,
+            RigProvider::OpenAI => self.call_openai(prompt, params, base_url).await,
+            RigProvider::Anthropic => self.call_anthropic(prompt, params, base_url).await,
+        }
+    }
+
+    /// `provider:model`, the key of this client's rate-limit bucket.
+    fn rate_limit_key(&self) -> String {
+        self.model_id()
+    }
+
+    fn http_client(&self) -> RateLimitedHttp {
+        RateLimitedHttp::new(self.config.rate_limits.clone(), self.rate_limit_key())
+    }
+
+    /// Wait out the provider's rate limit, or fail fast so the caller can
+    /// reroute when the wait would be too long.
+    async fn throttle(&self, prompt: &str) -> Result<()> {
+        let key = self.rate_limit_key();
+        let estimated = TokenCounter::for_model(&self.config.model).count(prompt) as u64;
+        if let Some(wait) = self.config.rate_limits.delay(&key, estimated) {
+            if wait > MAX_THROTTLE_WAIT {
+                return Err(Error::Throttled(format!(
+                    "{} rate limit resets in {}s",
+                    key,
+                    wait.as_secs()
+                )));
+            }
+            tracing::info!(provider = %key, wait_ms = wait.as_millis() as u64, "Delaying request for rate limit");
+            tokio::time::sleep(wait).await;
         }
+        self.config.rate_limits.reserve(&key, estimated);
+        Ok(())
+    }
+
+    /// Configured API key, or the provider's environment variable.
+    fn api_key(&self, key: &Option<Secret<String>>, env: &str) -> Result<String> {
+        match key {
+            Some(key) => Ok(key.expose_secret().clone()),
+            None => {
+                std::env::var(env).map_err(|_| Error::ModelProvider(format!("{} not set", env)))
+            }
+        }
+    }
+
+    pub fn provider(&self) -> RigProvider {
+        self.config.provider
+    }
+
+    /// `provider:model`, the key used for pricing and rate limits.
+    pub fn model_id(&self) -> String {
+        format!("{}:{}", self.config.provider.as_str(), self.config.model)
+    }
+
+    /// Regional endpoints this client routes through.
+    pub fn endpoints(&self) -> Option<Arc<EndpointPool>> {
+        self.config.endpoints.clone()
+    }
+}
+
+#[async_trait]
+impl LlmClient for RigLlmClient {
+    async fn complete(&self, prompt: &str) -> Result<LlmResponse> {
+        self.complete_with_params(prompt, &ProviderParams::new())
+            .await
     }
 
     async fn chat(&self, messages: &[ChatMessage]) -> Result<LlmResponse> {
+        self.chat_with_params(messages, &ProviderParams::new())
+            .await
+    }
+
+    async fn chat_with_params(
+        &self,
+        messages: &[ChatMessage],
+        params: &ProviderParams,
+    ) -> Result<LlmResponse> {
         let prompt = self.build_prompt(messages);
-        self.complete(&prompt).await
+        self.complete_with_params(&prompt, params).await
     }
 
     async fn embed(&self, text: &str) -> Result<Vec<f32>> {
@@ -333,4 +624,40 @@ mod tests {
         assert!(prompt.contains("System: You are helpful"));
         assert!(prompt.contains("User: Hello"));
     }
+
+    #[test]
+    fn test_resolve_params_per_provider() {
+        let mut request = ProviderParams::new();
+        request.insert("reasoning_effort".to_string(), "high".into());
+        request.insert("stop".to_string(), "END".into());
+
+        let gpt = RigLlmClient::new(
+            RigConfig::openai("gpt-4o")
+                .with_param("seed", 7)
+                .with_param("top_p", 0.9),
+        );
+        let params = gpt.resolve_params(&request);
+        assert_eq!(params["seed"], 7);
+        assert_eq!(params["stop"], "END");
+        assert_eq!(params["max_tokens"], 4096);
+        assert!(!params.contains_key("reasoning_effort"));
+
+        let o3 = RigLlmClient::new(RigConfig::openai("o3-mini").with_max_tokens(512));
+        let params = o3.resolve_params(&request);
+        assert_eq!(params["reasoning_effort"], "high");
+        assert_eq!(params["max_completion_tokens"], 512);
+        assert!(!params.contains_key("max_tokens"));
+
+        let claude = RigLlmClient::new(RigConfig::anthropic("claude-3-5-haiku-latest"));
+        request.insert("seed".to_string(), 1.into());
+        let params = claude.resolve_params(&request);
+        assert_eq!(params["stop_sequences"], serde_json::json!(["END"]));
+        assert!(!params.contains_key("seed"));
+        assert!(!params.contains_key("reasoning_effort"));
+        assert_eq!(
+            claude.dropped_params.lock().unwrap().len(),
+            2,
+            "each dropped parameter is remembered"
+        );
+    }
 }
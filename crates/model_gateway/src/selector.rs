@@ -1,15 +1,37 @@
 //! Adaptive model selector.
+//!
+//! Besides plain tier selection, the selector routes each ReAct iteration by
+//! its [`TaskClass`]: candidates are tried cheapest first, and a model is
+//! chosen once it has a proven success rate for that class. Models without
+//! enough history are only trusted if their quality score meets the class
+//! default; every few decisions a cheaper, unproven model is tried instead so
+//! it can earn a track record.
+//!
+//! Providers whose `provider.<key>` feature flag is off for the current
+//! request are skipped, so new providers can be rolled out per workspace.
 
 use async_trait::async_trait;
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use multi_agent_core::{
-    traits::{LlmClient, ModelSelector},
-    types::ModelTier,
+    traits::{ChatMessage, LlmClient, ModelSelector},
+    types::{flags, ModelTier, RequestContext},
     Error, Result,
 };
 
+use crate::pricing::PricingRegistry;
 use crate::providers::ProviderRegistry;
+use crate::tokenizer::TokenCounter;
+
+/// Outcomes needed before a model's history overrides its quality score.
+const MIN_SAMPLES: u64 = 5;
+/// Success rate a model must hold to keep serving a class.
+const TARGET_SUCCESS_RATE: f64 = 0.8;
+/// Every Nth decision per class explores a cheaper unproven model.
+const DEFAULT_EXPLORATION_INTERVAL: u64 = 10;
 
 /// Selection strategy.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -53,6 +75,130 @@ impl Default for TierMapping {
     }
 }
 
+/// Kind of work a ReAct iteration does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskClass {
+    Planning,
+    Coding,
+    Summarization,
+    Extraction,
+    General,
+}
+
+impl TaskClass {
+    /// Classify the next iteration from the conversation so far.
+    ///
+    /// The first turn of a mission is planning; later turns are classified by
+    /// keywords in the latest user message (usually an observation), falling
+    /// back to the goal in the system prompt.
+    pub fn classify(messages: &[ChatMessage]) -> Self {
+        if !messages.iter().any(|m| m.role == "assistant") {
+            return Self::Planning;
+        }
+        let latest = messages
+            .iter()
+            .rev()
+            .find(|m| m.role == "user")
+            .map(|m| Self::from_text(&m.content))
+            .unwrap_or(Self::General);
+        if latest != Self::General {
+            return latest;
+        }
+        messages
+            .iter()
+            .find(|m| m.role == "system")
+            .map(|m| Self::from_text(&m.content))
+            .unwrap_or(Self::General)
+    }
+
+    fn from_text(text: &str) -> Self {
+        let text = text.to_lowercase();
+        let has = |words: &[&str]| words.iter().any(|w| text.contains(w));
+        if has(&["summar", "tl;dr", "condense", "recap", "digest"]) {
+            Self::Summarization
+        } else if has(&["extract", "parse", "fields", "table", "csv", "structured"]) {
+            Self::Extraction
+        } else if has(&[
+            "code",
+            "function",
+            "compile",
+            "bug",
+            "refactor",
+            "unit test",
+            "patch",
+            "stack trace",
+            ".rs",
+            ".py",
+            ".ts",
+            ".js",
+        ]) {
+            Self::Coding
+        } else if has(&["plan", "strategy", "steps", "break down", "roadmap"]) {
+            Self::Planning
+        } else {
+            Self::General
+        }
+    }
+
+    /// Quality score (see [`crate::ModelPricing`]) trusted without history.
+    pub fn min_quality(&self) -> u8 {
+        match self {
+            Self::Planning | Self::Coding => 9,
+            Self::Summarization | Self::Extraction | Self::General => 7,
+        }
+    }
+}
+
+impl std::fmt::Display for TaskClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Planning => "planning",
+            Self::Coding => "coding",
+            Self::Summarization => "summarization",
+            Self::Extraction => "extraction",
+            Self::General => "general",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Why a model was chosen for an iteration.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelDecision {
+    pub class: TaskClass,
+    /// Provider key, e.g. `openai:gpt-4o-mini`.
+    pub model: String,
+    /// `proven`, `default_quality`, `exploring` or `fallback`.
+    pub reason: String,
+    /// Success rate of the model for this class, if it has history.
+    pub success_rate: Option<f64>,
+    pub samples: u64,
+}
+
+impl ModelDecision {
+    /// Split the model key into provider and model names.
+    pub fn provider_and_model(&self) -> (&str, &str) {
+        self.model.split_once(':').unwrap_or(("", &self.model))
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Outcomes {
+    successes: u64,
+    failures: u64,
+}
+
+impl Outcomes {
+    fn samples(&self) -> u64 {
+        self.successes + self.failures
+    }
+
+    fn success_rate(&self) -> Option<f64> {
+        (self.samples() > 0).then(|| self.successes as f64 / self.samples() as f64)
+    }
+}
+
 /// Adaptive model selector with fallback support.
 pub struct AdaptiveModelSelector {
     /// Provider registry.
@@ -61,6 +207,14 @@ pub struct AdaptiveModelSelector {
     strategy: SelectionStrategy,
     /// Tier mapping.
     tier_mapping: TierMapping,
+    /// Model prices and quality scores.
+    pricing: PricingRegistry,
+    /// Outcomes per (class, model key).
+    outcomes: DashMap<(TaskClass, String), Outcomes>,
+    /// Decisions made per class, for exploration.
+    decisions: DashMap<TaskClass, Arc<AtomicU64>>,
+    /// Every Nth decision explores; 0 disables exploration.
+    exploration_interval: u64,
 }
 
 impl AdaptiveModelSelector {
@@ -70,9 +224,25 @@ impl AdaptiveModelSelector {
             registry,
             strategy: SelectionStrategy::default(),
             tier_mapping: TierMapping::default(),
+            pricing: PricingRegistry::with_defaults(),
+            outcomes: DashMap::new(),
+            decisions: DashMap::new(),
+            exploration_interval: DEFAULT_EXPLORATION_INTERVAL,
         }
     }
 
+    /// Set model prices and quality scores.
+    pub fn with_pricing(mut self, pricing: PricingRegistry) -> Self {
+        self.pricing = pricing;
+        self
+    }
+
+    /// Explore a cheaper unproven model every `interval` decisions (0 = never).
+    pub fn with_exploration_interval(mut self, interval: u64) -> Self {
+        self.exploration_interval = interval;
+        self
+    }
+
     /// Set the selection strategy.
     pub fn with_strategy(mut self, strategy: SelectionStrategy) -> Self {
         self.strategy = strategy;
@@ -93,13 +263,156 @@ impl AdaptiveModelSelector {
             ModelTier::Premium => &self.tier_mapping.premium,
         }
     }
+
+    /// All tier models, cheapest first. Unpriced models keep tier order
+    /// after the priced ones.
+    fn candidates_by_cost(&self) -> Vec<String> {
+        let mut models: Vec<String> = Vec::new();
+        for key in self
+            .tier_mapping
+            .fast
+            .iter()
+            .chain(&self.tier_mapping.standard)
+            .chain(&self.tier_mapping.premium)
+        {
+            if !models.contains(key) {
+                models.push(key.clone());
+            }
+        }
+        let cost = |key: &String| {
+            self.pricing
+                .get(key)
+                .map(|p| p.input_cost_per_1k + p.output_cost_per_1k)
+                .unwrap_or(f64::MAX)
+        };
+        models.sort_by(|a, b| cost(a).total_cmp(&cost(b)));
+        models
+    }
+
+    /// Healthy providers not switched off for the current request.
+    fn available(&self) -> Vec<String> {
+        let mut healthy = self.registry.get_healthy();
+        healthy.retain(|key| !RequestContext::flag_off(&flags::provider(key)));
+        healthy
+    }
+
+    fn client_for(&self, key: &str) -> Option<Box<dyn LlmClient>> {
+        self.registry.get_raw(key).map(|entry| {
+            Box::new(crate::providers::CircuitBreakerClient::new(
+                entry,
+                self.registry.clone(),
+                key.to_string(),
+            )) as Box<dyn LlmClient>
+        })
+    }
+
+    /// Choose the cheapest model that historically succeeds for `class`.
+    pub fn select_for_class(
+        &self,
+        class: TaskClass,
+    ) -> Result<(Box<dyn LlmClient>, ModelDecision)> {
+        let healthy = self.available();
+        let decision_no = self
+            .decisions
+            .entry(class)
+            .or_default()
+            .fetch_add(1, Ordering::Relaxed)
+            + 1;
+        let explore =
+            self.exploration_interval > 0 && decision_no.is_multiple_of(self.exploration_interval);
+
+        let mut chosen: Option<(String, &'static str, Outcomes)> = None;
+        let mut exploration: Option<(String, Outcomes)> = None;
+        for key in self.candidates_by_cost() {
+            if !healthy.contains(&key) {
+                continue;
+            }
+            let outcomes = self.outcomes(class, &key);
+            if outcomes.samples() >= MIN_SAMPLES {
+                if outcomes.success_rate().unwrap_or(0.0) >= TARGET_SUCCESS_RATE {
+                    chosen = Some((key, "proven", outcomes));
+                    break;
+                }
+                continue;
+            }
+            let quality = self.pricing.get(&key).map(|p| p.quality_score).unwrap_or(0);
+            if quality >= class.min_quality() {
+                chosen = Some((key, "default_quality", outcomes));
+                break;
+            }
+            if exploration.is_none() {
+                exploration = Some((key, outcomes));
+            }
+        }
+
+        let (key, reason, outcomes) = match (explore, exploration, chosen) {
+            (true, Some((key, outcomes)), _) => (key, "exploring", outcomes),
+            (_, _, Some(chosen)) => chosen,
+            (_, exploration, None) => {
+                // Nothing qualifies: the cheapest unproven model, else any healthy one
+                let key = exploration
+                    .map(|(key, _)| key)
+                    .or_else(|| healthy.first().cloned())
+                    .ok_or(Error::AllProvidersUnavailable)?;
+                let outcomes = self.outcomes(class, &key);
+                (key, "fallback", outcomes)
+            }
+        };
+
+        let client = self
+            .client_for(&key)
+            .ok_or(Error::AllProvidersUnavailable)?;
+        let decision = ModelDecision {
+            class,
+            model: key,
+            reason: reason.to_string(),
+            success_rate: outcomes.success_rate(),
+            samples: outcomes.samples(),
+        };
+        tracing::info!(
+            class = %decision.class,
+            model = %decision.model,
+            reason = %decision.reason,
+            "Selected model for task class"
+        );
+        Ok((client, decision))
+    }
+
+    /// Whether any registered provider can take a request right now.
+    pub fn has_available(&self) -> bool {
+        !self.available().is_empty()
+    }
+
+    /// Token counter for a provider key, honoring its configured context window.
+    pub fn token_counter(&self, key: &str) -> TokenCounter {
+        self.registry
+            .token_counter(key)
+            .unwrap_or_else(|| TokenCounter::for_model(key))
+    }
+
+    /// Record whether `model` handled an iteration of `class` well.
+    pub fn record_outcome(&self, class: TaskClass, model: &str, success: bool) {
+        let mut entry = self.outcomes.entry((class, model.to_string())).or_default();
+        if success {
+            entry.successes += 1;
+        } else {
+            entry.failures += 1;
+        }
+    }
+
+    fn outcomes(&self, class: TaskClass, model: &str) -> Outcomes {
+        self.outcomes
+            .get(&(class, model.to_string()))
+            .map(|o| *o)
+            .unwrap_or_default()
+    }
 }
 
 #[async_trait]
 impl ModelSelector for AdaptiveModelSelector {
     async fn select(&self, tier: ModelTier) -> Result<Box<dyn LlmClient>> {
         let tier_models = self.get_tier_models(tier);
-        let healthy = self.registry.get_healthy();
+        let healthy = self.available();
 
         // Find first healthy model from tier priority list
         for model_key in tier_models {
@@ -168,6 +481,114 @@ mod tests {
         assert!(matches!(result, Err(Error::AllProvidersUnavailable)));
     }
 
+    #[tokio::test]
+    async fn test_skips_providers_flagged_off() {
+        let registry = Arc::new(ProviderRegistry::new());
+        registry.register("openai", "gpt-4o-mini", Arc::new(MockLlmClient::new("ok")));
+        registry.register("openai", "gpt-4o", Arc::new(MockLlmClient::new("ok")));
+        let selector = AdaptiveModelSelector::new(registry).with_exploration_interval(0);
+
+        let context = RequestContext {
+            feature_flags: [(flags::provider("openai:gpt-4o-mini"), false)]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+        let (_, decision) = context
+            .scope(async { selector.select_for_class(TaskClass::Summarization) })
+            .await
+            .unwrap();
+        assert_eq!(decision.model, "openai:gpt-4o");
+
+        let (_, decision) = selector.select_for_class(TaskClass::Summarization).unwrap();
+        assert_eq!(decision.model, "openai:gpt-4o-mini");
+    }
+
+    fn message(role: &str, content: &str) -> ChatMessage {
+        ChatMessage {
+            role: role.to_string(),
+            content: content.to_string(),
+            tool_calls: None,
+        }
+    }
+
+    #[test]
+    fn test_classify_iterations() {
+        let system = message("system", "GOAL: Fix the failing build");
+        assert_eq!(
+            TaskClass::classify(std::slice::from_ref(&system)),
+            TaskClass::Planning
+        );
+
+        let history = vec![
+            system,
+            message("assistant", "ACTION: read_file"),
+            message("user", "OBSERVATION: Please summarize the report"),
+        ];
+        assert_eq!(TaskClass::classify(&history), TaskClass::Summarization);
+
+        // Falls back to the goal when the observation says nothing
+        let history = vec![
+            message("system", "GOAL: refactor the login function"),
+            message("assistant", "THOUGHT: ok"),
+            message("user", "OBSERVATION: done"),
+        ];
+        assert_eq!(TaskClass::classify(&history), TaskClass::Coding);
+    }
+
+    #[test]
+    fn test_routes_to_cheapest_proven_model() {
+        let registry = Arc::new(ProviderRegistry::new());
+        for (provider, model) in [
+            ("openai", "gpt-4o-mini"),
+            ("anthropic", "claude-3-5-sonnet-20241022"),
+            ("openai", "gpt-4o"),
+        ] {
+            registry.register(provider, model, Arc::new(MockLlmClient::new("ok")));
+        }
+        let selector = AdaptiveModelSelector::new(registry).with_exploration_interval(0);
+
+        // Without history, coding needs a high-quality model: sonnet is the
+        // cheapest with quality 10 in the default price list.
+        let (_, decision) = selector.select_for_class(TaskClass::Coding).unwrap();
+        assert_eq!(decision.model, "anthropic:claude-3-5-sonnet-20241022");
+        assert_eq!(decision.reason, "default_quality");
+
+        // Summaries are fine on the cheap model
+        let (_, decision) = selector.select_for_class(TaskClass::Summarization).unwrap();
+        assert_eq!(decision.model, "openai:gpt-4o-mini");
+
+        // Once the cheap model proves itself at coding it takes over...
+        for _ in 0..MIN_SAMPLES {
+            selector.record_outcome(TaskClass::Coding, "openai:gpt-4o-mini", true);
+        }
+        let (_, decision) = selector.select_for_class(TaskClass::Coding).unwrap();
+        assert_eq!(decision.model, "openai:gpt-4o-mini");
+        assert_eq!(decision.reason, "proven");
+        assert_eq!(decision.success_rate, Some(1.0));
+
+        // ...and loses it again when it starts failing
+        for _ in 0..MIN_SAMPLES * 2 {
+            selector.record_outcome(TaskClass::Coding, "openai:gpt-4o-mini", false);
+        }
+        let (_, decision) = selector.select_for_class(TaskClass::Coding).unwrap();
+        assert_eq!(decision.model, "anthropic:claude-3-5-sonnet-20241022");
+    }
+
+    #[test]
+    fn test_exploration_tries_cheaper_model() {
+        let registry = Arc::new(ProviderRegistry::new());
+        registry.register("openai", "gpt-4o-mini", Arc::new(MockLlmClient::new("ok")));
+        registry.register("openai", "gpt-4o", Arc::new(MockLlmClient::new("ok")));
+        let selector = AdaptiveModelSelector::new(registry).with_exploration_interval(2);
+
+        let (_, first) = selector.select_for_class(TaskClass::Planning).unwrap();
+        let (_, second) = selector.select_for_class(TaskClass::Planning).unwrap();
+        assert_eq!(first.model, "openai:gpt-4o");
+        assert_eq!(second.model, "openai:gpt-4o-mini");
+        assert_eq!(second.reason, "exploring");
+    }
+
     #[tokio::test]
     async fn test_report_failure() {
         let registry = Arc::new(ProviderRegistry::new());
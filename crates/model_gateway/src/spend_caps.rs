@@ -0,0 +1,254 @@
+//! Monthly spend caps per provider.
+//!
+//! Each capped provider entry maps to a pricing key (`vendor:model`). Once
+//! its month-to-date spend in the [`UsageLedger`] passes the cap, the model is
+//! blocked for every workspace except the privileged ones until the month
+//! rolls over. The workspace of the current request is carried in a task-local
+//! set with [`in_workspace`].
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::future::Future;
+use std::sync::Arc;
+
+use multi_agent_core::{
+    traits::{ChatMessage, LlmClient, LlmResponse, ProviderParams},
+    Error, Result,
+};
+
+use crate::usage::UsageLedger;
+
+tokio::task_local! {
+    static CURRENT_WORKSPACE: Option<String>;
+}
+
+/// Run `fut` on behalf of `workspace`; spend caps are checked against it.
+pub async fn in_workspace<F: Future>(workspace: Option<String>, fut: F) -> F::Output {
+    CURRENT_WORKSPACE.scope(workspace, fut).await
+}
+
+/// Workspace of the current execution context, if any.
+pub fn current_workspace() -> Option<String> {
+    CURRENT_WORKSPACE.try_with(|w| w.clone()).ok().flatten()
+}
+
+/// A monthly spend cap on one provider entry.
+#[derive(Debug, Clone, Serialize)]
+pub struct SpendCap {
+    pub provider_id: String,
+    /// Pricing key, e.g. `openai:gpt-4o`.
+    pub model: String,
+    pub monthly_limit_usd: f64,
+}
+
+/// A provider crossing its cap, or being released at the start of a month.
+#[derive(Debug, Clone, Serialize)]
+pub struct SpendCapChange {
+    pub provider_id: String,
+    pub model: String,
+    pub spent_usd: f64,
+    pub limit_usd: f64,
+    /// `true` when the provider was just blocked, `false` when released.
+    pub exceeded: bool,
+}
+
+/// Blocks models whose provider went over its monthly cap.
+pub struct SpendGuard {
+    ledger: Arc<UsageLedger>,
+    privileged: HashSet<String>,
+    /// Blocked model -> the cap that blocked it.
+    blocked: DashMap<String, SpendCap>,
+}
+
+impl SpendGuard {
+    pub fn new(ledger: Arc<UsageLedger>) -> Self {
+        Self {
+            ledger,
+            privileged: HashSet::new(),
+            blocked: DashMap::new(),
+        }
+    }
+
+    /// Workspaces that keep access to capped providers.
+    pub fn with_privileged_workspaces(
+        mut self,
+        workspaces: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.privileged = workspaces.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Compare month-to-date spend with `caps` and block or release models.
+    ///
+    /// Returns only the providers whose state changed.
+    pub fn evaluate(&self, caps: &[SpendCap]) -> Vec<SpendCapChange> {
+        let mut changes = Vec::new();
+        for cap in caps {
+            let spent = self.ledger.month_to_date(&cap.model);
+            let over = spent >= cap.monthly_limit_usd;
+            let blocked = self
+                .blocked
+                .get(&cap.model)
+                .is_some_and(|b| b.provider_id == cap.provider_id);
+            if over && !blocked {
+                tracing::warn!(provider = %cap.provider_id, model = %cap.model, spent, limit = cap.monthly_limit_usd, "Monthly spend cap reached");
+                self.blocked.insert(cap.model.clone(), cap.clone());
+            } else if !over && blocked {
+                self.blocked.remove(&cap.model);
+            } else {
+                continue;
+            }
+            changes.push(SpendCapChange {
+                provider_id: cap.provider_id.clone(),
+                model: cap.model.clone(),
+                spent_usd: spent,
+                limit_usd: cap.monthly_limit_usd,
+                exceeded: over,
+            });
+        }
+        // Removed caps no longer block
+        let capped: HashSet<&str> = caps.iter().map(|c| c.provider_id.as_str()).collect();
+        let removed: Vec<SpendCap> = self
+            .blocked
+            .iter()
+            .filter(|b| !capped.contains(b.provider_id.as_str()))
+            .map(|b| b.value().clone())
+            .collect();
+        for cap in removed {
+            self.blocked.remove(&cap.model);
+            changes.push(SpendCapChange {
+                spent_usd: self.ledger.month_to_date(&cap.model),
+                limit_usd: cap.monthly_limit_usd,
+                provider_id: cap.provider_id,
+                model: cap.model,
+                exceeded: false,
+            });
+        }
+        changes
+    }
+
+    pub fn is_blocked(&self, model: &str) -> bool {
+        self.blocked.contains_key(model)
+    }
+
+    /// Whether `workspace` may use `model`.
+    pub fn allows(&self, model: &str, workspace: Option<&str>) -> bool {
+        !self.is_blocked(model) || workspace.is_some_and(|w| self.privileged.contains(w))
+    }
+
+    /// Fail if the current workspace may not use `model`.
+    pub fn check(&self, model: &str) -> Result<()> {
+        if self.allows(model, current_workspace().as_deref()) {
+            return Ok(());
+        }
+        let limit_usd = self
+            .blocked
+            .get(model)
+            .map(|cap| cap.monthly_limit_usd)
+            .unwrap_or_default();
+        metrics::counter!("llm_spend_cap_rejections_total", "model" => model.to_string())
+            .increment(1);
+        Err(Error::SpendCapExceeded {
+            model: model.to_string(),
+            limit_usd,
+        })
+    }
+}
+
+/// LLM client that refuses calls once its model is over the spend cap.
+pub struct SpendCappedLlmClient {
+    inner: Arc<dyn LlmClient>,
+    guard: Arc<SpendGuard>,
+    model: String,
+}
+
+impl SpendCappedLlmClient {
+    pub fn new(
+        inner: Arc<dyn LlmClient>,
+        guard: Arc<SpendGuard>,
+        model: impl Into<String>,
+    ) -> Self {
+        Self {
+            inner,
+            guard,
+            model: model.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl LlmClient for SpendCappedLlmClient {
+    async fn complete(&self, prompt: &str) -> Result<LlmResponse> {
+        self.guard.check(&self.model)?;
+        self.inner.complete(prompt).await
+    }
+
+    async fn chat(&self, messages: &[ChatMessage]) -> Result<LlmResponse> {
+        self.guard.check(&self.model)?;
+        self.inner.chat(messages).await
+    }
+
+    async fn chat_with_params(
+        &self,
+        messages: &[ChatMessage],
+        params: &ProviderParams,
+    ) -> Result<LlmResponse> {
+        self.guard.check(&self.model)?;
+        self.inner.chat_with_params(messages, params).await
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        self.guard.check(&self.model)?;
+        self.inner.embed(text).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pricing::{ModelPricing, PricingRegistry};
+    use crate::MockLlmClient;
+
+    #[tokio::test]
+    async fn test_cap_blocks_non_privileged_workspaces() {
+        let mut pricing = PricingRegistry::new();
+        pricing.register(ModelPricing::new("openai:gpt-4o", 10.0, 10.0));
+        let ledger = Arc::new(UsageLedger::new().with_pricing(pricing));
+        let guard = Arc::new(SpendGuard::new(ledger.clone()).with_privileged_workspaces(["ops"]));
+        let caps = vec![SpendCap {
+            provider_id: "prov-1".to_string(),
+            model: "openai:gpt-4o".to_string(),
+            monthly_limit_usd: 5.0,
+        }];
+        let client = SpendCappedLlmClient::new(
+            Arc::new(MockLlmClient::new("ok")),
+            guard.clone(),
+            "openai:gpt-4o",
+        );
+
+        ledger.record_call("openai:gpt-4o", 400, 0);
+        assert!(guard.evaluate(&caps).is_empty());
+        assert!(client.complete("hi").await.is_ok());
+
+        ledger.record_call("openai:gpt-4o", 200, 0);
+        let changes = guard.evaluate(&caps);
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].exceeded);
+        assert!(guard.evaluate(&caps).is_empty(), "reported once");
+
+        assert!(matches!(
+            in_workspace(Some("team-a".into()), client.complete("hi")).await,
+            Err(Error::SpendCapExceeded { .. })
+        ));
+        assert!(client.complete("hi").await.is_err());
+        assert!(in_workspace(Some("ops".into()), client.complete("hi"))
+            .await
+            .is_ok());
+
+        // Removing the cap lifts the block
+        assert!(!guard.evaluate(&[])[0].exceeded);
+        assert!(client.complete("hi").await.is_ok());
+    }
+}
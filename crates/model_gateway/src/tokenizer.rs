@@ -0,0 +1,282 @@
+//! Model-aware token counting.
+//!
+//! Provider-reported usage only arrives after a call, so budget checks,
+//! compression triggers and context-window guards need a pre-call estimate.
+//! OpenAI models are counted with their real BPE vocabularies (`o200k_base`
+//! for GPT-4o and the o-series, `cl100k_base` for GPT-4 and GPT-3.5).
+//! Anthropic does not publish the tokenizer for Claude 3 and later, so Claude
+//! prompts are counted with `cl100k_base` plus a safety margin. Unknown models
+//! fall back to ~4 characters per token.
+
+use serde::Serialize;
+use tiktoken_rs::tokenizer::Tokenizer;
+use tiktoken_rs::CoreBPE;
+
+use multi_agent_core::{traits::ChatMessage, Error, Result};
+
+/// Tokens added per chat message for role and separators (OpenAI format).
+const TOKENS_PER_MESSAGE: usize = 4;
+/// Tokens that prime the assistant reply.
+const REPLY_PRIMING_TOKENS: usize = 3;
+/// Claude counts run this much higher than `cl100k_base`, in percent.
+const CLAUDE_MARGIN_PERCENT: usize = 10;
+/// Context window assumed for models we know nothing about.
+pub const DEFAULT_CONTEXT_WINDOW: usize = 8_192;
+
+/// Tokenizer used for a model family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenizerKind {
+    O200k,
+    Cl100k,
+    P50k,
+    R50k,
+    /// `cl100k_base` with a margin; see the module docs.
+    Claude,
+    /// ~4 characters per token.
+    Approximate,
+}
+
+impl TokenizerKind {
+    /// Pick the tokenizer for a model id. Accepts `provider:model` keys.
+    pub fn for_model(model: &str) -> Self {
+        let name = model_name(model);
+        if name.starts_with("claude") {
+            return Self::Claude;
+        }
+        match tiktoken_rs::tokenizer::get_tokenizer(name) {
+            Some(Tokenizer::O200kBase) => Self::O200k,
+            Some(Tokenizer::Cl100kBase) => Self::Cl100k,
+            Some(Tokenizer::P50kBase) | Some(Tokenizer::P50kEdit) => Self::P50k,
+            Some(Tokenizer::R50kBase) | Some(Tokenizer::Gpt2) => Self::R50k,
+            None => Self::Approximate,
+        }
+    }
+
+    fn bpe(&self) -> Option<&'static CoreBPE> {
+        match self {
+            Self::O200k => Some(tiktoken_rs::o200k_base_singleton()),
+            Self::Cl100k | Self::Claude => Some(tiktoken_rs::cl100k_base_singleton()),
+            Self::P50k => Some(tiktoken_rs::p50k_base_singleton()),
+            Self::R50k => Some(tiktoken_rs::r50k_base_singleton()),
+            Self::Approximate => None,
+        }
+    }
+}
+
+/// Strip a `provider:` prefix, keeping fine-tune ids like `ft:gpt-4o:...`.
+fn model_name(model: &str) -> &str {
+    if model.starts_with("ft:") {
+        return model;
+    }
+    model.split_once(':').map(|(_, m)| m).unwrap_or(model)
+}
+
+/// Context window of a model, in tokens.
+pub fn context_window(model: &str) -> usize {
+    let name = model_name(model);
+    if name.starts_with("claude") {
+        return 200_000;
+    }
+    if name.starts_with("gpt-4.1") {
+        return 1_047_576;
+    }
+    if name.starts_with("o3") || name.starts_with("o4") || name == "o1" {
+        return 200_000;
+    }
+    match TokenizerKind::for_model(name) {
+        TokenizerKind::Approximate => DEFAULT_CONTEXT_WINDOW,
+        _ => tiktoken_rs::model::get_context_size(name),
+    }
+}
+
+/// Pre-call size estimate for a prompt.
+#[derive(Debug, Clone, Serialize)]
+pub struct PromptEstimate {
+    pub prompt_tokens: usize,
+    /// Prompt plus the reserved completion.
+    pub max_total_tokens: usize,
+    pub context_window: usize,
+}
+
+/// Counts tokens the way a specific model does.
+#[derive(Debug, Clone)]
+pub struct TokenCounter {
+    model: String,
+    kind: TokenizerKind,
+    context_window: usize,
+}
+
+impl TokenCounter {
+    /// Counter for a model id (`gpt-4o`, `openai:gpt-4o-mini`, `claude-3-5-sonnet-...`).
+    pub fn for_model(model: &str) -> Self {
+        Self {
+            model: model.to_string(),
+            kind: TokenizerKind::for_model(model),
+            context_window: context_window(model),
+        }
+    }
+
+    /// Character-based counter for unknown models.
+    pub fn approximate(context_window: usize) -> Self {
+        Self {
+            model: String::new(),
+            kind: TokenizerKind::Approximate,
+            context_window,
+        }
+    }
+
+    /// Override the context window (e.g. for self-hosted deployments).
+    pub fn with_context_window(mut self, tokens: usize) -> Self {
+        self.context_window = tokens;
+        self
+    }
+
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    pub fn kind(&self) -> TokenizerKind {
+        self.kind
+    }
+
+    pub fn context_window(&self) -> usize {
+        self.context_window
+    }
+
+    /// Tokens in a piece of text.
+    pub fn count(&self, text: &str) -> usize {
+        let Some(bpe) = self.kind.bpe() else {
+            return text.len().div_ceil(4);
+        };
+        let tokens = bpe.encode_with_special_tokens(text).len();
+        if self.kind == TokenizerKind::Claude {
+            tokens + (tokens * CLAUDE_MARGIN_PERCENT).div_ceil(100)
+        } else {
+            tokens
+        }
+    }
+
+    /// Tokens a chat request will use, including per-message overhead.
+    pub fn count_messages(&self, messages: &[ChatMessage]) -> usize {
+        let content: usize = messages
+            .iter()
+            .map(|m| TOKENS_PER_MESSAGE + self.count(&m.role) + self.count(&m.content))
+            .sum();
+        content + REPLY_PRIMING_TOKENS
+    }
+
+    /// Tokens left in the context window after the prompt and a reserved
+    /// completion. Negative when the request would overflow.
+    pub fn headroom(&self, messages: &[ChatMessage], max_output_tokens: usize) -> i64 {
+        self.context_window as i64 - (self.count_messages(messages) + max_output_tokens) as i64
+    }
+
+    /// Check a request before sending it.
+    ///
+    /// Fails if the prompt plus `max_output_tokens` overflows the context
+    /// window, or if it would exceed `remaining_budget` tokens.
+    pub fn preflight(
+        &self,
+        messages: &[ChatMessage],
+        max_output_tokens: usize,
+        remaining_budget: Option<u64>,
+    ) -> Result<PromptEstimate> {
+        let prompt_tokens = self.count_messages(messages);
+        let estimate = PromptEstimate {
+            prompt_tokens,
+            max_total_tokens: prompt_tokens + max_output_tokens,
+            context_window: self.context_window,
+        };
+        if estimate.max_total_tokens > self.context_window {
+            return Err(Error::ContextWindowExceeded {
+                tokens: estimate.max_total_tokens as u64,
+                limit: self.context_window as u64,
+            });
+        }
+        if let Some(remaining) = remaining_budget {
+            if prompt_tokens as u64 > remaining {
+                return Err(Error::BudgetExceeded {
+                    used: prompt_tokens as u64,
+                    limit: remaining,
+                });
+            }
+        }
+        Ok(estimate)
+    }
+}
+
+impl Default for TokenCounter {
+    fn default() -> Self {
+        Self::approximate(DEFAULT_CONTEXT_WINDOW)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(content: &str) -> ChatMessage {
+        ChatMessage {
+            role: "user".to_string(),
+            content: content.to_string(),
+            tool_calls: None,
+        }
+    }
+
+    #[test]
+    fn test_tokenizer_per_family() {
+        assert_eq!(TokenizerKind::for_model("gpt-4o"), TokenizerKind::O200k);
+        assert_eq!(
+            TokenizerKind::for_model("openai:gpt-4o-mini"),
+            TokenizerKind::O200k
+        );
+        assert_eq!(TokenizerKind::for_model("gpt-4"), TokenizerKind::Cl100k);
+        assert_eq!(
+            TokenizerKind::for_model("anthropic:claude-3-5-sonnet-20241022"),
+            TokenizerKind::Claude
+        );
+        assert_eq!(
+            TokenizerKind::for_model("llama3:70b"),
+            TokenizerKind::Approximate
+        );
+
+        assert_eq!(context_window("openai:gpt-4o"), 128_000);
+        assert_eq!(context_window("gpt-4"), 8_192);
+        assert_eq!(context_window("claude-3-haiku-20240307"), 200_000);
+    }
+
+    #[test]
+    fn test_counts_with_real_vocabulary() {
+        let gpt = TokenCounter::for_model("gpt-4o");
+        assert_eq!(gpt.count("hello world"), 2);
+        // CJK text is far denser than 4 chars per token suggests
+        let text = "你好，世界。今天天气很好。";
+        assert!(gpt.count(text) > text.chars().count() / 4);
+
+        let claude = TokenCounter::for_model("claude-3-5-sonnet-20241022");
+        let cl100k = TokenCounter::for_model("gpt-4");
+        let long = "The quick brown fox jumps over the lazy dog. ".repeat(20);
+        assert!(claude.count(&long) > cl100k.count(&long));
+
+        assert_eq!(TokenCounter::default().count("abcdefgh"), 2);
+    }
+
+    #[test]
+    fn test_preflight() {
+        let counter = TokenCounter::for_model("gpt-4o").with_context_window(100);
+        let messages = vec![message("hello world")];
+        let estimate = counter.preflight(&messages, 50, None).unwrap();
+        assert_eq!(estimate.prompt_tokens, counter.count_messages(&messages));
+
+        let long = vec![message(&"word ".repeat(200))];
+        assert!(matches!(
+            counter.preflight(&long, 0, None),
+            Err(Error::ContextWindowExceeded { limit: 100, .. })
+        ));
+        assert!(matches!(
+            counter.preflight(&messages, 0, Some(5)),
+            Err(Error::BudgetExceeded { limit: 5, .. })
+        ));
+    }
+}
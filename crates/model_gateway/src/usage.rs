@@ -0,0 +1,351 @@
+//! Daily usage ledger.
+//!
+//! Aggregates LLM calls (tokens, estimated cost, errors) per model, tokens per
+//! session and tool invocations per UTC day. The alert rules and the daily
+//! digest in [`crate::alerts`] read from it.
+
+use async_trait::async_trait;
+use chrono::{Datelike, NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use multi_agent_core::{
+    traits::{ChatMessage, LlmClient, LlmResponse, ProviderParams},
+    Result,
+};
+
+use crate::pricing::PricingRegistry;
+
+/// Days of history kept in memory.
+const RETENTION_DAYS: usize = 8;
+
+/// One model's usage over a day.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModelUsage {
+    pub model: String,
+    pub calls: u64,
+    pub errors: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    /// Estimated from the pricing registry; 0 for unpriced models.
+    pub cost_usd: f64,
+}
+
+impl ModelUsage {
+    /// Failed calls as a fraction of all calls.
+    pub fn error_rate(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.calls as f64
+        }
+    }
+}
+
+/// Tokens one session used over a day.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionUsage {
+    pub session_id: String,
+    pub tokens: u64,
+}
+
+/// Invocations of one tool over a day.
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolUsage {
+    pub tool: String,
+    pub calls: u64,
+}
+
+/// Usage summary of one UTC day.
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyUsage {
+    pub date: NaiveDate,
+    pub calls: u64,
+    pub errors: u64,
+    pub total_tokens: u64,
+    pub cost_usd: f64,
+    /// Most expensive first.
+    pub models: Vec<ModelUsage>,
+    /// Largest first.
+    pub sessions: Vec<SessionUsage>,
+    /// Most used first.
+    pub tools: Vec<ToolUsage>,
+}
+
+#[derive(Default)]
+struct DayUsage {
+    models: HashMap<String, ModelUsage>,
+    sessions: HashMap<String, u64>,
+    tools: HashMap<String, u64>,
+}
+
+/// Per-day usage aggregates.
+pub struct UsageLedger {
+    pricing: PricingRegistry,
+    days: Mutex<BTreeMap<NaiveDate, DayUsage>>,
+    /// Month-to-date cost per model, with the (year, month) it covers.
+    month: Mutex<((i32, u32), HashMap<String, f64>)>,
+}
+
+impl UsageLedger {
+    pub fn new() -> Self {
+        Self {
+            pricing: PricingRegistry::with_defaults(),
+            days: Mutex::new(BTreeMap::new()),
+            month: Mutex::new((current_month(), HashMap::new())),
+        }
+    }
+
+    /// Use custom prices for cost estimates.
+    pub fn with_pricing(mut self, pricing: PricingRegistry) -> Self {
+        self.pricing = pricing;
+        self
+    }
+
+    fn with_today<T>(&self, f: impl FnOnce(&mut DayUsage) -> T) -> T {
+        let mut days = self.days.lock().unwrap();
+        let today = Utc::now().date_naive();
+        while days.len() >= RETENTION_DAYS && !days.contains_key(&today) {
+            days.pop_first();
+        }
+        f(days.entry(today).or_default())
+    }
+
+    /// Record a completed LLM call.
+    pub fn record_call(&self, model: &str, prompt_tokens: u64, completion_tokens: u64) {
+        let cost = self
+            .pricing
+            .get(model)
+            .map(|p| p.estimate_cost(prompt_tokens, completion_tokens))
+            .unwrap_or_default();
+        self.with_today(|day| {
+            let usage = model_entry(day, model);
+            usage.calls += 1;
+            usage.prompt_tokens += prompt_tokens;
+            usage.completion_tokens += completion_tokens;
+            usage.cost_usd += cost;
+        });
+        let mut month = self.month.lock().unwrap();
+        if month.0 != current_month() {
+            *month = (current_month(), HashMap::new());
+        }
+        *month.1.entry(model.to_string()).or_default() += cost;
+    }
+
+    /// Estimated spend on a model since the start of the UTC month.
+    pub fn month_to_date(&self, model: &str) -> f64 {
+        let month = self.month.lock().unwrap();
+        if month.0 != current_month() {
+            return 0.0;
+        }
+        month.1.get(model).copied().unwrap_or_default()
+    }
+
+    /// Record a failed LLM call.
+    pub fn record_error(&self, model: &str) {
+        self.with_today(|day| {
+            let usage = model_entry(day, model);
+            usage.calls += 1;
+            usage.errors += 1;
+        });
+    }
+
+    /// Attribute tokens to a session.
+    pub fn record_session(&self, session_id: &str, tokens: u64) {
+        self.with_today(|day| *day.sessions.entry(session_id.to_string()).or_default() += tokens);
+    }
+
+    /// Record a tool invocation.
+    pub fn record_tool(&self, tool: &str) {
+        self.with_today(|day| *day.tools.entry(tool.to_string()).or_default() += 1);
+    }
+
+    /// Summary of a day; empty if nothing was recorded.
+    pub fn summary(&self, date: NaiveDate) -> DailyUsage {
+        let days = self.days.lock().unwrap();
+        let mut summary = DailyUsage {
+            date,
+            calls: 0,
+            errors: 0,
+            total_tokens: 0,
+            cost_usd: 0.0,
+            models: Vec::new(),
+            sessions: Vec::new(),
+            tools: Vec::new(),
+        };
+        let Some(day) = days.get(&date) else {
+            return summary;
+        };
+
+        summary.models = day.models.values().cloned().collect();
+        summary.models.sort_by(|a, b| {
+            b.cost_usd
+                .total_cmp(&a.cost_usd)
+                .then(b.calls.cmp(&a.calls))
+        });
+        for m in &summary.models {
+            summary.calls += m.calls;
+            summary.errors += m.errors;
+            summary.total_tokens += m.prompt_tokens + m.completion_tokens;
+            summary.cost_usd += m.cost_usd;
+        }
+        summary.sessions = day
+            .sessions
+            .iter()
+            .map(|(id, &tokens)| SessionUsage {
+                session_id: id.clone(),
+                tokens,
+            })
+            .collect();
+        summary.sessions.sort_by(|a, b| {
+            b.tokens
+                .cmp(&a.tokens)
+                .then(a.session_id.cmp(&b.session_id))
+        });
+        summary.tools = day
+            .tools
+            .iter()
+            .map(|(tool, &calls)| ToolUsage {
+                tool: tool.clone(),
+                calls,
+            })
+            .collect();
+        summary
+            .tools
+            .sort_by(|a, b| b.calls.cmp(&a.calls).then(a.tool.cmp(&b.tool)));
+        summary
+    }
+
+    /// Summary of the current UTC day.
+    pub fn today(&self) -> DailyUsage {
+        self.summary(Utc::now().date_naive())
+    }
+}
+
+impl Default for UsageLedger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn current_month() -> (i32, u32) {
+    let today = Utc::now().date_naive();
+    (today.year(), today.month())
+}
+
+fn model_entry<'a>(day: &'a mut DayUsage, model: &str) -> &'a mut ModelUsage {
+    day.models
+        .entry(model.to_string())
+        .or_insert_with(|| ModelUsage {
+            model: model.to_string(),
+            ..ModelUsage::default()
+        })
+}
+
+/// LLM client that records every call in a [`UsageLedger`].
+pub struct MeteredLlmClient {
+    inner: Arc<dyn LlmClient>,
+    ledger: Arc<UsageLedger>,
+    /// Pricing key, e.g. `openai:gpt-4o`.
+    model: String,
+}
+
+impl MeteredLlmClient {
+    pub fn new(
+        inner: Arc<dyn LlmClient>,
+        ledger: Arc<UsageLedger>,
+        model: impl Into<String>,
+    ) -> Self {
+        Self {
+            inner,
+            ledger,
+            model: model.into(),
+        }
+    }
+
+    fn record(&self, result: &Result<LlmResponse>) {
+        match result {
+            Ok(r) => self.ledger.record_call(
+                &self.model,
+                r.usage.prompt_tokens,
+                r.usage.completion_tokens,
+            ),
+            Err(_) => self.ledger.record_error(&self.model),
+        }
+    }
+}
+
+#[async_trait]
+impl LlmClient for MeteredLlmClient {
+    async fn complete(&self, prompt: &str) -> Result<LlmResponse> {
+        let result = self.inner.complete(prompt).await;
+        self.record(&result);
+        result
+    }
+
+    async fn chat(&self, messages: &[ChatMessage]) -> Result<LlmResponse> {
+        let result = self.inner.chat(messages).await;
+        self.record(&result);
+        result
+    }
+
+    async fn chat_with_params(
+        &self,
+        messages: &[ChatMessage],
+        params: &ProviderParams,
+    ) -> Result<LlmResponse> {
+        let result = self.inner.chat_with_params(messages, params).await;
+        self.record(&result);
+        result
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let result = self.inner.embed(text).await;
+        match &result {
+            Ok(_) => self.ledger.record_call(&self.model, 0, 0),
+            Err(_) => self.ledger.record_error(&self.model),
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pricing::ModelPricing;
+
+    #[test]
+    fn test_daily_summary() {
+        let mut pricing = PricingRegistry::new();
+        pricing.register(ModelPricing::new("test:model", 1.0, 2.0));
+        let ledger = UsageLedger::new().with_pricing(pricing);
+
+        ledger.record_call("test:model", 1000, 500);
+        ledger.record_call("test:model", 1000, 500);
+        ledger.record_error("test:model");
+        ledger.record_call("local:llama", 100, 100);
+        ledger.record_session("s1", 3000);
+        ledger.record_session("s2", 200);
+        ledger.record_session("s1", 10);
+        for tool in ["search", "search", "read_file"] {
+            ledger.record_tool(tool);
+        }
+
+        let today = ledger.today();
+        assert_eq!((today.calls, today.errors), (4, 1));
+        assert_eq!(today.total_tokens, 3200);
+        assert!((today.cost_usd - 4.0).abs() < 1e-9);
+        assert_eq!(today.models[0].model, "test:model");
+        assert!((today.models[0].error_rate() - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(today.sessions[0].session_id, "s1");
+        assert_eq!(today.sessions[0].tokens, 3010);
+        assert_eq!(today.tools[0].tool, "search");
+
+        assert!((ledger.month_to_date("test:model") - 4.0).abs() < 1e-9);
+        assert_eq!(ledger.month_to_date("local:llama"), 0.0);
+
+        let yesterday = today.date.pred_opt().unwrap();
+        assert_eq!(ledger.summary(yesterday).calls, 0);
+    }
+}
@@ -0,0 +1,636 @@
+//! Event bus backends for sharing the event stream across replicas.
+//!
+//! Producers keep writing JSON lines to the in-process logs channel; [`relay`]
+//! publishes those lines to the configured bus and feeds everything on the bus
+//! back into a local stream that the dashboard subscribes to.
+
+use async_nats::jetstream;
+use async_trait::async_trait;
+use futures::StreamExt;
+use multi_agent_core::config::{EventBusBackend, EventBusConfig, KafkaBusConfig, NatsBusConfig};
+use multi_agent_core::events::EventEnvelope;
+use multi_agent_core::traits::EventEmitter;
+use multi_agent_core::{Error, Result};
+use secrecy::ExposeSecret;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+
+const SUBSCRIPTION_BUFFER: usize = 1024;
+
+/// A message received from the bus.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BusMessage {
+    pub subject: String,
+    pub payload: String,
+}
+
+/// Transport shared by all replicas.
+#[async_trait]
+pub trait EventBus: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Publish one JSON line under `subject`.
+    async fn publish(&self, subject: &str, payload: &str) -> Result<()>;
+
+    /// Receive every message published under the bus prefix, from any replica.
+    async fn subscribe(&self) -> Result<mpsc::Receiver<BusMessage>>;
+}
+
+/// Subject for a logs-channel line: `<prefix>.<event_type>` for event
+/// envelopes, `<prefix>.log` for anything else.
+pub fn subject_for(prefix: &str, line: &str) -> String {
+    let kind = serde_json::from_str::<serde_json::Value>(line)
+        .ok()
+        .and_then(|v| v.get("event_type")?.as_str().map(str::to_lowercase))
+        .unwrap_or_else(|| "log".to_string());
+    format!("{}.{}", prefix, kind)
+}
+
+/// Connect the backend selected in `config`. Returns `None` for the
+/// in-process backend, where the logs channel already is the event stream.
+pub async fn connect(config: &EventBusConfig, replica: &str) -> Result<Option<Arc<dyn EventBus>>> {
+    match config.backend {
+        EventBusBackend::InProcess => Ok(None),
+        EventBusBackend::Nats => {
+            let nats = config.nats.as_ref().ok_or_else(|| {
+                Error::invalid_request("events.backend is nats but [events.nats] is missing")
+            })?;
+            Ok(Some(Arc::new(
+                NatsEventBus::connect(nats, &config.subject_prefix).await?,
+            )))
+        }
+        EventBusBackend::Kafka => {
+            let kafka = config.kafka.as_ref().ok_or_else(|| {
+                Error::invalid_request("events.backend is kafka but [events.kafka] is missing")
+            })?;
+            Ok(Some(Arc::new(KafkaRestEventBus::new(kafka, replica))))
+        }
+    }
+}
+
+/// Publish every line sent on `local` to `bus` and forward everything on the
+/// bus, including this replica's own lines, to `stream`.
+pub async fn relay(
+    bus: Arc<dyn EventBus>,
+    prefix: &str,
+    local: &broadcast::Sender<String>,
+    stream: broadcast::Sender<String>,
+) -> Result<JoinHandle<()>> {
+    let mut incoming = bus.subscribe().await?;
+    let mut outgoing = local.subscribe();
+    let prefix = prefix.to_string();
+    Ok(tokio::spawn(async move {
+        let publish = async {
+            loop {
+                match outgoing.recv().await {
+                    Ok(line) => {
+                        let subject = subject_for(&prefix, &line);
+                        if let Err(e) = bus.publish(&subject, &line).await {
+                            metrics::counter!("event_bus_publish_failures_total", "bus" => bus.name().to_string())
+                                .increment(1);
+                            tracing::warn!(bus = bus.name(), error = %e, "Event bus publish failed");
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!(skipped, "Event bus relay lagged behind the logs channel");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        };
+        let forward = async {
+            while let Some(message) = incoming.recv().await {
+                let _ = stream.send(message.payload);
+            }
+            tracing::warn!("Event bus subscription ended");
+        };
+        tokio::join!(publish, forward);
+    }))
+}
+
+/// In-process bus; mainly useful for tests and single-replica setups.
+pub struct InProcessEventBus {
+    tx: broadcast::Sender<BusMessage>,
+}
+
+impl InProcessEventBus {
+    pub fn new() -> Self {
+        let (tx, _) = broadcast::channel(SUBSCRIPTION_BUFFER);
+        Self { tx }
+    }
+}
+
+impl Default for InProcessEventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl EventBus for InProcessEventBus {
+    fn name(&self) -> &str {
+        "in_process"
+    }
+
+    async fn publish(&self, subject: &str, payload: &str) -> Result<()> {
+        let _ = self.tx.send(BusMessage {
+            subject: subject.to_string(),
+            payload: payload.to_string(),
+        });
+        Ok(())
+    }
+
+    async fn subscribe(&self) -> Result<mpsc::Receiver<BusMessage>> {
+        let mut rx = self.tx.subscribe();
+        let (tx, out) = mpsc::channel(SUBSCRIPTION_BUFFER);
+        tokio::spawn(async move {
+            loop {
+                match rx.recv().await {
+                    Ok(message) => {
+                        if tx.send(message).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+        Ok(out)
+    }
+}
+
+/// [`EventEmitter`] that writes envelopes to the logs channel as JSON lines.
+pub struct ChannelEventEmitter {
+    tx: broadcast::Sender<String>,
+}
+
+impl ChannelEventEmitter {
+    pub fn new(tx: broadcast::Sender<String>) -> Self {
+        Self { tx }
+    }
+}
+
+#[async_trait]
+impl EventEmitter for ChannelEventEmitter {
+    async fn emit(&self, event: EventEnvelope) {
+        if let Ok(json) = serde_json::to_string(&event) {
+            let _ = self.tx.send(json);
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// NATS
+// ---------------------------------------------------------------------------
+
+/// NATS core publish/subscribe, with an optional JetStream stream capturing
+/// `<prefix>.>` so events outlive restarts and can be replayed.
+///
+/// The client reconnects with backoff and re-issues subscriptions after a
+/// disconnect.
+pub struct NatsEventBus {
+    client: async_nats::Client,
+    jetstream: jetstream::Context,
+    prefix: String,
+    stream: Option<String>,
+    replay: Duration,
+}
+
+impl NatsEventBus {
+    /// Connect to `config.url`, waiting up to 5s for the first handshake, and
+    /// create the JetStream stream if one is configured and missing.
+    pub async fn connect(config: &NatsBusConfig, prefix: &str) -> Result<Self> {
+        let mut options = async_nats::ConnectOptions::new()
+            .name("opencoordex")
+            .connection_timeout(Duration::from_secs(5))
+            .event_callback(|event| async move {
+                match event {
+                    async_nats::Event::Disconnected => {
+                        metrics::counter!("event_bus_reconnects_total", "bus" => "nats")
+                            .increment(1);
+                        tracing::warn!("NATS connection lost; reconnecting");
+                    }
+                    async_nats::Event::SlowConsumer(_) => {
+                        metrics::counter!("event_bus_dropped_total", "bus" => "nats").increment(1);
+                    }
+                    event => tracing::debug!(%event, "NATS connection event"),
+                }
+            });
+        if let Some(token) = &config.token {
+            options = options.token(token.expose_secret().to_string());
+        }
+        let client = options.connect(config.url.as_str()).await.map_err(|e| {
+            Error::storage(format!("NATS server {} is unreachable: {}", config.url, e))
+        })?;
+
+        let bus = Self {
+            jetstream: jetstream::new(client.clone()),
+            client,
+            prefix: prefix.to_string(),
+            stream: config.stream.clone(),
+            replay: Duration::from_secs(config.replay_secs),
+        };
+        if let Some(stream) = &bus.stream {
+            bus.ensure_stream(stream).await?;
+        }
+        Ok(bus)
+    }
+
+    async fn ensure_stream(&self, stream: &str) -> Result<()> {
+        self.jetstream
+            .get_or_create_stream(jetstream::stream::Config {
+                name: stream.to_string(),
+                subjects: vec![format!("{}.>", self.prefix)],
+                retention: jetstream::stream::RetentionPolicy::Limits,
+                storage: jetstream::stream::StorageType::File,
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| {
+                Error::storage(format!(
+                    "Failed to create JetStream stream {}: {}",
+                    stream, e
+                ))
+            })?;
+        Ok(())
+    }
+
+    /// Messages stored in the stream within the replay window, up to its
+    /// current last sequence.
+    async fn replay(&self, stream: &str) -> Result<Vec<BusMessage>> {
+        let mut stream = self
+            .jetstream
+            .get_stream(stream)
+            .await
+            .map_err(|e| Error::storage(format!("JetStream stream unavailable: {}", e)))?;
+        let last_seq = stream
+            .info()
+            .await
+            .map_err(|e| Error::storage(format!("JetStream stream info failed: {}", e)))?
+            .state
+            .last_sequence;
+        if last_seq == 0 {
+            return Ok(Vec::new());
+        }
+
+        let consumer = stream
+            .create_consumer(jetstream::consumer::pull::Config {
+                deliver_policy: jetstream::consumer::DeliverPolicy::ByStartTime {
+                    start_time: time::OffsetDateTime::now_utc() - self.replay,
+                },
+                ack_policy: jetstream::consumer::AckPolicy::None,
+                filter_subject: format!("{}.>", self.prefix),
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| Error::storage(format!("JetStream consumer creation failed: {}", e)))?;
+        let name = consumer.cached_info().name.clone();
+        let mut messages = consumer
+            .messages()
+            .await
+            .map_err(|e| Error::storage(format!("JetStream replay failed: {}", e)))?;
+
+        let mut replayed = Vec::new();
+        while let Ok(Some(Ok(message))) =
+            tokio::time::timeout(Duration::from_secs(2), messages.next()).await
+        {
+            let (seq, pending) = message
+                .info()
+                .map(|info| (info.stream_sequence, info.pending))
+                .unwrap_or((last_seq, 0));
+            if seq <= last_seq {
+                replayed.push(BusMessage {
+                    subject: message.subject.to_string(),
+                    payload: String::from_utf8_lossy(&message.payload).into_owned(),
+                });
+            }
+            if seq >= last_seq || pending == 0 {
+                break;
+            }
+        }
+        let _ = stream.delete_consumer(&name).await;
+        Ok(replayed)
+    }
+}
+
+#[async_trait]
+impl EventBus for NatsEventBus {
+    fn name(&self) -> &str {
+        "nats"
+    }
+
+    async fn publish(&self, subject: &str, payload: &str) -> Result<()> {
+        self.client
+            .publish(subject.to_string(), payload.to_string().into())
+            .await
+            .map_err(|e| Error::storage(format!("NATS publish failed: {}", e)))
+    }
+
+    async fn subscribe(&self) -> Result<mpsc::Receiver<BusMessage>> {
+        let mut live = self
+            .client
+            .subscribe(format!("{}.>", self.prefix))
+            .await
+            .map_err(|e| Error::storage(format!("NATS subscribe failed: {}", e)))?;
+        let replayed = match &self.stream {
+            Some(stream) if !self.replay.is_zero() => match self.replay(stream).await {
+                Ok(messages) => messages,
+                Err(e) => {
+                    tracing::warn!(stream, error = %e, "Event replay from JetStream failed");
+                    Vec::new()
+                }
+            },
+            _ => Vec::new(),
+        };
+        let (tx, out) = mpsc::channel(SUBSCRIPTION_BUFFER);
+        tokio::spawn(async move {
+            for message in replayed {
+                if tx.send(message).await.is_err() {
+                    return;
+                }
+            }
+            while let Some(message) = live.next().await {
+                let message = BusMessage {
+                    subject: message.subject.to_string(),
+                    payload: String::from_utf8_lossy(&message.payload).into_owned(),
+                };
+                if tx.send(message).await.is_err() {
+                    return;
+                }
+            }
+        });
+        Ok(out)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Kafka (REST proxy)
+// ---------------------------------------------------------------------------
+
+const KAFKA_JSON: &str = "application/vnd.kafka.json.v2+json";
+
+/// Kafka through a Confluent-compatible REST proxy. Each replica consumes in
+/// its own group, so all replicas see all events and resume from committed
+/// offsets after a restart.
+pub struct KafkaRestEventBus {
+    http: reqwest::Client,
+    rest_url: String,
+    topic: String,
+    group: String,
+    instance: String,
+}
+
+impl KafkaRestEventBus {
+    pub fn new(config: &KafkaBusConfig, replica: &str) -> Self {
+        let group = config
+            .consumer_group
+            .clone()
+            .unwrap_or_else(|| "opencoordex-events".to_string());
+        Self {
+            http: reqwest::Client::new(),
+            rest_url: config.rest_url.trim_end_matches('/').to_string(),
+            topic: config.topic.clone(),
+            group: format!("{}-{}", group, replica),
+            instance: replica.to_string(),
+        }
+    }
+
+    /// Create (or reuse) this replica's consumer instance and subscribe it.
+    async fn consumer(&self) -> Result<String> {
+        let response = self
+            .http
+            .post(format!("{}/consumers/{}", self.rest_url, self.group))
+            .header(reqwest::header::CONTENT_TYPE, KAFKA_JSON)
+            .json(&serde_json::json!({
+                "name": self.instance,
+                "format": "json",
+                "auto.offset.reset": "latest",
+                "auto.commit.enable": "true",
+            }))
+            .send()
+            .await
+            .map_err(|e| Error::storage(format!("Kafka REST proxy unreachable: {}", e)))?;
+        let base = if response.status() == reqwest::StatusCode::CONFLICT {
+            format!(
+                "{}/consumers/{}/instances/{}",
+                self.rest_url, self.group, self.instance
+            )
+        } else {
+            let body: serde_json::Value = response
+                .error_for_status()
+                .map_err(|e| Error::storage(format!("Kafka consumer creation failed: {}", e)))?
+                .json()
+                .await
+                .map_err(|e| Error::storage(format!("Invalid Kafka REST response: {}", e)))?;
+            body["base_uri"]
+                .as_str()
+                .ok_or_else(|| Error::storage("Kafka REST response has no base_uri"))?
+                .to_string()
+        };
+        self.http
+            .post(format!("{}/subscription", base))
+            .header(reqwest::header::CONTENT_TYPE, KAFKA_JSON)
+            .json(&serde_json::json!({ "topics": [self.topic] }))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| Error::storage(format!("Kafka subscription failed: {}", e)))?;
+        Ok(base)
+    }
+}
+
+#[async_trait]
+impl EventBus for KafkaRestEventBus {
+    fn name(&self) -> &str {
+        "kafka"
+    }
+
+    async fn publish(&self, subject: &str, payload: &str) -> Result<()> {
+        let value = serde_json::from_str::<serde_json::Value>(payload)
+            .unwrap_or_else(|_| serde_json::Value::String(payload.to_string()));
+        self.http
+            .post(format!("{}/topics/{}", self.rest_url, self.topic))
+            .header(reqwest::header::CONTENT_TYPE, KAFKA_JSON)
+            .json(&serde_json::json!({ "records": [{ "key": subject, "value": value }] }))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| Error::storage(format!("Kafka produce failed: {}", e)))?;
+        Ok(())
+    }
+
+    async fn subscribe(&self) -> Result<mpsc::Receiver<BusMessage>> {
+        let mut base = self.consumer().await?;
+        let (tx, out) = mpsc::channel(SUBSCRIPTION_BUFFER);
+        let bus = Self {
+            http: self.http.clone(),
+            rest_url: self.rest_url.clone(),
+            topic: self.topic.clone(),
+            group: self.group.clone(),
+            instance: self.instance.clone(),
+        };
+        tokio::spawn(async move {
+            while !tx.is_closed() {
+                let response = bus
+                    .http
+                    .get(format!("{}/records?timeout=1000", base))
+                    .header(reqwest::header::ACCEPT, KAFKA_JSON)
+                    .send()
+                    .await;
+                let records = match response {
+                    Ok(r) if r.status() == reqwest::StatusCode::NOT_FOUND => {
+                        // The proxy expires idle consumer instances.
+                        match bus.consumer().await {
+                            Ok(renewed) => base = renewed,
+                            Err(e) => {
+                                tracing::warn!(error = %e, "Kafka consumer re-creation failed");
+                                tokio::time::sleep(Duration::from_secs(1)).await;
+                            }
+                        }
+                        continue;
+                    }
+                    Ok(r) => r.json::<Vec<serde_json::Value>>().await,
+                    Err(e) => Err(e),
+                };
+                match records {
+                    Ok(records) => {
+                        for record in records {
+                            let payload = match &record["value"] {
+                                serde_json::Value::String(s) => s.clone(),
+                                value => value.to_string(),
+                            };
+                            let message = BusMessage {
+                                subject: record["key"].as_str().unwrap_or_default().to_string(),
+                                payload,
+                            };
+                            if tx.send(message).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Kafka poll failed");
+                        tokio::time::sleep(Duration::from_secs(1)).await;
+                    }
+                }
+            }
+        });
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_subject_for() {
+        let envelope = EventEnvelope::new(
+            multi_agent_core::events::EventType::ToolExecStarted,
+            serde_json::json!({"tool_name": "read_file"}),
+        );
+        let line = serde_json::to_string(&envelope).unwrap();
+        assert_eq!(subject_for("oc", &line), "oc.tool_exec_started");
+        assert_eq!(subject_for("oc", "## Findings"), "oc.log");
+    }
+
+    #[tokio::test]
+    async fn test_relay_round_trip() {
+        let bus: Arc<dyn EventBus> = Arc::new(InProcessEventBus::new());
+        let (local, _) = broadcast::channel(16);
+        let (stream, mut dashboard) = broadcast::channel(16);
+        let mut external = bus.subscribe().await.unwrap();
+        relay(bus.clone(), "oc", &local, stream).await.unwrap();
+
+        ChannelEventEmitter::new(local.clone())
+            .emit(EventEnvelope::new(
+                multi_agent_core::events::EventType::DataDeletionInitiated,
+                serde_json::json!({"user_id": "u1"}),
+            ))
+            .await;
+        assert_eq!(
+            external.recv().await.unwrap().subject,
+            "oc.data_deletion_initiated"
+        );
+
+        // Another replica's events reach this replica's dashboard
+        bus.publish("oc.log", "from replica b").await.unwrap();
+        let lines = [
+            dashboard.recv().await.unwrap(),
+            dashboard.recv().await.unwrap(),
+        ];
+        assert!(lines.iter().any(|l| l.contains("DATA_DELETION_INITIATED")));
+        assert!(lines.iter().any(|l| l == "from replica b"));
+    }
+
+    /// Minimal NATS server: echoes every PUB to matching `<prefix>.>` subscriptions.
+    async fn fake_nats() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let (read, mut write) = socket.into_split();
+            let mut reader = BufReader::new(read);
+            write
+                .write_all(b"INFO {\"server_id\":\"test\",\"max_payload\":1048576}\r\n")
+                .await
+                .unwrap();
+            let mut subs: Vec<(String, String)> = Vec::new();
+            let mut line = String::new();
+            loop {
+                line.clear();
+                if reader.read_line(&mut line).await.unwrap() == 0 {
+                    return;
+                }
+                let parts: Vec<String> = line.split_whitespace().map(String::from).collect();
+                match parts[0].as_str() {
+                    "PING" => write.write_all(b"PONG\r\n").await.unwrap(),
+                    "SUB" => subs.push((parts[1].clone(), parts[2].clone())),
+                    "PUB" => {
+                        let len: usize = parts.last().unwrap().parse().unwrap();
+                        let mut body = vec![0; len + 2];
+                        reader.read_exact(&mut body).await.unwrap();
+                        for (subject, sid) in &subs {
+                            let prefix = subject.trim_end_matches('>');
+                            if parts[1].starts_with(prefix) {
+                                let frame = format!("MSG {} {} {}\r\n", parts[1], sid, len);
+                                write.write_all(frame.as_bytes()).await.unwrap();
+                                write.write_all(&body).await.unwrap();
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        });
+        address
+    }
+
+    #[tokio::test]
+    async fn test_nats_publish_subscribe() {
+        let address = fake_nats().await;
+        let config = NatsBusConfig {
+            url: format!("nats://{}", address),
+            token: None,
+            stream: None,
+            replay_secs: 0,
+        };
+        let bus = NatsEventBus::connect(&config, "oc").await.unwrap();
+        let mut rx = bus.subscribe().await.unwrap();
+        bus.publish("oc.tool_exec_finished", "{\"ok\":true}")
+            .await
+            .unwrap();
+
+        let message = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(message.subject, "oc.tool_exec_finished");
+        assert_eq!(message.payload, "{\"ok\":true}");
+    }
+}
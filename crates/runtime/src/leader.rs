@@ -0,0 +1,320 @@
+//! Lease-based leader election for singleton background jobs.
+
+use async_trait::async_trait;
+use multi_agent_core::{Error, Result};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{watch, Mutex};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+/// Storage for named, expiring leases.
+#[async_trait]
+pub trait LeaseBackend: Send + Sync {
+    /// Acquire `name` for `holder`, or renew it if `holder` already owns it.
+    /// Returns false while another holder's lease is live.
+    async fn try_acquire(&self, name: &str, holder: &str, ttl: Duration) -> Result<bool>;
+
+    /// Release `name` if `holder` owns it.
+    async fn release(&self, name: &str, holder: &str) -> Result<()>;
+}
+
+/// Process-local leases; suitable for single-replica deployments and tests.
+#[derive(Default)]
+pub struct InMemoryLeaseBackend {
+    leases: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+impl InMemoryLeaseBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl LeaseBackend for InMemoryLeaseBackend {
+    async fn try_acquire(&self, name: &str, holder: &str, ttl: Duration) -> Result<bool> {
+        let mut leases = self.leases.lock().await;
+        let now = Instant::now();
+        match leases.get(name) {
+            Some((owner, expires)) if owner != holder && *expires > now => Ok(false),
+            _ => {
+                leases.insert(name.to_string(), (holder.to_string(), now + ttl));
+                Ok(true)
+            }
+        }
+    }
+
+    async fn release(&self, name: &str, holder: &str) -> Result<()> {
+        let mut leases = self.leases.lock().await;
+        if leases.get(name).is_some_and(|(owner, _)| owner == holder) {
+            leases.remove(name);
+        }
+        Ok(())
+    }
+}
+
+const ACQUIRE_SCRIPT: &str = r#"
+local current = redis.call('GET', KEYS[1])
+if current == ARGV[1] then
+    redis.call('PEXPIRE', KEYS[1], ARGV[2])
+    return 1
+end
+if not current then
+    redis.call('SET', KEYS[1], ARGV[1], 'PX', ARGV[2])
+    return 1
+end
+return 0
+"#;
+
+const RELEASE_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+
+/// Leases stored as Redis keys with a PX expiry, shared by all replicas.
+pub struct RedisLeaseBackend {
+    client: redis::Client,
+    prefix: String,
+}
+
+impl RedisLeaseBackend {
+    pub fn new(url: &str, prefix: &str) -> Result<Self> {
+        let client = redis::Client::open(url)
+            .map_err(|e| Error::storage(format!("Failed to connect to Redis: {}", e)))?;
+        Ok(Self {
+            client,
+            prefix: prefix.to_string(),
+        })
+    }
+
+    fn key(&self, name: &str) -> String {
+        format!("{}:{}", self.prefix, name)
+    }
+
+    async fn connection(&self) -> Result<redis::aio::MultiplexedConnection> {
+        self.client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| Error::storage(format!("Redis connection error: {}", e)))
+    }
+}
+
+#[async_trait]
+impl LeaseBackend for RedisLeaseBackend {
+    async fn try_acquire(&self, name: &str, holder: &str, ttl: Duration) -> Result<bool> {
+        let mut conn = self.connection().await?;
+        let acquired: i64 = redis::Script::new(ACQUIRE_SCRIPT)
+            .key(self.key(name))
+            .arg(holder)
+            .arg(ttl.as_millis().max(1) as u64)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| Error::storage(format!("Redis lease error: {}", e)))?;
+        Ok(acquired == 1)
+    }
+
+    async fn release(&self, name: &str, holder: &str) -> Result<()> {
+        let mut conn = self.connection().await?;
+        let _: i64 = redis::Script::new(RELEASE_SCRIPT)
+            .key(self.key(name))
+            .arg(holder)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| Error::storage(format!("Redis lease error: {}", e)))?;
+        Ok(())
+    }
+}
+
+/// Runs singleton tasks only while this replica holds their lease.
+pub struct LeaderElector {
+    backend: Arc<dyn LeaseBackend>,
+    holder: String,
+    ttl: Duration,
+}
+
+impl LeaderElector {
+    /// Create an elector with a random holder id and a 15s lease.
+    pub fn new(backend: Arc<dyn LeaseBackend>) -> Self {
+        Self {
+            backend,
+            holder: uuid::Uuid::new_v4().to_string(),
+            ttl: Duration::from_secs(15),
+        }
+    }
+
+    /// Override the holder id (e.g. the pod name).
+    pub fn with_holder(mut self, holder: impl Into<String>) -> Self {
+        self.holder = holder.into();
+        self
+    }
+
+    /// Lease lifetime; leases are renewed every third of it.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn holder(&self) -> &str {
+        &self.holder
+    }
+
+    /// Supervise the singleton job `name`. `start` is invoked each time this
+    /// replica gains the lease; the task it returns is aborted when the lease
+    /// is lost or cannot be renewed.
+    pub fn gate<F, H>(self: &Arc<Self>, name: &str, start: F) -> GatedTask
+    where
+        F: Fn() -> H + Send + 'static,
+        H: Into<Option<JoinHandle<()>>>,
+    {
+        let elector = self.clone();
+        let name = name.to_string();
+        let (leader_tx, leader_rx) = watch::channel(false);
+        let (stop_tx, mut stop_rx) = watch::channel(false);
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval((elector.ttl / 3).max(Duration::from_millis(1)));
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            let mut task: Option<JoinHandle<()>> = None;
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    Ok(()) = stop_rx.changed() => break,
+                }
+                let held = match elector
+                    .backend
+                    .try_acquire(&name, &elector.holder, elector.ttl)
+                    .await
+                {
+                    Ok(held) => held,
+                    Err(e) => {
+                        tracing::warn!(job = %name, error = %e, "Lease renewal failed");
+                        false
+                    }
+                };
+                if held && !*leader_tx.borrow() {
+                    tracing::info!(job = %name, holder = %elector.holder, "Acquired leadership");
+                    task = start().into();
+                } else if !held && *leader_tx.borrow() {
+                    tracing::warn!(job = %name, holder = %elector.holder, "Lost leadership");
+                    if let Some(task) = task.take() {
+                        task.abort();
+                    }
+                }
+                leader_tx.send_if_modified(|leader| std::mem::replace(leader, held) != held);
+                metrics::gauge!("leader_election_is_leader", "job" => name.clone())
+                    .set(f64::from(u8::from(held)));
+            }
+            if let Some(task) = task.take() {
+                task.abort();
+            }
+            if *leader_tx.borrow() {
+                if let Err(e) = elector.backend.release(&name, &elector.holder).await {
+                    tracing::warn!(job = %name, error = %e, "Failed to release lease");
+                }
+                leader_tx.send_replace(false);
+            }
+        });
+        GatedTask {
+            leader: leader_rx,
+            stop: stop_tx,
+            handle,
+        }
+    }
+}
+
+/// Handle to a job supervised by [`LeaderElector::gate`]. Dropping it leaves
+/// the job supervised for the life of the process.
+pub struct GatedTask {
+    leader: watch::Receiver<bool>,
+    stop: watch::Sender<bool>,
+    handle: JoinHandle<()>,
+}
+
+impl GatedTask {
+    /// Whether this replica currently runs the job.
+    pub fn is_leader(&self) -> bool {
+        *self.leader.borrow()
+    }
+
+    /// Wait until this replica's leadership changes.
+    pub async fn changed(&mut self) -> bool {
+        let _ = self.leader.changed().await;
+        self.is_leader()
+    }
+
+    /// Stop the job and hand the lease to another replica immediately.
+    pub async fn stop(self) {
+        let _ = self.stop.send(true);
+        let _ = self.handle.await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn counting_job(counter: Arc<AtomicUsize>) -> impl Fn() -> JoinHandle<()> {
+        move || {
+            let counter = counter.clone();
+            tokio::spawn(async move {
+                loop {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+            })
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_single_leader_and_failover() {
+        let backend: Arc<dyn LeaseBackend> = Arc::new(InMemoryLeaseBackend::new());
+        let ttl = Duration::from_secs(3);
+        let a = Arc::new(
+            LeaderElector::new(backend.clone())
+                .with_holder("a")
+                .with_ttl(ttl),
+        );
+        let b = Arc::new(
+            LeaderElector::new(backend.clone())
+                .with_holder("b")
+                .with_ttl(ttl),
+        );
+
+        let runs_a = Arc::new(AtomicUsize::new(0));
+        let runs_b = Arc::new(AtomicUsize::new(0));
+        let mut task_a = a.gate("anchor", counting_job(runs_a.clone()));
+        assert!(task_a.changed().await);
+        let mut task_b = b.gate("anchor", counting_job(runs_b.clone()));
+
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        assert!(task_a.is_leader());
+        assert!(!task_b.is_leader());
+        assert_eq!(runs_b.load(Ordering::SeqCst), 0);
+
+        // Graceful stop releases the lease for the standby
+        task_a.stop().await;
+        assert!(task_b.changed().await);
+        let before = runs_a.load(Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert_eq!(runs_a.load(Ordering::SeqCst), before);
+        assert!(runs_b.load(Ordering::SeqCst) > 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_expired_lease_fails_over() {
+        let backend = InMemoryLeaseBackend::new();
+        let ttl = Duration::from_secs(3);
+        assert!(backend.try_acquire("recrawl", "a", ttl).await.unwrap());
+        assert!(!backend.try_acquire("recrawl", "b", ttl).await.unwrap());
+        assert!(backend.try_acquire("recrawl", "a", ttl).await.unwrap());
+
+        // Holder "a" crashes without releasing
+        tokio::time::sleep(ttl + Duration::from_millis(1)).await;
+        assert!(backend.try_acquire("recrawl", "b", ttl).await.unwrap());
+        assert!(!backend.try_acquire("recrawl", "a", ttl).await.unwrap());
+    }
+}
@@ -0,0 +1,13 @@
+#![deny(unused)]
+//! Process runtime coordination for OpenCoordex replicas.
+//!
+//! Provides lease-based leader election so that singleton background jobs
+//! (usage alerts, audit anchoring, spend caps, knowledge re-crawl) run on
+//! exactly one replica and fail over when it goes away, and event bus
+//! backends (NATS, Kafka) that share one event stream across replicas.
+
+pub mod bus;
+pub mod leader;
+
+pub use bus::{ChannelEventEmitter, EventBus};
+pub use leader::{GatedTask, InMemoryLeaseBackend, LeaderElector, LeaseBackend, RedisLeaseBackend};
@@ -12,6 +12,9 @@ use std::time::Duration;
 
 use multi_agent_core::Result;
 
+use crate::security::{HostSecurity, RunAs, SecurityProfiles, UserNamespace};
+use crate::terminal::{TerminalInput, TerminalSession, TerminalSize};
+
 // =============================================================================
 // Sandbox Types
 // =============================================================================
@@ -48,12 +51,23 @@ pub struct SandboxConfig {
     pub memory_limit: i64,
     /// CPU period/quota (default: 1 core equivalent).
     pub cpu_quota: i64,
+    /// Maximum number of processes (default: 100).
+    pub pids_limit: i64,
     /// Default execution timeout.
     pub default_timeout: Duration,
     /// Network isolation profile.
     pub network_profile: NetworkProfile,
     /// Working directory inside the container.
     pub workdir: String,
+    /// Seccomp/AppArmor/SELinux profiles, per image.
+    #[serde(default)]
+    pub security: SecurityProfiles,
+    /// UID/GID processes run as (default: 1000:1000).
+    #[serde(default)]
+    pub run_as: RunAs,
+    /// User namespace mode.
+    #[serde(default)]
+    pub user_namespace: UserNamespace,
 }
 
 impl Default for SandboxConfig {
@@ -62,9 +76,13 @@ impl Default for SandboxConfig {
             image: "opencoordex-sandbox:latest".to_string(),
             memory_limit: 512 * 1024 * 1024, // 512MB
             cpu_quota: 100_000,              // 1 CPU core
+            pids_limit: 100,
             default_timeout: Duration::from_secs(30),
             network_profile: NetworkProfile::None,
             workdir: "/workspace".to_string(),
+            security: SecurityProfiles::default(),
+            run_as: RunAs::default(),
+            user_namespace: UserNamespace::default(),
         }
     }
 }
@@ -116,8 +134,62 @@ pub trait SandboxEngine: Send + Sync {
 
     /// Check if the sandbox backend is available (e.g., Docker daemon running).
     async fn is_available(&self) -> bool;
+
+    /// Open an interactive PTY shell in the sandbox.
+    async fn open_terminal(&self, _id: &SandboxId, _size: TerminalSize) -> Result<TerminalSession> {
+        Err(multi_agent_core::Error::tool_execution(
+            "Interactive terminals are not supported by this sandbox engine",
+        ))
+    }
+
+    /// Verify the backend can honour `config` (e.g. user namespace support).
+    async fn check_config(&self, _config: &SandboxConfig) -> Result<()> {
+        Ok(())
+    }
+
+    /// Archive the workdir so it can be restored into another sandbox.
+    async fn snapshot(&self, id: &SandboxId) -> Result<Vec<u8>> {
+        let result = self
+            .exec(id, "tar -czf - . | base64 -w0", SNAPSHOT_TIMEOUT)
+            .await?;
+        if !result.success() {
+            return Err(multi_agent_core::Error::tool_execution(format!(
+                "Sandbox snapshot failed: {}",
+                result.stderr.trim()
+            )));
+        }
+        base64::engine::general_purpose::STANDARD
+            .decode(result.stdout.trim())
+            .map_err(|e| {
+                multi_agent_core::Error::tool_execution(format!("Invalid sandbox snapshot: {}", e))
+            })
+    }
+
+    /// Unpack a [`SandboxEngine::snapshot`] archive into the workdir.
+    async fn restore(&self, id: &SandboxId, archive: &[u8]) -> Result<()> {
+        self.write_file(id, SNAPSHOT_FILE, archive).await?;
+        let result = self
+            .exec(
+                id,
+                &format!("tar -xzf {0} && rm -f {0}", SNAPSHOT_FILE),
+                SNAPSHOT_TIMEOUT,
+            )
+            .await?;
+        if !result.success() {
+            return Err(multi_agent_core::Error::tool_execution(format!(
+                "Sandbox restore failed: {}",
+                result.stderr.trim()
+            )));
+        }
+        Ok(())
+    }
 }
 
+/// Archive name used while restoring a snapshot.
+const SNAPSHOT_FILE: &str = ".sandbox-snapshot.tgz";
+/// Time allowed for archiving or unpacking the workdir.
+const SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(120);
+
 // =============================================================================
 // Docker Sandbox Implementation
 // =============================================================================
@@ -129,6 +201,7 @@ pub trait SandboxEngine: Send + Sync {
 /// - Read-only root filesystem (writable `/workspace` only)
 /// - Memory and CPU limits
 /// - Non-root user execution
+/// - Seccomp filtering and optional AppArmor/SELinux confinement
 /// - Execution timeout enforcement
 pub struct DockerSandbox {
     docker: bollard::Docker,
@@ -166,6 +239,11 @@ impl DockerSandbox {
             event_emitter: None,
         }
     }
+
+    /// Security features the Docker host can enforce.
+    pub async fn host_security(&self) -> Result<HostSecurity> {
+        HostSecurity::detect(&self.docker).await
+    }
 }
 
 #[async_trait]
@@ -175,6 +253,7 @@ impl SandboxEngine for DockerSandbox {
         use bollard::models::{HostConfig, Mount, MountTypeEnum};
 
         let sandbox_id = format!("msa-sandbox-{}", uuid::Uuid::new_v4());
+        let security_opt = config.security.for_image(&config.image).security_opts()?;
 
         let host_config = HostConfig {
             memory: Some(config.memory_limit),
@@ -197,12 +276,16 @@ impl SandboxEngine for DockerSandbox {
                 ..Default::default()
             }]),
             readonly_rootfs: Some(true),
+            userns_mode: match config.user_namespace {
+                UserNamespace::Host => Some("host".to_string()),
+                UserNamespace::Default | UserNamespace::Remapped => None,
+            },
             // Drop all capabilities by default
             cap_drop: Some(vec!["ALL".to_string()]),
-            // Security: no privilege escalation
-            security_opt: Some(vec!["no-new-privileges:true".to_string()]),
+            // Security: no privilege escalation, seccomp and MAC labels
+            security_opt: Some(security_opt),
             // Resource limits: prevent fork bombs and too many open files
-            pids_limit: Some(100),
+            pids_limit: Some(config.pids_limit),
             ulimits: Some(vec![bollard::models::ResourcesUlimits {
                 name: Some("nofile".to_string()),
                 soft: Some(1024),
@@ -214,7 +297,7 @@ impl SandboxEngine for DockerSandbox {
         let container_config = Config {
             image: Some(config.image.clone()),
             working_dir: Some(config.workdir.clone()),
-            user: Some("agent".to_string()), // non-root
+            user: Some(config.run_as.docker_user()), // non-root
             cmd: Some(vec!["sleep".to_string(), "infinity".to_string()]),
             host_config: Some(host_config),
             labels: Some(std::collections::HashMap::from([(
@@ -263,7 +346,6 @@ impl SandboxEngine for DockerSandbox {
             attach_stdout: Some(true),
             attach_stderr: Some(true),
             working_dir: Some("/workspace"),
-            user: Some("agent"),
             ..Default::default()
         };
 
@@ -350,6 +432,8 @@ impl SandboxEngine for DockerSandbox {
                 } else {
                     Some(exec_result.stderr.clone())
                 },
+                success: Some(exec_result.success()),
+                output_len: None,
             };
             emitter
                 .emit(
@@ -486,6 +570,122 @@ impl SandboxEngine for DockerSandbox {
     async fn is_available(&self) -> bool {
         self.docker.ping().await.is_ok()
     }
+
+    async fn check_config(&self, config: &SandboxConfig) -> Result<()> {
+        if config.user_namespace == UserNamespace::Remapped {
+            let host = self.host_security().await?;
+            // Rootless Docker runs every container inside its own user namespace
+            if !host.userns && !host.rootless {
+                return Err(multi_agent_core::Error::invalid_request(
+                    "Sandbox requires user namespace remapping but the Docker daemon \
+                     is neither rootless nor running with userns-remap",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    async fn open_terminal(&self, id: &SandboxId, size: TerminalSize) -> Result<TerminalSession> {
+        use bollard::exec::{
+            CreateExecOptions, ResizeExecOptions, StartExecOptions, StartExecResults,
+        };
+        use futures::StreamExt;
+        use tokio::io::AsyncWriteExt;
+
+        let exec = self
+            .docker
+            .create_exec(
+                &id.0,
+                CreateExecOptions {
+                    cmd: Some(vec!["sh", "-l"]),
+                    attach_stdin: Some(true),
+                    attach_stdout: Some(true),
+                    attach_stderr: Some(true),
+                    tty: Some(true),
+                    env: Some(vec!["TERM=xterm-256color"]),
+                    working_dir: Some("/workspace"),
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(|e| {
+                multi_agent_core::Error::tool_execution(format!(
+                    "Failed to create terminal in sandbox: {}",
+                    e
+                ))
+            })?;
+
+        let started = self
+            .docker
+            .start_exec(
+                &exec.id,
+                Some(StartExecOptions {
+                    detach: false,
+                    tty: true,
+                    output_capacity: None,
+                }),
+            )
+            .await
+            .map_err(|e| {
+                multi_agent_core::Error::tool_execution(format!(
+                    "Failed to start terminal in sandbox: {}",
+                    e
+                ))
+            })?;
+        let StartExecResults::Attached {
+            mut output,
+            mut input,
+        } = started
+        else {
+            return Err(multi_agent_core::Error::tool_execution(
+                "Terminal exec did not attach",
+            ));
+        };
+
+        let (session, mut input_rx, output_tx) = TerminalSession::channel();
+        let resize = |docker: bollard::Docker, exec_id: String, size: TerminalSize| async move {
+            let options = ResizeExecOptions {
+                height: size.rows,
+                width: size.cols,
+            };
+            if let Err(e) = docker.resize_exec(&exec_id, options).await {
+                tracing::debug!(error = %e, "Failed to resize sandbox terminal");
+            }
+        };
+        resize(self.docker.clone(), exec.id.clone(), size).await;
+
+        tokio::spawn(async move {
+            while let Some(Ok(chunk)) = output.next().await {
+                if output_tx.send(chunk.into_bytes().to_vec()).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let docker = self.docker.clone();
+        let exec_id = exec.id;
+        tokio::spawn(async move {
+            while let Some(msg) = input_rx.recv().await {
+                match msg {
+                    TerminalInput::Data(data) => {
+                        if input.write_all(&data).await.is_err() {
+                            break;
+                        }
+                        let _ = input.flush().await;
+                    }
+                    TerminalInput::Resize(size) => {
+                        resize(docker.clone(), exec_id.clone(), size).await;
+                    }
+                }
+            }
+            // Input closed: end the shell
+            let _ = input.write_all(b"\x04").await;
+            let _ = input.shutdown().await;
+        });
+
+        tracing::info!(sandbox_id = %id, "Sandbox terminal opened");
+        Ok(session)
+    }
 }
 
 // =============================================================================
@@ -497,6 +697,10 @@ impl SandboxEngine for DockerSandbox {
 pub struct MockSandbox {
     pub exec_responses: std::sync::Arc<tokio::sync::Mutex<Vec<ExecResult>>>,
     pub files: std::sync::Arc<tokio::sync::Mutex<std::collections::HashMap<String, Vec<u8>>>>,
+    /// Configs passed to `create`, in order.
+    pub created: std::sync::Arc<tokio::sync::Mutex<Vec<SandboxConfig>>>,
+    /// IDs passed to `destroy`, in order.
+    pub destroyed: std::sync::Arc<tokio::sync::Mutex<Vec<SandboxId>>>,
 }
 
 impl MockSandbox {
@@ -505,13 +709,16 @@ impl MockSandbox {
         Self {
             exec_responses: std::sync::Arc::new(tokio::sync::Mutex::new(responses)),
             files: Default::default(),
+            created: Default::default(),
+            destroyed: Default::default(),
         }
     }
 }
 
 #[async_trait]
 impl SandboxEngine for MockSandbox {
-    async fn create(&self, _config: &SandboxConfig) -> Result<SandboxId> {
+    async fn create(&self, config: &SandboxConfig) -> Result<SandboxId> {
+        self.created.lock().await.push(config.clone());
         Ok(SandboxId(format!("mock-sandbox-{}", uuid::Uuid::new_v4())))
     }
 
@@ -551,13 +758,41 @@ impl SandboxEngine for MockSandbox {
         })
     }
 
-    async fn destroy(&self, _id: &SandboxId) -> Result<()> {
+    async fn destroy(&self, id: &SandboxId) -> Result<()> {
+        self.destroyed.lock().await.push(id.clone());
         Ok(())
     }
 
     async fn is_available(&self) -> bool {
         true
     }
+
+    /// The files map serialized as JSON.
+    async fn snapshot(&self, _id: &SandboxId) -> Result<Vec<u8>> {
+        serde_json::to_vec(&*self.files.lock().await)
+            .map_err(|e| multi_agent_core::Error::internal(e.to_string()))
+    }
+
+    async fn restore(&self, _id: &SandboxId, archive: &[u8]) -> Result<()> {
+        *self.files.lock().await = serde_json::from_slice(archive)
+            .map_err(|e| multi_agent_core::Error::tool_execution(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Echoes input back as output.
+    async fn open_terminal(&self, _id: &SandboxId, _size: TerminalSize) -> Result<TerminalSession> {
+        let (session, mut input_rx, output_tx) = TerminalSession::channel();
+        tokio::spawn(async move {
+            while let Some(msg) = input_rx.recv().await {
+                if let TerminalInput::Data(data) = msg {
+                    if output_tx.send(data).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+        Ok(session)
+    }
 }
 
 // =============================================================================
@@ -0,0 +1,758 @@
+//! Kubernetes sandbox engine.
+//!
+//! For server deployments where no Docker socket is available, each sandbox
+//! is an ephemeral Kubernetes Job whose single pod idles until commands are
+//! exec'd into it through the API server:
+//!
+//! - Resource limits derived from [`SandboxConfig`] (memory, CPU)
+//! - A deny-all `NetworkPolicy` when the network profile is `None`
+//! - An in-memory `emptyDir` as the only writable volume
+//! - Non-root, read-only root filesystem, no capabilities, seccomp
+//! - `activeDeadlineSeconds` and `ttlSecondsAfterFinished` so leaked
+//!   sandboxes are reaped by the cluster
+//!
+//! Files are transferred as raw blobs over the exec stream (stdin for
+//! writes, base64 on stdout for reads), so no shared volume is needed.
+//! Interactive terminals use the same stream with a TTY allocated.
+
+use async_trait::async_trait;
+use base64::Engine;
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_tungstenite::tungstenite::{client::IntoClientRequest, Message};
+
+use multi_agent_core::Result;
+
+use crate::engine::{ExecResult, NetworkProfile, SandboxConfig, SandboxEngine, SandboxId};
+use crate::security::{SeccompProfile, UserNamespace};
+use crate::terminal::{TerminalInput, TerminalSession, TerminalSize};
+
+const SERVICE_ACCOUNT_DIR: &str = "/var/run/secrets/kubernetes.io/serviceaccount";
+const SANDBOX_LABEL: &str = "opencoordex.io/sandbox";
+const NETWORK_LABEL: &str = "opencoordex.io/network";
+const CONTAINER_NAME: &str = "sandbox";
+
+type ExecStream =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// Connection and lifecycle settings for [`K8sSandbox`].
+#[derive(Debug, Clone)]
+pub struct K8sSandboxConfig {
+    /// API server base URL, e.g. `https://kubernetes.default.svc`.
+    pub api_server: String,
+    /// Namespace sandboxes are created in.
+    pub namespace: String,
+    /// Bearer token for the API server.
+    pub token: Option<String>,
+    /// PEM-encoded CA bundle for the API server.
+    pub ca_cert_pem: Option<Vec<u8>>,
+    /// Service account the sandbox pods run as.
+    pub service_account: Option<String>,
+    /// Hard lifetime of a sandbox Job.
+    pub active_deadline_seconds: i64,
+    /// How long finished Jobs are kept before the cluster deletes them.
+    pub ttl_seconds_after_finished: i32,
+    /// How long to wait for the sandbox pod to start.
+    pub startup_timeout: Duration,
+}
+
+impl Default for K8sSandboxConfig {
+    fn default() -> Self {
+        Self {
+            api_server: "https://kubernetes.default.svc".to_string(),
+            namespace: "default".to_string(),
+            token: None,
+            ca_cert_pem: None,
+            service_account: None,
+            active_deadline_seconds: 3600,
+            ttl_seconds_after_finished: 60,
+            startup_timeout: Duration::from_secs(120),
+        }
+    }
+}
+
+impl K8sSandboxConfig {
+    /// Load settings from the pod's service account (in-cluster config).
+    ///
+    /// `SANDBOX_K8S_NAMESPACE` overrides the namespace the gateway runs in.
+    pub fn in_cluster() -> Result<Self> {
+        let host = std::env::var("KUBERNETES_SERVICE_HOST")
+            .map_err(|_| multi_agent_core::Error::internal("KUBERNETES_SERVICE_HOST is not set"))?;
+        let port = std::env::var("KUBERNETES_SERVICE_PORT").unwrap_or_else(|_| "443".to_string());
+        let read = |file: &str| std::fs::read(format!("{}/{}", SERVICE_ACCOUNT_DIR, file));
+
+        let token = read("token").map_err(|e| {
+            multi_agent_core::Error::internal(format!(
+                "Failed to read service account token: {}",
+                e
+            ))
+        })?;
+        let namespace = match std::env::var("SANDBOX_K8S_NAMESPACE") {
+            Ok(ns) => ns,
+            Err(_) => read("namespace")
+                .map(|ns| String::from_utf8_lossy(&ns).trim().to_string())
+                .unwrap_or_else(|_| "default".to_string()),
+        };
+
+        Ok(Self {
+            api_server: format!("https://{}:{}", host, port),
+            namespace,
+            token: Some(String::from_utf8_lossy(&token).trim().to_string()),
+            ca_cert_pem: read("ca.crt").ok(),
+            ..Self::default()
+        })
+    }
+}
+
+/// Kubernetes-based sandbox engine.
+pub struct K8sSandbox {
+    config: K8sSandboxConfig,
+    http: reqwest::Client,
+    tls: Option<Arc<rustls::ClientConfig>>,
+    /// Pod backing each sandbox Job.
+    pods: tokio::sync::RwLock<HashMap<SandboxId, String>>,
+}
+
+impl K8sSandbox {
+    /// Create an engine for the given cluster.
+    pub fn new(config: K8sSandboxConfig) -> Result<Self> {
+        let mut http = reqwest::Client::builder();
+        let mut tls = None;
+
+        if let Some(ref pem) = config.ca_cert_pem {
+            let invalid_ca = |e: &dyn std::fmt::Display| {
+                multi_agent_core::Error::internal(format!(
+                    "Invalid Kubernetes CA certificate: {}",
+                    e
+                ))
+            };
+            let cert = reqwest::Certificate::from_pem(pem).map_err(|e| invalid_ca(&e))?;
+            http = http.add_root_certificate(cert);
+
+            let mut roots = rustls::RootCertStore::empty();
+            for cert in rustls_pemfile::certs(&mut pem.as_slice()) {
+                roots
+                    .add(cert.map_err(|e| invalid_ca(&e))?)
+                    .map_err(|e| invalid_ca(&e))?;
+            }
+            let provider = Arc::new(rustls::crypto::ring::default_provider());
+            let client_config = rustls::ClientConfig::builder_with_provider(provider)
+                .with_safe_default_protocol_versions()
+                .map_err(|e| multi_agent_core::Error::internal(e.to_string()))?
+                .with_root_certificates(roots)
+                .with_no_client_auth();
+            tls = Some(Arc::new(client_config));
+        }
+
+        let http = http.build().map_err(|e| {
+            multi_agent_core::Error::internal(format!("Failed to build Kubernetes client: {}", e))
+        })?;
+
+        Ok(Self {
+            config,
+            http,
+            tls,
+            pods: Default::default(),
+        })
+    }
+
+    /// Create an engine from the in-cluster service account.
+    pub fn in_cluster() -> Result<Self> {
+        Self::new(K8sSandboxConfig::in_cluster()?)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.config.api_server.trim_end_matches('/'), path)
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let req = self.http.request(method, self.url(path));
+        match self.config.token {
+            Some(ref token) => req.bearer_auth(token),
+            None => req,
+        }
+    }
+
+    async fn send(&self, req: reqwest::RequestBuilder, what: &str) -> Result<Value> {
+        let resp = req.send().await.map_err(|e| {
+            multi_agent_core::Error::internal(format!("Kubernetes API error ({}): {}", what, e))
+        })?;
+        let status = resp.status();
+        let body: Value = resp.json().await.unwrap_or(Value::Null);
+        if !status.is_success() {
+            return Err(multi_agent_core::Error::internal(format!(
+                "Kubernetes API error ({}): {} {}",
+                what,
+                status,
+                body["message"].as_str().unwrap_or_default()
+            )));
+        }
+        Ok(body)
+    }
+
+    async fn wait_for_pod(&self, name: &str) -> Result<String> {
+        let path = format!(
+            "/api/v1/namespaces/{}/pods?labelSelector={}%3D{}",
+            self.config.namespace, SANDBOX_LABEL, name
+        );
+        let deadline = tokio::time::Instant::now() + self.config.startup_timeout;
+
+        loop {
+            let pods = self
+                .send(self.request(reqwest::Method::GET, &path), "list pods")
+                .await?;
+            for pod in pods["items"].as_array().into_iter().flatten() {
+                match pod["status"]["phase"].as_str() {
+                    Some("Running") => {
+                        return Ok(pod["metadata"]["name"]
+                            .as_str()
+                            .unwrap_or_default()
+                            .to_string())
+                    }
+                    Some("Failed") | Some("Succeeded") => {
+                        return Err(multi_agent_core::Error::internal(format!(
+                            "Sandbox pod for '{}' exited before it was ready",
+                            name
+                        )))
+                    }
+                    _ => {}
+                }
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(multi_agent_core::Error::internal(format!(
+                    "Sandbox pod for '{}' did not start within {:?}",
+                    name, self.config.startup_timeout
+                )));
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+    }
+
+    async fn pod_for(&self, id: &SandboxId) -> Result<String> {
+        self.pods.read().await.get(id).cloned().ok_or_else(|| {
+            multi_agent_core::Error::tool_execution(format!("Unknown sandbox: {}", id))
+        })
+    }
+
+    /// Open the exec WebSocket for `argv` in the sandbox pod.
+    async fn connect_exec(
+        &self,
+        id: &SandboxId,
+        argv: &[&str],
+        stdin: bool,
+        tty: bool,
+    ) -> Result<ExecStream> {
+        let pod = self.pod_for(id).await?;
+
+        let mut url = reqwest::Url::parse(&self.url(&format!(
+            "/api/v1/namespaces/{}/pods/{}/exec",
+            self.config.namespace, pod
+        )))
+        .map_err(|e| multi_agent_core::Error::internal(e.to_string()))?;
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("container", CONTAINER_NAME);
+            for arg in argv {
+                query.append_pair("command", arg);
+            }
+            query.append_pair("stdout", "true");
+            // With a TTY, stderr is merged into stdout
+            query.append_pair("stderr", if tty { "false" } else { "true" });
+            if stdin {
+                query.append_pair("stdin", "true");
+            }
+            if tty {
+                query.append_pair("tty", "true");
+            }
+        }
+        let scheme = if url.scheme() == "https" { "wss" } else { "ws" };
+        let _ = url.set_scheme(scheme);
+
+        let mut request = url
+            .as_str()
+            .into_client_request()
+            .map_err(|e| multi_agent_core::Error::internal(e.to_string()))?;
+        let headers = request.headers_mut();
+        headers.insert(
+            "Sec-WebSocket-Protocol",
+            "v4.channel.k8s.io".parse().expect("static header"),
+        );
+        if let Some(ref token) = self.config.token {
+            if let Ok(value) = format!("Bearer {}", token).parse() {
+                headers.insert("Authorization", value);
+            }
+        }
+
+        let connector = self.tls.clone().map(tokio_tungstenite::Connector::Rustls);
+        let (ws, _) =
+            tokio_tungstenite::connect_async_tls_with_config(request, None, false, connector)
+                .await
+                .map_err(|e| {
+                    multi_agent_core::Error::tool_execution(format!(
+                        "Failed to start exec in sandbox: {}",
+                        e
+                    ))
+                })?;
+        Ok(ws)
+    }
+
+    /// Run `argv` in the sandbox pod over the exec WebSocket, optionally
+    /// feeding `stdin`.
+    async fn exec_argv(
+        &self,
+        id: &SandboxId,
+        argv: &[&str],
+        stdin: Option<&[u8]>,
+        timeout: Duration,
+    ) -> Result<ExecResult> {
+        let mut ws = self.connect_exec(id, argv, stdin.is_some(), false).await?;
+
+        if let Some(data) = stdin {
+            for chunk in data.chunks(64 * 1024) {
+                let mut frame = Vec::with_capacity(chunk.len() + 1);
+                frame.push(0u8);
+                frame.extend_from_slice(chunk);
+                ws.send(Message::Binary(frame)).await.map_err(|e| {
+                    multi_agent_core::Error::tool_execution(format!(
+                        "Failed to stream stdin to sandbox: {}",
+                        e
+                    ))
+                })?;
+            }
+        }
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut status = None;
+
+        let collect = async {
+            while let Some(msg) = ws.next().await {
+                let frame = match msg {
+                    Ok(Message::Binary(frame)) => frame,
+                    Ok(Message::Close(_)) => break,
+                    Ok(_) => continue,
+                    Err(e) => {
+                        stderr.extend_from_slice(format!("\n[sandbox error: {}]", e).as_bytes());
+                        break;
+                    }
+                };
+                match frame.split_first() {
+                    Some((1, data)) => stdout.extend_from_slice(data),
+                    Some((2, data)) => stderr.extend_from_slice(data),
+                    Some((3, data)) => status = Some(parse_exit_status(data)),
+                    _ => {}
+                }
+            }
+        };
+
+        if tokio::time::timeout(timeout, collect).await.is_err() {
+            tracing::warn!(sandbox = %id, "Sandbox exec timed out");
+            return Ok(ExecResult {
+                exit_code: -1,
+                stdout: String::from_utf8_lossy(&stdout).into_owned(),
+                stderr: format!(
+                    "{}\n[Execution timed out after {:?}]",
+                    String::from_utf8_lossy(&stderr),
+                    timeout
+                ),
+                timed_out: true,
+            });
+        }
+
+        Ok(ExecResult {
+            exit_code: status.unwrap_or(-1),
+            stdout: String::from_utf8_lossy(&stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&stderr).into_owned(),
+            timed_out: false,
+        })
+    }
+}
+
+#[async_trait]
+impl SandboxEngine for K8sSandbox {
+    async fn create(&self, config: &SandboxConfig) -> Result<SandboxId> {
+        let name = format!("msa-sandbox-{}", uuid::Uuid::new_v4());
+        let ns = &self.config.namespace;
+
+        let job = job_manifest(&name, config, &self.config)?;
+        self.send(
+            self.request(
+                reqwest::Method::POST,
+                &format!("/apis/batch/v1/namespaces/{}/jobs", ns),
+            )
+            .json(&job),
+            "create job",
+        )
+        .await?;
+
+        if let Some(policy) = network_policy_manifest(&name, &config.network_profile) {
+            self.send(
+                self.request(
+                    reqwest::Method::POST,
+                    &format!(
+                        "/apis/networking.k8s.io/v1/namespaces/{}/networkpolicies",
+                        ns
+                    ),
+                )
+                .json(&policy),
+                "create network policy",
+            )
+            .await?;
+        }
+
+        let id = SandboxId(name.clone());
+        let pod = match self.wait_for_pod(&name).await {
+            Ok(pod) => pod,
+            Err(e) => {
+                self.pods.write().await.insert(id.clone(), String::new());
+                let _ = self.destroy(&id).await;
+                return Err(e);
+            }
+        };
+        self.pods.write().await.insert(id.clone(), pod.clone());
+
+        tracing::info!(sandbox_id = %name, pod = %pod, namespace = %ns, image = %config.image, "Sandbox job created and running");
+        Ok(id)
+    }
+
+    async fn exec(&self, id: &SandboxId, command: &str, timeout: Duration) -> Result<ExecResult> {
+        self.exec_argv(id, &["sh", "-c", command], None, timeout)
+            .await
+    }
+
+    async fn write_file(&self, id: &SandboxId, path: &str, content: &[u8]) -> Result<()> {
+        // `head -c` reads exactly the payload, so stdin never needs closing
+        let command = format!(
+            "head -c {} > '/workspace/{}'",
+            content.len(),
+            path.trim_start_matches('/').replace('\'', r"'\''")
+        );
+        let result = self
+            .exec_argv(
+                id,
+                &["sh", "-c", &command],
+                Some(content),
+                Duration::from_secs(30),
+            )
+            .await?;
+
+        if !result.success() {
+            return Err(multi_agent_core::Error::tool_execution(format!(
+                "Failed to write file '{}' in sandbox: {}",
+                path, result.stderr
+            )));
+        }
+        Ok(())
+    }
+
+    async fn read_file(&self, id: &SandboxId, path: &str) -> Result<Vec<u8>> {
+        let command = format!(
+            "base64 '/workspace/{}'",
+            path.trim_start_matches('/').replace('\'', r"'\''")
+        );
+        let result = self.exec(id, &command, Duration::from_secs(30)).await?;
+
+        if !result.success() {
+            return Err(multi_agent_core::Error::tool_execution(format!(
+                "Failed to read file '{}' in sandbox: {}",
+                path, result.stderr
+            )));
+        }
+
+        let encoded: String = result.stdout.split_whitespace().collect();
+        base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|e| {
+                multi_agent_core::Error::tool_execution(format!(
+                    "Failed to decode file '{}' from sandbox: {}",
+                    path, e
+                ))
+            })
+    }
+
+    async fn destroy(&self, id: &SandboxId) -> Result<()> {
+        let ns = &self.config.namespace;
+        self.pods.write().await.remove(id);
+
+        // The network policy may not exist (non-`None` profiles)
+        let _ = self
+            .request(
+                reqwest::Method::DELETE,
+                &format!(
+                    "/apis/networking.k8s.io/v1/namespaces/{}/networkpolicies/{}",
+                    ns, id.0
+                ),
+            )
+            .send()
+            .await;
+
+        self.send(
+            self.request(
+                reqwest::Method::DELETE,
+                &format!(
+                    "/apis/batch/v1/namespaces/{}/jobs/{}?propagationPolicy=Background",
+                    ns, id.0
+                ),
+            ),
+            "delete job",
+        )
+        .await?;
+
+        tracing::info!(sandbox_id = %id, "Sandbox job deleted");
+        Ok(())
+    }
+
+    async fn is_available(&self) -> bool {
+        self.request(reqwest::Method::GET, "/version")
+            .send()
+            .await
+            .map(|r| r.status().is_success())
+            .unwrap_or(false)
+    }
+
+    async fn open_terminal(&self, id: &SandboxId, size: TerminalSize) -> Result<TerminalSession> {
+        let ws = self.connect_exec(id, &["sh", "-l"], true, true).await?;
+        let (mut sink, mut stream) = ws.split();
+        let (session, mut input_rx, output_tx) = TerminalSession::channel();
+
+        tokio::spawn(async move {
+            while let Some(Ok(Message::Binary(frame))) = stream.next().await {
+                if let Some((1, data)) = frame.split_first() {
+                    if output_tx.send(data.to_vec()).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        tokio::spawn(async move {
+            let mut pending = Some(TerminalInput::Resize(size));
+            loop {
+                let msg = match pending.take() {
+                    Some(msg) => msg,
+                    None => match input_rx.recv().await {
+                        Some(msg) => msg,
+                        None => break,
+                    },
+                };
+                // Channel 0 is stdin, channel 4 carries resize events
+                let frame = match msg {
+                    TerminalInput::Data(data) => [vec![0u8], data].concat(),
+                    TerminalInput::Resize(size) => [
+                        vec![4u8],
+                        json!({ "Width": size.cols, "Height": size.rows })
+                            .to_string()
+                            .into_bytes(),
+                    ]
+                    .concat(),
+                };
+                if sink.send(Message::Binary(frame)).await.is_err() {
+                    break;
+                }
+            }
+            let _ = sink.send(Message::Binary(vec![0u8, 0x04])).await;
+            let _ = sink.close().await;
+        });
+
+        tracing::info!(sandbox_id = %id, "Sandbox terminal opened");
+        Ok(session)
+    }
+}
+
+/// Job manifest for a sandbox.
+fn job_manifest(name: &str, config: &SandboxConfig, k8s: &K8sSandboxConfig) -> Result<Value> {
+    let security = config.security.for_image(&config.image);
+
+    let mut labels = json!({
+        "managed-by": "opencoordex-sandbox",
+        SANDBOX_LABEL: name,
+    });
+    match &config.network_profile {
+        NetworkProfile::Host => {
+            return Err(multi_agent_core::Error::invalid_request(
+                "Host networking is not supported for Kubernetes sandboxes",
+            ))
+        }
+        NetworkProfile::Custom(network) => labels[NETWORK_LABEL] = json!(network),
+        NetworkProfile::None | NetworkProfile::Bridge => {}
+    }
+
+    let seccomp = match &security.seccomp {
+        SeccompProfile::Builtin | SeccompProfile::DockerDefault => {
+            json!({ "type": "RuntimeDefault" })
+        }
+        // Path relative to the kubelet's seccomp directory
+        SeccompProfile::File(path) => {
+            json!({ "type": "Localhost", "localhostProfile": path.to_string_lossy() })
+        }
+        SeccompProfile::Unconfined => json!({ "type": "Unconfined" }),
+    };
+
+    let mut security_context = json!({
+        "runAsNonRoot": true,
+        "runAsUser": config.run_as.uid,
+        "runAsGroup": config.run_as.gid,
+        "allowPrivilegeEscalation": false,
+        "readOnlyRootFilesystem": true,
+        "capabilities": { "drop": ["ALL"] },
+        "seccompProfile": seccomp,
+    });
+    if let Some(ref profile) = security.apparmor {
+        security_context["appArmorProfile"] =
+            json!({ "type": "Localhost", "localhostProfile": profile });
+    }
+    if let Some(ref label) = security.selinux_label {
+        let options: serde_json::Map<String, Value> = label
+            .split(',')
+            .filter_map(|kv| kv.split_once(':'))
+            .map(|(k, v)| (k.to_string(), json!(v)))
+            .collect();
+        security_context["seLinuxOptions"] = Value::Object(options);
+    }
+
+    let memory = config.memory_limit.to_string();
+    let cpu = format!("{}m", (config.cpu_quota / 100).max(1));
+
+    let mut pod_spec = json!({
+        "restartPolicy": "Never",
+        "automountServiceAccountToken": false,
+        "enableServiceLinks": false,
+        "containers": [{
+            "name": CONTAINER_NAME,
+            "image": config.image,
+            "command": ["sleep", "infinity"],
+            "workingDir": config.workdir,
+            "securityContext": security_context,
+            "resources": {
+                "limits": { "memory": memory, "cpu": cpu },
+                "requests": { "memory": memory, "cpu": cpu },
+            },
+            "volumeMounts": [{ "name": "workspace", "mountPath": config.workdir }],
+        }],
+        "volumes": [{
+            "name": "workspace",
+            "emptyDir": { "medium": "Memory", "sizeLimit": (config.memory_limit / 2).to_string() },
+        }],
+    });
+    match config.user_namespace {
+        UserNamespace::Remapped => pod_spec["hostUsers"] = json!(false),
+        UserNamespace::Host => pod_spec["hostUsers"] = json!(true),
+        UserNamespace::Default => {}
+    }
+    if let Some(ref sa) = k8s.service_account {
+        pod_spec["serviceAccountName"] = json!(sa);
+    }
+
+    Ok(json!({
+        "apiVersion": "batch/v1",
+        "kind": "Job",
+        "metadata": { "name": name, "labels": labels },
+        "spec": {
+            "backoffLimit": 0,
+            "activeDeadlineSeconds": k8s.active_deadline_seconds,
+            "ttlSecondsAfterFinished": k8s.ttl_seconds_after_finished,
+            "template": {
+                "metadata": { "labels": labels },
+                "spec": pod_spec,
+            },
+        },
+    }))
+}
+
+/// Deny-all `NetworkPolicy` for sandboxes without network access.
+fn network_policy_manifest(name: &str, profile: &NetworkProfile) -> Option<Value> {
+    if !matches!(profile, NetworkProfile::None) {
+        return None;
+    }
+    Some(json!({
+        "apiVersion": "networking.k8s.io/v1",
+        "kind": "NetworkPolicy",
+        "metadata": {
+            "name": name,
+            "labels": { "managed-by": "opencoordex-sandbox" },
+        },
+        "spec": {
+            "podSelector": { "matchLabels": { SANDBOX_LABEL: name } },
+            "policyTypes": ["Ingress", "Egress"],
+        },
+    }))
+}
+
+/// Exit code from a `v4.channel.k8s.io` status frame.
+fn parse_exit_status(data: &[u8]) -> i64 {
+    let status: Value = serde_json::from_slice(data).unwrap_or(Value::Null);
+    if status["status"] == "Success" {
+        return 0;
+    }
+    status["details"]["causes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|c| c["reason"] == "ExitCode")
+        .and_then(|c| c["message"].as_str())
+        .and_then(|m| m.parse().ok())
+        .unwrap_or(-1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_manifest_limits_and_hardening() {
+        let config = SandboxConfig::default();
+        let job = job_manifest("msa-sandbox-1", &config, &K8sSandboxConfig::default()).unwrap();
+
+        let pod = &job["spec"]["template"]["spec"];
+        let container = &pod["containers"][0];
+        assert_eq!(container["resources"]["limits"]["memory"], "536870912");
+        assert_eq!(container["resources"]["limits"]["cpu"], "1000m");
+        assert_eq!(container["securityContext"]["runAsNonRoot"], true);
+        assert_eq!(container["securityContext"]["runAsUser"], 1000);
+        assert!(pod.get("hostUsers").is_none());
+        assert_eq!(container["securityContext"]["readOnlyRootFilesystem"], true);
+        assert_eq!(
+            container["securityContext"]["seccompProfile"]["type"],
+            "RuntimeDefault"
+        );
+        assert_eq!(pod["automountServiceAccountToken"], false);
+        assert_eq!(pod["volumes"][0]["emptyDir"]["medium"], "Memory");
+        assert_eq!(
+            job["spec"]["template"]["metadata"]["labels"][SANDBOX_LABEL],
+            "msa-sandbox-1"
+        );
+        assert_eq!(job["spec"]["backoffLimit"], 0);
+    }
+
+    #[test]
+    fn test_network_profiles() {
+        let policy = network_policy_manifest("sb", &NetworkProfile::None).unwrap();
+        assert_eq!(
+            policy["spec"]["podSelector"]["matchLabels"][SANDBOX_LABEL],
+            "sb"
+        );
+        assert!(policy["spec"].get("egress").is_none());
+        assert!(network_policy_manifest("sb", &NetworkProfile::Bridge).is_none());
+
+        let config = SandboxConfig {
+            network_profile: NetworkProfile::Host,
+            ..SandboxConfig::default()
+        };
+        assert!(job_manifest("sb", &config, &K8sSandboxConfig::default()).is_err());
+    }
+
+    #[test]
+    fn test_parse_exit_status() {
+        assert_eq!(parse_exit_status(br#"{"status":"Success"}"#), 0);
+        assert_eq!(
+            parse_exit_status(
+                br#"{"status":"Failure","reason":"NonZeroExitCode","details":{"causes":[{"reason":"ExitCode","message":"2"}]}}"#
+            ),
+            2
+        );
+        assert_eq!(parse_exit_status(b"garbage"), -1);
+    }
+}
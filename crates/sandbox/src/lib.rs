@@ -15,7 +15,7 @@
 //! │  L2: Skills (SandboxShellTool, etc.)   │
 //! │    ↓ delegates to SandboxManager       │
 //! ├────────────────────────────────────────┤
-//! │  Sandbox Engine (DockerSandbox)        │
+//! │  Sandbox Engine (Docker / K8s Job)     │
 //! │    ↓ Docker API via bollard            │
 //! ├────────────────────────────────────────┤
 //! │  Docker Container (isolated)           │
@@ -38,10 +38,22 @@
 //! registry.register(Box::new(SandboxWriteFileTool::new(manager.clone()))).await?;
 //! ```
 
+pub mod command_policy;
 pub mod engine;
+pub mod k8s;
+pub mod profiles;
+pub mod security;
+pub mod terminal;
 pub mod tools;
 
+pub use command_policy::{CommandPolicy, CommandPolicyConfig, CommandRule, CommandViolation};
 pub use engine::{DockerSandbox, ExecResult, MockSandbox, SandboxConfig, SandboxEngine, SandboxId};
+pub use k8s::{K8sSandbox, K8sSandboxConfig};
+pub use profiles::{SandboxProfiles, SandboxTier};
+pub use security::{
+    HostSecurity, RunAs, SeccompProfile, SecurityProfile, SecurityProfiles, UserNamespace,
+};
+pub use terminal::{TerminalInput, TerminalSession, TerminalSize, TranscriptRecorder};
 pub use tools::{
     SandboxListFilesTool, SandboxManager, SandboxReadFileTool, SandboxShellTool,
     SandboxWriteFileTool,
@@ -0,0 +1,186 @@
+//! Risk-tiered sandbox profiles.
+//!
+//! Maps a policy decision to where a command may run:
+//!
+//! | Risk            | Tier       | Sandbox                                      |
+//! |-----------------|------------|----------------------------------------------|
+//! | Low / Medium    | `Pooled`   | Shared, lazily created default sandbox       |
+//! | High            | `Isolated` | Fresh container per call, hardened limits    |
+//! | Critical        | `Refused`  | Not executed                                 |
+
+use serde::{Deserialize, Serialize};
+
+use multi_agent_core::config::IsolatedProfileConfig;
+use multi_agent_core::types::ToolRiskLevel;
+use multi_agent_governance::PolicyDecision;
+
+use crate::engine::{NetworkProfile, SandboxConfig};
+
+/// Where a command runs, based on its assessed risk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SandboxTier {
+    /// Reuse the manager's default sandbox.
+    Pooled,
+    /// Create a throwaway container with the isolated profile.
+    Isolated,
+    /// Do not run the command at all.
+    Refused,
+}
+
+impl SandboxTier {
+    /// Tier for a risk level.
+    pub fn for_risk(risk: ToolRiskLevel) -> Self {
+        match risk {
+            ToolRiskLevel::Low | ToolRiskLevel::Medium => Self::Pooled,
+            ToolRiskLevel::High => Self::Isolated,
+            ToolRiskLevel::Critical => Self::Refused,
+        }
+    }
+
+    /// Tier for a policy decision.
+    pub fn for_decision(decision: &PolicyDecision) -> Self {
+        Self::for_risk(decision.risk_level)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pooled => "pooled",
+            Self::Isolated => "isolated",
+            Self::Refused => "refused",
+        }
+    }
+}
+
+/// Sandbox configs per tier.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SandboxProfiles {
+    /// Config of the pooled sandbox.
+    pub pooled: SandboxConfig,
+    /// Config of per-call isolated sandboxes.
+    pub isolated: SandboxConfig,
+}
+
+impl SandboxProfiles {
+    /// Derive profiles from a base config: the pooled sandbox uses it as-is,
+    /// the isolated profile tightens memory, CPU and pids and drops network.
+    pub fn from_base(base: SandboxConfig) -> Self {
+        let isolated = SandboxConfig {
+            memory_limit: (base.memory_limit / 2).max(64 * 1024 * 1024),
+            cpu_quota: (base.cpu_quota / 2).max(10_000),
+            pids_limit: (base.pids_limit / 4).max(16),
+            network_profile: NetworkProfile::None,
+            ..base.clone()
+        };
+        Self {
+            pooled: base,
+            isolated,
+        }
+    }
+
+    /// Derive profiles from a base config, then apply the configured
+    /// isolated-profile overrides.
+    pub fn from_config(base: SandboxConfig, overrides: &IsolatedProfileConfig) -> Self {
+        let mut profiles = Self::from_base(base);
+        let isolated = &mut profiles.isolated;
+        if let Some(image) = &overrides.image {
+            isolated.image = image.clone();
+        }
+        if let Some(mb) = overrides.memory_limit_mb {
+            isolated.memory_limit = (mb * 1024 * 1024) as i64;
+        }
+        if let Some(quota) = overrides.cpu_quota {
+            isolated.cpu_quota = quota;
+        }
+        if let Some(pids) = overrides.pids_limit {
+            isolated.pids_limit = pids;
+        }
+        if let Some(secs) = overrides.timeout_secs {
+            isolated.default_timeout = std::time::Duration::from_secs(secs);
+        }
+        profiles
+    }
+
+    /// Config to use for a tier; `None` when the tier is refused.
+    pub fn config_for(&self, tier: SandboxTier) -> Option<&SandboxConfig> {
+        match tier {
+            SandboxTier::Pooled => Some(&self.pooled),
+            SandboxTier::Isolated => Some(&self.isolated),
+            SandboxTier::Refused => None,
+        }
+    }
+
+    /// Config to use for a policy decision; `None` when it is refused.
+    pub fn config_for_decision(&self, decision: &PolicyDecision) -> Option<&SandboxConfig> {
+        self.config_for(SandboxTier::for_decision(decision))
+    }
+}
+
+impl Default for SandboxProfiles {
+    fn default() -> Self {
+        Self::from_base(SandboxConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tier_for_risk() {
+        assert_eq!(
+            SandboxTier::for_risk(ToolRiskLevel::Low),
+            SandboxTier::Pooled
+        );
+        assert_eq!(
+            SandboxTier::for_risk(ToolRiskLevel::Medium),
+            SandboxTier::Pooled
+        );
+        assert_eq!(
+            SandboxTier::for_risk(ToolRiskLevel::High),
+            SandboxTier::Isolated
+        );
+        assert_eq!(
+            SandboxTier::for_risk(ToolRiskLevel::Critical),
+            SandboxTier::Refused
+        );
+    }
+
+    #[test]
+    fn test_isolated_profile_is_tighter() {
+        let base = SandboxConfig {
+            network_profile: NetworkProfile::Bridge,
+            ..SandboxConfig::default()
+        };
+        let profiles = SandboxProfiles::from_base(base);
+
+        let isolated = profiles.config_for(SandboxTier::Isolated).unwrap();
+        assert!(isolated.memory_limit < profiles.pooled.memory_limit);
+        assert!(isolated.pids_limit < profiles.pooled.pids_limit);
+        assert!(matches!(isolated.network_profile, NetworkProfile::None));
+        assert_eq!(isolated.image, profiles.pooled.image);
+        assert!(profiles.config_for(SandboxTier::Refused).is_none());
+    }
+
+    #[test]
+    fn test_isolated_profile_overrides() {
+        let overrides = IsolatedProfileConfig {
+            image: Some("hardened:1".into()),
+            memory_limit_mb: Some(128),
+            pids_limit: Some(8),
+            ..Default::default()
+        };
+        let profiles = SandboxProfiles::from_config(SandboxConfig::default(), &overrides);
+
+        assert_eq!(profiles.isolated.image, "hardened:1");
+        assert_eq!(profiles.isolated.memory_limit, 128 * 1024 * 1024);
+        assert_eq!(profiles.isolated.pids_limit, 8);
+        // Unset fields keep the derived values.
+        assert_eq!(profiles.isolated.cpu_quota, profiles.pooled.cpu_quota / 2);
+        assert!(matches!(
+            profiles.isolated.network_profile,
+            NetworkProfile::None
+        ));
+        assert_eq!(profiles.pooled.image, "opencoordex-sandbox:latest");
+    }
+}
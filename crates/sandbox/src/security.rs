@@ -0,0 +1,280 @@
+//! Kernel-level hardening for sandbox containers.
+//!
+//! On top of `cap_drop: ALL` and `no-new-privileges`, each container gets a
+//! seccomp profile and, optionally, an AppArmor profile or SELinux label.
+//! The shipped seccomp profile (`profiles/seccomp-default.json`) returns
+//! `EPERM` for syscalls an agent workload never needs: mounts, namespaces,
+//! kernel modules, ptrace, bpf, keyrings and clock changes.
+//!
+//! Profiles can differ per image, e.g. to relax seccomp for an image that
+//! runs a debugger.
+//!
+//! Processes always run under an explicit non-root UID/GID ([`RunAs`]),
+//! optionally inside a remapped user namespace ([`UserNamespace`]).
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use multi_agent_core::Result;
+
+/// Seccomp profile shipped with the sandbox.
+pub const DEFAULT_SECCOMP_PROFILE: &str = include_str!("../profiles/seccomp-default.json");
+
+/// Which seccomp profile a container runs under.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind", content = "path")]
+pub enum SeccompProfile {
+    /// The profile shipped with the sandbox (default).
+    #[default]
+    Builtin,
+    /// Docker's own default profile.
+    DockerDefault,
+    /// A JSON profile read from disk when the container is created.
+    File(PathBuf),
+    /// No seccomp filtering (dangerous).
+    Unconfined,
+}
+
+/// Security options for one image.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecurityProfile {
+    /// Seccomp filter.
+    #[serde(default)]
+    pub seccomp: SeccompProfile,
+    /// AppArmor profile name (must be loaded on the host).
+    #[serde(default)]
+    pub apparmor: Option<String>,
+    /// SELinux label, e.g. `type:container_t` or `level:s0:c100,c200`.
+    #[serde(default)]
+    pub selinux_label: Option<String>,
+}
+
+impl SecurityProfile {
+    /// Build Docker `security_opt` entries for this profile.
+    pub fn security_opts(&self) -> Result<Vec<String>> {
+        let mut opts = vec!["no-new-privileges:true".to_string()];
+
+        match &self.seccomp {
+            SeccompProfile::Builtin => opts.push(format!("seccomp={}", DEFAULT_SECCOMP_PROFILE)),
+            SeccompProfile::DockerDefault => {}
+            SeccompProfile::File(path) => {
+                let json = std::fs::read_to_string(path).map_err(|e| {
+                    multi_agent_core::Error::internal(format!(
+                        "Failed to read seccomp profile '{}': {}",
+                        path.display(),
+                        e
+                    ))
+                })?;
+                opts.push(format!("seccomp={}", json));
+            }
+            SeccompProfile::Unconfined => opts.push("seccomp=unconfined".to_string()),
+        }
+
+        if let Some(ref profile) = self.apparmor {
+            opts.push(format!("apparmor={}", profile));
+        }
+        if let Some(ref label) = self.selinux_label {
+            opts.push(format!("label={}", label));
+        }
+
+        Ok(opts)
+    }
+
+    /// Features this profile needs that the host does not provide.
+    ///
+    /// An empty result means the host can enforce the profile.
+    pub fn missing_host_support(&self, host: &HostSecurity) -> Vec<String> {
+        let mut missing = Vec::new();
+        if !matches!(self.seccomp, SeccompProfile::Unconfined) && !host.seccomp {
+            missing.push("seccomp".to_string());
+        }
+        if self.apparmor.is_some() && !host.apparmor {
+            missing.push("apparmor".to_string());
+        }
+        if self.selinux_label.is_some() && !host.selinux {
+            missing.push("selinux".to_string());
+        }
+        missing
+    }
+}
+
+/// Security profiles keyed by image, with a fallback for unlisted images.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SecurityProfiles {
+    /// Profile for images without an override.
+    #[serde(default)]
+    pub default: SecurityProfile,
+    /// Per-image overrides, keyed by the exact image reference.
+    #[serde(default)]
+    pub images: HashMap<String, SecurityProfile>,
+}
+
+impl SecurityProfiles {
+    /// Profile for the given image.
+    pub fn for_image(&self, image: &str) -> &SecurityProfile {
+        self.images.get(image).unwrap_or(&self.default)
+    }
+
+    /// Features needed by any configured profile that the host lacks.
+    pub fn missing_host_support(&self, host: &HostSecurity) -> Vec<String> {
+        let mut missing: Vec<String> = std::iter::once(&self.default)
+            .chain(self.images.values())
+            .flat_map(|p| p.missing_host_support(host))
+            .collect();
+        missing.sort();
+        missing.dedup();
+        missing
+    }
+}
+
+/// UID/GID sandbox processes run as. Root is rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunAs {
+    pub uid: u32,
+    pub gid: u32,
+}
+
+impl Default for RunAs {
+    fn default() -> Self {
+        Self {
+            uid: 1000,
+            gid: 1000,
+        }
+    }
+}
+
+impl RunAs {
+    /// `uid:gid` as accepted by Docker's `User` field.
+    pub fn docker_user(&self) -> String {
+        format!("{}:{}", self.uid, self.gid)
+    }
+
+    /// Reject root.
+    pub fn validate(&self) -> Result<()> {
+        if self.uid == 0 || self.gid == 0 {
+            return Err(multi_agent_core::Error::invalid_request(
+                "Sandbox must not run as uid or gid 0",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// User namespace handling for sandbox containers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserNamespace {
+    /// Whatever the daemon or cluster does by default.
+    #[default]
+    Default,
+    /// Require remapping so container UIDs map to unprivileged host UIDs
+    /// (Docker `userns-remap`, Kubernetes `hostUsers: false`).
+    Remapped,
+    /// Share the host user namespace, opting out of daemon remapping.
+    Host,
+}
+
+/// Security features the Docker host reports as enabled.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct HostSecurity {
+    pub seccomp: bool,
+    pub apparmor: bool,
+    pub selinux: bool,
+    pub rootless: bool,
+    /// Daemon runs with `userns-remap`.
+    pub userns: bool,
+}
+
+impl HostSecurity {
+    /// Parse the `SecurityOptions` list from `docker info`
+    /// (entries like `name=seccomp,profile=builtin`).
+    pub fn from_security_options<S: AsRef<str>>(options: &[S]) -> Self {
+        let mut host = Self::default();
+        for opt in options {
+            let name = opt
+                .as_ref()
+                .split(',')
+                .find_map(|kv| kv.strip_prefix("name="))
+                .unwrap_or(opt.as_ref());
+            match name {
+                "seccomp" => host.seccomp = true,
+                "apparmor" => host.apparmor = true,
+                "selinux" => host.selinux = true,
+                "rootless" => host.rootless = true,
+                "userns" => host.userns = true,
+                _ => {}
+            }
+        }
+        host
+    }
+
+    /// Query the Docker daemon.
+    pub async fn detect(docker: &bollard::Docker) -> Result<Self> {
+        let info = docker.info().await.map_err(|e| {
+            multi_agent_core::Error::internal(format!("Failed to query Docker info: {}", e))
+        })?;
+        Ok(Self::from_security_options(
+            &info.security_options.unwrap_or_default(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_profile_is_valid_json() {
+        let profile: serde_json::Value = serde_json::from_str(DEFAULT_SECCOMP_PROFILE).unwrap();
+        let blocked = profile["syscalls"][0]["names"].as_array().unwrap();
+        assert!(blocked.iter().any(|s| s == "ptrace"));
+        assert!(blocked.iter().any(|s| s == "mount"));
+    }
+
+    #[test]
+    fn test_security_opts() {
+        let opts = SecurityProfile {
+            seccomp: SeccompProfile::DockerDefault,
+            apparmor: Some("opencoordex-sandbox".to_string()),
+            selinux_label: Some("type:container_t".to_string()),
+        }
+        .security_opts()
+        .unwrap();
+        assert_eq!(
+            opts,
+            vec![
+                "no-new-privileges:true",
+                "apparmor=opencoordex-sandbox",
+                "label=type:container_t"
+            ]
+        );
+
+        let opts = SecurityProfile::default().security_opts().unwrap();
+        assert!(opts[1].starts_with("seccomp={"));
+    }
+
+    #[test]
+    fn test_per_image_profiles_and_host_support() {
+        let mut profiles = SecurityProfiles::default();
+        profiles.images.insert(
+            "debug:latest".to_string(),
+            SecurityProfile {
+                seccomp: SeccompProfile::Unconfined,
+                apparmor: Some("debug".to_string()),
+                selinux_label: None,
+            },
+        );
+        assert_eq!(
+            profiles.for_image("debug:latest").seccomp,
+            SeccompProfile::Unconfined
+        );
+        assert_eq!(profiles.for_image("other").seccomp, SeccompProfile::Builtin);
+
+        let host =
+            HostSecurity::from_security_options(&["name=seccomp,profile=builtin", "name=cgroupns"]);
+        assert!(host.seccomp && !host.apparmor && !host.userns);
+        assert_eq!(profiles.missing_host_support(&host), vec!["apparmor"]);
+    }
+}
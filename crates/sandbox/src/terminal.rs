@@ -0,0 +1,171 @@
+//! Interactive terminal sessions.
+//!
+//! A terminal is a PTY shell exec'd into a running sandbox. Engines hand
+//! back a pair of channels; the caller (e.g. the gateway's WebSocket
+//! endpoint) pumps keystrokes in and screen output out. Every session can be
+//! recorded as an asciicast v2 transcript for later review.
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+/// Terminal dimensions in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TerminalSize {
+    pub cols: u16,
+    pub rows: u16,
+}
+
+impl Default for TerminalSize {
+    fn default() -> Self {
+        Self { cols: 80, rows: 24 }
+    }
+}
+
+/// Input sent to a terminal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TerminalInput {
+    /// Raw bytes written to the PTY (keystrokes, pasted text).
+    Data(Vec<u8>),
+    /// Resize the PTY.
+    Resize(TerminalSize),
+}
+
+/// An open PTY session. Dropping `input` ends the session; `output` closes
+/// once the shell exits.
+pub struct TerminalSession {
+    pub input: mpsc::Sender<TerminalInput>,
+    pub output: mpsc::Receiver<Vec<u8>>,
+}
+
+impl TerminalSession {
+    /// Create a session and the engine-side ends of its channels.
+    pub fn channel() -> (Self, mpsc::Receiver<TerminalInput>, mpsc::Sender<Vec<u8>>) {
+        let (input_tx, input_rx) = mpsc::channel(64);
+        let (output_tx, output_rx) = mpsc::channel(256);
+        (
+            Self {
+                input: input_tx,
+                output: output_rx,
+            },
+            input_rx,
+            output_tx,
+        )
+    }
+}
+
+/// Records a terminal session in asciicast v2 format.
+pub struct TranscriptRecorder {
+    started: std::time::Instant,
+    timestamp: i64,
+    size: TerminalSize,
+    title: Option<String>,
+    events: Vec<(f64, &'static str, String)>,
+}
+
+impl TranscriptRecorder {
+    pub fn new(size: TerminalSize) -> Self {
+        Self {
+            started: std::time::Instant::now(),
+            timestamp: unix_now(),
+            size,
+            title: None,
+            events: Vec::new(),
+        }
+    }
+
+    /// Set the transcript title (shown by players).
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Record screen output.
+    pub fn output(&mut self, data: &[u8]) {
+        self.push("o", String::from_utf8_lossy(data).into_owned());
+    }
+
+    /// Record operator input.
+    pub fn input(&mut self, data: &[u8]) {
+        self.push("i", String::from_utf8_lossy(data).into_owned());
+    }
+
+    /// Record a resize.
+    pub fn resize(&mut self, size: TerminalSize) {
+        self.push("r", format!("{}x{}", size.cols, size.rows));
+    }
+
+    fn push(&mut self, kind: &'static str, data: String) {
+        let elapsed = self.started.elapsed().as_secs_f64();
+        self.events.push((elapsed, kind, data));
+    }
+
+    /// Number of recorded events.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Render the transcript as asciicast v2 (newline-delimited JSON).
+    pub fn to_asciicast(&self) -> String {
+        let mut header = serde_json::json!({
+            "version": 2,
+            "width": self.size.cols,
+            "height": self.size.rows,
+            "timestamp": self.timestamp,
+        });
+        if let Some(ref title) = self.title {
+            header["title"] = serde_json::json!(title);
+        }
+
+        let mut out = header.to_string();
+        for (time, kind, data) in &self.events {
+            out.push('\n');
+            out.push_str(&serde_json::json!([time, kind, data]).to_string());
+        }
+        out.push('\n');
+        out
+    }
+}
+
+fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asciicast_transcript() {
+        let mut recorder = TranscriptRecorder::new(TerminalSize {
+            cols: 100,
+            rows: 30,
+        })
+        .with_title("sandbox");
+        recorder.input(b"ls\r");
+        recorder.output(b"main.rs\r\n");
+        recorder.resize(TerminalSize {
+            cols: 120,
+            rows: 40,
+        });
+
+        let cast = recorder.to_asciicast();
+        let lines: Vec<serde_json::Value> = cast
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0]["version"], 2);
+        assert_eq!(lines[0]["width"], 100);
+        assert_eq!(lines[0]["title"], "sandbox");
+        assert_eq!(lines[1][1], "i");
+        assert_eq!(lines[2][2], "main.rs\r\n");
+        assert_eq!(lines[3][2], "120x40");
+    }
+}
@@ -6,11 +6,15 @@
 use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use multi_agent_core::{traits::Tool, types::ToolOutput, Result};
+use multi_agent_governance::PolicyEngine;
 
-use crate::engine::{SandboxConfig, SandboxEngine, SandboxId};
+use crate::command_policy::CommandPolicy;
+use crate::engine::{ExecResult, SandboxConfig, SandboxEngine, SandboxId};
+use crate::profiles::{SandboxProfiles, SandboxTier};
+use crate::terminal::{TerminalSession, TerminalSize};
 
 // =============================================================================
 // Sandbox Manager
@@ -20,11 +24,25 @@ use crate::engine::{SandboxConfig, SandboxEngine, SandboxId};
 ///
 /// Holds a reference to the sandbox engine and the active sandbox ID.
 /// On first use, creates a sandbox lazily. On drop, destroys it.
+///
+/// With a policy engine attached, commands run through [`exec_for`] are
+/// placed by risk tier (see [`crate::profiles`]).
+///
+/// An idle sandbox can be [hibernated](SandboxManager::hibernate): its
+/// workdir is archived and the container freed, and the next command
+/// restores the archive into a fresh sandbox.
+///
+/// [`exec_for`]: SandboxManager::exec_for
 pub struct SandboxManager {
     engine: Arc<dyn SandboxEngine>,
     config: SandboxConfig,
+    profiles: SandboxProfiles,
+    policy_engine: Option<Arc<tokio::sync::RwLock<PolicyEngine>>>,
     active_sandbox: tokio::sync::RwLock<Option<SandboxId>>,
     event_emitter: Option<Arc<dyn multi_agent_core::traits::EventEmitter>>,
+    last_used: std::sync::Mutex<Instant>,
+    /// Workdir archive of a hibernated sandbox.
+    snapshot: tokio::sync::Mutex<Option<Vec<u8>>>,
 }
 
 impl SandboxManager {
@@ -32,12 +50,30 @@ impl SandboxManager {
     pub fn new(engine: Arc<dyn SandboxEngine>, config: SandboxConfig) -> Self {
         Self {
             engine,
+            profiles: SandboxProfiles::from_base(config.clone()),
             config,
+            policy_engine: None,
             active_sandbox: tokio::sync::RwLock::new(None),
             event_emitter: None,
+            last_used: std::sync::Mutex::new(Instant::now()),
+            snapshot: tokio::sync::Mutex::new(None),
         }
     }
 
+    /// Override the isolated profile used for high-risk commands.
+    ///
+    /// The pooled profile always follows the manager's own config.
+    pub fn with_isolated_profile(mut self, config: SandboxConfig) -> Self {
+        self.profiles.isolated = config;
+        self
+    }
+
+    /// Select sandbox tiers from policy decisions.
+    pub fn with_policy_engine(mut self, engine: Arc<tokio::sync::RwLock<PolicyEngine>>) -> Self {
+        self.policy_engine = Some(engine);
+        self
+    }
+
     /// Set an event emitter for auditing.
     pub fn with_event_emitter(
         mut self,
@@ -49,6 +85,8 @@ impl SandboxManager {
 
     /// Get or create the active sandbox.
     pub async fn get_or_create(&self) -> Result<SandboxId> {
+        *self.last_used.lock().unwrap() = Instant::now();
+
         // Fast path: check if sandbox exists
         {
             let guard = self.active_sandbox.read().await;
@@ -65,10 +103,79 @@ impl SandboxManager {
         }
 
         let id = self.engine.create(&self.config).await?;
+        if let Some(archive) = self.snapshot.lock().await.take() {
+            match self.engine.restore(&id, &archive).await {
+                Ok(()) => tracing::info!(sandbox_id = %id, "Restored hibernated sandbox"),
+                Err(e) => {
+                    tracing::error!(sandbox_id = %id, error = %e, "Failed to restore sandbox snapshot")
+                }
+            }
+        }
         *guard = Some(id.clone());
         Ok(id)
     }
 
+    /// Time since the sandbox was last used.
+    pub fn idle_for(&self) -> Duration {
+        self.last_used.lock().unwrap().elapsed()
+    }
+
+    /// Whether a snapshot is waiting to be restored.
+    pub async fn is_hibernated(&self) -> bool {
+        self.snapshot.lock().await.is_some()
+    }
+
+    /// Archive the workdir and destroy the active sandbox.
+    ///
+    /// Returns whether a sandbox was freed. If the snapshot fails the sandbox
+    /// is kept.
+    pub async fn hibernate(&self) -> Result<bool> {
+        let mut guard = self.active_sandbox.write().await;
+        let Some(id) = guard.clone() else {
+            return Ok(false);
+        };
+        let archive = self.engine.snapshot(&id).await?;
+        self.engine.destroy(&id).await?;
+        *guard = None;
+        tracing::info!(sandbox_id = %id, snapshot_bytes = archive.len(), "Hibernated idle sandbox");
+        *self.snapshot.lock().await = Some(archive);
+        Ok(true)
+    }
+
+    /// Check that the configured image and backend meet the non-root
+    /// requirements: a non-zero UID/GID, user namespace support when
+    /// remapping is required, and a probe container that really runs as the
+    /// configured user with a writable workdir. Sandbox tools should not be
+    /// registered when this fails.
+    pub async fn validate(&self) -> Result<()> {
+        self.config.run_as.validate()?;
+        self.engine.check_config(&self.config).await?;
+
+        let id = self.engine.create(&self.config).await?;
+        let probe = self
+            .engine
+            .exec(
+                &id,
+                &format!(
+                    "id -u; id -g; test -w '{}' && echo writable || echo readonly",
+                    self.config.workdir
+                ),
+                Duration::from_secs(30),
+            )
+            .await;
+        if let Err(e) = self.engine.destroy(&id).await {
+            tracing::warn!(sandbox_id = %id, error = %e, "Failed to destroy probe sandbox");
+        }
+        check_user_probe(&probe?, &self.config)
+    }
+
+    /// Open an interactive shell in the active sandbox, creating it if needed.
+    pub async fn open_terminal(&self, size: TerminalSize) -> Result<(SandboxId, TerminalSession)> {
+        let id = self.get_or_create().await?;
+        let session = self.engine.open_terminal(&id, size).await?;
+        Ok((id, session))
+    }
+
     /// Destroy the active sandbox.
     pub async fn teardown(&self) -> Result<()> {
         let mut guard = self.active_sandbox.write().await;
@@ -78,6 +185,54 @@ impl SandboxManager {
         Ok(())
     }
 
+    /// Tier for a tool call. Without a policy engine everything is pooled.
+    pub async fn tier_for(&self, tool: &str, args: &Value) -> (SandboxTier, Option<String>) {
+        let Some(ref policy) = self.policy_engine else {
+            return (SandboxTier::Pooled, None);
+        };
+        let decision = policy.read().await.evaluate(tool, args);
+        (SandboxTier::for_decision(&decision), Some(decision.reason))
+    }
+
+    /// Run a command in the sandbox its policy decision calls for.
+    ///
+    /// Pooled commands share the active sandbox; isolated ones get a fresh
+    /// container that is destroyed afterwards; refused ones return a
+    /// governance error without touching the engine.
+    pub async fn exec_for(
+        &self,
+        tool: &str,
+        args: &Value,
+        command: &str,
+        timeout: Duration,
+    ) -> Result<ExecResult> {
+        let (tier, reason) = self.tier_for(tool, args).await;
+        tracing::debug!(tool = %tool, tier = tier.as_str(), "Selected sandbox tier");
+
+        match tier {
+            SandboxTier::Pooled => {
+                let id = self.get_or_create().await?;
+                self.engine.exec(&id, command, timeout).await
+            }
+            SandboxTier::Isolated => {
+                let id = self.engine.create(&self.profiles.isolated).await?;
+                tracing::info!(sandbox_id = %id, tool = %tool, "Running high-risk command in isolated sandbox");
+                let result = self.engine.exec(&id, command, timeout).await;
+                if let Err(e) = self.engine.destroy(&id).await {
+                    tracing::warn!(sandbox_id = %id, error = %e, "Failed to destroy isolated sandbox");
+                }
+                result
+            }
+            SandboxTier::Refused => {
+                tracing::warn!(tool = %tool, "Refused critical-risk sandbox command");
+                Err(multi_agent_core::Error::governance(format!(
+                    "Command refused by policy: {}",
+                    reason.unwrap_or_default()
+                )))
+            }
+        }
+    }
+
     /// Get a reference to the sandbox engine.
     pub fn engine(&self) -> &Arc<dyn SandboxEngine> {
         &self.engine
@@ -89,6 +244,46 @@ impl SandboxManager {
     }
 }
 
+/// Verify the output of the user probe run by [`SandboxManager::validate`].
+fn check_user_probe(probe: &ExecResult, config: &SandboxConfig) -> Result<()> {
+    let invalid = |msg: String| {
+        Err(multi_agent_core::Error::invalid_request(format!(
+            "Sandbox image '{}' does not meet non-root requirements: {}",
+            config.image, msg
+        )))
+    };
+    if !probe.success() {
+        return invalid(format!("probe failed: {}", probe.stderr.trim()));
+    }
+
+    let mut lines = probe.stdout.lines().map(str::trim);
+    let uid = lines.next().and_then(|l| l.parse::<u32>().ok());
+    let gid = lines.next().and_then(|l| l.parse::<u32>().ok());
+    let (Some(uid), Some(gid)) = (uid, gid) else {
+        return invalid(format!("unexpected probe output: {}", probe.stdout.trim()));
+    };
+
+    if uid == 0 || gid == 0 {
+        return invalid("processes run as root".to_string());
+    }
+    if uid != config.run_as.uid || gid != config.run_as.gid {
+        return invalid(format!(
+            "processes run as {}:{} instead of {}",
+            uid,
+            gid,
+            config.run_as.docker_user()
+        ));
+    }
+    if lines.next() != Some("writable") {
+        return invalid(format!(
+            "{} is not writable by {}",
+            config.workdir,
+            config.run_as.docker_user()
+        ));
+    }
+    Ok(())
+}
+
 // =============================================================================
 // Sandbox Shell Tool
 // =============================================================================
@@ -98,12 +293,22 @@ impl SandboxManager {
 /// Risk level: HIGH — requires human approval when HITL is enabled.
 pub struct SandboxShellTool {
     manager: Arc<SandboxManager>,
+    command_policy: CommandPolicy,
 }
 
 impl SandboxShellTool {
-    /// Create a new sandbox shell tool.
+    /// Create a new sandbox shell tool with the built-in command deny rules.
     pub fn new(manager: Arc<SandboxManager>) -> Self {
-        Self { manager }
+        Self {
+            manager,
+            command_policy: CommandPolicy::default(),
+        }
+    }
+
+    /// Replace the command allow/deny policy.
+    pub fn with_command_policy(mut self, policy: CommandPolicy) -> Self {
+        self.command_policy = policy;
+        self
     }
 }
 
@@ -155,11 +360,22 @@ impl Tool for SandboxShellTool {
 
         let timeout = Duration::from_secs(timeout_secs);
 
-        let sandbox_id = self.manager.get_or_create().await?;
+        // Blocked commands come back as observations so the model can retry
+        if let Some(violation) = self.command_policy.check(command) {
+            tracing::warn!(rule = %violation.rule, segment = %violation.segment, "Sandbox command blocked");
+            return Ok(ToolOutput::error(format!(
+                "Command blocked by sandbox policy (rule `{}`): {}.\nOffending part: `{}`\nRewrite the command without this pattern.",
+                violation.rule, violation.reason, violation.segment
+            ))
+            .with_data(json!({
+                "blocked": true,
+                "rule": violation.rule,
+            })));
+        }
+
         let result = self
             .manager
-            .engine()
-            .exec(&sandbox_id, command, timeout)
+            .exec_for(self.name(), &args, command, timeout)
             .await?;
 
         if result.timed_out {
@@ -566,4 +782,36 @@ mod tests {
         let id2 = manager.get_or_create().await.unwrap();
         assert_eq!(id1.0, id2.0);
     }
+
+    #[tokio::test]
+    async fn test_hibernate_and_restore() {
+        let engine = Arc::new(MockSandbox::default());
+        let manager = Arc::new(SandboxManager::new(
+            engine.clone(),
+            SandboxConfig::default(),
+        ));
+        assert!(!manager.hibernate().await.unwrap());
+
+        let write_tool = SandboxWriteFileTool::new(manager.clone());
+        let read_tool = SandboxReadFileTool::new(manager.clone());
+        write_tool
+            .execute(json!({"path": "notes.txt", "content": "kept"}))
+            .await
+            .unwrap();
+        let first = manager.get_or_create().await.unwrap();
+
+        assert!(manager.hibernate().await.unwrap());
+        assert!(manager.is_hibernated().await);
+        assert_eq!(*engine.destroyed.lock().await, vec![first.clone()]);
+        // The container and its files are gone
+        engine.files.lock().await.clear();
+
+        let r_result = read_tool
+            .execute(json!({"path": "notes.txt"}))
+            .await
+            .unwrap();
+        assert_eq!(r_result.content, "kept");
+        assert!(!manager.is_hibernated().await);
+        assert_ne!(manager.get_or_create().await.unwrap(), first);
+    }
 }
@@ -0,0 +1,293 @@
+//! Tool usage analytics.
+//!
+//! [`AnalyticsToolRegistry`] wraps a registry and records every call:
+//! invocation count, success rate, latency and the estimated token cost of
+//! the observation fed back to the model. The same statistics flow back into
+//! `list()`: tools that keep failing are moved to the end of the list and
+//! their description carries a warning, so the router's classifier (and
+//! anything else reading tool descriptions) prefers reliable tools.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use serde::Serialize;
+use serde_json::Value;
+
+use multi_agent_core::traits::{Tool, ToolRegistry};
+use multi_agent_core::types::{ToolDefinition, ToolOutput, ToolRiskLevel};
+use multi_agent_core::Result;
+
+/// Calls needed before a tool can be flagged as unreliable.
+const MIN_CALLS_FOR_RANKING: u64 = 5;
+/// Success rate below which a tool is flagged.
+const UNRELIABLE_SUCCESS_RATE: f64 = 0.5;
+
+#[derive(Debug, Default, Clone)]
+struct Counters {
+    invocations: u64,
+    successes: u64,
+    total_latency_ms: u64,
+    max_latency_ms: u64,
+    observation_tokens: u64,
+    last_error: Option<String>,
+    last_used: Option<String>,
+}
+
+/// Aggregated statistics for one tool.
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolStats {
+    pub tool: String,
+    pub invocations: u64,
+    pub successes: u64,
+    pub failures: u64,
+    pub success_rate: f64,
+    pub avg_latency_ms: u64,
+    pub max_latency_ms: u64,
+    /// Estimated tokens (~4 chars each) of all observations returned.
+    pub observation_tokens: u64,
+    pub avg_observation_tokens: u64,
+    /// Whether the tool is currently deprioritized.
+    pub unreliable: bool,
+    pub last_error: Option<String>,
+    pub last_used: Option<String>,
+}
+
+/// Per-tool usage statistics.
+#[derive(Default)]
+pub struct ToolAnalytics {
+    tools: DashMap<String, Counters>,
+}
+
+impl ToolAnalytics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one call. `error` is the failure message, if any.
+    pub fn record(
+        &self,
+        tool: &str,
+        latency: Duration,
+        observation_len: usize,
+        error: Option<String>,
+    ) {
+        let latency_ms = latency.as_millis() as u64;
+        let mut c = self.tools.entry(tool.to_string()).or_default();
+        c.invocations += 1;
+        if error.is_none() {
+            c.successes += 1;
+        } else {
+            c.last_error = error;
+        }
+        c.total_latency_ms += latency_ms;
+        c.max_latency_ms = c.max_latency_ms.max(latency_ms);
+        c.observation_tokens += (observation_len / 4) as u64;
+        c.last_used = Some(chrono::Utc::now().to_rfc3339());
+    }
+
+    /// Statistics for one tool.
+    pub fn stats(&self, tool: &str) -> Option<ToolStats> {
+        self.tools.get(tool).map(|c| to_stats(tool, &c))
+    }
+
+    /// Statistics for every tool, most used first.
+    pub fn snapshot(&self) -> Vec<ToolStats> {
+        let mut all: Vec<ToolStats> = self
+            .tools
+            .iter()
+            .map(|e| to_stats(e.key(), e.value()))
+            .collect();
+        all.sort_by(|a, b| {
+            b.invocations
+                .cmp(&a.invocations)
+                .then_with(|| a.tool.cmp(&b.tool))
+        });
+        all
+    }
+
+    /// Whether a tool fails often enough to be deprioritized.
+    pub fn is_unreliable(&self, tool: &str) -> bool {
+        self.tools.get(tool).is_some_and(|c| unreliable(&c))
+    }
+
+    /// Order tools reliable-first and annotate the unreliable ones.
+    pub fn rank(&self, mut tools: Vec<ToolDefinition>) -> Vec<ToolDefinition> {
+        let mut flagged = Vec::new();
+        tools.retain(|t| {
+            let Some(c) = self.tools.get(&t.name) else {
+                return true;
+            };
+            if !unreliable(&c) {
+                return true;
+            }
+            let mut t = t.clone();
+            t.description = format!(
+                "{} [Unreliable: {} of the last {} calls succeeded; prefer alternatives]",
+                t.description, c.successes, c.invocations
+            );
+            flagged.push(t);
+            false
+        });
+        tools.extend(flagged);
+        tools
+    }
+}
+
+fn unreliable(c: &Counters) -> bool {
+    c.invocations >= MIN_CALLS_FOR_RANKING && success_rate(c) < UNRELIABLE_SUCCESS_RATE
+}
+
+fn success_rate(c: &Counters) -> f64 {
+    if c.invocations == 0 {
+        return 0.0;
+    }
+    c.successes as f64 / c.invocations as f64
+}
+
+fn to_stats(tool: &str, c: &Counters) -> ToolStats {
+    let per_call = |total: u64| total.checked_div(c.invocations).unwrap_or(0);
+    ToolStats {
+        tool: tool.to_string(),
+        invocations: c.invocations,
+        successes: c.successes,
+        failures: c.invocations - c.successes,
+        success_rate: success_rate(c),
+        avg_latency_ms: per_call(c.total_latency_ms),
+        max_latency_ms: c.max_latency_ms,
+        observation_tokens: c.observation_tokens,
+        avg_observation_tokens: per_call(c.observation_tokens),
+        unreliable: unreliable(c),
+        last_error: c.last_error.clone(),
+        last_used: c.last_used.clone(),
+    }
+}
+
+/// Registry decorator that records usage into [`ToolAnalytics`].
+pub struct AnalyticsToolRegistry {
+    inner: Arc<dyn ToolRegistry>,
+    analytics: Arc<ToolAnalytics>,
+}
+
+impl AnalyticsToolRegistry {
+    pub fn new(inner: Arc<dyn ToolRegistry>, analytics: Arc<ToolAnalytics>) -> Self {
+        Self { inner, analytics }
+    }
+
+    /// The shared statistics.
+    pub fn analytics(&self) -> Arc<ToolAnalytics> {
+        self.analytics.clone()
+    }
+}
+
+#[async_trait]
+impl ToolRegistry for AnalyticsToolRegistry {
+    async fn register(&self, tool: Box<dyn Tool>) -> Result<()> {
+        self.inner.register(tool).await
+    }
+
+    async fn get(&self, name: &str) -> Result<Option<Box<dyn Tool>>> {
+        self.inner.get(name).await
+    }
+
+    async fn list(&self) -> Result<Vec<ToolDefinition>> {
+        Ok(self.analytics.rank(self.inner.list().await?))
+    }
+
+    async fn execute(&self, name: &str, args: Value) -> Result<ToolOutput> {
+        let started = Instant::now();
+        let result = self.inner.execute(name, args).await;
+        let (len, error) = match &result {
+            Ok(output) if output.success => (output.content.len(), None),
+            Ok(output) => (output.content.len(), Some(output.content.clone())),
+            Err(e) => (0, Some(e.to_string())),
+        };
+        self.analytics.record(name, started.elapsed(), len, error);
+        result
+    }
+
+    async fn get_risk_level(&self, name: &str) -> ToolRiskLevel {
+        self.inner.get_risk_level(name).await
+    }
+
+    async fn requires_approval(&self, name: &str) -> bool {
+        self.inner.requires_approval(name).await
+    }
+
+    async fn requires_approval_for(&self, name: &str, args: &Value) -> bool {
+        self.inner.requires_approval_for(name, args).await
+    }
+
+    async fn awaits_human_input(&self, name: &str) -> bool {
+        self.inner.awaits_human_input(name).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DefaultToolRegistry, EchoTool};
+
+    struct FlakyTool;
+
+    #[async_trait]
+    impl Tool for FlakyTool {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+        fn description(&self) -> &str {
+            "Sometimes works"
+        }
+        fn parameters(&self) -> Value {
+            serde_json::json!({"type": "object"})
+        }
+        async fn execute(&self, _args: Value) -> Result<ToolOutput> {
+            Ok(ToolOutput::error("upstream unavailable"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_records_usage_and_deprioritizes_failing_tools() {
+        let inner = Arc::new(DefaultToolRegistry::new());
+        inner.register(Box::new(FlakyTool)).await.unwrap();
+        inner.register(Box::new(EchoTool)).await.unwrap();
+        let analytics = Arc::new(ToolAnalytics::new());
+        let registry = AnalyticsToolRegistry::new(inner, analytics.clone());
+
+        for _ in 0..MIN_CALLS_FOR_RANKING {
+            registry
+                .execute("flaky", serde_json::json!({}))
+                .await
+                .unwrap();
+        }
+        registry
+            .execute("echo", serde_json::json!({"message": "hello world"}))
+            .await
+            .unwrap();
+
+        let flaky = analytics.stats("flaky").unwrap();
+        assert_eq!(flaky.invocations, 5);
+        assert_eq!(flaky.failures, 5);
+        assert!(flaky.unreliable);
+        assert_eq!(flaky.last_error.as_deref(), Some("upstream unavailable"));
+        assert_eq!(analytics.stats("echo").unwrap().success_rate, 1.0);
+        assert_eq!(analytics.snapshot()[0].tool, "flaky");
+
+        let listed = registry.list().await.unwrap();
+        let last = listed.last().unwrap();
+        assert_eq!(last.name, "flaky");
+        assert!(last.description.contains("Unreliable: 0 of the last 5"));
+        assert!(!listed[0].description.contains("Unreliable"));
+    }
+
+    #[test]
+    fn test_needs_minimum_calls_before_flagging() {
+        let analytics = ToolAnalytics::new();
+        analytics.record("new", Duration::from_millis(10), 400, Some("boom".into()));
+        assert!(!analytics.is_unreliable("new"));
+        let stats = analytics.stats("new").unwrap();
+        assert_eq!(stats.observation_tokens, 100);
+        assert_eq!(stats.avg_latency_ms, 10);
+    }
+}
@@ -0,0 +1,198 @@
+//! Human-input tool for mid-mission clarification questions.
+//!
+//! `ask_user` lets the agent stop and ask the operator a question instead of
+//! guessing. The question is delivered through a [`HumanInputChannel`]
+//! (WebSocket, chat connectors) and the answer comes back as the tool's
+//! observation. This is separate from risk approvals: nothing is being
+//! authorised, the session simply waits for information.
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use multi_agent_core::{
+    traits::{HumanInputChannel, Tool},
+    types::{ArtifactOwner, HumanAnswer, HumanQuestion, ToolOutput},
+    Error, Result,
+};
+
+/// Longest timeout an agent may request for a single question (24 hours).
+const MAX_TIMEOUT_SECS: u64 = 24 * 60 * 60;
+/// Timeout used to compute `expires_at` when the agent does not pick one.
+const DEFAULT_TIMEOUT_SECS: u64 = 30 * 60;
+
+/// Tool that asks the human a clarification question and waits for the answer.
+pub struct AskUserTool {
+    channel: Arc<dyn HumanInputChannel>,
+}
+
+impl AskUserTool {
+    /// Create a new ask_user tool delivering questions through `channel`.
+    pub fn new(channel: Arc<dyn HumanInputChannel>) -> Self {
+        Self { channel }
+    }
+}
+
+#[async_trait]
+impl Tool for AskUserTool {
+    fn name(&self) -> &str {
+        "ask_user"
+    }
+
+    fn description(&self) -> &str {
+        "Ask the user a clarification question and wait for their answer. \
+         Use this when the task is ambiguous or a decision needs the user's input; \
+         do not use it to request permission for risky actions."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "question": {
+                    "type": "string",
+                    "description": "The question to ask the user"
+                },
+                "options": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Optional suggested answers"
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "How long to wait for an answer, in seconds"
+                }
+            },
+            "required": ["question"]
+        })
+    }
+
+    fn awaits_human_input(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, args: Value) -> Result<ToolOutput> {
+        let question = args
+            .get("question")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|q| !q.is_empty())
+            .ok_or_else(|| Error::invalid_request("Missing 'question' argument"))?;
+        let options: Vec<String> = args
+            .get("options")
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|v| v.as_str())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let timeout_secs = args
+            .get("timeout_secs")
+            .and_then(|v| v.as_u64())
+            .map(|t| t.min(MAX_TIMEOUT_SECS));
+
+        let session_id = ArtifactOwner::current()
+            .and_then(|owner| owner.session_id)
+            .unwrap_or_default();
+        let expires_in = timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS) as i64;
+        let request = HumanQuestion {
+            request_id: uuid::Uuid::new_v4().to_string(),
+            session_id,
+            question: question.to_string(),
+            options,
+            timeout_secs,
+            nonce: uuid::Uuid::new_v4().to_string(),
+            expires_at: chrono::Utc::now().timestamp() + expires_in,
+        };
+
+        tracing::info!(
+            request_id = %request.request_id,
+            session_id = %request.session_id,
+            "Asking user for clarification"
+        );
+
+        match self.channel.ask(&request).await? {
+            HumanAnswer::Answered { answer } => {
+                Ok(
+                    ToolOutput::text(format!("User answered: {}", answer)).with_data(json!({
+                        "request_id": request.request_id,
+                        "question": request.question,
+                        "answer": answer,
+                    })),
+                )
+            }
+            HumanAnswer::TimedOut => Ok(ToolOutput::error(
+                "The user did not answer in time. Proceed using your best judgement \
+                 and state your assumptions, or ask again later.",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::Mutex;
+
+    struct ScriptedChannel {
+        answer: HumanAnswer,
+        asked: Mutex<Vec<HumanQuestion>>,
+    }
+
+    #[async_trait]
+    impl HumanInputChannel for ScriptedChannel {
+        async fn ask(&self, question: &HumanQuestion) -> Result<HumanAnswer> {
+            self.asked.lock().await.push(question.clone());
+            Ok(self.answer.clone())
+        }
+    }
+
+    fn channel(answer: HumanAnswer) -> Arc<ScriptedChannel> {
+        Arc::new(ScriptedChannel {
+            answer,
+            asked: Mutex::new(Vec::new()),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_answer_becomes_observation() {
+        let channel = channel(HumanAnswer::Answered {
+            answer: "Use staging".into(),
+        });
+        let tool = AskUserTool::new(channel.clone());
+        assert!(tool.awaits_human_input());
+
+        let output = ArtifactOwner::new(Some("alice".into()), Some("sess-1".into()))
+            .scope(tool.execute(json!({
+                "question": "Which environment?",
+                "options": ["staging", "production"],
+                "timeout_secs": 999_999
+            })))
+            .await
+            .unwrap();
+
+        assert!(output.success);
+        assert!(output.content.contains("Use staging"));
+        assert_eq!(output.data.unwrap()["answer"], "Use staging");
+
+        let asked = channel.asked.lock().await;
+        assert_eq!(asked.len(), 1);
+        assert_eq!(asked[0].session_id, "sess-1");
+        assert_eq!(asked[0].options, vec!["staging", "production"]);
+        assert_eq!(asked[0].timeout_secs, Some(MAX_TIMEOUT_SECS));
+    }
+
+    #[tokio::test]
+    async fn test_timeout_and_missing_question() {
+        let tool = AskUserTool::new(channel(HumanAnswer::TimedOut));
+
+        let output = tool.execute(json!({"question": "Proceed?"})).await.unwrap();
+        assert!(!output.success);
+        assert!(output.content.contains("did not answer"));
+
+        assert!(tool.execute(json!({"question": "  "})).await.is_err());
+    }
+}
@@ -6,9 +6,10 @@ use std::sync::Arc;
 
 use multi_agent_core::{
     traits::{ArtifactStore, Tool},
-    types::{RefId, ToolOutput},
+    types::{ArtifactOwner, RefId, ToolOutput},
     Result,
 };
+use multi_agent_store::ArtifactCatalog;
 
 // =============================================================================
 // Echo Tool
@@ -54,7 +55,19 @@ impl Tool for EchoTool {
 // Read Artifact Tool
 // =============================================================================
 
+/// Default number of lines returned per page.
+const DEFAULT_READ_LIMIT: usize = 200;
+/// Default maximum number of search matches returned.
+const DEFAULT_MAX_MATCHES: usize = 50;
+/// Upper bound on characters returned by a single call, whatever the line limit.
+const MAX_READ_CHARS: usize = 20_000;
+/// Individual lines longer than this are cut to keep minified output readable.
+const MAX_LINE_CHARS: usize = 2_000;
+
 /// Tool for reading artifacts from L3 store.
+///
+/// Large artifacts can be read incrementally with `offset`/`limit` (in lines)
+/// or searched with a regular expression via `pattern`.
 pub struct ReadArtifactTool {
     store: Arc<dyn ArtifactStore>,
 }
@@ -66,6 +79,128 @@ impl ReadArtifactTool {
     }
 }
 
+fn truncate_line(line: &str) -> std::borrow::Cow<'_, str> {
+    match line.char_indices().nth(MAX_LINE_CHARS) {
+        Some((idx, _)) => format!("{}... [line truncated]", &line[..idx]).into(),
+        None => line.into(),
+    }
+}
+
+fn usize_arg(args: &Value, key: &str) -> Option<usize> {
+    args.get(key).and_then(|v| v.as_u64()).map(|v| v as usize)
+}
+
+/// Return a window of lines starting at `offset`.
+fn read_page(content: &str, offset: usize, limit: usize) -> ToolOutput {
+    let lines: Vec<&str> = content.lines().collect();
+    let total = lines.len();
+    let start = offset.min(total);
+
+    let mut out = String::new();
+    let mut end = start;
+    for (idx, line) in lines.iter().enumerate().skip(start).take(limit) {
+        let numbered = format!("{:>6}\t{}\n", idx + 1, truncate_line(line));
+        // Always return at least one line so the caller can make progress.
+        if end > start && out.len() + numbered.len() > MAX_READ_CHARS {
+            break;
+        }
+        out.push_str(&numbered);
+        end = idx + 1;
+    }
+
+    let has_more = end < total;
+    let header = if total == 0 {
+        "Artifact is empty.".to_string()
+    } else if start >= total {
+        format!("Offset {} is past the end ({} lines).", offset, total)
+    } else if has_more {
+        format!(
+            "Lines {}-{} of {}. Use offset={} to continue.",
+            start + 1,
+            end,
+            total,
+            end
+        )
+    } else {
+        format!("Lines {}-{} of {}.", start + 1, end, total)
+    };
+
+    ToolOutput::text(format!("{}\n{}", header, out)).with_data(json!({
+        "total_lines": total,
+        "offset": start,
+        "returned_lines": end - start,
+        "has_more": has_more,
+        "next_offset": if has_more { Some(end) } else { None },
+    }))
+}
+
+/// Return lines matching `pattern`, with `context` lines around each match.
+fn search(
+    content: &str,
+    pattern: &regex::Regex,
+    context: usize,
+    offset: usize,
+    max_matches: usize,
+) -> ToolOutput {
+    let lines: Vec<&str> = content.lines().collect();
+    let matches: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .filter(|(_, line)| pattern.is_match(line))
+        .map(|(idx, _)| idx)
+        .collect();
+
+    let mut out = String::new();
+    let mut last_printed: Option<usize> = None;
+    let mut returned = 0;
+    for &idx in matches.iter().take(max_matches) {
+        let from = idx
+            .saturating_sub(context)
+            .max(last_printed.map_or(0, |l| l + 1));
+        let to = (idx + context).min(lines.len() - 1);
+        if out.len() > MAX_READ_CHARS {
+            break;
+        }
+        if last_printed.is_some_and(|l| from > l + 1) {
+            out.push_str("--\n");
+        }
+        for (n, line) in lines.iter().enumerate().take(to + 1).skip(from) {
+            let marker = if n == idx { ':' } else { '-' };
+            out.push_str(&format!("{:>6}{}{}\n", n + 1, marker, truncate_line(line)));
+        }
+        last_printed = Some(to);
+        returned += 1;
+    }
+
+    let truncated = returned < matches.len();
+    let header = if matches.is_empty() {
+        format!("No lines match /{}/.", pattern.as_str())
+    } else if truncated {
+        format!(
+            "{} matching lines for /{}/; showing the first {}. Narrow the pattern or search from offset={}.",
+            matches.len(),
+            pattern.as_str(),
+            returned,
+            matches[returned]
+        )
+    } else {
+        format!(
+            "{} matching lines for /{}/.",
+            matches.len(),
+            pattern.as_str()
+        )
+    };
+
+    ToolOutput::text(format!("{}\n{}", header, out)).with_data(json!({
+        "total_lines": lines.len(),
+        "total_matches": matches.len(),
+        "returned_matches": returned,
+        "match_lines": matches.iter().take(returned).map(|i| i + 1).collect::<Vec<_>>(),
+        "truncated": truncated,
+    }))
+}
+
 #[async_trait]
 impl Tool for ReadArtifactTool {
     fn name(&self) -> &str {
@@ -73,7 +208,7 @@ impl Tool for ReadArtifactTool {
     }
 
     fn description(&self) -> &str {
-        "Read content from a stored artifact using its RefID"
+        "Read content from a stored artifact using its RefID. Large artifacts are returned in pages of numbered lines; use offset/limit to page through them or pattern to search inside them."
     }
 
     fn parameters(&self) -> Value {
@@ -83,6 +218,34 @@ impl Tool for ReadArtifactTool {
                 "ref_id": {
                     "type": "string",
                     "description": "The reference ID of the artifact to read"
+                },
+                "offset": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Zero-based line to start reading (or searching) from"
+                },
+                "limit": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": format!("Maximum number of lines to return (default {})", DEFAULT_READ_LIMIT)
+                },
+                "pattern": {
+                    "type": "string",
+                    "description": "Regular expression; when set, only matching lines are returned"
+                },
+                "ignore_case": {
+                    "type": "boolean",
+                    "description": "Case-insensitive pattern matching"
+                },
+                "context": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Lines of context to show around each match"
+                },
+                "max_matches": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": format!("Maximum number of matches to return (default {})", DEFAULT_MAX_MATCHES)
                 }
             },
             "required": ["ref_id"]
@@ -97,16 +260,129 @@ impl Tool for ReadArtifactTool {
 
         let ref_id = RefId::from_string(ref_id_str);
 
-        match self.store.load(&ref_id).await? {
-            Some(bytes) => {
-                let content = String::from_utf8_lossy(&bytes).to_string();
-                Ok(ToolOutput::text(content))
-            }
-            None => Ok(ToolOutput::error(format!(
+        let Some(bytes) = self.store.load(&ref_id).await? else {
+            return Ok(ToolOutput::error(format!(
                 "Artifact not found: {}",
                 ref_id_str
-            ))),
+            )));
+        };
+        let content = String::from_utf8_lossy(&bytes);
+
+        let offset = usize_arg(&args, "offset").unwrap_or(0);
+
+        if let Some(pattern) = args.get("pattern").and_then(|v| v.as_str()) {
+            let ignore_case = args
+                .get("ignore_case")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let regex = match regex::RegexBuilder::new(pattern)
+                .case_insensitive(ignore_case)
+                .build()
+            {
+                Ok(regex) => regex,
+                Err(e) => return Ok(ToolOutput::error(format!("Invalid pattern: {}", e))),
+            };
+            let context = usize_arg(&args, "context").unwrap_or(0);
+            let max_matches = usize_arg(&args, "max_matches")
+                .unwrap_or(DEFAULT_MAX_MATCHES)
+                .max(1);
+            return Ok(search(&content, &regex, context, offset, max_matches));
+        }
+
+        let limit = usize_arg(&args, "limit");
+        // Small artifacts read without paging arguments come back verbatim.
+        if offset == 0
+            && limit.is_none()
+            && content.len() <= MAX_READ_CHARS
+            && content.lines().count() <= DEFAULT_READ_LIMIT
+        {
+            return Ok(ToolOutput::text(content.into_owned()));
         }
+
+        Ok(read_page(
+            &content,
+            offset,
+            limit.unwrap_or(DEFAULT_READ_LIMIT).max(1),
+        ))
+    }
+}
+
+// =============================================================================
+// List Artifacts Tool
+// =============================================================================
+
+/// Tool for finding artifacts saved earlier in the session, so they can be
+/// re-opened with `read_artifact` without the user pasting RefIds.
+pub struct ListArtifactsTool {
+    catalog: Arc<ArtifactCatalog>,
+}
+
+impl ListArtifactsTool {
+    /// Create a new list artifacts tool.
+    pub fn new(catalog: Arc<ArtifactCatalog>) -> Self {
+        Self { catalog }
+    }
+}
+
+#[async_trait]
+impl Tool for ListArtifactsTool {
+    fn name(&self) -> &str {
+        "list_artifacts"
+    }
+
+    fn description(&self) -> &str {
+        "List artifacts saved in this session (or by this user) with their RefIDs, types, sizes and descriptions. Open one with read_artifact."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "scope": {
+                    "type": "string",
+                    "enum": ["session", "user"],
+                    "description": "List this session's artifacts (default) or all of the user's"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: Value) -> Result<ToolOutput> {
+        let owner = ArtifactOwner::current().unwrap_or_default();
+        let scope = args
+            .get("scope")
+            .and_then(|v| v.as_str())
+            .unwrap_or("session");
+        let entries = match (scope, &owner.session_id, &owner.user_id) {
+            ("user", _, Some(user_id)) => self.catalog.for_user(user_id),
+            ("session", Some(session_id), _) => self.catalog.for_session(session_id),
+            _ => {
+                return Ok(ToolOutput::error(format!(
+                    "No {} in scope to list artifacts for",
+                    scope
+                )))
+            }
+        };
+
+        if entries.is_empty() {
+            return Ok(ToolOutput::text(format!("No artifacts in this {}.", scope))
+                .with_data(json!({ "artifacts": entries })));
+        }
+        let mut output = format!("{} artifacts:\n", entries.len());
+        for entry in &entries {
+            output.push_str(&format!(
+                "- {} ({}, {} bytes){}\n",
+                entry.ref_id,
+                entry.content_type,
+                entry.size,
+                entry
+                    .description
+                    .as_deref()
+                    .map(|d| format!(": {}", d))
+                    .unwrap_or_default()
+            ));
+        }
+        Ok(ToolOutput::text(output).with_data(json!({ "artifacts": entries })))
     }
 }
 
@@ -238,6 +514,8 @@ impl Tool for ListToolsTool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bytes::Bytes;
+    use multi_agent_store::InMemoryStore;
 
     #[tokio::test]
     async fn test_echo_tool() {
@@ -277,4 +555,103 @@ mod tests {
         assert!(!result.success);
         assert!(result.content.contains("Division by zero"));
     }
+
+    fn numbered(n: usize) -> Bytes {
+        Bytes::from(
+            (1..=n)
+                .map(|i| format!("line {}", i))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_read_artifact_small_is_verbatim() {
+        let store = Arc::new(InMemoryStore::new());
+        let id = store.save(Bytes::from("hello\nworld")).await.unwrap();
+        let tool = ReadArtifactTool::new(store);
+
+        let result = tool.execute(json!({"ref_id": id.as_str()})).await.unwrap();
+        assert_eq!(result.content, "hello\nworld");
+    }
+
+    #[tokio::test]
+    async fn test_read_artifact_pagination() {
+        let store = Arc::new(InMemoryStore::new());
+        let id = store.save(numbered(500)).await.unwrap();
+        let tool = ReadArtifactTool::new(store);
+
+        let result = tool.execute(json!({"ref_id": id.as_str()})).await.unwrap();
+        let data = result.data.unwrap();
+        assert_eq!(data["total_lines"], 500);
+        assert_eq!(data["returned_lines"], DEFAULT_READ_LIMIT);
+        assert_eq!(data["next_offset"], DEFAULT_READ_LIMIT);
+
+        let result = tool
+            .execute(json!({"ref_id": id.as_str(), "offset": 495, "limit": 10}))
+            .await
+            .unwrap();
+        assert!(result.content.starts_with("Lines 496-500 of 500."));
+        assert!(result.content.contains("line 500"));
+        assert!(!result.content.contains("line 495\n"));
+        assert_eq!(result.data.unwrap()["has_more"], false);
+    }
+
+    #[tokio::test]
+    async fn test_read_artifact_search() {
+        let store = Arc::new(InMemoryStore::new());
+        let id = store.save(numbered(100)).await.unwrap();
+        let tool = ReadArtifactTool::new(store);
+
+        let result = tool
+            .execute(json!({"ref_id": id.as_str(), "pattern": "^line 5\\d$", "context": 1}))
+            .await
+            .unwrap();
+        let data = result.data.unwrap();
+        assert_eq!(data["total_matches"], 10);
+        assert_eq!(data["match_lines"][0], 50);
+        assert!(result.content.contains("    49-line 49"));
+        assert!(result.content.contains("    50:line 50"));
+
+        let result = tool
+            .execute(json!({"ref_id": id.as_str(), "pattern": "LINE 7", "ignore_case": true, "max_matches": 2}))
+            .await
+            .unwrap();
+        let data = result.data.unwrap();
+        assert_eq!(data["returned_matches"], 2);
+        assert_eq!(data["truncated"], true);
+
+        let result = tool
+            .execute(json!({"ref_id": id.as_str(), "pattern": "("}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+    }
+
+    #[tokio::test]
+    async fn test_list_artifacts_in_session() {
+        use multi_agent_store::CatalogArtifactStore;
+
+        let catalog = Arc::new(ArtifactCatalog::new());
+        let store = CatalogArtifactStore::new(Arc::new(InMemoryStore::new()), catalog.clone());
+        let owner = ArtifactOwner::new(Some("alice".into()), Some("s1".into()));
+        let id = owner
+            .clone()
+            .scope(store.save_with_type(Bytes::from("Sales by region\n..."), "text/csv"))
+            .await
+            .unwrap();
+        let tool = ListArtifactsTool::new(catalog);
+
+        let result = owner.clone().scope(tool.execute(json!({}))).await.unwrap();
+        assert!(result.content.contains(id.as_str()));
+        assert!(result.content.contains("text/csv"));
+        assert!(result.content.contains("Sales by region"));
+
+        let other = ArtifactOwner::new(Some("bob".into()), Some("s2".into()));
+        let result = other.scope(tool.execute(json!({}))).await.unwrap();
+        assert!(result.content.starts_with("No artifacts"));
+
+        // Outside a session there is nothing to list
+        assert!(!tool.execute(json!({})).await.unwrap().success);
+    }
 }
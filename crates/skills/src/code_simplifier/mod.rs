@@ -3,6 +3,13 @@
 //! Extracts function signatures, struct definitions, and other
 //! important structural information from code without the implementation
 //! details. This reduces token usage when providing context to LLMs.
+//!
+//! Rust is parsed with `syn`; Python, JavaScript/TypeScript, Go and Java are
+//! parsed with tree-sitter (see [`polyglot`]).
+
+mod polyglot;
+
+use std::path::Path;
 
 use quote::ToTokens;
 use syn::{
@@ -19,6 +26,74 @@ pub struct SimplifiedCode {
     pub item_count: usize,
     /// Language of the source.
     pub language: String,
+    /// Names of the functions, types and members defined in the source.
+    pub symbols: Vec<String>,
+}
+
+/// Languages supported by the code simplifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CodeLanguage {
+    Rust,
+    Python,
+    JavaScript,
+    TypeScript,
+    /// TypeScript with JSX.
+    Tsx,
+    Go,
+    Java,
+}
+
+impl CodeLanguage {
+    /// Detect the language from a file extension (without the leading dot).
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext.to_ascii_lowercase().as_str() {
+            "rs" => Some(Self::Rust),
+            "py" | "pyi" => Some(Self::Python),
+            "js" | "mjs" | "cjs" | "jsx" => Some(Self::JavaScript),
+            "ts" | "mts" | "cts" => Some(Self::TypeScript),
+            "tsx" => Some(Self::Tsx),
+            "go" => Some(Self::Go),
+            "java" => Some(Self::Java),
+            _ => None,
+        }
+    }
+
+    /// Detect the language from a file path.
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        path.as_ref()
+            .extension()
+            .and_then(|e| e.to_str())
+            .and_then(Self::from_extension)
+    }
+
+    /// Lowercase language name, as reported in [`SimplifiedCode::language`].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Rust => "rust",
+            Self::Python => "python",
+            Self::JavaScript => "javascript",
+            Self::TypeScript => "typescript",
+            Self::Tsx => "tsx",
+            Self::Go => "go",
+            Self::Java => "java",
+        }
+    }
+}
+
+/// Simplify source code in any supported language to its structural skeleton.
+pub fn simplify_code(source: &str, language: CodeLanguage) -> Result<SimplifiedCode, String> {
+    match language {
+        CodeLanguage::Rust => simplify_rust_code(source),
+        other => polyglot::simplify(source, other),
+    }
+}
+
+/// Simplify a source file, detecting the language from its extension.
+pub fn simplify_file(path: impl AsRef<Path>, source: &str) -> Result<SimplifiedCode, String> {
+    let path = path.as_ref();
+    let language = CodeLanguage::from_path(path)
+        .ok_or_else(|| format!("Unsupported source file: {}", path.display()))?;
+    simplify_code(source, language)
 }
 
 /// Simplify Rust source code to its structural skeleton.
@@ -35,12 +110,14 @@ pub fn simplify_rust_code(source: &str) -> Result<SimplifiedCode, String> {
 
     let mut output = String::new();
     let mut item_count = 0;
+    let mut symbols = Vec::new();
 
     for item in &syntax.items {
         if let Some(simplified) = simplify_item(item) {
             output.push_str(&simplified);
             output.push_str("\n\n");
             item_count += 1;
+            collect_symbols(item, &mut symbols);
         }
     }
 
@@ -48,9 +125,42 @@ pub fn simplify_rust_code(source: &str) -> Result<SimplifiedCode, String> {
         skeleton: output.trim().to_string(),
         item_count,
         language: "rust".to_string(),
+        symbols,
     })
 }
 
+/// Collect the names defined by an item, including methods and nested modules.
+fn collect_symbols(item: &Item, symbols: &mut Vec<String>) {
+    match item {
+        Item::Fn(f) => symbols.push(f.sig.ident.to_string()),
+        Item::Struct(s) => symbols.push(s.ident.to_string()),
+        Item::Enum(e) => symbols.push(e.ident.to_string()),
+        Item::Const(c) => symbols.push(c.ident.to_string()),
+        Item::Static(s) => symbols.push(s.ident.to_string()),
+        Item::Type(t) => symbols.push(t.ident.to_string()),
+        Item::Trait(t) => {
+            symbols.push(t.ident.to_string());
+            symbols.extend(t.items.iter().filter_map(|item| match item {
+                TraitItem::Fn(m) => Some(m.sig.ident.to_string()),
+                _ => None,
+            }));
+        }
+        Item::Impl(i) => symbols.extend(i.items.iter().filter_map(|item| match item {
+            ImplItem::Fn(m) => Some(m.sig.ident.to_string()),
+            _ => None,
+        })),
+        Item::Mod(m) => {
+            symbols.push(m.ident.to_string());
+            if let Some((_, items)) = &m.content {
+                for item in items {
+                    collect_symbols(item, symbols);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Simplify a single item.
 fn simplify_item(item: &Item) -> Option<String> {
     match item {
@@ -412,6 +522,7 @@ mod tests {
         assert!(result.skeleton.contains("pub fn new"));
         assert!(result.skeleton.contains("fn get_age"));
         assert!(result.skeleton.contains("&self"));
+        assert_eq!(result.symbols, vec!["new", "get_age"]);
     }
 
     #[test]
@@ -431,4 +542,223 @@ mod tests {
         assert!(result.skeleton.contains("fn goodbye"));
         assert!(result.skeleton.contains("self"));
     }
+
+    #[test]
+    fn test_language_detection() {
+        assert_eq!(
+            CodeLanguage::from_path("src/app.py"),
+            Some(CodeLanguage::Python)
+        );
+        assert_eq!(
+            CodeLanguage::from_path("web/App.tsx"),
+            Some(CodeLanguage::Tsx)
+        );
+        assert_eq!(
+            CodeLanguage::from_path("Main.JAVA"),
+            Some(CodeLanguage::Java)
+        );
+        assert_eq!(CodeLanguage::from_path("README.md"), None);
+        assert!(simplify_file("notes.txt", "hello").is_err());
+    }
+
+    #[test]
+    fn test_simplify_python() {
+        let code = r#"
+import os
+
+MAX_RETRIES = 3
+
+# Greets people.
+@dataclass
+class Greeter(Base):
+    """Say hello."""
+    name: str = "world"
+
+    def greet(self, loud: bool = False) -> str:
+        """Return the greeting."""
+        msg = f"Hello, {self.name}"
+        return msg.upper() if loud else msg
+
+def main():
+    Greeter().greet()
+"#;
+        let result = simplify_code(code, CodeLanguage::Python).unwrap();
+        assert_eq!(result.language, "python");
+        assert_eq!(result.item_count, 3);
+        assert!(result.skeleton.contains("MAX_RETRIES = 3"));
+        assert!(result
+            .skeleton
+            .contains("# Greets people.\n@dataclass\nclass Greeter(Base):"));
+        assert!(result.skeleton.contains("    name: str = \"world\""));
+        assert!(result
+            .skeleton
+            .contains("    def greet(self, loud: bool = False) -> str:\n        \"\"\"Return the greeting.\"\"\""));
+        assert!(result.skeleton.contains("def main(): ..."));
+        assert!(!result.skeleton.contains("import os"));
+        assert!(!result.skeleton.contains("upper()"));
+        assert_eq!(
+            result.symbols,
+            vec!["MAX_RETRIES", "Greeter", "name", "greet", "main"]
+        );
+    }
+
+    #[test]
+    fn test_simplify_typescript() {
+        let code = r#"
+import { x } from "./x";
+
+export interface User {
+  id: string;
+}
+
+/** Fetch a user. */
+export async function getUser(id: string): Promise<User> {
+  return fetch(`/users/${id}`).then((r) => r.json());
+}
+
+export const add = (a: number, b: number): number => {
+  return a + b;
+};
+
+const LIMIT = 10;
+
+export class Service extends Base {
+  private cache: Map<string, User> = new Map();
+
+  constructor(private http: Http) {
+    super();
+  }
+
+  load(id: string): User | undefined {
+    return this.cache.get(id);
+  }
+}
+"#;
+        let result = simplify_code(code, CodeLanguage::TypeScript).unwrap();
+        assert!(result
+            .skeleton
+            .contains("export interface User {\n  id: string;\n}"));
+        assert!(result.skeleton.contains(
+            "/** Fetch a user. */\nexport async function getUser(id: string): Promise<User> { ... }"
+        ));
+        assert!(result
+            .skeleton
+            .contains("export const add = (a: number, b: number): number => { ... }"));
+        assert!(result
+            .skeleton
+            .contains("export class Service extends Base {"));
+        assert!(result
+            .skeleton
+            .contains("    constructor(private http: Http) { ... }"));
+        assert!(result
+            .skeleton
+            .contains("    load(id: string): User | undefined { ... }"));
+        assert!(!result.skeleton.contains("LIMIT"));
+        assert!(!result.skeleton.contains("fetch("));
+    }
+
+    #[test]
+    fn test_simplify_javascript() {
+        let code = r#"
+function handler(req, res) {
+  res.send("ok");
+}
+
+class Queue {
+  push(item) {
+    this.items.push(item);
+  }
+}
+"#;
+        let result = simplify_code(code, CodeLanguage::JavaScript).unwrap();
+        assert_eq!(result.item_count, 2);
+        assert!(result
+            .skeleton
+            .contains("function handler(req, res) { ... }"));
+        assert!(result
+            .skeleton
+            .contains("class Queue {\n    push(item) { ... }\n}"));
+    }
+
+    #[test]
+    fn test_simplify_go() {
+        let code = r#"
+package store
+
+import "fmt"
+
+// Store keeps items.
+type Store struct {
+	items map[string]string
+}
+
+func (s *Store) Get(key string) (string, bool) {
+	v, ok := s.items[key]
+	return v, ok
+}
+
+func New() *Store {
+	return &Store{items: map[string]string{}}
+}
+"#;
+        let result = simplify_code(code, CodeLanguage::Go).unwrap();
+        assert!(result.skeleton.starts_with("package store"));
+        assert!(result
+            .skeleton
+            .contains("// Store keeps items.\ntype Store struct {"));
+        assert!(result
+            .skeleton
+            .contains("func (s *Store) Get(key string) (string, bool) { ... }"));
+        assert!(result.skeleton.contains("func New() *Store { ... }"));
+        assert_eq!(result.symbols, vec!["Store", "Get", "New"]);
+        assert!(!result.skeleton.contains("fmt"));
+    }
+
+    #[test]
+    fn test_simplify_java() {
+        let code = r#"
+package com.example;
+
+import java.util.List;
+
+public class Repo implements Store {
+    private final List<String> items;
+
+    /** Create a repo. */
+    public Repo(List<String> items) {
+        this.items = items;
+    }
+
+    @Override
+    public int size() {
+        return items.size();
+    }
+
+    enum Mode { READ, WRITE }
+}
+
+interface Store {
+    int size();
+}
+"#;
+        let result = simplify_code(code, CodeLanguage::Java).unwrap();
+        assert!(result.skeleton.contains("package com.example;"));
+        assert!(result
+            .skeleton
+            .contains("public class Repo implements Store {"));
+        assert!(result
+            .skeleton
+            .contains("    private final List<String> items;"));
+        assert!(result
+            .skeleton
+            .contains("    /** Create a repo. */\n    public Repo(List<String> items) { ... }"));
+        assert!(result
+            .skeleton
+            .contains("    @Override\n    public int size() { ... }"));
+        assert!(result.skeleton.contains("READ"));
+        assert!(result
+            .skeleton
+            .contains("interface Store {\n    int size();\n}"));
+        assert!(!result.skeleton.contains("items.size()"));
+    }
 }
@@ -0,0 +1,369 @@
+//! Tree-sitter based skeletonization for non-Rust languages.
+//!
+//! Each language maps syntax node kinds to a [`Rule`]; a single walker then
+//! emits signatures, type declarations and class members with bodies elided.
+
+use std::cell::RefCell;
+
+use tree_sitter::{Node, Parser};
+
+use super::{CodeLanguage, SimplifiedCode};
+
+/// Single-line declarations longer than this are dropped rather than kept.
+const MAX_SHORT_DECL_CHARS: usize = 160;
+
+/// How a syntax node is rendered in the skeleton.
+#[derive(Debug, Clone, Copy)]
+enum Rule {
+    /// Emit the full node text.
+    Keep,
+    /// Emit the node only if it fits on one short line.
+    KeepShort,
+    /// Emit the declaration header with the body elided.
+    Signature,
+    /// Emit the header, then the simplified members of the body.
+    Container,
+    /// Emit the text before the wrapped declaration, then simplify it.
+    Wrapper(&'static str),
+    /// Simplify the children in place.
+    Flatten,
+    /// Variable declaration; kept as a signature when bound to a function.
+    Binding,
+    /// Python assignment statement (module constants, dataclass fields).
+    Assignment,
+    Skip,
+}
+
+fn rule(language: CodeLanguage, kind: &str) -> Rule {
+    match language {
+        CodeLanguage::Python => match kind {
+            "function_definition" => Rule::Signature,
+            "class_definition" => Rule::Container,
+            "decorated_definition" => Rule::Wrapper("definition"),
+            "expression_statement" => Rule::Assignment,
+            _ => Rule::Skip,
+        },
+        CodeLanguage::JavaScript | CodeLanguage::TypeScript | CodeLanguage::Tsx => match kind {
+            "function_declaration"
+            | "generator_function_declaration"
+            | "method_definition"
+            | "function_signature" => Rule::Signature,
+            "class_declaration" | "abstract_class_declaration" | "internal_module" | "module" => {
+                Rule::Container
+            }
+            "export_statement" => Rule::Wrapper("declaration"),
+            "lexical_declaration" | "variable_declaration" => Rule::Binding,
+            "interface_declaration"
+            | "type_alias_declaration"
+            | "enum_declaration"
+            | "ambient_declaration"
+            | "method_signature"
+            | "abstract_method_signature"
+            | "index_signature" => Rule::Keep,
+            "public_field_definition" | "field_definition" => Rule::KeepShort,
+            // `namespace Foo {}` parses as an expression statement.
+            "expression_statement" => Rule::Flatten,
+            _ => Rule::Skip,
+        },
+        CodeLanguage::Go => match kind {
+            "package_clause" | "type_declaration" => Rule::Keep,
+            "function_declaration" | "method_declaration" => Rule::Signature,
+            "const_declaration" | "var_declaration" => Rule::KeepShort,
+            _ => Rule::Skip,
+        },
+        CodeLanguage::Java => match kind {
+            "package_declaration" | "enum_constant" => Rule::Keep,
+            "class_declaration"
+            | "interface_declaration"
+            | "enum_declaration"
+            | "record_declaration"
+            | "annotation_type_declaration" => Rule::Container,
+            "method_declaration"
+            | "constructor_declaration"
+            | "compact_constructor_declaration" => Rule::Signature,
+            "field_declaration" | "constant_declaration" => Rule::KeepShort,
+            "enum_body_declarations" => Rule::Flatten,
+            _ => Rule::Skip,
+        },
+        CodeLanguage::Rust => Rule::Skip,
+    }
+}
+
+fn grammar(language: CodeLanguage) -> Option<tree_sitter::Language> {
+    Some(match language {
+        CodeLanguage::Python => tree_sitter_python::LANGUAGE.into(),
+        CodeLanguage::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+        CodeLanguage::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+        CodeLanguage::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+        CodeLanguage::Go => tree_sitter_go::LANGUAGE.into(),
+        CodeLanguage::Java => tree_sitter_java::LANGUAGE.into(),
+        CodeLanguage::Rust => return None,
+    })
+}
+
+/// Simplify source code in a tree-sitter supported language.
+pub(super) fn simplify(source: &str, language: CodeLanguage) -> Result<SimplifiedCode, String> {
+    let grammar =
+        grammar(language).ok_or_else(|| format!("No grammar for {}", language.as_str()))?;
+    let mut parser = Parser::new();
+    parser
+        .set_language(&grammar)
+        .map_err(|e| format!("Grammar error: {}", e))?;
+    let tree = parser
+        .parse(source, None)
+        .ok_or_else(|| "Parse error: parser returned no tree".to_string())?;
+
+    let walker = Walker {
+        source,
+        language,
+        symbols: RefCell::new(Vec::new()),
+    };
+    let items = walker.members(tree.root_node());
+
+    Ok(SimplifiedCode {
+        skeleton: items.join("\n\n"),
+        item_count: items.len(),
+        language: language.as_str().to_string(),
+        symbols: walker.symbols.into_inner(),
+    })
+}
+
+struct Walker<'a> {
+    source: &'a str,
+    language: CodeLanguage,
+    symbols: RefCell<Vec<String>>,
+}
+
+impl Walker<'_> {
+    fn braces(&self) -> bool {
+        self.language != CodeLanguage::Python
+    }
+
+    fn text(&self, node: Node) -> &str {
+        &self.source[node.byte_range()]
+    }
+
+    /// Text from the node start to `end`, with continuation lines dedented by
+    /// the indentation of the line the node starts on.
+    fn dedented(&self, node: Node, end: usize) -> String {
+        let line_start = self.source[..node.start_byte()]
+            .rfind('\n')
+            .map_or(0, |i| i + 1);
+        let line = &self.source[line_start..];
+        let column = line.len() - line.trim_start_matches([' ', '\t']).len();
+        let text = self.source[node.start_byte()..end].trim_end();
+        let mut lines = text.lines();
+        let mut out = lines.next().unwrap_or_default().to_string();
+        for line in lines {
+            let indent = line.len() - line.trim_start().len();
+            out.push('\n');
+            out.push_str(&line[indent.min(column)..]);
+        }
+        out
+    }
+
+    /// Simplify the named children of `node`, attaching adjacent leading comments.
+    fn members(&self, node: Node) -> Vec<String> {
+        let mut items = Vec::new();
+        let mut comments: Vec<Node> = Vec::new();
+        let mut cursor = node.walk();
+
+        for child in node.named_children(&mut cursor) {
+            if child.kind().contains("comment") {
+                let adjacent = comments
+                    .last()
+                    .is_none_or(|c| c.end_position().row + 1 >= child.start_position().row);
+                if !adjacent {
+                    comments.clear();
+                }
+                comments.push(child);
+                continue;
+            }
+
+            let rendered = self.render(child);
+            if let Some(rendered) = rendered {
+                let leading = comments
+                    .last()
+                    .filter(|c| c.end_position().row + 1 >= child.start_position().row)
+                    .map(|_| {
+                        comments
+                            .iter()
+                            .map(|c| self.dedented(*c, c.end_byte()))
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    });
+                items.extend(rendered.into_iter().enumerate().map(|(i, item)| {
+                    match (&leading, i) {
+                        (Some(doc), 0) => format!("{}\n{}", doc, item),
+                        _ => item,
+                    }
+                }));
+            }
+            comments.clear();
+        }
+
+        items
+    }
+
+    /// Names declared by a node.
+    fn names(&self, node: Node) -> Vec<String> {
+        if let Some(name) = node.child_by_field_name("name") {
+            return vec![self.text(name).to_string()];
+        }
+        // Go `type ( A ...; B ... )`, variable declarators and assignments.
+        let mut cursor = node.walk();
+        node.named_children(&mut cursor)
+            .filter_map(|child| match child.kind() {
+                "type_spec" | "type_alias" | "variable_declarator" => {
+                    child.child_by_field_name("name")
+                }
+                "assignment" => child.child_by_field_name("left"),
+                _ => None,
+            })
+            .filter(|name| name.kind().contains("identifier"))
+            .map(|name| self.text(name).to_string())
+            .collect()
+    }
+
+    fn render(&self, node: Node) -> Option<Vec<String>> {
+        let position = self.symbols.borrow().len();
+        let rendered = self.render_node(node)?;
+        // Wrappers and flattened nodes record their inner declarations themselves.
+        if !matches!(
+            rule(self.language, node.kind()),
+            Rule::Wrapper(_) | Rule::Flatten
+        ) {
+            let names = self.names(node);
+            self.symbols.borrow_mut().splice(position..position, names);
+        }
+        Some(rendered)
+    }
+
+    fn render_node(&self, node: Node) -> Option<Vec<String>> {
+        match rule(self.language, node.kind()) {
+            Rule::Skip => None,
+            Rule::Keep => Some(vec![self.dedented(node, node.end_byte())]),
+            Rule::KeepShort => self.short(node).map(|s| vec![s]),
+            Rule::Signature => Some(vec![self.signature(node)]),
+            Rule::Container => Some(vec![self.container(node)]),
+            Rule::Flatten => {
+                let items = self.members(node);
+                (!items.is_empty()).then_some(items)
+            }
+            Rule::Wrapper(field) => match node.child_by_field_name(field) {
+                Some(inner) => {
+                    let raw = &self.source[node.start_byte()..inner.start_byte()];
+                    let prefix = self.dedented(node, inner.start_byte());
+                    let inner = self.render(inner)?;
+                    let sep = if prefix.is_empty() {
+                        ""
+                    } else if raw[raw.trim_end().len()..].contains('\n') {
+                        "\n"
+                    } else {
+                        " "
+                    };
+                    Some(
+                        inner
+                            .into_iter()
+                            .map(|item| format!("{}{}{}", prefix, sep, item))
+                            .collect(),
+                    )
+                }
+                // e.g. `export { a, b };`
+                None => self.short(node).map(|s| vec![s]),
+            },
+            Rule::Binding => self.binding(node).map(|s| vec![s]),
+            Rule::Assignment => {
+                let is_assignment = node
+                    .named_child(0)
+                    .is_some_and(|c| c.kind() == "assignment");
+                if is_assignment {
+                    self.short(node).map(|s| vec![s])
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    fn short(&self, node: Node) -> Option<String> {
+        let text = self.text(node).trim();
+        (!text.contains('\n') && text.len() <= MAX_SHORT_DECL_CHARS).then(|| text.to_string())
+    }
+
+    fn elided(&self, header: String) -> String {
+        if self.braces() {
+            format!("{} {{ ... }}", header)
+        } else {
+            format!("{} ...", header)
+        }
+    }
+
+    fn signature(&self, node: Node) -> String {
+        let Some(body) = node.child_by_field_name("body") else {
+            // Abstract and interface methods have no body.
+            return self.dedented(node, node.end_byte());
+        };
+        let header = self.dedented(node, body.start_byte());
+
+        if let Some(doc) = self.python_docstring(body) {
+            return format!("{}\n    {}\n    ...", header, doc);
+        }
+        self.elided(header)
+    }
+
+    /// First statement of a Python block, if it is a docstring.
+    fn python_docstring(&self, body: Node) -> Option<String> {
+        if self.language != CodeLanguage::Python {
+            return None;
+        }
+        let first = body.named_child(0)?;
+        let string = first.named_child(0)?;
+        (first.kind() == "expression_statement" && string.kind() == "string")
+            .then(|| self.dedented(string, string.end_byte()))
+    }
+
+    fn container(&self, node: Node) -> String {
+        let Some(body) = node.child_by_field_name("body") else {
+            return self.dedented(node, node.end_byte());
+        };
+        let header = self.dedented(node, body.start_byte());
+        let mut members = Vec::new();
+        if let Some(doc) = self.python_docstring(body) {
+            members.push(doc);
+        }
+        members.extend(self.members(body));
+        let members: Vec<String> = members
+            .into_iter()
+            .map(|m| format!("    {}", m.replace('\n', "\n    ")))
+            .collect();
+
+        match (self.braces(), members.is_empty()) {
+            (true, true) => format!("{} {{}}", header),
+            (true, false) => format!("{} {{\n{}\n}}", header, members.join("\n")),
+            (false, true) => format!("{} ...", header),
+            (false, false) => format!("{}\n{}", header, members.join("\n")),
+        }
+    }
+
+    /// `const f = (a) => { ... }` and friends; other bindings are dropped.
+    fn binding(&self, node: Node) -> Option<String> {
+        let mut cursor = node.walk();
+        let declarator = node
+            .named_children(&mut cursor)
+            .find(|c| c.kind() == "variable_declarator")?;
+        let value = declarator.child_by_field_name("value")?;
+        match value.kind() {
+            "arrow_function" | "function_expression" | "function" | "generator_function" => {
+                let body = value.child_by_field_name("body")?;
+                if body.kind() == "statement_block" {
+                    Some(self.elided(self.dedented(node, body.start_byte())))
+                } else {
+                    // Expression-bodied arrow function.
+                    Some(format!("{} ...", self.dedented(node, body.start_byte())))
+                }
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,553 @@
+//! CalDAV calendar event listing and creation.
+//!
+//! Provider mapping: `base_url` is the calendar collection URL
+//! (e.g. `https://dav.example.com/calendars/me/work/`), `model_id` the
+//! username and the secret the password or app password.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDate, NaiveDateTime, Utc};
+use regex::Regex;
+use serde_json::{json, Value};
+use std::sync::OnceLock;
+
+use multi_agent_core::{
+    traits::Tool,
+    types::{ToolOutput, ToolRiskLevel},
+    Error, Result,
+};
+
+use super::{
+    error_output, provider_arg, request_error, required_str, ConnectorContext, Credentials,
+};
+
+pub(crate) const VENDOR: &str = "caldav";
+const DEFAULT_WINDOW_DAYS: i64 = 7;
+const MAX_EVENTS: usize = 100;
+const MAX_DESCRIPTION_CHARS: usize = 500;
+const ICS_TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+fn collection_url(creds: &Credentials) -> String {
+    format!("{}/", creds.provider.base_url.trim_end_matches('/'))
+}
+
+fn request(
+    ctx: &ConnectorContext,
+    creds: &Credentials,
+    method: reqwest::Method,
+    url: &str,
+) -> reqwest::RequestBuilder {
+    ctx.client()
+        .request(method, url)
+        .basic_auth(&creds.provider.model_id, Some(&creds.secret))
+}
+
+/// Parse an RFC 3339 timestamp or a bare `YYYY-MM-DD` date (midnight UTC).
+pub(crate) fn parse_time(value: &str, field: &str) -> Result<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+    Err(Error::invalid_request(format!(
+        "{} must be an RFC 3339 timestamp or YYYY-MM-DD date, got '{}'",
+        field, value
+    )))
+}
+
+/// Escape a TEXT property value (RFC 5545 §3.3.11).
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Fold a content line at 75 octets (RFC 5545 §3.1) without splitting characters.
+fn fold_line(line: &str) -> String {
+    let mut out = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out
+}
+
+/// A new event to be written as an iCalendar object.
+pub(crate) struct NewEvent<'a> {
+    pub uid: &'a str,
+    pub summary: &'a str,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub description: Option<&'a str>,
+    pub location: Option<&'a str>,
+    pub attendees: Vec<&'a str>,
+}
+
+pub(crate) fn build_ics(event: &NewEvent<'_>, now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//OpenCoordex//Connectors//EN".to_string(),
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}", event.uid),
+        format!("DTSTAMP:{}", now.format(ICS_TIME_FORMAT)),
+        format!("DTSTART:{}", event.start.format(ICS_TIME_FORMAT)),
+        format!("DTEND:{}", event.end.format(ICS_TIME_FORMAT)),
+        format!("SUMMARY:{}", escape_text(event.summary)),
+    ];
+    if let Some(description) = event.description {
+        lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+    }
+    if let Some(location) = event.location {
+        lines.push(format!("LOCATION:{}", escape_text(location)));
+    }
+    for attendee in &event.attendees {
+        lines.push(format!("ATTENDEE;RSVP=TRUE:mailto:{}", attendee));
+    }
+    lines.push("END:VEVENT".to_string());
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut ics = lines
+        .iter()
+        .map(|l| fold_line(l))
+        .collect::<Vec<_>>()
+        .join("\r\n");
+    ics.push_str("\r\n");
+    ics
+}
+
+/// Convert an iCalendar date or date-time value to RFC 3339 (or a bare date).
+fn ics_time(value: &str) -> String {
+    if let Ok(dt) = NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S") {
+        // Floating and TZID-qualified times are reported as written.
+        return if value.ends_with('Z') {
+            dt.and_utc().to_rfc3339()
+        } else {
+            dt.format("%Y-%m-%dT%H:%M:%S").to_string()
+        };
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y%m%d") {
+        return date.format("%Y-%m-%d").to_string();
+    }
+    value.to_string()
+}
+
+/// Extract VEVENT summaries from an iCalendar object.
+pub(crate) fn parse_events(ics: &str) -> Vec<Value> {
+    // Unfold continuation lines first.
+    let unfolded = ics
+        .replace("\r\n", "\n")
+        .replace("\n ", "")
+        .replace("\n\t", "");
+    let mut events = Vec::new();
+    let mut current: Option<serde_json::Map<String, Value>> = None;
+
+    for line in unfolded.lines() {
+        match line {
+            "BEGIN:VEVENT" => current = Some(serde_json::Map::new()),
+            "END:VEVENT" => events.extend(current.take().map(Value::Object)),
+            _ => {
+                let Some(event) = current.as_mut() else {
+                    continue;
+                };
+                let Some((name, value)) = line.split_once(':') else {
+                    continue;
+                };
+                let name = name
+                    .split(';')
+                    .next()
+                    .unwrap_or_default()
+                    .to_ascii_uppercase();
+                let (key, value) = match name.as_str() {
+                    "UID" => ("uid", json!(value)),
+                    "SUMMARY" => ("summary", json!(unescape_text(value))),
+                    "LOCATION" => ("location", json!(unescape_text(value))),
+                    "DESCRIPTION" => (
+                        "description",
+                        json!(unescape_text(value)
+                            .chars()
+                            .take(MAX_DESCRIPTION_CHARS)
+                            .collect::<String>()),
+                    ),
+                    "DTSTART" => ("start", json!(ics_time(value))),
+                    "DTEND" => ("end", json!(ics_time(value))),
+                    _ => continue,
+                };
+                event.insert(key.to_string(), value);
+            }
+        }
+    }
+    events
+}
+
+fn calendar_data_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?s)<(?:[A-Za-z0-9_-]+:)?calendar-data[^>]*>(.*?)</(?:[A-Za-z0-9_-]+:)?calendar-data>")
+            .expect("valid regex")
+    })
+}
+
+fn xml_unescape(value: &str) -> String {
+    let value = value.trim();
+    if let Some(cdata) = value
+        .strip_prefix("<![CDATA[")
+        .and_then(|v| v.strip_suffix("]]>"))
+    {
+        return cdata.to_string();
+    }
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#13;", "\r")
+        .replace("&#xD;", "\r")
+        .replace("&amp;", "&")
+}
+
+/// Extract events from a CalDAV multistatus REPORT response, sorted by start.
+pub(crate) fn parse_multistatus(xml: &str) -> Vec<Value> {
+    let mut events: Vec<Value> = calendar_data_re()
+        .captures_iter(xml)
+        .flat_map(|c| parse_events(&xml_unescape(&c[1])))
+        .collect();
+    events.sort_by(|a, b| a["start"].as_str().cmp(&b["start"].as_str()));
+    events
+}
+
+fn calendar_query(start: DateTime<Utc>, end: DateTime<Utc>) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8" ?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop><d:getetag/><c:calendar-data/></d:prop>
+  <c:filter>
+    <c:comp-filter name="VCALENDAR">
+      <c:comp-filter name="VEVENT">
+        <c:time-range start="{}" end="{}"/>
+      </c:comp-filter>
+    </c:comp-filter>
+  </c:filter>
+</c:calendar-query>"#,
+        start.format(ICS_TIME_FORMAT),
+        end.format(ICS_TIME_FORMAT)
+    )
+}
+
+/// List events in a CalDAV calendar.
+pub struct CalendarListEventsTool {
+    ctx: ConnectorContext,
+}
+
+impl CalendarListEventsTool {
+    pub fn new(ctx: ConnectorContext) -> Self {
+        Self { ctx }
+    }
+}
+
+#[async_trait]
+impl Tool for CalendarListEventsTool {
+    fn name(&self) -> &str {
+        "calendar_list_events"
+    }
+
+    fn description(&self) -> &str {
+        "List calendar events between two times (default: the next 7 days). \
+         Returns summary, start, end and location for each event."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "start": {"type": "string", "description": "RFC 3339 timestamp or YYYY-MM-DD; default now"},
+                "end": {"type": "string", "description": "RFC 3339 timestamp or YYYY-MM-DD; default start + 7 days"},
+                "provider_id": {"type": "string", "description": "CalDAV provider to use when several are registered"}
+            }
+        })
+    }
+
+    async fn execute(&self, args: Value) -> Result<ToolOutput> {
+        let start = match args.get("start").and_then(|v| v.as_str()) {
+            Some(s) => parse_time(s, "start")?,
+            None => Utc::now(),
+        };
+        let end = match args.get("end").and_then(|v| v.as_str()) {
+            Some(s) => parse_time(s, "end")?,
+            None => start + ChronoDuration::days(DEFAULT_WINDOW_DAYS),
+        };
+        if end <= start {
+            return Err(Error::invalid_request("end must be after start"));
+        }
+        let creds = self.ctx.credentials(VENDOR, provider_arg(&args)).await?;
+
+        let method = reqwest::Method::from_bytes(b"REPORT").expect("valid method");
+        let response = match request(&self.ctx, &creds, method, &collection_url(&creds))
+            .header("Depth", "1")
+            .header("Content-Type", "application/xml; charset=utf-8")
+            .body(calendar_query(start, end))
+            .send()
+            .await
+        {
+            Ok(r) => r,
+            Err(e) => return Ok(request_error("CalDAV", e)),
+        };
+        if !response.status().is_success() {
+            return Ok(error_output("CalDAV", response).await);
+        }
+        let body = response
+            .text()
+            .await
+            .map_err(|e| Error::tool_execution(format!("Invalid CalDAV response: {}", e)))?;
+
+        let mut events = parse_multistatus(&body);
+        let total = events.len();
+        events.truncate(MAX_EVENTS);
+        let mut text = format!(
+            "{} event(s) between {} and {}",
+            total,
+            start.to_rfc3339(),
+            end.to_rfc3339()
+        );
+        for event in &events {
+            text.push_str(&format!(
+                "\n{} – {}: {}",
+                event["start"].as_str().unwrap_or("?"),
+                event["end"].as_str().unwrap_or("?"),
+                event["summary"].as_str().unwrap_or("(no title)")
+            ));
+        }
+        Ok(ToolOutput::text(text).with_data(json!({ "total": total, "events": events })))
+    }
+}
+
+/// Create an event in a CalDAV calendar.
+pub struct CalendarCreateEventTool {
+    ctx: ConnectorContext,
+}
+
+impl CalendarCreateEventTool {
+    pub fn new(ctx: ConnectorContext) -> Self {
+        Self { ctx }
+    }
+}
+
+#[async_trait]
+impl Tool for CalendarCreateEventTool {
+    fn name(&self) -> &str {
+        "calendar_create_event"
+    }
+
+    fn description(&self) -> &str {
+        "Create a calendar event. Times are RFC 3339 timestamps; attendees are email addresses."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "summary": {"type": "string", "description": "Event title"},
+                "start": {"type": "string", "description": "RFC 3339 start time"},
+                "end": {"type": "string", "description": "RFC 3339 end time"},
+                "description": {"type": "string"},
+                "location": {"type": "string"},
+                "attendees": {"type": "array", "items": {"type": "string"}},
+                "provider_id": {"type": "string", "description": "CalDAV provider to use when several are registered"}
+            },
+            "required": ["summary", "start", "end"]
+        })
+    }
+
+    fn risk_level(&self) -> ToolRiskLevel {
+        ToolRiskLevel::Medium
+    }
+
+    async fn execute(&self, args: Value) -> Result<ToolOutput> {
+        let summary = required_str(&args, "summary")?;
+        let start = parse_time(required_str(&args, "start")?, "start")?;
+        let end = parse_time(required_str(&args, "end")?, "end")?;
+        if end <= start {
+            return Err(Error::invalid_request("end must be after start"));
+        }
+        let attendees: Vec<&str> = args
+            .get("attendees")
+            .and_then(|v| v.as_array())
+            .map(|a| a.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+        if let Some(bad) = attendees
+            .iter()
+            .find(|a| !a.contains('@') || a.contains(|c: char| c.is_whitespace() || c == ':'))
+        {
+            return Err(Error::invalid_request(format!(
+                "invalid attendee email '{}'",
+                bad
+            )));
+        }
+        let creds = self.ctx.credentials(VENDOR, provider_arg(&args)).await?;
+
+        let id = uuid::Uuid::new_v4();
+        let uid = format!("{}@opencoordex", id);
+        let ics = build_ics(
+            &NewEvent {
+                uid: &uid,
+                summary,
+                start,
+                end,
+                description: args.get("description").and_then(|v| v.as_str()),
+                location: args.get("location").and_then(|v| v.as_str()),
+                attendees,
+            },
+            Utc::now(),
+        );
+        let url = format!("{}{}.ics", collection_url(&creds), id);
+        let response = match request(&self.ctx, &creds, reqwest::Method::PUT, &url)
+            .header("Content-Type", "text/calendar; charset=utf-8")
+            .header("If-None-Match", "*")
+            .body(ics)
+            .send()
+            .await
+        {
+            Ok(r) => r,
+            Err(e) => return Ok(request_error("CalDAV", e)),
+        };
+        if !response.status().is_success() {
+            return Ok(error_output("CalDAV", response).await);
+        }
+        Ok(ToolOutput::text(format!(
+            "Created event '{}' from {} to {}",
+            summary,
+            start.to_rfc3339(),
+            end.to_rfc3339()
+        ))
+        .with_data(json!({ "uid": uid, "url": url })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support;
+    use super::*;
+
+    #[test]
+    fn test_build_and_parse_ics_roundtrip() {
+        let start = parse_time("2026-03-02T09:00:00+01:00", "start").unwrap();
+        let event = NewEvent {
+            uid: "abc@opencoordex",
+            summary: "Review; incident, follow-up",
+            start,
+            end: start + ChronoDuration::hours(1),
+            description: Some(&"x".repeat(120)),
+            location: Some("Room 1"),
+            attendees: vec!["a@example.com"],
+        };
+        let ics = build_ics(&event, start);
+        assert!(ics.contains("DTSTART:20260302T080000Z\r\n"));
+        assert!(ics.contains("SUMMARY:Review\\; incident\\, follow-up"));
+        assert!(ics.lines().all(|l| l.len() <= 75));
+
+        let parsed = parse_events(&ics);
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0]["summary"], "Review; incident, follow-up");
+        assert_eq!(parsed[0]["description"].as_str().unwrap().len(), 120);
+        assert_eq!(parsed[0]["start"], "2026-03-02T08:00:00+00:00");
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(
+            parse_time("2026-01-05", "start").unwrap().to_rfc3339(),
+            "2026-01-05T00:00:00+00:00"
+        );
+        assert!(parse_time("tomorrow", "start").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_events_parses_multistatus() {
+        let xml = r#"<?xml version="1.0"?>
+<d:multistatus xmlns:d="DAV:" xmlns:cal="urn:ietf:params:xml:ns:caldav">
+ <d:response><d:propstat><d:prop>
+  <cal:calendar-data>BEGIN:VCALENDAR&#13;
+BEGIN:VEVENT&#13;
+UID:2&#13;
+SUMMARY:Standup &amp; planning&#13;
+DTSTART;TZID=Europe/Berlin:20260302T100000&#13;
+END:VEVENT&#13;
+END:VCALENDAR</cal:calendar-data>
+ </d:prop></d:propstat></d:response>
+ <d:response><d:propstat><d:prop>
+  <cal:calendar-data><![CDATA[BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:1
+SUMMARY:Offsite
+DTSTART;VALUE=DATE:20260301
+END:VEVENT
+END:VCALENDAR]]></cal:calendar-data>
+ </d:prop></d:propstat></d:response>
+</d:multistatus>"#;
+        let (base, captured) = test_support::serve(vec![(207, xml.to_string())]).await;
+        let tool = CalendarListEventsTool::new(test_support::context(VENDOR, &base, "me").await);
+
+        let output = tool
+            .execute(json!({"start": "2026-03-01", "end": "2026-03-08"}))
+            .await
+            .unwrap();
+        assert!(output.success, "{}", output.content);
+        let events = output.data.unwrap()["events"].clone();
+        assert_eq!(events[0]["summary"], "Offsite");
+        assert_eq!(events[0]["start"], "2026-03-01");
+        assert_eq!(events[1]["summary"], "Standup & planning");
+        assert_eq!(events[1]["start"], "2026-03-02T10:00:00");
+
+        let requests = captured.lock().await;
+        assert!(requests[0].head.starts_with("REPORT / "));
+        assert!(requests[0].body.contains(r#"start="20260301T000000Z""#));
+    }
+
+    #[tokio::test]
+    async fn test_create_event_rejects_bad_input() {
+        let tool = CalendarCreateEventTool::new(
+            test_support::context(VENDOR, "http://127.0.0.1:9", "me").await,
+        );
+        let err = tool
+            .execute(json!({"summary": "x", "start": "2026-03-02T10:00:00Z", "end": "2026-03-02T09:00:00Z"}))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("end must be after start"));
+
+        let err = tool
+            .execute(json!({
+                "summary": "x",
+                "start": "2026-03-02T10:00:00Z",
+                "end": "2026-03-02T11:00:00Z",
+                "attendees": ["not-an-email"]
+            }))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not-an-email"));
+    }
+}
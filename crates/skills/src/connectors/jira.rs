@@ -0,0 +1,381 @@
+//! Jira issue search and creation over the REST v2 API.
+//!
+//! Provider mapping: `base_url` is the site (`https://acme.atlassian.net`),
+//! `model_id` the account email and the secret an API token. With an empty
+//! `model_id` the secret is sent as a bearer personal access token (Data Center).
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use multi_agent_core::{
+    traits::Tool,
+    types::{ToolOutput, ToolRiskLevel},
+    Error, Result,
+};
+
+use super::{
+    error_output, provider_arg, request_error, required_str, ConnectorContext, Credentials,
+};
+
+pub(crate) const VENDOR: &str = "jira";
+const DEFAULT_MAX_RESULTS: u64 = 20;
+const MAX_RESULTS_LIMIT: u64 = 100;
+const SEARCH_FIELDS: &str = "summary,status,assignee,priority,issuetype,updated";
+
+fn request(
+    ctx: &ConnectorContext,
+    creds: &Credentials,
+    method: reqwest::Method,
+    path: &str,
+) -> reqwest::RequestBuilder {
+    let url = format!("{}{}", creds.provider.base_url.trim_end_matches('/'), path);
+    let builder = ctx
+        .client()
+        .request(method, url)
+        .header("Accept", "application/json");
+    if creds.provider.model_id.is_empty() {
+        builder.bearer_auth(&creds.secret)
+    } else {
+        builder.basic_auth(&creds.provider.model_id, Some(&creds.secret))
+    }
+}
+
+/// Escape a value for use inside a double-quoted JQL string.
+fn jql_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Build JQL from the structured search arguments when no raw `jql` is given.
+pub(crate) fn build_jql(args: &Value) -> Result<String> {
+    if let Some(jql) = args.get("jql").and_then(|v| v.as_str()) {
+        return Ok(jql.to_string());
+    }
+    let mut clauses = Vec::new();
+    if let Some(project) = args.get("project").and_then(|v| v.as_str()) {
+        clauses.push(format!("project = {}", jql_quote(project)));
+    }
+    if let Some(status) = args.get("status").and_then(|v| v.as_str()) {
+        clauses.push(format!("status = {}", jql_quote(status)));
+    }
+    if let Some(text) = args.get("text").and_then(|v| v.as_str()) {
+        clauses.push(format!("text ~ {}", jql_quote(text)));
+    }
+    if clauses.is_empty() {
+        return Err(Error::invalid_request(
+            "provide jql, or at least one of project, status, text",
+        ));
+    }
+    Ok(format!("{} ORDER BY updated DESC", clauses.join(" AND ")))
+}
+
+/// Flatten a search response into compact issue summaries.
+pub(crate) fn summarize_issues(base_url: &str, response: &Value) -> Vec<Value> {
+    let base = base_url.trim_end_matches('/');
+    response
+        .get("issues")
+        .and_then(|v| v.as_array())
+        .map(|issues| {
+            issues
+                .iter()
+                .map(|issue| {
+                    let key = issue
+                        .get("key")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default();
+                    let fields = issue.get("fields").cloned().unwrap_or(Value::Null);
+                    let name =
+                        |field: &str, attr: &str| fields[field][attr].as_str().map(String::from);
+                    json!({
+                        "key": key,
+                        "summary": fields["summary"].as_str(),
+                        "status": name("status", "name"),
+                        "type": name("issuetype", "name"),
+                        "priority": name("priority", "name"),
+                        "assignee": name("assignee", "displayName"),
+                        "updated": fields["updated"].as_str(),
+                        "url": format!("{}/browse/{}", base, key),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Build the create-issue payload.
+pub(crate) fn issue_payload(args: &Value) -> Result<Value> {
+    let mut fields = json!({
+        "project": {"key": required_str(args, "project")?},
+        "summary": required_str(args, "summary")?,
+        "issuetype": {"name": args.get("issue_type").and_then(|v| v.as_str()).unwrap_or("Task")},
+    });
+    if let Some(description) = args.get("description").and_then(|v| v.as_str()) {
+        fields["description"] = json!(description);
+    }
+    if let Some(priority) = args.get("priority").and_then(|v| v.as_str()) {
+        fields["priority"] = json!({"name": priority});
+    }
+    if let Some(labels) = args.get("labels").and_then(|v| v.as_array()) {
+        // Jira rejects labels containing spaces.
+        let labels: Vec<String> = labels
+            .iter()
+            .filter_map(|l| l.as_str())
+            .map(|l| l.trim().replace(' ', "-"))
+            .filter(|l| !l.is_empty())
+            .collect();
+        fields["labels"] = json!(labels);
+    }
+    Ok(json!({ "fields": fields }))
+}
+
+/// Search Jira issues with JQL.
+pub struct JiraSearchIssuesTool {
+    ctx: ConnectorContext,
+}
+
+impl JiraSearchIssuesTool {
+    pub fn new(ctx: ConnectorContext) -> Self {
+        Self { ctx }
+    }
+}
+
+#[async_trait]
+impl Tool for JiraSearchIssuesTool {
+    fn name(&self) -> &str {
+        "jira_search_issues"
+    }
+
+    fn description(&self) -> &str {
+        "Search Jira issues. Pass a raw JQL query, or project/status/text filters. \
+         Returns key, summary, status, assignee and link for each match."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "jql": {"type": "string", "description": "Raw JQL; overrides the other filters"},
+                "project": {"type": "string", "description": "Project key, e.g. OPS"},
+                "status": {"type": "string"},
+                "text": {"type": "string", "description": "Full-text search"},
+                "max_results": {"type": "integer", "default": DEFAULT_MAX_RESULTS, "maximum": MAX_RESULTS_LIMIT},
+                "provider_id": {"type": "string", "description": "Jira provider to use when several are registered"}
+            }
+        })
+    }
+
+    async fn execute(&self, args: Value) -> Result<ToolOutput> {
+        let jql = build_jql(&args)?;
+        let max_results = args
+            .get("max_results")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_MAX_RESULTS)
+            .clamp(1, MAX_RESULTS_LIMIT);
+        let creds = self.ctx.credentials(VENDOR, provider_arg(&args)).await?;
+
+        let response = match request(
+            &self.ctx,
+            &creds,
+            reqwest::Method::GET,
+            "/rest/api/2/search",
+        )
+        .query(&[
+            ("jql", jql.as_str()),
+            ("maxResults", &max_results.to_string()),
+            ("fields", SEARCH_FIELDS),
+        ])
+        .send()
+        .await
+        {
+            Ok(r) => r,
+            Err(e) => return Ok(request_error("Jira", e)),
+        };
+        if !response.status().is_success() {
+            return Ok(error_output("Jira", response).await);
+        }
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| Error::tool_execution(format!("Invalid Jira response: {}", e)))?;
+
+        let issues = summarize_issues(&creds.provider.base_url, &body);
+        let total = body
+            .get("total")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(issues.len() as u64);
+        let mut text = format!("{} issue(s) match ({} shown)", total, issues.len());
+        for issue in &issues {
+            text.push_str(&format!(
+                "\n{} [{}] {}",
+                issue["key"].as_str().unwrap_or_default(),
+                issue["status"].as_str().unwrap_or("?"),
+                issue["summary"].as_str().unwrap_or_default()
+            ));
+        }
+        Ok(ToolOutput::text(text)
+            .with_data(json!({ "jql": jql, "total": total, "issues": issues })))
+    }
+}
+
+/// Create a Jira issue.
+pub struct JiraCreateIssueTool {
+    ctx: ConnectorContext,
+}
+
+impl JiraCreateIssueTool {
+    pub fn new(ctx: ConnectorContext) -> Self {
+        Self { ctx }
+    }
+}
+
+#[async_trait]
+impl Tool for JiraCreateIssueTool {
+    fn name(&self) -> &str {
+        "jira_create_issue"
+    }
+
+    fn description(&self) -> &str {
+        "Create a Jira issue in a project, e.g. to file an incident summary. \
+         Returns the new issue key and link."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "project": {"type": "string", "description": "Project key, e.g. OPS"},
+                "summary": {"type": "string"},
+                "description": {"type": "string"},
+                "issue_type": {"type": "string", "default": "Task"},
+                "priority": {"type": "string", "description": "Priority name, e.g. High"},
+                "labels": {"type": "array", "items": {"type": "string"}},
+                "provider_id": {"type": "string", "description": "Jira provider to use when several are registered"}
+            },
+            "required": ["project", "summary"]
+        })
+    }
+
+    fn risk_level(&self) -> ToolRiskLevel {
+        ToolRiskLevel::Medium
+    }
+
+    async fn execute(&self, args: Value) -> Result<ToolOutput> {
+        let payload = issue_payload(&args)?;
+        let creds = self.ctx.credentials(VENDOR, provider_arg(&args)).await?;
+
+        let response = match request(
+            &self.ctx,
+            &creds,
+            reqwest::Method::POST,
+            "/rest/api/2/issue",
+        )
+        .json(&payload)
+        .send()
+        .await
+        {
+            Ok(r) => r,
+            Err(e) => return Ok(request_error("Jira", e)),
+        };
+        if !response.status().is_success() {
+            return Ok(error_output("Jira", response).await);
+        }
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| Error::tool_execution(format!("Invalid Jira response: {}", e)))?;
+
+        let key = body.get("key").and_then(|v| v.as_str()).unwrap_or_default();
+        let url = format!(
+            "{}/browse/{}",
+            creds.provider.base_url.trim_end_matches('/'),
+            key
+        );
+        Ok(
+            ToolOutput::text(format!("Created Jira issue {} ({})", key, url))
+                .with_data(json!({ "id": body.get("id"), "key": key, "url": url })),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support;
+    use super::*;
+
+    #[test]
+    fn test_build_jql() {
+        let jql = build_jql(&json!({"project": "OPS", "text": "disk \"full\""})).unwrap();
+        assert_eq!(
+            jql,
+            "project = \"OPS\" AND text ~ \"disk \\\"full\\\"\" ORDER BY updated DESC"
+        );
+        assert_eq!(
+            build_jql(&json!({"jql": "key = OPS-1"})).unwrap(),
+            "key = OPS-1"
+        );
+        assert!(build_jql(&json!({})).is_err());
+    }
+
+    #[test]
+    fn test_issue_payload() {
+        let payload = issue_payload(&json!({
+            "project": "OPS",
+            "summary": "Outage",
+            "labels": ["incident review", ""],
+            "priority": "High"
+        }))
+        .unwrap();
+        assert_eq!(payload["fields"]["issuetype"]["name"], "Task");
+        assert_eq!(payload["fields"]["labels"], json!(["incident-review"]));
+        assert_eq!(payload["fields"]["priority"]["name"], "High");
+        assert!(issue_payload(&json!({"project": "OPS"})).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_issue_posts_with_basic_auth() {
+        let (base, captured) =
+            test_support::serve(vec![(201, r#"{"id":"10001","key":"OPS-42"}"#.to_string())]).await;
+        let ctx = test_support::context(VENDOR, &base, "bot@example.com").await;
+        let tool = JiraCreateIssueTool::new(ctx);
+
+        let output = tool
+            .execute(json!({"project": "OPS", "summary": "DB outage", "description": "Details"}))
+            .await
+            .unwrap();
+        assert!(output.success, "{}", output.content);
+        assert_eq!(
+            output.data.as_ref().unwrap()["url"],
+            format!("{}/browse/OPS-42", base)
+        );
+
+        let requests = captured.lock().await;
+        assert!(requests[0].head.starts_with("POST /rest/api/2/issue"));
+        // base64("bot@example.com:s3cret")
+        assert!(requests[0]
+            .head
+            .contains("Basic Ym90QGV4YW1wbGUuY29tOnMzY3JldA=="));
+        let sent: Value = serde_json::from_str(&requests[0].body).unwrap();
+        assert_eq!(sent["fields"]["summary"], "DB outage");
+    }
+
+    #[tokio::test]
+    async fn test_search_reports_http_errors() {
+        let body = json!({"issues": [{
+            "key": "OPS-1",
+            "fields": {"summary": "Slow API", "status": {"name": "Open"}, "assignee": null}
+        }], "total": 1});
+        let (base, _) = test_support::serve(vec![
+            (200, body.to_string()),
+            (400, r#"{"errorMessages":["bad jql"]}"#.to_string()),
+        ])
+        .await;
+        let tool = JiraSearchIssuesTool::new(test_support::context(VENDOR, &base, "").await);
+
+        let output = tool.execute(json!({"project": "OPS"})).await.unwrap();
+        assert!(output.content.contains("OPS-1 [Open] Slow API"));
+        assert_eq!(output.data.unwrap()["issues"][0]["assignee"], Value::Null);
+
+        let output = tool.execute(json!({"jql": "bogus"})).await.unwrap();
+        assert!(!output.success);
+        assert!(output.content.contains("bad jql"));
+    }
+}
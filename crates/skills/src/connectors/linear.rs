@@ -0,0 +1,344 @@
+//! Linear issue search and creation over the GraphQL API.
+//!
+//! Provider mapping: the secret is a personal API key (or `Bearer <token>` for
+//! OAuth apps); `base_url` may override the GraphQL endpoint.
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use multi_agent_core::{
+    traits::Tool,
+    types::{ToolOutput, ToolRiskLevel},
+    Error, Result,
+};
+
+use super::{
+    error_output, provider_arg, request_error, required_str, ConnectorContext, Credentials,
+};
+
+pub(crate) const VENDOR: &str = "linear";
+const DEFAULT_ENDPOINT: &str = "https://api.linear.app/graphql";
+const DEFAULT_LIMIT: u64 = 20;
+const MAX_LIMIT: u64 = 50;
+
+const SEARCH_QUERY: &str = "query($term: String!, $first: Int) { \
+    searchIssues(term: $term, first: $first) { \
+    nodes { identifier title url priority state { name } assignee { name } updatedAt } } }";
+
+const TEAM_QUERY: &str = "query($key: String!) { \
+    teams(filter: { key: { eq: $key } }) { nodes { id key } } }";
+
+const CREATE_MUTATION: &str = "mutation($input: IssueCreateInput!) { \
+    issueCreate(input: $input) { success issue { id identifier title url } } }";
+
+/// Outcome of a GraphQL call: data on success, or an output to hand back as-is.
+enum GraphQl {
+    Data(Value),
+    Failed(ToolOutput),
+}
+
+async fn graphql(
+    ctx: &ConnectorContext,
+    creds: &Credentials,
+    query: &str,
+    variables: Value,
+) -> Result<GraphQl> {
+    let endpoint = match creds.provider.base_url.trim() {
+        "" => DEFAULT_ENDPOINT,
+        url => url,
+    };
+    let response = match ctx
+        .client()
+        .post(endpoint)
+        .header("Authorization", &creds.secret)
+        .json(&json!({ "query": query, "variables": variables }))
+        .send()
+        .await
+    {
+        Ok(r) => r,
+        Err(e) => return Ok(GraphQl::Failed(request_error("Linear", e))),
+    };
+    if !response.status().is_success() {
+        return Ok(GraphQl::Failed(error_output("Linear", response).await));
+    }
+    let body: Value = response
+        .json()
+        .await
+        .map_err(|e| Error::tool_execution(format!("Invalid Linear response: {}", e)))?;
+    if let Some(message) = graphql_error(&body) {
+        return Ok(GraphQl::Failed(ToolOutput::error(format!(
+            "Linear request failed: {}",
+            message
+        ))));
+    }
+    Ok(GraphQl::Data(
+        body.get("data").cloned().unwrap_or(Value::Null),
+    ))
+}
+
+/// GraphQL reports failures in an `errors` array alongside a 200 status.
+pub(crate) fn graphql_error(body: &Value) -> Option<String> {
+    let errors = body.get("errors")?.as_array()?;
+    if errors.is_empty() {
+        return None;
+    }
+    Some(
+        errors
+            .iter()
+            .filter_map(|e| e.get("message").and_then(|m| m.as_str()))
+            .collect::<Vec<_>>()
+            .join("; "),
+    )
+}
+
+/// Team ids are UUIDs; anything else is treated as a team key like `ENG`.
+fn looks_like_id(team: &str) -> bool {
+    uuid::Uuid::parse_str(team).is_ok()
+}
+
+/// Search Linear issues.
+pub struct LinearSearchIssuesTool {
+    ctx: ConnectorContext,
+}
+
+impl LinearSearchIssuesTool {
+    pub fn new(ctx: ConnectorContext) -> Self {
+        Self { ctx }
+    }
+}
+
+#[async_trait]
+impl Tool for LinearSearchIssuesTool {
+    fn name(&self) -> &str {
+        "linear_search_issues"
+    }
+
+    fn description(&self) -> &str {
+        "Search Linear issues by text. Returns identifier, title, state, assignee and link."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {"type": "string", "description": "Search text"},
+                "limit": {"type": "integer", "default": DEFAULT_LIMIT, "maximum": MAX_LIMIT},
+                "provider_id": {"type": "string", "description": "Linear provider to use when several are registered"}
+            },
+            "required": ["query"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> Result<ToolOutput> {
+        let term = required_str(&args, "query")?;
+        let limit = args
+            .get("limit")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_LIMIT)
+            .clamp(1, MAX_LIMIT);
+        let creds = self.ctx.credentials(VENDOR, provider_arg(&args)).await?;
+
+        let data = match graphql(
+            &self.ctx,
+            &creds,
+            SEARCH_QUERY,
+            json!({ "term": term, "first": limit }),
+        )
+        .await?
+        {
+            GraphQl::Data(data) => data,
+            GraphQl::Failed(output) => return Ok(output),
+        };
+        let issues: Vec<Value> = data["searchIssues"]["nodes"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|n| {
+                json!({
+                    "identifier": n["identifier"],
+                    "title": n["title"],
+                    "state": n["state"]["name"],
+                    "assignee": n["assignee"]["name"],
+                    "priority": n["priority"],
+                    "updated": n["updatedAt"],
+                    "url": n["url"],
+                })
+            })
+            .collect();
+
+        let mut text = format!("{} issue(s) found", issues.len());
+        for issue in &issues {
+            text.push_str(&format!(
+                "\n{} [{}] {}",
+                issue["identifier"].as_str().unwrap_or_default(),
+                issue["state"].as_str().unwrap_or("?"),
+                issue["title"].as_str().unwrap_or_default()
+            ));
+        }
+        Ok(ToolOutput::text(text).with_data(json!({ "issues": issues })))
+    }
+}
+
+/// Create a Linear issue.
+pub struct LinearCreateIssueTool {
+    ctx: ConnectorContext,
+}
+
+impl LinearCreateIssueTool {
+    pub fn new(ctx: ConnectorContext) -> Self {
+        Self { ctx }
+    }
+}
+
+#[async_trait]
+impl Tool for LinearCreateIssueTool {
+    fn name(&self) -> &str {
+        "linear_create_issue"
+    }
+
+    fn description(&self) -> &str {
+        "Create a Linear issue in a team. Returns the new identifier and link."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "team": {"type": "string", "description": "Team key (e.g. ENG) or team id"},
+                "title": {"type": "string"},
+                "description": {"type": "string", "description": "Markdown description"},
+                "priority": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "maximum": 4,
+                    "description": "0 none, 1 urgent, 2 high, 3 medium, 4 low"
+                },
+                "provider_id": {"type": "string", "description": "Linear provider to use when several are registered"}
+            },
+            "required": ["team", "title"]
+        })
+    }
+
+    fn risk_level(&self) -> ToolRiskLevel {
+        ToolRiskLevel::Medium
+    }
+
+    async fn execute(&self, args: Value) -> Result<ToolOutput> {
+        let team = required_str(&args, "team")?;
+        let title = required_str(&args, "title")?;
+        let priority = args.get("priority").and_then(|v| v.as_u64());
+        if priority.is_some_and(|p| p > 4) {
+            return Err(Error::invalid_request("priority must be between 0 and 4"));
+        }
+        let creds = self.ctx.credentials(VENDOR, provider_arg(&args)).await?;
+
+        let team_id = if looks_like_id(team) {
+            team.to_string()
+        } else {
+            let data = match graphql(&self.ctx, &creds, TEAM_QUERY, json!({ "key": team })).await? {
+                GraphQl::Data(data) => data,
+                GraphQl::Failed(output) => return Ok(output),
+            };
+            match data["teams"]["nodes"][0]["id"].as_str() {
+                Some(id) => id.to_string(),
+                None => {
+                    return Ok(ToolOutput::error(format!(
+                        "Linear team '{}' not found",
+                        team
+                    )))
+                }
+            }
+        };
+
+        let mut input = json!({ "teamId": team_id, "title": title });
+        if let Some(description) = args.get("description").and_then(|v| v.as_str()) {
+            input["description"] = json!(description);
+        }
+        if let Some(priority) = priority {
+            input["priority"] = json!(priority);
+        }
+        let data = match graphql(
+            &self.ctx,
+            &creds,
+            CREATE_MUTATION,
+            json!({ "input": input }),
+        )
+        .await?
+        {
+            GraphQl::Data(data) => data,
+            GraphQl::Failed(output) => return Ok(output),
+        };
+        let result = &data["issueCreate"];
+        if result["success"].as_bool() != Some(true) {
+            return Ok(ToolOutput::error("Linear did not create the issue"));
+        }
+        let issue = &result["issue"];
+        Ok(ToolOutput::text(format!(
+            "Created Linear issue {} ({})",
+            issue["identifier"].as_str().unwrap_or_default(),
+            issue["url"].as_str().unwrap_or_default()
+        ))
+        .with_data(issue.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support;
+    use super::*;
+
+    #[test]
+    fn test_graphql_error() {
+        assert_eq!(
+            graphql_error(&json!({"errors": [{"message": "a"}, {"message": "b"}]})),
+            Some("a; b".to_string())
+        );
+        assert_eq!(graphql_error(&json!({"data": {}})), None);
+    }
+
+    #[tokio::test]
+    async fn test_create_issue_resolves_team_key() {
+        let (base, captured) = test_support::serve(vec![
+            (200, json!({"data": {"teams": {"nodes": [{"id": "team-uuid", "key": "ENG"}]}}}).to_string()),
+            (
+                200,
+                json!({"data": {"issueCreate": {"success": true, "issue": {
+                    "id": "i1", "identifier": "ENG-7", "title": "Fix", "url": "https://linear.app/x/ENG-7"
+                }}}})
+                .to_string(),
+            ),
+        ])
+        .await;
+        let tool = LinearCreateIssueTool::new(test_support::context(VENDOR, &base, "").await);
+
+        let output = tool
+            .execute(json!({"team": "ENG", "title": "Fix", "priority": 2}))
+            .await
+            .unwrap();
+        assert!(output.success, "{}", output.content);
+        assert!(output.content.contains("ENG-7"));
+
+        let requests = captured.lock().await;
+        assert!(requests[0]
+            .head
+            .to_ascii_lowercase()
+            .contains("authorization: s3cret"));
+        let create: Value = serde_json::from_str(&requests[1].body).unwrap();
+        assert_eq!(create["variables"]["input"]["teamId"], "team-uuid");
+        assert_eq!(create["variables"]["input"]["priority"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_search_surfaces_graphql_errors() {
+        let (base, _) = test_support::serve(vec![(
+            200,
+            json!({"errors": [{"message": "Authentication required"}]}).to_string(),
+        )])
+        .await;
+        let tool = LinearSearchIssuesTool::new(test_support::context(VENDOR, &base, "").await);
+        let output = tool.execute(json!({"query": "login"})).await.unwrap();
+        assert!(!output.success);
+        assert!(output.content.contains("Authentication required"));
+    }
+}
@@ -0,0 +1,276 @@
+//! Connectors to calendar and issue-tracker services.
+//!
+//! Credentials come from admin providers: the provider `vendor` selects the
+//! service (`caldav`, `jira`, `linear`), `base_url` is the service endpoint,
+//! `model_id` holds the account name where one is needed and the API key
+//! stored in the [`SecretsManager`] is the password or token.
+
+use reqwest::Response;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+
+use multi_agent_core::{
+    traits::{ProviderEntry, ProviderStore, Tool},
+    types::ToolOutput,
+    Error, Result,
+};
+use multi_agent_governance::SecretsManager;
+
+pub mod caldav;
+pub mod jira;
+pub mod linear;
+
+pub use caldav::{CalendarCreateEventTool, CalendarListEventsTool};
+pub use jira::{JiraCreateIssueTool, JiraSearchIssuesTool};
+pub use linear::{LinearCreateIssueTool, LinearSearchIssuesTool};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// Characters of an error response body echoed back to the agent.
+const ERROR_BODY_CHARS: usize = 500;
+
+/// An admin provider together with its decrypted secret.
+pub(crate) struct Credentials {
+    pub provider: ProviderEntry,
+    pub secret: String,
+}
+
+/// Shared state for connector tools: where credentials live and the HTTP client.
+#[derive(Clone)]
+pub struct ConnectorContext {
+    providers: Arc<dyn ProviderStore>,
+    secrets: Arc<dyn SecretsManager>,
+    client: reqwest::Client,
+}
+
+impl ConnectorContext {
+    /// Create a connector context.
+    pub fn new(providers: Arc<dyn ProviderStore>, secrets: Arc<dyn SecretsManager>) -> Self {
+        Self {
+            providers,
+            secrets,
+            client: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    pub(crate) fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    /// Resolve credentials for `vendor`, preferring `provider_id` when given.
+    pub(crate) async fn credentials(
+        &self,
+        vendor: &str,
+        provider_id: Option<&str>,
+    ) -> Result<Credentials> {
+        let provider = self
+            .providers
+            .list()
+            .await?
+            .into_iter()
+            .filter(|p| p.vendor.eq_ignore_ascii_case(vendor))
+            .find(|p| match provider_id {
+                Some(id) => p.id == id,
+                None => p.status != "error" && p.status != "disabled",
+            })
+            .ok_or_else(|| {
+                Error::tool_execution(match provider_id {
+                    Some(id) => format!("{} provider '{}' not found", vendor, id),
+                    None => format!(
+                        "No {} provider configured (add an admin provider with vendor '{}')",
+                        vendor, vendor
+                    ),
+                })
+            })?;
+        let secret = self
+            .secrets
+            .retrieve(&provider.api_key_id)
+            .await?
+            .ok_or_else(|| {
+                Error::tool_execution(format!(
+                    "No credentials stored for provider '{}'",
+                    provider.id
+                ))
+            })?;
+        Ok(Credentials { provider, secret })
+    }
+}
+
+/// All connector tools, ready to register.
+pub fn connector_tools(ctx: ConnectorContext) -> Vec<Box<dyn Tool>> {
+    vec![
+        Box::new(CalendarListEventsTool::new(ctx.clone())),
+        Box::new(CalendarCreateEventTool::new(ctx.clone())),
+        Box::new(JiraSearchIssuesTool::new(ctx.clone())),
+        Box::new(JiraCreateIssueTool::new(ctx.clone())),
+        Box::new(LinearSearchIssuesTool::new(ctx.clone())),
+        Box::new(LinearCreateIssueTool::new(ctx)),
+    ]
+}
+
+/// The optional `provider_id` argument shared by all connector tools.
+pub(crate) fn provider_arg(args: &Value) -> Option<&str> {
+    args.get("provider_id").and_then(|v| v.as_str())
+}
+
+pub(crate) fn required_str<'a>(args: &'a Value, field: &str) -> Result<&'a str> {
+    args.get(field)
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.trim().is_empty())
+        .ok_or_else(|| Error::invalid_request(format!("{} is required", field)))
+}
+
+/// Turn a non-success response into an error output the agent can act on.
+pub(crate) async fn error_output(service: &str, response: Response) -> ToolOutput {
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    let body: String = body.chars().take(ERROR_BODY_CHARS).collect();
+    ToolOutput::error(format!("{} request failed ({}): {}", service, status, body))
+}
+
+pub(crate) fn request_error(service: &str, e: reqwest::Error) -> ToolOutput {
+    ToolOutput::error(format!("{} request failed: {}", service, e))
+}
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+    use multi_agent_governance::AesGcmSecretsManager;
+    use multi_agent_store::InMemoryProviderStore;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::sync::Mutex;
+
+    /// A request captured by [`serve`].
+    #[derive(Debug, Clone)]
+    pub struct Captured {
+        pub head: String,
+        pub body: String,
+    }
+
+    /// Serve the canned `(status, body)` responses in order, one per connection.
+    pub async fn serve(responses: Vec<(u16, String)>) -> (String, Arc<Mutex<Vec<Captured>>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let captured = Arc::new(Mutex::new(Vec::new()));
+        let log = captured.clone();
+
+        tokio::spawn(async move {
+            for (status, body) in responses {
+                let Ok((mut socket, _)) = listener.accept().await else {
+                    return;
+                };
+                let mut buf = Vec::new();
+                let mut chunk = [0u8; 4096];
+                let (head, body_start) = loop {
+                    let n = socket.read(&mut chunk).await.unwrap_or(0);
+                    if n == 0 {
+                        break (String::from_utf8_lossy(&buf).to_string(), buf.len());
+                    }
+                    buf.extend_from_slice(&chunk[..n]);
+                    if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                        break (String::from_utf8_lossy(&buf[..pos]).to_string(), pos + 4);
+                    }
+                };
+                let length = head
+                    .lines()
+                    .find_map(|l| {
+                        let (name, value) = l.split_once(':')?;
+                        name.eq_ignore_ascii_case("content-length")
+                            .then(|| value.trim().parse::<usize>().ok())?
+                    })
+                    .unwrap_or(0);
+                while buf.len() < body_start + length {
+                    let n = socket.read(&mut chunk).await.unwrap_or(0);
+                    if n == 0 {
+                        break;
+                    }
+                    buf.extend_from_slice(&chunk[..n]);
+                }
+                let request_body =
+                    String::from_utf8_lossy(&buf[body_start.min(buf.len())..]).to_string();
+                log.lock().await.push(Captured {
+                    head,
+                    body: request_body,
+                });
+
+                let response = format!(
+                    "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+                let _ = socket.shutdown().await;
+            }
+        });
+
+        (base, captured)
+    }
+
+    /// A context with one provider of `vendor` pointing at `base_url`.
+    pub async fn context(vendor: &str, base_url: &str, account: &str) -> ConnectorContext {
+        let providers = Arc::new(InMemoryProviderStore::new());
+        providers
+            .upsert(&ProviderEntry {
+                id: format!("prov-{}", vendor),
+                vendor: vendor.to_string(),
+                model_id: account.to_string(),
+                description: None,
+                base_url: base_url.to_string(),
+                regional_urls: Vec::new(),
+                monthly_spend_cap_usd: None,
+                version: None,
+                api_key_id: format!("api_key:prov-{}", vendor),
+                capabilities: vec![],
+                status: "active".to_string(),
+            })
+            .await
+            .unwrap();
+        let secrets = Arc::new(AesGcmSecretsManager::new(None));
+        secrets
+            .store(&format!("api_key:prov-{}", vendor), "s3cret")
+            .await
+            .unwrap();
+        ConnectorContext::new(providers, secrets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_credentials_resolution() {
+        let ctx = test_support::context("jira", "https://jira.example.com", "me@example.com").await;
+        let creds = ctx.credentials("JIRA", None).await.unwrap();
+        assert_eq!(creds.provider.id, "prov-jira");
+        assert_eq!(creds.secret, "s3cret");
+
+        assert!(ctx.credentials("jira", Some("other")).await.is_err());
+        let err = ctx.credentials("linear", None).await.err().unwrap();
+        assert!(err.to_string().contains("vendor 'linear'"));
+    }
+
+    #[test]
+    fn test_tool_risk_declarations() {
+        use multi_agent_core::types::ToolRiskLevel;
+        use multi_agent_governance::AesGcmSecretsManager;
+        use multi_agent_store::InMemoryProviderStore;
+
+        let ctx = ConnectorContext::new(
+            Arc::new(InMemoryProviderStore::new()),
+            Arc::new(AesGcmSecretsManager::new(None)),
+        );
+        for tool in connector_tools(ctx) {
+            let expected = if tool.name().contains("create") {
+                ToolRiskLevel::Medium
+            } else {
+                ToolRiskLevel::Low
+            };
+            assert_eq!(tool.risk_level(), expected, "{}", tool.name());
+        }
+    }
+}
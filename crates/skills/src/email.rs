@@ -0,0 +1,726 @@
+//! Outbound email tool.
+//!
+//! SMTP servers are registered as admin providers with vendor `smtp`; the
+//! password lives in the [`SecretsManager`] under the provider's
+//! `api_key_id`. Every send is gated on human approval (see
+//! [`Tool::requires_approval`]) and recorded in the audit store with the full
+//! recipient list and subject.
+
+use async_trait::async_trait;
+use lettre::message::{header::ContentType, Mailbox};
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+use multi_agent_core::{
+    config::{EmailConfig, EmailTemplate},
+    traits::{ProviderEntry, ProviderStore, Tool},
+    types::{ArtifactOwner, ToolOutput, ToolRiskLevel},
+    Error, Result,
+};
+use multi_agent_governance::{AuditEntry, AuditOutcome, AuditStore, SecretsManager};
+
+/// Provider vendor that marks an SMTP server.
+pub const SMTP_VENDOR: &str = "smtp";
+
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Connection security for an SMTP endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpSecurity {
+    /// Implicit TLS (`smtps://`, usually port 465).
+    Tls,
+    /// STARTTLS upgrade (`smtp://`, usually port 587).
+    StartTls,
+    /// No encryption (`smtp+insecure://`); only for local relays.
+    None,
+}
+
+/// SMTP endpoint resolved from an admin provider.
+#[derive(Debug, Clone)]
+pub struct SmtpSettings {
+    pub provider_id: String,
+    pub host: String,
+    pub port: u16,
+    pub security: SmtpSecurity,
+    pub username: String,
+    pub password: Option<String>,
+}
+
+impl SmtpSettings {
+    /// Parse a provider `base_url` such as `smtps://smtp.example.com:465`.
+    pub fn from_url(
+        provider_id: &str,
+        url: &str,
+        username: &str,
+        password: Option<String>,
+    ) -> Result<Self> {
+        let parsed = url::Url::parse(url)
+            .map_err(|e| Error::invalid_request(format!("Invalid SMTP URL '{}': {}", url, e)))?;
+        let (security, default_port) = match parsed.scheme() {
+            "smtps" => (SmtpSecurity::Tls, 465),
+            "smtp" => (SmtpSecurity::StartTls, 587),
+            "smtp+insecure" => (SmtpSecurity::None, 25),
+            other => {
+                return Err(Error::invalid_request(format!(
+                    "Unsupported SMTP scheme '{}' (expected smtps, smtp or smtp+insecure)",
+                    other
+                )))
+            }
+        };
+        let host = parsed
+            .host_str()
+            .ok_or_else(|| Error::invalid_request(format!("SMTP URL '{}' has no host", url)))?;
+
+        Ok(Self {
+            provider_id: provider_id.to_string(),
+            host: host.to_string(),
+            port: parsed.port().unwrap_or(default_port),
+            security,
+            username: username.to_string(),
+            password,
+        })
+    }
+}
+
+/// A fully rendered message ready to send.
+#[derive(Debug, Clone)]
+pub struct OutgoingEmail {
+    pub from: Mailbox,
+    pub to: Vec<Mailbox>,
+    pub cc: Vec<Mailbox>,
+    pub bcc: Vec<Mailbox>,
+    pub subject: String,
+    pub body: String,
+    pub html: bool,
+}
+
+impl OutgoingEmail {
+    fn into_message(self) -> Result<lettre::Message> {
+        let mut builder = lettre::Message::builder()
+            .from(self.from)
+            .subject(self.subject)
+            .header(if self.html {
+                ContentType::TEXT_HTML
+            } else {
+                ContentType::TEXT_PLAIN
+            });
+        for to in self.to {
+            builder = builder.to(to);
+        }
+        for cc in self.cc {
+            builder = builder.cc(cc);
+        }
+        for bcc in self.bcc {
+            builder = builder.bcc(bcc);
+        }
+        builder
+            .body(self.body)
+            .map_err(|e| Error::invalid_request(format!("Invalid email: {}", e)))
+    }
+}
+
+/// Delivers rendered messages. Swappable for tests.
+#[async_trait]
+pub trait EmailTransport: Send + Sync {
+    async fn send(&self, settings: &SmtpSettings, email: OutgoingEmail) -> Result<()>;
+}
+
+/// SMTP delivery via `lettre`.
+pub struct SmtpTransport;
+
+#[async_trait]
+impl EmailTransport for SmtpTransport {
+    async fn send(&self, settings: &SmtpSettings, email: OutgoingEmail) -> Result<()> {
+        use lettre::{
+            transport::smtp::authentication::Credentials, AsyncSmtpTransport, AsyncTransport,
+            Tokio1Executor,
+        };
+
+        let builder = match settings.security {
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&settings.host),
+            SmtpSecurity::StartTls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&settings.host)
+            }
+            SmtpSecurity::None => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(
+                &settings.host,
+            )),
+        }
+        .map_err(|e| Error::tool_execution(format!("SMTP setup failed: {}", e)))?;
+
+        let mut builder = builder.port(settings.port).timeout(Some(SMTP_TIMEOUT));
+        if let Some(password) = &settings.password {
+            builder = builder.credentials(Credentials::new(
+                settings.username.clone(),
+                password.clone(),
+            ));
+        }
+
+        builder
+            .build()
+            .send(email.into_message()?)
+            .await
+            .map_err(|e| Error::tool_execution(format!("SMTP send failed: {}", e)))?;
+        Ok(())
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Substitute `{{name}}` placeholders from `vars`.
+///
+/// Returns the names of placeholders without a value as the error.
+pub fn render_template(
+    template: &str,
+    vars: &Map<String, Value>,
+    html: bool,
+) -> std::result::Result<String, Vec<String>> {
+    let mut out = String::with_capacity(template.len());
+    let mut missing = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let name = rest[start + 2..start + 2 + len].trim();
+        match vars.get(name) {
+            Some(value) => {
+                let text = match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                out.push_str(&if html { escape_html(&text) } else { text });
+            }
+            None => {
+                if !missing.iter().any(|m| m == name) {
+                    missing.push(name.to_string());
+                }
+            }
+        }
+        rest = &rest[start + 2 + len + 2..];
+    }
+    out.push_str(rest);
+
+    if missing.is_empty() {
+        Ok(out)
+    } else {
+        Err(missing)
+    }
+}
+
+/// Accept a single address or an array of addresses.
+fn recipients(args: &Value, field: &str) -> Result<Vec<Mailbox>> {
+    let raw: Vec<&str> = match args.get(field) {
+        None | Some(Value::Null) => vec![],
+        Some(Value::String(s)) => s.split(',').collect(),
+        Some(Value::Array(items)) => items.iter().filter_map(|v| v.as_str()).collect(),
+        Some(_) => {
+            return Err(Error::invalid_request(format!(
+                "{} must be an address or a list of addresses",
+                field
+            )))
+        }
+    };
+    raw.into_iter()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse::<Mailbox>()
+                .map_err(|e| Error::invalid_request(format!("Invalid address '{}': {}", s, e)))
+        })
+        .collect()
+}
+
+fn addresses(mailboxes: &[Mailbox]) -> Vec<String> {
+    mailboxes.iter().map(|m| m.to_string()).collect()
+}
+
+/// Tool that sends email through an admin-configured SMTP provider.
+///
+/// Risk level: HIGH, and every call requires human approval.
+pub struct SendEmailTool {
+    providers: Arc<dyn ProviderStore>,
+    secrets: Arc<dyn SecretsManager>,
+    audit: Arc<dyn AuditStore>,
+    config: EmailConfig,
+    transport: Arc<dyn EmailTransport>,
+}
+
+impl SendEmailTool {
+    /// Create a new send email tool delivering over SMTP.
+    pub fn new(
+        providers: Arc<dyn ProviderStore>,
+        secrets: Arc<dyn SecretsManager>,
+        audit: Arc<dyn AuditStore>,
+        config: EmailConfig,
+    ) -> Self {
+        Self {
+            providers,
+            secrets,
+            audit,
+            config,
+            transport: Arc::new(SmtpTransport),
+        }
+    }
+
+    /// Replace the delivery transport.
+    pub fn with_transport(mut self, transport: Arc<dyn EmailTransport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// The configured SMTP provider, or the first usable one.
+    async fn provider(&self) -> Result<ProviderEntry> {
+        let providers = self.providers.list().await?;
+        let mut smtp = providers
+            .into_iter()
+            .filter(|p| p.vendor.eq_ignore_ascii_case(SMTP_VENDOR));
+        let found = match &self.config.provider_id {
+            Some(id) => smtp.find(|p| &p.id == id),
+            None => smtp.find(|p| p.status != "error" && p.status != "disabled"),
+        };
+        found.ok_or_else(|| {
+            Error::tool_execution(match &self.config.provider_id {
+                Some(id) => format!("SMTP provider '{}' not found", id),
+                None => "No SMTP provider configured (add an admin provider with vendor 'smtp')"
+                    .to_string(),
+            })
+        })
+    }
+
+    /// SMTP settings and sender for a provider; the sender address doubles as the login.
+    async fn settings(&self, provider: &ProviderEntry) -> Result<(SmtpSettings, Mailbox)> {
+        let from = provider.model_id.parse::<Mailbox>().map_err(|e| {
+            Error::tool_execution(format!(
+                "SMTP provider '{}' has an invalid sender '{}': {}",
+                provider.id, provider.model_id, e
+            ))
+        })?;
+        let password = self
+            .secrets
+            .retrieve(&provider.api_key_id)
+            .await?
+            .filter(|p| !p.is_empty());
+        let settings = SmtpSettings::from_url(
+            &provider.id,
+            &provider.base_url,
+            from.email.as_ref(),
+            password,
+        )?;
+        Ok((settings, from))
+    }
+
+    /// Resolve subject, body and content type from a template or inline fields.
+    fn compose(&self, args: &Value) -> Result<(String, String, bool, Option<String>)> {
+        let empty = Map::new();
+        let vars = args
+            .get("variables")
+            .and_then(|v| v.as_object())
+            .unwrap_or(&empty);
+
+        let template_name = args.get("template").and_then(|v| v.as_str());
+        let template = match template_name {
+            Some(name) => self.config.templates.get(name).cloned().ok_or_else(|| {
+                let mut known: Vec<&String> = self.config.templates.keys().collect();
+                known.sort();
+                Error::invalid_request(format!(
+                    "Unknown email template '{}' (available: {:?})",
+                    name, known
+                ))
+            })?,
+            None => EmailTemplate {
+                subject: args
+                    .get("subject")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| {
+                        Error::invalid_request("subject is required without a template")
+                    })?
+                    .to_string(),
+                body: args
+                    .get("body")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| Error::invalid_request("body is required without a template"))?
+                    .to_string(),
+                html: args.get("html").and_then(|v| v.as_bool()).unwrap_or(false),
+            },
+        };
+
+        let fill = |text: &str, html: bool| {
+            render_template(text, vars, html).map_err(|missing| {
+                Error::invalid_request(format!(
+                    "Missing template variables: {}",
+                    missing.join(", ")
+                ))
+            })
+        };
+        // Subjects are single-line headers.
+        let subject = fill(&template.subject, false)?.replace(['\r', '\n'], " ");
+        let body = fill(&template.body, template.html)?;
+        Ok((
+            subject,
+            body,
+            template.html,
+            template_name.map(str::to_string),
+        ))
+    }
+
+    async fn audit(&self, resource: &str, outcome: AuditOutcome, metadata: Value) {
+        let owner = ArtifactOwner::current();
+        let session_id = owner.as_ref().and_then(|o| o.session_id.clone());
+        let mut metadata = metadata;
+        if let Some(session_id) = &session_id {
+            metadata["session_id"] = json!(session_id);
+        }
+        let entry = AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            user_id: owner
+                .and_then(|o| o.user_id)
+                .unwrap_or_else(|| "agent".to_string()),
+            action: "SEND_EMAIL".to_string(),
+            resource: resource.to_string(),
+            outcome,
+            metadata: Some(metadata),
+            previous_hash: None,
+            hash: None,
+            trace_id: None,
+            session_id,
+        };
+        if let Err(e) = self.audit.log(entry).await {
+            tracing::error!(error = %e, "Failed to write email audit entry");
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for SendEmailTool {
+    fn name(&self) -> &str {
+        "send_email"
+    }
+
+    fn description(&self) -> &str {
+        "Send an email through the configured SMTP provider. Provide subject and body, \
+         or a named template with variables for its {{placeholders}}. Every email is \
+         reviewed by a human before it is sent."
+    }
+
+    fn parameters(&self) -> Value {
+        let mut templates: Vec<&String> = self.config.templates.keys().collect();
+        templates.sort();
+        json!({
+            "type": "object",
+            "properties": {
+                "to": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Recipient addresses, e.g. \"Jane Doe <jane@example.com>\""
+                },
+                "cc": {"type": "array", "items": {"type": "string"}},
+                "bcc": {"type": "array", "items": {"type": "string"}},
+                "subject": {"type": "string", "description": "Subject (unless using a template)"},
+                "body": {"type": "string", "description": "Body (unless using a template)"},
+                "html": {"type": "boolean", "description": "Send body as HTML", "default": false},
+                "template": {
+                    "type": "string",
+                    "enum": templates,
+                    "description": "Named template to use instead of subject/body"
+                },
+                "variables": {
+                    "type": "object",
+                    "description": "Values for {{placeholders}} in the template or inline subject/body"
+                }
+            },
+            "required": ["to"]
+        })
+    }
+
+    fn risk_level(&self) -> ToolRiskLevel {
+        ToolRiskLevel::High
+    }
+
+    fn requires_approval(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, args: Value) -> Result<ToolOutput> {
+        let to = recipients(&args, "to")?;
+        let cc = recipients(&args, "cc")?;
+        let bcc = recipients(&args, "bcc")?;
+        if to.is_empty() {
+            return Err(Error::invalid_request(
+                "at least one 'to' recipient is required",
+            ));
+        }
+        let total = to.len() + cc.len() + bcc.len();
+        if total > self.config.max_recipients {
+            return Err(Error::invalid_request(format!(
+                "{} recipients exceeds the limit of {}",
+                total, self.config.max_recipients
+            )));
+        }
+        let (subject, body, html, template) = self.compose(&args)?;
+
+        let mut metadata = json!({
+            "to": addresses(&to),
+            "cc": addresses(&cc),
+            "bcc": addresses(&bcc),
+            "subject": subject,
+            "template": template,
+        });
+
+        let prepared = async {
+            let provider = self.provider().await?;
+            self.settings(&provider).await
+        }
+        .await;
+        let (settings, from) = match prepared {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                self.audit("smtp", AuditOutcome::Error(e.to_string()), metadata)
+                    .await;
+                return Ok(ToolOutput::error(e.to_string()));
+            }
+        };
+        metadata["from"] = json!(from.to_string());
+
+        let email = OutgoingEmail {
+            from,
+            to,
+            cc,
+            bcc,
+            subject: subject.clone(),
+            body,
+            html,
+        };
+        match self.transport.send(&settings, email).await {
+            Ok(()) => {
+                self.audit(&settings.provider_id, AuditOutcome::Success, metadata)
+                    .await;
+                Ok(ToolOutput::text(format!(
+                    "Email \"{}\" sent to {} recipient(s).",
+                    subject, total
+                ))
+                .with_data(json!({
+                    "provider_id": settings.provider_id,
+                    "recipients": total,
+                })))
+            }
+            Err(e) => {
+                self.audit(
+                    &settings.provider_id,
+                    AuditOutcome::Error(e.to_string()),
+                    metadata,
+                )
+                .await;
+                Ok(ToolOutput::error(format!("Failed to send email: {}", e)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multi_agent_governance::{AesGcmSecretsManager, AuditFilter, InMemoryAuditStore};
+    use multi_agent_store::InMemoryProviderStore;
+    use std::collections::HashMap;
+    use tokio::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingTransport {
+        sent: Mutex<Vec<(SmtpSettings, OutgoingEmail)>>,
+    }
+
+    #[async_trait]
+    impl EmailTransport for RecordingTransport {
+        async fn send(&self, settings: &SmtpSettings, email: OutgoingEmail) -> Result<()> {
+            self.sent.lock().await.push((settings.clone(), email));
+            Ok(())
+        }
+    }
+
+    struct Fixture {
+        tool: SendEmailTool,
+        transport: Arc<RecordingTransport>,
+        audit: Arc<InMemoryAuditStore>,
+    }
+
+    async fn fixture() -> Fixture {
+        let providers = Arc::new(InMemoryProviderStore::new());
+        providers
+            .upsert(&ProviderEntry {
+                id: "prov-smtp".into(),
+                vendor: "smtp".into(),
+                model_id: "Agent <agent@example.com>".into(),
+                description: None,
+                base_url: "smtps://mail.example.com".into(),
+                regional_urls: Vec::new(),
+                monthly_spend_cap_usd: None,
+                version: None,
+                api_key_id: "api_key:prov-smtp".into(),
+                capabilities: vec!["email".into()],
+                status: "active".into(),
+            })
+            .await
+            .unwrap();
+        let secrets = Arc::new(AesGcmSecretsManager::new(None));
+        secrets.store("api_key:prov-smtp", "hunter2").await.unwrap();
+        let audit = Arc::new(InMemoryAuditStore::new());
+        let transport = Arc::new(RecordingTransport::default());
+
+        let config = EmailConfig {
+            max_recipients: 3,
+            templates: HashMap::from([(
+                "welcome".to_string(),
+                EmailTemplate {
+                    subject: "Welcome, {{ name }}".into(),
+                    body: "<p>Hello {{name}}, your project is {{project}}.</p>".into(),
+                    html: true,
+                },
+            )]),
+            ..EmailConfig::default()
+        };
+        let tool = SendEmailTool::new(providers, secrets, audit.clone(), config)
+            .with_transport(transport.clone());
+        Fixture {
+            tool,
+            transport,
+            audit,
+        }
+    }
+
+    #[test]
+    fn test_render_template() {
+        let vars = json!({"name": "Ada", "n": 3}).as_object().unwrap().clone();
+        assert_eq!(
+            render_template("Hi {{name}}, {{ n }} new", &vars, false).unwrap(),
+            "Hi Ada, 3 new"
+        );
+        assert_eq!(
+            render_template("{{a}} {{name}} {{b}} {{a}}", &vars, false).unwrap_err(),
+            vec!["a".to_string(), "b".to_string()]
+        );
+        let vars = json!({"x": "<b>"}).as_object().unwrap().clone();
+        assert_eq!(render_template("{{x}}", &vars, true).unwrap(), "&lt;b&gt;");
+        assert_eq!(
+            render_template("no {{ end", &vars, false).unwrap(),
+            "no {{ end"
+        );
+    }
+
+    #[test]
+    fn test_smtp_url_parsing() {
+        let s = SmtpSettings::from_url("p", "smtps://mail.example.com", "u", None).unwrap();
+        assert_eq!((s.security, s.port), (SmtpSecurity::Tls, 465));
+        let s = SmtpSettings::from_url("p", "smtp://mail.example.com:2525", "u", None).unwrap();
+        assert_eq!((s.security, s.port), (SmtpSecurity::StartTls, 2525));
+        let s = SmtpSettings::from_url("p", "smtp+insecure://localhost", "u", None).unwrap();
+        assert_eq!((s.security, s.port), (SmtpSecurity::None, 25));
+        assert!(SmtpSettings::from_url("p", "https://mail.example.com", "u", None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_send_with_template_is_audited() {
+        let f = fixture().await;
+        assert!(f.tool.requires_approval());
+
+        let result = ArtifactOwner::new(Some("alice".into()), Some("s1".into()))
+            .scope(f.tool.execute(json!({
+                "to": ["Bob <bob@example.com>"],
+                "bcc": "audit@example.com",
+                "template": "welcome",
+                "variables": {"name": "Bob", "project": "<Apollo>"}
+            })))
+            .await
+            .unwrap();
+        assert!(result.success, "{}", result.content);
+
+        let sent = f.transport.sent.lock().await;
+        let (settings, email) = &sent[0];
+        assert_eq!(settings.password.as_deref(), Some("hunter2"));
+        assert_eq!(settings.username, "agent@example.com");
+        assert_eq!(email.subject, "Welcome, Bob");
+        assert!(email.html);
+        assert!(email.body.contains("&lt;Apollo&gt;"));
+        assert_eq!(email.from.email.to_string(), "agent@example.com");
+
+        let entries = f.audit.query(AuditFilter::default()).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.action, "SEND_EMAIL");
+        assert_eq!(entry.user_id, "alice");
+        assert_eq!(entry.resource, "prov-smtp");
+        let metadata = entry.metadata.as_ref().unwrap();
+        assert_eq!(metadata["subject"], "Welcome, Bob");
+        assert_eq!(metadata["to"][0], "Bob <bob@example.com>");
+        assert_eq!(metadata["bcc"][0], "audit@example.com");
+        assert_eq!(metadata["session_id"], "s1");
+    }
+
+    #[tokio::test]
+    async fn test_validation_errors() {
+        let f = fixture().await;
+
+        let missing = f
+            .tool
+            .execute(
+                json!({"to": ["a@example.com"], "template": "welcome", "variables": {"name": "A"}}),
+            )
+            .await
+            .unwrap_err();
+        assert!(missing.to_string().contains("project"));
+
+        let too_many = f
+            .tool
+            .execute(json!({
+                "to": ["a@example.com", "b@example.com"],
+                "cc": ["c@example.com", "d@example.com"],
+                "subject": "s",
+                "body": "b"
+            }))
+            .await;
+        assert!(too_many.is_err());
+
+        let bad_address = f
+            .tool
+            .execute(json!({"to": "not an address", "subject": "s", "body": "b"}))
+            .await;
+        assert!(bad_address.is_err());
+
+        assert!(f.transport.sent.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_missing_provider_is_reported_and_audited() {
+        let audit = Arc::new(InMemoryAuditStore::new());
+        let tool = SendEmailTool::new(
+            Arc::new(InMemoryProviderStore::new()),
+            Arc::new(AesGcmSecretsManager::new(None)),
+            audit.clone(),
+            EmailConfig::default(),
+        );
+        let result = tool
+            .execute(
+                json!({"to": "a@example.com", "subject": "Hi\r\nBcc: x@evil.com", "body": "b"}),
+            )
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.content.contains("No SMTP provider"));
+
+        let entries = audit.query(AuditFilter::default()).await.unwrap();
+        assert!(matches!(entries[0].outcome, AuditOutcome::Error(_)));
+        assert_eq!(
+            entries[0].metadata.as_ref().unwrap()["subject"],
+            "Hi  Bcc: x@evil.com"
+        );
+    }
+}
@@ -3,23 +3,58 @@
 //!
 //! This crate provides:
 //! - Tool registry for managing available tools
+//! - Tool usage analytics feeding back into tool ranking
 //! - Built-in tools (read_artifact, echo, etc.)
 //! - Code simplifier for AST-based skeletonization
+//! - Repository map tool for large sandbox workspaces
 //! - MCP adapter for external tool servers
+//! - Supervised, resource-limited stdio MCP server processes
+//! - `ask_user` tool for mid-mission clarification questions
+//! - Quarantine checks for downloads before they reach the sandbox
+//! - Tool-aware shortening of long observations
 
+pub mod analytics;
+pub mod ask_user;
 pub mod builtin;
 pub mod code_simplifier;
 pub mod composite_registry;
+pub mod connectors;
+pub mod email;
 pub mod loader;
 pub mod mcp_adapter;
+pub mod mcp_process;
 pub mod mcp_registry;
 pub mod network;
+pub mod observation;
+pub mod openapi;
+pub mod patch;
+pub mod quarantine;
 pub mod registry;
+pub mod repo_map;
+pub mod tabular;
 
+pub use analytics::{AnalyticsToolRegistry, ToolAnalytics, ToolStats};
+pub use ask_user::AskUserTool;
 pub use builtin::*;
-pub use code_simplifier::{simplify_rust_code, SimplifiedCode};
+pub use code_simplifier::{
+    simplify_code, simplify_file, simplify_rust_code, CodeLanguage, SimplifiedCode,
+};
 pub use composite_registry::CompositeToolRegistry;
-pub use loader::load_mcp_config;
-pub use mcp_adapter::{McpTool, McpToolAdapter, McpTransport};
+pub use connectors::{connector_tools, ConnectorContext};
+pub use email::SendEmailTool;
+pub use loader::{import_mcp_servers, load_mcp_config, parse_mcp_config, McpImportResult};
+pub use mcp_adapter::{McpCallPolicy, McpTool, McpToolAdapter, McpTransport};
+pub use mcp_process::{
+    McpIsolation, McpLaunchOptions, McpProcessStatus, McpProcessSupervisor, McpResourceLimits,
+    RestartPolicy,
+};
 pub use mcp_registry::{McpCapability, McpRegistry, McpServerInfo};
+pub use observation::{
+    HeadTailProcessor, ObservationProcessor, ObservationToolRegistry, ReadableTextExtractor,
+    TestFailureSummarizer,
+};
+pub use openapi::{AuthProfile, OpenApiRegistry, OpenApiSpec, OpenApiTool};
+pub use patch::ApplyPatchTool;
 pub use registry::DefaultToolRegistry;
+pub use repo_map::RepoMapTool;
+pub use tabular::TabularTool;
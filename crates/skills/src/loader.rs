@@ -1,21 +1,204 @@
+use crate::mcp_adapter::McpCallPolicy;
+use crate::mcp_process::McpLaunchOptions;
 use crate::mcp_registry::{McpRegistry, McpServerInfo};
 use multi_agent_core::{Error, Result};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
 use tokio::fs;
 
+/// Top-level `mcp.json` / Claude Desktop config.
+///
+/// VS Code's `.vscode/mcp.json` uses `servers` instead of `mcpServers`;
+/// both are accepted.
 #[derive(Deserialize)]
 struct McpConfig {
-    #[serde(rename = "mcpServers")]
+    #[serde(rename = "mcpServers", alias = "servers", default)]
     mcp_servers: HashMap<String, McpServerConfig>,
 }
 
 #[derive(Deserialize)]
 struct McpServerConfig {
-    command: String,
+    #[serde(default)]
+    command: Option<String>,
+    #[serde(default)]
     args: Vec<String>,
+    #[serde(default)]
+    env: HashMap<String, String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(rename = "type", alias = "transport", default)]
+    transport: Option<String>,
+    #[serde(default)]
+    disabled: bool,
+    /// OpenCoordex extension: isolation, limits and restart policy.
+    #[serde(default)]
+    launch: McpLaunchOptions,
+    /// OpenCoordex extension: timeouts, retries and result size limit.
+    #[serde(default)]
+    call_policy: McpCallPolicy,
+}
+
+impl McpServerConfig {
+    fn into_server_info(self, name: &str) -> Result<McpServerInfo> {
+        let info = McpServerInfo::new(name, name)
+            .with_keywords(vec![name])
+            .with_call_policy(self.call_policy);
+        match (self.command, self.url) {
+            (Some(command), _) => Ok(info
+                .with_uri(command)
+                .with_args(self.args.iter().map(|s| s.as_str()).collect())
+                .with_env(self.env)
+                .with_launch(self.launch)
+                .with_transport("stdio")),
+            (None, Some(url)) => {
+                let transport = match self.transport.as_deref() {
+                    Some("websocket") | Some("ws") => "websocket",
+                    Some("sse") | Some("http") | Some("streamable-http") => "sse",
+                    None if url.starts_with("ws") => "websocket",
+                    None => "sse",
+                    Some(other) => {
+                        return Err(Error::mcp_adapter(format!(
+                            "Unsupported transport '{}'",
+                            other
+                        )))
+                    }
+                };
+                Ok(info.with_uri(url).with_transport(transport))
+            }
+            (None, None) => Err(Error::mcp_adapter(
+                "Server needs either 'command' or 'url'".to_string(),
+            )),
+        }
+    }
+}
+
+/// A server entry from an MCP config that could not be converted.
+#[derive(Debug, Clone, Serialize)]
+pub struct McpConfigError {
+    pub name: String,
+    pub error: String,
+}
+
+/// Servers parsed from an MCP config document.
+#[derive(Debug, Default)]
+pub struct ParsedMcpConfig {
+    /// Valid, enabled servers.
+    pub servers: Vec<McpServerInfo>,
+    /// Names of entries marked `"disabled": true`.
+    pub disabled: Vec<String>,
+    /// Entries that could not be converted.
+    pub invalid: Vec<McpConfigError>,
+}
+
+/// Parse an `mcp.json` / Claude Desktop config document.
+///
+/// Fails only if the document itself is malformed; bad server entries are
+/// reported in [`ParsedMcpConfig::invalid`].
+pub fn parse_mcp_config(json: &str) -> Result<ParsedMcpConfig> {
+    let config: McpConfig = serde_json::from_str(json)
+        .map_err(|e| Error::mcp_adapter(format!("Failed to parse MCP config (JSON): {}", e)))?;
+    Ok(convert(config))
+}
+
+fn convert(config: McpConfig) -> ParsedMcpConfig {
+    let mut entries: Vec<_> = config.mcp_servers.into_iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut parsed = ParsedMcpConfig::default();
+    for (name, server_conf) in entries {
+        if server_conf.disabled {
+            parsed.disabled.push(name);
+            continue;
+        }
+        match server_conf.into_server_info(&name) {
+            Ok(info) => parsed.servers.push(info),
+            Err(e) => parsed.invalid.push(McpConfigError {
+                name,
+                error: e.to_string(),
+            }),
+        }
+    }
+    parsed
+}
+
+/// Outcome of importing one server.
+#[derive(Debug, Clone, Serialize)]
+pub struct McpImportResult {
+    pub id: String,
+    /// `registered`, `updated`, `unhealthy`, `disabled` or `invalid`.
+    pub status: String,
+    /// Transport type, for registered servers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transport: Option<String>,
+    /// Tools discovered after connecting.
+    pub tools: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Register every server from a parsed config, connect to it and discover
+/// its tools.
+///
+/// Servers that fail to connect stay registered but are marked unavailable
+/// so task selection skips them.
+pub async fn import_mcp_servers(
+    registry: &McpRegistry,
+    parsed: ParsedMcpConfig,
+) -> Vec<McpImportResult> {
+    let mut results = Vec::new();
+
+    for mut info in parsed.servers {
+        let id = info.id.clone();
+        let transport = Some(info.transport_type.clone());
+        let existed = registry.contains(&id);
+        info.available = true;
+        registry.register(info.clone());
+
+        let discovered = match registry.connect_server(&id).await {
+            Ok(()) => registry.adapter().get_server_tools(&id).await,
+            Err(e) => Err(e),
+        };
+        match discovered {
+            Ok(tools) => results.push(McpImportResult {
+                id,
+                status: if existed { "updated" } else { "registered" }.to_string(),
+                transport,
+                tools: tools.into_iter().map(|t| t.name).collect(),
+                error: None,
+            }),
+            Err(e) => {
+                tracing::warn!(server = %id, error = %e, "MCP server failed health check on import");
+                info.available = false;
+                registry.register(info);
+                results.push(McpImportResult {
+                    id,
+                    status: "unhealthy".to_string(),
+                    transport,
+                    tools: Vec::new(),
+                    error: Some(e.to_string()),
+                });
+            }
+        }
+    }
+
+    results.extend(parsed.disabled.into_iter().map(|id| McpImportResult {
+        id,
+        status: "disabled".to_string(),
+        transport: None,
+        tools: Vec::new(),
+        error: None,
+    }));
+    results.extend(parsed.invalid.into_iter().map(|e| McpImportResult {
+        id: e.name,
+        status: "invalid".to_string(),
+        transport: None,
+        tools: Vec::new(),
+        error: Some(e.error),
+    }));
+
+    results
 }
 
 /// Load an MCP configuration file and register servers to the registry.
@@ -30,36 +213,92 @@ pub async fn load_mcp_config(registry: Arc<McpRegistry>, path: &Path) -> Result<
         .map_err(|e| Error::mcp_adapter(format!("Failed to read MCP config: {}", e)))?;
 
     // Try parsing as TOML first, then JSON (naive approach, or rely on extension)
-    let config: McpConfig = if path.extension().is_some_and(|ext| ext == "json") {
-        serde_json::from_str(&content)
-            .map_err(|e| Error::mcp_adapter(format!("Failed to parse MCP config (JSON): {}", e)))?
-    } else {
-        toml::from_str(&content)
-            .map_err(|e| Error::mcp_adapter(format!("Failed to parse MCP config (TOML): {}", e)))?
-    };
-
-    for (id, server_conf) in config.mcp_servers {
-        let info = McpServerInfo::new(&id, &id)
-            .with_uri(server_conf.command)
-            .with_args(
-                server_conf
-                    .args
-                    .iter()
-                    .map(|s| s.as_str())
-                    .collect::<Vec<&str>>(),
-            )
-            .with_transport("stdio");
+    let parsed =
+        if path.extension().is_some_and(|ext| ext == "json") {
+            parse_mcp_config(&content)?
+        } else {
+            convert(toml::from_str(&content).map_err(|e| {
+                Error::mcp_adapter(format!("Failed to parse MCP config (TOML): {}", e))
+            })?)
+        };
 
+    for invalid in &parsed.invalid {
+        tracing::warn!(server = %invalid.name, error = %invalid.error, "Skipping MCP server");
+    }
+    for info in parsed.servers {
         // TODO: Map capabilities from config if available (currently config doesn't have them)
         registry.register(info);
 
-        // Auto-connect?
-        // Let's not auto-connect to all servers to save resources.
-        // Or should we?
-        // The plan said "Dynamically register MCP clients". Connection might happen on demand.
-        // But the previous implementation had comments about connecting.
-        // Let's just register for now.
+        // Connection happens on demand; see `import_mcp_servers` for eager discovery.
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLAUDE_DESKTOP: &str = r#"{
+        "mcpServers": {
+            "filesystem": {
+                "command": "npx",
+                "args": ["-y", "@modelcontextprotocol/server-filesystem", "/tmp"],
+                "env": {"DEBUG": "1"}
+            },
+            "remote": {"url": "https://mcp.example.com/sse"},
+            "old": {"command": "legacy", "disabled": true},
+            "broken": {"args": ["x"]}
+        }
+    }"#;
+
+    #[test]
+    fn test_parse_claude_desktop_config() {
+        let parsed = parse_mcp_config(CLAUDE_DESKTOP).unwrap();
+        assert_eq!(parsed.servers.len(), 2);
+
+        let fs = &parsed.servers[0];
+        assert_eq!(fs.id, "filesystem");
+        assert_eq!(fs.transport_type, "stdio");
+        assert_eq!(fs.args.len(), 3);
+        assert_eq!(fs.env["DEBUG"], "1");
+
+        let remote = &parsed.servers[1];
+        assert_eq!(remote.transport_type, "sse");
+        assert_eq!(remote.connection_uri, "https://mcp.example.com/sse");
+
+        assert_eq!(parsed.disabled, vec!["old"]);
+        assert_eq!(parsed.invalid[0].name, "broken");
+        assert!(parse_mcp_config("not json").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_import_registers_and_discovers() {
+        let registry = McpRegistry::new();
+        let parsed = parse_mcp_config(CLAUDE_DESKTOP).unwrap();
+        let results = import_mcp_servers(&registry, parsed).await;
+
+        let statuses: Vec<_> = results
+            .iter()
+            .map(|r| (r.id.as_str(), r.status.as_str()))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("filesystem", "registered"),
+                ("remote", "registered"),
+                ("old", "disabled"),
+                ("broken", "invalid"),
+            ]
+        );
+        assert!(!results[0].tools.is_empty());
+        assert!(registry.contains("filesystem"));
+        assert!(!registry.contains("old"));
+
+        // Re-importing updates in place
+        let parsed = parse_mcp_config(CLAUDE_DESKTOP).unwrap();
+        let results = import_mcp_servers(&registry, parsed).await;
+        assert_eq!(results[0].status, "updated");
+        assert_eq!(registry.list_all().len(), 2);
+    }
+}
@@ -5,14 +5,94 @@
 
 use async_trait::async_trait;
 use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 
+use crate::mcp_process::{McpLaunchOptions, McpProcessSupervisor};
 use multi_agent_core::{
+    traits::ArtifactStore,
     types::{ToolDefinition, ToolOutput},
     Error, Result,
 };
 
+/// Per-server limits applied to every tool call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpCallPolicy {
+    /// Deadline for a single attempt.
+    #[serde(default = "default_call_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Extra attempts after a timeout or other transient failure.
+    #[serde(default = "default_call_max_retries")]
+    pub max_retries: u32,
+    /// Delay before the first retry; doubles on each further retry.
+    #[serde(default = "default_call_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+    /// Results larger than this are spilled to the artifact store.
+    #[serde(default = "default_max_result_bytes")]
+    pub max_result_bytes: usize,
+}
+
+fn default_call_timeout_ms() -> u64 {
+    30_000
+}
+
+fn default_call_max_retries() -> u32 {
+    2
+}
+
+fn default_call_retry_backoff_ms() -> u64 {
+    250
+}
+
+fn default_max_result_bytes() -> usize {
+    32 * 1024
+}
+
+impl Default for McpCallPolicy {
+    fn default() -> Self {
+        Self {
+            timeout_ms: default_call_timeout_ms(),
+            max_retries: default_call_max_retries(),
+            retry_backoff_ms: default_call_retry_backoff_ms(),
+            max_result_bytes: default_max_result_bytes(),
+        }
+    }
+}
+
+impl McpCallPolicy {
+    /// Run `call` under the timeout, retrying transient failures.
+    pub async fn run<F, Fut>(&self, label: &str, mut call: F) -> Result<ToolOutput>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<ToolOutput>>,
+    {
+        let mut backoff = Duration::from_millis(self.retry_backoff_ms);
+        let mut attempt = 0;
+        loop {
+            let result =
+                match tokio::time::timeout(Duration::from_millis(self.timeout_ms), call()).await {
+                    Ok(result) => result,
+                    Err(_) => Err(Error::Timeout(format!(
+                        "MCP call '{}' timed out after {}ms",
+                        label, self.timeout_ms
+                    ))),
+                };
+            match result {
+                Err(e) if e.is_retryable() && attempt < self.max_retries => {
+                    attempt += 1;
+                    tracing::warn!(call = %label, attempt, error = %e, "Retrying MCP call");
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                other => return other,
+            }
+        }
+    }
+}
+
 /// MCP transport type for connecting to servers.
 #[derive(Debug, Clone)]
 pub enum McpTransport {
@@ -22,6 +102,10 @@ pub enum McpTransport {
         command: String,
         /// Command arguments
         args: Vec<String>,
+        /// Environment variables
+        env: std::collections::HashMap<String, String>,
+        /// Isolation, limits and restart policy
+        launch: Box<McpLaunchOptions>,
     },
     /// Connect via Server-Sent Events
     Sse {
@@ -46,6 +130,8 @@ pub struct McpServerConnection {
     pub connected: bool,
     /// Available tools from this server
     pub tools: Vec<ToolDefinition>,
+    /// Timeouts, retries and result size limit for tool calls
+    pub call_policy: McpCallPolicy,
 }
 
 /// MCP tool adapter for managing connections to MCP servers.
@@ -57,6 +143,10 @@ pub struct McpServerConnection {
 pub struct McpToolAdapter {
     /// Connected servers
     servers: DashMap<String, Arc<RwLock<McpServerConnection>>>,
+    /// Launches stdio servers; without one, stdio connections are not spawned.
+    supervisor: Option<Arc<McpProcessSupervisor>>,
+    /// Where oversized results are spilled; without one they are truncated.
+    artifact_store: Option<Arc<dyn ArtifactStore>>,
 }
 
 impl Default for McpToolAdapter {
@@ -70,9 +160,28 @@ impl McpToolAdapter {
     pub fn new() -> Self {
         Self {
             servers: DashMap::new(),
+            supervisor: None,
+            artifact_store: None,
         }
     }
 
+    /// Spill oversized tool results to this store.
+    pub fn with_artifact_store(mut self, store: Arc<dyn ArtifactStore>) -> Self {
+        self.artifact_store = Some(store);
+        self
+    }
+
+    /// Launch stdio servers under the given supervisor.
+    pub fn with_supervisor(mut self, supervisor: Arc<McpProcessSupervisor>) -> Self {
+        self.supervisor = Some(supervisor);
+        self
+    }
+
+    /// The process supervisor, if stdio servers are launched.
+    pub fn supervisor(&self) -> Option<Arc<McpProcessSupervisor>> {
+        self.supervisor.clone()
+    }
+
     /// Connect to an MCP server.
     ///
     /// # Arguments
@@ -84,17 +193,33 @@ impl McpToolAdapter {
     /// adapter.connect("local-tools", McpTransport::Stdio {
     ///     command: "npx".to_string(),
     ///     args: vec!["-y", "@modelcontextprotocol/server-filesystem", "/tmp"],
+    ///     env: Default::default(),
+    ///     launch: Default::default(),
     /// }).await?;
     /// ```
     pub async fn connect(&self, name: &str, transport: McpTransport) -> Result<()> {
         tracing::info!(server = %name, transport = ?transport, "Connecting to MCP server");
 
+        if let (
+            Some(supervisor),
+            McpTransport::Stdio {
+                command,
+                args,
+                env,
+                launch,
+            },
+        ) = (&self.supervisor, &transport)
+        {
+            supervisor.start(name, command, args, env, launch).await?;
+        }
+
         // Create connection state
         let connection = McpServerConnection {
             name: name.to_string(),
             transport: transport.clone(),
             connected: false,
             tools: Vec::new(),
+            call_policy: McpCallPolicy::default(),
         };
 
         // Store connection (actual MCP connection would happen here)
@@ -133,6 +258,9 @@ impl McpToolAdapter {
 
     /// Disconnect from an MCP server.
     pub async fn disconnect(&self, name: &str) -> Result<()> {
+        if let Some(ref supervisor) = self.supervisor {
+            supervisor.stop(name).await;
+        }
         if let Some((_, server)) = self.servers.remove(name) {
             let mut conn = server.write().await;
             conn.connected = false;
@@ -141,6 +269,16 @@ impl McpToolAdapter {
         Ok(())
     }
 
+    /// Set the call policy of a connected server.
+    pub async fn set_call_policy(&self, name: &str, policy: McpCallPolicy) -> Result<()> {
+        let server = self
+            .servers
+            .get(name)
+            .ok_or_else(|| Error::mcp_adapter(format!("MCP server '{}' not found", name)))?;
+        server.write().await.call_policy = policy;
+        Ok(())
+    }
+
     /// List all connected servers.
     pub fn list_servers(&self) -> Vec<String> {
         self.servers.iter().map(|e| e.key().clone()).collect()
@@ -284,23 +422,42 @@ impl McpToolAdapter {
             .get(server_name)
             .ok_or_else(|| Error::mcp_adapter(format!("MCP server '{}' not found", server_name)))?;
 
-        let conn = server.read().await;
-        if !conn.connected {
-            return Err(Error::mcp_adapter(format!(
-                "MCP server '{}' is not connected",
-                server_name
-            )));
-        }
+        let policy = {
+            let conn = server.read().await;
+            if !conn.connected {
+                return Err(Error::mcp_adapter(format!(
+                    "MCP server '{}' is not connected",
+                    server_name
+                )));
+            }
 
-        // Check if tool exists
-        let tool_exists = conn.tools.iter().any(|t| t.name == full_tool_name);
-        if !tool_exists {
-            return Err(Error::mcp_adapter(format!(
-                "Tool '{}' not found on server '{}'",
-                tool_name, server_name
-            )));
-        }
+            // Check if tool exists
+            let tool_exists = conn.tools.iter().any(|t| t.name == full_tool_name);
+            if !tool_exists {
+                return Err(Error::mcp_adapter(format!(
+                    "Tool '{}' not found on server '{}'",
+                    tool_name, server_name
+                )));
+            }
+            conn.call_policy.clone()
+        };
+        drop(server);
+
+        let output = policy
+            .run(full_tool_name, || {
+                self.send_tool_call(full_tool_name, &args)
+            })
+            .await?;
+        self.limit_result_size(full_tool_name, output, &policy)
+            .await
+    }
 
+    /// Send one tool call request to the server and wait for the response.
+    async fn send_tool_call(
+        &self,
+        full_tool_name: &str,
+        args: &serde_json::Value,
+    ) -> Result<ToolOutput> {
         // In a full implementation, we would:
         // 1. Send tool call request to the MCP server
         // 2. Wait for response
@@ -310,10 +467,49 @@ impl McpToolAdapter {
         Ok(ToolOutput::text(format!(
             "MCP tool '{}' executed with args: {}. (Mock response - real MCP integration pending)",
             full_tool_name,
-            serde_json::to_string_pretty(&args).unwrap_or_default()
+            serde_json::to_string_pretty(args).unwrap_or_default()
         )))
     }
 
+    /// Keep oversized results out of the session history.
+    async fn limit_result_size(
+        &self,
+        full_tool_name: &str,
+        mut output: ToolOutput,
+        policy: &McpCallPolicy,
+    ) -> Result<ToolOutput> {
+        let size = output.content.len();
+        if size <= policy.max_result_bytes {
+            return Ok(output);
+        }
+        tracing::info!(tool = %full_tool_name, size, limit = policy.max_result_bytes, "MCP result exceeds size limit");
+
+        match &self.artifact_store {
+            Some(store) => {
+                let content = std::mem::take(&mut output.content);
+                let (message, ref_id) = multi_agent_store::maybe_store_by_ref_with_threshold(
+                    store.as_ref(),
+                    content,
+                    policy.max_result_bytes,
+                )
+                .await?;
+                output.content = message;
+                output.created_refs.extend(ref_id);
+            }
+            None => {
+                let mut end = policy.max_result_bytes;
+                while !output.content.is_char_boundary(end) {
+                    end -= 1;
+                }
+                output.content.truncate(end);
+                output
+                    .content
+                    .push_str(&format!("\n[truncated: {} of {} bytes shown]", end, size));
+            }
+        }
+        Ok(output)
+    }
+
     /// Check if a tool name is an MCP tool (contains '/').
     pub fn is_mcp_tool(tool_name: &str) -> bool {
         tool_name.contains('/')
@@ -420,6 +616,8 @@ mod tests {
                 McpTransport::Stdio {
                     command: "echo".to_string(),
                     args: vec![],
+                    env: Default::default(),
+                    launch: Default::default(),
                 },
             )
             .await
@@ -455,6 +653,82 @@ mod tests {
         assert!(result.content.contains("list_files"));
     }
 
+    #[tokio::test]
+    async fn test_call_policy_retries_timeouts() {
+        let policy = McpCallPolicy {
+            timeout_ms: 20,
+            max_retries: 2,
+            retry_backoff_ms: 1,
+            ..Default::default()
+        };
+        let attempts = std::sync::atomic::AtomicU32::new(0);
+        let output = policy
+            .run("slow/tool", || async {
+                if attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst) < 2 {
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                }
+                Ok(ToolOutput::text("done"))
+            })
+            .await
+            .unwrap();
+        assert_eq!(output.content, "done");
+        assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), 3);
+
+        // Non-transient errors are returned immediately
+        let attempts = std::sync::atomic::AtomicU32::new(0);
+        let result = policy
+            .run("bad/tool", || async {
+                attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                Err(Error::mcp_adapter("invalid arguments"))
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // Exhausted retries surface the timeout
+        let result = policy
+            .run("stuck/tool", || async {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                Ok(ToolOutput::text("never"))
+            })
+            .await;
+        assert!(matches!(result, Err(Error::Timeout(_))));
+    }
+
+    #[tokio::test]
+    async fn test_oversized_result_spilled_or_truncated() {
+        let store = Arc::new(multi_agent_store::InMemoryStore::new());
+        let small = McpCallPolicy {
+            max_result_bytes: 40,
+            ..Default::default()
+        };
+        let transport = McpTransport::Sse {
+            url: "http://localhost:8080".to_string(),
+        };
+
+        let adapter = McpToolAdapter::new().with_artifact_store(store.clone());
+        adapter.connect("fs", transport.clone()).await.unwrap();
+        adapter.set_call_policy("fs", small.clone()).await.unwrap();
+        let output = adapter
+            .call_tool("fs/list_files", serde_json::json!({"path": "/tmp"}))
+            .await
+            .unwrap();
+        assert!(output.content.contains("RefID"));
+        assert_eq!(output.created_refs.len(), 1);
+        let saved = store.load(&output.created_refs[0]).await.unwrap().unwrap();
+        assert!(String::from_utf8_lossy(&saved).contains("list_files"));
+
+        let adapter = McpToolAdapter::new();
+        adapter.connect("fs", transport).await.unwrap();
+        adapter.set_call_policy("fs", small).await.unwrap();
+        let output = adapter
+            .call_tool("fs/list_files", serde_json::json!({"path": "/tmp"}))
+            .await
+            .unwrap();
+        assert!(output.content.ends_with("bytes shown]"));
+        assert!(output.created_refs.is_empty());
+    }
+
     #[test]
     fn test_is_mcp_tool() {
         assert!(McpToolAdapter::is_mcp_tool("server/tool"));
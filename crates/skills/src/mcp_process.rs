@@ -0,0 +1,733 @@
+//! Supervised stdio MCP server processes.
+//!
+//! A stdio server is launched in one of two ways:
+//! - **Process**: a child of the gateway with a cleared environment (only an
+//!   allowlist of host variables plus the server's own `env`) and optional
+//!   rlimits applied through `prlimit`.
+//! - **Sandbox**: a throwaway `docker run -i` container with the same
+//!   hardening as agent sandboxes (no capabilities, no network by default,
+//!   read-only root, non-root user) and cgroup memory/CPU/pid limits.
+//!
+//! The supervisor restarts servers according to their [`RestartPolicy`],
+//! forwards stderr into the log (keeping a short tail for the admin API) and
+//! samples CPU and memory usage into per-server metrics.
+
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use multi_agent_core::{Error, Result};
+use multi_agent_sandbox::RunAs;
+
+/// Host environment variables passed to servers unless overridden.
+pub const DEFAULT_ENV_ALLOWLIST: &[&str] = &[
+    "PATH",
+    "HOME",
+    "LANG",
+    "LC_ALL",
+    "TMPDIR",
+    "USER",
+    "NODE_PATH",
+    "DOCKER_HOST",
+];
+
+/// Stderr lines kept per server.
+const STDERR_TAIL_LINES: usize = 50;
+/// How often resource usage is sampled.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(15);
+/// Upper bound for the restart backoff.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Where a stdio server runs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum McpIsolation {
+    /// Restricted child process of the gateway.
+    #[default]
+    Process,
+    /// Throwaway container; the image must provide the server command.
+    Sandbox {
+        image: String,
+        /// Allow outbound network (default: none).
+        #[serde(default)]
+        network: bool,
+    },
+}
+
+/// Resource ceilings for a server.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct McpResourceLimits {
+    /// Memory limit in MB (address space under process isolation).
+    #[serde(default)]
+    pub memory_mb: Option<u64>,
+    /// CPU cores (sandbox isolation only).
+    #[serde(default)]
+    pub cpus: Option<f64>,
+    /// Total CPU seconds before the kernel kills the server (process isolation only).
+    #[serde(default)]
+    pub cpu_seconds: Option<u64>,
+    /// Max processes/threads.
+    #[serde(default)]
+    pub pids: Option<u64>,
+}
+
+/// When a server that exited is started again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "policy", rename_all = "snake_case")]
+pub enum RestartPolicy {
+    /// Leave it stopped.
+    Never,
+    /// Restart after a non-zero exit.
+    OnFailure { max_restarts: u32 },
+    /// Restart after any exit.
+    Always { max_restarts: u32 },
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self::OnFailure { max_restarts: 5 }
+    }
+}
+
+impl RestartPolicy {
+    /// Whether to restart after an exit, given the restarts so far.
+    pub fn should_restart(&self, success: bool, restarts: u32) -> bool {
+        match *self {
+            Self::Never => false,
+            Self::OnFailure { max_restarts } => !success && restarts < max_restarts,
+            Self::Always { max_restarts } => restarts < max_restarts,
+        }
+    }
+}
+
+/// How a stdio server is launched and supervised.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpLaunchOptions {
+    #[serde(default)]
+    pub isolation: McpIsolation,
+    #[serde(default)]
+    pub limits: McpResourceLimits,
+    /// Host variables passed through; everything else is dropped.
+    #[serde(default = "default_env_allowlist")]
+    pub env_allowlist: Vec<String>,
+    #[serde(default)]
+    pub restart: RestartPolicy,
+    /// Delay before the first restart; doubles on each further restart.
+    #[serde(default = "default_restart_backoff_ms")]
+    pub restart_backoff_ms: u64,
+}
+
+fn default_env_allowlist() -> Vec<String> {
+    DEFAULT_ENV_ALLOWLIST
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn default_restart_backoff_ms() -> u64 {
+    1000
+}
+
+impl Default for McpLaunchOptions {
+    fn default() -> Self {
+        Self {
+            isolation: McpIsolation::default(),
+            limits: McpResourceLimits::default(),
+            env_allowlist: default_env_allowlist(),
+            restart: RestartPolicy::default(),
+            restart_backoff_ms: default_restart_backoff_ms(),
+        }
+    }
+}
+
+/// Fully resolved command line for a server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchCommand {
+    pub program: String,
+    pub args: Vec<String>,
+    /// The complete environment; the host environment is not inherited.
+    pub env: Vec<(String, String)>,
+    /// File Docker writes the container id to (sandbox isolation).
+    pub cidfile: Option<PathBuf>,
+}
+
+impl LaunchCommand {
+    /// Build the command line for `command args` under `options`.
+    pub fn build(
+        server: &str,
+        command: &str,
+        args: &[String],
+        env: &HashMap<String, String>,
+        options: &McpLaunchOptions,
+    ) -> Self {
+        let mut full_env: Vec<(String, String)> = options
+            .env_allowlist
+            .iter()
+            .filter(|name| !env.contains_key(*name))
+            .filter_map(|name| std::env::var(name).ok().map(|v| (name.clone(), v)))
+            .collect();
+        let mut server_env: Vec<_> = env.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        server_env.sort();
+
+        let limits = &options.limits;
+        match &options.isolation {
+            McpIsolation::Process => {
+                full_env.extend(server_env);
+                if limits.cpus.is_some() {
+                    tracing::warn!(server = %server, "CPU core limits need sandbox isolation; ignoring");
+                }
+
+                let mut rlimits = Vec::new();
+                if let Some(mb) = limits.memory_mb {
+                    rlimits.push(format!("--as={}", mb * 1024 * 1024));
+                }
+                if let Some(secs) = limits.cpu_seconds {
+                    rlimits.push(format!("--cpu={}", secs));
+                }
+                if let Some(pids) = limits.pids {
+                    rlimits.push(format!("--nproc={}", pids));
+                }
+                if rlimits.is_empty() {
+                    return Self {
+                        program: command.to_string(),
+                        args: args.to_vec(),
+                        env: full_env,
+                        cidfile: None,
+                    };
+                }
+
+                rlimits.push("--".to_string());
+                rlimits.push(command.to_string());
+                rlimits.extend(args.iter().cloned());
+                Self {
+                    program: "prlimit".to_string(),
+                    args: rlimits,
+                    env: full_env,
+                    cidfile: None,
+                }
+            }
+            McpIsolation::Sandbox { image, network } => {
+                let cidfile = std::env::temp_dir().join(format!(
+                    "opencoordex-mcp-{}-{}.cid",
+                    server.replace(|c: char| !c.is_ascii_alphanumeric() && c != '-', "_"),
+                    uuid::Uuid::new_v4()
+                ));
+                let mut docker_args: Vec<String> = vec![
+                    "run".into(),
+                    "-i".into(),
+                    "--rm".into(),
+                    format!("--cidfile={}", cidfile.display()),
+                    format!("--network={}", if *network { "bridge" } else { "none" }),
+                    "--cap-drop=ALL".into(),
+                    "--security-opt=no-new-privileges:true".into(),
+                    "--read-only".into(),
+                    "--tmpfs=/tmp:rw,noexec,nosuid,size=64m".into(),
+                    format!("--user={}", RunAs::default().docker_user()),
+                ];
+                if let Some(mb) = limits.memory_mb {
+                    docker_args.push(format!("--memory={}m", mb));
+                }
+                if let Some(cpus) = limits.cpus {
+                    docker_args.push(format!("--cpus={}", cpus));
+                }
+                if let Some(pids) = limits.pids {
+                    docker_args.push(format!("--pids-limit={}", pids));
+                }
+                if limits.cpu_seconds.is_some() {
+                    tracing::warn!(server = %server, "CPU time limits need process isolation; ignoring");
+                }
+                // Values stay in the docker client's environment rather than on
+                // its command line, where any local user could read them.
+                for (key, _) in &server_env {
+                    docker_args.push("-e".into());
+                    docker_args.push(key.clone());
+                }
+                docker_args.push(image.clone());
+                docker_args.push(command.to_string());
+                docker_args.extend(args.iter().cloned());
+
+                full_env.extend(server_env);
+                Self {
+                    program: "docker".to_string(),
+                    args: docker_args,
+                    env: full_env,
+                    cidfile: Some(cidfile),
+                }
+            }
+        }
+    }
+
+    fn spawn(&self) -> Result<Child> {
+        if let Some(ref cidfile) = self.cidfile {
+            // Docker refuses to start if the cidfile exists
+            let _ = std::fs::remove_file(cidfile);
+        }
+        Command::new(&self.program)
+            .args(&self.args)
+            .env_clear()
+            .envs(self.env.iter().map(|(k, v)| (k, v)))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| Error::mcp_adapter(format!("Failed to launch '{}': {}", self.program, e)))
+    }
+}
+
+/// Runtime state of a supervised server.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct McpProcessStatus {
+    pub server: String,
+    pub running: bool,
+    pub pid: Option<u32>,
+    pub restarts: u32,
+    pub last_exit_code: Option<i32>,
+    /// CPU time consumed by the current instance.
+    pub cpu_seconds: Option<f64>,
+    /// Resident memory of the current instance.
+    pub memory_bytes: Option<u64>,
+    /// Most recent stderr lines.
+    pub stderr_tail: Vec<String>,
+}
+
+struct Supervised {
+    status: Arc<Mutex<McpProcessStatus>>,
+    stderr: Arc<Mutex<VecDeque<String>>>,
+    stop: watch::Sender<bool>,
+    task: tokio::sync::Mutex<Option<JoinHandle<()>>>,
+}
+
+/// Launches, restarts and monitors stdio MCP servers.
+#[derive(Default)]
+pub struct McpProcessSupervisor {
+    processes: DashMap<String, Arc<Supervised>>,
+}
+
+impl McpProcessSupervisor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Launch a server, replacing any running instance with the same name.
+    ///
+    /// Fails if the first launch fails; later crashes are handled by the
+    /// restart policy.
+    pub async fn start(
+        &self,
+        server: &str,
+        command: &str,
+        args: &[String],
+        env: &HashMap<String, String>,
+        options: &McpLaunchOptions,
+    ) -> Result<()> {
+        self.stop(server).await;
+
+        let launch = LaunchCommand::build(server, command, args, env, options);
+        let child = launch.spawn()?;
+        tracing::info!(server = %server, program = %launch.program, pid = ?child.id(), "Started MCP server process");
+
+        let (stop_tx, stop_rx) = watch::channel(false);
+        let supervised = Arc::new(Supervised {
+            status: Arc::new(Mutex::new(McpProcessStatus {
+                server: server.to_string(),
+                ..Default::default()
+            })),
+            stderr: Arc::new(Mutex::new(VecDeque::new())),
+            stop: stop_tx,
+            task: tokio::sync::Mutex::new(None),
+        });
+        let task = tokio::spawn(supervise(
+            server.to_string(),
+            launch,
+            options.restart,
+            Duration::from_millis(options.restart_backoff_ms),
+            supervised.status.clone(),
+            supervised.stderr.clone(),
+            stop_rx,
+            child,
+        ));
+        *supervised.task.lock().await = Some(task);
+        self.processes.insert(server.to_string(), supervised);
+        Ok(())
+    }
+
+    /// Stop a server. Returns false if it was not supervised.
+    pub async fn stop(&self, server: &str) -> bool {
+        let Some((_, supervised)) = self.processes.remove(server) else {
+            return false;
+        };
+        let _ = supervised.stop.send(true);
+        if let Some(task) = supervised.task.lock().await.take() {
+            let _ = task.await;
+        }
+        tracing::info!(server = %server, "Stopped MCP server process");
+        true
+    }
+
+    /// Current status of a server.
+    pub fn status(&self, server: &str) -> Option<McpProcessStatus> {
+        self.processes.get(server).map(|p| snapshot(&p))
+    }
+
+    /// Status of every supervised server.
+    pub fn list(&self) -> Vec<McpProcessStatus> {
+        let mut all: Vec<_> = self.processes.iter().map(|p| snapshot(&p)).collect();
+        all.sort_by(|a, b| a.server.cmp(&b.server));
+        all
+    }
+}
+
+fn snapshot(supervised: &Supervised) -> McpProcessStatus {
+    let mut status = supervised.status.lock().unwrap().clone();
+    status.stderr_tail = supervised.stderr.lock().unwrap().iter().cloned().collect();
+    status
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn supervise(
+    server: String,
+    launch: LaunchCommand,
+    policy: RestartPolicy,
+    backoff: Duration,
+    status: Arc<Mutex<McpProcessStatus>>,
+    stderr: Arc<Mutex<VecDeque<String>>>,
+    mut stop: watch::Receiver<bool>,
+    first: Child,
+) {
+    let mut next = Some(first);
+    let mut restarts = 0u32;
+    let mut delay = backoff;
+
+    loop {
+        let spawned = match next.take() {
+            Some(child) => Ok(child),
+            None => launch.spawn(),
+        };
+        let success = match spawned {
+            Ok(child) => match run_once(&server, &launch, child, &status, &stderr, &mut stop).await
+            {
+                Some(success) => success,
+                // Stopped on request
+                None => break,
+            },
+            Err(e) => {
+                tracing::error!(server = %server, error = %e, "MCP server failed to restart");
+                push_line(&stderr, e.to_string());
+                false
+            }
+        };
+
+        if !policy.should_restart(success, restarts) {
+            tracing::warn!(server = %server, restarts, "MCP server exited; not restarting");
+            break;
+        }
+        restarts += 1;
+        status.lock().unwrap().restarts = restarts;
+        metrics::counter!("mcp_server_restarts_total", "server" => server.clone()).increment(1);
+
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = stop.changed() => break,
+        }
+        delay = (delay * 2).min(MAX_BACKOFF);
+    }
+
+    if let Some(ref cidfile) = launch.cidfile {
+        let _ = std::fs::remove_file(cidfile);
+    }
+}
+
+/// Run one instance until it exits (`Some(success)`) or a stop is
+/// requested (`None`).
+async fn run_once(
+    server: &str,
+    launch: &LaunchCommand,
+    mut child: Child,
+    status: &Mutex<McpProcessStatus>,
+    stderr: &Arc<Mutex<VecDeque<String>>>,
+    stop: &mut watch::Receiver<bool>,
+) -> Option<bool> {
+    let pid = child.id();
+    {
+        let mut s = status.lock().unwrap();
+        s.running = true;
+        s.pid = pid;
+        s.cpu_seconds = None;
+        s.memory_bytes = None;
+    }
+
+    if let Some(err) = child.stderr.take() {
+        let server = server.to_string();
+        let stderr = stderr.clone();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(err).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                tracing::warn!(target: "mcp_server", server = %server, "{}", line);
+                push_line(&stderr, line);
+            }
+        });
+    }
+    if let Some(out) = child.stdout.take() {
+        let server = server.to_string();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(out).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                tracing::debug!(target: "mcp_server", server = %server, stdout = %line);
+            }
+        });
+    }
+    // Keep stdin open; servers exit on EOF.
+    let _stdin = child.stdin.take();
+
+    let mut ticker = tokio::time::interval(SAMPLE_INTERVAL);
+    let outcome = loop {
+        tokio::select! {
+            exit = child.wait() => {
+                let code = exit.as_ref().ok().and_then(|s| s.code());
+                let success = exit.map(|s| s.success()).unwrap_or(false);
+                tracing::warn!(server = %server, exit_code = ?code, "MCP server exited");
+                status.lock().unwrap().last_exit_code = code;
+                break Some(success);
+            }
+            _ = stop.changed() => {
+                let _ = child.kill().await;
+                break None;
+            }
+            _ = ticker.tick() => {
+                let usage = match (&launch.cidfile, pid) {
+                    (Some(cidfile), _) => container_usage(cidfile),
+                    (None, Some(pid)) => process_usage(pid),
+                    (None, None) => None,
+                };
+                if let Some((cpu, memory)) = usage {
+                    metrics::gauge!("mcp_server_cpu_seconds", "server" => server.to_string()).set(cpu);
+                    metrics::gauge!("mcp_server_memory_bytes", "server" => server.to_string())
+                        .set(memory as f64);
+                    let mut s = status.lock().unwrap();
+                    s.cpu_seconds = Some(cpu);
+                    s.memory_bytes = Some(memory);
+                }
+            }
+        }
+    };
+
+    let mut s = status.lock().unwrap();
+    s.running = false;
+    s.pid = None;
+    outcome
+}
+
+fn push_line(tail: &Mutex<VecDeque<String>>, line: String) {
+    let mut tail = tail.lock().unwrap();
+    if tail.len() == STDERR_TAIL_LINES {
+        tail.pop_front();
+    }
+    tail.push_back(line);
+}
+
+/// CPU seconds and resident bytes of a host process, from `/proc`.
+fn process_usage(pid: u32) -> Option<(f64, u64)> {
+    // Clock ticks and page size are 100 and 4 KiB on every mainstream Linux target.
+    const TICKS_PER_SEC: f64 = 100.0;
+    const PAGE_SIZE: u64 = 4096;
+
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // Fields after the parenthesised command name start at `state` (field 3)
+    let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+
+    let statm = std::fs::read_to_string(format!("/proc/{}/statm", pid)).ok()?;
+    let rss_pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+
+    Some((
+        (utime + stime) as f64 / TICKS_PER_SEC,
+        rss_pages * PAGE_SIZE,
+    ))
+}
+
+/// CPU seconds and memory of a container, read from its cgroup v2 directory.
+fn container_usage(cidfile: &Path) -> Option<(f64, u64)> {
+    let id = std::fs::read_to_string(cidfile).ok()?;
+    let id = id.trim();
+    if id.is_empty() {
+        return None;
+    }
+    // systemd and cgroupfs drivers respectively
+    let dir = [
+        PathBuf::from(format!("/sys/fs/cgroup/system.slice/docker-{}.scope", id)),
+        PathBuf::from(format!("/sys/fs/cgroup/docker/{}", id)),
+    ]
+    .into_iter()
+    .find(|d| d.exists())?;
+
+    let memory: u64 = std::fs::read_to_string(dir.join("memory.current"))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    let cpu_usec: u64 = std::fs::read_to_string(dir.join("cpu.stat"))
+        .ok()?
+        .lines()
+        .find_map(|l| l.strip_prefix("usage_usec "))?
+        .trim()
+        .parse()
+        .ok()?;
+    Some((cpu_usec as f64 / 1_000_000.0, memory))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_process_command() {
+        let env = HashMap::from([("API_TOKEN".to_string(), "t".to_string())]);
+        let options = McpLaunchOptions {
+            env_allowlist: vec!["PATH".to_string()],
+            limits: McpResourceLimits {
+                memory_mb: Some(256),
+                pids: Some(32),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let launch = LaunchCommand::build("fs", "npx", &["-y".to_string()], &env, &options);
+
+        assert_eq!(launch.program, "prlimit");
+        assert_eq!(
+            launch.args,
+            vec!["--as=268435456", "--nproc=32", "--", "npx", "-y"]
+        );
+        let names: Vec<_> = launch.env.iter().map(|(k, _)| k.as_str()).collect();
+        assert!(names.contains(&"API_TOKEN"));
+        assert!(names.iter().all(|n| *n == "PATH" || *n == "API_TOKEN"));
+
+        let plain = LaunchCommand::build("fs", "npx", &[], &env, &McpLaunchOptions::default());
+        assert_eq!(plain.program, "npx");
+    }
+
+    #[test]
+    fn test_build_sandbox_command() {
+        let env = HashMap::from([("API_TOKEN".to_string(), "secret".to_string())]);
+        let options = McpLaunchOptions {
+            isolation: McpIsolation::Sandbox {
+                image: "mcp/github:latest".to_string(),
+                network: false,
+            },
+            limits: McpResourceLimits {
+                memory_mb: Some(512),
+                cpus: Some(0.5),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let launch = LaunchCommand::build("github", "server", &[], &env, &options);
+
+        assert_eq!(launch.program, "docker");
+        assert!(launch.cidfile.is_some());
+        for expected in [
+            "--network=none",
+            "--memory=512m",
+            "--cpus=0.5",
+            "--user=1000:1000",
+        ] {
+            assert!(launch.args.iter().any(|a| a == expected), "{}", expected);
+        }
+        assert!(launch.args.iter().all(|a| !a.contains("secret")));
+        assert!(launch
+            .env
+            .contains(&("API_TOKEN".to_string(), "secret".to_string())));
+        assert_eq!(
+            &launch.args[launch.args.len() - 2..],
+            ["mcp/github:latest", "server"]
+        );
+    }
+
+    #[test]
+    fn test_restart_policy() {
+        let policy = RestartPolicy::OnFailure { max_restarts: 2 };
+        assert!(policy.should_restart(false, 1));
+        assert!(!policy.should_restart(false, 2));
+        assert!(!policy.should_restart(true, 0));
+        assert!(RestartPolicy::Always { max_restarts: 1 }.should_restart(true, 0));
+        assert!(!RestartPolicy::Never.should_restart(false, 0));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_supervisor_restarts_and_captures_stderr() {
+        let supervisor = McpProcessSupervisor::new();
+        let options = McpLaunchOptions {
+            restart: RestartPolicy::OnFailure { max_restarts: 2 },
+            restart_backoff_ms: 10,
+            ..Default::default()
+        };
+        supervisor
+            .start(
+                "crashy",
+                "sh",
+                &["-c".to_string(), "echo boom >&2; exit 3".to_string()],
+                &HashMap::new(),
+                &options,
+            )
+            .await
+            .unwrap();
+
+        let mut status = supervisor.status("crashy").unwrap();
+        for _ in 0..100 {
+            if status.restarts == 2 && !status.running {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            status = supervisor.status("crashy").unwrap();
+        }
+        assert_eq!(status.restarts, 2);
+        assert_eq!(status.last_exit_code, Some(3));
+        assert!(status.stderr_tail.iter().any(|l| l == "boom"));
+        assert!(supervisor.stop("crashy").await);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_supervisor_samples_usage_and_stops() {
+        let supervisor = McpProcessSupervisor::new();
+        supervisor
+            .start(
+                "idle",
+                "sleep",
+                &["30".to_string()],
+                &HashMap::new(),
+                &McpLaunchOptions::default(),
+            )
+            .await
+            .unwrap();
+
+        // The first sample is taken as soon as the instance starts
+        let mut status = supervisor.status("idle").unwrap();
+        for _ in 0..100 {
+            if status.memory_bytes.is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            status = supervisor.status("idle").unwrap();
+        }
+        assert!(status.running);
+        assert!(status.pid.is_some());
+        assert!(status.memory_bytes.unwrap() > 0);
+
+        assert!(supervisor.stop("idle").await);
+        assert!(supervisor.status("idle").is_none());
+        assert!(!supervisor.stop("idle").await);
+    }
+}
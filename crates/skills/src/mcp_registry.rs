@@ -6,9 +6,11 @@
 
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::mcp_adapter::{McpToolAdapter, McpTransport};
+use crate::mcp_adapter::{McpCallPolicy, McpToolAdapter, McpTransport};
+use crate::mcp_process::McpLaunchOptions;
 use multi_agent_core::{Error, Result};
 
 /// Capability category for MCP servers.
@@ -67,7 +69,15 @@ pub struct McpServerInfo {
     pub connection_uri: String,
     /// Command arguments (for stdio transport).
     pub args: Vec<String>,
-    /// Transport type (stdio, sse, websocket).
+    /// Environment variables (for stdio transport).
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// How the process is launched (for stdio transport).
+    #[serde(default)]
+    pub launch: McpLaunchOptions,
+    /// Timeouts, retries and result size limit for tool calls.
+    #[serde(default)]
+    pub call_policy: McpCallPolicy,
     /// Transport type (stdio, sse, websocket).
     pub transport_type: String,
     /// Priority (higher = preferred).
@@ -87,6 +97,9 @@ impl McpServerInfo {
             keywords: Vec::new(),
             connection_uri: String::new(),
             args: Vec::new(),
+            env: HashMap::new(),
+            launch: McpLaunchOptions::default(),
+            call_policy: McpCallPolicy::default(),
             transport_type: "stdio".to_string(),
             priority: 5,
             available: true,
@@ -123,6 +136,24 @@ impl McpServerInfo {
         self
     }
 
+    /// Set environment variables.
+    pub fn with_env(mut self, env: HashMap<String, String>) -> Self {
+        self.env = env;
+        self
+    }
+
+    /// Set launch options.
+    pub fn with_launch(mut self, launch: McpLaunchOptions) -> Self {
+        self.launch = launch;
+        self
+    }
+
+    /// Set the tool call policy.
+    pub fn with_call_policy(mut self, policy: McpCallPolicy) -> Self {
+        self.call_policy = policy;
+        self
+    }
+
     /// Set transport type.
     pub fn with_transport(mut self, transport: impl Into<String>) -> Self {
         self.transport_type = transport.into();
@@ -339,7 +370,7 @@ impl McpRegistry {
             .collect();
 
         // Sort by score descending
-        scored.sort_by(|a, b| b.1.cmp(&a.1));
+        scored.sort_by_key(|s| std::cmp::Reverse(s.1));
 
         scored
             .into_iter()
@@ -358,6 +389,8 @@ impl McpRegistry {
             "stdio" => McpTransport::Stdio {
                 command: server.connection_uri.clone(),
                 args: server.args.clone(),
+                env: server.env.clone(),
+                launch: Box::new(server.launch.clone()),
             },
             "sse" => McpTransport::Sse {
                 url: server.connection_uri.clone(),
@@ -373,7 +406,12 @@ impl McpRegistry {
             }
         };
 
-        self.adapter.connect(&server.id, transport).await
+        let policy = server.call_policy.clone();
+        let id = server.id.clone();
+        drop(server);
+
+        self.adapter.connect(&id, transport).await?;
+        self.adapter.set_call_policy(&id, policy).await
     }
 
     /// Get the underlying MCP adapter.
@@ -27,6 +27,7 @@ impl FetchTool {
     pub fn new(policy: Arc<RwLock<NetworkPolicy>>, safety: SafetyConfig) -> Self {
         let client = reqwest::Client::builder()
             .redirect(reqwest::redirect::Policy::none())
+            .tls_info(true)
             .build()
             .unwrap_or_else(|_| reqwest::Client::new());
 
@@ -177,6 +178,7 @@ impl DownloadTool {
     ) -> Self {
         let client = reqwest::Client::builder()
             .redirect(reqwest::redirect::Policy::none())
+            .tls_info(true)
             .build()
             .unwrap_or_else(|_| reqwest::Client::new());
 
@@ -203,13 +205,19 @@ impl Tool for DownloadTool {
     }
 
     fn description(&self) -> &str {
-        "Download a file from a URL and save it to the sandbox workspace."
+        "Download a file from a URL and save it to the sandbox workspace. The file extension must match its content; executables need approval."
     }
 
     fn parameters(&self) -> serde_json::Value {
         serde_json::to_value(schemars::schema_for!(DownloadArgs)).unwrap()
     }
 
+    fn requires_approval_for(&self, args: &serde_json::Value) -> bool {
+        args.get("destination_path")
+            .and_then(|p| p.as_str())
+            .is_some_and(crate::quarantine::is_executable_path)
+    }
+
     async fn execute(&self, args: serde_json::Value) -> Result<ToolOutput> {
         let args: DownloadArgs = serde_json::from_value(args)
             .map_err(|e| Error::tool_execution(format!("Invalid arguments: {}", e)))?;
@@ -240,6 +248,11 @@ impl Tool for DownloadTool {
                 resp.status()
             )));
         }
+        let content_type = resp
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
 
         let mut stream = resp.bytes_stream();
         let mut buffer = Vec::new();
@@ -259,6 +272,15 @@ impl Tool for DownloadTool {
             buffer.extend_from_slice(&chunk);
         }
 
+        let report = crate::quarantine::inspect(
+            &self.safety.quarantine,
+            &args.destination_path,
+            content_type.as_deref(),
+            &buffer,
+        )
+        .await
+        .map_err(|e| Error::tool_execution(e.to_string()))?;
+
         let sandbox_id: multi_agent_sandbox::SandboxId = self
             .sandbox_manager
             .get_or_create()
@@ -285,7 +307,8 @@ impl Tool for DownloadTool {
             "url": args.url,
             "destination": args.destination_path,
             "bytes": buffer.len(),
-            "body_hash": body_hash
+            "body_hash": body_hash,
+            "quarantine": report
         })))
     }
 }
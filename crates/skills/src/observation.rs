@@ -0,0 +1,344 @@
+//! Tool-aware observation shortening.
+//!
+//! Tool output is fed back to the model verbatim, so a long test run or a
+//! fetched web page can fill the context window in one call.
+//! [`ObservationToolRegistry`] wraps a registry and, when an output exceeds
+//! `max_chars`, replaces it with a summary from the tool's
+//! [`ObservationProcessor`] (head and tail otherwise). The full output is
+//! stored as an artifact the agent can page through with `read_artifact`.
+
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use dashmap::DashMap;
+use regex::Regex;
+use serde_json::Value;
+
+use multi_agent_core::traits::{ArtifactStore, Tool, ToolRegistry};
+use multi_agent_core::types::{ToolDefinition, ToolOutput, ToolRiskLevel};
+use multi_agent_core::Result;
+
+/// Turns a long tool output into a shorter observation.
+pub trait ObservationProcessor: Send + Sync {
+    /// Summarize `content` in about `max_chars`; `None` when the processor
+    /// does not recognise the output.
+    fn process(&self, content: &str, max_chars: usize) -> Option<String>;
+}
+
+/// Keep the first `max` characters of `s`.
+fn clip(s: &str, max: usize) -> &str {
+    match s.char_indices().nth(max) {
+        Some((idx, _)) => &s[..idx],
+        None => s,
+    }
+}
+
+/// Keeps the beginning and the end, where errors and summaries usually are.
+pub struct HeadTailProcessor;
+
+impl ObservationProcessor for HeadTailProcessor {
+    fn process(&self, content: &str, max_chars: usize) -> Option<String> {
+        let total = content.chars().count();
+        if total <= max_chars {
+            return Some(content.to_string());
+        }
+        let head = max_chars * 2 / 3;
+        let tail = max_chars - head;
+        let tail_start = content
+            .char_indices()
+            .nth(total - tail)
+            .map_or(content.len(), |(idx, _)| idx);
+        Some(format!(
+            "{}\n... [{} chars omitted] ...\n{}",
+            clip(content, head),
+            total - head - tail,
+            &content[tail_start..]
+        ))
+    }
+}
+
+/// Reduces test runner output (cargo, pytest, jest, go) to its failures.
+pub struct TestFailureSummarizer;
+
+fn test_summary_line() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?i)^(test result:|=+ .*\b(passed|failed)\b|tests?:\s+\d+|(ok|fail)\s+\S+\s+[\d.]+s$)")
+            .unwrap()
+    })
+}
+
+fn test_failure_line() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(FAILED|FAIL\b|panicked at|^failures:|^error|Error:|assert|Traceback|^E\s)")
+            .unwrap()
+    })
+}
+
+impl ObservationProcessor for TestFailureSummarizer {
+    fn process(&self, content: &str, max_chars: usize) -> Option<String> {
+        let lines: Vec<&str> = content.lines().map(str::trim_end).collect();
+        let summaries: Vec<&str> = lines
+            .iter()
+            .copied()
+            .filter(|l| test_summary_line().is_match(l.trim_start()))
+            .collect();
+        if summaries.is_empty() {
+            return None;
+        }
+
+        let mut out = String::from("Test run summary:\n");
+        for line in &summaries {
+            out.push_str(line);
+            out.push('\n');
+        }
+        let failures: Vec<&str> = lines
+            .iter()
+            .copied()
+            .filter(|l| test_failure_line().is_match(l.trim_start()) && !summaries.contains(l))
+            .collect();
+        if failures.is_empty() {
+            out.push_str("No failures reported.");
+            return Some(out);
+        }
+        out.push_str(&format!("Failure lines ({}):\n", failures.len()));
+        for (shown, line) in failures.iter().enumerate() {
+            if out.chars().count() + line.chars().count() > max_chars {
+                out.push_str(&format!("... {} more", failures.len() - shown));
+                break;
+            }
+            out.push_str(line);
+            out.push('\n');
+        }
+        Some(out)
+    }
+}
+
+/// Extracts the readable text of an HTML page.
+pub struct ReadableTextExtractor;
+
+fn html_noise() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?is)<!--.*?-->|<(script|style|noscript|svg|head|nav|footer)\b.*?</(script|style|noscript|svg|head|nav|footer)>")
+            .unwrap()
+    })
+}
+
+fn html_block_tag() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?i)</?(p|div|br|li|tr|h[1-6]|section|article|blockquote|pre)\b[^>]*>")
+            .unwrap()
+    })
+}
+
+fn html_tag() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"<[^>]*>").unwrap())
+}
+
+impl ObservationProcessor for ReadableTextExtractor {
+    fn process(&self, content: &str, max_chars: usize) -> Option<String> {
+        let lower = clip(content, 2_000).to_lowercase();
+        if !["<html", "<body", "<!doctype html"]
+            .iter()
+            .any(|marker| lower.contains(marker))
+        {
+            return None;
+        }
+        let text = html_noise().replace_all(content, "");
+        let text = html_block_tag().replace_all(&text, "\n");
+        let text = html_tag().replace_all(&text, "");
+        let text = text
+            .replace("&nbsp;", " ")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&amp;", "&");
+        let readable = text
+            .lines()
+            .map(|l| l.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|l| !l.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        HeadTailProcessor.process(&readable, max_chars)
+    }
+}
+
+/// Registry decorator that shortens long observations.
+pub struct ObservationToolRegistry {
+    inner: Arc<dyn ToolRegistry>,
+    store: Arc<dyn ArtifactStore>,
+    max_chars: usize,
+    processors: DashMap<String, Arc<dyn ObservationProcessor>>,
+}
+
+impl ObservationToolRegistry {
+    /// Shorten outputs above `max_chars`; 0 passes every output through.
+    pub fn new(
+        inner: Arc<dyn ToolRegistry>,
+        store: Arc<dyn ArtifactStore>,
+        max_chars: usize,
+    ) -> Self {
+        Self {
+            inner,
+            store,
+            max_chars,
+            processors: DashMap::new(),
+        }
+    }
+
+    /// Use `processor` for the outputs of `tool`.
+    pub fn with_processor(self, tool: &str, processor: Arc<dyn ObservationProcessor>) -> Self {
+        self.processors.insert(tool.to_string(), processor);
+        self
+    }
+
+    /// Register a tool together with the processor for its outputs.
+    pub async fn register_with_processor(
+        &self,
+        tool: Box<dyn Tool>,
+        processor: Arc<dyn ObservationProcessor>,
+    ) -> Result<()> {
+        let name = tool.name().to_string();
+        self.inner.register(tool).await?;
+        self.processors.insert(name, processor);
+        Ok(())
+    }
+
+    async fn shorten(&self, name: &str, mut output: ToolOutput) -> ToolOutput {
+        let total = output.content.chars().count();
+        if self.max_chars == 0 || total <= self.max_chars {
+            return output;
+        }
+        let processor = self.processors.get(name).map(|p| p.value().clone());
+        let summary = processor
+            .and_then(|p| p.process(&output.content, self.max_chars))
+            .or_else(|| HeadTailProcessor.process(&output.content, self.max_chars))
+            .unwrap_or_default();
+
+        let full = Bytes::from(std::mem::take(&mut output.content));
+        let note = match self.store.save_with_type(full, "text/plain").await {
+            Ok(id) => {
+                let note = format!(
+                    "[Output shortened from {} chars. Full output: read_artifact ref_id={}]",
+                    total, id
+                );
+                output.created_refs.push(id);
+                note
+            }
+            Err(e) => {
+                tracing::warn!(tool = %name, error = %e, "Failed to store full tool output");
+                format!(
+                    "[Output shortened from {} chars; the full output could not be stored]",
+                    total
+                )
+            }
+        };
+        metrics::counter!("observations_shortened_total", "tool" => name.to_string()).increment(1);
+        output.content = format!("{}\n\n{}", summary.trim_end(), note);
+        output
+    }
+}
+
+#[async_trait]
+impl ToolRegistry for ObservationToolRegistry {
+    async fn register(&self, tool: Box<dyn Tool>) -> Result<()> {
+        self.inner.register(tool).await
+    }
+
+    async fn get(&self, name: &str) -> Result<Option<Box<dyn Tool>>> {
+        self.inner.get(name).await
+    }
+
+    async fn list(&self) -> Result<Vec<ToolDefinition>> {
+        self.inner.list().await
+    }
+
+    async fn execute(&self, name: &str, args: Value) -> Result<ToolOutput> {
+        let output = self.inner.execute(name, args).await?;
+        Ok(self.shorten(name, output).await)
+    }
+
+    async fn get_risk_level(&self, name: &str) -> ToolRiskLevel {
+        self.inner.get_risk_level(name).await
+    }
+
+    async fn requires_approval(&self, name: &str) -> bool {
+        self.inner.requires_approval(name).await
+    }
+
+    async fn requires_approval_for(&self, name: &str, args: &Value) -> bool {
+        self.inner.requires_approval_for(name, args).await
+    }
+
+    async fn awaits_human_input(&self, name: &str) -> bool {
+        self.inner.awaits_human_input(name).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DefaultToolRegistry, EchoTool};
+    use multi_agent_store::InMemoryStore;
+
+    #[test]
+    fn test_processors() {
+        let mut run = String::from("running 40 tests\n");
+        for i in 0..40 {
+            run.push_str(&format!("test tests::case_{} ... ok\n", i));
+        }
+        run.push_str("test tests::broken ... FAILED\n");
+        run.push_str("thread 'tests::broken' panicked at src/lib.rs:10:5:\n");
+        run.push_str("test result: FAILED. 40 passed; 1 failed; 0 ignored\n");
+        let summary = TestFailureSummarizer.process(&run, 500).unwrap();
+        assert!(summary.contains("test result: FAILED"));
+        assert!(summary.contains("tests::broken ... FAILED"));
+        assert!(summary.contains("panicked at"));
+        assert!(!summary.contains("case_3"));
+        assert!(TestFailureSummarizer.process("hello", 500).is_none());
+
+        let page = "<!DOCTYPE html><html><head><title>x</title><script>track()</script></head>\
+                    <body><nav>Menu</nav><h1>Title</h1><p>Fish &amp; chips</p></body></html>";
+        let text = ReadableTextExtractor.process(page, 500).unwrap();
+        assert_eq!(text, "Title\nFish & chips");
+        assert!(ReadableTextExtractor.process("{\"a\": 1}", 500).is_none());
+
+        let cut = HeadTailProcessor.process(&"x".repeat(100), 30).unwrap();
+        assert!(cut.starts_with(&"x".repeat(20)));
+        assert!(cut.contains("[70 chars omitted]"));
+    }
+
+    #[tokio::test]
+    async fn test_long_output_is_stored_as_artifact() {
+        let inner = Arc::new(DefaultToolRegistry::new());
+        let store = Arc::new(InMemoryStore::new());
+        let registry = ObservationToolRegistry::new(inner, store.clone(), 50);
+        registry
+            .register_with_processor(Box::new(EchoTool), Arc::new(HeadTailProcessor))
+            .await
+            .unwrap();
+
+        let short = registry
+            .execute("echo", serde_json::json!({"message": "hi"}))
+            .await
+            .unwrap();
+        assert!(short.created_refs.is_empty());
+
+        let long = "y".repeat(500);
+        let output = registry
+            .execute("echo", serde_json::json!({"message": long}))
+            .await
+            .unwrap();
+        assert_eq!(output.created_refs.len(), 1);
+        assert!(output.content.contains("read_artifact ref_id="));
+        assert!(output.content.len() < 200);
+        let full = store.load(&output.created_refs[0]).await.unwrap().unwrap();
+        assert!(String::from_utf8_lossy(&full).contains(&long));
+    }
+}
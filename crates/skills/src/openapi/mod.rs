@@ -0,0 +1,534 @@
+//! REST API tools generated from OpenAPI specs.
+//!
+//! Each imported spec gets a namespace; every operation becomes a tool named
+//! `<namespace>__<operation>`. Arguments are validated against the spec,
+//! credentials come from the [`SecretsManager`] through an [`AuthProfile`],
+//! and every request goes through the network policy.
+
+use async_trait::async_trait;
+use base64::Engine;
+use dashmap::DashMap;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use multi_agent_core::{
+    config::SafetyConfig,
+    traits::{Tool, ToolRegistry},
+    types::{ToolDefinition, ToolOutput, ToolRiskLevel},
+    Error, Result,
+};
+use multi_agent_governance::network::{NetworkDecision, NetworkPolicy};
+use multi_agent_governance::SecretsManager;
+
+pub mod spec;
+
+pub use spec::{normalize_namespace, parse_document, Operation, ParsedSpec, NAMESPACE_SEPARATOR};
+
+/// Characters of response body returned inline to the agent.
+const MAX_RESPONSE_CHARS: usize = 16_000;
+
+/// Where an API key is attached.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiKeyLocation {
+    #[default]
+    Header,
+    Query,
+}
+
+/// How requests to an imported API authenticate. Secrets are referenced by
+/// their [`SecretsManager`] key, never stored inline.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuthProfile {
+    #[default]
+    None,
+    /// `Authorization: Bearer <secret>`.
+    Bearer { secret_id: String },
+    /// API key in a header or query parameter called `name`.
+    ApiKey {
+        secret_id: String,
+        name: String,
+        #[serde(default)]
+        location: ApiKeyLocation,
+    },
+    /// HTTP basic auth with the secret as password.
+    Basic { username: String, secret_id: String },
+}
+
+/// An imported API.
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenApiSpec {
+    pub namespace: String,
+    pub auth: AuthProfile,
+    #[serde(flatten)]
+    pub spec: ParsedSpec,
+}
+
+impl OpenApiSpec {
+    /// Parse a JSON or YAML OpenAPI 3 document into an importable API.
+    pub fn parse(namespace: &str, source: &str, server_url: Option<&str>) -> Result<Self> {
+        Self::from_document(namespace, &parse_document(source)?, server_url)
+    }
+
+    /// Build from an already-decoded document.
+    pub fn from_document(namespace: &str, doc: &Value, server_url: Option<&str>) -> Result<Self> {
+        let namespace = normalize_namespace(namespace)?;
+        let spec = ParsedSpec::from_document(&namespace, doc, server_url)?;
+        Ok(Self {
+            namespace,
+            auth: AuthProfile::None,
+            spec,
+        })
+    }
+
+    pub fn with_auth(mut self, auth: AuthProfile) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Names of the generated tools.
+    pub fn tool_names(&self) -> Vec<String> {
+        self.spec
+            .operations
+            .iter()
+            .map(|op| op.tool_name.clone())
+            .collect()
+    }
+}
+
+/// What generated tools share: secrets, network policy and the HTTP client.
+struct ExecContext {
+    secrets: Arc<dyn SecretsManager>,
+    policy: Arc<RwLock<NetworkPolicy>>,
+    safety: SafetyConfig,
+    client: reqwest::Client,
+}
+
+/// A tool for one API operation.
+pub struct OpenApiTool {
+    api: Arc<OpenApiSpec>,
+    index: usize,
+    ctx: Arc<ExecContext>,
+}
+
+impl OpenApiTool {
+    fn operation(&self) -> &Operation {
+        &self.api.spec.operations[self.index]
+    }
+
+    async fn authenticate(
+        &self,
+        url: &mut url::Url,
+        headers: &mut reqwest::header::HeaderMap,
+    ) -> Result<()> {
+        let secret = |id: &str| {
+            let secrets = self.ctx.secrets.clone();
+            let id = id.to_string();
+            async move {
+                secrets.retrieve(&id).await?.ok_or_else(|| {
+                    Error::tool_execution(format!("Secret '{}' for API credentials not found", id))
+                })
+            }
+        };
+        let header = match &self.api.auth {
+            AuthProfile::None => None,
+            AuthProfile::Bearer { secret_id } => Some((
+                "authorization".to_string(),
+                format!("Bearer {}", secret(secret_id).await?),
+            )),
+            AuthProfile::Basic {
+                username,
+                secret_id,
+            } => {
+                let raw = format!("{}:{}", username, secret(secret_id).await?);
+                Some((
+                    "authorization".to_string(),
+                    format!(
+                        "Basic {}",
+                        base64::engine::general_purpose::STANDARD.encode(raw)
+                    ),
+                ))
+            }
+            AuthProfile::ApiKey {
+                secret_id,
+                name,
+                location,
+            } => {
+                let key = secret(secret_id).await?;
+                match location {
+                    ApiKeyLocation::Header => Some((name.clone(), key)),
+                    ApiKeyLocation::Query => {
+                        url.query_pairs_mut().append_pair(name, &key);
+                        None
+                    }
+                }
+            }
+        };
+        if let Some((name, value)) = header {
+            insert_header(headers, &name, &value)?;
+        }
+        Ok(())
+    }
+}
+
+fn insert_header(headers: &mut reqwest::header::HeaderMap, name: &str, value: &str) -> Result<()> {
+    let name = reqwest::header::HeaderName::from_bytes(name.as_bytes())
+        .map_err(|_| Error::invalid_request(format!("invalid header name '{}'", name)))?;
+    let value = reqwest::header::HeaderValue::from_str(value)
+        .map_err(|_| Error::invalid_request(format!("invalid value for header '{}'", name)))?;
+    headers.insert(name, value);
+    Ok(())
+}
+
+#[async_trait]
+impl Tool for OpenApiTool {
+    fn name(&self) -> &str {
+        &self.operation().tool_name
+    }
+
+    fn description(&self) -> &str {
+        &self.operation().description
+    }
+
+    fn parameters(&self) -> Value {
+        self.operation().parameters_schema()
+    }
+
+    fn risk_level(&self) -> ToolRiskLevel {
+        match self.operation().method.as_str() {
+            "GET" | "HEAD" | "OPTIONS" => ToolRiskLevel::Low,
+            "DELETE" => ToolRiskLevel::High,
+            _ => ToolRiskLevel::Medium,
+        }
+    }
+
+    async fn execute(&self, args: Value) -> Result<ToolOutput> {
+        let op = self.operation();
+        let prepared = op.prepare(&self.api.spec.server_url, &args)?;
+        let method = prepared.method.parse::<reqwest::Method>().map_err(|_| {
+            Error::tool_execution(format!("Invalid HTTP method: {}", prepared.method))
+        })?;
+
+        let mut url = prepared.url;
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (name, value) in &prepared.headers {
+            insert_header(&mut headers, name, value)?;
+        }
+        self.authenticate(&mut url, &mut headers).await?;
+        let body = match &prepared.body {
+            Some(body) => {
+                insert_header(&mut headers, "content-type", "application/json")?;
+                Some(body.to_string())
+            }
+            None => None,
+        };
+        insert_header(&mut headers, "accept", "application/json")?;
+
+        let policy = self.ctx.policy.read().await.clone();
+        let response = multi_agent_governance::network::fetch_with_policy(
+            &self.ctx.client,
+            &policy,
+            &self.ctx.safety,
+            method,
+            url,
+            Some(&headers),
+            body.as_ref(),
+        )
+        .await?;
+
+        let status = response.status();
+        let mut stream = response.bytes_stream();
+        let mut buffer = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            let chunk =
+                chunk.map_err(|e| Error::tool_execution(format!("Download failed: {}", e)))?;
+            buffer.extend_from_slice(&chunk);
+            if buffer.len() as u64 > self.ctx.safety.max_download_size_bytes {
+                return Err(Error::tool_execution(format!(
+                    "Response size exceeded limit ({} bytes)",
+                    self.ctx.safety.max_download_size_bytes
+                )));
+            }
+        }
+        let text = String::from_utf8_lossy(&buffer);
+        let mut content: String = text.chars().take(MAX_RESPONSE_CHARS).collect();
+        if content.len() < text.len() {
+            content.push_str("\n... [truncated]");
+        }
+
+        if !status.is_success() {
+            return Ok(ToolOutput::error(format!(
+                "{} {} returned {}: {}",
+                op.method, op.path, status, content
+            )));
+        }
+        let mut output = ToolOutput::text(content);
+        if let Ok(data) = serde_json::from_slice::<Value>(&buffer) {
+            output = output.with_data(json!({ "status": status.as_u16(), "body": data }));
+        }
+        Ok(output)
+    }
+}
+
+/// Registry of imported APIs, exposing their operations as tools.
+pub struct OpenApiRegistry {
+    apis: DashMap<String, Arc<OpenApiSpec>>,
+    ctx: Arc<ExecContext>,
+}
+
+impl OpenApiRegistry {
+    pub fn new(
+        secrets: Arc<dyn SecretsManager>,
+        policy: Arc<RwLock<NetworkPolicy>>,
+        safety: SafetyConfig,
+    ) -> Self {
+        let client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .tls_info(true)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            apis: DashMap::new(),
+            ctx: Arc::new(ExecContext {
+                secrets,
+                policy,
+                safety,
+                client,
+            }),
+        }
+    }
+
+    /// Import (or replace) an API. The server URL must pass the network policy.
+    pub async fn import(&self, api: OpenApiSpec) -> Result<Arc<OpenApiSpec>> {
+        let decision = self
+            .ctx
+            .policy
+            .read()
+            .await
+            .check(&api.spec.server_url)
+            .map_err(|e| Error::governance(e.to_string()))?;
+        if let NetworkDecision::Denied(reason) = decision {
+            return Err(Error::governance(format!(
+                "Network policy denies server {}: {}",
+                api.spec.server_url, reason
+            )));
+        }
+        tracing::info!(
+            namespace = %api.namespace,
+            operations = api.spec.operations.len(),
+            "Importing OpenAPI spec"
+        );
+        let api = Arc::new(api);
+        self.apis.insert(api.namespace.clone(), api.clone());
+        Ok(api)
+    }
+
+    /// Remove an imported API.
+    pub fn remove(&self, namespace: &str) -> Option<Arc<OpenApiSpec>> {
+        self.apis.remove(namespace).map(|(_, api)| api)
+    }
+
+    /// All imported APIs.
+    pub fn apis(&self) -> Vec<Arc<OpenApiSpec>> {
+        let mut apis: Vec<_> = self.apis.iter().map(|e| e.value().clone()).collect();
+        apis.sort_by(|a, b| a.namespace.cmp(&b.namespace));
+        apis
+    }
+
+    fn tool(&self, name: &str) -> Option<OpenApiTool> {
+        let (namespace, _) = name.split_once(NAMESPACE_SEPARATOR)?;
+        let api = self.apis.get(namespace)?.value().clone();
+        let index = api
+            .spec
+            .operations
+            .iter()
+            .position(|op| op.tool_name == name)?;
+        Some(OpenApiTool {
+            api,
+            index,
+            ctx: self.ctx.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl ToolRegistry for OpenApiRegistry {
+    async fn register(&self, _tool: Box<dyn Tool>) -> Result<()> {
+        Err(Error::internal(
+            "Cannot register tools directly to OpenApiRegistry. Import a spec instead.",
+        ))
+    }
+
+    async fn get(&self, name: &str) -> Result<Option<Box<dyn Tool>>> {
+        Ok(self.tool(name).map(|t| Box::new(t) as Box<dyn Tool>))
+    }
+
+    async fn list(&self) -> Result<Vec<ToolDefinition>> {
+        Ok(self
+            .apis()
+            .iter()
+            .flat_map(|api| api.spec.operations.iter())
+            .map(|op| ToolDefinition {
+                name: op.tool_name.clone(),
+                description: op.description.clone(),
+                parameters: op.parameters_schema(),
+                supports_streaming: false,
+            })
+            .collect())
+    }
+
+    async fn execute(&self, name: &str, args: Value) -> Result<ToolOutput> {
+        match self.tool(name) {
+            Some(tool) => tool.execute(args).await,
+            None => Err(Error::tool_not_found(name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multi_agent_governance::AesGcmSecretsManager;
+
+    const SPEC: &str = r#"{
+        "openapi": "3.0.0",
+        "info": {"title": "CRM", "version": "2"},
+        "servers": [{"url": "https://api.crm.example.com"}],
+        "paths": {
+            "/contacts": {
+                "get": {"operationId": "listContacts", "parameters": [{"name": "q", "in": "query", "schema": {"type": "string"}}]},
+                "post": {"operationId": "createContact", "requestBody": {"content": {"application/json": {"schema": {"type": "object"}}}}}
+            },
+            "/contacts/{id}": {"delete": {"operationId": "deleteContact", "parameters": [{"name": "id", "in": "path", "required": true}]}}
+        }
+    }"#;
+
+    fn registry(allow: &[&str]) -> OpenApiRegistry {
+        let policy = NetworkPolicy::new(
+            allow.iter().map(|s| s.to_string()).collect(),
+            vec![],
+            vec![443],
+        );
+        OpenApiRegistry::new(
+            Arc::new(AesGcmSecretsManager::new(None)),
+            Arc::new(RwLock::new(policy)),
+            SafetyConfig::default(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_import_exposes_namespaced_tools() {
+        let registry = registry(&["*.crm.example.com"]);
+        let api = OpenApiSpec::parse("crm", SPEC, None).unwrap();
+        registry.import(api).await.unwrap();
+
+        let defs = registry.list().await.unwrap();
+        let names: Vec<&str> = defs.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "crm__list_contacts",
+                "crm__create_contact",
+                "crm__delete_contact"
+            ]
+        );
+
+        let risks = [
+            ToolRiskLevel::Low,
+            ToolRiskLevel::Medium,
+            ToolRiskLevel::High,
+        ];
+        for (name, risk) in names.iter().zip(risks) {
+            let tool = registry.get(name).await.unwrap().unwrap();
+            assert_eq!(tool.risk_level(), risk, "{}", name);
+        }
+        assert!(registry.get("crm__missing").await.unwrap().is_none());
+
+        // Validation runs before any network access.
+        let err = registry
+            .execute("crm__delete_contact", json!({}))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("args.id is required"));
+
+        registry.remove("crm");
+        assert!(registry.list().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_import_checks_network_policy() {
+        let registry = registry(&["other.example.com"]);
+        let api = OpenApiSpec::parse("crm", SPEC, None).unwrap();
+        let err = registry.import(api).await.unwrap_err();
+        assert!(err.to_string().contains("not in the allowlist"));
+        assert!(registry.apis().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_auth_profiles_apply_secrets() {
+        let registry = registry(&["*.crm.example.com"]);
+        registry
+            .ctx
+            .secrets
+            .store("openapi:crm", "tok")
+            .await
+            .unwrap();
+
+        let cases = [
+            (
+                AuthProfile::Bearer {
+                    secret_id: "openapi:crm".into(),
+                },
+                Some(("authorization", "Bearer tok")),
+                None,
+            ),
+            (
+                AuthProfile::Basic {
+                    username: "u".into(),
+                    secret_id: "openapi:crm".into(),
+                },
+                Some(("authorization", "Basic dTp0b2s=")),
+                None,
+            ),
+            (
+                AuthProfile::ApiKey {
+                    secret_id: "openapi:crm".into(),
+                    name: "api_key".into(),
+                    location: ApiKeyLocation::Query,
+                },
+                None,
+                Some("api_key=tok"),
+            ),
+        ];
+        for (auth, header, query) in cases {
+            let api = OpenApiSpec::parse("crm", SPEC, None)
+                .unwrap()
+                .with_auth(auth);
+            let api = registry.import(api).await.unwrap();
+            let tool = registry.tool(&api.tool_names()[0]).unwrap();
+
+            let mut url = url::Url::parse("https://api.crm.example.com/contacts").unwrap();
+            let mut headers = reqwest::header::HeaderMap::new();
+            tool.authenticate(&mut url, &mut headers).await.unwrap();
+            if let Some((name, value)) = header {
+                assert_eq!(headers.get(name).unwrap(), value);
+            }
+            assert_eq!(url.query(), query);
+        }
+
+        let api = OpenApiSpec::parse("crm", SPEC, None)
+            .unwrap()
+            .with_auth(AuthProfile::Bearer {
+                secret_id: "missing".into(),
+            });
+        registry.import(api).await.unwrap();
+        let tool = registry.tool("crm__list_contacts").unwrap();
+        let mut url = url::Url::parse("https://api.crm.example.com").unwrap();
+        let mut headers = reqwest::header::HeaderMap::new();
+        assert!(tool.authenticate(&mut url, &mut headers).await.is_err());
+    }
+}
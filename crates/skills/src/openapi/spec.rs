@@ -0,0 +1,626 @@
+//! OpenAPI 3.x parsing, argument validation and request construction.
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use multi_agent_core::{Error, Result};
+
+/// Separator between the API namespace and the operation in tool names.
+pub const NAMESPACE_SEPARATOR: &str = "__";
+const MAX_TOOL_NAME_LEN: usize = 64;
+const MAX_NAMESPACE_LEN: usize = 24;
+/// Depth at which `$ref` expansion stops (guards against recursive schemas).
+const MAX_REF_DEPTH: usize = 8;
+const METHODS: [&str; 7] = ["get", "put", "post", "delete", "patch", "head", "options"];
+
+/// Where an operation parameter is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamLocation {
+    Path,
+    Query,
+    Header,
+}
+
+/// An operation parameter.
+#[derive(Debug, Clone, Serialize)]
+pub struct ParamSpec {
+    pub name: String,
+    pub location: ParamLocation,
+    pub required: bool,
+    pub schema: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// A single API operation, exposed as one tool.
+#[derive(Debug, Clone, Serialize)]
+pub struct Operation {
+    pub tool_name: String,
+    pub method: String,
+    pub path: String,
+    pub description: String,
+    pub params: Vec<ParamSpec>,
+    /// JSON request body schema, if the operation takes one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+    pub body_required: bool,
+}
+
+/// A parsed OpenAPI document.
+#[derive(Debug, Clone, Serialize)]
+pub struct ParsedSpec {
+    pub title: String,
+    pub version: String,
+    pub server_url: String,
+    pub operations: Vec<Operation>,
+    /// Operations that could not be exposed, with the reason.
+    pub skipped: Vec<String>,
+}
+
+/// A request ready to send, before authentication is applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedRequest {
+    pub method: String,
+    pub url: url::Url,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Value>,
+}
+
+/// Validate and normalise an API namespace (lowercase letters, digits, `_`).
+pub fn normalize_namespace(namespace: &str) -> Result<String> {
+    let ns = namespace.trim().to_ascii_lowercase();
+    let valid = !ns.is_empty()
+        && ns.len() <= MAX_NAMESPACE_LEN
+        && ns.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !ns.contains(NAMESPACE_SEPARATOR);
+    if !valid {
+        return Err(Error::invalid_request(format!(
+            "namespace must be 1-{} characters of [a-z0-9_] without '__', got '{}'",
+            MAX_NAMESPACE_LEN, namespace
+        )));
+    }
+    Ok(ns)
+}
+
+/// Parse a JSON or YAML OpenAPI 3 document.
+pub fn parse_document(source: &str) -> Result<Value> {
+    serde_json::from_str(source)
+        .or_else(|_| serde_yaml::from_str(source))
+        .map_err(|e| Error::invalid_request(format!("spec is neither valid JSON nor YAML: {}", e)))
+}
+
+impl ParsedSpec {
+    /// Extract operations from an OpenAPI 3 document.
+    ///
+    /// `server_override` replaces `servers[0].url` and is required when the
+    /// spec only declares a relative server.
+    pub fn from_document(
+        namespace: &str,
+        doc: &Value,
+        server_override: Option<&str>,
+    ) -> Result<Self> {
+        let version = doc.get("openapi").and_then(|v| v.as_str()).unwrap_or("");
+        if !version.starts_with('3') {
+            return Err(Error::invalid_request(
+                "only OpenAPI 3.x documents are supported",
+            ));
+        }
+        let server_url = server_url(doc, server_override)?;
+        let paths = doc
+            .get("paths")
+            .and_then(|p| p.as_object())
+            .ok_or_else(|| Error::invalid_request("spec has no paths"))?;
+
+        let mut operations: Vec<Operation> = Vec::new();
+        let mut skipped = Vec::new();
+        for (path, item) in paths {
+            let item = resolve(doc, item, 0);
+            let shared = item.get("parameters").cloned().unwrap_or(json!([]));
+            for method in METHODS {
+                let Some(op) = item.get(method) else {
+                    continue;
+                };
+                match operation(doc, namespace, method, path, op, &shared) {
+                    Ok(mut op) => {
+                        op.tool_name = unique_name(&op.tool_name, &operations);
+                        operations.push(op);
+                    }
+                    Err(reason) => {
+                        skipped.push(format!("{} {}: {}", method.to_uppercase(), path, reason))
+                    }
+                }
+            }
+        }
+        if operations.is_empty() {
+            return Err(Error::invalid_request("spec defines no usable operations"));
+        }
+
+        Ok(Self {
+            title: doc["info"]["title"]
+                .as_str()
+                .unwrap_or(namespace)
+                .to_string(),
+            version: doc["info"]["version"].as_str().unwrap_or("").to_string(),
+            server_url,
+            operations,
+            skipped,
+        })
+    }
+}
+
+fn server_url(doc: &Value, server_override: Option<&str>) -> Result<String> {
+    let raw = match server_override {
+        Some(url) => url.to_string(),
+        None => {
+            let server = &doc["servers"][0];
+            let mut url = server["url"].as_str().unwrap_or_default().to_string();
+            // Substitute server variables with their defaults.
+            if let Some(vars) = server["variables"].as_object() {
+                for (name, var) in vars {
+                    if let Some(default) = var["default"].as_str() {
+                        url = url.replace(&format!("{{{}}}", name), default);
+                    }
+                }
+            }
+            url
+        }
+    };
+    let parsed = url::Url::parse(&raw).map_err(|_| {
+        Error::invalid_request(format!(
+            "server URL '{}' is not absolute; pass server_url when importing",
+            raw
+        ))
+    })?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(Error::invalid_request(format!(
+            "server URL must be http(s), got '{}'",
+            raw
+        )));
+    }
+    Ok(raw.trim_end_matches('/').to_string())
+}
+
+/// Expand local `$ref`s (`#/components/...`) up to a fixed depth.
+fn resolve(doc: &Value, value: &Value, depth: usize) -> Value {
+    if depth >= MAX_REF_DEPTH {
+        return json!({});
+    }
+    match value {
+        Value::Object(map) => {
+            if let Some(reference) = map.get("$ref").and_then(|r| r.as_str()) {
+                return match reference.strip_prefix('#').and_then(|p| doc.pointer(p)) {
+                    Some(target) => resolve(doc, target, depth + 1),
+                    None => json!({}),
+                };
+            }
+            Value::Object(
+                map.iter()
+                    .map(|(k, v)| (k.clone(), resolve(doc, v, depth)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.iter().map(|v| resolve(doc, v, depth)).collect()),
+        other => other.clone(),
+    }
+}
+
+fn operation(
+    doc: &Value,
+    namespace: &str,
+    method: &str,
+    path: &str,
+    op: &Value,
+    shared: &Value,
+) -> std::result::Result<Operation, String> {
+    let op = resolve(doc, op, 0);
+    let mut params: Vec<ParamSpec> = Vec::new();
+    // Operation-level parameters override path-level ones with the same name and location.
+    let declared = resolve(doc, shared, 0)
+        .as_array()
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .chain(op["parameters"].as_array().cloned().unwrap_or_default());
+    for p in declared {
+        let name = p["name"].as_str().unwrap_or_default().to_string();
+        let location = match p["in"].as_str() {
+            Some("path") => ParamLocation::Path,
+            Some("query") => ParamLocation::Query,
+            Some("header") => ParamLocation::Header,
+            Some("cookie") => {
+                if p["required"].as_bool() == Some(true) {
+                    return Err(format!(
+                        "required cookie parameter '{}' is unsupported",
+                        name
+                    ));
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        if name.is_empty() || name == "body" {
+            return Err(format!("unsupported parameter name '{}'", name));
+        }
+        params.retain(|existing| !(existing.name == name && existing.location == location));
+        params.push(ParamSpec {
+            required: location == ParamLocation::Path || p["required"].as_bool() == Some(true),
+            schema: p
+                .get("schema")
+                .cloned()
+                .unwrap_or(json!({"type": "string"})),
+            description: p["description"].as_str().map(String::from),
+            name,
+            location,
+        });
+    }
+
+    let (body, body_required) = match op.get("requestBody") {
+        None => (None, false),
+        Some(request_body) => {
+            let content = request_body["content"]
+                .as_object()
+                .cloned()
+                .unwrap_or_default();
+            let json_schema = content
+                .iter()
+                .find(|(media, _)| media.contains("json"))
+                .map(|(_, c)| c.get("schema").cloned().unwrap_or(json!({})));
+            let required = request_body["required"].as_bool() == Some(true);
+            match json_schema {
+                Some(schema) => (Some(schema), required),
+                None if required => {
+                    return Err("only JSON request bodies are supported".to_string())
+                }
+                None => (None, false),
+            }
+        }
+    };
+
+    let description = [op["summary"].as_str(), op["description"].as_str()]
+        .into_iter()
+        .flatten()
+        .next()
+        .map(|d| d.chars().take(500).collect())
+        .unwrap_or_else(|| format!("{} {}", method.to_uppercase(), path));
+    let base = op["operationId"]
+        .as_str()
+        .map(snake_case)
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| snake_case(&format!("{} {}", method, path)));
+
+    Ok(Operation {
+        tool_name: format!("{}{}{}", namespace, NAMESPACE_SEPARATOR, base),
+        method: method.to_uppercase(),
+        path: path.to_string(),
+        description,
+        params,
+        body,
+        body_required,
+    })
+}
+
+/// `listPets` / `list-pets` / `GET /pets/{id}` → `list_pets` / `get_pets_id`.
+fn snake_case(raw: &str) -> String {
+    let mut out = String::new();
+    let mut prev_lower = false;
+    for c in raw.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && prev_lower {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+            prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        } else {
+            if !out.ends_with('_') && !out.is_empty() {
+                out.push('_');
+            }
+            prev_lower = false;
+        }
+    }
+    out.trim_end_matches('_').to_string()
+}
+
+fn unique_name(name: &str, existing: &[Operation]) -> String {
+    let truncate = |s: &str, max: usize| s.chars().take(max).collect::<String>();
+    let mut candidate = truncate(name, MAX_TOOL_NAME_LEN);
+    let mut n = 2;
+    while existing.iter().any(|op| op.tool_name == candidate) {
+        let suffix = format!("_{}", n);
+        candidate = format!(
+            "{}{}",
+            truncate(name, MAX_TOOL_NAME_LEN - suffix.len()),
+            suffix
+        );
+        n += 1;
+    }
+    candidate
+}
+
+impl Operation {
+    /// JSON Schema for the tool arguments: one property per parameter, plus `body`.
+    pub fn parameters_schema(&self) -> Value {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for p in &self.params {
+            let mut schema = p.schema.clone();
+            if let (Some(obj), Some(desc)) = (schema.as_object_mut(), &p.description) {
+                obj.entry("description").or_insert(json!(desc));
+            }
+            properties.insert(p.name.clone(), schema);
+            if p.required {
+                required.push(json!(p.name));
+            }
+        }
+        if let Some(body) = &self.body {
+            properties.insert("body".to_string(), body.clone());
+            if self.body_required {
+                required.push(json!("body"));
+            }
+        }
+        json!({ "type": "object", "properties": properties, "required": required })
+    }
+
+    /// Validate `args` against the spec and build the request.
+    pub fn prepare(&self, server_url: &str, args: &Value) -> Result<PreparedRequest> {
+        let errors = validate(&self.parameters_schema(), args, "args");
+        if !errors.is_empty() {
+            return Err(Error::invalid_request(format!(
+                "arguments do not match the API spec: {}",
+                errors.join("; ")
+            )));
+        }
+
+        let mut path = self.path.clone();
+        let mut query = Vec::new();
+        let mut headers = Vec::new();
+        for p in &self.params {
+            let Some(value) = args.get(&p.name).filter(|v| !v.is_null()) else {
+                continue;
+            };
+            match p.location {
+                ParamLocation::Path => {
+                    path = path.replace(
+                        &format!("{{{}}}", p.name),
+                        &encode_path_segment(&scalar(value)),
+                    );
+                }
+                ParamLocation::Query => match value.as_array() {
+                    Some(items) => query.extend(items.iter().map(|i| (p.name.clone(), scalar(i)))),
+                    None => query.push((p.name.clone(), scalar(value))),
+                },
+                ParamLocation::Header => headers.push((p.name.clone(), scalar(value))),
+            }
+        }
+
+        let mut url = url::Url::parse(&format!("{}{}", server_url, path))
+            .map_err(|e| Error::invalid_request(format!("invalid request URL: {}", e)))?;
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+        let body = self
+            .body
+            .as_ref()
+            .and_then(|_| args.get("body").filter(|b| !b.is_null()).cloned());
+        Ok(PreparedRequest {
+            method: self.method.clone(),
+            url,
+            headers,
+            body,
+        })
+    }
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Percent-encode everything except RFC 3986 unreserved characters.
+fn encode_path_segment(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// A small JSON Schema validator covering the keywords APIs commonly rely on:
+/// `type`, `nullable`, `enum`, `required`, `properties` and `items`.
+pub fn validate(schema: &Value, value: &Value, at: &str) -> Vec<String> {
+    let mut errors = Vec::new();
+    if value.is_null() && schema["nullable"].as_bool() == Some(true) {
+        return errors;
+    }
+    if let Some(expected) = schema.get("type").and_then(|t| t.as_str()) {
+        let ok = match expected {
+            "string" => value.is_string(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "number" => value.is_number(),
+            "boolean" => value.is_boolean(),
+            "array" => value.is_array(),
+            "object" => value.is_object(),
+            _ => true,
+        };
+        if !ok {
+            errors.push(format!("{} must be of type {}", at, expected));
+            return errors;
+        }
+    }
+    if let Some(allowed) = schema.get("enum").and_then(|e| e.as_array()) {
+        if !allowed.contains(value) {
+            errors.push(format!(
+                "{} must be one of {}",
+                at,
+                Value::Array(allowed.clone())
+            ));
+        }
+    }
+    if let Some(obj) = value.as_object() {
+        for field in schema["required"].as_array().into_iter().flatten() {
+            if let Some(field) = field.as_str() {
+                if !obj.contains_key(field) {
+                    errors.push(format!("{}.{} is required", at, field));
+                }
+            }
+        }
+        if let Some(props) = schema["properties"].as_object() {
+            for (key, sub) in props {
+                if let Some(v) = obj.get(key) {
+                    errors.extend(validate(sub, v, &format!("{}.{}", at, key)));
+                }
+            }
+        }
+    }
+    if let (Some(items), Some(schema)) = (value.as_array(), schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            errors.extend(validate(schema, item, &format!("{}[{}]", at, i)));
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn petstore() -> Value {
+        parse_document(
+            r##"
+openapi: 3.0.3
+info: {title: Petstore, version: "1.0"}
+servers:
+  - url: https://{region}.pets.example.com/v1
+    variables:
+      region: {default: eu}
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      summary: List pets
+      parameters:
+        - {name: limit, in: query, schema: {type: integer}}
+        - {name: tags, in: query, schema: {type: array, items: {type: string}}}
+    post:
+      operationId: createPet
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema: {$ref: "#/components/schemas/Pet"}
+  /pets/{petId}:
+    parameters:
+      - {name: petId, in: path, required: true, schema: {type: string}}
+    delete:
+      summary: Delete a pet
+    put:
+      operationId: uploadPhoto
+      requestBody:
+        required: true
+        content:
+          image/png: {}
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [name]
+      properties:
+        name: {type: string}
+        kind: {type: string, enum: [cat, dog]}
+        parent: {$ref: "#/components/schemas/Pet"}
+"##,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_operations() {
+        let spec = ParsedSpec::from_document("pets", &petstore(), None).unwrap();
+        assert_eq!(spec.title, "Petstore");
+        assert_eq!(spec.server_url, "https://eu.pets.example.com/v1");
+
+        let names: Vec<&str> = spec
+            .operations
+            .iter()
+            .map(|o| o.tool_name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "pets__list_pets",
+                "pets__create_pet",
+                "pets__delete_pets_pet_id"
+            ]
+        );
+        assert_eq!(spec.skipped.len(), 1);
+        assert!(spec.skipped[0].contains("JSON request bodies"));
+
+        let create = &spec.operations[1];
+        assert_eq!(create.body.as_ref().unwrap()["required"], json!(["name"]));
+        let delete = &spec.operations[2];
+        assert!(delete.params[0].required);
+        assert_eq!(delete.description, "Delete a pet");
+    }
+
+    #[test]
+    fn test_prepare_builds_url_and_validates() {
+        let spec = ParsedSpec::from_document("pets", &petstore(), None).unwrap();
+        let list = &spec.operations[0];
+        let req = list
+            .prepare(&spec.server_url, &json!({"limit": 5, "tags": ["a b", "c"]}))
+            .unwrap();
+        assert_eq!(req.method, "GET");
+        assert_eq!(
+            req.url.as_str(),
+            "https://eu.pets.example.com/v1/pets?limit=5&tags=a+b&tags=c"
+        );
+        let err = list
+            .prepare(&spec.server_url, &json!({"limit": "five"}))
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("args.limit must be of type integer"));
+
+        let delete = &spec.operations[2];
+        let req = delete
+            .prepare(&spec.server_url, &json!({"petId": "a/../b"}))
+            .unwrap();
+        assert_eq!(req.url.path(), "/v1/pets/a%2F..%2Fb");
+        assert!(delete.prepare(&spec.server_url, &json!({})).is_err());
+
+        let create = &spec.operations[1];
+        let err = create
+            .prepare(&spec.server_url, &json!({"body": {"kind": "fish"}}))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("args.body.name is required"));
+        assert!(err.contains("args.body.kind must be one of"));
+        let req = create
+            .prepare(&spec.server_url, &json!({"body": {"name": "Rex"}}))
+            .unwrap();
+        assert_eq!(req.body, Some(json!({"name": "Rex"})));
+    }
+
+    #[test]
+    fn test_rejects_unusable_specs() {
+        assert!(normalize_namespace("Bad Name").is_err());
+        assert_eq!(normalize_namespace("CRM").unwrap(), "crm");
+
+        let swagger = json!({"swagger": "2.0", "paths": {}});
+        assert!(ParsedSpec::from_document("x", &swagger, None).is_err());
+
+        let relative =
+            json!({"openapi": "3.1.0", "servers": [{"url": "/api"}], "paths": {"/a": {"get": {}}}});
+        assert!(ParsedSpec::from_document("x", &relative, None).is_err());
+        let spec =
+            ParsedSpec::from_document("x", &relative, Some("https://api.example.com/")).unwrap();
+        assert_eq!(spec.server_url, "https://api.example.com");
+        assert_eq!(spec.operations[0].tool_name, "x__get_a");
+    }
+}
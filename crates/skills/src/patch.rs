@@ -0,0 +1,834 @@
+//! Unified diff application for sandbox files.
+//!
+//! Parses model-generated unified diffs, locates each hunk in the target file
+//! (tolerating line offsets and, with fuzz, mismatched outer context lines the
+//! way GNU `patch` does) and reports hunks that cannot be placed.
+
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+use multi_agent_core::{traits::Tool, types::ToolOutput, Error, Result};
+use multi_agent_sandbox::SandboxManager;
+
+/// Default fuzz factor, as in GNU `patch`.
+const DEFAULT_FUZZ: usize = 2;
+/// Maximum fuzz factor accepted from callers.
+const MAX_FUZZ: usize = 3;
+
+/// A line of a hunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HunkLine {
+    Context(String),
+    Remove(String),
+    Add(String),
+}
+
+/// A single `@@` hunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    /// 1-based start line in the original file.
+    pub old_start: usize,
+    pub old_len: usize,
+    pub new_start: usize,
+    pub new_len: usize,
+    pub lines: Vec<HunkLine>,
+    /// The hunk ends the original file without a trailing newline.
+    pub old_no_newline: bool,
+    /// The hunk ends the new file without a trailing newline.
+    pub new_no_newline: bool,
+}
+
+impl Hunk {
+    fn old_lines(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter_map(|l| match l {
+                HunkLine::Context(s) | HunkLine::Remove(s) => Some(s.as_str()),
+                HunkLine::Add(_) => None,
+            })
+            .collect()
+    }
+
+    fn new_lines(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter_map(|l| match l {
+                HunkLine::Context(s) | HunkLine::Add(s) => Some(s.as_str()),
+                HunkLine::Remove(_) => None,
+            })
+            .collect()
+    }
+
+    /// Number of leading and trailing context lines.
+    fn context_edges(&self) -> (usize, usize) {
+        let is_context = |l: &&HunkLine| matches!(l, HunkLine::Context(_));
+        let leading = self.lines.iter().take_while(is_context).count();
+        let trailing = self.lines.iter().rev().take_while(is_context).count();
+        if leading == self.lines.len() {
+            (leading, 0)
+        } else {
+            (leading, trailing)
+        }
+    }
+
+    /// Record a `\ No newline at end of file` marker for the last parsed line.
+    fn mark_no_newline(&mut self) {
+        match self.lines.last() {
+            Some(HunkLine::Remove(_)) => self.old_no_newline = true,
+            Some(HunkLine::Add(_)) => self.new_no_newline = true,
+            _ => {
+                self.old_no_newline = true;
+                self.new_no_newline = true;
+            }
+        }
+    }
+
+    fn header(&self) -> String {
+        format!(
+            "@@ -{},{} +{},{} @@",
+            self.old_start, self.old_len, self.new_start, self.new_len
+        )
+    }
+}
+
+/// All hunks for one file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilePatch {
+    /// Original path, `None` when the patch creates the file.
+    pub old_path: Option<String>,
+    /// New path, `None` when the patch deletes the file.
+    pub new_path: Option<String>,
+    pub hunks: Vec<Hunk>,
+}
+
+impl FilePatch {
+    /// Path the patch operates on.
+    pub fn path(&self) -> &str {
+        self.new_path
+            .as_deref()
+            .or(self.old_path.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+/// Strip the `a/` / `b/` prefixes and trailing timestamps from a diff header path.
+fn parse_header_path(raw: &str) -> Option<String> {
+    let path = raw.split('\t').next().unwrap_or_default().trim();
+    if path == "/dev/null" {
+        return None;
+    }
+    let path = path
+        .strip_prefix("a/")
+        .or_else(|| path.strip_prefix("b/"))
+        .unwrap_or(path);
+    Some(path.to_string())
+}
+
+/// Parse `-l,s` / `+l,s` ranges from a hunk header.
+fn parse_range(range: &str) -> Option<(usize, usize)> {
+    let (start, len) = match range.split_once(',') {
+        Some((start, len)) => (start, len.parse().ok()?),
+        None => (range, 1),
+    };
+    Some((start.parse().ok()?, len))
+}
+
+fn parse_hunk_header(line: &str) -> Option<(usize, usize, usize, usize)> {
+    let inner = line.strip_prefix("@@ ")?;
+    let (ranges, _) = inner.split_once(" @@")?;
+    let (old, new) = ranges.split_once(' ')?;
+    let (old_start, old_len) = parse_range(old.strip_prefix('-')?)?;
+    let (new_start, new_len) = parse_range(new.strip_prefix('+')?)?;
+    Some((old_start, old_len, new_start, new_len))
+}
+
+/// Parse a (possibly multi-file) unified diff.
+pub fn parse_unified_diff(diff: &str) -> std::result::Result<Vec<FilePatch>, String> {
+    let lines: Vec<&str> = diff.lines().collect();
+    let mut patches = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let Some(old) = lines[i].strip_prefix("--- ") else {
+            i += 1;
+            continue;
+        };
+        let new = lines
+            .get(i + 1)
+            .and_then(|l| l.strip_prefix("+++ "))
+            .ok_or_else(|| format!("line {}: '---' header without '+++'", i + 1))?;
+        let mut patch = FilePatch {
+            old_path: parse_header_path(old),
+            new_path: parse_header_path(new),
+            hunks: Vec::new(),
+        };
+        i += 2;
+
+        while let Some(line) = lines.get(i) {
+            if line.starts_with("--- ") || line.starts_with("diff ") {
+                break;
+            }
+            if !line.starts_with("@@") {
+                i += 1;
+                continue;
+            }
+            let (old_start, old_len, new_start, new_len) = parse_hunk_header(line)
+                .ok_or_else(|| format!("line {}: malformed hunk header '{}'", i + 1, line))?;
+            i += 1;
+
+            let mut hunk = Hunk {
+                old_start,
+                old_len,
+                new_start,
+                new_len,
+                lines: Vec::new(),
+                old_no_newline: false,
+                new_no_newline: false,
+            };
+            let (mut old_seen, mut new_seen) = (0, 0);
+            while old_seen < old_len || new_seen < new_len {
+                let Some(line) = lines.get(i) else {
+                    return Err(format!(
+                        "{}: hunk {} is truncated",
+                        patch.path(),
+                        hunk.header()
+                    ));
+                };
+                let kind = line.chars().next();
+                let text = &line[kind.map_or(0, char::len_utf8)..];
+                match kind {
+                    Some(' ') | None => {
+                        hunk.lines.push(HunkLine::Context(text.to_string()));
+                        old_seen += 1;
+                        new_seen += 1;
+                    }
+                    Some('-') => {
+                        hunk.lines.push(HunkLine::Remove(text.to_string()));
+                        old_seen += 1;
+                    }
+                    Some('+') => {
+                        hunk.lines.push(HunkLine::Add(text.to_string()));
+                        new_seen += 1;
+                    }
+                    Some('\\') => hunk.mark_no_newline(),
+                    _ => {
+                        return Err(format!(
+                            "line {}: unexpected '{}' inside hunk {}",
+                            i + 1,
+                            line,
+                            hunk.header()
+                        ))
+                    }
+                }
+                i += 1;
+            }
+            if lines.get(i).is_some_and(|l| l.starts_with('\\')) {
+                hunk.mark_no_newline();
+                i += 1;
+            }
+            patch.hunks.push(hunk);
+        }
+
+        if patch.hunks.is_empty() {
+            return Err(format!("{}: no hunks found", patch.path()));
+        }
+        patches.push(patch);
+    }
+
+    if patches.is_empty() {
+        return Err("no file headers ('--- a/...', '+++ b/...') found".to_string());
+    }
+    Ok(patches)
+}
+
+/// Where and how a hunk was applied.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct AppliedHunk {
+    pub header: String,
+    /// 1-based line the hunk was applied at in the original file.
+    pub line: usize,
+    /// Distance from the line stated in the header.
+    pub offset: isize,
+    /// Outer context lines ignored to place the hunk.
+    pub fuzz: usize,
+}
+
+/// A hunk that could not be placed.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct RejectedHunk {
+    pub header: String,
+    pub reason: String,
+    /// The hunk as it appeared in the diff, for the model to rework.
+    pub hunk: String,
+}
+
+/// Result of applying a file's hunks in memory.
+#[derive(Debug, Clone)]
+pub struct ApplyOutcome {
+    pub content: String,
+    pub applied: Vec<AppliedHunk>,
+    pub rejected: Vec<RejectedHunk>,
+}
+
+fn render_hunk(hunk: &Hunk) -> String {
+    let mut out = hunk.header();
+    for line in &hunk.lines {
+        let (prefix, text) = match line {
+            HunkLine::Context(s) => (' ', s),
+            HunkLine::Remove(s) => ('-', s),
+            HunkLine::Add(s) => ('+', s),
+        };
+        out.push('\n');
+        out.push(prefix);
+        out.push_str(text);
+    }
+    out
+}
+
+fn lines_match(file: &[String], at: usize, expected: &[&str]) -> bool {
+    at + expected.len() <= file.len()
+        && file[at..at + expected.len()]
+            .iter()
+            .zip(expected)
+            .all(|(a, b)| a.trim_end_matches('\r') == b.trim_end_matches('\r'))
+}
+
+/// Apply hunks to `original`, placing each at the nearest matching position
+/// at or after the previous hunk.
+pub fn apply_hunks(original: &str, hunks: &[Hunk], fuzz: usize) -> ApplyOutcome {
+    let mut file: Vec<String> = original.lines().map(str::to_string).collect();
+    let mut ends_with_newline = original.is_empty() || original.ends_with('\n');
+    let mut applied = Vec::new();
+    let mut rejected = Vec::new();
+    // Shift between original and patched line numbers so far.
+    let mut delta: isize = 0;
+    // First line hunks may still touch (hunks must not overlap).
+    let mut floor = 0usize;
+
+    for hunk in hunks {
+        let old = hunk.old_lines();
+        let new = hunk.new_lines();
+        let (leading, trailing) = hunk.context_edges();
+
+        let expected = (hunk.old_start.max(1) as isize - 1 + delta).max(0) as usize;
+        let mut placed = None;
+
+        'fuzz: for level in 0..=fuzz {
+            let skip_front = level.min(leading);
+            let skip_back = level.min(trailing);
+            if level > 0 && skip_front == 0 && skip_back == 0 {
+                break;
+            }
+            let core = &old[skip_front..old.len() - skip_back];
+            let target = expected + skip_front;
+
+            // Empty cores (pure additions) only apply at the stated line.
+            if core.is_empty() {
+                if target <= file.len() && target >= floor {
+                    placed = Some((target, skip_front, skip_back, level));
+                }
+                break;
+            }
+
+            let max_distance = file.len().max(target);
+            for distance in 0..=max_distance {
+                let candidates = [Some(target + distance), target.checked_sub(distance)];
+                for at in candidates.into_iter().flatten() {
+                    if at >= floor && lines_match(&file, at, core) {
+                        placed = Some((at, skip_front, skip_back, level));
+                        break 'fuzz;
+                    }
+                }
+                if target + distance >= file.len() && distance >= target {
+                    break;
+                }
+            }
+        }
+
+        match placed {
+            Some((at, skip_front, skip_back, level)) => {
+                let replacement: Vec<String> = new[skip_front..new.len() - skip_back]
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect();
+                let removed = old.len() - skip_front - skip_back;
+                let added = replacement.len();
+                let start_line = at - skip_front;
+                file.splice(at..at + removed, replacement);
+
+                applied.push(AppliedHunk {
+                    header: hunk.header(),
+                    line: (start_line as isize - delta) as usize + 1,
+                    offset: start_line as isize - expected as isize,
+                    fuzz: level,
+                });
+                delta += added as isize - removed as isize;
+                floor = at + added;
+                if hunk.new_no_newline {
+                    ends_with_newline = false;
+                } else if hunk.old_no_newline {
+                    ends_with_newline = true;
+                }
+            }
+            None => rejected.push(RejectedHunk {
+                header: hunk.header(),
+                reason: if expected > file.len() {
+                    format!(
+                        "hunk starts at line {} but the file has {} lines",
+                        hunk.old_start,
+                        file.len()
+                    )
+                } else {
+                    format!("context does not match (tried fuzz up to {})", fuzz)
+                },
+                hunk: render_hunk(hunk),
+            }),
+        }
+    }
+
+    let mut content = file.join("\n");
+    if ends_with_newline && !file.is_empty() {
+        content.push('\n');
+    }
+
+    ApplyOutcome {
+        content,
+        applied,
+        rejected,
+    }
+}
+
+/// Per-file report returned by the tool.
+#[derive(Debug, Serialize)]
+struct FileReport {
+    path: String,
+    action: &'static str,
+    applied: Vec<AppliedHunk>,
+    rejected: Vec<RejectedHunk>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Single-quote a string for `sh -c`.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+/// Tool that applies unified diffs to files in the sandbox workspace.
+///
+/// Risk level: HIGH — writes to the workspace; the default policy requires
+/// approval. Use `dry_run` to validate a diff without writing.
+pub struct ApplyPatchTool {
+    manager: Arc<SandboxManager>,
+}
+
+impl ApplyPatchTool {
+    /// Create a new apply patch tool.
+    pub fn new(manager: Arc<SandboxManager>) -> Self {
+        Self { manager }
+    }
+}
+
+#[async_trait]
+impl Tool for ApplyPatchTool {
+    fn name(&self) -> &str {
+        "sandbox_apply_patch"
+    }
+
+    fn description(&self) -> &str {
+        "Apply a unified diff (as produced by `diff -u` / `git diff`) to files in the \
+         sandbox's /workspace. Hunks are located even if line numbers drifted; hunks \
+         that cannot be placed are reported back with the reason. Nothing is written \
+         unless every hunk applies, or when dry_run is set."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "patch": {
+                    "type": "string",
+                    "description": "Unified diff with '--- a/path' / '+++ b/path' headers; paths relative to /workspace"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "Validate and report without modifying files",
+                    "default": false
+                },
+                "fuzz": {
+                    "type": "integer",
+                    "description": format!("Number of outer context lines that may mismatch (default: {}, max: {})", DEFAULT_FUZZ, MAX_FUZZ),
+                    "default": DEFAULT_FUZZ
+                }
+            },
+            "required": ["patch"]
+        })
+    }
+
+    fn risk_level(&self) -> multi_agent_core::types::ToolRiskLevel {
+        multi_agent_core::types::ToolRiskLevel::High
+    }
+
+    async fn execute(&self, args: Value) -> Result<ToolOutput> {
+        let diff = args
+            .get("patch")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::invalid_request("patch is required"))?;
+        let dry_run = args
+            .get("dry_run")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let fuzz = args
+            .get("fuzz")
+            .and_then(|v| v.as_u64())
+            .map_or(DEFAULT_FUZZ, |v| v as usize)
+            .min(MAX_FUZZ);
+
+        let patches = match parse_unified_diff(diff) {
+            Ok(patches) => patches,
+            Err(e) => return Ok(ToolOutput::error(format!("Invalid patch: {}", e))),
+        };
+
+        // Security: validate every path before touching the sandbox
+        let mut targets = Vec::with_capacity(patches.len());
+        for patch in &patches {
+            for path in [&patch.old_path, &patch.new_path].into_iter().flatten() {
+                multi_agent_core::fs_policy::validate_sandbox_path("/workspace", path)
+                    .map_err(|e| Error::invalid_request(format!("Invalid path: {}", e)))?;
+            }
+            let path =
+                multi_agent_core::fs_policy::validate_sandbox_path("/workspace", patch.path())
+                    .map_err(|e| Error::invalid_request(format!("Invalid path: {}", e)))?;
+            targets.push(path.to_string_lossy().to_string());
+        }
+
+        let sandbox_id = self.manager.get_or_create().await?;
+        let engine = self.manager.engine();
+
+        let mut reports = Vec::new();
+        let mut writes: Vec<(String, Option<String>)> = Vec::new();
+        for (patch, path) in patches.iter().zip(&targets) {
+            let source_path = patch.old_path.as_deref();
+            let original = match source_path {
+                Some(source) => match engine.read_file(&sandbox_id, source).await {
+                    Ok(bytes) => Some(String::from_utf8_lossy(&bytes).into_owned()),
+                    Err(_) => None,
+                },
+                None => None,
+            };
+
+            let action = match (&patch.old_path, &patch.new_path) {
+                (None, _) => "create",
+                (_, None) => "delete",
+                (Some(old), Some(new)) if old != new => "rename",
+                _ => "modify",
+            };
+
+            if source_path.is_some() && original.is_none() {
+                reports.push(FileReport {
+                    path: path.clone(),
+                    action,
+                    applied: vec![],
+                    rejected: vec![],
+                    error: Some("file not found in sandbox".to_string()),
+                });
+                continue;
+            }
+
+            let outcome = apply_hunks(original.as_deref().unwrap_or(""), &patch.hunks, fuzz);
+            if outcome.rejected.is_empty() {
+                if let (Some(old), "rename") = (&patch.old_path, action) {
+                    writes.push((old.clone(), None));
+                }
+                let content = (action != "delete").then(|| outcome.content.clone());
+                writes.push((path.clone(), content));
+            }
+            reports.push(FileReport {
+                path: path.clone(),
+                action,
+                applied: outcome.applied,
+                rejected: outcome.rejected,
+                error: None,
+            });
+        }
+
+        let failed = reports
+            .iter()
+            .any(|r| r.error.is_some() || !r.rejected.is_empty());
+
+        if !dry_run && !failed {
+            // Create/modify first so a rename never loses the only copy.
+            writes.sort_by_key(|(_, content)| content.is_none());
+            for (path, content) in &writes {
+                match content {
+                    Some(content) => {
+                        if let Some(parent) = std::path::Path::new(path).parent() {
+                            if !parent.as_os_str().is_empty() {
+                                let mkdir = format!(
+                                    "mkdir -p {}",
+                                    shell_quote(&format!("/workspace/{}", parent.display()))
+                                );
+                                engine
+                                    .exec(&sandbox_id, &mkdir, Duration::from_secs(5))
+                                    .await?;
+                            }
+                        }
+                        engine
+                            .write_file(&sandbox_id, path, content.as_bytes())
+                            .await?;
+                    }
+                    None => {
+                        let rm =
+                            format!("rm -f -- {}", shell_quote(&format!("/workspace/{}", path)));
+                        engine
+                            .exec(&sandbox_id, &rm, Duration::from_secs(5))
+                            .await?;
+                    }
+                }
+            }
+        }
+
+        let mut summary = String::new();
+        for report in &reports {
+            match (&report.error, report.rejected.len()) {
+                (Some(e), _) => {
+                    summary.push_str(&format!("✗ {} ({}): {}\n", report.path, report.action, e))
+                }
+                (None, 0) => summary.push_str(&format!(
+                    "✓ {} ({}): {} hunk(s) applied{}\n",
+                    report.path,
+                    report.action,
+                    report.applied.len(),
+                    if report.applied.iter().any(|h| h.offset != 0 || h.fuzz > 0) {
+                        " with offset/fuzz"
+                    } else {
+                        ""
+                    }
+                )),
+                (None, n) => {
+                    summary.push_str(&format!(
+                        "✗ {} ({}): {} hunk(s) applied, {} rejected\n",
+                        report.path,
+                        report.action,
+                        report.applied.len(),
+                        n
+                    ));
+                    for rejected in &report.rejected {
+                        summary.push_str(&format!(
+                            "  rejected {}: {}\n{}\n",
+                            rejected.header, rejected.reason, rejected.hunk
+                        ));
+                    }
+                }
+            }
+        }
+
+        let data = json!({
+            "dry_run": dry_run,
+            "written": !dry_run && !failed,
+            "files": reports,
+        });
+
+        if failed {
+            Ok(ToolOutput::error(format!(
+                "Patch {}; no files were modified.\n{}",
+                if dry_run {
+                    "would not apply cleanly"
+                } else {
+                    "did not apply cleanly"
+                },
+                summary.trim_end()
+            ))
+            .with_data(data))
+        } else if dry_run {
+            Ok(ToolOutput::text(format!(
+                "Dry run: patch applies cleanly.\n{}",
+                summary.trim_end()
+            ))
+            .with_data(data))
+        } else {
+            Ok(ToolOutput::text(format!("Patch applied.\n{}", summary.trim_end())).with_data(data))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multi_agent_sandbox::{MockSandbox, SandboxConfig};
+
+    const ORIGINAL: &str =
+        "fn main() {\n    let a = 1;\n    let b = 2;\n    println!(\"{}\", a + b);\n}\n";
+
+    const DIFF: &str = "\
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1,5 +1,5 @@
+ fn main() {
+     let a = 1;
+-    let b = 2;
++    let b = 40;
+     println!(\"{}\", a + b);
+ }
+";
+
+    #[test]
+    fn test_parse_unified_diff() {
+        let patches = parse_unified_diff(DIFF).unwrap();
+        assert_eq!(patches.len(), 1);
+        assert_eq!(patches[0].path(), "src/main.rs");
+        let hunk = &patches[0].hunks[0];
+        assert_eq!((hunk.old_start, hunk.old_len), (1, 5));
+        assert_eq!(hunk.lines.len(), 6);
+        assert_eq!(hunk.lines[2], HunkLine::Remove("    let b = 2;".into()));
+
+        assert!(parse_unified_diff("not a diff").is_err());
+        assert!(parse_unified_diff("--- a/x\n+++ b/x\n@@ -1,3 +1,3 @@\n a\n").is_err());
+    }
+
+    #[test]
+    fn test_apply_exact() {
+        let patches = parse_unified_diff(DIFF).unwrap();
+        let outcome = apply_hunks(ORIGINAL, &patches[0].hunks, 0);
+        assert!(outcome.rejected.is_empty());
+        assert!(outcome.content.contains("let b = 40;"));
+        assert!(outcome.content.ends_with("}\n"));
+        assert_eq!(outcome.applied[0].offset, 0);
+    }
+
+    #[test]
+    fn test_apply_with_offset() {
+        let shifted = format!("// header\n// more\n{}", ORIGINAL);
+        let patches = parse_unified_diff(DIFF).unwrap();
+        let outcome = apply_hunks(&shifted, &patches[0].hunks, 0);
+        assert!(outcome.rejected.is_empty());
+        assert_eq!(outcome.applied[0].offset, 2);
+        assert_eq!(outcome.applied[0].line, 3);
+        assert!(outcome.content.starts_with("// header\n"));
+    }
+
+    #[test]
+    fn test_apply_with_fuzz() {
+        let drifted = ORIGINAL.replace("fn main() {", "pub fn main() {");
+        let patches = parse_unified_diff(DIFF).unwrap();
+
+        let strict = apply_hunks(&drifted, &patches[0].hunks, 0);
+        assert_eq!(strict.rejected.len(), 1);
+        assert!(strict.rejected[0].hunk.contains("-    let b = 2;"));
+
+        let fuzzy = apply_hunks(&drifted, &patches[0].hunks, 1);
+        assert!(fuzzy.rejected.is_empty());
+        assert_eq!(fuzzy.applied[0].fuzz, 1);
+        assert!(fuzzy.content.starts_with("pub fn main() {\n"));
+        assert!(fuzzy.content.contains("let b = 40;"));
+    }
+
+    #[test]
+    fn test_create_and_no_newline() {
+        let diff = "\
+--- /dev/null
++++ b/notes.txt
+@@ -0,0 +1,2 @@
++hello
++world
+\\ No newline at end of file
+";
+        let patches = parse_unified_diff(diff).unwrap();
+        assert_eq!(patches[0].old_path, None);
+        let outcome = apply_hunks("", &patches[0].hunks, 0);
+        assert_eq!(outcome.content, "hello\nworld");
+    }
+
+    #[test]
+    fn test_multiple_hunks_track_delta() {
+        let original: String = (1..=20).map(|i| format!("line {}\n", i)).collect();
+        let diff = "\
+--- a/f.txt
++++ b/f.txt
+@@ -2,2 +2,3 @@
+ line 2
++inserted
+ line 3
+@@ -15,3 +16,2 @@
+ line 15
+-line 16
+ line 17
+";
+        let patches = parse_unified_diff(diff).unwrap();
+        let outcome = apply_hunks(&original, &patches[0].hunks, 0);
+        assert!(outcome.rejected.is_empty());
+        assert!(outcome.applied.iter().all(|h| h.offset == 0));
+        assert!(outcome.content.contains("line 2\ninserted\nline 3\n"));
+        assert!(!outcome.content.contains("line 16\n"));
+    }
+
+    async fn manager_with(files: &[(&str, &str)]) -> (Arc<SandboxManager>, Arc<MockSandbox>) {
+        let engine = Arc::new(MockSandbox::new(vec![]));
+        {
+            let mut stored = engine.files.lock().await;
+            for (path, content) in files {
+                stored.insert(path.to_string(), content.as_bytes().to_vec());
+            }
+        }
+        let manager = Arc::new(SandboxManager::new(
+            engine.clone(),
+            SandboxConfig::default(),
+        ));
+        (manager, engine)
+    }
+
+    #[tokio::test]
+    async fn test_tool_dry_run_does_not_write() {
+        let (manager, engine) = manager_with(&[("src/main.rs", ORIGINAL)]).await;
+        let tool = ApplyPatchTool::new(manager);
+
+        let result = tool
+            .execute(json!({"patch": DIFF, "dry_run": true}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.content.contains("Dry run"));
+        let stored = engine.files.lock().await;
+        assert_eq!(stored["src/main.rs"], ORIGINAL.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_tool_applies_and_reports_rejects() {
+        let (manager, engine) = manager_with(&[("src/main.rs", ORIGINAL)]).await;
+        let tool = ApplyPatchTool::new(manager);
+
+        let result = tool.execute(json!({"patch": DIFF})).await.unwrap();
+        assert!(result.success);
+        assert!(
+            String::from_utf8_lossy(&engine.files.lock().await["src/main.rs"])
+                .contains("let b = 40;")
+        );
+
+        // Applying again fails: the removed line is gone.
+        let result = tool
+            .execute(json!({"patch": DIFF, "fuzz": 0}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.content.contains("1 rejected"));
+        let data = result.data.unwrap();
+        assert_eq!(data["written"], false);
+        assert_eq!(data["files"][0]["rejected"][0]["header"], "@@ -1,5 +1,5 @@");
+    }
+
+    #[tokio::test]
+    async fn test_tool_rejects_traversal_and_is_high_risk() {
+        let (manager, _) = manager_with(&[]).await;
+        let tool = ApplyPatchTool::new(manager);
+        assert_eq!(
+            tool.risk_level(),
+            multi_agent_core::types::ToolRiskLevel::High
+        );
+
+        let diff = "--- a/../../etc/passwd\n+++ b/../../etc/passwd\n@@ -1 +1 @@\n-a\n+b\n";
+        assert!(tool.execute(json!({"patch": diff})).await.is_err());
+    }
+}
@@ -0,0 +1,346 @@
+//! Download quarantine.
+//!
+//! Downloads are held in memory and inspected before they reach the sandbox:
+//! the declared content type, the magic bytes against the destination's
+//! extension, and an optional external scanner (ClamAV, or a YARA wrapper).
+//! Executable content must be saved under an executable extension, which
+//! makes the `download` call require approval.
+
+use serde::Serialize;
+use std::path::Path;
+use std::time::Duration;
+
+use multi_agent_core::config::QuarantineConfig;
+use multi_agent_core::{Error, Result};
+
+/// Extensions whose downloads need human approval.
+const EXECUTABLE_EXTENSIONS: &[&str] = &[
+    "apk", "appimage", "bat", "bin", "cmd", "com", "deb", "dll", "dmg", "elf", "exe", "jar", "msi",
+    "ps1", "rpm", "scr", "sh", "so",
+];
+
+/// Script extensions whose `#!` line is expected and does not make the
+/// download an executable.
+const SCRIPT_EXTENSIONS: &[&str] = &["js", "mjs", "pl", "py", "rb", "ts"];
+
+/// File format recognised from magic bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileKind {
+    Pdf,
+    Png,
+    Jpeg,
+    Gif,
+    Zip,
+    Gzip,
+    Wasm,
+    /// Windows PE executable.
+    Pe,
+    Elf,
+    MachO,
+    /// Text starting with a `#!` interpreter line.
+    Script,
+    Text,
+}
+
+impl FileKind {
+    /// Sniff the format of `bytes`; `None` when unrecognised.
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        const MAGIC: &[(&[u8], FileKind)] = &[
+            (b"%PDF-", FileKind::Pdf),
+            (b"\x89PNG\r\n\x1a\n", FileKind::Png),
+            (b"\xff\xd8\xff", FileKind::Jpeg),
+            (b"GIF87a", FileKind::Gif),
+            (b"GIF89a", FileKind::Gif),
+            (b"PK\x03\x04", FileKind::Zip),
+            (b"PK\x05\x06", FileKind::Zip),
+            (b"\x1f\x8b", FileKind::Gzip),
+            (b"\0asm", FileKind::Wasm),
+            (b"MZ", FileKind::Pe),
+            (b"\x7fELF", FileKind::Elf),
+            (b"\xfe\xed\xfa\xce", FileKind::MachO),
+            (b"\xfe\xed\xfa\xcf", FileKind::MachO),
+            (b"\xce\xfa\xed\xfe", FileKind::MachO),
+            (b"\xcf\xfa\xed\xfe", FileKind::MachO),
+            (b"\xca\xfe\xba\xbe", FileKind::MachO),
+            (b"#!", FileKind::Script),
+        ];
+        if let Some((_, kind)) = MAGIC.iter().find(|(magic, _)| bytes.starts_with(magic)) {
+            return Some(*kind);
+        }
+        (!bytes.contains(&0) && std::str::from_utf8(bytes).is_ok()).then_some(FileKind::Text)
+    }
+
+    pub fn is_executable(self) -> bool {
+        matches!(
+            self,
+            FileKind::Pe | FileKind::Elf | FileKind::MachO | FileKind::Script
+        )
+    }
+
+    /// Formats a file with this extension may contain; `None` for unknown extensions.
+    fn expected_for(extension: &str) -> Option<&'static [FileKind]> {
+        use FileKind::*;
+        Some(match extension {
+            "pdf" => &[Pdf],
+            "png" => &[Png],
+            "jpg" | "jpeg" => &[Jpeg],
+            "gif" => &[Gif],
+            "zip" | "docx" | "xlsx" | "pptx" | "jar" | "apk" | "whl" => &[Zip],
+            "gz" | "tgz" => &[Gzip],
+            "wasm" => &[Wasm],
+            "exe" | "dll" | "scr" | "com" => &[Pe],
+            "elf" | "so" => &[Elf],
+            "sh" => &[Script, Text],
+            "txt" | "csv" | "json" | "md" | "html" | "htm" | "xml" | "yaml" | "yml" | "toml"
+            | "py" | "js" | "ts" | "rs" | "bat" | "cmd" | "ps1" => &[Text, Script],
+            _ => return None,
+        })
+    }
+}
+
+fn extension(path: &str) -> Option<String> {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+}
+
+/// Whether saving to `path` requires approval.
+pub fn is_executable_path(path: &str) -> bool {
+    extension(path).is_some_and(|ext| EXECUTABLE_EXTENSIONS.contains(&ext.as_str()))
+}
+
+/// Outcome of the external scanner.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "verdict", rename_all = "snake_case")]
+pub enum ScanVerdict {
+    /// No scanner configured.
+    Skipped,
+    Clean,
+    Infected {
+        signature: String,
+    },
+}
+
+/// What quarantine found out about a download.
+#[derive(Debug, Clone, Serialize)]
+pub struct QuarantineReport {
+    pub size: usize,
+    pub content_type: Option<String>,
+    pub detected: Option<FileKind>,
+    pub executable: bool,
+    pub scan: ScanVerdict,
+}
+
+/// Inspect a download destined for `destination`, rejecting it with a reason
+/// the agent can act on.
+pub async fn inspect(
+    config: &QuarantineConfig,
+    destination: &str,
+    content_type: Option<&str>,
+    bytes: &[u8],
+) -> Result<QuarantineReport> {
+    let reject = |reason: String| Err(Error::governance(format!("Download rejected: {}", reason)));
+
+    // Parameters such as `; charset=utf-8` do not matter for the allowlist
+    let content_type = content_type.map(|ct| {
+        ct.split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase()
+    });
+    if let Some(ct) = &content_type {
+        if !config.allowed_content_types.is_empty()
+            && !config.allowed_content_types.iter().any(|a| a == ct)
+        {
+            return reject(format!("content type '{}' is not allowed", ct));
+        }
+    }
+
+    let detected = FileKind::detect(bytes);
+    let ext = extension(destination);
+    if let Some(expected) = ext.as_deref().and_then(FileKind::expected_for) {
+        if !detected.is_some_and(|kind| expected.contains(&kind)) {
+            return reject(format!(
+                "'{}' claims .{} but the content is {}",
+                destination,
+                ext.unwrap_or_default(),
+                detected.map_or("unrecognised".to_string(), |k| format!("{:?}", k)),
+            ));
+        }
+    }
+    let script = detected == Some(FileKind::Script)
+        && ext
+            .as_deref()
+            .is_some_and(|ext| SCRIPT_EXTENSIONS.contains(&ext));
+    let executable = detected.is_some_and(FileKind::is_executable) && !script;
+    if executable && !is_executable_path(destination) {
+        return reject(format!(
+            "the content is an executable ({:?}); save it with an executable extension such as .exe, .sh or .bin so the download can be approved",
+            detected.unwrap_or(FileKind::Pe),
+        ));
+    }
+
+    let scan = scan(config, bytes).await?;
+    if let ScanVerdict::Infected { signature } = &scan {
+        return reject(format!("the malware scanner reported '{}'", signature));
+    }
+
+    Ok(QuarantineReport {
+        size: bytes.len(),
+        content_type,
+        detected,
+        executable,
+        scan,
+    })
+}
+
+/// Run the configured scanner on a temporary copy of the download.
+async fn scan(config: &QuarantineConfig, bytes: &[u8]) -> Result<ScanVerdict> {
+    let Some((program, args)) = config.scan_command.split_first() else {
+        return Ok(ScanVerdict::Skipped);
+    };
+    let path = std::env::temp_dir().join(format!("quarantine-{}", uuid::Uuid::new_v4()));
+    tokio::fs::write(&path, bytes)
+        .await
+        .map_err(|e| Error::internal(format!("Failed to quarantine download: {}", e)))?;
+
+    let path_str = path.display().to_string();
+    let output = tokio::time::timeout(
+        Duration::from_secs(config.scan_timeout_secs),
+        tokio::process::Command::new(program)
+            .args(args.iter().map(|a| a.replace("{path}", &path_str)))
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await;
+    let _ = tokio::fs::remove_file(&path).await;
+
+    let output = match output {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => {
+            return Err(Error::governance(format!(
+                "Download rejected: malware scanner could not run: {}",
+                e
+            )))
+        }
+        Err(_) => {
+            return Err(Error::governance(
+                "Download rejected: malware scan timed out".to_string(),
+            ))
+        }
+    };
+    match output.status.code() {
+        Some(0) => Ok(ScanVerdict::Clean),
+        Some(1) => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let signature = stdout
+                .lines()
+                .find(|line| !line.trim().is_empty())
+                .map(|line| {
+                    line.replace(&path_str, "")
+                        .trim_matches([':', ' '])
+                        .to_string()
+                })
+                .unwrap_or_else(|| "threat found".to_string());
+            Ok(ScanVerdict::Infected { signature })
+        }
+        code => Err(Error::governance(format!(
+            "Download rejected: malware scan failed (exit status {:?})",
+            code
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> QuarantineConfig {
+        QuarantineConfig::default()
+    }
+
+    #[tokio::test]
+    async fn test_magic_bytes_must_match_extension() {
+        let pdf = b"%PDF-1.7\n...";
+        let report = inspect(&config(), "report.pdf", Some("application/pdf"), pdf)
+            .await
+            .unwrap();
+        assert_eq!(report.detected, Some(FileKind::Pdf));
+        assert_eq!(report.scan, ScanVerdict::Skipped);
+
+        let err = inspect(&config(), "report.pdf", None, b"\x7fELF\x02\x01")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("claims .pdf"), "{}", err);
+
+        // Executables need an executable name so the call is approved
+        let err = inspect(&config(), "data", None, b"MZ\x90\x00")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("executable extension"), "{}", err);
+        let report = inspect(&config(), "setup.exe", None, b"MZ\x90\x00")
+            .await
+            .unwrap();
+        assert!(report.executable);
+        assert!(is_executable_path("tools/setup.EXE"));
+        assert!(!is_executable_path("notes.txt"));
+
+        // A shebang in a known script type is just a script
+        let report = inspect(&config(), "tool.py", None, b"#!/usr/bin/env python3\n")
+            .await
+            .unwrap();
+        assert!(!report.executable);
+        let err = inspect(&config(), "tool", None, b"#!/bin/sh\nrm -rf /\n")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("executable extension"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_content_type_allowlist() {
+        for ct in ["text/markdown", "application/xml", "application/javascript"] {
+            assert!(inspect(&config(), "page.txt", Some(ct), b"hello")
+                .await
+                .is_ok());
+        }
+
+        let config = QuarantineConfig {
+            allowed_content_types: vec!["text/plain".into()],
+            ..config()
+        };
+        let err = inspect(&config, "page.txt", Some("video/mp4"), b"hello")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("video/mp4"), "{}", err);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_scan_command_verdicts() {
+        let mut config = config();
+        config.scan_command = vec![
+            "sh".into(),
+            "-c".into(),
+            "grep -q EICAR \"$0\" && echo \"$0: Eicar-Test-Signature FOUND\" && exit 1; exit 0"
+                .into(),
+            "{path}".into(),
+        ];
+        let report = inspect(&config, "a.txt", None, b"clean").await.unwrap();
+        assert_eq!(report.scan, ScanVerdict::Clean);
+
+        let err = inspect(&config, "a.txt", None, b"EICAR test")
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("Eicar-Test-Signature FOUND"),
+            "{}",
+            err
+        );
+
+        config.scan_command = vec!["sh".into(), "-c".into(), "exit 2".into()];
+        assert!(inspect(&config, "a.txt", None, b"clean").await.is_err());
+    }
+}
@@ -136,6 +136,18 @@ impl Tool for LocalToolWrapper {
     fn risk_level(&self) -> multi_agent_core::types::ToolRiskLevel {
         self.tool.risk_level()
     }
+
+    fn requires_approval(&self) -> bool {
+        self.tool.requires_approval()
+    }
+
+    fn requires_approval_for(&self, args: &serde_json::Value) -> bool {
+        self.tool.requires_approval_for(args)
+    }
+
+    fn awaits_human_input(&self) -> bool {
+        self.tool.awaits_human_input()
+    }
 }
 
 /// Create a registry with built-in tools.
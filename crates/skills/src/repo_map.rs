@@ -0,0 +1,445 @@
+//! Repository map tool.
+//!
+//! Walks the sandbox workspace, skeletonizes every supported source file with
+//! the code simplifier and ranks files by how often the symbols they define
+//! are referenced elsewhere in the repository. The full map is stored as an
+//! artifact; the tool returns a summary that fits a token budget.
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use multi_agent_core::{
+    traits::{ArtifactStore, Tool},
+    types::ToolOutput,
+    Error, Result,
+};
+use multi_agent_sandbox::SandboxManager;
+
+use crate::code_simplifier::{simplify_file, CodeLanguage};
+
+/// Default summary budget in tokens.
+const DEFAULT_MAX_TOKENS: usize = 2_000;
+/// Hard cap on the summary budget.
+const MAX_TOKENS_CAP: usize = 16_000;
+/// Rough characters-per-token ratio used for budgeting.
+const CHARS_PER_TOKEN: usize = 4;
+/// Default maximum number of files mapped.
+const DEFAULT_MAX_FILES: usize = 500;
+/// Hard cap on the number of files mapped.
+const MAX_FILES_CAP: usize = 2_000;
+/// Files larger than this (in KiB) are skipped; they are usually generated.
+const MAX_FILE_KIB: usize = 256;
+/// Directories that never contain hand-written sources.
+const IGNORED_DIRS: &[&str] = &[
+    ".git",
+    "node_modules",
+    "target",
+    "vendor",
+    "dist",
+    "build",
+    "__pycache__",
+    ".venv",
+    "venv",
+];
+/// Symbols shorter than this are too generic to rank on.
+const MIN_SYMBOL_LEN: usize = 3;
+
+/// A mapped source file.
+#[derive(Debug, Clone)]
+struct FileEntry {
+    path: String,
+    language: &'static str,
+    skeleton: String,
+    symbols: Vec<String>,
+    score: usize,
+}
+
+/// Build the `find` command listing candidate source files under `root`.
+fn find_command(root: &str, max_files: usize) -> String {
+    let prunes = IGNORED_DIRS
+        .iter()
+        .map(|d| format!("-name '{}'", d))
+        .collect::<Vec<_>>()
+        .join(" -o ");
+    let names = [
+        "rs", "py", "pyi", "js", "mjs", "cjs", "jsx", "ts", "mts", "cts", "tsx", "go", "java",
+    ]
+    .iter()
+    .map(|ext| format!("-name '*.{}'", ext))
+    .collect::<Vec<_>>()
+    .join(" -o ");
+
+    format!(
+        "find {root} \\( {prunes} \\) -prune -o -type f \\( {names} \\) -size -{kib}k -print | sort | head -n {limit}",
+        root = shell_quote(root),
+        prunes = prunes,
+        names = names,
+        kib = MAX_FILE_KIB,
+        limit = max_files + 1,
+    )
+}
+
+/// Single-quote a string for `sh -c`.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+/// Identifier-like tokens in a source file.
+fn identifiers(source: &str) -> HashSet<&str> {
+    source
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|t| t.len() >= MIN_SYMBOL_LEN)
+        .collect()
+}
+
+/// Score each file by the number of other files referencing its symbols.
+///
+/// Returns per-symbol reference counts for reporting.
+fn rank(files: &mut [FileEntry], sources: &[String]) -> HashMap<String, usize> {
+    let idents: Vec<HashSet<&str>> = sources.iter().map(|s| identifiers(s)).collect();
+    let mut references = HashMap::new();
+
+    for (i, file) in files.iter_mut().enumerate() {
+        let mut score = 1;
+        for symbol in file.symbols.iter().filter(|s| s.len() >= MIN_SYMBOL_LEN) {
+            let count = idents
+                .iter()
+                .enumerate()
+                .filter(|(j, set)| *j != i && set.contains(symbol.as_str()))
+                .count();
+            score += count;
+            *references.entry(symbol.clone()).or_insert(0) += count;
+        }
+        file.score = score;
+    }
+
+    files.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.path.cmp(&b.path)));
+    references
+}
+
+/// Render the complete map stored as an artifact.
+fn render_full(root: &str, files: &[FileEntry]) -> String {
+    let mut out = format!(
+        "# Repository map: {}\n\n{} files, ranked by cross-file references.\n",
+        root,
+        files.len()
+    );
+    for file in files {
+        out.push_str(&format!(
+            "\n## {} (score {})\n\n```{}\n{}\n```\n",
+            file.path, file.score, file.language, file.skeleton
+        ));
+    }
+    out
+}
+
+/// Render a summary within `budget` characters.
+///
+/// Top-ranked files get their full skeleton while it fits; after that files
+/// are listed with their symbol names only.
+fn render_summary(files: &[FileEntry], budget: usize) -> (String, usize) {
+    let mut out = String::new();
+    let mut included = 0;
+
+    for file in files {
+        let full = format!("{}:\n{}\n\n", file.path, file.skeleton);
+        let compact = format!("{}: {}\n", file.path, file.symbols.join(", "));
+        if out.len() + full.len() <= budget {
+            out.push_str(&full);
+        } else if out.len() + compact.len() <= budget {
+            out.push_str(&compact);
+        } else {
+            break;
+        }
+        included += 1;
+    }
+
+    (out.trim_end().to_string(), included)
+}
+
+/// Tool that builds a ranked map of the sandbox workspace.
+///
+/// Risk level: LOW (read-only).
+pub struct RepoMapTool {
+    manager: Arc<SandboxManager>,
+    store: Arc<dyn ArtifactStore>,
+}
+
+impl RepoMapTool {
+    /// Create a new repo map tool.
+    pub fn new(manager: Arc<SandboxManager>, store: Arc<dyn ArtifactStore>) -> Self {
+        Self { manager, store }
+    }
+}
+
+#[async_trait]
+impl Tool for RepoMapTool {
+    fn name(&self) -> &str {
+        "repo_map"
+    }
+
+    fn description(&self) -> &str {
+        "Build a ranked map of the source files in the sandbox workspace: function, \
+         class and type signatures without bodies, most-referenced files first. \
+         Returns a summary within a token budget and stores the full map as an \
+         artifact readable with read_artifact."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Directory relative to /workspace to map (default: '.')",
+                    "default": "."
+                },
+                "max_tokens": {
+                    "type": "integer",
+                    "description": format!("Token budget for the returned summary (default: {}, max: {})", DEFAULT_MAX_TOKENS, MAX_TOKENS_CAP),
+                    "default": DEFAULT_MAX_TOKENS
+                },
+                "max_files": {
+                    "type": "integer",
+                    "description": format!("Maximum number of files to map (default: {}, max: {})", DEFAULT_MAX_FILES, MAX_FILES_CAP),
+                    "default": DEFAULT_MAX_FILES
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: Value) -> Result<ToolOutput> {
+        let path = args.get("path").and_then(|v| v.as_str()).unwrap_or(".");
+        let max_tokens = args
+            .get("max_tokens")
+            .and_then(|v| v.as_u64())
+            .map_or(DEFAULT_MAX_TOKENS, |v| v as usize)
+            .clamp(1, MAX_TOKENS_CAP);
+        let max_files = args
+            .get("max_files")
+            .and_then(|v| v.as_u64())
+            .map_or(DEFAULT_MAX_FILES, |v| v as usize)
+            .clamp(1, MAX_FILES_CAP);
+
+        // Security: validate path using fs_policy
+        let validated_path = multi_agent_core::fs_policy::validate_sandbox_path("/workspace", path)
+            .map_err(|e| Error::invalid_request(format!("Invalid path: {}", e)))?;
+        let root = validated_path.to_string_lossy();
+        let root = if root.is_empty() { "." } else { root.as_ref() };
+
+        let sandbox_id = self.manager.get_or_create().await?;
+        let listing = self
+            .manager
+            .engine()
+            .exec(
+                &sandbox_id,
+                &find_command(root, max_files),
+                Duration::from_secs(30),
+            )
+            .await?;
+        if !listing.success() {
+            return Ok(ToolOutput::error(format!(
+                "Failed to list files: {}",
+                listing.stderr
+            )));
+        }
+
+        let mut paths: Vec<&str> = listing
+            .stdout
+            .lines()
+            .map(|l| l.trim().trim_start_matches("./"))
+            .filter(|l| !l.is_empty() && CodeLanguage::from_path(l).is_some())
+            .collect();
+        let listing_truncated = paths.len() > max_files;
+        paths.truncate(max_files);
+
+        let mut files = Vec::new();
+        let mut sources = Vec::new();
+        let mut skipped = Vec::new();
+        for path in paths {
+            let bytes = match self.manager.engine().read_file(&sandbox_id, path).await {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    tracing::debug!(path = path, error = %e, "repo_map: failed to read file");
+                    skipped.push(path.to_string());
+                    continue;
+                }
+            };
+            let source = String::from_utf8_lossy(&bytes).into_owned();
+            match simplify_file(path, &source) {
+                Ok(simplified) if simplified.item_count > 0 => {
+                    files.push(FileEntry {
+                        path: path.to_string(),
+                        language: CodeLanguage::from_path(path).map_or("", |l| l.as_str()),
+                        skeleton: simplified.skeleton,
+                        symbols: simplified.symbols,
+                        score: 0,
+                    });
+                    sources.push(source);
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::debug!(path = path, error = %e, "repo_map: failed to simplify file");
+                    skipped.push(path.to_string());
+                }
+            }
+        }
+
+        if files.is_empty() {
+            return Ok(ToolOutput::text(format!(
+                "No supported source files found under /workspace/{}",
+                root.trim_start_matches("./")
+            )));
+        }
+
+        let references = rank(&mut files, &sources);
+        let full = render_full(root, &files);
+        let ref_id = self
+            .store
+            .save_with_type(Bytes::from(full), "text/markdown")
+            .await?;
+
+        let (summary, included) = render_summary(&files, max_tokens * CHARS_PER_TOKEN);
+        let mut content = format!(
+            "Repository map of {} ({} files, most referenced first):\n\n{}",
+            root,
+            files.len(),
+            summary
+        );
+        if included < files.len() {
+            content.push_str(&format!(
+                "\n\n... {} more files omitted to fit the budget.",
+                files.len() - included
+            ));
+        }
+        if listing_truncated {
+            content.push_str(&format!(
+                "\nOnly the first {} files were mapped; narrow `path` to map the rest.",
+                max_files
+            ));
+        }
+        content.push_str(&format!(
+            "\n\nFull map saved as RefID: {}. Use read_artifact with pattern to search it.",
+            ref_id
+        ));
+
+        let mut top_symbols: Vec<(&String, &usize)> =
+            references.iter().filter(|(_, n)| **n > 0).collect();
+        top_symbols.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+
+        let mut output = ToolOutput::text(content).with_data(json!({
+            "ref_id": ref_id.as_str(),
+            "files_mapped": files.len(),
+            "files_in_summary": included,
+            "files_skipped": skipped,
+            "truncated": listing_truncated,
+            "top_files": files.iter().take(10).map(|f| json!({"path": f.path, "score": f.score})).collect::<Vec<_>>(),
+            "top_symbols": top_symbols.iter().take(20).map(|(s, n)| json!({"symbol": s, "references": n})).collect::<Vec<_>>(),
+        }));
+        output.created_refs.push(ref_id);
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multi_agent_sandbox::{ExecResult, MockSandbox, SandboxConfig};
+    use multi_agent_store::InMemoryStore;
+
+    async fn sandbox_with(files: &[(&str, &str)]) -> Arc<SandboxManager> {
+        let listing = files
+            .iter()
+            .map(|(p, _)| format!("./{}", p))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let engine = MockSandbox::new(vec![ExecResult {
+            exit_code: 0,
+            stdout: listing,
+            stderr: String::new(),
+            timed_out: false,
+        }]);
+        {
+            let mut stored = engine.files.lock().await;
+            for (path, content) in files {
+                stored.insert(path.to_string(), content.as_bytes().to_vec());
+            }
+        }
+        Arc::new(SandboxManager::new(
+            Arc::new(engine),
+            SandboxConfig::default(),
+        ))
+    }
+
+    fn repo() -> Vec<(&'static str, &'static str)> {
+        vec![
+            (
+                "src/util.rs",
+                "pub fn parse_config(s: &str) -> Config { todo!() }\npub struct Config { pub name: String }",
+            ),
+            (
+                "src/main.rs",
+                "fn main() { let c = parse_config(\"x\"); run(c); }\nfn run(c: Config) { println!(\"{}\", c.name); }",
+            ),
+            (
+                "tools/report.py",
+                "from util import parse_config\n\ndef report(path):\n    return parse_config(path)\n",
+            ),
+            ("README.md", "# not code"),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_repo_map_ranks_and_stores_full_map() {
+        let manager = sandbox_with(&repo()).await;
+        let store = Arc::new(InMemoryStore::new());
+        let tool = RepoMapTool::new(manager, store.clone());
+
+        let result = tool.execute(json!({})).await.unwrap();
+        assert!(result.success);
+
+        let data = result.data.unwrap();
+        assert_eq!(data["files_mapped"], 3);
+        // util.rs defines the symbols everyone else uses.
+        assert_eq!(data["top_files"][0]["path"], "src/util.rs");
+        assert_eq!(data["top_symbols"][0]["symbol"], "parse_config");
+        assert!(result.content.contains("pub fn parse_config"));
+        assert!(!result.content.contains("todo!"));
+
+        assert_eq!(result.created_refs.len(), 1);
+        let full = store.load(&result.created_refs[0]).await.unwrap().unwrap();
+        let full = String::from_utf8_lossy(&full);
+        assert!(full.contains("## tools/report.py"));
+        assert!(full.contains("```python\ndef report(path): ...\n```"));
+    }
+
+    #[tokio::test]
+    async fn test_repo_map_respects_budget() {
+        let manager = sandbox_with(&repo()).await;
+        let tool = RepoMapTool::new(manager, Arc::new(InMemoryStore::new()));
+
+        let result = tool.execute(json!({"max_tokens": 20})).await.unwrap();
+        let data = result.data.unwrap();
+        assert!(data["files_in_summary"].as_u64().unwrap() < 3);
+        assert!(result.content.contains("more files omitted"));
+    }
+
+    #[test]
+    fn test_find_command_quotes_root() {
+        let cmd = find_command("it's", 10);
+        assert!(cmd.starts_with("find 'it'\\''s' "));
+        assert!(cmd.ends_with("head -n 11"));
+    }
+
+    #[tokio::test]
+    async fn test_repo_map_rejects_traversal() {
+        let manager = sandbox_with(&[]).await;
+        let tool = RepoMapTool::new(manager, Arc::new(InMemoryStore::new()));
+
+        assert!(tool.execute(json!({"path": "../etc"})).await.is_err());
+    }
+}
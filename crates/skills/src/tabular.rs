@@ -0,0 +1,794 @@
+//! CSV / XLSX analysis backed by polars.
+//!
+//! Loads tabular artifacts into a polars `DataFrame` and runs either a SQL
+//! query or a structured filter / group / aggregate / sort pipeline against
+//! them. Small results are returned inline; large ones are stored as CSV
+//! artifacts and returned by reference.
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use polars::prelude::*;
+use serde_json::{json, Value};
+use std::io::Cursor;
+use std::sync::Arc;
+
+use multi_agent_core::{
+    traits::{ArtifactStore, Tool},
+    types::{RefId, ToolOutput},
+    Error, Result,
+};
+
+/// Rows returned inline before a result is stored by reference.
+const DEFAULT_MAX_INLINE_ROWS: usize = 50;
+/// Rows shown as a preview when describing a table or returning by reference.
+const PREVIEW_ROWS: usize = 10;
+/// Rows sampled to infer CSV column types.
+const INFER_SCHEMA_ROWS: usize = 1_000;
+
+const XLSX_CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+/// Input formats understood by the tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TableFormat {
+    Csv,
+    Tsv,
+    Xlsx,
+}
+
+impl TableFormat {
+    fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "tsv" => Some(Self::Tsv),
+            "xlsx" | "xls" | "excel" => Some(Self::Xlsx),
+            _ => None,
+        }
+    }
+
+    /// Guess from the stored content type and magic bytes.
+    fn detect(content_type: Option<&str>, bytes: &[u8]) -> Self {
+        match content_type {
+            Some(XLSX_CONTENT_TYPE) | Some("application/vnd.ms-excel") => Self::Xlsx,
+            Some("text/tab-separated-values") => Self::Tsv,
+            _ if bytes.starts_with(b"PK\x03\x04") => Self::Xlsx,
+            _ => Self::Csv,
+        }
+    }
+}
+
+fn read_delimited(bytes: Bytes, separator: u8) -> PolarsResult<DataFrame> {
+    CsvReadOptions::default()
+        .with_has_header(true)
+        .with_infer_schema_length(Some(INFER_SCHEMA_ROWS))
+        .map_parse_options(|o| o.with_separator(separator))
+        .into_reader_with_file_handle(Cursor::new(bytes))
+        .finish()
+}
+
+/// Convert the first (or named) worksheet into a `DataFrame`, using the first
+/// row as the header.
+fn read_xlsx(bytes: Bytes, sheet: Option<&str>) -> std::result::Result<DataFrame, String> {
+    use calamine::{open_workbook_auto_from_rs, Data, DataType, Reader};
+
+    let mut workbook = open_workbook_auto_from_rs(Cursor::new(bytes)).map_err(|e| e.to_string())?;
+    let sheet = match sheet {
+        Some(name) => name.to_string(),
+        None => workbook
+            .sheet_names()
+            .first()
+            .cloned()
+            .ok_or_else(|| "workbook has no sheets".to_string())?,
+    };
+    let range = workbook
+        .worksheet_range(&sheet)
+        .map_err(|e| format!("sheet '{}': {}", sheet, e))?;
+
+    let mut rows = range.rows();
+    let header: Vec<String> = rows
+        .next()
+        .map(|r| {
+            r.iter()
+                .enumerate()
+                .map(|(i, c)| match c {
+                    Data::Empty => format!("column_{}", i + 1),
+                    c => c.to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let body: Vec<&[Data]> = rows.collect();
+
+    let columns = header
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let cells: Vec<&Data> = body
+                .iter()
+                .map(|r| r.get(i).unwrap_or(&Data::Empty))
+                .collect();
+            let present = || cells.iter().filter(|c| !matches!(c, Data::Empty));
+            let name = PlSmallStr::from(name.as_str());
+
+            if present().all(|c| matches!(c, Data::Int(_))) {
+                let values: Vec<Option<i64>> = cells
+                    .iter()
+                    .map(|c| match c {
+                        Data::Int(v) => Some(*v),
+                        _ => None,
+                    })
+                    .collect();
+                Column::new(name, values)
+            } else if present().all(|c| matches!(c, Data::Int(_) | Data::Float(_))) {
+                let values: Vec<Option<f64>> = cells.iter().map(|c| c.as_f64()).collect();
+                Column::new(name, values)
+            } else if present().all(|c| matches!(c, Data::Bool(_))) {
+                let values: Vec<Option<bool>> = cells.iter().map(|c| c.get_bool()).collect();
+                Column::new(name, values)
+            } else {
+                let values: Vec<Option<String>> = cells
+                    .iter()
+                    .map(|c| match c {
+                        Data::Empty => None,
+                        Data::DateTime(_) => c.as_datetime().map(|d| d.to_string()),
+                        c => Some(c.to_string()),
+                    })
+                    .collect();
+                Column::new(name, values)
+            }
+        })
+        .collect();
+
+    DataFrame::new(columns).map_err(|e| e.to_string())
+}
+
+/// Build a literal from a JSON scalar.
+fn json_lit(value: &Value) -> std::result::Result<Expr, String> {
+    match value {
+        Value::Bool(b) => Ok(lit(*b)),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => Ok(lit(i)),
+            None => Ok(lit(n.as_f64().unwrap_or_default())),
+        },
+        Value::String(s) => Ok(lit(s.clone())),
+        other => Err(format!("unsupported filter value: {}", other)),
+    }
+}
+
+fn str_field<'a>(spec: &'a Value, field: &str) -> std::result::Result<&'a str, String> {
+    spec.get(field)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| format!("'{}' is required in {}", field, spec))
+}
+
+/// `{"column": "...", "op": "gt", "value": 10}`
+fn filter_expr(spec: &Value) -> std::result::Result<Expr, String> {
+    let column = col(str_field(spec, "column")?);
+    let op = spec.get("op").and_then(|v| v.as_str()).unwrap_or("eq");
+    let value = || json_lit(spec.get("value").unwrap_or(&Value::Null));
+
+    Ok(match op {
+        "eq" | "==" => column.eq(value()?),
+        "ne" | "!=" => column.neq(value()?),
+        "gt" | ">" => column.gt(value()?),
+        "gte" | ">=" => column.gt_eq(value()?),
+        "lt" | "<" => column.lt(value()?),
+        "lte" | "<=" => column.lt_eq(value()?),
+        "contains" => column.str().contains_literal(value()?),
+        "is_null" => column.is_null(),
+        "not_null" => column.is_not_null(),
+        other => return Err(format!("unknown filter op '{}'", other)),
+    })
+}
+
+/// `{"column": "...", "op": "sum", "alias": "total"}`
+fn aggregate_expr(spec: &Value) -> std::result::Result<Expr, String> {
+    let name = str_field(spec, "column")?;
+    let op = str_field(spec, "op")?;
+    let column = col(name);
+
+    let expr = match op {
+        "sum" => column.sum(),
+        "mean" | "avg" => column.mean(),
+        "median" => column.median(),
+        "min" => column.min(),
+        "max" => column.max(),
+        "count" => column.count(),
+        "n_unique" => column.n_unique(),
+        "first" => column.first(),
+        "last" => column.last(),
+        other => return Err(format!("unknown aggregate op '{}'", other)),
+    };
+    let alias = spec
+        .get("alias")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{}_{}", name, op));
+    Ok(expr.alias(alias))
+}
+
+fn string_list(args: &Value, field: &str) -> Vec<String> {
+    args.get(field)
+        .and_then(|v| v.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn object_list(args: &Value, field: &str) -> Vec<Value> {
+    args.get(field)
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Apply the structured pipeline: filter → group_by/aggregate → select → sort → limit.
+fn structured_query(df: DataFrame, args: &Value) -> std::result::Result<LazyFrame, String> {
+    let mut lf = df.lazy();
+
+    for spec in object_list(args, "filter") {
+        lf = lf.filter(filter_expr(&spec)?);
+    }
+
+    let group_by = string_list(args, "group_by");
+    let aggregates = object_list(args, "aggregate")
+        .iter()
+        .map(aggregate_expr)
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    if !group_by.is_empty() {
+        let keys: Vec<Expr> = group_by.iter().map(|c| col(c.as_str())).collect();
+        let aggs = if aggregates.is_empty() {
+            vec![len().alias("count")]
+        } else {
+            aggregates
+        };
+        lf = lf.group_by_stable(keys).agg(aggs);
+    } else if !aggregates.is_empty() {
+        lf = lf.select(aggregates);
+    }
+
+    let select = string_list(args, "select");
+    if !select.is_empty() {
+        lf = lf.select(select.iter().map(|c| col(c.as_str())).collect::<Vec<_>>());
+    }
+
+    let sort = object_list(args, "sort");
+    if !sort.is_empty() {
+        let mut exprs = Vec::with_capacity(sort.len());
+        let mut descending = Vec::with_capacity(sort.len());
+        for spec in &sort {
+            exprs.push(col(str_field(spec, "column")?));
+            descending.push(
+                spec.get("descending")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false),
+            );
+        }
+        lf = lf.sort_by_exprs(
+            exprs,
+            SortMultipleOptions::default()
+                .with_order_descending_multi(descending)
+                .with_nulls_last(true),
+        );
+    }
+
+    if let Some(limit) = args.get("limit").and_then(|v| v.as_u64()) {
+        lf = lf.limit(limit as IdxSize);
+    }
+    Ok(lf)
+}
+
+fn sql_query(df: DataFrame, query: &str) -> PolarsResult<LazyFrame> {
+    let mut ctx = polars::sql::SQLContext::new();
+    ctx.register("data", df.lazy());
+    ctx.execute(query)
+}
+
+fn cell_json(value: AnyValue<'_>) -> Value {
+    match value {
+        AnyValue::Null => Value::Null,
+        AnyValue::Boolean(b) => json!(b),
+        AnyValue::String(s) => json!(s),
+        AnyValue::StringOwned(s) => json!(s.as_str()),
+        AnyValue::Int8(v) => json!(v),
+        AnyValue::Int16(v) => json!(v),
+        AnyValue::Int32(v) => json!(v),
+        AnyValue::Int64(v) => json!(v),
+        AnyValue::UInt8(v) => json!(v),
+        AnyValue::UInt16(v) => json!(v),
+        AnyValue::UInt32(v) => json!(v),
+        AnyValue::UInt64(v) => json!(v),
+        AnyValue::Float32(v) => json!(v),
+        AnyValue::Float64(v) => json!(v),
+        other => json!(other.to_string()),
+    }
+}
+
+/// Rows of `df` as JSON arrays.
+fn rows_json(df: &DataFrame) -> Vec<Value> {
+    (0..df.height())
+        .map(|i| {
+            Value::Array(
+                df.get_columns()
+                    .iter()
+                    .map(|c| c.get(i).map(cell_json).unwrap_or(Value::Null))
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+fn column_names(df: &DataFrame) -> Vec<String> {
+    df.get_column_names()
+        .iter()
+        .map(|n| n.to_string())
+        .collect()
+}
+
+fn markdown_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.replace('|', "\\|").replace('\n', " "),
+        other => other.to_string(),
+    }
+}
+
+fn markdown_table(df: &DataFrame) -> String {
+    let names = column_names(df);
+    let mut out = format!(
+        "| {} |\n|{}\n",
+        names.join(" | "),
+        "---|".repeat(names.len())
+    );
+    for row in rows_json(df) {
+        let cells: Vec<String> = row
+            .as_array()
+            .map(|r| r.iter().map(markdown_cell).collect())
+            .unwrap_or_default();
+        out.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    out
+}
+
+fn schema_json(df: &DataFrame) -> Vec<Value> {
+    df.get_columns()
+        .iter()
+        .map(|c| json!({"name": c.name().as_str(), "dtype": c.dtype().to_string()}))
+        .collect()
+}
+
+/// Tool for querying CSV / TSV / XLSX artifacts.
+///
+/// Risk level: LOW (read-only; results are written as new artifacts).
+pub struct TabularTool {
+    store: Arc<dyn ArtifactStore>,
+    max_inline_rows: usize,
+}
+
+impl TabularTool {
+    /// Create a new tabular tool.
+    pub fn new(store: Arc<dyn ArtifactStore>) -> Self {
+        Self {
+            store,
+            max_inline_rows: DEFAULT_MAX_INLINE_ROWS,
+        }
+    }
+
+    /// Set how many result rows are returned inline before storing by reference.
+    pub fn with_max_inline_rows(mut self, rows: usize) -> Self {
+        self.max_inline_rows = rows;
+        self
+    }
+
+    async fn load(
+        &self,
+        args: &Value,
+    ) -> Result<std::result::Result<(Bytes, TableFormat), String>> {
+        let ref_id_str = args
+            .get("ref_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::invalid_request("ref_id is required"))?;
+        let ref_id = RefId::from_string(ref_id_str);
+
+        let Some(bytes) = self.store.load(&ref_id).await? else {
+            return Ok(Err(format!("Artifact not found: {}", ref_id_str)));
+        };
+
+        let format = match args.get("format").and_then(|v| v.as_str()) {
+            Some(f) => TableFormat::parse(f)
+                .ok_or_else(|| Error::invalid_request(format!("unsupported format '{}'", f)))?,
+            None => {
+                let content_type = self.store.metadata(&ref_id).await?.map(|m| m.content_type);
+                TableFormat::detect(content_type.as_deref(), &bytes)
+            }
+        };
+        Ok(Ok((bytes, format)))
+    }
+}
+
+/// Outcome of parsing and querying a table.
+enum Evaluation {
+    Output(ToolOutput),
+    /// Too many rows to return inline; `csv` is stored as an artifact.
+    Large {
+        rows: usize,
+        width: usize,
+        csv: Vec<u8>,
+        preview: DataFrame,
+    },
+}
+
+/// Parse the table and run the query. CPU-bound, so it runs on the blocking pool.
+fn evaluate(
+    bytes: Bytes,
+    format: TableFormat,
+    args: &Value,
+    max_inline_rows: usize,
+) -> Result<Evaluation> {
+    let ref_id = args
+        .get("ref_id")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default();
+    let sheet = args.get("sheet").and_then(|v| v.as_str());
+    let parsed = match format {
+        TableFormat::Csv => read_delimited(bytes, b',').map_err(|e| e.to_string()),
+        TableFormat::Tsv => read_delimited(bytes, b'\t').map_err(|e| e.to_string()),
+        TableFormat::Xlsx => read_xlsx(bytes, sheet),
+    };
+    let df = match parsed {
+        Ok(df) => df,
+        Err(e) => {
+            return Ok(Evaluation::Output(ToolOutput::error(format!(
+                "Failed to parse {}: {}",
+                ref_id, e
+            ))))
+        }
+    };
+
+    let has_query = ["filter", "group_by", "aggregate", "select", "sort", "limit"]
+        .iter()
+        .any(|f| args.get(f).is_some());
+    let query = args.get("sql").and_then(|v| v.as_str());
+
+    if query.is_none() && !has_query {
+        let preview = df.head(Some(PREVIEW_ROWS));
+        return Ok(Evaluation::Output(
+            ToolOutput::text(format!(
+                "{} rows × {} columns\n\nColumns: {}\n\nFirst {} rows:\n{}",
+                df.height(),
+                df.width(),
+                df.get_columns()
+                    .iter()
+                    .map(|c| format!("{} ({})", c.name(), c.dtype()))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                preview.height(),
+                markdown_table(&preview)
+            ))
+            .with_data(json!({
+                "row_count": df.height(),
+                "schema": schema_json(&df),
+                "columns": column_names(&preview),
+                "rows": rows_json(&preview),
+            })),
+        ));
+    }
+
+    let lazy = match query {
+        Some(sql) => sql_query(df, sql).map_err(|e| e.to_string()),
+        None => structured_query(df, args),
+    };
+    let mut result = match lazy.and_then(|lf| lf.collect().map_err(|e| e.to_string())) {
+        Ok(result) => result,
+        Err(e) => {
+            return Ok(Evaluation::Output(ToolOutput::error(format!(
+                "Query failed: {}",
+                e
+            ))))
+        }
+    };
+
+    let rows = result.height();
+    if rows <= max_inline_rows {
+        return Ok(Evaluation::Output(
+            ToolOutput::text(format!(
+                "{} rows × {} columns\n\n{}",
+                rows,
+                result.width(),
+                markdown_table(&result)
+            ))
+            .with_data(json!({
+                "row_count": rows,
+                "columns": column_names(&result),
+                "rows": rows_json(&result),
+            })),
+        ));
+    }
+
+    let mut csv = Vec::new();
+    CsvWriter::new(&mut csv)
+        .include_header(true)
+        .finish(&mut result)
+        .map_err(|e| Error::tool_execution(format!("Failed to write CSV: {}", e)))?;
+    Ok(Evaluation::Large {
+        rows,
+        width: result.width(),
+        csv,
+        preview: result.head(Some(PREVIEW_ROWS)),
+    })
+}
+
+#[async_trait]
+impl Tool for TabularTool {
+    fn name(&self) -> &str {
+        "tabular_query"
+    }
+
+    fn description(&self) -> &str {
+        "Analyze a CSV, TSV or XLSX artifact. Without a query, returns the schema and a \
+         preview. Query with `sql` (the table is named `data`) or with structured \
+         `filter`, `group_by`, `aggregate`, `select`, `sort` and `limit` fields. Small \
+         results are returned inline; large ones are saved as a CSV artifact."
+    }
+
+    fn parameters(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "ref_id": {
+                    "type": "string",
+                    "description": "RefID of the CSV/TSV/XLSX artifact"
+                },
+                "format": {
+                    "type": "string",
+                    "enum": ["csv", "tsv", "xlsx"],
+                    "description": "Input format (default: detected from the artifact)"
+                },
+                "sheet": {
+                    "type": "string",
+                    "description": "Worksheet name for XLSX (default: first sheet)"
+                },
+                "sql": {
+                    "type": "string",
+                    "description": "SQL query against the table `data`, e.g. SELECT region, SUM(amount) AS total FROM data GROUP BY region"
+                },
+                "filter": {
+                    "type": "array",
+                    "description": "Row filters, all must match",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "column": {"type": "string"},
+                            "op": {"type": "string", "enum": ["eq", "ne", "gt", "gte", "lt", "lte", "contains", "is_null", "not_null"]},
+                            "value": {}
+                        },
+                        "required": ["column", "op"]
+                    }
+                },
+                "group_by": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Columns to group by"
+                },
+                "aggregate": {
+                    "type": "array",
+                    "description": "Aggregations, per group when group_by is set",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "column": {"type": "string"},
+                            "op": {"type": "string", "enum": ["sum", "mean", "median", "min", "max", "count", "n_unique", "first", "last"]},
+                            "alias": {"type": "string"}
+                        },
+                        "required": ["column", "op"]
+                    }
+                },
+                "select": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Columns to return"
+                },
+                "sort": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "column": {"type": "string"},
+                            "descending": {"type": "boolean"}
+                        },
+                        "required": ["column"]
+                    }
+                },
+                "limit": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Maximum rows to return"
+                }
+            },
+            "required": ["ref_id"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> Result<ToolOutput> {
+        let (bytes, format) = match self.load(&args).await? {
+            Ok(loaded) => loaded,
+            Err(e) => return Ok(ToolOutput::error(e)),
+        };
+
+        let max_inline_rows = self.max_inline_rows;
+        let evaluation =
+            tokio::task::spawn_blocking(move || evaluate(bytes, format, &args, max_inline_rows))
+                .await
+                .map_err(|e| Error::tool_execution(format!("Tabular query panicked: {}", e)))??;
+        let (rows, width, csv, preview) = match evaluation {
+            Evaluation::Output(output) => return Ok(output),
+            Evaluation::Large {
+                rows,
+                width,
+                csv,
+                preview,
+            } => (rows, width, csv, preview),
+        };
+
+        let ref_id = self
+            .store
+            .save_with_type(Bytes::from(csv), "text/csv")
+            .await?;
+
+        let mut output = ToolOutput::text(format!(
+            "{} rows × {} columns (too large to show inline). Full result saved as RefID: {}. \
+             Query it again with tabular_query.\n\nFirst {} rows:\n{}",
+            rows,
+            width,
+            ref_id,
+            preview.height(),
+            markdown_table(&preview)
+        ))
+        .with_data(json!({
+            "row_count": rows,
+            "ref_id": ref_id.as_str(),
+            "columns": column_names(&preview),
+            "rows": rows_json(&preview),
+        }));
+        output.created_refs.push(ref_id);
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multi_agent_store::InMemoryStore;
+
+    const SALES: &str = "region,product,amount\n\
+                         north,widget,10\n\
+                         south,widget,25\n\
+                         north,gadget,5\n\
+                         east,gadget,40\n\
+                         south,gadget,15\n";
+
+    async fn setup(data: &str, content_type: &str) -> (TabularTool, Arc<InMemoryStore>, String) {
+        let store = Arc::new(InMemoryStore::new());
+        let id = store
+            .save_with_type(Bytes::from(data.to_string()), content_type)
+            .await
+            .unwrap();
+        (TabularTool::new(store.clone()), store, id.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_describe_without_query() {
+        let (tool, _, id) = setup(SALES, "text/csv").await;
+        let result = tool.execute(json!({"ref_id": id})).await.unwrap();
+        assert!(result.success);
+        assert!(result.content.contains("5 rows × 3 columns"));
+        let data = result.data.unwrap();
+        assert_eq!(data["schema"][2]["name"], "amount");
+        assert_eq!(data["schema"][2]["dtype"], "i64");
+    }
+
+    #[tokio::test]
+    async fn test_structured_group_and_sort() {
+        let (tool, _, id) = setup(SALES, "text/csv").await;
+        let result = tool
+            .execute(json!({
+                "ref_id": id,
+                "filter": [{"column": "amount", "op": "gte", "value": 10}],
+                "group_by": ["region"],
+                "aggregate": [{"column": "amount", "op": "sum", "alias": "total"}],
+                "sort": [{"column": "total", "descending": true}, {"column": "region"}]
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{}", result.content);
+        let data = result.data.unwrap();
+        assert_eq!(data["columns"], json!(["region", "total"]));
+        assert_eq!(
+            data["rows"],
+            json!([["east", 40], ["south", 40], ["north", 10]])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sql_query() {
+        let (tool, _, id) = setup(SALES, "text/csv").await;
+        let result = tool
+            .execute(json!({
+                "ref_id": id,
+                "sql": "SELECT product, COUNT(*) AS n FROM data WHERE product = 'gadget' GROUP BY product"
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{}", result.content);
+        assert_eq!(result.data.unwrap()["rows"], json!([["gadget", 3]]));
+
+        let result = tool
+            .execute(json!({"ref_id": id, "sql": "SELECT missing FROM data"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.content.starts_with("Query failed"));
+    }
+
+    #[tokio::test]
+    async fn test_large_result_by_reference() {
+        let csv: String = std::iter::once("n\n".to_string())
+            .chain((0..100).map(|i| format!("{}\n", i)))
+            .collect();
+        let (tool, store, id) = setup(&csv, "text/tab-separated-values").await;
+        let tool = tool.with_max_inline_rows(20);
+
+        let result = tool
+            .execute(json!({"ref_id": id, "filter": [{"column": "n", "op": "lt", "value": 50}]}))
+            .await
+            .unwrap();
+        assert!(result.success, "{}", result.content);
+        assert_eq!(result.created_refs.len(), 1);
+        let data = result.data.unwrap();
+        assert_eq!(data["row_count"], 50);
+        assert_eq!(data["rows"].as_array().unwrap().len(), PREVIEW_ROWS);
+
+        let stored = store.load(&result.created_refs[0]).await.unwrap().unwrap();
+        let text = String::from_utf8_lossy(&stored);
+        assert!(text.starts_with("n\n0\n"));
+        assert_eq!(text.lines().count(), 51);
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_reported() {
+        let (tool, _, id) = setup(SALES, "text/csv").await;
+        let result = tool
+            .execute(json!({"ref_id": id, "filter": [{"column": "amount", "op": "between"}]}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.content.contains("unknown filter op"));
+
+        let result = tool.execute(json!({"ref_id": "missing"})).await.unwrap();
+        assert!(!result.success);
+        assert!(tool.execute(json!({})).await.is_err());
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(
+            TableFormat::detect(None, b"PK\x03\x04rest"),
+            TableFormat::Xlsx
+        );
+        assert_eq!(
+            TableFormat::detect(Some(XLSX_CONTENT_TYPE), b""),
+            TableFormat::Xlsx
+        );
+        assert_eq!(
+            TableFormat::detect(Some("text/tab-separated-values"), b"a\tb"),
+            TableFormat::Tsv
+        );
+        assert_eq!(
+            TableFormat::detect(Some("text/plain"), b"a,b"),
+            TableFormat::Csv
+        );
+        assert!(read_xlsx(Bytes::from_static(b"not a workbook"), None).is_err());
+    }
+}
@@ -0,0 +1,19 @@
+-- Sessions: the full session as JSON, with the fields stores filter on
+-- kept in columns.
+CREATE TABLE IF NOT EXISTS sessions (
+    id TEXT PRIMARY KEY,
+    user_id TEXT,
+    status TEXT NOT NULL,
+    updated_at BIGINT NOT NULL,
+    data JSONB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS sessions_status_idx ON sessions (status);
+CREATE INDEX IF NOT EXISTS sessions_user_id_idx ON sessions (user_id);
+CREATE INDEX IF NOT EXISTS sessions_updated_at_idx ON sessions (updated_at);
+
+-- Admin provider configurations. API keys stay in the secrets manager.
+CREATE TABLE IF NOT EXISTS providers (
+    id TEXT PRIMARY KEY,
+    data JSONB NOT NULL
+);
@@ -0,0 +1,243 @@
+//! Catalog of saved artifacts by session and user.
+//!
+//! Artifact stores are keyed by RefId only, so neither the model nor the user
+//! can find an earlier output without the id. [`CatalogArtifactStore`] wraps a
+//! store and records every write in an [`ArtifactCatalog`] under the owner in
+//! scope ([`ArtifactOwner::current`]), with a short description taken from the
+//! first line of text content.
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use dashmap::DashMap;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use multi_agent_core::{
+    traits::{ArtifactFilter, ArtifactMetadata, ArtifactStore},
+    types::{ArtifactOwner, RefId},
+    Result,
+};
+
+use crate::retention::Erasable;
+
+/// Characters kept from the first line of text content.
+const DESCRIPTION_CHARS: usize = 120;
+
+/// One cataloged artifact.
+#[derive(Debug, Clone, Serialize)]
+pub struct ArtifactEntry {
+    pub ref_id: String,
+    pub content_type: String,
+    pub size: usize,
+    pub created_at: i64,
+    /// First line of text content; `None` for binary artifacts.
+    pub description: Option<String>,
+    pub user_id: Option<String>,
+    pub session_id: Option<String>,
+}
+
+/// Describe text content by its first non-empty line.
+fn describe(data: &[u8], content_type: &str) -> Option<String> {
+    let textual = content_type.starts_with("text/")
+        || content_type.contains("json")
+        || content_type.contains("xml")
+        || content_type == "application/octet-stream";
+    if !textual {
+        return None;
+    }
+    let head = &data[..data.len().min(4 * DESCRIPTION_CHARS)];
+    let text = match std::str::from_utf8(head) {
+        Ok(text) => text,
+        // A multi-byte character may straddle the cut
+        Err(e) if e.error_len().is_none() => std::str::from_utf8(&head[..e.valid_up_to()]).ok()?,
+        Err(_) => return None,
+    };
+    let line = text.lines().map(str::trim).find(|l| !l.is_empty())?;
+    Some(line.chars().take(DESCRIPTION_CHARS).collect())
+}
+
+/// In-memory index of artifacts by session and user.
+#[derive(Debug, Default)]
+pub struct ArtifactCatalog {
+    entries: DashMap<String, ArtifactEntry>,
+}
+
+impl ArtifactCatalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a write of `data` under the owner in scope.
+    pub fn record(&self, id: &RefId, data: &[u8], content_type: &str) {
+        let owner = ArtifactOwner::current().unwrap_or_default();
+        self.entries.insert(
+            id.0.clone(),
+            ArtifactEntry {
+                ref_id: id.0.clone(),
+                content_type: content_type.to_string(),
+                size: data.len(),
+                created_at: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs() as i64)
+                    .unwrap_or(0),
+                description: describe(data, content_type),
+                user_id: owner.user_id,
+                session_id: owner.session_id,
+            },
+        );
+    }
+
+    pub fn remove(&self, id: &RefId) {
+        self.entries.remove(&id.0);
+    }
+
+    fn matching(&self, keep: impl Fn(&ArtifactEntry) -> bool) -> Vec<ArtifactEntry> {
+        let mut entries: Vec<ArtifactEntry> = self
+            .entries
+            .iter()
+            .filter(|e| keep(e.value()))
+            .map(|e| e.value().clone())
+            .collect();
+        entries.sort_by(|a, b| {
+            a.created_at
+                .cmp(&b.created_at)
+                .then_with(|| a.ref_id.cmp(&b.ref_id))
+        });
+        entries
+    }
+
+    /// Artifacts created in a session, oldest first.
+    pub fn for_session(&self, session_id: &str) -> Vec<ArtifactEntry> {
+        self.matching(|e| e.session_id.as_deref() == Some(session_id))
+    }
+
+    /// Artifacts owned by a user, oldest first.
+    pub fn for_user(&self, user_id: &str) -> Vec<ArtifactEntry> {
+        self.matching(|e| e.user_id.as_deref() == Some(user_id))
+    }
+}
+
+/// Artifact store wrapper that records writes in an [`ArtifactCatalog`].
+pub struct CatalogArtifactStore {
+    inner: Arc<dyn ArtifactStore>,
+    catalog: Arc<ArtifactCatalog>,
+}
+
+impl CatalogArtifactStore {
+    pub fn new(inner: Arc<dyn ArtifactStore>, catalog: Arc<ArtifactCatalog>) -> Self {
+        Self { inner, catalog }
+    }
+}
+
+#[async_trait]
+impl ArtifactStore for CatalogArtifactStore {
+    async fn save(&self, data: Bytes) -> Result<RefId> {
+        let id = self.inner.save(data.clone()).await?;
+        self.catalog.record(&id, &data, "application/octet-stream");
+        Ok(id)
+    }
+
+    async fn save_with_id(&self, id: &RefId, data: Bytes) -> Result<()> {
+        self.inner.save_with_id(id, data.clone()).await?;
+        self.catalog.record(id, &data, "application/octet-stream");
+        Ok(())
+    }
+
+    async fn save_with_type(&self, data: Bytes, content_type: &str) -> Result<RefId> {
+        let id = self
+            .inner
+            .save_with_type(data.clone(), content_type)
+            .await?;
+        self.catalog.record(&id, &data, content_type);
+        Ok(id)
+    }
+
+    async fn load(&self, id: &RefId) -> Result<Option<Bytes>> {
+        self.inner.load(id).await
+    }
+
+    async fn load_range(&self, id: &RefId, start: u64, end: u64) -> Result<Option<Bytes>> {
+        self.inner.load_range(id, start, end).await
+    }
+
+    async fn presigned_url(
+        &self,
+        id: &RefId,
+        expires_in: std::time::Duration,
+    ) -> Result<Option<String>> {
+        self.inner.presigned_url(id, expires_in).await
+    }
+
+    async fn delete(&self, id: &RefId) -> Result<()> {
+        self.inner.delete(id).await?;
+        self.catalog.remove(id);
+        Ok(())
+    }
+
+    async fn exists(&self, id: &RefId) -> Result<bool> {
+        self.inner.exists(id).await
+    }
+
+    async fn metadata(&self, id: &RefId) -> Result<Option<ArtifactMetadata>> {
+        self.inner.metadata(id).await
+    }
+
+    async fn list(&self, filter: ArtifactFilter) -> Result<Vec<ArtifactMetadata>> {
+        self.inner.list(filter).await
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        self.inner.health_check().await
+    }
+}
+
+#[async_trait]
+impl Erasable for CatalogArtifactStore {
+    async fn erase_user(&self, user_id: &str) -> Result<usize> {
+        let erased = self.inner.erase_user(user_id).await?;
+        self.catalog
+            .entries
+            .retain(|_, e| e.user_id.as_deref() != Some(user_id));
+        Ok(erased)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InMemoryStore;
+
+    #[tokio::test]
+    async fn test_catalogs_writes_by_session() {
+        let catalog = Arc::new(ArtifactCatalog::new());
+        let store = CatalogArtifactStore::new(Arc::new(InMemoryStore::new()), catalog.clone());
+
+        let report = ArtifactOwner::new(Some("alice".into()), Some("s1".into()))
+            .scope(store.save_with_type(Bytes::from("\n# Quarterly report\nbody"), "text/markdown"))
+            .await
+            .unwrap();
+        let image = ArtifactOwner::new(Some("alice".into()), Some("s1".into()))
+            .scope(store.save_with_type(Bytes::from_static(b"\x89PNG"), "image/png"))
+            .await
+            .unwrap();
+        ArtifactOwner::new(Some("bob".into()), Some("s2".into()))
+            .scope(store.save(Bytes::from("other")))
+            .await
+            .unwrap();
+
+        let entries = catalog.for_session("s1");
+        assert_eq!(entries.len(), 2);
+        let described = entries.iter().find(|e| e.ref_id == report.0).unwrap();
+        assert_eq!(described.description.as_deref(), Some("# Quarterly report"));
+        let binary = entries.iter().find(|e| e.ref_id == image.0).unwrap();
+        assert_eq!(binary.description, None);
+        assert_eq!(binary.size, 4);
+
+        store.delete(&image).await.unwrap();
+        assert_eq!(catalog.for_session("s1").len(), 1);
+        store.erase_user("alice").await.unwrap();
+        assert!(catalog.for_user("alice").is_empty());
+        assert_eq!(catalog.for_user("bob").len(), 1);
+    }
+}
@@ -0,0 +1,633 @@
+//! Native Google Cloud Storage and Azure Blob implementations of ArtifactStore.
+//!
+//! Both backends talk to their provider's own API (GCS JSON API, Azure Blob REST)
+//! rather than going through an S3 compatibility layer.
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::{StreamExt, TryStreamExt};
+use object_store::{
+    azure::MicrosoftAzureBuilder, gcp::GoogleCloudStorageBuilder, path::Path, signer::Signer,
+    Attribute, Attributes, GetOptions, ObjectMeta, ObjectStore, PutOptions, PutPayload,
+};
+use secrecy::ExposeSecret;
+use std::sync::Arc;
+
+use crate::retention::{Erasable, Prunable};
+use multi_agent_core::{
+    config::{AzureBlobStoreConfig, GcsStoreConfig},
+    traits::{ArtifactFilter, ArtifactMetadata, ArtifactStore, StorageTier},
+    types::{ArtifactOwner, RefId},
+    Error, Result,
+};
+
+/// Key segment under which per-user ownership markers are written.
+const OWNER_INDEX: &str = "_owners";
+/// Custom object metadata keys.
+const META_OWNER_USER: &str = "owner-user-id";
+const META_SESSION: &str = "session-id";
+
+/// Cloud provider backing a [`CloudArtifactStore`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloudProvider {
+    /// Google Cloud Storage.
+    Gcs,
+    /// Azure Blob Storage.
+    AzureBlob,
+    /// In-memory backend (testing).
+    Memory,
+}
+
+impl CloudProvider {
+    fn as_label(self) -> &'static str {
+        match self {
+            Self::Gcs => "gcs",
+            Self::AzureBlob => "azure_blob",
+            Self::Memory => "memory",
+        }
+    }
+}
+
+/// Artifact store backed by a native cloud object store.
+pub struct CloudArtifactStore {
+    store: Arc<dyn ObjectStore>,
+    /// URL signer for direct downloads, when the provider supports it.
+    signer: Option<Arc<dyn Signer>>,
+    provider: CloudProvider,
+    prefix: String,
+}
+
+impl CloudArtifactStore {
+    /// Create a Google Cloud Storage artifact store.
+    pub fn gcs(config: &GcsStoreConfig) -> Result<Self> {
+        let mut builder = GoogleCloudStorageBuilder::from_env().with_bucket_name(&config.bucket);
+        if let Some(path) = &config.service_account_path {
+            builder = builder.with_service_account_path(path);
+        }
+        let store = Arc::new(
+            builder
+                .build()
+                .map_err(|e| Error::storage(format!("GCS init failed: {}", e)))?,
+        );
+
+        Ok(
+            Self::new_with_store(store.clone(), CloudProvider::Gcs, &config.prefix)
+                .with_signer(store),
+        )
+    }
+
+    /// Create an Azure Blob Storage artifact store.
+    pub fn azure(config: &AzureBlobStoreConfig) -> Result<Self> {
+        let mut builder = MicrosoftAzureBuilder::from_env()
+            .with_account(&config.account)
+            .with_container_name(&config.container);
+        if let Some(key) = &config.access_key {
+            builder = builder.with_access_key(key.expose_secret());
+        }
+        if let Some(endpoint) = &config.endpoint {
+            builder = builder
+                .with_endpoint(endpoint.clone())
+                .with_allow_http(endpoint.starts_with("http://"));
+        }
+        let store = Arc::new(
+            builder
+                .build()
+                .map_err(|e| Error::storage(format!("Azure Blob init failed: {}", e)))?,
+        );
+
+        Ok(
+            Self::new_with_store(store.clone(), CloudProvider::AzureBlob, &config.prefix)
+                .with_signer(store),
+        )
+    }
+
+    /// Create with a custom object store (for testing/custom config).
+    pub fn new_with_store(
+        store: Arc<dyn ObjectStore>,
+        provider: CloudProvider,
+        prefix: &str,
+    ) -> Self {
+        Self {
+            store,
+            signer: None,
+            provider,
+            prefix: prefix.trim_matches('/').to_string(),
+        }
+    }
+
+    /// Set the URL signer used for pre-signed downloads.
+    pub fn with_signer(mut self, signer: Arc<dyn Signer>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// The provider backing this store.
+    pub fn provider(&self) -> CloudProvider {
+        self.provider
+    }
+
+    fn path(&self, id: &RefId) -> Path {
+        self.prefixed(id.as_str())
+    }
+
+    fn prefixed(&self, key: &str) -> Path {
+        if self.prefix.is_empty() {
+            Path::from(key)
+        } else {
+            Path::from(format!("{}/{}", self.prefix, key))
+        }
+    }
+
+    /// Prefix of the ownership markers for `user_id`; a marker's suffix is the artifact's RefId.
+    fn owner_index_prefix(&self, user_id: &str) -> Path {
+        self.prefixed(&format!("{}/{}", OWNER_INDEX, user_id))
+    }
+
+    fn owner_marker(&self, user_id: &str, id: &RefId) -> Path {
+        self.prefixed(&format!("{}/{}/{}", OWNER_INDEX, user_id, id))
+    }
+
+    /// The RefId an artifact or marker path under `prefix` stands for.
+    fn relative_id(location: &Path, prefix: &Path) -> Option<RefId> {
+        let parts: Vec<String> = location
+            .prefix_match(prefix)?
+            .map(|p| p.as_ref().to_string())
+            .collect();
+        Some(RefId::from_string(parts.join("/")))
+    }
+
+    fn map_err(&self, op: &str, err: object_store::Error) -> Error {
+        let provider = self.provider.as_label();
+        match err {
+            object_store::Error::NotFound { path, .. } => Error::ArtifactNotFound(path),
+            object_store::Error::PermissionDenied { path, .. }
+            | object_store::Error::Unauthenticated { path, .. } => {
+                Error::access_denied(format!("{} {} {}", provider, op, path))
+            }
+            other => Error::storage(format!("{} {} failed: {}", provider, op, other)),
+        }
+    }
+
+    /// Save an artifact, recording the current owner (if any) in object metadata
+    /// and in the owner index.
+    async fn save_object(&self, id: &RefId, data: Bytes, content_type: Option<&str>) -> Result<()> {
+        let owner = ArtifactOwner::current();
+        self.put(self.path(id), data, content_type, owner.as_ref())
+            .await?;
+
+        if let Some(user_id) = owner.and_then(|o| o.user_id) {
+            self.put(self.owner_marker(&user_id, id), Bytes::new(), None, None)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn put(
+        &self,
+        path: Path,
+        data: Bytes,
+        content_type: Option<&str>,
+        owner: Option<&ArtifactOwner>,
+    ) -> Result<()> {
+        let mut attributes = Attributes::new();
+        if let Some(ct) = content_type {
+            attributes.insert(Attribute::ContentType, ct.to_string().into());
+        }
+        if let Some(user_id) = owner.and_then(|o| o.user_id.clone()) {
+            attributes.insert(Attribute::Metadata(META_OWNER_USER.into()), user_id.into());
+        }
+        if let Some(session_id) = owner.and_then(|o| o.session_id.clone()) {
+            attributes.insert(Attribute::Metadata(META_SESSION.into()), session_id.into());
+        }
+        let opts = PutOptions {
+            attributes,
+            ..Default::default()
+        };
+        self.store
+            .put_opts(&path, PutPayload::from(data), opts)
+            .await
+            .map(|_| ())
+            .map_err(|e| self.map_err("put", e))
+    }
+
+    /// Delete every object under `prefix` that satisfies `filter`.
+    async fn delete_matching<P>(&self, prefix: Option<&Path>, filter: P) -> Result<usize>
+    where
+        P: Fn(&ObjectMeta) -> bool + Send + Sync,
+    {
+        let locations: Vec<Path> = self
+            .store
+            .list(prefix)
+            .try_filter_map(|meta| {
+                let keep = filter(&meta);
+                async move { Ok(keep.then_some(meta.location)) }
+            })
+            .try_collect()
+            .await
+            .map_err(|e| self.map_err("list", e))?;
+
+        let count = locations.len();
+        if count == 0 {
+            return Ok(0);
+        }
+
+        let mut deleted = self
+            .store
+            .delete_stream(futures::stream::iter(locations.into_iter().map(Ok)).boxed());
+        while let Some(result) = deleted.next().await {
+            result.map_err(|e| self.map_err("delete", e))?;
+        }
+
+        Ok(count)
+    }
+}
+
+#[async_trait]
+impl ArtifactStore for CloudArtifactStore {
+    async fn save(&self, data: Bytes) -> Result<RefId> {
+        let id = RefId::new();
+        self.save_object(&id, data, None).await?;
+        Ok(id)
+    }
+
+    async fn save_with_id(&self, id: &RefId, data: Bytes) -> Result<()> {
+        self.save_object(id, data, None).await
+    }
+
+    async fn save_with_type(&self, data: Bytes, content_type: &str) -> Result<RefId> {
+        let id = RefId::new();
+        self.save_object(&id, data, Some(content_type)).await?;
+        Ok(id)
+    }
+
+    async fn load(&self, id: &RefId) -> Result<Option<Bytes>> {
+        let result = match self.store.get(&self.path(id)).await {
+            Ok(result) => result,
+            Err(object_store::Error::NotFound { .. }) => return Ok(None),
+            Err(e) => return Err(self.map_err("get", e)),
+        };
+        result
+            .bytes()
+            .await
+            .map(Some)
+            .map_err(|e| self.map_err("get", e))
+    }
+
+    async fn load_range(&self, id: &RefId, start: u64, end: u64) -> Result<Option<Bytes>> {
+        let path = self.path(id);
+        let size = match self.store.head(&path).await {
+            Ok(meta) => meta.size as u64,
+            Err(object_store::Error::NotFound { .. }) => return Ok(None),
+            Err(e) => return Err(self.map_err("head", e)),
+        };
+        let (start, end) = (start.min(size), end.saturating_add(1).min(size));
+        if start >= end {
+            return Ok(Some(Bytes::new()));
+        }
+        match self
+            .store
+            .get_range(&path, start as usize..end as usize)
+            .await
+        {
+            Ok(data) => Ok(Some(data)),
+            Err(object_store::Error::NotFound { .. }) => Ok(None),
+            Err(e) => Err(self.map_err("get_range", e)),
+        }
+    }
+
+    async fn presigned_url(
+        &self,
+        id: &RefId,
+        expires_in: std::time::Duration,
+    ) -> Result<Option<String>> {
+        let Some(signer) = &self.signer else {
+            return Ok(None);
+        };
+        signer
+            .signed_url(http::Method::GET, &self.path(id), expires_in)
+            .await
+            .map(|url| Some(url.to_string()))
+            .map_err(|e| self.map_err("sign", e))
+    }
+
+    async fn delete(&self, id: &RefId) -> Result<()> {
+        // Drop the ownership marker too, so erase_user doesn't count stale entries.
+        if let Ok(Some(ArtifactMetadata {
+            owner_user_id: Some(user_id),
+            ..
+        })) = self.metadata(id).await
+        {
+            match self.store.delete(&self.owner_marker(&user_id, id)).await {
+                Ok(()) | Err(object_store::Error::NotFound { .. }) => {}
+                Err(e) => return Err(self.map_err("delete", e)),
+            }
+        }
+
+        match self.store.delete(&self.path(id)).await {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(e) => Err(self.map_err("delete", e)),
+        }
+    }
+
+    async fn exists(&self, id: &RefId) -> Result<bool> {
+        match self.store.head(&self.path(id)).await {
+            Ok(_) => Ok(true),
+            Err(object_store::Error::NotFound { .. }) => Ok(false),
+            Err(e) => Err(self.map_err("head", e)),
+        }
+    }
+
+    async fn metadata(&self, id: &RefId) -> Result<Option<ArtifactMetadata>> {
+        let opts = GetOptions {
+            head: true,
+            ..Default::default()
+        };
+        let result = match self.store.get_opts(&self.path(id), opts).await {
+            Ok(result) => result,
+            Err(object_store::Error::NotFound { .. }) => return Ok(None),
+            Err(e) => return Err(self.map_err("head", e)),
+        };
+
+        Ok(Some(ArtifactMetadata {
+            id: id.clone(),
+            size: result.meta.size,
+            content_type: result
+                .attributes
+                .get(&Attribute::ContentType)
+                .map(|v| v.to_string())
+                .unwrap_or_else(|| "application/octet-stream".to_string()),
+            created_at: result.meta.last_modified.timestamp(),
+            tier: StorageTier::Cold,
+            owner_user_id: result
+                .attributes
+                .get(&Attribute::Metadata(META_OWNER_USER.into()))
+                .map(|v| v.to_string()),
+            session_id: result
+                .attributes
+                .get(&Attribute::Metadata(META_SESSION.into()))
+                .map(|v| v.to_string()),
+        }))
+    }
+
+    async fn list(&self, filter: ArtifactFilter) -> Result<Vec<ArtifactMetadata>> {
+        // With an owner, walk that user's markers instead of the whole prefix.
+        let (prefix, cursor) = match &filter.owner_user_id {
+            Some(user_id) => (
+                self.owner_index_prefix(user_id),
+                filter
+                    .after_id
+                    .as_ref()
+                    .map(|id| self.owner_marker(user_id, id)),
+            ),
+            None => (
+                Path::from(self.prefix.as_str()),
+                filter.after_id.as_ref().map(|id| self.path(id)),
+            ),
+        };
+        let listed_prefix = (!prefix.as_ref().is_empty()).then_some(&prefix);
+        let listing = match &cursor {
+            Some(offset) => self.store.list_with_offset(listed_prefix, offset),
+            None => self.store.list(listed_prefix),
+        };
+        let mut objects: Vec<ObjectMeta> = listing
+            .try_collect()
+            .await
+            .map_err(|e| self.map_err("list", e))?;
+        // Not every backend lists in key order.
+        objects.sort_by(|a, b| a.location.cmp(&b.location));
+
+        let index_prefix = self.prefixed(OWNER_INDEX);
+        let limit = filter.limit.unwrap_or(usize::MAX);
+        let mut artifacts = Vec::new();
+        for object in objects {
+            if filter.owner_user_id.is_none()
+                && (object.location.prefix_matches(&index_prefix)
+                    || !filter.created_in_range(object.last_modified.timestamp()))
+            {
+                continue;
+            }
+            let Some(id) = Self::relative_id(&object.location, &prefix) else {
+                continue;
+            };
+            let Some(meta) = self.metadata(&id).await? else {
+                continue;
+            };
+            if filter.matches(&meta) {
+                artifacts.push(meta);
+                if artifacts.len() >= limit {
+                    break;
+                }
+            }
+        }
+        Ok(artifacts)
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        let prefix = (!self.prefix.is_empty()).then(|| Path::from(self.prefix.as_str()));
+        self.store
+            .list_with_delimiter(prefix.as_ref())
+            .await
+            .map(|_| ())
+            .map_err(|e| self.map_err("list", e))
+    }
+}
+
+#[async_trait]
+impl Prunable for CloudArtifactStore {
+    async fn prune(&self, max_age: std::time::Duration) -> Result<usize> {
+        let cutoff = chrono::Utc::now()
+            - chrono::Duration::from_std(max_age).unwrap_or(chrono::Duration::MAX);
+        let prefix = (!self.prefix.is_empty()).then(|| Path::from(self.prefix.as_str()));
+
+        self.delete_matching(prefix.as_ref(), |meta| meta.last_modified < cutoff)
+            .await
+    }
+}
+
+#[async_trait]
+impl Erasable for CloudArtifactStore {
+    async fn erase_user(&self, user_id: &str) -> Result<usize> {
+        // Artifacts recorded in the owner index: delete each artifact, then its marker.
+        let index_prefix = self.owner_index_prefix(user_id);
+        let markers: Vec<Path> = self
+            .store
+            .list(Some(&index_prefix))
+            .map_ok(|meta| meta.location)
+            .try_collect()
+            .await
+            .map_err(|e| self.map_err("list", e))?;
+
+        let mut owned = 0;
+        for marker in markers {
+            let Some(id) = Self::relative_id(&marker, &index_prefix) else {
+                continue;
+            };
+            for path in [self.path(&id), marker] {
+                match self.store.delete(&path).await {
+                    Ok(()) | Err(object_store::Error::NotFound { .. }) => {}
+                    Err(e) => return Err(self.map_err("delete", e)),
+                }
+            }
+            owned += 1;
+        }
+
+        // Legacy user-namespaced keys: prefix/user_id/
+        let legacy = self
+            .delete_matching(Some(&self.prefixed(user_id)), |_| true)
+            .await?;
+
+        Ok(owned + legacy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use object_store::memory::InMemory;
+
+    fn store(prefix: &str) -> CloudArtifactStore {
+        CloudArtifactStore::new_with_store(Arc::new(InMemory::new()), CloudProvider::Memory, prefix)
+    }
+
+    #[tokio::test]
+    async fn test_roundtrip_and_metadata() {
+        let store = store("artifacts");
+
+        let id = store
+            .save_with_type(Bytes::from("hello"), "text/plain")
+            .await
+            .unwrap();
+        assert_eq!(store.load(&id).await.unwrap(), Some(Bytes::from("hello")));
+        assert!(store.exists(&id).await.unwrap());
+
+        let meta = store.metadata(&id).await.unwrap().unwrap();
+        assert_eq!(meta.size, 5);
+        assert_eq!(meta.content_type, "text/plain");
+        assert_eq!(meta.tier, StorageTier::Cold);
+
+        store.delete(&id).await.unwrap();
+        assert!(!store.exists(&id).await.unwrap());
+        assert_eq!(store.load(&id).await.unwrap(), None);
+        assert!(store.metadata(&id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_erase_user_only_removes_user_prefix() {
+        let inner: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let store = CloudArtifactStore::new_with_store(inner.clone(), CloudProvider::Memory, "p");
+
+        for path in ["p/alice/1", "p/alice/2", "p/bob/1"] {
+            inner
+                .put(&Path::from(path), PutPayload::from_static(b"x"))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(store.erase_user("alice").await.unwrap(), 2);
+        assert!(inner.head(&Path::from("p/bob/1")).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_prune() {
+        let store = store("");
+        store.save(Bytes::from("a")).await.unwrap();
+
+        assert_eq!(
+            store
+                .prune(std::time::Duration::from_secs(3600))
+                .await
+                .unwrap(),
+            0
+        );
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        assert_eq!(store.prune(std::time::Duration::ZERO).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_owner_metadata_and_erase() {
+        let store = store("artifacts");
+        let owner = ArtifactOwner::new(Some("alice".into()), Some("s1".into()));
+
+        let owned = owner
+            .scope(store.save_with_type(Bytes::from("report"), "text/plain"))
+            .await
+            .unwrap();
+        let other = store.save(Bytes::from("anonymous")).await.unwrap();
+
+        let meta = store.metadata(&owned).await.unwrap().unwrap();
+        assert_eq!(meta.owner_user_id.as_deref(), Some("alice"));
+        assert_eq!(meta.session_id.as_deref(), Some("s1"));
+
+        assert_eq!(store.erase_user("alice").await.unwrap(), 1);
+        assert!(!store.exists(&owned).await.unwrap());
+        assert!(store.exists(&other).await.unwrap());
+        assert_eq!(store.erase_user("alice").await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_list_skips_owner_index() {
+        let store = store("artifacts");
+        let owner = ArtifactOwner::new(Some("alice".into()), Some("s1".into()));
+        for id in ["b", "a"] {
+            owner
+                .clone()
+                .scope(store.save_with_id(&RefId::from_string(id), Bytes::from(id)))
+                .await
+                .unwrap();
+        }
+        store
+            .save_with_id(&RefId::from_string("c"), Bytes::from("c"))
+            .await
+            .unwrap();
+
+        let ids = |artifacts: Vec<ArtifactMetadata>| {
+            artifacts
+                .into_iter()
+                .map(|m| m.id.0)
+                .collect::<Vec<String>>()
+        };
+        let all = store.list(ArtifactFilter::default()).await.unwrap();
+        assert_eq!(ids(all), ["a", "b", "c"]);
+
+        let owned = store
+            .list(ArtifactFilter {
+                owner_user_id: Some("alice".into()),
+                after_id: Some(RefId::from_string("a")),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(owned[0].owner_user_id.as_deref(), Some("alice"));
+        assert_eq!(ids(owned), ["b"]);
+    }
+
+    #[tokio::test]
+    async fn test_load_range() {
+        let store = store("");
+        let id = store.save(Bytes::from("0123456789")).await.unwrap();
+
+        assert_eq!(
+            store.load_range(&id, 2, 4).await.unwrap(),
+            Some(Bytes::from("234"))
+        );
+        assert_eq!(
+            store.load_range(&id, 8, 100).await.unwrap(),
+            Some(Bytes::from("89"))
+        );
+        assert_eq!(
+            store
+                .load_range(&RefId::from_string("missing"), 0, 1)
+                .await
+                .unwrap(),
+            None
+        );
+        // The in-memory backend has no signer.
+        assert_eq!(
+            store
+                .presigned_url(&id, std::time::Duration::from_secs(60))
+                .await
+                .unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! Binary encoding of sessions for Redis.
+//!
+//! Encoded values start with a header naming the encoding version, the
+//! serialization format and the compression:
+//!
+//! ```text
+//! 0x00 'M' 'S' <version> <format> <compression> <payload...>
+//! ```
+//!
+//! JSON text never starts with a NUL byte, so values without the header are
+//! read as the legacy untagged JSON. Plain uncompressed JSON is still written
+//! untagged, which keeps the default encoding readable by older instances.
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use multi_agent_core::config::{SessionCompression, SessionEncodingConfig, SessionFormat};
+use multi_agent_core::{Error, Result};
+
+const MAGIC: &[u8] = b"\0MS";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = MAGIC.len() + 3;
+
+const FORMAT_JSON: u8 = 0;
+const FORMAT_MSGPACK: u8 = 1;
+const FORMAT_CBOR: u8 = 2;
+
+const COMPRESSION_NONE: u8 = 0;
+const COMPRESSION_ZSTD: u8 = 1;
+
+/// Encoder and decoder for stored sessions.
+#[derive(Debug, Clone)]
+pub struct SessionCodec {
+    format: SessionFormat,
+    compression: SessionCompression,
+    zstd_level: i32,
+    min_compress_bytes: usize,
+}
+
+impl Default for SessionCodec {
+    fn default() -> Self {
+        Self::from_config(&SessionEncodingConfig::default())
+    }
+}
+
+impl SessionCodec {
+    pub fn from_config(config: &SessionEncodingConfig) -> Self {
+        Self {
+            format: config.format,
+            compression: config.compression,
+            zstd_level: config.zstd_level,
+            min_compress_bytes: config.min_compress_bytes,
+        }
+    }
+
+    /// Serialize `value` with the configured format and compression.
+    pub fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>> {
+        let (format, payload) = match self.format {
+            SessionFormat::Json => (FORMAT_JSON, serde_json::to_vec(value).map_err(encode_err)?),
+            SessionFormat::Msgpack => (
+                FORMAT_MSGPACK,
+                rmp_serde::to_vec_named(value).map_err(encode_err)?,
+            ),
+            SessionFormat::Cbor => {
+                let mut buf = Vec::new();
+                ciborium::into_writer(value, &mut buf).map_err(encode_err)?;
+                (FORMAT_CBOR, buf)
+            }
+        };
+
+        let compress = self.compression == SessionCompression::Zstd
+            && payload.len() >= self.min_compress_bytes;
+        if format == FORMAT_JSON && !compress {
+            return Ok(payload);
+        }
+        let (compression, payload) = if compress {
+            let compressed = zstd::bulk::compress(&payload, self.zstd_level)
+                .map_err(|e| Error::storage(format!("Failed to compress session: {}", e)))?;
+            (COMPRESSION_ZSTD, compressed)
+        } else {
+            (COMPRESSION_NONE, payload)
+        };
+
+        let mut out = Vec::with_capacity(HEADER_LEN + payload.len());
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&[VERSION, format, compression]);
+        out.extend_from_slice(&payload);
+        Ok(out)
+    }
+
+    /// Deserialize a value written with any encoding, including legacy JSON.
+    pub fn decode<T: DeserializeOwned>(&self, data: &[u8]) -> Result<T> {
+        let Some(header) = data.strip_prefix(MAGIC) else {
+            return serde_json::from_slice(data).map_err(decode_err);
+        };
+        let [version, format, compression, ..] = *header else {
+            return Err(Error::storage("Truncated session header"));
+        };
+        if version != VERSION {
+            return Err(Error::storage(format!(
+                "Unsupported session encoding version {}",
+                version
+            )));
+        }
+
+        let body = &data[HEADER_LEN..];
+        let decompressed;
+        let payload = match compression {
+            COMPRESSION_NONE => body,
+            COMPRESSION_ZSTD => {
+                decompressed = zstd::stream::decode_all(body)
+                    .map_err(|e| Error::storage(format!("Failed to decompress session: {}", e)))?;
+                &decompressed[..]
+            }
+            other => {
+                return Err(Error::storage(format!(
+                    "Unknown session compression {}",
+                    other
+                )))
+            }
+        };
+
+        match format {
+            FORMAT_JSON => serde_json::from_slice(payload).map_err(decode_err),
+            FORMAT_MSGPACK => rmp_serde::from_slice(payload).map_err(decode_err),
+            FORMAT_CBOR => ciborium::from_reader(payload).map_err(decode_err),
+            other => Err(Error::storage(format!("Unknown session format {}", other))),
+        }
+    }
+}
+
+fn encode_err(e: impl std::fmt::Display) -> Error {
+    Error::storage(format!("Failed to serialize session: {}", e))
+}
+
+fn decode_err(e: impl std::fmt::Display) -> Error {
+    Error::storage(format!("Failed to deserialize session: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use multi_agent_core::types::{
+        HistoryEntry, Session, SessionStatus, TaskState, TokenUsage, ToolCallInfo,
+    };
+    use std::sync::Arc;
+
+    fn session() -> Session {
+        let history = (0..50)
+            .map(|i| HistoryEntry {
+                role: if i % 2 == 0 { "user" } else { "assistant" }.into(),
+                content: Arc::new(format!("message {} about the quarterly report", i)),
+                tool_call: (i % 5 == 0).then(|| ToolCallInfo {
+                    name: "search".into(),
+                    arguments: serde_json::json!({ "query": "report", "limit": i, "exact": true }),
+                    result: Some(Arc::new("found".into())),
+                }),
+                timestamp: 1_700_000_000 + i,
+            })
+            .collect();
+        Session {
+            id: "s1".into(),
+            trace_id: "t1".into(),
+            user_id: Some("alice".into()),
+            workspace_id: None,
+            dry_run: false,
+            status: SessionStatus::Running,
+            history,
+            task_state: Some(TaskState {
+                iteration: 3,
+                goal: "summarize".into(),
+                observations: vec![Arc::new("obs".into())],
+                pending_actions: vec![serde_json::json!({ "tool": "search", "score": 0.5 })],
+                consecutive_rejections: 0,
+            }),
+            token_usage: TokenUsage::with_budget(10_000),
+            created_at: 1_700_000_000,
+            updated_at: 1_700_000_050,
+        }
+    }
+
+    fn codec(format: SessionFormat, compression: SessionCompression) -> SessionCodec {
+        SessionCodec::from_config(&SessionEncodingConfig {
+            format,
+            compression,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_roundtrip_all_encodings() {
+        let session = session();
+        let json = serde_json::to_value(&session).unwrap();
+        let reader = SessionCodec::default();
+        for format in [
+            SessionFormat::Json,
+            SessionFormat::Msgpack,
+            SessionFormat::Cbor,
+        ] {
+            for compression in [SessionCompression::None, SessionCompression::Zstd] {
+                let encoded = codec(format, compression).encode(&session).unwrap();
+                let decoded: Session = reader.decode(&encoded).unwrap();
+                assert_eq!(
+                    serde_json::to_value(&decoded).unwrap(),
+                    json,
+                    "{:?}/{:?}",
+                    format,
+                    compression
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_default_writes_legacy_json() {
+        let session = session();
+        let encoded = SessionCodec::default().encode(&session).unwrap();
+        assert_eq!(encoded, serde_json::to_vec(&session).unwrap());
+
+        let decoded: Session = codec(SessionFormat::Msgpack, SessionCompression::Zstd)
+            .decode(&encoded)
+            .unwrap();
+        assert_eq!(decoded.history.len(), 50);
+    }
+
+    #[test]
+    fn test_compression_shrinks_and_respects_threshold() {
+        let session = session();
+        let json = serde_json::to_vec(&session).unwrap();
+        let compressed = codec(SessionFormat::Msgpack, SessionCompression::Zstd)
+            .encode(&session)
+            .unwrap();
+        assert!(compressed.len() * 3 < json.len());
+        assert_eq!(compressed[HEADER_LEN - 1], COMPRESSION_ZSTD);
+
+        let small = SessionCodec::from_config(&SessionEncodingConfig {
+            format: SessionFormat::Msgpack,
+            compression: SessionCompression::Zstd,
+            min_compress_bytes: usize::MAX,
+            ..Default::default()
+        })
+        .encode(&session)
+        .unwrap();
+        assert_eq!(small[HEADER_LEN - 1], COMPRESSION_NONE);
+    }
+
+    #[test]
+    fn test_rejects_unknown_version() {
+        let mut encoded = codec(SessionFormat::Cbor, SessionCompression::None)
+            .encode(&session())
+            .unwrap();
+        encoded[MAGIC.len()] = VERSION + 1;
+        assert!(SessionCodec::default().decode::<Session>(&encoded).is_err());
+        assert!(SessionCodec::default()
+            .decode::<Session>(&encoded[..MAGIC.len() + 1])
+            .is_err());
+    }
+}
@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use bytes::Bytes;
 use multi_agent_core::{
     error::Result,
-    traits::{ArtifactMetadata, ArtifactStore, SessionStore},
+    traits::{ArtifactFilter, ArtifactMetadata, ArtifactStore, Erasable, SessionStore},
     types::{RefId, Session},
 };
 use std::sync::Arc;
@@ -23,9 +23,6 @@ impl<S> NamespacedArtifactStore<S> {
     fn namespace_id(&self, id: &RefId) -> RefId {
         RefId::from_string(format!("{}/{}", self.namespace, id))
     }
-
-    // Reverse operation isn't typically needed for load since we just pass the full ID
-    // But if we list, we might need it. ArtifactStore doesn't support listing yet.
 }
 
 #[async_trait]
@@ -111,6 +108,52 @@ impl<S: ArtifactStore> ArtifactStore for NamespacedArtifactStore<S> {
         }
         self.inner.metadata(id).await
     }
+
+    async fn list(&self, mut filter: ArtifactFilter) -> Result<Vec<ArtifactMetadata>> {
+        // Ids in the namespace sort together, right after the bare prefix.
+        let prefix = format!("{}/", self.namespace);
+        if filter
+            .after_id
+            .as_ref()
+            .is_none_or(|after| after.as_str() < prefix.as_str())
+        {
+            filter.after_id = Some(RefId::from_string(prefix.clone()));
+        }
+        let mut artifacts = self.inner.list(filter).await?;
+        if let Some(end) = artifacts
+            .iter()
+            .position(|meta| !meta.id.as_str().starts_with(&prefix))
+        {
+            artifacts.truncate(end);
+        }
+        Ok(artifacts)
+    }
+
+    async fn load_range(&self, id: &RefId, start: u64, end: u64) -> Result<Option<Bytes>> {
+        if !id.as_str().starts_with(&format!("{}/", self.namespace)) {
+            return Ok(None);
+        }
+        self.inner.load_range(id, start, end).await
+    }
+
+    async fn presigned_url(
+        &self,
+        id: &RefId,
+        expires_in: std::time::Duration,
+    ) -> Result<Option<String>> {
+        if !id.as_str().starts_with(&format!("{}/", self.namespace)) {
+            return Ok(None);
+        }
+        self.inner.presigned_url(id, expires_in).await
+    }
+}
+
+#[async_trait]
+impl<S: ArtifactStore> Erasable for NamespacedArtifactStore<S> {
+    async fn erase_user(&self, user_id: &str) -> Result<usize> {
+        // Namespaced keys live in the inner store; let it apply its own user matching.
+        self.inner.erase_user(user_id).await
+    }
 }
 
 /// A SessionStore that enforces keyspace isolation.
@@ -209,3 +252,39 @@ impl<S: SessionStore> SessionStore for NamespacedSessionStore<S> {
             .collect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InMemoryStore;
+
+    #[tokio::test]
+    async fn test_list_stays_in_namespace() {
+        let inner = Arc::new(InMemoryStore::new());
+        for id in ["a/1", "b/1", "b/2", "c/1"] {
+            inner
+                .save_with_id(&RefId::from_string(id), Bytes::from("x"))
+                .await
+                .unwrap();
+        }
+        let store = NamespacedArtifactStore::new(inner, "b".to_string());
+
+        let ids = |artifacts: Vec<ArtifactMetadata>| {
+            artifacts
+                .into_iter()
+                .map(|m| m.id.0)
+                .collect::<Vec<String>>()
+        };
+        let listed = store.list(ArtifactFilter::default()).await.unwrap();
+        assert_eq!(ids(listed), ["b/1", "b/2"]);
+
+        let rest = store
+            .list(ArtifactFilter {
+                after_id: Some(RefId::from_string("b/1")),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(ids(rest), ["b/2"]);
+    }
+}
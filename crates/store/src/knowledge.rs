@@ -5,6 +5,7 @@
 //! or Qdrant-backed implementation.
 
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -33,6 +34,72 @@ impl Default for InMemoryKnowledgeStore {
     }
 }
 
+/// Re-crawlable entries refreshed before `older_than`, most hits first.
+fn select_stale(
+    entries: impl Iterator<Item = KnowledgeEntry>,
+    older_than: i64,
+    limit: usize,
+) -> Vec<KnowledgeEntry> {
+    let mut stale: Vec<KnowledgeEntry> = entries
+        .filter(|e| e.source_url.is_some() && e.last_refreshed() < older_than)
+        .collect();
+    stale.sort_by(|a, b| {
+        b.hits
+            .cmp(&a.hits)
+            .then(a.last_refreshed().cmp(&b.last_refreshed()))
+    });
+    stale.truncate(limit);
+    stale
+}
+
+/// Keyword matches of `query` in an entry; 0 means no match.
+fn keyword_score(entry: &KnowledgeEntry, terms: &[String]) -> usize {
+    let summary = entry.summary.to_lowercase();
+    let task = entry.source_task.to_lowercase();
+    terms
+        .iter()
+        .map(|term| {
+            summary.matches(term.as_str()).count()
+                + 2 * task.matches(term.as_str()).count()
+                + 2 * entry
+                    .tags
+                    .iter()
+                    .filter(|t| t.to_lowercase().contains(term.as_str()))
+                    .count()
+        })
+        .sum()
+}
+
+/// Rank entries by keyword score, newest first on ties.
+fn rank_by_keywords(
+    entries: impl Iterator<Item = KnowledgeEntry>,
+    query: &str,
+    user_id: Option<&str>,
+    limit: usize,
+) -> Vec<KnowledgeEntry> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    let mut scored: Vec<(usize, KnowledgeEntry)> = entries
+        .filter(|e| user_id.is_none_or(|u| e.user_id == u))
+        .map(|e| (keyword_score(&e, &terms), e))
+        .filter(|(score, _)| *score > 0)
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.created_at.cmp(&a.1.created_at)));
+    scored.into_iter().take(limit).map(|(_, e)| e).collect()
+}
+
+fn count_tags<'a>(entries: impl Iterator<Item = &'a KnowledgeEntry>) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for entry in entries {
+        for tag in &entry.tags {
+            *counts.entry(tag.clone()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
 /// Compute cosine similarity between two vectors.
 fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     if a.len() != b.len() || a.is_empty() {
@@ -49,9 +116,22 @@ fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
 
 #[async_trait]
 impl KnowledgeStore for InMemoryKnowledgeStore {
-    async fn store(&self, entry: KnowledgeEntry) -> Result<String> {
-        let id = entry.id.clone();
+    async fn store(&self, mut entry: KnowledgeEntry) -> Result<String> {
+        let hash = entry.effective_hash();
+        entry.content_hash = Some(hash.clone());
         let mut entries = self.entries.write().await;
+
+        // Dedup: same content for the same user folds into the existing entry
+        if let Some(existing) = entries
+            .iter_mut()
+            .find(|e| e.id != entry.id && e.user_id == entry.user_id && e.effective_hash() == hash)
+        {
+            existing.absorb(&entry);
+            tracing::debug!(id = %existing.id, hits = existing.hits, "Duplicate knowledge merged");
+            return Ok(existing.id.clone());
+        }
+
+        let id = entry.id.clone();
         // Upsert: replace if same ID exists
         entries.retain(|e| e.id != id);
         entries.push(entry);
@@ -91,6 +171,21 @@ impl KnowledgeStore for InMemoryKnowledgeStore {
         Ok(results)
     }
 
+    async fn search_text(
+        &self,
+        query: &str,
+        user_id: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<KnowledgeEntry>> {
+        let entries = self.entries.read().await;
+        Ok(rank_by_keywords(
+            entries.iter().cloned(),
+            query,
+            user_id,
+            limit,
+        ))
+    }
+
     async fn delete(&self, id: &str) -> Result<()> {
         let mut entries = self.entries.write().await;
         entries.retain(|e| e.id != id);
@@ -100,6 +195,16 @@ impl KnowledgeStore for InMemoryKnowledgeStore {
     async fn count(&self) -> Result<usize> {
         Ok(self.entries.read().await.len())
     }
+
+    async fn stale(&self, older_than: i64, limit: usize) -> Result<Vec<KnowledgeEntry>> {
+        let entries = self.entries.read().await;
+        Ok(select_stale(entries.iter().cloned(), older_than, limit))
+    }
+
+    async fn tag_counts(&self) -> Result<HashMap<String, usize>> {
+        let entries = self.entries.read().await;
+        Ok(count_tags(entries.iter()))
+    }
 }
 
 #[async_trait]
@@ -112,7 +217,43 @@ impl Erasable for InMemoryKnowledgeStore {
     }
 }
 
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
+
+const KNOWLEDGE_COLUMNS: &str =
+    "id, summary, source_task, user_id, session_id, embedding, tags, created_at, \
+     content_hash, source_url, refreshed_at, hits";
+
+fn row_to_entry(row: &rusqlite::Row<'_>) -> rusqlite::Result<KnowledgeEntry> {
+    let embedding_str: String = row.get(5)?;
+    let tags_str: String = row.get(6)?;
+
+    Ok(KnowledgeEntry {
+        id: row.get(0)?,
+        summary: row.get(1)?,
+        source_task: row.get(2)?,
+        user_id: row.get(3)?,
+        session_id: row.get(4)?,
+        embedding: serde_json::from_str(&embedding_str).unwrap_or_default(),
+        tags: serde_json::from_str(&tags_str).unwrap_or_default(),
+        created_at: row.get(7)?,
+        content_hash: row.get(8)?,
+        source_url: row.get(9)?,
+        refreshed_at: row.get(10)?,
+        hits: row.get(11)?,
+    })
+}
+
+fn load_all(conn: &Connection) -> Result<Vec<KnowledgeEntry>> {
+    let mut stmt = conn
+        .prepare(&format!("SELECT {} FROM knowledge", KNOWLEDGE_COLUMNS))
+        .map_err(|e| multi_agent_core::error::Error::Internal(format!("Prepare error: {}", e)))?;
+    let entries = stmt
+        .query_map([], row_to_entry)
+        .map_err(|e| multi_agent_core::error::Error::Internal(format!("Query error: {}", e)))?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| multi_agent_core::error::Error::Internal(format!("Result error: {}", e)))?;
+    Ok(entries)
+}
 
 /// SQLite-backed knowledge store for persistent research summaries.
 pub struct SqliteKnowledgeStore {
@@ -140,6 +281,7 @@ impl SqliteKnowledgeStore {
             [],
         )
         .map_err(|e| multi_agent_core::error::Error::Internal(format!("Schema error: {}", e)))?;
+        Self::migrate_freshness(&conn)?;
 
         // Index for tag and user search
         conn.execute(
@@ -152,30 +294,116 @@ impl SqliteKnowledgeStore {
             [],
         )
         .map_err(|e| multi_agent_core::error::Error::Internal(format!("Index error: {}", e)))?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_knowledge_hash ON knowledge (user_id, content_hash)",
+            [],
+        )
+        .map_err(|e| multi_agent_core::error::Error::Internal(format!("Index error: {}", e)))?;
 
         Ok(Self {
             conn: Arc::new(tokio::sync::Mutex::new(conn)),
         })
     }
+
+    /// Add dedup/freshness columns to databases created before they existed
+    /// and backfill content hashes so old entries take part in dedup.
+    fn migrate_freshness(conn: &Connection) -> Result<()> {
+        let existing: Vec<String> = conn
+            .prepare("PRAGMA table_info(knowledge)")
+            .and_then(|mut stmt| {
+                stmt.query_map([], |row| row.get::<_, String>(1))?
+                    .collect::<std::result::Result<Vec<_>, _>>()
+            })
+            .map_err(|e| {
+                multi_agent_core::error::Error::Internal(format!("Schema error: {}", e))
+            })?;
+
+        for (column, ddl) in [
+            ("content_hash", "content_hash TEXT"),
+            ("source_url", "source_url TEXT"),
+            ("refreshed_at", "refreshed_at INTEGER"),
+            ("hits", "hits INTEGER NOT NULL DEFAULT 0"),
+        ] {
+            if !existing.iter().any(|c| c == column) {
+                conn.execute(&format!("ALTER TABLE knowledge ADD COLUMN {}", ddl), [])
+                    .map_err(|e| {
+                        multi_agent_core::error::Error::Internal(format!("Migration error: {}", e))
+                    })?;
+            }
+        }
+
+        let missing: Vec<(String, String)> = conn
+            .prepare("SELECT id, summary FROM knowledge WHERE content_hash IS NULL")
+            .and_then(|mut stmt| {
+                stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                    .collect::<std::result::Result<Vec<_>, _>>()
+            })
+            .map_err(|e| {
+                multi_agent_core::error::Error::Internal(format!("Migration error: {}", e))
+            })?;
+        for (id, summary) in missing {
+            conn.execute(
+                "UPDATE knowledge SET content_hash = ?1 WHERE id = ?2",
+                params![KnowledgeEntry::hash_content(&summary), id],
+            )
+            .map_err(|e| {
+                multi_agent_core::error::Error::Internal(format!("Migration error: {}", e))
+            })?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl KnowledgeStore for SqliteKnowledgeStore {
-    async fn store(&self, entry: KnowledgeEntry) -> Result<String> {
+    async fn store(&self, mut entry: KnowledgeEntry) -> Result<String> {
         let conn = self.conn.clone();
-        let id = entry.id.clone();
-
-        // Convert vectors to JSON strings for storage
-        let embedding_json = serde_json::to_string(&entry.embedding)
-            .map_err(|e| multi_agent_core::error::Error::Internal(e.to_string()))?;
-        let tags_json = serde_json::to_string(&entry.tags)
-            .map_err(|e| multi_agent_core::error::Error::Internal(e.to_string()))?;
+        entry.content_hash = Some(entry.effective_hash());
 
         tokio::task::spawn_blocking(move || {
             let conn = conn.blocking_lock();
+
+            // Dedup: same content for the same user folds into the existing entry
+            let duplicate = conn
+                .query_row(
+                    &format!(
+                        "SELECT {} FROM knowledge WHERE user_id = ?1 AND content_hash = ?2 AND id != ?3 LIMIT 1",
+                        KNOWLEDGE_COLUMNS
+                    ),
+                    params![entry.user_id, entry.content_hash, entry.id],
+                    row_to_entry,
+                )
+                .optional()
+                .map_err(|e| multi_agent_core::error::Error::Internal(format!("Query error: {}", e)))?;
+            if let Some(mut existing) = duplicate {
+                existing.absorb(&entry);
+                let tags_json = serde_json::to_string(&existing.tags)
+                    .map_err(|e| multi_agent_core::error::Error::Internal(e.to_string()))?;
+                conn.execute(
+                    "UPDATE knowledge SET tags = ?1, source_url = ?2, refreshed_at = ?3, hits = ?4 WHERE id = ?5",
+                    params![
+                        tags_json,
+                        existing.source_url,
+                        existing.refreshed_at,
+                        existing.hits,
+                        existing.id
+                    ],
+                )
+                .map_err(|e| multi_agent_core::error::Error::Internal(format!("Update error: {}", e)))?;
+                tracing::debug!(id = %existing.id, hits = existing.hits, "Duplicate knowledge merged");
+                return Ok(existing.id);
+            }
+
+            // Convert vectors to JSON strings for storage
+            let embedding_json = serde_json::to_string(&entry.embedding)
+                .map_err(|e| multi_agent_core::error::Error::Internal(e.to_string()))?;
+            let tags_json = serde_json::to_string(&entry.tags)
+                .map_err(|e| multi_agent_core::error::Error::Internal(e.to_string()))?;
             conn.execute(
-                "INSERT OR REPLACE INTO knowledge (id, summary, source_task, user_id, session_id, embedding, tags, created_at)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                &format!(
+                    "INSERT OR REPLACE INTO knowledge ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                    KNOWLEDGE_COLUMNS
+                ),
                 params![
                     entry.id,
                     entry.summary,
@@ -184,10 +412,14 @@ impl KnowledgeStore for SqliteKnowledgeStore {
                     entry.session_id,
                     embedding_json,
                     tags_json,
-                    entry.created_at
+                    entry.created_at,
+                    entry.content_hash,
+                    entry.source_url,
+                    entry.refreshed_at,
+                    entry.hits
                 ],
             ).map_err(|e| multi_agent_core::error::Error::Internal(format!("Insert error: {}", e)))?;
-            Ok(id)
+            Ok(entry.id)
         })
         .await
         .map_err(|e| multi_agent_core::error::Error::Internal(e.to_string()))?
@@ -199,27 +431,7 @@ impl KnowledgeStore for SqliteKnowledgeStore {
 
         tokio::task::spawn_blocking(move || {
             let conn = conn.blocking_lock();
-            let mut stmt = conn.prepare(
-                "SELECT id, summary, source_task, user_id, session_id, embedding, tags, created_at FROM knowledge"
-            ).map_err(|e| multi_agent_core::error::Error::Internal(format!("Prepare error: {}", e)))?;
-
-            let entries = stmt.query_map([], |row| {
-                let embedding_str: String = row.get(5)?;
-                let tags_str: String = row.get(6)?;
-
-                Ok(KnowledgeEntry {
-                    id: row.get(0)?,
-                    summary: row.get(1)?,
-                    source_task: row.get(2)?,
-                    user_id: row.get(3)?,
-                    session_id: row.get(4)?,
-                    embedding: serde_json::from_str(&embedding_str).unwrap_or_default(),
-                    tags: serde_json::from_str(&tags_str).unwrap_or_default(),
-                    created_at: row.get(7)?,
-                })
-            }).map_err(|e| multi_agent_core::error::Error::Internal(format!("Query error: {}", e)))?
-            .collect::<std::result::Result<Vec<_>, _>>()
-            .map_err(|e| multi_agent_core::error::Error::Internal(format!("Result error: {}", e)))?;
+            let entries = load_all(&conn)?;
 
             // Compute similarity in-memory (SQLite-vec is preferred but we use manual approach for now)
             let mut scored: Vec<(f32, KnowledgeEntry)> = entries
@@ -246,27 +458,7 @@ impl KnowledgeStore for SqliteKnowledgeStore {
 
         tokio::task::spawn_blocking(move || {
             let conn = conn.blocking_lock();
-            let mut stmt = conn.prepare(
-                "SELECT id, summary, source_task, user_id, session_id, embedding, tags, created_at FROM knowledge"
-            ).map_err(|e| multi_agent_core::error::Error::Internal(format!("Prepare error: {}", e)))?;
-
-            let entries = stmt.query_map([], |row| {
-                let embedding_str: String = row.get(5)?;
-                let tags_str: String = row.get(6)?;
-
-                Ok(KnowledgeEntry {
-                    id: row.get(0)?,
-                    summary: row.get(1)?,
-                    source_task: row.get(2)?,
-                    user_id: row.get(3)?,
-                    session_id: row.get(4)?,
-                    embedding: serde_json::from_str(&embedding_str).unwrap_or_default(),
-                    tags: serde_json::from_str(&tags_str).unwrap_or_default(),
-                    created_at: row.get(7)?,
-                })
-            }).map_err(|e| multi_agent_core::error::Error::Internal(format!("Query error: {}", e)))?
-            .collect::<std::result::Result<Vec<_>, _>>()
-            .map_err(|e| multi_agent_core::error::Error::Internal(format!("Result error: {}", e)))?;
+            let entries = load_all(&conn)?;
 
             Ok(entries
                 .into_iter()
@@ -278,6 +470,55 @@ impl KnowledgeStore for SqliteKnowledgeStore {
         .map_err(|e| multi_agent_core::error::Error::Internal(e.to_string()))?
     }
 
+    async fn search_text(
+        &self,
+        query: &str,
+        user_id: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<KnowledgeEntry>> {
+        let conn = self.conn.clone();
+        let query = query.to_string();
+        let user_id = user_id.map(str::to_string);
+
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.blocking_lock();
+            let entries = match &user_id {
+                Some(uid) => {
+                    let mut stmt = conn
+                        .prepare(&format!(
+                            "SELECT {} FROM knowledge WHERE user_id = ?1",
+                            KNOWLEDGE_COLUMNS
+                        ))
+                        .map_err(|e| {
+                            multi_agent_core::error::Error::Internal(format!(
+                                "Prepare error: {}",
+                                e
+                            ))
+                        })?;
+                    let rows = stmt
+                        .query_map(params![uid], row_to_entry)
+                        .map_err(|e| {
+                            multi_agent_core::error::Error::Internal(format!("Query error: {}", e))
+                        })?
+                        .collect::<std::result::Result<Vec<_>, _>>()
+                        .map_err(|e| {
+                            multi_agent_core::error::Error::Internal(format!("Result error: {}", e))
+                        })?;
+                    rows
+                }
+                None => load_all(&conn)?,
+            };
+            Ok(rank_by_keywords(
+                entries.into_iter(),
+                &query,
+                user_id.as_deref(),
+                limit,
+            ))
+        })
+        .await
+        .map_err(|e| multi_agent_core::error::Error::Internal(e.to_string()))?
+    }
+
     async fn delete(&self, id: &str) -> Result<()> {
         let conn = self.conn.clone();
         let target_id = id.to_string();
@@ -308,6 +549,45 @@ impl KnowledgeStore for SqliteKnowledgeStore {
         .await
         .map_err(|e| multi_agent_core::error::Error::Internal(e.to_string()))?
     }
+
+    async fn stale(&self, older_than: i64, limit: usize) -> Result<Vec<KnowledgeEntry>> {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.blocking_lock();
+            let mut stmt = conn
+                .prepare(&format!(
+                    "SELECT {} FROM knowledge
+                     WHERE source_url IS NOT NULL AND COALESCE(refreshed_at, created_at) < ?1",
+                    KNOWLEDGE_COLUMNS
+                ))
+                .map_err(|e| {
+                    multi_agent_core::error::Error::Internal(format!("Prepare error: {}", e))
+                })?;
+            let entries = stmt
+                .query_map(params![older_than], row_to_entry)
+                .map_err(|e| {
+                    multi_agent_core::error::Error::Internal(format!("Query error: {}", e))
+                })?
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(|e| {
+                    multi_agent_core::error::Error::Internal(format!("Result error: {}", e))
+                })?;
+            Ok(select_stale(entries.into_iter(), older_than, limit))
+        })
+        .await
+        .map_err(|e| multi_agent_core::error::Error::Internal(e.to_string()))?
+    }
+
+    async fn tag_counts(&self) -> Result<HashMap<String, usize>> {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.blocking_lock();
+            let entries = load_all(&conn)?;
+            Ok(count_tags(entries.iter()))
+        })
+        .await
+        .map_err(|e| multi_agent_core::error::Error::Internal(e.to_string()))?
+    }
 }
 
 #[async_trait]
@@ -343,6 +623,10 @@ mod tests {
             embedding,
             tags: tags.into_iter().map(String::from).collect(),
             created_at: 1000,
+            content_hash: None,
+            source_url: None,
+            refreshed_at: None,
+            hits: 0,
         }
     }
 
@@ -478,6 +762,183 @@ mod tests {
         assert_eq!(store.count().await.unwrap(), 1);
     }
 
+    fn sourced(
+        mut entry: KnowledgeEntry,
+        url: &str,
+        refreshed_at: i64,
+        hits: u32,
+    ) -> KnowledgeEntry {
+        entry.source_url = Some(url.to_string());
+        entry.refreshed_at = Some(refreshed_at);
+        entry.hits = hits;
+        entry
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_content_is_merged() {
+        let store = InMemoryKnowledgeStore::new();
+
+        store
+            .store(make_entry("k1", "Rust is  fast", vec![1.0], vec!["lang"]))
+            .await
+            .unwrap();
+        let id = store
+            .store(make_entry("k2", "Rust is fast\n", vec![1.0], vec!["perf"]))
+            .await
+            .unwrap();
+        assert_eq!(id, "k1");
+        assert_eq!(store.count().await.unwrap(), 1);
+
+        let merged = &store.search(&[1.0], 1).await.unwrap()[0];
+        assert_eq!(merged.tags, vec!["lang", "perf"]);
+        assert_eq!(merged.hits, 1);
+        assert!(merged.refreshed_at.is_some());
+
+        // Another user's copy is kept separate
+        let mut other = make_entry("k3", "Rust is fast", vec![1.0], vec![]);
+        other.user_id = "user-2".to_string();
+        assert_eq!(store.store(other).await.unwrap(), "k3");
+        assert_eq!(store.count().await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_stale_prefers_high_hit_sources() {
+        let store = InMemoryKnowledgeStore::new();
+        store
+            .store(sourced(
+                make_entry("a", "A", vec![1.0], vec!["t"]),
+                "https://a",
+                100,
+                0,
+            ))
+            .await
+            .unwrap();
+        store
+            .store(sourced(
+                make_entry("b", "B", vec![1.0], vec!["t"]),
+                "https://b",
+                200,
+                5,
+            ))
+            .await
+            .unwrap();
+        store
+            .store(sourced(
+                make_entry("c", "C", vec![1.0], vec![]),
+                "https://c",
+                900,
+                9,
+            ))
+            .await
+            .unwrap();
+        store
+            .store(make_entry("d", "D", vec![1.0], vec!["t"]))
+            .await
+            .unwrap();
+
+        let stale = store.stale(500, 10).await.unwrap();
+        let ids: Vec<_> = stale.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "a"]);
+        assert_eq!(store.stale(500, 1).await.unwrap().len(), 1);
+
+        let counts = store.tag_counts().await.unwrap();
+        assert_eq!(counts["t"], 3);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_dedup_and_migration() {
+        use tempfile::NamedTempFile;
+        let temp_file = NamedTempFile::new().unwrap();
+
+        // Database created before the freshness columns existed
+        {
+            let conn = Connection::open(temp_file.path()).unwrap();
+            conn.execute(
+                "CREATE TABLE knowledge (
+                    id TEXT PRIMARY KEY, summary TEXT NOT NULL, source_task TEXT NOT NULL,
+                    user_id TEXT NOT NULL, session_id TEXT NOT NULL, embedding TEXT NOT NULL,
+                    tags TEXT NOT NULL, created_at INTEGER NOT NULL
+                )",
+                [],
+            )
+            .unwrap();
+            conn.execute(
+                "INSERT INTO knowledge VALUES ('old', 'Legacy fact', 't', 'user-1', 's', '[1.0]', '[\"a\"]', 10)",
+                [],
+            )
+            .unwrap();
+        }
+
+        let store = SqliteKnowledgeStore::new(temp_file.path()).unwrap();
+        let id = store
+            .store(make_entry("new", "Legacy fact", vec![1.0], vec!["b"]))
+            .await
+            .unwrap();
+        assert_eq!(id, "old");
+        assert_eq!(store.count().await.unwrap(), 1);
+
+        let merged = &store.search(&[1.0], 1).await.unwrap()[0];
+        assert_eq!(merged.tags, vec!["a", "b"]);
+        assert_eq!(merged.hits, 1);
+
+        store
+            .store(sourced(
+                make_entry("src", "Page", vec![1.0], vec!["b"]),
+                "https://p",
+                5,
+                0,
+            ))
+            .await
+            .unwrap();
+        let stale = store.stale(100, 10).await.unwrap();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].source_url.as_deref(), Some("https://p"));
+        assert_eq!(store.tag_counts().await.unwrap()["b"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_text_search_is_scoped_and_ranked() {
+        use tempfile::NamedTempFile;
+        let temp_file = NamedTempFile::new().unwrap();
+        let sqlite = SqliteKnowledgeStore::new(temp_file.path()).unwrap();
+        let memory = InMemoryKnowledgeStore::new();
+        let stores: [&dyn KnowledgeStore; 2] = [&memory, &sqlite];
+
+        for store in stores {
+            store
+                .store(make_entry("k1", "Tokio runtime notes", vec![1.0], vec![]))
+                .await
+                .unwrap();
+            store
+                .store(make_entry(
+                    "k2",
+                    "Tokio and tokio tasks",
+                    vec![1.0],
+                    vec!["tokio"],
+                ))
+                .await
+                .unwrap();
+            let mut other = make_entry("k3", "Tokio for another user", vec![1.0], vec![]);
+            other.user_id = "user-2".to_string();
+            store.store(other).await.unwrap();
+
+            let hits = store
+                .search_text("TOKIO", Some("user-1"), 10)
+                .await
+                .unwrap();
+            let ids: Vec<_> = hits.iter().map(|e| e.id.as_str()).collect();
+            assert_eq!(ids, vec!["k2", "k1"]);
+
+            assert_eq!(store.search_text("tokio", None, 10).await.unwrap().len(), 3);
+            assert!(store.search_text("  ", None, 10).await.unwrap().is_empty());
+            assert!(store
+                .search_text("python", Some("user-1"), 10)
+                .await
+                .unwrap()
+                .is_empty());
+        }
+    }
+
     #[tokio::test]
     async fn test_sqlite_knowledge_store() {
         use tempfile::NamedTempFile;
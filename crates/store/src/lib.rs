@@ -4,10 +4,14 @@
 //! This crate provides tiered storage (Hot/Warm/Cold) for artifacts,
 //! implementing the pass-by-reference pattern to prevent context explosion.
 
+pub mod catalog;
+pub mod cloud;
+pub mod codec;
 pub mod file_provider;
 pub mod isolation;
 pub mod knowledge;
 pub mod memory;
+pub mod postgres;
 pub mod qdrant;
 pub mod redis;
 pub mod retention;
@@ -19,14 +23,18 @@ use bytes::Bytes;
 use std::sync::Arc;
 
 use multi_agent_core::{
-    traits::{ArtifactMetadata, ArtifactStore, StorageTier},
+    traits::{ArtifactFilter, ArtifactMetadata, ArtifactStore, StorageTier},
     types::RefId,
     Result,
 };
 
-pub use memory::{InMemorySessionStore, InMemoryStore};
+pub use memory::{InMemoryProviderStore, InMemorySessionStore, InMemoryStore};
+pub use postgres::{PostgresProviderStore, PostgresSessionStore};
 pub use redis::{RedisProviderStore, RedisRateLimiter, RedisSessionStore, RedisStateStore};
 
+pub use catalog::{ArtifactCatalog, ArtifactEntry, CatalogArtifactStore};
+pub use cloud::{CloudArtifactStore, CloudProvider};
+pub use codec::SessionCodec;
 pub use file_provider::FileProviderStore;
 pub use knowledge::InMemoryKnowledgeStore;
 pub use qdrant::{QdrantConfig, QdrantMemoryStore};
@@ -161,6 +169,34 @@ impl ArtifactStore for TieredStore {
         Ok(None)
     }
 
+    async fn load_range(&self, id: &RefId, start: u64, end: u64) -> Result<Option<Bytes>> {
+        if let Some(data) = self.hot.load_range(id, start, end).await? {
+            return Ok(Some(data));
+        }
+        if let Some(ref warm) = self.warm {
+            if let Some(data) = warm.load_range(id, start, end).await? {
+                return Ok(Some(data));
+            }
+        }
+        if let Some(ref cold) = self.cold {
+            return cold.load_range(id, start, end).await;
+        }
+        Ok(None)
+    }
+
+    async fn presigned_url(
+        &self,
+        id: &RefId,
+        expires_in: std::time::Duration,
+    ) -> Result<Option<String>> {
+        // Only the cold tier serves direct downloads; hot and warm copies are
+        // cheap enough to stream through the gateway.
+        match self.cold {
+            Some(ref cold) if cold.exists(id).await? => cold.presigned_url(id, expires_in).await,
+            _ => Ok(None),
+        }
+    }
+
     async fn delete(&self, id: &RefId) -> Result<()> {
         // Try to delete from all tiers
         let _ = self.hot.delete(id).await;
@@ -203,6 +239,42 @@ impl ArtifactStore for TieredStore {
         Ok(None)
     }
 
+    async fn list(&self, filter: ArtifactFilter) -> Result<Vec<ArtifactMetadata>> {
+        let tiers = [
+            (StorageTier::Hot, Some(&self.hot)),
+            (StorageTier::Warm, self.warm.as_ref()),
+            (StorageTier::Cold, self.cold.as_ref()),
+        ];
+
+        // Tiers that cannot enumerate their contents are left out of the
+        // listing rather than failing it; only fail if no tier can list.
+        let mut artifacts = Vec::new();
+        let mut unsupported = None;
+        let mut listed_any = false;
+        for (tier, store) in tiers {
+            let Some(store) = store else { continue };
+            match store.list(filter.clone()).await {
+                Ok(found) => {
+                    listed_any = true;
+                    artifacts.extend(found);
+                }
+                Err(e @ multi_agent_core::Error::Unsupported(_)) => {
+                    tracing::debug!(tier = ?tier, error = %e, "Skipping tier in artifact listing");
+                    unsupported = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        if let (false, Some(e)) = (listed_any, unsupported) {
+            return Err(e);
+        }
+        // A stable sort keeps the hottest copy of an artifact first.
+        artifacts.sort_by(|a, b| a.id.as_str().cmp(b.id.as_str()));
+        artifacts.dedup_by(|later, earlier| later.id == earlier.id);
+        artifacts.truncate(filter.limit.unwrap_or(usize::MAX));
+        Ok(artifacts)
+    }
+
     async fn health_check(&self) -> Result<()> {
         self.hot.health_check().await?;
         if let Some(ref warm) = self.warm {
@@ -217,29 +289,35 @@ impl ArtifactStore for TieredStore {
 
 #[async_trait]
 impl multi_agent_core::traits::Erasable for TieredStore {
-    async fn erase_user(&self, _user_id: &str) -> Result<usize> {
-        let total = 0;
-
-        // Use a dynamic cast or assumed implementation for Erasable
-        // Note: ArtifactStore doesn't inherit Erasable, but concrete types do.
-        // For simplicity in TieredStore, we attempt to cast or just ignore if not erasable.
-        // Actually, we should ideally have ArtifactStore inherit Erasable or just cast.
-        // For now, since we know InMemoryStore and S3ArtifactStore are Erasable,
-        // we can try to cast them.
-
-        // Wait, self.hot is Arc<dyn ArtifactStore>.
-        // We need a way to call erase_user on it.
-        // Since we know our implementations, let's just cast.
-
-        // This is a bit hacky but works for now as all our stores implement Erasable.
-        // In a real system, we'd add Erasable as a supertrait or use a registry.
-
-        // Note: Rust doesn't support casting between unrelated traits easily.
-        // But we can add it to the ArtifactStore trait if we want,
-        // or just accept that TieredStore only erases from stores that we know are erasable.
+    async fn erase_user(&self, user_id: &str) -> Result<usize> {
+        // Cascade through every tier; a failure in one tier must not stop the others.
+        let tiers = [
+            (StorageTier::Hot, Some(&self.hot)),
+            (StorageTier::Warm, self.warm.as_ref()),
+            (StorageTier::Cold, self.cold.as_ref()),
+        ];
+
+        let mut total = 0;
+        let mut errors = Vec::new();
+        for (tier, store) in tiers {
+            let Some(store) = store else { continue };
+            match store.erase_user(user_id).await {
+                Ok(count) => {
+                    tracing::debug!(tier = ?tier, count = count, "Erased user artifacts from tier");
+                    total += count;
+                }
+                Err(e) => errors.push(format!("{:?} tier ({}): {}", tier, store.store_name(), e)),
+            }
+        }
 
-        // Actually, let's just assume they are erasable for now or ignore errors.
-        Ok(total)
+        if errors.is_empty() {
+            Ok(total)
+        } else {
+            Err(multi_agent_core::Error::PartialErasure {
+                erased: total,
+                failures: errors,
+            })
+        }
     }
 }
 
@@ -253,7 +331,16 @@ pub async fn maybe_store_by_ref(
     store: &dyn ArtifactStore,
     content: String,
 ) -> Result<(String, Option<RefId>)> {
-    if should_store_by_ref(&content) {
+    maybe_store_by_ref_with_threshold(store, content, LARGE_CONTENT_THRESHOLD).await
+}
+
+/// Like [`maybe_store_by_ref`], with a caller-chosen size threshold in bytes.
+pub async fn maybe_store_by_ref_with_threshold(
+    store: &dyn ArtifactStore,
+    content: String,
+    threshold: usize,
+) -> Result<(String, Option<RefId>)> {
+    if content.len() > threshold {
         let ref_id = store.save(Bytes::from(content)).await?;
         let message = format!(
             "Output too large. Saved as RefID: {}. Use 'read_artifact' to view.",
@@ -289,4 +376,142 @@ mod tests {
         store.delete(&ref_id).await.unwrap();
         assert!(!store.exists(&ref_id).await.unwrap());
     }
+
+    #[tokio::test]
+    async fn test_tiered_erase_cascades() {
+        use multi_agent_core::traits::Erasable;
+
+        let hot = Arc::new(InMemoryStore::new());
+        let cold = Arc::new(InMemoryStore::new());
+        let store = TieredStore::new(hot.clone()).with_cold(cold.clone());
+
+        hot.save_with_id(&RefId::from_string("alice/1"), Bytes::from("a"))
+            .await
+            .unwrap();
+        cold.save_with_id(&RefId::from_string("alice/2"), Bytes::from("b"))
+            .await
+            .unwrap();
+        cold.save_with_id(&RefId::from_string("bob/1"), Bytes::from("c"))
+            .await
+            .unwrap();
+
+        assert_eq!(store.erase_user("alice").await.unwrap(), 2);
+        assert!(store.exists(&RefId::from_string("bob/1")).await.unwrap());
+        assert_eq!(store.store_name(), "TieredStore");
+    }
+
+    /// Cold tier whose erasure always fails and which cannot list artifacts.
+    struct BrokenColdTier(InMemoryStore);
+
+    #[async_trait]
+    impl ArtifactStore for BrokenColdTier {
+        async fn save(&self, data: Bytes) -> Result<RefId> {
+            self.0.save(data).await
+        }
+
+        async fn save_with_id(&self, id: &RefId, data: Bytes) -> Result<()> {
+            self.0.save_with_id(id, data).await
+        }
+
+        async fn save_with_type(&self, data: Bytes, content_type: &str) -> Result<RefId> {
+            self.0.save_with_type(data, content_type).await
+        }
+
+        async fn load(&self, id: &RefId) -> Result<Option<Bytes>> {
+            self.0.load(id).await
+        }
+
+        async fn delete(&self, id: &RefId) -> Result<()> {
+            self.0.delete(id).await
+        }
+
+        async fn exists(&self, id: &RefId) -> Result<bool> {
+            self.0.exists(id).await
+        }
+
+        async fn metadata(&self, id: &RefId) -> Result<Option<ArtifactMetadata>> {
+            self.0.metadata(id).await
+        }
+    }
+
+    #[async_trait]
+    impl multi_agent_core::traits::Erasable for BrokenColdTier {
+        async fn erase_user(&self, _user_id: &str) -> Result<usize> {
+            Err(multi_agent_core::Error::storage("bucket unreachable"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tiered_erase_reports_partial_failure() {
+        use multi_agent_core::traits::Erasable;
+
+        let hot = Arc::new(InMemoryStore::new());
+        let store =
+            TieredStore::new(hot.clone()).with_cold(Arc::new(BrokenColdTier(InMemoryStore::new())));
+        hot.save_with_id(&RefId::from_string("alice/1"), Bytes::from("a"))
+            .await
+            .unwrap();
+
+        match store.erase_user("alice").await {
+            Err(multi_agent_core::Error::PartialErasure { erased, failures }) => {
+                assert_eq!(erased, 1);
+                assert_eq!(failures.len(), 1);
+                assert!(failures[0].contains("bucket unreachable"), "{:?}", failures);
+            }
+            other => panic!("expected a partial erasure, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tiered_list_merges_tiers() {
+        let hot = Arc::new(InMemoryStore::new());
+        let cold = Arc::new(InMemoryStore::new());
+        let store = TieredStore::new(hot.clone()).with_cold(cold.clone());
+
+        for (tier, id) in [(&hot, "b"), (&cold, "a"), (&cold, "b"), (&cold, "c")] {
+            tier.save_with_id(&RefId::from_string(id), Bytes::from(id))
+                .await
+                .unwrap();
+        }
+
+        let listed = store
+            .list(ArtifactFilter {
+                limit: Some(2),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let ids: Vec<&str> = listed.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b"]);
+
+        let rest = store
+            .list(ArtifactFilter {
+                after_id: Some(RefId::from_string("b")),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].id.as_str(), "c");
+    }
+
+    #[tokio::test]
+    async fn test_tiered_list_skips_tiers_without_listing() {
+        let hot = Arc::new(InMemoryStore::new());
+        let store =
+            TieredStore::new(hot.clone()).with_cold(Arc::new(BrokenColdTier(InMemoryStore::new())));
+        hot.save_with_id(&RefId::from_string("a"), Bytes::from("a"))
+            .await
+            .unwrap();
+
+        let listed = store.list(ArtifactFilter::default()).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id.as_str(), "a");
+
+        let unlistable = TieredStore::new(Arc::new(BrokenColdTier(InMemoryStore::new())));
+        assert!(matches!(
+            unlistable.list(ArtifactFilter::default()).await,
+            Err(multi_agent_core::Error::Unsupported(_))
+        ));
+    }
 }
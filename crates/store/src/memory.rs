@@ -2,13 +2,19 @@
 
 use async_trait::async_trait;
 use bytes::Bytes;
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
+use futures::StreamExt;
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast::{self, error::RecvError};
 
 use crate::retention::{Erasable, Prunable};
 use multi_agent_core::{
-    traits::{ArtifactMetadata, ArtifactStore, SessionStore, StorageTier},
-    types::{RefId, Session, SessionStatus},
+    traits::{
+        ArtifactFilter, ArtifactMetadata, ArtifactStore, ProviderEntry, ProviderStore,
+        SessionStore, SessionWatch, StorageTier,
+    },
+    types::{ArtifactOwner, RefId, Session, SessionChange, SessionStatus},
     Result,
 };
 
@@ -21,6 +27,8 @@ struct StoredArtifact {
     content_type: String,
     /// Creation timestamp.
     created_at: i64,
+    /// Owner at save time, if any.
+    owner: Option<ArtifactOwner>,
 }
 
 /// In-memory artifact store using DashMap for concurrent access.
@@ -31,6 +39,8 @@ struct StoredArtifact {
 pub struct InMemoryStore {
     /// Thread-safe concurrent hashmap.
     data: DashMap<String, StoredArtifact>,
+    /// Owner index: user_id -> artifact keys.
+    owners: DashMap<String, DashSet<String>>,
 }
 
 impl InMemoryStore {
@@ -38,6 +48,7 @@ impl InMemoryStore {
     pub fn new() -> Self {
         Self {
             data: DashMap::new(),
+            owners: DashMap::new(),
         }
     }
 
@@ -54,6 +65,7 @@ impl InMemoryStore {
     /// Clear all artifacts.
     pub fn clear(&self) {
         self.data.clear();
+        self.owners.clear();
     }
 
     /// Get total memory usage in bytes (approximate).
@@ -61,6 +73,40 @@ impl InMemoryStore {
         self.data.iter().map(|r| r.value().data.len()).sum()
     }
 
+    fn insert(&self, key: String, data: Bytes, content_type: &str) {
+        let owner = ArtifactOwner::current();
+        if let Some(user_id) = owner.as_ref().and_then(|o| o.user_id.clone()) {
+            self.owners.entry(user_id).or_default().insert(key.clone());
+        }
+        let artifact = StoredArtifact {
+            data,
+            content_type: content_type.to_string(),
+            created_at: Self::current_timestamp(),
+            owner,
+        };
+        if let Some(previous) = self.data.insert(key.clone(), artifact) {
+            self.unindex(&key, &previous);
+        }
+    }
+
+    fn unindex(&self, key: &str, artifact: &StoredArtifact) {
+        let Some(user_id) = artifact.owner.as_ref().and_then(|o| o.user_id.as_deref()) else {
+            return;
+        };
+        // Re-saving under the same owner keeps the key indexed.
+        let still_owned = self
+            .data
+            .get(key)
+            .and_then(|a| a.owner.as_ref().and_then(|o| o.user_id.clone()))
+            .is_some_and(|current| current == user_id);
+        if !still_owned {
+            if let Some(keys) = self.owners.get(user_id) {
+                keys.remove(key);
+            }
+            self.owners.remove_if(user_id, |_, keys| keys.is_empty());
+        }
+    }
+
     fn current_timestamp() -> i64 {
         SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -75,9 +121,13 @@ impl Default for InMemoryStore {
     }
 }
 
+/// Changes buffered per session watcher before it lags.
+const WATCH_CAPACITY: usize = 256;
+
 /// In-memory session store.
 pub struct InMemorySessionStore {
     sessions: DashMap<String, Session>,
+    changes: broadcast::Sender<(String, SessionChange)>,
 }
 
 impl InMemorySessionStore {
@@ -85,8 +135,14 @@ impl InMemorySessionStore {
     pub fn new() -> Self {
         Self {
             sessions: DashMap::new(),
+            changes: broadcast::channel(WATCH_CAPACITY).0,
         }
     }
+
+    fn notify(&self, session_id: &str, change: SessionChange) {
+        // No watchers is not an error
+        let _ = self.changes.send((session_id.to_string(), change));
+    }
 }
 
 impl Default for InMemorySessionStore {
@@ -99,6 +155,7 @@ impl Default for InMemorySessionStore {
 impl SessionStore for InMemorySessionStore {
     async fn save(&self, session: &Session) -> Result<()> {
         self.sessions.insert(session.id.clone(), session.clone());
+        self.notify(&session.id, SessionChange::Saved(Box::new(session.clone())));
         Ok(())
     }
 
@@ -107,7 +164,9 @@ impl SessionStore for InMemorySessionStore {
     }
 
     async fn delete(&self, session_id: &str) -> Result<()> {
-        self.sessions.remove(session_id);
+        if self.sessions.remove(session_id).is_some() {
+            self.notify(session_id, SessionChange::Deleted);
+        }
         Ok(())
     }
 
@@ -136,6 +195,29 @@ impl SessionStore for InMemorySessionStore {
             .map(|r| r.value().clone())
             .collect())
     }
+
+    async fn watch(self: Arc<Self>, session_id: &str) -> Result<SessionWatch> {
+        let changes = self.changes.subscribe();
+        let stream = futures::stream::unfold(
+            (self, changes, session_id.to_string()),
+            |(store, mut changes, session_id)| async move {
+                loop {
+                    let change = match changes.recv().await {
+                        Ok((id, change)) if id == session_id => change,
+                        Ok(_) => continue,
+                        // The current state sums up the missed changes
+                        Err(RecvError::Lagged(_)) => match store.sessions.get(&session_id) {
+                            Some(session) => SessionChange::Saved(Box::new(session.clone())),
+                            None => SessionChange::Deleted,
+                        },
+                        Err(RecvError::Closed) => return None,
+                    };
+                    return Some((change, (store, changes, session_id)));
+                }
+            },
+        );
+        Ok(stream.boxed())
+    }
 }
 
 #[async_trait]
@@ -153,37 +235,38 @@ impl Prunable for InMemorySessionStore {
         // For now, let's iterate and remove to count active deletions (slower but correct return value)
         // OR better: use retain and just return 0 for now as counting in retain is hard in Rust closures without atomics.
         // Let's use atomics for counting.
-        use std::sync::atomic::{AtomicUsize, Ordering};
-        let count = AtomicUsize::new(0);
-
-        self.sessions.retain(|_, v| {
+        let mut pruned = Vec::new();
+        self.sessions.retain(|id, v| {
             if v.updated_at < cutoff {
-                count.fetch_add(1, Ordering::Relaxed);
+                pruned.push(id.clone());
                 false
             } else {
                 true
             }
         });
-        Ok(count.load(Ordering::Relaxed))
+        for id in &pruned {
+            self.notify(id, SessionChange::Deleted);
+        }
+        Ok(pruned.len())
     }
 }
 
 #[async_trait]
 impl Erasable for InMemorySessionStore {
     async fn erase_user(&self, user_id: &str) -> Result<usize> {
-        use std::sync::atomic::{AtomicUsize, Ordering};
-        let count = AtomicUsize::new(0);
-
-        // DashMap retain
-        self.sessions.retain(|_, v| {
+        let mut erased = Vec::new();
+        self.sessions.retain(|id, v| {
             if v.user_id.as_deref() == Some(user_id) {
-                count.fetch_add(1, Ordering::Relaxed);
+                erased.push(id.clone());
                 false
             } else {
                 true
             }
         });
-        Ok(count.load(Ordering::Relaxed))
+        for id in &erased {
+            self.notify(id, SessionChange::Deleted);
+        }
+        Ok(erased.len())
     }
 }
 
@@ -194,31 +277,21 @@ impl ArtifactStore for InMemoryStore {
     }
 
     async fn save_with_id(&self, id: &RefId, data: Bytes) -> Result<()> {
-        let artifact = StoredArtifact {
-            data,
-            content_type: "application/octet-stream".to_string(),
-            created_at: Self::current_timestamp(),
-        };
-        self.data.insert(id.0.clone(), artifact);
+        self.insert(id.0.clone(), data, "application/octet-stream");
         Ok(())
     }
 
     async fn save_with_type(&self, data: Bytes, content_type: &str) -> Result<RefId> {
         let ref_id = RefId::new();
-        let artifact = StoredArtifact {
-            data,
-            content_type: content_type.to_string(),
-            created_at: Self::current_timestamp(),
-        };
 
         tracing::trace!(
             ref_id = %ref_id,
-            size = artifact.data.len(),
+            size = data.len(),
             content_type = content_type,
             "Storing artifact in memory"
         );
 
-        self.data.insert(ref_id.0.clone(), artifact);
+        self.insert(ref_id.0.clone(), data, content_type);
         Ok(ref_id)
     }
 
@@ -227,7 +300,9 @@ impl ArtifactStore for InMemoryStore {
     }
 
     async fn delete(&self, id: &RefId) -> Result<()> {
-        self.data.remove(&id.0);
+        if let Some((key, artifact)) = self.data.remove(&id.0) {
+            self.unindex(&key, &artifact);
+        }
         Ok(())
     }
 
@@ -236,12 +311,31 @@ impl ArtifactStore for InMemoryStore {
     }
 
     async fn metadata(&self, id: &RefId) -> Result<Option<ArtifactMetadata>> {
-        Ok(self.data.get(&id.0).map(|r| ArtifactMetadata {
-            size: r.data.len(),
-            content_type: r.content_type.clone(),
-            created_at: r.created_at,
-            tier: StorageTier::Hot,
-        }))
+        Ok(self.data.get(&id.0).map(|r| describe(r.key(), &r)))
+    }
+
+    async fn list(&self, filter: ArtifactFilter) -> Result<Vec<ArtifactMetadata>> {
+        let mut artifacts: Vec<ArtifactMetadata> = self
+            .data
+            .iter()
+            .map(|r| describe(r.key(), r.value()))
+            .filter(|meta| filter.matches(meta))
+            .collect();
+        artifacts.sort_by(|a, b| a.id.as_str().cmp(b.id.as_str()));
+        artifacts.truncate(filter.limit.unwrap_or(usize::MAX));
+        Ok(artifacts)
+    }
+}
+
+fn describe(key: &str, artifact: &StoredArtifact) -> ArtifactMetadata {
+    ArtifactMetadata {
+        id: RefId::from_string(key),
+        size: artifact.data.len(),
+        content_type: artifact.content_type.clone(),
+        created_at: artifact.created_at,
+        tier: StorageTier::Hot,
+        owner_user_id: artifact.owner.as_ref().and_then(|o| o.user_id.clone()),
+        session_id: artifact.owner.as_ref().and_then(|o| o.session_id.clone()),
     }
 }
 
@@ -250,38 +344,103 @@ impl Prunable for InMemoryStore {
     async fn prune(&self, max_age: std::time::Duration) -> Result<usize> {
         let now = Self::current_timestamp();
         let cutoff = now - max_age.as_secs() as i64;
-        use std::sync::atomic::{AtomicUsize, Ordering};
-        let count = AtomicUsize::new(0);
 
-        self.data.retain(|_, v| {
-            if v.created_at < cutoff {
-                count.fetch_add(1, Ordering::Relaxed);
-                false
-            } else {
-                true
+        let expired: Vec<String> = self
+            .data
+            .iter()
+            .filter(|r| r.created_at < cutoff)
+            .map(|r| r.key().clone())
+            .collect();
+        let mut count = 0;
+        for key in expired {
+            if let Some((key, artifact)) = self.data.remove_if(&key, |_, v| v.created_at < cutoff) {
+                self.unindex(&key, &artifact);
+                count += 1;
             }
-        });
-        Ok(count.load(Ordering::Relaxed))
+        }
+        Ok(count)
     }
 }
 
 #[async_trait]
 impl Erasable for InMemoryStore {
     async fn erase_user(&self, user_id: &str) -> Result<usize> {
-        use std::sync::atomic::{AtomicUsize, Ordering};
-        let count = AtomicUsize::new(0);
-        // Assuming keys are namespaced: "user_id/..."
+        // Artifacts recorded as owned by the user.
+        let mut keys: Vec<String> = self
+            .owners
+            .remove(user_id)
+            .map(|(_, keys)| keys.into_iter().collect())
+            .unwrap_or_default();
+        // Legacy user-namespaced keys: "user_id/..."
         let prefix = format!("{}/", user_id);
+        keys.extend(
+            self.data
+                .iter()
+                .filter(|r| r.key().starts_with(&prefix))
+                .map(|r| r.key().clone()),
+        );
 
-        self.data.retain(|k, _| {
-            if k.starts_with(&prefix) {
-                count.fetch_add(1, Ordering::Relaxed);
-                false
-            } else {
-                true
+        let mut count = 0;
+        for key in keys {
+            if let Some((key, artifact)) = self.data.remove(&key) {
+                self.unindex(&key, &artifact);
+                count += 1;
             }
-        });
-        Ok(count.load(Ordering::Relaxed))
+        }
+        Ok(count)
+    }
+}
+
+/// In-memory provider store, used when no persistent backend is configured.
+pub struct InMemoryProviderStore {
+    providers: std::sync::RwLock<Vec<ProviderEntry>>,
+}
+
+impl InMemoryProviderStore {
+    /// Create a new in-memory provider store.
+    pub fn new() -> Self {
+        Self {
+            providers: std::sync::RwLock::new(Vec::new()),
+        }
+    }
+}
+
+impl Default for InMemoryProviderStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ProviderStore for InMemoryProviderStore {
+    async fn list(&self) -> Result<Vec<ProviderEntry>> {
+        Ok(self.providers.read().unwrap().clone())
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<ProviderEntry>> {
+        Ok(self
+            .providers
+            .read()
+            .unwrap()
+            .iter()
+            .find(|p| p.id == id)
+            .cloned())
+    }
+
+    async fn upsert(&self, provider: &ProviderEntry) -> Result<()> {
+        let mut providers = self.providers.write().unwrap();
+        match providers.iter_mut().find(|p| p.id == provider.id) {
+            Some(existing) => *existing = provider.clone(),
+            None => providers.push(provider.clone()),
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool> {
+        let mut providers = self.providers.write().unwrap();
+        let len_before = providers.len();
+        providers.retain(|p| p.id != id);
+        Ok(providers.len() != len_before)
     }
 }
 
@@ -353,4 +512,109 @@ mod tests {
         assert_eq!(store.len(), 2);
         assert_eq!(store.memory_usage(), data1.len() + data2.len());
     }
+
+    #[tokio::test]
+    async fn test_owner_metadata_and_erase() {
+        let store = InMemoryStore::new();
+        let owner = ArtifactOwner::new(Some("alice".into()), Some("s1".into()));
+
+        let owned = owner
+            .scope(store.save(Bytes::from("alice's data")))
+            .await
+            .unwrap();
+        let other = store.save(Bytes::from("anonymous")).await.unwrap();
+
+        let meta = store.metadata(&owned).await.unwrap().unwrap();
+        assert_eq!(meta.owner_user_id.as_deref(), Some("alice"));
+        assert_eq!(meta.session_id.as_deref(), Some("s1"));
+        assert!(store
+            .metadata(&other)
+            .await
+            .unwrap()
+            .unwrap()
+            .owner_user_id
+            .is_none());
+
+        assert_eq!(store.erase_user("alice").await.unwrap(), 1);
+        assert!(!store.exists(&owned).await.unwrap());
+        assert!(store.exists(&other).await.unwrap());
+        assert!(store.owners.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_list_filters_and_pages_by_id() {
+        let store = InMemoryStore::new();
+        let alice = ArtifactOwner::new(Some("alice".into()), Some("s1".into()));
+        for id in ["c", "a", "b"] {
+            alice
+                .clone()
+                .scope(store.save_with_id(&RefId::from_string(id), Bytes::from(id)))
+                .await
+                .unwrap();
+        }
+        store
+            .save_with_id(&RefId::from_string("d"), Bytes::from("d"))
+            .await
+            .unwrap();
+
+        let ids = |artifacts: Vec<ArtifactMetadata>| {
+            artifacts
+                .into_iter()
+                .map(|m| m.id.0)
+                .collect::<Vec<String>>()
+        };
+        let all = store.list(ArtifactFilter::default()).await.unwrap();
+        assert_eq!(ids(all), ["a", "b", "c", "d"]);
+
+        let page = store
+            .list(ArtifactFilter {
+                owner_user_id: Some("alice".into()),
+                after_id: Some(RefId::from_string("a")),
+                limit: Some(1),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(page[0].session_id.as_deref(), Some("s1"));
+        assert_eq!(ids(page), ["b"]);
+
+        let none = store
+            .list(ArtifactFilter {
+                created_before: Some(0),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(none.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_provider_store_upsert_and_delete() {
+        let store = InMemoryProviderStore::new();
+        let mut entry = ProviderEntry {
+            id: "prov-1".into(),
+            vendor: "smtp".into(),
+            model_id: "agent@example.com".into(),
+            description: None,
+            base_url: "smtps://mail.example.com".into(),
+            regional_urls: Vec::new(),
+            monthly_spend_cap_usd: None,
+            version: None,
+            api_key_id: "api_key:prov-1".into(),
+            capabilities: vec![],
+            status: "active".into(),
+        };
+        store.upsert(&entry).await.unwrap();
+        entry.status = "error".into();
+        store.upsert(&entry).await.unwrap();
+
+        let providers = store.list().await.unwrap();
+        assert_eq!(providers.len(), 1);
+        assert_eq!(providers[0].status, "error");
+        assert!(store.get("prov-1").await.unwrap().is_some());
+
+        assert!(store.delete("prov-1").await.unwrap());
+        assert!(!store.delete("prov-1").await.unwrap());
+        assert!(store.list().await.unwrap().is_empty());
+    }
 }
@@ -0,0 +1,244 @@
+//! Postgres implementations of SessionStore and ProviderStore.
+//!
+//! Both stores share the pool returned by [`connect`], which applies the
+//! schema migrations in `migrations/postgres` before handing it out.
+//! Records are kept whole as JSONB, with the fields queries filter on
+//! copied into indexed columns.
+
+use async_trait::async_trait;
+use sqlx::migrate::Migrator;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use sqlx::types::Json;
+use std::time::Duration;
+
+use crate::retention::{Erasable, Prunable};
+use multi_agent_core::{
+    traits::{ProviderEntry, ProviderStore, SessionStore},
+    types::{Session, SessionStatus},
+    Error, Result,
+};
+
+/// Schema migrations, applied in order by [`connect`].
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations/postgres");
+
+const MAX_CONNECTIONS: u32 = 10;
+
+fn db_err(e: sqlx::Error) -> Error {
+    Error::storage(format!("Postgres error: {}", e))
+}
+
+/// Connect to `url` and bring the schema up to date.
+pub async fn connect(url: &str) -> Result<PgPool> {
+    let pool = PgPoolOptions::new()
+        .max_connections(MAX_CONNECTIONS)
+        .connect(url)
+        .await
+        .map_err(|e| Error::storage(format!("Failed to connect to Postgres: {}", e)))?;
+    MIGRATOR
+        .run(&pool)
+        .await
+        .map_err(|e| Error::storage(format!("Postgres migration failed: {}", e)))?;
+    Ok(pool)
+}
+
+/// Name a status is stored under, as serialized.
+fn status_name(status: SessionStatus) -> String {
+    serde_json::to_value(status)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("{:?}", status))
+}
+
+// =============================================================================
+// Postgres Session Store
+// =============================================================================
+
+/// Postgres persistence for sessions.
+pub struct PostgresSessionStore {
+    pool: PgPool,
+}
+
+impl PostgresSessionStore {
+    /// Store on a pool from [`connect`].
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SessionStore for PostgresSessionStore {
+    async fn save(&self, session: &Session) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO sessions (id, user_id, status, updated_at, data)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (id) DO UPDATE SET
+                 user_id = EXCLUDED.user_id,
+                 status = EXCLUDED.status,
+                 updated_at = EXCLUDED.updated_at,
+                 data = EXCLUDED.data",
+        )
+        .bind(&session.id)
+        .bind(&session.user_id)
+        .bind(status_name(session.status))
+        .bind(session.updated_at)
+        .bind(Json(session))
+        .execute(&self.pool)
+        .await
+        .map_err(db_err)?;
+        Ok(())
+    }
+
+    async fn load(&self, session_id: &str) -> Result<Option<Session>> {
+        let data: Option<Json<Session>> =
+            sqlx::query_scalar("SELECT data FROM sessions WHERE id = $1")
+                .bind(session_id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(db_err)?;
+        Ok(data.map(|Json(session)| session))
+    }
+
+    async fn delete(&self, session_id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM sessions WHERE id = $1")
+            .bind(session_id)
+            .execute(&self.pool)
+            .await
+            .map_err(db_err)?;
+        Ok(())
+    }
+
+    async fn list_running(&self) -> Result<Vec<String>> {
+        sqlx::query_scalar("SELECT id FROM sessions WHERE status = $1")
+            .bind(status_name(SessionStatus::Running))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(db_err)
+    }
+
+    async fn list_sessions(
+        &self,
+        status: Option<SessionStatus>,
+        user_id: Option<&str>,
+    ) -> Result<Vec<Session>> {
+        let rows: Vec<Json<Session>> = sqlx::query_scalar(
+            "SELECT data FROM sessions
+             WHERE ($1::TEXT IS NULL OR status = $1)
+               AND ($2::TEXT IS NULL OR user_id = $2)
+             ORDER BY updated_at DESC",
+        )
+        .bind(status.map(status_name))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_err)?;
+        Ok(rows.into_iter().map(|Json(session)| session).collect())
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::storage(format!("Postgres health check failed: {}", e)))?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Prunable for PostgresSessionStore {
+    async fn prune(&self, max_age: Duration) -> Result<usize> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        let cutoff = now - max_age.as_secs() as i64;
+        let result = sqlx::query("DELETE FROM sessions WHERE updated_at < $1")
+            .bind(cutoff)
+            .execute(&self.pool)
+            .await
+            .map_err(db_err)?;
+        Ok(result.rows_affected() as usize)
+    }
+}
+
+#[async_trait]
+impl Erasable for PostgresSessionStore {
+    async fn erase_user(&self, user_id: &str) -> Result<usize> {
+        let result = sqlx::query("DELETE FROM sessions WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(db_err)?;
+        Ok(result.rows_affected() as usize)
+    }
+}
+
+// =============================================================================
+// Postgres Provider Store (for Admin)
+// =============================================================================
+
+/// Postgres persistence for providers.
+pub struct PostgresProviderStore {
+    pool: PgPool,
+}
+
+impl PostgresProviderStore {
+    /// Store on a pool from [`connect`].
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ProviderStore for PostgresProviderStore {
+    async fn list(&self) -> Result<Vec<ProviderEntry>> {
+        let rows: Vec<Json<ProviderEntry>> =
+            sqlx::query_scalar("SELECT data FROM providers ORDER BY id")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(db_err)?;
+        Ok(rows.into_iter().map(|Json(provider)| provider).collect())
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<ProviderEntry>> {
+        let data: Option<Json<ProviderEntry>> =
+            sqlx::query_scalar("SELECT data FROM providers WHERE id = $1")
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(db_err)?;
+        Ok(data.map(|Json(provider)| provider))
+    }
+
+    async fn upsert(&self, provider: &ProviderEntry) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO providers (id, data) VALUES ($1, $2)
+             ON CONFLICT (id) DO UPDATE SET data = EXCLUDED.data",
+        )
+        .bind(&provider.id)
+        .bind(Json(provider))
+        .execute(&self.pool)
+        .await
+        .map_err(db_err)?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM providers WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(db_err)?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_names_match_serialization() {
+        assert_eq!(status_name(SessionStatus::Running), "Running");
+        assert_eq!(status_name(SessionStatus::Degraded), "Degraded");
+    }
+}
@@ -2,12 +2,17 @@
 
 use async_trait::async_trait;
 use redis::{AsyncCommands, Client, Script};
+use std::sync::Arc;
 use std::time::Duration;
 
+use crate::codec::SessionCodec;
 use crate::retention::{Erasable, Prunable};
 use multi_agent_core::{
-    traits::{DistributedRateLimiter, ProviderEntry, ProviderStore, SessionStore, StateStore},
-    types::Session,
+    traits::{
+        poll_session, DistributedRateLimiter, ProviderEntry, ProviderStore, SessionStore,
+        SessionWatch, StateStore, SESSION_POLL_INTERVAL,
+    },
+    types::{Session, SessionChange},
     Error, Result,
 };
 
@@ -156,6 +161,7 @@ pub struct RedisSessionStore {
     prefix: String,
     ttl_seconds: usize,
     strict_mode: bool,
+    codec: SessionCodec,
 }
 
 impl RedisSessionStore {
@@ -169,6 +175,7 @@ impl RedisSessionStore {
             prefix: prefix.to_string(),
             ttl_seconds,
             strict_mode: false,
+            codec: SessionCodec::default(),
         })
     }
 
@@ -178,9 +185,72 @@ impl RedisSessionStore {
         self
     }
 
+    /// Encoding of written sessions; any encoding is read back.
+    pub fn with_codec(mut self, codec: SessionCodec) -> Self {
+        self.codec = codec;
+        self
+    }
+
     fn key(&self, id: &str) -> String {
         format!("{}:{}", self.prefix, id)
     }
+
+    /// Watch a session through keyspace notifications, which the server
+    /// must publish for generic (`g`) and string (`$`) commands.
+    async fn keyspace_watch(self: Arc<Self>, id: &str) -> Result<SessionWatch> {
+        let mut conn = self
+            .client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| Error::storage(format!("Redis connection error: {}", e)))?;
+        let config: Vec<String> = redis::cmd("CONFIG")
+            .arg("GET")
+            .arg("notify-keyspace-events")
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| Error::storage(format!("Redis config error: {}", e)))?;
+        let flags = config.get(1).map(String::as_str).unwrap_or_default();
+        let all = flags.contains('A');
+        if !flags.contains('K') || !(all || flags.contains('g') && flags.contains('$')) {
+            return Err(Error::storage(format!(
+                "Keyspace notifications are not enabled (notify-keyspace-events = {:?})",
+                flags
+            )));
+        }
+
+        let mut pubsub = self
+            .client
+            .get_async_pubsub()
+            .await
+            .map_err(|e| Error::storage(format!("Redis connection error: {}", e)))?;
+        let db = self.client.get_connection_info().redis.db;
+        pubsub
+            .subscribe(format!("__keyspace@{}__:{}", db, self.key(id)))
+            .await
+            .map_err(|e| Error::storage(format!("Redis subscribe error: {}", e)))?;
+
+        use futures::StreamExt;
+        let id = id.to_string();
+        let changes = pubsub.into_on_message().filter_map(move |msg| {
+            let store = self.clone();
+            let id = id.clone();
+            async move {
+                match msg.get_payload::<String>().ok()?.as_str() {
+                    "set" => match store.load(&id).await {
+                        Ok(Some(session)) => Some(SessionChange::Saved(Box::new(session))),
+                        Ok(None) => Some(SessionChange::Deleted),
+                        Err(e) => {
+                            tracing::warn!(session_id = %id, error = %e, "Failed to load watched session");
+                            None
+                        }
+                    },
+                    "del" | "expired" | "evicted" => Some(SessionChange::Deleted),
+                    _ => None,
+                }
+            }
+        });
+        Ok(changes.boxed())
+    }
 }
 
 #[async_trait]
@@ -193,18 +263,21 @@ impl SessionStore for RedisSessionStore {
             .map_err(|e| Error::storage(format!("Redis connection error: {}", e)))?;
 
         let key = self.key(id);
-        let data: Option<String> = conn
+        let data: Option<Vec<u8>> = conn
             .get(&key)
             .await
             .map_err(|e| Error::storage(format!("Redis get error: {}", e)))?;
 
-        match data {
-            Some(json) => {
-                let session = serde_json::from_str(&json)
-                    .map_err(|e| Error::storage(format!("Failed to deserialize session: {}", e)))?;
-                Ok(Some(session))
+        data.map(|bytes| self.codec.decode(&bytes)).transpose()
+    }
+
+    async fn watch(self: Arc<Self>, id: &str) -> Result<SessionWatch> {
+        match self.clone().keyspace_watch(id).await {
+            Ok(watch) => Ok(watch),
+            Err(e) => {
+                tracing::debug!(error = %e, "Falling back to polling for session watch");
+                poll_session(self, id, SESSION_POLL_INTERVAL).await
             }
-            None => Ok(None),
         }
     }
 
@@ -216,12 +289,11 @@ impl SessionStore for RedisSessionStore {
             .map_err(|e| Error::storage(format!("Redis connection error: {}", e)))?;
 
         let key = self.key(&session.id);
-        let json = serde_json::to_string(session)
-            .map_err(|e| Error::storage(format!("Failed to serialize session: {}", e)))?;
+        let encoded = self.codec.encode(session)?;
 
         // Set with TTL
         let _: () = conn
-            .set_ex(&key, json, self.ttl_seconds as u64)
+            .set_ex(&key, encoded, self.ttl_seconds as u64)
             .await
             .map_err(|e| Error::storage(format!("Redis set error: {}", e)))?;
 
@@ -272,13 +344,13 @@ impl SessionStore for RedisSessionStore {
 
         let mut running_ids = Vec::new();
         for key in keys {
-            let data: Option<String> = conn
+            let data: Option<Vec<u8>> = conn
                 .get(&key)
                 .await
                 .map_err(|e| Error::storage(format!("Redis get error: {}", e)))?;
 
-            if let Some(json) = data {
-                if let Ok(session) = serde_json::from_str::<Session>(&json) {
+            if let Some(bytes) = data {
+                if let Ok(session) = self.codec.decode::<Session>(&bytes) {
                     if session.status == multi_agent_core::types::SessionStatus::Running {
                         running_ids.push(session.id);
                     }
@@ -321,13 +393,13 @@ impl SessionStore for RedisSessionStore {
 
         let mut sessions = Vec::new();
         for key in keys {
-            let data: Option<String> = conn
+            let data: Option<Vec<u8>> = conn
                 .get(&key)
                 .await
                 .map_err(|e| Error::storage(format!("Redis get error: {}", e)))?;
 
-            if let Some(json) = data {
-                if let Ok(session) = serde_json::from_str::<Session>(&json) {
+            if let Some(bytes) = data {
+                if let Ok(session) = self.codec.decode::<Session>(&bytes) {
                     let status_match = status.is_none_or(|s| session.status == s);
                     let user_match = user_id.is_none_or(|u| session.user_id.as_deref() == Some(u));
                     if status_match && user_match {
@@ -394,13 +466,13 @@ impl Prunable for RedisSessionStore {
             // We optimized by only fetching if we need to check content.
             // But here we need to check updated_at inside the JSON.
             // Alternatively, we could check IDLETIME but that's access time, not update time.
-            let data: Option<String> = conn
+            let data: Option<Vec<u8>> = conn
                 .get(&key)
                 .await
                 .map_err(|e| Error::storage(format!("Redis get error: {}", e)))?;
 
-            if let Some(json) = data {
-                if let Ok(session) = serde_json::from_str::<Session>(&json) {
+            if let Some(bytes) = data {
+                if let Ok(session) = self.codec.decode::<Session>(&bytes) {
                     if session.updated_at < cutoff {
                         let _: () = conn
                             .del(&key)
@@ -447,13 +519,13 @@ impl Erasable for RedisSessionStore {
         let mut deleted_count = 0;
 
         for key in keys {
-            let data: Option<String> = conn
+            let data: Option<Vec<u8>> = conn
                 .get(&key)
                 .await
                 .map_err(|e| Error::storage(format!("Redis get error: {}", e)))?;
 
-            if let Some(json) = data {
-                if let Ok(session) = serde_json::from_str::<Session>(&json) {
+            if let Some(bytes) = data {
+                if let Ok(session) = self.codec.decode::<Session>(&bytes) {
                     if session.user_id.as_deref() == Some(user_id) {
                         let _: () = conn
                             .del(&key)
@@ -1,23 +1,212 @@
 //! S3 implementation of ArtifactStore.
 
 use async_trait::async_trait;
-use aws_sdk_s3::{primitives::ByteStream, Client};
+use aws_sdk_s3::{
+    config::http::HttpResponse,
+    error::{DisplayErrorContext, ProvideErrorMetadata, SdkError},
+    primitives::ByteStream,
+    types::ServerSideEncryption,
+    Client,
+};
 use bytes::Bytes;
+use rand::Rng;
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::{Duration, Instant};
 
 use crate::retention::{Erasable, Prunable};
-use multi_agent_core::{traits::ArtifactStore, types::RefId, Error, Result};
+use multi_agent_core::{
+    config::{S3RetryConfig, S3SseConfig, S3SseMode},
+    traits::ArtifactStore,
+    types::{ArtifactOwner, RefId},
+    Error, Result,
+};
+
+/// Key segment under which per-user ownership markers are written.
+const OWNER_INDEX: &str = "_owners";
+/// Object metadata keys (sent as `x-amz-meta-*`).
+const META_OWNER_USER: &str = "owner-user-id";
+const META_SESSION: &str = "session-id";
+/// HEAD requests in flight at once while listing.
+const LIST_HEAD_CONCURRENCY: usize = 16;
+
+/// Artifact metadata from a HEAD response.
+fn head_metadata(
+    id: RefId,
+    output: aws_sdk_s3::operation::head_object::HeadObjectOutput,
+) -> multi_agent_core::traits::ArtifactMetadata {
+    let user_metadata = |key: &str| output.metadata.as_ref().and_then(|m| m.get(key).cloned());
+    multi_agent_core::traits::ArtifactMetadata {
+        id,
+        size: output.content_length.unwrap_or(0) as usize,
+        created_at: output.last_modified.map(|d| d.secs()).unwrap_or(0),
+        tier: multi_agent_core::traits::StorageTier::Cold,
+        owner_user_id: user_metadata(META_OWNER_USER),
+        session_id: user_metadata(META_SESSION),
+        content_type: output
+            .content_type
+            .clone()
+            .unwrap_or_else(|| "application/octet-stream".to_string()),
+    }
+}
+
+/// Object metadata recording the artifact's owner.
+fn owner_metadata(owner: &ArtifactOwner) -> HashMap<String, String> {
+    let mut metadata = HashMap::new();
+    if let Some(user_id) = &owner.user_id {
+        metadata.insert(META_OWNER_USER.to_string(), user_id.clone());
+    }
+    if let Some(session_id) = &owner.session_id {
+        metadata.insert(META_SESSION.to_string(), session_id.clone());
+    }
+    metadata
+}
+
+/// Classification of an S3 failure, used for retry decisions and error mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum S3ErrorKind {
+    /// The key or bucket does not exist.
+    NotFound,
+    /// Credentials are invalid or lack permission.
+    AccessDenied,
+    /// The service asked us to slow down (SlowDown, 429, 503).
+    Throttled,
+    /// The attempt did not complete within the configured timeout.
+    Timeout,
+    /// Connection failures and 5xx responses.
+    Transient,
+    /// Anything else (validation errors, malformed requests).
+    Other,
+}
+
+impl S3ErrorKind {
+    /// Classify from the S3 error code and HTTP status.
+    pub fn from_code(code: Option<&str>, status: Option<u16>) -> Self {
+        match code {
+            Some("NoSuchKey" | "NoSuchBucket" | "NotFound") => return Self::NotFound,
+            Some(
+                "AccessDenied"
+                | "Forbidden"
+                | "InvalidAccessKeyId"
+                | "SignatureDoesNotMatch"
+                | "AllAccessDisabled",
+            ) => return Self::AccessDenied,
+            Some(
+                "SlowDown"
+                | "Throttling"
+                | "ThrottlingException"
+                | "RequestLimitExceeded"
+                | "TooManyRequests",
+            ) => return Self::Throttled,
+            Some("RequestTimeout" | "RequestTimeoutException") => return Self::Timeout,
+            Some("InternalError" | "ServiceUnavailable") => return Self::Transient,
+            _ => {}
+        }
+        match status {
+            Some(404) => Self::NotFound,
+            Some(401 | 403) => Self::AccessDenied,
+            Some(429 | 503) => Self::Throttled,
+            Some(408) => Self::Timeout,
+            Some(500..=599) => Self::Transient,
+            _ => Self::Other,
+        }
+    }
+
+    /// Whether an operation failing with this kind should be retried.
+    pub fn is_retryable(self) -> bool {
+        matches!(self, Self::Throttled | Self::Timeout | Self::Transient)
+    }
+
+    fn as_label(self) -> &'static str {
+        match self {
+            Self::NotFound => "not_found",
+            Self::AccessDenied => "access_denied",
+            Self::Throttled => "throttled",
+            Self::Timeout => "timeout",
+            Self::Transient => "transient",
+            Self::Other => "error",
+        }
+    }
+}
+
+/// A classified S3 failure for a single attempt.
+#[derive(Debug)]
+struct S3Failure {
+    kind: S3ErrorKind,
+    message: String,
+}
+
+impl S3Failure {
+    fn from_sdk<E>(err: SdkError<E, HttpResponse>) -> Self
+    where
+        E: ProvideErrorMetadata + std::error::Error + 'static,
+    {
+        let kind = match &err {
+            SdkError::TimeoutError(_) => S3ErrorKind::Timeout,
+            SdkError::DispatchFailure(_) | SdkError::ResponseError(_) => S3ErrorKind::Transient,
+            _ => {
+                S3ErrorKind::from_code(err.code(), err.raw_response().map(|r| r.status().as_u16()))
+            }
+        };
+        Self {
+            kind,
+            message: DisplayErrorContext(&err).to_string(),
+        }
+    }
+
+    fn into_error(self, op: &str, target: &str) -> Error {
+        let msg = format!("S3 {} '{}': {}", op, target, self.message);
+        match self.kind {
+            S3ErrorKind::NotFound => Error::ArtifactNotFound(msg),
+            S3ErrorKind::AccessDenied => Error::access_denied(msg),
+            S3ErrorKind::Throttled => Error::throttled(msg),
+            S3ErrorKind::Timeout => Error::Timeout(msg),
+            S3ErrorKind::Transient | S3ErrorKind::Other => Error::storage(msg),
+        }
+    }
+}
+
+/// Compute the backoff before retry number `attempt` (1-based), with equal jitter.
+fn backoff_delay(config: &S3RetryConfig, attempt: u32) -> Duration {
+    let exp = config
+        .base_delay_ms
+        .saturating_mul(1u64 << attempt.saturating_sub(1).min(20));
+    let capped = exp.min(config.max_delay_ms);
+    let half = capped / 2;
+    let jitter = if half > 0 {
+        rand::thread_rng().gen_range(0..=half)
+    } else {
+        0
+    };
+    Duration::from_millis(capped - half + jitter)
+}
+
+/// Map the configured SSE mode to the `x-amz-server-side-encryption` and
+/// `x-amz-server-side-encryption-aws-kms-key-id` request headers.
+fn sse_headers(sse: &S3SseConfig) -> (Option<ServerSideEncryption>, Option<String>) {
+    match sse.mode {
+        S3SseMode::None => (None, None),
+        S3SseMode::SseS3 => (Some(ServerSideEncryption::Aes256), None),
+        S3SseMode::SseKms => (Some(ServerSideEncryption::AwsKms), sse.kms_key_id.clone()),
+    }
+}
 
 /// S3 storage for artifacts.
 pub struct S3ArtifactStore {
     client: Client,
     bucket: String,
     prefix: String,
+    retry: S3RetryConfig,
+    sse: S3SseConfig,
 }
 
 impl S3ArtifactStore {
     /// Create a new S3 artifact store.
     pub async fn new(bucket: &str, prefix: &str, endpoint: Option<&str>) -> Self {
-        let mut config_loader = aws_config::defaults(aws_config::BehaviorVersion::latest());
+        // Retries are handled by `run` so that they are classified and metered;
+        // disable the SDK's own retry layer to avoid multiplying attempts.
+        let mut config_loader = aws_config::defaults(aws_config::BehaviorVersion::latest())
+            .retry_config(aws_config::retry::RetryConfig::disabled());
 
         if let Some(url) = endpoint {
             config_loader = config_loader.endpoint_url(url);
@@ -34,6 +223,8 @@ impl S3ArtifactStore {
             client,
             bucket: bucket.to_string(),
             prefix: prefix.to_string(),
+            retry: S3RetryConfig::default(),
+            sse: S3SseConfig::default(),
         }
     }
 
@@ -43,132 +234,400 @@ impl S3ArtifactStore {
             client,
             bucket: bucket.to_string(),
             prefix: prefix.to_string(),
+            retry: S3RetryConfig::default(),
+            sse: S3SseConfig::default(),
         }
     }
 
+    /// Set the timeout and retry policy.
+    pub fn with_retry_config(mut self, retry: S3RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Set the server-side encryption applied to every write.
+    pub fn with_sse(mut self, sse: S3SseConfig) -> Self {
+        self.sse = sse;
+        self
+    }
+
     fn key(&self, id: &RefId) -> String {
+        self.prefixed(id.as_str())
+    }
+
+    fn prefixed(&self, key: &str) -> String {
         if self.prefix.is_empty() {
-            id.to_string()
+            key.to_string()
         } else {
-            format!("{}/{}", self.prefix, id)
+            format!("{}/{}", self.prefix, key)
+        }
+    }
+
+    /// Prefix of the ownership markers for `user_id`; a marker's suffix is the artifact's RefId.
+    fn owner_index_prefix(&self, user_id: &str) -> String {
+        self.prefixed(&format!("{}/{}/", OWNER_INDEX, user_id))
+    }
+
+    /// Save an artifact, recording the current owner (if any) in object metadata
+    /// and in the owner index.
+    async fn save_object(&self, id: &RefId, data: Bytes, content_type: Option<&str>) -> Result<()> {
+        let owner = ArtifactOwner::current();
+        let metadata = owner.as_ref().map(owner_metadata);
+        self.put(&self.key(id), data, content_type, metadata)
+            .await?;
+
+        if let Some(user_id) = owner.and_then(|o| o.user_id) {
+            let marker = format!("{}{}", self.owner_index_prefix(&user_id), id);
+            self.put(&marker, Bytes::new(), None, None).await?;
         }
+        Ok(())
+    }
+
+    /// Run an S3 operation with per-attempt timeout, classified retries and metrics.
+    async fn run<T, F, Fut>(&self, op: &'static str, target: &str, mut attempt_fn: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = std::result::Result<T, S3Failure>>,
+    {
+        let started = Instant::now();
+        let timeout = Duration::from_millis(self.retry.timeout_ms);
+        let mut attempt = 0u32;
+
+        loop {
+            let outcome = match tokio::time::timeout(timeout, attempt_fn()).await {
+                Ok(outcome) => outcome,
+                Err(_) => Err(S3Failure {
+                    kind: S3ErrorKind::Timeout,
+                    message: format!("attempt timed out after {}ms", self.retry.timeout_ms),
+                }),
+            };
+
+            let failure = match outcome {
+                Ok(value) => {
+                    record_operation(op, "success", started);
+                    return Ok(value);
+                }
+                Err(failure) => failure,
+            };
+
+            if failure.kind.is_retryable() && attempt < self.retry.max_retries {
+                attempt += 1;
+                let delay = backoff_delay(&self.retry, attempt);
+                metrics::counter!("s3_retries_total", "operation" => op, "kind" => failure.kind.as_label())
+                    .increment(1);
+                tracing::warn!(
+                    operation = op,
+                    key = target,
+                    attempt = attempt,
+                    delay_ms = delay.as_millis() as u64,
+                    kind = failure.kind.as_label(),
+                    "Retrying S3 operation: {}",
+                    failure.message
+                );
+                tokio::time::sleep(delay).await;
+                continue;
+            }
+
+            record_operation(op, failure.kind.as_label(), started);
+            return Err(failure.into_error(op, target));
+        }
+    }
+
+    async fn put(
+        &self,
+        key: &str,
+        data: Bytes,
+        content_type: Option<&str>,
+        metadata: Option<HashMap<String, String>>,
+    ) -> Result<()> {
+        let (client, bucket) = (&self.client, &self.bucket);
+        let (sse, kms_key_id) = sse_headers(&self.sse);
+        self.run("put_object", key, move || {
+            let data = data.clone();
+            let (sse, kms_key_id) = (sse.clone(), kms_key_id.clone());
+            let metadata = metadata.clone();
+            async move {
+                client
+                    .put_object()
+                    .bucket(bucket)
+                    .key(key)
+                    .body(ByteStream::from(data))
+                    .set_content_type(content_type.map(str::to_string))
+                    .set_metadata(metadata)
+                    .set_server_side_encryption(sse)
+                    .set_ssekms_key_id(kms_key_id)
+                    .send()
+                    .await
+                    .map(|_| ())
+                    .map_err(S3Failure::from_sdk)
+            }
+        })
+        .await
+    }
+
+    async fn head(
+        &self,
+        key: &str,
+    ) -> Result<aws_sdk_s3::operation::head_object::HeadObjectOutput> {
+        let (client, bucket) = (&self.client, &self.bucket);
+        self.run("head_object", key, move || async move {
+            client
+                .head_object()
+                .bucket(bucket)
+                .key(key)
+                .send()
+                .await
+                .map_err(S3Failure::from_sdk)
+        })
+        .await
+    }
+
+    async fn delete_key(&self, key: &str) -> Result<()> {
+        let (client, bucket) = (&self.client, &self.bucket);
+        self.run("delete_object", key, move || async move {
+            client
+                .delete_object()
+                .bucket(bucket)
+                .key(key)
+                .send()
+                .await
+                .map(|_| ())
+                .map_err(S3Failure::from_sdk)
+        })
+        .await
     }
+
+    /// Delete every object whose key starts with `prefix` and satisfies `filter`.
+    async fn delete_matching<P>(&self, prefix: &str, filter: P) -> Result<usize>
+    where
+        P: Fn(&aws_sdk_s3::types::Object) -> bool,
+    {
+        self.delete_listed(prefix, |object| match object.key() {
+            Some(key) if filter(object) => vec![key.to_string()],
+            _ => Vec::new(),
+        })
+        .await
+    }
+
+    /// List objects under `prefix` and delete the keys `select` returns for each.
+    /// Returns the number of listed objects that selected at least one key.
+    async fn delete_listed<F>(&self, prefix: &str, select: F) -> Result<usize>
+    where
+        F: Fn(&aws_sdk_s3::types::Object) -> Vec<String>,
+    {
+        let (client, bucket) = (&self.client, &self.bucket);
+        let mut continuation_token: Option<String> = None;
+        let mut count = 0;
+
+        loop {
+            let token = continuation_token.clone();
+            let output = self
+                .run("list_objects_v2", prefix, move || {
+                    let token = token.clone();
+                    async move {
+                        client
+                            .list_objects_v2()
+                            .bucket(bucket)
+                            .prefix(prefix)
+                            .set_continuation_token(token)
+                            .send()
+                            .await
+                            .map_err(S3Failure::from_sdk)
+                    }
+                })
+                .await?;
+
+            let mut keys_to_delete = Vec::new();
+
+            for object in output.contents() {
+                let keys = select(object);
+                if keys.is_empty() {
+                    continue;
+                }
+                count += 1;
+                for key in keys {
+                    // construct ObjectIdentifier for batch delete
+                    keys_to_delete.push(
+                        aws_sdk_s3::types::ObjectIdentifier::builder()
+                            .key(key)
+                            .build()
+                            .map_err(|e| {
+                                Error::storage(format!("Failed to build object identifier: {}", e))
+                            })?,
+                    );
+                }
+            }
+
+            // DeleteObjects accepts at most 1000 keys per request.
+            for chunk in keys_to_delete.chunks(1000) {
+                let delete = aws_sdk_s3::types::Delete::builder()
+                    .set_objects(Some(chunk.to_vec()))
+                    .build()
+                    .map_err(|e| {
+                        Error::storage(format!("Failed to build delete request: {}", e))
+                    })?;
+
+                self.run("delete_objects", prefix, move || {
+                    let delete = delete.clone();
+                    async move {
+                        client
+                            .delete_objects()
+                            .bucket(bucket)
+                            .delete(delete)
+                            .send()
+                            .await
+                            .map_err(S3Failure::from_sdk)
+                    }
+                })
+                .await?;
+            }
+
+            if output.is_truncated.unwrap_or(false) {
+                continuation_token = output.next_continuation_token;
+            } else {
+                break;
+            }
+        }
+
+        Ok(count)
+    }
+}
+
+fn record_operation(op: &'static str, outcome: &'static str, started: Instant) {
+    metrics::counter!("s3_operations_total", "operation" => op, "outcome" => outcome).increment(1);
+    metrics::histogram!("s3_operation_duration_seconds", "operation" => op)
+        .record(started.elapsed().as_secs_f64());
 }
 
 #[async_trait]
 impl ArtifactStore for S3ArtifactStore {
     async fn save(&self, data: Bytes) -> Result<RefId> {
         let id = RefId::new();
-        let key = self.key(&id);
-
-        self.client
-            .put_object()
-            .bucket(&self.bucket)
-            .key(&key)
-            .body(ByteStream::from(data))
-            .send()
-            .await
-            .map_err(|e| Error::storage(format!("S3 upload error: {}", e)))?;
-
+        self.save_object(&id, data, None).await?;
         Ok(id)
     }
 
     async fn save_with_id(&self, id: &RefId, data: Bytes) -> Result<()> {
-        let key = self.key(id);
-        self.client
-            .put_object()
-            .bucket(&self.bucket)
-            .key(&key)
-            .body(ByteStream::from(data))
-            .send()
-            .await
-            .map_err(|e| Error::storage(format!("S3 upload error: {}", e)))?;
-        Ok(())
+        self.save_object(id, data, None).await
     }
 
     async fn save_with_type(&self, data: Bytes, content_type: &str) -> Result<RefId> {
         let id = RefId::new();
-        let key = self.key(&id);
-
-        self.client
-            .put_object()
-            .bucket(&self.bucket)
-            .key(&key)
-            .body(ByteStream::from(data))
-            .content_type(content_type)
-            .send()
-            .await
-            .map_err(|e| Error::storage(format!("S3 upload error: {}", e)))?;
-
+        self.save_object(&id, data, Some(content_type)).await?;
         Ok(id)
     }
 
     async fn load(&self, id: &RefId) -> Result<Option<Bytes>> {
         let key = self.key(id);
+        let (client, bucket, key_ref) = (&self.client, &self.bucket, key.as_str());
 
         let result = self
-            .client
-            .get_object()
-            .bucket(&self.bucket)
-            .key(&key)
-            .send()
+            .run("get_object", key_ref, move || async move {
+                let output = client
+                    .get_object()
+                    .bucket(bucket)
+                    .key(key_ref)
+                    .send()
+                    .await
+                    .map_err(S3Failure::from_sdk)?;
+                output
+                    .body
+                    .collect()
+                    .await
+                    .map(|data| data.into_bytes())
+                    .map_err(|e| S3Failure {
+                        kind: S3ErrorKind::Transient,
+                        message: format!("body read error: {}", e),
+                    })
+            })
             .await;
 
         match result {
-            Ok(output) => {
-                let data = output
+            Ok(data) => Ok(Some(data)),
+            Err(Error::ArtifactNotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn load_range(&self, id: &RefId, start: u64, end: u64) -> Result<Option<Bytes>> {
+        let key = self.key(id);
+        let range = format!("bytes={}-{}", start, end);
+        let (client, bucket, key_ref, range) =
+            (&self.client, &self.bucket, key.as_str(), range.as_str());
+
+        let result = self
+            .run("get_object_range", key_ref, move || async move {
+                let output = client
+                    .get_object()
+                    .bucket(bucket)
+                    .key(key_ref)
+                    .range(range)
+                    .send()
+                    .await
+                    .map_err(S3Failure::from_sdk)?;
+                output
                     .body
                     .collect()
                     .await
-                    .map_err(|e| Error::storage(format!("S3 body read error: {}", e)))?
-                    .into_bytes();
-                Ok(Some(data))
-            }
-            Err(e) => {
-                let msg = e.to_string();
-                if msg.contains("NoSuchKey") || msg.contains("NotFound") || msg.contains("404") {
-                    Ok(None)
-                } else {
-                    Err(Error::storage(format!("S3 download error: {}", e)))
-                }
-            }
+                    .map(|data| data.into_bytes())
+                    .map_err(|e| S3Failure {
+                        kind: S3ErrorKind::Transient,
+                        message: format!("body read error: {}", e),
+                    })
+            })
+            .await;
+
+        match result {
+            Ok(data) => Ok(Some(data)),
+            Err(Error::ArtifactNotFound(_)) => Ok(None),
+            Err(e) => Err(e),
         }
     }
 
+    async fn presigned_url(
+        &self,
+        id: &RefId,
+        expires_in: std::time::Duration,
+    ) -> Result<Option<String>> {
+        let config = aws_sdk_s3::presigning::PresigningConfig::expires_in(expires_in)
+            .map_err(|e| Error::storage(format!("Invalid presign expiry: {}", e)))?;
+        let request = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(self.key(id))
+            .presigned(config)
+            .await
+            .map_err(|e| {
+                Error::storage(format!("S3 presign failed: {}", DisplayErrorContext(e)))
+            })?;
+        Ok(Some(request.uri().to_string()))
+    }
+
     async fn delete(&self, id: &RefId) -> Result<()> {
         let key = self.key(id);
 
-        self.client
-            .delete_object()
-            .bucket(&self.bucket)
-            .key(&key)
-            .send()
-            .await
-            .map_err(|e| Error::storage(format!("S3 delete error: {}", e)))?;
+        // Drop the ownership marker too, so erase_user doesn't count stale entries.
+        let owner = match self.head(&key).await {
+            Ok(output) => output
+                .metadata()
+                .and_then(|m| m.get(META_OWNER_USER))
+                .cloned(),
+            Err(_) => None,
+        };
+        if let Some(user_id) = owner {
+            let marker = format!("{}{}", self.owner_index_prefix(&user_id), id);
+            self.delete_key(&marker).await?;
+        }
 
-        Ok(())
+        self.delete_key(&key).await
     }
 
     async fn exists(&self, id: &RefId) -> Result<bool> {
-        let key = self.key(id);
-
-        match self
-            .client
-            .head_object()
-            .bucket(&self.bucket)
-            .key(&key)
-            .send()
-            .await
-        {
+        match self.head(&self.key(id)).await {
             Ok(_) => Ok(true),
-            Err(e) => {
-                let msg = e.to_string();
-                if msg.contains("NoSuchKey") || msg.contains("NotFound") || msg.contains("404") {
-                    Ok(false)
-                } else {
-                    Err(Error::storage(format!("S3 head error: {}", e)))
-                }
-            }
+            Err(Error::ArtifactNotFound(_)) => Ok(false),
+            Err(e) => Err(e),
         }
     }
 
@@ -176,190 +635,315 @@ impl ArtifactStore for S3ArtifactStore {
         &self,
         id: &RefId,
     ) -> Result<Option<multi_agent_core::traits::ArtifactMetadata>> {
-        let key = self.key(id);
+        match self.head(&self.key(id)).await {
+            Ok(output) => Ok(Some(head_metadata(id.clone(), output))),
+            Err(Error::ArtifactNotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
 
-        match self
-            .client
-            .head_object()
-            .bucket(&self.bucket)
-            .key(&key)
-            .send()
-            .await
-        {
-            Ok(output) => {
-                use multi_agent_core::traits::{ArtifactMetadata, StorageTier};
-
-                Ok(Some(ArtifactMetadata {
-                    size: output.content_length.unwrap_or(0) as usize,
-                    content_type: output
-                        .content_type
-                        .unwrap_or_else(|| "application/octet-stream".to_string()),
-                    created_at: output.last_modified.map(|d| d.secs()).unwrap_or(0),
-                    tier: StorageTier::Cold,
-                }))
-            }
-            Err(e) => {
-                let msg = e.to_string();
-                if msg.contains("NoSuchKey") || msg.contains("NotFound") || msg.contains("404") {
-                    Ok(None)
+    async fn list(
+        &self,
+        filter: multi_agent_core::traits::ArtifactFilter,
+    ) -> Result<Vec<multi_agent_core::traits::ArtifactMetadata>> {
+        use futures::StreamExt;
+
+        // With an owner, walk that user's markers instead of the whole prefix.
+        let listing_prefix = match &filter.owner_user_id {
+            Some(user_id) => self.owner_index_prefix(user_id),
+            None => self.prefixed(""),
+        };
+        let index_prefix = self.prefixed(&format!("{}/", OWNER_INDEX));
+        let limit = filter.limit.unwrap_or(usize::MAX);
+        let (client, bucket, prefix) = (&self.client, &self.bucket, listing_prefix.as_str());
+        // S3 lists keys in ascending order, so the cursor maps to StartAfter.
+        let mut start_after = filter
+            .after_id
+            .as_ref()
+            .map(|id| format!("{}{}", listing_prefix, id));
+        let mut artifacts = Vec::new();
+
+        loop {
+            let after = start_after.clone();
+            let output = self
+                .run("list_objects_v2", prefix, move || {
+                    let after = after.clone();
+                    async move {
+                        client
+                            .list_objects_v2()
+                            .bucket(bucket)
+                            .prefix(prefix)
+                            .set_start_after(after)
+                            .send()
+                            .await
+                            .map_err(S3Failure::from_sdk)
+                    }
+                })
+                .await?;
+
+            // Size and modification time come with the listing; markers are
+            // empty objects, so theirs are taken from the artifact itself.
+            let mut candidates = Vec::new();
+            for object in output.contents() {
+                let Some(key) = object.key() else { continue };
+                start_after = Some(key.to_string());
+                let Some(id) = key.strip_prefix(prefix) else {
+                    continue;
+                };
+                let listed = if filter.owner_user_id.is_none() {
+                    if key.starts_with(&index_prefix) {
+                        continue;
+                    }
+                    let created_at = object.last_modified().map(|d| d.secs()).unwrap_or(0);
+                    if !filter.created_in_range(created_at) {
+                        continue;
+                    }
+                    Some((object.size().unwrap_or(0) as usize, created_at))
                 } else {
-                    Err(Error::storage(format!("S3 metadata error: {}", e)))
+                    None
+                };
+                candidates.push((RefId::from_string(id), listed));
+            }
+
+            // Owner, session and content type are only in the object metadata:
+            // fetch it for just enough candidates to fill the page.
+            let mut candidates = candidates.into_iter();
+            while artifacts.len() < limit {
+                let batch: Vec<_> = candidates
+                    .by_ref()
+                    .take((limit - artifacts.len()).min(LIST_HEAD_CONCURRENCY))
+                    .collect();
+                if batch.is_empty() {
+                    break;
                 }
+                let heads: Vec<_> = futures::stream::iter(batch)
+                    .map(|(id, listed)| async move {
+                        let key = self.key(&id);
+                        (id, listed, self.head(&key).await)
+                    })
+                    .buffered(LIST_HEAD_CONCURRENCY)
+                    .collect()
+                    .await;
+                for (id, listed, head) in heads {
+                    let mut meta = match head {
+                        Ok(output) => head_metadata(id, output),
+                        // Deleted since it was listed.
+                        Err(Error::ArtifactNotFound(_)) => continue,
+                        Err(e) => return Err(e),
+                    };
+                    if let Some((size, created_at)) = listed {
+                        meta.size = size;
+                        meta.created_at = created_at;
+                    }
+                    if filter.matches(&meta) {
+                        artifacts.push(meta);
+                    }
+                }
+            }
+
+            if artifacts.len() >= limit || !output.is_truncated.unwrap_or(false) {
+                break;
             }
         }
+
+        artifacts.truncate(limit);
+        Ok(artifacts)
     }
 
     async fn health_check(&self) -> Result<()> {
-        self.client
-            .head_bucket()
-            .bucket(&self.bucket)
-            .send()
-            .await
-            .map_err(|e| {
-                Error::storage(format!(
-                    "S3 health check failed for bucket '{}': {}",
-                    self.bucket, e
-                ))
-            })?;
-
-        Ok(())
+        let (client, bucket) = (&self.client, &self.bucket);
+        self.run("head_bucket", bucket, move || async move {
+            client
+                .head_bucket()
+                .bucket(bucket)
+                .send()
+                .await
+                .map(|_| ())
+                .map_err(S3Failure::from_sdk)
+        })
+        .await
     }
 }
 
 #[async_trait]
 impl Prunable for S3ArtifactStore {
     async fn prune(&self, max_age: std::time::Duration) -> Result<usize> {
-        let mut continuation_token = None;
-        let mut count = 0;
         let now_secs = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .map(|d| d.as_secs() as i64)
             .unwrap_or(0);
         let cutoff = now_secs - max_age.as_secs() as i64;
 
-        loop {
-            let output = self
-                .client
-                .list_objects_v2()
-                .bucket(&self.bucket)
-                .prefix(&self.prefix)
-                .set_continuation_token(continuation_token)
-                .send()
-                .await
-                .map_err(|e| Error::storage(format!("S3 list error: {}", e)))?;
-
-            let mut keys_to_delete = Vec::new();
+        self.delete_matching(&self.prefix, |object| {
+            object
+                .last_modified()
+                .is_some_and(|last_modified| last_modified.secs() < cutoff)
+        })
+        .await
+    }
+}
 
-            for object in output.contents.unwrap_or_default() {
-                if let Some(last_modified) = object.last_modified {
-                    if last_modified.secs() < cutoff {
-                        if let Some(key) = object.key {
-                            // construct ObjectIdentifier for batch delete
-                            keys_to_delete.push(
-                                aws_sdk_s3::types::ObjectIdentifier::builder()
-                                    .key(key)
-                                    .build()
-                                    .unwrap(),
-                            );
-                        }
-                    }
-                }
-            }
+#[async_trait]
+impl Erasable for S3ArtifactStore {
+    async fn erase_user(&self, user_id: &str) -> Result<usize> {
+        // Artifacts recorded in the owner index: delete each artifact and its marker.
+        let index_prefix = self.owner_index_prefix(user_id);
+        let owned = self
+            .delete_listed(&index_prefix, |marker| {
+                let Some(marker_key) = marker.key() else {
+                    return Vec::new();
+                };
+                let Some(id) = marker_key.strip_prefix(&index_prefix) else {
+                    return Vec::new();
+                };
+                vec![self.key(&RefId::from_string(id)), marker_key.to_string()]
+            })
+            .await?;
+
+        // Legacy user-namespaced keys: prefix/user_id/
+        let legacy = self
+            .delete_matching(&self.prefixed(&format!("{}/", user_id)), |_| true)
+            .await?;
+
+        Ok(owned + legacy)
+    }
+}
 
-            if !keys_to_delete.is_empty() {
-                let len = keys_to_delete.len();
-                let delete = aws_sdk_s3::types::Delete::builder()
-                    .set_objects(Some(keys_to_delete))
-                    .build()
-                    .map_err(|e| {
-                        Error::storage(format!("Failed to build delete request: {}", e))
-                    })?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_by_code() {
+        assert_eq!(
+            S3ErrorKind::from_code(Some("NoSuchKey"), Some(404)),
+            S3ErrorKind::NotFound
+        );
+        assert_eq!(
+            S3ErrorKind::from_code(Some("AccessDenied"), Some(403)),
+            S3ErrorKind::AccessDenied
+        );
+        assert_eq!(
+            S3ErrorKind::from_code(Some("SlowDown"), Some(503)),
+            S3ErrorKind::Throttled
+        );
+        assert_eq!(
+            S3ErrorKind::from_code(Some("InvalidArgument"), Some(400)),
+            S3ErrorKind::Other
+        );
+    }
 
-                self.client
-                    .delete_objects()
-                    .bucket(&self.bucket)
-                    .delete(delete)
-                    .send()
-                    .await
-                    .map_err(|e| Error::storage(format!("S3 delete objects error: {}", e)))?;
+    #[test]
+    fn test_classify_by_status() {
+        // HEAD responses carry no error code
+        assert_eq!(
+            S3ErrorKind::from_code(None, Some(404)),
+            S3ErrorKind::NotFound
+        );
+        assert_eq!(
+            S3ErrorKind::from_code(None, Some(403)),
+            S3ErrorKind::AccessDenied
+        );
+        assert_eq!(
+            S3ErrorKind::from_code(None, Some(429)),
+            S3ErrorKind::Throttled
+        );
+        assert_eq!(
+            S3ErrorKind::from_code(None, Some(502)),
+            S3ErrorKind::Transient
+        );
+        assert_eq!(S3ErrorKind::from_code(None, None), S3ErrorKind::Other);
+    }
 
-                count += len;
-            }
+    #[test]
+    fn test_retryable_kinds() {
+        assert!(S3ErrorKind::Throttled.is_retryable());
+        assert!(S3ErrorKind::Timeout.is_retryable());
+        assert!(S3ErrorKind::Transient.is_retryable());
+        assert!(!S3ErrorKind::NotFound.is_retryable());
+        assert!(!S3ErrorKind::AccessDenied.is_retryable());
+    }
 
-            if output.is_truncated.unwrap_or(false) {
-                continuation_token = output.next_continuation_token;
-            } else {
-                break;
-            }
+    #[test]
+    fn test_failure_maps_to_core_error() {
+        let err = S3Failure {
+            kind: S3ErrorKind::AccessDenied,
+            message: "denied".into(),
         }
+        .into_error("get_object", "a/b");
+        assert!(matches!(err, Error::AccessDenied(_)));
 
-        Ok(count)
+        let err = S3Failure {
+            kind: S3ErrorKind::Other,
+            message: "bad".into(),
+        }
+        .into_error("put_object", "a/b");
+        assert!(matches!(err, Error::Storage(_)));
     }
-}
 
-#[async_trait]
-impl Erasable for S3ArtifactStore {
-    async fn erase_user(&self, user_id: &str) -> Result<usize> {
-        let mut continuation_token = None;
-        let mut count = 0;
-        // Assume namespacing: prefix/user_id/
-        let prefix = if self.prefix.is_empty() {
-            format!("{}/", user_id)
-        } else {
-            format!("{}/{}/", self.prefix, user_id)
+    #[test]
+    fn test_backoff_is_bounded() {
+        let config = S3RetryConfig {
+            timeout_ms: 1000,
+            max_retries: 10,
+            base_delay_ms: 100,
+            max_delay_ms: 1000,
         };
+        for attempt in 1..=10 {
+            let delay = backoff_delay(&config, attempt).as_millis() as u64;
+            let exp = (100u64 << (attempt - 1)).min(1000);
+            assert!(
+                delay >= exp / 2 && delay <= exp,
+                "attempt {attempt}: {delay}"
+            );
+        }
+    }
 
-        loop {
-            let output = self
-                .client
-                .list_objects_v2()
-                .bucket(&self.bucket)
-                .prefix(&prefix)
-                .set_continuation_token(continuation_token)
-                .send()
-                .await
-                .map_err(|e| Error::storage(format!("S3 list error: {}", e)))?;
-
-            let mut keys_to_delete = Vec::new();
-
-            for object in output.contents.unwrap_or_default() {
-                if let Some(key) = object.key {
-                    keys_to_delete.push(
-                        aws_sdk_s3::types::ObjectIdentifier::builder()
-                            .key(key)
-                            .build()
-                            .unwrap(),
-                    );
-                }
-            }
-
-            if !keys_to_delete.is_empty() {
-                let len = keys_to_delete.len();
-                let delete = aws_sdk_s3::types::Delete::builder()
-                    .set_objects(Some(keys_to_delete))
-                    .build()
-                    .map_err(|e| {
-                        Error::storage(format!("Failed to build delete request: {}", e))
-                    })?;
+    #[test]
+    fn test_sse_headers() {
+        assert_eq!(sse_headers(&S3SseConfig::default()), (None, None));
 
-                self.client
-                    .delete_objects()
-                    .bucket(&self.bucket)
-                    .delete(delete)
-                    .send()
-                    .await
-                    .map_err(|e| Error::storage(format!("S3 delete objects error: {}", e)))?;
+        let s3 = S3SseConfig {
+            mode: S3SseMode::SseS3,
+            kms_key_id: Some("ignored".into()),
+        };
+        assert_eq!(sse_headers(&s3), (Some(ServerSideEncryption::Aes256), None));
 
-                count += len;
-            }
+        let kms = S3SseConfig {
+            mode: S3SseMode::SseKms,
+            kms_key_id: Some("arn:aws:kms:us-east-1:123:key/abc".into()),
+        };
+        assert_eq!(
+            sse_headers(&kms),
+            (
+                Some(ServerSideEncryption::AwsKms),
+                Some("arn:aws:kms:us-east-1:123:key/abc".to_string())
+            )
+        );
+    }
 
-            if output.is_truncated.unwrap_or(false) {
-                continuation_token = output.next_continuation_token;
-            } else {
-                break;
-            }
-        }
+    #[test]
+    fn test_owner_metadata() {
+        let owner = ArtifactOwner::new(Some("alice".into()), None);
+        let metadata = owner_metadata(&owner);
+        assert_eq!(
+            metadata.get(META_OWNER_USER).map(String::as_str),
+            Some("alice")
+        );
+        assert!(!metadata.contains_key(META_SESSION));
+    }
 
-        Ok(count)
+    #[test]
+    fn test_head_metadata() {
+        let owner = ArtifactOwner::new(Some("alice".into()), Some("s1".into()));
+        let output = aws_sdk_s3::operation::head_object::HeadObjectOutput::builder()
+            .content_length(42)
+            .set_metadata(Some(owner_metadata(&owner)))
+            .build();
+        let meta = head_metadata(RefId::from_string("a"), output);
+        assert_eq!(meta.size, 42);
+        assert_eq!(meta.content_type, "application/octet-stream");
+        assert_eq!(meta.owner_user_id.as_deref(), Some("alice"));
+        assert_eq!(meta.session_id.as_deref(), Some("s1"));
     }
 }
@@ -0,0 +1,340 @@
+//! Test harness for end-to-end tests of the gateway and admin routers.
+//!
+//! [`TestApp`] builds the full router stack the way `src/main.rs` does, but
+//! with in-memory stores and mock clients, and sends requests through it
+//! without binding a socket:
+//!
+//! ```ignore
+//! let app = TestApp::new();
+//! let response = app.post("/v1/admin/providers").admin().json(&body).send().await;
+//! assert_eq!(response.status, StatusCode::OK);
+//! assert_json_snapshot!("provider_created", redact(response.json(), &["id"]));
+//! ```
+//!
+//! Responses are compared against JSON snapshots with
+//! [`assert_json_snapshot!`]; see [`snapshot`].
+
+pub mod snapshot;
+
+use axum::{
+    body::Body,
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, Request, StatusCode},
+    Router,
+};
+use bytes::Bytes;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+use multi_agent_admin::AdminState;
+use multi_agent_core::config::AppConfig;
+use multi_agent_core::traits::{Controller, LlmClient};
+use multi_agent_gateway::{DefaultRouter, GatewayConfig, GatewayServer, InMemorySemanticCache};
+use multi_agent_governance::{AesGcmSecretsManager, InMemoryAuditStore, NoOpRbacConnector};
+use multi_agent_store::{InMemorySessionStore, InMemoryStore};
+
+pub use snapshot::redact;
+
+/// Bearer token the admin API accepts in tests.
+pub const ADMIN_TOKEN: &str = "admin";
+
+/// Admin state over in-memory audit and secrets stores, with no artifact or
+/// session store. Tests override fields with struct update syntax.
+pub fn admin_state(config: AppConfig) -> AdminState {
+    AdminState {
+        audit_store: Arc::new(InMemoryAuditStore::new()),
+        rbac: Arc::new(NoOpRbacConnector),
+        metrics: None,
+        mcp_registry: Arc::new(multi_agent_skills::McpRegistry::new()),
+        openapi_registry: None,
+        providers: Arc::new(tokio::sync::RwLock::new(Vec::new())),
+        provider_store: None,
+        secrets: Arc::new(AesGcmSecretsManager::new(None)),
+        privacy_controller: None,
+        artifact_store: None,
+        session_store: None,
+        app_config: config,
+        network_policy: Arc::new(tokio::sync::RwLock::new(
+            multi_agent_governance::network::NetworkPolicy::default(),
+        )),
+        audit_presets: Arc::new(multi_agent_admin::audit_view::AuditPresetStore::new()),
+        audit_anchorer: None,
+        guardrails: Arc::new(multi_agent_governance::RouteGuardrails::default()),
+        tool_analytics: None,
+        endpoint_registry: None,
+        active_client: None,
+        provider_sync: None,
+        model_canary: None,
+    }
+}
+
+type AdminHook = Box<dyn FnOnce(&mut AdminState)>;
+type ServerHook = Box<dyn FnOnce(GatewayServer) -> GatewayServer>;
+
+/// Builds a [`TestApp`].
+pub struct TestAppBuilder {
+    config: AppConfig,
+    controller: Option<Arc<dyn Controller>>,
+    llm: Arc<dyn LlmClient>,
+    cache: Option<Arc<InMemorySemanticCache>>,
+    peer: SocketAddr,
+    admin_hooks: Vec<AdminHook>,
+    server_hooks: Vec<ServerHook>,
+}
+
+impl TestAppBuilder {
+    /// Adjust the application config before the routers are built.
+    pub fn config(mut self, configure: impl FnOnce(&mut AppConfig)) -> Self {
+        configure(&mut self.config);
+        self
+    }
+
+    /// Controller executing chat missions; without one, chat only routes.
+    pub fn controller(mut self, controller: Arc<dyn Controller>) -> Self {
+        self.controller = Some(controller);
+        self
+    }
+
+    /// Client behind the semantic cache.
+    pub fn llm(mut self, llm: Arc<dyn LlmClient>) -> Self {
+        self.llm = llm;
+        self
+    }
+
+    /// Semantic cache of the gateway; one over [`Self::llm`] by default.
+    pub fn cache(mut self, cache: Arc<InMemorySemanticCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Adjust the admin state, e.g. to swap the RBAC connector.
+    pub fn admin(mut self, configure: impl FnOnce(&mut AdminState) + 'static) -> Self {
+        self.admin_hooks.push(Box::new(configure));
+        self
+    }
+
+    /// Add gateway components, e.g. `|server| server.with_feature_flags(flags)`.
+    pub fn server(
+        mut self,
+        configure: impl FnOnce(GatewayServer) -> GatewayServer + 'static,
+    ) -> Self {
+        self.server_hooks.push(Box::new(configure));
+        self
+    }
+
+    /// Address requests appear to come from; loopback by default.
+    pub fn peer(mut self, peer: SocketAddr) -> Self {
+        self.peer = peer;
+        self
+    }
+
+    pub fn build(self) -> TestApp {
+        let audit = Arc::new(InMemoryAuditStore::new());
+        let secrets = Arc::new(AesGcmSecretsManager::new(None));
+        let artifacts = Arc::new(InMemoryStore::new());
+        let sessions = Arc::new(InMemorySessionStore::new());
+        let mut admin = AdminState {
+            audit_store: audit.clone(),
+            secrets: secrets.clone(),
+            artifact_store: Some(artifacts.clone()),
+            session_store: Some(sessions.clone()),
+            ..admin_state(self.config.clone())
+        };
+        for hook in self.admin_hooks {
+            hook(&mut admin);
+        }
+        let admin = Arc::new(admin);
+
+        let gateway_config = GatewayConfig {
+            host: "127.0.0.1".to_string(),
+            port: 0,
+            enable_cors: false,
+            enable_tracing: false,
+            allowed_origins: vec![],
+            tls: Default::default(),
+            unix_socket: None,
+        };
+        let cache = self
+            .cache
+            .unwrap_or_else(|| Arc::new(InMemorySemanticCache::new(self.llm)));
+        let mut server = GatewayServer::new(gateway_config, Arc::new(DefaultRouter::new()), cache)
+            .with_admin(admin.clone())
+            .with_artifact_store(artifacts.clone());
+        if let Some(controller) = self.controller {
+            server = server.with_controller(controller);
+        }
+        for hook in self.server_hooks {
+            server = hook(server);
+        }
+
+        TestApp {
+            router: server.build_router(),
+            admin_router: multi_agent_admin::admin_router(admin.clone()),
+            admin,
+            audit,
+            secrets,
+            artifacts,
+            sessions,
+            peer: self.peer,
+        }
+    }
+}
+
+/// The gateway and standalone admin routers over in-memory state.
+pub struct TestApp {
+    router: Router,
+    admin_router: Router,
+    pub admin: Arc<AdminState>,
+    pub audit: Arc<InMemoryAuditStore>,
+    pub secrets: Arc<AesGcmSecretsManager>,
+    pub artifacts: Arc<InMemoryStore>,
+    pub sessions: Arc<InMemorySessionStore>,
+    peer: SocketAddr,
+}
+
+impl Default for TestApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestApp {
+    /// App with the default config and no controller.
+    pub fn new() -> Self {
+        Self::builder().build()
+    }
+
+    pub fn builder() -> TestAppBuilder {
+        TestAppBuilder {
+            config: AppConfig::default(),
+            controller: None,
+            llm: Arc::new(multi_agent_model_gateway::MockLlmClient::new("dummy")),
+            cache: None,
+            peer: SocketAddr::from(([127, 0, 0, 1], 40000)),
+            admin_hooks: Vec::new(),
+            server_hooks: Vec::new(),
+        }
+    }
+
+    /// The gateway router, for tests that send their own requests.
+    pub fn router(&self) -> Router {
+        self.router.clone()
+    }
+
+    /// Request to the gateway router.
+    pub fn request(&self, method: Method, uri: &str) -> TestRequest {
+        TestRequest::new(self.router.clone(), self.peer, method, uri)
+    }
+
+    /// Request to the standalone admin router served under `/api`.
+    pub fn admin_request(&self, method: Method, uri: &str) -> TestRequest {
+        TestRequest::new(self.admin_router.clone(), self.peer, method, uri)
+    }
+
+    pub fn get(&self, uri: &str) -> TestRequest {
+        self.request(Method::GET, uri)
+    }
+
+    pub fn post(&self, uri: &str) -> TestRequest {
+        self.request(Method::POST, uri)
+    }
+
+    pub fn put(&self, uri: &str) -> TestRequest {
+        self.request(Method::PUT, uri)
+    }
+
+    pub fn patch(&self, uri: &str) -> TestRequest {
+        self.request(Method::PATCH, uri)
+    }
+
+    pub fn delete(&self, uri: &str) -> TestRequest {
+        self.request(Method::DELETE, uri)
+    }
+}
+
+/// A request being built against a [`TestApp`] router.
+pub struct TestRequest {
+    router: Router,
+    request: Request<Body>,
+}
+
+impl TestRequest {
+    fn new(router: Router, peer: SocketAddr, method: Method, uri: &str) -> Self {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .expect("valid request URI");
+        request
+            .extensions_mut()
+            .insert(axum::extract::ConnectInfo(peer));
+        Self { router, request }
+    }
+
+    pub fn header(mut self, name: &'static str, value: &str) -> Self {
+        self.request.headers_mut().insert(
+            HeaderName::from_static(name),
+            HeaderValue::from_str(value).expect("valid header value"),
+        );
+        self
+    }
+
+    /// Authenticate with a bearer token.
+    pub fn bearer(self, token: &str) -> Self {
+        self.header("authorization", &format!("Bearer {}", token))
+    }
+
+    /// Authenticate as the admin user.
+    pub fn admin(self) -> Self {
+        self.bearer(ADMIN_TOKEN)
+    }
+
+    /// Send `body` as JSON.
+    pub fn json(mut self, body: &serde_json::Value) -> Self {
+        self.request.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        *self.request.body_mut() = Body::from(body.to_string());
+        self
+    }
+
+    /// Run the request through the router and buffer the response.
+    pub async fn send(self) -> TestResponse {
+        let response = self
+            .router
+            .oneshot(self.request)
+            .await
+            .expect("router is infallible");
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("buffered response body");
+        TestResponse {
+            status,
+            headers,
+            body,
+        }
+    }
+}
+
+/// A buffered response.
+#[derive(Debug)]
+pub struct TestResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+impl TestResponse {
+    /// Body parsed as JSON; panics with the body if it is not JSON.
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::from_slice(&self.body)
+            .unwrap_or_else(|e| panic!("response is not JSON ({}): {}", e, self.text()))
+    }
+
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
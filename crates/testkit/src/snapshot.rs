@@ -0,0 +1,108 @@
+//! JSON snapshot assertions.
+//!
+//! Snapshots live in `tests/snapshots/<name>.json` of the crate running the
+//! test. A missing or stale snapshot fails the test; run with
+//! `UPDATE_SNAPSHOTS=1` to write the current values instead, then review the
+//! diff before committing it.
+
+use serde_json::Value;
+use std::path::Path;
+
+/// Placeholder replacing redacted values.
+pub const REDACTED: &str = "[redacted]";
+
+/// Replace the values of `keys`, at any depth, with [`REDACTED`] so ids and
+/// timestamps do not break snapshots.
+pub fn redact(mut value: Value, keys: &[&str]) -> Value {
+    redact_in_place(&mut value, keys);
+    value
+}
+
+fn redact_in_place(value: &mut Value, keys: &[&str]) {
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if keys.contains(&key.as_str()) && !field.is_null() {
+                    *field = Value::String(REDACTED.to_string());
+                } else {
+                    redact_in_place(field, keys);
+                }
+            }
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| redact_in_place(item, keys)),
+        _ => {}
+    }
+}
+
+/// Compare `value` with the snapshot stored at `path`.
+///
+/// Prefer [`assert_json_snapshot!`](crate::assert_json_snapshot), which
+/// resolves the path from the calling crate.
+pub fn assert_snapshot(path: &Path, value: &Value) {
+    let actual = format!(
+        "{}\n",
+        serde_json::to_string_pretty(value).expect("JSON values serialize")
+    );
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some_and(|v| v != "0") {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).expect("create snapshot directory");
+        }
+        std::fs::write(path, &actual).expect("write snapshot");
+        return;
+    }
+    let expected = match std::fs::read_to_string(path) {
+        Ok(expected) => expected,
+        Err(_) => panic!(
+            "Snapshot {} does not exist; run with UPDATE_SNAPSHOTS=1 to create it.\nActual:\n{}",
+            path.display(),
+            actual
+        ),
+    };
+    if expected != actual {
+        panic!(
+            "Snapshot {} does not match; run with UPDATE_SNAPSHOTS=1 to update it.\nExpected:\n{}\nActual:\n{}",
+            path.display(),
+            expected,
+            actual
+        );
+    }
+}
+
+/// Assert that a JSON value matches `tests/snapshots/<name>.json`.
+#[macro_export]
+macro_rules! assert_json_snapshot {
+    ($name:expr, $value:expr $(,)?) => {
+        $crate::snapshot::assert_snapshot(
+            &::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests")
+                .join("snapshots")
+                .join(format!("{}.json", $name)),
+            &$value,
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_redact_nested_keys() {
+        let value = json!({
+            "id": "abc",
+            "name": "kept",
+            "entries": [{ "id": 1, "timestamp": "now", "parent": null }],
+        });
+        assert_eq!(
+            redact(value, &["id", "timestamp", "parent"]),
+            json!({
+                "id": REDACTED,
+                "name": "kept",
+                "entries": [{ "id": REDACTED, "timestamp": REDACTED, "parent": null }],
+            })
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use async_trait::async_trait;
+use axum::http::{Method, StatusCode};
+use bytes::Bytes;
+use multi_agent_core::traits::{ArtifactStore, Controller};
+use multi_agent_core::types::{AgentResult, ArtifactOwner, RefId, UserIntent};
+use multi_agent_testkit::{assert_json_snapshot, redact, TestApp};
+use serde_json::json;
+use std::sync::Arc;
+
+struct MockController;
+
+#[async_trait]
+impl Controller for MockController {
+    async fn execute(
+        &self,
+        _intent: UserIntent,
+        _trace_id: String,
+    ) -> multi_agent_core::Result<AgentResult> {
+        Ok(AgentResult::Text("Mock response".to_string()))
+    }
+    async fn resume(
+        &self,
+        _session_id: &str,
+        _user_id: Option<&str>,
+    ) -> multi_agent_core::Result<AgentResult> {
+        Ok(AgentResult::Text("Resumed".to_string()))
+    }
+    async fn cancel(&self, _session_id: &str) -> multi_agent_core::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_health_snapshot() {
+    let app = TestApp::new();
+    let response = app.get("/health").send().await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_json_snapshot!("health", redact(response.json(), &["version"]));
+}
+
+#[tokio::test]
+async fn test_chat_snapshot() {
+    let app = TestApp::builder()
+        .controller(Arc::new(MockController))
+        .build();
+    let response = app
+        .post("/v1/chat")
+        .json(&json!({ "message": "hello" }))
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_json_snapshot!(
+        "chat",
+        redact(
+            response.json(),
+            &["trace_id", "session_id", "timestamp", "duration_ms"]
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_admin_requires_token() {
+    let app = TestApp::new();
+    let response = app.get("/v1/admin/providers").send().await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_provider_lifecycle_snapshots() {
+    let app = TestApp::new();
+
+    let created = app
+        .post("/v1/admin/providers")
+        .admin()
+        .json(&json!({
+            "vendor": "openai",
+            "model_id": "gpt-4o",
+            "description": "Primary",
+            "base_url": "https://api.openai.com/v1",
+            "api_key": "sk-test",
+            "capabilities": ["chat"],
+        }))
+        .send()
+        .await;
+    assert_eq!(created.status, StatusCode::OK, "{}", created.text());
+    let id = created.json()["id"].as_str().unwrap().to_string();
+
+    let patched = app
+        .patch(&format!("/v1/admin/providers/{}", id))
+        .admin()
+        .json(&json!({ "description": null, "api_key": "sk-rotated" }))
+        .send()
+        .await;
+    assert_eq!(patched.status, StatusCode::OK, "{}", patched.text());
+
+    let listed = app.get("/v1/admin/providers").admin().send().await;
+    assert_eq!(listed.status, StatusCode::OK);
+    assert_json_snapshot!(
+        "providers_after_patch",
+        redact(listed.json(), &["id", "api_key_id"])
+    );
+
+    let audit = app
+        .get("/v1/admin/audit?order=oldest_first&page_size=10")
+        .admin()
+        .send()
+        .await;
+    assert_eq!(audit.status, StatusCode::OK);
+    assert_json_snapshot!(
+        "provider_audit_page",
+        redact(
+            audit.json(),
+            &["id", "timestamp", "trace_id", "resource", "next_cursor"]
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_artifact_listing_pages() {
+    let app = TestApp::new();
+    let owner = ArtifactOwner::new(Some("alice".into()), Some("s1".into()));
+    for id in ["a1", "a2", "a3"] {
+        owner
+            .clone()
+            .scope(
+                app.artifacts
+                    .save_with_id(&RefId::from_string(id), Bytes::from("report")),
+            )
+            .await
+            .unwrap();
+    }
+    app.artifacts
+        .save_with_id(&RefId::from_string("b1"), Bytes::from("other"))
+        .await
+        .unwrap();
+
+    let first = app
+        .get("/v1/admin/artifacts?user_id=alice&page_size=2")
+        .admin()
+        .send()
+        .await;
+    assert_eq!(first.status, StatusCode::OK, "{}", first.text());
+    assert_json_snapshot!(
+        "artifacts_first_page",
+        redact(first.json(), &["created_at"])
+    );
+
+    let cursor = first.json()["next_cursor"].as_str().unwrap().to_string();
+    let last = app
+        .get(&format!(
+            "/v1/admin/artifacts?user_id=alice&page_size=2&after_id={}",
+            cursor
+        ))
+        .admin()
+        .send()
+        .await
+        .json();
+    assert_eq!(last["artifacts"].as_array().unwrap().len(), 1);
+    assert_eq!(last["artifacts"][0]["id"], "a3");
+    assert!(last.get("next_cursor").is_none());
+}
+
+#[tokio::test]
+async fn test_standalone_admin_router() {
+    let app = TestApp::new();
+    let response = app
+        .admin_request(Method::GET, "/api/providers")
+        .admin()
+        .send()
+        .await;
+    assert_eq!(response.status, StatusCode::OK);
+    assert_eq!(response.json(), json!([]));
+}
@@ -263,7 +263,7 @@ document.getElementById('btn-test-s3')?.addEventListener('click', async () => {
     btn.innerHTML = '<i class="fa-solid fa-spinner fa-spin"></i> Testing...';
 
     try {
-        const res = await fetchWithAuth(`${API_BASE}/persistence/test`, {
+        const res = await fetchWithAuth(`${API_BASE}/config/s3/test`, {
             method: 'POST',
             body: JSON.stringify({
                 bucket: document.getElementById('s3-bucket').value,
@@ -276,8 +276,15 @@ document.getElementById('btn-test-s3')?.addEventListener('click', async () => {
 
         status.classList.remove('hidden');
         if (res.ok) {
-            status.className = 'status-message success';
-            status.textContent = '✓ Connection successful! Bucket is accessible.';
+            const data = await res.json();
+            const warnings = data.compliance?.warnings || [];
+            if (warnings.length > 0) {
+                status.className = 'status-message warning';
+                status.textContent = '⚠ Connected, with compliance warnings:\n- ' + warnings.join('\n- ');
+            } else {
+                status.className = 'status-message success';
+                status.textContent = '✓ Connection successful! Bucket is accessible and compliant.';
+            }
         } else {
             status.className = 'status-message error';
             status.textContent = '✗ Connection failed. Check your credentials.';
@@ -494,6 +501,7 @@ function handleApprovalRequest(req) {
         document.getElementById('appr-req-id').value = req.request_id;
         document.getElementById('appr-nonce').value = req.nonce;
         document.getElementById('appr-reason').value = '';
+        document.getElementById('appr-scope').value = 'once';
 
         // Update Risk Badge
         const riskBadge = document.getElementById('appr-risk-badge');
@@ -532,6 +540,15 @@ document.addEventListener('DOMContentLoaded', () => {
 document.getElementById('btn-approve')?.addEventListener('click', () => submitDecision('approved'));
 document.getElementById('btn-deny')?.addEventListener('click', () => submitDecision('denied'));
 
+function approvalScope() {
+    const kind = document.getElementById('appr-scope')?.value || 'once';
+    if (kind === 'calls') {
+        const count = parseInt(document.getElementById('appr-scope-count').value, 10) || 1;
+        return { kind, count };
+    }
+    return { kind };
+}
+
 async function submitDecision(decision) {
     const reqId = document.getElementById('appr-req-id').value;
     const nonce = document.getElementById('appr-nonce').value;
@@ -548,7 +565,8 @@ async function submitDecision(decision) {
         nonce: nonce,
         decision: decision,
         reason: reason,
-        reason_code: decision === 'approved' ? 'USER_APPROVED' : 'USER_DENIED'
+        reason_code: decision === 'approved' ? 'USER_APPROVED' : 'USER_DENIED',
+        scope: approvalScope()
     };
 
     approvalSocket.send(JSON.stringify(payload));
@@ -598,15 +616,14 @@ document.getElementById('btn-refresh-approvals')?.addEventListener('click', load
 // =========================================
 // Network Governance (Domain Rules)
 // =========================================
-let currentPolicy = { allow_domains: [], deny_domains: [] };
+let currentPolicy = { version: '', allow_domains: [], deny_domains: [], allow_ports: [80, 443] };
 
 async function loadDomainGovernance() {
     try {
-        const res = await fetchWithAuth(`${API_BASE}/config`);
-        const data = await res.json();
-
-        // Use policy from config if available, fallback to defaults
-        currentPolicy = data.network_policy || { allow_domains: [], deny_domains: [] };
+        const res = await fetchWithAuth(`${API_BASE}/config/network`);
+        if (res.ok) {
+            currentPolicy = await res.json();
+        }
         renderDomainLists();
     } catch (err) {
         console.error('Failed to load domain governance:', err);
@@ -677,13 +694,17 @@ document.getElementById('btn-save-domains')?.addEventListener('click', async ()
         });
 
         if (res.ok) {
+            currentPolicy = await res.json();
             btn.innerHTML = '<i class="fa-solid fa-check"></i> Saved!';
             setTimeout(() => {
                 btn.innerHTML = '<i class="fa-solid fa-save"></i> Save Changes';
                 btn.disabled = false;
             }, 2000);
         } else {
-            alert('Failed to save network policy');
+            const data = await res.json().catch(() => ({}));
+            const problems = (data.problems || []).join('\n');
+            alert(`Failed to save network policy${problems ? ':\n' + problems : ''}`);
+            btn.innerHTML = '<i class="fa-solid fa-save"></i> Save Changes';
             btn.disabled = false;
         }
     } catch (err) {
@@ -725,17 +746,34 @@ document.getElementById('btn-export-audit')?.addEventListener('click', async ()
     }, 2000);
 });
 
-async function loadAuditLogs() {
+const AUDIT_PAGE_SIZE = 50;
+let auditPage = 1;
+
+function auditQuery() {
+    const params = new URLSearchParams();
     const userId = document.getElementById('filter-user')?.value || '';
     const action = document.getElementById('filter-action')?.value || '';
+    const preset = document.getElementById('audit-preset')?.value || '';
+    if (userId) params.set('user_id', userId);
+    if (action) params.set('action', action);
+    if (preset) params.set('preset', preset);
+    return params;
+}
 
-    let url = `${API_BASE}/audit?limit=50`;
-    if (userId) url += `&user_id=${encodeURIComponent(userId)}`;
-    if (action) url += `&action=${encodeURIComponent(action)}`;
+async function loadAuditLogs() {
+    const params = auditQuery();
+    params.set('page', auditPage);
+    params.set('page_size', AUDIT_PAGE_SIZE);
 
     try {
-        const res = await fetchWithAuth(url);
-        const entries = await res.json();
+        const res = await fetchWithAuth(`${API_BASE}/audit?${params}`);
+        const page = await res.json();
+        const entries = page.entries || [];
+
+        const info = document.getElementById('audit-page-info');
+        if (info) info.textContent = `Page ${page.page} of ${Math.max(page.total_pages, 1)} (${page.total} entries)`;
+        document.getElementById('btn-audit-prev').disabled = page.page <= 1;
+        document.getElementById('btn-audit-next').disabled = page.page >= page.total_pages;
 
         const tbody = document.getElementById('audit-body');
         if (entries.length === 0) {
@@ -757,7 +795,76 @@ async function loadAuditLogs() {
     }
 }
 
-document.getElementById('btn-refresh')?.addEventListener('click', loadAuditLogs);
+async function loadAuditPresets() {
+    const select = document.getElementById('audit-preset');
+    if (!select) return;
+    try {
+        const res = await fetchWithAuth(`${API_BASE}/audit/presets`);
+        const presets = await res.json();
+        const current = select.value;
+        select.innerHTML = '<option value="">No preset</option>' +
+            presets.map(p => `<option value="${p.name}">${p.name}</option>`).join('');
+        select.value = current;
+    } catch (err) {
+        console.error('Failed to load audit presets:', err);
+    }
+}
+
+document.getElementById('btn-save-audit-preset')?.addEventListener('click', async () => {
+    const name = prompt('Preset name');
+    if (!name) return;
+    const filter = {};
+    const userId = document.getElementById('filter-user')?.value || '';
+    const action = document.getElementById('filter-action')?.value || '';
+    if (userId) filter.user_id = userId;
+    if (action) filter.action = action;
+
+    const res = await fetchWithAuth(`${API_BASE}/audit/presets`, {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/json' },
+        body: JSON.stringify({ name, filter })
+    });
+    if (res.ok) {
+        await loadAuditPresets();
+        document.getElementById('audit-preset').value = name;
+    } else {
+        alert('Failed to save preset');
+    }
+});
+
+document.getElementById('btn-export-audit-csv')?.addEventListener('click', async () => {
+    const res = await fetchWithAuth(`${API_BASE}/audit/export.csv?${auditQuery()}`);
+    if (!res.ok) {
+        alert('Export failed');
+        return;
+    }
+    const blob = await res.blob();
+    const url = window.URL.createObjectURL(blob);
+    const a = document.createElement('a');
+    a.href = url;
+    a.download = `audit_${new Date().toISOString().slice(0, 19).replace(/[:T]/g, '_')}.csv`;
+    document.body.appendChild(a);
+    a.click();
+    a.remove();
+});
+
+document.getElementById('audit-preset')?.addEventListener('change', () => {
+    auditPage = 1;
+    loadAuditLogs();
+});
+document.getElementById('btn-audit-prev')?.addEventListener('click', () => {
+    auditPage = Math.max(1, auditPage - 1);
+    loadAuditLogs();
+});
+document.getElementById('btn-audit-next')?.addEventListener('click', () => {
+    auditPage += 1;
+    loadAuditLogs();
+});
+
+document.getElementById('btn-refresh')?.addEventListener('click', () => {
+    auditPage = 1;
+    loadAuditLogs();
+});
 
 // =========================================
 // Initial Load
@@ -768,6 +875,7 @@ document.addEventListener('DOMContentLoaded', () => {
     loadPersistenceConfig();
     loadMcpServers();
     loadAuditLogs();
+    loadAuditPresets();
     loadResearchRuns();
     loadPendingApprovals();
     loadDomainGovernance();
@@ -8,12 +8,13 @@ use multi_agent_sandbox::SandboxEngine;
 use std::sync::Arc;
 
 use multi_agent_controller::ReActController;
+use multi_agent_core::config::StateFile;
 use multi_agent_core::traits::{ArtifactStore, SessionStore, ToolRegistry};
 use multi_agent_gateway::{DefaultRouter, GatewayConfig, GatewayServer, InMemorySemanticCache};
 use multi_agent_skills::{CalculatorTool, DefaultToolRegistry, EchoTool};
 use multi_agent_store::{
-    knowledge::SqliteKnowledgeStore, InMemorySessionStore, InMemoryStore, RedisSessionStore,
-    S3ArtifactStore, TieredStore,
+    knowledge::SqliteKnowledgeStore, CloudArtifactStore, InMemorySessionStore, InMemoryStore,
+    PostgresSessionStore, RedisSessionStore, S3ArtifactStore, SessionCodec, TieredStore,
 };
 use secrecy::ExposeSecret;
 
@@ -35,32 +36,90 @@ async fn main() -> anyhow::Result<()> {
         app_config.governance.json_logs,
     )?;
 
+    if app_config.chaos.enabled {
+        if cfg!(feature = "chaos") {
+            tracing::warn!(
+                "⚠️ Chaos mode ENABLED: faults are injected into LLM, store, tool and HTTP calls"
+            );
+        } else {
+            tracing::warn!("chaos.enabled is ignored: this build has no `chaos` feature");
+        }
+    }
+
     tracing::info!("Starting OpenCoordex v{}", env!("CARGO_PKG_VERSION"));
 
+    // `--apply <file>` reconciles the instance with a bootstrap file before
+    // serving; with `--dry-run` the plan is printed and the process exits.
+    let args: Vec<String> = std::env::args().collect();
+    let bootstrap = match args.iter().position(|a| a == "--apply") {
+        Some(i) => {
+            let path = args
+                .get(i + 1)
+                .ok_or_else(|| anyhow::anyhow!("--apply requires a bootstrap file path"))?;
+            Some(multi_agent_gateway::bootstrap::Bootstrap::load(path)?)
+        }
+        None => None,
+    };
+    let dry_run = args.iter().any(|a| a == "--dry-run");
+
     // =========================================================================
     // Initialize L3: Artifact Store
     // =========================================================================
-    let (store_raw, store): (
-        Arc<dyn multi_agent_core::traits::Erasable>,
-        Arc<dyn ArtifactStore>,
-    ) = if let Some(bucket) = &app_config.store.s3_bucket {
+    let cold: Option<Arc<dyn ArtifactStore>> = if let Some(bucket) = &app_config.store.s3_bucket {
         let endpoint = app_config.store.s3_endpoint.as_deref();
         tracing::info!(bucket = %bucket, endpoint = ?endpoint, "Initializing S3 Artifact Store (Tiered)");
 
-        let s3 = Arc::new(S3ArtifactStore::new(bucket, "", endpoint).await);
+        Some(Arc::new(
+            S3ArtifactStore::new(bucket, "", endpoint)
+                .await
+                .with_retry_config(app_config.store.s3_retry.clone())
+                .with_sse(app_config.store.s3_sse.clone()),
+        ))
+    } else if let Some(gcs) = &app_config.store.gcs {
+        tracing::info!(bucket = %gcs.bucket, "Initializing GCS Artifact Store (Tiered)");
+        Some(Arc::new(CloudArtifactStore::gcs(gcs)?))
+    } else if let Some(azure) = &app_config.store.azure_blob {
+        tracing::info!(
+            account = %azure.account,
+            container = %azure.container,
+            "Initializing Azure Blob Artifact Store (Tiered)"
+        );
+        Some(Arc::new(CloudArtifactStore::azure(azure)?))
+    } else {
+        None
+    };
+
+    // Test builds only: the cold tier, or the whole store without one, fails
+    // like an unreliable object store
+    #[cfg(feature = "chaos")]
+    let chaos_artifacts = multi_agent_core::chaos::FaultInjector::from_config(
+        "artifacts",
+        &app_config.chaos,
+        &app_config.chaos.artifacts,
+    );
+    #[cfg(feature = "chaos")]
+    let cold = match (&chaos_artifacts, cold) {
+        (Some(injector), Some(cold)) => Some(Arc::new(
+            multi_agent_core::chaos::ChaosArtifactStore::new(cold, injector.clone()),
+        ) as Arc<dyn ArtifactStore>),
+        (_, cold) => cold,
+    };
+
+    let store: Arc<dyn ArtifactStore> = if let Some(cold) = cold {
         let hot = Arc::new(InMemoryStore::new());
-        let tiered = Arc::new(TieredStore::new(hot).with_cold(s3));
-        (
-            tiered.clone() as Arc<dyn multi_agent_core::traits::Erasable>,
-            tiered as Arc<dyn ArtifactStore>,
-        )
+        Arc::new(TieredStore::new(hot).with_cold(cold))
     } else {
         tracing::info!("Initializing In-Memory Artifact Store");
-        let memory = Arc::new(InMemoryStore::new());
-        (
-            memory.clone() as Arc<dyn multi_agent_core::traits::Erasable>,
-            memory as Arc<dyn ArtifactStore>,
-        )
+        let memory: Arc<dyn ArtifactStore> = Arc::new(InMemoryStore::new());
+        #[cfg(feature = "chaos")]
+        let memory = match &chaos_artifacts {
+            Some(injector) => Arc::new(multi_agent_core::chaos::ChaosArtifactStore::new(
+                memory,
+                injector.clone(),
+            )),
+            None => memory,
+        };
+        memory
     };
 
     // Data-at-rest Encryption
@@ -86,8 +145,37 @@ async fn main() -> anyhow::Result<()> {
         store
     };
 
+    // Audit store, created early so artifact policy violations are recorded
+    if let Some(parent) = std::path::Path::new(&app_config.governance.audit_log_path).parent() {
+        std::fs::create_dir_all(parent).map_err(|e| {
+            multi_agent_core::Error::storage(format!(
+                "Failed to create audit log directory '{}': {}",
+                parent.display(),
+                e
+            ))
+        })?;
+    }
+    let audit_store = Arc::new(multi_agent_governance::SqliteAuditStore::new(
+        &app_config.governance.audit_log_path,
+    )?);
+
+    // File type policy on every artifact write
+    let store: Arc<dyn ArtifactStore> = Arc::new(
+        multi_agent_governance::PolicyArtifactStore::new(
+            store,
+            multi_agent_governance::FileTypePolicy::from_config(&app_config.store.file_policy),
+        )
+        .with_audit(audit_store.clone()),
+    );
+
+    // Index of artifacts by session, for list_artifacts and the session listing API
+    let artifact_catalog = Arc::new(multi_agent_store::ArtifactCatalog::new());
+    let store: Arc<dyn ArtifactStore> = Arc::new(multi_agent_store::CatalogArtifactStore::new(
+        store,
+        artifact_catalog.clone(),
+    ));
+
     // Secrets manager for encrypting API keys
-    let secrets_path = std::path::PathBuf::from("secrets.json");
     let master_key_bytes = if let Some(key) = &app_config.store.encryption.master_key {
         use secrecy::ExposeSecret;
         let key_str = key.expose_secret();
@@ -101,18 +189,32 @@ async fn main() -> anyhow::Result<()> {
         None
     };
 
-    let secrets_manager: Arc<dyn multi_agent_governance::SecretsManager> = Arc::new(
-        multi_agent_governance::secrets::FilePersistentSecretsManager::new(
-            secrets_path,
-            master_key_bytes,
-        )
-        .await?,
-    );
+    let secrets_manager: Arc<dyn multi_agent_governance::SecretsManager> = match app_config
+        .state_path(StateFile::Secrets)
+    {
+        Some(secrets_path) => Arc::new(
+            multi_agent_governance::secrets::FilePersistentSecretsManager::new(
+                secrets_path,
+                master_key_bytes,
+            )
+            .await?,
+        ),
+        None => {
+            tracing::warn!(
+                    "Local state files disabled; secrets are kept in memory. Set store.data_dir to persist them."
+                );
+            Arc::new(multi_agent_governance::AesGcmSecretsManager::new(
+                master_key_bytes,
+            ))
+        }
+    };
 
     // M11.2: Secrets Migration
     // Check for legacy onboarding.json and migrate to SecretsManager
-    let legacy_path = std::path::PathBuf::from(".sovereign_claw/onboarding.json");
-    if legacy_path.exists() {
+    if let Some(legacy_path) = app_config
+        .state_path(StateFile::Onboarding)
+        .filter(|path| path.exists())
+    {
         tracing::info!("Found legacy onboarding.json - migrating secrets...");
         match tokio::fs::read_to_string(&legacy_path).await {
             Ok(content) => {
@@ -155,17 +257,33 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
+    // Postgres, when configured, holds sessions and providers
+    let postgres_pool = match &app_config.store.postgres_url {
+        Some(url) => {
+            tracing::info!("Connecting to Postgres and applying migrations");
+            Some(multi_agent_store::postgres::connect(url).await?)
+        }
+        None => None,
+    };
+
     // Initialize Session Store
     let (session_store_raw, session_store): (
         Arc<dyn multi_agent_core::traits::Erasable>,
         Arc<dyn SessionStore>,
-    ) = if let Some(redis_url) = &app_config.store.redis_url {
+    ) = if let Some(pool) = &postgres_pool {
+        tracing::info!("Initializing Postgres Session Store");
+        let postgres = Arc::new(PostgresSessionStore::new(pool.clone()));
+        (
+            postgres.clone() as Arc<dyn multi_agent_core::traits::Erasable>,
+            postgres as Arc<dyn SessionStore>,
+        )
+    } else if let Some(redis_url) = &app_config.store.redis_url {
         tracing::info!(url = %redis_url, "Initializing Redis Session Store");
-        let redis = Arc::new(RedisSessionStore::new(
-            redis_url,
-            "opencoordex:session",
-            3600 * 24,
-        )?);
+        let redis = Arc::new(
+            RedisSessionStore::new(redis_url, "opencoordex:session", 3600 * 24)?.with_codec(
+                SessionCodec::from_config(&app_config.store.session_encoding),
+            ),
+        );
         (
             redis.clone() as Arc<dyn multi_agent_core::traits::Erasable>,
             redis as Arc<dyn SessionStore>,
@@ -178,6 +296,19 @@ async fn main() -> anyhow::Result<()> {
             memory as Arc<dyn SessionStore>,
         )
     };
+    #[cfg(feature = "chaos")]
+    let session_store: Arc<dyn SessionStore> =
+        match multi_agent_core::chaos::FaultInjector::from_config(
+            "sessions",
+            &app_config.chaos,
+            &app_config.chaos.sessions,
+        ) {
+            Some(injector) => Arc::new(multi_agent_core::chaos::ChaosSessionStore::new(
+                session_store,
+                injector,
+            )),
+            None => session_store,
+        };
 
     // =========================================================================
     // Initialize L2: Skills & Tools
@@ -187,57 +318,159 @@ async fn main() -> anyhow::Result<()> {
     // Register built-in tools
     tools.register(Box::new(EchoTool)).await?;
     tools.register(Box::new(CalculatorTool)).await?;
+    tools
+        .register(Box::new(multi_agent_skills::TabularTool::new(
+            store.clone(),
+        )))
+        .await?;
+    tools
+        .register(Box::new(multi_agent_skills::ReadArtifactTool::new(
+            store.clone(),
+        )))
+        .await?;
+    tools
+        .register(Box::new(multi_agent_skills::ListArtifactsTool::new(
+            artifact_catalog.clone(),
+        )))
+        .await?;
 
     // =========================================================================
     // Initialize Sandbox (Sovereign Execution Plane)
     // =========================================================================
-    let sandbox_manager = match multi_agent_sandbox::DockerSandbox::new() {
-        Ok(engine) => {
-            let engine = std::sync::Arc::new(engine);
-            if engine.is_available().await {
-                let config = multi_agent_sandbox::SandboxConfig::default();
-                let manager = Arc::new(multi_agent_sandbox::SandboxManager::new(engine, config));
-
-                // Register sandbox tools
-                tools
-                    .register(Box::new(multi_agent_sandbox::SandboxShellTool::new(
-                        manager.clone(),
-                    )))
-                    .await?;
-                tools
-                    .register(Box::new(multi_agent_sandbox::SandboxWriteFileTool::new(
-                        manager.clone(),
-                    )))
-                    .await?;
-                tools
-                    .register(Box::new(multi_agent_sandbox::SandboxReadFileTool::new(
-                        manager.clone(),
-                    )))
-                    .await?;
-                tools
-                    .register(Box::new(multi_agent_sandbox::SandboxListFilesTool::new(
-                        manager.clone(),
-                    )))
-                    .await?;
-
-                tracing::info!("🐳 Sovereign Sandbox initialized (Docker available)");
-                Some(manager)
-            } else {
-                tracing::warn!("Docker daemon not reachable — sandbox tools disabled");
-                None
+    // Docker first; on Kubernetes without a Docker socket, fall back to Jobs.
+    let sandbox_engine: Option<Arc<dyn multi_agent_sandbox::SandboxEngine>> =
+        match multi_agent_sandbox::DockerSandbox::new() {
+            Ok(engine) if engine.is_available().await => {
+                tracing::info!("🐳 Sovereign Sandbox using Docker");
+                Some(Arc::new(engine))
+            }
+            docker => {
+                let reason = match docker {
+                    Err(e) => e.to_string(),
+                    Ok(_) => "Docker daemon not reachable".to_string(),
+                };
+                if std::env::var("KUBERNETES_SERVICE_HOST").is_ok() {
+                    match multi_agent_sandbox::K8sSandbox::in_cluster() {
+                        Ok(engine) if engine.is_available().await => {
+                            tracing::info!("☸️ Sovereign Sandbox using Kubernetes Jobs");
+                            Some(Arc::new(engine))
+                        }
+                        Ok(_) => {
+                            tracing::warn!("Kubernetes API not reachable — sandbox tools disabled");
+                            None
+                        }
+                        Err(e) => {
+                            tracing::warn!(
+                                "Kubernetes sandbox unavailable ({}). Sandbox tools disabled.",
+                                e
+                            );
+                            None
+                        }
+                    }
+                } else {
+                    tracing::warn!("{} — sandbox tools disabled", reason);
+                    None
+                }
+            }
+        };
+
+    // Risk policy shared by the sandbox tiers, the controller's approval
+    // checks and the policy admin API. Saved edits live in the policy state
+    // file; until there is one, the built-in default applies.
+    let policy_file = match app_config
+        .state_path(StateFile::Policy)
+        .filter(|path| path.exists())
+    {
+        Some(path) => match multi_agent_governance::PolicyEngine::load(&path) {
+            Ok(engine) => engine.policy,
+            Err(e) => {
+                tracing::error!("Failed to load policy ({}). Using the default policy.", e);
+                multi_agent_governance::PolicyFile::default()
+            }
+        },
+        None => multi_agent_governance::PolicyFile::default(),
+    };
+    let policy_engine = Arc::new(tokio::sync::RwLock::new(
+        multi_agent_governance::PolicyEngine::from_file(policy_file),
+    ));
+
+    // Refuse to register sandbox tools when the image would not run as the
+    // configured non-root user.
+    let validated_sandbox = match sandbox_engine {
+        Some(engine) => {
+            let config = multi_agent_sandbox::SandboxConfig::default();
+            let isolated = multi_agent_sandbox::SandboxProfiles::from_config(
+                config.clone(),
+                &app_config.sandbox.isolated,
+            )
+            .isolated;
+            let manager = Arc::new(
+                multi_agent_sandbox::SandboxManager::new(engine, config)
+                    .with_isolated_profile(isolated)
+                    .with_policy_engine(policy_engine.clone()),
+            );
+            match manager.validate().await {
+                Ok(()) => Some(manager),
+                Err(e) => {
+                    tracing::error!("Sandbox validation failed ({}). Sandbox tools disabled.", e);
+                    None
+                }
             }
         }
-        Err(e) => {
-            tracing::warn!("Docker not available ({}). Sandbox tools disabled.", e);
-            None
+        None => None,
+    };
+
+    let sandbox_manager = match validated_sandbox {
+        Some(manager) => {
+            // Register sandbox tools
+            tools
+                .register(Box::new(
+                    multi_agent_sandbox::SandboxShellTool::new(manager.clone())
+                        .with_command_policy(multi_agent_sandbox::CommandPolicy::from_config(
+                            &app_config.sandbox.commands,
+                        )?),
+                ))
+                .await?;
+            tools
+                .register(Box::new(multi_agent_sandbox::SandboxWriteFileTool::new(
+                    manager.clone(),
+                )))
+                .await?;
+            tools
+                .register(Box::new(multi_agent_sandbox::SandboxReadFileTool::new(
+                    manager.clone(),
+                )))
+                .await?;
+            tools
+                .register(Box::new(multi_agent_sandbox::SandboxListFilesTool::new(
+                    manager.clone(),
+                )))
+                .await?;
+            tools
+                .register(Box::new(multi_agent_skills::RepoMapTool::new(
+                    manager.clone(),
+                    store.clone(),
+                )))
+                .await?;
+            tools
+                .register(Box::new(multi_agent_skills::ApplyPatchTool::new(
+                    manager.clone(),
+                )))
+                .await?;
+
+            tracing::info!("Sovereign Sandbox initialized");
+            Some(manager)
         }
+        None => None,
     };
 
     // Network Policy setup
     // Load from network_policy.json if exists, else AppConfig
-    let policy_path = std::path::PathBuf::from("network_policy.json");
-    let initial_policy = if policy_path.exists() {
-        tracing::info!("Loading network policy from network_policy.json");
+    let policy_path = app_config
+        .state_path(StateFile::NetworkPolicy)
+        .filter(|path| path.exists());
+    let initial_policy = if let Some(policy_path) = policy_path {
+        tracing::info!(path = %policy_path.display(), "Loading network policy");
         let content = tokio::fs::read_to_string(&policy_path).await?;
         serde_json::from_str(&content).unwrap_or_else(|e| {
             tracing::error!(
@@ -260,6 +493,24 @@ async fn main() -> anyhow::Result<()> {
 
     let network_policy = Arc::new(tokio::sync::RwLock::new(initial_policy));
 
+    // Per-channel guardrails, edited through the admin API
+    let guardrail_policy_path = std::path::PathBuf::from("guardrail_policy.json");
+    let guardrail_policy = if guardrail_policy_path.exists() {
+        let content = tokio::fs::read_to_string(&guardrail_policy_path).await?;
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            tracing::error!(
+                "Failed to parse guardrail_policy.json: {}. Guardrails disabled.",
+                e
+            );
+            multi_agent_governance::GuardrailPolicy::default()
+        })
+    } else {
+        multi_agent_governance::GuardrailPolicy::default()
+    };
+    let guardrails = Arc::new(multi_agent_governance::RouteGuardrails::new(
+        guardrail_policy,
+    ));
+
     // Register Network tools
     tools
         .register(Box::new(multi_agent_skills::network::FetchTool::new(
@@ -278,42 +529,86 @@ async fn main() -> anyhow::Result<()> {
             .await?;
     }
 
-    let _sandbox_manager = sandbox_manager; // keep alive
-
     tracing::info!(tools_count = tools.len(), "L2 Skills registry initialized");
 
-    // =========================================================================
-    // Initialize L1: Controller
-    // =========================================================================
-    let controller = Arc::new(
-        ReActController::builder()
-            .with_store(store.clone())
-            .with_session_store(session_store.clone())
-            .with_capability(Arc::new(
-                multi_agent_controller::MemoryWritebackCapability::from_env(),
-            ))
-            .with_compressor(Arc::new(
-                multi_agent_controller::context::TruncationCompressor::new(),
-            ))
-            .build(),
+    // Every call through this view is recorded; failing tools sink in listings.
+    let tool_analytics = Arc::new(multi_agent_skills::ToolAnalytics::new());
+    // Long outputs reach the model summarized; the full text is kept as an artifact.
+    let observed_tools: Arc<dyn ToolRegistry> = Arc::new(
+        multi_agent_skills::ObservationToolRegistry::new(
+            tools.clone(),
+            store.clone(),
+            app_config.controller.observations.max_chars,
+        )
+        .with_processor(
+            "sandbox_shell",
+            Arc::new(multi_agent_skills::TestFailureSummarizer),
+        )
+        .with_processor("fetch", Arc::new(multi_agent_skills::ReadableTextExtractor)),
     );
-    tracing::info!("L1 Controller initialized (mock ReAct)");
+    #[cfg(feature = "chaos")]
+    let observed_tools: Arc<dyn ToolRegistry> =
+        match multi_agent_core::chaos::FaultInjector::from_config(
+            "tools",
+            &app_config.chaos,
+            &app_config.chaos.tools,
+        ) {
+            Some(injector) => Arc::new(multi_agent_core::chaos::ChaosToolRegistry::new(
+                observed_tools,
+                injector,
+            )),
+            None => observed_tools,
+        };
+    let tracked_tools: Arc<dyn ToolRegistry> = Arc::new(
+        multi_agent_skills::AnalyticsToolRegistry::new(observed_tools, tool_analytics.clone()),
+    );
+
+    // Daily LLM cost, session tokens and tool calls for alerts and digests
+    let usage_ledger = Arc::new(multi_agent_model_gateway::UsageLedger::new());
+
+    // Providers over their monthly spend cap only serve privileged workspaces
+    let spend_guard = Arc::new(
+        multi_agent_model_gateway::SpendGuard::new(usage_ledger.clone())
+            .with_privileged_workspaces(
+                app_config
+                    .model_gateway
+                    .spend_caps
+                    .privileged_workspaces
+                    .clone(),
+            ),
+    );
+
+    // Provider quota state from rate-limit headers, shared by every client and
+    // the registry so throttled providers are routed around
+    let rate_limits = Arc::new(multi_agent_model_gateway::RateLimitTracker::new());
+
+    // Providers registered through the admin API, selectable per iteration
+    let provider_registry = Arc::new(
+        multi_agent_model_gateway::ProviderRegistry::new()
+            .with_rate_limits(rate_limits.clone())
+            .with_spend_guard(spend_guard.clone()),
+    );
+    let model_selector = Arc::new(multi_agent_model_gateway::AdaptiveModelSelector::new(
+        provider_registry.clone(),
+    ));
 
     // =========================================================================
     // Initialize L0: Gateway
     // =========================================================================
-    let approval_gate = Arc::new(multi_agent_governance::approval::ChannelApprovalGate::new(
-        multi_agent_core::types::ToolRiskLevel::High,
-    ));
-
     // Initialize LLM Client for embeddings
     use multi_agent_core::traits::LlmClient;
 
+    // Regional endpoints of configured providers, probed for latency
+    let endpoint_registry = Arc::new(multi_agent_model_gateway::EndpointRegistry::new());
+    let mut llm_model_id = app_config.model_gateway.default_provider.clone();
+
     let llm_client: Arc<dyn LlmClient> = {
-        let providers_path = std::path::Path::new("providers.json");
-        if providers_path.exists() {
-            tracing::info!("Loading LLM config from providers.json");
-            match multi_agent_model_gateway::config::ProviderConfig::load(providers_path).await {
+        let providers_path = app_config
+            .state_path(StateFile::Providers)
+            .filter(|path| path.exists());
+        if let Some(providers_path) = providers_path {
+            tracing::info!(path = %providers_path.display(), "Loading LLM config");
+            match multi_agent_model_gateway::config::ProviderConfig::load(&providers_path).await {
                 Ok(cfg) => {
                     let client_result = {
                         let openai_key =
@@ -342,14 +637,22 @@ async fn main() -> anyhow::Result<()> {
                         )
                     };
                     match client_result {
-                        Ok(client) => Arc::new(client),
+                        Ok(client) => {
+                            if let Some(pool) = client.endpoints() {
+                                endpoint_registry.register(client.provider().as_str(), pool);
+                            }
+                            llm_model_id = client.model_id();
+                            Arc::new(client.with_rate_limits(rate_limits.clone()))
+                        }
                         Err(e) => {
                             tracing::warn!(
                                 "Failed to create client from config: {}. Fallback to env vars.",
                                 e
                             );
                             match multi_agent_model_gateway::create_default_client() {
-                                Ok(client) => Arc::new(client),
+                                Ok(client) => {
+                                    Arc::new(client.with_rate_limits(rate_limits.clone()))
+                                }
                                 Err(_) => {
                                     Arc::new(multi_agent_model_gateway::MockLlmClient::new("dummy"))
                                 }
@@ -363,7 +666,7 @@ async fn main() -> anyhow::Result<()> {
                         e
                     );
                     match multi_agent_model_gateway::create_default_client() {
-                        Ok(client) => Arc::new(client),
+                        Ok(client) => Arc::new(client.with_rate_limits(rate_limits.clone())),
                         Err(_) => Arc::new(multi_agent_model_gateway::MockLlmClient::new("dummy")),
                     }
                 }
@@ -371,7 +674,7 @@ async fn main() -> anyhow::Result<()> {
         } else {
             tracing::info!("No providers.json found. Using environment variables.");
             match multi_agent_model_gateway::create_default_client() {
-                Ok(client) => Arc::new(client),
+                Ok(client) => Arc::new(client.with_rate_limits(rate_limits.clone())),
                 Err(e) => {
                     tracing::warn!("Failed to create default LLM client: {}. Semantic cache will fallback to exact match.", e);
                     Arc::new(multi_agent_model_gateway::MockLlmClient::new("dummy"))
@@ -380,12 +683,203 @@ async fn main() -> anyhow::Result<()> {
         }
     };
 
+    // The default client can be switched from the admin API; every client
+    // swapped in is metered and spend-capped under its own pricing key
+    let active_llm_client = Arc::new(
+        multi_agent_model_gateway::ActiveLlmClient::new(llm_client, llm_model_id).with_layer({
+            let usage_ledger = usage_ledger.clone();
+            let spend_guard = spend_guard.clone();
+            move |client, model| {
+                let metered: Arc<dyn LlmClient> =
+                    Arc::new(multi_agent_model_gateway::MeteredLlmClient::new(
+                        client,
+                        usage_ledger.clone(),
+                        model,
+                    ));
+                Arc::new(multi_agent_model_gateway::SpendCappedLlmClient::new(
+                    metered,
+                    spend_guard.clone(),
+                    model,
+                ))
+            }
+        }),
+    );
+    let llm_client: Arc<dyn LlmClient> = active_llm_client.clone();
+    #[cfg(feature = "chaos")]
+    let llm_client: Arc<dyn LlmClient> = match multi_agent_core::chaos::FaultInjector::from_config(
+        "llm",
+        &app_config.chaos,
+        &app_config.chaos.llm,
+    ) {
+        Some(injector) => Arc::new(multi_agent_core::chaos::ChaosLlmClient::new(
+            llm_client, injector,
+        )),
+        None => llm_client,
+    };
+
+    // Singleton background jobs run on whichever replica holds their lease
+    let lease_config = &app_config.store.leader_election;
+    let lease_backend: Arc<dyn multi_agent_runtime::LeaseBackend> =
+        match &app_config.store.redis_url {
+            Some(url) => Arc::new(
+                multi_agent_runtime::RedisLeaseBackend::new(url, &lease_config.key_prefix)
+                    .map_err(|e| anyhow::anyhow!("Leader election init failed: {}", e))?,
+            ),
+            None => Arc::new(multi_agent_runtime::InMemoryLeaseBackend::new()),
+        };
+    let mut elector = multi_agent_runtime::LeaderElector::new(lease_backend).with_ttl(
+        std::time::Duration::from_secs(lease_config.lease_ttl_secs.max(3)),
+    );
+    if let Ok(hostname) = std::env::var("HOSTNAME") {
+        elector = elector.with_holder(format!("{}-{}", hostname, std::process::id()));
+    }
+    let elector = Arc::new(elector);
+    tracing::info!(holder = %elector.holder(), "Leader election initialized");
+
+    let alerts_config = &app_config.model_gateway.alerts;
+    if alerts_config.enabled {
+        let mut alerter = multi_agent_model_gateway::UsageAlerter::from_config(
+            usage_ledger.clone(),
+            alerts_config,
+        )
+        .with_sink(Arc::new(multi_agent_governance::LogSink));
+        for url in &alerts_config.webhook_urls {
+            alerter = alerter.with_sink(Arc::new(multi_agent_governance::WebhookSink::new(url)));
+        }
+        let alerter = Arc::new(alerter);
+        let interval = std::time::Duration::from_secs(alerts_config.interval_secs.max(10));
+        elector.gate("usage-alerts", move || alerter.clone().spawn(interval));
+        tracing::info!("Usage alerts and daily digest enabled");
+    }
+
+    // Interactive traffic goes ahead of background and eval jobs
+    let inference_queue =
+        multi_agent_model_gateway::InferenceQueue::new(app_config.model_gateway.queue.clone());
+    let llm_client: Arc<dyn LlmClient> = Arc::new(multi_agent_model_gateway::QueuedLlmClient::new(
+        llm_client,
+        inference_queue.clone(),
+        multi_agent_model_gateway::RequestPriority::Interactive,
+    ));
+
+    // =========================================================================
+    // Initialize L1: Controller
+    // =========================================================================
+    let human_input = Arc::new(multi_agent_governance::ChannelHumanInput::new());
+    let approval_gate = Arc::new(multi_agent_governance::approval::ChannelApprovalGate::new(
+        multi_agent_core::types::ToolRiskLevel::High,
+    ));
+    // Remembers "N identical calls" and "rest of session" approvals
+    let batching_gate = Arc::new(
+        multi_agent_governance::BatchingApprovalGate::new(approval_gate.clone())
+            .with_audit(audit_store.clone()),
+    );
+    let prompts = Arc::new(
+        multi_agent_core::prompts::PromptRegistry::from_config(&app_config.i18n).unwrap_or_else(
+            |e| {
+                tracing::warn!(error = %e, "Failed to load prompt catalogs; using built-in texts");
+                multi_agent_core::prompts::PromptRegistry::new(&app_config.i18n.default_locale)
+            },
+        ),
+    );
+    let planning = &app_config.controller.planning;
+    let mut plan_estimator = multi_agent_controller::PlanEstimator::new()
+        .with_analytics(tool_analytics.clone())
+        .with_tools(tracked_tools.clone())
+        .with_approval_threshold(multi_agent_controller::PlanApprovalThreshold::from_config(
+            planning,
+        ));
+    if let Some(pricing) = multi_agent_model_gateway::PricingRegistry::with_defaults()
+        .get(&active_llm_client.model_id())
+    {
+        plan_estimator = plan_estimator.with_pricing(pricing.clone());
+    }
+    let mut controller = ReActController::builder()
+        .with_prompts(prompts.clone())
+        .with_config(multi_agent_controller::ReActConfig {
+            deadlock: app_config.controller.deadlock.clone(),
+            ..Default::default()
+        })
+        .with_human_input(human_input.clone())
+        .with_approval_gate(batching_gate)
+        .with_policy_engine(policy_engine.clone())
+        .with_tools(tracked_tools.clone())
+        .with_usage_ledger(usage_ledger.clone())
+        .with_model_selector(model_selector)
+        .with_store(store.clone())
+        .with_session_store(session_store.clone())
+        .with_capability(Arc::new(
+            multi_agent_controller::MemoryWritebackCapability::from_env(),
+        ))
+        .with_compressor(Arc::new(
+            multi_agent_controller::context::TruncationCompressor::new(),
+        ));
+    if planning.enabled {
+        controller = controller
+            .with_planning(llm_client.clone())
+            .with_plan_estimator(Arc::new(plan_estimator));
+    }
+    let speculative = &app_config.controller.speculative;
+    if let Some(drafter) = &speculative.drafter {
+        let drafter = Arc::new(multi_agent_model_gateway::RegisteredProviderClient::new(
+            provider_registry.clone(),
+            drafter,
+        ));
+        let verifier: Arc<dyn LlmClient> = match &speculative.verifier {
+            Some(key) => Arc::new(
+                multi_agent_model_gateway::RegisteredProviderClient::new(
+                    provider_registry.clone(),
+                    key,
+                )
+                .with_fallback(llm_client.clone()),
+            ),
+            None => llm_client.clone(),
+        };
+        controller = controller.with_capability(Arc::new(
+            multi_agent_controller::SpeculativeCapability::new(drafter, verifier).with_config(
+                multi_agent_controller::SpeculativeConfig::from_config(speculative),
+            ),
+        ));
+        tracing::info!("Speculative drafting enabled for opted-in workspaces");
+    }
+    let controller = Arc::new(controller.build());
+    tracing::info!("L1 Controller initialized (mock ReAct)");
+
+    // Stored providers get the same metering, spend caps and queueing as the
+    // default client
+    let provider_sync = Arc::new(
+        multi_agent_admin::provider_sync::ProviderSync::new(provider_registry).with_layer({
+            let usage_ledger = usage_ledger.clone();
+            let spend_guard = spend_guard.clone();
+            move |client, model| {
+                let metered: Arc<dyn LlmClient> =
+                    Arc::new(multi_agent_model_gateway::MeteredLlmClient::new(
+                        client,
+                        usage_ledger.clone(),
+                        model,
+                    ));
+                let capped: Arc<dyn LlmClient> =
+                    Arc::new(multi_agent_model_gateway::SpendCappedLlmClient::new(
+                        metered,
+                        spend_guard.clone(),
+                        model,
+                    ));
+                Arc::new(multi_agent_model_gateway::QueuedLlmClient::new(
+                    capped,
+                    inference_queue.clone(),
+                    multi_agent_model_gateway::RequestPriority::Interactive,
+                ))
+            }
+        }),
+    );
+
     let routing_policy_store = Arc::new(
-        match multi_agent_gateway::routing_policy::RoutingPolicyStore::new_persistent(
-            ".sovereign_claw/routing/policies.json",
-        ) {
-            Ok(store) => store,
-            Err(e) => {
+        match app_config
+            .state_path(StateFile::RoutingPolicies)
+            .map(multi_agent_gateway::routing_policy::RoutingPolicyStore::new_persistent)
+        {
+            None => multi_agent_gateway::routing_policy::RoutingPolicyStore::new(),
+            Some(Ok(store)) => store,
+            Some(Err(e)) => {
                 tracing::warn!(
                     error = %e,
                     "Failed to load persistent routing policy store; using in-memory store"
@@ -394,13 +888,54 @@ async fn main() -> anyhow::Result<()> {
             }
         },
     );
+    let workspace_store = Arc::new(
+        match app_config
+            .state_path(StateFile::Workspaces)
+            .map(multi_agent_gateway::workspaces::WorkspaceStore::new_persistent)
+        {
+            None => multi_agent_gateway::workspaces::WorkspaceStore::new(),
+            Some(Ok(store)) => store,
+            Some(Err(e)) => {
+                tracing::warn!(
+                    error = %e,
+                    "Failed to load persistent workspace store; using in-memory store"
+                );
+                multi_agent_gateway::workspaces::WorkspaceStore::new()
+            }
+        },
+    );
+    let flag_definitions = app_config.governance.feature_flags.clone();
+    let feature_flags = Arc::new(
+        match app_config.state_path(StateFile::FeatureFlags).map(|path| {
+            multi_agent_governance::FeatureFlagService::new_persistent(
+                flag_definitions.clone(),
+                path,
+            )
+        }) {
+            None => multi_agent_governance::FeatureFlagService::new(flag_definitions),
+            Some(Ok(flags)) => flags,
+            Some(Err(e)) => {
+                tracing::warn!(
+                    error = %e,
+                    "Failed to load feature flag overrides; keeping overrides in memory"
+                );
+                multi_agent_governance::FeatureFlagService::new(flag_definitions)
+            }
+        },
+    );
     let router = Arc::new(
         DefaultRouter::new()
-            .with_llm_classifier(llm_client.clone(), tools.clone() as Arc<dyn ToolRegistry>)
+            .with_llm_classifier(llm_client.clone(), tracked_tools.clone())
             .with_routing_policy_store(routing_policy_store.clone()),
     );
 
-    let cache = Arc::new(InMemorySemanticCache::new(llm_client));
+    let cache = Arc::new(
+        InMemorySemanticCache::new(llm_client)
+            .with_ttl(std::time::Duration::from_secs(
+                app_config.gateway.cache.ttl_secs,
+            ))
+            .with_disabled_workspaces(app_config.gateway.cache.disabled_workspaces.clone()),
+    );
 
     let gateway_config = GatewayConfig {
         host: app_config.server.host.clone(),
@@ -409,15 +944,101 @@ async fn main() -> anyhow::Result<()> {
         enable_tracing: true,
         allowed_origins: app_config.gateway.allowed_origins.clone(),
         tls: app_config.gateway.tls.clone(),
+        unix_socket: app_config.server.unix_socket.clone(),
     };
 
     let (logs_tx, _logs_rx) = tokio::sync::broadcast::channel(100);
 
-    let server = GatewayServer::new(gateway_config.clone(), router, cache)
+    // With an external event bus, the dashboard reads the stream shared by all replicas
+    let replica = std::env::var("HOSTNAME").unwrap_or_else(|_| elector.holder().to_string());
+    let event_stream = match multi_agent_runtime::bus::connect(&app_config.events, &replica)
+        .await
+        .map_err(|e| anyhow::anyhow!("Event bus init failed: {}", e))?
+    {
+        Some(bus) => {
+            let (stream_tx, _) = tokio::sync::broadcast::channel(1024);
+            multi_agent_runtime::bus::relay(
+                bus.clone(),
+                &app_config.events.subject_prefix,
+                &logs_tx,
+                stream_tx.clone(),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Event bus subscription failed: {}", e))?;
+            tracing::info!(bus = bus.name(), "Events fanned out over external bus");
+            Some(stream_tx)
+        }
+        None => None,
+    };
+
+    let server = GatewayServer::new(gateway_config.clone(), router, cache.clone())
         .with_controller(controller)
         .with_logs_channel(logs_tx.clone())
         .with_approval_gate(approval_gate.clone())
-        .with_routing_policy_store(routing_policy_store.clone());
+        .with_human_input(human_input.clone())
+        .with_tool_registry(tracked_tools.clone())
+        .with_policy_engine(policy_engine.clone())
+        .with_routing_policy_store(routing_policy_store.clone())
+        .with_workspace_store(workspace_store)
+        .with_feature_flags(feature_flags)
+        .with_prompts(prompts)
+        .with_artifact_store(store.clone())
+        .with_artifact_catalog(artifact_catalog.clone());
+    let moderation = &app_config.governance.moderation;
+    let server = match multi_agent_governance::Moderator::from_config(
+        moderation,
+        moderation
+            .api_key
+            .as_ref()
+            .map(|k| k.expose_secret().clone()),
+    ) {
+        Ok(Some(moderator)) => {
+            tracing::info!(
+                provider = moderator.provider_name(),
+                "Content moderation enabled"
+            );
+            server.with_moderator(Arc::new(moderator))
+        }
+        Ok(None) => server,
+        Err(e) => {
+            tracing::warn!(error = %e, "Content moderation disabled");
+            server
+        }
+    };
+    // Pause sessions idling on human input and free the sandbox meanwhile.
+    // Approvals, questions and the sandbox are per replica, so every replica runs it.
+    let idle = &app_config.controller.idle;
+    if idle.pause_after_secs > 0 {
+        let mut reclaimer = multi_agent_gateway::idle::IdleReclaimer::new(
+            session_store.clone(),
+            std::time::Duration::from_secs(idle.pause_after_secs),
+        )
+        .with_approval_gate(approval_gate.clone())
+        .with_human_input(human_input.clone());
+        if let Some(manager) = &sandbox_manager {
+            reclaimer = reclaimer.with_sandbox(manager.clone());
+        }
+        Arc::new(reclaimer).spawn(std::time::Duration::from_secs(
+            idle.check_interval_secs.max(10),
+        ));
+    }
+    let server = match sandbox_manager {
+        Some(manager) => server.with_sandbox_manager(manager),
+        None => server,
+    };
+    // Keep recent events from whichever stream the dashboard is served
+    let replay_capacity = app_config.governance.log_stream.replay_capacity;
+    let server = if replay_capacity > 0 {
+        let buffer = Arc::new(multi_agent_gateway::logs::LogBuffer::new(replay_capacity));
+        buffer.record(event_stream.as_ref().unwrap_or(&logs_tx).subscribe());
+        server.with_log_buffer(buffer)
+    } else {
+        server
+    };
+    let server = match event_stream {
+        Some(stream) => server.with_event_stream(stream),
+        None => server,
+    };
 
     tracing::info!(
         host = %gateway_config.host,
@@ -454,19 +1075,67 @@ async fn main() -> anyhow::Result<()> {
     // =========================================================================
     let metrics_handle = multi_agent_governance::setup_metrics_recorder()?;
 
-    // Initialize Governance Components
-    if let Some(parent) = std::path::Path::new(&app_config.governance.audit_log_path).parent() {
-        std::fs::create_dir_all(parent).map_err(|e| {
-            multi_agent_core::Error::storage(format!(
-                "Failed to create audit log directory '{}': {}",
-                parent.display(),
-                e
-            ))
-        })?;
-    }
-    let audit_store = Arc::new(multi_agent_governance::SqliteAuditStore::new(
-        &app_config.governance.audit_log_path,
-    )?);
+    // WORM audit mode: append-only table, chain head anchored externally
+    let worm = &app_config.governance.audit_worm;
+    let audit_anchorer = if worm.enabled {
+        audit_store.enable_worm().await?;
+        let notary: Option<Arc<dyn multi_agent_governance::AuditNotary>> = match worm.notary {
+            multi_agent_core::config::AuditNotaryKind::None => None,
+            multi_agent_core::config::AuditNotaryKind::Rfc3161 => {
+                match (&worm.tsa_url, &worm.tsa_certificate) {
+                    (Some(url), Some(path)) => {
+                        let pem = std::fs::read(path).map_err(|e| {
+                            anyhow::anyhow!("Failed to read TSA certificate '{}': {}", path, e)
+                        })?;
+                        let certificate =
+                            multi_agent_governance::Rfc3161Notary::parse_certificate(&pem)?;
+                        Some(Arc::new(multi_agent_governance::Rfc3161Notary::new(
+                            url.clone(),
+                            certificate,
+                        ))
+                            as Arc<dyn multi_agent_governance::AuditNotary>)
+                    }
+                    _ => None,
+                }
+            }
+            multi_agent_core::config::AuditNotaryKind::S3 => match &worm.s3_bucket {
+                Some(bucket) => Some(Arc::new(
+                    multi_agent_admin::s3_anchor::S3ObjectLockNotary::new(
+                        bucket,
+                        &worm.s3_prefix,
+                        worm.s3_endpoint.as_deref(),
+                        worm.retention_days,
+                    )
+                    .await,
+                )
+                    as Arc<dyn multi_agent_governance::AuditNotary>),
+                None => None,
+            },
+        };
+        match notary {
+            Some(notary) => {
+                let anchorer = Arc::new(multi_agent_governance::AuditAnchorer::new(
+                    audit_store.clone(),
+                    notary,
+                ));
+                let interval = std::time::Duration::from_secs(worm.anchor_interval_secs.max(60));
+                elector.gate("audit-anchor", {
+                    let anchorer = anchorer.clone();
+                    move || anchorer.clone().spawn(interval)
+                });
+                tracing::info!(notary = ?worm.notary, "Audit WORM mode enabled with external anchoring");
+                Some(anchorer)
+            }
+            None => {
+                tracing::warn!(
+                    "Audit WORM mode enabled without a configured notary; the chain head will not be anchored"
+                );
+                None
+            }
+        }
+    } else {
+        None
+    };
 
     // RBAC: Check environment for production mode
     let is_production = app_config.governance.multiagent_env.to_lowercase() == "production";
@@ -485,9 +1154,23 @@ async fn main() -> anyhow::Result<()> {
     };
 
     // Initialize MCP Registry
-    let mcp_registry = Arc::new(multi_agent_skills::McpRegistry::new());
+    // Stdio servers run as supervised, environment-restricted processes;
+    // oversized tool results are spilled to the artifact store.
+    let mcp_adapter = multi_agent_skills::McpToolAdapter::new()
+        .with_supervisor(Arc::new(multi_agent_skills::McpProcessSupervisor::new()))
+        .with_artifact_store(store.clone());
+    let mcp_registry = Arc::new(multi_agent_skills::McpRegistry::with_adapter(Arc::new(
+        mcp_adapter,
+    )));
     mcp_registry.register_defaults(); // Register built-in defaults
 
+    // REST APIs imported through the admin API as OpenAPI specs.
+    let openapi_registry = Arc::new(multi_agent_skills::OpenApiRegistry::new(
+        secrets_manager.clone(),
+        network_policy.clone(),
+        app_config.safety.clone(),
+    ));
+
     // Initialize Redis components if configured
     let redis_url = app_config.store.redis_url.as_ref();
 
@@ -515,9 +1198,46 @@ async fn main() -> anyhow::Result<()> {
         (provider_store, rate_limiter)
     } else {
         tracing::info!("REDIS_URL not set - using in-memory stores");
-        (None, None)
+        (
+            Some(Arc::new(multi_agent_store::InMemoryProviderStore::new())
+                as Arc<dyn multi_agent_core::traits::ProviderStore>),
+            None,
+        )
+    };
+    let provider_store = match &postgres_pool {
+        Some(pool) => Some(
+            Arc::new(multi_agent_store::PostgresProviderStore::new(pool.clone()))
+                as Arc<dyn multi_agent_core::traits::ProviderStore>,
+        ),
+        None => provider_store,
     };
 
+    // SMTP servers are admin providers; every email goes through the approval gate.
+    if let Some(providers) = &provider_store {
+        tools
+            .register(Box::new(multi_agent_skills::SendEmailTool::new(
+                providers.clone(),
+                secrets_manager.clone(),
+                audit_store.clone(),
+                app_config.email.clone(),
+            )))
+            .await?;
+
+        // Calendar and issue-tracker connectors read their credentials from admin providers.
+        let connectors =
+            multi_agent_skills::ConnectorContext::new(providers.clone(), secrets_manager.clone());
+        for tool in multi_agent_skills::connector_tools(connectors) {
+            tools.register(tool).await?;
+        }
+    }
+
+    // Clarification questions are answered over /ws/approval or /v1/answer.
+    tools
+        .register(Box::new(multi_agent_skills::AskUserTool::new(
+            human_input.clone(),
+        )))
+        .await?;
+
     // Initialize Knowledge Store (M10.3)
     let knowledge_db_path = app_config
         .governance
@@ -529,21 +1249,41 @@ async fn main() -> anyhow::Result<()> {
 
     // Initialize Privacy Controller (M10.4)
     let erasable_stores: Vec<Arc<dyn multi_agent_core::traits::Erasable>> = vec![
-        store_raw,
+        // The outermost artifact store, so erasure cascades through encryption and tiers.
+        store.clone() as Arc<dyn multi_agent_core::traits::Erasable>,
         session_store_raw,
+        cache as Arc<dyn multi_agent_core::traits::Erasable>,
         knowledge_store_raw.clone() as Arc<dyn multi_agent_core::traits::Erasable>,
         audit_store.clone() as Arc<dyn multi_agent_core::traits::Erasable>,
     ];
     let privacy_controller = Arc::new(multi_agent_governance::PrivacyController::new(
         erasable_stores,
-        Arc::new(multi_agent_core::traits::NoOpEventEmitter),
+        Arc::new(multi_agent_runtime::ChannelEventEmitter::new(
+            logs_tx.clone(),
+        )),
     ));
 
+    // Model upgrades of stored providers are canaried against the previous model
+    let canary_config = &app_config.model_gateway.canary;
+    let model_canary = canary_config.enabled.then(|| {
+        let mut canary = multi_agent_admin::canary::ModelCanary::from_config(canary_config)
+            .with_sink(Arc::new(multi_agent_governance::LogSink));
+        for url in &app_config.model_gateway.alerts.webhook_urls {
+            canary = canary.with_sink(Arc::new(multi_agent_governance::WebhookSink::new(url)));
+        }
+        tracing::info!(
+            cases = canary_config.cases.len(),
+            "Model upgrade canary enabled"
+        );
+        Arc::new(canary)
+    });
+
     let admin_state = Arc::new(multi_agent_admin::AdminState {
         audit_store,
         rbac,
         metrics: Some(metrics_handle.clone()),
         mcp_registry: mcp_registry.clone(),
+        openapi_registry: Some(openapi_registry),
         providers: Arc::new(tokio::sync::RwLock::new(Vec::new())),
         provider_store,
         secrets: secrets_manager,
@@ -552,19 +1292,68 @@ async fn main() -> anyhow::Result<()> {
         privacy_controller: Some(privacy_controller),
         app_config: app_config.clone(),
         network_policy: network_policy.clone(),
+        audit_presets: Arc::new(multi_agent_admin::audit_view::AuditPresetStore::load(
+            "audit_presets.json",
+        )),
+        audit_anchorer,
+        guardrails: guardrails.clone(),
+        tool_analytics: Some(tool_analytics),
+        endpoint_registry: Some(endpoint_registry.clone()),
+        active_client: Some(active_llm_client.clone()),
+        provider_sync: Some(provider_sync.clone()),
+        model_canary,
+    });
+    endpoint_registry.spawn(std::time::Duration::from_secs(30));
+
+    let registered = provider_sync.sync(&admin_state).await;
+    tracing::info!(
+        count = registered,
+        "Registered stored providers for inference"
+    );
+    provider_sync
+        .clone()
+        .spawn(admin_state.clone(), std::time::Duration::from_secs(30));
+
+    let mut spend_cap_enforcer =
+        multi_agent_admin::spend_caps::SpendCapEnforcer::new(admin_state.clone(), spend_guard)
+            .with_sink(Arc::new(multi_agent_governance::LogSink));
+    for url in &app_config.model_gateway.alerts.webhook_urls {
+        spend_cap_enforcer =
+            spend_cap_enforcer.with_sink(Arc::new(multi_agent_governance::WebhookSink::new(url)));
+    }
+    let spend_cap_enforcer = Arc::new(spend_cap_enforcer);
+    let interval = std::time::Duration::from_secs(
+        app_config
+            .model_gateway
+            .spend_caps
+            .check_interval_secs
+            .max(10),
+    );
+    elector.gate("spend-caps", move || {
+        spend_cap_enforcer.clone().spawn(interval)
     });
 
     // Initialize Research Orchestrator (M10.1, M10.5)
-    let research_orchestrator = Arc::new(multi_agent_gateway::research::ResearchOrchestrator::new(
-        admin_state.clone(),
-        approval_gate.clone(),
-        network_policy.clone(),
-        None,
-        app_config.safety.clone(),
-        store.clone(),
-        knowledge_store.clone(),
-        Some(logs_tx.clone()),
-    ));
+    let research_orchestrator = Arc::new(
+        multi_agent_gateway::research::ResearchOrchestrator::new(
+            admin_state.clone(),
+            approval_gate.clone(),
+            network_policy.clone(),
+            None,
+            app_config.safety.clone(),
+            store.clone(),
+            knowledge_store.clone(),
+            Some(logs_tx.clone()),
+        )
+        .with_budgets(app_config.governance.research.clone()),
+    );
+    let recrawl = app_config.governance.research.recrawl.clone();
+    if recrawl.enabled {
+        let research_orchestrator = research_orchestrator.clone();
+        elector.gate("research-recrawl", move || {
+            research_orchestrator.clone().spawn_recrawl(recrawl.clone())
+        });
+    }
 
     // =========================================================================
     // Start the server
@@ -572,12 +1361,22 @@ async fn main() -> anyhow::Result<()> {
     let mut server = server
         .with_metrics(metrics_handle)
         .with_admin(admin_state)
-        .with_research_orchestrator(research_orchestrator);
+        .with_research_orchestrator(research_orchestrator)
+        .with_knowledge_store(knowledge_store)
+        .with_guardrails(guardrails);
 
     if let Some(limiter) = rate_limiter {
         server = server.with_rate_limiter(limiter);
     }
 
+    if let Some(desired) = &bootstrap {
+        let plan = server.reconcile(desired, !dry_run).await?;
+        println!("{}", plan);
+        if dry_run {
+            return Ok(());
+        }
+    }
+
     server.run().await?;
 
     Ok(())
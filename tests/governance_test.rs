@@ -113,6 +113,8 @@ async fn test_budget_exceeded() -> anyhow::Result<()> {
                 context_summary: "".to_string(),
                 visual_refs: vec![],
                 user_id: None,
+                workspace_id: None,
+                dry_run: false,
             },
             "test-trace".to_string(),
         )
@@ -188,6 +190,8 @@ async fn test_deadlock_breaker() -> anyhow::Result<()> {
                 context_summary: "".to_string(),
                 visual_refs: vec![],
                 user_id: None,
+                workspace_id: None,
+                dry_run: false,
             },
             "test-trace".to_string(),
         )